@@ -0,0 +1,129 @@
+use std::{path::PathBuf, process::ExitCode, time::Duration};
+
+use circom_types::groth16::JsonVerificationKey;
+use clap::{Parser, Subcommand};
+use co_circom_age_verification::{
+    client::{self, Attributes, Statement},
+    server, BLACKLIST_LEN,
+};
+use color_eyre::eyre::{eyre, Context};
+use tracing_subscriber::fmt::format::FmtSpan;
+
+fn install_tracing() {
+    use tracing_subscriber::prelude::*;
+    use tracing_subscriber::{fmt, EnvFilter};
+
+    let fmt_layer = fmt::layer()
+        .with_target(false)
+        .with_line_number(false)
+        .with_span_events(FmtSpan::CLOSE | FmtSpan::ENTER);
+    let filter_layer = EnvFilter::try_from_default_env()
+        .or_else(|_| EnvFilter::try_new("info"))
+        .unwrap();
+
+    tracing_subscriber::registry()
+        .with(filter_layer)
+        .with(fmt_layer)
+        .init();
+}
+
+#[derive(Parser)]
+#[command(version, about, long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Runs the HTTP API of one of the three parties
+    Server {
+        /// The path to the config file of the party. The network config is reloaded from it on SIGHUP
+        #[arg(long)]
+        config: PathBuf,
+        /// Checks the config file and the key and certificate files it references every this many seconds and reloads the network config if they changed
+        #[arg(long)]
+        watch_interval_secs: Option<u64>,
+    },
+    /// Shares the attributes of a user, requests a proof from the three parties, and verifies it
+    Client {
+        /// The age of the user
+        #[arg(long)]
+        age: u64,
+        /// The numeric code of the country of residence of the user
+        #[arg(long)]
+        country: u64,
+        /// The minimum age
+        #[arg(long, default_value_t = 18)]
+        min_age: u64,
+        /// The numeric codes of the blacklisted countries
+        #[arg(long, value_delimiter = ',', default_values_t = [1, 2, 3])]
+        blacklist: Vec<u64>,
+        /// The base URLs of the parties, ordered by their id
+        #[arg(long, value_delimiter = ',')]
+        parties: Vec<String>,
+        /// The path to the verification key
+        #[arg(long)]
+        vk: PathBuf,
+    },
+}
+
+#[tokio::main]
+async fn main() -> color_eyre::Result<ExitCode> {
+    install_tracing();
+    rustls::crypto::aws_lc_rs::default_provider()
+        .install_default()
+        .map_err(|_| eyre!("Could not install default rustls crypto provider"))?;
+    let args = Cli::parse();
+
+    match args.command {
+        Commands::Server {
+            config,
+            watch_interval_secs,
+        } => {
+            server::serve_with_reload(config, watch_interval_secs.map(Duration::from_secs)).await?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Commands::Client {
+            age,
+            country,
+            min_age,
+            blacklist,
+            parties,
+            vk,
+        } => {
+            let statement = Statement {
+                min_age,
+                blacklist: blacklist
+                    .try_into()
+                    .map_err(|_| eyre!("expected {BLACKLIST_LEN} blacklisted countries"))?,
+            };
+            let parties: [String; 3] = parties
+                .try_into()
+                .map_err(|_| eyre!("expected the URLs of exactly 3 parties"))?;
+            let vk_file = std::fs::File::open(&vk).context("while opening verification key")?;
+            let vk: JsonVerificationKey<ark_bn254::Bn254> =
+                serde_json::from_reader(std::io::BufReader::new(vk_file))
+                    .context("while deserializing verification key")?;
+
+            let shares = client::share_attributes(
+                Attributes { age, country },
+                statement,
+                &mut rand::thread_rng(),
+            )?;
+            let response =
+                client::request_proof(&reqwest::Client::new(), &parties, &shares).await?;
+            tracing::info!("received proof from all parties");
+            match client::verify_proof(&vk, &statement, &response) {
+                Ok(()) => {
+                    tracing::info!("Proof verified successfully");
+                    Ok(ExitCode::SUCCESS)
+                }
+                Err(err) => {
+                    tracing::error!("Proof verification failed: {err:#}");
+                    Ok(ExitCode::FAILURE)
+                }
+            }
+        }
+    }
+}
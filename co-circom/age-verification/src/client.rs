@@ -0,0 +1,151 @@
+//! The client side of the age verification.
+//!
+//! The user secret-shares their attributes with [share_attributes], sends one share to each party with [request_proof], and the verifier checks the resulting proof with [verify_proof].
+use ark_bn254::{Bn254, Fr};
+use circom_types::groth16::JsonVerificationKey;
+use co_circom::SeedRng;
+use co_circom_snarks::{SerializeableSharedRep3Input, VerificationError};
+use co_groth16::Groth16;
+use color_eyre::eyre::{self, bail, eyre, Context};
+use rand::{CryptoRng, Rng};
+
+use crate::{ProofResponse, AGE_BIT_WIDTH, BLACKLIST_LEN};
+
+/// The private attributes of a user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Attributes {
+    /// The age in years
+    pub age: u64,
+    /// The numeric code of the country of residence
+    pub country: u64,
+}
+
+/// The public statement about the [Attributes] of a user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Statement {
+    /// The minimum age in years
+    pub min_age: u64,
+    /// The numeric codes of the countries the user must not reside in
+    pub blacklist: [u64; BLACKLIST_LEN],
+}
+
+impl Statement {
+    /// Returns the public inputs of the `kyc` circuit for this statement in the order in which they appear in the proof.
+    pub fn public_inputs(&self) -> Vec<Fr> {
+        self.blacklist
+            .iter()
+            .chain(std::iter::once(&self.min_age))
+            .map(|x| Fr::from(*x))
+            .collect()
+    }
+}
+
+/// Secret-shares the [Attributes] of a user and adds the public [Statement], resulting in one input share per party.
+pub fn share_attributes<R: Rng + CryptoRng>(
+    attributes: Attributes,
+    statement: Statement,
+    rng: &mut R,
+) -> eyre::Result<[SerializeableSharedRep3Input<Fr, SeedRng>; 3]> {
+    // the circuit only compares the lower bits of the ages
+    for (name, value) in [("age", attributes.age), ("min_age", statement.min_age)] {
+        if value >> AGE_BIT_WIDTH != 0 {
+            bail!("{name} must be smaller than 2^{AGE_BIT_WIDTH}");
+        }
+    }
+
+    let mut shares = [
+        SerializeableSharedRep3Input::default(),
+        SerializeableSharedRep3Input::default(),
+        SerializeableSharedRep3Input::default(),
+    ];
+    for (name, value) in [("age", attributes.age), ("country", attributes.country)] {
+        let [share0, share1, share2] = SerializeableSharedRep3Input::<_, SeedRng>::share_rep3(
+            &[Fr::from(value)],
+            rng,
+            false,
+            false,
+        );
+        shares[0].shared_inputs.insert(name.to_owned(), share0);
+        shares[1].shared_inputs.insert(name.to_owned(), share1);
+        shares[2].shared_inputs.insert(name.to_owned(), share2);
+    }
+    let blacklist = statement
+        .blacklist
+        .iter()
+        .map(|x| Fr::from(*x))
+        .collect::<Vec<_>>();
+    for share in shares.iter_mut() {
+        share
+            .public_inputs
+            .insert("blacklist".to_owned(), blacklist.clone());
+        share
+            .public_inputs
+            .insert("min_age".to_owned(), vec![Fr::from(statement.min_age)]);
+        share.bit_widths.insert("age".to_owned(), AGE_BIT_WIDTH);
+    }
+    Ok(shares)
+}
+
+async fn request_proof_from_party(
+    client: &reqwest::Client,
+    url: &str,
+    share: &SerializeableSharedRep3Input<Fr, SeedRng>,
+) -> eyre::Result<ProofResponse> {
+    let body = bincode::serialize(share).context("while serializing input share")?;
+    let response = client
+        .post(format!("{}/prove", url.trim_end_matches('/')))
+        .body(body)
+        .send()
+        .await
+        .context(format!("while sending request to {url}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        let message = response.text().await.unwrap_or_default();
+        bail!("{url} responded with {status}: {message}");
+    }
+    response
+        .json()
+        .await
+        .context(format!("while parsing response of {url}"))
+}
+
+/// Sends the input shares to the parties at the given base URLs and waits for their proofs. The share at index `i` is sent to the party with id `i`. Fails if one of the parties fails or if the parties return different proofs.
+pub async fn request_proof(
+    client: &reqwest::Client,
+    urls: &[String; 3],
+    shares: &[SerializeableSharedRep3Input<Fr, SeedRng>; 3],
+) -> eyre::Result<ProofResponse> {
+    let (response0, response1, response2) = tokio::try_join!(
+        request_proof_from_party(client, &urls[0], &shares[0]),
+        request_proof_from_party(client, &urls[1], &shares[1]),
+        request_proof_from_party(client, &urls[2], &shares[2]),
+    )?;
+    if response0 != response1 || response0 != response2 {
+        bail!("parties returned different proofs");
+    }
+    Ok(response0)
+}
+
+/// Verifies a [ProofResponse] for the given [Statement]. Besides checking the proof, this ensures that the proof is actually about the expected statement.
+pub fn verify_proof(
+    vk: &JsonVerificationKey<Bn254>,
+    statement: &Statement,
+    response: &ProofResponse,
+) -> eyre::Result<()> {
+    let public_inputs = response
+        .public_inputs
+        .iter()
+        .map(|s| {
+            s.parse::<Fr>()
+                .map_err(|_| eyre!("could not parse as field element: {}", s))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if public_inputs != statement.public_inputs() {
+        bail!("proof is for a different statement");
+    }
+    match Groth16::<Bn254>::verify(vk, &response.proof, &public_inputs) {
+        Ok(()) => Ok(()),
+        Err(VerificationError::InvalidProof) => bail!("invalid proof"),
+        Err(VerificationError::Malformed(err)) => Err(err),
+    }
+}
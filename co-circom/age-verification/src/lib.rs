@@ -0,0 +1,30 @@
+#![warn(missing_docs)]
+//! An example application that uses co-circom to prove a statement about private attributes of a user.
+//!
+//! The user holds their age and their country of residence and wants to convince a verifier that they are at least `min_age` years old and that their country is not on a public blacklist, without revealing either value. Instead of computing the proof themselves, they secret-share the attributes to three parties, which collaboratively compute the witness extension and the Groth16 proof using the REP3 protocol.
+//!
+//! * The [server] module contains the HTTP API each of the three parties exposes.
+//! * The [client] module contains the functionality to share the attributes, request the proofs, and verify them.
+//!
+//! The statement is given by the `kyc` circuit in the co-circom examples.
+use ark_bn254::Bn254;
+use circom_types::groth16::Groth16Proof;
+use serde::{Deserialize, Serialize};
+
+pub mod client;
+pub mod server;
+
+/// The number of countries on the blacklist of the `kyc` circuit.
+pub const BLACKLIST_LEN: usize = 3;
+
+/// The bit width of the age as used by the comparison in the `kyc` circuit.
+pub const AGE_BIT_WIDTH: usize = 8;
+
+/// The response of a party to a successful proof request.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofResponse {
+    /// The Groth16 proof
+    pub proof: Groth16Proof<Bn254>,
+    /// The public inputs of the proof as stringified field elements, without the constant 1 at position 0.
+    pub public_inputs: Vec<String>,
+}
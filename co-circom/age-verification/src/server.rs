@@ -0,0 +1,346 @@
+//! The HTTP API of a party.
+//!
+//! Each party exposes two endpoints:
+//!
+//! * `GET /health` returns `200 OK` once the circuit and the zkey are loaded.
+//! * `POST /prove` expects a bincode-serialized [SerializeableSharedRep3Input](co_circom_snarks::SerializeableSharedRep3Input) as body, i.e., the input share of this party as produced by [client::share_attributes](crate::client::share_attributes). It connects to the other two parties, runs the witness extension and the Groth16 prover in MPC, and returns the resulting [ProofResponse] as JSON.
+//!
+//! All three parties need to receive their share of the same request. Since the MPC sessions of the parties are matched by the order in which they are established, a party handles only one proof request at a time.
+//!
+//! A party started with [serve_with_reload] reloads the `network` section of its config file, i.e., the key, the certificates, and the addresses of the parties, on SIGHUP and optionally whenever one of these files changed. The reloaded config only applies to new sessions, a session that is already running finishes with the config it was started with.
+use std::{
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, SystemTime},
+};
+
+use ark_bn254::{Bn254, Fr};
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use circom_mpc_compiler::{CoCircomCompiler, CompilerConfig};
+use circom_mpc_vm::{mpc_vm::VMConfig, types::CoCircomCompilerParsed};
+use circom_types::{groth16::ZKey, traits::CheckElement};
+use co_circom::file_utils::MAX_SHARED_ELEMENTS;
+use co_groth16::Rep3CoGroth16;
+use color_eyre::eyre::{self, bail, eyre, Context};
+use mpc_core::protocols::rep3::network::Rep3MpcNet;
+use mpc_net::config::{NetworkConfig, NetworkConfigFile};
+use num_traits::Zero;
+use serde::{Deserialize, Serialize};
+
+use crate::ProofResponse;
+
+/// The configuration of a party.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerConfig {
+    /// The address the HTTP API binds to
+    pub http_addr: SocketAddr,
+    /// The path to the circom file of the circuit
+    pub circuit: PathBuf,
+    /// The path to the Groth16 zkey of the circuit
+    pub zkey: PathBuf,
+    /// The compiler config
+    #[serde(default)]
+    pub compiler: CompilerConfig,
+    /// The VM config
+    #[serde(default)]
+    pub vm: VMConfig,
+    /// The MPC network config
+    pub network: NetworkConfigFile,
+}
+
+impl ServerConfig {
+    /// Reads the configuration from a TOML file.
+    pub fn from_file(path: &Path) -> eyre::Result<Self> {
+        let config = std::fs::read_to_string(path)
+            .context(format!("while reading config file {}", path.display()))?;
+        toml::from_str(&config).context("while parsing config file")
+    }
+}
+
+/// A party of the MPC computation. It holds the parsed circuit and the zkey, such that they are only loaded once.
+pub struct Party {
+    parsed: CoCircomCompilerParsed<Fr>,
+    zkey: Arc<ZKey<Bn254>>,
+    vm_config: VMConfig,
+    // replaced by reloads, every session clones the config it was started with
+    network_config: RwLock<NetworkConfig>,
+    // only one MPC session may run at a time, see module docs
+    session: Mutex<()>,
+}
+
+impl Party {
+    /// Compiles the circuit and reads the zkey specified in the provided [ServerConfig].
+    pub fn new(config: ServerConfig) -> eyre::Result<Self> {
+        let parsed = CoCircomCompiler::<Bn254>::parse(config.circuit, config.compiler)
+            .context("while compiling circuit")?;
+        let zkey_file = std::fs::File::open(&config.zkey).context("while opening zkey")?;
+        let zkey =
+            ZKey::<Bn254>::from_reader(std::io::BufReader::new(zkey_file), CheckElement::Yes)
+                .context("while reading zkey")?;
+        let network_config =
+            NetworkConfig::try_from(config.network).context("while converting network config")?;
+        network_config.check_config()?;
+        Ok(Self {
+            parsed,
+            zkey: Arc::new(zkey),
+            vm_config: config.vm,
+            network_config: RwLock::new(network_config),
+            session: Mutex::new(()),
+        })
+    }
+
+    /// Returns our own id in the MPC network.
+    pub fn id(&self) -> usize {
+        self.network_config().my_id
+    }
+
+    /// Returns the network config that is used for new sessions.
+    pub fn network_config(&self) -> NetworkConfig {
+        // the lock is only held for swapping or cloning the config, so it can not be poisoned by a panicking session
+        self.network_config
+            .read()
+            .expect("network config lock is not poisoned")
+            .clone()
+    }
+
+    /// Replaces the network config that is used for new sessions, e.g., after rotating the key or changing the addresses of the parties. A session that is already running keeps the config it was started with.
+    ///
+    /// Returns whether the config changed. Fails if the new config is invalid or changes our own id, which requires a restart.
+    pub fn reload_network(&self, network: NetworkConfigFile) -> eyre::Result<bool> {
+        let network_config =
+            NetworkConfig::try_from(network).context("while converting network config")?;
+        network_config.check_config()?;
+        let mut current = self
+            .network_config
+            .write()
+            .expect("network config lock is not poisoned");
+        if network_config.my_id != current.my_id {
+            bail!(
+                "cannot change the id of the party from {} to {} without a restart",
+                current.my_id,
+                network_config.my_id
+            );
+        }
+        if *current == network_config {
+            return Ok(false);
+        }
+        *current = network_config;
+        Ok(true)
+    }
+
+    /// Reads the [ServerConfig] from the provided file and applies its `network` section with [reload_network](Self::reload_network). Changes of the remaining sections require a restart.
+    pub fn reload_network_from_file(&self, path: &Path) -> eyre::Result<bool> {
+        let config = ServerConfig::from_file(path)?;
+        self.reload_network(config.network)
+    }
+
+    /// Computes a Groth16 proof in MPC for the bincode-serialized input share. This connects to the other parties and blocks until the proof is computed, so it must not be called from within an async context.
+    pub fn prove(&self, input_share: &[u8]) -> eyre::Result<ProofResponse> {
+        let _session = self
+            .session
+            .lock()
+            .map_err(|_| eyre!("a previous session panicked"))?;
+        let network_config = self.network_config();
+        tracing::info!("party {}: starting new session", network_config.my_id);
+
+        let mut mpc_net =
+            Rep3MpcNet::new(network_config).context("while connecting to the other parties")?;
+        let input_share = co_circom::parse_shared_input::<_, Fr, _>(
+            input_share,
+            &mut mpc_net,
+            input_share.len() as u64,
+            MAX_SHARED_ELEMENTS,
+        )
+        .context("while parsing input share")?;
+
+        let (witness, mpc_net) = self
+            .parsed
+            .clone()
+            .to_rep3_vm_with_network(mpc_net, self.vm_config.clone())
+            .context("while constructing MPC VM")?
+            .run_and_get_network(input_share)
+            .context("while running witness generation")?;
+        let witness = witness.into_shared_witness();
+
+        let public_inputs = witness
+            .public_inputs
+            .iter()
+            .skip(1) // we skip the constant 1 at position 0
+            .map(|f| {
+                if f.is_zero() {
+                    "0".to_string()
+                } else {
+                    f.to_string()
+                }
+            })
+            .collect();
+        let prover = Rep3CoGroth16::with_network(mpc_net).context("while building prover")?;
+        let proof = prover
+            .prove(Arc::clone(&self.zkey), witness)
+            .context("while computing proof")?;
+        tracing::info!("party {}: finished session", self.id());
+        Ok(ProofResponse {
+            proof,
+            public_inputs,
+        })
+    }
+}
+
+/// The error returned by the HTTP API. The error chain is returned as plain text body.
+#[derive(Debug)]
+pub struct ApiError(eyre::Report);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        tracing::error!("{:?}", self.0);
+        (StatusCode::INTERNAL_SERVER_ERROR, format!("{:#}", self.0)).into_response()
+    }
+}
+
+impl From<eyre::Report> for ApiError {
+    fn from(value: eyre::Report) -> Self {
+        Self(value)
+    }
+}
+
+async fn health() -> StatusCode {
+    StatusCode::OK
+}
+
+async fn prove(
+    State(party): State<Arc<Party>>,
+    body: Bytes,
+) -> Result<Json<ProofResponse>, ApiError> {
+    // the MPC protocols are blocking, so we run them outside of the async runtime
+    let response = tokio::task::spawn_blocking(move || party.prove(&body))
+        .await
+        .map_err(|e| eyre!("proving task failed: {e}"))??;
+    Ok(Json(response))
+}
+
+/// Builds the [Router] of the HTTP API for the given [Party].
+pub fn router(party: Arc<Party>) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/prove", post(prove))
+        .with_state(party)
+}
+
+/// Loads the [Party] and serves the HTTP API on the configured address until the returned future is dropped.
+pub async fn serve(config: ServerConfig) -> eyre::Result<()> {
+    serve_party(config, None).await
+}
+
+/// Loads the [Party] from the config file at the provided path and serves the HTTP API like [serve]. Additionally, the party reloads the network config from the file on SIGHUP and, if `watch_interval` is set, whenever the modification time of the config file, the key, or one of the certificates changed. A failed reload is logged and the party keeps its current network config.
+pub async fn serve_with_reload(
+    config_path: PathBuf,
+    watch_interval: Option<Duration>,
+) -> eyre::Result<()> {
+    let config = ServerConfig::from_file(&config_path)?;
+    serve_party(config, Some((config_path, watch_interval))).await
+}
+
+async fn serve_party(
+    config: ServerConfig,
+    reload: Option<(PathBuf, Option<Duration>)>,
+) -> eyre::Result<()> {
+    let http_addr = config.http_addr;
+    let party = tokio::task::spawn_blocking(move || Party::new(config))
+        .await
+        .context("while loading party")??;
+    let party = Arc::new(party);
+    if let Some((config_path, watch_interval)) = reload {
+        let party = Arc::clone(&party);
+        tokio::spawn(async move {
+            if let Err(err) = reload_on_change(party, config_path, watch_interval).await {
+                tracing::error!("stopped reloading the network config: {err:?}");
+            }
+        });
+    }
+    let listener = tokio::net::TcpListener::bind(http_addr)
+        .await
+        .context(format!("while binding to {http_addr}"))?;
+    tracing::info!("party {}: listening on {http_addr}", party.id());
+    axum::serve(listener, router(party))
+        .await
+        .context("while serving HTTP API")
+}
+
+/// The modification times of the config file and of the key and certificate files it references. Files that can not be read are recorded as `None`, such that they are picked up once they are written.
+fn config_fingerprint(config_path: &Path) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let mut paths = vec![config_path.to_path_buf()];
+    if let Ok(config) = ServerConfig::from_file(config_path) {
+        paths.push(config.network.key_path);
+        paths.extend(config.network.parties.into_iter().map(|p| p.cert_path));
+    }
+    paths
+        .into_iter()
+        .map(|path| {
+            let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+async fn reload_on_change(
+    party: Arc<Party>,
+    config_path: PathBuf,
+    watch_interval: Option<Duration>,
+) -> eyre::Result<()> {
+    #[cfg(unix)]
+    let mut sighup = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())
+        .context("while installing SIGHUP handler")?;
+    let mut fingerprint = config_fingerprint(&config_path);
+    loop {
+        #[cfg(unix)]
+        let hangup = sighup.recv();
+        #[cfg(not(unix))]
+        let hangup = std::future::pending::<Option<()>>();
+        let tick = async {
+            match watch_interval {
+                Some(interval) => tokio::time::sleep(interval).await,
+                None => std::future::pending().await,
+            }
+        };
+        let reason = tokio::select! {
+            _ = hangup => "SIGHUP",
+            _ = tick => {
+                if config_fingerprint(&config_path) == fingerprint {
+                    continue;
+                }
+                "changed config files"
+            }
+        };
+        fingerprint = config_fingerprint(&config_path);
+        tracing::info!(
+            "party {}: reloading network config due to {reason}",
+            party.id()
+        );
+        let reloaded = {
+            let party = Arc::clone(&party);
+            let config_path = config_path.clone();
+            tokio::task::spawn_blocking(move || party.reload_network_from_file(&config_path))
+                .await
+                .context("reload task failed")?
+        };
+        match reloaded {
+            Ok(true) => tracing::info!(
+                "party {}: reloaded network config, it applies to new sessions",
+                party.id()
+            ),
+            Ok(false) => tracing::info!("party {}: network config is unchanged", party.id()),
+            Err(err) => tracing::error!(
+                "party {}: keeping the current network config, reload failed: {err:?}",
+                party.id()
+            ),
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use std::{path::PathBuf, time::Duration};
+
+use ark_bn254::Bn254;
+use circom_types::groth16::JsonVerificationKey;
+use co_circom_age_verification::{
+    client::{self, Attributes, Statement},
+    server::{self, ServerConfig},
+};
+use rand::thread_rng;
+
+const STATEMENT: Statement = Statement {
+    min_age: 18,
+    blacklist: [1, 2, 3],
+};
+
+async fn start_parties() -> [String; 3] {
+    let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+    let mut urls = Vec::with_capacity(3);
+    for i in 1..=3 {
+        let config = ServerConfig::from_file(&PathBuf::from(format!("configs/party{i}.toml")))
+            .expect("can read config");
+        urls.push(format!("http://{}", config.http_addr));
+        tokio::spawn(server::serve(config));
+    }
+    let client = reqwest::Client::new();
+    for url in urls.iter() {
+        let mut ready = false;
+        for _ in 0..600 {
+            if let Ok(response) = client.get(format!("{url}/health")).send().await {
+                if response.status().is_success() {
+                    ready = true;
+                    break;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert!(ready, "party at {url} did not start");
+    }
+    urls.try_into().expect("three parties")
+}
+
+fn read_vk() -> JsonVerificationKey<Bn254> {
+    let vk_file = std::fs::File::open(
+        "../co-circom/examples/groth16/test_vectors/kyc/bn254/verification_key.json",
+    )
+    .expect("can open vk");
+    serde_json::from_reader(vk_file).expect("can parse vk")
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn age_verification_http_e2e() {
+    let urls = start_parties().await;
+    let http_client = reqwest::Client::new();
+    let vk = read_vk();
+
+    let shares = client::share_attributes(
+        Attributes {
+            age: 21,
+            country: 4,
+        },
+        STATEMENT,
+        &mut thread_rng(),
+    )
+    .unwrap();
+    let response = client::request_proof(&http_client, &urls, &shares)
+        .await
+        .unwrap();
+    client::verify_proof(&vk, &STATEMENT, &response).unwrap();
+
+    // a proof for a different statement is rejected
+    let other_statement = Statement {
+        min_age: 21,
+        ..STATEMENT
+    };
+    assert!(client::verify_proof(&vk, &other_statement, &response).is_err());
+
+    // the parties refuse to prove a false statement, afterwards they are still able to serve requests
+    let shares = client::share_attributes(
+        Attributes {
+            age: 17,
+            country: 4,
+        },
+        STATEMENT,
+        &mut thread_rng(),
+    )
+    .unwrap();
+    assert!(client::request_proof(&http_client, &urls, &shares)
+        .await
+        .is_err());
+    let shares = client::share_attributes(
+        Attributes {
+            age: 30,
+            country: 2,
+        },
+        STATEMENT,
+        &mut thread_rng(),
+    )
+    .unwrap();
+    assert!(client::request_proof(&http_client, &urls, &shares)
+        .await
+        .is_err());
+
+    let shares = client::share_attributes(
+        Attributes {
+            age: 18,
+            country: 5,
+        },
+        STATEMENT,
+        &mut thread_rng(),
+    )
+    .unwrap();
+    let response = client::request_proof(&http_client, &urls, &shares)
+        .await
+        .unwrap();
+    client::verify_proof(&vk, &STATEMENT, &response).unwrap();
+}
+
+#[test]
+fn network_config_reload() {
+    let config = ServerConfig::from_file(&PathBuf::from("configs/party1.toml")).unwrap();
+    let mut network = config.network.clone();
+    let party = server::Party::new(config).unwrap();
+
+    // reloading the same config is a no-op
+    assert!(!party.reload_network(network.clone()).unwrap());
+
+    // a new bind address applies to new sessions
+    network.bind_addr = "0.0.0.0:10200".parse().unwrap();
+    assert!(party.reload_network(network.clone()).unwrap());
+    assert_eq!(party.network_config().bind_addr, network.bind_addr);
+
+    // invalid configs and a change of the id are rejected and keep the current config
+    let mut other_id = network.clone();
+    other_id.my_id = 1;
+    assert!(party.reload_network(other_id).is_err());
+    let mut missing_key = network.clone();
+    missing_key.key_path = "does/not/exist.der".into();
+    assert!(party.reload_network(missing_key).is_err());
+    assert_eq!(party.network_config().my_id, 0);
+    assert_eq!(party.network_config().bind_addr, network.bind_addr);
+}
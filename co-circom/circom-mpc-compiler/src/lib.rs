@@ -8,11 +8,14 @@
 //! The [`CoCircomCompiler`], provides two methods for interacting with circom files
 //!     * [`CoCircomCompiler::parse`] - to parse a circuit
 //!     * [`CoCircomCompiler::get_public_inputs`] - to obtain the name of the public inputs of the circuit
+//!     * [`CoCircomCompiler::get_input_layouts`] - to obtain the layout of the inputs of the circuit, which defines how inputs with buses are flattened
 //!
 //! To configure the compiler, have a look at [`CompilerConfig`].
 //!
 //! The [`parse()`](CoCircomCompiler::parse) method consumes the compiler and returns an instance of [`CoCircomCompilerParsed`].
 //! Refer to its documentation to learn how to create an MPC-VM for the witness extension.
+//!
+//! The [`linear_signals`] module provides an optimization pass over the R1CS of a circuit that eliminates linear intermediate signals from the shared witness.
 use ark_ec::pairing::Pairing;
 use ark_ff::{BigInteger, PrimeField};
 use circom_compiler::{
@@ -39,12 +42,15 @@ use circom_program_structure::{
 use circom_type_analysis::check_types;
 use circom_types::traits::CircomArkworksPairingBridge;
 use circom_types::traits::CircomArkworksPrimeFieldBridge;
+use circom_types::InputLayout;
 use eyre::eyre;
 use eyre::{bail, Result};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, marker::PhantomData, path::PathBuf};
 
+pub mod linear_signals;
+
 /// The simplification level applied during constraint generation
 #[derive(
     Debug, Default, Copy, Clone, Serialize, Deserialize, Eq, PartialEq, PartialOrd, Ord, Hash,
@@ -626,6 +632,28 @@ where
         Self::new(file, config).get_public_inputs_inner()
     }
 
+    /// Returns a `Result<Vec<InputLayout>>`
+    /// containing the layouts of the inputs of the main component of the provided .circom file.
+    ///
+    /// The layouts define the order in which the values of inputs, in particular of buses and arrays of buses, are flattened, see [`InputLayout::flatten`]. In contrast to [`CoCircomCompiler::get_public_inputs`], this method builds the circuit.
+    ///
+    /// # Params
+    /// * **file** - a `String` denoting the path to circom file.
+    /// * **config** - the [CompilerConfig]
+    /// # Returns
+    ///
+    /// Returns a `Result` where:
+    ///
+    /// - `Ok(layouts)` contains the layouts of the inputs in the order of the signals.
+    /// - `Err(err)` indicates an error occurred during parsing or compilation.
+    pub fn get_input_layouts<Pth>(file: Pth, config: CompilerConfig) -> Result<Vec<InputLayout>>
+    where
+        PathBuf: From<Pth>,
+        Pth: std::fmt::Debug,
+    {
+        Self::new(file, config).get_input_layouts_inner()
+    }
+
     /// Parsed the circuit provided by `file` and returns a `Result` of [`CoCircomCompilerParsed`].
     ///
     /// # Params
@@ -656,6 +684,41 @@ where
         Ok(program_archive.public_inputs)
     }
 
+    fn get_input_layouts_inner(self) -> Result<Vec<InputLayout>> {
+        let program_archive = self.get_program_archive()?;
+        let (circuit, _) = self.build_circuit(program_archive)?;
+        let producer = &circuit.c_producer;
+        // the fields of every bus sorted by their offset, i.e., in the order of their declaration
+        let bus_fields = producer
+            .busid_field_info
+            .iter()
+            .map(|fields| {
+                fields
+                    .iter()
+                    .sorted_by_key(|field| field.offset)
+                    .map(|field| {
+                        let layout = InputLayout {
+                            name: field.name.clone(),
+                            dimensions: field.dimensions.clone(),
+                            fields: vec![],
+                        };
+                        (layout, field.bus_id)
+                    })
+                    .collect_vec()
+            })
+            .collect_vec();
+        Ok(producer
+            .main_input_list
+            .iter()
+            .sorted_by_key(|input| input.start)
+            .map(|input| InputLayout {
+                name: input.name.clone(),
+                dimensions: input.dimensions.clone(),
+                fields: get_bus_layout(&bus_fields, input.bus_id),
+            })
+            .collect())
+    }
+
     fn parse_inner(mut self) -> Result<CoCircomCompilerParsed<P::ScalarField>> {
         tracing::debug!("compiler starts parsing..");
         let program_archive = self.get_program_archive()?;
@@ -744,6 +807,23 @@ where
     }
 }
 
+/// Returns the layouts of the fields of the bus with the provided id, or no fields for signals.
+fn get_bus_layout(
+    bus_fields: &[Vec<(InputLayout, Option<usize>)>],
+    bus_id: Option<usize>,
+) -> Vec<InputLayout> {
+    let Some(bus_id) = bus_id else {
+        return vec![];
+    };
+    bus_fields[bus_id]
+        .iter()
+        .map(|(layout, field_bus_id)| InputLayout {
+            fields: get_bus_layout(bus_fields, *field_bus_id),
+            ..layout.clone()
+        })
+        .collect()
+}
+
 fn get_size_from_size_option(size_option: &SizeOption) -> usize {
     match size_option {
         SizeOption::Single(v) => *v,
@@ -763,6 +843,7 @@ mod tests {
     use circom_mpc_vm::mpc_vm::VMConfig;
 
     use crate::{CoCircomCompiler, CompilerConfig};
+    use circom_types::InputLayout;
     use std::str::FromStr;
     macro_rules! to_field_vec {
         ($vec: expr) => {
@@ -808,4 +889,38 @@ mod tests {
             .get_output("SomeThingThatIsNotAnOutput")
             .is_none());
     }
+
+    #[test]
+    fn test_get_input_layouts_with_buses() {
+        let layouts = CoCircomCompiler::<Bn254>::get_input_layouts(
+            "../../test_vectors/WitnessExtension/tests/bus_input.circom".to_owned(),
+            CompilerConfig::default(),
+        )
+        .unwrap();
+        let layout = |name: &str, dimensions: Vec<usize>, fields| InputLayout {
+            name: name.to_owned(),
+            dimensions,
+            fields,
+        };
+        let point = |name| {
+            layout(
+                name,
+                vec![],
+                vec![layout("y", vec![], vec![]), layout("x", vec![], vec![])],
+            )
+        };
+        let segment = layout(
+            "in",
+            vec![2],
+            vec![
+                point("start"),
+                layout("weights", vec![2], vec![]),
+                point("end"),
+            ],
+        );
+        assert_eq!(layouts.len(), 2);
+        assert!(layouts.contains(&segment));
+        assert!(layouts.contains(&layout("scale", vec![], vec![])));
+        assert_eq!(segment.size(), 12);
+    }
 }
@@ -0,0 +1,283 @@
+//! Elimination of linear intermediate signals from the shared witness.
+//!
+//! Many intermediate signals of a circuit are linear combinations of other signals, e.g., the sum of the bits of a decomposition or the output of a linear layer. Circom proves this with a linear constraint, i.e., a constraint `A * B = C` where `A` or `B` is a constant. As linear combinations of shares can be computed without interaction, the computing parties do not need to store and exchange the shares of such signals: they can recompute them from the public inputs and the shares of the other signals.
+//!
+//! The [LinearSignals] pass analyzes the linear constraints of an R1CS and selects signals that are defined by a linear combination of kept signals. [LinearSignals::reduce] removes them from the private witness after the witness extension, [LinearSignals::expand] recomputes them before the proof generation. As the proving key still covers all signals, the prover works on the expanded witness.
+//!
+//! Only intermediate signals are eliminated, the inputs of the main component and the public signals are always kept. The definitions of the eliminated signals only reference kept signals, so all of them can be recomputed in a single pass.
+
+use ark_ec::pairing::Pairing;
+use ark_ff::PrimeField;
+use circom_types::R1CS;
+use eyre::{bail, Result};
+
+/// An eliminated signal together with its definition as a linear combination of kept signals.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivedSignal<F: PrimeField> {
+    /// The index of the eliminated signal in the witness, i.e., the R1CS wire
+    pub index: usize,
+    /// The `(coefficient, wire)` pairs of the defining linear combination. Wire 0 is the constant 1.
+    pub terms: Vec<(F, usize)>,
+}
+
+/// The result of the linear signal elimination pass, see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinearSignals<F: PrimeField> {
+    num_public: usize,
+    num_private: usize,
+    // sorted by the index of the eliminated signal
+    derived: Vec<DerivedSignal<F>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WireState {
+    /// Can still be eliminated
+    Free,
+    /// Referenced by the definition of an eliminated signal or never eliminated
+    Pinned,
+    Eliminated,
+}
+
+/// Returns the linear combination `lc` with `lc * w = 0` if the constraint `a * b = c` is linear, i.e., if `a` or `b` is constant.
+fn as_linear<F: PrimeField>(
+    a: &[(usize, F)],
+    b: &[(usize, F)],
+    c: &[(usize, F)],
+) -> Option<Vec<(F, usize)>> {
+    let constant = |lc: &[(usize, F)]| -> Option<F> {
+        lc.iter().try_fold(F::zero(), |acc, (wire, coeff)| {
+            (*wire == 0).then_some(acc + coeff)
+        })
+    };
+    // a * b - c = 0 with a or b constant
+    let (factor, other) = if let Some(factor) = constant(a) {
+        (factor, b)
+    } else if let Some(factor) = constant(b) {
+        (factor, a)
+    } else {
+        return None;
+    };
+    let mut lc = other
+        .iter()
+        .map(|(wire, coeff)| (factor * coeff, *wire))
+        .chain(c.iter().map(|(wire, coeff)| (-*coeff, *wire)))
+        .collect::<Vec<_>>();
+    // merge duplicate wires
+    lc.sort_by_key(|(_, wire)| *wire);
+    let mut merged: Vec<(F, usize)> = Vec::with_capacity(lc.len());
+    for (coeff, wire) in lc {
+        match merged.last_mut() {
+            Some((last_coeff, last_wire)) if *last_wire == wire => *last_coeff += coeff,
+            _ => merged.push((coeff, wire)),
+        }
+    }
+    merged.retain(|(coeff, _)| !coeff.is_zero());
+    Some(merged)
+}
+
+impl<F: PrimeField> LinearSignals<F> {
+    /// Runs the elimination pass on the constraints of the provided R1CS.
+    pub fn from_r1cs<P: Pairing<ScalarField = F>>(r1cs: &R1CS<P>) -> Self {
+        let num_public = r1cs.num_inputs;
+        let num_private = r1cs.num_variables - num_public;
+        // the private inputs of the main component directly follow the public signals
+        let first_intermediate = num_public + r1cs.n_prv_in as usize;
+        let mut state = (0..r1cs.num_variables)
+            .map(|wire| {
+                if wire < first_intermediate {
+                    WireState::Pinned
+                } else {
+                    WireState::Free
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut derived = Vec::new();
+        for (a, b, c) in r1cs.constraints.iter() {
+            let Some(lc) = as_linear(a, b, c) else {
+                continue;
+            };
+            if lc
+                .iter()
+                .any(|(_, wire)| state[*wire] == WireState::Eliminated)
+            {
+                continue;
+            }
+            // prefer the latest signal, which is usually the result of the linear combination
+            let Some(pos) = lc
+                .iter()
+                .rposition(|(_, wire)| state[*wire] == WireState::Free)
+            else {
+                continue;
+            };
+            let (coeff, index) = lc[pos];
+            let inv = -coeff.inverse().expect("merged coefficients are nonzero");
+            let terms = lc
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != pos)
+                .map(|(_, (c, wire))| (*c * inv, *wire))
+                .collect::<Vec<_>>();
+            for (_, wire) in terms.iter() {
+                state[*wire] = WireState::Pinned;
+            }
+            state[index] = WireState::Eliminated;
+            derived.push(DerivedSignal { index, terms });
+        }
+        derived.sort_by_key(|signal| signal.index);
+        tracing::debug!(
+            "eliminated {} of {num_private} private signals",
+            derived.len()
+        );
+        Self {
+            num_public,
+            num_private,
+            derived,
+        }
+    }
+
+    /// Returns the number of public inputs, including the constant 1.
+    pub fn num_public(&self) -> usize {
+        self.num_public
+    }
+
+    /// Returns the number of private signals of the full witness.
+    pub fn num_private(&self) -> usize {
+        self.num_private
+    }
+
+    /// Returns the eliminated signals, sorted by their index.
+    pub fn derived(&self) -> &[DerivedSignal<F>] {
+        &self.derived
+    }
+
+    /// Returns the number of private signals of the reduced witness.
+    pub fn num_reduced(&self) -> usize {
+        self.num_private - self.derived.len()
+    }
+
+    /// Removes the eliminated signals from the private witness.
+    pub fn reduce<S>(&self, witness: Vec<S>) -> Result<Vec<S>> {
+        if witness.len() != self.num_private {
+            bail!(
+                "expected {} private witness elements, got {}",
+                self.num_private,
+                witness.len()
+            );
+        }
+        let mut derived = self.derived.iter().map(|signal| signal.index).peekable();
+        Ok(witness
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| {
+                if derived.peek() == Some(&(i + self.num_public)) {
+                    derived.next();
+                    false
+                } else {
+                    true
+                }
+            })
+            .map(|(_, value)| value)
+            .collect())
+    }
+
+    /// Recomputes the eliminated signals and returns the full private witness. The function `eval` evaluates a linear combination of shares plus a public constant, which is a local operation in all MPC protocols.
+    pub fn expand<S, E>(&self, public_inputs: &[F], reduced: Vec<S>, eval: E) -> Result<Vec<S>>
+    where
+        E: Fn(F, &[(F, &S)]) -> S,
+    {
+        if public_inputs.len() != self.num_public {
+            bail!(
+                "expected {} public inputs, got {}",
+                self.num_public,
+                public_inputs.len()
+            );
+        }
+        if reduced.len() != self.num_reduced() {
+            bail!(
+                "expected {} private witness elements in the reduced witness, got {}",
+                self.num_reduced(),
+                reduced.len()
+            );
+        }
+        let mut witness = Vec::with_capacity(self.num_private);
+        let mut derived = self.derived.iter().map(|signal| signal.index).peekable();
+        let mut reduced = reduced.into_iter();
+        for i in 0..self.num_private {
+            if derived.peek() == Some(&(i + self.num_public)) {
+                derived.next();
+                witness.push(None);
+            } else {
+                witness.push(reduced.next());
+            }
+        }
+        let values = self
+            .derived
+            .iter()
+            .map(|signal| {
+                let mut constant = F::zero();
+                let mut shared = Vec::with_capacity(signal.terms.len());
+                for (coeff, wire) in signal.terms.iter() {
+                    if *wire < self.num_public {
+                        constant += *coeff * public_inputs[*wire];
+                    } else {
+                        let share = witness[*wire - self.num_public]
+                            .as_ref()
+                            .expect("definitions only reference kept signals");
+                        shared.push((*coeff, share));
+                    }
+                }
+                eval(constant, &shared)
+            })
+            .collect::<Vec<_>>();
+        for (signal, value) in self.derived.iter().zip(values) {
+            witness[signal.index - self.num_public] = Some(value);
+        }
+        Ok(witness
+            .into_iter()
+            .map(|value| value.expect("all signals are set"))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::Bn254;
+    use circom_types::{Witness, R1CS};
+    use std::fs::File;
+
+    use super::LinearSignals;
+
+    #[test]
+    fn reduce_and_expand_poseidon() {
+        let r1cs = R1CS::<Bn254>::from_reader(
+            File::open("../../test_vectors/Groth16/bn254/poseidon/circuit.r1cs").unwrap(),
+        )
+        .unwrap();
+        let witness = Witness::<ark_bn254::Fr>::from_reader(
+            File::open("../../test_vectors/Groth16/bn254/poseidon/witness.wtns").unwrap(),
+        )
+        .unwrap();
+        let linear = LinearSignals::from_r1cs(&r1cs);
+        let (public_inputs, private) = witness.values.split_at(linear.num_public());
+        for signal in linear.derived() {
+            assert!(signal
+                .terms
+                .iter()
+                .all(|(_, wire)| linear.derived().iter().all(|s| s.index != *wire)));
+        }
+
+        let reduced = linear.reduce(private.to_vec()).unwrap();
+        assert_eq!(reduced.len(), linear.num_reduced());
+        let expanded = linear
+            .expand(public_inputs, reduced, |constant, terms| {
+                constant
+                    + terms
+                        .iter()
+                        .map(|(coeff, value)| *coeff * **value)
+                        .sum::<ark_bn254::Fr>()
+            })
+            .unwrap();
+        assert_eq!(expanded, private);
+    }
+}
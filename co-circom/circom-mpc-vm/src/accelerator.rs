@@ -1,21 +1,29 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use ark_ff::PrimeField;
 use eyre::bail;
 
-use crate::mpc::VmCircomWitnessExtension;
+use crate::{
+    compiled_circuit::{circuit_hash_to_hex, TemplateHash},
+    mpc::VmCircomWitnessExtension,
+    types::CoCircomCompilerParsed,
+};
 
 type AcceleratorFunction<F, C> = Box<
     dyn Fn(
             &mut C,
             &[<C as VmCircomWitnessExtension<F>>::VmType],
         ) -> eyre::Result<Vec<<C as VmCircomWitnessExtension<F>>::VmType>>
-        + Send,
+        + Send
+        + Sync,
 >;
 
+/// The result of a component accelerator, i.e., the values of the output signals and of the intermediate signals of the component.
 pub struct ComponentAcceleratorOutput<T> {
-    pub(crate) output: Vec<T>,
-    pub(crate) intermediate: Vec<T>,
+    /// The values of the output signals of the component
+    pub output: Vec<T>,
+    /// The values of the intermediate signals of the component, in the order of their declaration
+    pub intermediate: Vec<T>,
 }
 
 type AcceleratorComponent<F, C> = Box<
@@ -25,9 +33,11 @@ type AcceleratorComponent<F, C> = Box<
             usize,
         )
             -> eyre::Result<ComponentAcceleratorOutput<<C as VmCircomWitnessExtension<F>>::VmType>>
-        + Send,
+        + Send
+        + Sync,
 >;
 
+/// Configures which of the pre-defined accelerators are used.
 #[derive(Debug, Clone)]
 pub struct MpcAcceleratorConfig {
     /// Whether to use the pre-defined SQRT accelerator
@@ -101,25 +111,34 @@ impl MpcAcceleratorConfig {
     }
 }
 
+/// The accelerators of the MPC-VM, which replace the bytecode of functions and templates with native implementations.
+///
+/// Function and component accelerators are registered by name and are used for every function or template with this name. Template accelerators are registered by name and [TemplateHash], and are only used for the instantiations of the template whose bytecode matches the hash. The MPC-VM interprets the bytecode of all other instantiations.
 #[derive(Default)]
 pub struct MpcAccelerator<F: PrimeField, C: VmCircomWitnessExtension<F>> {
     registered_functions: HashMap<String, AcceleratorFunction<F, C>>,
     registered_component: HashMap<String, AcceleratorComponent<F, C>>,
+    registered_templates: HashMap<(String, TemplateHash), AcceleratorComponent<F, C>>,
+    bound_templates: HashMap<String, (String, TemplateHash)>,
 }
 
 impl<F: PrimeField, C: VmCircomWitnessExtension<F>> MpcAccelerator<F, C> {
+    /// Constructs an accelerator without any registered functions or templates.
     pub fn empty() -> Self {
         Self {
             registered_functions: HashMap::default(),
             registered_component: HashMap::default(),
+            registered_templates: HashMap::default(),
+            bound_templates: HashMap::default(),
         }
     }
 
-    #[expect(unused)]
+    /// Constructs an accelerator with all pre-defined accelerators.
     pub fn full() -> Self {
         Self::from_config(Default::default())
     }
 
+    /// Constructs an accelerator with the pre-defined accelerators enabled in the provided [MpcAcceleratorConfig].
     pub fn from_config(config: MpcAcceleratorConfig) -> Self {
         let mut accelerator = Self::empty();
         if config.sqrt {
@@ -137,24 +156,94 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> MpcAccelerator<F, C> {
         accelerator
     }
 
+    /// Registers an accelerator for all functions with the provided name.
     pub fn register_function(
         &mut self,
         name: String,
-        fun: impl Fn(&mut C, &[C::VmType]) -> eyre::Result<Vec<C::VmType>> + Send + 'static,
+        fun: impl Fn(&mut C, &[C::VmType]) -> eyre::Result<Vec<C::VmType>> + Send + Sync + 'static,
     ) {
         self.registered_functions.insert(name, Box::new(fun));
     }
 
+    /// Registers an accelerator for all templates with the provided name.
     pub fn register_component(
         &mut self,
         name: String,
         fun: impl Fn(&mut C, &[C::VmType], usize) -> eyre::Result<ComponentAcceleratorOutput<C::VmType>>
             + Send
+            + Sync
             + 'static,
     ) {
         self.registered_component.insert(name, Box::new(fun));
     }
 
+    /// Registers an accelerator for the instantiations of the template with the provided name whose bytecode has the provided [TemplateHash], see [CoCircomCompilerParsed::template_hashes]. The accelerator receives the values of the input signals and the amount of output signals.
+    ///
+    /// If the bytecode of an instantiation does not match any registered hash, e.g., because the template was changed, the MPC-VM logs a warning and interprets its bytecode.
+    pub fn register_template(
+        &mut self,
+        name: String,
+        hash: TemplateHash,
+        fun: impl Fn(&mut C, &[C::VmType], usize) -> eyre::Result<ComponentAcceleratorOutput<C::VmType>>
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        self.registered_templates
+            .insert((name, hash), Box::new(fun));
+    }
+
+    /// Matches the registered template accelerators against the template instantiations of the provided circuit. Must be called before the accelerator is used for this circuit.
+    pub(crate) fn bind_templates(
+        &mut self,
+        parsed: &CoCircomCompilerParsed<F>,
+    ) -> eyre::Result<()> {
+        self.bound_templates.clear();
+        let names = self
+            .registered_templates
+            .keys()
+            .map(|(name, _)| name.as_str())
+            .collect::<HashSet<_>>();
+        for (symbol, templ_decl) in parsed.templ_decls.iter() {
+            if !names.contains(templ_decl.component_name.as_str()) {
+                continue;
+            }
+            let key = (
+                templ_decl.component_name.clone(),
+                parsed.template_hash(templ_decl)?,
+            );
+            if self.registered_templates.contains_key(&key) {
+                tracing::debug!("using template accelerator for {symbol}");
+                self.bound_templates.insert(symbol.clone(), key);
+            } else {
+                tracing::warn!(
+                    "no template accelerator for {symbol} with hash {}, interpreting the bytecode",
+                    circuit_hash_to_hex(&key.1)
+                );
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn has_template_accelerator(&self, symbol: &str) -> bool {
+        self.bound_templates.contains_key(symbol)
+    }
+
+    pub(crate) fn run_template_accelerator(
+        &self,
+        symbol: &str,
+        protocol: &mut C,
+        args: &[C::VmType],
+        amount_outputs: usize,
+    ) -> eyre::Result<ComponentAcceleratorOutput<C::VmType>> {
+        let fun = self
+            .bound_templates
+            .get(symbol)
+            .and_then(|key| self.registered_templates.get(key))
+            .ok_or(eyre::eyre!("cannot find template accelerator {symbol}"))?;
+        fun(protocol, args, amount_outputs)
+    }
+
     pub(crate) fn has_fn_accelerator(&self, name: &str) -> bool {
         self.registered_functions.contains_key(name)
     }
@@ -0,0 +1,159 @@
+use std::io::{Read, Write};
+
+use ark_ff::{BigInteger, PrimeField};
+use bincode::Options;
+use eyre::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    op_codes::MpcOpCode,
+    types::{CoCircomCompilerParsed, TemplateDecl},
+};
+
+/// The version of the compiled circuit format. It is increased whenever the format or the bytecode of the MPC-VM changes in an incompatible way.
+pub const COMPILED_CIRCUIT_FORMAT_VERSION: u32 = 1;
+
+/// The maximum size of a compiled circuit that is accepted when reading it.
+pub const MAX_COMPILED_CIRCUIT_SIZE: u64 = 1 << 34;
+
+/// The SHA-256 hash of the bytecode of a compiled circuit.
+pub type CircuitHash = [u8; 32];
+
+/// The SHA-256 hash of the bytecode of a single template, see [CoCircomCompilerParsed::template_hashes].
+pub type TemplateHash = [u8; 32];
+
+/// Returns the lowercase hex encoding of a [CircuitHash], e.g., for logging it.
+pub fn circuit_hash_to_hex(hash: &[u8]) -> String {
+    hash.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+const MAGIC: [u8; 8] = *b"COCIRCVM";
+
+#[derive(Serialize, Deserialize)]
+struct CompiledCircuitHeader {
+    magic: [u8; 8],
+    format_version: u32,
+    vm_version: String,
+    modulus: Vec<u8>,
+    hash: CircuitHash,
+}
+
+impl<F: PrimeField> CoCircomCompilerParsed<F> {
+    /// Serializes the circuit to its canonical bytecode. Maps are serialized in order of their keys, so compiling the same circuit with the same compiler config always results in the same bytes.
+    fn bytecode(&self) -> Result<Vec<u8>> {
+        bincode::serialize(self).context("while serializing compiled circuit")
+    }
+
+    /// Returns the [CircuitHash] of the canonical bytecode of the circuit. This is the same hash that is stored by [write_compiled](Self::write_compiled).
+    ///
+    /// The MPC-VMs exchange this hash when they are constructed and abort if the parties do not execute the same circuit.
+    pub fn circuit_hash(&self) -> Result<CircuitHash> {
+        Ok(Sha256::digest(self.bytecode()?).into())
+    }
+
+    /// Returns the [TemplateHash] of the provided template instantiation. The hash covers the signal layout and the bytecode of the template, where constants are replaced by their values, so it does not depend on the remaining circuit. It does not cover the sub-components of the template.
+    pub(crate) fn template_hash(&self, templ_decl: &TemplateDecl) -> Result<TemplateHash> {
+        let mut hasher = Sha256::new();
+        let layout = (
+            &templ_decl.component_name,
+            templ_decl.input_signals,
+            templ_decl.output_signals,
+            templ_decl.sub_components,
+            templ_decl.vars,
+            &templ_decl.mappings,
+        );
+        hasher.update(bincode::serialize(&layout).context("while serializing template")?);
+        for op in templ_decl.body.iter() {
+            match op {
+                MpcOpCode::PushConstant(index) => {
+                    let constant = self.constant_table.get(*index).ok_or_else(|| {
+                        eyre::eyre!("template references unknown constant {index}")
+                    })?;
+                    hasher.update(b"constant");
+                    hasher.update(constant.into_bigint().to_bytes_le());
+                }
+                op => hasher.update(bincode::serialize(op).context("while serializing template")?),
+            }
+        }
+        Ok(hasher.finalize().into())
+    }
+
+    /// Returns the [TemplateHash]es of all instantiations of the template with the provided name, i.e., one hash per set of template parameters used in the circuit.
+    ///
+    /// An accelerator registered with [MpcAccelerator::register_template](crate::accelerator::MpcAccelerator::register_template) for one of these hashes replaces the bytecode of the corresponding instantiation.
+    pub fn template_hashes(&self, name: &str) -> Result<Vec<TemplateHash>> {
+        let mut templ_decls = self
+            .templ_decls
+            .values()
+            .filter(|templ_decl| templ_decl.component_name == name)
+            .collect::<Vec<_>>();
+        templ_decls.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        templ_decls
+            .into_iter()
+            .map(|templ_decl| self.template_hash(templ_decl))
+            .collect()
+    }
+
+    /// Writes the compiled circuit to the provided writer. The format consists of a header with the format version, the version of the MPC-VM, the modulus of the prime field, and the [CircuitHash] of the bytecode, followed by the bytecode itself.
+    ///
+    /// Returns the [CircuitHash], which can be compared between the parties to ensure that all of them use the same circuit.
+    pub fn write_compiled<W: Write>(&self, mut writer: W) -> Result<CircuitHash> {
+        let body = self.bytecode()?;
+        let hash: CircuitHash = Sha256::digest(&body).into();
+        let header = CompiledCircuitHeader {
+            magic: MAGIC,
+            format_version: COMPILED_CIRCUIT_FORMAT_VERSION,
+            vm_version: env!("CARGO_PKG_VERSION").to_owned(),
+            modulus: F::MODULUS.to_bytes_le(),
+            hash,
+        };
+        bincode::serialize_into(&mut writer, &header).context("while writing header")?;
+        bincode::serialize_into(&mut writer, &body).context("while writing bytecode")?;
+        Ok(hash)
+    }
+
+    /// Reads a compiled circuit written by [write_compiled](Self::write_compiled). Fails if the circuit was compiled with a different format version, a different version of the MPC-VM, or for a different prime field, or if the bytecode does not match the hash in the header.
+    ///
+    /// Returns the circuit together with its [CircuitHash].
+    pub fn read_compiled<R: Read>(mut reader: R) -> Result<(Self, CircuitHash)> {
+        let options = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .allow_trailing_bytes()
+            .with_limit(MAX_COMPILED_CIRCUIT_SIZE);
+        let header: CompiledCircuitHeader = options
+            .deserialize_from(&mut reader)
+            .context("while reading header")?;
+        if header.magic != MAGIC {
+            bail!("not a compiled circuit");
+        }
+        if header.format_version != COMPILED_CIRCUIT_FORMAT_VERSION {
+            bail!(
+                "compiled circuit has format version {}, but expected {}",
+                header.format_version,
+                COMPILED_CIRCUIT_FORMAT_VERSION
+            );
+        }
+        if header.vm_version != env!("CARGO_PKG_VERSION") {
+            bail!(
+                "circuit was compiled for MPC-VM version {}, but this is version {}, please compile it again",
+                header.vm_version,
+                env!("CARGO_PKG_VERSION")
+            );
+        }
+        if header.modulus != F::MODULUS.to_bytes_le() {
+            bail!("circuit was compiled for a different prime field");
+        }
+        let body: Vec<u8> = options
+            .deserialize_from(&mut reader)
+            .context("while reading bytecode")?;
+        let hash: CircuitHash = Sha256::digest(&body).into();
+        if hash != header.hash {
+            bail!(
+                "hash of the bytecode does not match the header, the compiled circuit is corrupted"
+            );
+        }
+        let parsed = bincode::deserialize(&body).context("while deserializing compiled circuit")?;
+        Ok((parsed, hash))
+    }
+}
@@ -13,7 +13,10 @@
 //!
 //! Major changes and optimizations are expected in the near future.
 
-mod accelerator;
+/// This module contains the accelerators, which replace the bytecode of functions and templates with native implementations
+pub mod accelerator;
+/// This module contains the serialization format for compiled circuits
+pub mod compiled_circuit;
 /// This module contains the MPC-VM witness extension trait
 pub mod mpc;
 /// This module contains the MPC-VM
@@ -24,4 +27,5 @@ mod stack;
 /// Defines the types for the MPC-VM, including [template declaration](types::TemplateDecl) and [function declarations](types::FunDecl).
 pub mod types;
 
-pub use mpc::rep3::Rep3VmType;
+pub use mpc::plain::CircomPlainVmWitnessExtension;
+pub use mpc::rep3::{CircomRep3VmWitnessExtension, Rep3VmType};
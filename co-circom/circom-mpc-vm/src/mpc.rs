@@ -1,4 +1,4 @@
-use crate::mpc_vm::VMConfig;
+use crate::{compiled_circuit::CircuitHash, mpc_vm::VMConfig};
 use ark_ff::PrimeField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use eyre::Result;
@@ -8,11 +8,21 @@ pub(crate) mod plain;
 pub(crate) mod rep3;
 
 /// This trait represents the operations used during witness extension by the co-circom MPC-VM
-pub trait VmCircomWitnessExtension<F: PrimeField> {
+pub trait VmCircomWitnessExtension<F: PrimeField>: Send + Sized {
     /// The arithemitc share type
     type ArithmeticShare: CanonicalSerialize + CanonicalDeserialize + Clone + Default;
     /// The VM type
-    type VmType: Clone + Default + fmt::Debug + fmt::Display + From<F> + From<Self::ArithmeticShare>;
+    type VmType: Clone
+        + Default
+        + fmt::Debug
+        + fmt::Display
+        + From<F>
+        + From<Self::ArithmeticShare>
+        + Send
+        + Sync;
+
+    /// Forks the protocol. The fork can be used concurrently with its parent, e.g., to execute independent components in parallel.
+    fn fork(&mut self) -> Result<Self>;
 
     /// Add two VM-types: c = a + b.
     fn add(&mut self, a: Self::VmType, b: Self::VmType) -> Result<Self::VmType>;
@@ -115,6 +125,9 @@ pub trait VmCircomWitnessExtension<F: PrimeField> {
     /// Compares the VM Config with other parties
     fn compare_vm_config(&mut self, config: &VMConfig) -> Result<()>;
 
+    /// Compares the [CircuitHash] of the bytecode with other parties
+    fn compare_circuit_hash(&mut self, hash: &CircuitHash) -> Result<()>;
+
     /// num2bits accelerator
     fn num2bits(&mut self, a: Self::VmType, bits: usize) -> Result<Vec<Self::VmType>>;
 
@@ -1,5 +1,5 @@
 use super::VmCircomWitnessExtension;
-use crate::mpc_vm::VMConfig;
+use crate::{compiled_circuit::CircuitHash, mpc_vm::VMConfig};
 use ark_ff::{One, PrimeField};
 use eyre::eyre;
 use eyre::Result;
@@ -43,6 +43,7 @@ macro_rules! to_bigint {
     }};
 }
 
+/// The [VmCircomWitnessExtension] implementation of the plain MPC-VM, which operates on field elements without MPC.
 pub struct CircomPlainVmWitnessExtension<F: PrimeField> {
     negative_one: F,
 }
@@ -80,6 +81,10 @@ impl<F: PrimeField> VmCircomWitnessExtension<F> for CircomPlainVmWitnessExtensio
 
     type VmType = F;
 
+    fn fork(&mut self) -> Result<Self> {
+        Ok(Self::default())
+    }
+
     fn add(&mut self, a: Self::VmType, b: Self::VmType) -> Result<Self::VmType> {
         let result = a + b;
         tracing::trace!("{a}+{b}={result}");
@@ -270,6 +275,10 @@ impl<F: PrimeField> VmCircomWitnessExtension<F> for CircomPlainVmWitnessExtensio
         Ok(())
     }
 
+    fn compare_circuit_hash(&mut self, _hash: &CircuitHash) -> Result<()> {
+        Ok(())
+    }
+
     fn num2bits(&mut self, a: Self::VmType, bits: usize) -> Result<Vec<Self::VmType>> {
         let a = to_bigint!(a);
         let mut res = Vec::with_capacity(bits);
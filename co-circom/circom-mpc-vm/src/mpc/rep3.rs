@@ -2,7 +2,10 @@ use super::{
     plain::{to_usize, CircomPlainVmWitnessExtension},
     VmCircomWitnessExtension,
 };
-use crate::mpc_vm::VMConfig;
+use crate::{
+    compiled_circuit::{circuit_hash_to_hex, CircuitHash},
+    mpc_vm::VMConfig,
+};
 use ark_ff::{One, PrimeField};
 use eyre::{bail, eyre};
 use itertools::Itertools;
@@ -58,6 +61,7 @@ impl<F: PrimeField> Default for Rep3VmType<F> {
     }
 }
 
+/// The [VmCircomWitnessExtension] implementation of the MPC-VM using the REP3 protocol.
 pub struct CircomRep3VmWitnessExtension<F: PrimeField, N: Rep3Network> {
     io_context0: IoContext<N>,
     io_context1: IoContext<N>,
@@ -65,6 +69,7 @@ pub struct CircomRep3VmWitnessExtension<F: PrimeField, N: Rep3Network> {
 }
 
 impl<F: PrimeField, N: Rep3Network> CircomRep3VmWitnessExtension<F, N> {
+    /// Constructs the protocol from an already established [Rep3Network].
     pub fn from_network(network: N, a2b_type: conversion::A2BType) -> io::Result<Self> {
         let mut io_context = IoContext::init(network)?;
         io_context.set_a2b_type(a2b_type);
@@ -76,10 +81,19 @@ impl<F: PrimeField, N: Rep3Network> CircomRep3VmWitnessExtension<F, N> {
         })
     }
 
+    /// Consumes `self` and returns the underlying network.
     pub fn get_network(self) -> N {
         self.io_context0.network
     }
 
+    /// Checks whether a shared value is zero without opening the result. With garbled circuits as conversion protocol, the check needs a constant number of rounds, see [arithmetic::is_zero_shared].
+    fn is_zero_shared(&mut self, a: ArithmeticShare<F>) -> io::Result<ArithmeticShare<F>> {
+        match self.io_context0.a2b_type {
+            conversion::A2BType::Yao => arithmetic::is_zero_shared(a, &mut self.io_context0),
+            conversion::A2BType::Direct => arithmetic::eq_public(a, F::ZERO, &mut self.io_context0),
+        }
+    }
+
     /// Normally F is split into positive and negative numbers in the range [0, p/2] and [p/2 + 1, p)
     /// However, for comparisons, we want the negative numbers to be "lower" than the positive ones.
     /// Therefore we shift the input by p/2 + 1 to the left, which results in a mapping of [negative, 0, positive] into F.
@@ -102,6 +116,14 @@ impl<F: PrimeField, N: Rep3Network> VmCircomWitnessExtension<F>
 
     type VmType = Rep3VmType<F>;
 
+    fn fork(&mut self) -> eyre::Result<Self> {
+        Ok(Self {
+            io_context0: self.io_context0.fork()?,
+            io_context1: self.io_context1.fork()?,
+            plain: CircomPlainVmWitnessExtension::default(),
+        })
+    }
+
     fn add(&mut self, a: Self::VmType, b: Self::VmType) -> eyre::Result<Self::VmType> {
         match (a, b) {
             (Rep3VmType::Public(a), Rep3VmType::Public(b)) => Ok(self.plain.add(a, b)?.into()),
@@ -304,10 +326,12 @@ impl<F: PrimeField, N: Rep3Network> VmCircomWitnessExtension<F>
             (Rep3VmType::Public(a), Rep3VmType::Public(b)) => Ok(self.plain.eq(a, b)?.into()),
             (Rep3VmType::Public(b), Rep3VmType::Arithmetic(a))
             | (Rep3VmType::Arithmetic(a), Rep3VmType::Public(b)) => {
-                Ok(arithmetic::eq_public(a, b, &mut self.io_context0)?.into())
+                let diff = arithmetic::sub_shared_by_public(a, b, self.io_context0.id);
+                Ok(self.is_zero_shared(diff)?.into())
             }
             (Rep3VmType::Arithmetic(a), Rep3VmType::Arithmetic(b)) => {
-                Ok(arithmetic::eq(a, b, &mut self.io_context0)?.into())
+                let diff = arithmetic::sub(a, b);
+                Ok(self.is_zero_shared(diff)?.into())
             }
         }
     }
@@ -317,10 +341,13 @@ impl<F: PrimeField, N: Rep3Network> VmCircomWitnessExtension<F>
             (Rep3VmType::Public(a), Rep3VmType::Public(b)) => Ok(self.plain.neq(a, b)?.into()),
             (Rep3VmType::Public(b), Rep3VmType::Arithmetic(a))
             | (Rep3VmType::Arithmetic(a), Rep3VmType::Public(b)) => {
-                Ok(arithmetic::neq_public(a, b, &mut self.io_context0)?.into())
+                let diff = arithmetic::sub_shared_by_public(a, b, self.io_context0.id);
+                let eq = self.is_zero_shared(diff)?;
+                Ok(arithmetic::sub_public_by_shared(F::one(), eq, self.io_context0.id).into())
             }
             (Rep3VmType::Arithmetic(a), Rep3VmType::Arithmetic(b)) => {
-                Ok(arithmetic::neq(a, b, &mut self.io_context0)?.into())
+                let eq = self.is_zero_shared(arithmetic::sub(a, b))?;
+                Ok(arithmetic::sub_public_by_shared(F::one(), eq, self.io_context0.id).into())
             }
         }
     }
@@ -552,6 +579,22 @@ impl<F: PrimeField, N: Rep3Network> VmCircomWitnessExtension<F>
         Ok(())
     }
 
+    fn compare_circuit_hash(&mut self, hash: &CircuitHash) -> eyre::Result<()> {
+        // exchange with both other parties, such that every party detects a mismatch
+        let (prev, next) = self.io_context0.network.broadcast(hash.to_vec())?;
+        for rcv in [prev, next] {
+            if rcv[..] != hash[..] {
+                bail!(
+                    "Circuit hash does not match: {} != {}, the parties do not execute the same circuit",
+                    circuit_hash_to_hex(hash),
+                    circuit_hash_to_hex(&rcv)
+                );
+            }
+        }
+
+        Ok(())
+    }
+
     fn num2bits(&mut self, a: Self::VmType, bits: usize) -> eyre::Result<Vec<Self::VmType>> {
         match a {
             Rep3VmType::Public(a) => Ok(self
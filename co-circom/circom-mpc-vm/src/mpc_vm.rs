@@ -31,6 +31,17 @@ pub struct VMConfig {
     /// Define the implementation of the arithmetic/binary conversions.
     #[serde(default)]
     pub a2b_type: A2BType,
+    /// The maximum amount of sessions that execute independent sub-components concurrently. Every session uses its own forked network channel.
+    ///
+    /// Sub-components are independent if all of their inputs are provided before the parent reads one of their outputs. Values below 2 execute all components sequentially.
+    #[serde(default)]
+    pub max_parallel_sessions: usize,
+}
+
+impl VMConfig {
+    fn parallel_components(&self) -> bool {
+        self.max_parallel_sessions > 1
+    }
 }
 
 /// The MPC-VM that performs the witness extension.
@@ -43,6 +54,7 @@ pub struct VMConfig {
 pub struct WitnessExtension<F: PrimeField, C: VmCircomWitnessExtension<F>> {
     main: String,
     ctx: WitnessExtensionCtx<F, C>,
+    signals: Vec<C::VmType>,
     signal_to_witness: Vec<usize>,
     main_inputs: usize,
     main_outputs: usize,
@@ -84,12 +96,81 @@ struct Component<F: PrimeField, C: VmCircomWitnessExtension<F>> {
     functions_ctx: Stack<FunctionCtx<C::VmType>>,
     mappings: Vec<usize>,
     sub_components: Vec<Component<F, C>>,
+    /// the indices of sub components that received all inputs but were not executed yet
+    ready_sub_components: Vec<usize>,
     component_body: Arc<CodeBlock>,
     log_buf: String,
 }
 
+/// Read and write access to the signals during the witness extension.
+trait Signals<T> {
+    fn get(&self, index: usize) -> T;
+
+    fn set(&mut self, index: usize, value: T);
+
+    fn get_range(&self, start: usize, amount: usize) -> Vec<T> {
+        (start..start + amount)
+            .map(|index| self.get(index))
+            .collect()
+    }
+
+    fn set_range(&mut self, start: usize, values: &[T])
+    where
+        T: Clone,
+    {
+        for (index, value) in (start..).zip(values) {
+            self.set(index, value.clone());
+        }
+    }
+}
+
+impl<T: Clone> Signals<T> for Vec<T> {
+    fn get(&self, index: usize) -> T {
+        self[index].clone()
+    }
+
+    fn set(&mut self, index: usize, value: T) {
+        self[index] = value;
+    }
+
+    fn get_range(&self, start: usize, amount: usize) -> Vec<T> {
+        self[start..start + amount].to_vec()
+    }
+
+    fn set_range(&mut self, start: usize, values: &[T]) {
+        self[start..start + values.len()].clone_from_slice(values);
+    }
+}
+
+/// The signals of a session that executes sub-components concurrently. Reads fall through to the signals of the parent, writes are collected and applied to the parent after the session finished.
+struct SignalOverlay<'a, T> {
+    base: &'a (dyn Signals<T> + Sync),
+    writes: HashMap<usize, T>,
+}
+
+impl<'a, T> SignalOverlay<'a, T> {
+    fn new(base: &'a (dyn Signals<T> + Sync)) -> Self {
+        Self {
+            base,
+            writes: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Clone> Signals<T> for SignalOverlay<'_, T> {
+    fn get(&self, index: usize) -> T {
+        match self.writes.get(&index) {
+            Some(value) => value.clone(),
+            None => self.base.get(index),
+        }
+    }
+
+    fn set(&mut self, index: usize, value: T) {
+        self.writes.insert(index, value);
+    }
+}
+
 struct WitnessExtensionCtx<F: PrimeField, C: VmCircomWitnessExtension<F>> {
-    signals: Vec<C::VmType>,
     fun_decls: HashMap<String, FunDecl>,
     templ_decls: HashMap<String, TemplateDecl>,
     constant_table: Vec<C::VmType>,
@@ -178,7 +259,6 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> IfCtxStack<F, C> {
 
 impl<F: PrimeField, C: VmCircomWitnessExtension<F>> WitnessExtensionCtx<F, C> {
     fn new(
-        signals: Vec<C::VmType>,
         constant_table: Vec<C::VmType>,
         fun_decls: HashMap<String, FunDecl>,
         templ_decls: HashMap<String, TemplateDecl>,
@@ -186,7 +266,6 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> WitnessExtensionCtx<F, C> {
         mpc_accelerator: MpcAccelerator<F, C>,
     ) -> Self {
         Self {
-            signals,
             constant_table,
             fun_decls,
             templ_decls,
@@ -250,6 +329,7 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> Component<F, C> {
             functions_ctx: Stack::default(),
             mappings: templ_decl.mappings.clone(),
             sub_components: Vec::with_capacity(templ_decl.sub_components),
+            ready_sub_components: Vec::new(),
             component_body: Arc::clone(&templ_decl.body),
             log_buf: String::with_capacity(1024),
         }
@@ -281,10 +361,77 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> Component<F, C> {
             tracing::info!("{idx:0>4}|   {inst}");
         }
     }
-    pub fn run(
+    /// Executes the sub components that received all inputs. If enabled in the [VMConfig], the components are executed concurrently on forks of the protocol.
+    fn run_ready_sub_components<S: Signals<C::VmType> + Sync>(
+        &mut self,
+        protocol: &mut C,
+        signals: &mut S,
+        ctx: &WitnessExtensionCtx<F, C>,
+        config: &VMConfig,
+    ) -> Result<()> {
+        let mut ready = std::mem::take(&mut self.ready_sub_components);
+        if ready.len() < 2 {
+            for index in ready {
+                self.sub_components[index].run(protocol, signals, ctx, config)?;
+            }
+            return Ok(());
+        }
+        ready.sort_unstable();
+        let amount_sessions = config.max_parallel_sessions.min(ready.len());
+        tracing::debug!(
+            "running {} sub components of {} in {amount_sessions} sessions",
+            ready.len(),
+            self.symbol
+        );
+        let mut sessions = (0..amount_sessions)
+            .map(|_| Ok::<_, eyre::Report>((protocol.fork()?, Vec::new())))
+            .collect::<Result<Vec<_>>>()?;
+        // all parties must assign the components to the same sessions
+        for (i, component) in self
+            .sub_components
+            .iter_mut()
+            .enumerate()
+            .filter(|(index, _)| ready.binary_search(index).is_ok())
+            .map(|(_, component)| component)
+            .enumerate()
+        {
+            sessions[i % amount_sessions].1.push(component);
+        }
+        let base: &(dyn Signals<C::VmType> + Sync) = signals;
+        let writes = std::thread::scope(|scope| {
+            let handles = sessions
+                .iter_mut()
+                .map(|(protocol, components)| {
+                    scope.spawn(move || {
+                        let mut overlay = SignalOverlay::new(base);
+                        for component in components.iter_mut() {
+                            component.run(protocol, &mut overlay, ctx, config)?;
+                        }
+                        Ok::<_, eyre::Report>(overlay.writes)
+                    })
+                })
+                .collect_vec();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                })
+                .collect::<Result<Vec<_>>>()
+        })?;
+        // the components write disjoint signals, therefore the order does not matter
+        for (index, value) in writes.into_iter().flatten() {
+            signals.set(index, value);
+        }
+        Ok(())
+    }
+
+    pub fn run<S: Signals<C::VmType> + Sync>(
         &mut self,
         protocol: &mut C,
-        ctx: &mut WitnessExtensionCtx<F, C>,
+        signals: &mut S,
+        ctx: &WitnessExtensionCtx<F, C>,
         config: &VMConfig,
     ) -> Result<()> {
         let mut ip = 0;
@@ -294,30 +441,36 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> Component<F, C> {
 
         let name = self.symbol.clone();
         tracing::trace!("running component {name}");
-        // check if we have an accelerator for this
-        if ctx
-            .mpc_accelerator
-            .has_cmp_accelerator(&self.component_name)
+        // check if we have an accelerator for this, template accelerators are bound to this instantiation and take precedence
+        let has_template_accelerator = ctx.mpc_accelerator.has_template_accelerator(&name);
+        if has_template_accelerator
+            || ctx
+                .mpc_accelerator
+                .has_cmp_accelerator(&self.component_name)
         {
             let component_input_signals_start = self.my_offset + self.output_signals;
             let component_intermediate_signals_start =
                 component_input_signals_start + self.input_signals;
-            let inputs = &ctx.signals
-                [component_input_signals_start..component_input_signals_start + self.input_signals];
-            let result = ctx.mpc_accelerator.run_cmp_accelerator(
-                &self.component_name,
-                protocol,
-                inputs,
-                self.output_signals,
-            )?;
+            let inputs = signals.get_range(component_input_signals_start, self.input_signals);
+            let result = if has_template_accelerator {
+                ctx.mpc_accelerator.run_template_accelerator(
+                    &name,
+                    protocol,
+                    &inputs,
+                    self.output_signals,
+                )?
+            } else {
+                ctx.mpc_accelerator.run_cmp_accelerator(
+                    &self.component_name,
+                    protocol,
+                    &inputs,
+                    self.output_signals,
+                )?
+            };
             // insert outputs into the signals
-            let start = self.my_offset;
-            let end = start + self.output_signals;
-            ctx.signals[start..end].clone_from_slice(&result.output);
+            signals.set_range(self.my_offset, &result.output);
             // insert intermediate values into the signals
-            let start = component_intermediate_signals_start;
-            let end = start + result.intermediate.len();
-            ctx.signals[start..end].clone_from_slice(&result.intermediate);
+            signals.set_range(component_intermediate_signals_start, &result.intermediate);
             return Ok(());
         }
 
@@ -334,9 +487,9 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> Component<F, C> {
                 op_codes::MpcOpCode::LoadSignals(amount) => {
                     let index = self.pop_index();
                     let start = self.my_offset + index;
-                    ctx.signals[start..start + amount]
-                        .iter()
-                        .cloned()
+                    signals
+                        .get_range(start, *amount)
+                        .into_iter()
                         .for_each(|signal| {
                             tracing::trace!("pushing signal {signal}");
                             self.push_field(signal);
@@ -348,14 +501,18 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> Component<F, C> {
                     if self.if_stack.is_shared() {
                         let shared_condition = self.if_stack.get_shared_condition();
                         for i in 0..*amount {
-                            let old = ctx.signals[self.my_offset + index + amount - i - 1].clone();
+                            let signal_index = self.my_offset + index + amount - i - 1;
+                            let old = signals.get(signal_index);
                             let new = self.pop_field();
-                            ctx.signals[self.my_offset + index + amount - i - 1] =
-                                protocol.cmux(shared_condition.clone(), new, old)?;
+                            signals.set(
+                                signal_index,
+                                protocol.cmux(shared_condition.clone(), new, old)?,
+                            );
                         }
                     } else {
                         for i in 0..*amount {
-                            ctx.signals[self.my_offset + index + amount - i - 1] = self.pop_field();
+                            let value = self.pop_field();
+                            signals.set(self.my_offset + index + amount - i - 1, value);
                         }
                     }
                 }
@@ -449,7 +606,11 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> Component<F, C> {
                     //check if we can run it instantly
                     for mut component in new_components {
                         if component.input_signals == 0 {
-                            component.run(protocol, ctx, config)?;
+                            if config.parallel_components() {
+                                self.ready_sub_components.push(self.sub_components.len());
+                            } else {
+                                component.run(protocol, signals, ctx, config)?;
+                            }
                         }
                         self.sub_components.push(component);
                     }
@@ -457,13 +618,16 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> Component<F, C> {
                 op_codes::MpcOpCode::OutputSubComp(mapped, signal_code, amount) => {
                     let sub_comp_index = self.pop_index();
                     let mut index = self.pop_index();
+                    if self.ready_sub_components.contains(&sub_comp_index) {
+                        self.run_ready_sub_components(protocol, signals, ctx, config)?;
+                    }
                     let component = &mut self.sub_components[sub_comp_index];
                     if *mapped {
                         index += component.mappings[*signal_code];
                     }
                     let offset_in_component = component.my_offset + index;
-                    for ele in &ctx.signals[offset_in_component..offset_in_component + (*amount)] {
-                        self.push_field(ele.clone());
+                    for ele in signals.get_range(offset_in_component, *amount) {
+                        self.push_field(ele);
                     }
                 }
                 op_codes::MpcOpCode::InputSubComp(mapped, signal_code, amount) => {
@@ -485,11 +649,14 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> Component<F, C> {
                         index += component.mappings[*signal_code];
                     }
                     let offset_in_component = component.my_offset + index;
-                    ctx.signals[offset_in_component..offset_in_component + *amount]
-                        .clone_from_slice(&input_signals);
+                    signals.set_range(offset_in_component, &input_signals);
                     component.provided_input_signals += amount;
                     if component.provided_input_signals == component.input_signals {
-                        component.run(protocol, ctx, config)?;
+                        if config.parallel_components() {
+                            self.ready_sub_components.push(sub_comp_index);
+                        } else {
+                            component.run(protocol, signals, ctx, config)?;
+                        }
                     }
                 }
                 op_codes::MpcOpCode::Assert(line) => {
@@ -688,7 +855,8 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> Component<F, C> {
                 }
                 op_codes::MpcOpCode::Return => {
                     //we are done
-                    //just return
+                    //run the remaining sub components and return
+                    self.run_ready_sub_components(protocol, signals, ctx, config)?;
                     break;
                 }
                 op_codes::MpcOpCode::ReturnFun => {
@@ -864,9 +1032,9 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> WitnessExtension<F, C> {
         for (count, idx) in self.signal_to_witness.iter().enumerate() {
             // the +1 here is for the constant 1 which always is at position 0.
             if count < total_public_amount {
-                public_inputs.push(self.driver.open(self.ctx.signals[*idx].clone())?);
+                public_inputs.push(self.driver.open(self.signals[*idx].clone())?);
             } else {
-                witness.push(self.driver.to_share(self.ctx.signals[*idx].clone())?);
+                witness.push(self.driver.to_share(self.signals[*idx].clone())?);
             }
         }
         Ok(FinalizedWitnessExtension {
@@ -883,6 +1051,12 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> WitnessExtension<F, C> {
         &mut self,
         mut input_signals: SharedInput<F, C::ArithmeticShare>,
     ) -> Result<usize> {
+        for (name, bits) in input_signals.bit_widths.iter() {
+            let values = input_signals.shared_inputs.get(name).ok_or(eyre!(
+                "bit width declared for \"{name}\", which is not a shared input"
+            ))?;
+            self.check_bit_width(name, values, *bits)?;
+        }
         let mut amount_public_inputs = 0;
         for (name, offset, size) in self.main_input_list.iter() {
             let input_signals =
@@ -904,18 +1078,47 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> WitnessExtension<F, C> {
                     input_signals.len()
                 );
             }
-            self.ctx.signals[*offset..*offset + *size].clone_from_slice(input_signals.as_slice());
+            self.signals[*offset..*offset + *size].clone_from_slice(input_signals.as_slice());
         }
         Ok(amount_public_inputs)
     }
 
+    // Checks that all elements of a shared input are smaller than 2^bits. Only whether an element exceeds the declared bit width is opened.
+    fn check_bit_width(
+        &mut self,
+        name: &str,
+        values: &[C::ArithmeticShare],
+        bits: usize,
+    ) -> Result<()> {
+        if bits > F::MODULUS_BIT_SIZE as usize {
+            bail!(
+                "declared bit width {bits} of shared input \"{name}\" exceeds the field size of {} bits",
+                F::MODULUS_BIT_SIZE
+            );
+        }
+        if bits == F::MODULUS_BIT_SIZE as usize {
+            // all field elements fit
+            return Ok(());
+        }
+        let shift = C::VmType::from(F::from(bits as u64));
+        for (i, value) in values.iter().enumerate() {
+            let high_bits = self
+                .driver
+                .shift_r(C::VmType::from(value.to_owned()), shift.clone())?;
+            if !self.driver.is_zero(high_bits, true)? {
+                bail!("element {i} of shared input \"{name}\" does not fit into the declared bit width of {bits} bits");
+            }
+        }
+        Ok(())
+    }
+
     fn set_flat_input_signals(&mut self, input_signals: Vec<C::VmType>) {
         assert_eq!(
             self.main_inputs,
             input_signals.len(),
             "You have to provide the input signals"
         );
-        self.ctx.signals[1 + self.main_outputs..1 + self.main_outputs + self.main_inputs]
+        self.signals[1 + self.main_outputs..1 + self.main_outputs + self.main_inputs]
             .clone_from_slice(&input_signals);
     }
 
@@ -926,7 +1129,7 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> WitnessExtension<F, C> {
             .get(&self.main)
             .ok_or(eyre!("cannot find main template: {}", self.main))?;
         let mut main_component = Component::init(main_templ, 1);
-        main_component.run(&mut self.driver, &mut self.ctx, &self.config)?;
+        main_component.run(&mut self.driver, &mut self.signals, &self.ctx, &self.config)?;
         Ok(())
     }
 
@@ -1044,6 +1247,28 @@ impl<F: PrimeField, C: VmCircomWitnessExtension<F>> FinalizedWitnessExtension<F,
 
 impl<F: PrimeField> PlainWitnessExtension<F> {
     pub(crate) fn new(parser: CoCircomCompilerParsed<F>, config: VMConfig) -> Self {
+        // the pre-defined accelerators do not contain template accelerators, so there is nothing to bind
+        Self::from_parts(
+            parser,
+            MpcAccelerator::from_config(MpcAcceleratorConfig::from_env()),
+            config,
+        )
+    }
+
+    pub(crate) fn with_accelerator(
+        parser: CoCircomCompilerParsed<F>,
+        mut mpc_accelerator: MpcAccelerator<F, CircomPlainVmWitnessExtension<F>>,
+        config: VMConfig,
+    ) -> Result<Self> {
+        mpc_accelerator.bind_templates(&parser)?;
+        Ok(Self::from_parts(parser, mpc_accelerator, config))
+    }
+
+    fn from_parts(
+        parser: CoCircomCompilerParsed<F>,
+        mpc_accelerator: MpcAccelerator<F, CircomPlainVmWitnessExtension<F>>,
+        config: VMConfig,
+    ) -> Self {
         let mut signals = vec![F::default(); parser.amount_signals];
         signals[0] = F::one();
         Self {
@@ -1051,13 +1276,13 @@ impl<F: PrimeField> PlainWitnessExtension<F> {
             signal_to_witness: parser.signal_to_witness,
             main: parser.main,
             ctx: WitnessExtensionCtx::new(
-                signals,
                 parser.constant_table,
                 parser.fun_decls,
                 parser.templ_decls,
                 parser.string_table,
-                MpcAccelerator::from_config(MpcAcceleratorConfig::from_env()),
+                mpc_accelerator,
             ),
+            signals,
             main_inputs: parser.main_inputs,
             main_outputs: parser.main_outputs,
             main_input_list: parser.main_input_list,
@@ -1071,10 +1296,14 @@ impl<F: PrimeField, N: Rep3Network> Rep3WitnessExtension<F, N> {
     pub(crate) fn from_network(
         parser: CoCircomCompilerParsed<F>,
         network: N,
-        mpc_accelerator: MpcAccelerator<F, CircomRep3VmWitnessExtension<F, N>>,
+        mut mpc_accelerator: MpcAccelerator<F, CircomRep3VmWitnessExtension<F, N>>,
         config: VMConfig,
     ) -> Result<Self> {
-        let driver = CircomRep3VmWitnessExtension::from_network(network, config.a2b_type)?;
+        mpc_accelerator.bind_templates(&parser)?;
+        let circuit_hash = parser.circuit_hash()?;
+        let mut driver = CircomRep3VmWitnessExtension::from_network(network, config.a2b_type)?;
+        // abort before any shares are used if the parties do not execute the same bytecode
+        driver.compare_circuit_hash(&circuit_hash)?;
         let mut signals = vec![Rep3VmType::default(); parser.amount_signals];
         signals[0] = Rep3VmType::Public(F::one());
         let constant_table = parser
@@ -1087,13 +1316,13 @@ impl<F: PrimeField, N: Rep3Network> Rep3WitnessExtension<F, N> {
             signal_to_witness: parser.signal_to_witness,
             main: parser.main,
             ctx: WitnessExtensionCtx::new(
-                signals,
                 constant_table,
                 parser.fun_decls,
                 parser.templ_decls,
                 parser.string_table,
                 mpc_accelerator,
             ),
+            signals,
             main_inputs: parser.main_inputs,
             main_outputs: parser.main_outputs,
             main_input_list: parser.main_input_list,
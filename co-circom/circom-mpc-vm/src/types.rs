@@ -6,11 +6,12 @@ use mpc_net::config::NetworkConfig;
 
 use crate::{
     accelerator::{MpcAccelerator, MpcAcceleratorConfig},
-    mpc::plain::CircomPlainVmWitnessExtension,
+    mpc::{plain::CircomPlainVmWitnessExtension, rep3::CircomRep3VmWitnessExtension},
     mpc_vm::{PlainWitnessExtension, Rep3WitnessExtension, VMConfig, WitnessExtension},
     op_codes::CodeBlock,
 };
 use eyre::Result;
+use serde::{Deserialize, Serialize, Serializer};
 
 /// A template declaration.
 ///
@@ -19,7 +20,7 @@ use eyre::Result;
 ///
 /// > **Warning**: Users should usually not interact directly with this struct. It is only public because the
 /// > compiler requires these declarations, and the compiler is a separate crate due to licensing constraints.
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct TemplateDecl {
     pub(crate) symbol: String,
     pub(crate) component_name: String,
@@ -64,7 +65,7 @@ impl TemplateDecl {
 ///
 /// > **Warning**: Users should usually not interact directly with this struct. It is only public because the
 /// > compiler requires these declarations, and the compiler is a separate crate due to licensing constraints.
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct FunDecl {
     pub(crate) num_params: usize,
     pub(crate) vars: usize,
@@ -95,21 +96,41 @@ pub(crate) type InputList = Vec<(String, usize, usize)>;
 ///
 /// The struct provides certain methods to consume it and create an
 /// [MPC-VM](WitnessExtension).
-#[derive(Clone)]
+///
+/// It can be serialized with [write_compiled](CoCircomCompilerParsed::write_compiled), such that the circuit does not have to be compiled again for every witness extension.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct CoCircomCompilerParsed<F: PrimeField> {
     pub(crate) main: String,
     pub(crate) amount_signals: usize,
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
     pub(crate) constant_table: Vec<F>,
     pub(crate) string_table: Vec<String>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub(crate) fun_decls: HashMap<String, FunDecl>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub(crate) templ_decls: HashMap<String, TemplateDecl>,
     pub(crate) signal_to_witness: Vec<usize>,
     pub(crate) main_inputs: usize,
     pub(crate) main_outputs: usize,
     pub(crate) main_input_list: InputList,
+    #[serde(serialize_with = "serialize_sorted")]
     pub(crate) output_mapping: OutputMapping,
 }
 
+// serializes the map in order of its keys, such that compiling the same circuit twice results in the same bytes
+fn serialize_sorted<S: Serializer, V: Serialize>(
+    map: &HashMap<String, V>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    map.iter()
+        .collect::<std::collections::BTreeMap<_, _>>()
+        .serialize(serializer)
+}
+
 impl<F: PrimeField> CoCircomCompilerParsed<F> {
     /// > **Warning**: DO NOT CALL THIS DIRECTLY! This struct is intended for internal use by the compiler crate
     /// > and should not be instantiated directly. It is publicly visible due to requirements imposed by licensing constraints.
@@ -160,6 +181,17 @@ impl<F: PrimeField> CoCircomCompilerParsed<F> {
         PlainWitnessExtension::new(self, vm_config)
     }
 
+    /// Consumes `self` and constructs an instance of [`PlainWitnessExtension`] that uses the provided [`MpcAccelerator`] instead of the pre-defined accelerators, e.g., with additional template accelerators registered with [`MpcAccelerator::register_template`].
+    ///
+    /// The same cautions as for [`to_plain_vm`](Self::to_plain_vm) apply.
+    pub fn to_plain_vm_with_accelerator(
+        self,
+        vm_config: VMConfig,
+        mpc_accelerator: MpcAccelerator<F, CircomPlainVmWitnessExtension<F>>,
+    ) -> Result<WitnessExtension<F, CircomPlainVmWitnessExtension<F>>> {
+        PlainWitnessExtension::with_accelerator(self, mpc_accelerator, vm_config)
+    }
+
     /// Consumes `self` and a [`NetworkConfig`], and constructs an instance of [`Rep3WitnessExtension`].
     ///
     /// # Arguments
@@ -201,4 +233,22 @@ impl<F: PrimeField> CoCircomCompilerParsed<F> {
             vm_config,
         )
     }
+
+    /// Consumes `self`, an already established [`Rep3Network`], and an [`MpcAccelerator`], and constructs an instance of [`Rep3WitnessExtension`] that uses the provided accelerator instead of the pre-defined accelerators.
+    ///
+    /// # Arguments
+    /// - `network`: Am already established [`Rep3Network`].
+    /// - `mpc_accelerator`: The accelerator, e.g., with additional template accelerators registered with [`MpcAccelerator::register_template`].
+    ///
+    /// # Returns
+    /// - `Ok(Rep3WitnessExtension)`: The MPC-VM capable of performing the witness extension using the Rep3 protocol.
+    /// - `Err(err)`: An error indicating a failure.
+    pub fn to_rep3_vm_with_network_and_accelerator<N: Rep3Network>(
+        self,
+        network: N,
+        vm_config: VMConfig,
+        mpc_accelerator: MpcAccelerator<F, CircomRep3VmWitnessExtension<F, N>>,
+    ) -> Result<Rep3WitnessExtension<F, N>> {
+        Rep3WitnessExtension::from_network(self, network, mpc_accelerator, vm_config)
+    }
 }
@@ -1,5 +1,6 @@
 use std::{
-    io::{Cursor, Read},
+    collections::BTreeMap,
+    io::{self, Cursor, Read, Seek, SeekFrom},
     marker::PhantomData,
 };
 
@@ -42,7 +43,7 @@ where
     ftype: String,
     #[expect(dead_code)]
     version: u32,
-    sections: Vec<Vec<u8>>,
+    sections: BTreeMap<usize, Vec<u8>>,
     phantom_data: PhantomData<P>,
 }
 
@@ -68,26 +69,34 @@ where
             .try_into()
             .expect("u32 fits into usize");
         tracing::debug!("we got {} sections in binfile", num_sections);
-        let mut sections = vec![vec![]; num_sections];
+        let mut sections = BTreeMap::new();
 
         for _ in 0..num_sections {
             let section_id: usize = reader
                 .read_u32::<LittleEndian>()?
                 .try_into()
                 .expect("u32 fits into usize");
-            let section_length: usize = reader
-                .read_u64::<LittleEndian>()?
-                .try_into()
-                .expect("u64 fits into usize");
-
-            let section = &mut sections[section_id - 1];
-            if !section.is_empty() {
-                return Err(ZKeyParserError::CorruptedBinFile(
-                    "sections are empty!".to_owned(),
-                ));
+            let section_length = reader.read_u64::<LittleEndian>()?;
+            if section_id == 0 || section_id > num_sections {
+                return Err(ZKeyParserError::CorruptedBinFile(format!(
+                    "invalid section id {section_id}"
+                )));
+            }
+            if sections.contains_key(&section_id) {
+                return Err(ZKeyParserError::CorruptedBinFile(format!(
+                    "section {section_id} is present multiple times"
+                )));
             }
-            section.resize(section_length, 0);
-            reader.read_exact(section)?;
+            // the section is read without allocating the declared length upfront, so that a corrupted length cannot exhaust the memory
+            let mut section = Vec::new();
+            reader
+                .by_ref()
+                .take(section_length)
+                .read_to_end(&mut section)?;
+            if section.len() as u64 != section_length {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+            }
+            sections.insert(section_id, section);
         }
         tracing::debug!("successfully read bin file!");
         Ok(Self {
@@ -98,11 +107,125 @@ where
         })
     }
 
-    pub(crate) fn take_section(&mut self, id: usize) -> Cursor<Vec<u8>> {
-        Cursor::new(std::mem::take(&mut self.sections[id - 1]))
+    pub(crate) fn take_section(&mut self, id: usize) -> ZKeyParserResult<Cursor<Vec<u8>>> {
+        Ok(Cursor::new(self.take_section_raw(id)?))
     }
 
-    pub(crate) fn take_section_raw(&mut self, id: usize) -> Vec<u8> {
-        std::mem::take(&mut self.sections[id - 1])
+    pub(crate) fn take_section_raw(&mut self, id: usize) -> ZKeyParserResult<Vec<u8>> {
+        self.sections
+            .remove(&id)
+            .ok_or_else(|| ZKeyParserError::CorruptedBinFile(format!("missing section {id}")))
+    }
+}
+
+/// The position of a section in a bin file, see [BinFileIndex].
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct SectionPosition {
+    /// The offset of the content of the section from the start of the file
+    pub(crate) offset: u64,
+    /// The length of the section in bytes
+    pub(crate) len: u64,
+}
+
+/// The positions of the sections of a bin file. In contrast to [BinFile], the content of the sections is not read, so that large sections can be read on demand.
+#[derive(Debug)]
+pub(crate) struct BinFileIndex {
+    sections: BTreeMap<usize, SectionPosition>,
+}
+
+impl BinFileIndex {
+    /// Reads the header of the bin file and the headers of its sections, and skips the content of the sections.
+    pub(crate) fn new<R: Read + Seek>(reader: &mut R) -> ZKeyParserResult<Self> {
+        tracing::debug!("indexing bin file");
+        let file_len = reader.seek(SeekFrom::End(0))?;
+        reader.seek(SeekFrom::Start(0))?;
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        std::str::from_utf8(&magic[..]).map_err(|_| {
+            ZKeyParserError::CorruptedBinFile("cannot parse magic number".to_owned())
+        })?;
+        let _version = reader.read_u32::<LittleEndian>()?;
+        let num_sections: usize = reader
+            .read_u32::<LittleEndian>()?
+            .try_into()
+            .expect("u32 fits into usize");
+        let mut sections = BTreeMap::new();
+        for _ in 0..num_sections {
+            let section_id: usize = reader
+                .read_u32::<LittleEndian>()?
+                .try_into()
+                .expect("u32 fits into usize");
+            let len = reader.read_u64::<LittleEndian>()?;
+            if section_id == 0 || section_id > num_sections {
+                return Err(ZKeyParserError::CorruptedBinFile(format!(
+                    "invalid section id {section_id}"
+                )));
+            }
+            let offset = reader.stream_position()?;
+            let end = offset
+                .checked_add(len)
+                .filter(|end| *end <= file_len)
+                .ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof))?;
+            if sections
+                .insert(section_id, SectionPosition { offset, len })
+                .is_some()
+            {
+                return Err(ZKeyParserError::CorruptedBinFile(format!(
+                    "section {section_id} is present multiple times"
+                )));
+            }
+            reader.seek(SeekFrom::Start(end))?;
+        }
+        tracing::debug!("indexed {} sections of bin file", sections.len());
+        Ok(Self { sections })
+    }
+
+    /// Returns the position of the section with the given id.
+    pub(crate) fn section(&self, id: usize) -> ZKeyParserResult<SectionPosition> {
+        self.sections
+            .get(&id)
+            .copied()
+            .ok_or_else(|| ZKeyParserError::CorruptedBinFile(format!("missing section {id}")))
+    }
+
+    /// Reads the content of the section with the given id.
+    pub(crate) fn read_section<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+        id: usize,
+    ) -> ZKeyParserResult<Cursor<Vec<u8>>> {
+        let position = self.section(id)?;
+        reader.seek(SeekFrom::Start(position.offset))?;
+        let mut section = Vec::new();
+        reader
+            .by_ref()
+            .take(position.len)
+            .read_to_end(&mut section)?;
+        Ok(Cursor::new(section))
+    }
+}
+
+/// Checks that the section holds at least `num` elements of `size` bytes. This bounds the memory allocated for the elements by the size of the file.
+pub(crate) fn check_section_size(
+    section: &[u8],
+    id: usize,
+    num: usize,
+    size: usize,
+) -> ZKeyParserResult<()> {
+    check_section_len(section.len() as u64, id, num, size)
+}
+
+/// Checks that a section of `section_len` bytes holds at least `num` elements of `size` bytes, see [check_section_size].
+pub(crate) fn check_section_len(
+    section_len: u64,
+    id: usize,
+    num: usize,
+    size: usize,
+) -> ZKeyParserResult<()> {
+    match num.checked_mul(size) {
+        Some(needed) if needed as u64 <= section_len => Ok(()),
+        _ => Err(ZKeyParserError::CorruptedBinFile(format!(
+            "section {id} is too small for {num} elements"
+        ))),
     }
 }
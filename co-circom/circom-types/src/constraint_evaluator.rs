@@ -0,0 +1,240 @@
+//! Evaluation of R1CS constraint matrices over (shared) witnesses.
+//!
+//! Circom stores the constraint matrices as one vector of `(coefficient, index)` pairs per constraint. The [ConstraintEvaluator] flattens such a matrix into a compressed sparse row layout with separate arrays for the coefficients and the indices, and evaluates all constraints in parallel. How a single linear combination is evaluated is left to the caller, so the same evaluator can be used for plain values and for all MPC protocols.
+//!
+//! Machine generated circuits often contain the same linear combination in many constraints, e.g., when a signal is copied. [ConstraintEvaluator::deduplicated] stores every distinct linear combination only once, such that it is evaluated once per evaluation of the matrix.
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+use ark_ff::PrimeField;
+use rayon::prelude::*;
+
+/// A single constraint, i.e., a row of a constraint matrix, borrowed from a [ConstraintEvaluator].
+#[derive(Debug, Clone, Copy)]
+pub struct Constraint<'a, F> {
+    /// The coefficients of the linear combination
+    pub coeffs: &'a [F],
+    /// The indices of the witness elements the coefficients are multiplied with. Indices smaller than the number of public inputs refer to the public inputs, the remaining ones to the private witness.
+    pub indices: &'a [usize],
+}
+
+impl<'a, F: Copy> Constraint<'a, F> {
+    /// Returns an iterator over the `(coefficient, index)` pairs of the constraint.
+    pub fn iter(&self) -> impl Iterator<Item = (F, usize)> + 'a {
+        self.coeffs
+            .iter()
+            .copied()
+            .zip(self.indices.iter().copied())
+    }
+
+    /// Returns the number of terms of the linear combination.
+    pub fn len(&self) -> usize {
+        self.coeffs.len()
+    }
+
+    /// Returns `true` if the linear combination has no terms.
+    pub fn is_empty(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+}
+
+/// A constraint matrix in compressed sparse row layout, which evaluates its constraints in parallel.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConstraintEvaluator<F> {
+    // row i consists of the entries row_offsets[i]..row_offsets[i + 1]
+    row_offsets: Vec<usize>,
+    coeffs: Vec<F>,
+    indices: Vec<usize>,
+    // if the matrix contains duplicate rows, constraint i is the stored row rows[i]
+    rows: Option<Vec<usize>>,
+}
+
+impl<F: PrimeField> ConstraintEvaluator<F> {
+    /// The minimum number of constraints evaluated by a single rayon task.
+    const MIN_CONSTRAINTS_PER_TASK: usize = 256;
+
+    /// Flattens a constraint matrix in the layout of circom, i.e., a vector of `(coefficient, index)` pairs per constraint.
+    pub fn new(matrix: &[Vec<(F, usize)>]) -> Self {
+        let num_entries = matrix.iter().map(Vec::len).sum();
+        let mut row_offsets = Vec::with_capacity(matrix.len() + 1);
+        let mut coeffs = Vec::with_capacity(num_entries);
+        let mut indices = Vec::with_capacity(num_entries);
+        row_offsets.push(0);
+        for row in matrix {
+            for (coeff, index) in row {
+                coeffs.push(*coeff);
+                indices.push(*index);
+            }
+            row_offsets.push(coeffs.len());
+        }
+        Self {
+            row_offsets,
+            coeffs,
+            indices,
+            rows: None,
+        }
+    }
+
+    /// Flattens a constraint matrix in the layout of circom like [ConstraintEvaluator::new], but stores every distinct linear combination only once. Linear combinations that only differ in the order of their terms are considered equal.
+    pub fn deduplicated(matrix: &[Vec<(F, usize)>]) -> Self {
+        // sorting and hashing the rows is independent for every row, only the lookup of the duplicates is sequential
+        let sorted = matrix
+            .par_iter()
+            .map(|constraint| {
+                let mut row = constraint.clone();
+                row.sort_unstable_by_key(|(_, index)| *index);
+                let mut hasher = DefaultHasher::new();
+                row.hash(&mut hasher);
+                (hasher.finish(), row)
+            })
+            .collect::<Vec<_>>();
+        let mut row_offsets = vec![0];
+        let mut coeffs = Vec::new();
+        let mut indices = Vec::new();
+        let mut rows = Vec::with_capacity(matrix.len());
+        // the stored rows by the hash of their terms, as the first constraint with these terms and the stored row
+        let mut stored: HashMap<u64, Vec<(usize, usize)>> = HashMap::new();
+        for (i, (hash, row)) in sorted.iter().enumerate() {
+            let candidates = stored.entry(*hash).or_default();
+            let existing = candidates
+                .iter()
+                .find(|(first, _)| sorted[*first].1 == *row)
+                .map(|(_, unique)| *unique);
+            let unique = existing.unwrap_or_else(|| {
+                for (coeff, index) in row.iter() {
+                    coeffs.push(*coeff);
+                    indices.push(*index);
+                }
+                row_offsets.push(coeffs.len());
+                candidates.push((i, row_offsets.len() - 2));
+                row_offsets.len() - 2
+            });
+            rows.push(unique);
+        }
+        let num_unique = row_offsets.len() - 1;
+        Self {
+            row_offsets,
+            coeffs,
+            indices,
+            rows: (num_unique < matrix.len()).then_some(rows),
+        }
+    }
+
+    /// Returns the number of constraints.
+    pub fn num_constraints(&self) -> usize {
+        match &self.rows {
+            Some(rows) => rows.len(),
+            None => self.num_unique_constraints(),
+        }
+    }
+
+    /// Returns the number of distinct linear combinations that are evaluated. This is smaller than [num_constraints](Self::num_constraints) if the evaluator was [deduplicated](Self::deduplicated) and the matrix contains duplicate rows.
+    pub fn num_unique_constraints(&self) -> usize {
+        self.row_offsets.len().saturating_sub(1)
+    }
+
+    /// Returns the largest witness index referenced by any constraint, or `None` if there are no entries.
+    pub fn max_index(&self) -> Option<usize> {
+        self.indices.par_iter().copied().max()
+    }
+
+    /// Returns the constraint at position `i`.
+    ///
+    /// # Panics
+    /// Panics if `i` is not smaller than [num_constraints](Self::num_constraints).
+    pub fn constraint(&self, i: usize) -> Constraint<'_, F> {
+        match &self.rows {
+            Some(rows) => self.row(rows[i]),
+            None => self.row(i),
+        }
+    }
+
+    fn row(&self, i: usize) -> Constraint<'_, F> {
+        let range = self.row_offsets[i]..self.row_offsets[i + 1];
+        Constraint {
+            coeffs: &self.coeffs[range.clone()],
+            indices: &self.indices[range],
+        }
+    }
+
+    /// Evaluates all constraints in parallel with the provided function and returns the results in order of the constraints. Every distinct linear combination is evaluated only once. The result is padded with `S::default()` to a length of `min_len`, if it is shorter.
+    pub fn evaluate<S, E>(&self, min_len: usize, eval: E) -> Vec<S>
+    where
+        S: Default + Clone + Send,
+        E: Fn(Constraint<'_, F>) -> S + Sync,
+    {
+        let unique = (0..self.num_unique_constraints())
+            .into_par_iter()
+            .with_min_len(Self::MIN_CONSTRAINTS_PER_TASK)
+            .map(|i| eval(self.row(i)))
+            .collect::<Vec<_>>();
+        let mut result = match &self.rows {
+            Some(rows) => rows.iter().map(|row| unique[*row].clone()).collect(),
+            None => unique,
+        };
+        if result.len() < min_len {
+            result.resize(min_len, S::default());
+        }
+        result
+    }
+
+    /// Evaluates all constraints over a plain witness, e.g., to check whether a witness satisfies a circuit before starting an MPC computation.
+    pub fn evaluate_plain(&self, public_inputs: &[F], private_witness: &[F]) -> Vec<F> {
+        self.evaluate(0, |constraint| {
+            constraint
+                .iter()
+                .map(|(coeff, index)| {
+                    if index < public_inputs.len() {
+                        coeff * public_inputs[index]
+                    } else {
+                        coeff * private_witness[index - public_inputs.len()]
+                    }
+                })
+                .sum()
+        })
+    }
+}
+
+impl<F: PrimeField> From<&[Vec<(F, usize)>]> for ConstraintEvaluator<F> {
+    fn from(matrix: &[Vec<(F, usize)>]) -> Self {
+        Self::new(matrix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+
+    #[test]
+    fn deduplicated_matches_new() {
+        let terms = |terms: &[(u64, usize)]| {
+            terms
+                .iter()
+                .map(|(coeff, index)| (Fr::from(*coeff), *index))
+                .collect::<Vec<_>>()
+        };
+        let matrix = vec![
+            terms(&[(1, 0), (2, 3)]),
+            terms(&[(5, 2)]),
+            terms(&[(2, 3), (1, 0)]),
+            terms(&[]),
+            terms(&[(5, 2)]),
+            terms(&[(5, 1)]),
+        ];
+        let plain = ConstraintEvaluator::new(&matrix);
+        let dedup = ConstraintEvaluator::deduplicated(&matrix);
+        assert_eq!(dedup.num_constraints(), 6);
+        assert_eq!(dedup.num_unique_constraints(), 4);
+        let public_inputs = [Fr::from(1), Fr::from(7)];
+        let private_witness = [Fr::from(11), Fr::from(13)];
+        assert_eq!(
+            dedup.evaluate_plain(&public_inputs, &private_witness),
+            plain.evaluate_plain(&public_inputs, &private_witness)
+        );
+        assert_eq!(ConstraintEvaluator::deduplicated(&matrix), dedup);
+    }
+}
@@ -8,7 +8,9 @@ pub use proof::Groth16Proof;
 pub use public_input::JsonPublicInput;
 pub use verification_key::JsonVerificationKey;
 pub use zkey::ConstraintMatrix;
+pub use zkey::LazyZKey;
 pub use zkey::ZKey;
+pub use zkey::ZKeySection;
 
 #[cfg(test)]
 pub(crate) mod test_utils {
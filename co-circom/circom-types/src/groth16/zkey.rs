@@ -25,16 +25,28 @@
 //DEALINGS IN THE SOFTWARE.R
 
 //! This module defines the [`ZKey`] struct that implements deserialization of circom zkey files via [`ZKey::from_reader`].
+//! For large proving keys, [`LazyZKey`] only reads the header and the constraint matrices and reads the curve points on demand.
 //! Inspired by <https://github.com/arkworks-rs/circom-compat/blob/170b10fc9ed182b5f72ecf379033dda023d0bf07/src/zkey.rs>
 use ark_ec::pairing::Pairing;
 use ark_ff::PrimeField;
-use ark_serialize::CanonicalDeserialize;
-
-use std::io::Read;
+use ark_serialize::{CanonicalDeserialize, SerializationError};
+use rayon::prelude::*;
+
+use std::{
+    fs::File,
+    io::{BufReader, Read, Seek, SeekFrom},
+    ops::Range,
+    path::Path,
+    sync::Arc,
+};
 
 use crate::{
-    binfile::{BinFile, ZKeyParserError, ZKeyParserResult},
+    binfile::{
+        check_section_len, check_section_size, BinFile, BinFileIndex, ZKeyParserError,
+        ZKeyParserResult,
+    },
     traits::{CheckElement, CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge},
+    ConstraintEvaluator,
 };
 
 macro_rules! u32_to_usize {
@@ -71,15 +83,15 @@ pub struct ZKey<P: Pairing> {
     pub beta_g2: P::G2Affine,
     /// delta_g1
     pub delta_g2: P::G2Affine,
-    /// The constraint matrices A
-    pub a_matrix: ConstraintMatrix<P::ScalarField>,
-    /// The constraint matrices B
-    pub b_matrix: ConstraintMatrix<P::ScalarField>,
+    /// The constraint matrix A, deduplicated when the zkey is read, see [`ConstraintEvaluator::deduplicated`]
+    pub a_matrix: ConstraintEvaluator<P::ScalarField>,
+    /// The constraint matrix B, deduplicated when the zkey is read, see [`ConstraintEvaluator::deduplicated`]
+    pub b_matrix: ConstraintEvaluator<P::ScalarField>,
 }
 
 /// A constraint matrix used in Groth16.
 pub type ConstraintMatrix<F> = Vec<Vec<(F, usize)>>;
-type ConstraintMatrixAB<F> = (usize, ConstraintMatrix<F>, ConstraintMatrix<F>);
+type ConstraintMatrixAB<F> = (usize, ConstraintEvaluator<F>, ConstraintEvaluator<F>);
 
 #[derive(Clone, Debug)]
 struct HeaderGroth<P: Pairing> {
@@ -114,19 +126,32 @@ where
         let mut binfile = BinFile::<P>::new(&mut reader)?;
 
         tracing::debug!("start transforming bin file into zkey...");
-        let header = HeaderGroth::<P>::read(&mut binfile.take_section(2), check)?;
+        let header = HeaderGroth::<P>::read(&mut binfile.take_section(2)?, check)?;
         let n_vars = header.n_vars;
         let n_public = header.n_public;
         let domain_size = usize::try_from(header.domain_size).expect("fits into usize");
 
         // parse proving key
 
-        let matrices_section = binfile.take_section(4);
-        let a_section = binfile.take_section(5);
-        let b_g1_section = binfile.take_section(6);
-        let b_g2_section = binfile.take_section(7);
-        let l_section = binfile.take_section(8);
-        let h_section = binfile.take_section(9);
+        let n_private = n_vars.checked_sub(n_public + 1).ok_or_else(|| {
+            ZKeyParserError::CorruptedBinFile(format!(
+                "{n_public} public inputs do not fit into {n_vars} variables"
+            ))
+        })?;
+
+        let matrices_section = binfile.take_section(4)?;
+        let a_section = binfile.take_section(5)?;
+        let b_g1_section = binfile.take_section(6)?;
+        let b_g2_section = binfile.take_section(7)?;
+        let l_section = binfile.take_section(8)?;
+        let h_section = binfile.take_section(9)?;
+        // the constraint matrices are allocated with the domain size, which is bounded by the size of the h query
+        check_section_size(
+            h_section.get_ref(),
+            9,
+            domain_size,
+            P::G1_SERIALIZED_BYTE_SIZE_UNCOMPRESSED,
+        )?;
 
         let mut a_query = None;
         let mut b_g1_query = None;
@@ -140,7 +165,7 @@ where
             s.spawn(|_| a_query = Some(Self::a_query(n_vars, a_section, check)));
             s.spawn(|_| b_g1_query = Some(Self::b_g1_query(n_vars, b_g1_section, check)));
             s.spawn(|_| b_g2_query = Some(Self::b_g2_query(n_vars, b_g2_section, check)));
-            s.spawn(|_| l_query = Some(Self::l_query(n_vars - n_public - 1, l_section, check)));
+            s.spawn(|_| l_query = Some(Self::l_query(n_private, l_section, check)));
             s.spawn(|_| h_query = Some(Self::h_query(domain_size, h_section, check)));
             s.spawn(|_| {
                 matrices = Some(Self::constraint_matrices(
@@ -234,21 +259,222 @@ where
             let signal = u32::deserialize_uncompressed(&mut matrices_section)?;
 
             let value = P::ScalarField::from_reader_for_groth16_zkey(&mut matrices_section)?;
+            if matrix > 1 || constraint as usize >= domain_size {
+                return Err(ZKeyParserError::CorruptedBinFile(format!(
+                    "invalid coefficient for matrix {matrix} and constraint {constraint}"
+                )));
+            }
             max_constraint_index = std::cmp::max(max_constraint_index, constraint);
             matrices[matrix as usize][constraint as usize].push((value, signal as usize));
         }
 
-        let num_constraints = max_constraint_index as usize - n_public;
+        let num_constraints = (max_constraint_index as usize)
+            .checked_sub(n_public)
+            .ok_or_else(|| {
+                ZKeyParserError::CorruptedBinFile("less constraints than public inputs".to_owned())
+            })?;
         // Remove the public input constraints, Arkworks adds them later
         matrices.iter_mut().for_each(|m| {
             m.truncate(num_constraints);
         });
 
         let [a, b] = matrices;
+        let (a, b) = rayon::join(
+            || ConstraintEvaluator::deduplicated(&a),
+            || ConstraintEvaluator::deduplicated(&b),
+        );
         Ok((num_constraints, a, b))
     }
 }
 
+/// A section of a zkey file that holds curve points. In contrast to the queries of [`ZKey`], the points are not kept in memory, but are read from the file whenever they are requested. The file is opened for every request, so a section can be shared across threads.
+#[derive(Clone)]
+pub struct ZKeySection<A> {
+    path: Arc<Path>,
+    offset: u64,
+    num: usize,
+    element_size: usize,
+    check: CheckElement,
+    parse: fn(&[u8], CheckElement) -> Result<A, SerializationError>,
+}
+
+impl<A: Send> ZKeySection<A> {
+    /// Returns the number of points in the section.
+    pub fn len(&self) -> usize {
+        self.num
+    }
+
+    /// Returns `true` if the section holds no points.
+    pub fn is_empty(&self) -> bool {
+        self.num == 0
+    }
+
+    /// Reads the points in the given range. Panics if the range is out of bounds.
+    pub fn read_range(&self, range: Range<usize>) -> Result<Vec<A>, ZKeyParserError> {
+        assert!(
+            range.start <= range.end && range.end <= self.num,
+            "range {range:?} out of bounds for section with {} points",
+            self.num
+        );
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(
+            self.offset + (range.start * self.element_size) as u64,
+        ))?;
+        let mut bytes = vec![0u8; range.len() * self.element_size];
+        file.read_exact(&mut bytes)?;
+        self.parse_points(&bytes)
+    }
+
+    /// Reads the points at the given indices. Panics if an index is out of bounds. Reading is most efficient for ascending indices.
+    pub fn read_indices(&self, indices: &[usize]) -> Result<Vec<A>, ZKeyParserError> {
+        let mut reader = BufReader::new(File::open(&self.path)?);
+        reader.seek(SeekFrom::Start(self.offset))?;
+        let mut position = 0;
+        let mut bytes = vec![0u8; indices.len() * self.element_size];
+        for (index, buf) in indices
+            .iter()
+            .zip(bytes.chunks_exact_mut(self.element_size))
+        {
+            assert!(
+                *index < self.num,
+                "index {index} out of bounds for section with {} points",
+                self.num
+            );
+            let skip = (*index as i64 - position as i64) * self.element_size as i64;
+            reader.seek_relative(skip)?;
+            reader.read_exact(buf)?;
+            position = index + 1;
+        }
+        self.parse_points(&bytes)
+    }
+
+    fn parse_points(&self, bytes: &[u8]) -> Result<Vec<A>, ZKeyParserError> {
+        Ok(bytes
+            .par_chunks_exact(self.element_size)
+            .map(|bytes| (self.parse)(bytes, self.check))
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+}
+
+/// Represents a zkey in the format defined by circom, where the queries are read from the file on demand, see [`ZKeySection`]. Only the header and the constraint matrices are kept in memory, which makes it possible to prove with proving keys that do not fit into memory. Implements [`LazyZKey::from_path`] to open a zkey file.
+#[derive(Clone)]
+pub struct LazyZKey<P: Pairing> {
+    /// amount of public inputs
+    pub n_public: usize,
+    /// domain size
+    pub pow: usize,
+    /// the amount of constraints
+    pub num_constraints: usize,
+    /// beta
+    pub beta_g1: P::G1Affine,
+    /// delta
+    pub delta_g1: P::G1Affine,
+    /// a_query
+    pub a_query: ZKeySection<P::G1Affine>,
+    /// b_query in G1
+    pub b_g1_query: ZKeySection<P::G1Affine>,
+    /// b_query in G2
+    pub b_g2_query: ZKeySection<P::G2Affine>,
+    /// h_query
+    pub h_query: ZKeySection<P::G1Affine>,
+    /// l_query
+    pub l_query: ZKeySection<P::G1Affine>,
+    /// alpha_g1
+    pub alpha_g1: P::G1Affine,
+    /// beta_g1
+    pub beta_g2: P::G2Affine,
+    /// delta_g1
+    pub delta_g2: P::G2Affine,
+    /// The constraint matrix A, deduplicated when the zkey is read, see [`ConstraintEvaluator::deduplicated`]
+    pub a_matrix: ConstraintEvaluator<P::ScalarField>,
+    /// The constraint matrix B, deduplicated when the zkey is read, see [`ConstraintEvaluator::deduplicated`]
+    pub b_matrix: ConstraintEvaluator<P::ScalarField>,
+}
+
+impl<P: Pairing + CircomArkworksPairingBridge> LazyZKey<P>
+where
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+{
+    /// Opens the zkey file at the given path. Reads the header and the constraint matrices and checks that the sections of the queries have the expected sizes. The queries are read on demand, so the file must not change as long as the [`LazyZKey`] is used.
+    ///
+    /// The second parameter specifies whether the points are checked to be on their respective curve when they are read, see [`ZKey::from_reader`] and [`CheckElement`].
+    pub fn from_path(path: impl AsRef<Path>, check: CheckElement) -> ZKeyParserResult<Self> {
+        let path: Arc<Path> = Arc::from(path.as_ref());
+        let mut reader = BufReader::new(File::open(&path)?);
+        let index = BinFileIndex::new(&mut reader)?;
+
+        tracing::debug!("start reading lazy zkey...");
+        let header = HeaderGroth::<P>::read(&mut index.read_section(&mut reader, 2)?, check)?;
+        let n_vars = header.n_vars;
+        let n_public = header.n_public;
+        let domain_size = usize::try_from(header.domain_size).expect("fits into usize");
+        let n_private = n_vars.checked_sub(n_public + 1).ok_or_else(|| {
+            ZKeyParserError::CorruptedBinFile(format!(
+                "{n_public} public inputs do not fit into {n_vars} variables"
+            ))
+        })?;
+
+        let g1_section = |id, num| -> ZKeyParserResult<ZKeySection<P::G1Affine>> {
+            let position = index.section(id)?;
+            let element_size = P::G1_SERIALIZED_BYTE_SIZE_UNCOMPRESSED;
+            check_section_len(position.len, id, num, element_size)?;
+            Ok(ZKeySection {
+                path: Arc::clone(&path),
+                offset: position.offset,
+                num,
+                element_size,
+                check,
+                parse: P::g1_from_bytes,
+            })
+        };
+        let a_query = g1_section(5, n_vars)?;
+        let b_g1_query = g1_section(6, n_vars)?;
+        let l_query = g1_section(8, n_private)?;
+        // the constraint matrices are allocated with the domain size, which is bounded by the size of the h query
+        let h_query = g1_section(9, domain_size)?;
+        let b_g2_position = index.section(7)?;
+        check_section_len(
+            b_g2_position.len,
+            7,
+            n_vars,
+            P::G2_SERIALIZED_BYTE_SIZE_UNCOMPRESSED,
+        )?;
+        let b_g2_query = ZKeySection {
+            path: Arc::clone(&path),
+            offset: b_g2_position.offset,
+            num: n_vars,
+            element_size: P::G2_SERIALIZED_BYTE_SIZE_UNCOMPRESSED,
+            check,
+            parse: P::g2_from_bytes,
+        };
+
+        let (num_constraints, a_matrix, b_matrix) = ZKey::<P>::constraint_matrices(
+            domain_size,
+            n_public,
+            index.read_section(&mut reader, 4)?,
+        )?;
+        tracing::debug!("lazy zkey reading done!");
+        Ok(LazyZKey {
+            n_public,
+            pow: header.pow,
+            num_constraints,
+            beta_g1: header.beta_g1,
+            delta_g1: header.delta_g1,
+            a_query,
+            b_g1_query,
+            b_g2_query,
+            h_query,
+            l_query,
+            alpha_g1: header.alpha_g1,
+            beta_g2: header.beta_g2,
+            delta_g2: header.delta_g2,
+            a_matrix,
+            b_matrix,
+        })
+    }
+}
+
 impl<P: Pairing + CircomArkworksPairingBridge> HeaderGroth<P>
 where
     P::BaseField: CircomArkworksPrimeFieldBridge,
@@ -286,7 +512,7 @@ where
         let n_public = u32_to_usize!(u32::deserialize_uncompressed(&mut reader)?);
         let domain_size = u32::deserialize_uncompressed(&mut reader)?;
         tracing::debug!("n_vars: {n_vars}; n_public: {n_public}, domain_size: {domain_size}");
-        if domain_size & (domain_size - 1) == 0 && domain_size > 0 {
+        if domain_size.is_power_of_two() {
             let alpha_g1 = P::g1_from_reader(&mut reader, check)?;
             let beta_g1 = P::g1_from_reader(&mut reader, check)?;
             let beta_g2 = P::g2_from_reader(&mut reader, check)?;
@@ -434,6 +660,65 @@ mod tests {
         }
     }
 
+    #[test]
+    fn lazy_zkey_matches_zkey() {
+        let path = "../../test_vectors/Groth16/bn254/multiplier2/circuit.zkey";
+        let zkey =
+            ZKey::<Bn254>::from_reader(File::open(path).unwrap(), CheckElement::Yes).unwrap();
+        let lazy = LazyZKey::<Bn254>::from_path(path, CheckElement::Yes).unwrap();
+        assert_eq!(zkey.n_public, lazy.n_public);
+        assert_eq!(zkey.pow, lazy.pow);
+        assert_eq!(zkey.num_constraints, lazy.num_constraints);
+        assert_eq!(zkey.alpha_g1, lazy.alpha_g1);
+        assert_eq!(zkey.beta_g1, lazy.beta_g1);
+        assert_eq!(zkey.beta_g2, lazy.beta_g2);
+        assert_eq!(zkey.delta_g1, lazy.delta_g1);
+        assert_eq!(zkey.delta_g2, lazy.delta_g2);
+        assert_eq!(zkey.a_matrix, lazy.a_matrix);
+        assert_eq!(zkey.b_matrix, lazy.b_matrix);
+        for (query, section) in [
+            (&zkey.a_query, &lazy.a_query),
+            (&zkey.b_g1_query, &lazy.b_g1_query),
+            (&zkey.h_query, &lazy.h_query),
+            (&zkey.l_query, &lazy.l_query),
+        ] {
+            assert_eq!(*query, section.read_range(0..section.len()).unwrap());
+            assert_eq!(query[1..], section.read_range(1..section.len()).unwrap());
+        }
+        assert_eq!(
+            zkey.b_g2_query,
+            lazy.b_g2_query
+                .read_range(0..lazy.b_g2_query.len())
+                .unwrap()
+        );
+        let indices = [0, 2, 3];
+        assert_eq!(
+            indices.map(|i| zkey.a_query[i]).to_vec(),
+            lazy.a_query.read_indices(&indices).unwrap()
+        );
+        assert_eq!(
+            indices.map(|i| zkey.b_g2_query[i]).to_vec(),
+            lazy.b_g2_query.read_indices(&indices).unwrap()
+        );
+    }
+
+    #[test]
+    fn truncated_or_corrupted_key_is_rejected() {
+        let zkey =
+            std::fs::read("../../test_vectors/Groth16/bn254/multiplier2/circuit.zkey").unwrap();
+        for len in 0..zkey.len() {
+            assert!(ZKey::<Bn254>::from_reader(&zkey[..len], CheckElement::No).is_err());
+        }
+        // a section id larger than the number of sections
+        let mut corrupted = zkey.clone();
+        corrupted[12..16].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(ZKey::<Bn254>::from_reader(corrupted.as_slice(), CheckElement::No).is_err());
+        // a section length larger than the file
+        let mut corrupted = zkey;
+        corrupted[16..24].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(ZKey::<Bn254>::from_reader(corrupted.as_slice(), CheckElement::No).is_err());
+    }
+
     #[test]
     fn can_deser_bn254_mult2_key() {
         let checks = [CheckElement::Yes, CheckElement::No];
@@ -545,8 +830,8 @@ mod tests {
             )]];
             let b = vec![vec![(ark_bn254::Fr::from_str("1").unwrap(), 3)]];
             assert_eq!(1, pk.num_constraints);
-            assert_eq!(a, pk.a_matrix);
-            assert_eq!(b, pk.b_matrix);
+            assert_eq!(ConstraintEvaluator::deduplicated(&a), pk.a_matrix);
+            assert_eq!(ConstraintEvaluator::deduplicated(&b), pk.b_matrix);
         }
     }
     fn fq_from_str(s: &str) -> Fq {
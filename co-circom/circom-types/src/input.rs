@@ -0,0 +1,258 @@
+//! This module defines the [`InputLayout`] of the inputs of a circuit, which flattens the values of inputs in JSON files in the order circom assigns them to signals.
+//!
+//! Circom (and the witness calculators of snarkjs) qualify every element of an input JSON file by its full name, e.g., `in[1].point.x`, and assign it to the signal of the same name. Thus, the order of the keys of the objects of bus inputs is irrelevant in the JSON file. The signals of a bus are laid out in the order of the declaration of the fields, and arrays are laid out in row-major order. [`InputLayout::flatten`] follows these semantics exactly, independent of the order in which a JSON parser returns the keys of an object.
+
+use serde_json::Value;
+use thiserror::Error;
+
+/// Error type describing inputs whose JSON values do not match their [`InputLayout`]
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum InputLayoutError {
+    /// The value is not an array of the expected length
+    #[error("expected \"{0}\" to be an array of length {1}")]
+    ExpectedArray(String, usize),
+    /// The value of a signal does not have the expected number of elements
+    #[error("expected \"{0}\" to have {1} elements, but it has {2}")]
+    WrongNumberOfElements(String, usize, usize),
+    /// The value of a signal is an object
+    #[error("expected \"{0}\" to be a signal, but it is an object")]
+    ExpectedSignal(String),
+    /// The value of a bus is not an object
+    #[error("expected \"{0}\" to be an object with the fields of a bus")]
+    ExpectedBus(String),
+    /// A field of a bus is missing
+    #[error("missing field \"{0}\"")]
+    MissingField(String),
+    /// The object of a bus has a field the bus does not declare
+    #[error("unknown field \"{0}\"")]
+    UnknownField(String),
+}
+
+/// The layout of an input of the main component or of a field of a bus.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputLayout {
+    /// The name of the input or field
+    pub name: String,
+    /// The dimensions of the input or field, empty if it is not an array
+    pub dimensions: Vec<usize>,
+    /// The fields of a bus in the order of their declaration, empty if the input or field is a signal
+    pub fields: Vec<InputLayout>,
+}
+
+impl InputLayout {
+    /// Returns whether the input or field is a bus (or an array of buses).
+    pub fn is_bus(&self) -> bool {
+        !self.fields.is_empty()
+    }
+
+    /// Returns the number of signals of the input or field.
+    pub fn size(&self) -> usize {
+        let element_size = if self.is_bus() {
+            self.fields.iter().map(InputLayout::size).sum()
+        } else {
+            1
+        };
+        self.dimensions.iter().product::<usize>() * element_size
+    }
+
+    /// Flattens the JSON value of the input into the values of its signals in the order circom assigns them, see the [module documentation](self).
+    ///
+    /// The fields of buses are taken in the order of their declaration. Arrays of buses need to be nested according to their dimensions, whereas the values of (arrays of) signals may be nested arbitrarily, as long as they have the expected number of elements. The leaves are returned as they are, i.e., the caller parses the field elements.
+    pub fn flatten<'a>(&self, value: &'a Value) -> Result<Vec<&'a Value>, InputLayoutError> {
+        let mut leaves = Vec::with_capacity(self.size());
+        self.flatten_into(value, &self.dimensions, self.name.clone(), &mut leaves)?;
+        Ok(leaves)
+    }
+
+    fn flatten_into<'a>(
+        &self,
+        value: &'a Value,
+        dimensions: &[usize],
+        path: String,
+        leaves: &mut Vec<&'a Value>,
+    ) -> Result<(), InputLayoutError> {
+        if !self.is_bus() {
+            let len = leaves.len();
+            flatten_signals(value, &path, leaves)?;
+            let expected = dimensions.iter().product::<usize>();
+            if leaves.len() - len != expected {
+                return Err(InputLayoutError::WrongNumberOfElements(
+                    path,
+                    expected,
+                    leaves.len() - len,
+                ));
+            }
+            return Ok(());
+        }
+        if let Some((len, dimensions)) = dimensions.split_first() {
+            let elements = value
+                .as_array()
+                .filter(|elements| elements.len() == *len)
+                .ok_or_else(|| InputLayoutError::ExpectedArray(path.clone(), *len))?;
+            for (i, element) in elements.iter().enumerate() {
+                self.flatten_into(element, dimensions, format!("{path}[{i}]"), leaves)?;
+            }
+            return Ok(());
+        }
+        let object = value
+            .as_object()
+            .ok_or_else(|| InputLayoutError::ExpectedBus(path.clone()))?;
+        if let Some(key) = object
+            .keys()
+            .find(|key| !self.fields.iter().any(|field| &&field.name == key))
+        {
+            return Err(InputLayoutError::UnknownField(format!("{path}.{key}")));
+        }
+        for field in self.fields.iter() {
+            let path = format!("{path}.{}", field.name);
+            let value = object
+                .get(&field.name)
+                .ok_or_else(|| InputLayoutError::MissingField(path.clone()))?;
+            field.flatten_into(value, &field.dimensions, path, leaves)?;
+        }
+        Ok(())
+    }
+}
+
+/// Flattens (nested) arrays of signal values in row-major order.
+fn flatten_signals<'a>(
+    value: &'a Value,
+    path: &str,
+    leaves: &mut Vec<&'a Value>,
+) -> Result<(), InputLayoutError> {
+    match value {
+        Value::Array(elements) => {
+            for element in elements {
+                flatten_signals(element, path, leaves)?;
+            }
+        }
+        Value::Object(_) => return Err(InputLayoutError::ExpectedSignal(path.to_owned())),
+        _ => leaves.push(value),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{InputLayout, InputLayoutError};
+
+    fn signal(name: &str, dimensions: &[usize]) -> InputLayout {
+        InputLayout {
+            name: name.to_owned(),
+            dimensions: dimensions.to_vec(),
+            fields: vec![],
+        }
+    }
+
+    fn bus(name: &str, dimensions: &[usize], fields: Vec<InputLayout>) -> InputLayout {
+        InputLayout {
+            name: name.to_owned(),
+            dimensions: dimensions.to_vec(),
+            fields,
+        }
+    }
+
+    // bus Point { signal y; signal x; }
+    // bus Segment { Point start; signal weights[2]; Point end; }
+    // input Segment in[2][1];
+    fn segments() -> InputLayout {
+        let point = |name: &str| bus(name, &[], vec![signal("y", &[]), signal("x", &[])]);
+        bus(
+            "in",
+            &[2, 1],
+            vec![point("start"), signal("weights", &[2]), point("end")],
+        )
+    }
+
+    fn flattened(layout: &InputLayout, value: &serde_json::Value) -> Vec<String> {
+        layout
+            .flatten(value)
+            .unwrap()
+            .into_iter()
+            .map(|leaf| leaf.as_str().unwrap().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn flattens_buses_in_declaration_order() {
+        let layout = segments();
+        assert_eq!(layout.size(), 12);
+        // the keys are neither in declaration nor in alphabetical order
+        let value = json!([
+            [{
+                "end": { "x": "6", "y": "5" },
+                "weights": ["3", "4"],
+                "start": { "x": "2", "y": "1" }
+            }],
+            [{
+                "weights": [["9"], ["10"]],
+                "start": { "y": "7", "x": "8" },
+                "end": { "x": "12", "y": "11" }
+            }]
+        ]);
+        assert_eq!(
+            flattened(&layout, &value),
+            (1..=12).map(|i| i.to_string()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn flattens_signals_like_circom() {
+        let layout = signal("in", &[2, 2]);
+        let expected = vec!["1", "2", "3", "4"];
+        assert_eq!(
+            flattened(&layout, &json!([["1", "2"], ["3", "4"]])),
+            expected
+        );
+        assert_eq!(flattened(&layout, &json!(["1", "2", "3", "4"])), expected);
+        assert_eq!(
+            layout.flatten(&json!(["1", "2", "3"])),
+            Err(InputLayoutError::WrongNumberOfElements(
+                "in".to_owned(),
+                4,
+                3
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_values_that_do_not_match_the_layout() {
+        let layout = segments();
+        let point = json!({ "x": "1", "y": "2" });
+        let segment = json!({ "start": point, "weights": ["1", "2"], "end": point });
+        assert_eq!(
+            layout.flatten(&json!([[segment], [segment], [segment]])),
+            Err(InputLayoutError::ExpectedArray("in".to_owned(), 2))
+        );
+        // arrays of buses need to be nested according to their dimensions
+        assert_eq!(
+            layout.flatten(&json!([segment, segment])),
+            Err(InputLayoutError::ExpectedArray("in[0]".to_owned(), 1))
+        );
+        let missing = json!({ "start": point, "weights": ["1", "2"] });
+        assert_eq!(
+            layout.flatten(&json!([[segment], [missing]])),
+            Err(InputLayoutError::MissingField("in[1][0].end".to_owned()))
+        );
+        let unknown = json!({ "start": { "x": "1", "y": "2", "z": "3" }, "weights": ["1", "2"], "end": point });
+        assert_eq!(
+            layout.flatten(&json!([[unknown], [segment]])),
+            Err(InputLayoutError::UnknownField(
+                "in[0][0].start.z".to_owned()
+            ))
+        );
+        let nested = json!({ "start": point, "weights": [point, "2"], "end": point });
+        assert_eq!(
+            layout.flatten(&json!([[segment], [nested]])),
+            Err(InputLayoutError::ExpectedSignal(
+                "in[1][0].weights".to_owned()
+            ))
+        );
+        assert_eq!(
+            layout.flatten(&json!([["1"], [segment]])),
+            Err(InputLayoutError::ExpectedBus("in[0][0]".to_owned()))
+        );
+    }
+}
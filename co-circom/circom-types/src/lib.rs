@@ -1,12 +1,18 @@
 #![warn(missing_docs)]
 //! This crate defines types used in circom and utilities to read these types from files.
 mod binfile;
+mod constraint_evaluator;
 pub mod groth16;
+mod input;
 pub mod plonk;
 mod r1cs;
 pub mod traits;
 mod witness;
 
+pub use constraint_evaluator::{Constraint, ConstraintEvaluator};
+pub use input::InputLayout;
+pub use input::InputLayoutError;
+pub use r1cs::CircomPrime;
 pub use r1cs::R1CSParserError;
 pub use r1cs::R1CS;
 
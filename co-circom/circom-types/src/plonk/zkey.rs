@@ -32,7 +32,7 @@ use ark_serialize::CanonicalDeserialize;
 use std::io::{Cursor, Read};
 
 use crate::{
-    binfile::{BinFile, ZKeyParserError, ZKeyParserResult},
+    binfile::{check_section_size, BinFile, ZKeyParserError, ZKeyParserResult},
     traits::{CheckElement, CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge},
 };
 
@@ -166,7 +166,7 @@ where
         let mut binfile = BinFile::<P>::new(&mut reader)?;
 
         tracing::debug!("start transforming bin file into zkey...");
-        let header = PlonkHeader::<P>::read(&mut binfile.take_section(2))?;
+        let header = PlonkHeader::<P>::read(&mut binfile.take_section(2)?)?;
         let n_vars = header.n_vars;
         let n_additions = header.n_additions;
         let n_constraints = header.n_constraints;
@@ -175,18 +175,25 @@ where
         //the sigmas are in the same section - so we split it here in separate chunks
         let sigma_section_size = domain_size * header.n8r + domain_size * 4 * header.n8r;
 
-        let add_section = binfile.take_section(3);
-        let a_section = binfile.take_section(4);
-        let b_section = binfile.take_section(5);
-        let c_section = binfile.take_section(6);
-        let qm_section = binfile.take_section(7);
-        let ql_section = binfile.take_section(8);
-        let qr_section = binfile.take_section(9);
-        let q0_section = binfile.take_section(10);
-        let qc_section = binfile.take_section(11);
-        let sigma_sections = binfile.take_section_raw(12);
-        let l_section = binfile.take_section(13);
-        let t_section = binfile.take_section(14);
+        let add_section = binfile.take_section(3)?;
+        let a_section = binfile.take_section(4)?;
+        let b_section = binfile.take_section(5)?;
+        let c_section = binfile.take_section(6)?;
+        let qm_section = binfile.take_section(7)?;
+        let ql_section = binfile.take_section(8)?;
+        let qr_section = binfile.take_section(9)?;
+        let q0_section = binfile.take_section(10)?;
+        let qc_section = binfile.take_section(11)?;
+        let sigma_sections = binfile.take_section_raw(12)?;
+        let l_section = binfile.take_section(13)?;
+        let t_section = binfile.take_section(14)?;
+        // the sizes from the header determine the allocations while parsing, so they must be backed by the sections
+        check_section_size(add_section.get_ref(), 3, n_additions, 8 + 2 * header.n8r)?;
+        for (id, section) in [(4, &a_section), (5, &b_section), (6, &c_section)] {
+            check_section_size(section.get_ref(), id, n_constraints, 4)?;
+        }
+        check_section_size(&sigma_sections, 12, 3, sigma_section_size)?;
+        check_section_size(l_section.get_ref(), 13, n_public, sigma_section_size)?;
         let sigma1_section = Cursor::new(&sigma_sections[..sigma_section_size]);
         let sigma2_section =
             Cursor::new(&sigma_sections[sigma_section_size..sigma_section_size * 2]);
@@ -409,7 +416,7 @@ where
         let n_constraints = u32::deserialize_uncompressed(&mut reader)?;
         tracing::debug!("n_vars: {n_vars}; n_public: {n_public}, domain_size: {domain_size}");
         let verifying_key = VerifyingKey::new(&mut reader)?;
-        if domain_size & (domain_size - 1) == 0 && domain_size > 0 {
+        if domain_size.is_power_of_two() {
             tracing::debug!("read header done!");
             Ok(Self {
                 n8r: u32_to_usize!(n8r),
@@ -32,7 +32,7 @@ use std::io::{Error, ErrorKind};
 use thiserror::Error;
 
 use ark_ec::pairing::Pairing;
-use ark_serialize::{CanonicalDeserialize, SerializationError};
+use ark_serialize::{CanonicalSerialize, SerializationError};
 use ark_std::io::{Read, Seek, SeekFrom};
 
 use std::collections::HashMap;
@@ -44,6 +44,40 @@ use crate::{
 
 const R1CS_HEADER: &str = "r1cs";
 const MAX_VERSION: u32 = 1;
+/// The largest field size in bytes accepted in the header, i.e., a 512-bit prime.
+const MAX_FIELD_SIZE: u32 = 64;
+/// The primes circom can compile circuits for (see `circom --prime`), as big-endian hex strings.
+const CIRCOM_PRIMES: &[(&str, &str)] = &[
+    (
+        "bn128",
+        "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+    ),
+    (
+        "bls12381",
+        "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001",
+    ),
+    (
+        "bls12377",
+        "12ab655e9a2ca55660b44d1e5c37b00159aa76fed00000010a11800000000001",
+    ),
+    ("goldilocks", "ffffffff00000001"),
+    (
+        "grumpkin",
+        "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47",
+    ),
+    (
+        "pallas",
+        "40000000000000000000000000000000224698fc094cf91b992d30ed00000001",
+    ),
+    (
+        "vesta",
+        "40000000000000000000000000000000224698fc0994a8dd8c46eb2100000001",
+    ),
+    (
+        "secq256r1",
+        "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+    ),
+];
 type Result<T> = std::result::Result<T, R1CSParserError>;
 
 //TODO maybe write something better that is not so convoluted to access
@@ -68,6 +102,92 @@ pub enum R1CSParserError {
     /// Error describing that the ScalarField from curve does not match in witness file
     #[error("ScalarField from curve does not match in witness file")]
     WrongScalarField,
+    /// Error describing that the R1CS file is defined over a different prime than the scalar field of the chosen curve
+    #[error("The R1CS file is defined over the {found} prime, but the chosen curve requires the {expected} prime. Compile the circuit with `--prime {expected}` or choose the matching curve")]
+    FieldMismatch {
+        /// The circom name of the prime of the chosen curve
+        expected: String,
+        /// The prime of the R1CS file
+        found: CircomPrime,
+    },
+}
+
+/// The prime field an R1CS file is defined over, see [`CircomPrime::from_reader`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CircomPrime {
+    /// The circom name of the prime (see `circom --prime`), if it is one of the primes supported by circom
+    pub name: Option<&'static str>,
+    /// The prime as big-endian hex string without leading zeros
+    pub modulus: String,
+    /// The size of a field element in bytes
+    pub field_size: u32,
+}
+
+impl CircomPrime {
+    fn from_le_bytes(bytes: &[u8]) -> Self {
+        let hex = bytes
+            .iter()
+            .rev()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>();
+        let modulus = match hex.trim_start_matches('0') {
+            "" => "0".to_owned(),
+            trimmed => trimmed.to_owned(),
+        };
+        let name = CIRCOM_PRIMES
+            .iter()
+            .find(|(_, prime)| *prime == modulus)
+            .map(|(name, _)| *name);
+        Self {
+            name,
+            modulus,
+            field_size: u32::try_from(bytes.len()).expect("field size is bounded"),
+        }
+    }
+
+    /// Reads the prime from the header of an R1CS file without parsing the constraints. This works for all primes, also for the ones without a supported curve, e.g., to check which curve a circuit was compiled for.
+    pub fn from_reader<R: Read + Seek>(mut reader: R) -> Result<Self> {
+        reader_utils::read_header(&mut reader, R1CS_HEADER)?;
+        let version = reader.read_u32::<LittleEndian>()?;
+        if version != MAX_VERSION {
+            return Err(R1CSParserError::VersionNotSupported(MAX_VERSION, version));
+        }
+        let num_sections = reader.read_u32::<LittleEndian>()?;
+        for _ in 0..num_sections {
+            let sec_type = reader.read_u32::<LittleEndian>()?;
+            let sec_size = reader.read_u64::<LittleEndian>()?;
+            if sec_type == 1 {
+                return read_prime(&mut reader, sec_size);
+            }
+            reader.seek(SeekFrom::Current(sec_size as i64))?;
+        }
+        Err(Error::new(ErrorKind::InvalidData, "No header section found").into())
+    }
+}
+
+impl std::fmt::Display for CircomPrime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.name {
+            Some(name) => f.write_str(name),
+            None => write!(f, "0x{}", self.modulus),
+        }
+    }
+}
+
+/// Reads the field size and the prime at the start of the header section.
+fn read_prime<R: Read>(mut reader: R, header_size: u64) -> Result<CircomPrime> {
+    let field_size = reader.read_u32::<LittleEndian>()?;
+    // check the size before allocating the buffer, as the field size is untrusted
+    if field_size == 0 || field_size > MAX_FIELD_SIZE || header_size != 32 + u64::from(field_size) {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("Invalid field size {field_size} in header of size {header_size}"),
+        )
+        .into());
+    }
+    let mut prime = vec![0; field_size as usize];
+    reader.read_exact(&mut prime)?;
+    Ok(CircomPrime::from_le_bytes(&prime))
 }
 
 /// Struct representing a R1CS file produced by circom that implements [`R1CS::from_reader`] for parsing the R1CS file format used by circom.
@@ -144,20 +264,16 @@ where
             )
         });
 
-        let field_size =
-            usize::try_from(reader.read_u32::<LittleEndian>()?).expect("u32 fits into usize");
-        if field_size != P::ScalarField::SERIALIZED_BYTE_SIZE {
-            return Err(R1CSParserError::WrongScalarField);
-        }
-
-        if *header_size? != 32 + field_size as u64 {
-            return Err(R1CSParserError::WrongScalarField);
-        }
-
-        let q = <P::ScalarField as PrimeField>::BigInt::deserialize_uncompressed(&mut reader)?;
-        let modulus = P::ScalarField::MODULUS;
-        if q != modulus {
-            return Err(R1CSParserError::WrongScalarField);
+        let prime = read_prime(&mut reader, *header_size?)?;
+        let mut modulus = Vec::new();
+        P::ScalarField::MODULUS.serialize_uncompressed(&mut modulus)?;
+        if prime.field_size as usize != P::ScalarField::SERIALIZED_BYTE_SIZE
+            || prime != CircomPrime::from_le_bytes(&modulus)
+        {
+            return Err(R1CSParserError::FieldMismatch {
+                expected: P::get_circom_name(),
+                found: prime,
+            });
         }
 
         let num_variables =
@@ -278,6 +394,7 @@ mod tests {
     use super::*;
     use ark_bls12_381::Bls12_381;
     use ark_bn254::Bn254;
+    use ark_ff::BigInteger;
 
     use std::{fs::File, str::FromStr};
 
@@ -333,4 +450,53 @@ mod tests {
         );
         assert_eq!(r1cs.wire_mapping, vec![0, 1, 2, 3]);
     }
+
+    #[test]
+    fn reads_prime_of_r1cs() {
+        let r1cs_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/circuit.r1cs").unwrap();
+        let prime = CircomPrime::from_reader(r1cs_file).unwrap();
+        assert_eq!(prime.name, Some("bn128"));
+        assert_eq!(prime.field_size, 32);
+
+        // a header section with the goldilocks prime
+        let mut goldilocks = b"r1cs".to_vec();
+        goldilocks.extend(1u32.to_le_bytes());
+        goldilocks.extend(1u32.to_le_bytes());
+        goldilocks.extend(1u32.to_le_bytes());
+        goldilocks.extend(40u64.to_le_bytes());
+        goldilocks.extend(8u32.to_le_bytes());
+        goldilocks.extend(0xffffffff00000001u64.to_le_bytes());
+        goldilocks.extend([0; 28]);
+        let prime = CircomPrime::from_reader(std::io::Cursor::new(goldilocks)).unwrap();
+        assert_eq!(prime.name, Some("goldilocks"));
+        assert_eq!(prime.to_string(), "goldilocks");
+    }
+
+    #[test]
+    fn circom_primes_match_supported_curves() {
+        fn name_of_scalar_field<P: Pairing>() -> Option<&'static str> {
+            CircomPrime::from_le_bytes(&P::ScalarField::MODULUS.to_bytes_le()).name
+        }
+        assert_eq!(name_of_scalar_field::<Bn254>(), Some("bn128"));
+        assert_eq!(name_of_scalar_field::<Bls12_381>(), Some("bls12381"));
+        assert_eq!(
+            name_of_scalar_field::<ark_bls12_377::Bls12_377>(),
+            Some("bls12377")
+        );
+    }
+
+    #[test]
+    fn rejects_r1cs_of_other_prime() {
+        let r1cs_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/circuit.r1cs").unwrap();
+        let err = R1CS::<Bls12_381>::from_reader(r1cs_file).unwrap_err();
+        match err {
+            R1CSParserError::FieldMismatch { expected, found } => {
+                assert_eq!(expected, "bls12381");
+                assert_eq!(found.name, Some("bn128"));
+            }
+            err => panic!("unexpected error {err}"),
+        }
+    }
 }
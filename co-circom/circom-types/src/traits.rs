@@ -12,6 +12,16 @@ use std::str::FromStr;
 
 type IoResult<T> = Result<T, SerializationError>;
 
+/// Reads exactly `len` bytes without allocating them upfront, so that a corrupted length cannot exhaust the memory.
+fn read_exact_vec(reader: impl Read, len: usize) -> std::io::Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    reader.take(len as u64).read_to_end(&mut buf)?;
+    if buf.len() != len {
+        return Err(std::io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(buf)
+}
+
 macro_rules! impl_bn256 {
     () => {
         //TODO use stringify
@@ -33,6 +43,20 @@ macro_rules! impl_bls12_381 {
     };
 }
 
+macro_rules! impl_bls12_377 {
+    () => {
+        impl_serde_for_curve!(
+            bls12_377,
+            Bls12_377,
+            ark_bls12_377,
+            "bls12_377",
+            48,
+            32,
+            "bls12377"
+        );
+    };
+}
+
 macro_rules! impl_serde_for_curve {
     ($mod_name: ident, $config: ident, $curve: ident, $name: expr, $field_size: expr, $scalar_field_size: expr, $circom_name: expr) => {
 
@@ -569,12 +593,14 @@ where
     /// Deserializes vec of G1 from reader where the elements are already in montgomery form (no montgomery reduction performed)
     /// The default implementation runs multithreaded using rayon
     fn g1_vec_from_reader(
-        mut reader: impl Read,
+        reader: impl Read,
         num: usize,
         check: CheckElement,
     ) -> IoResult<Vec<Self::G1Affine>> {
-        let mut buf = vec![0u8; Self::G1_SERIALIZED_BYTE_SIZE_UNCOMPRESSED * num];
-        reader.read_exact(&mut buf)?;
+        let len = Self::G1_SERIALIZED_BYTE_SIZE_UNCOMPRESSED
+            .checked_mul(num)
+            .ok_or(SerializationError::InvalidData)?;
+        let buf = read_exact_vec(reader, len)?;
         buf.par_chunks_exact(Self::G1_SERIALIZED_BYTE_SIZE_UNCOMPRESSED)
             .map(|chunk| Self::g1_from_bytes(chunk, check))
             .collect::<Result<Vec<_>, SerializationError>>()
@@ -582,12 +608,14 @@ where
     /// Deserializes vec of G2 from reader where the elements are already in montgomery form (no montgomery reduction performed)
     /// The default implementation runs multithreaded using rayon
     fn g2_vec_from_reader(
-        mut reader: impl Read,
+        reader: impl Read,
         num: usize,
         check: CheckElement,
     ) -> IoResult<Vec<Self::G2Affine>> {
-        let mut buf = vec![0u8; Self::G2_SERIALIZED_BYTE_SIZE_UNCOMPRESSED * num];
-        reader.read_exact(&mut buf)?;
+        let len = Self::G2_SERIALIZED_BYTE_SIZE_UNCOMPRESSED
+            .checked_mul(num)
+            .ok_or(SerializationError::InvalidData)?;
+        let buf = read_exact_vec(reader, len)?;
         buf.par_chunks_exact(Self::G2_SERIALIZED_BYTE_SIZE_UNCOMPRESSED)
             .map(|chunk| Self::g2_from_bytes(chunk, check))
             .collect::<Result<Vec<_>, SerializationError>>()
@@ -685,3 +713,4 @@ pub enum CheckElement {
 
 impl_bn256!();
 impl_bls12_381!();
+impl_bls12_377!();
@@ -1,4 +1,6 @@
 //! This module defines the [`Witness`] struct that implements deserialization of circom witness files via [`Witness::from_reader`].
+//!
+//! A witness file is a container of sections, each prefixed by its id and length. Versions 1 and 2 of the container are supported, which are the versions written by circom and snarkjs. Both versions use the same layout of the header (id 1) and witness (id 2) sections. Sections with other ids are skipped, so additional sections do not break parsing.
 
 use std::io;
 
@@ -13,8 +15,11 @@ use crate::reader_utils::{self, InvalidHeaderError};
 
 type Result<T> = std::result::Result<T, WitnessParserError>;
 const WITNESS_HEADER: &str = "wtns";
+const MIN_VERSION: u32 = 1;
 const MAX_VERSION: u32 = 2;
 const N_SECTIONS: u32 = 2;
+const HEADER_SECTION: u32 = 1;
+const WITNESS_SECTION: u32 = 2;
 
 /// Error type describing errors during parsing witness files
 #[derive(Debug, Error)]
@@ -26,11 +31,14 @@ pub enum WitnessParserError {
     #[error(transparent)]
     SerializationError(#[from] SerializationError),
     /// Error describing that the version of the file is not supported for parsing
-    #[error("Max supported version is {0}, but got {1}")]
+    #[error("Unsupported witness file version {1}, supported versions are 1 to {0}")]
     VersionNotSupported(u32, u32),
     /// Error describing that the number of sections in the file is invalid
-    #[error("Wrong number of sections is {0}, but got {1}")]
+    #[error("Expected at least {0} sections, but got {1}")]
     InvalidSectionNumber(u32, u32),
+    /// Error describing that the sections of the file are missing, duplicated, or do not match the header
+    #[error("Invalid section layout: {0}")]
+    InvalidSectionLayout(String),
     /// Error describing that the ScalarField from curve does not match in witness file
     #[error("ScalarField from curve does not match in witness file")]
     WrongScalarField,
@@ -52,42 +60,93 @@ impl<F: CircomArkworksPrimeFieldBridge> Witness<F> {
         tracing::trace!("trying to read witness");
         reader_utils::read_header(&mut reader, WITNESS_HEADER)?;
         let version = reader.read_u32::<LittleEndian>()?;
-        if version > MAX_VERSION {
+        if !(MIN_VERSION..=MAX_VERSION).contains(&version) {
             return Err(WitnessParserError::VersionNotSupported(
                 MAX_VERSION,
                 version,
             ));
         }
+        tracing::trace!("witness file version {version}");
 
         let n_sections = reader.read_u32::<LittleEndian>()?;
-        if n_sections > N_SECTIONS {
+        if n_sections < N_SECTIONS {
             return Err(WitnessParserError::InvalidSectionNumber(
                 N_SECTIONS, n_sections,
             ));
         }
-        //this is the section id and length
-        //don't know if we need them, maybe at least log them later
-        let _ = reader.read_u32::<LittleEndian>()?;
-        let _ = reader.read_u64::<LittleEndian>()?;
+        let mut header = None;
+        let mut values = None;
+        for _ in 0..n_sections {
+            let section_id = reader.read_u32::<LittleEndian>()?;
+            let section_len = reader.read_u64::<LittleEndian>()?;
+            match section_id {
+                HEADER_SECTION if header.is_none() => {
+                    header = Some(Self::read_header_section(&mut reader, section_len)?);
+                }
+                WITNESS_SECTION if values.is_none() => {
+                    let n_witness = header.ok_or_else(|| {
+                        WitnessParserError::InvalidSectionLayout(
+                            "witness section before header section".to_owned(),
+                        )
+                    })?;
+                    let n8 = F::MODULUS.to_bytes_le().len() as u64;
+                    if section_len != u64::from(n_witness) * n8 {
+                        return Err(WitnessParserError::InvalidSectionLayout(format!(
+                            "witness section has {section_len} bytes, but the header declares {n_witness} elements"
+                        )));
+                    }
+                    values = Some(
+                        (0..n_witness)
+                            .map(|_| {
+                                F::from_reader(&mut reader)
+                                    .map_err(WitnessParserError::SerializationError)
+                            })
+                            .collect::<Result<Vec<F>>>()?,
+                    );
+                }
+                HEADER_SECTION | WITNESS_SECTION => {
+                    return Err(WitnessParserError::InvalidSectionLayout(format!(
+                        "duplicate section {section_id}"
+                    )));
+                }
+                _ => {
+                    tracing::trace!("skipping unknown section {section_id}");
+                    let skipped = io::copy(&mut (&mut reader).take(section_len), &mut io::sink())?;
+                    if skipped != section_len {
+                        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+                    }
+                }
+            }
+        }
+        let values = values.ok_or_else(|| {
+            WitnessParserError::InvalidSectionLayout("missing witness section".to_owned())
+        })?;
+        Ok(Self { values })
+    }
+
+    /// Reads the header section and returns the number of witness elements.
+    fn read_header_section<R: Read>(reader: &mut R, section_len: u64) -> Result<u32> {
         let n8 = reader.read_u32::<LittleEndian>()?;
-        let mut buf = vec![0; usize::try_from(n8).expect("u32 fits into usize")];
+        let modulus = F::MODULUS.to_bytes_le();
+        // check the size before allocating the buffer, as n8 is untrusted
+        if usize::try_from(n8).expect("u32 fits into usize") != modulus.len() {
+            tracing::trace!("wrong scalar field");
+            return Err(WitnessParserError::WrongScalarField);
+        }
+        let mut buf = vec![0; modulus.len()];
         reader.read_exact(buf.as_mut_slice())?;
-        if F::MODULUS.to_bytes_le() != buf {
+        if modulus != buf {
             tracing::trace!("wrong scalar field");
             return Err(WitnessParserError::WrongScalarField);
         }
         let n_witness = reader.read_u32::<LittleEndian>()?;
-        //this is the section id and length
-        //don't know if we need them, maybe at least log them later
-        let _ = reader.read_u32::<LittleEndian>()?;
-        let _ = reader.read_u64::<LittleEndian>()?;
-        Ok(Self {
-            values: (0..n_witness)
-                .map(|_| {
-                    F::from_reader(&mut reader).map_err(WitnessParserError::SerializationError)
-                })
-                .collect::<Result<Vec<F>>>()?,
-        })
+        if section_len != 8 + u64::from(n8) {
+            return Err(WitnessParserError::InvalidSectionLayout(format!(
+                "header section has {section_len} bytes, expected {}",
+                8 + u64::from(n8)
+            )));
+        }
+        Ok(n_witness)
     }
 }
 
@@ -95,7 +154,18 @@ impl<F: CircomArkworksPrimeFieldBridge> Witness<F> {
 mod tests {
     use std::fs::File;
 
-    use super::Witness;
+    use super::{Witness, WitnessParserError};
+
+    fn multiplier2_bn254() -> Witness<ark_bn254::Fr> {
+        Witness {
+            values: vec![
+                ark_bn254::Fr::from(1),
+                ark_bn254::Fr::from(33),
+                ark_bn254::Fr::from(3),
+                ark_bn254::Fr::from(11),
+            ],
+        }
+    }
 
     #[test]
     fn can_deser_witness_bn254() {
@@ -132,4 +202,30 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn can_deser_witness_v1() {
+        let witness =
+            File::open("../../test_vectors/Witness/bn254/multiplier2/witness_v1.wtns").unwrap();
+        let is_witness = Witness::<ark_bn254::Fr>::from_reader(witness).unwrap();
+        assert_eq!(is_witness, multiplier2_bn254());
+    }
+
+    #[test]
+    fn can_deser_witness_with_unknown_section() {
+        let witness = File::open(
+            "../../test_vectors/Witness/bn254/multiplier2/witness_v2_extra_section.wtns",
+        )
+        .unwrap();
+        let is_witness = Witness::<ark_bn254::Fr>::from_reader(witness).unwrap();
+        assert_eq!(is_witness, multiplier2_bn254());
+    }
+
+    #[test]
+    fn rejects_unsupported_witness_version() {
+        let witness =
+            File::open("../../test_vectors/Witness/bn254/multiplier2/witness_v3.wtns").unwrap();
+        let err = Witness::<ark_bn254::Fr>::from_reader(witness).unwrap_err();
+        assert!(matches!(err, WitnessParserError::VersionNotSupported(2, 3)));
+    }
 }
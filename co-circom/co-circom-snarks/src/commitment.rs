@@ -0,0 +1,295 @@
+use std::collections::BTreeMap;
+
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{PrimeField, UniformRand};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use mpc_core::protocols::rep3::{network::Rep3Network, Rep3PointShare, Rep3PrimeFieldShare};
+use rand::{CryptoRng, Rng};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// The domain separator of the generators of a [PedersenKey].
+const GENERATOR_DOMAIN: &[u8] = b"co-circom-snarks pedersen generator";
+/// The domain separator of the public input an [InputCommitment] is exposed as.
+const PUBLIC_INPUT_DOMAIN: &[u8] = b"co-circom-snarks commitment public input";
+
+/// The public parameters of a Pedersen vector commitment `C = sum x_i * G_i + r * H`.
+///
+/// The generators are derived by hashing a public label to the curve, so no party knows a discrete logarithm relation between them and the commitment is binding.
+#[derive(Debug, Clone)]
+pub struct PedersenKey<C: CurveGroup> {
+    generators: Vec<C::Affine>,
+    blinding_generator: C::Affine,
+}
+
+impl<C: CurveGroup> PedersenKey<C> {
+    /// Derives a key for vectors of up to `size` elements from the provided label. Keys with the same label and a different size share their generators.
+    pub fn new(label: &[u8], size: usize) -> Self {
+        Self {
+            generators: (0..size)
+                .map(|i| hash_to_curve::<C>(label, i as u64 + 1))
+                .collect(),
+            blinding_generator: hash_to_curve::<C>(label, 0),
+        }
+    }
+
+    /// The maximum number of elements a commitment with this key can cover.
+    pub fn size(&self) -> usize {
+        self.generators.len()
+    }
+
+    /// Commits to the provided values with the provided blinding factor.
+    pub fn commit(&self, values: &[C::ScalarField], blinding: C::ScalarField) -> eyre::Result<C> {
+        if values.len() > self.size() {
+            eyre::bail!(
+                "cannot commit to {} elements with a key of size {}",
+                values.len(),
+                self.size()
+            );
+        }
+        Ok(C::msm_unchecked(&self.generators[..values.len()], values)
+            + self.blinding_generator * blinding)
+    }
+
+    /// Computes the REP3 share of the commitment to the shared values with the shared blinding factor. This is a local operation.
+    pub fn commit_rep3(
+        &self,
+        values: &[Rep3PrimeFieldShare<C::ScalarField>],
+        blinding: Rep3PrimeFieldShare<C::ScalarField>,
+    ) -> eyre::Result<Rep3PointShare<C>> {
+        if values.len() > self.size() {
+            eyre::bail!(
+                "cannot commit to {} elements with a key of size {}",
+                values.len(),
+                self.size()
+            );
+        }
+        let mut commitment = mpc_core::protocols::rep3::pointshare::msm_public_points(
+            &self.generators[..values.len()],
+            values,
+        );
+        let blinding = mpc_core::protocols::rep3::pointshare::scalar_mul_public_point(
+            &self.blinding_generator.into_group(),
+            blinding,
+        );
+        mpc_core::protocols::rep3::pointshare::add_assign(&mut commitment, &blinding);
+        Ok(commitment)
+    }
+}
+
+/// Hashes the label and the index to a point in the prime order subgroup with try-and-increment.
+fn hash_to_curve<C: CurveGroup>(label: &[u8], index: u64) -> C::Affine {
+    let mut counter = 0u64;
+    loop {
+        // two digests, such that the bytes cover the base fields of all supported curves
+        let bytes = [0u8, 1u8]
+            .into_iter()
+            .flat_map(|half| {
+                Sha256::new()
+                    .chain_update(GENERATOR_DOMAIN)
+                    .chain_update((label.len() as u64).to_le_bytes())
+                    .chain_update(label)
+                    .chain_update(index.to_le_bytes())
+                    .chain_update(counter.to_le_bytes())
+                    .chain_update([half])
+                    .finalize()
+            })
+            .collect::<Vec<_>>();
+        if let Some(point) = C::Affine::from_random_bytes(&bytes) {
+            let point = point.clear_cofactor();
+            if !point.is_zero() {
+                return point;
+            }
+        }
+        counter += 1;
+    }
+}
+
+/// A Pedersen commitment to the concatenation of some shared inputs of a circuit, created when the inputs are shared and published by the data owner.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct InputCommitment<C: CurveGroup> {
+    /// The label the [PedersenKey] is derived from
+    pub label: String,
+    /// The names of the committed inputs and their lengths, in the order of their concatenation
+    pub inputs: Vec<(String, usize)>,
+    /// The commitment
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    pub commitment: C::Affine,
+    /// The public input of the circuit the commitment is exposed as, if any, see [InputCommitment::public_input_value]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_input: Option<CommitmentPublicInput>,
+}
+
+/// The public input of a circuit an [InputCommitment] is exposed as. The value is part of the public inputs of the proof, so verifiers of the proof can link it to the published commitment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitmentPublicInput {
+    /// The name of the public input signal
+    pub name: String,
+    /// The value of the public input in decimal, as it appears in the public inputs of the proof
+    pub value: String,
+}
+
+impl<C: CurveGroup> InputCommitment<C> {
+    /// Commits to the inputs with the provided names, using a fresh blinding factor. Returns the commitment and the blinding factor, which needs to be shared with the computing parties as the opening of the commitment.
+    pub fn commit<R: Rng + CryptoRng>(
+        label: &str,
+        inputs: &BTreeMap<String, Vec<C::ScalarField>>,
+        names: &[String],
+        rng: &mut R,
+    ) -> eyre::Result<(Self, C::ScalarField)> {
+        let mut values = Vec::new();
+        let mut committed = Vec::with_capacity(names.len());
+        for name in names {
+            let input = inputs
+                .get(name)
+                .ok_or_else(|| eyre::eyre!("cannot commit to unknown input \"{name}\""))?;
+            values.extend_from_slice(input);
+            committed.push((name.to_owned(), input.len()));
+        }
+        let blinding = C::ScalarField::rand(rng);
+        let key = PedersenKey::<C>::new(label.as_bytes(), values.len());
+        let commitment = key.commit(&values, blinding)?;
+        Ok((
+            Self {
+                label: label.to_owned(),
+                inputs: committed,
+                commitment: commitment.into_affine(),
+                public_input: None,
+            },
+            blinding,
+        ))
+    }
+
+    /// Exposes the commitment as the public input `name` of the circuit. Returns the value of the public input, which the data owner sets in the shared inputs.
+    pub fn expose_as_public_input(&mut self, name: &str) -> C::ScalarField {
+        let value = self.public_input_value();
+        self.public_input = Some(CommitmentPublicInput {
+            name: name.to_owned(),
+            value: value.to_string(),
+        });
+        value
+    }
+
+    /// The value of the public input the commitment is exposed as, i.e., the hash of the label and the compressed commitment reduced modulo the order of the scalar field.
+    pub fn public_input_value(&self) -> C::ScalarField {
+        let mut commitment = Vec::new();
+        self.commitment
+            .serialize_compressed(&mut commitment)
+            .expect("serializing into a vector does not fail");
+        let digest = Sha256::new()
+            .chain_update(PUBLIC_INPUT_DOMAIN)
+            .chain_update((self.label.len() as u64).to_le_bytes())
+            .chain_update(self.label.as_bytes())
+            .chain_update(commitment)
+            .finalize();
+        C::ScalarField::from_le_bytes_mod_order(&digest)
+    }
+
+    /// Checks that the public inputs of the circuit contain the commitment, if it is exposed as a public input. This binds the commitment to the statement of the proof.
+    pub fn check_public_input(
+        &self,
+        public_inputs: &BTreeMap<String, Vec<C::ScalarField>>,
+    ) -> eyre::Result<()> {
+        let Some(public_input) = &self.public_input else {
+            return Ok(());
+        };
+        let expected = self.public_input_value();
+        if public_input.value != expected.to_string() {
+            eyre::bail!(
+                "the value of public input \"{}\" does not match the commitment",
+                public_input.name
+            );
+        }
+        match public_inputs.get(&public_input.name).map(Vec::as_slice) {
+            Some([value]) if *value == expected => Ok(()),
+            Some(_) => eyre::bail!(
+                "public input \"{}\" differs from the commitment",
+                public_input.name
+            ),
+            None => eyre::bail!(
+                "the commitment is exposed as public input \"{}\", which is missing",
+                public_input.name
+            ),
+        }
+    }
+
+    /// Checks in MPC that the shared inputs open to this commitment, i.e., that the computing parties use the inputs the data owner committed to. The parties only open the commitment to their shares, which hides the inputs. If the commitment is exposed as a public input, the public inputs need to contain it, see [InputCommitment::check_public_input].
+    pub fn verify_rep3<N: Rep3Network>(
+        &self,
+        public_inputs: &BTreeMap<String, Vec<C::ScalarField>>,
+        shared_inputs: &BTreeMap<String, Vec<Rep3PrimeFieldShare<C::ScalarField>>>,
+        opening: &CommitmentOpening<Rep3PrimeFieldShare<C::ScalarField>>,
+        network: &mut N,
+    ) -> eyre::Result<()> {
+        self.check_public_input(public_inputs)?;
+        let names = self
+            .inputs
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        if names
+            != opening
+                .inputs
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+        {
+            eyre::bail!("the opening covers different inputs than the commitment");
+        }
+        let mut values = Vec::new();
+        for (name, len) in self.inputs.iter() {
+            let input = shared_inputs
+                .get(name)
+                .ok_or_else(|| eyre::eyre!("committed input \"{name}\" is not shared"))?;
+            if input.len() != *len {
+                eyre::bail!(
+                    "committed input \"{name}\" has {} elements, but the commitment covers {len}",
+                    input.len()
+                );
+            }
+            values.extend_from_slice(input);
+        }
+        let key = PedersenKey::<C>::new(self.label.as_bytes(), values.len());
+        let share = key.commit_rep3(&values, opening.blinding)?;
+        // the opened point is the commitment itself if the check succeeds, which is public anyways
+        let c = network.reshare(share.b)?;
+        let opened = share.a + share.b + c;
+        if opened.into_affine() != self.commitment {
+            eyre::bail!("the shared inputs do not open to the commitment");
+        }
+        Ok(())
+    }
+}
+
+/// The share of the opening of an [InputCommitment], i.e., the share of its blinding factor, which is distributed together with the input shares.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct CommitmentOpening<S: CanonicalSerialize + CanonicalDeserialize> {
+    /// The names of the committed inputs, in the order of their concatenation
+    pub inputs: Vec<String>,
+    /// The share of the blinding factor
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    pub blinding: S,
+}
+
+impl<F: PrimeField> CommitmentOpening<Rep3PrimeFieldShare<F>> {
+    /// Shares the blinding factor of a commitment to the provided inputs for the three parties.
+    pub fn share_rep3<R: Rng + CryptoRng>(
+        inputs: Vec<String>,
+        blinding: F,
+        rng: &mut R,
+    ) -> [Self; 3] {
+        let [a, b, c] = mpc_core::protocols::rep3::share_field_element(blinding, rng);
+        [a, b, c].map(|blinding| Self {
+            inputs: inputs.clone(),
+            blinding,
+        })
+    }
+}
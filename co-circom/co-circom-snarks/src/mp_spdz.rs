@@ -0,0 +1,255 @@
+//! Converters between the share files of [MP-SPDZ](https://github.com/data61/MP-SPDZ) and the [SharedWitness] and [SharedInput] containers.
+//!
+//! MP-SPDZ stores the shares of a party, e.g., the outputs of a computation written with `sint.write_to_file()`, in a binary file that starts with a header followed by the shares. We support the files of the semi-honest three-party replicated protocol (`replicated-field-party.x`) and of the Shamir protocol (`shamir-party.x`) over prime fields. The file layout is:
+//!
+//! - the length of the rest of the header as 8-byte little-endian integer,
+//! - the descriptor of the share type (e.g., `replicated gfp` or `Shamir gfp`) as 4-byte big-endian length followed by the string,
+//! - the prime of the field as 1-byte sign (always 0), 4-byte big-endian length and the big-endian bytes of the prime,
+//! - the shares, where each field element is stored in Montgomery representation with `R = 2^(64 * L)` as `L` little-endian 64-bit limbs, and `L` is the number of 64-bit limbs required for the prime.
+//!
+//! A replicated share of MP-SPDZ consists of two field elements, where party `i` stores the additive shares `x_i` and `x_{i+1}`. Our [Rep3PrimeFieldShare] stores the additive share of the party and the one of the previous party, thus the two elements are swapped during the conversion. A Shamir share consists of one field element, the evaluation of the sharing polynomial at `i + 1`, which matches [ShamirPrimeFieldShare].
+//!
+//! MP-SPDZ files only contain shares, so public inputs and the names of the inputs need to be provided when importing and are not part of the exported files.
+use std::{
+    collections::BTreeMap,
+    io::{Read, Write},
+};
+
+use ark_ff::{BigInteger, Field, PrimeField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use mpc_core::protocols::{rep3::Rep3PrimeFieldShare, shamir::ShamirPrimeFieldShare};
+
+use crate::{SharedInput, SharedWitness};
+
+/// A share type that can be stored in an MP-SPDZ share file.
+pub trait MpSpdzShare<F: PrimeField>: Sized {
+    /// The descriptor of the share type in the file header
+    const DESCRIPTOR: &'static str;
+    /// The number of field elements per share
+    const NUM_ELEMENTS: usize;
+
+    /// Creates a share from the field elements in the order MP-SPDZ stores them.
+    fn from_elements(elements: &[F]) -> Self;
+
+    /// Returns the field elements of the share in the order MP-SPDZ stores them.
+    fn to_elements(&self) -> Vec<F>;
+}
+
+impl<F: PrimeField> MpSpdzShare<F> for Rep3PrimeFieldShare<F> {
+    const DESCRIPTOR: &'static str = "replicated gfp";
+    const NUM_ELEMENTS: usize = 2;
+
+    fn from_elements(elements: &[F]) -> Self {
+        Rep3PrimeFieldShare::new(elements[1], elements[0])
+    }
+
+    fn to_elements(&self) -> Vec<F> {
+        vec![self.b, self.a]
+    }
+}
+
+impl<F: PrimeField> MpSpdzShare<F> for ShamirPrimeFieldShare<F> {
+    const DESCRIPTOR: &'static str = "Shamir gfp";
+    const NUM_ELEMENTS: usize = 1;
+
+    fn from_elements(elements: &[F]) -> Self {
+        ShamirPrimeFieldShare::new(elements[0])
+    }
+
+    fn to_elements(&self) -> Vec<F> {
+        vec![self.inner()]
+    }
+}
+
+/// The number of bytes of a field element in an MP-SPDZ file.
+fn element_size<F: PrimeField>() -> usize {
+    (F::MODULUS_BIT_SIZE as usize).div_ceil(64) * 8
+}
+
+/// The Montgomery factor `R = 2^(64 * L)` of MP-SPDZ.
+fn montgomery_factor<F: PrimeField>() -> F {
+    F::from(2u64).pow([element_size::<F>() as u64 * 8])
+}
+
+fn write_header<F: PrimeField, W: Write>(descriptor: &str, writer: &mut W) -> eyre::Result<()> {
+    let prime = F::MODULUS.to_bytes_be();
+    let prime = &prime[prime.iter().take_while(|b| **b == 0).count()..];
+    let mut header = Vec::new();
+    header.extend((descriptor.len() as u32).to_be_bytes());
+    header.extend(descriptor.as_bytes());
+    header.push(0);
+    header.extend((prime.len() as u32).to_be_bytes());
+    header.extend(prime);
+    writer.write_all(&(header.len() as u64).to_le_bytes())?;
+    writer.write_all(&header)?;
+    Ok(())
+}
+
+/// Splits the first `n` bytes off the header.
+fn take<'a>(rest: &mut &'a [u8], n: usize) -> eyre::Result<&'a [u8]> {
+    if rest.len() < n {
+        eyre::bail!("MP-SPDZ header is truncated");
+    }
+    let (taken, remaining) = rest.split_at(n);
+    *rest = remaining;
+    Ok(taken)
+}
+
+/// Reads the header and checks that the file stores shares of type `S` over the field `F`.
+fn read_header<F: PrimeField, S: MpSpdzShare<F>, R: Read>(reader: &mut R) -> eyre::Result<()> {
+    let mut len = [0u8; 8];
+    reader.read_exact(&mut len)?;
+    let len = u64::from_le_bytes(len);
+    if len > 1 << 16 {
+        eyre::bail!("MP-SPDZ header of {len} bytes is too large");
+    }
+    let mut header = vec![0u8; len as usize];
+    reader.read_exact(&mut header)?;
+
+    let mut rest = header.as_slice();
+    let descriptor_len = u32::from_be_bytes(take(&mut rest, 4)?.try_into()?) as usize;
+    let descriptor = String::from_utf8_lossy(take(&mut rest, descriptor_len)?).into_owned();
+    let sign = take(&mut rest, 1)?[0];
+    let prime_len = u32::from_be_bytes(take(&mut rest, 4)?.try_into()?) as usize;
+    let prime = take(&mut rest, prime_len)?;
+
+    if descriptor != S::DESCRIPTOR {
+        eyre::bail!(
+            "MP-SPDZ file stores \"{descriptor}\" shares, expected \"{}\"",
+            S::DESCRIPTOR
+        );
+    }
+    let expected = F::MODULUS.to_bytes_be();
+    let expected = &expected[expected.iter().take_while(|b| **b == 0).count()..];
+    if sign != 0 || prime != expected {
+        eyre::bail!("MP-SPDZ file stores shares of a different prime field");
+    }
+    Ok(())
+}
+
+/// Reads the shares of an MP-SPDZ share file, see the [module documentation](self).
+///
+/// Returns an error if the header does not match the share type and the field, or if a field element is not canonically encoded.
+pub fn read_shares<F: PrimeField, S: MpSpdzShare<F>, R: Read>(
+    mut reader: R,
+) -> eyre::Result<Vec<S>> {
+    read_header::<F, S, _>(&mut reader)?;
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+
+    let element_size = element_size::<F>();
+    let share_size = element_size * S::NUM_ELEMENTS;
+    if bytes.len() % share_size != 0 {
+        eyre::bail!(
+            "MP-SPDZ file has {} bytes of shares, which is not a multiple of the share size {share_size}",
+            bytes.len()
+        );
+    }
+    let r_inv = montgomery_factor::<F>()
+        .inverse()
+        .expect("Montgomery factor is invertible");
+    bytes
+        .chunks(share_size)
+        .map(|share| {
+            let elements = share
+                .chunks(element_size)
+                .map(|element| {
+                    let montgomery = F::from_le_bytes_mod_order(element);
+                    let mut canonical = montgomery.into_bigint().to_bytes_le();
+                    canonical.resize(element_size, 0);
+                    if canonical != element {
+                        eyre::bail!("MP-SPDZ file contains a field element that is not reduced");
+                    }
+                    Ok(montgomery * r_inv)
+                })
+                .collect::<eyre::Result<Vec<_>>>()?;
+            Ok(S::from_elements(&elements))
+        })
+        .collect()
+}
+
+/// Writes the shares to an MP-SPDZ share file, see the [module documentation](self).
+pub fn write_shares<F: PrimeField, S: MpSpdzShare<F>, W: Write>(
+    shares: &[S],
+    mut writer: W,
+) -> eyre::Result<()> {
+    write_header::<F, _>(S::DESCRIPTOR, &mut writer)?;
+    let element_size = element_size::<F>();
+    let r = montgomery_factor::<F>();
+    for share in shares {
+        for element in share.to_elements() {
+            let mut bytes = (element * r).into_bigint().to_bytes_le();
+            bytes.resize(element_size, 0);
+            writer.write_all(&bytes)?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+impl<F: PrimeField, S> SharedWitness<F, S>
+where
+    S: MpSpdzShare<F> + CanonicalSerialize + CanonicalDeserialize + Clone,
+{
+    /// Creates a shared witness from an MP-SPDZ share file of the witness and the public inputs, including the constant 1 at position 0.
+    pub fn from_mp_spdz<R: Read>(reader: R, public_inputs: Vec<F>) -> eyre::Result<Self> {
+        if public_inputs.first() != Some(&F::one()) {
+            eyre::bail!("The public inputs must start with the constant 1");
+        }
+        Ok(Self {
+            public_inputs,
+            witness: read_shares(reader)?,
+        })
+    }
+
+    /// Writes the shared witness to an MP-SPDZ share file. The public inputs are not part of the file.
+    pub fn to_mp_spdz<W: Write>(&self, writer: W) -> eyre::Result<()> {
+        write_shares(&self.witness, writer)
+    }
+}
+
+impl<F: PrimeField, S> SharedInput<F, S>
+where
+    S: MpSpdzShare<F> + CanonicalSerialize + CanonicalDeserialize + Clone,
+{
+    /// Creates a shared input from an MP-SPDZ share file, which stores the shared inputs in the order of the `layout` of input names and lengths.
+    pub fn from_mp_spdz<R: Read>(reader: R, layout: &[(String, usize)]) -> eyre::Result<Self> {
+        let shares = read_shares(reader)?;
+        let expected = layout.iter().map(|(_, len)| len).sum::<usize>();
+        if shares.len() != expected {
+            eyre::bail!(
+                "MP-SPDZ file stores {} shares, but the layout expects {expected}",
+                shares.len()
+            );
+        }
+        let mut shares = shares.into_iter();
+        let mut shared_inputs = BTreeMap::new();
+        for (name, len) in layout {
+            let elements = shares.by_ref().take(*len).collect();
+            if shared_inputs.insert(name.to_owned(), elements).is_some() {
+                eyre::bail!("Input \"{name}\" is part of the layout multiple times");
+            }
+        }
+        Ok(Self {
+            shared_inputs,
+            ..Default::default()
+        })
+    }
+
+    /// Writes the shared inputs to an MP-SPDZ share file in the order of their names and returns the layout of the file, see [SharedInput::from_mp_spdz]. Public inputs are not part of the file.
+    pub fn to_mp_spdz<W: Write>(&self, writer: W) -> eyre::Result<Vec<(String, usize)>> {
+        let layout = self
+            .shared_inputs
+            .iter()
+            .map(|(name, shares)| (name.to_owned(), shares.len()))
+            .collect();
+        let shares = self
+            .shared_inputs
+            .values()
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>();
+        write_shares(&shares, writer)?;
+        Ok(layout)
+    }
+}
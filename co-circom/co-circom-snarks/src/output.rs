@@ -0,0 +1,296 @@
+use std::{collections::BTreeMap, path::PathBuf, process::ExitCode, time::Duration};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// The version of the schema of [CommandReport]. It is incremented on every change of the schema that is not backwards compatible, i.e., on removing or renaming fields, but not on adding fields.
+pub const OUTPUT_SCHEMA_VERSION: u32 = 1;
+
+/// Error type for config parsing and merging
+#[derive(thiserror::Error, Debug)]
+#[error(transparent)]
+pub struct ConfigError(#[from] figment::error::Error);
+
+/// The format of the output of a command.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable log lines on stdout
+    #[default]
+    Text,
+    /// A single [CommandReport] as JSON object on stdout, the log lines are written to stderr
+    Json,
+}
+
+/// The results of a command that finished, i.e., the files it wrote and further values like the validity of a proof.
+#[derive(Debug, Default, Serialize)]
+pub struct CommandOutput {
+    #[serde(skip)]
+    failed: bool,
+    /// The paths of the written files by their kind, e.g., `proof` or `witness_share`
+    pub files: BTreeMap<String, Vec<PathBuf>>,
+    /// Further results by their name, e.g., `valid` for `verify`
+    pub values: BTreeMap<String, serde_json::Value>,
+}
+
+impl CommandOutput {
+    /// The output of a command that succeeded.
+    pub fn success() -> Self {
+        Self::default()
+    }
+
+    /// The output of a command that finished without an error but with a negative result, e.g., `verify` for an invalid proof.
+    pub fn failure() -> Self {
+        Self {
+            failed: true,
+            ..Default::default()
+        }
+    }
+
+    /// Records a file of the provided kind that was written by the command.
+    pub fn with_file(mut self, kind: &str, path: impl Into<PathBuf>) -> Self {
+        self.add_file(kind, path);
+        self
+    }
+
+    /// Records a file of the provided kind that was written by the command.
+    pub fn add_file(&mut self, kind: &str, path: impl Into<PathBuf>) {
+        self.files
+            .entry(kind.to_owned())
+            .or_default()
+            .push(path.into());
+    }
+
+    /// Records a further result of the command. Values that can not be represented as JSON are recorded as `null`.
+    pub fn with_value(mut self, name: &str, value: impl Serialize) -> Self {
+        self.values.insert(
+            name.to_owned(),
+            serde_json::to_value(value).unwrap_or_default(),
+        );
+        self
+    }
+
+    /// The exit code of the process for this output.
+    pub fn exit_code(&self) -> ExitCode {
+        if self.failed {
+            ExitCode::FAILURE
+        } else {
+            ExitCode::SUCCESS
+        }
+    }
+}
+
+/// Whether a command succeeded, finished with a negative result, or failed with an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandStatus {
+    /// The command succeeded
+    Success,
+    /// The command finished with a negative result, e.g., an invalid proof
+    Failure,
+    /// The command failed with an error
+    Error,
+}
+
+/// The category of the error a command failed with, which orchestration systems can use to decide, e.g., whether to retry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// The config file, environment variables, or arguments are invalid
+    Config,
+    /// Reading or writing a file or a network connection failed
+    Io,
+    /// A JSON file could not be parsed
+    Parse,
+    /// Any other error, e.g., a malformed share file or a failed MPC protocol
+    Other,
+}
+
+impl ErrorCategory {
+    /// Categorizes an error by the errors in its chain of causes.
+    pub fn of(err: &eyre::Report) -> Self {
+        if err.chain().any(|e| e.is::<ConfigError>()) {
+            Self::Config
+        } else if err.chain().any(|e| e.is::<serde_json::Error>()) {
+            Self::Parse
+        } else if err.chain().any(|e| e.is::<std::io::Error>()) {
+            Self::Io
+        } else {
+            Self::Other
+        }
+    }
+}
+
+/// The error a command failed with.
+#[derive(Debug, Serialize)]
+pub struct CommandError {
+    /// The category of the error
+    pub category: ErrorCategory,
+    /// The error message including its chain of causes
+    pub message: String,
+}
+
+/// The structured output of a command in the [OutputFormat::Json] format.
+#[derive(Debug, Serialize)]
+pub struct CommandReport {
+    /// The version of the schema, see [OUTPUT_SCHEMA_VERSION]
+    pub schema_version: u32,
+    /// The name of the command, e.g., `generate-proof`
+    pub command: String,
+    /// Whether the command succeeded
+    pub status: CommandStatus,
+    /// The time the command took in milliseconds
+    pub duration_ms: f64,
+    /// The files written by the command and its further results, empty on errors
+    #[serde(flatten)]
+    pub output: CommandOutput,
+    /// The error the command failed with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<CommandError>,
+}
+
+impl CommandReport {
+    /// Builds the report of the command with the provided name from its result.
+    pub fn new(command: &str, duration: Duration, result: eyre::Result<CommandOutput>) -> Self {
+        let (status, output, error) = match result {
+            Ok(output) if output.failed => (CommandStatus::Failure, output, None),
+            Ok(output) => (CommandStatus::Success, output, None),
+            Err(err) => (
+                CommandStatus::Error,
+                CommandOutput::default(),
+                Some(CommandError {
+                    category: ErrorCategory::of(&err),
+                    message: format!("{err:#}"),
+                }),
+            ),
+        };
+        Self {
+            schema_version: OUTPUT_SCHEMA_VERSION,
+            command: command.to_owned(),
+            status,
+            duration_ms: duration.as_micros() as f64 / 1000.,
+            output,
+            error,
+        }
+    }
+
+    /// The exit code of the process for this report.
+    pub fn exit_code(&self) -> ExitCode {
+        match self.status {
+            CommandStatus::Success => ExitCode::SUCCESS,
+            CommandStatus::Failure | CommandStatus::Error => ExitCode::FAILURE,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use eyre::WrapErr;
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Config {
+        #[allow(dead_code)]
+        path: PathBuf,
+    }
+
+    fn config_error() -> eyre::Report {
+        let err = figment::Figment::new()
+            .extract::<Config>()
+            .map_err(ConfigError::from)
+            .unwrap_err();
+        eyre::Report::new(err).wrap_err("while parsing config")
+    }
+
+    #[test]
+    fn success_report() {
+        let output = CommandOutput::success()
+            .with_file("proof", "proof.json")
+            .with_file("proof", "proof.1.json")
+            .with_value("valid", true);
+        let report = CommandReport::new("verify", Duration::from_micros(1500), Ok(output));
+        assert_eq!(report.status, CommandStatus::Success);
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            json!({
+                "schema_version": OUTPUT_SCHEMA_VERSION,
+                "command": "verify",
+                "status": "success",
+                "duration_ms": 1.5,
+                "files": {"proof": ["proof.json", "proof.1.json"]},
+                "values": {"valid": true},
+            })
+        );
+    }
+
+    #[test]
+    fn failure_report() {
+        let output = CommandOutput::failure().with_value("valid", false);
+        let report = CommandReport::new("verify", Duration::ZERO, Ok(output));
+        assert_eq!(report.status, CommandStatus::Failure);
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["status"], "failure");
+        assert_eq!(json["values"], json!({"valid": false}));
+        assert!(json.get("error").is_none());
+    }
+
+    #[test]
+    fn error_report() {
+        let err = eyre::eyre!("share file is malformed").wrap_err("while reading shares");
+        let report = CommandReport::new("generate-proof", Duration::ZERO, Err(err));
+        assert_eq!(report.status, CommandStatus::Error);
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            json!({
+                "schema_version": OUTPUT_SCHEMA_VERSION,
+                "command": "generate-proof",
+                "status": "error",
+                "duration_ms": 0.0,
+                "files": {},
+                "values": {},
+                "error": {
+                    "category": "other",
+                    "message": "while reading shares: share file is malformed",
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn error_categories() {
+        assert_eq!(ErrorCategory::of(&config_error()), ErrorCategory::Config);
+        let parse = serde_json::from_str::<u32>("x")
+            .wrap_err("while parsing proof")
+            .unwrap_err();
+        assert_eq!(ErrorCategory::of(&parse), ErrorCategory::Parse);
+        let io = Err::<(), _>(std::io::Error::from(std::io::ErrorKind::NotFound))
+            .wrap_err("while opening proof")
+            .unwrap_err();
+        assert_eq!(ErrorCategory::of(&io), ErrorCategory::Io);
+        assert_eq!(
+            ErrorCategory::of(&eyre::eyre!("invalid share")),
+            ErrorCategory::Other
+        );
+    }
+
+    #[test]
+    fn values_that_are_not_json_are_null() {
+        let output = CommandOutput::success()
+            .with_value("map", BTreeMap::from([((1, 2), "non-string key")]));
+        assert_eq!(output.values["map"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn output_format_from_arg() {
+        assert_eq!(
+            OutputFormat::from_str("json", true).unwrap(),
+            OutputFormat::Json
+        );
+        assert_eq!(
+            OutputFormat::from_str("text", true).unwrap(),
+            OutputFormat::Text
+        );
+        assert!(OutputFormat::from_str("yaml", true).is_err());
+    }
+}
@@ -0,0 +1,353 @@
+use std::{
+    fmt,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// A usage policy that a data owner embeds into its share files to scope for which circuits, for which proof systems, and how long the shares may be used. The policy is enforced when the shares are loaded for the witness generation and the proof generation.
+///
+/// The policy is written as a string of `;`-separated clauses, e.g., `circuits=<hash>,<hash>;expires=1767225600;proof-systems=groth16`, where the circuit hashes are the hex-encoded hashes printed by `compile` and `expires` is a UNIX timestamp in seconds. Omitted clauses do not restrict the usage.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct UsagePolicy {
+    /// The lowercase hex-encoded hashes of the compiled circuits the shares may be used for. All circuits are allowed if empty.
+    pub allowed_circuits: Vec<String>,
+    /// The UNIX timestamp in seconds after which the shares may no longer be used
+    pub expires_at: Option<u64>,
+    /// The lowercase names of the proof systems the shares may be used for. All proof systems are allowed if empty.
+    pub allowed_proof_systems: Vec<String>,
+}
+
+impl UsagePolicy {
+    /// Checks that the shares may be used for the circuit with the provided hex-encoded hash.
+    pub fn check_circuit(&self, circuit_hash: &str) -> eyre::Result<()> {
+        if !self.allowed_circuits.is_empty()
+            && !self
+                .allowed_circuits
+                .iter()
+                .any(|c| c.eq_ignore_ascii_case(circuit_hash))
+        {
+            eyre::bail!("the usage policy of the shares does not allow circuit {circuit_hash}");
+        }
+        Ok(())
+    }
+
+    /// Checks that the shares may be used for the proof system with the provided name.
+    pub fn check_proof_system(&self, proof_system: &str) -> eyre::Result<()> {
+        if !self.allowed_proof_systems.is_empty()
+            && !self
+                .allowed_proof_systems
+                .iter()
+                .any(|p| p.eq_ignore_ascii_case(proof_system))
+        {
+            eyre::bail!(
+                "the usage policy of the shares does not allow proof system {proof_system}"
+            );
+        }
+        Ok(())
+    }
+
+    /// Checks that the shares are not expired at the provided UNIX timestamp in seconds.
+    pub fn check_expiry(&self, now: u64) -> eyre::Result<()> {
+        match self.expires_at {
+            Some(expires_at) if now > expires_at => {
+                eyre::bail!("the usage policy of the shares expired at {expires_at}")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that the shares are not expired at the current system time.
+    pub fn check_not_expired(&self) -> eyre::Result<()> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| eyre::eyre!("system time is before the UNIX epoch"))?
+            .as_secs();
+        self.check_expiry(now)
+    }
+
+    /// Combines the policies of two share files that are merged into one, e.g., the input shares of two data owners. The combined policy only allows what both policies allow.
+    pub fn merge(policy: Option<Self>, other: Option<Self>) -> eyre::Result<Option<Self>> {
+        let (policy, other) = match (policy, other) {
+            (None, None) => return Ok(None),
+            (Some(policy), None) | (None, Some(policy)) => return Ok(Some(policy)),
+            (Some(policy), Some(other)) => (policy, other),
+        };
+        let allowed_circuits = intersect(policy.allowed_circuits, other.allowed_circuits)
+            .ok_or_else(|| eyre::eyre!("the usage policies do not allow a common circuit"))?;
+        let allowed_proof_systems =
+            intersect(policy.allowed_proof_systems, other.allowed_proof_systems).ok_or_else(
+                || eyre::eyre!("the usage policies do not allow a common proof system"),
+            )?;
+        let expires_at = match (policy.expires_at, other.expires_at) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        Ok(Some(Self {
+            allowed_circuits,
+            expires_at,
+            allowed_proof_systems,
+        }))
+    }
+}
+
+/// Intersects two allow-lists, where an empty list allows everything. Returns `None` if the intersection of two non-empty lists is empty.
+fn intersect(list: Vec<String>, other: Vec<String>) -> Option<Vec<String>> {
+    if list.is_empty() {
+        return Some(other);
+    }
+    if other.is_empty() {
+        return Some(list);
+    }
+    let intersection = list
+        .into_iter()
+        .filter(|x| other.contains(x))
+        .collect::<Vec<_>>();
+    if intersection.is_empty() {
+        None
+    } else {
+        Some(intersection)
+    }
+}
+
+impl fmt::Display for UsagePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut clauses = Vec::new();
+        if !self.allowed_circuits.is_empty() {
+            clauses.push(format!("circuits={}", self.allowed_circuits.join(",")));
+        }
+        if let Some(expires_at) = self.expires_at {
+            clauses.push(format!("expires={expires_at}"));
+        }
+        if !self.allowed_proof_systems.is_empty() {
+            clauses.push(format!(
+                "proof-systems={}",
+                self.allowed_proof_systems.join(",")
+            ));
+        }
+        write!(f, "{}", clauses.join(";"))
+    }
+}
+
+impl FromStr for UsagePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut policy = UsagePolicy::default();
+        let mut seen = Vec::new();
+        for clause in s.split(';').map(str::trim).filter(|c| !c.is_empty()) {
+            let (key, value) = clause
+                .split_once('=')
+                .ok_or_else(|| format!("expected a clause of the form key=value, got {clause}"))?;
+            let key = key.trim();
+            if seen.contains(&key) {
+                return Err(format!("clause {key} is present multiple times"));
+            }
+            seen.push(key);
+            let values = value
+                .split(',')
+                .map(|v| v.trim().to_ascii_lowercase())
+                .filter(|v| !v.is_empty())
+                .collect::<Vec<_>>();
+            match key {
+                "circuits" => {
+                    if let Some(hash) = values
+                        .iter()
+                        .find(|h| h.len() != 64 || !h.chars().all(|c| c.is_ascii_hexdigit()))
+                    {
+                        return Err(format!("invalid circuit hash {hash}"));
+                    }
+                    policy.allowed_circuits = values;
+                }
+                "expires" => {
+                    policy.expires_at = Some(
+                        value
+                            .trim()
+                            .parse()
+                            .map_err(|_| format!("invalid expiry timestamp {value}"))?,
+                    );
+                }
+                "proof-systems" => policy.allowed_proof_systems = values,
+                _ => return Err(format!("unknown clause {key}")),
+            }
+        }
+        Ok(policy)
+    }
+}
+
+impl TryFrom<String> for UsagePolicy {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<UsagePolicy> for String {
+    fn from(value: UsagePolicy) -> Self {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(byte: char) -> String {
+        byte.to_string().repeat(64)
+    }
+
+    fn policy(circuits: &[String], expires_at: Option<u64>, proof_systems: &[&str]) -> UsagePolicy {
+        UsagePolicy {
+            allowed_circuits: circuits.to_vec(),
+            expires_at,
+            allowed_proof_systems: proof_systems.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn parse_display_roundtrip() {
+        let full = policy(
+            &[hash('a'), hash('b')],
+            Some(1767225600),
+            &["groth16", "plonk"],
+        );
+        for policy in [
+            full.clone(),
+            policy(&[], None, &[]),
+            policy(&[hash('c')], None, &[]),
+            policy(&[], Some(0), &["plonk"]),
+        ] {
+            let parsed = policy.to_string().parse::<UsagePolicy>().unwrap();
+            assert_eq!(parsed, policy);
+            assert_eq!(
+                UsagePolicy::try_from(String::from(policy.clone())),
+                Ok(policy)
+            );
+        }
+        assert_eq!(
+            full.to_string(),
+            format!(
+                "circuits={},{};expires=1767225600;proof-systems=groth16,plonk",
+                hash('a'),
+                hash('b')
+            )
+        );
+    }
+
+    #[test]
+    fn parse_normalizes_clauses() {
+        let parsed = format!(
+            " proof-systems = Groth16 ,, ; circuits={} ;; expires= 42 ",
+            hash('A')
+        )
+        .parse::<UsagePolicy>()
+        .unwrap();
+        assert_eq!(parsed, policy(&[hash('a')], Some(42), &["groth16"]));
+        assert_eq!("".parse::<UsagePolicy>(), Ok(UsagePolicy::default()));
+    }
+
+    #[test]
+    fn parse_rejects_invalid_clauses() {
+        let invalid_hash = format!("circuits={}", hash('g'));
+        let too_long_hash = format!("circuits={}0", hash('a'));
+        for invalid in [
+            "expires=1;expires=2",
+            "proof-systems=groth16;proof-systems=plonk",
+            "owner=alice",
+            "expires",
+            "expires=tomorrow",
+            "expires=-1",
+            "circuits=abc",
+            invalid_hash.as_str(),
+            too_long_hash.as_str(),
+        ] {
+            assert!(invalid.parse::<UsagePolicy>().is_err(), "{invalid}");
+        }
+        assert_eq!(
+            "expires=1;expires=2".parse::<UsagePolicy>(),
+            Err("clause expires is present multiple times".to_owned())
+        );
+        assert_eq!(
+            "owner=alice".parse::<UsagePolicy>(),
+            Err("unknown clause owner".to_owned())
+        );
+    }
+
+    #[test]
+    fn checks_allow_lists() {
+        let restricted = policy(&[hash('a')], None, &["groth16"]);
+        assert!(restricted.check_circuit(&hash('a')).is_ok());
+        assert!(restricted.check_circuit(&hash('A')).is_ok());
+        assert!(restricted.check_circuit(&hash('b')).is_err());
+        assert!(restricted.check_proof_system("Groth16").is_ok());
+        assert!(restricted.check_proof_system("plonk").is_err());
+
+        let unrestricted = UsagePolicy::default();
+        assert!(unrestricted.check_circuit(&hash('b')).is_ok());
+        assert!(unrestricted.check_proof_system("plonk").is_ok());
+        assert!(unrestricted.check_expiry(u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn expiry_at_the_boundary() {
+        let policy = policy(&[], Some(1000), &[]);
+        assert!(policy.check_expiry(0).is_ok());
+        assert!(policy.check_expiry(999).is_ok());
+        // the shares may still be used at the expiry timestamp itself
+        assert!(policy.check_expiry(1000).is_ok());
+        assert!(policy.check_expiry(1001).is_err());
+        assert!(UsagePolicy {
+            expires_at: Some(0),
+            ..Default::default()
+        }
+        .check_not_expired()
+        .is_err());
+        assert!(UsagePolicy {
+            expires_at: Some(u64::MAX),
+            ..Default::default()
+        }
+        .check_not_expired()
+        .is_ok());
+    }
+
+    #[test]
+    fn merge_intersects_policies() {
+        let a = policy(&[hash('a'), hash('b')], Some(2000), &[]);
+        let b = policy(&[hash('b'), hash('c')], Some(1000), &["plonk"]);
+        assert_eq!(
+            UsagePolicy::merge(Some(a.clone()), Some(b.clone())).unwrap(),
+            Some(policy(&[hash('b')], Some(1000), &["plonk"]))
+        );
+        // merging is symmetric
+        assert_eq!(
+            UsagePolicy::merge(Some(b.clone()), Some(a.clone())).unwrap(),
+            Some(policy(&[hash('b')], Some(1000), &["plonk"]))
+        );
+        assert_eq!(
+            UsagePolicy::merge(Some(a.clone()), None).unwrap(),
+            Some(a.clone())
+        );
+        assert_eq!(UsagePolicy::merge(None, Some(b.clone())).unwrap(), Some(b));
+        assert_eq!(UsagePolicy::merge(None, None).unwrap(), None);
+        // a missing expiry does not lift the expiry of the other policy
+        let unrestricted = UsagePolicy::default();
+        assert_eq!(
+            UsagePolicy::merge(Some(unrestricted), Some(a.clone())).unwrap(),
+            Some(a)
+        );
+    }
+
+    #[test]
+    fn merge_with_empty_intersection_fails() {
+        let groth16 = policy(&[], None, &["groth16"]);
+        let plonk = policy(&[], None, &["plonk"]);
+        assert!(UsagePolicy::merge(Some(groth16), Some(plonk)).is_err());
+
+        let circuit_a = policy(&[hash('a')], None, &[]);
+        let circuit_b = policy(&[hash('b')], None, &[]);
+        assert!(UsagePolicy::merge(Some(circuit_a), Some(circuit_b)).is_err());
+    }
+}
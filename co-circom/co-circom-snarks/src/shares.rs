@@ -0,0 +1,879 @@
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use circom_types::Witness;
+use mpc_core::protocols::{
+    rep3::{
+        self, network::Rep3Network, MaybeRep3ShareVecType, Rep3PrimeFieldShare, Rep3ShareVecType,
+    },
+    shamir::{self, ShamirPrimeFieldShare},
+};
+use rand::{distributions::Standard, prelude::Distribution, CryptoRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+use crate::{commitment::CommitmentOpening, UsagePolicy};
+
+/// This type represents the serialized version of a Rep3 witness. Its share can be either additive or replicated, and in both cases also compressed.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SerializeableSharedRep3Witness<F: PrimeField, U: Rng + SeedableRng + CryptoRng>
+where
+    U::Seed: Serialize + for<'a> Deserialize<'a> + Clone + std::fmt::Debug,
+{
+    /// The public inputs (which are the outputs of the circom circuit).
+    /// This also includes the constant 1 at position 0.
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    pub public_inputs: Vec<F>,
+    /// The secret-shared witness elements.
+    pub witness: Rep3ShareVecType<F, U>,
+    /// The usage policy of the data owner, enforced when the shares are loaded.
+    #[serde(default)]
+    pub usage_policy: Option<UsagePolicy>,
+}
+
+impl<F: PrimeField, U: Rng + SeedableRng + CryptoRng> SerializeableSharedRep3Witness<F, U>
+where
+    U::Seed: Serialize + for<'a> Deserialize<'a> + Clone + std::fmt::Debug,
+{
+    /// Transforms a shared witness into a serializable version.
+    pub fn from_shared_witness(inp: SharedWitness<F, Rep3PrimeFieldShare<F>>) -> Self {
+        Self {
+            public_inputs: inp.public_inputs,
+            witness: Rep3ShareVecType::Replicated(inp.witness),
+            usage_policy: None,
+        }
+    }
+}
+
+//TODO THE SECRETSHARED TRAIT IS REALLY BAD. WE DO WANT SOMETHING ELSE!
+/// A shared witness in the circom ecosystem.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SharedWitness<F: PrimeField, S>
+where
+    S: CanonicalSerialize + CanonicalDeserialize + Clone,
+{
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    /// The public inputs (which are the outputs of the circom circuit).
+    /// This also includes the constant 1 at position 0.
+    pub public_inputs: Vec<F>,
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    /// The secret-shared witness elements.
+    pub witness: Vec<S>,
+}
+
+/// A shared witness in the circom ecosystem in sparse representation. Only the shares of the nonzero witness elements are stored together with their indices, all other witness elements are zero and implicitly shared with the all-zero share, i.e., the [Default] share. Thus, the size of the shares scales with the number of nonzero witness elements.
+///
+/// # Security
+/// The sparse representation reveals to the computing parties which witness elements are zero. Only use it if the positions of the zeros are not secret, e.g., if they are determined by the structure of the circuit.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SparseSharedWitness<F: PrimeField, S>
+where
+    S: CanonicalSerialize + CanonicalDeserialize + Clone,
+{
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    /// The public inputs (which are the outputs of the circom circuit).
+    /// This also includes the constant 1 at position 0.
+    pub public_inputs: Vec<F>,
+    /// The number of witness elements, including the zero elements.
+    pub num_witness: usize,
+    /// The strictly increasing indices of the nonzero witness elements.
+    pub indices: Vec<usize>,
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    /// The secret-shared nonzero witness elements.
+    pub values: Vec<S>,
+}
+
+/// This type represents the serialized version of a Rep3 witness. Its share can be either additive or replicated, and in both cases also compressed.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SerializeableSharedRep3Input<F: PrimeField, U: Rng + SeedableRng + CryptoRng>
+where
+    U::Seed: Serialize + for<'a> Deserialize<'a> + Clone + std::fmt::Debug,
+{
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    /// A map from variable names to the public field elements.
+    /// This is a BTreeMap because it implements Canonical(De)Serialize.
+    pub public_inputs: BTreeMap<String, Vec<F>>,
+    /// A map from variable names to the share of the field element.
+    /// This is a BTreeMap because it implements Canonical(De)Serialize.
+    pub shared_inputs: BTreeMap<String, Rep3ShareVecType<F, U>>,
+    /// A map from variable names to vecs with maybe unknown elements that need to be merged.
+    /// This is a BTreeMap because it implements Canonical(De)Serialize.
+    #[serde(default)]
+    pub maybe_shared_inputs: BTreeMap<String, MaybeRep3ShareVecType<F>>,
+    /// A map from variable names to the declared bit widths of the shared inputs. All elements of a shared input with a declared bit width are smaller than 2^bit_width.
+    #[serde(default)]
+    pub bit_widths: BTreeMap<String, usize>,
+    /// The usage policy of the data owner, enforced when the shares are loaded.
+    #[serde(default)]
+    pub usage_policy: Option<UsagePolicy>,
+    /// The shares of the openings of the commitments of the data owners to some of the shared inputs, by the labels of the [InputCommitment](crate::commitment::InputCommitment)s.
+    #[serde(default)]
+    pub commitment_openings: BTreeMap<String, CommitmentOpening<Rep3PrimeFieldShare<F>>>,
+}
+
+impl<F: PrimeField, U: Rng + SeedableRng + CryptoRng> Default for SerializeableSharedRep3Input<F, U>
+where
+    U::Seed: Serialize + for<'a> Deserialize<'a> + Clone + std::fmt::Debug,
+{
+    fn default() -> Self {
+        Self {
+            public_inputs: BTreeMap::new(),
+            shared_inputs: BTreeMap::new(),
+            maybe_shared_inputs: BTreeMap::new(),
+            bit_widths: BTreeMap::new(),
+            usage_policy: None,
+            commitment_openings: BTreeMap::new(),
+        }
+    }
+}
+
+impl<F: PrimeField, U: Rng + SeedableRng + CryptoRng> SerializeableSharedRep3Input<F, U>
+where
+    U::Seed: Serialize + for<'a> Deserialize<'a> + Clone + std::fmt::Debug,
+    Standard: Distribution<U::Seed>,
+{
+    /// Shares a given input into a [Rep3ShareVecType] type.
+    pub fn share_rep3<R: Rng + CryptoRng>(
+        input: &[F],
+        rng: &mut R,
+        seeded: bool,
+        additive: bool,
+    ) -> [Rep3ShareVecType<F, U>; 3] {
+        match (seeded, additive) {
+            (true, true) => {
+                let [share1, share2, share3] =
+                    rep3::share_field_elements_additive_seeded::<_, _, U>(input, rng);
+                let share1 = Rep3ShareVecType::SeededAdditive(share1);
+                let share2 = Rep3ShareVecType::SeededAdditive(share2);
+                let share3 = Rep3ShareVecType::SeededAdditive(share3);
+                [share1, share2, share3]
+            }
+            (true, false) => {
+                let [share1, share2, share3] =
+                    rep3::share_field_elements_seeded::<_, _, U>(input, rng);
+                let share1 = Rep3ShareVecType::SeededReplicated(share1);
+                let share2 = Rep3ShareVecType::SeededReplicated(share2);
+                let share3 = Rep3ShareVecType::SeededReplicated(share3);
+                [share1, share2, share3]
+            }
+            (false, true) => {
+                let [share1, share2, share3] = rep3::share_field_elements_additive(input, rng);
+                let share1 = Rep3ShareVecType::Additive(share1);
+                let share2 = Rep3ShareVecType::Additive(share2);
+                let share3 = Rep3ShareVecType::Additive(share3);
+                [share1, share2, share3]
+            }
+            (false, false) => {
+                let [share1, share2, share3] = rep3::share_field_elements(input, rng);
+                let share1 = Rep3ShareVecType::Replicated(share1);
+                let share2 = Rep3ShareVecType::Replicated(share2);
+                let share3 = Rep3ShareVecType::Replicated(share3);
+                [share1, share2, share3]
+            }
+        }
+    }
+
+    /// Shares a given input with unknown elements into a [MaybeRep3ShareVecType] type.
+    pub fn maybe_share_rep3<R: Rng + CryptoRng>(
+        input: &[Option<F>],
+        rng: &mut R,
+        additive: bool,
+    ) -> [MaybeRep3ShareVecType<F>; 3] {
+        if additive {
+            let [share1, share2, share3] = rep3::share_maybe_field_elements_additive(input, rng);
+            let share1 = MaybeRep3ShareVecType::Additive(share1);
+            let share2 = MaybeRep3ShareVecType::Additive(share2);
+            let share3 = MaybeRep3ShareVecType::Additive(share3);
+            [share1, share2, share3]
+        } else {
+            let [share1, share2, share3] = rep3::share_maybe_field_elements(input, rng);
+            let share1 = MaybeRep3ShareVecType::Replicated(share1);
+            let share2 = MaybeRep3ShareVecType::Replicated(share2);
+            let share3 = MaybeRep3ShareVecType::Replicated(share3);
+            [share1, share2, share3]
+        }
+    }
+
+    /// Merges two [SerializeableSharedRep3Input]s into one, performing basic sanity checks.
+    pub fn merge(self, other: Self) -> eyre::Result<Self> {
+        let mut shared_inputs = self.shared_inputs;
+        let maybe_shared_inputs = self.maybe_shared_inputs;
+        let public_inputs = self.public_inputs;
+        let bit_widths = merge_bit_widths(self.bit_widths, other.bit_widths)?;
+        let usage_policy = UsagePolicy::merge(self.usage_policy, other.usage_policy)?;
+        let commitment_openings =
+            merge_commitment_openings(self.commitment_openings, other.commitment_openings)?;
+
+        for (key, value) in other.public_inputs.iter() {
+            if !public_inputs.contains_key(key) {
+                eyre::bail!("Public input \"{key}\" must be present in all files");
+            }
+            if public_inputs.get(key).expect("is there we checked") != value {
+                eyre::bail!("Public input \"{key}\" must be same in all files");
+            }
+        }
+
+        for (key, value) in other.shared_inputs {
+            if shared_inputs.contains_key(&key) {
+                eyre::bail!("Input with name {} present in multiple input shares", key);
+            }
+            if public_inputs.contains_key(&key) || other.public_inputs.contains_key(&key) {
+                eyre::bail!(
+                    "Input name is once in shared inputs and once in public inputs: \"{key}\""
+                );
+            }
+            shared_inputs.insert(key, value);
+        }
+
+        let mut merged_maybe_shared_inputs = BTreeMap::new();
+        if maybe_shared_inputs.len() != other.maybe_shared_inputs.len() {
+            eyre::bail!("Both inputs must have the same number of unmerged entries");
+        }
+        for ((k1, v1), (k2, v2)) in maybe_shared_inputs
+            .into_iter()
+            .zip(other.maybe_shared_inputs.into_iter())
+        {
+            if k1 != k2 {
+                eyre::bail!("Both inputs must have the same keys for unmerged elements");
+            }
+
+            match (v1, v2) {
+                (
+                    MaybeRep3ShareVecType::Replicated(shares),
+                    MaybeRep3ShareVecType::Replicated(other_shares),
+                ) => {
+                    let merged = shares
+                        .into_iter()
+                        .zip(other_shares.into_iter())
+                        .map(|(a, b)| match (a, b) {
+                            (None, None) => Ok(None),
+                            (a @ Some(_), None) | (None, a @ Some(_)) => Ok(a),
+                            _ => Err(eyre::eyre!("Input {} present in both unmerged inputs", k1)),
+                        })
+                        .collect::<Result<Vec<_>, eyre::Report>>()?;
+                    if let Some(merged) = merged.iter().cloned().collect::<Option<Vec<_>>>() {
+                        shared_inputs.insert(k1.clone(), Rep3ShareVecType::Replicated(merged));
+                    } else {
+                        merged_maybe_shared_inputs
+                            .insert(k1.clone(), MaybeRep3ShareVecType::Replicated(merged));
+                    }
+                }
+                (
+                    MaybeRep3ShareVecType::Additive(shares),
+                    MaybeRep3ShareVecType::Additive(other_shares),
+                ) => {
+                    let merged = shares
+                        .into_iter()
+                        .zip(other_shares.into_iter())
+                        .map(|(a, b)| match (a, b) {
+                            (None, None) => Ok(None),
+                            (a @ Some(_), None) | (None, a @ Some(_)) => Ok(a),
+                            _ => Err(eyre::eyre!("Input {} present in both unmerged inputs", k1)),
+                        })
+                        .collect::<Result<Vec<_>, eyre::Report>>()?;
+                    if let Some(merged) = merged.iter().cloned().collect::<Option<Vec<_>>>() {
+                        shared_inputs.insert(k1.clone(), Rep3ShareVecType::Additive(merged));
+                    } else {
+                        merged_maybe_shared_inputs
+                            .insert(k1.clone(), MaybeRep3ShareVecType::Additive(merged));
+                    }
+                }
+                _ => {
+                    eyre::bail!("Input {} cannot be merged, the share type is different", k1);
+                }
+            }
+        }
+
+        Ok(Self {
+            public_inputs,
+            shared_inputs,
+            maybe_shared_inputs: merged_maybe_shared_inputs,
+            bit_widths,
+            usage_policy,
+            commitment_openings,
+        })
+    }
+}
+
+/// A shared input for a collaborative circom witness extension.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SharedInput<F: PrimeField, S>
+where
+    S: CanonicalSerialize + CanonicalDeserialize + Clone,
+{
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    /// A map from variable names to the public field elements.
+    /// This is a BTreeMap because it implements Canonical(De)Serialize.
+    pub public_inputs: BTreeMap<String, Vec<F>>,
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    /// A map from variable names to the share of the field element.
+    /// This is a BTreeMap because it implements Canonical(De)Serialize.
+    pub shared_inputs: BTreeMap<String, Vec<S>>,
+    /// A map from variable names to the declared bit widths of the shared inputs. All elements of a shared input with a declared bit width are smaller than 2^bit_width, which allows the witness extension to use cheaper protocols for bounded values.
+    #[serde(default)]
+    pub bit_widths: BTreeMap<String, usize>,
+    /// The usage policy of the data owners, enforced by the witness extension and copied to the resulting witness share.
+    #[serde(default)]
+    pub usage_policy: Option<UsagePolicy>,
+    /// The shares of the openings of the commitments of the data owners to some of the shared inputs, by the labels of the commitments. They are carried along, such that the parties can check the inputs against the published commitments, see [InputCommitment](crate::commitment::InputCommitment).
+    #[serde(default)]
+    pub commitment_openings: BTreeMap<String, CommitmentOpening<S>>,
+}
+
+fn merge_commitment_openings<S: CanonicalSerialize + CanonicalDeserialize>(
+    mut openings: BTreeMap<String, CommitmentOpening<S>>,
+    other: BTreeMap<String, CommitmentOpening<S>>,
+) -> eyre::Result<BTreeMap<String, CommitmentOpening<S>>> {
+    for (label, opening) in other {
+        if openings.contains_key(&label) {
+            eyre::bail!("Commitment \"{label}\" is opened in multiple input shares");
+        }
+        openings.insert(label, opening);
+    }
+    Ok(openings)
+}
+
+fn merge_bit_widths(
+    mut bit_widths: BTreeMap<String, usize>,
+    other: BTreeMap<String, usize>,
+) -> eyre::Result<BTreeMap<String, usize>> {
+    for (key, value) in other {
+        match bit_widths.get(&key) {
+            Some(bits) if *bits != value => {
+                eyre::bail!(
+                    "Input \"{key}\" has different declared bit widths: {bits} and {value}"
+                );
+            }
+            _ => {
+                bit_widths.insert(key, value);
+            }
+        }
+    }
+    Ok(bit_widths)
+}
+
+/// We manually implement Clone here since it was not derived correctly and it added bounds on T, P which are not needed
+impl<F: PrimeField, S> Clone for SharedWitness<F, S>
+where
+    S: CanonicalSerialize + CanonicalDeserialize + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            public_inputs: self.public_inputs.clone(),
+            witness: self.witness.clone(),
+        }
+    }
+}
+
+/// We manually implement Clone here since it was not derived correctly and it added bounds on T, P which are not needed
+impl<F: PrimeField, S> Clone for SparseSharedWitness<F, S>
+where
+    S: CanonicalSerialize + CanonicalDeserialize + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            public_inputs: self.public_inputs.clone(),
+            num_witness: self.num_witness,
+            indices: self.indices.clone(),
+            values: self.values.clone(),
+        }
+    }
+}
+
+impl<F: PrimeField, S> SparseSharedWitness<F, S>
+where
+    S: CanonicalSerialize + CanonicalDeserialize + Clone + Default,
+{
+    /// Checks that the indices are strictly increasing, in range, and match the amount of shared values.
+    pub fn validate(&self) -> eyre::Result<()> {
+        if self.indices.len() != self.values.len() {
+            eyre::bail!(
+                "sparse witness has {} indices but {} values",
+                self.indices.len(),
+                self.values.len()
+            );
+        }
+        if self.indices.windows(2).any(|w| w[0] >= w[1]) {
+            eyre::bail!("indices of the sparse witness are not strictly increasing");
+        }
+        if let Some(last) = self.indices.last() {
+            if *last >= self.num_witness {
+                eyre::bail!(
+                    "index {last} of the sparse witness is out of range for {} witness elements",
+                    self.num_witness
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Expands the sparse witness into the dense [SharedWitness], filling in the all-zero share for the zero elements. Fails if the sparse witness is not valid, see [SparseSharedWitness::validate].
+    pub fn to_dense(&self) -> eyre::Result<SharedWitness<F, S>> {
+        self.validate()?;
+        let mut witness = vec![S::default(); self.num_witness];
+        for (index, value) in self.indices.iter().zip(self.values.iter()) {
+            witness[*index] = value.clone();
+        }
+        Ok(SharedWitness {
+            public_inputs: self.public_inputs.clone(),
+            witness,
+        })
+    }
+}
+
+/// Splits the witness into the public inputs, the nonzero private witness elements, and their indices.
+fn split_sparse<F: PrimeField>(
+    witness: &Witness<F>,
+    num_pub_inputs: usize,
+) -> (&[F], Vec<usize>, Vec<F>) {
+    let public_inputs = &witness.values[..num_pub_inputs];
+    let (indices, values) = witness.values[num_pub_inputs..]
+        .iter()
+        .enumerate()
+        .filter(|(_, value)| !value.is_zero())
+        .map(|(index, value)| (index, *value))
+        .unzip();
+    (public_inputs, indices, values)
+}
+
+impl<F: PrimeField> SparseSharedWitness<F, Rep3PrimeFieldShare<F>> {
+    /// Shares the nonzero elements of a given witness and the public input vector using the rep3 protocol.
+    pub fn share_rep3<R: Rng + CryptoRng>(
+        witness: Witness<F>,
+        num_pub_inputs: usize,
+        rng: &mut R,
+    ) -> [Self; 3] {
+        let num_witness = witness.values.len() - num_pub_inputs;
+        let (public_inputs, indices, values) = split_sparse(&witness, num_pub_inputs);
+        rep3::share_field_elements(&values, rng).map(|share| Self {
+            public_inputs: public_inputs.to_vec(),
+            num_witness,
+            indices: indices.clone(),
+            values: share,
+        })
+    }
+}
+
+impl<F: PrimeField> SparseSharedWitness<F, ShamirPrimeFieldShare<F>> {
+    /// Shares the nonzero elements of a given witness and the public input vector using the Shamir protocol.
+    pub fn share_shamir<R: Rng + CryptoRng>(
+        witness: Witness<F>,
+        num_pub_inputs: usize,
+        degree: usize,
+        num_parties: usize,
+        rng: &mut R,
+    ) -> Vec<Self> {
+        let num_witness = witness.values.len() - num_pub_inputs;
+        let (public_inputs, indices, values) = split_sparse(&witness, num_pub_inputs);
+        shamir::share_field_elements(&values, degree, num_parties, rng)
+            .into_iter()
+            .map(|share| Self {
+                public_inputs: public_inputs.to_vec(),
+                num_witness,
+                indices: indices.clone(),
+                values: share,
+            })
+            .collect()
+    }
+}
+
+/// We manually implement Clone here since it was not derived correctly and it added bounds on T, P which are not needed
+impl<F: PrimeField, S> Clone for SharedInput<F, S>
+where
+    S: CanonicalSerialize + CanonicalDeserialize + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            public_inputs: self.public_inputs.clone(),
+            shared_inputs: self.shared_inputs.clone(),
+            bit_widths: self.bit_widths.clone(),
+            usage_policy: self.usage_policy.clone(),
+            commitment_openings: self.commitment_openings.clone(),
+        }
+    }
+}
+
+impl<F: PrimeField, S> Default for SharedInput<F, S>
+where
+    S: CanonicalSerialize + CanonicalDeserialize + Clone,
+{
+    fn default() -> Self {
+        Self {
+            public_inputs: BTreeMap::new(),
+            shared_inputs: BTreeMap::new(),
+            bit_widths: BTreeMap::new(),
+            usage_policy: None,
+            commitment_openings: BTreeMap::new(),
+        }
+    }
+}
+
+impl<F: PrimeField, S> SharedInput<F, S>
+where
+    S: CanonicalSerialize + CanonicalDeserialize + Clone,
+{
+    /// Adds a public input with a given name to the [SharedInput].
+    pub fn add_public_input(&mut self, key: String, elements: Vec<F>) {
+        self.public_inputs.insert(key, elements);
+    }
+
+    /// Adds a shared input with a given name to the [SharedInput].
+    pub fn add_shared_input(&mut self, key: String, elements: Vec<S>) {
+        self.shared_inputs.insert(key, elements);
+    }
+
+    /// Declares the bit width of the shared input with the given name. The caller is responsible that all elements of the input are smaller than 2^bits.
+    pub fn add_bit_width(&mut self, key: String, bits: usize) {
+        self.bit_widths.insert(key, bits);
+    }
+
+    /// Merges two [SharedInput]s into one, performing basic sanity checks.
+    pub fn merge(self, other: Self) -> eyre::Result<Self> {
+        let mut shared_inputs = self.shared_inputs;
+        let public_inputs = self.public_inputs;
+        let bit_widths = merge_bit_widths(self.bit_widths, other.bit_widths)?;
+        let usage_policy = UsagePolicy::merge(self.usage_policy, other.usage_policy)?;
+        let commitment_openings =
+            merge_commitment_openings(self.commitment_openings, other.commitment_openings)?;
+        for (key, value) in other.shared_inputs {
+            if shared_inputs.contains_key(&key) {
+                eyre::bail!("Input with name {} present in multiple input shares", key);
+            }
+            if public_inputs.contains_key(&key) || other.public_inputs.contains_key(&key) {
+                eyre::bail!(
+                    "Input name is once in shared inputs and once in public inputs: \"{key}\""
+                );
+            }
+            shared_inputs.insert(key, value);
+        }
+        for (key, value) in other.public_inputs {
+            if !public_inputs.contains_key(&key) {
+                eyre::bail!("Public input \"{key}\" must be present in all files");
+            }
+            if public_inputs.get(&key).expect("is there we checked") != &value {
+                eyre::bail!("Public input \"{key}\" must be same in all files");
+            }
+        }
+
+        Ok(Self {
+            shared_inputs,
+            public_inputs,
+            bit_widths,
+            usage_policy,
+            commitment_openings,
+        })
+    }
+}
+
+impl<F: PrimeField, U: Rng + SeedableRng + CryptoRng> SerializeableSharedRep3Witness<F, U>
+where
+    U::Seed: Serialize + for<'a> Deserialize<'a> + Clone + std::fmt::Debug,
+
+    Standard: Distribution<U::Seed>,
+{
+    /// Shares a given witness and public input vector using the Rep3 protocol.
+    pub fn share_rep3<R: Rng + CryptoRng>(
+        witness: Witness<F>,
+        num_pub_inputs: usize,
+        rng: &mut R,
+        seeded: bool,
+        additive: bool,
+    ) -> [Self; 3] {
+        let public_inputs = &witness.values[..num_pub_inputs];
+        let witness = &witness.values[num_pub_inputs..];
+
+        let [share1, share2, share3] =
+            SerializeableSharedRep3Input::share_rep3(witness, rng, seeded, additive);
+
+        let witness1 = Self {
+            public_inputs: public_inputs.to_vec(),
+            witness: share1,
+            usage_policy: None,
+        };
+        let witness2 = Self {
+            public_inputs: public_inputs.to_vec(),
+            witness: share2,
+            usage_policy: None,
+        };
+        let witness3 = Self {
+            public_inputs: public_inputs.to_vec(),
+            witness: share3,
+            usage_policy: None,
+        };
+        [witness1, witness2, witness3]
+    }
+}
+
+/// The number of components that are hashed together during [SharedWitness::check_determinism_rep3].
+const DETERMINISM_CHUNK_SIZE: usize = 1024;
+
+/// A section of a witness share, see [WitnessDivergence].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WitnessSection {
+    /// The public inputs, i.e., the outputs and the public inputs of the circuit
+    PublicInputs,
+    /// The replicated components of the secret-shared witness
+    Witness,
+}
+
+/// The first component in which the witness share of a REP3 party diverges from the share of the previous party.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WitnessDivergence {
+    /// The previous party, whose share diverges
+    pub other_party: usize,
+    /// The section of the divergent component
+    pub section: WitnessSection,
+    /// The index of the divergent component in the section
+    pub index: usize,
+    /// Our value of the component, `None` if our section is shorter
+    pub ours: Option<String>,
+    /// The value of the previous party, `None` if its section is shorter
+    pub theirs: Option<String>,
+}
+
+/// The outcome of [SharedWitness::check_determinism_rep3].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DeterminismReport {
+    /// The id of this party
+    pub party: usize,
+    /// The number of public inputs of this party
+    pub num_public_inputs: usize,
+    /// The number of witness components of this party
+    pub num_witness: usize,
+    /// The first divergent component this party found, if any
+    pub divergence: Option<WitnessDivergence>,
+    /// The parties that found a divergence, including this party
+    pub diverged: Vec<usize>,
+}
+
+impl DeterminismReport {
+    /// Returns whether all parties executed identical traces.
+    pub fn is_deterministic(&self) -> bool {
+        self.diverged.is_empty()
+    }
+}
+
+fn chunk_digests<F: PrimeField>(components: &[F]) -> eyre::Result<Vec<Vec<u8>>> {
+    let mut bytes = Vec::new();
+    components
+        .chunks(DETERMINISM_CHUNK_SIZE)
+        .map(|chunk| {
+            let mut hasher = Sha256::new();
+            hasher.update(b"co-circom-snarks rep3 witness determinism");
+            for component in chunk {
+                bytes.clear();
+                component.serialize_uncompressed(&mut bytes)?;
+                hasher.update(&bytes);
+            }
+            Ok(hasher.finalize().to_vec())
+        })
+        .collect()
+}
+
+/// Returns the index of the first chunk whose digests differ, including the first chunk that only one side has.
+fn first_divergent_chunk(ours: &[Vec<u8>], theirs: &[Vec<u8>]) -> Option<usize> {
+    ours.iter()
+        .zip(theirs.iter())
+        .position(|(ours, theirs)| ours != theirs)
+        .or_else(|| (ours.len() != theirs.len()).then(|| ours.len().min(theirs.len())))
+}
+
+impl<F: PrimeField> SharedWitness<F, Rep3PrimeFieldShare<F>> {
+    /// Shares a given witness and public input vector using the rep3 protocol.
+    pub fn share_rep3<R: Rng + CryptoRng>(
+        witness: Witness<F>,
+        num_pub_inputs: usize,
+        rng: &mut R,
+    ) -> [Self; 3] {
+        let public_inputs = &witness.values[..num_pub_inputs];
+        let witness = &witness.values[num_pub_inputs..];
+        let [share1, share2, share3] = rep3::share_field_elements(witness, rng);
+        let witness1 = Self {
+            public_inputs: public_inputs.to_vec(),
+            witness: share1,
+        };
+        let witness2 = Self {
+            public_inputs: public_inputs.to_vec(),
+            witness: share2,
+        };
+        let witness3 = Self {
+            public_inputs: public_inputs.to_vec(),
+            witness: share3,
+        };
+        [witness1, witness2, witness3]
+    }
+
+    /// Checks with the other two parties that the witness shares of all parties come from the same sharing, e.g., to detect mixed-up share files before the proof generation. Every party sends a hash of the first components of its shares to the next party, which compares it to the hash of its second components, as these need to be equal for a valid replicated sharing. The next party already knows these components, so the check reveals nothing about the witness. Afterwards, the parties exchange the outcomes of their checks, such that all parties fail if one check fails.
+    pub fn verify_consistency_rep3<N: Rep3Network>(&self, network: &mut N) -> eyre::Result<()> {
+        let digest = |components: &mut dyn Iterator<Item = &F>| -> eyre::Result<Vec<u8>> {
+            let mut hasher = Sha256::new();
+            hasher.update(b"co-circom-snarks rep3 witness consistency");
+            hasher.update((self.public_inputs.len() as u64).to_le_bytes());
+            let mut bytes = Vec::new();
+            for public in self.public_inputs.iter() {
+                bytes.clear();
+                public.serialize_uncompressed(&mut bytes)?;
+                hasher.update(&bytes);
+            }
+            hasher.update((self.witness.len() as u64).to_le_bytes());
+            for component in components {
+                bytes.clear();
+                component.serialize_uncompressed(&mut bytes)?;
+                hasher.update(&bytes);
+            }
+            Ok(hasher.finalize().to_vec())
+        };
+        let digest_a = digest(&mut self.witness.iter().map(|share| &share.a))?;
+        let digest_b = digest(&mut self.witness.iter().map(|share| &share.b))?;
+        network.send_next(digest_a)?;
+        let digest_prev: Vec<u8> = network.recv_prev()?;
+        let consistent = digest_prev == digest_b;
+        let (consistent_prev, consistent_next) = network.broadcast(consistent)?;
+        let id = usize::from(network.get_id());
+        if !consistent {
+            eyre::bail!(
+                "the witness share of party {id} does not match the share of party {}",
+                (id + 2) % 3
+            );
+        }
+        if !consistent_prev || !consistent_next {
+            eyre::bail!("the witness shares of the other parties do not match");
+        }
+        Ok(())
+    }
+
+    /// Checks with the other two parties that all parties executed identical traces of the witness extension, e.g., to detect nondeterminism in the MPC-VM. Every party sends digests of chunks of its public inputs and of the first components of its shares to the next party, which compares them to the digests of its public inputs and of its second components. If a chunk differs, the next party requests the components of the first divergent chunk to locate the first divergent component. The next party already knows these components, so the check reveals nothing about the witness. Afterwards, the parties exchange the outcomes of their checks.
+    ///
+    /// Returns a [DeterminismReport] instead of an error if the traces diverge, such that the caller can dump it as a diagnostic trace.
+    pub fn check_determinism_rep3<N: Rep3Network>(
+        &self,
+        network: &mut N,
+    ) -> eyre::Result<DeterminismReport> {
+        let id = usize::from(network.get_id());
+        let a = self.witness.iter().map(|share| share.a).collect::<Vec<_>>();
+        let b = self.witness.iter().map(|share| share.b).collect::<Vec<_>>();
+
+        // exchange the digests of the chunks
+        let public_digests = chunk_digests(&self.public_inputs)?;
+        network.send_next(public_digests.clone())?;
+        network.send_next(chunk_digests(&a)?)?;
+        let public_prev: Vec<Vec<u8>> = network.recv_prev()?;
+        let witness_prev: Vec<Vec<u8>> = network.recv_prev()?;
+        let divergent_chunk = match first_divergent_chunk(&public_digests, &public_prev) {
+            Some(chunk) => Some((WitnessSection::PublicInputs, chunk)),
+            None => first_divergent_chunk(&chunk_digests(&b)?, &witness_prev)
+                .map(|chunk| (WitnessSection::Witness, chunk)),
+        };
+
+        // request the components of the first divergent chunk from the previous party
+        let request = match divergent_chunk {
+            Some((WitnessSection::PublicInputs, chunk)) => vec![0, chunk as u64],
+            Some((WitnessSection::Witness, chunk)) => vec![1, chunk as u64],
+            None => vec![],
+        };
+        network.send(network.get_id().prev_id(), request)?;
+        let request: Vec<u64> = network.recv(network.get_id().next_id())?;
+        let chunk_of = |components: &[F], chunk: u64| {
+            components
+                .chunks(DETERMINISM_CHUNK_SIZE)
+                .nth(chunk as usize)
+                .unwrap_or_default()
+                .to_vec()
+        };
+        let response = match request.as_slice() {
+            [] => vec![],
+            [0, chunk] => chunk_of(&self.public_inputs, *chunk),
+            [1, chunk] => chunk_of(&a, *chunk),
+            _ => eyre::bail!("party {} sent a malformed request", (id + 1) % 3),
+        };
+        network.send_next(response)?;
+        let theirs: Vec<F> = network.recv_prev()?;
+
+        let divergence = divergent_chunk.map(|(section, chunk)| {
+            let ours = match section {
+                WitnessSection::PublicInputs => chunk_of(&self.public_inputs, chunk as u64),
+                WitnessSection::Witness => chunk_of(&b, chunk as u64),
+            };
+            let offset = (0..ours.len().max(theirs.len()))
+                .find(|i| ours.get(*i) != theirs.get(*i))
+                .unwrap_or_default();
+            WitnessDivergence {
+                other_party: (id + 2) % 3,
+                section,
+                index: chunk * DETERMINISM_CHUNK_SIZE + offset,
+                ours: ours.get(offset).map(ToString::to_string),
+                theirs: theirs.get(offset).map(ToString::to_string),
+            }
+        });
+
+        // exchange the outcomes
+        let deterministic = divergence.is_none();
+        let (deterministic_prev, deterministic_next) = network.broadcast(deterministic)?;
+        let mut diverged = [
+            (id, deterministic),
+            ((id + 1) % 3, deterministic_next),
+            ((id + 2) % 3, deterministic_prev),
+        ]
+        .into_iter()
+        .filter(|(_, deterministic)| !deterministic)
+        .map(|(party, _)| party)
+        .collect::<Vec<_>>();
+        diverged.sort();
+        Ok(DeterminismReport {
+            party: id,
+            num_public_inputs: self.public_inputs.len(),
+            num_witness: self.witness.len(),
+            divergence,
+            diverged,
+        })
+    }
+}
+
+impl<F: PrimeField> SharedWitness<F, ShamirPrimeFieldShare<F>> {
+    /// Shares a given witness and public input vector using the Shamir protocol.
+    pub fn share_shamir<R: Rng + CryptoRng>(
+        witness: Witness<F>,
+        num_pub_inputs: usize,
+        degree: usize,
+        num_parties: usize,
+        rng: &mut R,
+    ) -> Vec<Self> {
+        let public_inputs = &witness.values[..num_pub_inputs];
+        let witness = &witness.values[num_pub_inputs..];
+        let shares = shamir::share_field_elements(witness, degree, num_parties, rng);
+        shares
+            .into_iter()
+            .map(|share| Self {
+                public_inputs: public_inputs.to_vec(),
+                witness: share,
+            })
+            .collect()
+    }
+}
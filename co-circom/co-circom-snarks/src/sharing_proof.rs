@@ -0,0 +1,521 @@
+//! Honest-verifier zero-knowledge proofs of correct sharing of a witness.
+//!
+//! The party that splits a witness into shares can prove to the computing parties that the shares reconstruct to a witness `W` that satisfies the R1CS of the circuit for the public inputs `x`, i.e., `(Az) ∘ (Bz) = Cz` for `z = (1, x, W)`. Every computing party checks the proof and the shares it holds against it, so the parties notice garbage shares before they start a proof generation, without reconstructing the witness.
+//!
+//! The splitting party commits to the witness and to every share of the sharing with [PedersenKey]s. The commitments are additively homomorphic, so every party can check on the commitments that the shares reconstruct to the committed witness. The R1CS relation is proven with a sigma protocol, which is made non-interactive with the Fiat-Shamir transform: after a challenge `y`, the prover commits to the coefficients of the polynomial `Σ_k y^k ((As)_k (Bs)_k - e (Cs)_k)` in `e`, where `s = t + e * W` is the witness masked with a random vector `t`. After the challenge `e`, the prover reveals `s`. The polynomial has degree one if all constraints are satisfied, otherwise its quadratic coefficient is non-zero with overwhelming probability over `y`, and the verifier catches the prover with overwhelming probability over `e`.
+//!
+//! The proof is not succinct, the masked witness is as large as a witness share, but it needs no trusted setup and its creation and verification are linear in the size of the R1CS.
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{One, PrimeField, UniformRand, Zero};
+use ark_serialize::CanonicalSerialize;
+use circom_types::R1CS;
+use rand::{CryptoRng, Rng};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{commitment::PedersenKey, ConstraintEvaluator};
+
+const TRANSCRIPT_LABEL: &[u8] = b"co-circom-snarks sharing proof";
+
+/// The label of the [PedersenKey] of the commitments to the witness and its shares.
+const WITNESS_LABEL: &[u8] = b"co-circom-snarks sharing proof witness";
+
+/// The label of the [PedersenKey] of the commitments to the coefficients of the product polynomial.
+const PRODUCT_LABEL: &[u8] = b"co-circom-snarks sharing proof product";
+
+/// The secret sharing scheme of a [SharingProof].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SharingScheme {
+    /// Replicated secret sharing for three parties. The proof commits to the three additive shares, where party `i` holds the additive shares `i` and `i - 1`.
+    Rep3,
+    /// Shamir secret sharing with the provided threshold. The proof commits to the share of every party, i.e., to the evaluation of the sharing polynomials at the point `i + 1` for party `i`.
+    Shamir {
+        /// The threshold of tolerated colluding parties, i.e., the degree of the sharing polynomials
+        threshold: usize,
+    },
+}
+
+impl SharingScheme {
+    /// Returns the indices of the committed shares that the party with the provided id holds.
+    pub fn held_shares(&self, party_id: usize) -> Vec<usize> {
+        match self {
+            SharingScheme::Rep3 => vec![party_id, (party_id + 2) % 3],
+            SharingScheme::Shamir { .. } => vec![party_id],
+        }
+    }
+
+    /// Shares the blinding factor of the commitment to the witness like the witness itself and returns the blinding factor and its shares.
+    fn share_blinding<F: PrimeField, R: Rng + CryptoRng>(
+        &self,
+        num_shares: usize,
+        rng: &mut R,
+    ) -> eyre::Result<(F, Vec<F>)> {
+        self.check_num_shares(num_shares)?;
+        match self {
+            SharingScheme::Rep3 => {
+                let shares = (0..3).map(|_| F::rand(rng)).collect::<Vec<_>>();
+                Ok((shares.iter().sum(), shares))
+            }
+            SharingScheme::Shamir { threshold } => {
+                let coeffs = (0..=*threshold).map(|_| F::rand(rng)).collect::<Vec<_>>();
+                let shares = (1..=num_shares as u64)
+                    .map(|point| {
+                        let point = F::from(point);
+                        coeffs
+                            .iter()
+                            .rev()
+                            .fold(F::zero(), |acc, c| acc * point + c)
+                    })
+                    .collect();
+                Ok((coeffs[0], shares))
+            }
+        }
+    }
+
+    fn check_num_shares(&self, num_shares: usize) -> eyre::Result<()> {
+        match self {
+            SharingScheme::Rep3 if num_shares != 3 => {
+                eyre::bail!("REP3 sharings consist of 3 additive shares, but got {num_shares}")
+            }
+            SharingScheme::Shamir { threshold } if num_shares <= *threshold => eyre::bail!(
+                "Shamir sharings with threshold {threshold} need more than {threshold} shares, but got {num_shares}"
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that the commitments to the shares reconstruct to the commitment to the witness.
+    fn check_reconstruction<C: CurveGroup>(
+        &self,
+        witness_commitment: C,
+        share_commitments: &[C],
+    ) -> eyre::Result<()> {
+        self.check_num_shares(share_commitments.len())?;
+        match self {
+            SharingScheme::Rep3 => {
+                if share_commitments.iter().copied().sum::<C>() != witness_commitment {
+                    eyre::bail!("the committed shares do not reconstruct to the committed witness");
+                }
+            }
+            SharingScheme::Shamir { threshold } => {
+                // the first threshold + 1 shares define the sharing polynomials, all other shares need to lie on them
+                let points = (1..=*threshold as u64 + 1)
+                    .map(C::ScalarField::from)
+                    .collect::<Vec<_>>();
+                let interpolate = |x: C::ScalarField| {
+                    lagrange_coefficients(&points, x)
+                        .into_iter()
+                        .zip(share_commitments.iter())
+                        .map(|(coeff, commitment)| *commitment * coeff)
+                        .sum::<C>()
+                };
+                if interpolate(C::ScalarField::zero()) != witness_commitment {
+                    eyre::bail!("the committed shares do not reconstruct to the committed witness");
+                }
+                for (i, commitment) in share_commitments.iter().enumerate().skip(threshold + 1) {
+                    if interpolate(C::ScalarField::from(i as u64 + 1)) != *commitment {
+                        eyre::bail!(
+                            "the committed share of party {i} does not lie on a polynomial of degree {threshold}"
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the Lagrange coefficients of the provided points for the evaluation at `x`.
+fn lagrange_coefficients<F: PrimeField>(points: &[F], x: F) -> Vec<F> {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, xi)| {
+            points
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, xj)| (x - xj) / (*xi - xj))
+                .product()
+        })
+        .collect()
+}
+
+/// The opening of the commitments to the shares a party holds, i.e., the blinding factors of the commitments. The splitting party hands the opening to the party together with its share, it must not be published.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SharingOpening<F: PrimeField> {
+    /// The id of the party the opening belongs to
+    pub party_id: usize,
+    /// The indices of the committed shares the party holds, see [SharingScheme::held_shares]
+    pub indices: Vec<usize>,
+    /// The blinding factors of the commitments to the shares, in the order of the indices
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    pub blindings: Vec<F>,
+}
+
+/// A non-interactive honest-verifier zero-knowledge proof that a sharing of a witness reconstructs to a witness satisfying an R1CS, see the [module documentation](self). The proof can be published.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct SharingProof<P: Pairing> {
+    /// The secret sharing scheme of the shares
+    pub scheme: SharingScheme,
+    /// The public inputs, including the constant 1 at position 0
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    pub public_inputs: Vec<P::ScalarField>,
+    /// The commitment to the private witness
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    pub witness_commitment: P::G1Affine,
+    /// The commitments to the shares of the private witness
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    pub share_commitments: Vec<P::G1Affine>,
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    mask_commitment: P::G1Affine,
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    constant_commitment: P::G1Affine,
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    linear_commitment: P::G1Affine,
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    masked_witness: Vec<P::ScalarField>,
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    masked_blinding: P::ScalarField,
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    masked_product_blinding: P::ScalarField,
+}
+
+impl<P: Pairing> SharingProof<P> {
+    /// Proves that the `shares` of the private `witness` reconstruct to a witness satisfying the R1CS for the `public_inputs`. The shares are the committed shares of the [SharingScheme], i.e., the three additive shares for REP3 and the shares of the parties for Shamir. Returns the proof and the [SharingOpening] of every party.
+    pub fn prove<R: Rng + CryptoRng>(
+        r1cs: &R1CS<P>,
+        public_inputs: &[P::ScalarField],
+        witness: &[P::ScalarField],
+        scheme: SharingScheme,
+        shares: &[Vec<P::ScalarField>],
+        rng: &mut R,
+    ) -> eyre::Result<(Self, Vec<SharingOpening<P::ScalarField>>)> {
+        let statement = Statement::new(r1cs)?;
+        statement.check_lengths(public_inputs, witness)?;
+        if let Some(i) = shares.iter().position(|share| share.len() != witness.len()) {
+            eyre::bail!(
+                "share {i} has {} elements, but the witness has {}",
+                shares[i].len(),
+                witness.len()
+            );
+        }
+        let (az, bz, cz) = statement.evaluate(public_inputs, witness);
+        if let Some(k) = (0..az.len()).find(|k| az[*k] * bz[*k] != cz[*k]) {
+            eyre::bail!("the witness does not satisfy constraint {k} of the R1CS");
+        }
+
+        let key = PedersenKey::<P::G1>::new(WITNESS_LABEL, witness.len());
+        let product_key = PedersenKey::<P::G1>::new(PRODUCT_LABEL, 1);
+        let (blinding, share_blindings) = scheme.share_blinding(shares.len(), rng)?;
+        let witness_commitment = key.commit(witness, blinding)?;
+        let share_commitments = shares
+            .iter()
+            .zip(share_blindings.iter())
+            .map(|(share, blinding)| key.commit(share, *blinding))
+            .collect::<eyre::Result<Vec<_>>>()?;
+        let mask = (0..witness.len())
+            .map(|_| P::ScalarField::rand(rng))
+            .collect::<Vec<_>>();
+        let mask_blinding = P::ScalarField::rand(rng);
+        let mask_commitment = key.commit(&mask, mask_blinding)?;
+
+        let mut transcript = Transcript::new(&statement, scheme);
+        transcript.absorb(public_inputs)?;
+        transcript.absorb(&witness_commitment)?;
+        transcript.absorb(&share_commitments)?;
+        transcript.absorb(&mask_commitment)?;
+        let y = transcript.challenge::<P::ScalarField>();
+
+        let zeros = vec![P::ScalarField::zero(); public_inputs.len()];
+        let (at, bt, ct) = statement.evaluate(&zeros, &mask);
+        let mut constant = P::ScalarField::zero();
+        let mut linear = P::ScalarField::zero();
+        let mut y_pow = P::ScalarField::one();
+        for k in 0..az.len() {
+            constant += y_pow * at[k] * bt[k];
+            linear += y_pow * (at[k] * bz[k] + az[k] * bt[k] - ct[k]);
+            y_pow *= y;
+        }
+        let constant_blinding = P::ScalarField::rand(rng);
+        let linear_blinding = P::ScalarField::rand(rng);
+        let constant_commitment = product_key.commit(&[constant], constant_blinding)?;
+        let linear_commitment = product_key.commit(&[linear], linear_blinding)?;
+        transcript.absorb(&constant_commitment)?;
+        transcript.absorb(&linear_commitment)?;
+        let e = transcript.challenge::<P::ScalarField>();
+
+        let masked_witness = mask
+            .iter()
+            .zip(witness.iter())
+            .map(|(t, w)| *t + e * w)
+            .collect();
+        let proof = Self {
+            scheme,
+            public_inputs: public_inputs.to_vec(),
+            witness_commitment: witness_commitment.into_affine(),
+            share_commitments: P::G1::normalize_batch(&share_commitments),
+            mask_commitment: mask_commitment.into_affine(),
+            constant_commitment: constant_commitment.into_affine(),
+            linear_commitment: linear_commitment.into_affine(),
+            masked_witness,
+            masked_blinding: mask_blinding + e * blinding,
+            masked_product_blinding: constant_blinding + e * linear_blinding,
+        };
+        // every party holds one of the committed shares (and the one of the previous party for REP3)
+        let openings = (0..shares.len())
+            .map(|party_id| {
+                let indices = scheme.held_shares(party_id);
+                SharingOpening {
+                    party_id,
+                    blindings: indices.iter().map(|i| share_blindings[*i]).collect(),
+                    indices,
+                }
+            })
+            .collect();
+        Ok((proof, openings))
+    }
+
+    /// Verifies the public part of the proof, i.e., that the committed shares reconstruct to a witness satisfying the R1CS for the public inputs of the proof. Every party additionally checks the shares it holds with [SharingProof::verify_share].
+    pub fn verify(&self, r1cs: &R1CS<P>) -> eyre::Result<()> {
+        let statement = Statement::new(r1cs)?;
+        statement.check_lengths(&self.public_inputs, &self.masked_witness)?;
+        if self.public_inputs.first() != Some(&P::ScalarField::one()) {
+            eyre::bail!("the first public input must be the constant 1");
+        }
+        let share_commitments = self
+            .share_commitments
+            .iter()
+            .map(|c| c.into_group())
+            .collect::<Vec<_>>();
+        self.scheme
+            .check_reconstruction(self.witness_commitment.into_group(), &share_commitments)?;
+
+        let mut transcript = Transcript::new(&statement, self.scheme);
+        transcript.absorb(&self.public_inputs)?;
+        transcript.absorb(&self.witness_commitment.into_group())?;
+        transcript.absorb(&share_commitments)?;
+        transcript.absorb(&self.mask_commitment.into_group())?;
+        let y = transcript.challenge::<P::ScalarField>();
+        transcript.absorb(&self.constant_commitment.into_group())?;
+        transcript.absorb(&self.linear_commitment.into_group())?;
+        let e = transcript.challenge::<P::ScalarField>();
+
+        let key = PedersenKey::<P::G1>::new(WITNESS_LABEL, self.masked_witness.len());
+        if key.commit(&self.masked_witness, self.masked_blinding)?
+            != self.mask_commitment.into_group() + self.witness_commitment * e
+        {
+            eyre::bail!("the masked witness does not open to the committed witness");
+        }
+
+        // the public inputs are not masked, so z_s = (e * x, s) = z_t + e * z
+        let masked_public_inputs = self.public_inputs.iter().map(|x| e * x).collect::<Vec<_>>();
+        let (a, b, c) = statement.evaluate(&masked_public_inputs, &self.masked_witness);
+        let mut value = P::ScalarField::zero();
+        let mut y_pow = P::ScalarField::one();
+        for k in 0..a.len() {
+            value += y_pow * (a[k] * b[k] - e * c[k]);
+            y_pow *= y;
+        }
+        let product_key = PedersenKey::<P::G1>::new(PRODUCT_LABEL, 1);
+        if product_key.commit(&[value], self.masked_product_blinding)?
+            != self.constant_commitment.into_group() + self.linear_commitment * e
+        {
+            eyre::bail!("the committed witness does not satisfy the R1CS");
+        }
+        Ok(())
+    }
+
+    /// Checks the shares a party holds against the commitments of the proof, using the opening the party received from the splitting party. The `shares` are the committed shares in the order of [SharingOpening::indices]. A party that holds fewer shares, e.g., only its additive share of a REP3 sharing, passes only the first ones.
+    pub fn verify_share(
+        &self,
+        opening: &SharingOpening<P::ScalarField>,
+        public_inputs: &[P::ScalarField],
+        shares: &[&[P::ScalarField]],
+    ) -> eyre::Result<()> {
+        if public_inputs != self.public_inputs {
+            eyre::bail!(
+                "the public inputs of the share differ from the public inputs of the proof"
+            );
+        }
+        if opening.indices != self.scheme.held_shares(opening.party_id)
+            || opening.blindings.len() != opening.indices.len()
+        {
+            eyre::bail!(
+                "the opening does not match the shares of party {}",
+                opening.party_id
+            );
+        }
+        if shares.is_empty() || shares.len() > opening.indices.len() {
+            eyre::bail!(
+                "expected between 1 and {} shares, but got {}",
+                opening.indices.len(),
+                shares.len()
+            );
+        }
+        let key = PedersenKey::<P::G1>::new(WITNESS_LABEL, self.masked_witness.len());
+        for ((share, index), blinding) in shares
+            .iter()
+            .zip(opening.indices.iter())
+            .zip(opening.blindings.iter())
+        {
+            if share.len() != self.masked_witness.len() {
+                eyre::bail!(
+                    "the share has {} elements, but the witness has {}",
+                    share.len(),
+                    self.masked_witness.len()
+                );
+            }
+            let commitment = self
+                .share_commitments
+                .get(*index)
+                .ok_or_else(|| eyre::eyre!("the proof has no commitment to share {index}"))?;
+            if key.commit(share, *blinding)?.into_affine() != *commitment {
+                eyre::bail!(
+                    "share {index} of party {} does not open to its commitment",
+                    opening.party_id
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The constraint matrices of the R1CS and a digest of them, which binds the proof to the circuit.
+struct Statement<F: PrimeField> {
+    a: ConstraintEvaluator<F>,
+    b: ConstraintEvaluator<F>,
+    c: ConstraintEvaluator<F>,
+    num_public: usize,
+    num_private: usize,
+    digest: [u8; 32],
+}
+
+impl<F: PrimeField> Statement<F> {
+    fn new<P: Pairing<ScalarField = F>>(r1cs: &R1CS<P>) -> eyre::Result<Self> {
+        let mut hasher = Sha256::new()
+            .chain_update((r1cs.num_inputs as u64).to_le_bytes())
+            .chain_update((r1cs.num_aux as u64).to_le_bytes());
+        let mut bytes = Vec::new();
+        let mut flip = |row: &Vec<(usize, F)>| -> eyre::Result<Vec<(F, usize)>> {
+            hasher.update((row.len() as u64).to_le_bytes());
+            for (index, coeff) in row {
+                bytes.clear();
+                coeff.serialize_compressed(&mut bytes)?;
+                hasher.update((*index as u64).to_le_bytes());
+                hasher.update(&bytes);
+            }
+            Ok(row.iter().map(|(i, c)| (*c, *i)).collect())
+        };
+        let mut a = Vec::with_capacity(r1cs.constraints.len());
+        let mut b = Vec::with_capacity(r1cs.constraints.len());
+        let mut c = Vec::with_capacity(r1cs.constraints.len());
+        for (row_a, row_b, row_c) in r1cs.constraints.iter() {
+            a.push(flip(row_a)?);
+            b.push(flip(row_b)?);
+            c.push(flip(row_c)?);
+        }
+        Ok(Self {
+            a: ConstraintEvaluator::deduplicated(&a),
+            b: ConstraintEvaluator::deduplicated(&b),
+            c: ConstraintEvaluator::deduplicated(&c),
+            num_public: r1cs.num_inputs,
+            num_private: r1cs.num_aux,
+            digest: hasher.finalize().into(),
+        })
+    }
+
+    fn check_lengths(&self, public_inputs: &[F], witness: &[F]) -> eyre::Result<()> {
+        if public_inputs.len() != self.num_public {
+            eyre::bail!(
+                "expected {} public inputs, but got {}",
+                self.num_public,
+                public_inputs.len()
+            );
+        }
+        if witness.len() != self.num_private {
+            eyre::bail!(
+                "expected {} private witness elements, but got {}",
+                self.num_private,
+                witness.len()
+            );
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self, public_inputs: &[F], witness: &[F]) -> (Vec<F>, Vec<F>, Vec<F>) {
+        (
+            self.a.evaluate_plain(public_inputs, witness),
+            self.b.evaluate_plain(public_inputs, witness),
+            self.c.evaluate_plain(public_inputs, witness),
+        )
+    }
+}
+
+/// The Fiat-Shamir transcript of a [SharingProof].
+struct Transcript(Sha256);
+
+impl Transcript {
+    fn new<F: PrimeField>(statement: &Statement<F>, scheme: SharingScheme) -> Self {
+        let scheme = match scheme {
+            SharingScheme::Rep3 => [0u8; 9],
+            SharingScheme::Shamir { threshold } => {
+                let mut bytes = [1u8; 9];
+                bytes[1..].copy_from_slice(&(threshold as u64).to_le_bytes());
+                bytes
+            }
+        };
+        Self(
+            Sha256::new()
+                .chain_update(TRANSCRIPT_LABEL)
+                .chain_update(statement.digest)
+                .chain_update(scheme),
+        )
+    }
+
+    fn absorb<T: CanonicalSerialize + ?Sized>(&mut self, value: &T) -> eyre::Result<()> {
+        let mut bytes = Vec::with_capacity(value.compressed_size());
+        value.serialize_compressed(&mut bytes)?;
+        self.0.update((bytes.len() as u64).to_le_bytes());
+        self.0.update(bytes);
+        Ok(())
+    }
+
+    fn challenge<F: PrimeField>(&mut self) -> F {
+        let digest = self.0.clone().finalize();
+        self.0.update(digest);
+        F::from_le_bytes_mod_order(&digest)
+    }
+}
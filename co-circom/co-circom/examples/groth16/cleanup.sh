@@ -1,4 +1,6 @@
 # rm all proof files
-rm -rf proof.0.json proof.1.json proof.2.json
+rm -rf proof.0.json proof.1.json proof.2.json proof.local.json public_input.local.json
 # delete all shared files
 find . -name "*.shared" -type f -delete
+# delete all PVSS files
+find . -name "*.pvss*" -type f -delete
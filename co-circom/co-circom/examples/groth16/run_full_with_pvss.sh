@@ -0,0 +1,24 @@
+EXAMPLE_NAME=multiplier2
+
+# every party generates a PVSS key pair and publishes the public key
+cargo run --release --bin co-circom -- generate-pvss-key --curve BN254 --key-out test_vectors/$EXAMPLE_NAME/party0.pvss.key --public-key-out test_vectors/$EXAMPLE_NAME/party0.pvss.pub
+cargo run --release --bin co-circom -- generate-pvss-key --curve BN254 --key-out test_vectors/$EXAMPLE_NAME/party1.pvss.key --public-key-out test_vectors/$EXAMPLE_NAME/party1.pvss.pub
+cargo run --release --bin co-circom -- generate-pvss-key --curve BN254 --key-out test_vectors/$EXAMPLE_NAME/party2.pvss.key --public-key-out test_vectors/$EXAMPLE_NAME/party2.pvss.pub
+# split input into encrypted shares, the data owner can go offline afterwards
+cargo run --release --bin co-circom -- split-input --circuit test_vectors/$EXAMPLE_NAME/circuit.circom --input test_vectors/$EXAMPLE_NAME/input.json --protocol REP3 --curve BN254 --out-dir test_vectors/$EXAMPLE_NAME --pvss-keys test_vectors/$EXAMPLE_NAME/party0.pvss.pub test_vectors/$EXAMPLE_NAME/party1.pvss.pub test_vectors/$EXAMPLE_NAME/party2.pvss.pub
+# every party verifies the published shares and decrypts its share
+cargo run --release --bin co-circom -- decrypt-input-share --input test_vectors/$EXAMPLE_NAME/input.json.pvss --key test_vectors/$EXAMPLE_NAME/party0.pvss.key --curve BN254 --out test_vectors/$EXAMPLE_NAME/input.json.0.shared
+cargo run --release --bin co-circom -- decrypt-input-share --input test_vectors/$EXAMPLE_NAME/input.json.pvss --key test_vectors/$EXAMPLE_NAME/party1.pvss.key --curve BN254 --out test_vectors/$EXAMPLE_NAME/input.json.1.shared
+cargo run --release --bin co-circom -- decrypt-input-share --input test_vectors/$EXAMPLE_NAME/input.json.pvss --key test_vectors/$EXAMPLE_NAME/party2.pvss.key --curve BN254 --out test_vectors/$EXAMPLE_NAME/input.json.2.shared
+# run witness extension in MPC
+cargo run --release --bin co-circom -- generate-witness -O2 --input test_vectors/$EXAMPLE_NAME/input.json.0.shared --circuit test_vectors/$EXAMPLE_NAME/circuit.circom --protocol REP3 --curve BN254 --config ../configs/party1.toml --out test_vectors/$EXAMPLE_NAME/witness.wtns.0.shared &
+cargo run --release --bin co-circom -- generate-witness -O2 --input test_vectors/$EXAMPLE_NAME/input.json.1.shared --circuit test_vectors/$EXAMPLE_NAME/circuit.circom --protocol REP3 --curve BN254 --config ../configs/party2.toml --out test_vectors/$EXAMPLE_NAME/witness.wtns.1.shared &
+cargo run --release --bin co-circom -- generate-witness -O2 --input test_vectors/$EXAMPLE_NAME/input.json.2.shared --circuit test_vectors/$EXAMPLE_NAME/circuit.circom --protocol REP3 --curve BN254 --config ../configs/party3.toml --out test_vectors/$EXAMPLE_NAME/witness.wtns.2.shared
+wait $(jobs -p)
+# run proving in MPC
+cargo run --release --bin co-circom -- generate-proof groth16 --witness test_vectors/$EXAMPLE_NAME/witness.wtns.0.shared --zkey test_vectors/$EXAMPLE_NAME/$EXAMPLE_NAME.zkey --protocol REP3 --curve BN254 --config ../configs/party1.toml --out proof.0.json --public-input public_input.json &
+cargo run --release --bin co-circom -- generate-proof groth16 --witness test_vectors/$EXAMPLE_NAME/witness.wtns.1.shared --zkey test_vectors/$EXAMPLE_NAME/$EXAMPLE_NAME.zkey --protocol REP3 --curve BN254 --config ../configs/party2.toml --out proof.1.json &
+cargo run --release --bin co-circom -- generate-proof groth16 --witness test_vectors/$EXAMPLE_NAME/witness.wtns.2.shared --zkey test_vectors/$EXAMPLE_NAME/$EXAMPLE_NAME.zkey --protocol REP3 --curve BN254 --config ../configs/party3.toml --out proof.2.json
+wait $(jobs -p)
+# verify proof
+cargo run --release --bin co-circom -- verify groth16 --proof proof.0.json --vk test_vectors/$EXAMPLE_NAME/verification_key.json --public-input public_input.json --curve BN254
@@ -0,0 +1,6 @@
+# split input into shares
+cargo run --release --bin co-circom -- split-input --circuit test_vectors/multiplier2/circuit.circom --input test_vectors/multiplier2/input.json --protocol REP3 --curve BN254 --out-dir test_vectors/multiplier2
+# reconstruct the input shares of all parties and generate the witness and proof locally without MPC
+cargo run --release --bin co-circom -- prove-local groth16 --inputs test_vectors/multiplier2/input.json.0.shared --inputs test_vectors/multiplier2/input.json.1.shared --inputs test_vectors/multiplier2/input.json.2.shared --circuit test_vectors/multiplier2/circuit.circom -O2 --zkey test_vectors/multiplier2/multiplier2.zkey --curve BN254 --out proof.local.json --public-input public_input.local.json
+# verify proof
+cargo run --release --bin co-circom -- verify groth16 --proof proof.local.json --vk test_vectors/multiplier2/verification_key.json --public-input public_input.local.json --curve BN254
@@ -0,0 +1,141 @@
+//! Proof generation for many independent circuits in one invocation.
+//!
+//! A batch is described by a [BatchManifest], which lists the witness share and the proving key of every job. All parties need to use manifests with the same jobs in the same order. The jobs are distributed over a fixed number of slots, job `i` runs on slot `i % slots`, and every slot runs its jobs one after another over its own network channels. Thus, the parties agree on the channels of every job without further coordination. Every slot runs its jobs in a dedicated thread pool, so a single job can not occupy all cores of the machine.
+
+use std::{
+    fs::File,
+    io::BufReader,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::{self, Context};
+use serde::{Deserialize, Serialize};
+
+/// A single proof generation of a batch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchJob {
+    /// The path to the witness share file
+    pub witness: PathBuf,
+    /// The path to the proving key (.zkey) file
+    pub zkey: PathBuf,
+    /// The output file where the proof is written to. If not passed, this party will not write the proof to a file.
+    #[serde(default)]
+    pub out: Option<PathBuf>,
+    /// The output JSON file where the public inputs are written to. If not passed, this party will not write the public inputs to a file.
+    #[serde(default)]
+    pub public_input: Option<PathBuf>,
+}
+
+/// The JSON manifest of a batch, e.g., `{"jobs": [{"witness": "a.shared", "zkey": "a.zkey", "out": "a.proof.json"}]}`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchManifest {
+    /// The jobs of the batch, in the same order for all parties
+    pub jobs: Vec<BatchJob>,
+}
+
+impl BatchManifest {
+    /// Reads a manifest from a JSON file. Fails if the manifest does not contain any jobs.
+    pub fn from_file(path: &Path) -> eyre::Result<Self> {
+        let file = BufReader::new(File::open(path).context("while opening batch manifest")?);
+        let manifest: Self =
+            serde_json::from_reader(file).context("while parsing batch manifest")?;
+        if manifest.jobs.is_empty() {
+            eyre::bail!(
+                "batch manifest {} does not contain any jobs",
+                path.display()
+            );
+        }
+        Ok(manifest)
+    }
+}
+
+/// The resources of a single job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct JobQuota {
+    /// The number of threads of the thread pool of a slot
+    pub threads: usize,
+}
+
+impl JobQuota {
+    /// Computes the quota for the provided number of concurrent jobs. If the number of threads per job is not set, the available cores are split evenly between the jobs.
+    pub fn new(concurrent_jobs: usize, threads_per_job: Option<usize>) -> Self {
+        let threads = threads_per_job.unwrap_or_else(|| {
+            let cores = std::thread::available_parallelism()
+                .map(NonZeroUsize::get)
+                .unwrap_or(1);
+            cores / concurrent_jobs.max(1)
+        });
+        Self {
+            threads: threads.max(1),
+        }
+    }
+}
+
+/// Runs the jobs on the provided slots, see the [module documentation](self), and returns the results in the order of the jobs. The function `run` gets the slot of the job and the index of the job. A failed job does not abort the remaining jobs.
+pub fn run_jobs<S, J, R, E>(
+    slots: Vec<S>,
+    jobs: Vec<J>,
+    quota: JobQuota,
+    run: E,
+) -> Vec<eyre::Result<R>>
+where
+    S: Send,
+    J: Send,
+    R: Send,
+    E: Fn(&mut S, usize, J) -> eyre::Result<R> + Sync,
+{
+    let num_jobs = jobs.len();
+    let num_slots = slots.len();
+    if num_slots == 0 {
+        return jobs
+            .into_iter()
+            .map(|_| Err(eyre::eyre!("no slots to run the job on")))
+            .collect();
+    }
+    let mut assigned = (0..num_slots).map(|_| Vec::new()).collect::<Vec<_>>();
+    for (i, job) in jobs.into_iter().enumerate() {
+        assigned[i % num_slots].push((i, job));
+    }
+
+    let mut results = std::thread::scope(|scope| {
+        let handles = slots
+            .into_iter()
+            .zip(assigned)
+            .map(|(mut slot, jobs)| {
+                let run = &run;
+                scope.spawn(move || {
+                    let pool = rayon::ThreadPoolBuilder::new()
+                        .num_threads(quota.threads)
+                        .build();
+                    jobs.into_iter()
+                        .map(|(i, job)| {
+                            let result = match &pool {
+                                Ok(pool) => pool.install(|| run(&mut slot, i, job)),
+                                Err(err) => Err(eyre::eyre!("cannot build thread pool: {err}")),
+                            };
+                            (i, result)
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .flat_map(|handle| match handle.join() {
+                Ok(results) => results,
+                Err(_) => Vec::new(),
+            })
+            .collect::<Vec<_>>()
+    });
+
+    // jobs of a panicked slot have no result
+    results.sort_by_key(|(i, _)| *i);
+    let mut results = results.into_iter().peekable();
+    (0..num_jobs)
+        .map(|i| match results.next_if(|(j, _)| *j == i) {
+            Some((_, result)) => result,
+            None => Err(eyre::eyre!("job {i} panicked")),
+        })
+        .collect()
+}
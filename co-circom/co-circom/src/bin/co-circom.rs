@@ -1,67 +1,171 @@
+use ark_bls12_377::Bls12_377;
 use ark_bls12_381::Bls12_381;
 use ark_bn254::Bn254;
 use ark_ec::pairing::Pairing;
+#[cfg(feature = "mpc")]
 use ark_ff::PrimeField;
+#[cfg(feature = "mpc")]
+use circom_mpc_vm::compiled_circuit::{circuit_hash_to_hex, CircuitHash};
+#[cfg(feature = "mpc")]
 use circom_types::R1CS;
+#[cfg(feature = "mpc")]
 use num_traits::Zero;
+#[cfg(feature = "mpc")]
 use std::sync::Arc;
 
+#[cfg(feature = "mpc")]
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+#[cfg(feature = "mpc")]
 use circom_types::{
-    groth16::{
-        Groth16Proof, JsonVerificationKey as Groth16JsonVerificationKey, ZKey as Groth16ZKey,
-    },
-    plonk::{JsonVerificationKey as PlonkJsonVerificationKey, PlonkProof, ZKey as PlonkZKey},
-    traits::{CheckElement, CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge},
-    Witness,
+    groth16::LazyZKey as Groth16LazyZKey, groth16::ZKey as Groth16ZKey, plonk::ZKey as PlonkZKey,
+    traits::CheckElement, Witness,
 };
-use clap::{Parser, Subcommand};
+use circom_types::{
+    groth16::{Groth16Proof, JsonVerificationKey as Groth16JsonVerificationKey},
+    plonk::{JsonVerificationKey as PlonkJsonVerificationKey, PlonkProof},
+    traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge},
+};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use co_circom::calibrate::CalibrationReport;
+#[cfg(feature = "tui")]
+use co_circom::monitor::{self, Monitor, SessionMetrics};
+use co_circom::output::{CommandOutput, CommandReport, OutputFormat};
+use co_circom::session;
+use co_circom::storage;
+#[cfg(feature = "mpc")]
+use co_circom::CompileCli;
+#[cfg(feature = "mpc")]
+use co_circom::CompileConfig;
+#[cfg(feature = "mpc")]
 use co_circom::GenerateProofCli;
+#[cfg(feature = "mpc")]
 use co_circom::GenerateProofConfig;
+#[cfg(feature = "mpc")]
 use co_circom::GenerateWitnessCli;
+#[cfg(feature = "mpc")]
 use co_circom::GenerateWitnessConfig;
+#[cfg(feature = "mpc")]
 use co_circom::MergeInputSharesCli;
+#[cfg(feature = "mpc")]
 use co_circom::MergeInputSharesConfig;
+#[cfg(feature = "mpc")]
+use co_circom::PvssSharedRep3Input;
+#[cfg(feature = "mpc")]
 use co_circom::SplitInputCli;
+#[cfg(feature = "mpc")]
 use co_circom::SplitInputConfig;
+#[cfg(feature = "mpc")]
 use co_circom::SplitWitnessCli;
+#[cfg(feature = "mpc")]
 use co_circom::SplitWitnessConfig;
+#[cfg(feature = "mpc")]
 use co_circom::TranslateWitnessCli;
+#[cfg(feature = "mpc")]
 use co_circom::TranslateWitnessConfig;
 use co_circom::VerifyCli;
 use co_circom::VerifyConfig;
-use co_circom::{file_utils, MPCCurve, MPCProtocol, ProofSystem, SeedRng};
+#[cfg(feature = "mpc")]
+use co_circom::WitnessStatsCli;
+#[cfg(feature = "mpc")]
+use co_circom::WitnessStatsConfig;
+#[cfg(feature = "mpc")]
+use co_circom::{
+    batch::{self, BatchJob, BatchManifest, JobQuota},
+    inspect::ShareInspection,
+    migrate::ShareFileKind,
+    plugins::{PluginProof, PluginProveArgs, PluginVerifyArgs, ProofSystemRegistry},
+    recommend::{self, CircuitProfile, NetworkProfile},
+    rehearsal::{self, RehearsalFingerprint, RehearsalReport},
+    signing::{self, Committee, ProofStatement, SignedStatement, SigningKeyPair},
+    transcript::{self, SessionTranscript, TranscriptRecorder},
+    warmup::{WarmUp, WarmUpReport},
+    MPCProtocol, SeedRng, WitnessShareStats,
+};
+use co_circom::{file_utils, MPCCurve, ProofSystem};
+#[cfg(feature = "mpc")]
+use co_circom::{AuditCli, AuditConfig};
+use co_circom::{CalibrateCli, CalibrateConfig};
+use co_circom::{CleanupCli, CleanupConfig};
+#[cfg(feature = "mpc")]
+use co_circom::{DecryptInputShareCli, DecryptInputShareConfig};
+#[cfg(feature = "mpc")]
+use co_circom::{GenerateProofBatchCli, GenerateProofBatchConfig};
+#[cfg(feature = "mpc")]
+use co_circom::{GeneratePvssKeyCli, GeneratePvssKeyConfig};
+#[cfg(feature = "mpc")]
+use co_circom::{GenerateSigningKeyCli, GenerateSigningKeyConfig};
+#[cfg(feature = "mpc")]
+use co_circom::{InspectShareCli, InspectShareConfig};
+#[cfg(feature = "mpc")]
+use co_circom::{MigrateSharesCli, MigrateSharesConfig};
+#[cfg(feature = "mpc")]
+use co_circom::{ProveLocalCli, ProveLocalConfig};
+#[cfg(feature = "mpc")]
+use co_circom::{RecommendCli, RecommendConfig};
+#[cfg(feature = "verify-server")]
+use co_circom::{VerifyServerCli, VerifyServerConfig};
+#[cfg(feature = "mpc")]
+use co_circom::{VerifySignaturesCli, VerifySignaturesConfig};
+#[cfg(feature = "mpc")]
+use co_circom::{VerifyWitnessShareCli, VerifyWitnessShareConfig};
+use co_circom_snarks::VerificationError;
+#[cfg(feature = "mpc")]
 use co_circom_snarks::{
-    SerializeableSharedRep3Input, SerializeableSharedRep3Witness, SharedWitness, VerificationError,
+    commitment::InputCommitment,
+    sharing_proof::{SharingOpening, SharingProof},
+    SerializeableSharedRep3Input, SerializeableSharedRep3Witness, SharedWitness,
 };
 use co_groth16::Groth16;
-use co_groth16::{Rep3CoGroth16, ShamirCoGroth16};
-use co_plonk::Rep3CoPlonk;
-use co_plonk::{Plonk, ShamirCoPlonk};
-use color_eyre::eyre::{self, eyre, Context, ContextCompat};
+#[cfg(feature = "mpc")]
+use co_groth16::{mpc::Rep3Groth16Driver, ProvingKey, Rep3CoGroth16, ShamirCoGroth16};
+use co_plonk::Plonk;
+#[cfg(feature = "mpc")]
+use co_plonk::{DomainCache, Rep3CoPlonk, ShamirCoPlonk};
+#[cfg(feature = "mpc")]
+use color_eyre::eyre::ContextCompat;
+use color_eyre::eyre::{self, eyre, Context};
+#[cfg(feature = "mpc")]
+use mpc_core::protocols::rep3::pvss::PvssKeyPair;
+#[cfg(feature = "mpc")]
 use mpc_core::protocols::{
     bridges::network::RepToShamirNetwork,
-    rep3::network::Rep3MpcNet,
-    shamir::{ShamirPreprocessing, ShamirProtocol},
+    rep3::network::{IoContext, Rep3MpcNet, TranscriptDigest},
+    shamir::{network::ShamirMpcNet, ShamirPreprocessing, ShamirProtocol},
 };
+#[cfg(feature = "mpc")]
 use mpc_core::protocols::{rep3::network::Rep3Network, shamir::ShamirPrimeFieldShare};
+#[cfg(feature = "mpc")]
+use mpc_net::{config::NetworkConfig, SessionGuard};
+#[cfg(feature = "mpc")]
+use std::io::{Read, Write};
 use std::time::Instant;
+#[cfg(feature = "mpc")]
+use std::{collections::BTreeMap, path::PathBuf};
+use std::{collections::HashMap, marker::PhantomData, path::Path};
 use std::{
     fs::File,
     io::{BufReader, BufWriter},
-    path::PathBuf,
     process::ExitCode,
 };
 use tracing::instrument;
 use tracing_subscriber::fmt::format::FmtSpan;
 
-fn install_tracing() {
+fn install_tracing(output_format: OutputFormat) {
+    use tracing_subscriber::fmt::writer::BoxMakeWriter;
     use tracing_subscriber::prelude::*;
     use tracing_subscriber::{fmt, EnvFilter};
 
+    // keep stdout free for the structured output and for files written to `-`
+    let stdio = std::env::args_os().skip(1).any(|arg| arg == storage::STDIO);
+    let writer = match output_format {
+        OutputFormat::Text if !stdio => BoxMakeWriter::new(std::io::stdout),
+        _ => BoxMakeWriter::new(std::io::stderr),
+    };
     let fmt_layer = fmt::layer()
         .with_target(false)
         .with_line_number(false)
-        .with_span_events(FmtSpan::CLOSE | FmtSpan::ENTER);
+        .with_span_events(FmtSpan::CLOSE | FmtSpan::ENTER)
+        .with_writer(writer);
     let filter_layer = EnvFilter::try_from_default_env()
         .or_else(|_| EnvFilter::try_new("info"))
         .unwrap();
@@ -72,9 +176,39 @@ fn install_tracing() {
         .init();
 }
 
+/// Installs the tracing subscriber for the `--tui` monitor. The log lines are buffered in the metrics instead of being written to the terminal.
+#[cfg(feature = "tui")]
+fn install_monitor_tracing(metrics: SessionMetrics) {
+    use tracing_subscriber::prelude::*;
+    use tracing_subscriber::{fmt, EnvFilter};
+
+    let filter_layer = EnvFilter::try_from_default_env()
+        .or_else(|_| EnvFilter::try_new("info"))
+        .unwrap();
+    let fmt_layer = fmt::layer()
+        .with_target(false)
+        .with_line_number(false)
+        .with_ansi(false)
+        .with_span_events(FmtSpan::CLOSE | FmtSpan::ENTER)
+        .with_writer(metrics.clone())
+        .with_filter(filter_layer);
+
+    tracing_subscriber::registry()
+        .with(fmt_layer)
+        .with(monitor::MetricsLayer::new(metrics).with_filter(monitor::metrics_filter()))
+        .init();
+}
+
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
 struct Cli {
+    /// The format of the output. With `json`, a single JSON object with the schema version, the status, the written files, and the results of the command is written to stdout and the log lines are written to stderr
+    #[arg(long, value_enum, global = true, default_value_t = OutputFormat::Text)]
+    output_format: OutputFormat,
+    /// Shows the progress of the session, the bytes exchanged with the other parties, and their liveness in an interactive terminal UI. Only supported by `generate-witness` and `generate-proof`
+    #[cfg(feature = "tui")]
+    #[arg(long, global = true)]
+    tui: bool,
     #[command(subcommand)]
     command: Commands,
 }
@@ -82,541 +216,2229 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Splits an existing witness file generated by circom into secret shares for use in MPC
+    #[cfg(feature = "mpc")]
     SplitWitness(SplitWitnessCli),
     /// Splits a JSON input file into secret shares for use in MPC
+    #[cfg(feature = "mpc")]
     SplitInput(SplitInputCli),
     /// Merge multiple shared inputs received from multiple parties into a single one
+    #[cfg(feature = "mpc")]
     MergeInputShares(MergeInputSharesCli),
+    /// Generates a key pair for receiving input shares published with PVSS
+    #[cfg(feature = "mpc")]
+    GeneratePvssKey(GeneratePvssKeyCli),
+    /// Verifies an input shared with PVSS and decrypts the input share of this party
+    #[cfg(feature = "mpc")]
+    DecryptInputShare(DecryptInputShareCli),
+    /// Compiles a circuit to MPC VM bytecode once, so that all parties can reuse it for the witness generation
+    #[cfg(feature = "mpc")]
+    Compile(CompileCli),
     /// Evaluates the extended witness generation for the specified circuit and input share in MPC
+    #[cfg(feature = "mpc")]
     GenerateWitness(GenerateWitnessCli),
     /// Translates the witness generated with one MPC protocol to a witness for a different one
+    #[cfg(feature = "mpc")]
     TranslateWitness(TranslateWitnessCli),
+    /// Computes statistics about a witness share file, which help to debug wrongly shared witnesses without reconstructing them
+    #[cfg(feature = "mpc")]
+    WitnessStats(WitnessStatsCli),
+    /// Checks a witness share against the proof of correct sharing of the splitting party, without reconstructing the witness
+    #[cfg(feature = "mpc")]
+    VerifyWitnessShare(VerifyWitnessShareCli),
+    /// Upgrades a REP3 input or witness share file written by an older release to the current format
+    #[cfg(feature = "mpc")]
+    MigrateShares(MigrateSharesCli),
+    /// Prints the structure of an input or witness share file, e.g., the names and lengths of the inputs, the public values, the protocol, the curve, and the version, without revealing the shares
+    #[cfg(feature = "mpc")]
+    InspectShare(InspectShareCli),
     /// Evaluates the prover algorithm for the specified circuit and witness share in MPC
+    #[cfg(feature = "mpc")]
     GenerateProof(GenerateProofCli),
+    /// Evaluates the prover algorithm for many independent circuits and witness shares in MPC, running several proof generations concurrently
+    #[cfg(feature = "mpc")]
+    GenerateProofBatch(GenerateProofBatchCli),
+    /// Reconstructs the input or witness shares of all parties and generates the proof locally without MPC, e.g., for testing or as a correctness oracle for the MPC proof generation
+    #[cfg(feature = "mpc")]
+    ProveLocal(ProveLocalCli),
     /// Verification of a circom proof.
     Verify(VerifyCli),
+    /// Generates a long-term key pair for signing proofs
+    #[cfg(feature = "mpc")]
+    GenerateSigningKey(GenerateSigningKeyCli),
+    /// Verifies the signatures of the parties on a proof against a committee descriptor
+    #[cfg(feature = "mpc")]
+    VerifySignatures(VerifySignaturesCli),
+    /// Cross-checks the session transcripts of all parties, see `generate-proof --transcript`, e.g., for compliance reviews after the fact
+    #[cfg(feature = "mpc")]
+    Audit(AuditCli),
+    /// Measures the throughput of the field and curve arithmetic on this machine and reports whether the assembly backend is enabled
+    Calibrate(CalibrateCli),
+    /// Recommends the MPC protocol, the arithmetic-to-binary conversion, and the number of threads for a circuit and a network, and reports the projected costs
+    #[cfg(feature = "mpc")]
+    Recommend(RecommendCli),
+    /// Deletes the artifacts of finished sessions according to a retention policy, e.g., keeps the proofs and securely deletes the shares
+    Cleanup(CleanupCli),
+    /// Serves an HTTP API that verifies proofs against a registry of verification keys
+    #[cfg(feature = "verify-server")]
+    VerifyServer(VerifyServerCli),
 }
 
 fn main() -> color_eyre::Result<ExitCode> {
-    install_tracing();
+    let matches = Cli::command().get_matches();
+    let args = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    let output_format = args.output_format;
+    #[cfg(feature = "tui")]
+    let monitor = {
+        let command = matches.subcommand_name().unwrap_or_default();
+        let supported = matches!(command, "generate-witness" | "generate-proof");
+        if args.tui && supported {
+            let metrics = SessionMetrics::new(command);
+            install_monitor_tracing(metrics.clone());
+            Some(Monitor::start(metrics).context("while starting the monitor")?)
+        } else {
+            install_tracing(output_format);
+            if args.tui {
+                tracing::warn!("--tui is not supported by {command}, ignoring it");
+            }
+            None
+        }
+    };
+    #[cfg(not(feature = "tui"))]
+    install_tracing(output_format);
+    #[cfg(feature = "mpc")]
     rustls::crypto::aws_lc_rs::default_provider()
         .install_default()
         .map_err(|_| eyre!("Could not install default rustls crypto provider"))?;
-    let args = Cli::parse();
 
-    match args.command {
+    let start = Instant::now();
+    let result = run_command(args.command, output_format);
+    #[cfg(feature = "tui")]
+    if let Some(monitor) = monitor {
+        monitor.stop().context("while stopping the monitor")?;
+    }
+    match output_format {
+        OutputFormat::Text => result.map(|output| output.exit_code()),
+        OutputFormat::Json => {
+            let command = matches.subcommand_name().unwrap_or_default();
+            let report = CommandReport::new(command, start.elapsed(), result);
+            println!(
+                "{}",
+                serde_json::to_string(&report).context("while serializing the output")?
+            );
+            Ok(report.exit_code())
+        }
+    }
+}
+
+fn run_command(
+    command: Commands,
+    output_format: OutputFormat,
+) -> color_eyre::Result<CommandOutput> {
+    let registry = CurveRegistry::new();
+    match command {
+        #[cfg(feature = "mpc")]
         Commands::SplitWitness(cli) => {
             let config = SplitWitnessConfig::parse(cli).context("while parsing config")?;
-            match config.curve {
-                MPCCurve::BN254 => run_split_witness::<Bn254>(config),
-                MPCCurve::BLS12_381 => run_split_witness::<Bls12_381>(config),
-            }
+            run_split_witness(registry.get(config.curve)?, config)
         }
+        #[cfg(feature = "mpc")]
         Commands::SplitInput(cli) => {
             let config = SplitInputConfig::parse(cli).context("while parsing config")?;
-            match config.curve {
-                MPCCurve::BN254 => run_split_input::<Bn254>(config),
-                MPCCurve::BLS12_381 => run_split_input::<Bls12_381>(config),
-            }
+            run_split_input(registry.get(config.curve)?, config)
         }
+        #[cfg(feature = "mpc")]
         Commands::MergeInputShares(cli) => {
             let config = MergeInputSharesConfig::parse(cli).context("while parsing config")?;
-            match config.curve {
-                MPCCurve::BN254 => run_merge_input_shares::<Bn254>(config),
-                MPCCurve::BLS12_381 => run_merge_input_shares::<Bls12_381>(config),
-            }
+            run_merge_input_shares(registry.get(config.curve)?, config)
+        }
+        #[cfg(feature = "mpc")]
+        Commands::GeneratePvssKey(cli) => {
+            let config = GeneratePvssKeyConfig::parse(cli).context("while parsing config")?;
+            run_generate_pvss_key(registry.get(config.curve)?, config)
+        }
+        #[cfg(feature = "mpc")]
+        Commands::DecryptInputShare(cli) => {
+            let config = DecryptInputShareConfig::parse(cli).context("while parsing config")?;
+            run_decrypt_input_share(registry.get(config.curve)?, config)
+        }
+        #[cfg(feature = "mpc")]
+        Commands::Compile(cli) => {
+            let config = CompileConfig::parse(cli).context("while parsing config")?;
+            run_compile(registry.get(config.curve)?, config)
         }
+        #[cfg(feature = "mpc")]
         Commands::GenerateWitness(cli) => {
             let config = GenerateWitnessConfig::parse(cli).context("while parsing config")?;
-            match config.curve {
-                MPCCurve::BN254 => run_generate_witness::<Bn254>(config),
-                MPCCurve::BLS12_381 => run_generate_witness::<Bls12_381>(config),
-            }
+            run_generate_witness(registry.get(config.curve)?, config)
         }
+        #[cfg(feature = "mpc")]
         Commands::TranslateWitness(cli) => {
             let config = TranslateWitnessConfig::parse(cli).context("while parsing config")?;
-            match config.curve {
-                MPCCurve::BN254 => run_translate_witness::<Bn254>(config),
-                MPCCurve::BLS12_381 => run_translate_witness::<Bls12_381>(config),
-            }
+            run_translate_witness(registry.get(config.curve)?, config)
+        }
+        #[cfg(feature = "mpc")]
+        Commands::WitnessStats(cli) => {
+            let config = WitnessStatsConfig::parse(cli).context("while parsing config")?;
+            run_witness_stats(registry.get(config.curve)?, config, output_format)
+        }
+        #[cfg(feature = "mpc")]
+        Commands::VerifyWitnessShare(cli) => {
+            let config = VerifyWitnessShareConfig::parse(cli).context("while parsing config")?;
+            run_verify_witness_share(registry.get(config.curve)?, config)
         }
+        #[cfg(feature = "mpc")]
+        Commands::MigrateShares(cli) => {
+            let config = MigrateSharesConfig::parse(cli).context("while parsing config")?;
+            run_migrate_shares(registry.get(config.curve)?, config)
+        }
+        #[cfg(feature = "mpc")]
+        Commands::InspectShare(cli) => {
+            let config = InspectShareConfig::parse(cli).context("while parsing config")?;
+            run_inspect_share(config, &registry, output_format)
+        }
+        #[cfg(feature = "mpc")]
         Commands::GenerateProof(cli) => {
             let config = GenerateProofConfig::parse(cli).context("while parsing config")?;
-            match config.curve {
-                MPCCurve::BN254 => run_generate_proof::<Bn254>(config),
-                MPCCurve::BLS12_381 => run_generate_proof::<Bls12_381>(config),
-            }
+            run_generate_proof(registry.get(config.curve)?, config)
+        }
+        #[cfg(feature = "mpc")]
+        Commands::GenerateProofBatch(cli) => {
+            let config = GenerateProofBatchConfig::parse(cli).context("while parsing config")?;
+            run_generate_proof_batch(registry.get(config.curve)?, config)
+        }
+        #[cfg(feature = "mpc")]
+        Commands::ProveLocal(cli) => {
+            let config = ProveLocalConfig::parse(cli).context("while parsing config")?;
+            run_prove_local(registry.get(config.curve)?, config)
         }
         Commands::Verify(cli) => {
             let config = VerifyConfig::parse(cli).context("while parsing config")?;
-            match config.curve {
-                MPCCurve::BN254 => run_verify::<Bn254>(config),
-                MPCCurve::BLS12_381 => run_verify::<Bls12_381>(config),
-            }
+            run_verify(registry.get(config.curve)?, config)
+        }
+        #[cfg(feature = "mpc")]
+        Commands::GenerateSigningKey(cli) => {
+            let config = GenerateSigningKeyConfig::parse(cli).context("while parsing config")?;
+            run_generate_signing_key(config)
+        }
+        #[cfg(feature = "mpc")]
+        Commands::VerifySignatures(cli) => {
+            let config = VerifySignaturesConfig::parse(cli).context("while parsing config")?;
+            run_verify_signatures(registry.get(config.curve)?, config)
+        }
+        #[cfg(feature = "mpc")]
+        Commands::Audit(cli) => {
+            let config = AuditConfig::parse(cli).context("while parsing config")?;
+            run_audit(config)
+        }
+        Commands::Calibrate(cli) => {
+            let config = CalibrateConfig::parse(cli).context("while parsing config")?;
+            run_calibrate(registry.get(config.curve)?, config, output_format)
+        }
+        #[cfg(feature = "mpc")]
+        Commands::Recommend(cli) => {
+            let config = RecommendConfig::parse(cli).context("while parsing config")?;
+            run_recommend(registry.get(config.curve)?, config, output_format)
+        }
+        Commands::Cleanup(cli) => {
+            let config = CleanupConfig::parse(cli).context("while parsing config")?;
+            run_cleanup(config)
+        }
+        #[cfg(feature = "verify-server")]
+        Commands::VerifyServer(cli) => {
+            let config = VerifyServerConfig::parse(cli).context("while parsing config")?;
+            run_verify_server(config)
         }
     }
 }
 
-#[instrument(level = "debug", skip(config))]
-fn run_split_witness<P: Pairing + CircomArkworksPairingBridge>(
-    config: SplitWitnessConfig,
-) -> color_eyre::Result<ExitCode>
-where
-    P::ScalarField: CircomArkworksPrimeFieldBridge,
-    P::BaseField: CircomArkworksPrimeFieldBridge,
-{
-    let witness_path = config.witness;
-    let r1cs = config.r1cs;
-    let protocol = config.protocol;
-    let out_dir = config.out_dir;
-    let t = config.threshold;
-    let n = config.num_parties;
+/// The curve-specific operations of the CLI. The commands are compiled once and call the operations of the curve selected at runtime, see [CurveRegistry], so only these operations and the MPC protocols below them are monomorphized per curve. Values of the curve cross this boundary as [Encoded] output files, JSON values, or the public inputs in the format of snarkjs.
+trait CurveOps: Send + Sync {
+    /// Shares the witness of a circom witness file, see `split-witness`.
+    #[cfg(feature = "mpc")]
+    fn share_witness(&self, config: &SplitWitnessConfig) -> color_eyre::Result<SplitWitness>;
+    /// Shares an input file for REP3, see `split-input`.
+    #[cfg(feature = "mpc")]
+    fn share_input(
+        &self,
+        config: &SplitInputConfig,
+        base_name: &str,
+        bit_widths: BTreeMap<String, usize>,
+    ) -> color_eyre::Result<SplitInput>;
+    /// Shares an input file with PVSS for the public keys of the parties, see `split-input --pvss-keys`.
+    #[cfg(feature = "mpc")]
+    fn share_input_pvss(
+        &self,
+        config: &SplitInputConfig,
+        bit_widths: BTreeMap<String, usize>,
+    ) -> color_eyre::Result<Encoded>;
+    /// Merges the input shares of several data owners. The public inputs of the shares are checked against each other and against the public inputs file, if one is passed.
+    #[cfg(feature = "mpc")]
+    fn merge_input_shares(
+        &self,
+        inputs: &[PathBuf],
+        public_inputs: Option<&Path>,
+    ) -> color_eyre::Result<Encoded>;
+    /// Generates a PVSS key pair. Returns the key pair and the public key.
+    #[cfg(feature = "mpc")]
+    fn generate_pvss_key(&self) -> color_eyre::Result<(Encoded, Encoded)>;
+    /// Verifies an input published with PVSS and decrypts the input share of the key pair.
+    #[cfg(feature = "mpc")]
+    fn decrypt_input_share(&self, published: &Path, key: &Path) -> color_eyre::Result<Encoded>;
+    /// Compiles a circuit to MPC VM bytecode, see `compile`.
+    #[cfg(feature = "mpc")]
+    fn compile_circuit(&self, config: CompileConfig) -> color_eyre::Result<CircuitHash>;
+    /// Checks the input share against the commitments of the data owners and extends it to a witness share in MPC.
+    #[cfg(feature = "mpc")]
+    fn generate_witness(
+        &self,
+        input_share: (Box<dyn Read + Send>, u64),
+        net: Rep3MpcNet,
+        config: GenerateWitnessConfig,
+    ) -> color_eyre::Result<Encoded>;
+    /// Translates a REP3 witness share to a Shamir witness share in MPC.
+    #[cfg(feature = "mpc")]
+    fn translate_witness(
+        &self,
+        witness: (Box<dyn Read + Send>, u64),
+        net: Rep3MpcNet,
+    ) -> color_eyre::Result<Encoded>;
+    /// Computes the statistics of a witness share file, see [co_circom::witness_share_stats].
+    #[cfg(feature = "mpc")]
+    fn witness_share_stats(
+        &self,
+        witness: &Path,
+        protocol: MPCProtocol,
+    ) -> color_eyre::Result<WitnessShareStats>;
+    /// Checks a witness share against the proof of correct sharing. Returns the id of the party the share belongs to.
+    #[cfg(feature = "mpc")]
+    fn verify_witness_share(&self, config: &VerifyWitnessShareConfig) -> color_eyre::Result<usize>;
+    /// Migrates a share file to the current format, see [co_circom::migrate].
+    #[cfg(feature = "mpc")]
+    fn migrate_share_file(&self, config: &MigrateSharesConfig) -> color_eyre::Result<Vec<u8>>;
+    /// Summarizes a share file, see [co_circom::inspect].
+    #[cfg(feature = "mpc")]
+    fn inspect_share(
+        &self,
+        share: &Path,
+        curve: MPCCurve,
+        protocol: Option<MPCProtocol>,
+        kind: Option<ShareFileKind>,
+    ) -> color_eyre::Result<ShareInspection>;
+    /// Generates a Groth16 or Plonk proof in MPC. The network is established through the `session` once the proving key is parsed.
+    #[cfg(feature = "mpc")]
+    fn prove(
+        &self,
+        session: &mut ProverSession,
+        args: ProveArgs<'_>,
+    ) -> color_eyre::Result<GeneratedProof>;
+    /// Generates a proof with the proof system plugin `name`, see [proof_system_registry].
+    #[cfg(feature = "mpc")]
+    fn prove_plugin(&self, name: &str, args: PluginProveArgs)
+        -> color_eyre::Result<GeneratedProof>;
+    /// Generates the Groth16 proof of a job of a batch in MPC over the network of its slot.
+    #[cfg(feature = "mpc")]
+    fn prove_batch_job(
+        &self,
+        slot: &mut IoContext<Rep3MpcNet>,
+        job: &BatchJob,
+    ) -> color_eyre::Result<GeneratedProof>;
+    /// Reconstructs the witness from the input or witness shares of all parties and generates the proof without MPC.
+    #[cfg(feature = "mpc")]
+    fn prove_local(&self, config: &ProveLocalConfig) -> color_eyre::Result<GeneratedProof>;
+    /// Runs the checks of a rehearsal after the configuration was checked, see [co_circom::rehearsal].
+    #[cfg(feature = "mpc")]
+    fn rehearse(&self, report: &mut RehearsalReport, setup: RehearsalSetup);
+    /// Verifies a proof against the public inputs in the format of snarkjs. The outer result fails if the files can not be parsed.
+    fn verify(
+        &self,
+        proof_system: &ProofSystem,
+        proof: &Path,
+        vk: &Path,
+        public_inputs: Vec<String>,
+    ) -> color_eyre::Result<Result<(), VerificationError>>;
+    /// Computes the binding of the elements of a Groth16 proof, see [signing::groth16_proof_binding].
+    #[cfg(feature = "mpc")]
+    fn groth16_proof_binding(&self, proof: &serde_json::Value) -> color_eyre::Result<String>;
+    /// Measures the arithmetic throughput of the curve, see [co_circom::calibrate].
+    fn calibrate(&self, size: usize) -> color_eyre::Result<CalibrationReport>;
+    /// Reads an R1CS file and computes the structure of the circuit that drives the costs in MPC, see [recommend::profile].
+    #[cfg(feature = "mpc")]
+    fn circuit_profile(&self, r1cs: &Path) -> color_eyre::Result<CircuitProfile>;
+}
+
+/// A value of the curve returned by a [CurveOps] operation, which is written to an output file by the command without knowing its type.
+#[cfg(feature = "mpc")]
+type Encoded = Box<dyn Fn(&mut dyn Write) -> color_eyre::Result<()>>;
+
+/// Defers the serialization of `value` with `encode` until the command writes it, see [Encoded].
+#[cfg(feature = "mpc")]
+fn encoded<T: 'static>(
+    value: T,
+    encode: impl Fn(&mut dyn Write, &T) -> color_eyre::Result<()> + 'static,
+) -> Encoded {
+    Box::new(move |writer| encode(writer, &value))
+}
 
-    file_utils::check_file_exists(&witness_path)?;
-    file_utils::check_file_exists(&r1cs)?;
-    file_utils::check_dir_exists(&out_dir)?;
+/// The shares created by [CurveOps::share_witness].
+#[cfg(feature = "mpc")]
+struct SplitWitness {
+    /// The witness shares of the parties
+    shares: Vec<Encoded>,
+    /// The proof of correct sharing and the openings with the ids of their parties, if requested
+    sharing_proof: Option<(Encoded, Vec<(usize, Encoded)>)>,
+}
 
-    // read the circom witness file
-    let witness_file =
-        BufReader::new(File::open(&witness_path).context("while opening witness file")?);
-    let witness = Witness::<P::ScalarField>::from_reader(witness_file)
-        .context("while parsing witness file")?;
+/// The shares created by [CurveOps::share_input].
+#[cfg(feature = "mpc")]
+struct SplitInput {
+    /// The input shares of the parties
+    shares: Vec<Encoded>,
+    /// The commitment to the inputs, if requested
+    commitment: Option<Encoded>,
+}
 
-    // read the circom r1cs file
-    let r1cs_file = BufReader::new(File::open(&r1cs).context("while opening r1cs file")?);
-    let r1cs = R1CS::<P>::from_reader(r1cs_file).context("while parsing r1cs file")?;
+/// A proof generated by a [CurveOps] operation.
+#[cfg(feature = "mpc")]
+struct GeneratedProof {
+    /// The proof as written to the proof file
+    proof: serde_json::Value,
+    /// The public inputs without the constant 1, see [public_input_strings]
+    public_inputs: Vec<String>,
+    /// The digest of the protocol transcript, if the proof system and the MPC protocol record one
+    transcript_digest: Option<TranscriptDigest>,
+}
 
-    let mut rng = rand::thread_rng();
+/// The arguments of [CurveOps::prove].
+#[cfg(feature = "mpc")]
+struct ProveArgs<'a> {
+    proof_system: &'a ProofSystem,
+    protocol: MPCProtocol,
+    threshold: usize,
+    /// The witness share file and its size
+    witness: (Box<dyn Read + Send>, u64),
+    zkey: &'a Path,
+    /// The R1CS file of the linear signals eliminated during the witness generation, see `--linear-signals`
+    linear_signals: Option<&'a Path>,
+    check_witness_consistency: bool,
+    insecure_non_hiding: bool,
+    /// The MSM memory budget in bytes, see `--msm-memory-budget-mb`
+    msm_memory_budget: Option<usize>,
+    domain_cache: Option<DomainCache>,
+}
 
-    match protocol {
-        MPCProtocol::REP3 => {
-            if t != 1 {
-                return Err(eyre!("REP3 only allows the threshold to be 1"));
-            }
-            if n != 3 {
-                return Err(eyre!("REP3 only allows the number of parties to be 3"));
-            }
-            // create witness shares
-            let start = Instant::now();
-            let shares = SerializeableSharedRep3Witness::<_, SeedRng>::share_rep3(
-                witness,
-                r1cs.num_inputs,
-                &mut rng,
-                config.seeded,
-                config.additive,
-            );
-            let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
-            tracing::info!("Sharing took {} ms", duration_ms);
-
-            // write out the shares to the output directory
-            let base_name = witness_path
-                .file_name()
-                .context("we have a file name")?
-                .to_str()
-                .context("witness file name is not valid UTF-8")?;
-            for (i, share) in shares.iter().enumerate() {
-                let path = out_dir.join(format!("{}.{}.shared", base_name, i));
-                let out_file =
-                    BufWriter::new(File::create(&path).context("while creating output file")?);
-                bincode::serialize_into(out_file, share)
-                    .context("while serializing witness share")?;
-                tracing::info!("Wrote witness share {} to file {}", i, path.display());
-            }
-        }
-        MPCProtocol::SHAMIR => {
-            // create witness shares
-            let start = Instant::now();
-            let shares =
-                SharedWitness::<P::ScalarField, ShamirPrimeFieldShare<P::ScalarField>>::share_shamir(
-                    witness,
-                    r1cs.num_inputs,
-                    t,
-                    n,
-                    &mut rng,
-                );
-            let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
-            tracing::info!("Sharing took {} ms", duration_ms);
-
-            // write out the shares to the output directory
-            let base_name = witness_path
-                .file_name()
-                .context("we have a file name")?
-                .to_str()
-                .context("witness file name is not valid UTF-8")?;
-            for (i, share) in shares.iter().enumerate() {
-                let path = out_dir.join(format!("{}.{}.shared", base_name, i));
-                let out_file =
-                    BufWriter::new(File::create(&path).context("while creating output file")?);
-                bincode::serialize_into(out_file, share)
-                    .context("while serializing witness share")?;
-                tracing::info!("Wrote witness share {} to file {}", i, path.display());
-            }
-        }
-    }
-    tracing::info!("Split witness into shares successfully");
-    Ok(ExitCode::SUCCESS)
+/// The curve-independent part of a proof generation in MPC. Connects to the other parties, warms up the network, proves the possession of the signing keys, and records the phases of the session transcript.
+#[cfg(feature = "mpc")]
+struct ProverSession {
+    network_config: Option<NetworkConfig>,
+    recorder: TranscriptRecorder,
+    warm_up: Option<WarmUp>,
+    warm_up_report: Option<WarmUpReport>,
+    possession: Option<(Committee, SigningKeyPair)>,
+    guard: Option<SessionGuard>,
 }
 
-#[instrument(level = "debug", skip(config))]
-fn run_split_input<P: Pairing + CircomArkworksPairingBridge>(
-    config: SplitInputConfig,
-) -> color_eyre::Result<ExitCode>
-where
-    P::ScalarField: CircomArkworksPrimeFieldBridge,
-    P::BaseField: CircomArkworksPrimeFieldBridge,
-{
-    let input = config.input;
-    let circuit = config.circuit;
-    let protocol = config.protocol;
-    let out_dir = config.out_dir;
+#[cfg(feature = "mpc")]
+impl ProverSession {
+    fn take_network_config(&mut self) -> color_eyre::Result<NetworkConfig> {
+        self.network_config
+            .take()
+            .context("the session is already connected")
+    }
 
-    if protocol != MPCProtocol::REP3 {
-        return Err(eyre!(
-            "Only REP3 protocol is supported for splitting inputs"
-        ));
+    fn connect_rep3(&mut self) -> color_eyre::Result<Rep3MpcNet> {
+        let mut mpc_net = Rep3MpcNet::new(self.take_network_config()?)?;
+        // aborts the session at all parties if the command fails or the user presses Ctrl-C
+        self.guard = Some(mpc_net.session_guard().abort_on_ctrl_c());
+        self.recorder.attach(mpc_net.message_counter());
+        self.recorder.finish_phase("connect");
+        if let Some(warm_up) = self.warm_up.take() {
+            self.warm_up_report = Some(warm_up.finish_rep3(&mut mpc_net)?);
+            self.recorder.finish_phase("warm-up");
+        }
+        if let Some((committee, key)) = &self.possession {
+            signing::prove_possession_rep3(committee, key, &mut mpc_net)
+                .context("while proving possession of the signing keys")?;
+            tracing::info!("All parties proved possession of their signing keys");
+            self.recorder.finish_phase("proof of possession");
+        }
+        Ok(mpc_net)
     }
-    file_utils::check_file_exists(&input)?;
-    let circuit_path = PathBuf::from(&circuit);
-    file_utils::check_file_exists(&circuit_path)?;
-    file_utils::check_dir_exists(&out_dir)?;
 
-    let start = Instant::now();
-    let shares = co_circom::split_input::<P>(
-        input.clone(),
-        circuit_path,
-        config.compiler,
-        config.seeded,
-        config.additive,
-    )?;
-    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
-    tracing::info!("Sharing took {} ms", duration_ms);
+    fn connect_shamir(&mut self) -> color_eyre::Result<ShamirMpcNet> {
+        let mut mpc_net = ShamirMpcNet::new(self.take_network_config()?)?;
+        // aborts the session at all parties if the command fails or the user presses Ctrl-C
+        self.guard = Some(mpc_net.session_guard().abort_on_ctrl_c());
+        self.recorder.attach(mpc_net.message_counter());
+        self.recorder.finish_phase("connect");
+        if let Some(warm_up) = self.warm_up.take() {
+            self.warm_up_report = Some(warm_up.finish_shamir(&mut mpc_net)?);
+            self.recorder.finish_phase("warm-up");
+        }
+        if let Some((committee, key)) = &self.possession {
+            signing::prove_possession_shamir(committee, key, &mut mpc_net)
+                .context("while proving possession of the signing keys")?;
+            tracing::info!("All parties proved possession of their signing keys");
+            self.recorder.finish_phase("proof of possession");
+        }
+        Ok(mpc_net)
+    }
 
-    // write out the shares to the output directory
-    let base_name = input
-        .file_name()
-        .context("we have a file name")?
-        .to_str()
-        .context("input file name is not valid UTF-8")?;
-    for (i, share) in shares.iter().enumerate() {
-        let path = out_dir.join(format!("{}.{}.shared", base_name, i));
-        let out_file = BufWriter::new(File::create(&path).context("while creating output file")?);
-        bincode::serialize_into(out_file, share).context("while serializing witness share")?;
-        tracing::info!("Wrote input share {} to file {}", i, path.display());
+    /// Marks the session as finished, so that it is not aborted at the other parties if this party fails afterwards.
+    fn finish(&mut self) {
+        if let Some(guard) = self.guard.take() {
+            guard.finish();
+        }
     }
-    tracing::info!("Split input into shares successfully");
-    Ok(ExitCode::SUCCESS)
 }
 
-#[instrument(level = "debug", skip(config))]
-fn run_merge_input_shares<P: Pairing + CircomArkworksPairingBridge>(
-    config: MergeInputSharesConfig,
-) -> color_eyre::Result<ExitCode>
+/// The setup of a rehearsal, which passed the checks of the configuration.
+#[cfg(feature = "mpc")]
+struct RehearsalSetup {
+    proof_system: ProofSystem,
+    protocol: MPCProtocol,
+    threshold: usize,
+    zkey: PathBuf,
+    fingerprint: RehearsalFingerprint,
+    network_config: NetworkConfig,
+}
+
+/// The [CurveOps] of the pairing `P`.
+struct CurveImpl<P>(PhantomData<P>);
+
+impl<P: Pairing + CircomArkworksPairingBridge> CurveOps for CurveImpl<P>
 where
     P::ScalarField: CircomArkworksPrimeFieldBridge,
     P::BaseField: CircomArkworksPrimeFieldBridge,
 {
-    let inputs = config.inputs;
-    let protocol = config.protocol;
-    let out = config.out;
+    #[cfg(feature = "mpc")]
+    fn share_witness(&self, config: &SplitWitnessConfig) -> color_eyre::Result<SplitWitness> {
+        // read the circom witness file
+        let witness_file = storage::open(&config.witness).context("while opening witness file")?;
+        let witness = Witness::<P::ScalarField>::from_reader(witness_file)
+            .context("while parsing witness file")?;
+
+        // read the circom r1cs file
+        let r1cs_file =
+            BufReader::new(File::open(&config.r1cs).context("while opening r1cs file")?);
+        let r1cs = R1CS::<P>::from_reader(r1cs_file).context("while parsing r1cs file")?;
+
+        let mut rng = rand::thread_rng();
+        // the plain witness is only kept around if the sharing is proven
+        let private_witness = config
+            .prove_sharing
+            .then(|| witness.values[r1cs.num_inputs.min(witness.values.len())..].to_vec());
+
+        let (shares, sharing_proof) = match config.protocol {
+            MPCProtocol::REP3 => {
+                let mut shares = SerializeableSharedRep3Witness::<_, SeedRng>::share_rep3(
+                    witness,
+                    r1cs.num_inputs,
+                    &mut rng,
+                    config.seeded,
+                    config.additive,
+                );
+                for share in shares.iter_mut() {
+                    share.usage_policy = config.usage_policy.clone();
+                }
+                let sharing_proof = private_witness
+                    .map(|witness| co_circom::prove_sharing_rep3(&r1cs, &witness, &shares))
+                    .transpose()?;
+                let shares = shares
+                    .into_iter()
+                    .map(|share| {
+                        encoded(share, |writer, share| {
+                            co_circom::write_witness_share_rep3(writer, share)
+                                .context("while serializing witness share")
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                (shares, sharing_proof)
+            }
+            MPCProtocol::SHAMIR => {
+                let shares = SharedWitness::<
+                    P::ScalarField,
+                    ShamirPrimeFieldShare<P::ScalarField>,
+                >::share_shamir(
+                    witness,
+                    r1cs.num_inputs,
+                    config.threshold,
+                    config.num_parties,
+                    &mut rng,
+                );
+                let sharing_proof = private_witness
+                    .map(|witness| {
+                        co_circom::prove_sharing_shamir(&r1cs, &witness, config.threshold, &shares)
+                    })
+                    .transpose()?;
+                let shares = shares
+                    .into_iter()
+                    .map(|share| {
+                        encoded(share, |writer, share| {
+                            co_circom::write_witness_share_shamir(writer, share)
+                                .context("while serializing witness share")
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                (shares, sharing_proof)
+            }
+        };
+        let sharing_proof = sharing_proof.map(|(proof, openings)| {
+            let openings = openings
+                .into_iter()
+                .map(|opening| {
+                    let party_id = opening.party_id;
+                    let opening = encoded(opening, |writer, opening| {
+                        bincode::serialize_into(writer, opening)
+                            .context("while serializing sharing opening")
+                    });
+                    (party_id, opening)
+                })
+                .collect();
+            let proof = encoded(proof, |writer, proof| {
+                bincode::serialize_into(writer, proof).context("while serializing sharing proof")
+            });
+            (proof, openings)
+        });
+        Ok(SplitWitness {
+            shares,
+            sharing_proof,
+        })
+    }
 
-    if protocol != MPCProtocol::REP3 {
-        return Err(eyre!(
-            "Only REP3 protocol is supported for merging input shares"
-        ));
+    #[cfg(feature = "mpc")]
+    fn share_input(
+        &self,
+        config: &SplitInputConfig,
+        base_name: &str,
+        bit_widths: BTreeMap<String, usize>,
+    ) -> color_eyre::Result<SplitInput> {
+        let mut shares = co_circom::split_input::<P>(
+            config.input.clone(),
+            PathBuf::from(&config.circuit),
+            config.compiler.clone(),
+            config.seeded,
+            config.additive,
+            bit_widths,
+        )?;
+        for share in shares.iter_mut() {
+            share.usage_policy = config.usage_policy.clone();
+        }
+        let commitment = if config.commit.is_empty() {
+            None
+        } else {
+            let commitment = co_circom::commit_input_shares::<P>(
+                &mut shares,
+                base_name,
+                &config.commit,
+                config.commitment_public_input.as_deref(),
+            )?;
+            Some(encoded(commitment, |writer, commitment| {
+                serde_json::to_writer_pretty(writer, commitment)
+                    .context("while serializing commitment")
+            }))
+        };
+        let shares = shares
+            .into_iter()
+            .map(|share| {
+                encoded(share, |writer, share| {
+                    bincode::serialize_into(writer, share)
+                        .context("while serializing witness share")
+                })
+            })
+            .collect();
+        Ok(SplitInput { shares, commitment })
     }
 
-    if inputs.len() < 2 {
-        return Err(eyre!("Need at least two input shares to merge"));
+    #[cfg(feature = "mpc")]
+    fn share_input_pvss(
+        &self,
+        config: &SplitInputConfig,
+        bit_widths: BTreeMap<String, usize>,
+    ) -> color_eyre::Result<Encoded> {
+        let mut public_keys = Vec::with_capacity(3);
+        for path in &config.pvss_keys {
+            let reader = BufReader::new(File::open(path).context("while opening public key")?);
+            public_keys.push(
+                P::G1Affine::deserialize_compressed(reader)
+                    .context("while deserializing public key")?,
+            );
+        }
+        let public_keys = public_keys
+            .try_into()
+            .map_err(|_| eyre!("Need the PVSS public keys of exactly three parties"))?;
+
+        let mut published = co_circom::split_input_pvss::<P>(
+            config.input.clone(),
+            PathBuf::from(&config.circuit),
+            config.compiler.clone(),
+            bit_widths,
+            public_keys,
+        )?;
+        published.usage_policy = config.usage_policy.clone();
+        Ok(encoded(published, |writer, published| {
+            bincode::serialize_into(writer, published).context("while serializing published input")
+        }))
     }
-    for input in &inputs {
-        file_utils::check_file_exists(input)?;
+
+    #[cfg(feature = "mpc")]
+    fn merge_input_shares(
+        &self,
+        inputs: &[PathBuf],
+        public_inputs: Option<&Path>,
+    ) -> color_eyre::Result<Encoded> {
+        let public_inputs = public_inputs
+            .map(co_circom::parse_public_inputs::<P::ScalarField>)
+            .transpose()?;
+        let merged = merge_input_shares::<P::ScalarField>(inputs, public_inputs)?;
+        Ok(encoded(merged, |writer, merged| {
+            bincode::serialize_into(writer, merged).context("while serializing witness share")
+        }))
     }
 
-    merge_input_shares::<P::ScalarField>(inputs, out)?;
+    #[cfg(feature = "mpc")]
+    fn generate_pvss_key(&self) -> color_eyre::Result<(Encoded, Encoded)> {
+        let key = PvssKeyPair::<P::G1>::generate(&mut rand::thread_rng());
+        let public_key = encoded(key.public_key, |writer, public_key| {
+            public_key
+                .serialize_compressed(writer)
+                .context("while serializing public key")
+        });
+        let key = encoded(key, |writer, key| {
+            key.serialize_compressed(writer)
+                .context("while serializing key pair")
+        });
+        Ok((key, public_key))
+    }
 
-    Ok(ExitCode::SUCCESS)
-}
+    #[cfg(feature = "mpc")]
+    fn decrypt_input_share(&self, published: &Path, key: &Path) -> color_eyre::Result<Encoded> {
+        let key_file = BufReader::new(File::open(key).context("while opening key file")?);
+        let key = PvssKeyPair::<P::G1>::deserialize_compressed(key_file)
+            .context("while deserializing key pair")?;
+        let (input_file, input_size) =
+            file_utils::open_untrusted(published, file_utils::MAX_SHARE_FILE_SIZE)
+                .context("while opening published input")?;
+        let published: PvssSharedRep3Input<P> = co_circom::parse_published_input_pvss(
+            input_file,
+            input_size,
+            file_utils::MAX_SHARED_ELEMENTS,
+        )?;
 
-#[instrument(level = "debug", skip(config))]
-fn run_generate_witness<P: Pairing + CircomArkworksPairingBridge>(
-    config: GenerateWitnessConfig,
-) -> color_eyre::Result<ExitCode>
-where
-    P::ScalarField: CircomArkworksPrimeFieldBridge,
-    P::BaseField: CircomArkworksPrimeFieldBridge,
-{
-    let input = config.input.clone();
-    let circuit = config.circuit.clone();
-    let protocol = config.protocol;
-    let out = config.out.clone();
+        let share = co_circom::decrypt_input_share(&published, &key)?;
+        Ok(encoded(share, |writer, share| {
+            bincode::serialize_into(writer, share).context("while serializing input share")
+        }))
+    }
 
-    if protocol != MPCProtocol::REP3 {
-        return Err(eyre!(
-            "Only REP3 protocol is supported for merging input shares"
-        ));
+    #[cfg(feature = "mpc")]
+    fn compile_circuit(&self, config: CompileConfig) -> color_eyre::Result<CircuitHash> {
+        co_circom::compile_circuit::<P>(config.circuit, config.compiler, &config.out)
     }
-    file_utils::check_file_exists(&input)?;
-    let circuit_path = PathBuf::from(&circuit);
-    file_utils::check_file_exists(&circuit_path)?;
 
-    // connect to network
-    let network_config = config
-        .network
-        .to_owned()
-        .try_into()
-        .context("while converting network config")?;
-    let mut mpc_net = Rep3MpcNet::new(network_config).context("while connecting to network")?;
+    #[cfg(feature = "mpc")]
+    fn generate_witness(
+        &self,
+        (input_share_file, input_share_size): (Box<dyn Read + Send>, u64),
+        mut mpc_net: Rep3MpcNet,
+        config: GenerateWitnessConfig,
+    ) -> color_eyre::Result<Encoded> {
+        let mut commitments = Vec::with_capacity(config.commitment.len());
+        for path in config.commitment.iter() {
+            let commitment_file =
+                BufReader::new(File::open(path).context("while opening commitment file")?);
+            let commitment: InputCommitment<P::G1> = serde_json::from_reader(commitment_file)
+                .context("while parsing commitment file")?;
+            commitments.push(commitment);
+        }
 
-    // parse input shares
-    let input_share_file =
-        BufReader::new(File::open(&input).context("while opening input share file")?);
-    let input_share = co_circom::parse_shared_input(input_share_file, &mut mpc_net)
+        // parse input shares
+        let input_share = co_circom::parse_shared_input(
+            input_share_file,
+            &mut mpc_net,
+            input_share_size,
+            file_utils::MAX_SHARED_ELEMENTS,
+        )
         .context("while parsing input")?;
 
-    // Extend the witness
-    let result_witness_share =
-        co_circom::generate_witness_rep3::<P, SeedRng>(circuit, input_share, mpc_net, config)?;
-
-    // write result to output file
-    let out_file = BufWriter::new(std::fs::File::create(&out)?);
-    bincode::serialize_into(out_file, &result_witness_share)?;
-    tracing::info!("Witness successfully written to {}", out.display());
-    Ok(ExitCode::SUCCESS)
-}
-
-#[instrument(level = "debug", skip(config))]
-fn run_translate_witness<P: Pairing + CircomArkworksPairingBridge>(
-    config: TranslateWitnessConfig,
-) -> color_eyre::Result<ExitCode>
-where
-    P::ScalarField: CircomArkworksPrimeFieldBridge,
-    P::BaseField: CircomArkworksPrimeFieldBridge,
-{
-    let witness = config.witness;
-    let src_protocol = config.src_protocol;
-    let target_protocol = config.target_protocol;
-    let out = config.out;
+        // check the inputs against the commitments of the data owners
+        co_circom::verify_input_commitments::<P, _>(&input_share, &commitments, &mut mpc_net)?;
+        if !commitments.is_empty() {
+            tracing::info!("Verified {} input commitments", commitments.len());
+        }
 
-    if src_protocol != MPCProtocol::REP3 || target_protocol != MPCProtocol::SHAMIR {
-        return Err(eyre!("Only REP3 to SHAMIR translation is supported"));
+        // Extend the witness
+        let witness_share =
+            co_circom::generate_witness_rep3::<P, SeedRng>(input_share, mpc_net, config)?;
+        Ok(encoded(witness_share, |writer, witness_share| {
+            co_circom::write_witness_share_rep3(writer, witness_share)
+                .context("while serializing witness share")
+        }))
     }
-    file_utils::check_file_exists(&witness)?;
-
-    // parse witness shares
-    let witness_file =
-        BufReader::new(File::open(witness).context("trying to open witness share file")?);
-    let witness_share: SharedWitness<P::ScalarField, P::ScalarField> =
-        co_circom::parse_witness_share_rep3_as_additive(witness_file)?;
-
-    // connect to network
-    let network_config = config
-        .network
-        .to_owned()
-        .try_into()
-        .context("while converting network config")?;
-    let net = Rep3MpcNet::new(network_config).context("while connecting to network")?;
-    let id = usize::from(net.get_id());
 
-    // init MPC protocol
-    let threshold = 1;
-    let num_pairs = witness_share.witness.len();
-    let preprocessing = ShamirPreprocessing::new(threshold, net.to_shamir_net(), num_pairs)
-        .context("while shamir preprocessing")?;
-    let mut protocol = ShamirProtocol::from(preprocessing);
-    // Translate witness to shamir shares
-    let start = Instant::now();
-    let translated_witness = protocol
-        .translate_primefield_addshare_vec(witness_share.witness)
-        .context("while translating witness")?;
-    let shamir_witness_share: SharedWitness<P::ScalarField, ShamirPrimeFieldShare<P::ScalarField>> =
-        SharedWitness {
+    #[cfg(feature = "mpc")]
+    fn translate_witness(
+        &self,
+        (witness_file, witness_size): (Box<dyn Read + Send>, u64),
+        net: Rep3MpcNet,
+    ) -> color_eyre::Result<Encoded> {
+        let witness_share: SharedWitness<P::ScalarField, P::ScalarField> =
+            co_circom::parse_witness_share_rep3_as_additive(
+                witness_file,
+                witness_size,
+                file_utils::MAX_SHARED_ELEMENTS,
+            )?;
+
+        // init MPC protocol
+        let threshold = 1;
+        let num_pairs = witness_share.witness.len();
+        let preprocessing = ShamirPreprocessing::new(threshold, net.to_shamir_net(), num_pairs)
+            .context("while shamir preprocessing")?;
+        let mut protocol = ShamirProtocol::from(preprocessing);
+        // Translate witness to shamir shares
+        let translated_witness = protocol
+            .translate_primefield_addshare_vec(witness_share.witness)
+            .context("while translating witness")?;
+        let shamir_witness_share: SharedWitness<
+            P::ScalarField,
+            ShamirPrimeFieldShare<P::ScalarField>,
+        > = SharedWitness {
             public_inputs: witness_share.public_inputs,
             witness: translated_witness,
         };
-    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
-    tracing::info!("Party {}: Translating witness took {} ms", id, duration_ms);
-
-    // write result to output file
-    let out_file = BufWriter::new(std::fs::File::create(&out)?);
-    bincode::serialize_into(out_file, &shamir_witness_share)?;
-    tracing::info!("Witness successfully written to {}", out.display());
-    Ok(ExitCode::SUCCESS)
-}
-
-#[instrument(level = "debug", skip(config))]
-fn run_generate_proof<P: Pairing + CircomArkworksPairingBridge>(
-    config: GenerateProofConfig,
-) -> color_eyre::Result<ExitCode>
-where
-    P::ScalarField: CircomArkworksPrimeFieldBridge,
-    P::BaseField: CircomArkworksPrimeFieldBridge,
-{
-    let proof_system = config.proof_system;
-    let witness = config.witness;
-    let zkey = config.zkey;
-    let protocol = config.protocol;
-    let out = config.out;
-    let public_input_filename = config.public_input;
-    let t = config.threshold;
-
-    file_utils::check_file_exists(&witness)?;
-    file_utils::check_file_exists(&zkey)?;
+        Ok(encoded(shamir_witness_share, |writer, witness_share| {
+            co_circom::write_witness_share_shamir(writer, witness_share)
+                .context("while serializing witness share")
+        }))
+    }
 
-    // parse witness shares
-    let witness_file =
-        BufReader::new(File::open(witness).context("trying to open witness share file")?);
+    #[cfg(feature = "mpc")]
+    fn witness_share_stats(
+        &self,
+        witness: &Path,
+        protocol: MPCProtocol,
+    ) -> color_eyre::Result<WitnessShareStats> {
+        co_circom::witness_share_stats::<P::ScalarField>(witness, protocol)
+    }
 
-    // parse Circom zkey file
-    let zkey_file = File::open(zkey)?;
+    #[cfg(feature = "mpc")]
+    fn verify_witness_share(&self, config: &VerifyWitnessShareConfig) -> color_eyre::Result<usize> {
+        let r1cs_file =
+            BufReader::new(File::open(&config.r1cs).context("while opening r1cs file")?);
+        let r1cs = R1CS::<P>::from_reader(r1cs_file).context("while parsing r1cs file")?;
+        let (proof_file, proof_size) =
+            file_utils::open_untrusted(&config.sharing_proof, file_utils::MAX_SHARE_FILE_SIZE)
+                .context("while opening sharing proof file")?;
+        let proof: SharingProof<P> = file_utils::deserialize_untrusted(proof_file, proof_size)
+            .context("while parsing sharing proof file")?;
+        let (opening_file, opening_size) =
+            file_utils::open_untrusted(&config.opening, file_utils::MAX_SHARE_FILE_SIZE)
+                .context("while opening sharing opening file")?;
+        let opening: SharingOpening<P::ScalarField> =
+            file_utils::deserialize_untrusted(opening_file, opening_size)
+                .context("while parsing sharing opening file")?;
+
+        proof
+            .verify(&r1cs)
+            .context("the proof of correct sharing is invalid")?;
+        co_circom::verify_witness_share(&config.witness, config.protocol, &proof, &opening)
+            .context("the witness share does not match the proof of correct sharing")?;
+        Ok(opening.party_id)
+    }
 
-    let network_config = config
-        .network
-        .to_owned()
-        .try_into()
-        .context("while converting network config")?;
+    #[cfg(feature = "mpc")]
+    fn migrate_share_file(&self, config: &MigrateSharesConfig) -> color_eyre::Result<Vec<u8>> {
+        co_circom::migrate::migrate_share_file::<P::ScalarField>(
+            &config.input,
+            config.kind,
+            config.from,
+        )
+    }
 
-    let public_input = match proof_system {
-        ProofSystem::Groth16 => {
-            let zkey = Arc::new(
-                Groth16ZKey::<P>::from_reader(zkey_file, CheckElement::Yes)
-                    .context("reading zkey")?,
-            );
+    #[cfg(feature = "mpc")]
+    fn inspect_share(
+        &self,
+        share: &Path,
+        curve: MPCCurve,
+        protocol: Option<MPCProtocol>,
+        kind: Option<ShareFileKind>,
+    ) -> color_eyre::Result<ShareInspection> {
+        co_circom::inspect::inspect_share::<P::ScalarField>(share, curve, protocol, kind)
+    }
 
-            let (proof, public_input) = match protocol {
-                MPCProtocol::REP3 => {
-                    if t != 1 {
-                        return Err(eyre!("REP3 only allows the threshold to be 1"));
+    #[cfg(feature = "mpc")]
+    fn prove(
+        &self,
+        session: &mut ProverSession,
+        args: ProveArgs<'_>,
+    ) -> color_eyre::Result<GeneratedProof> {
+        let ProveArgs {
+            proof_system,
+            protocol,
+            threshold: t,
+            witness: (witness_file, witness_size),
+            zkey,
+            linear_signals,
+            check_witness_consistency,
+            insecure_non_hiding,
+            msm_memory_budget,
+            domain_cache,
+        } = args;
+
+        // parse Circom zkey file
+        let zkey_file = storage::open(zkey).context("while opening zkey")?;
+
+        // the linear signals eliminated from the witness share during the witness generation
+        let linear_signals = linear_signals
+            .map(co_circom::load_linear_signals::<P>)
+            .transpose()?;
+
+        // the digest of the protocol transcript, if the proof system and MPC protocol record one
+        let mut transcript_digest = None;
+        let (proof, public_input) = match proof_system {
+            ProofSystem::Groth16 => {
+                // with an MSM memory budget, the points of a local zkey are read on demand, so the zkey does not have to fit into memory
+                let zkey: Arc<dyn ProvingKey<P>> = if msm_memory_budget.is_some() && zkey.is_file()
+                {
+                    Arc::new(
+                        Groth16LazyZKey::<P>::from_path(zkey, CheckElement::Yes)
+                            .context("reading zkey")?,
+                    )
+                } else {
+                    Arc::new(
+                        Groth16ZKey::<P>::from_reader(zkey_file, CheckElement::Yes)
+                            .context("reading zkey")?,
+                    )
+                };
+                let num_public = zkey.n_public() + 1;
+                let num_vars = zkey.a_query().num_points();
+                let num_private = num_vars.checked_sub(num_public).context("invalid zkey")?;
+
+                let (proof, public_input) = match protocol {
+                    MPCProtocol::REP3 => {
+                        let mut mpc_net = session.connect_rep3()?;
+                        let mut witness_share = co_circom::parse_witness_share_rep3(
+                            witness_file,
+                            &mut mpc_net,
+                            witness_size,
+                            num_vars,
+                            proof_system,
+                        )?;
+                        if check_witness_consistency {
+                            witness_share
+                                .verify_consistency_rep3(&mut mpc_net)
+                                .context("while checking the consistency of the witness shares")?;
+                            tracing::info!("Witness shares of all parties are consistent");
+                        }
+                        if let Some(linear_signals) = &linear_signals {
+                            witness_share = co_circom::expand_linear_signals_rep3(
+                                witness_share,
+                                linear_signals,
+                                mpc_net.get_id(),
+                            )?;
+                        }
+                        check_witness_dimensions(&witness_share, num_public, num_private)?;
+                        session.recorder.finish_phase("witness share");
+                        let public_input = witness_share.public_inputs.clone();
+                        // connect to network
+                        let mut prover = Rep3CoGroth16::with_network(mpc_net)
+                            .context("while building prover")?;
+                        if let Some(budget) = msm_memory_budget {
+                            prover = prover.with_msm_memory_budget(budget);
+                        }
+
+                        // execute prover in MPC
+                        let proof = if insecure_non_hiding {
+                            prover.prove_insecure_non_hiding(zkey, witness_share)?
+                        } else {
+                            let (proof, digest) =
+                                prover.prove_with_transcript(zkey, witness_share)?;
+                            transcript_digest = digest;
+                            proof
+                        };
+                        (proof, public_input)
                     }
-
-                    let mut mpc_net = Rep3MpcNet::new(network_config)?;
-                    let witness_share =
-                        co_circom::parse_witness_share_rep3(witness_file, &mut mpc_net)?;
-                    let public_input = witness_share.public_inputs.clone();
-                    // connect to network
-                    let prover =
-                        Rep3CoGroth16::with_network(mpc_net).context("while building prover")?;
-
-                    // execute prover in MPC
-                    let proof = prover.prove(zkey, witness_share)?;
-                    (proof, public_input)
-                }
-                MPCProtocol::SHAMIR => {
-                    let witness_share = co_circom::parse_witness_share_shamir(witness_file)?;
-                    let public_input = witness_share.public_inputs.clone();
-
-                    // connect to network
-                    let prover = ShamirCoGroth16::with_network_config(t, network_config)
-                        .context("while building prover")?;
-
-                    // execute prover in MPC
-                    let proof = prover.prove(zkey, witness_share)?;
-                    (proof, public_input)
-                }
-            };
-
-            // write result to output file
-            if let Some(out) = out {
-                let out_file = BufWriter::new(
-                    std::fs::File::create(&out).context("while creating output file")?,
-                );
-
-                serde_json::to_writer(out_file, &proof)
-                    .context("while serializing proof to JSON file")?;
-                tracing::info!("Wrote proof to file {}", out.display());
+                    MPCProtocol::SHAMIR => {
+                        let mut witness_share = co_circom::parse_witness_share_shamir(
+                            witness_file,
+                            witness_size,
+                            num_vars,
+                        )?;
+                        if let Some(linear_signals) = &linear_signals {
+                            witness_share = co_circom::expand_linear_signals_shamir(
+                                witness_share,
+                                linear_signals,
+                            )?;
+                        }
+                        check_witness_dimensions(&witness_share, num_public, num_private)?;
+                        let public_input = witness_share.public_inputs.clone();
+
+                        // connect to network
+                        let mpc_net = session.connect_shamir()?;
+                        let mut prover = ShamirCoGroth16::with_network(t, mpc_net)
+                            .context("while building prover")?;
+                        if let Some(budget) = msm_memory_budget {
+                            prover = prover.with_msm_memory_budget(budget);
+                        }
+
+                        // execute prover in MPC
+                        let proof = if insecure_non_hiding {
+                            prover.prove_insecure_non_hiding(zkey, witness_share)?
+                        } else {
+                            prover.prove(zkey, witness_share)?
+                        };
+                        (proof, public_input)
+                    }
+                };
+                (serde_json::to_value(&proof)?, public_input)
             }
-            public_input
-        }
-        ProofSystem::Plonk => {
-            let zkey = Arc::new(
-                PlonkZKey::<P>::from_reader(zkey_file, CheckElement::Yes)
-                    .context("while parsing zkey")?,
-            );
-
-            let (proof, public_input) = match protocol {
-                MPCProtocol::REP3 => {
-                    if t != 1 {
-                        return Err(eyre!("REP3 only allows the threshold to be 1"));
+            ProofSystem::Plonk => {
+                let zkey = Arc::new(
+                    PlonkZKey::<P>::from_reader(zkey_file, CheckElement::Yes)
+                        .context("while parsing zkey")?,
+                );
+                let num_public = zkey.n_public + 1;
+                let num_vars = zkey.n_vars - zkey.n_additions;
+                let num_private = num_vars.checked_sub(num_public).context("invalid zkey")?;
+
+                let (proof, public_input) = match protocol {
+                    MPCProtocol::REP3 => {
+                        let mut mpc_net = session.connect_rep3()?;
+                        let mut witness_share = co_circom::parse_witness_share_rep3(
+                            witness_file,
+                            &mut mpc_net,
+                            witness_size,
+                            num_vars,
+                            proof_system,
+                        )?;
+                        if check_witness_consistency {
+                            witness_share
+                                .verify_consistency_rep3(&mut mpc_net)
+                                .context("while checking the consistency of the witness shares")?;
+                            tracing::info!("Witness shares of all parties are consistent");
+                        }
+                        if let Some(linear_signals) = &linear_signals {
+                            witness_share = co_circom::expand_linear_signals_rep3(
+                                witness_share,
+                                linear_signals,
+                                mpc_net.get_id(),
+                            )?;
+                        }
+                        check_witness_dimensions(&witness_share, num_public, num_private)?;
+                        session.recorder.finish_phase("witness share");
+
+                        let public_input = witness_share.public_inputs.clone();
+
+                        //init prover
+                        let mut prover =
+                            Rep3CoPlonk::with_network(mpc_net).context("while building prover")?;
+                        if let Some(cache) = domain_cache {
+                            prover = prover.with_domain_cache(cache);
+                        }
+
+                        // execute prover in MPC
+                        let proof = prover.prove(zkey, witness_share)?;
+                        (proof, public_input)
                     }
+                    MPCProtocol::SHAMIR => {
+                        let mut witness_share = co_circom::parse_witness_share_shamir(
+                            witness_file,
+                            witness_size,
+                            num_vars,
+                        )?;
+                        if let Some(linear_signals) = &linear_signals {
+                            witness_share = co_circom::expand_linear_signals_shamir(
+                                witness_share,
+                                linear_signals,
+                            )?;
+                        }
+                        check_witness_dimensions(&witness_share, num_public, num_private)?;
+                        let public_input = witness_share.public_inputs.clone();
+
+                        //init prover
+                        let mpc_net = session.connect_shamir()?;
+                        let mut prover = ShamirCoPlonk::with_network(t, mpc_net, &zkey)
+                            .context("while building prover")?;
+                        if let Some(cache) = domain_cache {
+                            prover = prover.with_domain_cache(cache);
+                        }
+
+                        // execute prover in MPC
+                        let proof = prover.prove(zkey, witness_share)?;
+                        (proof, public_input)
+                    }
+                };
+                (serde_json::to_value(&proof)?, public_input)
+            }
+            ProofSystem::Plugin(_) => unreachable!("plugins are run with CurveOps::prove_plugin"),
+        };
+        session.recorder.finish_phase("proof");
+        Ok(GeneratedProof {
+            proof,
+            public_inputs: public_input_strings(&public_input),
+            transcript_digest,
+        })
+    }
 
-                    let mut mpc_net = Rep3MpcNet::new(network_config)?;
-                    let witness_share =
-                        co_circom::parse_witness_share_rep3(witness_file, &mut mpc_net)?;
+    #[cfg(feature = "mpc")]
+    fn prove_plugin(
+        &self,
+        name: &str,
+        args: PluginProveArgs,
+    ) -> color_eyre::Result<GeneratedProof> {
+        let registry = proof_system_registry::<P>()?;
+        let plugin = registry.get(name)?;
+        let PluginProof {
+            proof,
+            public_inputs,
+        } = (plugin.prove)(args).with_context(|| format!("while computing {name} proof"))?;
+        Ok(GeneratedProof {
+            proof,
+            public_inputs: public_input_strings(&public_inputs),
+            transcript_digest: None,
+        })
+    }
 
-                    let public_input = witness_share.public_inputs.clone();
+    #[cfg(feature = "mpc")]
+    fn prove_batch_job(
+        &self,
+        slot: &mut IoContext<Rep3MpcNet>,
+        job: &BatchJob,
+    ) -> color_eyre::Result<GeneratedProof> {
+        let zkey_file = storage::open(&job.zkey).context("while opening zkey")?;
+        let zkey = Arc::new(
+            Groth16ZKey::<P>::from_reader(zkey_file, CheckElement::Yes).context("reading zkey")?,
+        );
+        let num_public = zkey.n_public + 1;
+        let num_vars = zkey.a_query.len();
+        let num_private = num_vars.checked_sub(num_public).context("invalid zkey")?;
+
+        let (witness_file, witness_size) =
+            file_utils::open_untrusted(&job.witness, file_utils::MAX_SHARE_FILE_SIZE)
+                .context("trying to open witness share file")?;
+        let witness_share = co_circom::parse_witness_share_rep3(
+            witness_file,
+            &mut slot.network,
+            witness_size,
+            num_vars,
+            &ProofSystem::Groth16,
+        )?;
+        check_witness_dimensions(&witness_share, num_public, num_private)?;
+        let public_input = witness_share.public_inputs.clone();
+
+        let mut io_context0 = slot.fork()?;
+        let io_context1 = io_context0.fork()?;
+        let prover =
+            Rep3CoGroth16::<P, Rep3MpcNet>::new(Rep3Groth16Driver::new(io_context0, io_context1));
+        let proof = prover.prove(zkey, witness_share)?;
+        Ok(GeneratedProof {
+            proof: serde_json::to_value(&proof)?,
+            public_inputs: public_input_strings(&public_input),
+            transcript_digest: None,
+        })
+    }
 
-                    //init prover
-                    let prover =
-                        Rep3CoPlonk::with_network(mpc_net).context("while building prover")?;
+    #[cfg(feature = "mpc")]
+    fn prove_local(&self, config: &ProveLocalConfig) -> color_eyre::Result<GeneratedProof> {
+        let witness = if !config.inputs.is_empty() {
+            let input_shares = config
+                .inputs
+                .iter()
+                .map(|input| {
+                    let (input_file, input_size) =
+                        file_utils::open_untrusted(input, file_utils::MAX_SHARE_FILE_SIZE)
+                            .context("while opening input share file")?;
+                    co_circom::parse_serialized_input_share_rep3(
+                        input_file,
+                        input_size,
+                        file_utils::MAX_SHARED_ELEMENTS,
+                    )
+                })
+                .collect::<color_eyre::Result<Vec<_>>>()?;
+            let input = co_circom::reconstruct_input_rep3(input_shares)
+                .context("while reconstructing input")?;
+            co_circom::generate_witness_plain::<P>(
+                input,
+                config.circuit.clone(),
+                config.compiled_circuit.as_deref(),
+                config.compiler.clone(),
+                config.vm.clone(),
+            )?
+        } else {
+            let witness_shares = config
+                .witnesses
+                .iter()
+                .map(|witness| {
+                    let (witness_file, witness_size) =
+                        file_utils::open_untrusted(witness, file_utils::MAX_SHARE_FILE_SIZE)
+                            .context("trying to open witness share file")?;
+                    co_circom::parse_serialized_witness_share_rep3(
+                        witness_file,
+                        witness_size,
+                        file_utils::MAX_SHARED_ELEMENTS,
+                    )
+                })
+                .collect::<color_eyre::Result<Vec<_>>>()?;
+            co_circom::reconstruct_witness_rep3(witness_shares, &config.proof_system)
+                .context("while reconstructing witness")?
+        };
+        let public_input = witness.public_inputs.clone();
+
+        // parse Circom zkey file
+        let zkey_file = storage::open(&config.zkey).context("while opening zkey")?;
+        let proof = match &config.proof_system {
+            ProofSystem::Groth16 => {
+                let zkey = Arc::new(
+                    Groth16ZKey::<P>::from_reader(zkey_file, CheckElement::Yes)
+                        .context("reading zkey")?,
+                );
+                let num_public = zkey.n_public + 1;
+                let num_private = zkey
+                    .a_query
+                    .len()
+                    .checked_sub(num_public)
+                    .context("invalid zkey")?;
+                check_witness_dimensions(&witness, num_public, num_private)?;
+                let proof = Groth16::<P>::plain_prove(zkey, witness)?;
+                serde_json::to_value(proof)?
+            }
+            ProofSystem::Plonk => {
+                let zkey = Arc::new(
+                    PlonkZKey::<P>::from_reader(zkey_file, CheckElement::Yes)
+                        .context("while parsing zkey")?,
+                );
+                let num_public = zkey.n_public + 1;
+                let num_private = (zkey.n_vars - zkey.n_additions)
+                    .checked_sub(num_public)
+                    .context("invalid zkey")?;
+                check_witness_dimensions(&witness, num_public, num_private)?;
+                let proof = Plonk::<P>::plain_prove(zkey, witness)?;
+                serde_json::to_value(proof)?
+            }
+            ProofSystem::Plugin(name) => {
+                return Err(eyre!(
+                    "The proof system {name} does not support local proving"
+                ));
+            }
+        };
+        Ok(GeneratedProof {
+            proof,
+            public_inputs: public_input_strings(&public_input),
+            transcript_digest: None,
+        })
+    }
 
-                    // execute prover in MPC
-                    let proof = prover.prove(zkey, witness_share)?;
-                    (proof, public_input)
+    #[cfg(feature = "mpc")]
+    fn rehearse(&self, report: &mut RehearsalReport, setup: RehearsalSetup) {
+        let RehearsalSetup {
+            proof_system,
+            protocol,
+            threshold: t,
+            zkey: zkey_path,
+            fingerprint,
+            network_config,
+        } = setup;
+        match proof_system {
+            ProofSystem::Groth16 => {
+                let Some(zkey) = report.check("zkey", || {
+                    let zkey_file = storage::open(&zkey_path)?;
+                    Ok(Arc::new(
+                        Groth16ZKey::<P>::from_reader(zkey_file, CheckElement::Yes)
+                            .context("reading zkey")?,
+                    ))
+                }) else {
+                    return;
+                };
+                let num_public = zkey.n_public + 1;
+                let num_private = zkey.a_query.len().saturating_sub(num_public);
+                match protocol {
+                    MPCProtocol::REP3 => {
+                        let Some(mut mpc_net) =
+                            report.check("network", || Ok(Rep3MpcNet::new(network_config)?))
+                        else {
+                            return;
+                        };
+                        if report
+                            .check("setup", || fingerprint.compare_rep3(&mut mpc_net))
+                            .is_some()
+                        {
+                            report.check("prove", || {
+                                let prover = Rep3CoGroth16::with_network(mpc_net)?;
+                                prover.prove(
+                                    zkey,
+                                    rehearsal::zero_witness_rep3(num_public, num_private),
+                                )
+                            });
+                        }
+                    }
+                    MPCProtocol::SHAMIR => {
+                        let Some(mut mpc_net) =
+                            report.check("network", || Ok(ShamirMpcNet::new(network_config)?))
+                        else {
+                            return;
+                        };
+                        if report
+                            .check("setup", || fingerprint.compare_shamir(&mut mpc_net))
+                            .is_some()
+                        {
+                            report.check("prove", || {
+                                let prover = ShamirCoGroth16::with_network(t, mpc_net)?;
+                                prover.prove(
+                                    zkey,
+                                    rehearsal::zero_witness_shamir(num_public, num_private),
+                                )
+                            });
+                        }
+                    }
                 }
-                MPCProtocol::SHAMIR => {
-                    let witness_share = co_circom::parse_witness_share_shamir(witness_file)?;
-                    let public_input = witness_share.public_inputs.clone();
+            }
+            ProofSystem::Plonk => {
+                let Some(zkey) = report.check("zkey", || {
+                    let zkey_file = storage::open(&zkey_path)?;
+                    Ok(Arc::new(
+                        PlonkZKey::<P>::from_reader(zkey_file, CheckElement::Yes)
+                            .context("while parsing zkey")?,
+                    ))
+                }) else {
+                    return;
+                };
+                let num_public = zkey.n_public + 1;
+                let num_private = (zkey.n_vars - zkey.n_additions).saturating_sub(num_public);
+                match protocol {
+                    MPCProtocol::REP3 => {
+                        let Some(mut mpc_net) =
+                            report.check("network", || Ok(Rep3MpcNet::new(network_config)?))
+                        else {
+                            return;
+                        };
+                        if report
+                            .check("setup", || fingerprint.compare_rep3(&mut mpc_net))
+                            .is_some()
+                        {
+                            report.check("prove", || {
+                                let prover = Rep3CoPlonk::with_network(mpc_net)?;
+                                Ok(prover.prove(
+                                    zkey,
+                                    rehearsal::zero_witness_rep3(num_public, num_private),
+                                )?)
+                            });
+                        }
+                    }
+                    MPCProtocol::SHAMIR => {
+                        let Some(mut mpc_net) =
+                            report.check("network", || Ok(ShamirMpcNet::new(network_config)?))
+                        else {
+                            return;
+                        };
+                        if report
+                            .check("setup", || fingerprint.compare_shamir(&mut mpc_net))
+                            .is_some()
+                        {
+                            report.check("prove", || {
+                                let prover = ShamirCoPlonk::with_network(t, mpc_net, &zkey)?;
+                                Ok(prover.prove(
+                                    zkey,
+                                    rehearsal::zero_witness_shamir(num_public, num_private),
+                                )?)
+                            });
+                        }
+                    }
+                }
+            }
+            ProofSystem::Plugin(_) => unreachable!("rejected by the config check"),
+        }
+    }
+
+    fn verify(
+        &self,
+        proof_system: &ProofSystem,
+        proof: &Path,
+        vk: &Path,
+        public_inputs: Vec<String>,
+    ) -> color_eyre::Result<Result<(), VerificationError>> {
+        // parse circom proof file
+        let proof_file = storage::open(proof).context("while opening proof file")?;
+
+        // parse circom verification key file
+        let vk_file = storage::open(vk).context("while opening verification key file")?;
+
+        // skip 1 atm
+        let public_inputs = public_inputs
+            .into_iter()
+            .map(|s| {
+                s.parse::<P::ScalarField>()
+                    .map_err(|_| eyre!("could not parse as field element: {}", s))
+            })
+            .collect::<Result<Vec<P::ScalarField>, _>>()
+            .context("while converting public input strings to field elements")?;
+
+        // verify proof
+        let res = match proof_system {
+            ProofSystem::Groth16 => {
+                let proof: Groth16Proof<P> = serde_json::from_reader(proof_file)
+                    .context("while deserializing proof from file")?;
+
+                let vk: Groth16JsonVerificationKey<P> = serde_json::from_reader(vk_file)
+                    .context("while deserializing verification key from file")?;
+
+                // The actual verifier
+                let start = Instant::now();
+                let res = Groth16::<P>::verify(&vk, &proof, &public_inputs);
+                let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+                tracing::info!("Proof verification took {} ms", duration_ms);
+                res
+            }
+            ProofSystem::Plonk => {
+                let proof: PlonkProof<P> = serde_json::from_reader(proof_file)
+                    .context("while deserializing proof from file")?;
+
+                let vk: PlonkJsonVerificationKey<P> = serde_json::from_reader(vk_file)
+                    .context("while deserializing verification key from file")?;
+
+                // The actual verifier
+                let start = Instant::now();
+                let res = Plonk::<P>::verify(&vk, &proof, &public_inputs);
+                let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+                tracing::info!("Proof verification took {} ms", duration_ms);
+                res
+            }
+            #[cfg(feature = "mpc")]
+            ProofSystem::Plugin(name) => {
+                let registry = proof_system_registry::<P>()?;
+                let plugin = registry.get(name)?;
+
+                // The actual verifier
+                let start = Instant::now();
+                let res = (plugin.verify)(PluginVerifyArgs {
+                    proof: proof.to_path_buf(),
+                    vk: vk.to_path_buf(),
+                    public_inputs,
+                });
+                let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+                tracing::info!("Proof verification took {} ms", duration_ms);
+                res
+            }
+            #[cfg(not(feature = "mpc"))]
+            ProofSystem::Plugin(name) => {
+                eyre::bail!("proof system {name} is a plugin, which requires the mpc feature")
+            }
+        };
+        Ok(res)
+    }
 
-                    //init prover
-                    let prover = ShamirCoPlonk::with_network_config(t, network_config, &zkey)
-                        .context("while building prover")?;
+    #[cfg(feature = "mpc")]
+    fn groth16_proof_binding(&self, proof: &serde_json::Value) -> color_eyre::Result<String> {
+        let proof: Groth16Proof<P> = serde_json::from_value(proof.clone())
+            .context("while parsing Groth16 proof to check the binding of its elements")?;
+        signing::groth16_proof_binding::<P>(&proof.pi_a, &proof.pi_b, &proof.pi_c)
+    }
 
-                    // execute prover in MPC
-                    let proof = prover.prove(zkey, witness_share)?;
-                    (proof, public_input)
-                }
-            };
+    fn calibrate(&self, size: usize) -> color_eyre::Result<CalibrationReport> {
+        co_circom::calibrate::calibrate::<P>(size)
+    }
 
-            // write result to output file
-            if let Some(out) = out {
-                let out_file = BufWriter::new(
-                    std::fs::File::create(&out).context("while creating output file")?,
-                );
+    #[cfg(feature = "mpc")]
+    fn circuit_profile(&self, r1cs: &Path) -> color_eyre::Result<CircuitProfile> {
+        let r1cs_file = BufReader::new(File::open(r1cs).context("while opening r1cs file")?);
+        let r1cs = R1CS::<P>::from_reader(r1cs_file).context("while parsing r1cs file")?;
+        Ok(recommend::profile(&r1cs))
+    }
+}
+
+/// A registry of the supported curves. Adding a curve only requires registering its [CurveImpl] in [CurveRegistry::new].
+struct CurveRegistry {
+    curves: HashMap<MPCCurve, Box<dyn CurveOps>>,
+}
+
+impl CurveRegistry {
+    fn new() -> Self {
+        let mut curves: HashMap<MPCCurve, Box<dyn CurveOps>> = HashMap::new();
+        curves.insert(MPCCurve::BN254, Box::new(CurveImpl::<Bn254>(PhantomData)));
+        curves.insert(
+            MPCCurve::BLS12_381,
+            Box::new(CurveImpl::<Bls12_381>(PhantomData)),
+        );
+        curves.insert(
+            MPCCurve::BLS12_377,
+            Box::new(CurveImpl::<Bls12_377>(PhantomData)),
+        );
+        Self { curves }
+    }
+
+    fn get(&self, curve: MPCCurve) -> color_eyre::Result<&dyn CurveOps> {
+        self.curves
+            .get(&curve)
+            .map(Box::as_ref)
+            .ok_or_else(|| eyre!("curve {curve} is not supported by this build"))
+    }
+}
+
+/// Writes a value returned by a [CurveOps] operation to `path`.
+#[cfg(feature = "mpc")]
+fn write_encoded(path: &Path, value: &Encoded, what: &str) -> color_eyre::Result<()> {
+    let mut out_file =
+        storage::create(path).with_context(|| format!("while creating {what} file"))?;
+    value(&mut out_file)?;
+    out_file
+        .finish()
+        .with_context(|| format!("while writing {what}"))?;
+    Ok(())
+}
+
+#[cfg(feature = "mpc")]
+#[instrument(level = "debug", skip(curve, config))]
+fn run_split_witness(
+    curve: &dyn CurveOps,
+    config: SplitWitnessConfig,
+) -> color_eyre::Result<CommandOutput> {
+    let witness_path = &config.witness;
+    let out_dir = &config.out_dir;
+    let t = config.threshold;
+    let n = config.num_parties;
+
+    file_utils::check_file_exists(witness_path)?;
+    file_utils::check_file_exists(&config.r1cs)?;
+    file_utils::check_dir_exists(out_dir)?;
+    match config.protocol {
+        MPCProtocol::REP3 => {
+            if t != 1 {
+                return Err(eyre!("REP3 only allows the threshold to be 1"));
+            }
+            if n != 3 {
+                return Err(eyre!("REP3 only allows the number of parties to be 3"));
+            }
+        }
+        MPCProtocol::SHAMIR => {
+            if config.usage_policy.is_some() {
+                return Err(eyre!(
+                    "Usage policies are only supported for REP3 witness shares"
+                ));
+            }
+        }
+    }
+
+    let mut output = CommandOutput::success();
+    let base_name = witness_path
+        .file_name()
+        .context("we have a file name")?
+        .to_str()
+        .context("witness file name is not valid UTF-8")?;
+
+    // create witness shares
+    let start = Instant::now();
+    let split = curve.share_witness(&config)?;
+    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+    tracing::info!("Sharing took {} ms", duration_ms);
+
+    // write out the shares to the output directory
+    for (i, share) in split.shares.iter().enumerate() {
+        let path = out_dir.join(format!("{}.{}.shared", base_name, i));
+        write_encoded(&path, share, "witness share")?;
+        tracing::info!("Wrote witness share {} to file {}", i, path.display());
+        output.add_file("witness_share", path);
+    }
+
+    if let Some((proof, openings)) = split.sharing_proof {
+        let path = out_dir.join(format!("{}.sharing_proof", base_name));
+        write_encoded(&path, &proof, "sharing proof")?;
+        tracing::info!("Wrote proof of correct sharing to file {}", path.display());
+        output.add_file("sharing_proof", path);
+        for (party_id, opening) in openings {
+            let path = out_dir.join(format!("{}.{}.sharing_opening", base_name, party_id));
+            write_encoded(&path, &opening, "sharing opening")?;
+            tracing::info!(
+                "Wrote sharing opening {} to file {}",
+                party_id,
+                path.display()
+            );
+            output.add_file("sharing_opening", path);
+        }
+    }
+    tracing::info!("Split witness into shares successfully");
+    Ok(output)
+}
+
+#[cfg(feature = "mpc")]
+#[instrument(level = "debug", skip(curve, config))]
+fn run_split_input(
+    curve: &dyn CurveOps,
+    config: SplitInputConfig,
+) -> color_eyre::Result<CommandOutput> {
+    let input = &config.input;
+    let out_dir = &config.out_dir;
+
+    if config.protocol != MPCProtocol::REP3 {
+        return Err(eyre!(
+            "Only REP3 protocol is supported for splitting inputs"
+        ));
+    }
+    file_utils::check_file_exists(input)?;
+    file_utils::check_file_exists(Path::new(&config.circuit))?;
+    file_utils::check_dir_exists(out_dir)?;
+    let bit_widths = match &config.bit_widths {
+        Some(path) => co_circom::parse_bit_widths(path)?,
+        None => BTreeMap::new(),
+    };
+
+    let base_name = input
+        .file_name()
+        .context("we have a file name")?
+        .to_str()
+        .context("input file name is not valid UTF-8")?;
+
+    if !config.pvss_keys.is_empty() {
+        if config.pvss_keys.len() != 3 {
+            return Err(eyre!("Need the PVSS public keys of exactly three parties"));
+        }
+        if config.seeded {
+            return Err(eyre!("Seeded shares are not supported with PVSS"));
+        }
+        for path in &config.pvss_keys {
+            file_utils::check_file_exists(path)?;
+        }
+
+        let start = Instant::now();
+        let published = curve.share_input_pvss(&config, bit_widths)?;
+        let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+        tracing::info!("Sharing took {} ms", duration_ms);
+
+        let path = out_dir.join(format!("{}.pvss", base_name));
+        write_encoded(&path, &published, "published input")?;
+        tracing::info!("Wrote published input shares to file {}", path.display());
+        return Ok(CommandOutput::success().with_file("published_input", path));
+    }
+
+    let start = Instant::now();
+    let split = curve.share_input(&config, base_name, bit_widths)?;
+    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+    tracing::info!("Sharing took {} ms", duration_ms);
+
+    // write out the shares to the output directory
+    let mut output = CommandOutput::success();
+    if let Some(commitment) = split.commitment {
+        let path = out_dir.join(format!("{}.commitment.json", base_name));
+        write_encoded(&path, &commitment, "commitment")?;
+        tracing::info!("Wrote input commitment to file {}", path.display());
+        output.add_file("commitment", path);
+    }
+    for (i, share) in split.shares.iter().enumerate() {
+        let path = out_dir.join(format!("{}.{}.shared", base_name, i));
+        write_encoded(&path, share, "input share")?;
+        tracing::info!("Wrote input share {} to file {}", i, path.display());
+        output.add_file("input_share", path);
+    }
+    tracing::info!("Split input into shares successfully");
+    Ok(output)
+}
+
+#[cfg(feature = "mpc")]
+#[instrument(level = "debug", skip(curve, config))]
+fn run_merge_input_shares(
+    curve: &dyn CurveOps,
+    config: MergeInputSharesConfig,
+) -> color_eyre::Result<CommandOutput> {
+    let inputs = config.inputs;
+    let out = config.out;
+
+    if config.protocol != MPCProtocol::REP3 {
+        return Err(eyre!(
+            "Only REP3 protocol is supported for merging input shares"
+        ));
+    }
+
+    if inputs.len() < 2 {
+        return Err(eyre!("Need at least two input shares to merge"));
+    }
+    for input in &inputs {
+        file_utils::check_file_exists(input)?;
+    }
+
+    let start = Instant::now();
+    let merged = curve.merge_input_shares(&inputs, config.public_inputs.as_deref())?;
+    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+    tracing::info!("Merging took {} ms", duration_ms);
+
+    write_encoded(&out, &merged, "merged input share")?;
+    tracing::info!("Wrote merged input share to file {}", out.display());
+    Ok(CommandOutput::success().with_file("input_share", out))
+}
+
+#[cfg(feature = "mpc")]
+#[instrument(level = "debug", skip(curve, config))]
+fn run_generate_pvss_key(
+    curve: &dyn CurveOps,
+    config: GeneratePvssKeyConfig,
+) -> color_eyre::Result<CommandOutput> {
+    let (key, public_key) = curve.generate_pvss_key()?;
+    write_encoded(&config.key_out, &key, "key pair")?;
+    write_encoded(&config.public_key_out, &public_key, "public key")?;
+    tracing::info!(
+        "Wrote key pair to file {} and public key to file {}",
+        config.key_out.display(),
+        config.public_key_out.display()
+    );
+    Ok(CommandOutput::success()
+        .with_file("key_pair", config.key_out)
+        .with_file("public_key", config.public_key_out))
+}
+
+#[cfg(feature = "mpc")]
+#[instrument(level = "debug", skip(curve, config))]
+fn run_decrypt_input_share(
+    curve: &dyn CurveOps,
+    config: DecryptInputShareConfig,
+) -> color_eyre::Result<CommandOutput> {
+    file_utils::check_file_exists(&config.input)?;
+    file_utils::check_file_exists(&config.key)?;
+
+    let share = curve.decrypt_input_share(&config.input, &config.key)?;
+
+    write_encoded(&config.out, &share, "input share")?;
+    tracing::info!("Wrote input share to file {}", config.out.display());
+    Ok(CommandOutput::success().with_file("input_share", config.out))
+}
+
+#[cfg(feature = "mpc")]
+#[instrument(level = "debug", skip(curve, config))]
+fn run_compile(curve: &dyn CurveOps, config: CompileConfig) -> color_eyre::Result<CommandOutput> {
+    let out = config.out.clone();
+    let hash = curve.compile_circuit(config)?;
+    tracing::info!("Compiled circuit successfully written to {}", out.display());
+    Ok(CommandOutput::success()
+        .with_file("compiled_circuit", out)
+        .with_value("circuit_hash", circuit_hash_to_hex(&hash)))
+}
+
+#[cfg(feature = "mpc")]
+#[instrument(level = "debug", skip(curve, config))]
+fn run_generate_witness(
+    curve: &dyn CurveOps,
+    config: GenerateWitnessConfig,
+) -> color_eyre::Result<CommandOutput> {
+    let input = config.input.clone();
+    let out = config.out.clone();
+    let num_commitments = config.commitment.len();
+
+    if config.protocol != MPCProtocol::REP3 {
+        return Err(eyre!(
+            "Only REP3 protocol is supported for merging input shares"
+        ));
+    }
+    file_utils::check_file_exists(&input)?;
+    if config.circuit.is_none() && config.compiled_circuit.is_none() {
+        return Err(eyre!("Either a circuit or a compiled circuit is required"));
+    }
+    for path in config.commitment.iter() {
+        file_utils::check_file_exists(path)?;
+    }
+
+    // connect to network
+    let network_config = config
+        .network
+        .to_owned()
+        .try_into()
+        .context("while converting network config")?;
+    let mpc_net = Rep3MpcNet::new(network_config).context("while connecting to network")?;
+    let session = mpc_net.session_guard().abort_on_ctrl_c();
+
+    // parse input shares
+    let input_share = file_utils::open_untrusted(&input, file_utils::MAX_SHARE_FILE_SIZE)
+        .context("while opening input share file")?;
+
+    // Extend the witness
+    let witness_share = curve.generate_witness(input_share, mpc_net, config)?;
+    session.finish();
+
+    // write result to output file
+    write_encoded(&out, &witness_share, "witness share")?;
+    tracing::info!("Witness successfully written to {}", out.display());
+    Ok(CommandOutput::success()
+        .with_file("witness_share", out)
+        .with_value("commitments_verified", num_commitments))
+}
+
+#[cfg(feature = "mpc")]
+#[instrument(level = "debug", skip(curve, config))]
+fn run_translate_witness(
+    curve: &dyn CurveOps,
+    config: TranslateWitnessConfig,
+) -> color_eyre::Result<CommandOutput> {
+    let out = config.out;
+
+    if config.src_protocol != MPCProtocol::REP3 || config.target_protocol != MPCProtocol::SHAMIR {
+        return Err(eyre!("Only REP3 to SHAMIR translation is supported"));
+    }
+
+    // parse witness shares
+    let witness_share =
+        file_utils::open_untrusted(&config.witness, file_utils::MAX_SHARE_FILE_SIZE)
+            .context("trying to open witness share file")?;
+
+    // connect to network
+    let network_config = config
+        .network
+        .to_owned()
+        .try_into()
+        .context("while converting network config")?;
+    let net = Rep3MpcNet::new(network_config).context("while connecting to network")?;
+    let session = net.session_guard().abort_on_ctrl_c();
+    let id = usize::from(net.get_id());
+
+    // Translate witness to shamir shares
+    let start = Instant::now();
+    let shamir_witness_share = curve.translate_witness(witness_share, net)?;
+    session.finish();
+    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+    tracing::info!("Party {}: Translating witness took {} ms", id, duration_ms);
+
+    // write result to output file
+    write_encoded(&out, &shamir_witness_share, "witness share")?;
+    tracing::info!("Witness successfully written to {}", out.display());
+    Ok(CommandOutput::success().with_file("witness_share", out))
+}
+
+#[cfg(feature = "mpc")]
+#[instrument(level = "debug", skip(curve, config))]
+fn run_witness_stats(
+    curve: &dyn CurveOps,
+    config: WitnessStatsConfig,
+    output_format: OutputFormat,
+) -> color_eyre::Result<CommandOutput> {
+    let stats = curve
+        .witness_share_stats(&config.witness, config.protocol)
+        .context("while computing witness share statistics")?;
+
+    let mut output = CommandOutput::success().with_value("statistics", &stats);
+    if let Some(out) = config.out {
+        let out_file = BufWriter::new(std::fs::File::create(&out)?);
+        serde_json::to_writer_pretty(out_file, &stats)?;
+        tracing::info!(
+            "Witness share statistics successfully written to {}",
+            out.display()
+        );
+        output.add_file("statistics", out);
+    } else if output_format == OutputFormat::Text {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+    }
+    if stats.num_shared_signals > 0 && stats.zero_share_ratio > 0.5 {
+        tracing::warn!(
+            "More than half of the local shares are zero, the witness might not be shared correctly"
+        );
+    }
+    Ok(output)
+}
+
+#[cfg(feature = "mpc")]
+#[instrument(level = "debug", skip(curve, config))]
+fn run_verify_witness_share(
+    curve: &dyn CurveOps,
+    config: VerifyWitnessShareConfig,
+) -> color_eyre::Result<CommandOutput> {
+    file_utils::check_file_exists(&config.r1cs)?;
+
+    let start = Instant::now();
+    let party_id = curve.verify_witness_share(&config)?;
+    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+    tracing::info!(
+        "Witness share of party {} matches the proof of correct sharing, checking took {} ms",
+        party_id,
+        duration_ms
+    );
+    Ok(CommandOutput::success()
+        .with_value("party_id", party_id)
+        .with_value("verify_time_ms", duration_ms))
+}
 
-                serde_json::to_writer(out_file, &proof)
-                    .context("while serializing proof to JSON file")?;
-                tracing::info!("Wrote proof to file {}", out.display());
+#[cfg(feature = "mpc")]
+#[instrument(level = "debug", skip(curve, config))]
+fn run_migrate_shares(
+    curve: &dyn CurveOps,
+    config: MigrateSharesConfig,
+) -> color_eyre::Result<CommandOutput> {
+    let migrated = curve
+        .migrate_share_file(&config)
+        .context("while migrating share file")?;
+    storage::write(&config.out, &migrated).context("while writing migrated share file")?;
+    tracing::info!(
+        "Share file of version {} successfully migrated to {}",
+        config.from,
+        config.out.display()
+    );
+    Ok(CommandOutput::success().with_file("share", config.out))
+}
+
+/// Inspects a share file with the curves of the registry, see [co_circom::inspect]. If no curve is configured, the first curve that can parse the file is reported.
+#[cfg(feature = "mpc")]
+#[instrument(level = "debug", skip(config, registry))]
+fn run_inspect_share(
+    config: InspectShareConfig,
+    registry: &CurveRegistry,
+    output_format: OutputFormat,
+) -> color_eyre::Result<CommandOutput> {
+    file_utils::check_file_exists(&config.share)?;
+    let curves = match config.curve {
+        Some(curve) => vec![curve],
+        None => <MPCCurve as clap::ValueEnum>::value_variants()
+            .iter()
+            .copied()
+            .filter(|curve| registry.get(*curve).is_ok())
+            .collect(),
+    };
+    let mut errors = Vec::new();
+    let mut inspection = None;
+    for curve in curves {
+        match registry
+            .get(curve)?
+            .inspect_share(&config.share, curve, config.protocol, config.kind)
+        {
+            Ok(found) => {
+                inspection = Some(found);
+                break;
             }
-            public_input
+            Err(err) => errors.push(format!("{err:#}")),
+        }
+    }
+    let inspection = inspection.with_context(|| {
+        format!(
+            "{} is not a share file of co-circom: {}",
+            config.share.display(),
+            errors.join("; ")
+        )
+    })?;
+
+    let mut output = CommandOutput::success().with_value("share", &inspection);
+    if let Some(out) = config.out {
+        let out_file = BufWriter::new(File::create(&out)?);
+        serde_json::to_writer_pretty(out_file, &inspection)?;
+        tracing::info!(
+            "Share file summary successfully written to {}",
+            out.display()
+        );
+        output.add_file("summary", out);
+    } else if output_format == OutputFormat::Text {
+        println!("{}", serde_json::to_string_pretty(&inspection)?);
+    }
+    if !inspection.also_parses_as.is_empty() {
+        tracing::warn!(
+            "The share file can also be parsed as {}, pass --protocol and --kind to disambiguate",
+            inspection.also_parses_as.join(", ")
+        );
+    }
+    Ok(output)
+}
+
+#[cfg(feature = "mpc")]
+#[instrument(level = "debug", skip(curve, config))]
+fn run_generate_proof(
+    curve: &dyn CurveOps,
+    config: GenerateProofConfig,
+) -> color_eyre::Result<CommandOutput> {
+    if config.rehearsal {
+        return run_rehearsal(curve, config);
+    }
+    let proof_system = config.proof_system;
+    let witness = config
+        .witness
+        .context("a witness share file is required unless rehearsing")?;
+    let zkey = config.zkey;
+    let protocol = config.protocol;
+    let out = config.out;
+    let public_input_filename = config.public_input;
+    let metadata_filename = config.metadata;
+    let transcript_filename = config.transcript;
+    let t = config.threshold;
+    let insecure_non_hiding = config.insecure_non_hiding;
+    let check_witness_consistency = config.check_witness_consistency;
+    let domain_cache = match (&config.cache_dir, &proof_system) {
+        (Some(dir), ProofSystem::Plonk) => Some(
+            DomainCache::open(dir.join(co_circom::PLONK_DOMAIN_CACHE_DIR))
+                .context("while opening the Plonk domain cache")?,
+        ),
+        _ => None,
+    };
+
+    if protocol == MPCProtocol::REP3 && t != 1 {
+        return Err(eyre!("REP3 only allows the threshold to be 1"));
+    }
+    if check_witness_consistency && protocol != MPCProtocol::REP3 {
+        return Err(eyre!(
+            "Checking the consistency of the witness shares is only supported for REP3"
+        ));
+    }
+    if insecure_non_hiding && !matches!(proof_system, ProofSystem::Groth16) {
+        return Err(eyre!(
+            "The insecure non-hiding mode is only supported for Groth16"
+        ));
+    }
+    if config.cleanup_on_success && config.session.is_none() {
+        return Err(eyre!("Cleanup on success requires a session directory"));
+    }
+    if config.bind_proof && !matches!(proof_system, ProofSystem::Groth16) {
+        return Err(eyre!(
+            "Binding the proof elements is only supported for Groth16"
+        ));
+    }
+    if config.msm_memory_budget_mb.is_some() && !matches!(proof_system, ProofSystem::Groth16) {
+        return Err(eyre!("The MSM memory budget is only supported for Groth16"));
+    }
+    let msm_memory_budget = config.msm_memory_budget_mb.map(|mb| mb << 20);
+    let signing_key = match config.signing_key {
+        Some(key) => {
+            file_utils::check_file_exists(&key)?;
+            let key_bytes = std::fs::read(&key).context("while reading signing key")?;
+            Some(SigningKeyPair::from_bytes(&key_bytes).context("while parsing signing key")?)
+        }
+        None => None,
+    };
+    if signing_key.is_none() && (config.signature.is_some() || config.committee.is_some()) {
+        return Err(eyre!(
+            "Signing the proof and proving possession of the signing key require a signing key"
+        ));
+    }
+    if signing_key.is_some() && config.signature.is_none() && config.committee.is_none() {
+        return Err(eyre!(
+            "A signing key requires a signature output file or a committee descriptor"
+        ));
+    }
+    let possession = match (&config.committee, &signing_key) {
+        (Some(committee), Some(key)) => Some((read_committee(committee)?, key.clone())),
+        _ => None,
+    };
+    let signing = signing_key.zip(config.signature);
+
+    file_utils::check_file_exists(&zkey)?;
+    if witness.as_os_str() == storage::STDIO && zkey.as_os_str() == storage::STDIO {
+        return Err(eyre!(
+            "Only one of the witness share and the zkey can be read from stdin"
+        ));
+    }
+
+    // the warm-up is finished after the network was warmed up
+    let warm_up = config
+        .warm_up
+        .then(|| {
+            WarmUp::start(
+                std::time::Duration::from_millis(config.warm_up_ms),
+                &[zkey.as_path()],
+            )
+        })
+        .transpose()
+        .context("while warming up")?;
+
+    let network_config: NetworkConfig = config
+        .network
+        .to_owned()
+        .try_into()
+        .context("while converting network config")?;
+
+    // the session transcript of this party, see `--transcript`
+    let recorder = TranscriptRecorder::new(
+        "generate-proof",
+        network_config.my_id,
+        network_config.parties.len(),
+        protocol,
+        config.curve,
+        proof_system.clone(),
+    );
+    let mut session = ProverSession {
+        network_config: Some(network_config),
+        recorder,
+        warm_up,
+        warm_up_report: None,
+        possession,
+        guard: None,
+    };
+    let mut output = CommandOutput::success();
+    let generated = match &proof_system {
+        ProofSystem::Plugin(name) => {
+            // plugins establish their own network, which is not warmed up
+            session.warm_up_report = session.warm_up.take().map(WarmUp::finish);
+            let generated = curve.prove_plugin(
+                name,
+                PluginProveArgs {
+                    witness,
+                    zkey,
+                    protocol,
+                    threshold: t,
+                    network: session.take_network_config()?,
+                },
+            )?;
+            session.recorder.finish_phase("proof");
+            generated
+        }
+        _ => {
+            // parse witness shares
+            let witness = file_utils::open_untrusted(&witness, file_utils::MAX_SHARE_FILE_SIZE)
+                .context("trying to open witness share file")?;
+            let generated = curve.prove(
+                &mut session,
+                ProveArgs {
+                    proof_system: &proof_system,
+                    protocol,
+                    threshold: t,
+                    witness,
+                    zkey: &zkey,
+                    linear_signals: config.linear_signals.as_deref(),
+                    check_witness_consistency,
+                    insecure_non_hiding,
+                    msm_memory_budget,
+                    domain_cache,
+                },
+            )?;
+            session.finish();
+            generated
         }
     };
+    let ProverSession {
+        mut recorder,
+        warm_up_report,
+        ..
+    } = session;
+    let GeneratedProof {
+        proof,
+        public_inputs,
+        transcript_digest,
+    } = generated;
+    // the binding of the Groth16 proof elements, see `--bind-proof`
+    let proof_binding = config
+        .bind_proof
+        .then(|| curve.groth16_proof_binding(&proof))
+        .transpose()?;
+
+    // write result to output file
+    if let Some(out) = out {
+        write_proof(&proof, &out)?;
+        output.add_file("proof", out);
+    }
 
     // write public input to output file
     if let Some(public_input_filename) = public_input_filename {
-        let public_input_as_strings = public_input
+        write_public_input(&public_inputs, &public_input_filename)?;
+        output.add_file("public_input", public_input_filename);
+    }
+    let transcript_digest = transcript_digest.map(|digest| {
+        digest
             .iter()
-            .skip(1) // we skip the constant 1 at position 0
-            .map(|f| {
-                if f.is_zero() {
-                    "0".to_string()
-                } else {
-                    f.to_string()
-                }
-            })
-            .collect::<Vec<String>>();
-        let public_input_file = BufWriter::new(
-            std::fs::File::create(&public_input_filename)
-                .context("while creating public input file")?,
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>()
+    });
+    output = output.with_value("transcript_digest", &transcript_digest);
+    recorder.set_public_values(public_inputs.clone());
+    recorder.set_protocol_digest(transcript_digest.clone());
+    let written = |kind: &str| {
+        output
+            .files
+            .get(kind)
+            .and_then(|files| files.first())
+            .cloned()
+    };
+    recorder.add_artifact("proof", &proof, written("proof"), true)?;
+    recorder.add_artifact(
+        "public_input",
+        &public_inputs,
+        written("public_input"),
+        true,
+    )?;
+    if let Some(report) = warm_up_report {
+        output = output.with_value("warm_up", &report);
+    }
+    let mut metadata = serde_json::json!({ "transcript_digest": transcript_digest });
+    if let Some(binding) = proof_binding {
+        metadata[signing::PROOF_BINDING_KEY] = binding.into();
+    }
+    // write metadata to output file
+    if let Some(metadata_filename) = metadata_filename {
+        let mut metadata_file =
+            storage::create(&metadata_filename).context("while creating metadata file")?;
+        serde_json::to_writer(&mut metadata_file, &metadata)
+            .context("while writing out metadata to JSON file")?;
+        metadata_file.finish().context("while writing metadata")?;
+        tracing::info!("Wrote metadata to file {}", metadata_filename.display());
+        output.add_file("metadata", metadata_filename.clone());
+        recorder.add_artifact("metadata", &metadata, Some(metadata_filename), false)?;
+    }
+    // sign proof, public input, and metadata with the long-term key of this party
+    if let Some((key, signature_filename)) = signing {
+        let statement = ProofStatement {
+            proof,
+            public_inputs,
+            metadata,
+        };
+        let signed = SignedStatement::sign(&statement, &key)?;
+        let mut signature_file =
+            storage::create(&signature_filename).context("while creating signature file")?;
+        serde_json::to_writer_pretty(&mut signature_file, &signed)
+            .context("while writing out signature to JSON file")?;
+        signature_file.finish().context("while writing signature")?;
+        tracing::info!("Wrote signature to file {}", signature_filename.display());
+        output.add_file("signature", signature_filename.clone());
+        recorder.add_artifact("signature", &signed, Some(signature_filename), false)?;
+    }
+    // write the session transcript of this party for a later audit
+    if let Some(transcript_filename) = transcript_filename {
+        let mut transcript_file =
+            storage::create(&transcript_filename).context("while creating transcript file")?;
+        serde_json::to_writer_pretty(&mut transcript_file, &recorder.finish())
+            .context("while writing out transcript to JSON file")?;
+        transcript_file
+            .finish()
+            .context("while writing transcript")?;
+        tracing::info!(
+            "Wrote session transcript to file {}",
+            transcript_filename.display()
         );
-        serde_json::to_writer(public_input_file, &public_input_as_strings)
-            .context("while writing out public inputs to JSON file")?;
+        output.add_file("transcript", transcript_filename);
+    }
+    tracing::info!("Proof generation finished successfully");
+
+    if config.cleanup_on_success {
+        let session = session::Session::open(config.session.context("checked above")?);
+        let report = session::cleanup_session(&session, &config.retention)
+            .context("while cleaning up the session")?;
         tracing::info!(
-            "Wrote public inputs to file {}",
-            public_input_filename.display()
+            "Cleaned up session {}, removed {} files ({} bytes)",
+            session.root().display(),
+            report.removed_files,
+            report.removed_bytes
         );
+        output = output.with_value("cleanup", &report);
+    }
+    Ok(output)
+}
+
+#[cfg(feature = "mpc")]
+#[instrument(level = "debug", skip(curve, config))]
+fn run_prove_local(
+    curve: &dyn CurveOps,
+    config: ProveLocalConfig,
+) -> color_eyre::Result<CommandOutput> {
+    if config.inputs.is_empty() == config.witnesses.is_empty() {
+        return Err(eyre!(
+            "Either the input shares or the witness shares of all parties are required"
+        ));
+    }
+    file_utils::check_file_exists(&config.zkey)?;
+    tracing::warn!("Proving locally without MPC, the reconstructed witness is not protected");
+
+    let start = Instant::now();
+    let generated = curve.prove_local(&config)?;
+    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+    tracing::info!("Local proof generation took {} ms", duration_ms);
+
+    // write result to output file
+    write_proof(&generated.proof, &config.out)?;
+    let mut output = CommandOutput::success().with_file("proof", config.out);
+
+    // write public input to output file
+    if let Some(public_input_filename) = config.public_input {
+        write_public_input(&generated.public_inputs, &public_input_filename)?;
+        output.add_file("public_input", public_input_filename);
     }
     tracing::info!("Proof generation finished successfully");
-    Ok(ExitCode::SUCCESS)
+    Ok(output)
 }
 
-#[instrument(level = "debug", skip(config))]
-fn run_verify<P: Pairing + CircomArkworksPairingBridge>(
-    config: VerifyConfig,
-) -> color_eyre::Result<ExitCode>
-where
-    P::ScalarField: CircomArkworksPrimeFieldBridge,
-    P::BaseField: CircomArkworksPrimeFieldBridge,
-{
-    let proofsystem = config.proof_system;
+#[instrument(level = "debug", skip(curve, config))]
+fn run_verify(curve: &dyn CurveOps, config: VerifyConfig) -> color_eyre::Result<CommandOutput> {
     let proof = config.proof;
     let vk = config.vk;
     let public_input = config.public_input;
@@ -625,96 +2447,528 @@ where
     file_utils::check_file_exists(&vk)?;
     file_utils::check_file_exists(&public_input)?;
 
-    // parse circom proof file
-    let proof_file = BufReader::new(File::open(&proof).context("while opening proof file")?);
-
-    // parse circom verification key file
-    let vk_file = BufReader::new(File::open(&vk).context("while opening verification key file")?);
-
     // parse public inputs
     let public_inputs_file =
-        BufReader::new(File::open(&public_input).context("while opening public inputs file")?);
-    let public_inputs_as_strings: Vec<String> = serde_json::from_reader(public_inputs_file)
-        .context(
-            "while parsing public inputs, expect them to be array of stringified field elements",
-        )?;
-    // skip 1 atm
-    let public_inputs = public_inputs_as_strings
-        .into_iter()
-        .map(|s| {
-            s.parse::<P::ScalarField>()
-                .map_err(|_| eyre!("could not parse as field element: {}", s))
-        })
-        .collect::<Result<Vec<P::ScalarField>, _>>()
-        .context("while converting public input strings to field elements")?;
+        storage::open(&public_input).context("while opening public inputs file")?;
+    let public_inputs: Vec<String> = serde_json::from_reader(public_inputs_file).context(
+        "while parsing public inputs, expect them to be array of stringified field elements",
+    )?;
 
     // verify proof
-    let res = match proofsystem {
-        ProofSystem::Groth16 => {
-            let proof: Groth16Proof<P> = serde_json::from_reader(proof_file)
-                .context("while deserializing proof from file")?;
-
-            let vk: Groth16JsonVerificationKey<P> = serde_json::from_reader(vk_file)
-                .context("while deserializing verification key from file")?;
-
-            // The actual verifier
-            let start = Instant::now();
-            let res = Groth16::<P>::verify(&vk, &proof, &public_inputs);
-            let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
-            tracing::info!("Proof verification took {} ms", duration_ms);
-            res
-        }
-        ProofSystem::Plonk => {
-            let proof: PlonkProof<P> = serde_json::from_reader(proof_file)
-                .context("while deserializing proof from file")?;
-
-            let vk: PlonkJsonVerificationKey<P> = serde_json::from_reader(vk_file)
-                .context("while deserializing verification key from file")?;
-
-            // The actual verifier
-            let start = Instant::now();
-            let res = Plonk::<P>::verify(&vk, &proof, &public_inputs);
-            let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
-            tracing::info!("Proof verification took {} ms", duration_ms);
-            res
-        }
-    };
-
-    match res {
+    match curve.verify(&config.proof_system, &proof, &vk, public_inputs)? {
         Ok(_) => {
             tracing::info!("Proof verified successfully");
-            Ok(ExitCode::SUCCESS)
+            Ok(CommandOutput::success().with_value("valid", true))
         }
         Err(VerificationError::InvalidProof) => {
             tracing::error!("Proof verification failed");
-            Ok(ExitCode::FAILURE)
+            Ok(CommandOutput::failure().with_value("valid", false))
         }
         Err(VerificationError::Malformed(err)) => eyre::bail!(err),
     }
 }
 
-fn merge_input_shares<F: PrimeField>(inputs: Vec<PathBuf>, out: PathBuf) -> color_eyre::Result<()> {
+#[cfg(feature = "mpc")]
+#[instrument(level = "debug", skip(config))]
+fn run_generate_signing_key(config: GenerateSigningKeyConfig) -> color_eyre::Result<CommandOutput> {
+    let key = SigningKeyPair::generate(&mut rand::thread_rng());
+
+    std::fs::write(&config.key_out, key.to_bytes()).context("while writing key file")?;
+    let public_key = key.public_key();
+    tracing::info!(
+        "Wrote signing key pair to file {}, the public key is {public_key}",
+        config.key_out.display()
+    );
+    Ok(CommandOutput::success()
+        .with_file("key_pair", config.key_out)
+        .with_value("public_key", public_key))
+}
+
+#[cfg(feature = "mpc")]
+fn read_committee(path: &Path) -> color_eyre::Result<Committee> {
+    file_utils::check_file_exists(path)?;
+    let committee_file = BufReader::new(File::open(path).context("while opening committee file")?);
+    serde_json::from_reader(committee_file).context("while parsing committee file")
+}
+
+#[cfg(feature = "mpc")]
+#[instrument(level = "debug", skip(curve, config))]
+fn run_verify_signatures(
+    curve: &dyn CurveOps,
+    config: VerifySignaturesConfig,
+) -> color_eyre::Result<CommandOutput> {
+    if config.signatures.is_empty() {
+        return Err(eyre!("At least one signature file is required"));
+    }
+    let committee = read_committee(&config.committee)?;
+
+    file_utils::check_file_exists(&config.proof)?;
+    file_utils::check_file_exists(&config.public_input)?;
+    let proof: serde_json::Value =
+        serde_json::from_reader(storage::open(&config.proof).context("while opening proof file")?)
+            .context("while parsing proof file")?;
+    let public_inputs: Vec<String> = serde_json::from_reader(
+        storage::open(&config.public_input).context("while opening public inputs file")?,
+    )
+    .context("while parsing public inputs file")?;
+    let signatures = config
+        .signatures
+        .iter()
+        .map(|path| {
+            file_utils::check_file_exists(path)?;
+            let file = storage::open(path).context("while opening signature file")?;
+            serde_json::from_reader(file).context("while parsing signature file")
+        })
+        .collect::<color_eyre::Result<Vec<SignedStatement>>>()?;
+
+    let proof_bound = if config.check_proof_binding {
+        let binding = curve.groth16_proof_binding(&proof)?;
+        Some(signing::check_proof_binding(&signatures, &binding)?)
+    } else {
+        None
+    };
+
+    let result = committee.verify(proof, public_inputs, &signatures)?;
+    if proof_bound == Some(false) {
+        tracing::error!(
+            "The proof elements differ from the ones the committee signed, the proof was re-randomized"
+        );
+        Ok(CommandOutput::failure()
+            .with_value("valid", false)
+            .with_value("proof_bound", false)
+            .with_value("signatures", &result))
+    } else if result.is_valid() {
+        tracing::info!(
+            "All {} members of the committee signed the proof",
+            result.signed.len()
+        );
+        Ok(CommandOutput::success()
+            .with_value("valid", true)
+            .with_value("proof_bound", proof_bound)
+            .with_value("signatures", &result))
+    } else {
+        tracing::error!(
+            "Signature verification failed, missing: {:?}, unknown: {}, invalid: {}",
+            result.missing,
+            result.unknown.len(),
+            result.invalid.len()
+        );
+        Ok(CommandOutput::failure()
+            .with_value("valid", false)
+            .with_value("signatures", &result))
+    }
+}
+
+#[instrument(level = "debug", skip(curve, config))]
+fn run_calibrate(
+    curve: &dyn CurveOps,
+    config: CalibrateConfig,
+    output_format: OutputFormat,
+) -> color_eyre::Result<CommandOutput> {
+    let report = curve
+        .calibrate(config.size)
+        .context("while measuring the arithmetic throughput")?;
+
+    let mut output = CommandOutput::success().with_value("calibration", &report);
+    if let Some(out) = config.out {
+        let out_file = BufWriter::new(File::create(&out)?);
+        serde_json::to_writer_pretty(out_file, &report)?;
+        tracing::info!("Calibration successfully written to {}", out.display());
+        output.add_file("calibration", out);
+    } else if output_format == OutputFormat::Text {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    }
+    if !report.backend.fast_path && report.backend.adx_bmi2_supported {
+        tracing::warn!(
+            "The CPU supports the assembly backend, but it is not enabled. Build with `--features asm` and `RUSTFLAGS=\"-C target-cpu=native\"` to enable it"
+        );
+    }
+    Ok(output)
+}
+
+#[cfg(feature = "mpc")]
+#[instrument(level = "debug", skip(curve, config))]
+fn run_recommend(
+    curve: &dyn CurveOps,
+    config: RecommendConfig,
+    output_format: OutputFormat,
+) -> color_eyre::Result<CommandOutput> {
+    file_utils::check_file_exists(&config.r1cs)?;
+    let profile = curve.circuit_profile(&config.r1cs)?;
+    let calibration = match &config.calibration {
+        Some(calibration) => {
+            file_utils::check_file_exists(calibration)?;
+            let calibration_file =
+                BufReader::new(File::open(calibration).context("while opening calibration file")?);
+            Some(
+                serde_json::from_reader::<_, CalibrationReport>(calibration_file)
+                    .context("while parsing calibration file")?,
+            )
+        }
+        None => None,
+    };
+    let network = NetworkProfile {
+        latency_ms: config.latency_ms,
+        bandwidth_mbps: config.bandwidth_mbps,
+        parties: config.parties,
+    };
+    let available_threads = std::thread::available_parallelism().map_or(1, usize::from);
+    let recommendation =
+        recommend::recommend(profile, network, calibration.as_ref(), available_threads)
+            .context("while recommending a configuration")?;
+
+    let mut output = CommandOutput::success().with_value("recommendation", &recommendation);
+    if let Some(out) = config.out {
+        let out_file = BufWriter::new(File::create(&out)?);
+        serde_json::to_writer_pretty(out_file, &recommendation)?;
+        tracing::info!("Recommendation successfully written to {}", out.display());
+        output.add_file("recommendation", out);
+    } else if output_format == OutputFormat::Text {
+        println!("{}", serde_json::to_string_pretty(&recommendation)?);
+    }
+    Ok(output)
+}
+
+/// Generates the proofs of all jobs of a batch manifest over a single network connection, see [co_circom::batch].
+#[cfg(feature = "mpc")]
+#[instrument(level = "debug", skip(curve, config))]
+fn run_generate_proof_batch(
+    curve: &dyn CurveOps,
+    config: GenerateProofBatchConfig,
+) -> color_eyre::Result<CommandOutput> {
+    if config.proof_system != ProofSystem::Groth16 {
+        return Err(eyre!(
+            "Batch proof generation is only supported for Groth16"
+        ));
+    }
+    if config.protocol != MPCProtocol::REP3 {
+        return Err(eyre!("Batch proof generation is only supported for REP3"));
+    }
+    if config.jobs == 0 {
+        return Err(eyre!("At least one job needs to run at a time"));
+    }
+    let manifest = BatchManifest::from_file(&config.batch)?;
+    for job in manifest.jobs.iter() {
+        file_utils::check_file_exists(&job.witness)?;
+        file_utils::check_file_exists(&job.zkey)?;
+    }
+    let num_jobs = manifest.jobs.len();
+    let concurrent_jobs = config.jobs.min(num_jobs);
+    let quota = JobQuota::new(concurrent_jobs, config.threads_per_job);
+    tracing::info!(
+        "Proving {num_jobs} jobs, {concurrent_jobs} at a time with {} threads each",
+        quota.threads
+    );
+
+    let network_config: NetworkConfig = config
+        .network
+        .try_into()
+        .context("while converting network config")?;
+    let mpc_net = Rep3MpcNet::new(network_config).context("while connecting to network")?;
+    let session = mpc_net.session_guard().abort_on_ctrl_c();
+    // every slot gets its own channels, which are forked again for every job
+    let mut io_context = IoContext::init(mpc_net).context("while initializing MPC protocol")?;
+    let slots = (0..concurrent_jobs)
+        .map(|_| io_context.fork())
+        .collect::<Result<Vec<_>, _>>()
+        .context("while forking network for the jobs")?;
+
     let start = Instant::now();
+    let results = batch::run_jobs(slots, manifest.jobs, quota, |slot, i, job| {
+        let generated = curve.prove_batch_job(slot, &job)?;
+        tracing::info!("Job {i}: proof generation finished successfully");
+
+        if let Some(out) = &job.out {
+            write_proof(&generated.proof, out)?;
+        }
+        if let Some(public_input_filename) = &job.public_input {
+            write_public_input(&generated.public_inputs, public_input_filename)?;
+        }
+        Ok(job)
+    });
+    // failed jobs are reported below, the session itself finished
+    session.finish();
+    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+    tracing::info!("Batch proof generation took {duration_ms} ms");
+
+    let mut output = CommandOutput::success();
+    let mut failed = Vec::new();
+    for (i, result) in results.into_iter().enumerate() {
+        match result {
+            Ok(job) => {
+                if let Some(out) = job.out {
+                    output.add_file("proof", out);
+                }
+                if let Some(public_input) = job.public_input {
+                    output.add_file("public_input", public_input);
+                }
+            }
+            Err(err) => {
+                tracing::error!("Job {i} failed: {err:?}");
+                failed.push(i);
+            }
+        }
+    }
+    if !failed.is_empty() {
+        return Err(eyre!(
+            "{} of {num_jobs} jobs failed: {failed:?}",
+            failed.len()
+        ));
+    }
+    Ok(output
+        .with_value("jobs", num_jobs)
+        .with_value("duration_ms", duration_ms))
+}
+
+/// Rehearses the proof generation of [run_generate_proof] with dummy shares of a trivial witness, see [co_circom::rehearsal].
+#[cfg(feature = "mpc")]
+#[instrument(level = "debug", skip(curve, config))]
+fn run_rehearsal(
+    curve: &dyn CurveOps,
+    config: GenerateProofConfig,
+) -> color_eyre::Result<CommandOutput> {
+    let proof_system = config.proof_system;
+    let protocol = config.protocol;
+    let t = config.threshold;
+    let zkey = config.zkey;
+    let mut report = RehearsalReport::default();
+
+    let setup = report.check("config", || {
+        if matches!(proof_system, ProofSystem::Plugin(_)) {
+            eyre::bail!("rehearsals are only supported for groth16 and plonk");
+        }
+        if protocol == MPCProtocol::REP3 && t != 1 {
+            eyre::bail!("REP3 only allows the threshold to be 1");
+        }
+        file_utils::check_file_exists(&zkey)?;
+        let fingerprint =
+            RehearsalFingerprint::new(&proof_system, protocol, config.curve, t, &zkey)?;
+        let network_config: NetworkConfig = config
+            .network
+            .try_into()
+            .context("while converting network config")?;
+        network_config.check_config()?;
+        Ok((fingerprint, network_config))
+    });
+    if let Some((fingerprint, network_config)) = setup {
+        curve.rehearse(
+            &mut report,
+            RehearsalSetup {
+                proof_system,
+                protocol,
+                threshold: t,
+                zkey,
+                fingerprint,
+                network_config,
+            },
+        );
+    }
+    rehearsal_output(report)
+}
+#[cfg(feature = "mpc")]
+fn rehearsal_output(report: RehearsalReport) -> color_eyre::Result<CommandOutput> {
+    let report = report.finish();
+    let output = if report.go {
+        CommandOutput::success()
+    } else {
+        CommandOutput::failure()
+    };
+    Ok(output.with_value("rehearsal", &report))
+}
+
+#[instrument(level = "debug", skip(config))]
+/// Serves the verification service until the process is terminated, see [co_circom::verify_server].
+#[cfg(feature = "verify-server")]
+fn run_verify_server(config: VerifyServerConfig) -> color_eyre::Result<CommandOutput> {
+    let runtime = tokio::runtime::Runtime::new().context("while starting the async runtime")?;
+    runtime.block_on(co_circom::verify_server::serve(config))?;
+    Ok(CommandOutput::success())
+}
+
+#[cfg(feature = "mpc")]
+fn run_audit(config: AuditConfig) -> color_eyre::Result<CommandOutput> {
+    if config.transcripts.is_empty() {
+        return Err(eyre!("Provide the session transcripts of all parties"));
+    }
+    let transcripts = config
+        .transcripts
+        .iter()
+        .map(|path| {
+            let file = storage::open(path)
+                .with_context(|| format!("while opening transcript {}", path.display()))?;
+            serde_json::from_reader::<_, SessionTranscript>(file)
+                .with_context(|| format!("while parsing transcript {}", path.display()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let report = transcript::audit(&transcripts);
+    for finding in report.findings.iter() {
+        tracing::warn!("{finding}");
+    }
+    let output = if report.passed() {
+        tracing::info!(
+            "The transcripts of {} parties are consistent over {} phases",
+            report.transcripts,
+            report.phases
+        );
+        CommandOutput::success()
+    } else {
+        tracing::error!(
+            "The transcripts are inconsistent, found {} issues",
+            report.findings.len()
+        );
+        CommandOutput::failure()
+    };
+    Ok(output.with_value("audit", &report))
+}
+
+fn run_cleanup(config: CleanupConfig) -> color_eyre::Result<CommandOutput> {
+    let policy = config.retention_policy();
+    let report = match (&config.session, &config.workspace) {
+        (Some(session), None) => {
+            file_utils::check_dir_exists(session)?;
+            session::cleanup_session(&session::Session::open(session.clone()), &policy)
+                .context("while cleaning up the session")?
+        }
+        (None, Some(workspace)) => {
+            file_utils::check_dir_exists(workspace)?;
+            let older_than = config
+                .older_than_hours
+                .map(|hours| std::time::Duration::from_secs(hours.saturating_mul(60 * 60)));
+            session::cleanup_workspace(workspace, &policy, older_than)
+                .context("while cleaning up the workspace")?
+        }
+        _ => return Err(eyre!("Provide either a session or a workspace directory")),
+    };
+    tracing::info!(
+        "Cleaned up {} sessions, removed {} files ({} bytes), {} files were overwritten before deletion",
+        report.sessions,
+        report.removed_files,
+        report.removed_bytes,
+        report.wiped_files
+    );
+    Ok(CommandOutput::success().with_value("cleanup", &report))
+}
+
+/// Collects the additional proof systems, which can be selected by name in `generate-proof` and `verify`.
+///
+/// A crate providing a proof system is added as an optional dependency, which is enabled with a feature of the same name, lists the names of its plugins in [co_circom::PLUGIN_NAMES], and registers them here, e.g.:
+/// ```ignore
+/// #[cfg(feature = "my-proof-system")]
+/// my_proof_system::register::<P>(&mut registry)?;
+/// ```
+fn proof_system_registry<P: Pairing + CircomArkworksPairingBridge>(
+) -> color_eyre::Result<ProofSystemRegistry<P>>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    // mutable once a plugin feature is enabled
+    #[allow(unused_mut)]
+    let mut registry = ProofSystemRegistry::new();
+    Ok(registry)
+}
+
+/// Returns the public inputs without the constant 1 at position 0 in the format of snarkjs.
+#[cfg(feature = "mpc")]
+fn public_input_strings<F: PrimeField>(public_input: &[F]) -> Vec<String> {
+    public_input
+        .iter()
+        .skip(1) // we skip the constant 1 at position 0
+        .map(|f| {
+            if f.is_zero() {
+                "0".to_string()
+            } else {
+                f.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Writes the public inputs without the constant 1 at position 0, see [public_input_strings], to a JSON file in the format of snarkjs.
+#[cfg(feature = "mpc")]
+fn write_public_input(
+    public_inputs: &[String],
+    public_input_filename: &std::path::Path,
+) -> color_eyre::Result<()> {
+    let mut public_input_file =
+        storage::create(public_input_filename).context("while creating public input file")?;
+    serde_json::to_writer(&mut public_input_file, public_inputs)
+        .context("while writing out public inputs to JSON file")?;
+    public_input_file
+        .finish()
+        .context("while writing public inputs")?;
+    tracing::info!(
+        "Wrote public inputs to file {}",
+        public_input_filename.display()
+    );
+    Ok(())
+}
+
+/// Writes a proof to a JSON file.
+#[cfg(feature = "mpc")]
+fn write_proof(proof: &serde_json::Value, out: &Path) -> color_eyre::Result<()> {
+    let mut out_file = storage::create(out).context("while creating output file")?;
+    serde_json::to_writer(&mut out_file, proof).context("while serializing proof to JSON file")?;
+    out_file.finish().context("while writing proof")?;
+    tracing::info!("Wrote proof to file {}", out.display());
+    Ok(())
+}
+
+#[cfg(feature = "mpc")]
+fn check_witness_dimensions<F: PrimeField, S>(
+    witness_share: &SharedWitness<F, S>,
+    num_public: usize,
+    num_private: usize,
+) -> color_eyre::Result<()> {
+    if witness_share.public_inputs.len() != num_public {
+        eyre::bail!(
+            "expected {} public inputs according to the zkey, but the witness share has {}",
+            num_public,
+            witness_share.public_inputs.len()
+        );
+    }
+    if witness_share.witness.len() != num_private {
+        eyre::bail!(
+            "expected {} private witness elements according to the zkey, but the witness share has {}",
+            num_private,
+            witness_share.witness.len()
+        );
+    }
+    Ok(())
+}
+
+/// Merges the input shares of several data owners, see [CurveOps::merge_input_shares].
+#[cfg(feature = "mpc")]
+fn merge_input_shares<F: PrimeField>(
+    inputs: &[PathBuf],
+    public_inputs: Option<BTreeMap<String, Vec<F>>>,
+) -> color_eyre::Result<SerializeableSharedRep3Input<F, SeedRng>> {
     let mut input_shares = inputs
         .iter()
         .map(|input| {
-            let input_share_file =
-                BufReader::new(File::open(input).context("while opening input share file")?);
+            let (input_share_file, input_share_size) =
+                file_utils::open_untrusted(input, file_utils::MAX_SHARE_FILE_SIZE)
+                    .context("while opening input share file")?;
             let input_share: SerializeableSharedRep3Input<F, SeedRng> =
-                bincode::deserialize_from(input_share_file)
+                file_utils::deserialize_untrusted(input_share_file, input_share_size)
                     .context("trying to parse input share file")?;
             color_eyre::Result::<_>::Ok(input_share)
         })
         .collect::<Result<Vec<_>, _>>()?;
+    co_circom::check_public_inputs(
+        inputs
+            .iter()
+            .map(PathBuf::as_path)
+            .zip(input_shares.iter().map(|share| &share.public_inputs)),
+        public_inputs.as_ref(),
+    )?;
+    if let Some(public_inputs) = public_inputs {
+        for input_share in input_shares.iter_mut() {
+            input_share.public_inputs = public_inputs.clone();
+        }
+    }
     let start_item = input_shares.pop().expect("we have at least two inputs");
-    let merged = input_shares.into_iter().try_fold(start_item, |a, b| {
+    input_shares.into_iter().try_fold(start_item, |a, b| {
         a.merge(b).context("while merging input shares")
-    })?;
-    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
-    tracing::info!("Merging took {} ms", duration_ms);
-
-    let out_file = BufWriter::new(File::create(&out).context("while creating output file")?);
-    bincode::serialize_into(out_file, &merged).context("while serializing witness share")?;
-    tracing::info!("Wrote merged input share to file {}", out.display());
-    Ok(())
+    })
 }
@@ -0,0 +1,128 @@
+use std::{hint::black_box, time::Instant};
+
+use ark_ec::{pairing::Pairing, scalar_mul::variable_base::VariableBaseMSM, CurveGroup, Group};
+use ark_ff::Field;
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+use color_eyre::eyre::ContextCompat;
+use serde::{Deserialize, Serialize};
+
+/// The amount of field multiplications measured by [calibrate].
+const NUM_FIELD_MULS: usize = 1 << 22;
+
+/// The field arithmetic backend co-circom was built with, and the features supported by the CPU of the local machine.
+///
+/// The assembly backend of arkworks is only used if co-circom is built with the `asm` feature *and* the `adx` and `bmi2` target features are enabled at compile time, e.g., with `RUSTFLAGS="-C target-cpu=native"`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldBackend {
+    /// co-circom was built with the `asm` feature
+    pub asm_feature: bool,
+    /// co-circom was built for x86_64 with the `adx` and `bmi2` target features
+    pub adx_bmi2_enabled: bool,
+    /// The CPU of the local machine supports the `adx` and `bmi2` instructions
+    pub adx_bmi2_supported: bool,
+    /// The CPU of the local machine supports the `avx512f` and `avx512ifma` instructions
+    pub avx512_supported: bool,
+    /// The assembly backend is used for the field multiplications
+    pub fast_path: bool,
+}
+
+impl FieldBackend {
+    /// Detects the backend of this build and the features of the CPU.
+    pub fn detect() -> Self {
+        let asm_feature = cfg!(feature = "asm");
+        let adx_bmi2_enabled = cfg!(all(
+            target_arch = "x86_64",
+            target_feature = "adx",
+            target_feature = "bmi2"
+        ));
+        #[cfg(target_arch = "x86_64")]
+        let (adx_bmi2_supported, avx512_supported) = (
+            std::is_x86_feature_detected!("adx") && std::is_x86_feature_detected!("bmi2"),
+            std::is_x86_feature_detected!("avx512f") && std::is_x86_feature_detected!("avx512ifma"),
+        );
+        #[cfg(not(target_arch = "x86_64"))]
+        let (adx_bmi2_supported, avx512_supported) = (false, false);
+        Self {
+            asm_feature,
+            adx_bmi2_enabled,
+            adx_bmi2_supported,
+            avx512_supported,
+            fast_path: asm_feature && adx_bmi2_enabled,
+        }
+    }
+}
+
+/// The throughput of the field and curve arithmetic measured on the local machine, see [calibrate].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalibrationReport {
+    /// The field arithmetic backend
+    pub backend: FieldBackend,
+    /// The measured multiplications in the scalar field per second
+    pub field_muls_per_sec: f64,
+    /// The size of the FFT and the MSM
+    pub size: usize,
+    /// The time of an FFT of `size` elements of the scalar field in ms
+    pub fft_ms: f64,
+    /// The time of an MSM of `size` points in G1 in ms
+    pub msm_ms: f64,
+}
+
+/// Measures the throughput of the scalar field multiplication, an FFT of `size` elements and an MSM of `size` points in G1 of the pairing `P`. `size` must be a power of two.
+///
+/// The inputs are derived deterministically, as only the time of the computations is of interest.
+pub fn calibrate<P: Pairing>(size: usize) -> color_eyre::Result<CalibrationReport> {
+    let domain = Radix2EvaluationDomain::<P::ScalarField>::new(size)
+        .filter(|domain| domain.size() == size)
+        .with_context(|| format!("size {size} must be a power of two supported by the field"))?;
+
+    // four independent chains, so that the measurement is not bound by the latency of a single multiplication
+    let mut acc = [
+        P::ScalarField::from(2u64),
+        P::ScalarField::from(3u64),
+        P::ScalarField::from(5u64),
+        P::ScalarField::from(7u64),
+    ];
+    let factor = P::ScalarField::from(3u64).inverse().expect("non-zero");
+    let start = Instant::now();
+    for _ in 0..NUM_FIELD_MULS / acc.len() {
+        for a in acc.iter_mut() {
+            *a *= factor;
+        }
+    }
+    black_box(acc);
+    let field_muls_per_sec = NUM_FIELD_MULS as f64 / start.elapsed().as_secs_f64();
+
+    let mut scalars = Vec::with_capacity(size);
+    let mut cur = factor;
+    for _ in 0..size {
+        scalars.push(cur);
+        cur *= factor;
+    }
+
+    let mut evals = scalars.clone();
+    let start = Instant::now();
+    domain.fft_in_place(&mut evals);
+    let fft_ms = start.elapsed().as_micros() as f64 / 1000.;
+    black_box(evals);
+
+    let generator = P::G1::generator();
+    let mut bases = Vec::with_capacity(size);
+    let mut point = generator;
+    for _ in 0..size {
+        bases.push(point);
+        point += generator;
+    }
+    let bases = P::G1::normalize_batch(&bases);
+    let start = Instant::now();
+    let result = P::G1::msm_unchecked(&bases, &scalars);
+    let msm_ms = start.elapsed().as_micros() as f64 / 1000.;
+    black_box(result);
+
+    Ok(CalibrationReport {
+        backend: FieldBackend::detect(),
+        field_muls_per_sec,
+        size,
+        fft_ms,
+        msm_ms,
+    })
+}
@@ -0,0 +1,286 @@
+//! The chunked layout of share files with large share vectors.
+//!
+//! A file in the chunked layout starts with [MAGIC], followed by a bincode encoded header and the chunks of the share vector. Each chunk holds the compressed [CanonicalSerialize] encodings of up to [DEFAULT_CHUNK_LEN] elements, and the header stores the number of elements, the size, and the SHA-256 checksum of every chunk. This allows to serialize and deserialize the chunks in parallel and to detect corrupted chunks.
+//!
+//! Files in the chunked layout can be distinguished from the plain bincode layout by their first bytes, see [detect]. The plain bincode layout of the shares starts with the length of the public inputs as `u64`, which is far beyond any valid length if read from [MAGIC].
+use std::io::{self, Cursor, Read, Write};
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+use color_eyre::eyre::{self, Context};
+use rayon::prelude::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::file_utils;
+
+/// The first bytes of a file in the chunked layout.
+pub const MAGIC: [u8; 8] = *b"COCHUNKD";
+
+/// The version of the chunked layout.
+pub const VERSION: u32 = 1;
+
+/// The default number of elements per chunk.
+pub const DEFAULT_CHUNK_LEN: usize = 1 << 16;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ChunkInfo {
+    elements: u64,
+    bytes: u64,
+    checksum: [u8; 32],
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ChunkedHeader<H> {
+    version: u32,
+    header: H,
+    chunks: Vec<ChunkInfo>,
+}
+
+/// The layout of a share file, see [detect].
+pub enum Layout<R: Read> {
+    /// The chunked layout. The reader is positioned after [MAGIC].
+    Chunked(R),
+    /// The plain bincode layout. The reader is positioned at the start of the file.
+    Bincode(io::Chain<Cursor<Vec<u8>>, R>),
+}
+
+/// Reads the first bytes of `reader` to detect the layout of the share file.
+pub fn detect<R: Read>(mut reader: R) -> io::Result<Layout<R>> {
+    let mut prefix = Vec::with_capacity(MAGIC.len());
+    (&mut reader)
+        .take(MAGIC.len() as u64)
+        .read_to_end(&mut prefix)?;
+    if prefix == MAGIC {
+        Ok(Layout::Chunked(reader))
+    } else {
+        Ok(Layout::Bincode(Cursor::new(prefix).chain(reader)))
+    }
+}
+
+fn checksum(bytes: &[u8]) -> [u8; 32] {
+    Sha256::digest(bytes).into()
+}
+
+/// Writes `header` and `elements` in the chunked layout to `writer`, with `chunk_len` elements per chunk. The chunks are serialized in parallel.
+pub fn write<W, H, T>(
+    mut writer: W,
+    header: &H,
+    elements: &[T],
+    chunk_len: usize,
+) -> color_eyre::Result<()>
+where
+    W: Write,
+    H: Serialize,
+    T: CanonicalSerialize + Sync,
+{
+    eyre::ensure!(chunk_len > 0, "the chunk length must not be zero");
+    let chunks = elements
+        .par_chunks(chunk_len)
+        .map(|chunk| {
+            let mut bytes = Vec::with_capacity(chunk.compressed_size());
+            for element in chunk {
+                element.serialize_with_mode(&mut bytes, Compress::Yes)?;
+            }
+            Ok(bytes)
+        })
+        .collect::<Result<Vec<_>, ark_serialize::SerializationError>>()
+        .context("while serializing chunks")?;
+    let header = ChunkedHeader {
+        version: VERSION,
+        header,
+        chunks: chunks
+            .par_iter()
+            .zip(elements.par_chunks(chunk_len))
+            .map(|(bytes, chunk)| ChunkInfo {
+                elements: chunk.len() as u64,
+                bytes: bytes.len() as u64,
+                checksum: checksum(bytes),
+            })
+            .collect(),
+    };
+    writer.write_all(&MAGIC)?;
+    bincode::serialize_into(&mut writer, &header).context("while serializing header")?;
+    for bytes in chunks {
+        writer.write_all(&bytes)?;
+    }
+    Ok(())
+}
+
+/// A reader for the chunked layout, which reads the header first, so that the type of the elements can depend on the header.
+pub struct Reader<R, H> {
+    reader: R,
+    header: H,
+    chunks: Vec<ChunkInfo>,
+}
+
+impl<R: Read, H: DeserializeOwned> Reader<R, H> {
+    /// Reads the header from an untrusted `reader`, which is positioned after [MAGIC] (see [detect]). At most `limit` bytes are read and at most `max_len` elements are accepted.
+    pub fn new(mut reader: R, limit: u64, max_len: usize) -> color_eyre::Result<Self> {
+        let header: ChunkedHeader<H> = file_utils::deserialize_untrusted(&mut reader, limit)
+            .context("while deserializing header")?;
+        if header.version != VERSION {
+            eyre::bail!(
+                "unsupported version {} of the chunked layout, expected {VERSION}",
+                header.version
+            );
+        }
+        let num_elements = header
+            .chunks
+            .iter()
+            .try_fold(0u64, |acc, chunk| acc.checked_add(chunk.elements))
+            .context("number of elements overflows")?;
+        file_utils::check_num_elements(
+            "share vector",
+            usize::try_from(num_elements).unwrap_or(usize::MAX),
+            max_len,
+        )?;
+        let num_bytes = header
+            .chunks
+            .iter()
+            .try_fold(0u64, |acc, chunk| acc.checked_add(chunk.bytes))
+            .context("size of the chunks overflows")?;
+        if num_bytes > limit {
+            eyre::bail!("the chunks have {num_bytes} bytes, but at most {limit} are expected");
+        }
+        Ok(Self {
+            reader,
+            header: header.header,
+            chunks: header.chunks,
+        })
+    }
+
+    /// Returns the header.
+    pub fn header(&self) -> &H {
+        &self.header
+    }
+
+    /// Reads the chunks, verifies them against their checksums, and deserializes the elements in parallel.
+    pub fn read_elements<T: CanonicalDeserialize + Send>(
+        mut self,
+    ) -> color_eyre::Result<(H, Vec<T>)> {
+        let mut chunks = Vec::with_capacity(self.chunks.len());
+        for info in self.chunks.iter() {
+            let mut bytes = Vec::new();
+            (&mut self.reader)
+                .take(info.bytes)
+                .read_to_end(&mut bytes)?;
+            if bytes.len() as u64 != info.bytes {
+                eyre::bail!("unexpected end of file while reading chunk");
+            }
+            chunks.push(bytes);
+        }
+
+        let elements = chunks
+            .into_par_iter()
+            .zip(self.chunks.par_iter())
+            .enumerate()
+            .map(|(i, (bytes, info))| {
+                if checksum(&bytes) != info.checksum {
+                    eyre::bail!("checksum mismatch in chunk {i}");
+                }
+                let mut slice = bytes.as_slice();
+                let elements = (0..info.elements)
+                    .map(|_| T::deserialize_with_mode(&mut slice, Compress::Yes, Validate::Yes))
+                    .collect::<Result<Vec<_>, _>>()
+                    .with_context(|| format!("while deserializing chunk {i}"))?;
+                if !slice.is_empty() {
+                    eyre::bail!("chunk {i} has {} trailing bytes", slice.len());
+                }
+                Ok(elements)
+            })
+            .collect::<color_eyre::Result<Vec<_>>>()?;
+        Ok((self.header, elements.into_iter().flatten().collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::Fr;
+    use ark_ff::UniformRand;
+
+    use super::*;
+
+    const LIMIT: u64 = 1 << 20;
+
+    fn elements() -> Vec<Fr> {
+        let mut rng = rand::thread_rng();
+        (0..10).map(|_| Fr::rand(&mut rng)).collect()
+    }
+
+    // 10 elements in chunks of 3 elements, i.e., four chunks
+    fn chunked_file(elements: &[Fr]) -> Vec<u8> {
+        let mut file = Vec::new();
+        write(&mut file, &"header".to_owned(), elements, 3).unwrap();
+        file
+    }
+
+    fn read(file: &[u8]) -> color_eyre::Result<(String, Vec<Fr>)> {
+        let Layout::Chunked(reader) = detect(file)? else {
+            panic!("expected the chunked layout");
+        };
+        Reader::<_, String>::new(reader, LIMIT, 10)?.read_elements()
+    }
+
+    #[test]
+    fn round_trip_of_multiple_chunks() {
+        let elements = elements();
+        let (header, read) = read(&chunked_file(&elements)).unwrap();
+        assert_eq!(header, "header");
+        assert_eq!(read, elements);
+    }
+
+    #[test]
+    fn flipped_byte_is_detected() {
+        let mut file = chunked_file(&elements());
+        // the last byte belongs to the last chunk
+        *file.last_mut().unwrap() ^= 1;
+        let err = read(&file).unwrap_err();
+        assert_eq!(err.to_string(), "checksum mismatch in chunk 3");
+    }
+
+    #[test]
+    fn truncated_file_is_rejected() {
+        let file = chunked_file(&elements());
+        let err = read(&file[..file.len() - 1]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unexpected end of file while reading chunk"
+        );
+    }
+
+    #[test]
+    fn unsupported_version_is_rejected() {
+        let mut file = chunked_file(&elements());
+        // the version follows MAGIC
+        file[MAGIC.len()..MAGIC.len() + 4].copy_from_slice(&(VERSION + 1).to_le_bytes());
+        let err = read(&file).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "unsupported version {} of the chunked layout, expected {VERSION}",
+                VERSION + 1
+            )
+        );
+    }
+
+    #[test]
+    fn too_many_elements_are_rejected() {
+        let file = chunked_file(&elements());
+        let Layout::Chunked(reader) = detect(file.as_slice()).unwrap() else {
+            panic!("expected the chunked layout");
+        };
+        assert!(Reader::<_, String>::new(reader, LIMIT, 9).is_err());
+    }
+
+    #[test]
+    fn detect_legacy_bincode() {
+        let legacy = bincode::serialize(&(vec![1u64, 2, 3], "legacy".to_owned())).unwrap();
+        let Layout::Bincode(reader) = detect(legacy.as_slice()).unwrap() else {
+            panic!("expected the bincode layout");
+        };
+        // the reader still starts at the beginning of the file
+        let read: (Vec<u64>, String) =
+            file_utils::deserialize_untrusted(reader, legacy.len() as u64).unwrap();
+        assert_eq!(read, (vec![1, 2, 3], "legacy".to_owned()));
+    }
+}
@@ -1,9 +1,29 @@
-use std::path::{Path, PathBuf};
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
 
+#[cfg(feature = "mpc")]
 use ark_ff::PrimeField;
-use color_eyre::eyre::{self, Context, ContextCompat};
+use bincode::Options;
+#[cfg(feature = "mpc")]
+use circom_types::InputLayout;
+use color_eyre::eyre;
+#[cfg(feature = "mpc")]
+use color_eyre::eyre::{Context, ContextCompat};
+#[cfg(feature = "mpc")]
 use num_bigint::BigUint;
+#[cfg(feature = "mpc")]
 use num_traits::Num;
+use serde::de::DeserializeOwned;
+
+use crate::storage::{self, Location};
+
+/// The maximum size in bytes of a share file read from an untrusted source.
+pub const MAX_SHARE_FILE_SIZE: u64 = 1 << 34;
+
+/// The maximum number of shared elements accepted from an untrusted source, if the expected dimensions of the circuit are not known yet.
+pub const MAX_SHARED_ELEMENTS: usize = 1 << 28;
 
 /// An error type for file utility functions.
 #[derive(Debug, thiserror::Error)]
@@ -20,13 +40,28 @@ pub enum Error {
     /// The path was expected to be a file, but it is a directory.
     #[error("Expected {0} to be a file, but it is a directory.")]
     ExpectedFile(PathBuf),
+    /// The file exceeds the maximum allowed size.
+    #[error("File {0} has {1} bytes, which exceeds the maximum of {2} bytes.")]
+    FileTooLarge(PathBuf, u64, u64),
     /// An I/O error occurred.
     #[error(transparent)]
     IoError(#[from] std::io::Error),
+    /// An object storage error occurred.
+    #[error(transparent)]
+    StorageError(#[from] storage::Error),
 }
 
-/// Check if a file exists at the given path, and is actually a file.
+/// Check if a file exists at the given path, and is actually a file. If the path is an object storage URI, checks that the object exists. The standard input `-` and named pipes are always accepted.
 pub fn check_file_exists(file_path: &Path) -> Result<(), Error> {
+    if Location::parse(file_path)?.is_stream() {
+        return Ok(());
+    }
+    if storage::is_remote(file_path) {
+        return match Location::parse(file_path)?.size()? {
+            Some(_) => Ok(()),
+            None => Err(Error::FileNotFound(file_path.to_path_buf())),
+        };
+    }
     if !file_path.exists() {
         return Err(Error::FileNotFound(file_path.to_path_buf()));
     }
@@ -36,8 +71,11 @@ pub fn check_file_exists(file_path: &Path) -> Result<(), Error> {
     Ok(())
 }
 
-/// Check if a directory exists at the given path, and is actually a directory.
+/// Check if a directory exists at the given path, and is actually a directory. Object storage URIs have no directories, so they are always accepted.
 pub fn check_dir_exists(dir_path: &Path) -> Result<(), Error> {
+    if storage::is_remote(dir_path) {
+        return Ok(());
+    }
     if !dir_path.exists() {
         return Err(Error::DirNotFound(dir_path.to_path_buf()));
     }
@@ -47,6 +85,48 @@ pub fn check_dir_exists(dir_path: &Path) -> Result<(), Error> {
     Ok(())
 }
 
+/// Opens a file or object from an untrusted source and checks that it is not larger than `max_size` bytes. Returns a buffered reader together with the size of the file, which should be used as the limit for [deserialize_untrusted]. Objects are only downloaded if they pass the size check. The size of the standard input `-` and of named pipes is unknown, so `max_size` is returned as the limit instead.
+pub fn open_untrusted(
+    file_path: &Path,
+    max_size: u64,
+) -> Result<(Box<dyn Read + Send>, u64), Error> {
+    let location = Location::parse(file_path)?;
+    if location.is_stream() {
+        return Ok((location.open()?, max_size));
+    }
+    if let Location::Local(path) = &location {
+        check_file_exists(path)?;
+    }
+    let size = location
+        .size()?
+        .ok_or_else(|| Error::FileNotFound(file_path.to_path_buf()))?;
+    if size > max_size {
+        return Err(Error::FileTooLarge(file_path.to_path_buf(), size, max_size));
+    }
+    Ok((location.open()?, size))
+}
+
+/// Deserializes a bincode encoded value from an untrusted source. In contrast to [bincode::deserialize_from], at most `limit` bytes are read, so that a forged length prefix cannot make the deserializer allocate arbitrary amounts of memory. The encoding is the same as the one of [bincode::serialize_into].
+pub fn deserialize_untrusted<T: DeserializeOwned, R: Read>(
+    reader: R,
+    limit: u64,
+) -> bincode::Result<T> {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+        .with_limit(limit)
+        .deserialize_from(reader)
+}
+
+/// Checks that the `len` elements of `what` read from an untrusted source do not exceed the `max_len` expected elements.
+pub fn check_num_elements(what: &str, len: usize, max_len: usize) -> color_eyre::Result<()> {
+    if len > max_len {
+        eyre::bail!("{what} has {len} elements, but at most {max_len} are expected");
+    }
+    Ok(())
+}
+
+#[cfg(feature = "mpc")]
 pub(crate) fn parse_field<F>(val: &serde_json::Value) -> color_eyre::Result<F>
 where
     F: std::str::FromStr + PrimeField,
@@ -90,6 +170,21 @@ where
     }
 }
 
+/// Parses the value of an input in an input JSON file, i.e., a field element, a boolean, or a (nested) array of them. Unknown elements `"?"` in arrays are returned as `None`.
+#[cfg(feature = "mpc")]
+pub(crate) fn parse_input_value<F: PrimeField>(
+    val: &serde_json::Value,
+) -> color_eyre::Result<Vec<Option<F>>> {
+    if val.is_array() {
+        parse_array(val)
+    } else if val.is_boolean() {
+        Ok(vec![Some(parse_boolean(val)?)])
+    } else {
+        Ok(vec![Some(parse_field(val)?)])
+    }
+}
+
+#[cfg(feature = "mpc")]
 pub(crate) fn parse_array<F: PrimeField>(
     val: &serde_json::Value,
 ) -> color_eyre::Result<Vec<Option<F>>> {
@@ -98,17 +193,50 @@ pub(crate) fn parse_array<F: PrimeField>(
     for ele in json_arr {
         if ele.is_array() {
             field_elements.extend(parse_array::<F>(ele)?);
-        } else if ele.is_boolean() {
-            field_elements.push(Some(parse_boolean(ele)?));
-        } else if ele.as_str().is_some_and(|e| e == "?") {
-            field_elements.push(None);
         } else {
-            field_elements.push(Some(parse_field(ele)?));
+            field_elements.push(parse_element(ele)?);
         }
     }
     Ok(field_elements)
 }
 
+/// Parses a single element of an input, i.e., a field element, a boolean, or the unknown element `"?"`, which is returned as `None`.
+#[cfg(feature = "mpc")]
+fn parse_element<F: PrimeField>(val: &serde_json::Value) -> color_eyre::Result<Option<F>> {
+    if val.is_boolean() {
+        Ok(Some(parse_boolean(val)?))
+    } else if val.as_str().is_some_and(|e| e == "?") {
+        Ok(None)
+    } else {
+        Ok(Some(parse_field(val)?))
+    }
+}
+
+/// Parses the value of an input with the provided layout, which is required for inputs that are (arrays of) buses. The elements are returned in the order circom assigns them to signals, see [InputLayout::flatten].
+#[cfg(feature = "mpc")]
+pub(crate) fn parse_input_value_with_layout<F: PrimeField>(
+    val: &serde_json::Value,
+    layout: &InputLayout,
+) -> color_eyre::Result<Vec<Option<F>>> {
+    layout
+        .flatten(val)
+        .with_context(|| format!("while flattening input \"{}\"", layout.name))?
+        .into_iter()
+        .map(parse_element)
+        .collect()
+}
+
+/// Returns whether the value of an input contains an object, i.e., whether it is a bus or an array of buses.
+#[cfg(feature = "mpc")]
+pub(crate) fn contains_object(val: &serde_json::Value) -> bool {
+    match val {
+        serde_json::Value::Object(_) => true,
+        serde_json::Value::Array(elements) => elements.iter().any(contains_object),
+        _ => false,
+    }
+}
+
+#[cfg(feature = "mpc")]
 pub(crate) fn parse_boolean<F: PrimeField>(val: &serde_json::Value) -> color_eyre::Result<F> {
     let bool = val
         .as_bool()
@@ -119,3 +247,61 @@ pub(crate) fn parse_boolean<F: PrimeField>(val: &serde_json::Value) -> color_eyr
         Ok(F::ZERO)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn forged_length_prefix_is_rejected() {
+        // claims a string of u64::MAX bytes, which must fail before the buffer is allocated
+        let mut bytes = u64::MAX.to_le_bytes().to_vec();
+        bytes.extend(b"short");
+        let err =
+            deserialize_untrusted::<String, _>(bytes.as_slice(), bytes.len() as u64).unwrap_err();
+        assert!(matches!(*err, bincode::ErrorKind::SizeLimit), "{err}");
+
+        // claims more elements than the limit allows
+        let mut bytes = (1u64 << 40).to_le_bytes().to_vec();
+        bytes.extend(1u64.to_le_bytes());
+        let err =
+            deserialize_untrusted::<Vec<u64>, _>(bytes.as_slice(), bytes.len() as u64).unwrap_err();
+        assert!(matches!(*err, bincode::ErrorKind::SizeLimit), "{err}");
+
+        // the encoding matches the one of bincode::serialize_into
+        let bytes = bincode::serialize(&vec![1u64, 2, 3]).unwrap();
+        let values: Vec<u64> = deserialize_untrusted(bytes.as_slice(), bytes.len() as u64).unwrap();
+        assert_eq!(values, [1, 2, 3]);
+    }
+
+    #[test]
+    fn open_untrusted_checks_size() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&[0u8; 100]).unwrap();
+        file.flush().unwrap();
+
+        let (_, size) = open_untrusted(file.path(), 100).unwrap();
+        assert_eq!(size, 100);
+        let err = open_untrusted(file.path(), 99).err().unwrap();
+        assert!(
+            matches!(err, Error::FileTooLarge(ref path, 100, 99) if path == file.path()),
+            "{err}"
+        );
+        let err = open_untrusted(&file.path().with_extension("missing"), 100)
+            .err()
+            .unwrap();
+        assert!(matches!(err, Error::FileNotFound(_)), "{err}");
+    }
+
+    #[test]
+    fn check_num_elements_rejects_too_many() {
+        check_num_elements("witness", 10, 10).unwrap();
+        let err = check_num_elements("witness", 11, 10).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "witness has 11 elements, but at most 10 are expected"
+        );
+    }
+}
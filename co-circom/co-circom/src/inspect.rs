@@ -0,0 +1,305 @@
+//! Human-readable summaries of share files.
+//!
+//! [inspect_share] tries to parse a share file as every kind of share file co-circom writes, i.e., REP3 input and witness shares and Shamir witness shares, in the current layout and in the layouts of older releases, see [migrate](crate::migrate). The [ShareInspection] of the first match lists the structure of the file, e.g., the names and lengths of the inputs and the public values, but never the values of the shares. It helps to debug mix-ups of share files without reconstructing or revealing any secret.
+use std::{collections::BTreeMap, io::Read, path::Path};
+
+use ark_ff::PrimeField;
+use co_circom_snarks::{SerializeableSharedRep3Input, SerializeableSharedRep3Witness, UsagePolicy};
+use color_eyre::eyre::{self, Context, ContextCompat};
+use mpc_core::protocols::rep3::{MaybeRep3ShareVecType, Rep3ShareVecType};
+use serde::Serialize;
+
+use crate::{
+    chunked, file_utils,
+    migrate::{self, ShareFileKind, ShareFormatVersion},
+    MPCCurve, MPCProtocol, SeedRng,
+};
+
+/// The layout of the bytes of a share file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ShareLayout {
+    /// The [chunked] layout
+    Chunked,
+    /// The plain bincode layout
+    Bincode,
+}
+
+/// The structure of a shared value, without the values of the shares.
+#[derive(Debug, Clone, Serialize)]
+pub struct SharedEntry {
+    /// The name of the input, or `witness` for a witness share
+    pub name: String,
+    /// The type of the shares, e.g., `seeded replicated`
+    pub share_type: String,
+    /// The number of shared elements
+    pub len: usize,
+    /// The declared bit width of the input
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bit_width: Option<usize>,
+    /// The number of elements that are still unknown and need to be merged from another input share
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unknown: Option<usize>,
+}
+
+/// The contents of a share file, without the values of the shares.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ShareContents {
+    /// An input share
+    Input {
+        /// The public inputs by name
+        public_inputs: BTreeMap<String, Vec<String>>,
+        /// The shared inputs
+        shared_inputs: Vec<SharedEntry>,
+        /// The inputs with elements that still need to be merged, see `merge-input-shares`
+        unmerged_inputs: Vec<SharedEntry>,
+        /// The labels of the commitments whose openings are stored in the file
+        commitment_openings: Vec<String>,
+    },
+    /// A witness share
+    Witness {
+        /// The public inputs, starting with the constant 1
+        public_inputs: Vec<String>,
+        /// The shared witness
+        witness: SharedEntry,
+    },
+}
+
+/// A summary of a share file, see the [module documentation](self).
+#[derive(Debug, Clone, Serialize)]
+pub struct ShareInspection {
+    /// The size of the file in bytes
+    pub file_size: u64,
+    /// The curve whose scalar field the file was parsed with
+    pub curve: MPCCurve,
+    /// The MPC protocol of the shares
+    pub protocol: MPCProtocol,
+    /// The release of co-circom that wrote the file, `current` for the layout of this release
+    pub version: String,
+    /// The layout of the file
+    pub layout: ShareLayout,
+    /// The contents of the file
+    #[serde(flatten)]
+    pub contents: ShareContents,
+    /// The usage policy of the shares
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage_policy: Option<UsagePolicy>,
+    /// The other kinds of share files the file can be parsed as, which indicates an ambiguous file
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub also_parses_as: Vec<String>,
+}
+
+/// A kind of share file [inspect_share] tries to parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Candidate {
+    protocol: MPCProtocol,
+    kind: ShareFileKind,
+    version: Option<ShareFormatVersion>,
+}
+
+impl std::fmt::Display for Candidate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            ShareFileKind::Input => "input",
+            ShareFileKind::Witness => "witness",
+        };
+        match self.version {
+            Some(version) => write!(f, "{} {kind} share of version {version}", self.protocol),
+            None => write!(f, "{} {kind} share", self.protocol),
+        }
+    }
+}
+
+/// The kinds of share files in the order they are tried. The current layouts come first, as they are the most likely.
+fn candidates(protocol: Option<MPCProtocol>, kind: Option<ShareFileKind>) -> Vec<Candidate> {
+    let mut candidates = Vec::new();
+    let mut push = |protocol, kind, version| {
+        candidates.push(Candidate {
+            protocol,
+            kind,
+            version,
+        })
+    };
+    push(MPCProtocol::REP3, ShareFileKind::Witness, None);
+    push(MPCProtocol::REP3, ShareFileKind::Input, None);
+    push(MPCProtocol::SHAMIR, ShareFileKind::Witness, None);
+    for version in [ShareFormatVersion::V0_6, ShareFormatVersion::V0_5] {
+        push(MPCProtocol::REP3, ShareFileKind::Witness, Some(version));
+        push(MPCProtocol::REP3, ShareFileKind::Input, Some(version));
+    }
+    candidates.retain(|candidate| {
+        protocol.map_or(true, |protocol| candidate.protocol == protocol)
+            && kind.map_or(true, |kind| candidate.kind == kind)
+    });
+    candidates
+}
+
+fn field_to_string<F: PrimeField>(f: &F) -> String {
+    if f.is_zero() {
+        "0".to_string()
+    } else {
+        f.to_string()
+    }
+}
+
+fn rep3_entry<F: PrimeField>(
+    name: String,
+    share: &Rep3ShareVecType<F, SeedRng>,
+    bit_width: Option<usize>,
+) -> eyre::Result<SharedEntry> {
+    let share_type = match share {
+        Rep3ShareVecType::Replicated(_) => "replicated",
+        Rep3ShareVecType::SeededReplicated(_) => "seeded replicated",
+        Rep3ShareVecType::Additive(_) => "additive",
+        Rep3ShareVecType::SeededAdditive(_) => "seeded additive",
+    };
+    Ok(SharedEntry {
+        name,
+        share_type: share_type.to_owned(),
+        len: crate::mpc::rep3_share_vec_len(share)?,
+        bit_width,
+        unknown: None,
+    })
+}
+
+fn input_contents<F: PrimeField>(
+    share: SerializeableSharedRep3Input<F, SeedRng>,
+) -> eyre::Result<(ShareContents, Option<UsagePolicy>)> {
+    let shared_inputs = share
+        .shared_inputs
+        .iter()
+        .map(|(name, vec)| rep3_entry(name.clone(), vec, share.bit_widths.get(name).copied()))
+        .collect::<eyre::Result<_>>()?;
+    let unmerged_inputs = share
+        .maybe_shared_inputs
+        .iter()
+        .map(|(name, vec)| {
+            let (share_type, len, unknown) = match vec {
+                MaybeRep3ShareVecType::Replicated(vec) => (
+                    "replicated",
+                    vec.len(),
+                    vec.iter().filter(|x| x.is_none()).count(),
+                ),
+                MaybeRep3ShareVecType::Additive(vec) => (
+                    "additive",
+                    vec.len(),
+                    vec.iter().filter(|x| x.is_none()).count(),
+                ),
+            };
+            SharedEntry {
+                name: name.clone(),
+                share_type: share_type.to_owned(),
+                len,
+                bit_width: share.bit_widths.get(name).copied(),
+                unknown: Some(unknown),
+            }
+        })
+        .collect();
+    let contents = ShareContents::Input {
+        public_inputs: share
+            .public_inputs
+            .iter()
+            .map(|(name, values)| (name.clone(), values.iter().map(field_to_string).collect()))
+            .collect(),
+        shared_inputs,
+        unmerged_inputs,
+        commitment_openings: share.commitment_openings.into_keys().collect(),
+    };
+    Ok((contents, share.usage_policy))
+}
+
+fn rep3_witness_contents<F: PrimeField>(
+    share: SerializeableSharedRep3Witness<F, SeedRng>,
+) -> eyre::Result<(ShareContents, Option<UsagePolicy>)> {
+    let contents = ShareContents::Witness {
+        public_inputs: share.public_inputs.iter().map(field_to_string).collect(),
+        witness: rep3_entry("witness".to_owned(), &share.witness, None)?,
+    };
+    Ok((contents, share.usage_policy))
+}
+
+/// Parses the bytes of a share file as the `candidate` and returns its contents.
+fn parse_candidate<F: PrimeField>(
+    bytes: &[u8],
+    candidate: Candidate,
+) -> eyre::Result<(ShareContents, Option<UsagePolicy>)> {
+    let limit = bytes.len() as u64;
+    let max_len = file_utils::MAX_SHARED_ELEMENTS;
+    match (candidate.protocol, candidate.kind, candidate.version) {
+        (MPCProtocol::REP3, ShareFileKind::Witness, None) => rep3_witness_contents(
+            crate::parse_serialized_witness_share_rep3::<_, F>(bytes, limit, max_len)?,
+        ),
+        (MPCProtocol::REP3, ShareFileKind::Input, None) => input_contents(
+            crate::parse_serialized_input_share_rep3::<_, F>(bytes, limit, max_len)?,
+        ),
+        (MPCProtocol::REP3, ShareFileKind::Witness, Some(version)) => rep3_witness_contents(
+            migrate::migrate_witness_share::<F, _>(bytes, version, limit)?,
+        ),
+        (MPCProtocol::REP3, ShareFileKind::Input, Some(version)) => {
+            input_contents(migrate::migrate_input_share::<F, _>(bytes, version, limit)?)
+        }
+        (MPCProtocol::SHAMIR, ShareFileKind::Witness, None) => {
+            let share = crate::parse_witness_share_shamir::<_, F>(bytes, limit, max_len)?;
+            let contents = ShareContents::Witness {
+                public_inputs: share.public_inputs.iter().map(field_to_string).collect(),
+                witness: SharedEntry {
+                    name: "witness".to_owned(),
+                    share_type: "shamir".to_owned(),
+                    len: share.witness.len(),
+                    bit_width: None,
+                    unknown: None,
+                },
+            };
+            Ok((contents, None))
+        }
+        _ => eyre::bail!("{candidate} files do not exist"),
+    }
+}
+
+/// Inspects the share file at `path` with the scalar field `F` of `curve`, see the [module documentation](self). Only the kinds of share files matching the `protocol` and `kind` are tried, if provided.
+pub fn inspect_share<F: PrimeField>(
+    path: &Path,
+    curve: MPCCurve,
+    protocol: Option<MPCProtocol>,
+    kind: Option<ShareFileKind>,
+) -> eyre::Result<ShareInspection> {
+    let (mut file, file_size) = file_utils::open_untrusted(path, file_utils::MAX_SHARE_FILE_SIZE)
+        .context("trying to open share file")?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)
+        .context("while reading share file")?;
+    let layout = if bytes.starts_with(&chunked::MAGIC) {
+        ShareLayout::Chunked
+    } else {
+        ShareLayout::Bincode
+    };
+
+    let mut matches = candidates(protocol, kind)
+        .into_iter()
+        // older releases did not write the chunked layout
+        .filter(|candidate| layout == ShareLayout::Bincode || candidate.version.is_none())
+        .filter_map(|candidate| {
+            parse_candidate::<F>(&bytes, candidate)
+                .inspect_err(|err| tracing::debug!("not a {candidate}: {err:#}"))
+                .ok()
+                .map(|parsed| (candidate, parsed))
+        });
+    let (candidate, (contents, usage_policy)) = matches.next().with_context(|| {
+        format!("the file is not a share file of curve {curve} with the provided protocol and kind")
+    })?;
+    Ok(ShareInspection {
+        file_size,
+        curve,
+        protocol: candidate.protocol,
+        version: candidate
+            .version
+            .map_or_else(|| "current".to_owned(), |version| version.to_string()),
+        layout,
+        contents,
+        usage_policy,
+        also_parses_as: matches
+            .map(|(candidate, _)| candidate.to_string())
+            .collect(),
+    })
+}
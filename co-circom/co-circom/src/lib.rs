@@ -1,69 +1,127 @@
 #![warn(missing_docs)]
 //! This crate provides a binary and associated helper library for running collaborative SNARK proofs.
-use std::{
-    collections::BTreeMap,
-    fs::File,
-    io::{BufReader, Read},
-    path::PathBuf,
-    sync::Arc,
-    time::Instant,
-};
+use std::path::PathBuf;
 
-use ark_ec::pairing::Pairing;
-use ark_ff::PrimeField;
-use circom_mpc_compiler::{CoCircomCompiler, CompilerConfig, SimplificationLevel};
-use circom_mpc_vm::mpc_vm::VMConfig;
-use circom_types::{
-    groth16::{Groth16Proof, ZKey},
-    traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge},
-};
 use clap::Args;
 use clap::ValueEnum;
-use co_circom_snarks::{
-    SerializeableSharedRep3Input, SerializeableSharedRep3Witness, SharedInput, SharedWitness,
-};
-use co_groth16::Rep3CoGroth16;
-use color_eyre::eyre::{bail, Context, ContextCompat};
 use figment::{
     providers::{Env, Format, Serialized, Toml},
     Figment,
 };
-use mpc_core::protocols::{
-    rep3::{
-        network::{Rep3MpcNet, Rep3Network},
-        Rep3PrimeFieldShare, Rep3ShareVecType,
-    },
-    shamir::ShamirPrimeFieldShare,
-};
-use mpc_net::config::{NetworkConfig, NetworkConfigFile};
-use rand::{CryptoRng, Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 
-/// The Rng used for expanding compressed Shares
-pub type SeedRng = rand_chacha::ChaCha12Rng;
-
+/// A module for proving many independent circuits concurrently in one invocation.
+#[cfg(feature = "mpc")]
+pub mod batch;
+/// A module for measuring the throughput of the field and curve arithmetic.
+pub mod calibrate;
+/// A module for the chunked layout of share files, which is written and read in parallel.
+#[cfg(feature = "mpc")]
+pub mod chunked;
 /// A module for file utility functions.
 pub mod file_utils;
+/// A module for summarizing share files without revealing the shares.
+#[cfg(feature = "mpc")]
+pub mod inspect;
+/// A module for upgrading share files written by older releases.
+#[cfg(feature = "mpc")]
+pub mod migrate;
+/// A module for the terminal UI that monitors a running session.
+#[cfg(feature = "tui")]
+pub mod monitor;
+pub use co_circom_snarks::output;
+/// A module for registering additional proof systems.
+#[cfg(feature = "mpc")]
+pub mod plugins;
+/// A module for recommending the MPC configuration of a circuit.
+#[cfg(feature = "mpc")]
+pub mod recommend;
+/// A module for rehearsing the proof generation with dummy shares before the real data is used.
+#[cfg(feature = "mpc")]
+pub mod rehearsal;
+/// A module for the session directory convention and the cleanup of session artifacts.
+pub mod session;
+/// A module for signing proofs with the long-term keys of the parties.
+#[cfg(feature = "mpc")]
+pub mod signing;
+/// A module for reading and writing files from object storage.
+pub mod storage;
+/// A module for the machine-readable session transcripts and their audit.
+#[cfg(feature = "mpc")]
+pub mod transcript;
+/// A module for the HTTP service that verifies proofs against registered verification keys.
+#[cfg(feature = "verify-server")]
+pub mod verify_server;
+/// A module for the warm-up phase at the start of a session.
+#[cfg(feature = "mpc")]
+pub mod warmup;
+
+/// The names of the additional proof systems that are compiled in, which are the only names besides the built-in proof systems that [ProofSystem] accepts. A crate providing a proof system lists its names here behind the feature that enables it, e.g., `#[cfg(feature = "my-proof-system")] "my-proof-system"`, and registers them in a [ProofSystemRegistry](plugins::ProofSystemRegistry).
+pub const PLUGIN_NAMES: &[&str] = &[
+    #[cfg(test)]
+    "test-plugin",
+];
 
 /// An enum representing the ZK proof system to use.
-#[derive(Debug, Clone, ValueEnum, Serialize, Deserialize)]
-#[clap(rename_all = "lower")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
 pub enum ProofSystem {
     /// The Groth16 proof system.
     Groth16,
     /// The Plonk proof system.
     Plonk,
+    /// A proof system registered in a [ProofSystemRegistry](plugins::ProofSystemRegistry) under the given name.
+    Plugin(String),
 }
 
 impl std::fmt::Display for ProofSystem {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            ProofSystem::Groth16 => write!(f, "Plonk"),
-            ProofSystem::Plonk => write!(f, "Groth16"),
+            ProofSystem::Groth16 => write!(f, "Groth16"),
+            ProofSystem::Plonk => write!(f, "Plonk"),
+            ProofSystem::Plugin(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+impl std::str::FromStr for ProofSystem {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("groth16") {
+            Ok(ProofSystem::Groth16)
+        } else if s.eq_ignore_ascii_case("plonk") {
+            Ok(ProofSystem::Plonk)
+        } else if PLUGIN_NAMES.contains(&s) {
+            Ok(ProofSystem::Plugin(s.to_owned()))
+        } else {
+            let expected = ["groth16", "plonk"]
+                .iter()
+                .chain(PLUGIN_NAMES)
+                .copied()
+                .collect::<Vec<_>>()
+                .join(", ");
+            Err(format!(
+                "unknown proof system \"{s}\", expected one of [{expected}]"
+            ))
         }
     }
 }
 
+impl TryFrom<String> for ProofSystem {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<ProofSystem> for String {
+    fn from(value: ProofSystem) -> Self {
+        value.to_string()
+    }
+}
+
 /// An enum representing the MPC protocol to use.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum MPCCurve {
@@ -71,17 +129,20 @@ pub enum MPCCurve {
     BN254,
     /// The BLS12_381 curve.
     BLS12_381,
+    /// The BLS12_377 curve.
+    BLS12_377,
 }
 
 impl ValueEnum for MPCCurve {
     fn value_variants<'a>() -> &'a [Self] {
-        &[MPCCurve::BN254, MPCCurve::BLS12_381]
+        &[MPCCurve::BN254, MPCCurve::BLS12_381, MPCCurve::BLS12_377]
     }
 
     fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
         match self {
             MPCCurve::BN254 => Some(clap::builder::PossibleValue::new("BN254")),
             MPCCurve::BLS12_381 => Some(clap::builder::PossibleValue::new("BLS12-381")),
+            MPCCurve::BLS12_377 => Some(clap::builder::PossibleValue::new("BLS12-377")),
         }
     }
 }
@@ -91,6 +152,7 @@ impl std::fmt::Display for MPCCurve {
         match self {
             MPCCurve::BN254 => write!(f, "BN254"),
             MPCCurve::BLS12_381 => write!(f, "BLS12-381"),
+            MPCCurve::BLS12_377 => write!(f, "BLS12-377"),
         }
     }
 }
@@ -116,381 +178,192 @@ impl std::fmt::Display for MPCProtocol {
     }
 }
 
-/// Cli arguments for `split_witness`
-#[derive(Debug, Default, Serialize, Args)]
-pub struct SplitWitnessCli {
+/// Cli arguments for `verify`
+#[derive(Debug, Serialize, Args)]
+pub struct VerifyCli {
+    /// The proof system to be used, either groth16, plonk, or the name of a registered plugin
+    #[arg()]
+    pub proof_system: ProofSystem,
     /// The path to the config file
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub config: Option<PathBuf>,
-    /// The path to the input witness file generated by Circom
-    #[arg(long)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub witness: Option<PathBuf>,
-    /// The path to the r1cs file, generated by Circom compiler
+    /// The path to the proof file
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub r1cs: Option<PathBuf>,
-    /// The MPC protocol to be used
-    #[arg(long, value_enum)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub protocol: Option<MPCProtocol>,
+    pub proof: Option<PathBuf>,
     /// The pairing friendly curve to be used
     #[arg(long, value_enum)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub curve: Option<MPCCurve>,
-    /// The path to the (existing) output directory
-    #[arg(long)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub out_dir: Option<PathBuf>,
-    /// The threshold of tolerated colluding parties
-    #[arg(short, long, default_value_t = 1)]
-    pub threshold: usize,
-    /// The number of parties
-    #[arg(short, long, default_value_t = 3)]
-    pub num_parties: usize,
-    /// Share with compression using Seeds
-    #[arg(short, long, default_value_t = false)]
-    pub seeded: bool,
-    /// Share compressed as additive shares
-    #[arg(short, long, default_value_t = false)]
-    pub additive: bool,
-}
-
-/// Config for `split_witness`
-#[derive(Debug, Deserialize)]
-pub struct SplitWitnessConfig {
-    /// The path to the input witness file generated by Circom
-    pub witness: PathBuf,
-    /// The path to the r1cs file, generated by Circom compiler
-    pub r1cs: PathBuf,
-    /// The MPC protocol to be used
-    pub protocol: MPCProtocol,
-    /// The pairing friendly curve to be used
-    pub curve: MPCCurve,
-    /// The path to the (existing) output directory
-    pub out_dir: PathBuf,
-    /// The threshold of tolerated colluding parties
-    pub threshold: usize,
-    /// The number of parties
-    pub num_parties: usize,
-    /// Share with compression using Seeds
-    pub seeded: bool,
-    /// Share compressed as additive shares
-    pub additive: bool,
-}
-
-/// Cli arguments for `split_input`
-#[derive(Debug, Default, Clone, Serialize, Args)]
-pub struct SplitInputCli {
-    /// The path to the config file
-    #[arg(long)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub config: Option<PathBuf>,
-    /// The path to the input JSON file
-    #[arg(long)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub input: Option<PathBuf>,
-    /// The path to the circuit file
+    /// The path to the verification key file
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub circuit: Option<String>,
-    /// The MPC protocol to be used
-    #[arg(long, value_enum)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub protocol: Option<MPCProtocol>,
-    /// The pairing friendly curve to be used
-    #[arg(long, value_enum)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub curve: Option<MPCCurve>,
-    /// The path to the (existing) output directory
+    pub vk: Option<PathBuf>,
+    /// The path to the public input JSON file
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub out_dir: Option<PathBuf>,
-    /// Share with compression using Seeds
-    #[arg(short, long, default_value_t = false)]
-    pub seeded: bool,
-    /// Share compressed as additive shares
-    #[arg(short, long, default_value_t = false)]
-    pub additive: bool,
+    pub public_input: Option<PathBuf>,
 }
 
-/// Config for `split_input`
-#[derive(Debug, Clone, Deserialize)]
-pub struct SplitInputConfig {
-    /// The path to the input JSON file
-    pub input: PathBuf,
-    /// The path to the circuit file
-    pub circuit: String,
-    /// The MPC protocol to be used
-    pub protocol: MPCProtocol,
+/// Config for `verify`
+#[derive(Debug, Deserialize)]
+pub struct VerifyConfig {
+    /// The proof system to be used
+    pub proof_system: ProofSystem,
+    /// The path to the proof file
+    pub proof: PathBuf,
     /// The pairing friendly curve to be used
     pub curve: MPCCurve,
-    /// The path to the (existing) output directory
-    pub out_dir: PathBuf,
-    /// MPC compiler config
-    #[serde(default)]
-    pub compiler: CompilerConfig,
-    /// Share with compression using Seeds
-    pub seeded: bool,
-    /// Share compressed as additive shares
-    pub additive: bool,
+    /// The path to the verification key file
+    pub vk: PathBuf,
+    /// The path to the public input JSON file
+    pub public_input: PathBuf,
 }
 
-/// Cli arguments for `merge_input_shares`
-#[derive(Debug, Default, Serialize, Args)]
-pub struct MergeInputSharesCli {
+/// Cli arguments for `calibrate`
+#[derive(Debug, Serialize, Args)]
+pub struct CalibrateCli {
     /// The path to the config file
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub config: Option<PathBuf>,
-    /// The path to the input JSON file
-    #[arg(long)]
-    pub inputs: Vec<PathBuf>,
-    /// The MPC protocol to be used
-    #[arg(long, value_enum)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub protocol: Option<MPCProtocol>,
     /// The pairing friendly curve to be used
     #[arg(long, value_enum)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub curve: Option<MPCCurve>,
-    /// The output file where the merged input share is written to
+    /// The size of the measured FFT and MSM, must be a power of two
+    #[arg(long, default_value_t = 1 << 16)]
+    pub size: usize,
+    /// The output file where the measurements are written to as JSON, printed to stdout if not set
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub out: Option<PathBuf>,
 }
 
-/// Config for `merge_input_shares`
+/// Config for `calibrate`
 #[derive(Debug, Deserialize)]
-pub struct MergeInputSharesConfig {
-    /// The path to the input JSON file
-    pub inputs: Vec<PathBuf>,
-    /// The MPC protocol to be used
-    pub protocol: MPCProtocol,
+pub struct CalibrateConfig {
     /// The pairing friendly curve to be used
     pub curve: MPCCurve,
-    /// The output file where the merged input share is written to
-    pub out: PathBuf,
+    /// The size of the measured FFT and MSM, must be a power of two
+    pub size: usize,
+    /// The output file where the measurements are written to as JSON, printed to stdout if not set
+    pub out: Option<PathBuf>,
 }
 
-/// Cli arguments for `generate_witness`
+/// Cli arguments for `cleanup`
 #[derive(Debug, Default, Serialize, Args)]
-pub struct GenerateWitnessCli {
+pub struct CleanupCli {
     /// The path to the config file
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub config: Option<PathBuf>,
-    /// The path to the input share file
+    /// The path to a session directory that is cleaned up
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub input: Option<PathBuf>,
-    /// The path to the circuit file
+    pub session: Option<PathBuf>,
+    /// The path to a workspace directory, all sessions in this directory are cleaned up
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub circuit: Option<PathBuf>,
-    /// The path to the circuit file
-    #[arg(long, short = 'l')]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub link_library: Option<Vec<PathBuf>>,
-    /// The MPC protocol to be used
-    #[arg(long, value_enum)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub protocol: Option<MPCProtocol>,
-    /// The pairing friendly curve to be used
-    #[arg(long, value_enum)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub curve: Option<MPCCurve>,
-    /// The output file where the final witness share is written to
+    pub workspace: Option<PathBuf>,
+    /// Only clean up the sessions of the workspace that were not modified within this amount of hours
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub out: Option<PathBuf>,
-    /// The simplification level passed to the circom compiler (0-2)
-    #[arg(short = 'O', default_value_t = 1, value_parser = clap::value_parser!(u8).range(0..3))]
-    pub simplification_level: u8,
+    pub older_than_hours: Option<u64>,
+    /// The kinds of artifacts that are kept, defaults to proofs and metadata
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::vec::Vec::is_empty")]
+    pub keep: Vec<session::ArtifactKind>,
+    /// Delete shares and checkpoints without overwriting them first
+    #[arg(long, default_value_t = false)]
+    pub insecure_delete: bool,
 }
 
-/// Config for `generate_witness`
+/// Config for `cleanup`
 #[derive(Debug, Deserialize)]
-pub struct GenerateWitnessConfig {
-    /// The path to the input share file
-    pub input: PathBuf,
-    /// The path to the circuit file
-    pub circuit: String,
-    /// The MPC protocol to be used
-    pub protocol: MPCProtocol,
-    /// The pairing friendly curve to be used
-    pub curve: MPCCurve,
-    /// The output file where the final witness share is written to
-    pub out: PathBuf,
-    /// MPC compiler config
+pub struct CleanupConfig {
+    /// The path to a session directory that is cleaned up
+    pub session: Option<PathBuf>,
+    /// The path to a workspace directory, all sessions in this directory are cleaned up
+    pub workspace: Option<PathBuf>,
+    /// Only clean up the sessions of the workspace that were not modified within this amount of hours
+    pub older_than_hours: Option<u64>,
+    /// The kinds of artifacts that are kept
+    #[serde(default = "default_keep")]
+    pub keep: Vec<session::ArtifactKind>,
+    /// Delete shares and checkpoints without overwriting them first
     #[serde(default)]
-    pub compiler: CompilerConfig,
-    /// MPC VM config
-    #[serde(default)]
-    pub vm: VMConfig,
-    /// Network config
-    pub network: NetworkConfigFile,
+    pub insecure_delete: bool,
 }
 
-/// Cli arguments for `transalte_witness`
+/// Cli arguments for `verify-server`
+#[cfg(feature = "verify-server")]
 #[derive(Debug, Serialize, Args)]
-pub struct TranslateWitnessCli {
+pub struct VerifyServerCli {
     /// The path to the config file
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub config: Option<PathBuf>,
-    /// The path to the witness share file
+    /// The address the HTTP API binds to
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub witness: Option<PathBuf>,
-    /// The MPC protocol that was used for the witness generation
-    #[arg(long, value_enum)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub src_protocol: Option<MPCProtocol>,
-    /// The MPC protocol to be used for the proof generation
-    #[arg(long, value_enum)]
+    pub bind_addr: Option<std::net::SocketAddr>,
+    /// The path to the JSON registry of the verification keys, see [verify_server::KeyRegistry]
+    #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub target_protocol: Option<MPCProtocol>,
-    /// The pairing friendly curve to be used
-    #[arg(long, value_enum)]
+    pub registry: Option<PathBuf>,
+    /// The maximum amount of verification requests per second over all clients. Unlimited if not set
+    #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub curve: Option<MPCCurve>,
-    /// The output file where the final witness share is written to
+    pub requests_per_second: Option<f64>,
+    /// The amount of requests that may exceed the rate limit in a burst
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub out: Option<PathBuf>,
+    pub burst: Option<u32>,
 }
 
-/// Config for `transalte_witness`
+/// Config for `verify-server`
+#[cfg(feature = "verify-server")]
 #[derive(Debug, Deserialize)]
-pub struct TranslateWitnessConfig {
-    /// The path to the witness share file
-    pub witness: PathBuf,
-    /// The MPC protocol that was used for the witness generation
-    pub src_protocol: MPCProtocol,
-    /// The MPC protocol to be used for the proof generation
-    pub target_protocol: MPCProtocol,
-    /// The pairing friendly curve to be used
-    pub curve: MPCCurve,
-    /// The output file where the final witness share is written to
-    pub out: PathBuf,
-    /// Network config
-    pub network: NetworkConfigFile,
+pub struct VerifyServerConfig {
+    /// The address the HTTP API binds to
+    pub bind_addr: std::net::SocketAddr,
+    /// The path to the JSON registry of the verification keys
+    pub registry: PathBuf,
+    /// The maximum amount of verification requests per second over all clients
+    pub requests_per_second: Option<f64>,
+    /// The amount of requests that may exceed the rate limit in a burst
+    #[serde(default = "default_burst")]
+    pub burst: u32,
 }
 
-/// Cli arguments for `generate_proof`
-#[derive(Debug, Serialize, Args)]
-pub struct GenerateProofCli {
-    /// The proof system to be used
-    #[arg(value_enum)]
-    pub proof_system: ProofSystem,
-    /// The path to the config file
-    #[arg(long)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub config: Option<PathBuf>,
-    /// The path to the witness share file
-    #[arg(long)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub witness: Option<PathBuf>,
-    /// The path to the proving key (.zkey) file, generated by snarkjs setup phase
-    #[arg(long)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub zkey: Option<PathBuf>,
-    /// The MPC protocol to be used
-    #[arg(long, value_enum)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub protocol: Option<MPCProtocol>,
-    /// The pairing friendly curve to be used
-    #[arg(long, value_enum)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub curve: Option<MPCCurve>,
-    /// The output file where the final proof is written to. If not passed, this party will not write the proof to a file.
-    #[arg(long)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub out: Option<PathBuf>,
-    /// The output JSON file where the public inputs are written to. If not passed, this party will not write the public inputs to a file.
-    #[arg(long)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub public_input: Option<PathBuf>,
-    /// The threshold of tolerated colluding parties
-    #[arg(short, long, default_value_t = 1)]
-    pub threshold: usize,
+#[cfg(feature = "verify-server")]
+fn default_burst() -> u32 {
+    verify_server::DEFAULT_BURST
 }
 
-/// Config for `generate_proof`
-#[derive(Debug, Deserialize)]
-pub struct GenerateProofConfig {
-    /// The proof system to be used
-    pub proof_system: ProofSystem,
-    /// The path to the witness share file
-    pub witness: PathBuf,
-    /// The path to the proving key (.zkey) file, generated by snarkjs setup phase
-    pub zkey: PathBuf,
-    /// The MPC protocol to be used
-    pub protocol: MPCProtocol,
-    /// The pairing friendly curve to be used
-    pub curve: MPCCurve,
-    /// The output file where the final proof is written to. If not passed, this party will not write the proof to a file.
-    pub out: Option<PathBuf>,
-    /// The output JSON file where the public inputs are written to. If not passed, this party will not write the public inputs to a file.
-    pub public_input: Option<PathBuf>,
-    /// The threshold of tolerated colluding parties
-    pub threshold: usize,
-    /// Network config
-    pub network: NetworkConfigFile,
+fn default_keep() -> Vec<session::ArtifactKind> {
+    session::RetentionPolicy::default().keep
 }
 
-/// Cli arguments for `verify`
-#[derive(Debug, Serialize, Args)]
-pub struct VerifyCli {
-    /// The proof system to be used
-    #[arg(value_enum)]
-    pub proof_system: ProofSystem,
-    /// The path to the config file
-    #[arg(long)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub config: Option<PathBuf>,
-    /// The path to the proof file
-    #[arg(long)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub proof: Option<PathBuf>,
-    /// The pairing friendly curve to be used
-    #[arg(long, value_enum)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub curve: Option<MPCCurve>,
-    /// The path to the verification key file
-    #[arg(long)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub vk: Option<PathBuf>,
-    /// The path to the public input JSON file
-    #[arg(long)]
-    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
-    pub public_input: Option<PathBuf>,
+impl CleanupConfig {
+    /// Returns the retention policy of this config.
+    pub fn retention_policy(&self) -> session::RetentionPolicy {
+        session::RetentionPolicy {
+            keep: self.keep.clone(),
+            secure_delete: !self.insecure_delete,
+        }
+    }
 }
 
-/// Config for `verify`
-#[derive(Debug, Deserialize)]
-pub struct VerifyConfig {
-    /// The proof system to be used
-    pub proof_system: ProofSystem,
-    /// The path to the proof file
-    pub proof: PathBuf,
-    /// The pairing friendly curve to be used
-    pub curve: MPCCurve,
-    /// The path to the verification key file
-    pub vk: PathBuf,
-    /// The path to the public input JSON file
-    pub public_input: PathBuf,
-}
+/// The subdirectory of the cache directory (`--cache-dir`) holding the `DomainCache` of the Plonk prover. Other persistent caches get their own subdirectories.
+pub const PLONK_DOMAIN_CACHE_DIR: &str = "plonk-domains";
 
 /// Prefix for config env variables
 pub const CONFIG_ENV_PREFIX: &str = "COCIRCOM_";
 
-/// Error type for config parsing and merging
-#[derive(thiserror::Error, Debug)]
-#[error(transparent)]
-pub struct ConfigError(#[from] figment::error::Error);
+pub use output::ConfigError;
 
 macro_rules! impl_config {
     ($cli: ty, $config: ty) => {
@@ -514,329 +387,14 @@ macro_rules! impl_config {
     };
 }
 
-impl_config!(SplitInputCli, SplitInputConfig);
-impl_config!(SplitWitnessCli, SplitWitnessConfig);
-impl_config!(MergeInputSharesCli, MergeInputSharesConfig);
-impl_config!(TranslateWitnessCli, TranslateWitnessConfig);
-impl_config!(GenerateProofCli, GenerateProofConfig);
 impl_config!(VerifyCli, VerifyConfig);
-
-// manual one since this is a bit more complex
-impl GenerateWitnessConfig {
-    /// Parse config from file, env, cli
-    pub fn parse(mut cli: GenerateWitnessCli) -> Result<Self, ConfigError> {
-        let simplification_level = cli.simplification_level;
-        let link_library = cli.link_library.take().unwrap_or_default();
-        let mut config: GenerateWitnessConfig = if let Some(path) = &cli.config {
-            Figment::new()
-                .merge(Toml::file(path))
-                .merge(Env::prefixed(CONFIG_ENV_PREFIX))
-                .merge(Serialized::defaults(cli))
-                .extract()?
-        } else {
-            Figment::new()
-                .merge(Env::prefixed(CONFIG_ENV_PREFIX))
-                .merge(Serialized::defaults(cli))
-                .extract()?
-        };
-        match simplification_level {
-            0 => config.compiler.simplification = SimplificationLevel::O0,
-            1 => config.compiler.simplification = SimplificationLevel::O1,
-            2 => config.compiler.simplification = SimplificationLevel::O2(usize::MAX),
-            _ => {}
-        }
-        config.compiler.link_library.extend(link_library);
-        Ok(config)
-    }
-}
-
-fn reshare_vec<F: PrimeField, N: Rep3Network>(
-    vec: Vec<F>,
-    mpc_net: &mut N,
-) -> color_eyre::Result<Vec<Rep3PrimeFieldShare<F>>> {
-    mpc_net.send_next_many(&vec)?;
-    let b: Vec<F> = mpc_net.recv_prev_many()?;
-
-    if vec.len() != b.len() {
-        return Err(color_eyre::eyre::eyre!(
-            "reshare_vec: vec and b have different lengths"
-        ));
-    }
-
-    let shares = vec
-        .into_iter()
-        .zip(b)
-        .map(|(a, b)| Rep3PrimeFieldShare { a, b })
-        .collect();
-
-    Ok(shares)
-}
-
-/// Try to parse a [SharedWitness] from a [Read]er.
-pub fn parse_witness_share_rep3<R: Read, F: PrimeField>(
-    reader: R,
-    mpc_net: &mut Rep3MpcNet,
-) -> color_eyre::Result<SharedWitness<F, Rep3PrimeFieldShare<F>>> {
-    let deserialized: SerializeableSharedRep3Witness<F, SeedRng> =
-        bincode::deserialize_from(reader).context("trying to parse witness share file")?;
-
-    let public_inputs = deserialized.public_inputs;
-    let witness = deserialized.witness;
-    let witness = match witness {
-        Rep3ShareVecType::Replicated(vec) => vec,
-        Rep3ShareVecType::SeededReplicated(replicated_seed_type) => {
-            replicated_seed_type.expand_vec()?
-        }
-        Rep3ShareVecType::Additive(vec) => reshare_vec(vec, mpc_net)?,
-        Rep3ShareVecType::SeededAdditive(seeded_type) => {
-            reshare_vec(seeded_type.expand_vec(), mpc_net)?
-        }
-    };
-
-    Ok(SharedWitness {
-        public_inputs,
-        witness,
-    })
-}
-
-/// Try to parse a [SharedWitness] from a [Read]er, returning only the additive shares
-pub fn parse_witness_share_rep3_as_additive<R: Read, F: PrimeField>(
-    reader: R,
-) -> color_eyre::Result<SharedWitness<F, F>> {
-    let deserialized: SerializeableSharedRep3Witness<F, SeedRng> =
-        bincode::deserialize_from(reader).context("trying to parse witness share file")?;
-
-    let public_inputs = deserialized.public_inputs;
-    let witness = deserialized.witness;
-    let witness = match witness {
-        Rep3ShareVecType::Replicated(vec) => vec.into_iter().map(|x| x.a).collect::<Vec<_>>(),
-        Rep3ShareVecType::SeededReplicated(replicated_seed_type) => {
-            replicated_seed_type.a.expand_vec()
-        }
-        Rep3ShareVecType::Additive(vec) => vec,
-        Rep3ShareVecType::SeededAdditive(seeded_type) => seeded_type.expand_vec(),
-    };
-
-    Ok(SharedWitness {
-        public_inputs,
-        witness,
-    })
-}
-
-/// Try to parse a [SharedWitness] from a [Read]er.
-pub fn parse_witness_share_shamir<R: Read, F: PrimeField>(
-    reader: R,
-) -> color_eyre::Result<SharedWitness<F, ShamirPrimeFieldShare<F>>> {
-    bincode::deserialize_from(reader).context("trying to parse witness share file")
-}
-
-/// Splits the input according to the provided parameters.
-pub fn split_input<P>(
-    input: PathBuf,
-    circuit_path: PathBuf,
-    config: CompilerConfig,
-    seeded: bool,
-    additive: bool,
-) -> color_eyre::Result<[SerializeableSharedRep3Input<P::ScalarField, SeedRng>; 3]>
-where
-    P: Pairing + CircomArkworksPairingBridge,
-    P::BaseField: CircomArkworksPrimeFieldBridge,
-    P::ScalarField: CircomArkworksPrimeFieldBridge,
-{
-    //get the public inputs if any from parser
-    let public_inputs = CoCircomCompiler::<P>::get_public_inputs(circuit_path, config)
-        .context("while reading public inputs from circuit")?;
-
-    // read the input file
-    let input_file = BufReader::new(File::open(&input).context("while opening input file")?);
-
-    let input_json: serde_json::Map<String, serde_json::Value> =
-        serde_json::from_reader(input_file).context("while parsing input file")?;
-
-    // create input shares
-    let mut shares = [
-        SerializeableSharedRep3Input::<P::ScalarField, SeedRng>::default(),
-        SerializeableSharedRep3Input::<P::ScalarField, SeedRng>::default(),
-        SerializeableSharedRep3Input::<P::ScalarField, SeedRng>::default(),
-    ];
-
-    let mut rng = rand::thread_rng();
-    for (name, val) in input_json {
-        let parsed_vals = if val.is_array() {
-            file_utils::parse_array(&val)?
-        } else if val.is_boolean() {
-            vec![Some(file_utils::parse_boolean(&val)?)]
-        } else {
-            vec![Some(file_utils::parse_field(&val)?)]
-        };
-        if public_inputs.contains(&name) {
-            let parsed_vals = parsed_vals
-                .into_iter()
-                .collect::<Option<Vec<P::ScalarField>>>()
-                .context("Public inputs must not be unkown")?;
-            shares[0]
-                .public_inputs
-                .insert(name.clone(), parsed_vals.clone());
-            shares[1]
-                .public_inputs
-                .insert(name.clone(), parsed_vals.clone());
-            shares[2].public_inputs.insert(name.clone(), parsed_vals);
-        } else {
-            // if all elements are Some, then we can share normally
-            // else we can only share as Vec<Option<T>> and we have to merge unknown inputs later
-            if parsed_vals.iter().all(Option::is_some) {
-                let parsed_vals = parsed_vals
-                    .into_iter()
-                    .collect::<Option<Vec<_>>>()
-                    .expect("all are Some");
-                let [share0, share1, share2] = SerializeableSharedRep3Input::share_rep3(
-                    &parsed_vals,
-                    &mut rng,
-                    seeded,
-                    additive,
-                );
-                shares[0].shared_inputs.insert(name.clone(), share0);
-                shares[1].shared_inputs.insert(name.clone(), share1);
-                shares[2].shared_inputs.insert(name.clone(), share2);
-            } else {
-                let [share0, share1, share2] =
-                    SerializeableSharedRep3Input::<_, SeedRng>::maybe_share_rep3(
-                        &parsed_vals,
-                        &mut rng,
-                        additive,
-                    );
-                shares[0].maybe_shared_inputs.insert(name.clone(), share0);
-                shares[1].maybe_shared_inputs.insert(name.clone(), share1);
-                shares[2].maybe_shared_inputs.insert(name.clone(), share2);
-            };
-        }
-    }
-    Ok(shares)
-}
-
-/// Try to parse a [SharedInput] from a [Read]er.
-pub fn parse_shared_input<R: Read, F: PrimeField, N: Rep3Network>(
-    reader: R,
-    mpc_net: &mut N,
-) -> color_eyre::Result<SharedInput<F, Rep3PrimeFieldShare<F>>> {
-    let deserialized: SerializeableSharedRep3Input<F, SeedRng> =
-        bincode::deserialize_from(reader).context("trying to parse input share file")?;
-
-    if !deserialized.maybe_shared_inputs.is_empty() {
-        bail!("still unmerged elements left");
-    }
-
-    let public_inputs = deserialized.public_inputs;
-    let shared_inputs_ = deserialized.shared_inputs;
-
-    let mut shared_inputs = BTreeMap::new();
-
-    let mut to_reshare = Vec::new();
-
-    for (_, share) in shared_inputs_.iter() {
-        match share {
-            Rep3ShareVecType::Replicated(_) => {}
-            Rep3ShareVecType::SeededReplicated(_) => {}
-            Rep3ShareVecType::Additive(vec) => to_reshare.extend_from_slice(vec),
-            Rep3ShareVecType::SeededAdditive(seeded_type) => {
-                to_reshare.extend_from_slice(&(seeded_type.to_owned().expand_vec()))
-            }
-        }
-    }
-
-    let mut reshared = reshare_vec(to_reshare, mpc_net)?;
-
-    for (name, share) in shared_inputs_ {
-        match share {
-            Rep3ShareVecType::Replicated(vec) => {
-                shared_inputs.insert(name, vec);
-            }
-            Rep3ShareVecType::SeededReplicated(replicated_seed_type) => {
-                shared_inputs.insert(name, replicated_seed_type.expand_vec()?);
-            }
-            Rep3ShareVecType::Additive(vec) => {
-                shared_inputs.insert(name, reshared.drain(..vec.len()).collect());
-            }
-            Rep3ShareVecType::SeededAdditive(seeded_type) => {
-                shared_inputs.insert(name, reshared.drain(..seeded_type.length()).collect());
-            }
-        }
-    }
-
-    Ok(SharedInput {
-        public_inputs,
-        shared_inputs,
-    })
-}
-
-/// Invoke the MPC witness generation process. It will return a [SharedWitness] if successful.
-/// It executes several steps:
-/// 1. Parse the circuit file.
-/// 2. Compile the circuit to MPC VM bytecode.
-/// 3. Set up a network connection to the MPC network.
-/// 4. Execute the bytecode on the MPC VM to generate the witness.
-pub fn generate_witness_rep3<P, U: Rng + SeedableRng + CryptoRng>(
-    circuit: String,
-    input_share: SharedInput<P::ScalarField, Rep3PrimeFieldShare<P::ScalarField>>,
-    net: Rep3MpcNet,
-    config: GenerateWitnessConfig,
-) -> color_eyre::Result<SerializeableSharedRep3Witness<P::ScalarField, U>>
-where
-    P: Pairing + CircomArkworksPairingBridge,
-    P::BaseField: CircomArkworksPrimeFieldBridge,
-    P::ScalarField: CircomArkworksPrimeFieldBridge,
-    U::Seed: Serialize + for<'a> Deserialize<'a> + Clone + std::fmt::Debug,
-{
-    let circuit_path = PathBuf::from(&circuit);
-    file_utils::check_file_exists(&circuit_path)?;
-
-    // parse circuit file & put through our compiler
-    let parsed_circom_circuit = CoCircomCompiler::<P>::parse(circuit, config.compiler)
-        .context("while parsing circuit file")?;
-
-    let id = usize::from(net.get_id());
-
-    // init MPC protocol
-    let rep3_vm = parsed_circom_circuit
-        .to_rep3_vm_with_network(net, config.vm)
-        .context("while constructing MPC VM")?;
-
-    // execute witness generation in MPC
-    let start = Instant::now();
-    let result_witness_share = rep3_vm
-        .run(input_share)
-        .context("while running witness generation")?;
-
-    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
-    tracing::info!("Party {}: Witness extension took {} ms", id, duration_ms);
-
-    let res = SerializeableSharedRep3Witness::from_shared_witness(
-        result_witness_share.into_shared_witness(),
-    );
-
-    Ok(res)
-}
-
-/// Invoke the MPC proof generation process. It will return a [`Groth16Proof`] if successful.
-/// It executes several steps:
-/// 1. Establishes a TLS connection from the network configuration.
-/// 2. Constructs a [`Rep3CoGroth16`] prover from the protocol.
-/// 3. Executes the prover in MPC
-pub fn prove_with_matrices_rep3<P: Pairing + CircomArkworksPairingBridge>(
-    witness_share: SharedWitness<P::ScalarField, Rep3PrimeFieldShare<P::ScalarField>>,
-    config: NetworkConfig,
-    zkey: ZKey<P>,
-) -> color_eyre::Result<Groth16Proof<P>>
-where
-    P::ScalarField: CircomArkworksPrimeFieldBridge,
-    P::BaseField: CircomArkworksPrimeFieldBridge,
-{
-    tracing::info!("establishing network and building protocol....");
-    let prover = Rep3CoGroth16::with_network_config(config)?;
-    // connect to network
-    tracing::info!("done!");
-    tracing::info!("starting prover...");
-    // execute prover in MPC
-    let zkey = Arc::new(zkey);
-    prover.prove(zkey, witness_share)
-}
+impl_config!(CalibrateCli, CalibrateConfig);
+impl_config!(CleanupCli, CleanupConfig);
+#[cfg(feature = "verify-server")]
+impl_config!(VerifyServerCli, VerifyServerConfig);
+
+// declared after `impl_config` so that the macro is visible in the module
+#[cfg(feature = "mpc")]
+mod mpc;
+#[cfg(feature = "mpc")]
+pub use mpc::*;
@@ -0,0 +1,296 @@
+use std::{collections::BTreeMap, fmt, io::Read, path::Path};
+
+use ark_ff::PrimeField;
+use clap::ValueEnum;
+use co_circom_snarks::{
+    SerializeableSharedRep3Input, SerializeableSharedRep3Witness, SharedWitness,
+};
+use color_eyre::eyre::Context;
+use mpc_core::protocols::rep3::{MaybeRep3ShareVecType, Rep3PrimeFieldShare, Rep3ShareVecType};
+use serde::{Deserialize, Serialize};
+
+use crate::{file_utils, SeedRng};
+
+/// The layout of a REP3 share file written by an older release of co-circom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ShareFormatVersion {
+    /// co-circom 0.5: input shares store one vector of replicated shares per input and witness shares store the replicated shares of the witness.
+    V0_5,
+    /// co-circom 0.6: shares may be additive or compressed, and input shares store the elements of arrays that still need to be merged. The shares do not have declared bit widths, usage policies, or commitment openings.
+    V0_6,
+}
+
+impl ValueEnum for ShareFormatVersion {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[ShareFormatVersion::V0_5, ShareFormatVersion::V0_6]
+    }
+
+    fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+        match self {
+            ShareFormatVersion::V0_5 => Some(clap::builder::PossibleValue::new("0.5")),
+            ShareFormatVersion::V0_6 => Some(clap::builder::PossibleValue::new("0.6")),
+        }
+    }
+}
+
+impl fmt::Display for ShareFormatVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShareFormatVersion::V0_5 => write!(f, "0.5"),
+            ShareFormatVersion::V0_6 => write!(f, "0.6"),
+        }
+    }
+}
+
+/// The kind of a REP3 share file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ValueEnum)]
+#[clap(rename_all = "lower")]
+pub enum ShareFileKind {
+    /// An input share, as written by `split-input` or `merge-input-shares`
+    Input,
+    /// A witness share, as written by `split-witness` or `generate-witness`
+    Witness,
+}
+
+#[derive(Deserialize)]
+#[serde(bound = "")]
+struct Rep3InputV0_5<F: PrimeField> {
+    #[serde(deserialize_with = "mpc_core::ark_de")]
+    public_inputs: BTreeMap<String, Vec<F>>,
+    #[serde(deserialize_with = "mpc_core::ark_de")]
+    shared_inputs: BTreeMap<String, Vec<Rep3PrimeFieldShare<F>>>,
+}
+
+#[derive(Deserialize)]
+#[serde(bound = "")]
+struct Rep3InputV0_6<F: PrimeField> {
+    #[serde(deserialize_with = "mpc_core::ark_de")]
+    public_inputs: BTreeMap<String, Vec<F>>,
+    shared_inputs: BTreeMap<String, Rep3ShareVecType<F, SeedRng>>,
+    maybe_shared_inputs: BTreeMap<String, MaybeRep3ShareVecType<F>>,
+}
+
+#[derive(Deserialize)]
+#[serde(bound = "")]
+struct Rep3WitnessV0_6<F: PrimeField> {
+    #[serde(deserialize_with = "mpc_core::ark_de")]
+    public_inputs: Vec<F>,
+    witness: Rep3ShareVecType<F, SeedRng>,
+}
+
+/// Reads a REP3 input share in the layout of an older release and converts it to the current layout. At most `limit` bytes are read.
+pub fn migrate_input_share<F: PrimeField, R: Read>(
+    reader: R,
+    from: ShareFormatVersion,
+    limit: u64,
+) -> color_eyre::Result<SerializeableSharedRep3Input<F, SeedRng>> {
+    let migrated = match from {
+        ShareFormatVersion::V0_5 => {
+            let old: Rep3InputV0_5<F> = file_utils::deserialize_untrusted(reader, limit)
+                .with_context(|| format!("trying to parse input share file of version {from}"))?;
+            SerializeableSharedRep3Input {
+                public_inputs: old.public_inputs,
+                shared_inputs: old
+                    .shared_inputs
+                    .into_iter()
+                    .map(|(name, shares)| (name, Rep3ShareVecType::Replicated(shares)))
+                    .collect(),
+                maybe_shared_inputs: BTreeMap::new(),
+                bit_widths: BTreeMap::new(),
+                usage_policy: None,
+                commitment_openings: BTreeMap::new(),
+            }
+        }
+        ShareFormatVersion::V0_6 => {
+            let old: Rep3InputV0_6<F> = file_utils::deserialize_untrusted(reader, limit)
+                .with_context(|| format!("trying to parse input share file of version {from}"))?;
+            SerializeableSharedRep3Input {
+                public_inputs: old.public_inputs,
+                shared_inputs: old.shared_inputs,
+                maybe_shared_inputs: old.maybe_shared_inputs,
+                bit_widths: BTreeMap::new(),
+                usage_policy: None,
+                commitment_openings: BTreeMap::new(),
+            }
+        }
+    };
+    Ok(migrated)
+}
+
+/// Reads a REP3 witness share in the layout of an older release and converts it to the current layout. At most `limit` bytes are read.
+pub fn migrate_witness_share<F: PrimeField, R: Read>(
+    reader: R,
+    from: ShareFormatVersion,
+    limit: u64,
+) -> color_eyre::Result<SerializeableSharedRep3Witness<F, SeedRng>> {
+    let migrated = match from {
+        ShareFormatVersion::V0_5 => {
+            let old: SharedWitness<F, Rep3PrimeFieldShare<F>> =
+                file_utils::deserialize_untrusted(reader, limit).with_context(|| {
+                    format!("trying to parse witness share file of version {from}")
+                })?;
+            SerializeableSharedRep3Witness::from_shared_witness(old)
+        }
+        ShareFormatVersion::V0_6 => {
+            let old: Rep3WitnessV0_6<F> = file_utils::deserialize_untrusted(reader, limit)
+                .with_context(|| format!("trying to parse witness share file of version {from}"))?;
+            SerializeableSharedRep3Witness {
+                public_inputs: old.public_inputs,
+                witness: old.witness,
+                usage_policy: None,
+            }
+        }
+    };
+    Ok(migrated)
+}
+
+/// Reads a REP3 share file of the given kind in the layout of an older release and returns it serialized in the current layout.
+pub fn migrate_share_file<F: PrimeField>(
+    path: &Path,
+    kind: ShareFileKind,
+    from: ShareFormatVersion,
+) -> color_eyre::Result<Vec<u8>> {
+    let (file, file_size) = file_utils::open_untrusted(path, file_utils::MAX_SHARE_FILE_SIZE)
+        .context("trying to open share file")?;
+    let bytes = match kind {
+        ShareFileKind::Input => {
+            bincode::serialize(&migrate_input_share::<F, _>(file, from, file_size)?)
+        }
+        ShareFileKind::Witness => {
+            bincode::serialize(&migrate_witness_share::<F, _>(file, from, file_size)?)
+        }
+    }
+    .context("while serializing migrated share")?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use ark_bn254::Fr;
+    use mpc_core::protocols::rep3;
+    use serde::de::DeserializeOwned;
+
+    use super::*;
+
+    // The fixtures are the shares of the three parties written by the respective release
+    fn fixture(from: ShareFormatVersion, name: &str, party: usize) -> PathBuf {
+        PathBuf::from(format!(
+            "../../test_vectors/Shares/v{from}/{name}.{party}.shared"
+        ))
+    }
+
+    // Migrates the share files of all parties and reads them back in the current layout
+    fn migrate_all<T: DeserializeOwned>(
+        kind: ShareFileKind,
+        from: ShareFormatVersion,
+        name: &str,
+    ) -> Vec<T> {
+        (0..3)
+            .map(|party| {
+                let bytes =
+                    migrate_share_file::<Fr>(&fixture(from, name, party), kind, from).unwrap();
+                file_utils::deserialize_untrusted(bytes.as_slice(), bytes.len() as u64).unwrap()
+            })
+            .collect()
+    }
+
+    fn replicated(shares: &Rep3ShareVecType<Fr, SeedRng>) -> Vec<Rep3PrimeFieldShare<Fr>> {
+        match shares {
+            Rep3ShareVecType::Replicated(shares) => shares.clone(),
+            _ => panic!("expected replicated shares"),
+        }
+    }
+
+    fn combine(shares: &[Vec<Rep3PrimeFieldShare<Fr>>]) -> Vec<Fr> {
+        rep3::combine_field_elements(&shares[0], &shares[1], &shares[2])
+    }
+
+    fn fields(values: &[u64]) -> Vec<Fr> {
+        values.iter().copied().map(Fr::from).collect()
+    }
+
+    fn combine_input(inputs: &[SerializeableSharedRep3Input<Fr, SeedRng>], name: &str) -> Vec<Fr> {
+        combine(
+            &inputs
+                .iter()
+                .map(|input| replicated(&input.shared_inputs[name]))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn assert_no_new_fields(input: &SerializeableSharedRep3Input<Fr, SeedRng>) {
+        assert!(input.bit_widths.is_empty());
+        assert!(input.usage_policy.is_none());
+        assert!(input.commitment_openings.is_empty());
+    }
+
+    #[test]
+    fn migrate_input_share_v0_5() {
+        let inputs: Vec<SerializeableSharedRep3Input<Fr, SeedRng>> =
+            migrate_all(ShareFileKind::Input, ShareFormatVersion::V0_5, "input.json");
+        for input in inputs.iter() {
+            assert_eq!(input.public_inputs["pub"], fields(&[7]));
+            assert!(input.maybe_shared_inputs.is_empty());
+            assert_no_new_fields(input);
+        }
+        assert_eq!(combine_input(&inputs, "a"), fields(&[3]));
+        assert_eq!(combine_input(&inputs, "b"), fields(&[11, 12]));
+    }
+
+    #[test]
+    fn migrate_input_share_v0_6() {
+        let inputs: Vec<SerializeableSharedRep3Input<Fr, SeedRng>> =
+            migrate_all(ShareFileKind::Input, ShareFormatVersion::V0_6, "input.json");
+        for input in inputs.iter() {
+            assert_eq!(input.public_inputs["pub"], fields(&[7]));
+            assert_no_new_fields(input);
+        }
+        assert_eq!(combine_input(&inputs, "a"), fields(&[3]));
+        // the second element of b is not known yet and still needs to be merged
+        let b = inputs
+            .iter()
+            .map(|input| match &input.maybe_shared_inputs["b"] {
+                MaybeRep3ShareVecType::Replicated(shares) => {
+                    assert_eq!(shares.len(), 2);
+                    assert!(shares[1].is_none());
+                    vec![shares[0].unwrap()]
+                }
+                _ => panic!("expected replicated shares"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(combine(&b), fields(&[11]));
+    }
+
+    #[test]
+    fn migrate_witness_share() {
+        for from in ShareFormatVersion::value_variants() {
+            let witnesses: Vec<SerializeableSharedRep3Witness<Fr, SeedRng>> =
+                migrate_all(ShareFileKind::Witness, *from, "witness.wtns");
+            for witness in witnesses.iter() {
+                assert_eq!(witness.public_inputs, fields(&[1, 33]));
+                assert!(witness.usage_policy.is_none());
+            }
+            let shares = witnesses
+                .iter()
+                .map(|witness| replicated(&witness.witness))
+                .collect::<Vec<_>>();
+            assert_eq!(combine(&shares), fields(&[3, 11]));
+        }
+    }
+
+    #[test]
+    fn old_witness_share_needs_migration() {
+        for from in ShareFormatVersion::value_variants() {
+            let path = fixture(*from, "witness.wtns", 0);
+            let (file, size) =
+                file_utils::open_untrusted(&path, file_utils::MAX_SHARE_FILE_SIZE).unwrap();
+            let current = file_utils::deserialize_untrusted::<
+                SerializeableSharedRep3Witness<Fr, SeedRng>,
+                _,
+            >(file, size);
+            assert!(current.is_err(), "{from} share parsed without migration");
+        }
+    }
+}
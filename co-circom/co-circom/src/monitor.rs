@@ -0,0 +1,493 @@
+//! A terminal UI for monitoring a running session.
+//!
+//! The [MetricsLayer] is a tracing layer that collects the [SessionMetrics] of a running `generate-witness` or `generate-proof` command: the phases of the prover from its spans, and the bytes exchanged with and the liveness of the other parties from the connection metrics reported by the network, see [mpc_net::METRICS_TARGET]. The [Monitor] renders them with ratatui until the command finishes. While the monitor is shown, the log lines are written to the [SessionMetrics] instead of the terminal and are printed again when the monitor is closed.
+//!
+//! The estimated completion is only available for provers with a known sequence of phases, and is extrapolated from the durations of the finished phases.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    io::Write,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use color_eyre::eyre::{self, Context};
+use mpc_net::METRICS_TARGET;
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyModifiers},
+    layout::{Constraint, Layout},
+    text::Line,
+    widgets::{Block, Borders, Gauge, Paragraph, Row, Table},
+    Frame,
+};
+use tracing::{
+    field::{Field, Visit},
+    span, Event as TracingEvent, Metadata, Subscriber,
+};
+use tracing_subscriber::{
+    filter::{filter_fn, FilterFn},
+    fmt::MakeWriter,
+    layer::Context as LayerContext,
+    registry::LookupSpan,
+    Layer,
+};
+
+/// The number of log lines kept for the monitor.
+const MAX_LOG_LINES: usize = 200;
+/// The interval in which the monitor is redrawn.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+/// The time after which a peer without new connection metrics is shown as stale.
+const STALE_AFTER: Duration = Duration::from_secs(3);
+
+/// The known sequences of prover phases, identified by the names of their spans.
+const PHASE_SEQUENCES: &[&[&str]] = &[
+    &[
+        "witness map from matrices",
+        "msm with blinding",
+        "Groth16 - Openings",
+    ],
+    &["Plonk - Round Init", "Plonk - Round 1", "Plonk - Round 2"],
+];
+
+/// A phase of the session.
+#[derive(Debug, Clone)]
+pub struct Phase {
+    /// The name of the span of the phase
+    pub name: &'static str,
+    /// The time the phase started
+    pub started: Instant,
+    /// The duration of the phase, if it is finished
+    pub duration: Option<Duration>,
+}
+
+/// The connection state of another party.
+#[derive(Debug, Clone)]
+pub struct PeerState {
+    /// The bytes sent to the party
+    pub bytes_sent: u64,
+    /// The bytes received from the party
+    pub bytes_received: u64,
+    /// Whether the connection is open
+    pub alive: bool,
+    /// The round-trip time in milliseconds
+    pub rtt_ms: u64,
+    /// The time of the last connection metrics of the party
+    pub updated: Instant,
+}
+
+#[derive(Debug)]
+struct MetricsState {
+    command: String,
+    started: Instant,
+    phases: Vec<Phase>,
+    peers: BTreeMap<u64, PeerState>,
+    logs: VecDeque<String>,
+}
+
+/// The metrics of a running session, shared between the [MetricsLayer] and the [Monitor].
+#[derive(Debug, Clone)]
+pub struct SessionMetrics(Arc<Mutex<MetricsState>>);
+
+impl SessionMetrics {
+    /// Creates empty metrics for the provided command.
+    pub fn new(command: &str) -> Self {
+        Self(Arc::new(Mutex::new(MetricsState {
+            command: command.to_owned(),
+            started: Instant::now(),
+            phases: Vec::new(),
+            peers: BTreeMap::new(),
+            logs: VecDeque::new(),
+        })))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MetricsState> {
+        // a panic while holding the lock does not leave the metrics in an inconsistent state
+        self.0.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Returns the phases of the session in the order they started.
+    pub fn phases(&self) -> Vec<Phase> {
+        self.lock().phases.clone()
+    }
+
+    /// Returns the total bytes sent to and received from all parties.
+    pub fn total_bytes(&self) -> (u64, u64) {
+        self.lock().peers.values().fold((0, 0), |(tx, rx), peer| {
+            (tx + peer.bytes_sent, rx + peer.bytes_received)
+        })
+    }
+
+    /// Returns the fraction of finished phases and the estimated remaining time, if the phases of the session follow a known sequence and at least one phase finished.
+    pub fn progress(&self) -> Option<(f64, Duration)> {
+        let state = self.lock();
+        let first = state.phases.first()?;
+        let sequence = PHASE_SEQUENCES
+            .iter()
+            .find(|sequence| sequence.first() == Some(&first.name))?;
+        let finished = state
+            .phases
+            .iter()
+            .filter_map(|phase| phase.duration)
+            .collect::<Vec<_>>();
+        if finished.is_empty() {
+            return None;
+        }
+        let average = finished.iter().sum::<Duration>() / finished.len() as u32;
+        let remaining_phases = sequence.len().saturating_sub(finished.len()) as u32;
+        let running = state
+            .phases
+            .iter()
+            .find(|phase| phase.duration.is_none())
+            .map(|phase| phase.started.elapsed())
+            .unwrap_or_default();
+        let remaining = (average * remaining_phases).saturating_sub(running);
+        Some((
+            (finished.len() as f64 / sequence.len() as f64).min(1.0),
+            remaining,
+        ))
+    }
+
+    /// Removes and returns the buffered log lines.
+    pub fn take_logs(&self) -> Vec<String> {
+        self.lock().logs.drain(..).collect()
+    }
+
+    fn push_log(&self, line: String) {
+        let mut state = self.lock();
+        if state.logs.len() == MAX_LOG_LINES {
+            state.logs.pop_front();
+        }
+        state.logs.push_back(line);
+    }
+}
+
+/// A writer for the log lines of the formatting layer, see [SessionMetrics::take_logs].
+pub struct LogWriter {
+    metrics: SessionMetrics,
+    buf: Vec<u8>,
+}
+
+impl Write for LogWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for LogWriter {
+    fn drop(&mut self) {
+        for line in String::from_utf8_lossy(&self.buf).lines() {
+            self.metrics.push_log(line.to_owned());
+        }
+    }
+}
+
+impl<'a> MakeWriter<'a> for SessionMetrics {
+    type Writer = LogWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        LogWriter {
+            metrics: self.clone(),
+            buf: Vec::new(),
+        }
+    }
+}
+
+fn is_phase(metadata: &Metadata<'_>) -> bool {
+    metadata.is_span()
+        && PHASE_SEQUENCES
+            .iter()
+            .any(|sequence| sequence.contains(&metadata.name()))
+}
+
+/// The per-layer filter of the [MetricsLayer], which enables the connection metrics and the phase spans independently of the log level.
+pub fn metrics_filter() -> FilterFn<impl Fn(&Metadata<'_>) -> bool> {
+    filter_fn(|metadata| metadata.target() == METRICS_TARGET || is_phase(metadata))
+}
+
+/// A tracing layer that collects the [SessionMetrics], see the [module documentation](self).
+pub struct MetricsLayer {
+    metrics: SessionMetrics,
+}
+
+impl MetricsLayer {
+    /// Creates a layer that writes to the provided metrics.
+    pub fn new(metrics: SessionMetrics) -> Self {
+        Self { metrics }
+    }
+}
+
+#[derive(Default)]
+struct PeerVisitor {
+    peer: Option<u64>,
+    bytes_sent: u64,
+    bytes_received: u64,
+    alive: bool,
+    rtt_ms: u64,
+}
+
+impl Visit for PeerVisitor {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match field.name() {
+            "peer" => self.peer = Some(value),
+            "bytes_sent" => self.bytes_sent = value,
+            "bytes_received" => self.bytes_received = value,
+            "rtt_ms" => self.rtt_ms = value,
+            _ => {}
+        }
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        if field.name() == "alive" {
+            self.alive = value;
+        }
+    }
+
+    fn record_debug(&mut self, _: &Field, _: &dyn std::fmt::Debug) {}
+}
+
+/// The index of a phase in [MetricsState::phases], stored in the extensions of its span.
+struct PhaseIndex(usize);
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for MetricsLayer {
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: LayerContext<'_, S>) {
+        if !is_phase(attrs.metadata()) {
+            return;
+        }
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut state = self.metrics.lock();
+        state.phases.push(Phase {
+            name: attrs.metadata().name(),
+            started: Instant::now(),
+            duration: None,
+        });
+        span.extensions_mut()
+            .insert(PhaseIndex(state.phases.len() - 1));
+    }
+
+    fn on_close(&self, id: span::Id, ctx: LayerContext<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        if let Some(PhaseIndex(index)) = span.extensions().get::<PhaseIndex>() {
+            let mut state = self.metrics.lock();
+            let phase = &mut state.phases[*index];
+            phase.duration = Some(phase.started.elapsed());
+        }
+    }
+
+    fn on_event(&self, event: &TracingEvent<'_>, _: LayerContext<'_, S>) {
+        if event.metadata().target() != METRICS_TARGET {
+            return;
+        }
+        let mut visitor = PeerVisitor::default();
+        event.record(&mut visitor);
+        if let Some(peer) = visitor.peer {
+            self.metrics.lock().peers.insert(
+                peer,
+                PeerState {
+                    bytes_sent: visitor.bytes_sent,
+                    bytes_received: visitor.bytes_received,
+                    alive: visitor.alive,
+                    rtt_ms: visitor.rtt_ms,
+                    updated: Instant::now(),
+                },
+            );
+        }
+    }
+}
+
+/// A running terminal UI, see the [module documentation](self).
+pub struct Monitor {
+    metrics: SessionMetrics,
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<eyre::Result<()>>,
+}
+
+impl Monitor {
+    /// Takes over the terminal and renders the metrics until [Monitor::stop] is called. Pressing Ctrl-C restores the terminal and aborts the process.
+    pub fn start(metrics: SessionMetrics) -> eyre::Result<Self> {
+        let mut terminal = ratatui::try_init().context("while initializing the terminal")?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = std::thread::spawn({
+            let metrics = metrics.clone();
+            let stop = Arc::clone(&stop);
+            move || {
+                while !stop.load(Ordering::Relaxed) {
+                    terminal.draw(|frame| draw(frame, &metrics))?;
+                    if event::poll(REFRESH_INTERVAL)? {
+                        if let Event::Key(key) = event::read()? {
+                            // the terminal is in raw mode, so Ctrl-C does not send a signal
+                            if key.code == KeyCode::Char('c')
+                                && key.modifiers.contains(KeyModifiers::CONTROL)
+                            {
+                                ratatui::restore();
+                                std::process::exit(130);
+                            }
+                        }
+                    }
+                }
+                Ok(())
+            }
+        });
+        Ok(Self {
+            metrics,
+            stop,
+            handle,
+        })
+    }
+
+    /// Restores the terminal and prints the log lines written while the monitor was shown.
+    pub fn stop(self) -> eyre::Result<()> {
+        self.stop.store(true, Ordering::Relaxed);
+        let result = self
+            .handle
+            .join()
+            .map_err(|_| eyre::eyre!("the monitor panicked"));
+        ratatui::restore();
+        let mut stderr = std::io::stderr().lock();
+        for line in self.metrics.take_logs() {
+            writeln!(stderr, "{line}")?;
+        }
+        result?
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+fn draw(frame: &mut Frame, metrics: &SessionMetrics) {
+    let progress = metrics.progress();
+    let (bytes_sent, bytes_received) = metrics.total_bytes();
+    let (command, elapsed, phases, peers, logs) = {
+        let state = metrics.lock();
+        (
+            state.command.clone(),
+            state.started.elapsed(),
+            state.phases.clone(),
+            state.peers.clone(),
+            state.logs.iter().cloned().collect::<Vec<_>>(),
+        )
+    };
+
+    let [header, gauge, tables, log_area] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Length(3),
+        Constraint::Percentage(50),
+        Constraint::Fill(1),
+    ])
+    .areas(frame.area());
+    let [phase_area, peer_area] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(tables);
+
+    let eta = match progress {
+        Some((_, remaining)) => format!("~{:.0} s", remaining.as_secs_f64()),
+        None => "unknown".to_owned(),
+    };
+    frame.render_widget(
+        Paragraph::new(format!(
+            "elapsed: {:.1} s | estimated remaining: {eta} | sent: {} | received: {}",
+            elapsed.as_secs_f64(),
+            format_bytes(bytes_sent),
+            format_bytes(bytes_received)
+        ))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("co-circom {command} (Ctrl-C to abort)")),
+        ),
+        header,
+    );
+    frame.render_widget(
+        Gauge::default()
+            .block(Block::default().borders(Borders::ALL).title("progress"))
+            .ratio(progress.map(|(ratio, _)| ratio).unwrap_or_default()),
+        gauge,
+    );
+
+    let phase_rows = phases.iter().map(|phase| {
+        let (status, duration) = match phase.duration {
+            Some(duration) => ("done", duration),
+            None => ("running", phase.started.elapsed()),
+        };
+        Row::new([
+            phase.name.to_owned(),
+            status.to_owned(),
+            format!("{:.2} s", duration.as_secs_f64()),
+        ])
+    });
+    frame.render_widget(
+        Table::new(
+            phase_rows,
+            [
+                Constraint::Fill(1),
+                Constraint::Length(8),
+                Constraint::Length(10),
+            ],
+        )
+        .header(Row::new(["phase", "status", "duration"]))
+        .block(Block::default().borders(Borders::ALL).title("phases")),
+        phase_area,
+    );
+
+    let peer_rows = peers.iter().map(|(id, peer)| {
+        let status = if !peer.alive {
+            "closed"
+        } else if peer.updated.elapsed() > STALE_AFTER {
+            "stale"
+        } else {
+            "alive"
+        };
+        Row::new([
+            id.to_string(),
+            status.to_owned(),
+            format!("{} ms", peer.rtt_ms),
+            format_bytes(peer.bytes_sent),
+            format_bytes(peer.bytes_received),
+        ])
+    });
+    frame.render_widget(
+        Table::new(
+            peer_rows,
+            [
+                Constraint::Length(6),
+                Constraint::Length(8),
+                Constraint::Length(8),
+                Constraint::Fill(1),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(Row::new(["party", "status", "rtt", "sent", "received"]))
+        .block(Block::default().borders(Borders::ALL).title("parties")),
+        peer_area,
+    );
+
+    let visible = log_area.height.saturating_sub(2) as usize;
+    let lines = logs
+        .iter()
+        .skip(logs.len().saturating_sub(visible))
+        .map(|line| Line::raw(line.as_str()))
+        .collect::<Vec<_>>();
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("log")),
+        log_area,
+    );
+}
@@ -0,0 +1,2849 @@
+//! The MPC parts of co-circom, i.e., splitting inputs and witnesses, compiling circuits, and generating witnesses and proofs. These are only available with the `mpc` feature.
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+
+use ark_ec::pairing::Pairing;
+use ark_ff::{BigInteger, PrimeField};
+use circom_mpc_compiler::{
+    linear_signals::LinearSignals, CoCircomCompiler, CompilerConfig, SimplificationLevel,
+};
+use circom_mpc_vm::{
+    compiled_circuit::{circuit_hash_to_hex, CircuitHash},
+    mpc_vm::VMConfig,
+    types::CoCircomCompilerParsed,
+};
+use circom_types::{
+    groth16::{Groth16Proof, ZKey},
+    traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge},
+    R1CS,
+};
+use clap::Args;
+use co_circom_snarks::{
+    commitment::{CommitmentOpening, InputCommitment},
+    sharing_proof::{SharingOpening, SharingProof, SharingScheme},
+    SerializeableSharedRep3Input, SerializeableSharedRep3Witness, SharedInput, SharedWitness,
+    UsagePolicy,
+};
+use co_groth16::Rep3CoGroth16;
+use color_eyre::eyre::{bail, Context, ContextCompat};
+use figment::{
+    providers::{Env, Format, Serialized, Toml},
+    Figment,
+};
+use mpc_core::protocols::{
+    rep3::{
+        self,
+        id::PartyID,
+        network::{Rep3MpcNet, Rep3Network},
+        pvss::{self, PvssKeyPair, PvssSharing},
+        Rep3PrimeFieldShare, Rep3ShareVecType,
+    },
+    shamir::{self, ShamirPrimeFieldShare},
+};
+use mpc_net::config::{NetworkConfig, NetworkConfigFile};
+use rand::{CryptoRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    chunked, file_utils, migrate, session::RetentionPolicy, storage, ConfigError, MPCCurve,
+    MPCProtocol, ProofSystem, CONFIG_ENV_PREFIX,
+};
+
+/// The Rng used for expanding compressed Shares
+pub type SeedRng = rand_chacha::ChaCha12Rng;
+
+/// Cli arguments for `split_witness`
+#[derive(Debug, Default, Serialize, Args)]
+pub struct SplitWitnessCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the input witness file generated by Circom
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub witness: Option<PathBuf>,
+    /// The path to the r1cs file, generated by Circom compiler
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub r1cs: Option<PathBuf>,
+    /// The MPC protocol to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub protocol: Option<MPCProtocol>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The path to the (existing) output directory
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out_dir: Option<PathBuf>,
+    /// The threshold of tolerated colluding parties
+    #[arg(short, long, default_value_t = 1)]
+    pub threshold: usize,
+    /// The number of parties
+    #[arg(short, long, default_value_t = 3)]
+    pub num_parties: usize,
+    /// Share with compression using Seeds
+    #[arg(short, long, default_value_t = false)]
+    pub seeded: bool,
+    /// Share compressed as additive shares
+    #[arg(short, long, default_value_t = false)]
+    pub additive: bool,
+    /// An optional usage policy embedded into the shares, e.g., `circuits=<hash>;expires=<unix-timestamp>;proof-systems=groth16`
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub usage_policy: Option<UsagePolicy>,
+    /// Additionally write a zero-knowledge proof that the shares reconstruct to a witness satisfying the R1CS, and the openings the parties need to check their shares against it with `verify-witness-share`
+    #[arg(long, default_value_t = false)]
+    pub prove_sharing: bool,
+}
+
+/// Config for `split_witness`
+#[derive(Debug, Deserialize)]
+pub struct SplitWitnessConfig {
+    /// The path to the input witness file generated by Circom
+    pub witness: PathBuf,
+    /// The path to the r1cs file, generated by Circom compiler
+    pub r1cs: PathBuf,
+    /// The MPC protocol to be used
+    pub protocol: MPCProtocol,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The path to the (existing) output directory
+    pub out_dir: PathBuf,
+    /// The threshold of tolerated colluding parties
+    pub threshold: usize,
+    /// The number of parties
+    pub num_parties: usize,
+    /// Share with compression using Seeds
+    pub seeded: bool,
+    /// Share compressed as additive shares
+    pub additive: bool,
+    /// An optional usage policy embedded into the shares
+    #[serde(default)]
+    pub usage_policy: Option<UsagePolicy>,
+    /// Additionally write a zero-knowledge proof of the correct sharing
+    #[serde(default)]
+    pub prove_sharing: bool,
+}
+
+/// Cli arguments for `split_input`
+#[derive(Debug, Default, Clone, Serialize, Args)]
+pub struct SplitInputCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the input JSON file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub input: Option<PathBuf>,
+    /// The path to the circuit file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub circuit: Option<String>,
+    /// The MPC protocol to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub protocol: Option<MPCProtocol>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The path to the (existing) output directory
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out_dir: Option<PathBuf>,
+    /// Share with compression using Seeds
+    #[arg(short, long, default_value_t = false)]
+    pub seeded: bool,
+    /// Share compressed as additive shares
+    #[arg(short, long, default_value_t = false)]
+    pub additive: bool,
+    /// The path to an optional JSON file declaring the bit widths of private inputs, e.g., `{"a": 32}`
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub bit_widths: Option<PathBuf>,
+    /// The paths to the PVSS public keys of the three parties. If provided, the shares are encrypted to the parties and published in a single file, see `decrypt-input-share`
+    #[arg(long, num_args = 3)]
+    #[serde(skip_serializing_if = "::std::vec::Vec::is_empty")]
+    pub pvss_keys: Vec<PathBuf>,
+    /// An optional usage policy embedded into the shares, e.g., `circuits=<hash>;expires=<unix-timestamp>;proof-systems=groth16`
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub usage_policy: Option<UsagePolicy>,
+    /// The names of private inputs to commit to, e.g., `a,b`. The commitment is written next to the shares and can be published, the parties check the shares against it during the witness generation
+    #[arg(long, value_delimiter = ',')]
+    #[serde(skip_serializing_if = "::std::vec::Vec::is_empty")]
+    pub commit: Vec<String>,
+    /// The name of a public input of the circuit the commitment is exposed as, which links the proof to the published commitment. Requires `--commit`
+    #[arg(long, requires = "commit")]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub commitment_public_input: Option<String>,
+}
+
+/// Config for `split_input`
+#[derive(Debug, Clone, Deserialize)]
+pub struct SplitInputConfig {
+    /// The path to the input JSON file
+    pub input: PathBuf,
+    /// The path to the circuit file
+    pub circuit: String,
+    /// The MPC protocol to be used
+    pub protocol: MPCProtocol,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The path to the (existing) output directory
+    pub out_dir: PathBuf,
+    /// MPC compiler config
+    #[serde(default)]
+    pub compiler: CompilerConfig,
+    /// Share with compression using Seeds
+    pub seeded: bool,
+    /// Share compressed as additive shares
+    pub additive: bool,
+    /// The path to an optional JSON file declaring the bit widths of private inputs
+    pub bit_widths: Option<PathBuf>,
+    /// The paths to the PVSS public keys of the three parties
+    #[serde(default)]
+    pub pvss_keys: Vec<PathBuf>,
+    /// An optional usage policy embedded into the shares
+    #[serde(default)]
+    pub usage_policy: Option<UsagePolicy>,
+    /// The names of private inputs to commit to
+    #[serde(default)]
+    pub commit: Vec<String>,
+    /// The name of a public input of the circuit the commitment is exposed as
+    #[serde(default)]
+    pub commitment_public_input: Option<String>,
+}
+
+/// Cli arguments for `generate_pvss_key`
+#[derive(Debug, Default, Serialize, Args)]
+pub struct GeneratePvssKeyCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The output file where the key pair is written to. Keep this file secret
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub key_out: Option<PathBuf>,
+    /// The output file where the public key is written to
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub public_key_out: Option<PathBuf>,
+}
+
+/// Config for `generate_pvss_key`
+#[derive(Debug, Deserialize)]
+pub struct GeneratePvssKeyConfig {
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The output file where the key pair is written to
+    pub key_out: PathBuf,
+    /// The output file where the public key is written to
+    pub public_key_out: PathBuf,
+}
+
+/// Cli arguments for `generate_signing_key`
+#[derive(Debug, Default, Serialize, Args)]
+pub struct GenerateSigningKeyCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The output file where the key pair is written to. Keep this file secret
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub key_out: Option<PathBuf>,
+}
+
+/// Config for `generate_signing_key`
+#[derive(Debug, Deserialize)]
+pub struct GenerateSigningKeyConfig {
+    /// The output file where the key pair is written to
+    pub key_out: PathBuf,
+}
+
+/// Cli arguments for `verify_signatures`
+#[derive(Debug, Default, Serialize, Args)]
+pub struct VerifySignaturesCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the proof file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub proof: Option<PathBuf>,
+    /// The path to the public input JSON file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub public_input: Option<PathBuf>,
+    /// The path to the JSON committee descriptor listing the public signing keys of the parties
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub committee: Option<PathBuf>,
+    /// The paths to the signature files of the parties
+    #[arg(long, num_args = 1..)]
+    #[serde(skip_serializing_if = "::std::vec::Vec::is_empty")]
+    pub signatures: Vec<PathBuf>,
+    /// Check that the signed metadata binds the elements of the Groth16 proof, see `generate-proof --bind-proof`, to detect a re-randomized proof
+    #[arg(long, default_value_t = false)]
+    pub check_proof_binding: bool,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+}
+
+/// Config for `verify_signatures`
+#[derive(Debug, Deserialize)]
+pub struct VerifySignaturesConfig {
+    /// The path to the proof file
+    pub proof: PathBuf,
+    /// The path to the public input JSON file
+    pub public_input: PathBuf,
+    /// The path to the JSON committee descriptor
+    pub committee: PathBuf,
+    /// The paths to the signature files of the parties
+    #[serde(default)]
+    pub signatures: Vec<PathBuf>,
+    /// Check that the signed metadata binds the elements of the Groth16 proof
+    #[serde(default)]
+    pub check_proof_binding: bool,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+}
+
+/// Cli arguments for `audit`
+#[derive(Debug, Default, Serialize, Args)]
+pub struct AuditCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The paths to the session transcripts of all parties, see `generate-proof --transcript`
+    #[arg(long, num_args = 1..)]
+    #[serde(skip_serializing_if = "::std::vec::Vec::is_empty")]
+    pub transcripts: Vec<PathBuf>,
+}
+
+/// Config for `audit`
+#[derive(Debug, Deserialize)]
+pub struct AuditConfig {
+    /// The paths to the session transcripts of all parties
+    #[serde(default)]
+    pub transcripts: Vec<PathBuf>,
+}
+
+/// Cli arguments for `decrypt_input_share`
+#[derive(Debug, Default, Serialize, Args)]
+pub struct DecryptInputShareCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the published PVSS input file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub input: Option<PathBuf>,
+    /// The path to the PVSS key pair of this party
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub key: Option<PathBuf>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The output file where the input share is written to
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+}
+
+/// Config for `decrypt_input_share`
+#[derive(Debug, Deserialize)]
+pub struct DecryptInputShareConfig {
+    /// The path to the published PVSS input file
+    pub input: PathBuf,
+    /// The path to the PVSS key pair of this party
+    pub key: PathBuf,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The output file where the input share is written to
+    pub out: PathBuf,
+}
+
+/// Cli arguments for `merge_input_shares`
+#[derive(Debug, Default, Serialize, Args)]
+pub struct MergeInputSharesCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the input JSON file
+    #[arg(long)]
+    pub inputs: Vec<PathBuf>,
+    /// The MPC protocol to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub protocol: Option<MPCProtocol>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The output file where the merged input share is written to
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+    /// The path to a JSON file with the authoritative values of the public inputs. If passed, the merged input share contains these values and the copies of the public inputs in the input shares are checked against them
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub public_inputs: Option<PathBuf>,
+}
+
+/// Config for `merge_input_shares`
+#[derive(Debug, Deserialize)]
+pub struct MergeInputSharesConfig {
+    /// The path to the input JSON file
+    pub inputs: Vec<PathBuf>,
+    /// The MPC protocol to be used
+    pub protocol: MPCProtocol,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The output file where the merged input share is written to
+    pub out: PathBuf,
+    /// The path to a JSON file with the authoritative values of the public inputs
+    pub public_inputs: Option<PathBuf>,
+}
+
+/// Cli arguments for `compile`
+#[derive(Debug, Default, Serialize, Args)]
+pub struct CompileCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the circuit file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub circuit: Option<PathBuf>,
+    /// The path to the circuit file
+    #[arg(long, short = 'l')]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub link_library: Option<Vec<PathBuf>>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The output file where the compiled circuit is written to
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+    /// The simplification level passed to the circom compiler (0-2)
+    #[arg(short = 'O', default_value_t = 1, value_parser = clap::value_parser!(u8).range(0..3))]
+    pub simplification_level: u8,
+}
+
+/// Config for `compile`
+#[derive(Debug, Deserialize)]
+pub struct CompileConfig {
+    /// The path to the circuit file
+    pub circuit: String,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The output file where the compiled circuit is written to
+    pub out: PathBuf,
+    /// MPC compiler config
+    #[serde(default)]
+    pub compiler: CompilerConfig,
+}
+
+/// Cli arguments for `generate_witness`
+#[derive(Debug, Default, Serialize, Args)]
+pub struct GenerateWitnessCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the input share file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub input: Option<PathBuf>,
+    /// The path to the circuit file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub circuit: Option<PathBuf>,
+    /// The path to a circuit compiled with the `compile` command, which is used instead of compiling the circuit file
+    #[arg(long, conflicts_with = "circuit")]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub compiled_circuit: Option<PathBuf>,
+    /// The path to the circuit file
+    #[arg(long, short = 'l')]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub link_library: Option<Vec<PathBuf>>,
+    /// The MPC protocol to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub protocol: Option<MPCProtocol>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The output file where the final witness share is written to
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+    /// The paths to published input commitments of the data owners, the shared inputs are checked against them before the witness generation
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::vec::Vec::is_empty")]
+    pub commitment: Vec<PathBuf>,
+    /// The path to the R1CS file of the circuit. If passed, linear intermediate signals are eliminated from the witness share and the proof generation needs the same R1CS file to recompute them
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub eliminate_linear_signals: Option<PathBuf>,
+    /// Check with the other parties that all parties executed identical traces of the witness extension, by comparing hashes of the public outputs and of the replicated components of the shares. On a mismatch, a diagnostic trace pointing to the first divergent component is written next to the output file. All parties need to pass this flag
+    #[arg(long, default_value_t = false)]
+    pub check_determinism: bool,
+    /// The simplification level passed to the circom compiler (0-2)
+    #[arg(short = 'O', default_value_t = 1, value_parser = clap::value_parser!(u8).range(0..3))]
+    pub simplification_level: u8,
+}
+
+/// Config for `generate_witness`
+#[derive(Debug, Deserialize)]
+pub struct GenerateWitnessConfig {
+    /// The path to the input share file
+    pub input: PathBuf,
+    /// The path to the circuit file, required if no compiled circuit is provided
+    pub circuit: Option<String>,
+    /// The path to a circuit compiled with the `compile` command
+    pub compiled_circuit: Option<PathBuf>,
+    /// The MPC protocol to be used
+    pub protocol: MPCProtocol,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The output file where the final witness share is written to
+    pub out: PathBuf,
+    /// MPC compiler config
+    #[serde(default)]
+    pub compiler: CompilerConfig,
+    /// MPC VM config
+    #[serde(default)]
+    pub vm: VMConfig,
+    /// The paths to published input commitments of the data owners
+    #[serde(default)]
+    pub commitment: Vec<PathBuf>,
+    /// The path to the R1CS file of the circuit, used to eliminate linear intermediate signals from the witness share
+    pub eliminate_linear_signals: Option<PathBuf>,
+    /// Check with the other parties that all parties executed identical traces of the witness extension
+    #[serde(default)]
+    pub check_determinism: bool,
+    /// Network config
+    pub network: NetworkConfigFile,
+}
+
+/// Cli arguments for `transalte_witness`
+#[derive(Debug, Serialize, Args)]
+pub struct TranslateWitnessCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the witness share file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub witness: Option<PathBuf>,
+    /// The MPC protocol that was used for the witness generation
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub src_protocol: Option<MPCProtocol>,
+    /// The MPC protocol to be used for the proof generation
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub target_protocol: Option<MPCProtocol>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The output file where the final witness share is written to
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+}
+
+/// Config for `transalte_witness`
+#[derive(Debug, Deserialize)]
+pub struct TranslateWitnessConfig {
+    /// The path to the witness share file
+    pub witness: PathBuf,
+    /// The MPC protocol that was used for the witness generation
+    pub src_protocol: MPCProtocol,
+    /// The MPC protocol to be used for the proof generation
+    pub target_protocol: MPCProtocol,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The output file where the final witness share is written to
+    pub out: PathBuf,
+    /// Network config
+    pub network: NetworkConfigFile,
+}
+
+/// Cli arguments for `witness_stats`
+#[derive(Debug, Serialize, Args)]
+pub struct WitnessStatsCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the witness share file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub witness: Option<PathBuf>,
+    /// The MPC protocol that was used for the witness share
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub protocol: Option<MPCProtocol>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The output file where the statistics are written to as JSON, printed to stdout if not set
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+}
+
+/// Config for `witness_stats`
+#[derive(Debug, Deserialize)]
+pub struct WitnessStatsConfig {
+    /// The path to the witness share file
+    pub witness: PathBuf,
+    /// The MPC protocol that was used for the witness share
+    pub protocol: MPCProtocol,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The output file where the statistics are written to as JSON, printed to stdout if not set
+    pub out: Option<PathBuf>,
+}
+
+/// Cli arguments for `recommend`
+#[derive(Debug, Serialize, Args)]
+pub struct RecommendCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the r1cs file, generated by Circom compiler
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub r1cs: Option<PathBuf>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The one-way latency between two parties in milliseconds
+    #[arg(long, default_value_t = 1.0)]
+    pub latency_ms: f64,
+    /// The bandwidth of every party in Mbit/s
+    #[arg(long, default_value_t = 1000.0)]
+    pub bandwidth_mbps: f64,
+    /// The number of parties
+    #[arg(long, default_value_t = 3)]
+    pub parties: usize,
+    /// The path to the JSON output of `calibrate` on the machines of the parties. If passed, the compute time of the proof generation is projected as well
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub calibration: Option<PathBuf>,
+    /// The output file where the recommendation is written to as JSON, printed to stdout if not set
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+}
+
+/// Config for `recommend`
+#[derive(Debug, Deserialize)]
+pub struct RecommendConfig {
+    /// The path to the r1cs file, generated by Circom compiler
+    pub r1cs: PathBuf,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The one-way latency between two parties in milliseconds
+    pub latency_ms: f64,
+    /// The bandwidth of every party in Mbit/s
+    pub bandwidth_mbps: f64,
+    /// The number of parties
+    pub parties: usize,
+    /// The path to the JSON output of `calibrate`
+    pub calibration: Option<PathBuf>,
+    /// The output file where the recommendation is written to as JSON, printed to stdout if not set
+    pub out: Option<PathBuf>,
+}
+
+/// Cli arguments for `verify_witness_share`
+#[derive(Debug, Serialize, Args)]
+pub struct VerifyWitnessShareCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the witness share file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub witness: Option<PathBuf>,
+    /// The path to the r1cs file, generated by Circom compiler
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub r1cs: Option<PathBuf>,
+    /// The path to the proof of correct sharing written by `split-witness --prove-sharing`
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub sharing_proof: Option<PathBuf>,
+    /// The path to the opening of this party written by `split-witness --prove-sharing`
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub opening: Option<PathBuf>,
+    /// The MPC protocol that was used for the witness share
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub protocol: Option<MPCProtocol>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+}
+
+/// Config for `verify_witness_share`
+#[derive(Debug, Deserialize)]
+pub struct VerifyWitnessShareConfig {
+    /// The path to the witness share file
+    pub witness: PathBuf,
+    /// The path to the r1cs file, generated by Circom compiler
+    pub r1cs: PathBuf,
+    /// The path to the proof of correct sharing
+    pub sharing_proof: PathBuf,
+    /// The path to the opening of this party
+    pub opening: PathBuf,
+    /// The MPC protocol that was used for the witness share
+    pub protocol: MPCProtocol,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+}
+
+/// Cli arguments for `migrate_shares`
+#[derive(Debug, Serialize, Args)]
+pub struct MigrateSharesCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the REP3 share file written by an older release
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub input: Option<PathBuf>,
+    /// The kind of the share file
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub kind: Option<migrate::ShareFileKind>,
+    /// The release of co-circom that wrote the share file
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub from: Option<migrate::ShareFormatVersion>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The output file where the migrated share file is written to
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+}
+
+/// Config for `migrate_shares`
+#[derive(Debug, Deserialize)]
+pub struct MigrateSharesConfig {
+    /// The path to the REP3 share file written by an older release
+    pub input: PathBuf,
+    /// The kind of the share file
+    pub kind: migrate::ShareFileKind,
+    /// The release of co-circom that wrote the share file
+    pub from: migrate::ShareFormatVersion,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The output file where the migrated share file is written to
+    pub out: PathBuf,
+}
+
+/// Cli arguments for `inspect_share`
+#[derive(Debug, Serialize, Args)]
+pub struct InspectShareCli {
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the input or witness share file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub share: Option<PathBuf>,
+    /// The pairing friendly curve of the share file. All supported curves are tried if not set
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The MPC protocol of the share file. All protocols are tried if not set
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub protocol: Option<MPCProtocol>,
+    /// The kind of the share file. All kinds are tried if not set
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub kind: Option<migrate::ShareFileKind>,
+    /// The output file where the summary is written to as JSON, printed to stdout if not set
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+}
+
+/// Config for `inspect_share`
+#[derive(Debug, Deserialize)]
+pub struct InspectShareConfig {
+    /// The path to the input or witness share file
+    pub share: PathBuf,
+    /// The pairing friendly curve of the share file
+    pub curve: Option<MPCCurve>,
+    /// The MPC protocol of the share file
+    pub protocol: Option<MPCProtocol>,
+    /// The kind of the share file
+    pub kind: Option<migrate::ShareFileKind>,
+    /// The output file where the summary is written to as JSON, printed to stdout if not set
+    pub out: Option<PathBuf>,
+}
+
+/// Cli arguments for `generate_proof`
+#[derive(Debug, Serialize, Args)]
+pub struct GenerateProofCli {
+    /// The proof system to be used, either groth16, plonk, or the name of a registered plugin
+    #[arg()]
+    pub proof_system: ProofSystem,
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the witness share file, `-` for stdin, or a named pipe
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub witness: Option<PathBuf>,
+    /// The path to the proving key (.zkey) file, generated by snarkjs setup phase, `-` for stdin, or a named pipe
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub zkey: Option<PathBuf>,
+    /// The MPC protocol to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub protocol: Option<MPCProtocol>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The output file where the final proof is written to, `-` for stdout, or a named pipe. If not passed, this party will not write the proof to a file.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+    /// The output JSON file where the public inputs are written to. If not passed, this party will not write the public inputs to a file.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub public_input: Option<PathBuf>,
+    /// The output JSON file where the metadata of the proof generation, e.g., the digest of the protocol transcript, is written to. If not passed, this party will not write the metadata to a file.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub metadata: Option<PathBuf>,
+    /// The output JSON file where the session transcript of this party, i.e., the phases, the message counts, the public values, and the digests of the artifacts, is written to for a later `audit`. If not passed, this party will not write a transcript.
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub transcript: Option<PathBuf>,
+    /// The threshold of tolerated colluding parties
+    #[arg(short, long, default_value_t = 1)]
+    pub threshold: usize,
+    /// INSECURE: Create a deterministic Groth16 proof without blinding (r = s = 0), which is NOT zero-knowledge. Only for benchmarking and debugging.
+    #[arg(long, default_value_t = false)]
+    pub insecure_non_hiding: bool,
+    /// The path to the session directory of this proof generation, see `cleanup`
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub session: Option<PathBuf>,
+    /// Clean up the session directory with the configured retention policy after the proof was generated successfully
+    #[arg(long, default_value_t = false)]
+    pub cleanup_on_success: bool,
+    /// Rehearse the proof generation with dummy shares of a trivial witness instead of the witness share, to validate the connectivity, the configs, and the versions of all parties before the real data is used. All parties need to pass this flag. Reports a go/no-go summary and does not write any files.
+    #[arg(long, default_value_t = false)]
+    pub rehearsal: bool,
+    /// The path to the R1CS file that was used to eliminate linear signals from the witness share during the witness generation. The eliminated signals are recomputed before the proof generation
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub linear_signals: Option<PathBuf>,
+    /// Check with the other parties that the witness shares of all parties come from the same sharing before the proof generation, to detect mixed-up share files. Only supported for REP3. All parties need to pass this flag
+    #[arg(long, default_value_t = false)]
+    pub check_witness_consistency: bool,
+    /// The path to the long-term signing key of this party, see `generate-signing-key`. If passed together with `--signature`, this party signs the proof, the public inputs, and the metadata
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub signing_key: Option<PathBuf>,
+    /// The output JSON file where the signature of this party is written to, see `verify-signatures`
+    #[arg(long, requires = "signing_key")]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub signature: Option<PathBuf>,
+    /// The path to the JSON committee descriptor, with the members listed in the order of the party ids. If passed, every party proves possession of the signing key of its committee member before the session starts. All parties need to pass this flag
+    #[arg(long, requires = "signing_key")]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub committee: Option<PathBuf>,
+    /// Bind the Groth16 proof elements into the metadata, so the signatures of the parties only cover the exact proof produced by the session and a re-randomized proof is detected by `verify-signatures --check-proof-binding`. Only supported for Groth16
+    #[arg(long, default_value_t = false)]
+    pub bind_proof: bool,
+    /// Run a warm-up phase before the proof generation, which loads the zkey into the page cache, keeps the thread pool busy, and exchanges dummy traffic with the other parties, so that the measured latency reflects the steady-state performance. All parties need to pass this flag
+    #[arg(long, default_value_t = false)]
+    pub warm_up: bool,
+    /// The duration of the warm-up phase in milliseconds
+    #[arg(long, default_value_t = crate::warmup::DEFAULT_DURATION_MS)]
+    pub warm_up_ms: u64,
+    /// Bound the peak memory of the MSMs of the Groth16 prover to roughly this many MiB by computing them in chunks. The points of a local zkey are then read from the file on demand instead of loading the whole zkey into memory. Slows down the MSMs, so only pass it if the party would otherwise run out of memory. Only supported for Groth16
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub msm_memory_budget_mb: Option<usize>,
+    /// The directory of the persistent caches, e.g., the evaluation domains of the Plonk prover per curve and domain size. Can be shared by all commands with the `COCIRCOM_CACHE_DIR` environment variable. If not passed, nothing is cached
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub cache_dir: Option<PathBuf>,
+}
+
+/// Config for `generate_proof`
+#[derive(Debug, Deserialize)]
+pub struct GenerateProofConfig {
+    /// The proof system to be used
+    pub proof_system: ProofSystem,
+    /// The path to the witness share file, required unless rehearsing
+    pub witness: Option<PathBuf>,
+    /// The path to the proving key (.zkey) file, generated by snarkjs setup phase
+    pub zkey: PathBuf,
+    /// The MPC protocol to be used
+    pub protocol: MPCProtocol,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The output file where the final proof is written to. If not passed, this party will not write the proof to a file.
+    pub out: Option<PathBuf>,
+    /// The output JSON file where the public inputs are written to. If not passed, this party will not write the public inputs to a file.
+    pub public_input: Option<PathBuf>,
+    /// The output JSON file where the metadata of the proof generation, e.g., the digest of the protocol transcript, is written to. If not passed, this party will not write the metadata to a file.
+    pub metadata: Option<PathBuf>,
+    /// The output JSON file where the session transcript of this party is written to. If not passed, this party will not write a transcript.
+    pub transcript: Option<PathBuf>,
+    /// The threshold of tolerated colluding parties
+    pub threshold: usize,
+    /// INSECURE: Create a deterministic Groth16 proof without blinding (r = s = 0), which is NOT zero-knowledge. Only for benchmarking and debugging.
+    #[serde(default)]
+    pub insecure_non_hiding: bool,
+    /// The path to the session directory of this proof generation
+    pub session: Option<PathBuf>,
+    /// Clean up the session directory after the proof was generated successfully
+    #[serde(default)]
+    pub cleanup_on_success: bool,
+    /// The retention policy of the cleanup on success, keeps proofs and metadata and securely deletes shares by default
+    #[serde(default)]
+    pub retention: RetentionPolicy,
+    /// Rehearse the proof generation with dummy shares instead of the witness share
+    #[serde(default)]
+    pub rehearsal: bool,
+    /// The path to the R1CS file that was used to eliminate linear signals from the witness share
+    pub linear_signals: Option<PathBuf>,
+    /// Check that the witness shares of all parties come from the same sharing before the proof generation
+    #[serde(default)]
+    pub check_witness_consistency: bool,
+    /// The path to the long-term signing key of this party
+    pub signing_key: Option<PathBuf>,
+    /// The output JSON file where the signature of this party is written to
+    pub signature: Option<PathBuf>,
+    /// The path to the JSON committee descriptor for the proof-of-possession handshake
+    pub committee: Option<PathBuf>,
+    /// Bind the Groth16 proof elements into the metadata
+    #[serde(default)]
+    pub bind_proof: bool,
+    /// Run a warm-up phase before the proof generation
+    #[serde(default)]
+    pub warm_up: bool,
+    /// The duration of the warm-up phase in milliseconds
+    pub warm_up_ms: u64,
+    /// The memory budget of the MSMs in MiB
+    pub msm_memory_budget_mb: Option<usize>,
+    /// The directory of the persistent caches
+    pub cache_dir: Option<PathBuf>,
+    /// Network config
+    pub network: NetworkConfigFile,
+}
+
+/// Cli arguments for `generate_proof_batch`
+#[derive(Debug, Serialize, Args)]
+pub struct GenerateProofBatchCli {
+    /// The proof system to be used, only groth16 is supported
+    #[arg()]
+    pub proof_system: ProofSystem,
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The path to the JSON manifest listing the witness share, the proving key, and the output files of every job, see [BatchManifest](crate::batch::BatchManifest)
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub batch: Option<PathBuf>,
+    /// The MPC protocol to be used, only REP3 is supported
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub protocol: Option<MPCProtocol>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The maximum number of jobs that are proven concurrently, each over its own network channels. All parties need to use the same number of jobs
+    #[arg(long, default_value_t = 1)]
+    pub jobs: usize,
+    /// The number of threads of every concurrent job. Defaults to the number of cores divided by the number of concurrent jobs
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub threads_per_job: Option<usize>,
+}
+
+/// Config for `generate_proof_batch`
+#[derive(Debug, Deserialize)]
+pub struct GenerateProofBatchConfig {
+    /// The proof system to be used
+    pub proof_system: ProofSystem,
+    /// The path to the JSON manifest of the batch
+    pub batch: PathBuf,
+    /// The MPC protocol to be used
+    pub protocol: MPCProtocol,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The maximum number of jobs that are proven concurrently
+    pub jobs: usize,
+    /// The number of threads of every concurrent job
+    pub threads_per_job: Option<usize>,
+    /// Network config
+    pub network: NetworkConfigFile,
+}
+
+/// Cli arguments for `prove_local`
+#[derive(Debug, Serialize, Args)]
+pub struct ProveLocalCli {
+    /// The proof system to be used, either groth16 or plonk
+    #[arg()]
+    pub proof_system: ProofSystem,
+    /// The path to the config file
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub config: Option<PathBuf>,
+    /// The paths to the REP3 input share files of all parties. The witness is generated locally from the reconstructed input.
+    #[arg(long, conflicts_with = "witnesses")]
+    pub inputs: Vec<PathBuf>,
+    /// The paths to the REP3 witness share files of all parties
+    #[arg(long)]
+    pub witnesses: Vec<PathBuf>,
+    /// The path to the circuit file, required for input shares
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub circuit: Option<PathBuf>,
+    /// The path to a circuit compiled with the `compile` command, which is used instead of compiling the circuit file
+    #[arg(long, conflicts_with = "circuit")]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub compiled_circuit: Option<PathBuf>,
+    /// The path to the circuit file
+    #[arg(long, short = 'l')]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub link_library: Option<Vec<PathBuf>>,
+    /// The path to the proving key (.zkey) file, generated by snarkjs setup phase
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub zkey: Option<PathBuf>,
+    /// The pairing friendly curve to be used
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub curve: Option<MPCCurve>,
+    /// The output file where the final proof is written to
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub out: Option<PathBuf>,
+    /// The output JSON file where the public inputs are written to
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub public_input: Option<PathBuf>,
+    /// The simplification level passed to the circom compiler (0-2)
+    #[arg(short = 'O', default_value_t = 1, value_parser = clap::value_parser!(u8).range(0..3))]
+    pub simplification_level: u8,
+}
+
+/// Config for `prove_local`
+#[derive(Debug, Deserialize)]
+pub struct ProveLocalConfig {
+    /// The proof system to be used
+    pub proof_system: ProofSystem,
+    /// The paths to the REP3 input share files of all parties
+    #[serde(default)]
+    pub inputs: Vec<PathBuf>,
+    /// The paths to the REP3 witness share files of all parties
+    #[serde(default)]
+    pub witnesses: Vec<PathBuf>,
+    /// The path to the circuit file, required for input shares if no compiled circuit is provided
+    pub circuit: Option<String>,
+    /// The path to a circuit compiled with the `compile` command
+    pub compiled_circuit: Option<PathBuf>,
+    /// The path to the proving key (.zkey) file, generated by snarkjs setup phase
+    pub zkey: PathBuf,
+    /// The pairing friendly curve to be used
+    pub curve: MPCCurve,
+    /// The output file where the final proof is written to
+    pub out: PathBuf,
+    /// The output JSON file where the public inputs are written to. If not passed, the public inputs are not written to a file.
+    pub public_input: Option<PathBuf>,
+    /// MPC compiler config
+    #[serde(default)]
+    pub compiler: CompilerConfig,
+    /// MPC VM config
+    #[serde(default)]
+    pub vm: VMConfig,
+}
+
+impl_config!(SplitInputCli, SplitInputConfig);
+impl_config!(SplitWitnessCli, SplitWitnessConfig);
+impl_config!(MergeInputSharesCli, MergeInputSharesConfig);
+impl_config!(GeneratePvssKeyCli, GeneratePvssKeyConfig);
+impl_config!(GenerateSigningKeyCli, GenerateSigningKeyConfig);
+impl_config!(VerifySignaturesCli, VerifySignaturesConfig);
+impl_config!(AuditCli, AuditConfig);
+impl_config!(DecryptInputShareCli, DecryptInputShareConfig);
+impl_config!(TranslateWitnessCli, TranslateWitnessConfig);
+impl_config!(WitnessStatsCli, WitnessStatsConfig);
+impl_config!(RecommendCli, RecommendConfig);
+impl_config!(VerifyWitnessShareCli, VerifyWitnessShareConfig);
+impl_config!(MigrateSharesCli, MigrateSharesConfig);
+impl_config!(InspectShareCli, InspectShareConfig);
+impl_config!(GenerateProofCli, GenerateProofConfig);
+impl_config!(GenerateProofBatchCli, GenerateProofBatchConfig);
+
+fn apply_compiler_args(
+    compiler: &mut CompilerConfig,
+    simplification_level: u8,
+    link_library: Vec<PathBuf>,
+) {
+    match simplification_level {
+        0 => compiler.simplification = SimplificationLevel::O0,
+        1 => compiler.simplification = SimplificationLevel::O1,
+        2 => compiler.simplification = SimplificationLevel::O2(usize::MAX),
+        _ => {}
+    }
+    compiler.link_library.extend(link_library);
+}
+
+// manual ones since these are a bit more complex
+impl CompileConfig {
+    /// Parse config from file, env, cli
+    pub fn parse(mut cli: CompileCli) -> Result<Self, ConfigError> {
+        let simplification_level = cli.simplification_level;
+        let link_library = cli.link_library.take().unwrap_or_default();
+        let mut config: CompileConfig = if let Some(path) = &cli.config {
+            Figment::new()
+                .merge(Toml::file(path))
+                .merge(Env::prefixed(CONFIG_ENV_PREFIX))
+                .merge(Serialized::defaults(cli))
+                .extract()?
+        } else {
+            Figment::new()
+                .merge(Env::prefixed(CONFIG_ENV_PREFIX))
+                .merge(Serialized::defaults(cli))
+                .extract()?
+        };
+        apply_compiler_args(&mut config.compiler, simplification_level, link_library);
+        Ok(config)
+    }
+}
+
+impl GenerateWitnessConfig {
+    /// Parse config from file, env, cli
+    pub fn parse(mut cli: GenerateWitnessCli) -> Result<Self, ConfigError> {
+        let simplification_level = cli.simplification_level;
+        let link_library = cli.link_library.take().unwrap_or_default();
+        let mut config: GenerateWitnessConfig = if let Some(path) = &cli.config {
+            Figment::new()
+                .merge(Toml::file(path))
+                .merge(Env::prefixed(CONFIG_ENV_PREFIX))
+                .merge(Serialized::defaults(cli))
+                .extract()?
+        } else {
+            Figment::new()
+                .merge(Env::prefixed(CONFIG_ENV_PREFIX))
+                .merge(Serialized::defaults(cli))
+                .extract()?
+        };
+        apply_compiler_args(&mut config.compiler, simplification_level, link_library);
+        Ok(config)
+    }
+}
+
+impl ProveLocalConfig {
+    /// Parse config from file, env, cli
+    pub fn parse(mut cli: ProveLocalCli) -> Result<Self, ConfigError> {
+        let simplification_level = cli.simplification_level;
+        let link_library = cli.link_library.take().unwrap_or_default();
+        let mut config: ProveLocalConfig = if let Some(path) = &cli.config {
+            Figment::new()
+                .merge(Toml::file(path))
+                .merge(Env::prefixed(CONFIG_ENV_PREFIX))
+                .merge(Serialized::defaults(cli))
+                .extract()?
+        } else {
+            Figment::new()
+                .merge(Env::prefixed(CONFIG_ENV_PREFIX))
+                .merge(Serialized::defaults(cli))
+                .extract()?
+        };
+        apply_compiler_args(&mut config.compiler, simplification_level, link_library);
+        Ok(config)
+    }
+}
+
+fn reshare_vec<F: PrimeField, N: Rep3Network>(
+    vec: Vec<F>,
+    mpc_net: &mut N,
+) -> color_eyre::Result<Vec<Rep3PrimeFieldShare<F>>> {
+    mpc_net.send_next_many(&vec)?;
+    let b: Vec<F> = mpc_net.recv_prev_many()?;
+
+    if vec.len() != b.len() {
+        return Err(color_eyre::eyre::eyre!(
+            "reshare_vec: vec and b have different lengths"
+        ));
+    }
+
+    let shares = vec
+        .into_iter()
+        .zip(b)
+        .map(|(a, b)| Rep3PrimeFieldShare { a, b })
+        .collect();
+
+    Ok(shares)
+}
+
+pub(crate) fn rep3_share_vec_len<F: PrimeField>(
+    share: &Rep3ShareVecType<F, SeedRng>,
+) -> color_eyre::Result<usize> {
+    let len = match share {
+        Rep3ShareVecType::Replicated(vec) => vec.len(),
+        Rep3ShareVecType::SeededReplicated(replicated_seed_type) => {
+            replicated_seed_type.length()?
+        }
+        Rep3ShareVecType::Additive(vec) => vec.len(),
+        Rep3ShareVecType::SeededAdditive(seeded_type) => seeded_type.length(),
+    };
+    Ok(len)
+}
+
+/// The header of a REP3 witness share in the [chunked] layout.
+#[derive(Serialize, Deserialize)]
+struct ChunkedRep3WitnessHeader<F: PrimeField> {
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    public_inputs: Vec<F>,
+    additive: bool,
+    usage_policy: Option<UsagePolicy>,
+}
+
+/// The header of a Shamir witness share in the [chunked] layout.
+#[derive(Serialize, Deserialize)]
+struct ChunkedShamirWitnessHeader<F: PrimeField> {
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    public_inputs: Vec<F>,
+}
+
+/// Writes a [SerializeableSharedRep3Witness] to `writer`. Expanded shares are written in the [chunked] layout, which is serialized in parallel, seeded shares are small and written with bincode. Both can be read with [parse_serialized_witness_share_rep3].
+pub fn write_witness_share_rep3<W: Write, F: PrimeField>(
+    writer: W,
+    share: &SerializeableSharedRep3Witness<F, SeedRng>,
+) -> color_eyre::Result<()> {
+    let header = |additive| ChunkedRep3WitnessHeader {
+        public_inputs: share.public_inputs.clone(),
+        additive,
+        usage_policy: share.usage_policy.clone(),
+    };
+    match &share.witness {
+        Rep3ShareVecType::Replicated(vec) => {
+            chunked::write(writer, &header(false), vec, chunked::DEFAULT_CHUNK_LEN)
+        }
+        Rep3ShareVecType::Additive(vec) => {
+            chunked::write(writer, &header(true), vec, chunked::DEFAULT_CHUNK_LEN)
+        }
+        Rep3ShareVecType::SeededReplicated(_) | Rep3ShareVecType::SeededAdditive(_) => {
+            bincode::serialize_into(writer, share).context("while serializing witness share")
+        }
+    }
+}
+
+/// Writes a Shamir [SharedWitness] in the [chunked] layout to `writer`, which is serialized in parallel. It can be read with [parse_witness_share_shamir].
+pub fn write_witness_share_shamir<W: Write, F: PrimeField>(
+    writer: W,
+    share: &SharedWitness<F, ShamirPrimeFieldShare<F>>,
+) -> color_eyre::Result<()> {
+    let header = ChunkedShamirWitnessHeader {
+        public_inputs: share.public_inputs.clone(),
+    };
+    chunked::write(writer, &header, &share.witness, chunked::DEFAULT_CHUNK_LEN)
+}
+
+/// Try to parse a [SerializeableSharedRep3Witness] from an untrusted [Read]er, either in the [chunked] layout or in the bincode layout. At most `limit` bytes are read and the witness may contain at most `max_len` elements, which is checked before expanding seeded shares.
+pub fn parse_serialized_witness_share_rep3<R: Read, F: PrimeField>(
+    reader: R,
+    limit: u64,
+    max_len: usize,
+) -> color_eyre::Result<SerializeableSharedRep3Witness<F, SeedRng>> {
+    let deserialized: SerializeableSharedRep3Witness<F, SeedRng> =
+        match chunked::detect(reader).context("trying to read witness share file")? {
+            chunked::Layout::Chunked(reader) => {
+                let reader =
+                    chunked::Reader::<_, ChunkedRep3WitnessHeader<F>>::new(reader, limit, max_len)
+                        .context("trying to parse chunked witness share file")?;
+                let (header, witness) = if reader.header().additive {
+                    let (header, witness) = reader.read_elements()?;
+                    (header, Rep3ShareVecType::Additive(witness))
+                } else {
+                    let (header, witness) = reader.read_elements()?;
+                    (header, Rep3ShareVecType::Replicated(witness))
+                };
+                SerializeableSharedRep3Witness {
+                    public_inputs: header.public_inputs,
+                    witness,
+                    usage_policy: header.usage_policy,
+                }
+            }
+            chunked::Layout::Bincode(reader) => file_utils::deserialize_untrusted(reader, limit)
+                .context("trying to parse witness share file")?,
+        };
+    file_utils::check_num_elements("public inputs", deserialized.public_inputs.len(), max_len)?;
+    file_utils::check_num_elements(
+        "witness",
+        rep3_share_vec_len(&deserialized.witness)?,
+        max_len,
+    )?;
+    Ok(deserialized)
+}
+
+/// Try to parse a [SharedWitness] from an untrusted [Read]er for a proof with `proof_system`. At most `limit` bytes are read and the witness may contain at most `max_len` elements. Fails if the [UsagePolicy] of the share does not allow the proof system or is expired.
+pub fn parse_witness_share_rep3<R: Read, F: PrimeField>(
+    reader: R,
+    mpc_net: &mut Rep3MpcNet,
+    limit: u64,
+    max_len: usize,
+    proof_system: &ProofSystem,
+) -> color_eyre::Result<SharedWitness<F, Rep3PrimeFieldShare<F>>> {
+    let deserialized = parse_serialized_witness_share_rep3(reader, limit, max_len)?;
+    if let Some(policy) = &deserialized.usage_policy {
+        policy.check_not_expired()?;
+        policy.check_proof_system(&proof_system.to_string())?;
+    }
+
+    let public_inputs = deserialized.public_inputs;
+    let witness = deserialized.witness;
+    let witness = match witness {
+        Rep3ShareVecType::Replicated(vec) => vec,
+        Rep3ShareVecType::SeededReplicated(replicated_seed_type) => {
+            replicated_seed_type.expand_vec()?
+        }
+        Rep3ShareVecType::Additive(vec) => reshare_vec(vec, mpc_net)?,
+        Rep3ShareVecType::SeededAdditive(seeded_type) => {
+            reshare_vec(seeded_type.expand_vec(), mpc_net)?
+        }
+    };
+
+    Ok(SharedWitness {
+        public_inputs,
+        witness,
+    })
+}
+
+/// Try to parse a [SharedWitness] from an untrusted [Read]er, returning only the additive shares. At most `limit` bytes are read and the witness may contain at most `max_len` elements. Fails if the share has a [UsagePolicy], as the policy can not be carried over to the additive shares.
+pub fn parse_witness_share_rep3_as_additive<R: Read, F: PrimeField>(
+    reader: R,
+    limit: u64,
+    max_len: usize,
+) -> color_eyre::Result<SharedWitness<F, F>> {
+    let deserialized = parse_serialized_witness_share_rep3(reader, limit, max_len)?;
+    if deserialized.usage_policy.is_some() {
+        bail!("witness shares with a usage policy can not be translated");
+    }
+
+    let public_inputs = deserialized.public_inputs;
+    let witness = deserialized.witness;
+    let witness = match witness {
+        Rep3ShareVecType::Replicated(vec) => vec.into_iter().map(|x| x.a).collect::<Vec<_>>(),
+        Rep3ShareVecType::SeededReplicated(replicated_seed_type) => {
+            replicated_seed_type.a.expand_vec()
+        }
+        Rep3ShareVecType::Additive(vec) => vec,
+        Rep3ShareVecType::SeededAdditive(seeded_type) => seeded_type.expand_vec(),
+    };
+
+    Ok(SharedWitness {
+        public_inputs,
+        witness,
+    })
+}
+
+/// Try to parse a [SharedWitness] from an untrusted [Read]er, either in the [chunked] layout or in the bincode layout of older releases. At most `limit` bytes are read and the witness may contain at most `max_len` elements.
+pub fn parse_witness_share_shamir<R: Read, F: PrimeField>(
+    reader: R,
+    limit: u64,
+    max_len: usize,
+) -> color_eyre::Result<SharedWitness<F, ShamirPrimeFieldShare<F>>> {
+    let witness_share: SharedWitness<F, ShamirPrimeFieldShare<F>> = match chunked::detect(reader)
+        .context("trying to read witness share file")?
+    {
+        chunked::Layout::Chunked(reader) => {
+            let (header, witness) =
+                chunked::Reader::<_, ChunkedShamirWitnessHeader<F>>::new(reader, limit, max_len)
+                    .and_then(chunked::Reader::read_elements)
+                    .context("trying to parse chunked witness share file")?;
+            SharedWitness {
+                public_inputs: header.public_inputs,
+                witness,
+            }
+        }
+        chunked::Layout::Bincode(reader) => file_utils::deserialize_untrusted(reader, limit)
+            .context("trying to parse witness share file")?,
+    };
+    file_utils::check_num_elements("public inputs", witness_share.public_inputs.len(), max_len)?;
+    file_utils::check_num_elements("witness", witness_share.witness.len(), max_len)?;
+    Ok(witness_share)
+}
+
+/// Splits a REP3 share vector into the additive component of the party and, if the share is replicated, the component of the previous party.
+fn rep3_share_vec_components<F: PrimeField>(
+    share: Rep3ShareVecType<F, SeedRng>,
+) -> color_eyre::Result<(Vec<F>, Option<Vec<F>>)> {
+    let components = match share {
+        Rep3ShareVecType::Replicated(vec) => {
+            let (a, b) = vec.into_iter().map(|x| (x.a, x.b)).unzip();
+            (a, Some(b))
+        }
+        Rep3ShareVecType::SeededReplicated(replicated_seed_type) => {
+            let (a, b) = replicated_seed_type
+                .expand_vec()?
+                .into_iter()
+                .map(|x| (x.a, x.b))
+                .unzip();
+            (a, Some(b))
+        }
+        Rep3ShareVecType::Additive(vec) => (vec, None),
+        Rep3ShareVecType::SeededAdditive(seeded_type) => (seeded_type.expand_vec(), None),
+    };
+    Ok(components)
+}
+
+/// Reconstructs the values of the REP3 share vectors of all three parties, given in any order. If all shares are replicated, checks that they belong to three distinct parties of the same sharing, i.e., that the second component of every party is the first component of the previous party.
+fn reconstruct_rep3_share_vecs<F: PrimeField>(
+    shares: Vec<Rep3ShareVecType<F, SeedRng>>,
+) -> color_eyre::Result<Vec<F>> {
+    if shares.len() != 3 {
+        bail!(
+            "REP3 requires the shares of all 3 parties, but got {}",
+            shares.len()
+        );
+    }
+    let components = shares
+        .into_iter()
+        .map(rep3_share_vec_components)
+        .collect::<color_eyre::Result<Vec<_>>>()?;
+    let len = components[0].0.len();
+    if components.iter().any(|(a, _)| a.len() != len) {
+        bail!("the shares of the parties have different lengths");
+    }
+    if components.iter().all(|(_, b)| b.is_some()) {
+        // the parties can be given in either direction of the ring
+        let consistent = [1, 2].into_iter().any(|offset| {
+            (0..3).all(|i| components[i].1.as_ref() == Some(&components[(i + offset) % 3].0))
+        });
+        if !consistent {
+            bail!("the shares do not belong to three distinct parties of the same sharing");
+        }
+    } else {
+        tracing::warn!("the shares are not replicated, so their consistency can not be checked");
+    }
+    Ok((0..len)
+        .map(|i| components.iter().fold(F::ZERO, |acc, (a, _)| acc + a[i]))
+        .collect())
+}
+
+/// Reconstructs the witness from the REP3 witness shares of all parties for proving locally without MPC, see [ProveLocalConfig]. Fails if the shares are inconsistent or if the [UsagePolicy] of a share does not allow the proof system or is expired.
+pub fn reconstruct_witness_rep3<F: PrimeField>(
+    shares: Vec<SerializeableSharedRep3Witness<F, SeedRng>>,
+    proof_system: &ProofSystem,
+) -> color_eyre::Result<SharedWitness<F, F>> {
+    let mut public_inputs = None;
+    let mut witness_shares = Vec::with_capacity(shares.len());
+    for share in shares {
+        if let Some(policy) = &share.usage_policy {
+            policy.check_not_expired()?;
+            policy.check_proof_system(&proof_system.to_string())?;
+        }
+        match &public_inputs {
+            None => public_inputs = Some(share.public_inputs),
+            Some(public_inputs) if *public_inputs != share.public_inputs => {
+                bail!("the witness shares of the parties have different public inputs")
+            }
+            Some(_) => {}
+        }
+        witness_shares.push(share.witness);
+    }
+    Ok(SharedWitness {
+        public_inputs: public_inputs.context("no witness shares provided")?,
+        witness: reconstruct_rep3_share_vecs(witness_shares)?,
+    })
+}
+
+/// Reconstructs the input from the merged REP3 input shares of all parties for generating the witness locally without MPC. The usage policies of the shares are combined with [UsagePolicy::merge]. Fails if the shares are inconsistent or if a usage policy is expired.
+pub fn reconstruct_input_rep3<F: PrimeField>(
+    shares: Vec<SerializeableSharedRep3Input<F, SeedRng>>,
+) -> color_eyre::Result<SharedInput<F, F>> {
+    let mut shares = shares.into_iter();
+    let first = shares.next().context("no input shares provided")?;
+    let mut usage_policy = first.usage_policy;
+    let mut shared_inputs = first
+        .shared_inputs
+        .into_iter()
+        .map(|(name, share)| (name, vec![share]))
+        .collect::<BTreeMap<_, _>>();
+    if !first.maybe_shared_inputs.is_empty() {
+        bail!("still unmerged elements left");
+    }
+    for share in shares {
+        if !share.maybe_shared_inputs.is_empty() {
+            bail!("still unmerged elements left");
+        }
+        if share.public_inputs != first.public_inputs || share.bit_widths != first.bit_widths {
+            bail!("the input shares of the parties have different public inputs or bit widths");
+        }
+        if share.shared_inputs.len() != shared_inputs.len() {
+            bail!("the input shares of the parties have different shared inputs");
+        }
+        for (name, share) in share.shared_inputs {
+            shared_inputs
+                .get_mut(&name)
+                .with_context(|| format!("shared input \"{name}\" is missing in a share"))?
+                .push(share);
+        }
+        usage_policy = UsagePolicy::merge(usage_policy, share.usage_policy)?;
+    }
+    if let Some(policy) = &usage_policy {
+        policy.check_not_expired()?;
+    }
+    let shared_inputs = shared_inputs
+        .into_iter()
+        .map(|(name, shares)| {
+            let values = reconstruct_rep3_share_vecs(shares)
+                .with_context(|| format!("while reconstructing shared input \"{name}\""))?;
+            Ok((name, values))
+        })
+        .collect::<color_eyre::Result<_>>()?;
+    Ok(SharedInput {
+        public_inputs: first.public_inputs,
+        shared_inputs,
+        bit_widths: first.bit_widths,
+        usage_policy,
+        commitment_openings: BTreeMap::new(),
+    })
+}
+
+/// Statistics about a witness share file, which do not reveal anything about the shared witness. They can be used to detect a wrongly shared witness without reconstructing it, e.g., many zero shares or a low entropy indicate that the file contains the plain witness.
+#[derive(Debug, Clone, Serialize)]
+pub struct WitnessShareStats {
+    /// The size of the witness share file in bytes
+    pub file_size: u64,
+    /// The type of the shares stored in the file
+    pub share_type: String,
+    /// The number of public inputs
+    pub num_public_inputs: usize,
+    /// The number of shared signals
+    pub num_shared_signals: usize,
+    /// The number of shared signals whose local additive share is zero
+    pub num_zero_shares: usize,
+    /// The ratio of shared signals whose local additive share is zero
+    pub zero_share_ratio: f64,
+    /// The Shannon entropy in bits per byte of the little-endian encoding of the local additive shares, at most 8
+    pub byte_entropy: f64,
+    /// The number of bytes of the local additive shares falling into each of the 16 buckets given by the upper nibble
+    pub byte_histogram: Vec<u64>,
+}
+
+impl WitnessShareStats {
+    /// Computes the statistics from the local additive shares of a witness.
+    pub fn from_additive_shares<F: PrimeField>(
+        file_size: u64,
+        share_type: &str,
+        num_public_inputs: usize,
+        shares: &[F],
+    ) -> Self {
+        let num_zero_shares = shares.iter().filter(|x| x.is_zero()).count();
+        let zero_share_ratio = if shares.is_empty() {
+            0.0
+        } else {
+            num_zero_shares as f64 / shares.len() as f64
+        };
+
+        let mut byte_counts = [0u64; 256];
+        for share in shares {
+            for byte in share.into_bigint().to_bytes_le() {
+                byte_counts[byte as usize] += 1;
+            }
+        }
+        let num_bytes = byte_counts.iter().sum::<u64>();
+        let byte_entropy = byte_counts
+            .iter()
+            .filter(|count| **count > 0)
+            .map(|count| {
+                let p = *count as f64 / num_bytes as f64;
+                -p * p.log2()
+            })
+            .sum();
+        let byte_histogram = byte_counts
+            .chunks(16)
+            .map(|bucket| bucket.iter().sum())
+            .collect();
+
+        Self {
+            file_size,
+            share_type: share_type.to_owned(),
+            num_public_inputs,
+            num_shared_signals: shares.len(),
+            num_zero_shares,
+            zero_share_ratio,
+            byte_entropy,
+            byte_histogram,
+        }
+    }
+}
+
+/// Computes [WitnessShareStats] of a witness share file, without any interaction with the other parties.
+pub fn witness_share_stats<F: PrimeField>(
+    witness: &Path,
+    protocol: MPCProtocol,
+) -> color_eyre::Result<WitnessShareStats> {
+    let (witness_file, file_size) =
+        file_utils::open_untrusted(witness, file_utils::MAX_SHARE_FILE_SIZE)
+            .context("trying to open witness share file")?;
+
+    let stats = match protocol {
+        MPCProtocol::REP3 => {
+            let deserialized = parse_serialized_witness_share_rep3::<_, F>(
+                witness_file,
+                file_size,
+                file_utils::MAX_SHARED_ELEMENTS,
+            )?;
+            let (share_type, shares): (_, Vec<F>) = match deserialized.witness {
+                Rep3ShareVecType::Replicated(vec) => {
+                    ("replicated", vec.into_iter().map(|x| x.a).collect())
+                }
+                Rep3ShareVecType::SeededReplicated(replicated_seed_type) => {
+                    ("seeded replicated", replicated_seed_type.a.expand_vec())
+                }
+                Rep3ShareVecType::Additive(vec) => ("additive", vec),
+                Rep3ShareVecType::SeededAdditive(seeded_type) => {
+                    ("seeded additive", seeded_type.expand_vec())
+                }
+            };
+            WitnessShareStats::from_additive_shares(
+                file_size,
+                share_type,
+                deserialized.public_inputs.len(),
+                &shares,
+            )
+        }
+        MPCProtocol::SHAMIR => {
+            let witness_share = parse_witness_share_shamir::<_, F>(
+                witness_file,
+                file_size,
+                file_utils::MAX_SHARED_ELEMENTS,
+            )?;
+            let shares = ShamirPrimeFieldShare::convert_vec(witness_share.witness);
+            WitnessShareStats::from_additive_shares(
+                file_size,
+                "shamir",
+                witness_share.public_inputs.len(),
+                &shares,
+            )
+        }
+    };
+    Ok(stats)
+}
+
+/// Returns the additive share of a REP3 witness share and, if the share is replicated, the additive share of the previous party.
+fn rep3_additive_shares<F: PrimeField>(
+    witness: Rep3ShareVecType<F, SeedRng>,
+) -> color_eyre::Result<(Vec<F>, Option<Vec<F>>)> {
+    Ok(match witness {
+        Rep3ShareVecType::Replicated(vec) => {
+            let (a, b) = vec.into_iter().map(|x| (x.a, x.b)).unzip();
+            (a, Some(b))
+        }
+        Rep3ShareVecType::SeededReplicated(replicated_seed_type) => {
+            let a = replicated_seed_type.a.expand_vec();
+            let b = replicated_seed_type.b.expand_vec();
+            if a.len() != b.len() {
+                bail!("the seeded additive shares have different lengths");
+            }
+            (a, Some(b))
+        }
+        Rep3ShareVecType::Additive(vec) => (vec, None),
+        Rep3ShareVecType::SeededAdditive(seeded_type) => (seeded_type.expand_vec(), None),
+    })
+}
+
+/// Proves that the REP3 witness shares reconstruct to the private `witness`, which satisfies the R1CS, see [SharingProof]. Returns the proof, which can be published, and the [SharingOpening]s of the three parties.
+pub fn prove_sharing_rep3<P: Pairing>(
+    r1cs: &R1CS<P>,
+    witness: &[P::ScalarField],
+    shares: &[SerializeableSharedRep3Witness<P::ScalarField, SeedRng>; 3],
+) -> color_eyre::Result<(SharingProof<P>, Vec<SharingOpening<P::ScalarField>>)> {
+    let additive_shares = shares
+        .iter()
+        .map(|share| match &share.witness {
+            Rep3ShareVecType::Replicated(vec) => vec.iter().map(|x| x.a).collect(),
+            Rep3ShareVecType::SeededReplicated(replicated_seed_type) => {
+                replicated_seed_type.a.clone().expand_vec()
+            }
+            Rep3ShareVecType::Additive(vec) => vec.clone(),
+            Rep3ShareVecType::SeededAdditive(seeded_type) => seeded_type.clone().expand_vec(),
+        })
+        .collect::<Vec<_>>();
+    SharingProof::prove(
+        r1cs,
+        &shares[0].public_inputs,
+        witness,
+        SharingScheme::Rep3,
+        &additive_shares,
+        &mut rand::thread_rng(),
+    )
+}
+
+/// Proves that the Shamir witness shares reconstruct to the private `witness`, which satisfies the R1CS, see [SharingProof]. Returns the proof, which can be published, and the [SharingOpening]s of the parties.
+pub fn prove_sharing_shamir<P: Pairing>(
+    r1cs: &R1CS<P>,
+    witness: &[P::ScalarField],
+    threshold: usize,
+    shares: &[SharedWitness<P::ScalarField, ShamirPrimeFieldShare<P::ScalarField>>],
+) -> color_eyre::Result<(SharingProof<P>, Vec<SharingOpening<P::ScalarField>>)> {
+    let public_inputs = shares
+        .first()
+        .map(|share| share.public_inputs.as_slice())
+        .unwrap_or_default();
+    let shares = shares
+        .iter()
+        .map(|share| ShamirPrimeFieldShare::convert_slice(&share.witness).to_vec())
+        .collect::<Vec<_>>();
+    SharingProof::prove(
+        r1cs,
+        public_inputs,
+        witness,
+        SharingScheme::Shamir { threshold },
+        &shares,
+        &mut rand::thread_rng(),
+    )
+}
+
+/// Checks a witness share file against the [SharingProof] of the splitting party, using the [SharingOpening] of this party. This does not verify the proof itself, see [SharingProof::verify].
+pub fn verify_witness_share<P: Pairing>(
+    witness: &Path,
+    protocol: MPCProtocol,
+    proof: &SharingProof<P>,
+    opening: &SharingOpening<P::ScalarField>,
+) -> color_eyre::Result<()> {
+    let (witness_file, file_size) =
+        file_utils::open_untrusted(witness, file_utils::MAX_SHARE_FILE_SIZE)
+            .context("trying to open witness share file")?;
+    match (protocol, proof.scheme) {
+        (MPCProtocol::REP3, SharingScheme::Rep3) => {
+            let deserialized = parse_serialized_witness_share_rep3::<_, P::ScalarField>(
+                witness_file,
+                file_size,
+                file_utils::MAX_SHARED_ELEMENTS,
+            )?;
+            let (a, b) = rep3_additive_shares(deserialized.witness)?;
+            let shares = match &b {
+                Some(b) => vec![a.as_slice(), b.as_slice()],
+                None => vec![a.as_slice()],
+            };
+            proof.verify_share(opening, &deserialized.public_inputs, &shares)?;
+        }
+        (MPCProtocol::SHAMIR, SharingScheme::Shamir { .. }) => {
+            let witness_share = parse_witness_share_shamir::<_, P::ScalarField>(
+                witness_file,
+                file_size,
+                file_utils::MAX_SHARED_ELEMENTS,
+            )?;
+            proof.verify_share(
+                opening,
+                &witness_share.public_inputs,
+                &[ShamirPrimeFieldShare::convert_slice(&witness_share.witness)],
+            )?;
+        }
+        (protocol, scheme) => {
+            bail!("a {protocol} witness share can not be checked against a proof for {scheme:?} shares")
+        }
+    }
+    Ok(())
+}
+
+/// Parses a JSON file mapping the names of private inputs to their declared bit widths.
+pub fn parse_bit_widths(path: &Path) -> color_eyre::Result<BTreeMap<String, usize>> {
+    file_utils::check_file_exists(path)?;
+    let file = BufReader::new(File::open(path).context("while opening bit widths file")?);
+    serde_json::from_reader(file).context("while parsing bit widths file")
+}
+
+/// Parses a JSON file with the authoritative values of the public inputs, in the same format as the input JSON file of `split_input`.
+pub fn parse_public_inputs<F: PrimeField>(
+    path: &Path,
+) -> color_eyre::Result<BTreeMap<String, Vec<F>>> {
+    file_utils::check_file_exists(path)?;
+    let file = BufReader::new(File::open(path).context("while opening public inputs file")?);
+    let json: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_reader(file).context("while parsing public inputs file")?;
+    json.into_iter()
+        .map(|(name, val)| {
+            let vals = file_utils::parse_input_value(&val)?
+                .into_iter()
+                .collect::<Option<Vec<F>>>()
+                .with_context(|| format!("public input \"{name}\" must not be unknown"))?;
+            Ok((name, vals))
+        })
+        .collect()
+}
+
+/// Checks the copies of the public inputs in the input shares of the data owners. Without authoritative public inputs, all input shares need to contain identical copies of every public input. With authoritative public inputs, every copy needs to match the authoritative value, and input shares may omit public inputs. The error names the conflicting input share and public input.
+pub fn check_public_inputs<'a, F: PrimeField>(
+    copies: impl IntoIterator<Item = (&'a Path, &'a BTreeMap<String, Vec<F>>)>,
+    authoritative: Option<&BTreeMap<String, Vec<F>>>,
+) -> color_eyre::Result<()> {
+    let mut copies = copies.into_iter();
+    if let Some(authoritative) = authoritative {
+        for (path, public_inputs) in copies {
+            for (name, value) in public_inputs {
+                match authoritative.get(name) {
+                    None => bail!(
+                        "input share {} contains public input \"{name}\", which is not in the authoritative public inputs",
+                        path.display()
+                    ),
+                    Some(expected) if expected != value => bail!(
+                        "public input \"{name}\" of input share {} differs from the authoritative public inputs",
+                        path.display()
+                    ),
+                    Some(_) => {}
+                }
+            }
+        }
+        return Ok(());
+    }
+    let Some((first_path, first)) = copies.next() else {
+        return Ok(());
+    };
+    for (path, public_inputs) in copies {
+        for name in first.keys().chain(public_inputs.keys()) {
+            match (first.get(name), public_inputs.get(name)) {
+                (Some(_), None) => bail!(
+                    "public input \"{name}\" is missing in input share {}, but present in input share {}",
+                    path.display(),
+                    first_path.display()
+                ),
+                (None, Some(_)) => bail!(
+                    "public input \"{name}\" is present in input share {}, but missing in input share {}",
+                    path.display(),
+                    first_path.display()
+                ),
+                (Some(expected), Some(value)) if expected != value => bail!(
+                    "public input \"{name}\" differs between input shares {} and {}",
+                    first_path.display(),
+                    path.display()
+                ),
+                _ => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_bit_width<F: PrimeField>(
+    name: &str,
+    vals: &[Option<F>],
+    bits: usize,
+) -> color_eyre::Result<()> {
+    if bits > F::MODULUS_BIT_SIZE as usize {
+        bail!(
+            "declared bit width {bits} of input \"{name}\" exceeds the field size of {} bits",
+            F::MODULUS_BIT_SIZE
+        );
+    }
+    for (i, val) in vals.iter().enumerate() {
+        if let Some(val) = val {
+            if val.into_bigint().num_bits() as usize > bits {
+                bail!("element {i} of input \"{name}\" does not fit into the declared bit width of {bits} bits");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Splits the input according to the provided parameters. Private inputs with a declared bit width in `bit_widths` are range-checked before sharing and the declared widths are embedded in the shares.
+pub fn split_input<P>(
+    input: PathBuf,
+    circuit_path: PathBuf,
+    config: CompilerConfig,
+    seeded: bool,
+    additive: bool,
+    bit_widths: BTreeMap<String, usize>,
+) -> color_eyre::Result<[SerializeableSharedRep3Input<P::ScalarField, SeedRng>; 3]>
+where
+    P: Pairing + CircomArkworksPairingBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+{
+    //get the public inputs if any from parser
+    let public_inputs =
+        CoCircomCompiler::<P>::get_public_inputs(circuit_path.clone(), config.clone())
+            .context("while reading public inputs from circuit")?;
+
+    // read the input file
+    let input_file = BufReader::new(File::open(&input).context("while opening input file")?);
+
+    let input_json: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_reader(input_file).context("while parsing input file")?;
+
+    // the order of the keys of JSON objects is not canonical, thus buses are flattened in the order of the declaration of their fields, which requires building the circuit
+    let input_layouts = if input_json.values().any(file_utils::contains_object) {
+        CoCircomCompiler::<P>::get_input_layouts(circuit_path, config)
+            .context("while reading input layouts from circuit")?
+    } else {
+        vec![]
+    };
+
+    // create input shares
+    let mut shares = [
+        SerializeableSharedRep3Input::<P::ScalarField, SeedRng>::default(),
+        SerializeableSharedRep3Input::<P::ScalarField, SeedRng>::default(),
+        SerializeableSharedRep3Input::<P::ScalarField, SeedRng>::default(),
+    ];
+
+    for name in bit_widths.keys() {
+        if !input_json.contains_key(name) {
+            bail!("bit width declared for unknown input \"{name}\"");
+        }
+        if public_inputs.contains(name) {
+            bail!("bit width declared for public input \"{name}\"");
+        }
+    }
+
+    let mut rng = rand::thread_rng();
+    for (name, val) in input_json {
+        let parsed_vals = match input_layouts.iter().find(|layout| layout.name == name) {
+            Some(layout) if layout.is_bus() => {
+                file_utils::parse_input_value_with_layout(&val, layout)?
+            }
+            _ => file_utils::parse_input_value(&val)?,
+        };
+        if public_inputs.contains(&name) {
+            let parsed_vals = parsed_vals
+                .into_iter()
+                .collect::<Option<Vec<P::ScalarField>>>()
+                .context("Public inputs must not be unkown")?;
+            shares[0]
+                .public_inputs
+                .insert(name.clone(), parsed_vals.clone());
+            shares[1]
+                .public_inputs
+                .insert(name.clone(), parsed_vals.clone());
+            shares[2].public_inputs.insert(name.clone(), parsed_vals);
+        } else {
+            if let Some(bits) = bit_widths.get(&name) {
+                check_bit_width(&name, &parsed_vals, *bits)?;
+                for share in shares.iter_mut() {
+                    share.bit_widths.insert(name.clone(), *bits);
+                }
+            }
+            // if all elements are Some, then we can share normally
+            // else we can only share as Vec<Option<T>> and we have to merge unknown inputs later
+            if parsed_vals.iter().all(Option::is_some) {
+                let parsed_vals = parsed_vals
+                    .into_iter()
+                    .collect::<Option<Vec<_>>>()
+                    .expect("all are Some");
+                let [share0, share1, share2] = SerializeableSharedRep3Input::share_rep3(
+                    &parsed_vals,
+                    &mut rng,
+                    seeded,
+                    additive,
+                );
+                shares[0].shared_inputs.insert(name.clone(), share0);
+                shares[1].shared_inputs.insert(name.clone(), share1);
+                shares[2].shared_inputs.insert(name.clone(), share2);
+            } else {
+                let [share0, share1, share2] =
+                    SerializeableSharedRep3Input::<_, SeedRng>::maybe_share_rep3(
+                        &parsed_vals,
+                        &mut rng,
+                        additive,
+                    );
+                shares[0].maybe_shared_inputs.insert(name.clone(), share0);
+                shares[1].maybe_shared_inputs.insert(name.clone(), share1);
+                shares[2].maybe_shared_inputs.insert(name.clone(), share2);
+            };
+        }
+    }
+    Ok(shares)
+}
+
+/// Commits to the private inputs with the provided `names` of the REP3 input shares of a data owner and embeds the shared opening of the commitment under `label` into the shares. If `public_input` is provided, the commitment is exposed as this public input of the circuit, which makes it part of the public inputs of the proof. The returned [InputCommitment] can be published, the parties check their shares against it with [verify_input_commitments].
+pub fn commit_input_shares<P: Pairing>(
+    shares: &mut [SerializeableSharedRep3Input<P::ScalarField, SeedRng>; 3],
+    label: &str,
+    names: &[String],
+    public_input: Option<&str>,
+) -> color_eyre::Result<InputCommitment<P::G1>> {
+    let mut inputs = BTreeMap::new();
+    for name in names {
+        if shares[0].maybe_shared_inputs.contains_key(name) {
+            bail!("can not commit to input \"{name}\" with unknown elements");
+        }
+        let components = shares
+            .iter()
+            .map(|share| share.shared_inputs.get(name).cloned())
+            .collect::<Option<Vec<_>>>()
+            .with_context(|| format!("can not commit to unknown private input \"{name}\""))?;
+        inputs.insert(name.clone(), reconstruct_rep3_share_vecs(components)?);
+    }
+    let mut rng = rand::thread_rng();
+    let (mut commitment, blinding) =
+        InputCommitment::<P::G1>::commit(label, &inputs, names, &mut rng)?;
+    if let Some(name) = public_input {
+        if shares[0].public_inputs.contains_key(name)
+            || shares[0].shared_inputs.contains_key(name)
+            || shares[0].maybe_shared_inputs.contains_key(name)
+        {
+            bail!("input \"{name}\" is set by the commitment and must not be in the input file");
+        }
+        let value = commitment.expose_as_public_input(name);
+        for share in shares.iter_mut() {
+            share.public_inputs.insert(name.to_owned(), vec![value]);
+        }
+    }
+    let openings = CommitmentOpening::share_rep3(names.to_vec(), blinding, &mut rng);
+    for (share, opening) in shares.iter_mut().zip(openings) {
+        share.commitment_openings.insert(label.to_owned(), opening);
+    }
+    Ok(commitment)
+}
+
+/// Checks in MPC that the shared inputs open to the published commitments of the data owners, using the openings embedded in the input share. Fails if an opening is missing, the inputs differ from the committed ones, or a commitment exposed as a public input differs from that public input.
+pub fn verify_input_commitments<P: Pairing, N: Rep3Network>(
+    input_share: &SharedInput<P::ScalarField, Rep3PrimeFieldShare<P::ScalarField>>,
+    commitments: &[InputCommitment<P::G1>],
+    net: &mut N,
+) -> color_eyre::Result<()> {
+    for commitment in commitments {
+        let opening = input_share
+            .commitment_openings
+            .get(&commitment.label)
+            .with_context(|| {
+                format!(
+                    "the input share contains no opening of commitment \"{}\"",
+                    commitment.label
+                )
+            })?;
+        commitment
+            .verify_rep3(
+                &input_share.public_inputs,
+                &input_share.shared_inputs,
+                opening,
+                net,
+            )
+            .with_context(|| format!("while verifying commitment \"{}\"", commitment.label))?;
+    }
+    Ok(())
+}
+
+/// The input of a data owner shared with publicly verifiable secret sharing, see [split_input_pvss]. This file can be published, every party decrypts its input share with [decrypt_input_share].
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct PvssSharedRep3Input<P: Pairing> {
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    /// A map from variable names to the public field elements.
+    pub public_inputs: BTreeMap<String, Vec<P::ScalarField>>,
+    /// A map from variable names of the shared inputs to their amount of elements. The shared elements of all inputs are concatenated in the order of this map.
+    pub shared_input_lens: BTreeMap<String, usize>,
+    /// A map from variable names to the declared bit widths of the shared inputs.
+    #[serde(default)]
+    pub bit_widths: BTreeMap<String, usize>,
+    /// The usage policy of the data owner, copied to the decrypted input shares. Note that the policy is not bound by the proof of correct sharing.
+    #[serde(default)]
+    pub usage_policy: Option<UsagePolicy>,
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    /// The PVSS public keys of the three parties.
+    pub public_keys: Vec<P::G1Affine>,
+    #[serde(
+        serialize_with = "mpc_core::ark_se",
+        deserialize_with = "mpc_core::ark_de"
+    )]
+    /// The encrypted additive shares together with the proof of correct sharing.
+    pub sharing: PvssSharing<P::G1>,
+}
+
+/// Splits the input like [split_input] into additive shares and encrypts the shares of party `i` to `public_keys[i]`. Inputs with unknown elements are not supported, as the shares can not be merged before publishing.
+pub fn split_input_pvss<P>(
+    input: PathBuf,
+    circuit_path: PathBuf,
+    config: CompilerConfig,
+    bit_widths: BTreeMap<String, usize>,
+    public_keys: [P::G1Affine; 3],
+) -> color_eyre::Result<PvssSharedRep3Input<P>>
+where
+    P: Pairing + CircomArkworksPairingBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+{
+    let shares = split_input::<P>(input, circuit_path, config, false, true, bit_widths)?;
+    if shares
+        .iter()
+        .any(|share| !share.maybe_shared_inputs.is_empty())
+    {
+        bail!("inputs with unknown elements can not be shared with PVSS");
+    }
+
+    let mut shared_input_lens = BTreeMap::new();
+    for (name, share) in shares[0].shared_inputs.iter() {
+        shared_input_lens.insert(name.clone(), rep3_share_vec_len(share)?);
+    }
+    let mut additive_shares: [Vec<P::ScalarField>; 3] = Default::default();
+    for (share, additive) in shares.iter().zip(additive_shares.iter_mut()) {
+        for share in share.shared_inputs.values() {
+            match share {
+                Rep3ShareVecType::Additive(vec) => additive.extend_from_slice(vec),
+                _ => bail!("expected additive shares"),
+            }
+        }
+    }
+
+    let sharing =
+        pvss::encrypt_shares::<P::G1, _>(additive_shares, &public_keys, &mut rand::thread_rng())?;
+    let [share0, _, _] = shares;
+    Ok(PvssSharedRep3Input {
+        public_inputs: share0.public_inputs,
+        shared_input_lens,
+        bit_widths: share0.bit_widths,
+        usage_policy: share0.usage_policy,
+        public_keys: public_keys.to_vec(),
+        sharing,
+    })
+}
+
+/// Verifies the proof of correct sharing of a published [PvssSharedRep3Input] and decrypts the input share of the party owning `key`. The decrypted shares are additive and are reshared when the input share is parsed for the witness generation.
+pub fn decrypt_input_share<P: Pairing>(
+    published: &PvssSharedRep3Input<P>,
+    key: &PvssKeyPair<P::G1>,
+) -> color_eyre::Result<SerializeableSharedRep3Input<P::ScalarField, SeedRng>> {
+    let public_keys: [P::G1Affine; 3] = published
+        .public_keys
+        .clone()
+        .try_into()
+        .map_err(|_| color_eyre::eyre::eyre!("expected the PVSS public keys of 3 parties"))?;
+    let id = public_keys
+        .iter()
+        .position(|pk| *pk == key.public_key)
+        .context("the key pair does not belong to any party of the committee")?;
+    let shares = published
+        .sharing
+        .decrypt(key, &public_keys, PartyID::try_from(id)?)?;
+
+    let num_shared = published
+        .shared_input_lens
+        .values()
+        .try_fold(0usize, |acc, len| acc.checked_add(*len))
+        .context("amount of shared elements overflows")?;
+    if shares.len() != num_shared {
+        bail!(
+            "expected {num_shared} shared elements, but the sharing contains {}",
+            shares.len()
+        );
+    }
+
+    let mut result = SerializeableSharedRep3Input::<P::ScalarField, SeedRng> {
+        public_inputs: published.public_inputs.clone(),
+        bit_widths: published.bit_widths.clone(),
+        usage_policy: published.usage_policy.clone(),
+        ..Default::default()
+    };
+    let mut shares = shares.into_iter();
+    for (name, len) in published.shared_input_lens.iter() {
+        let vec = shares.by_ref().take(*len).collect();
+        result
+            .shared_inputs
+            .insert(name.clone(), Rep3ShareVecType::Additive(vec));
+    }
+    Ok(result)
+}
+
+/// Try to parse a [PvssSharedRep3Input] from an untrusted [Read]er. At most `limit` bytes are read, and the declared shared inputs and the ciphertexts of every party may contain at most `max_len` elements, which is checked before decrypting the shares.
+pub fn parse_published_input_pvss<R: Read, P: Pairing>(
+    reader: R,
+    limit: u64,
+    max_len: usize,
+) -> color_eyre::Result<PvssSharedRep3Input<P>> {
+    let published: PvssSharedRep3Input<P> = file_utils::deserialize_untrusted(reader, limit)
+        .context("trying to parse published input")?;
+    file_utils::check_num_elements("public keys", published.public_keys.len(), 3)?;
+    file_utils::check_num_elements("ciphertexts", published.sharing.ciphertexts.len(), 3)?;
+    let num_shared = published
+        .shared_input_lens
+        .values()
+        .fold(0usize, |acc, len| acc.saturating_add(*len));
+    file_utils::check_num_elements("shared inputs", num_shared, max_len)?;
+    for ciphertext in published.sharing.ciphertexts.iter() {
+        file_utils::check_num_elements("ciphertext", ciphertext.len(), num_shared)?;
+    }
+    Ok(published)
+}
+
+/// Try to parse a [SerializeableSharedRep3Input] from an untrusted [Read]er. At most `limit` bytes are read and the shared inputs may contain at most `max_len` elements in total, which is checked before expanding seeded shares.
+pub fn parse_serialized_input_share_rep3<R: Read, F: PrimeField>(
+    reader: R,
+    limit: u64,
+    max_len: usize,
+) -> color_eyre::Result<SerializeableSharedRep3Input<F, SeedRng>> {
+    let deserialized: SerializeableSharedRep3Input<F, SeedRng> =
+        file_utils::deserialize_untrusted(reader, limit)
+            .context("trying to parse input share file")?;
+    let mut num_shared = 0usize;
+    for share in deserialized.shared_inputs.values() {
+        num_shared = num_shared.saturating_add(rep3_share_vec_len(share)?);
+    }
+    file_utils::check_num_elements("shared inputs", num_shared, max_len)?;
+    Ok(deserialized)
+}
+
+/// Try to parse a [SharedInput] from an untrusted [Read]er. At most `limit` bytes are read and the shared inputs may contain at most `max_len` elements in total.
+pub fn parse_shared_input<R: Read, F: PrimeField, N: Rep3Network>(
+    reader: R,
+    mpc_net: &mut N,
+    limit: u64,
+    max_len: usize,
+) -> color_eyre::Result<SharedInput<F, Rep3PrimeFieldShare<F>>> {
+    let deserialized = parse_serialized_input_share_rep3(reader, limit, max_len)?;
+
+    if !deserialized.maybe_shared_inputs.is_empty() {
+        bail!("still unmerged elements left");
+    }
+    if let Some(policy) = &deserialized.usage_policy {
+        policy.check_not_expired()?;
+    }
+
+    let public_inputs = deserialized.public_inputs;
+    let shared_inputs_ = deserialized.shared_inputs;
+    let bit_widths = deserialized.bit_widths;
+    let usage_policy = deserialized.usage_policy;
+    let commitment_openings = deserialized.commitment_openings;
+    for name in bit_widths.keys() {
+        if !shared_inputs_.contains_key(name) {
+            bail!("bit width declared for unknown shared input \"{name}\"");
+        }
+    }
+
+    let mut shared_inputs = BTreeMap::new();
+
+    let mut to_reshare = Vec::new();
+
+    for (_, share) in shared_inputs_.iter() {
+        match share {
+            Rep3ShareVecType::Replicated(_) => {}
+            Rep3ShareVecType::SeededReplicated(_) => {}
+            Rep3ShareVecType::Additive(vec) => to_reshare.extend_from_slice(vec),
+            Rep3ShareVecType::SeededAdditive(seeded_type) => {
+                to_reshare.extend_from_slice(&(seeded_type.to_owned().expand_vec()))
+            }
+        }
+    }
+
+    let mut reshared = reshare_vec(to_reshare, mpc_net)?;
+
+    for (name, share) in shared_inputs_ {
+        match share {
+            Rep3ShareVecType::Replicated(vec) => {
+                shared_inputs.insert(name, vec);
+            }
+            Rep3ShareVecType::SeededReplicated(replicated_seed_type) => {
+                shared_inputs.insert(name, replicated_seed_type.expand_vec()?);
+            }
+            Rep3ShareVecType::Additive(vec) => {
+                shared_inputs.insert(name, reshared.drain(..vec.len()).collect());
+            }
+            Rep3ShareVecType::SeededAdditive(seeded_type) => {
+                shared_inputs.insert(name, reshared.drain(..seeded_type.length()).collect());
+            }
+        }
+    }
+
+    Ok(SharedInput {
+        public_inputs,
+        shared_inputs,
+        bit_widths,
+        usage_policy,
+        commitment_openings,
+    })
+}
+
+/// Compiles the circuit to MPC VM bytecode and writes it to `out` in the format of [CoCircomCompilerParsed::write_compiled]. Returns the [CircuitHash] of the bytecode.
+///
+/// The circuit only needs to be compiled once and can then be distributed to all parties, which load it with [read_compiled_circuit].
+pub fn compile_circuit<P>(
+    circuit: String,
+    compiler: CompilerConfig,
+    out: &Path,
+) -> color_eyre::Result<CircuitHash>
+where
+    P: Pairing + CircomArkworksPairingBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+{
+    let circuit_path = PathBuf::from(&circuit);
+    file_utils::check_file_exists(&circuit_path)?;
+    let parsed_circom_circuit =
+        CoCircomCompiler::<P>::parse(circuit, compiler).context("while parsing circuit file")?;
+    let out_file = BufWriter::new(File::create(out).context("while creating output file")?);
+    let hash = parsed_circom_circuit
+        .write_compiled(out_file)
+        .context("while writing compiled circuit")?;
+    tracing::info!("Compiled circuit with hash {}", circuit_hash_to_hex(&hash));
+    Ok(hash)
+}
+
+/// Reads a circuit compiled with [compile_circuit]. Fails if the circuit was compiled for a different prime field or with an incompatible version of the MPC VM.
+pub fn read_compiled_circuit<F: PrimeField>(
+    path: &Path,
+) -> color_eyre::Result<(CoCircomCompilerParsed<F>, CircuitHash)> {
+    file_utils::check_file_exists(path)?;
+    let file = BufReader::new(File::open(path).context("while opening compiled circuit")?);
+    let (parsed, hash) =
+        CoCircomCompilerParsed::read_compiled(file).context("while reading compiled circuit")?;
+    tracing::info!(
+        "Loaded compiled circuit with hash {}",
+        circuit_hash_to_hex(&hash)
+    );
+    Ok((parsed, hash))
+}
+
+/// Loads the circuit compiled with [compile_circuit] or, if none is provided, parses the circuit file and compiles it to MPC VM bytecode.
+fn load_circuit<P>(
+    circuit: Option<String>,
+    compiled_circuit: Option<&Path>,
+    compiler: CompilerConfig,
+) -> color_eyre::Result<(CoCircomCompilerParsed<P::ScalarField>, CircuitHash)>
+where
+    P: Pairing + CircomArkworksPairingBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+{
+    if let Some(compiled_circuit) = compiled_circuit {
+        read_compiled_circuit(compiled_circuit)
+    } else if let Some(circuit) = circuit {
+        let circuit_path = PathBuf::from(&circuit);
+        file_utils::check_file_exists(&circuit_path)?;
+        // parse circuit file & put through our compiler
+        let parsed = CoCircomCompiler::<P>::parse(circuit, compiler)
+            .context("while parsing circuit file")?;
+        let hash = parsed
+            .circuit_hash()
+            .context("while computing circuit hash")?;
+        Ok((parsed, hash))
+    } else {
+        bail!("either a circuit file or a compiled circuit is required");
+    }
+}
+
+/// Invoke the witness generation locally on the plain VM, e.g., with an input reconstructed by [reconstruct_input_rep3]. Produces the same witness as [generate_witness_rep3] without any MPC.
+pub fn generate_witness_plain<P>(
+    input: SharedInput<P::ScalarField, P::ScalarField>,
+    circuit: Option<String>,
+    compiled_circuit: Option<&Path>,
+    compiler: CompilerConfig,
+    vm: VMConfig,
+) -> color_eyre::Result<SharedWitness<P::ScalarField, P::ScalarField>>
+where
+    P: Pairing + CircomArkworksPairingBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+{
+    let (parsed_circom_circuit, hash) = load_circuit::<P>(circuit, compiled_circuit, compiler)?;
+    if let Some(policy) = &input.usage_policy {
+        policy.check_circuit(&circuit_hash_to_hex(&hash))?;
+    }
+
+    let start = Instant::now();
+    let witness = parsed_circom_circuit
+        .to_plain_vm(vm)
+        .run(input)
+        .context("while running witness generation")?;
+    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+    tracing::info!("Local witness extension took {} ms", duration_ms);
+
+    Ok(witness.into_shared_witness())
+}
+
+/// Invoke the MPC witness generation process. It will return a [SharedWitness] if successful.
+/// It executes several steps:
+/// 1. Load the circuit compiled with [compile_circuit] or, if none is provided, parse the circuit file and compile it to MPC VM bytecode.
+/// 2. Set up a network connection to the MPC network.
+/// 3. Execute the bytecode on the MPC VM to generate the witness.
+pub fn generate_witness_rep3<P, U: Rng + SeedableRng + CryptoRng>(
+    input_share: SharedInput<P::ScalarField, Rep3PrimeFieldShare<P::ScalarField>>,
+    net: Rep3MpcNet,
+    config: GenerateWitnessConfig,
+) -> color_eyre::Result<SerializeableSharedRep3Witness<P::ScalarField, U>>
+where
+    P: Pairing + CircomArkworksPairingBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    U::Seed: Serialize + for<'a> Deserialize<'a> + Clone + std::fmt::Debug,
+{
+    let (parsed_circom_circuit, hash) = load_circuit::<P>(
+        config.circuit,
+        config.compiled_circuit.as_deref(),
+        config.compiler,
+    )?;
+
+    // the policy travels with the input into the witness share
+    let usage_policy = input_share.usage_policy.clone();
+    if let Some(policy) = &usage_policy {
+        policy.check_circuit(&circuit_hash_to_hex(&hash))?;
+        policy.check_not_expired()?;
+    }
+
+    let id = usize::from(net.get_id());
+
+    // init MPC protocol
+    let rep3_vm = parsed_circom_circuit
+        .to_rep3_vm_with_network(net, config.vm)
+        .context("while constructing MPC VM")?;
+
+    // execute witness generation in MPC
+    let start = Instant::now();
+    let (result_witness_share, mut net) = rep3_vm
+        .run_and_get_network(input_share)
+        .context("while running witness generation")?;
+
+    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+    tracing::info!("Party {}: Witness extension took {} ms", id, duration_ms);
+
+    let mut result_witness_share = result_witness_share.into_shared_witness();
+    if config.check_determinism {
+        check_determinism_rep3(&result_witness_share, &mut net, &config.out)?;
+        tracing::info!("Party {}: All parties executed identical traces", id);
+    }
+    if let Some(r1cs) = config.eliminate_linear_signals {
+        let linear_signals = load_linear_signals::<P>(&r1cs)?;
+        result_witness_share.witness = linear_signals
+            .reduce(result_witness_share.witness)
+            .context("while eliminating linear signals")?;
+        tracing::info!(
+            "Party {}: Eliminated {} linear signals from the witness",
+            id,
+            linear_signals.derived().len()
+        );
+    }
+
+    let mut res = SerializeableSharedRep3Witness::from_shared_witness(result_witness_share);
+    res.usage_policy = usage_policy;
+
+    Ok(res)
+}
+
+/// Compares the traces of the witness extension of all parties, see [SharedWitness::check_determinism_rep3]. If they diverge, the [co_circom_snarks::DeterminismReport] is written to `<out>.determinism.json` and an error is returned.
+fn check_determinism_rep3<F: PrimeField>(
+    witness_share: &SharedWitness<F, Rep3PrimeFieldShare<F>>,
+    net: &mut Rep3MpcNet,
+    out: &Path,
+) -> color_eyre::Result<()> {
+    let report = witness_share
+        .check_determinism_rep3(net)
+        .context("while checking the determinism of the witness extension")?;
+    if report.is_deterministic() {
+        return Ok(());
+    }
+    let report_path = PathBuf::from(format!("{}.determinism.json", out.display()));
+    let mut report_file =
+        storage::create(&report_path).context("while creating determinism report")?;
+    serde_json::to_writer_pretty(&mut report_file, &report)?;
+    report_file
+        .finish()
+        .context("while writing determinism report")?;
+    match &report.divergence {
+        Some(divergence) => bail!(
+            "the trace of party {} diverges from party {} at {:?} component {}, see {}",
+            report.party,
+            divergence.other_party,
+            divergence.section,
+            divergence.index,
+            report_path.display()
+        ),
+        None => bail!(
+            "the traces of parties {:?} diverge, see {}",
+            report.diverged,
+            report_path.display()
+        ),
+    }
+}
+
+/// Reads the R1CS file of a circuit and runs the linear signal elimination pass on it, see [LinearSignals].
+pub fn load_linear_signals<P>(r1cs: &Path) -> color_eyre::Result<LinearSignals<P::ScalarField>>
+where
+    P: Pairing + CircomArkworksPairingBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+{
+    file_utils::check_file_exists(r1cs)?;
+    let r1cs_file = BufReader::new(File::open(r1cs).context("while opening r1cs file")?);
+    let r1cs = R1CS::<P>::from_reader(r1cs_file).context("while parsing r1cs file")?;
+    Ok(LinearSignals::from_r1cs(&r1cs))
+}
+
+/// Recomputes the linear signals that were eliminated from a REP3 witness share. This is a local operation.
+pub fn expand_linear_signals_rep3<F: PrimeField>(
+    witness_share: SharedWitness<F, Rep3PrimeFieldShare<F>>,
+    linear_signals: &LinearSignals<F>,
+    id: PartyID,
+) -> color_eyre::Result<SharedWitness<F, Rep3PrimeFieldShare<F>>> {
+    let witness = linear_signals
+        .expand(
+            &witness_share.public_inputs,
+            witness_share.witness,
+            |constant, terms| {
+                let sum =
+                    terms
+                        .iter()
+                        .fold(Rep3PrimeFieldShare::zero_share(), |acc, (coeff, share)| {
+                            rep3::arithmetic::add(
+                                acc,
+                                rep3::arithmetic::mul_public(**share, *coeff),
+                            )
+                        });
+                rep3::arithmetic::add_public(sum, constant, id)
+            },
+        )
+        .context("while recomputing linear signals")?;
+    Ok(SharedWitness {
+        public_inputs: witness_share.public_inputs,
+        witness,
+    })
+}
+
+/// Recomputes the linear signals that were eliminated from a Shamir witness share. This is a local operation.
+pub fn expand_linear_signals_shamir<F: PrimeField>(
+    witness_share: SharedWitness<F, ShamirPrimeFieldShare<F>>,
+    linear_signals: &LinearSignals<F>,
+) -> color_eyre::Result<SharedWitness<F, ShamirPrimeFieldShare<F>>> {
+    let witness = linear_signals
+        .expand(
+            &witness_share.public_inputs,
+            witness_share.witness,
+            |constant, terms| {
+                let sum = terms.iter().fold(
+                    ShamirPrimeFieldShare::zero_share(),
+                    |acc, (coeff, share)| {
+                        shamir::arithmetic::add(
+                            acc,
+                            shamir::arithmetic::mul_public(**share, *coeff),
+                        )
+                    },
+                );
+                shamir::arithmetic::add_public(sum, constant)
+            },
+        )
+        .context("while recomputing linear signals")?;
+    Ok(SharedWitness {
+        public_inputs: witness_share.public_inputs,
+        witness,
+    })
+}
+
+/// Invoke the MPC proof generation process. It will return a [`Groth16Proof`] if successful.
+/// It executes several steps:
+/// 1. Establishes a TLS connection from the network configuration.
+/// 2. Constructs a [`Rep3CoGroth16`] prover from the protocol.
+/// 3. Executes the prover in MPC
+pub fn prove_with_matrices_rep3<P: Pairing + CircomArkworksPairingBridge>(
+    witness_share: SharedWitness<P::ScalarField, Rep3PrimeFieldShare<P::ScalarField>>,
+    config: NetworkConfig,
+    zkey: ZKey<P>,
+) -> color_eyre::Result<Groth16Proof<P>>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    tracing::info!("establishing network and building protocol....");
+    let prover = Rep3CoGroth16::with_network_config(config)?;
+    // connect to network
+    tracing::info!("done!");
+    tracing::info!("starting prover...");
+    // execute prover in MPC
+    let zkey = Arc::new(zkey);
+    prover.prove(zkey, witness_share)
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::Fr;
+    use ark_ff::{UniformRand, Zero};
+    use circom_types::Witness;
+    use rand::thread_rng;
+
+    use super::*;
+
+    const NUM_PUBLIC_INPUTS: usize = 2;
+
+    fn plain_witness() -> Witness<Fr> {
+        Witness {
+            values: (0..64u64).map(Fr::from).collect(),
+        }
+    }
+
+    fn write_share(dir: &Path, share: &impl Serialize) -> (PathBuf, u64) {
+        let path = dir.join("witness.wtns.0.shared");
+        let bytes = bincode::serialize(share).unwrap();
+        std::fs::write(&path, &bytes).unwrap();
+        (path, bytes.len() as u64)
+    }
+
+    #[test]
+    fn stats_of_random_shares() {
+        let mut rng = thread_rng();
+        let shares = (0..1000).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+        let stats = WitnessShareStats::from_additive_shares(42, "additive", 3, &shares);
+        assert_eq!(stats.file_size, 42);
+        assert_eq!(stats.share_type, "additive");
+        assert_eq!(stats.num_public_inputs, 3);
+        assert_eq!(stats.num_shared_signals, 1000);
+        assert_eq!(stats.num_zero_shares, 0);
+        assert_eq!(stats.zero_share_ratio, 0.0);
+        assert!(stats.byte_entropy > 7.5 && stats.byte_entropy <= 8.0);
+        assert_eq!(stats.byte_histogram.len(), 16);
+        assert_eq!(stats.byte_histogram.iter().sum::<u64>(), 32 * 1000);
+    }
+
+    #[test]
+    fn stats_of_plain_witness() {
+        let values = plain_witness().values;
+        let stats = WitnessShareStats::from_additive_shares(0, "additive", 0, &values);
+        assert_eq!(stats.num_zero_shares, 1);
+        assert_eq!(stats.zero_share_ratio, 1.0 / 64.0);
+        // all bytes but the lowest one of each element are zero
+        assert!(stats.byte_entropy < 1.0);
+        assert_eq!(stats.byte_histogram[0], 31 * 64 + 16);
+        assert_eq!(stats.byte_histogram[1..4], [16, 16, 16]);
+    }
+
+    #[test]
+    fn stats_of_zero_and_empty_shares() {
+        let stats = WitnessShareStats::from_additive_shares(0, "additive", 0, &[Fr::zero(); 10]);
+        assert_eq!(stats.num_zero_shares, 10);
+        assert_eq!(stats.zero_share_ratio, 1.0);
+        assert_eq!(stats.byte_entropy, 0.0);
+        assert_eq!(stats.byte_histogram[0], 320);
+
+        let stats = WitnessShareStats::from_additive_shares::<Fr>(0, "additive", 0, &[]);
+        assert_eq!(stats.num_shared_signals, 0);
+        assert_eq!(stats.zero_share_ratio, 0.0);
+        assert_eq!(stats.byte_entropy, 0.0);
+        assert_eq!(stats.byte_histogram, vec![0; 16]);
+    }
+
+    #[test]
+    fn stats_of_rep3_witness_share_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut rng = thread_rng();
+        for (seeded, additive, share_type) in [
+            (false, false, "replicated"),
+            (true, false, "seeded replicated"),
+            (false, true, "additive"),
+            (true, true, "seeded additive"),
+        ] {
+            let [share, _, _] = SerializeableSharedRep3Witness::<Fr, SeedRng>::share_rep3(
+                plain_witness(),
+                NUM_PUBLIC_INPUTS,
+                &mut rng,
+                seeded,
+                additive,
+            );
+            let (path, file_size) = write_share(dir.path(), &share);
+            let stats = witness_share_stats::<Fr>(&path, MPCProtocol::REP3).unwrap();
+            assert_eq!(stats.file_size, file_size);
+            assert_eq!(stats.share_type, share_type);
+            assert_eq!(stats.num_public_inputs, NUM_PUBLIC_INPUTS);
+            assert_eq!(stats.num_shared_signals, 64 - NUM_PUBLIC_INPUTS);
+            // in contrast to the plain witness, the shares look random
+            assert!(stats.byte_entropy > 7.0, "{share_type}");
+        }
+    }
+
+    #[test]
+    fn stats_of_shamir_witness_share_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let shares = SharedWitness::<Fr, ShamirPrimeFieldShare<Fr>>::share_shamir(
+            plain_witness(),
+            NUM_PUBLIC_INPUTS,
+            1,
+            3,
+            &mut thread_rng(),
+        );
+        let (path, file_size) = write_share(dir.path(), &shares[0]);
+        let stats = witness_share_stats::<Fr>(&path, MPCProtocol::SHAMIR).unwrap();
+        assert_eq!(stats.file_size, file_size);
+        assert_eq!(stats.share_type, "shamir");
+        assert_eq!(stats.num_public_inputs, NUM_PUBLIC_INPUTS);
+        assert_eq!(stats.num_shared_signals, 64 - NUM_PUBLIC_INPUTS);
+        assert!(stats.byte_entropy > 7.0);
+    }
+
+    #[test]
+    fn stats_of_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing.shared");
+        assert!(witness_share_stats::<Fr>(&path, MPCProtocol::REP3).is_err());
+    }
+
+    fn public_inputs(values: &[(&str, u64)]) -> BTreeMap<String, Vec<Fr>> {
+        values
+            .iter()
+            .map(|(name, value)| (name.to_string(), vec![Fr::from(*value)]))
+            .collect()
+    }
+
+    fn assert_error_names(result: color_eyre::Result<()>, parts: &[&str]) {
+        let err = result.unwrap_err().to_string();
+        for part in parts {
+            assert!(err.contains(part), "\"{part}\" is not part of \"{err}\"");
+        }
+    }
+
+    #[test]
+    fn identical_public_inputs_are_accepted() {
+        let a = public_inputs(&[("x", 1), ("y", 2)]);
+        let b = a.clone();
+        check_public_inputs(
+            [(Path::new("a.shared"), &a), (Path::new("b.shared"), &b)],
+            None,
+        )
+        .unwrap();
+        check_public_inputs::<Fr>([], None).unwrap();
+    }
+
+    #[test]
+    fn missing_public_input_is_rejected() {
+        let a = public_inputs(&[("x", 1), ("y", 2)]);
+        let b = public_inputs(&[("x", 1)]);
+        let result = check_public_inputs(
+            [(Path::new("a.shared"), &a), (Path::new("b.shared"), &b)],
+            None,
+        );
+        assert_error_names(
+            result,
+            &["\"y\"", "missing in input share b.shared", "a.shared"],
+        );
+    }
+
+    #[test]
+    fn extra_public_input_is_rejected() {
+        let a = public_inputs(&[("x", 1)]);
+        let b = public_inputs(&[("x", 1), ("z", 3)]);
+        let result = check_public_inputs(
+            [(Path::new("a.shared"), &a), (Path::new("b.shared"), &b)],
+            None,
+        );
+        assert_error_names(
+            result,
+            &[
+                "\"z\"",
+                "present in input share b.shared",
+                "missing in input share a.shared",
+            ],
+        );
+    }
+
+    #[test]
+    fn differing_public_input_is_rejected() {
+        let a = public_inputs(&[("x", 1), ("y", 2)]);
+        let b = public_inputs(&[("x", 1), ("y", 3)]);
+        let result = check_public_inputs(
+            [(Path::new("a.shared"), &a), (Path::new("b.shared"), &b)],
+            None,
+        );
+        assert_error_names(result, &["\"y\"", "differs", "a.shared", "b.shared"]);
+    }
+
+    #[test]
+    fn authoritative_public_inputs() {
+        let authoritative = public_inputs(&[("x", 1), ("y", 2)]);
+        // input shares may omit public inputs
+        let a = public_inputs(&[("x", 1)]);
+        let b = public_inputs(&[("y", 2)]);
+        check_public_inputs(
+            [(Path::new("a.shared"), &a), (Path::new("b.shared"), &b)],
+            Some(&authoritative),
+        )
+        .unwrap();
+
+        let differs = public_inputs(&[("x", 1), ("y", 3)]);
+        let result = check_public_inputs(
+            [
+                (Path::new("a.shared"), &a),
+                (Path::new("c.shared"), &differs),
+            ],
+            Some(&authoritative),
+        );
+        assert_error_names(
+            result,
+            &[
+                "\"y\"",
+                "input share c.shared",
+                "differs from the authoritative",
+            ],
+        );
+
+        let unknown = public_inputs(&[("z", 1)]);
+        let result = check_public_inputs([(Path::new("d.shared"), &unknown)], Some(&authoritative));
+        assert_error_names(
+            result,
+            &["input share d.shared", "\"z\"", "not in the authoritative"],
+        );
+    }
+}
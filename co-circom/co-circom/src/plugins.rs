@@ -0,0 +1,184 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use ark_ec::pairing::Pairing;
+use co_circom_snarks::VerificationError;
+use color_eyre::eyre::{bail, ContextCompat};
+use mpc_net::config::NetworkConfig;
+
+use crate::{MPCProtocol, ProofSystem, PLUGIN_NAMES};
+
+/// The arguments passed to the [prove function](ProofSystemPlugin::prove) of a plugin by the `generate-proof` command.
+#[derive(Debug)]
+pub struct PluginProveArgs {
+    /// The path to the witness share file, which was generated with [`protocol`](Self::protocol)
+    pub witness: PathBuf,
+    /// The path to the proving key file
+    pub zkey: PathBuf,
+    /// The MPC protocol to be used
+    pub protocol: MPCProtocol,
+    /// The threshold of tolerated colluding parties
+    pub threshold: usize,
+    /// The network config of this party
+    pub network: NetworkConfig,
+}
+
+/// The output of the [prove function](ProofSystemPlugin::prove) of a plugin.
+#[derive(Debug)]
+pub struct PluginProof<F> {
+    /// The proof, which is written to the output file as JSON
+    pub proof: serde_json::Value,
+    /// The public inputs of the proof, including the constant 1 at position 0
+    pub public_inputs: Vec<F>,
+}
+
+/// The arguments passed to the [verify function](ProofSystemPlugin::verify) of a plugin by the `verify` command.
+#[derive(Debug)]
+pub struct PluginVerifyArgs<F> {
+    /// The path to the proof file
+    pub proof: PathBuf,
+    /// The path to the verification key file
+    pub vk: PathBuf,
+    /// The public inputs of the proof, without the constant 1
+    pub public_inputs: Vec<F>,
+}
+
+/// Computes a proof in MPC. Implementations are usually generic over the pairing and dispatch on the [MPCProtocol] to the Rep3 or Shamir driver of the proof system.
+pub type ProveFn<P> =
+    fn(PluginProveArgs) -> color_eyre::Result<PluginProof<<P as Pairing>::ScalarField>>;
+
+/// Verifies a proof computed by the corresponding [ProveFn].
+pub type VerifyFn<P> =
+    fn(PluginVerifyArgs<<P as Pairing>::ScalarField>) -> Result<(), VerificationError>;
+
+/// An additional proof system, which can be selected by its name in the `generate-proof` and `verify` commands.
+pub struct ProofSystemPlugin<P: Pairing> {
+    /// The name of the proof system on the command line
+    pub name: &'static str,
+    /// Computes a proof in MPC
+    pub prove: ProveFn<P>,
+    /// Verifies a proof
+    pub verify: VerifyFn<P>,
+}
+
+impl<P: Pairing> ProofSystemPlugin<P> {
+    /// Creates a new plugin from its name and the prove and verify functions.
+    pub fn new(name: &'static str, prove: ProveFn<P>, verify: VerifyFn<P>) -> Self {
+        Self {
+            name,
+            prove,
+            verify,
+        }
+    }
+}
+
+/// A registry of additional proof systems for a pairing.
+///
+/// External crates register their proof systems with [register](Self::register), typically from a `register` function that is generic over the pairing. The co-circom binary calls these functions for every crate that is enabled via a cargo feature, so new proof systems do not require changes to the dispatch of the built-in proof systems.
+pub struct ProofSystemRegistry<P: Pairing> {
+    plugins: BTreeMap<&'static str, ProofSystemPlugin<P>>,
+}
+
+impl<P: Pairing> Default for ProofSystemRegistry<P> {
+    fn default() -> Self {
+        Self {
+            plugins: BTreeMap::new(),
+        }
+    }
+}
+
+impl<P: Pairing> ProofSystemRegistry<P> {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a plugin. Fails if the name is the name of a built-in proof system, is not listed in [PLUGIN_NAMES], or is already registered.
+    pub fn register(&mut self, plugin: ProofSystemPlugin<P>) -> color_eyre::Result<()> {
+        match plugin.name.parse::<ProofSystem>() {
+            Ok(ProofSystem::Plugin(_)) => {}
+            Ok(_) => bail!(
+                "cannot register {}, as it is a built-in proof system",
+                plugin.name
+            ),
+            Err(_) => bail!(
+                "cannot register {}, as it is not listed in PLUGIN_NAMES",
+                plugin.name
+            ),
+        }
+        if self.plugins.contains_key(plugin.name) {
+            bail!("proof system {} is already registered", plugin.name);
+        }
+        self.plugins.insert(plugin.name, plugin);
+        Ok(())
+    }
+
+    /// Returns the plugin with the given name.
+    pub fn get(&self, name: &str) -> color_eyre::Result<&ProofSystemPlugin<P>> {
+        self.plugins.get(name).with_context(|| {
+            format!(
+                "unknown proof system {name}, available plugins: [{}]",
+                self.names().collect::<Vec<_>>().join(", ")
+            )
+        })
+    }
+
+    /// Returns the names of all registered plugins in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.plugins.keys().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::Bn254;
+    use color_eyre::eyre::eyre;
+
+    use super::*;
+
+    fn plugin(name: &'static str) -> ProofSystemPlugin<Bn254> {
+        ProofSystemPlugin::new(
+            name,
+            |_| Err(eyre!("not implemented")),
+            |_| Err(VerificationError::InvalidProof),
+        )
+    }
+
+    #[test]
+    fn parse_proof_system() {
+        assert_eq!("groth16".parse(), Ok(ProofSystem::Groth16));
+        assert_eq!("Plonk".parse(), Ok(ProofSystem::Plonk));
+        assert_eq!(
+            "test-plugin".parse(),
+            Ok(ProofSystem::Plugin("test-plugin".to_owned()))
+        );
+        for name in ["grot16", "", "Test-Plugin"] {
+            let err = name.parse::<ProofSystem>().unwrap_err();
+            assert!(err.contains("unknown proof system"), "{err}");
+        }
+        assert!(serde_json::from_str::<ProofSystem>("\"grot16\"").is_err());
+        assert_eq!(
+            serde_json::to_string(&ProofSystem::Plugin("test-plugin".to_owned())).unwrap(),
+            "\"test-plugin\""
+        );
+    }
+
+    #[test]
+    fn register_and_get_plugin() {
+        let mut registry = ProofSystemRegistry::<Bn254>::new();
+        registry.register(plugin("test-plugin")).unwrap();
+        assert_eq!(registry.names().collect::<Vec<_>>(), ["test-plugin"]);
+        assert_eq!(registry.get("test-plugin").unwrap().name, "test-plugin");
+        assert!(registry.get("grot16").is_err());
+        // the name is registered already
+        assert!(registry.register(plugin("test-plugin")).is_err());
+    }
+
+    #[test]
+    fn register_rejects_invalid_names() {
+        let mut registry = ProofSystemRegistry::<Bn254>::new();
+        for name in ["groth16", "PLONK", "", "unlisted-plugin"] {
+            assert!(registry.register(plugin(name)).is_err(), "{name}");
+        }
+        assert_eq!(registry.names().count(), 0);
+    }
+}
@@ -0,0 +1,336 @@
+//! Recommendations of the MPC configuration for a circuit.
+//!
+//! The [CircuitProfile] of an R1CS file summarizes the size of the circuit and how many of its constraints stem from bit decompositions, which are expensive in MPC as every decomposition of a secret value needs an arithmetic-to-binary conversion during the witness extension. Together with the declared latency and bandwidth of the network in the [NetworkProfile], [recommend] picks the MPC protocol, the [A2BType] of the witness extension, and the number of threads, and reports the projected costs of the witness extension and the Groth16 proof generation.
+//!
+//! The projections use a coarse cost model, which counts the communication rounds and the bytes sent by the busiest party. They are meant to compare configurations and to spot bottlenecks, not to predict the running time exactly. The compute time is only projected if a [CalibrationReport] of the local machine is provided.
+use std::collections::HashSet;
+
+use ark_ec::pairing::Pairing;
+use ark_ff::{Field, PrimeField};
+use circom_types::r1cs::R1CS;
+use color_eyre::eyre;
+use mpc_core::protocols::rep3::conversion::A2BType;
+use serde::Serialize;
+
+use crate::{calibrate::CalibrationReport, MPCProtocol};
+
+/// The number of decomposed bits that are assumed per bit decomposition if the decompositions can not be detected in the R1CS, e.g., because the linear constraints were optimized away by the circom compiler.
+pub const DEFAULT_BITS_PER_DECOMPOSITION: usize = 32;
+
+/// The minimum number of elements of the evaluation domain per thread. Below, the overhead of the thread pool outweighs the parallel speedup.
+pub const MIN_ELEMENTS_PER_THREAD: usize = 1 << 12;
+
+/// The number of communication rounds of a Groth16 proof generation with REP3, independent of the circuit size.
+const GROTH16_ROUNDS_REP3: u64 = 6;
+
+/// The number of communication rounds of a Groth16 proof generation with Shamir, including the translation of the witness share.
+const GROTH16_ROUNDS_SHAMIR: u64 = 7;
+
+/// The number of FFTs of the size of the evaluation domain in a Groth16 proof generation.
+const GROTH16_NUM_FFTS: usize = 7;
+
+/// The number of MSMs of the size of the witness in a Groth16 proof generation.
+const GROTH16_NUM_MSMS: usize = 4;
+
+/// The size of a wire label of the garbled circuits in bytes.
+const WIRE_LABEL_BYTES: u64 = 16;
+
+/// The structure of a circuit that drives the costs in MPC.
+#[derive(Debug, Clone, Serialize)]
+pub struct CircuitProfile {
+    /// The number of constraints
+    pub num_constraints: usize,
+    /// The number of variables, i.e., the size of the witness
+    pub num_variables: usize,
+    /// The number of public inputs, including the constant 1
+    pub num_public_inputs: usize,
+    /// The number of constraints that multiply two linear combinations of variables
+    pub num_non_linear: usize,
+    /// The number of constraints that force a variable to be a bit, i.e., `x * (x - 1) = 0`
+    pub num_boolean: usize,
+    /// The fraction of the constraints that are boolean constraints
+    pub bit_density: f64,
+    /// The number of bit decompositions, i.e., the arithmetic-to-binary conversions during the witness extension
+    pub num_decompositions: usize,
+    /// The number of bit decompositions is estimated from the boolean constraints, as no linear constraint of a decomposition was found
+    pub decompositions_estimated: bool,
+    /// The size of the evaluation domain of the Groth16 proof generation
+    pub domain_size: usize,
+    /// The bit size of the scalar field
+    pub field_bits: usize,
+}
+
+/// The network between the parties, as declared by the user.
+#[derive(Debug, Clone, Serialize)]
+pub struct NetworkProfile {
+    /// The one-way latency between two parties in milliseconds
+    pub latency_ms: f64,
+    /// The bandwidth of every party in Mbit/s
+    pub bandwidth_mbps: f64,
+    /// The number of parties
+    pub parties: usize,
+}
+
+impl NetworkProfile {
+    fn time_ms(&self, rounds: u64, bytes_sent: u64) -> f64 {
+        rounds as f64 * self.latency_ms + bytes_sent as f64 * 8. / (self.bandwidth_mbps * 1000.)
+    }
+}
+
+/// The projected costs of a phase of the session for the busiest party.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProjectedCost {
+    /// The number of communication rounds
+    pub rounds: u64,
+    /// The number of bytes sent
+    pub bytes_sent: u64,
+    /// The time spent on the network in milliseconds
+    pub network_ms: f64,
+    /// The time spent on the local computation in milliseconds, if a calibration was provided
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compute_ms: Option<f64>,
+}
+
+impl ProjectedCost {
+    fn new(network: &NetworkProfile, rounds: u64, bytes_sent: u64) -> Self {
+        Self {
+            rounds,
+            bytes_sent,
+            network_ms: network.time_ms(rounds, bytes_sent),
+            compute_ms: None,
+        }
+    }
+}
+
+/// The recommended configuration of a session and its projected costs, see [recommend].
+#[derive(Debug, Clone, Serialize)]
+pub struct Recommendation {
+    /// The profile of the circuit
+    pub circuit: CircuitProfile,
+    /// The declared network
+    pub network: NetworkProfile,
+    /// The recommended MPC protocol of the proof generation
+    pub protocol: MPCProtocol,
+    /// The recommended arithmetic-to-binary conversion of the witness extension, see the `a2b_type` of the VM config
+    pub a2b_type: A2BType,
+    /// The recommended number of threads of the proof generation
+    pub threads: usize,
+    /// The projected costs of the witness extension with the recommended conversion
+    pub witness_extension: ProjectedCost,
+    /// The projected costs of the conversions with [A2BType::Direct]
+    pub a2b_direct: ProjectedCost,
+    /// The projected costs of the conversions with [A2BType::Yao]
+    pub a2b_yao: ProjectedCost,
+    /// The projected costs of the Groth16 proof generation with the recommended protocol
+    pub proof: ProjectedCost,
+    /// The reasons for the recommendations
+    pub reasons: Vec<String>,
+}
+
+/// Computes the [CircuitProfile] of an R1CS.
+pub fn profile<P: Pairing>(r1cs: &R1CS<P>) -> CircuitProfile {
+    let mut boolean_wires = HashSet::new();
+    let mut num_non_linear = 0;
+    for (a, b, c) in r1cs.constraints.iter() {
+        if a.is_empty() || b.is_empty() {
+            continue;
+        }
+        num_non_linear += 1;
+        if let Some(wire) = boolean_wire::<P::ScalarField>(a, b, c) {
+            boolean_wires.insert(wire);
+        }
+    }
+    let num_boolean = boolean_wires.len();
+
+    // a decomposition `x = sum_i 2^i b_i` is a linear constraint with power-of-two coefficients on boolean wires
+    let field_bits = P::ScalarField::MODULUS_BIT_SIZE as usize;
+    let mut powers_of_two = HashSet::new();
+    let mut power = P::ScalarField::ONE;
+    for _ in 0..field_bits {
+        powers_of_two.insert(power);
+        powers_of_two.insert(-power);
+        power.double_in_place();
+    }
+    let num_detected = r1cs
+        .constraints
+        .iter()
+        .filter(|(a, b, c)| {
+            (a.is_empty() || b.is_empty())
+                && c.iter()
+                    .filter(|(wire, coeff)| {
+                        boolean_wires.contains(wire) && powers_of_two.contains(coeff)
+                    })
+                    .count()
+                    >= 2
+        })
+        .count();
+    let decompositions_estimated = num_detected == 0 && num_boolean > 0;
+    let num_decompositions = if decompositions_estimated {
+        num_boolean.div_ceil(DEFAULT_BITS_PER_DECOMPOSITION)
+    } else {
+        num_detected
+    };
+
+    let num_constraints = r1cs.constraints.len();
+    CircuitProfile {
+        num_constraints,
+        num_variables: r1cs.num_variables,
+        num_public_inputs: r1cs.num_inputs,
+        num_non_linear,
+        num_boolean,
+        bit_density: if num_constraints == 0 {
+            0.
+        } else {
+            num_boolean as f64 / num_constraints as f64
+        },
+        num_decompositions,
+        decompositions_estimated,
+        domain_size: (num_constraints + r1cs.num_inputs).next_power_of_two(),
+        field_bits,
+    }
+}
+
+/// Returns the wire `x` if the constraint is `x * (x - 1) = 0` up to the order of the factors and scaling.
+fn boolean_wire<F: PrimeField>(
+    a: &[(usize, F)],
+    b: &[(usize, F)],
+    c: &[(usize, F)],
+) -> Option<usize> {
+    if !c.is_empty() {
+        return None;
+    }
+    let is_boolean = |single: &[(usize, F)], pair: &[(usize, F)]| match (single, pair) {
+        ([(x, _)], [(w0, c0), (w1, c1)]) if *x != 0 => {
+            let ((w, cw), c_one) = if *w0 == 0 {
+                ((w1, c1), c0)
+            } else if *w1 == 0 {
+                ((w0, c0), c1)
+            } else {
+                return None;
+            };
+            (w == x && *cw + c_one == F::zero()).then_some(*x)
+        }
+        _ => None,
+    };
+    is_boolean(a, b).or_else(|| is_boolean(b, a))
+}
+
+/// The costs of the arithmetic-to-binary conversions of all decompositions with the given conversion.
+fn a2b_cost(
+    circuit: &CircuitProfile,
+    network: &NetworkProfile,
+    a2b_type: A2BType,
+) -> ProjectedCost {
+    let bits = circuit.field_bits as u64;
+    let log_bits = u64::from(bits.next_power_of_two().trailing_zeros());
+    let (rounds, bytes) = match a2b_type {
+        // a parallel prefix adder, one bit per AND gate for the generate and propagate signals
+        A2BType::Direct => (log_bits + 2, 2 * bits * (log_bits + 1) / 8),
+        // a garbled adder modulo p with three AND gates per bit, two ciphertexts per AND gate, and the input labels
+        A2BType::Yao => (2, (6 + 2) * bits * WIRE_LABEL_BYTES),
+    };
+    let num = circuit.num_decompositions as u64;
+    ProjectedCost::new(network, num * rounds, num * bytes)
+}
+
+/// Scales a measurement of the calibration from `from` to `to` elements, assuming a complexity of `n log n`.
+fn scale_n_log_n(ms: f64, from: usize, to: usize) -> f64 {
+    let n_log_n = |n: usize| n.max(2) as f64 * (n.max(2) as f64).log2();
+    ms * n_log_n(to) / n_log_n(from)
+}
+
+/// Scales a measurement of the calibration from `from` to `to` elements, assuming a complexity of `n / log n` as for Pippenger's MSM.
+fn scale_n_div_log_n(ms: f64, from: usize, to: usize) -> f64 {
+    let n_div_log_n = |n: usize| n.max(2) as f64 / (n.max(2) as f64).log2();
+    ms * n_div_log_n(to) / n_div_log_n(from)
+}
+
+/// Recommends the configuration of a session for the circuit and the network. `available_threads` is the number of threads of the local machine, and the compute time is projected from the `calibration` if provided.
+pub fn recommend(
+    circuit: CircuitProfile,
+    network: NetworkProfile,
+    calibration: Option<&CalibrationReport>,
+    available_threads: usize,
+) -> eyre::Result<Recommendation> {
+    if network.parties < 3 {
+        eyre::bail!("at least 3 parties are required, got {}", network.parties);
+    }
+    if network.latency_ms.is_nan()
+        || network.latency_ms < 0.
+        || network.bandwidth_mbps.is_nan()
+        || network.bandwidth_mbps <= 0.
+    {
+        eyre::bail!("the latency must not be negative and the bandwidth must be positive");
+    }
+    let mut reasons = Vec::new();
+
+    let protocol = if network.parties == 3 {
+        reasons.push("REP3 is the cheapest protocol for 3 parties, and the witness extension produces REP3 shares, so no translation is needed".to_owned());
+        MPCProtocol::REP3
+    } else {
+        reasons.push(format!("REP3 only supports 3 parties, so the proof is generated with Shamir for {} parties after translating the REP3 witness share with `translate-witness`", network.parties));
+        MPCProtocol::SHAMIR
+    };
+
+    let a2b_direct = a2b_cost(&circuit, &network, A2BType::Direct);
+    let a2b_yao = a2b_cost(&circuit, &network, A2BType::Yao);
+    let (a2b_type, conversions) = if a2b_direct.network_ms < a2b_yao.network_ms {
+        reasons.push(format!("bit decomposition with A2BType::Direct needs more rounds but fewer bytes, which is faster with {} ms latency and {} Mbit/s", network.latency_ms, network.bandwidth_mbps));
+        (A2BType::Direct, &a2b_direct)
+    } else {
+        reasons.push(format!("garbled circuits with A2BType::Yao need fewer rounds but more bytes, which is faster with {} ms latency and {} Mbit/s", network.latency_ms, network.bandwidth_mbps));
+        (A2BType::Yao, &a2b_yao)
+    };
+    if circuit.decompositions_estimated {
+        reasons.push(format!("no bit decomposition was found in the linear constraints, so one decomposition per {DEFAULT_BITS_PER_DECOMPOSITION} boolean constraints is assumed"));
+    }
+
+    // every other multiplication of the witness extension is a REP3 multiplication with one round and one field element
+    let field_bytes = circuit.field_bits.div_ceil(8) as u64;
+    let num_muls = circuit.num_non_linear.saturating_sub(circuit.num_boolean) as u64;
+    let witness_extension = ProjectedCost::new(
+        &network,
+        num_muls + conversions.rounds,
+        num_muls * field_bytes + conversions.bytes_sent,
+    );
+
+    let domain_size = circuit.domain_size as u64;
+    let mut proof = match protocol {
+        // resharing the products of the evaluations of A and B
+        MPCProtocol::REP3 => {
+            ProjectedCost::new(&network, GROTH16_ROUNDS_REP3, domain_size * field_bytes)
+        }
+        // the degree reduction of the products with every other party, and the translation of the witness
+        MPCProtocol::SHAMIR => ProjectedCost::new(
+            &network,
+            GROTH16_ROUNDS_SHAMIR,
+            (network.parties as u64 - 1) * domain_size * field_bytes
+                + circuit.num_variables as u64 * field_bytes,
+        ),
+    };
+    if let Some(calibration) = calibration {
+        let fft_ms = scale_n_log_n(calibration.fft_ms, calibration.size, circuit.domain_size);
+        let msm_ms = scale_n_div_log_n(calibration.msm_ms, calibration.size, circuit.num_variables);
+        proof.compute_ms =
+            Some(GROTH16_NUM_FFTS as f64 * fft_ms + GROTH16_NUM_MSMS as f64 * msm_ms);
+    }
+
+    let threads =
+        (circuit.domain_size / MIN_ELEMENTS_PER_THREAD).clamp(1, available_threads.max(1));
+    if threads < available_threads {
+        reasons.push(format!("the evaluation domain of {} elements is too small to keep more than {threads} threads busy", circuit.domain_size));
+    }
+
+    Ok(Recommendation {
+        circuit,
+        network,
+        protocol,
+        a2b_type,
+        threads,
+        witness_extension,
+        a2b_direct,
+        a2b_yao,
+        proof,
+        reasons,
+    })
+}
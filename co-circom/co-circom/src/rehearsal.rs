@@ -0,0 +1,213 @@
+use std::{path::Path, time::Instant};
+
+use ark_ff::PrimeField;
+use co_circom_snarks::SharedWitness;
+use color_eyre::eyre::{self, Context};
+use mpc_core::protocols::{
+    rep3::{network::Rep3Network, Rep3PrimeFieldShare},
+    shamir::{network::ShamirNetwork, ShamirPrimeFieldShare},
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{MPCCurve, MPCProtocol, ProofSystem};
+
+/// The outcome of a single check of a rehearsal.
+#[derive(Debug, Clone, Serialize)]
+pub struct RehearsalCheck {
+    /// The name of the check, e.g., `network`
+    pub name: String,
+    /// Whether the check passed
+    pub passed: bool,
+    /// The time the check took in milliseconds
+    pub duration_ms: f64,
+    /// The error the check failed with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The go/no-go summary of a rehearsal. The checks run in order and the rehearsal stops at the first failed check.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RehearsalReport {
+    /// Whether all checks passed
+    pub go: bool,
+    /// The checks that were executed
+    pub checks: Vec<RehearsalCheck>,
+}
+
+impl RehearsalReport {
+    /// Runs a check and records its outcome. Returns `None` if the check failed or a previous check failed, in which case the check is not executed.
+    pub fn check<T>(&mut self, name: &str, f: impl FnOnce() -> eyre::Result<T>) -> Option<T> {
+        if self.checks.iter().any(|check| !check.passed) {
+            return None;
+        }
+        tracing::info!("rehearsal: running check {name}");
+        let start = Instant::now();
+        let result = f();
+        let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+        let (passed, error, value) = match result {
+            Ok(value) => (true, None, Some(value)),
+            Err(err) => (false, Some(format!("{err:#}")), None),
+        };
+        self.checks.push(RehearsalCheck {
+            name: name.to_owned(),
+            passed,
+            duration_ms,
+            error,
+        });
+        value
+    }
+
+    /// Finishes the rehearsal, i.e., computes the go/no-go decision and logs the summary.
+    pub fn finish(mut self) -> Self {
+        self.go = !self.checks.is_empty() && self.checks.iter().all(|check| check.passed);
+        for check in self.checks.iter() {
+            match &check.error {
+                None => tracing::info!(
+                    "rehearsal: [ok]     {} ({:.3} ms)",
+                    check.name,
+                    check.duration_ms
+                ),
+                Some(err) => tracing::error!(
+                    "rehearsal: [failed] {} ({:.3} ms): {err}",
+                    check.name,
+                    check.duration_ms
+                ),
+            }
+        }
+        if self.go {
+            tracing::info!("rehearsal: GO, all checks passed");
+        } else {
+            tracing::error!("rehearsal: NO-GO, see the failed check above");
+        }
+        self
+    }
+}
+
+/// The setup of a party that needs to match between all parties of a session. The parties exchange it during a rehearsal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RehearsalFingerprint {
+    /// The version of co-circom
+    pub version: String,
+    /// The proof system
+    pub proof_system: String,
+    /// The MPC protocol
+    pub protocol: String,
+    /// The pairing friendly curve
+    pub curve: String,
+    /// The threshold of tolerated colluding parties
+    pub threshold: usize,
+    /// The lowercase hex encoded SHA-256 hash of the zkey file
+    pub zkey_hash: String,
+}
+
+impl RehearsalFingerprint {
+    /// Computes the fingerprint of this party, which includes the hash of the zkey file.
+    pub fn new(
+        proof_system: &ProofSystem,
+        protocol: MPCProtocol,
+        curve: MPCCurve,
+        threshold: usize,
+        zkey: &Path,
+    ) -> eyre::Result<Self> {
+        let mut zkey_file = crate::storage::open(zkey).context("while opening zkey")?;
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut zkey_file, &mut hasher).context("while hashing zkey")?;
+        Ok(Self {
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            proof_system: proof_system.to_string(),
+            protocol: protocol.to_string(),
+            curve: curve.to_string(),
+            threshold,
+            zkey_hash: hasher
+                .finalize()
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect(),
+        })
+    }
+
+    /// Returns an error listing the fields in which the fingerprint of the provided party differs from ours.
+    fn compare(&self, party: usize, other: &[u8]) -> eyre::Result<()> {
+        let other: Self = serde_json::from_slice(other)
+            .with_context(|| format!("while parsing the fingerprint of party {party}"))?;
+        let mismatches = [
+            ("version", &self.version, &other.version),
+            ("proof system", &self.proof_system, &other.proof_system),
+            ("protocol", &self.protocol, &other.protocol),
+            ("curve", &self.curve, &other.curve),
+            ("zkey hash", &self.zkey_hash, &other.zkey_hash),
+        ]
+        .into_iter()
+        .filter(|(_, ours, theirs)| ours != theirs)
+        .map(|(field, ours, theirs)| format!("{field} {theirs} != {ours}"))
+        .chain(
+            (self.threshold != other.threshold)
+                .then(|| format!("threshold {} != {}", other.threshold, self.threshold)),
+        )
+        .collect::<Vec<_>>();
+        if !mismatches.is_empty() {
+            eyre::bail!(
+                "party {party} has a different setup: {}",
+                mismatches.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    fn to_bytes(&self) -> eyre::Result<Vec<u8>> {
+        serde_json::to_vec(self).context("while serializing fingerprint")
+    }
+
+    /// Exchanges the fingerprints with the other two REP3 parties and fails if they differ.
+    pub fn compare_rep3<N: Rep3Network>(&self, network: &mut N) -> eyre::Result<()> {
+        let id = usize::from(network.get_id());
+        let (prev, next) = network.broadcast(self.to_bytes()?)?;
+        self.compare((id + 2) % 3, &prev)?;
+        self.compare((id + 1) % 3, &next)
+    }
+
+    /// Exchanges the fingerprints with all other Shamir parties and fails if they differ.
+    pub fn compare_shamir<N: ShamirNetwork>(&self, network: &mut N) -> eyre::Result<()> {
+        let id = network.get_id();
+        let fingerprints = network.broadcast(self.to_bytes()?)?;
+        for (party, fingerprint) in fingerprints.iter().enumerate() {
+            if party != id {
+                self.compare(party, fingerprint)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn zero_public_inputs<F: PrimeField>(num_public: usize) -> Vec<F> {
+    let mut public_inputs = vec![F::zero(); num_public];
+    if let Some(one) = public_inputs.first_mut() {
+        *one = F::one();
+    }
+    public_inputs
+}
+
+/// Returns this party's REP3 share of the witness that is zero except for the constant 1. Every party can compute its share locally, as all components of the shares of zero are zero.
+///
+/// The witness does not satisfy the circuit in general, so the resulting proof is not valid. It is only used to rehearse the proof generation.
+pub fn zero_witness_rep3<F: PrimeField>(
+    num_public: usize,
+    num_private: usize,
+) -> SharedWitness<F, Rep3PrimeFieldShare<F>> {
+    SharedWitness {
+        public_inputs: zero_public_inputs(num_public),
+        witness: vec![Rep3PrimeFieldShare::zero_share(); num_private],
+    }
+}
+
+/// Returns this party's Shamir share of the witness that is zero except for the constant 1, see [zero_witness_rep3].
+pub fn zero_witness_shamir<F: PrimeField>(
+    num_public: usize,
+    num_private: usize,
+) -> SharedWitness<F, ShamirPrimeFieldShare<F>> {
+    SharedWitness {
+        public_inputs: zero_public_inputs(num_public),
+        witness: vec![ShamirPrimeFieldShare::zero_share(); num_private],
+    }
+}
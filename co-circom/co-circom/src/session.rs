@@ -0,0 +1,378 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// The size of the chunks that are written when overwriting a file before deletion.
+const WIPE_CHUNK_SIZE: usize = 1 << 16;
+
+/// The kinds of artifacts a session leaves behind. Every kind has its own subdirectory in the session directory, see [Session].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ArtifactKind {
+    /// Input and witness shares
+    Share,
+    /// Protocol transcripts, e.g., of garbled circuits
+    Transcript,
+    /// Checkpoints of interrupted computations
+    Checkpoint,
+    /// Proofs and public inputs
+    Proof,
+    /// Metadata of the proof generation
+    Metadata,
+}
+
+impl ArtifactKind {
+    /// All kinds of artifacts.
+    pub const ALL: [ArtifactKind; 5] = [
+        ArtifactKind::Share,
+        ArtifactKind::Transcript,
+        ArtifactKind::Checkpoint,
+        ArtifactKind::Proof,
+        ArtifactKind::Metadata,
+    ];
+
+    /// Returns the name of the subdirectory of the session directory holding this kind of artifacts.
+    pub fn dir_name(&self) -> &'static str {
+        match self {
+            ArtifactKind::Share => "shares",
+            ArtifactKind::Transcript => "transcripts",
+            ArtifactKind::Checkpoint => "checkpoints",
+            ArtifactKind::Proof => "proofs",
+            ArtifactKind::Metadata => "metadata",
+        }
+    }
+
+    /// Returns `true` if this kind of artifacts may contain secret shares and is therefore overwritten before deletion.
+    pub fn is_secret(&self) -> bool {
+        matches!(self, ArtifactKind::Share | ArtifactKind::Checkpoint)
+    }
+}
+
+/// A session directory. All artifacts of a session are stored in the subdirectories of the session directory, one per [ArtifactKind], e.g., `<workspace>/<session>/shares`. Files outside of these subdirectories are never touched by the cleanup.
+#[derive(Debug, Clone)]
+pub struct Session {
+    root: PathBuf,
+}
+
+impl Session {
+    /// Returns the session with the provided id in the workspace directory.
+    pub fn new(workspace: &Path, id: &str) -> Self {
+        Self {
+            root: workspace.join(id),
+        }
+    }
+
+    /// Returns the session located at the provided directory.
+    pub fn open(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Creates the session directory and the subdirectories of all kinds of artifacts.
+    pub fn create(&self) -> io::Result<()> {
+        for kind in ArtifactKind::ALL {
+            fs::create_dir_all(self.dir(kind))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the session directory.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the subdirectory of the provided kind of artifacts.
+    pub fn dir(&self, kind: ArtifactKind) -> PathBuf {
+        self.root.join(kind.dir_name())
+    }
+}
+
+/// Defines which artifacts are kept by the cleanup of a session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionPolicy {
+    /// The kinds of artifacts that are kept
+    pub keep: Vec<ArtifactKind>,
+    /// Overwrite files that may contain secret shares before deleting them
+    pub secure_delete: bool,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            keep: vec![ArtifactKind::Proof, ArtifactKind::Metadata],
+            secure_delete: true,
+        }
+    }
+}
+
+/// Statistics about a cleanup.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CleanupReport {
+    /// The amount of cleaned up sessions
+    pub sessions: usize,
+    /// The amount of deleted files
+    pub removed_files: usize,
+    /// The total size of the deleted files in bytes
+    pub removed_bytes: u64,
+    /// The amount of files that were overwritten before deletion
+    pub wiped_files: usize,
+}
+
+/// Deletes the artifacts of the session that are not kept by the retention policy. Empty artifact directories and an empty session directory are removed as well.
+///
+/// The secure deletion overwrites the files with zeros before deleting them. Note that this does not guarantee that the data is unrecoverable on file systems or storage devices that do not write in place, e.g., copy-on-write file systems and SSDs.
+pub fn cleanup_session(session: &Session, policy: &RetentionPolicy) -> io::Result<CleanupReport> {
+    let mut report = CleanupReport {
+        sessions: 1,
+        ..Default::default()
+    };
+    for kind in ArtifactKind::ALL {
+        let dir = session.dir(kind);
+        if policy.keep.contains(&kind) {
+            continue;
+        }
+        let Ok(metadata) = fs::symlink_metadata(&dir) else {
+            continue;
+        };
+        if metadata.is_symlink() {
+            // the link is removed without following it
+            fs::remove_file(&dir)?;
+            report.removed_files += 1;
+            continue;
+        }
+        if !metadata.is_dir() {
+            continue;
+        }
+        let wipe = policy.secure_delete && kind.is_secret();
+        remove_dir(&dir, wipe, &mut report)?;
+        tracing::debug!("removed {kind:?} artifacts in {}", dir.display());
+    }
+    // only removes the session directory if it is empty
+    if fs::read_dir(session.root())?.next().is_none() {
+        fs::remove_dir(session.root())?;
+    }
+    Ok(report)
+}
+
+/// Cleans up all sessions in the workspace directory with [cleanup_session]. If `older_than` is set, only sessions that were not modified within this duration are cleaned up.
+pub fn cleanup_workspace(
+    workspace: &Path,
+    policy: &RetentionPolicy,
+    older_than: Option<Duration>,
+) -> io::Result<CleanupReport> {
+    let mut report = CleanupReport::default();
+    let now = SystemTime::now();
+    for entry in fs::read_dir(workspace)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        if let Some(older_than) = older_than {
+            let modified = entry.metadata()?.modified()?;
+            // sessions with a modification time in the future are considered recent
+            if now.duration_since(modified).unwrap_or_default() < older_than {
+                continue;
+            }
+        }
+        let session_report = cleanup_session(&Session::open(entry.path()), policy)?;
+        report.sessions += session_report.sessions;
+        report.removed_files += session_report.removed_files;
+        report.removed_bytes += session_report.removed_bytes;
+        report.wiped_files += session_report.wiped_files;
+    }
+    Ok(report)
+}
+
+fn remove_dir(dir: &Path, wipe: bool, report: &mut CleanupReport) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            remove_dir(&entry.path(), wipe, report)?;
+        } else {
+            let len = entry.metadata()?.len();
+            // symlinks are removed without following them
+            if wipe && file_type.is_file() {
+                wipe_file(&entry.path(), len)?;
+                report.wiped_files += 1;
+            }
+            fs::remove_file(entry.path())?;
+            report.removed_files += 1;
+            report.removed_bytes += len;
+        }
+    }
+    fs::remove_dir(dir)
+}
+
+fn wipe_file(path: &Path, len: u64) -> io::Result<()> {
+    let mut file = OpenOptions::new().write(true).open(path)?;
+    let zeros = vec![0u8; WIPE_CHUNK_SIZE];
+    let mut remaining = len;
+    while remaining > 0 {
+        let chunk = remaining.min(WIPE_CHUNK_SIZE as u64) as usize;
+        file.write_all(&zeros[..chunk])?;
+        remaining -= chunk as u64;
+    }
+    file.sync_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, content: &[u8]) {
+        fs::write(path, content).unwrap();
+    }
+
+    fn session_with_artifacts(workspace: &Path, id: &str) -> Session {
+        let session = Session::new(workspace, id);
+        session.create().unwrap();
+        for kind in ArtifactKind::ALL {
+            write(&session.dir(kind).join("artifact"), b"artifact");
+        }
+        fs::create_dir(session.dir(ArtifactKind::Share).join("nested")).unwrap();
+        write(
+            &session
+                .dir(ArtifactKind::Share)
+                .join("nested")
+                .join("share"),
+            b"secret share",
+        );
+        session
+    }
+
+    #[test]
+    fn removes_only_artifacts_that_are_not_kept() {
+        let workspace = tempfile::tempdir().unwrap();
+        let session = session_with_artifacts(workspace.path(), "session");
+        write(&session.root().join("notes.txt"), b"not an artifact");
+        write(&workspace.path().join("other.txt"), b"not a session");
+
+        let report = cleanup_session(&session, &RetentionPolicy::default()).unwrap();
+        assert!(!session.dir(ArtifactKind::Share).exists());
+        assert!(!session.dir(ArtifactKind::Transcript).exists());
+        assert!(!session.dir(ArtifactKind::Checkpoint).exists());
+        assert!(session.dir(ArtifactKind::Proof).join("artifact").exists());
+        assert!(session
+            .dir(ArtifactKind::Metadata)
+            .join("artifact")
+            .exists());
+        assert!(session.root().join("notes.txt").exists());
+        assert!(workspace.path().join("other.txt").exists());
+        assert_eq!(report.sessions, 1);
+        assert_eq!(report.removed_files, 4);
+        assert_eq!(report.removed_bytes, 3 * 8 + 12);
+        assert_eq!(report.wiped_files, 3);
+    }
+
+    #[test]
+    fn removes_empty_session_directory() {
+        let workspace = tempfile::tempdir().unwrap();
+        let session = session_with_artifacts(workspace.path(), "session");
+        let policy = RetentionPolicy {
+            keep: vec![],
+            secure_delete: false,
+        };
+        let report = cleanup_session(&session, &policy).unwrap();
+        assert!(!session.root().exists());
+        assert_eq!(report.removed_files, 6);
+        assert_eq!(report.wiped_files, 0);
+    }
+
+    #[test]
+    fn wiped_files_are_overwritten() {
+        let workspace = tempfile::tempdir().unwrap();
+        let session = session_with_artifacts(workspace.path(), "session");
+        let share = vec![0xab; 3 * WIPE_CHUNK_SIZE / 2];
+        let share_path = session.dir(ArtifactKind::Share).join("large");
+        write(&share_path, &share);
+        // the hard links outlive the deletion and show what was written to the files
+        let wiped = workspace.path().join("wiped");
+        let kept = workspace.path().join("kept");
+        fs::hard_link(&share_path, &wiped).unwrap();
+        fs::hard_link(
+            session.dir(ArtifactKind::Transcript).join("artifact"),
+            &kept,
+        )
+        .unwrap();
+
+        cleanup_session(&session, &RetentionPolicy::default()).unwrap();
+        assert_eq!(fs::read(&wiped).unwrap(), vec![0; share.len()]);
+        // transcripts do not contain secret shares and are not overwritten
+        assert_eq!(fs::read(&kept).unwrap(), b"artifact");
+    }
+
+    #[test]
+    fn files_are_not_wiped_without_secure_delete() {
+        let workspace = tempfile::tempdir().unwrap();
+        let session = session_with_artifacts(workspace.path(), "session");
+        let link = workspace.path().join("link");
+        fs::hard_link(session.dir(ArtifactKind::Share).join("artifact"), &link).unwrap();
+        let policy = RetentionPolicy {
+            secure_delete: false,
+            ..Default::default()
+        };
+        let report = cleanup_session(&session, &policy).unwrap();
+        assert_eq!(report.wiped_files, 0);
+        assert_eq!(fs::read(&link).unwrap(), b"artifact");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_are_not_followed() {
+        use std::os::unix::fs::symlink;
+
+        let workspace = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        let secret = outside.path().join("secret");
+        write(&secret, b"outside");
+        let session = session_with_artifacts(workspace.path(), "session");
+        // links to a file and to a directory in an artifact directory, and an artifact directory that is a link
+        symlink(&secret, session.dir(ArtifactKind::Share).join("file_link")).unwrap();
+        symlink(
+            outside.path(),
+            session.dir(ArtifactKind::Share).join("dir_link"),
+        )
+        .unwrap();
+        fs::remove_dir_all(session.dir(ArtifactKind::Checkpoint)).unwrap();
+        symlink(outside.path(), session.dir(ArtifactKind::Checkpoint)).unwrap();
+        // a session that is a link to a directory outside of the workspace
+        symlink(outside.path(), workspace.path().join("linked_session")).unwrap();
+
+        cleanup_workspace(workspace.path(), &RetentionPolicy::default(), None).unwrap();
+        assert_eq!(fs::read(&secret).unwrap(), b"outside");
+        assert_eq!(fs::read_dir(outside.path()).unwrap().count(), 1);
+        assert!(!session.dir(ArtifactKind::Share).exists());
+        assert!(fs::symlink_metadata(session.dir(ArtifactKind::Checkpoint)).is_err());
+        assert!(workspace.path().join("linked_session").exists());
+    }
+
+    #[test]
+    fn workspace_cleanup_skips_recent_sessions() {
+        let workspace = tempfile::tempdir().unwrap();
+        let sessions = ["a", "b"].map(|id| session_with_artifacts(workspace.path(), id));
+        write(&workspace.path().join("file"), b"not a session");
+
+        let policy = RetentionPolicy::default();
+        let report =
+            cleanup_workspace(workspace.path(), &policy, Some(Duration::from_secs(3600))).unwrap();
+        assert_eq!(report.sessions, 0);
+        assert!(sessions
+            .iter()
+            .all(|session| session.dir(ArtifactKind::Share).exists()));
+
+        let report = cleanup_workspace(workspace.path(), &policy, None).unwrap();
+        assert_eq!(report.sessions, 2);
+        assert_eq!(report.removed_files, 8);
+        assert!(sessions
+            .iter()
+            .all(|session| !session.dir(ArtifactKind::Share).exists()));
+        assert!(workspace.path().join("file").exists());
+    }
+}
@@ -0,0 +1,671 @@
+//! Signatures of the parties on a proof.
+//!
+//! Every party can sign the proof, the public inputs, and the metadata of a proof generation with its long-term signing key, e.g., to provide non-repudiation about who participated in producing the proof. The parties compute the signatures locally after the proof generation, so signing does not need further communication.
+//!
+//! The signatures are Ed25519 signatures ([RFC 8032](https://www.rfc-editor.org/rfc/rfc8032)) computed with the `ed25519-dalek` crate, so the keys do not depend on the curve of the proof. The signed message is the digest of the [ProofStatement]. A [Committee] descriptor lists the public keys of the parties that are expected to sign, and [Committee::verify] checks that every member signed the statement.
+//!
+//! Groth16 proofs are malleable, i.e., anyone can re-randomize a valid proof into a different valid proof of the same statement. With `generate-proof --bind-proof`, the metadata contains the [groth16_proof_binding] of the proof elements produced by the session, so the signatures cover these exact elements independently of how the proof is encoded, and [check_proof_binding] detects a re-randomized proof.
+//!
+//! The same keys serve as the long-term identities of the parties before a session starts. In the proof-of-possession handshake ([prove_possession_rep3] and [prove_possession_shamir]), every party signs a fresh session id, and the other parties check the signature against the public key of the committee member with the id of the party. This catches configuration mistakes where the address of a party in the network config points at a different party.
+
+use std::collections::BTreeSet;
+
+use ark_ec::pairing::Pairing;
+use ark_serialize::CanonicalSerialize;
+use color_eyre::eyre::{self, Context, ContextCompat};
+use ed25519_dalek::{Signer, SigningKey, VerifyingKey, SECRET_KEY_LENGTH};
+use mpc_core::protocols::{rep3::network::Rep3Network, shamir::network::ShamirNetwork};
+use rand::{CryptoRng, Rng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+
+/// The domain separator of the digest of a [ProofStatement].
+const STATEMENT_DOMAIN: &[u8] = b"co-circom proof statement v1";
+/// The domain separator of the session ids of the proof-of-possession handshake.
+const SESSION_DOMAIN: &[u8] = b"co-circom session id";
+/// The domain separator of the messages signed in the proof-of-possession handshake.
+const POSSESSION_DOMAIN: &[u8] = b"co-circom proof of possession";
+/// The domain separator of the binding of the Groth16 proof elements.
+const PROOF_BINDING_DOMAIN: &[u8] = b"co-circom groth16 proof binding";
+/// The key of the [groth16_proof_binding] in the metadata of a proof generation.
+pub const PROOF_BINDING_KEY: &str = "proof_binding";
+/// The length of the random contribution of every party to the session id.
+const SESSION_NONCE_LEN: usize = 32;
+
+/// The long-term Ed25519 signing key of a party.
+#[derive(Debug, Clone)]
+pub struct SigningKeyPair(SigningKey);
+
+impl SigningKeyPair {
+    /// Generates a new random key pair.
+    pub fn generate<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        Self(SigningKey::generate(rng))
+    }
+
+    /// Reads a key pair from the 32 bytes of its secret key, as written by [SigningKeyPair::to_bytes].
+    pub fn from_bytes(bytes: &[u8]) -> eyre::Result<Self> {
+        let secret_key: &[u8; SECRET_KEY_LENGTH] = bytes.try_into().map_err(|_| {
+            eyre::eyre!(
+                "a signing key has {SECRET_KEY_LENGTH} bytes, but got {}",
+                bytes.len()
+            )
+        })?;
+        Ok(Self(SigningKey::from_bytes(secret_key)))
+    }
+
+    /// Returns the secret key. Keep it secret.
+    pub fn to_bytes(&self) -> [u8; SECRET_KEY_LENGTH] {
+        self.0.to_bytes()
+    }
+
+    /// Returns the hex encoded public key, as listed in a [Committee].
+    pub fn public_key(&self) -> String {
+        to_hex(self.0.verifying_key().as_bytes())
+    }
+
+    /// Signs the message. Ed25519 signatures are deterministic, so signing the same message twice results in the same signature.
+    pub fn sign(&self, msg: &[u8]) -> Vec<u8> {
+        self.0.sign(msg).to_bytes().to_vec()
+    }
+}
+
+/// Decodes a hex encoded public key, see [SigningKeyPair::public_key]. Fails for encodings that are not a point on the curve and for weak keys of small order.
+pub fn parse_public_key(hex: &str) -> eyre::Result<VerifyingKey> {
+    let bytes: [u8; 32] = from_hex(hex)?.try_into().map_err(|bytes: Vec<u8>| {
+        eyre::eyre!("a public key has 32 bytes, but got {}", bytes.len())
+    })?;
+    let public_key = VerifyingKey::from_bytes(&bytes).context("while decoding public key")?;
+    if public_key.is_weak() {
+        eyre::bail!("the public key has small order");
+    }
+    Ok(public_key)
+}
+
+/// Verifies the signature on the message against the public key. Uses the strict verification of `ed25519-dalek`, which rejects malleable signatures.
+pub fn verify(public_key: &VerifyingKey, msg: &[u8], signature: &[u8]) -> bool {
+    ed25519_dalek::Signature::from_slice(signature)
+        .is_ok_and(|signature| public_key.verify_strict(msg, &signature).is_ok())
+}
+
+/// Encodes bytes as lowercase hex.
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Decodes bytes encoded with [to_hex].
+pub fn from_hex(hex: &str) -> eyre::Result<Vec<u8>> {
+    if !hex.is_ascii() {
+        eyre::bail!("hex string contains non-ASCII characters");
+    }
+    if hex.len() % 2 != 0 {
+        eyre::bail!("hex string has an odd length");
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+        .collect::<Result<Vec<_>, _>>()
+        .context("while decoding hex string")
+}
+
+/// The statement the parties sign after a proof generation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofStatement {
+    /// The proof as written to the proof file
+    pub proof: serde_json::Value,
+    /// The public inputs as written to the public input file, i.e., without the constant 1
+    pub public_inputs: Vec<String>,
+    /// The metadata of the proof generation as written to the metadata file
+    pub metadata: serde_json::Value,
+}
+
+impl ProofStatement {
+    /// Returns the signed message, i.e., a digest of the statement.
+    pub fn digest(&self) -> eyre::Result<Vec<u8>> {
+        let mut hasher = Sha512::new();
+        hasher.update(STATEMENT_DOMAIN);
+        for part in [
+            serde_json::to_vec(&self.proof)?,
+            serde_json::to_vec(&self.public_inputs)?,
+            serde_json::to_vec(&self.metadata)?,
+        ] {
+            hasher.update((part.len() as u64).to_le_bytes());
+            hasher.update(&part);
+        }
+        Ok(hasher.finalize().to_vec())
+    }
+}
+
+/// The signature file written by a party, see [SignedStatement::sign].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedStatement {
+    /// The hex encoded public key of the signer
+    pub public_key: String,
+    /// The signed metadata of the proof generation. The proof and the public inputs are read from their files by the verifier.
+    pub metadata: serde_json::Value,
+    /// The hex encoded signature
+    pub signature: String,
+}
+
+impl SignedStatement {
+    /// Signs the statement with the provided key.
+    pub fn sign(statement: &ProofStatement, key: &SigningKeyPair) -> eyre::Result<Self> {
+        let signature = key.sign(&statement.digest()?);
+        Ok(Self {
+            public_key: key.public_key(),
+            metadata: statement.metadata.clone(),
+            signature: to_hex(&signature),
+        })
+    }
+}
+
+/// Returns the binding of the Groth16 proof elements `a`, `b`, and `c`, i.e., the hex encoded digest of their compressed serialization, see the [module documentation](self).
+pub fn groth16_proof_binding<P: Pairing>(
+    a: &P::G1Affine,
+    b: &P::G2Affine,
+    c: &P::G1Affine,
+) -> eyre::Result<String> {
+    let mut bytes = Vec::new();
+    a.serialize_compressed(&mut bytes)?;
+    b.serialize_compressed(&mut bytes)?;
+    c.serialize_compressed(&mut bytes)?;
+    let mut hasher = Sha512::new();
+    hasher.update(PROOF_BINDING_DOMAIN);
+    hasher.update(&bytes);
+    Ok(to_hex(&hasher.finalize()))
+}
+
+/// Checks that the signed metadata binds the proof elements with the provided `binding`, see [groth16_proof_binding]. Returns false if the proof elements differ from the ones produced by the session, e.g., because the proof was re-randomized. Fails if a signature does not contain a binding.
+pub fn check_proof_binding(signatures: &[SignedStatement], binding: &str) -> eyre::Result<bool> {
+    let mut bound = true;
+    for signature in signatures {
+        let signed = signature
+            .metadata
+            .get(PROOF_BINDING_KEY)
+            .and_then(serde_json::Value::as_str)
+            .with_context(|| {
+                format!(
+                    "the signature of {} does not bind the proof elements, see `generate-proof --bind-proof`",
+                    signature.public_key
+                )
+            })?;
+        bound &= signed == binding;
+    }
+    Ok(bound)
+}
+
+/// A member of a [Committee].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitteeMember {
+    /// The name of the party, only used for reporting
+    pub name: String,
+    /// The hex encoded public signing key of the party
+    pub public_key: String,
+}
+
+/// The JSON descriptor of the parties that are expected to sign a proof, e.g., `{"members": [{"name": "party0", "public_key": "..."}]}`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Committee {
+    /// The members of the committee
+    pub members: Vec<CommitteeMember>,
+}
+
+/// The result of [Committee::verify].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CommitteeVerification {
+    /// The names of the members with a valid signature
+    pub signed: Vec<String>,
+    /// The names of the members without a valid signature
+    pub missing: Vec<String>,
+    /// The hex encoded public keys of the signatures that do not belong to the committee
+    pub unknown: Vec<String>,
+    /// The hex encoded public keys of the signatures that are invalid
+    pub invalid: Vec<String>,
+}
+
+impl CommitteeVerification {
+    /// Returns whether every member signed and all signatures are valid signatures of members.
+    pub fn is_valid(&self) -> bool {
+        self.missing.is_empty() && self.unknown.is_empty() && self.invalid.is_empty()
+    }
+}
+
+impl Committee {
+    /// Checks the signatures on the proof and the public inputs against the committee. All signatures need to sign the same metadata.
+    pub fn verify(
+        &self,
+        proof: serde_json::Value,
+        public_inputs: Vec<String>,
+        signatures: &[SignedStatement],
+    ) -> eyre::Result<CommitteeVerification> {
+        if self.members.is_empty() {
+            eyre::bail!("the committee does not have any members");
+        }
+        let metadata = signatures
+            .first()
+            .map(|signature| signature.metadata.clone())
+            .unwrap_or_default();
+        if signatures
+            .iter()
+            .any(|signature| signature.metadata != metadata)
+        {
+            eyre::bail!("the signatures cover different metadata");
+        }
+        let msg = ProofStatement {
+            proof,
+            public_inputs,
+            metadata,
+        }
+        .digest()?;
+
+        let members = self
+            .members
+            .iter()
+            .map(|member| {
+                parse_public_key(&member.public_key)
+                    .with_context(|| format!("invalid public key of member {}", member.name))
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+        let mut result = CommitteeVerification::default();
+        let mut signed = BTreeSet::new();
+        for signature in signatures {
+            let Ok(public_key) = parse_public_key(&signature.public_key) else {
+                result.invalid.push(signature.public_key.clone());
+                continue;
+            };
+            let Some(member) = members.iter().position(|key| *key == public_key) else {
+                result.unknown.push(signature.public_key.clone());
+                continue;
+            };
+            let valid =
+                from_hex(&signature.signature).is_ok_and(|sig| verify(&public_key, &msg, &sig));
+            if valid {
+                signed.insert(member);
+            } else {
+                result.invalid.push(signature.public_key.clone());
+            }
+        }
+        for (i, member) in self.members.iter().enumerate() {
+            if signed.contains(&i) {
+                result.signed.push(member.name.clone());
+            } else {
+                result.missing.push(member.name.clone());
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl Committee {
+    /// Returns the member of the party with the provided id. For the proof-of-possession handshake, the members need to be listed in the order of the party ids.
+    pub fn member(&self, party_id: usize) -> eyre::Result<&CommitteeMember> {
+        self.members.get(party_id).with_context(|| {
+            format!(
+                "the committee has {} members, but there is a party with id {party_id}",
+                self.members.len()
+            )
+        })
+    }
+
+    /// Checks that the `signature` of the party with the provided id on the session id verifies under the public key of its committee member.
+    pub fn check_possession(
+        &self,
+        party_id: usize,
+        session_id: &[u8],
+        signature: &[u8],
+    ) -> eyre::Result<()> {
+        let member = self.member(party_id)?;
+        let public_key = parse_public_key(&member.public_key)
+            .with_context(|| format!("invalid public key of member {}", member.name))?;
+        if !verify(
+            &public_key,
+            &possession_message(session_id, party_id),
+            signature,
+        ) {
+            eyre::bail!(
+                "party {party_id} could not prove possession of the signing key of committee member {}, check that the address of party {party_id} in the network config points at {}",
+                member.name,
+                member.name
+            );
+        }
+        Ok(())
+    }
+}
+
+fn possession_message(session_id: &[u8], party_id: usize) -> Vec<u8> {
+    let mut msg = POSSESSION_DOMAIN.to_vec();
+    msg.extend_from_slice(&(party_id as u64).to_le_bytes());
+    msg.extend_from_slice(session_id);
+    msg
+}
+
+/// Derives the session id from the random contributions of all parties, in the order of the party ids.
+fn session_id(nonces: &[Vec<u8>]) -> eyre::Result<Vec<u8>> {
+    let mut hasher = Sha512::new().chain_update(SESSION_DOMAIN);
+    for (party, nonce) in nonces.iter().enumerate() {
+        if nonce.len() != SESSION_NONCE_LEN {
+            eyre::bail!(
+                "party {party} contributed {} bytes to the session id, expected {SESSION_NONCE_LEN}",
+                nonce.len()
+            );
+        }
+        hasher.update(nonce);
+    }
+    Ok(hasher.finalize().to_vec())
+}
+
+/// Checks that our own key belongs to our committee member and signs the session id.
+fn sign_possession(
+    committee: &Committee,
+    key: &SigningKeyPair,
+    party_id: usize,
+    session_id: &[u8],
+) -> eyre::Result<Vec<u8>> {
+    let member = committee.member(party_id)?;
+    if member.public_key != key.public_key() {
+        eyre::bail!(
+            "our signing key does not belong to committee member {}, but we are party {party_id}",
+            member.name
+        );
+    }
+    Ok(key.sign(&possession_message(session_id, party_id)))
+}
+
+/// Runs the proof-of-possession handshake with the other two REP3 parties before a session starts. Every party contributes to a fresh session id and signs it with its long-term signing key, and the signatures of the other parties are checked against the committee. Returns the session id.
+pub fn prove_possession_rep3<N: Rep3Network>(
+    committee: &Committee,
+    key: &SigningKeyPair,
+    network: &mut N,
+) -> eyre::Result<Vec<u8>> {
+    if committee.members.len() != 3 {
+        eyre::bail!(
+            "a REP3 committee needs 3 members, but it has {}",
+            committee.members.len()
+        );
+    }
+    let id = usize::from(network.get_id());
+    let (prev_id, next_id) = ((id + 2) % 3, (id + 1) % 3);
+    let nonce = rand::thread_rng().gen::<[u8; SESSION_NONCE_LEN]>().to_vec();
+    let (prev, next) = network
+        .broadcast(nonce.clone())
+        .context("while exchanging the session id")?;
+    let mut nonces = vec![Vec::new(); 3];
+    nonces[id] = nonce;
+    nonces[prev_id] = prev;
+    nonces[next_id] = next;
+    let session_id = session_id(&nonces)?;
+
+    let signature = sign_possession(committee, key, id, &session_id)?;
+    let (prev, next) = network
+        .broadcast(signature)
+        .context("while exchanging the proofs of possession")?;
+    committee.check_possession(prev_id, &session_id, &prev)?;
+    committee.check_possession(next_id, &session_id, &next)?;
+    Ok(session_id)
+}
+
+/// Runs the proof-of-possession handshake with all other Shamir parties before a session starts, see [prove_possession_rep3]. Returns the session id.
+pub fn prove_possession_shamir<N: ShamirNetwork>(
+    committee: &Committee,
+    key: &SigningKeyPair,
+    network: &mut N,
+) -> eyre::Result<Vec<u8>> {
+    if committee.members.len() != network.get_num_parties() {
+        eyre::bail!(
+            "the committee has {} members, but there are {} parties",
+            committee.members.len(),
+            network.get_num_parties()
+        );
+    }
+    let id = network.get_id();
+    let nonce = rand::thread_rng().gen::<[u8; SESSION_NONCE_LEN]>().to_vec();
+    let nonces = network
+        .broadcast(nonce)
+        .context("while exchanging the session id")?;
+    let session_id = session_id(&nonces)?;
+
+    let signature = sign_possession(committee, key, id, &session_id)?;
+    let signatures = network
+        .broadcast(signature)
+        .context("while exchanging the proofs of possession")?;
+    for (party, signature) in signatures.iter().enumerate() {
+        if party != id {
+            committee.check_possession(party, &session_id, signature)?;
+        }
+    }
+    Ok(session_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use ark_bn254::Bn254;
+    use ark_ec::CurveGroup;
+    use ark_ff::{Field, UniformRand};
+    use circom_types::groth16::{Groth16Proof, JsonVerificationKey};
+    use co_groth16::Groth16;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+    use serde_json::json;
+
+    use super::*;
+
+    fn keys(n: usize) -> Vec<SigningKeyPair> {
+        let mut rng = ChaCha12Rng::seed_from_u64(42);
+        (0..n).map(|_| SigningKeyPair::generate(&mut rng)).collect()
+    }
+
+    fn committee(keys: &[SigningKeyPair]) -> Committee {
+        Committee {
+            members: keys
+                .iter()
+                .enumerate()
+                .map(|(i, key)| CommitteeMember {
+                    name: format!("party{i}"),
+                    public_key: key.public_key(),
+                })
+                .collect(),
+        }
+    }
+
+    fn statement() -> ProofStatement {
+        ProofStatement {
+            proof: json!({"pi_a": ["1", "2", "1"], "protocol": "groth16"}),
+            public_inputs: vec!["42".to_owned()],
+            metadata: json!({"session": "test"}),
+        }
+    }
+
+    #[test]
+    fn sign_and_verify() {
+        let [key] = keys(1).try_into().unwrap();
+        let public_key = parse_public_key(&key.public_key()).unwrap();
+        let signature = key.sign(b"message");
+        assert!(verify(&public_key, b"message", &signature));
+        assert_eq!(signature, key.sign(b"message"));
+    }
+
+    #[test]
+    fn tampered_signatures_are_rejected() {
+        let [key, other] = keys(2).try_into().unwrap();
+        let public_key = parse_public_key(&key.public_key()).unwrap();
+        let signature = key.sign(b"message");
+        assert!(!verify(&public_key, b"massage", &signature));
+        for i in [0, 31, 32, 63] {
+            let mut tampered = signature.clone();
+            tampered[i] ^= 1;
+            assert!(!verify(&public_key, b"message", &tampered));
+        }
+        assert!(!verify(&public_key, b"message", &signature[..63]));
+        let other_key = parse_public_key(&other.public_key()).unwrap();
+        assert!(!verify(&other_key, b"message", &signature));
+    }
+
+    #[test]
+    fn key_roundtrip() {
+        let [key] = keys(1).try_into().unwrap();
+        let read = SigningKeyPair::from_bytes(&key.to_bytes()).unwrap();
+        assert_eq!(read.public_key(), key.public_key());
+        assert!(SigningKeyPair::from_bytes(&key.to_bytes()[1..]).is_err());
+    }
+
+    #[test]
+    fn invalid_public_keys_are_rejected() {
+        let [key] = keys(1).try_into().unwrap();
+        let public_key = key.public_key();
+        assert!(parse_public_key(&public_key[2..]).is_err());
+        assert!(parse_public_key(&public_key[1..]).is_err());
+        assert!(parse_public_key(&format!("{}zz", &public_key[2..])).is_err());
+        // the identity has small order
+        let mut identity = [0; 32];
+        identity[0] = 1;
+        assert!(parse_public_key(&to_hex(&identity)).is_err());
+    }
+
+    #[test]
+    fn committee_accepts_signatures_of_all_members() {
+        let keys = keys(3);
+        let statement = statement();
+        let signatures = keys
+            .iter()
+            .map(|key| SignedStatement::sign(&statement, key).unwrap())
+            .collect::<Vec<_>>();
+        let result = committee(&keys)
+            .verify(statement.proof, statement.public_inputs, &signatures)
+            .unwrap();
+        assert!(result.is_valid());
+        assert_eq!(result.signed, ["party0", "party1", "party2"]);
+    }
+
+    #[test]
+    fn committee_reports_missing_unknown_and_invalid_signatures() {
+        let keys = keys(4);
+        let statement = statement();
+        let mut signatures = keys
+            .iter()
+            .map(|key| SignedStatement::sign(&statement, key).unwrap())
+            .collect::<Vec<_>>();
+        // party1 signs a different proof, party2 does not sign, the last key is not a member
+        signatures[1] = SignedStatement::sign(
+            &ProofStatement {
+                public_inputs: vec!["43".to_owned()],
+                ..statement.clone()
+            },
+            &keys[1],
+        )
+        .unwrap();
+        signatures.remove(2);
+        let result = committee(&keys[..3])
+            .verify(statement.proof, statement.public_inputs, &signatures)
+            .unwrap();
+        assert!(!result.is_valid());
+        assert_eq!(result.signed, ["party0"]);
+        assert_eq!(result.missing, ["party1", "party2"]);
+        assert_eq!(result.invalid, [keys[1].public_key()]);
+        assert_eq!(result.unknown, [keys[3].public_key()]);
+    }
+
+    #[test]
+    fn committee_rejects_tampered_statement() {
+        let keys = keys(3);
+        let statement = statement();
+        let mut signatures = keys
+            .iter()
+            .map(|key| SignedStatement::sign(&statement, key).unwrap())
+            .collect::<Vec<_>>();
+        let result = committee(&keys)
+            .verify(
+                json!({"pi_a": ["1", "3", "1"]}),
+                statement.public_inputs.clone(),
+                &signatures,
+            )
+            .unwrap();
+        assert_eq!(result.missing.len(), 3);
+        assert_eq!(result.invalid.len(), 3);
+
+        for signature in signatures.iter_mut() {
+            signature.metadata = json!({"session": "other"});
+        }
+        let result = committee(&keys)
+            .verify(statement.proof, statement.public_inputs, &signatures)
+            .unwrap();
+        assert_eq!(result.invalid.len(), 3);
+    }
+
+    #[test]
+    fn possession_is_checked_against_the_member_of_the_party() {
+        let keys = keys(3);
+        let committee = committee(&keys);
+        let session_id = session_id(&[vec![1; 32], vec![2; 32], vec![3; 32]]).unwrap();
+        let signature = sign_possession(&committee, &keys[1], 1, &session_id).unwrap();
+        committee
+            .check_possession(1, &session_id, &signature)
+            .unwrap();
+        // a signature of party 1 is not valid for party 2 or another session
+        assert!(committee
+            .check_possession(2, &session_id, &signature)
+            .is_err());
+        assert!(committee
+            .check_possession(1, &session_id[1..], &signature)
+            .is_err());
+        // a party can not sign with the key of another member
+        assert!(sign_possession(&committee, &keys[1], 2, &session_id).is_err());
+    }
+
+    #[test]
+    fn rerandomized_groth16_proof_fails_the_binding_check() {
+        let vk: JsonVerificationKey<Bn254> = serde_json::from_str(
+            &fs::read_to_string(
+                "../../test_vectors/Groth16/bn254/multiplier2/verification_key.json",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let proof: Groth16Proof<Bn254> = serde_json::from_str(
+            &fs::read_to_string("../../test_vectors/Groth16/bn254/multiplier2/circom.proof")
+                .unwrap(),
+        )
+        .unwrap();
+        let public_input = [ark_bn254::Fr::from(33u64)];
+
+        let binding =
+            groth16_proof_binding::<Bn254>(&proof.pi_a, &proof.pi_b, &proof.pi_c).unwrap();
+        let mut metadata = serde_json::Map::new();
+        metadata.insert(PROOF_BINDING_KEY.to_owned(), binding.clone().into());
+        let bound = ProofStatement {
+            metadata: metadata.into(),
+            ..statement()
+        };
+        let keys = keys(3);
+        let signatures = keys
+            .iter()
+            .map(|key| SignedStatement::sign(&bound, key).unwrap())
+            .collect::<Vec<_>>();
+        assert!(check_proof_binding(&signatures, &binding).unwrap());
+
+        // (r * a, b / r, c) is a different valid proof of the same statement
+        let r = ark_bn254::Fr::rand(&mut ChaCha12Rng::seed_from_u64(42));
+        let rerandomized = Groth16Proof::<Bn254> {
+            pi_a: (proof.pi_a * r).into_affine(),
+            pi_b: (proof.pi_b * r.inverse().unwrap()).into_affine(),
+            pi_c: proof.pi_c,
+            protocol: proof.protocol.clone(),
+            curve: proof.curve.clone(),
+        };
+        Groth16::<Bn254>::verify(&vk, &proof, &public_input).expect("can verify");
+        Groth16::<Bn254>::verify(&vk, &rerandomized, &public_input).expect("can verify");
+        let rebinding = groth16_proof_binding::<Bn254>(
+            &rerandomized.pi_a,
+            &rerandomized.pi_b,
+            &rerandomized.pi_c,
+        )
+        .unwrap();
+        assert_ne!(rebinding, binding);
+        assert!(!check_proof_binding(&signatures, &rebinding).unwrap());
+
+        // signatures without a binding can not be checked
+        let unbound = [SignedStatement::sign(&statement(), &keys[0]).unwrap()];
+        let err = check_proof_binding(&unbound, &binding).unwrap_err();
+        assert!(err.to_string().contains("does not bind the proof elements"));
+    }
+}
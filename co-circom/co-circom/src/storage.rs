@@ -0,0 +1,606 @@
+//! Reading and writing of share files, proving keys, and proofs from object storage.
+//!
+//! Every path of a share file, proving key, proof, or public input file passed to a command can also be an object storage URI, i.e., `s3://<bucket>/<key>` for Amazon S3 and `gs://<bucket>/<key>` for Google Cloud Storage. The backends implement the [ObjectStore](object_store::ObjectStore) abstraction of the `object_store` crate and are only available with the `object-store` feature. The credentials and the region are read from the environment, e.g., `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY`, and `AWS_REGION` for S3 or `GOOGLE_SERVICE_ACCOUNT` for Google Cloud Storage.
+//!
+//! The path `-` denotes the standard input when reading and the standard output when writing, e.g., to pass secrets between containers without writing them to a file. Named pipes are read as streams as well.
+//!
+//! Objects are streamed with ranged GET requests of [REMOTE_CHUNK_SIZE] bytes, so large proving keys are never held in memory as a whole. Written objects are uploaded as a multipart upload with parts of [REMOTE_CHUNK_SIZE] bytes, which is only completed by [ObjectWriter::finish] and aborted otherwise, so a failed command does not leave a partial object behind. Likewise, local files are written to a temporary `<file>.partial` file next to the file, which is renamed by [ObjectWriter::finish] and removed if the writer is dropped before, e.g., because the MPC session was aborted. Named pipes are written directly.
+
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
+
+/// The URI scheme of Amazon S3 objects.
+pub const S3_SCHEME: &str = "s3://";
+/// The URI scheme of Google Cloud Storage objects.
+pub const GCS_SCHEME: &str = "gs://";
+/// The path of the standard input and output.
+pub const STDIO: &str = "-";
+/// The size of the ranged GET requests and of the parts of multipart uploads. It is above the minimum part size of 5 MiB of S3 and GCS.
+pub const REMOTE_CHUNK_SIZE: usize = 8 << 20;
+
+/// An error type for the storage backends.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The URI does not name a bucket and a key.
+    #[error("Invalid object storage URI {0}, expected <scheme>://<bucket>/<key>")]
+    InvalidUri(String),
+    /// The binary was built without object storage support.
+    #[error("Cannot access {0}, object storage requires the `object-store` feature")]
+    Unsupported(String),
+    /// The object store returned an error.
+    #[error("Object storage error for {0}: {1}")]
+    Backend(String, String),
+    /// An I/O error occurred.
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+}
+
+/// The location of a file, either on the local file system or in an object store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Location {
+    /// A path on the local file system
+    Local(PathBuf),
+    /// An object in an Amazon S3 bucket
+    S3 {
+        /// The name of the bucket
+        bucket: String,
+        /// The key of the object
+        key: String,
+    },
+    /// An object in a Google Cloud Storage bucket
+    Gcs {
+        /// The name of the bucket
+        bucket: String,
+        /// The key of the object
+        key: String,
+    },
+    /// The standard input when reading, the standard output when writing
+    Stdio,
+}
+
+/// Returns whether the path is an object storage URI.
+pub fn is_remote(path: &Path) -> bool {
+    path.to_str()
+        .is_some_and(|s| s.starts_with(S3_SCHEME) || s.starts_with(GCS_SCHEME))
+}
+
+impl Location {
+    /// Parses a path, which is either a local path or an object storage URI.
+    pub fn parse(path: &Path) -> Result<Self, Error> {
+        let Some(uri) = path.to_str() else {
+            return Ok(Self::Local(path.to_path_buf()));
+        };
+        if uri == STDIO {
+            return Ok(Self::Stdio);
+        }
+        let split = |rest: &str| match rest.split_once('/') {
+            Some((bucket, key)) if !bucket.is_empty() && !key.is_empty() => {
+                Ok((bucket.to_owned(), key.to_owned()))
+            }
+            _ => Err(Error::InvalidUri(uri.to_owned())),
+        };
+        if let Some(rest) = uri.strip_prefix(S3_SCHEME) {
+            let (bucket, key) = split(rest)?;
+            Ok(Self::S3 { bucket, key })
+        } else if let Some(rest) = uri.strip_prefix(GCS_SCHEME) {
+            let (bucket, key) = split(rest)?;
+            Ok(Self::Gcs { bucket, key })
+        } else {
+            Ok(Self::Local(path.to_path_buf()))
+        }
+    }
+
+    /// Returns whether the location can only be read once from start to end and has no known size, i.e., it is the standard input or a named pipe.
+    pub fn is_stream(&self) -> bool {
+        match self {
+            Location::Stdio => true,
+            #[cfg(unix)]
+            Location::Local(path) => {
+                use std::os::unix::fs::FileTypeExt;
+                std::fs::metadata(path).is_ok_and(|metadata| metadata.file_type().is_fifo())
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the size of the file or object in bytes, or `None` if it does not exist or is the standard input.
+    pub fn size(&self) -> Result<Option<u64>, Error> {
+        match self {
+            Location::Stdio => Ok(None),
+            Location::Local(path) => match std::fs::metadata(path) {
+                Ok(metadata) => Ok(Some(metadata.len())),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(err) => Err(err.into()),
+            },
+            _ => remote::size(self),
+        }
+    }
+
+    /// Opens the file or object for reading.
+    pub fn open(&self) -> Result<Box<dyn Read + Send>, Error> {
+        match self {
+            Location::Local(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+            Location::Stdio => Ok(Box::new(BufReader::new(std::io::stdin()))),
+            _ => Ok(Box::new(remote::open(self)?)),
+        }
+    }
+
+    /// Creates the file or object for writing. The content is only guaranteed to be written after [ObjectWriter::finish].
+    pub fn create(&self) -> Result<ObjectWriter, Error> {
+        let inner = match self {
+            Location::Local(path) if self.is_stream() => {
+                WriterInner::Local(BufWriter::new(File::create(path)?))
+            }
+            Location::Local(path) => match path.file_name() {
+                Some(name) => {
+                    let mut partial_name = name.to_owned();
+                    partial_name.push(".partial");
+                    let partial = path.with_file_name(partial_name);
+                    WriterInner::Partial(PartialFile {
+                        writer: BufWriter::new(File::create(&partial)?),
+                        partial,
+                        path: path.clone(),
+                    })
+                }
+                None => WriterInner::Local(BufWriter::new(File::create(path)?)),
+            },
+            Location::Stdio => WriterInner::Stdout(BufWriter::new(std::io::stdout())),
+            _ => WriterInner::Remote(remote::create(self)?),
+        };
+        Ok(ObjectWriter { inner })
+    }
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Location::Local(path) => write!(f, "{}", path.display()),
+            Location::S3 { bucket, key } => write!(f, "{S3_SCHEME}{bucket}/{key}"),
+            Location::Gcs { bucket, key } => write!(f, "{GCS_SCHEME}{bucket}/{key}"),
+            Location::Stdio => write!(f, "{STDIO}"),
+        }
+    }
+}
+
+/// Opens the file or object at the provided path or URI for reading, see [Location::open].
+pub fn open(path: &Path) -> Result<Box<dyn Read + Send>, Error> {
+    Location::parse(path)?.open()
+}
+
+/// Creates the file or object at the provided path or URI for writing, see [Location::create].
+pub fn create(path: &Path) -> Result<ObjectWriter, Error> {
+    Location::parse(path)?.create()
+}
+
+/// Writes the provided bytes to the file or object at the provided path or URI.
+pub fn write(path: &Path, bytes: &[u8]) -> Result<(), Error> {
+    let mut writer = create(path)?;
+    writer.write_all(bytes)?;
+    writer.finish()
+}
+
+/// A local file that is written to a temporary file first, see the [module documentation](self).
+struct PartialFile {
+    writer: BufWriter<File>,
+    partial: PathBuf,
+    path: PathBuf,
+}
+
+impl PartialFile {
+    fn persist(&mut self) -> std::io::Result<()> {
+        self.writer.flush()?;
+        self.writer.get_ref().sync_all()?;
+        std::fs::rename(&self.partial, &self.path)
+    }
+}
+
+impl Drop for PartialFile {
+    fn drop(&mut self) {
+        // the temporary file no longer exists if it was persisted
+        if self.partial.exists() {
+            tracing::warn!("removing incomplete output file {}", self.partial.display());
+            let _ = std::fs::remove_file(&self.partial);
+        }
+    }
+}
+
+enum WriterInner {
+    Local(BufWriter<File>),
+    Partial(PartialFile),
+    Stdout(BufWriter<std::io::Stdout>),
+    Remote(remote::RemoteWriter),
+}
+
+/// A writer for a file or an object, see [Location::create].
+pub struct ObjectWriter {
+    inner: WriterInner,
+}
+
+impl ObjectWriter {
+    /// Flushes the file or uploads the object.
+    pub fn finish(self) -> Result<(), Error> {
+        match self.inner {
+            WriterInner::Local(mut writer) => Ok(writer.flush()?),
+            WriterInner::Partial(mut file) => Ok(file.persist()?),
+            WriterInner::Stdout(mut writer) => Ok(writer.flush()?),
+            WriterInner::Remote(writer) => writer.finish(),
+        }
+    }
+}
+
+impl Write for ObjectWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match &mut self.inner {
+            WriterInner::Local(writer) => writer.write(buf),
+            WriterInner::Partial(file) => file.writer.write(buf),
+            WriterInner::Stdout(writer) => writer.write(buf),
+            WriterInner::Remote(writer) => writer.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match &mut self.inner {
+            WriterInner::Local(writer) => writer.flush(),
+            WriterInner::Partial(file) => file.writer.flush(),
+            WriterInner::Stdout(writer) => writer.flush(),
+            WriterInner::Remote(writer) => writer.flush(),
+        }
+    }
+}
+
+#[cfg(feature = "object-store")]
+mod remote {
+    use std::{
+        io::{Read, Write},
+        sync::Arc,
+    };
+
+    use object_store::{
+        aws::AmazonS3Builder, gcp::GoogleCloudStorageBuilder, path::Path as ObjectPath,
+        MultipartUpload, ObjectStore, PutPayload,
+    };
+    use tokio::runtime::Runtime;
+
+    use super::{Error, Location, REMOTE_CHUNK_SIZE};
+
+    fn store(location: &Location) -> Result<(Arc<dyn ObjectStore>, ObjectPath), Error> {
+        let backend =
+            |err: object_store::Error| Error::Backend(location.to_string(), err.to_string());
+        match location {
+            Location::S3 { bucket, key } => {
+                let store = AmazonS3Builder::from_env()
+                    .with_bucket_name(bucket)
+                    .build()
+                    .map_err(backend)?;
+                Ok((Arc::new(store), ObjectPath::from(key.as_str())))
+            }
+            Location::Gcs { bucket, key } => {
+                let store = GoogleCloudStorageBuilder::from_env()
+                    .with_bucket_name(bucket)
+                    .build()
+                    .map_err(backend)?;
+                Ok((Arc::new(store), ObjectPath::from(key.as_str())))
+            }
+            Location::Local(_) | Location::Stdio => {
+                unreachable!("local files are not read from an object store")
+            }
+        }
+    }
+
+    // the commands are synchronous, so every reader and writer drives its requests on its own runtime
+    fn runtime(location: &Location) -> Result<Runtime, Error> {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|err| Error::Backend(location.to_string(), err.to_string()))
+    }
+
+    fn io_error(err: object_store::Error) -> std::io::Error {
+        std::io::Error::other(err)
+    }
+
+    pub(super) fn size(location: &Location) -> Result<Option<u64>, Error> {
+        let (store, path) = store(location)?;
+        match runtime(location)?.block_on(store.head(&path)) {
+            Ok(meta) => Ok(Some(meta.size as u64)),
+            Err(object_store::Error::NotFound { .. }) => Ok(None),
+            Err(err) => Err(Error::Backend(location.to_string(), err.to_string())),
+        }
+    }
+
+    pub(super) fn open(location: &Location) -> Result<RemoteReader, Error> {
+        let (store, path) = store(location)?;
+        RemoteReader::new(runtime(location)?, store, path, REMOTE_CHUNK_SIZE)
+            .map_err(|err| Error::Backend(location.to_string(), err.to_string()))
+    }
+
+    pub(super) fn create(location: &Location) -> Result<RemoteWriter, Error> {
+        let (store, path) = store(location)?;
+        RemoteWriter::new(
+            runtime(location)?,
+            store,
+            path,
+            location.to_string(),
+            REMOTE_CHUNK_SIZE,
+        )
+        .map_err(|err| Error::Backend(location.to_string(), err.to_string()))
+    }
+
+    /// Reads an object with ranged GET requests of at most `chunk_size` bytes.
+    pub(super) struct RemoteReader {
+        runtime: Runtime,
+        store: Arc<dyn ObjectStore>,
+        path: ObjectPath,
+        size: usize,
+        chunk_size: usize,
+        // the offset of the next chunk in the object
+        offset: usize,
+        chunk: Vec<u8>,
+        // the position of the next byte in the current chunk
+        position: usize,
+    }
+
+    impl RemoteReader {
+        fn new(
+            runtime: Runtime,
+            store: Arc<dyn ObjectStore>,
+            path: ObjectPath,
+            chunk_size: usize,
+        ) -> object_store::Result<Self> {
+            let size = runtime.block_on(store.head(&path))?.size;
+            Ok(Self {
+                runtime,
+                store,
+                path,
+                size,
+                chunk_size,
+                offset: 0,
+                chunk: Vec::new(),
+                position: 0,
+            })
+        }
+    }
+
+    impl Read for RemoteReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.position == self.chunk.len() && self.offset < self.size {
+                let end = self.size.min(self.offset + self.chunk_size);
+                let chunk = self
+                    .runtime
+                    .block_on(self.store.get_range(&self.path, self.offset..end))
+                    .map_err(io_error)?;
+                if chunk.len() != end - self.offset {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::UnexpectedEof,
+                        format!("object {} changed while reading it", self.path),
+                    ));
+                }
+                self.chunk = chunk.to_vec();
+                self.position = 0;
+                self.offset = end;
+            }
+            let len = buf.len().min(self.chunk.len() - self.position);
+            buf[..len].copy_from_slice(&self.chunk[self.position..self.position + len]);
+            self.position += len;
+            Ok(len)
+        }
+    }
+
+    /// Writes an object as a multipart upload with parts of `part_size` bytes. The upload is aborted if the writer is dropped before [RemoteWriter::finish].
+    pub(super) struct RemoteWriter {
+        runtime: Runtime,
+        upload: Option<Box<dyn MultipartUpload>>,
+        name: String,
+        part_size: usize,
+        buffer: Vec<u8>,
+        num_parts: usize,
+    }
+
+    impl RemoteWriter {
+        fn new(
+            runtime: Runtime,
+            store: Arc<dyn ObjectStore>,
+            path: ObjectPath,
+            name: String,
+            part_size: usize,
+        ) -> object_store::Result<Self> {
+            let upload = runtime.block_on(store.put_multipart(&path))?;
+            Ok(Self {
+                runtime,
+                upload: Some(upload),
+                name,
+                part_size,
+                buffer: Vec::with_capacity(part_size),
+                num_parts: 0,
+            })
+        }
+
+        fn upload(&mut self) -> &mut Box<dyn MultipartUpload> {
+            self.upload
+                .as_mut()
+                .expect("the upload is only taken by finish")
+        }
+
+        fn put_part(&mut self) -> std::io::Result<()> {
+            let part = std::mem::replace(&mut self.buffer, Vec::with_capacity(self.part_size));
+            let part = self.upload().put_part(PutPayload::from(part));
+            self.runtime.block_on(part).map_err(io_error)?;
+            self.num_parts += 1;
+            Ok(())
+        }
+
+        pub(super) fn finish(mut self) -> Result<(), Error> {
+            // a multipart upload needs at least one part, even if the object is empty
+            if !self.buffer.is_empty() || self.num_parts == 0 {
+                self.put_part()
+                    .map_err(|err| Error::Backend(self.name.clone(), err.to_string()))?;
+            }
+            let mut upload = self.upload.take().expect("finish is only called once");
+            self.runtime
+                .block_on(upload.complete())
+                .map_err(|err| Error::Backend(self.name.clone(), err.to_string()))?;
+            tracing::debug!("Uploaded {}", self.name);
+            Ok(())
+        }
+    }
+
+    impl Write for RemoteWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let len = buf.len().min(self.part_size - self.buffer.len());
+            self.buffer.extend_from_slice(&buf[..len]);
+            if self.buffer.len() == self.part_size {
+                self.put_part()?;
+            }
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            // parts are only uploaded once they are full, since all but the last part need the minimum size
+            Ok(())
+        }
+    }
+
+    impl Drop for RemoteWriter {
+        fn drop(&mut self) {
+            if let Some(mut upload) = self.upload.take() {
+                tracing::warn!("aborting incomplete upload of {}", self.name);
+                let _ = self.runtime.block_on(upload.abort());
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::io::{Read, Write};
+
+        use object_store::memory::InMemory;
+
+        use super::*;
+
+        fn memory() -> (Runtime, Arc<dyn ObjectStore>, ObjectPath) {
+            (
+                new_runtime(),
+                Arc::new(InMemory::new()),
+                ObjectPath::from("dir/key"),
+            )
+        }
+
+        fn new_runtime() -> Runtime {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap()
+        }
+
+        fn bytes(len: usize) -> Vec<u8> {
+            (0..len).map(|i| (i * 7 % 251) as u8).collect()
+        }
+
+        #[test]
+        fn reads_in_ranged_chunks() {
+            let (runtime, store, path) = memory();
+            let content = bytes(1000);
+            runtime
+                .block_on(store.put(&path, PutPayload::from(content.clone())))
+                .unwrap();
+
+            let mut reader = RemoteReader::new(runtime, store, path, 64).unwrap();
+            // reads never cross a chunk boundary, so only one chunk is in memory
+            let mut buf = [0u8; 100];
+            assert_eq!(reader.read(&mut buf).unwrap(), 64);
+            assert_eq!(&buf[..64], &content[..64]);
+            let mut rest = Vec::new();
+            reader.read_to_end(&mut rest).unwrap();
+            assert_eq!(rest, content[64..]);
+        }
+
+        #[test]
+        fn reads_empty_object() {
+            let (runtime, store, path) = memory();
+            runtime
+                .block_on(store.put(&path, PutPayload::default()))
+                .unwrap();
+            let mut content = Vec::new();
+            RemoteReader::new(runtime, store, path, 64)
+                .unwrap()
+                .read_to_end(&mut content)
+                .unwrap();
+            assert!(content.is_empty());
+        }
+
+        #[test]
+        fn reading_missing_object_fails() {
+            let (runtime, store, path) = memory();
+            assert!(RemoteReader::new(runtime, store, path, 64).is_err());
+        }
+
+        #[test]
+        fn writes_multipart_upload() {
+            for len in [0, 1, 64, 1000] {
+                let (runtime, store, path) = memory();
+                let content = bytes(len);
+                let mut writer =
+                    RemoteWriter::new(runtime, store.clone(), path.clone(), "test".into(), 64)
+                        .unwrap();
+                writer.write_all(&content).unwrap();
+                writer.finish().unwrap();
+
+                let mut read = Vec::new();
+                RemoteReader::new(new_runtime(), store, path, 100)
+                    .unwrap()
+                    .read_to_end(&mut read)
+                    .unwrap();
+                assert_eq!(read, content);
+            }
+        }
+
+        #[test]
+        fn dropped_writer_leaves_no_object() {
+            let (runtime, store, path) = memory();
+            let mut writer =
+                RemoteWriter::new(runtime, store.clone(), path.clone(), "test".into(), 64).unwrap();
+            writer.write_all(&bytes(1000)).unwrap();
+            drop(writer);
+
+            let result = new_runtime().block_on(store.head(&path));
+            assert!(matches!(result, Err(object_store::Error::NotFound { .. })));
+        }
+    }
+}
+
+#[cfg(not(feature = "object-store"))]
+mod remote {
+    use super::{Error, Location};
+
+    /// Uninhabited, since remote objects cannot be created without the `object-store` feature.
+    pub(super) enum RemoteWriter {}
+
+    impl RemoteWriter {
+        pub(super) fn finish(self) -> Result<(), Error> {
+            match self {}
+        }
+
+        pub(super) fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            match *self {}
+        }
+
+        pub(super) fn flush(&mut self) -> std::io::Result<()> {
+            match *self {}
+        }
+    }
+
+    pub(super) fn size(location: &Location) -> Result<Option<u64>, Error> {
+        Err(Error::Unsupported(location.to_string()))
+    }
+
+    pub(super) fn open(location: &Location) -> Result<std::io::Empty, Error> {
+        Err(Error::Unsupported(location.to_string()))
+    }
+
+    pub(super) fn create(location: &Location) -> Result<RemoteWriter, Error> {
+        Err(Error::Unsupported(location.to_string()))
+    }
+}
@@ -0,0 +1,575 @@
+//! Machine-readable transcripts of sessions for conformance audits.
+//!
+//! With `--transcript <file>`, `generate-proof` writes a [SessionTranscript] of the session of this party as JSON. It is a high-level record of what the party did and saw, which contains no secret shares and is therefore safe to hand to a compliance reviewer. The `audit` command cross-checks the transcripts of all parties of a session with [audit], e.g., after the fact or when a proof is disputed.
+//!
+//! # Schema
+//!
+//! The schema is versioned with [TRANSCRIPT_SCHEMA_VERSION], which is incremented on every change that is not backwards compatible, i.e., on removing or renaming fields, but not on adding fields. A transcript is a JSON object with the fields:
+//!
+//! - `schema_version`: the version of the schema,
+//! - `command`: the command that ran the session, e.g., `generate-proof`,
+//! - `party_id` and `num_parties`: the id of the party and the number of parties of the session,
+//! - `protocol`, `curve`, and `proof_system`: the configuration of the session,
+//! - `phases`: the [PhaseRecord]s in the order the party ran them, each with its `name`, its `duration_ms`, and the `messages` exchanged with every other party during the phase by the id of the other party, see [mpc_net::MessageCount],
+//! - `public_values`: the values opened to all parties, i.e., the public inputs of the proof in the format of snarkjs,
+//! - `protocol_digest`: the hex encoded digest of all messages of the MPC protocol, if the protocol records one (see [Rep3Network::check_transcript](mpc_core::protocols::rep3::network::Rep3Network::check_transcript)), otherwise `null`. The digest covers the messages of the party in both directions and therefore differs between parties,
+//! - `artifacts`: the [ArtifactRecord]s of the final artifacts by their kind, e.g., `proof` or `public_input`, with the hex encoded SHA-256 digest of their compact JSON serialization and the path of the written file, if any.
+//!
+//! # Audit
+//!
+//! The [audit] checks that the transcripts belong to the same session and that the parties agree on everything they observed together: the configuration, the sequence of phases, the number of messages and bytes on every link in every phase (what party `i` sent to party `j` is what party `j` received from party `i`), the public values, and the artifacts that are identical for all parties, e.g., the proof. It can not detect a party that consistently lies in its own transcript, but it pinpoints the phase and the link at which the observations of honest parties diverge.
+//!
+//! With the `FASTEST` reconstruction of the Shamir protocol, a party might receive a late message after it finished its transcript, which is reported as a mismatch of the last phase.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+    sync::Arc,
+    time::Instant,
+};
+
+use mpc_net::{MessageCount, MessageCounter};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{MPCCurve, MPCProtocol, ProofSystem};
+
+/// The version of the schema of [SessionTranscript], see the [module documentation](self).
+pub const TRANSCRIPT_SCHEMA_VERSION: u32 = 1;
+
+/// The messages exchanged with another party during a phase, see [mpc_net::MessageCount].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkRecord {
+    /// The number of messages sent to the party
+    pub messages_sent: u64,
+    /// The number of payload bytes sent to the party
+    pub bytes_sent: u64,
+    /// The number of messages received from the party
+    pub messages_received: u64,
+    /// The number of payload bytes received from the party
+    pub bytes_received: u64,
+}
+
+/// A phase of a session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhaseRecord {
+    /// The name of the phase, e.g., `proof`
+    pub name: String,
+    /// The duration of the phase in milliseconds
+    pub duration_ms: f64,
+    /// The messages exchanged with the other parties during the phase by their ids
+    pub messages: BTreeMap<usize, LinkRecord>,
+}
+
+/// A final artifact of a session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactRecord {
+    /// The hex encoded SHA-256 digest of the compact JSON serialization of the artifact
+    pub sha256: String,
+    /// The path of the written file, if the artifact was written
+    pub path: Option<PathBuf>,
+    /// Whether all parties produce the same artifact, e.g., the proof, in contrast to, e.g., the metadata
+    pub shared: bool,
+}
+
+/// The transcript of a session of one party, see the [module documentation](self) for the schema.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionTranscript {
+    /// The version of the schema, see [TRANSCRIPT_SCHEMA_VERSION]
+    pub schema_version: u32,
+    /// The command that ran the session, e.g., `generate-proof`
+    pub command: String,
+    /// The id of the party
+    pub party_id: usize,
+    /// The number of parties of the session
+    pub num_parties: usize,
+    /// The MPC protocol of the session
+    pub protocol: MPCProtocol,
+    /// The curve of the session
+    pub curve: MPCCurve,
+    /// The proof system of the session
+    pub proof_system: ProofSystem,
+    /// The phases in the order the party ran them
+    pub phases: Vec<PhaseRecord>,
+    /// The values opened to all parties, i.e., the public inputs
+    pub public_values: Vec<String>,
+    /// The hex encoded digest of all messages of the MPC protocol, if the protocol records one
+    pub protocol_digest: Option<String>,
+    /// The final artifacts by their kind
+    pub artifacts: BTreeMap<String, ArtifactRecord>,
+}
+
+/// Records the [SessionTranscript] of a running session.
+///
+/// The phases are recorded when they are finished with [TranscriptRecorder::finish_phase], and the messages of a phase are the messages counted by the [MessageCounter] of the network since the previous phase was finished.
+#[derive(Debug)]
+pub struct TranscriptRecorder {
+    transcript: SessionTranscript,
+    counter: Option<Arc<MessageCounter>>,
+    counts: BTreeMap<usize, MessageCount>,
+    phase_started: Instant,
+}
+
+impl TranscriptRecorder {
+    /// Starts recording the transcript of a session of the party with id `party_id`.
+    pub fn new(
+        command: &str,
+        party_id: usize,
+        num_parties: usize,
+        protocol: MPCProtocol,
+        curve: MPCCurve,
+        proof_system: ProofSystem,
+    ) -> Self {
+        Self {
+            transcript: SessionTranscript {
+                schema_version: TRANSCRIPT_SCHEMA_VERSION,
+                command: command.to_owned(),
+                party_id,
+                num_parties,
+                protocol,
+                curve,
+                proof_system,
+                phases: Vec::new(),
+                public_values: Vec::new(),
+                protocol_digest: None,
+                artifacts: BTreeMap::new(),
+            },
+            counter: None,
+            counts: BTreeMap::new(),
+            phase_started: Instant::now(),
+        }
+    }
+
+    /// Counts the messages of the phases with the [MessageCounter] of the network of the session. The messages counted before are not attributed to any phase.
+    pub fn attach(&mut self, counter: Arc<MessageCounter>) {
+        self.counts = counter.counts();
+        self.counter = Some(counter);
+    }
+
+    /// Records the phase with the provided name, which started when the previous phase finished.
+    pub fn finish_phase(&mut self, name: &str) {
+        let counts = self
+            .counter
+            .as_ref()
+            .map(|counter| counter.counts())
+            .unwrap_or_default();
+        let messages = counts
+            .iter()
+            .map(|(party, count)| {
+                let before = self.counts.get(party).copied().unwrap_or_default();
+                let link = LinkRecord {
+                    messages_sent: count.messages_sent - before.messages_sent,
+                    bytes_sent: count.bytes_sent - before.bytes_sent,
+                    messages_received: count.messages_received - before.messages_received,
+                    bytes_received: count.bytes_received - before.bytes_received,
+                };
+                (*party, link)
+            })
+            .collect();
+        self.transcript.phases.push(PhaseRecord {
+            name: name.to_owned(),
+            duration_ms: self.phase_started.elapsed().as_micros() as f64 / 1000.,
+            messages,
+        });
+        self.counts = counts;
+        self.phase_started = Instant::now();
+    }
+
+    /// Records the values opened to all parties.
+    pub fn set_public_values(&mut self, public_values: Vec<String>) {
+        self.transcript.public_values = public_values;
+    }
+
+    /// Records the digest of all messages of the MPC protocol.
+    pub fn set_protocol_digest(&mut self, digest: Option<String>) {
+        self.transcript.protocol_digest = digest;
+    }
+
+    /// Records a final artifact of the provided kind. `shared` tells whether all parties produce the same artifact.
+    pub fn add_artifact(
+        &mut self,
+        kind: &str,
+        artifact: &impl Serialize,
+        path: Option<PathBuf>,
+        shared: bool,
+    ) -> serde_json::Result<()> {
+        let json = serde_json::to_vec(artifact)?;
+        let sha256 = Sha256::digest(&json)
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        self.transcript.artifacts.insert(
+            kind.to_owned(),
+            ArtifactRecord {
+                sha256,
+                path,
+                shared,
+            },
+        );
+        Ok(())
+    }
+
+    /// Returns the recorded transcript.
+    pub fn finish(self) -> SessionTranscript {
+        self.transcript
+    }
+}
+
+/// The result of an [audit] of the transcripts of a session.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AuditReport {
+    /// The number of audited transcripts
+    pub transcripts: usize,
+    /// The number of phases of the session
+    pub phases: usize,
+    /// The inconsistencies between the transcripts, empty if the transcripts are consistent
+    pub findings: Vec<String>,
+}
+
+impl AuditReport {
+    /// Returns `true` if the transcripts are consistent.
+    pub fn passed(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+/// Cross-checks the transcripts of all parties of a session for consistency, see the [module documentation](self). The transcripts may be passed in any order.
+pub fn audit(transcripts: &[SessionTranscript]) -> AuditReport {
+    let mut report = AuditReport {
+        transcripts: transcripts.len(),
+        ..Default::default()
+    };
+    let findings = &mut report.findings;
+    let Some(first) = transcripts.first() else {
+        findings.push("no transcripts to audit".to_owned());
+        return report;
+    };
+    report.phases = first.phases.len();
+
+    for transcript in transcripts {
+        if transcript.schema_version != TRANSCRIPT_SCHEMA_VERSION {
+            findings.push(format!(
+                "transcript of party {} has schema version {}, but only version {TRANSCRIPT_SCHEMA_VERSION} is supported",
+                transcript.party_id, transcript.schema_version
+            ));
+        }
+    }
+    if !findings.is_empty() {
+        return report;
+    }
+
+    // all parties ran the same session
+    let session = |t: &SessionTranscript| {
+        (
+            t.command.clone(),
+            t.num_parties,
+            t.protocol,
+            t.curve,
+            t.proof_system.clone(),
+        )
+    };
+    for transcript in &transcripts[1..] {
+        if session(transcript) != session(first) {
+            findings.push(format!(
+                "party {} ran {} with {} parties, {:?}, {:?}, and {}, but party {} ran {} with {} parties, {:?}, {:?}, and {}",
+                transcript.party_id,
+                transcript.command,
+                transcript.num_parties,
+                transcript.protocol,
+                transcript.curve,
+                transcript.proof_system,
+                first.party_id,
+                first.command,
+                first.num_parties,
+                first.protocol,
+                first.curve,
+                first.proof_system,
+            ));
+        }
+    }
+    let mut by_id = BTreeMap::new();
+    for transcript in transcripts {
+        if by_id.insert(transcript.party_id, transcript).is_some() {
+            findings.push(format!(
+                "party {} has more than one transcript",
+                transcript.party_id
+            ));
+        }
+    }
+    let missing = (0..first.num_parties)
+        .filter(|id| !by_id.contains_key(id))
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        findings.push(format!("transcripts of parties {missing:?} are missing"));
+    }
+    if let Some(id) = by_id.keys().find(|id| **id >= first.num_parties) {
+        findings.push(format!(
+            "party {id} is not part of a session of {} parties",
+            first.num_parties
+        ));
+    }
+    if !findings.is_empty() {
+        return report;
+    }
+
+    // all parties ran the same phases
+    let phase_names = |t: &SessionTranscript| {
+        t.phases
+            .iter()
+            .map(|phase| phase.name.clone())
+            .collect::<Vec<_>>()
+    };
+    for transcript in by_id.values() {
+        if phase_names(transcript) != phase_names(first) {
+            findings.push(format!(
+                "party {} ran the phases {:?}, but party {} ran {:?}",
+                transcript.party_id,
+                phase_names(transcript),
+                first.party_id,
+                phase_names(first)
+            ));
+        }
+    }
+    if !findings.is_empty() {
+        return report;
+    }
+
+    // what one party sent is what the other party received
+    for (phase, name) in phase_names(first).iter().enumerate() {
+        for (from, sender) in by_id.iter() {
+            for (to, receiver) in by_id.iter().filter(|(to, _)| to != &from) {
+                let sent = sender.phases[phase]
+                    .messages
+                    .get(to)
+                    .copied()
+                    .unwrap_or_default();
+                let received = receiver.phases[phase]
+                    .messages
+                    .get(from)
+                    .copied()
+                    .unwrap_or_default();
+                if sent.messages_sent != received.messages_received
+                    || sent.bytes_sent != received.bytes_received
+                {
+                    findings.push(format!(
+                        "phase \"{name}\": party {from} sent {} messages ({} bytes) to party {to}, but party {to} received {} messages ({} bytes) from party {from}",
+                        sent.messages_sent,
+                        sent.bytes_sent,
+                        received.messages_received,
+                        received.bytes_received
+                    ));
+                }
+            }
+        }
+    }
+
+    // all parties opened the same values and produced the same shared artifacts
+    for transcript in by_id.values() {
+        if transcript.public_values != first.public_values {
+            findings.push(format!(
+                "party {} opened other public values than party {}",
+                transcript.party_id, first.party_id
+            ));
+        }
+        if transcript.protocol_digest.is_some() != first.protocol_digest.is_some() {
+            findings.push(format!(
+                "only one of party {} and party {} recorded a protocol digest",
+                transcript.party_id, first.party_id
+            ));
+        }
+    }
+    let kinds = by_id
+        .values()
+        .flat_map(|t| t.artifacts.iter())
+        .filter(|(_, artifact)| artifact.shared)
+        .map(|(kind, _)| kind)
+        .collect::<BTreeSet<_>>();
+    for kind in kinds {
+        let digests = by_id
+            .iter()
+            .map(|(id, t)| (*id, t.artifacts.get(kind).map(|a| a.sha256.as_str())))
+            .collect::<Vec<_>>();
+        let (first_id, first_digest) = digests[0];
+        for (id, digest) in &digests[1..] {
+            if *digest != first_digest {
+                findings.push(format!(
+                    "artifact \"{kind}\" of party {id} ({}) differs from the one of party {first_id} ({})",
+                    digest.unwrap_or("missing"),
+                    first_digest.unwrap_or("missing")
+                ));
+            }
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    // the transcripts of a consistent session of three parties, in which every party sends one message of 32 bytes to every other party
+    fn transcripts() -> Vec<SessionTranscript> {
+        let proof = json!({"pi_a": ["1", "2", "1"], "protocol": "groth16"});
+        let link = LinkRecord {
+            messages_sent: 1,
+            bytes_sent: 32,
+            messages_received: 1,
+            bytes_received: 32,
+        };
+        (0..3)
+            .map(|id| {
+                let mut recorder = TranscriptRecorder::new(
+                    "generate-proof",
+                    id,
+                    3,
+                    MPCProtocol::REP3,
+                    MPCCurve::BN254,
+                    ProofSystem::Groth16,
+                );
+                recorder.finish_phase("witness extension");
+                recorder.transcript.phases.push(PhaseRecord {
+                    name: "proof".to_owned(),
+                    duration_ms: 1.,
+                    messages: (0..3)
+                        .filter(|other| *other != id)
+                        .map(|other| (other, link))
+                        .collect(),
+                });
+                recorder.set_public_values(vec!["33".to_owned()]);
+                recorder.set_protocol_digest(Some(format!("digest of party {id}")));
+                recorder.add_artifact("proof", &proof, None, true).unwrap();
+                recorder
+                    .add_artifact("metadata", &json!({"party": id}), None, false)
+                    .unwrap();
+                recorder.finish()
+            })
+            .collect()
+    }
+
+    fn assert_single_finding(report: &AuditReport, expected: &[&str]) {
+        assert_eq!(report.findings.len(), 1, "{:?}", report.findings);
+        for expected in expected {
+            assert!(
+                report.findings[0].contains(expected),
+                "{:?} does not contain {expected:?}",
+                report.findings[0]
+            );
+        }
+    }
+
+    #[test]
+    fn consistent_transcripts_pass() {
+        let mut transcripts = transcripts();
+        let report = audit(&transcripts);
+        assert!(report.passed(), "{:?}", report.findings);
+        assert_eq!(report.transcripts, 3);
+        assert_eq!(report.phases, 2);
+
+        // the order of the transcripts does not matter
+        transcripts.reverse();
+        assert!(audit(&transcripts).passed());
+    }
+
+    #[test]
+    fn transcripts_survive_a_json_round_trip() {
+        let transcripts = transcripts()
+            .iter()
+            .map(|transcript| {
+                serde_json::from_str(&serde_json::to_string(transcript).unwrap()).unwrap()
+            })
+            .collect::<Vec<SessionTranscript>>();
+        assert_eq!(transcripts, self::transcripts());
+        assert!(audit(&transcripts).passed());
+    }
+
+    #[test]
+    fn mismatched_artifact_digest_names_the_party() {
+        let mut transcripts = transcripts();
+        transcripts[2].artifacts.get_mut("proof").unwrap().sha256 = "00".repeat(32);
+        let report = audit(&transcripts);
+        assert!(!report.passed());
+        assert_single_finding(
+            &report,
+            &["artifact \"proof\"", "of party 2", &"00".repeat(32)],
+        );
+    }
+
+    #[test]
+    fn missing_shared_artifact_names_the_party() {
+        let mut transcripts = transcripts();
+        transcripts[1].artifacts.remove("proof");
+        assert_single_finding(
+            &audit(&transcripts),
+            &["artifact \"proof\" of party 1 (missing)"],
+        );
+    }
+
+    #[test]
+    fn artifacts_that_are_not_shared_may_differ() {
+        let transcripts = transcripts();
+        assert_ne!(
+            transcripts[0].artifacts["metadata"],
+            transcripts[1].artifacts["metadata"]
+        );
+        assert!(audit(&transcripts).passed());
+    }
+
+    #[test]
+    fn mismatched_opened_value_names_the_party() {
+        let mut transcripts = transcripts();
+        transcripts[1].public_values = vec!["34".to_owned()];
+        assert_single_finding(
+            &audit(&transcripts),
+            &["party 1 opened other public values than party 0"],
+        );
+    }
+
+    #[test]
+    fn mismatched_message_count_names_the_link() {
+        let mut transcripts = transcripts();
+        let link = transcripts[1].phases[1].messages.get_mut(&2).unwrap();
+        link.bytes_sent = 64;
+        assert_single_finding(
+            &audit(&transcripts),
+            &[
+                "phase \"proof\"",
+                "party 1 sent 1 messages (64 bytes) to party 2",
+                "party 2 received 1 messages (32 bytes) from party 1",
+            ],
+        );
+    }
+
+    #[test]
+    fn missing_or_foreign_transcripts_fail() {
+        let mut transcripts = transcripts();
+        transcripts.remove(1);
+        assert_single_finding(
+            &audit(&transcripts),
+            &["transcripts of parties [1] are missing"],
+        );
+
+        let mut transcripts = self::transcripts();
+        transcripts[2].protocol = MPCProtocol::SHAMIR;
+        assert_single_finding(&audit(&transcripts), &["party 2 ran generate-proof"]);
+
+        let mut transcripts = self::transcripts();
+        transcripts[2].schema_version = TRANSCRIPT_SCHEMA_VERSION + 1;
+        assert_single_finding(
+            &audit(&transcripts),
+            &["transcript of party 2 has schema version"],
+        );
+
+        assert_single_finding(&audit(&[]), &["no transcripts to audit"]);
+    }
+
+    #[test]
+    fn diverging_phases_name_the_party() {
+        let mut transcripts = transcripts();
+        transcripts[2].phases.pop();
+        assert_single_finding(
+            &audit(&transcripts),
+            &["party 2 ran the phases [\"witness extension\"]"],
+        );
+    }
+}
@@ -0,0 +1,420 @@
+//! An HTTP service for verifying proofs against a registry of verification keys.
+//!
+//! The service is started with `co-circom verify-server` and exposes three endpoints:
+//!
+//! * `GET /health` returns `200 OK` once the verification keys are loaded.
+//! * `POST /verify` expects a [VerifyRequest] as JSON body and returns a [VerifyResponse]. An invalid proof is not an error, the response then has `valid: false`. Malformed requests are answered with `400 Bad Request`, unknown keys with `404 Not Found`, and requests exceeding the rate limit with `429 Too Many Requests`.
+//! * `GET /metrics` returns the request counters in the Prometheus text format.
+//!
+//! The verification keys are registered in a [KeyRegistry] file and are addressed by their id, so clients only send the proof and the public inputs. Groth16 keys are prepared once when the registry is loaded. Proofs of plugin proof systems can not be verified by the service.
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::BufReader,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
+
+use ark_bls12_377::Bls12_377;
+use ark_bls12_381::Bls12_381;
+use ark_bn254::Bn254;
+use ark_ec::pairing::Pairing;
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use circom_types::{
+    groth16::{Groth16Proof, JsonVerificationKey as Groth16JsonVerificationKey},
+    plonk::{JsonVerificationKey as PlonkJsonVerificationKey, PlonkProof},
+    traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge},
+};
+use co_circom_snarks::VerificationError;
+use co_groth16::{Groth16, PreparedVerificationKey};
+use co_plonk::Plonk;
+use color_eyre::eyre::{self, Context};
+use serde::{Deserialize, Serialize};
+
+use crate::{MPCCurve, ProofSystem, VerifyServerConfig};
+
+/// A verification key of the registry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegisteredKey {
+    /// The id under which clients address the key
+    pub id: String,
+    /// The proof system of the key, either groth16 or plonk
+    pub proof_system: ProofSystem,
+    /// The curve of the key
+    pub curve: MPCCurve,
+    /// The path to the verification key file
+    pub vk: PathBuf,
+}
+
+/// The JSON file listing the verification keys of the service, e.g., `{"keys": [{"id": "adder", "proof_system": "groth16", "curve": "BN254", "vk": "verification_key.json"}]}`. Relative paths are resolved relative to the registry file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyRegistry {
+    /// The registered keys
+    pub keys: Vec<RegisteredKey>,
+}
+
+impl KeyRegistry {
+    /// Reads a registry from a JSON file.
+    pub fn from_file(path: &Path) -> eyre::Result<Self> {
+        let file = BufReader::new(File::open(path).context("while opening key registry")?);
+        let mut registry: Self =
+            serde_json::from_reader(file).context("while parsing key registry")?;
+        let base = path.parent().unwrap_or(Path::new(""));
+        for key in registry.keys.iter_mut() {
+            if key.vk.is_relative() {
+                key.vk = base.join(&key.vk);
+            }
+        }
+        Ok(registry)
+    }
+}
+
+/// The body of a `POST /verify` request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyRequest {
+    /// The id of the verification key in the [KeyRegistry]
+    pub vk_id: String,
+    /// The proof as written by `generate-proof`
+    pub proof: serde_json::Value,
+    /// The public inputs as written by `generate-proof`
+    pub public_inputs: Vec<String>,
+}
+
+/// The body of the response to a `POST /verify` request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyResponse {
+    /// The id of the verification key
+    pub vk_id: String,
+    /// Whether the proof is valid
+    pub valid: bool,
+}
+
+/// A loaded verification key that can verify proofs in their JSON representation.
+trait Verifier: Send + Sync {
+    fn verify(&self, proof: serde_json::Value, public_inputs: &[String]) -> eyre::Result<bool>;
+}
+
+fn parse_public_inputs<P: Pairing>(public_inputs: &[String]) -> eyre::Result<Vec<P::ScalarField>> {
+    public_inputs
+        .iter()
+        .map(|s| {
+            s.parse::<P::ScalarField>()
+                .map_err(|_| eyre::eyre!("could not parse as field element: {s}"))
+        })
+        .collect()
+}
+
+fn into_validity(result: Result<(), VerificationError>) -> eyre::Result<bool> {
+    match result {
+        Ok(()) => Ok(true),
+        Err(VerificationError::InvalidProof) => Ok(false),
+        Err(VerificationError::Malformed(err)) => Err(err),
+    }
+}
+
+struct Groth16Verifier<P: Pairing>(PreparedVerificationKey<P>);
+
+impl<P: Pairing + CircomArkworksPairingBridge> Verifier for Groth16Verifier<P>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    fn verify(&self, proof: serde_json::Value, public_inputs: &[String]) -> eyre::Result<bool> {
+        let proof: Groth16Proof<P> =
+            serde_json::from_value(proof).context("while deserializing proof")?;
+        let public_inputs = parse_public_inputs::<P>(public_inputs)?;
+        into_validity(Groth16::<P>::verify_prepared(
+            &self.0,
+            &proof,
+            &public_inputs,
+        ))
+    }
+}
+
+struct PlonkVerifier<P: Pairing>(PlonkJsonVerificationKey<P>);
+
+impl<P: Pairing + CircomArkworksPairingBridge> Verifier for PlonkVerifier<P>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    fn verify(&self, proof: serde_json::Value, public_inputs: &[String]) -> eyre::Result<bool> {
+        let proof: PlonkProof<P> =
+            serde_json::from_value(proof).context("while deserializing proof")?;
+        let public_inputs = parse_public_inputs::<P>(public_inputs)?;
+        into_validity(Plonk::<P>::verify(&self.0, &proof, &public_inputs))
+    }
+}
+
+fn load_verifier<P: Pairing + CircomArkworksPairingBridge>(
+    key: &RegisteredKey,
+) -> eyre::Result<Box<dyn Verifier>>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    let file = BufReader::new(File::open(&key.vk).context("while opening verification key")?);
+    match &key.proof_system {
+        ProofSystem::Groth16 => {
+            let vk: Groth16JsonVerificationKey<P> =
+                serde_json::from_reader(file).context("while parsing verification key")?;
+            Ok(Box::new(Groth16Verifier(
+                Groth16::<P>::prepare_verification_key(&vk),
+            )))
+        }
+        ProofSystem::Plonk => {
+            let vk: PlonkJsonVerificationKey<P> =
+                serde_json::from_reader(file).context("while parsing verification key")?;
+            Ok(Box::new(PlonkVerifier(vk)))
+        }
+        ProofSystem::Plugin(name) => {
+            eyre::bail!("the proof system {name} is not supported by the verification service")
+        }
+    }
+}
+
+/// The default burst of the rate limit, see [VerifyService::new].
+pub const DEFAULT_BURST: u32 = 10;
+
+/// A token bucket limiting the rate of verification requests over all clients.
+struct RateLimiter {
+    requests_per_second: f64,
+    burst: f64,
+    // the available tokens and the time they were last refilled
+    state: Mutex<(f64, Instant)>,
+}
+
+impl RateLimiter {
+    fn new(requests_per_second: f64, burst: u32) -> Self {
+        let burst = f64::from(burst.max(1));
+        Self {
+            requests_per_second,
+            burst,
+            state: Mutex::new((burst, Instant::now())),
+        }
+    }
+
+    fn try_acquire(&self) -> bool {
+        let mut state = self.state.lock().expect("not poisoned");
+        let (tokens, last) = &mut *state;
+        let now = Instant::now();
+        *tokens = (*tokens + now.duration_since(*last).as_secs_f64() * self.requests_per_second)
+            .min(self.burst);
+        *last = now;
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// The request counters of the service, see the `/metrics` endpoint.
+#[derive(Default)]
+struct Metrics {
+    valid: AtomicU64,
+    invalid: AtomicU64,
+    malformed: AtomicU64,
+    unknown_key: AtomicU64,
+    rate_limited: AtomicU64,
+    verification_micros: AtomicU64,
+}
+
+impl Metrics {
+    fn render(&self) -> String {
+        let counter = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        let mut out = String::new();
+        out.push_str("# HELP co_circom_verify_requests_total Verification requests by result.\n");
+        out.push_str("# TYPE co_circom_verify_requests_total counter\n");
+        for (result, value) in [
+            ("valid", counter(&self.valid)),
+            ("invalid", counter(&self.invalid)),
+            ("malformed", counter(&self.malformed)),
+            ("unknown_key", counter(&self.unknown_key)),
+            ("rate_limited", counter(&self.rate_limited)),
+        ] {
+            out.push_str(&format!(
+                "co_circom_verify_requests_total{{result=\"{result}\"}} {value}\n"
+            ));
+        }
+        out.push_str(
+            "# HELP co_circom_verify_seconds_total Time spent verifying proofs in seconds.\n",
+        );
+        out.push_str("# TYPE co_circom_verify_seconds_total counter\n");
+        out.push_str(&format!(
+            "co_circom_verify_seconds_total {}\n",
+            counter(&self.verification_micros) as f64 / 1e6
+        ));
+        out
+    }
+}
+
+/// The state of the verification service.
+pub struct VerifyService {
+    verifiers: HashMap<String, Box<dyn Verifier>>,
+    rate_limiter: Option<RateLimiter>,
+    metrics: Metrics,
+}
+
+impl VerifyService {
+    /// Loads all keys of the registry. `requests_per_second` limits the verification requests over all clients, with bursts of up to `burst` requests.
+    pub fn new(
+        registry: &KeyRegistry,
+        requests_per_second: Option<f64>,
+        burst: u32,
+    ) -> eyre::Result<Self> {
+        let mut verifiers = HashMap::new();
+        for key in registry.keys.iter() {
+            let verifier = match key.curve {
+                MPCCurve::BN254 => load_verifier::<Bn254>(key),
+                MPCCurve::BLS12_381 => load_verifier::<Bls12_381>(key),
+                MPCCurve::BLS12_377 => load_verifier::<Bls12_377>(key),
+            }
+            .with_context(|| format!("while loading verification key {}", key.id))?;
+            if verifiers.insert(key.id.clone(), verifier).is_some() {
+                eyre::bail!("the verification key id {} is registered twice", key.id);
+            }
+        }
+        tracing::info!("loaded {} verification keys", verifiers.len());
+        Ok(Self {
+            verifiers,
+            rate_limiter: requests_per_second.map(|rps| RateLimiter::new(rps, burst)),
+            metrics: Metrics::default(),
+        })
+    }
+
+    /// Verifies the proof of the request. Returns an [ApiError] if the request is rate limited, the key is unknown, or the request is malformed.
+    pub fn verify(&self, request: VerifyRequest) -> Result<VerifyResponse, ApiError> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            if !rate_limiter.try_acquire() {
+                self.metrics.rate_limited.fetch_add(1, Ordering::Relaxed);
+                return Err(ApiError::RateLimited);
+            }
+        }
+        let Some(verifier) = self.verifiers.get(&request.vk_id) else {
+            self.metrics.unknown_key.fetch_add(1, Ordering::Relaxed);
+            return Err(ApiError::UnknownKey(request.vk_id));
+        };
+        let start = Instant::now();
+        let result = verifier.verify(request.proof, &request.public_inputs);
+        self.metrics.verification_micros.fetch_add(
+            start.elapsed().as_micros().try_into().unwrap_or(u64::MAX),
+            Ordering::Relaxed,
+        );
+        match result {
+            Ok(valid) => {
+                let counter = if valid {
+                    &self.metrics.valid
+                } else {
+                    &self.metrics.invalid
+                };
+                counter.fetch_add(1, Ordering::Relaxed);
+                Ok(VerifyResponse {
+                    vk_id: request.vk_id,
+                    valid,
+                })
+            }
+            Err(err) => {
+                self.metrics.malformed.fetch_add(1, Ordering::Relaxed);
+                Err(ApiError::Malformed(err))
+            }
+        }
+    }
+}
+
+/// The error returned by the HTTP API. The error is returned as plain text body.
+#[derive(Debug)]
+pub enum ApiError {
+    /// The request exceeded the rate limit
+    RateLimited,
+    /// The requested verification key is not registered
+    UnknownKey(String),
+    /// The proof or the public inputs could not be parsed
+    Malformed(eyre::Report),
+    /// The verification task failed
+    Internal(eyre::Report),
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        match self {
+            ApiError::RateLimited => {
+                (StatusCode::TOO_MANY_REQUESTS, "rate limit exceeded").into_response()
+            }
+            ApiError::UnknownKey(id) => (
+                StatusCode::NOT_FOUND,
+                format!("unknown verification key {id}"),
+            )
+                .into_response(),
+            ApiError::Malformed(err) => {
+                (StatusCode::BAD_REQUEST, format!("{err:#}")).into_response()
+            }
+            ApiError::Internal(err) => {
+                tracing::error!("{err:?}");
+                (StatusCode::INTERNAL_SERVER_ERROR, format!("{err:#}")).into_response()
+            }
+        }
+    }
+}
+
+async fn health() -> StatusCode {
+    StatusCode::OK
+}
+
+async fn verify(
+    State(service): State<Arc<VerifyService>>,
+    Json(request): Json<VerifyRequest>,
+) -> Result<Json<VerifyResponse>, ApiError> {
+    // the pairings are expensive, so we run them outside of the async runtime
+    let response = tokio::task::spawn_blocking(move || service.verify(request))
+        .await
+        .map_err(|e| ApiError::Internal(eyre::eyre!("verification task failed: {e}")))??;
+    Ok(Json(response))
+}
+
+async fn metrics(State(service): State<Arc<VerifyService>>) -> String {
+    service.metrics.render()
+}
+
+/// Builds the [Router] of the HTTP API for the given [VerifyService].
+pub fn router(service: Arc<VerifyService>) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/verify", post(verify))
+        .route("/metrics", get(metrics))
+        .with_state(service)
+}
+
+/// Loads the key registry and serves the HTTP API on the configured address until the process is terminated.
+pub async fn serve(config: VerifyServerConfig) -> eyre::Result<()> {
+    let registry = KeyRegistry::from_file(&config.registry)?;
+    let service = tokio::task::spawn_blocking(move || {
+        VerifyService::new(&registry, config.requests_per_second, config.burst)
+    })
+    .await
+    .context("while loading verification keys")??;
+    serve_service(config.bind_addr, Arc::new(service)).await
+}
+
+async fn serve_service(bind_addr: SocketAddr, service: Arc<VerifyService>) -> eyre::Result<()> {
+    let listener = tokio::net::TcpListener::bind(bind_addr)
+        .await
+        .context(format!("while binding to {bind_addr}"))?;
+    tracing::info!("verification service listening on {bind_addr}");
+    axum::serve(listener, router(service))
+        .await
+        .context("while serving HTTP API")
+}
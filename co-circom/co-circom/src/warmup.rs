@@ -0,0 +1,139 @@
+//! An optional warm-up phase at the start of a session.
+//!
+//! The first proof of a session is slower than the following ones: the proving key is read from disk instead of the page cache, the threads of the thread pool are spawned and the CPUs clock up, and the congestion windows of the connections to the other parties are still small. The [WarmUp] front-loads these effects, so that the measured latencies of a session and of benchmarks reflect the steady-state performance.
+//!
+//! The warm-up does not use the MPC protocols, so it neither consumes correlated randomness nor is its dummy traffic added to the transcript of the network. Its duration and traffic are reported separately in the [WarmUpReport] instead of being attributed to the proof generation.
+use std::{
+    hint::black_box,
+    io::Read,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use color_eyre::eyre::{self, Context};
+use mpc_core::protocols::{rep3::network::Rep3MpcNet, shamir::network::ShamirMpcNet};
+use mpc_net::WarmUpStats;
+use serde::Serialize;
+
+use crate::storage::Location;
+
+/// The default duration of the warm-up in milliseconds.
+pub const DEFAULT_DURATION_MS: u64 = 1000;
+
+/// The maximum size in bytes of the dummy messages exchanged with the other parties.
+pub const MAX_MESSAGE_LEN: usize = 1 << 20;
+
+/// The summary of a warm-up.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WarmUpReport {
+    /// The time the warm-up took in milliseconds
+    pub duration_ms: f64,
+    /// The number of bytes of the touched files
+    pub touched_bytes: u64,
+    /// The time the thread pool was kept busy in milliseconds
+    pub thread_pool_ms: f64,
+    /// The statistics of the dummy traffic, if the network was warmed up
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<WarmUpStats>,
+}
+
+/// A running warm-up phase, see the [module documentation](self).
+#[derive(Debug)]
+pub struct WarmUp {
+    start: Instant,
+    deadline: Instant,
+    report: WarmUpReport,
+}
+
+impl WarmUp {
+    /// Starts a warm-up of the given duration. The local files are read once to load them into the page cache, and the threads of the rayon thread pool are kept busy for a quarter of the duration. The remaining time is spent warming up the network, see [WarmUp::finish_rep3] and [WarmUp::finish_shamir].
+    pub fn start(duration: Duration, files: &[&Path]) -> eyre::Result<Self> {
+        let _span = tracing::info_span!("warm_up").entered();
+        let start = Instant::now();
+        let mut report = WarmUpReport::default();
+        for file in files {
+            report.touched_bytes +=
+                touch_file(file).with_context(|| format!("while touching {}", file.display()))?;
+        }
+        let heat_start = Instant::now();
+        heat_thread_pool(heat_start + duration / 4);
+        report.thread_pool_ms = heat_start.elapsed().as_micros() as f64 / 1000.;
+        Ok(Self {
+            start,
+            deadline: start + duration,
+            report,
+        })
+    }
+
+    /// Exchanges dummy messages with the other REP3 parties until the deadline of the warm-up has passed for one of the parties and finishes the warm-up.
+    pub fn finish_rep3(mut self, net: &mut Rep3MpcNet) -> eyre::Result<WarmUpReport> {
+        let stats = {
+            let _span = tracing::info_span!("warm_up").entered();
+            net.warm_up(self.deadline, MAX_MESSAGE_LEN)
+                .context("while warming up the network")?
+        };
+        self.report.network = Some(stats);
+        Ok(self.finish())
+    }
+
+    /// Exchanges dummy messages with the other Shamir parties until the deadline of the warm-up has passed for one of the parties and finishes the warm-up.
+    pub fn finish_shamir(mut self, net: &mut ShamirMpcNet) -> eyre::Result<WarmUpReport> {
+        let stats = {
+            let _span = tracing::info_span!("warm_up").entered();
+            net.warm_up(self.deadline, MAX_MESSAGE_LEN)
+                .context("while warming up the network")?
+        };
+        self.report.network = Some(stats);
+        Ok(self.finish())
+    }
+
+    /// Finishes the warm-up without warming up the network and logs its summary.
+    pub fn finish(mut self) -> WarmUpReport {
+        self.report.duration_ms = self.start.elapsed().as_micros() as f64 / 1000.;
+        match &self.report.network {
+            Some(stats) => tracing::info!(
+                "Warm-up took {:.3} ms: touched {} bytes, exchanged {} dummy messages ({} bytes sent)",
+                self.report.duration_ms,
+                self.report.touched_bytes,
+                stats.rounds,
+                stats.bytes_sent
+            ),
+            None => tracing::info!(
+                "Warm-up took {:.3} ms: touched {} bytes",
+                self.report.duration_ms,
+                self.report.touched_bytes
+            ),
+        }
+        self.report
+    }
+}
+
+/// Reads a local file once to load its pages into the page cache and returns its size. Remote objects and streams are skipped, as they can not be read twice.
+pub fn touch_file(path: &Path) -> eyre::Result<u64> {
+    let location = Location::parse(path)?;
+    if !matches!(location, Location::Local(_)) || location.is_stream() {
+        return Ok(0);
+    }
+    let mut file = location.open()?;
+    let mut buf = vec![0u8; 1 << 20];
+    let mut touched = 0;
+    loop {
+        match file.read(&mut buf)? {
+            0 => return Ok(touched),
+            n => touched += n as u64,
+        }
+    }
+}
+
+/// Keeps all threads of the rayon thread pool busy until the `deadline`, so that the threads are spawned and the CPUs clock up before the session.
+pub fn heat_thread_pool(deadline: Instant) {
+    rayon::broadcast(|_| {
+        let mut x = 0u64;
+        while Instant::now() < deadline {
+            for i in 0..1024 {
+                x = black_box(x.wrapping_mul(6364136223846793005).wrapping_add(i));
+            }
+        }
+        black_box(x);
+    });
+}
@@ -0,0 +1,379 @@
+//! Collaborative folding of R1CS instances for incrementally verifiable computation.
+//!
+//! This module implements the folding scheme of [Nova](https://eprint.iacr.org/2021/370) over secret-shared witnesses. A circom circuit is interpreted as a relaxed R1CS `(Az) ∘ (Bz) = u · (Cz) + E`, where `z = (u, x, W)`. A [SharedWitness] of the circuit is exported to a relaxed instance with `u = 1` and `E = 0` by [CoFolding::export]. Two relaxed instances of the same circuit are folded into one with [CoFolding::fold], so repeated executions of a computation can be accumulated by the parties without ever reconstructing a witness. Only the commitments to the witnesses and the cross term are opened.
+//!
+//! After the last folding step, [CoFolding::decider_witness] lays out the accumulated instance and witness as a [SharedWitness] for a decider circuit, which is then proven with [CoGroth16](crate::CoGroth16).
+use std::marker::PhantomData;
+
+use ark_ec::pairing::Pairing;
+use ark_ec::scalar_mul::variable_base::VariableBaseMSM;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{One, PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use circom_types::traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge};
+use circom_types::R1CS;
+use co_circom_snarks::{ConstraintEvaluator, SharedWitness};
+use eyre::Result;
+use rand::{CryptoRng, Rng};
+use sha2::{Digest, Sha256};
+use tracing::instrument;
+
+use crate::mpc::CircomGroth16Prover;
+
+const TRANSCRIPT_LABEL: &[u8] = b"co-groth16 folding";
+
+/// The constraint matrices of a circom circuit, interpreted as relaxed R1CS.
+#[derive(Debug, Clone)]
+pub struct RelaxedR1CS<F: PrimeField> {
+    a: ConstraintEvaluator<F>,
+    b: ConstraintEvaluator<F>,
+    c: ConstraintEvaluator<F>,
+    num_public: usize,
+    num_private: usize,
+}
+
+impl<F: PrimeField> RelaxedR1CS<F> {
+    /// Creates the relaxed R1CS from a parsed circom R1CS file.
+    pub fn from_r1cs<P>(r1cs: &R1CS<P>) -> Self
+    where
+        P: Pairing<ScalarField = F> + CircomArkworksPairingBridge,
+        P::BaseField: CircomArkworksPrimeFieldBridge,
+        P::ScalarField: CircomArkworksPrimeFieldBridge,
+    {
+        let flip = |row: &Vec<(usize, F)>| row.iter().map(|(i, c)| (*c, *i)).collect::<Vec<_>>();
+        let (a, (b, c)): (Vec<_>, (Vec<_>, Vec<_>)) = r1cs
+            .constraints
+            .iter()
+            .map(|(a, b, c)| (flip(a), (flip(b), flip(c))))
+            .unzip();
+        Self {
+            a: ConstraintEvaluator::deduplicated(&a),
+            b: ConstraintEvaluator::deduplicated(&b),
+            c: ConstraintEvaluator::deduplicated(&c),
+            num_public: r1cs.num_inputs,
+            num_private: r1cs.num_aux,
+        }
+    }
+
+    /// Returns the number of constraints, which is also the length of the error vector `E`.
+    pub fn num_constraints(&self) -> usize {
+        self.a.num_constraints()
+    }
+
+    /// Returns the number of public inputs, including the constant 1 (or `u` for relaxed instances) at position 0.
+    pub fn num_public(&self) -> usize {
+        self.num_public
+    }
+
+    /// Returns the number of private witness elements.
+    pub fn num_private(&self) -> usize {
+        self.num_private
+    }
+
+    /// Checks whether a plain witness satisfies a relaxed instance, including the commitments. This is meant for testing and debugging, as the parties never hold the plain witness.
+    pub fn is_satisfied<C>(
+        &self,
+        ck: &CommitmentKey<C>,
+        instance: &RelaxedR1CSInstance<C>,
+        witness: &RelaxedR1CSWitness<F>,
+    ) -> bool
+    where
+        C: CurveGroup<ScalarField = F>,
+    {
+        if instance.public_inputs.len() != self.num_public
+            || witness.w.len() != self.num_private
+            || witness.e.len() != self.num_constraints()
+        {
+            return false;
+        }
+        let u = instance.u();
+        let az = self.a.evaluate_plain(&instance.public_inputs, &witness.w);
+        let bz = self.b.evaluate_plain(&instance.public_inputs, &witness.w);
+        let cz = self.c.evaluate_plain(&instance.public_inputs, &witness.w);
+        let constraints_hold = az
+            .iter()
+            .zip(bz.iter())
+            .zip(cz.iter())
+            .zip(witness.e.iter())
+            .all(|(((a, b), c), e)| *a * b == u * c + e);
+        constraints_hold
+            && ck.commit_plain(&witness.w, witness.r_w) == instance.comm_w
+            && ck.commit_plain(&witness.e, witness.r_e) == instance.comm_e
+    }
+}
+
+/// The public parameters of the Pedersen commitments to the witness, the error vector, and the cross term.
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct CommitmentKey<C: CurveGroup> {
+    generators: Vec<C::Affine>,
+    h: C::Affine,
+}
+
+impl<C: CurveGroup> CommitmentKey<C> {
+    /// Samples a commitment key for a [RelaxedR1CS] from the provided rng. All parties need to use the same key, e.g., by sampling it with a seed agreed on in advance.
+    pub fn setup<F, R>(r1cs: &RelaxedR1CS<F>, rng: &mut R) -> Self
+    where
+        F: PrimeField,
+        R: Rng + CryptoRng,
+    {
+        let len = r1cs.num_private().max(r1cs.num_constraints());
+        let generators = (0..len).map(|_| C::rand(rng)).collect::<Vec<_>>();
+        Self {
+            generators: C::normalize_batch(&generators),
+            h: C::rand(rng).into_affine(),
+        }
+    }
+
+    fn commit_plain(&self, values: &[C::ScalarField], blinding: C::ScalarField) -> C {
+        C::msm_unchecked(&self.generators[..values.len()], values) + self.h * blinding
+    }
+}
+
+/// A relaxed R1CS instance. The instance is public, i.e., every party holds the same instance.
+#[derive(Debug, Clone, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct RelaxedR1CSInstance<C: CurveGroup> {
+    /// The commitment to the witness `W`
+    pub comm_w: C,
+    /// The commitment to the error vector `E`
+    pub comm_e: C,
+    /// The public inputs. The position 0 holds the scalar `u` instead of the constant 1.
+    pub public_inputs: Vec<C::ScalarField>,
+}
+
+impl<C: CurveGroup> RelaxedR1CSInstance<C> {
+    /// Returns the scalar `u` of the relaxed instance.
+    pub fn u(&self) -> C::ScalarField {
+        self.public_inputs[0]
+    }
+}
+
+/// The (shared) witness of a [RelaxedR1CSInstance].
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct RelaxedR1CSWitness<S: CanonicalSerialize + CanonicalDeserialize> {
+    /// The private witness `W`
+    pub w: Vec<S>,
+    /// The error vector `E`
+    pub e: Vec<S>,
+    /// The blinding factor of the commitment to `W`
+    pub r_w: S,
+    /// The blinding factor of the commitment to `E`
+    pub r_e: S,
+}
+
+impl<S: CanonicalSerialize + CanonicalDeserialize> RelaxedR1CSWitness<S> {
+    /// Creates a witness from its parts.
+    pub fn new(w: Vec<S>, e: Vec<S>, r_w: S, r_e: S) -> Self {
+        Self { w, e, r_w, r_e }
+    }
+}
+
+/// The collaborative folding scheme, which uses the MPC driver of [CoGroth16](crate::CoGroth16).
+pub struct CoFolding<P: Pairing, T: CircomGroth16Prover<P>> {
+    driver: T,
+    phantom_data: PhantomData<P>,
+}
+
+impl<P: Pairing, T: CircomGroth16Prover<P>> CoFolding<P, T> {
+    /// Creates a new [CoFolding] protocol with a given MPC driver.
+    pub fn new(driver: T) -> Self {
+        Self {
+            driver,
+            phantom_data: PhantomData,
+        }
+    }
+
+    /// Returns the MPC driver, e.g., to create the final proof with [CoGroth16](crate::CoGroth16).
+    pub fn into_driver(self) -> T {
+        self.driver
+    }
+
+    fn commit(
+        &mut self,
+        ck: &CommitmentKey<P::G1>,
+        values: &[T::ArithmeticShare],
+        blinding: T::ArithmeticShare,
+    ) -> Result<P::G1> {
+        let mut comm = T::msm_public_points(&ck.generators[..values.len()], values);
+        let blind = T::scalar_mul_public_point(&ck.h.into_group(), blinding);
+        T::add_assign_points(&mut comm, &blind);
+        Ok(self.driver.open_point(&comm)?)
+    }
+
+    fn evaluate(
+        party_id: T::PartyID,
+        matrix: &ConstraintEvaluator<P::ScalarField>,
+        public_inputs: &[P::ScalarField],
+        private_witness: &[T::ArithmeticShare],
+    ) -> Vec<T::ArithmeticShare> {
+        matrix.evaluate(0, |constraint| {
+            T::evaluate_constraint(party_id, constraint, public_inputs, private_witness)
+        })
+    }
+
+    /// Exports a shared witness of the circuit to a relaxed instance with `u = 1` and `E = 0`. Opens the commitment to the witness.
+    #[instrument(level = "debug", name = "Folding - export", skip_all)]
+    pub fn export(
+        &mut self,
+        r1cs: &RelaxedR1CS<P::ScalarField>,
+        ck: &CommitmentKey<P::G1>,
+        witness: SharedWitness<P::ScalarField, T::ArithmeticShare>,
+    ) -> Result<(
+        RelaxedR1CSInstance<P::G1>,
+        RelaxedR1CSWitness<T::ArithmeticShare>,
+    )> {
+        if witness.public_inputs.len() != r1cs.num_public() {
+            eyre::bail!(
+                "amount of public inputs do not match with provided r1cs! Expected {}, but got {}",
+                r1cs.num_public(),
+                witness.public_inputs.len()
+            )
+        }
+        if witness.witness.len() != r1cs.num_private() {
+            eyre::bail!(
+                "amount of private inputs do not match with provided r1cs! Expected {}, but got {}",
+                r1cs.num_private(),
+                witness.witness.len()
+            )
+        }
+        let r_w = self.driver.rand()?;
+        let comm_w = self.commit(ck, &witness.witness, r_w)?;
+        let instance = RelaxedR1CSInstance {
+            comm_w,
+            comm_e: P::G1::zero(),
+            public_inputs: witness.public_inputs,
+        };
+        let witness = RelaxedR1CSWitness::new(
+            witness.witness,
+            vec![T::ArithmeticShare::default(); r1cs.num_constraints()],
+            r_w,
+            T::ArithmeticShare::default(),
+        );
+        Ok((instance, witness))
+    }
+
+    /// Folds two relaxed instances of the same circuit into one. The cross term `T = Az1 ∘ Bz2 + Az2 ∘ Bz1 - u1 · Cz2 - u2 · Cz1` is computed in MPC, only its commitment is opened. The folding challenge is derived from both instances and the commitment to the cross term.
+    ///
+    /// If both inputs are satisfying, the folded instance is satisfying as well.
+    #[instrument(level = "debug", name = "Folding - fold", skip_all)]
+    pub fn fold(
+        &mut self,
+        r1cs: &RelaxedR1CS<P::ScalarField>,
+        ck: &CommitmentKey<P::G1>,
+        (instance1, witness1): (
+            &RelaxedR1CSInstance<P::G1>,
+            &RelaxedR1CSWitness<T::ArithmeticShare>,
+        ),
+        (instance2, witness2): (
+            &RelaxedR1CSInstance<P::G1>,
+            &RelaxedR1CSWitness<T::ArithmeticShare>,
+        ),
+    ) -> Result<(
+        RelaxedR1CSInstance<P::G1>,
+        RelaxedR1CSWitness<T::ArithmeticShare>,
+    )> {
+        for (instance, witness) in [(instance1, witness1), (instance2, witness2)] {
+            if instance.public_inputs.len() != r1cs.num_public()
+                || witness.w.len() != r1cs.num_private()
+                || witness.e.len() != r1cs.num_constraints()
+            {
+                eyre::bail!("relaxed instance does not match with provided r1cs");
+            }
+        }
+        let party_id = self.driver.get_party_id();
+        let (u1, u2) = (instance1.u(), instance2.u());
+        let x1 = &instance1.public_inputs;
+        let x2 = &instance2.public_inputs;
+
+        let eval_span = tracing::debug_span!("evaluate constraints").entered();
+        let az1 = Self::evaluate(party_id, &r1cs.a, x1, &witness1.w);
+        let bz1 = Self::evaluate(party_id, &r1cs.b, x1, &witness1.w);
+        let cz1 = Self::evaluate(party_id, &r1cs.c, x1, &witness1.w);
+        let az2 = Self::evaluate(party_id, &r1cs.a, x2, &witness2.w);
+        let bz2 = Self::evaluate(party_id, &r1cs.b, x2, &witness2.w);
+        let cz2 = Self::evaluate(party_id, &r1cs.c, x2, &witness2.w);
+        eval_span.exit();
+
+        let cross_term_span = tracing::debug_span!("cross term with networking").entered();
+        let n = r1cs.num_constraints();
+        let lhs = [az1, az2].concat();
+        let rhs = [bz2, bz1].concat();
+        let products = self.driver.mul_vec(&lhs, &rhs)?;
+        let cross_term = (0..n)
+            .map(|i| {
+                let mut t = products[i] + products[n + i];
+                t -= scale(cz1[i], u2);
+                t -= scale(cz2[i], u1);
+                t
+            })
+            .collect::<Vec<_>>();
+        let r_t = self.driver.rand()?;
+        let comm_t = self.commit(ck, &cross_term, r_t)?;
+        cross_term_span.exit();
+
+        let r = challenge(instance1, instance2, &comm_t)?;
+        let r_squared = r.square();
+
+        let instance = RelaxedR1CSInstance {
+            comm_w: instance1.comm_w + instance2.comm_w * r,
+            comm_e: instance1.comm_e + comm_t * r + instance2.comm_e * r_squared,
+            public_inputs: x1.iter().zip(x2.iter()).map(|(a, b)| *a + r * b).collect(),
+        };
+        let w = witness1
+            .w
+            .iter()
+            .zip(witness2.w.iter())
+            .map(|(a, b)| *a + scale(*b, r))
+            .collect();
+        let e = witness1
+            .e
+            .iter()
+            .zip(cross_term.iter())
+            .zip(witness2.e.iter())
+            .map(|((e1, t), e2)| *e1 + scale(*t, r) + scale(*e2, r_squared))
+            .collect();
+        let witness = RelaxedR1CSWitness::new(
+            w,
+            e,
+            witness1.r_w + scale(witness2.r_w, r),
+            witness1.r_e + scale(r_t, r) + scale(witness2.r_e, r_squared),
+        );
+        Ok((instance, witness))
+    }
+
+    /// Lays out a folded instance and its witness as the [SharedWitness] of a decider circuit, which is proven with [CoGroth16](crate::CoGroth16) after the last folding step.
+    ///
+    /// The public inputs of the decider circuit are the constant 1, followed by the public inputs of the relaxed instance (starting with `u`). The private inputs are `W` followed by `E`. The decider circuit has to check `(Az) ∘ (Bz) = u · (Cz) + E` for the folded circuit. Binding the proof to the commitments [comm_w](RelaxedR1CSInstance::comm_w) and [comm_e](RelaxedR1CSInstance::comm_e) is up to the application, e.g., by opening them to the verifier.
+    pub fn decider_witness(
+        instance: &RelaxedR1CSInstance<P::G1>,
+        witness: RelaxedR1CSWitness<T::ArithmeticShare>,
+    ) -> SharedWitness<P::ScalarField, T::ArithmeticShare> {
+        let mut public_inputs = Vec::with_capacity(instance.public_inputs.len() + 1);
+        public_inputs.push(P::ScalarField::one());
+        public_inputs.extend_from_slice(&instance.public_inputs);
+        let mut private = witness.w;
+        private.extend(witness.e);
+        SharedWitness {
+            public_inputs,
+            witness: private,
+        }
+    }
+}
+
+fn scale<F: PrimeField, S: ark_poly::domain::DomainCoeff<F>>(mut share: S, factor: F) -> S {
+    share *= factor;
+    share
+}
+
+fn challenge<C: CurveGroup>(
+    instance1: &RelaxedR1CSInstance<C>,
+    instance2: &RelaxedR1CSInstance<C>,
+    comm_t: &C,
+) -> Result<C::ScalarField> {
+    let mut bytes = TRANSCRIPT_LABEL.to_vec();
+    instance1.serialize_compressed(&mut bytes)?;
+    instance2.serialize_compressed(&mut bytes)?;
+    comm_t.serialize_compressed(&mut bytes)?;
+    Ok(C::ScalarField::from_le_bytes_mod_order(&Sha256::digest(
+        bytes,
+    )))
+}
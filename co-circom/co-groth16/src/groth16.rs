@@ -3,18 +3,20 @@ use ark_ec::pairing::Pairing;
 use ark_ec::scalar_mul::variable_base::VariableBaseMSM;
 use ark_ec::{AffineRepr, CurveGroup};
 use ark_ff::{FftField, PrimeField};
-use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
-use circom_types::groth16::{ConstraintMatrix, Groth16Proof, ZKey};
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use circom_types::groth16::Groth16Proof;
 use circom_types::traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge};
-use co_circom_snarks::SharedWitness;
+use co_circom_snarks::{ConstraintEvaluator, SharedWitness, SparseSharedWitness};
 use eyre::Result;
-use mpc_core::protocols::rep3::network::{IoContext, Rep3MpcNet};
+use mpc_core::protocols::rep3::network::{IoContext, Rep3MpcNet, TranscriptDigest};
 use mpc_core::protocols::shamir::network::ShamirMpcNet;
 use mpc_core::protocols::shamir::{ShamirPreprocessing, ShamirProtocol};
 use mpc_net::config::NetworkConfig;
 use num_traits::identities::One;
 use num_traits::ToPrimitive;
 use rayon::prelude::*;
+use std::borrow::Cow;
 use std::marker::PhantomData;
 use std::sync::Arc;
 use std::time::Instant;
@@ -25,6 +27,11 @@ use crate::mpc::plain::PlainGroth16Driver;
 use crate::mpc::rep3::Rep3Groth16Driver;
 use crate::mpc::shamir::ShamirGroth16Driver;
 use crate::mpc::CircomGroth16Prover;
+use crate::msm;
+use crate::proving_key::{PointQuery, ProvingKey};
+
+/// The maximal number of MSMs that run concurrently in the MSM phase: the A, B in G1, B in G2, L and H queries are computed in parallel, and the MSM of a query computes the MSM of the public inputs and the (with REP3 two) MSMs of the private witness in parallel.
+const CONCURRENT_MSMS: usize = 15;
 
 macro_rules! rayon_join {
     ($t1: expr, $t2: expr, $t3: expr) => {{
@@ -65,19 +72,11 @@ then if log2(domain_size) equals s we take as root of unity q^2, and else we tak
 #[instrument(level = "debug", name = "root of unity", skip_all)]
 fn root_of_unity_for_groth16<F: PrimeField + FftField>(
     pow: usize,
-    domain: &mut GeneralEvaluationDomain<F>,
+    domain: &mut Radix2EvaluationDomain<F>,
 ) -> F {
     let (q, roots) = co_circom_snarks::utils::roots_of_unity::<F>();
-    match domain {
-        GeneralEvaluationDomain::Radix2(domain) => {
-            domain.group_gen = roots[pow];
-            domain.group_gen_inv = domain.group_gen.inverse().expect("can compute inverse");
-        }
-        GeneralEvaluationDomain::MixedRadix(domain) => {
-            domain.group_gen = roots[pow];
-            domain.group_gen_inv = domain.group_gen.inverse().expect("can compute inverse");
-        }
-    };
+    domain.group_gen = roots[pow];
+    domain.group_gen_inv = domain.group_gen.inverse().expect("can compute inverse");
     if F::TWO_ADICITY.to_u64().unwrap() == domain.log_size_of_group() {
         q.square()
     } else {
@@ -85,9 +84,66 @@ fn root_of_unity_for_groth16<F: PrimeField + FftField>(
     }
 }
 
+/// The result of the witness map phase of the Groth16 prover, see [`CoGroth16::witness_map`].
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct WitnessMapArtifact<F: PrimeField> {
+    /// The additive share of the evaluations of the quotient polynomial h on the coset of the domain. The shares of the parties are *NOT* replicated, i.e., this vector must only be used in the MSM with the h query.
+    pub h: Vec<F>,
+}
+
+/// The result of the MSM phase of the Groth16 prover, see [`CoGroth16::msm`]. All contained values are still shared, the proof is reconstructed in [`CoGroth16::openings`].
+#[derive(CanonicalSerialize, CanonicalDeserialize)]
+pub struct MsmArtifact<P: Pairing, T: CircomGroth16Prover<P>> {
+    /// The blinding value r
+    pub r: T::ArithmeticShare,
+    /// The blinding value s
+    pub s: T::ArithmeticShare,
+    /// The shared proof element A
+    pub g_a: T::PointShare<P::G1>,
+    /// The shared proof element B in G1
+    pub g1_b: T::PointShare<P::G1>,
+    /// The shared proof element B in G2
+    pub g2_b: T::PointShare<P::G2>,
+    /// The part of the proof element C that is independent of A and B, i.e., the MSM of the l query minus r * s * delta
+    pub g_c: T::PointShare<P::G1>,
+    /// The additive share of the MSM of the h query
+    pub h_acc: P::G1,
+}
+
+/// The private witness used by the prover, either dense or only the nonzero elements with their indices, see [`SparseSharedWitness`].
+enum AuxAssignment<S> {
+    Dense(Vec<S>),
+    Sparse {
+        num_witness: usize,
+        indices: Vec<usize>,
+        values: Vec<S>,
+    },
+}
+
+impl<S: Copy + Default> AuxAssignment<S> {
+    /// Returns the dense witness, expanding the sparse witness with the all-zero share if necessary.
+    fn dense(&self) -> Cow<'_, [S]> {
+        match self {
+            AuxAssignment::Dense(witness) => Cow::Borrowed(witness),
+            AuxAssignment::Sparse {
+                num_witness,
+                indices,
+                values,
+            } => {
+                let mut witness = vec![S::default(); *num_witness];
+                for (index, value) in indices.iter().zip(values.iter()) {
+                    witness[*index] = *value;
+                }
+                Cow::Owned(witness)
+            }
+        }
+    }
+}
+
 /// A Groth16 proof protocol that uses a collaborative MPC protocol to generate the proof.
 pub struct CoGroth16<P: Pairing, T: CircomGroth16Prover<P>> {
     pub(crate) driver: T,
+    msm_memory_budget: Option<usize>,
     phantom_data: PhantomData<P>,
 }
 
@@ -100,78 +156,246 @@ where
     pub fn new(driver: T) -> Self {
         Self {
             driver,
+            msm_memory_budget: None,
             phantom_data: PhantomData,
         }
     }
 
+    /// Bounds the peak memory of the MSMs to roughly `budget` bytes. The MSMs are split into chunks that are processed one after the other, such that the MSMs running concurrently fit into the budget. This trades parallelism for memory, so only set a budget if the MSMs would otherwise exceed the available memory.
+    pub fn with_msm_memory_budget(mut self, budget: usize) -> Self {
+        self.msm_memory_budget = Some(budget);
+        self
+    }
+
     /// Execute the Groth16 prover using the internal MPC driver.
     /// This version takes the Circom-generated constraint matrices as input and does not re-calculate them.
+    /// The witness map always uses the radix-2 evaluation domain of size `2^zkey.pow()`, other domains are not supported.
     #[instrument(level = "debug", name = "Groth16 - Proof", skip_all)]
-    pub fn prove(
-        mut self,
-        zkey: Arc<ZKey<P>>,
+    pub fn prove<Z: ProvingKey<P> + ?Sized + 'static>(
+        self,
+        zkey: Arc<Z>,
         private_witness: SharedWitness<P::ScalarField, T::ArithmeticShare>,
     ) -> Result<Groth16Proof<P>> {
-        let id = self.driver.get_party_id();
-        tracing::info!("Party {}: starting proof generation..", id);
-        let start = Instant::now();
-        let public_inputs = Arc::new(private_witness.public_inputs);
-        if public_inputs.len() != zkey.n_public + 1 {
+        let (proof, _) = self.prove_inner(
+            zkey,
+            private_witness.public_inputs,
+            AuxAssignment::Dense(private_witness.witness),
+            true,
+        )?;
+        Ok(proof)
+    }
+
+    /// Execute the Groth16 prover using the internal MPC driver with a witness in sparse representation, see [`SparseSharedWitness`].
+    /// The witness map expands the witness to the dense representation, whereas the MSMs only use the nonzero witness elements.
+    #[instrument(level = "debug", name = "Groth16 - Sparse Proof", skip_all)]
+    pub fn prove_sparse<Z: ProvingKey<P> + ?Sized + 'static>(
+        self,
+        zkey: Arc<Z>,
+        private_witness: SparseSharedWitness<P::ScalarField, T::ArithmeticShare>,
+    ) -> Result<Groth16Proof<P>> {
+        private_witness.validate()?;
+        if private_witness.num_witness > zkey.l_query().num_points() {
+            eyre::bail!(
+                "sparse witness has {} elements, but the zkey only has {} private witness elements",
+                private_witness.num_witness,
+                zkey.l_query().num_points()
+            );
+        }
+        let aux_assignment = AuxAssignment::Sparse {
+            num_witness: private_witness.num_witness,
+            indices: private_witness.indices,
+            values: private_witness.values,
+        };
+        let (proof, _) =
+            self.prove_inner(zkey, private_witness.public_inputs, aux_assignment, true)?;
+        Ok(proof)
+    }
+
+    /// Execute the Groth16 prover using the internal MPC driver and additionally return the digest of the protocol transcript.
+    ///
+    /// Before the proof is returned, the parties compare the digests of all messages they sent and received. A mismatch indicates that messages were tampered with or that the parties are out of sync, and aborts the proof generation. The digest is `None` if the MPC driver does not record a transcript.
+    #[instrument(level = "debug", name = "Groth16 - Proof", skip_all)]
+    pub fn prove_with_transcript<Z: ProvingKey<P> + ?Sized + 'static>(
+        self,
+        zkey: Arc<Z>,
+        private_witness: SharedWitness<P::ScalarField, T::ArithmeticShare>,
+    ) -> Result<(Groth16Proof<P>, Option<TranscriptDigest>)> {
+        self.prove_inner(
+            zkey,
+            private_witness.public_inputs,
+            AuxAssignment::Dense(private_witness.witness),
+            true,
+        )
+    }
+
+    /// Execute the Groth16 prover using the internal MPC driver, with the blinding values r and s set to zero.
+    ///
+    /// # Security
+    /// The resulting proof is deterministic and *NOT* zero-knowledge, i.e., it leaks information about the witness. This mode is only meant for benchmarking and for differential debugging, e.g., comparing the proof of an MPC run with a plain run or with deterministic test vectors. Never publish a proof created with this method.
+    #[instrument(
+        level = "debug",
+        name = "Groth16 - Insecure Non-Hiding Proof",
+        skip_all
+    )]
+    pub fn prove_insecure_non_hiding<Z: ProvingKey<P> + ?Sized + 'static>(
+        self,
+        zkey: Arc<Z>,
+        private_witness: SharedWitness<P::ScalarField, T::ArithmeticShare>,
+    ) -> Result<Groth16Proof<P>> {
+        tracing::warn!("creating an insecure non-hiding Groth16 proof with r = s = 0");
+        let (proof, _) = self.prove_inner(
+            zkey,
+            private_witness.public_inputs,
+            AuxAssignment::Dense(private_witness.witness),
+            false,
+        )?;
+        Ok(proof)
+    }
+
+    /// Executes only the witness map phase of the Groth16 prover, i.e., the evaluation of the constraints and the computation of the quotient polynomial h with the FFTs. The result can be passed to [`CoGroth16::msm`].
+    ///
+    /// Together with [`CoGroth16::msm`] and [`CoGroth16::openings`], this allows measuring or replacing individual phases of the prover. [`CoGroth16::prove`] executes all three phases.
+    #[instrument(level = "debug", name = "Groth16 - Witness Map", skip_all)]
+    pub fn witness_map<Z: ProvingKey<P> + ?Sized>(
+        &mut self,
+        zkey: &Z,
+        private_witness: &SharedWitness<P::ScalarField, T::ArithmeticShare>,
+    ) -> Result<WitnessMapArtifact<P::ScalarField>> {
+        Self::check_public_inputs(zkey, &private_witness.public_inputs)?;
+        let h = self.witness_map_from_matrices(
+            zkey,
+            &private_witness.public_inputs,
+            &private_witness.witness,
+        )?;
+        Ok(WitnessMapArtifact { h })
+    }
+
+    /// Executes only the MSM phase of the Groth16 prover with freshly sampled blinding values r and s. The result can be passed to [`CoGroth16::openings`].
+    #[instrument(level = "debug", name = "Groth16 - MSM", skip_all)]
+    pub fn msm<Z: ProvingKey<P> + ?Sized + 'static>(
+        &mut self,
+        zkey: Arc<Z>,
+        private_witness: SharedWitness<P::ScalarField, T::ArithmeticShare>,
+        witness_map: WitnessMapArtifact<P::ScalarField>,
+    ) -> Result<MsmArtifact<P, T>> {
+        let (r, s) = (self.driver.rand()?, self.driver.rand()?);
+        self.msm_with_blinding(
+            zkey,
+            private_witness.public_inputs,
+            AuxAssignment::Dense(private_witness.witness),
+            witness_map,
+            r,
+            s,
+        )
+    }
+
+    /// Executes only the opening phase of the Groth16 prover, which reconstructs the proof from the shared result of the MSM phase.
+    #[instrument(level = "debug", name = "Groth16 - Openings", skip_all)]
+    pub fn openings(&mut self, msm: MsmArtifact<P, T>) -> Result<Groth16Proof<P>> {
+        let network_round = tracing::debug_span!("network round after calc coeff").entered();
+        let (g_a_opened, r_g1_b) = self
+            .driver
+            .open_point_and_scalar_mul(&msm.g_a, &msm.g1_b, msm.r)?;
+        network_round.exit();
+
+        let last_round = tracing::debug_span!("finish - open two points and some adds").entered();
+        let mut g_c = T::scalar_mul_public_point(&g_a_opened, msm.s);
+        T::add_assign_points(&mut g_c, &r_g1_b);
+        T::add_assign_points(&mut g_c, &msm.g_c);
+        let g_c = T::add_points_half_share(g_c, &msm.h_acc);
+
+        let (g_c_opened, g2_b_opened) = self.driver.open_two_points(g_c, msm.g2_b)?;
+        last_round.exit();
+
+        Ok(Groth16Proof {
+            pi_a: g_a_opened.into_affine(),
+            pi_b: g2_b_opened.into_affine(),
+            pi_c: g_c_opened.into_affine(),
+            protocol: "groth16".to_owned(),
+            curve: P::get_circom_name(),
+        })
+    }
+
+    fn check_public_inputs<Z: ProvingKey<P> + ?Sized>(
+        zkey: &Z,
+        public_inputs: &[P::ScalarField],
+    ) -> Result<()> {
+        if public_inputs.len() != zkey.n_public() + 1 {
             eyre::bail!(
                 "amount of public inputs do not match with provided zkey! Expected {}, but got {}",
-                zkey.n_public + 1,
+                zkey.n_public() + 1,
                 public_inputs.len()
             )
         }
+        Ok(())
+    }
 
-        let private_witness = Arc::new(private_witness.witness);
-        let h = self.witness_map_from_matrices(&zkey, &public_inputs, &private_witness)?;
-        let (r, s) = (self.driver.rand()?, self.driver.rand()?);
+    fn prove_inner<Z: ProvingKey<P> + ?Sized + 'static>(
+        mut self,
+        zkey: Arc<Z>,
+        public_inputs: Vec<P::ScalarField>,
+        aux_assignment: AuxAssignment<T::ArithmeticShare>,
+        hiding: bool,
+    ) -> Result<(Groth16Proof<P>, Option<TranscriptDigest>)> {
+        let id = self.driver.get_party_id();
+        tracing::info!("Party {}: starting proof generation..", id);
+        let start = Instant::now();
 
-        let proof = self.create_proof_with_assignment(
-            Arc::clone(&zkey),
-            r,
-            s,
-            h,
-            public_inputs,
-            private_witness,
-        )?;
+        Self::check_public_inputs(zkey.as_ref(), &public_inputs)?;
+        let h =
+            self.witness_map_from_matrices(zkey.as_ref(), &public_inputs, &aux_assignment.dense())?;
+        let witness_map = WitnessMapArtifact { h };
+        let (r, s) = if hiding {
+            (self.driver.rand()?, self.driver.rand()?)
+        } else {
+            (T::ArithmeticShare::default(), T::ArithmeticShare::default())
+        };
+        let msm = self.msm_with_blinding(zkey, public_inputs, aux_assignment, witness_map, r, s)?;
+        let proof = self.openings(msm)?;
+        let transcript_digest = self.driver.check_transcript()?;
 
         let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
         tracing::info!("Party {}: Proof generation took {} ms", id, duration_ms);
-        Ok(proof)
+        Ok((proof, transcript_digest))
     }
 
     fn evaluate_constraint(
         party_id: T::PartyID,
         domain_size: usize,
-        matrix: &ConstraintMatrix<P::ScalarField>,
+        evaluator: &ConstraintEvaluator<P::ScalarField>,
         public_inputs: &[P::ScalarField],
         private_witness: &[T::ArithmeticShare],
     ) -> Vec<T::ArithmeticShare> {
-        let mut result = matrix
-            .par_iter()
-            .with_min_len(256)
-            .map(|x| T::evaluate_constraint(party_id, x, public_inputs, private_witness))
-            .collect::<Vec<_>>();
-        result.resize(domain_size, T::ArithmeticShare::default());
-        result
+        tracing::debug!(
+            "evaluating {} distinct linear combinations of {} constraints",
+            evaluator.num_unique_constraints(),
+            evaluator.num_constraints()
+        );
+        evaluator.evaluate(domain_size, |constraint| {
+            T::evaluate_constraint(party_id, constraint, public_inputs, private_witness)
+        })
     }
 
     #[instrument(level = "debug", name = "witness map from matrices", skip_all)]
-    fn witness_map_from_matrices(
+    fn witness_map_from_matrices<Z: ProvingKey<P> + ?Sized>(
         &mut self,
-        zkey: &ZKey<P>,
+        zkey: &Z,
         public_inputs: &[P::ScalarField],
         private_witness: &[T::ArithmeticShare],
     ) -> Result<Vec<P::ScalarField>> {
-        let num_constraints = zkey.num_constraints;
-        let num_inputs = zkey.n_public + 1;
-        let power = zkey.pow;
-        let mut domain =
-            GeneralEvaluationDomain::<P::ScalarField>::new(num_constraints + num_inputs)
-                .ok_or(eyre::eyre!("Polynomial Degree too large"))?;
+        let num_constraints = zkey.num_constraints();
+        let num_inputs = zkey.n_public() + 1;
+        let power = zkey.pow();
+        // The h query of the zkey is computed by snarkjs for the radix-2 domain of size 2^pow, so this is the only domain the witness map can use
+        let mut domain = Radix2EvaluationDomain::<P::ScalarField>::new(1 << power)
+            .ok_or(eyre::eyre!("Polynomial Degree too large"))?;
         let domain_size = domain.size();
+        if num_constraints + num_inputs > domain_size {
+            eyre::bail!(
+                "zkey is corrupted: {} constraints and inputs do not fit into the domain of size {domain_size}",
+                num_constraints + num_inputs
+            );
+        }
         let party_id = self.driver.get_party_id();
         let eval_constraint_span =
             tracing::debug_span!("evaluate constraints + root of unity computation").entered();
@@ -195,7 +419,7 @@ where
                 let mut result = Self::evaluate_constraint(
                     party_id,
                     domain_size,
-                    &zkey.a_matrix,
+                    zkey.a_matrix(),
                     public_inputs,
                     private_witness,
                 );
@@ -211,7 +435,7 @@ where
                 let result = Self::evaluate_constraint(
                     party_id,
                     domain_size,
-                    &zkey.b_matrix,
+                    zkey.b_matrix(),
                     public_inputs,
                     private_witness,
                 );
@@ -223,14 +447,19 @@ where
         eval_constraint_span.exit();
         let domain = Arc::new(domain);
 
+        // compute the local product of a and b before the FFTs, so that a and b can be transformed in place instead of transforming copies of them
+        let local_mul_vec_span = tracing::debug_span!("c: local_mul_vec").entered();
+        let mut ab = self.driver.local_mul_vec(&a, &b);
+        local_mul_vec_span.exit();
+
         let (a_tx, a_rx) = oneshot::channel();
         let (b_tx, b_rx) = oneshot::channel();
         let (c_tx, c_rx) = oneshot::channel();
         let a_domain = Arc::clone(&domain);
         let b_domain = Arc::clone(&domain);
         let c_domain = Arc::clone(&domain);
-        let mut a_result = a.clone();
-        let mut b_result = b.clone();
+        let mut a_result = a;
+        let mut b_result = b;
         let a_roots = Arc::clone(&roots_to_power_domain);
         let b_roots = Arc::clone(&roots_to_power_domain);
         let c_roots = Arc::clone(&roots_to_power_domain);
@@ -252,9 +481,6 @@ where
             b_span.exit();
         });
 
-        let local_mul_vec_span = tracing::debug_span!("c: local_mul_vec").entered();
-        let mut ab = self.driver.local_mul_vec(a, b);
-        local_mul_vec_span.exit();
         rayon::spawn(move || {
             let ifft_span = tracing::debug_span!("c: ifft in dist pows").entered();
             c_domain.ifft_in_place(&mut ab);
@@ -277,58 +503,120 @@ where
         let b = b_rx.blocking_recv()?;
 
         let compute_ab_span = tracing::debug_span!("compute ab").entered();
+        let mut c = c_rx.blocking_recv()?;
         let local_ab_span = tracing::debug_span!("local part (mul and sub)").entered();
         // same as above. No IO task is run at the moment.
-        let mut ab = self.driver.local_mul_vec(a, b);
+        // the product is written directly into the buffer of c, no additional vector is materialized
+        self.driver.local_mul_sub_vec(&a, &b, &mut c);
         local_ab_span.exit();
-        let c = c_rx.blocking_recv()?;
-        ab.par_iter_mut()
-            .zip_eq(c.par_iter())
-            .with_min_len(512)
-            .for_each(|(a, b)| {
-                *a -= b;
-            });
         compute_ab_span.exit();
-        Ok(ab)
+        Ok(c)
     }
 
     fn calculate_coeff<C>(
         id: T::PartyID,
         initial: T::PointShare<C>,
-        query: &[C::Affine],
+        query: &dyn PointQuery<C::Affine>,
         vk_param: C::Affine,
         input_assignment: &[P::ScalarField],
-        aux_assignment: &[T::ArithmeticShare],
-    ) -> T::PointShare<C>
+        aux_assignment: &AuxAssignment<T::ArithmeticShare>,
+        budget: Option<usize>,
+    ) -> Result<T::PointShare<C>>
     where
         C: CurveGroup<ScalarField = P::ScalarField>,
     {
         let pub_len = input_assignment.len();
+        let chunk_size = Self::chunk_size::<C>(query, budget);
 
         let (priv_acc, pub_acc) = rayon::join(
-            || T::msm_public_points(&query[1 + pub_len..], aux_assignment),
-            || C::msm_unchecked(&query[1..=pub_len], input_assignment),
+            || Self::msm_aux_assignment(query, 1 + pub_len, aux_assignment, budget),
+            || {
+                msm::chunked(
+                    pub_len,
+                    chunk_size,
+                    |range| {
+                        let points = query.points(1 + range.start..1 + range.end)?;
+                        Ok(C::msm_unchecked(&points, &input_assignment[range]))
+                    },
+                    |acc, chunk| *acc += chunk,
+                )
+            },
         );
 
         let mut res = initial;
-        T::add_assign_points_public(id, &mut res, &query[0].into_group());
+        T::add_assign_points_public(id, &mut res, &query.points(0..1)?[0].into_group());
         T::add_assign_points_public(id, &mut res, &vk_param.into_group());
-        T::add_assign_points_public(id, &mut res, &pub_acc);
-        T::add_assign_points(&mut res, &priv_acc);
-        res
+        T::add_assign_points_public(id, &mut res, &pub_acc?);
+        T::add_assign_points(&mut res, &priv_acc?);
+        Ok(res)
     }
 
-    #[instrument(level = "debug", name = "create proof with assignment", skip_all)]
-    fn create_proof_with_assignment(
-        mut self,
-        zkey: Arc<ZKey<P>>,
+    /// Returns the number of points of the query that are processed at once, see [`msm::chunk_size`] and [`PointQuery::max_chunk_size`].
+    fn chunk_size<C: CurveGroup>(
+        query: &dyn PointQuery<C::Affine>,
+        budget: Option<usize>,
+    ) -> usize {
+        budget
+            .map_or(usize::MAX, msm::chunk_size::<C>)
+            .min(query.max_chunk_size())
+    }
+
+    /// Performs the msm between the points of the query starting at `offset` and the private witness. For a sparse witness, only the points of the nonzero witness elements are used.
+    /// With a memory `budget` per MSM, the msm is computed in chunks, see [`CoGroth16::with_msm_memory_budget`].
+    fn msm_aux_assignment<C>(
+        query: &dyn PointQuery<C::Affine>,
+        offset: usize,
+        aux_assignment: &AuxAssignment<T::ArithmeticShare>,
+        budget: Option<usize>,
+    ) -> Result<T::PointShare<C>>
+    where
+        C: CurveGroup<ScalarField = P::ScalarField>,
+    {
+        let chunk_size = Self::chunk_size::<C>(query, budget);
+        let num_points = query.num_points().saturating_sub(offset);
+        match aux_assignment {
+            AuxAssignment::Dense(witness) => msm::chunked(
+                num_points.min(witness.len()),
+                chunk_size,
+                |range| {
+                    let points = query.points(offset + range.start..offset + range.end)?;
+                    Ok(T::msm_public_points(&points, &witness[range]))
+                },
+                T::add_assign_points,
+            ),
+            AuxAssignment::Sparse {
+                indices, values, ..
+            } => msm::chunked(
+                indices.len(),
+                chunk_size,
+                |range| {
+                    let indices = indices[range.clone()]
+                        .iter()
+                        .map(|i| offset + i)
+                        .collect::<Vec<_>>();
+                    let points = query.points_at(&indices)?;
+                    Ok(T::msm_public_points(&points, &values[range]))
+                },
+                T::add_assign_points,
+            ),
+        }
+    }
+
+    #[instrument(level = "debug", name = "msm with blinding", skip_all)]
+    fn msm_with_blinding<Z: ProvingKey<P> + ?Sized + 'static>(
+        &mut self,
+        zkey: Arc<Z>,
+        public_inputs: Vec<P::ScalarField>,
+        aux_assignment: AuxAssignment<T::ArithmeticShare>,
+        witness_map: WitnessMapArtifact<P::ScalarField>,
         r: T::ArithmeticShare,
         s: T::ArithmeticShare,
-        h: Vec<P::ScalarField>,
-        input_assignment: Arc<Vec<P::ScalarField>>,
-        aux_assignment: Arc<Vec<T::ArithmeticShare>>,
-    ) -> Result<Groth16Proof<P>> {
-        let delta_g1 = zkey.delta_g1.into_group();
+    ) -> Result<MsmArtifact<P, T>> {
+        Self::check_public_inputs(zkey.as_ref(), &public_inputs)?;
+        let h = witness_map.h;
+        let input_assignment = Arc::new(public_inputs);
+        let aux_assignment = Arc::new(aux_assignment);
+        let delta_g1 = zkey.delta_g1().into_group();
         let (l_acc_tx, l_acc_rx) = oneshot::channel();
         let (h_acc_tx, h_acc_rx) = oneshot::channel();
         let h_query = Arc::clone(&zkey);
@@ -348,10 +636,13 @@ where
         let aux_assignment2 = Arc::clone(&aux_assignment);
         let aux_assignment3 = Arc::clone(&aux_assignment);
         let aux_assignment4 = Arc::clone(&aux_assignment);
-        let alpha_g1 = zkey.alpha_g1;
-        let beta_g1 = zkey.beta_g1;
-        let beta_g2 = zkey.beta_g2;
-        let delta_g2 = zkey.delta_g2.into_group();
+        let alpha_g1 = zkey.alpha_g1();
+        let beta_g1 = zkey.beta_g1();
+        let beta_g2 = zkey.beta_g2();
+        let delta_g2 = zkey.delta_g2().into_group();
+        let budget = self
+            .msm_memory_budget
+            .map(|budget| budget / CONCURRENT_MSMS);
 
         rayon::spawn(move || {
             let compute_a =
@@ -361,10 +652,11 @@ where
             let r_g1 = Self::calculate_coeff(
                 party_id,
                 r_g1,
-                &a_query.a_query,
+                a_query.a_query(),
                 alpha_g1,
                 &input_assignment1[1..],
                 &aux_assignment1,
+                budget,
             );
             r_g1_tx.send(r_g1).expect("not dropped");
             compute_a.exit();
@@ -379,10 +671,11 @@ where
             let s_g1 = Self::calculate_coeff(
                 party_id,
                 s_g1,
-                &b_g1_query.b_g1_query,
+                b_g1_query.b_g1_query(),
                 beta_g1,
                 &input_assignment2[1..],
                 &aux_assignment2,
+                budget,
             );
             s_g1_tx.send(s_g1).expect("not dropped");
             compute_b.exit();
@@ -396,10 +689,11 @@ where
             let s_g2 = Self::calculate_coeff(
                 party_id,
                 s_g2,
-                &b_g2_query.b_g2_query,
+                b_g2_query.b_g2_query(),
                 beta_g2,
                 &input_assignment3[1..],
                 &aux_assignment3,
+                budget,
             );
             s_g2_tx.send(s_g2).expect("not dropped");
             compute_b.exit();
@@ -407,7 +701,7 @@ where
 
         rayon::spawn(move || {
             let msm_l_query = tracing::debug_span!("msm l_query").entered();
-            let result = T::msm_public_points(&l_query.l_query, &aux_assignment4);
+            let result = Self::msm_aux_assignment(l_query.l_query(), 0, &aux_assignment4, budget);
             l_acc_tx.send(result).expect("channel not dropped");
             msm_l_query.exit();
         });
@@ -415,7 +709,16 @@ where
         rayon::spawn(move || {
             let msm_h_query = tracing::debug_span!("msm h_query").entered();
             //perform the msm for h
-            let result = P::G1::msm_unchecked(&h_query.h_query, &h);
+            let h_query = h_query.h_query();
+            let result = msm::chunked(
+                h_query.num_points().min(h.len()),
+                Self::chunk_size::<P::G1>(h_query, budget),
+                |range| {
+                    let points = h_query.points(range.clone())?;
+                    Ok(P::G1::msm_unchecked(&points, &h[range]))
+                },
+                |acc, chunk| *acc += chunk,
+            );
             h_acc_tx.send(result).expect("channel not dropped");
             msm_h_query.exit();
         });
@@ -427,35 +730,22 @@ where
         let r_s_delta_g1 = T::scalar_mul_public_point(&delta_g1, rs);
         rs_span.exit();
 
-        let g_a = r_g1_rx.blocking_recv()?;
-        let g1_b = s_g1_rx.blocking_recv()?;
-
-        let network_round = tracing::debug_span!("network round after calc coeff").entered();
-        let (g_a_opened, r_g1_b) = self.driver.open_point_and_scalar_mul(&g_a, &g1_b, r)?;
-        network_round.exit();
-
-        let last_round = tracing::debug_span!("finish - open two points and some adds").entered();
-        let s_g_a = T::scalar_mul_public_point(&g_a_opened, s);
+        let g_a = r_g1_rx.blocking_recv()??;
+        let g1_b = s_g1_rx.blocking_recv()??;
+        let g2_b = s_g2_rx.blocking_recv()??;
 
-        let mut g_c = s_g_a;
-        T::add_assign_points(&mut g_c, &r_g1_b);
+        let mut g_c = l_acc_rx.blocking_recv().expect("channel not dropped")?;
         T::sub_assign_points(&mut g_c, &r_s_delta_g1);
-        let l_aux_acc = l_acc_rx.blocking_recv().expect("channel not dropped");
-        T::add_assign_points(&mut g_c, &l_aux_acc);
-
-        let h_acc = h_acc_rx.blocking_recv()?;
-        let g_c = T::add_points_half_share(g_c, &h_acc);
-
-        let g2_b = s_g2_rx.blocking_recv()?;
-        let (g_c_opened, g2_b_opened) = self.driver.open_two_points(g_c, g2_b)?;
-        last_round.exit();
+        let h_acc = h_acc_rx.blocking_recv()??;
 
-        Ok(Groth16Proof {
-            pi_a: g_a_opened.into_affine(),
-            pi_b: g2_b_opened.into_affine(),
-            pi_c: g_c_opened.into_affine(),
-            protocol: "groth16".to_owned(),
-            curve: P::get_circom_name(),
+        Ok(MsmArtifact {
+            r,
+            s,
+            g_a,
+            g1_b,
+            g2_b,
+            g_c,
+            h_acc,
         })
     }
 }
@@ -473,6 +763,7 @@ where
         let driver = Rep3Groth16Driver::new(io_context0, io_context1);
         Ok(CoGroth16 {
             driver,
+            msm_memory_budget: None,
             phantom_data: PhantomData,
         })
     }
@@ -492,10 +783,15 @@ where
 {
     /// Create a new [ShamirCoGroth16] protocol with a given network configuration.
     pub fn with_network_config(threshold: usize, config: NetworkConfig) -> Result<Self> {
+        let mpc_net = ShamirMpcNet::new(config)?;
+        Self::with_network(threshold, mpc_net)
+    }
+
+    /// Create a new [ShamirCoGroth16] protocol from an already established network.
+    pub fn with_network(threshold: usize, mpc_net: ShamirMpcNet) -> Result<Self> {
         // we need 2 + 1 number of corr rand pairs. We need the values r/s (1 pair) and 2 muls (2
         // pairs)
         let num_pairs = 3;
-        let mpc_net = ShamirMpcNet::new(config)?;
         let preprocessing = ShamirPreprocessing::new(threshold, mpc_net, num_pairs)?;
         let mut protocol0 = ShamirProtocol::from(preprocessing);
         // the protocol1 is only used for scalar_mul and a field_mul which need 1 pair each (ergo 2
@@ -504,6 +800,7 @@ where
         let driver = ShamirGroth16Driver::new(protocol0, protocol1);
         Ok(CoGroth16 {
             driver,
+            msm_memory_budget: None,
             phantom_data: PhantomData,
         })
     }
@@ -519,14 +816,31 @@ where
     /// initialized with the [`PlainGroth16Driver`].
     ///
     /// DOES NOT PERFORM ANY MPC. For a plain prover checkout the [Groth16 implementation of arkworks](https://docs.rs/ark-groth16/latest/ark_groth16/).
-    pub fn plain_prove(
-        zkey: Arc<ZKey<P>>,
+    pub fn plain_prove<Z: ProvingKey<P> + ?Sized + 'static>(
+        zkey: Arc<Z>,
         private_witness: SharedWitness<P::ScalarField, P::ScalarField>,
     ) -> Result<Groth16Proof<P>> {
         let prover = Self {
             driver: PlainGroth16Driver,
+            msm_memory_budget: None,
             phantom_data: PhantomData,
         };
         prover.prove(zkey, private_witness)
     }
+
+    /// *Locally* create a `Groth16` proof with the blinding values r and s set to zero. See [`CoGroth16::prove_insecure_non_hiding`].
+    ///
+    /// # Security
+    /// The resulting proof is deterministic and *NOT* zero-knowledge. Only use it for benchmarking and debugging.
+    pub fn plain_prove_insecure_non_hiding<Z: ProvingKey<P> + ?Sized + 'static>(
+        zkey: Arc<Z>,
+        private_witness: SharedWitness<P::ScalarField, P::ScalarField>,
+    ) -> Result<Groth16Proof<P>> {
+        let prover = Self {
+            driver: PlainGroth16Driver,
+            msm_memory_budget: None,
+            phantom_data: PhantomData,
+        };
+        prover.prove_insecure_non_hiding(zkey, private_witness)
+    }
 }
@@ -1,33 +1,64 @@
 //! A library for creating and verifying Groth16 proofs in a collaborative fashion using MPC.
 #![warn(missing_docs)]
+/// This module contains the collaborative folding scheme for incrementally verifiable computation
+#[cfg(feature = "prover")]
+pub mod folding;
+#[cfg(feature = "prover")]
 mod groth16;
 /// This module contains the Groth16 prover trait
+#[cfg(feature = "prover")]
 pub mod mpc;
+#[cfg(feature = "prover")]
+mod msm;
+/// This module contains the proving keys of the Groth16 prover
+#[cfg(feature = "prover")]
+pub mod proving_key;
 #[cfg(feature = "verifier")]
 mod verifier;
 
+#[cfg(feature = "prover")]
 pub use groth16::CoGroth16;
+#[cfg(feature = "prover")]
 pub use groth16::Groth16;
+#[cfg(feature = "prover")]
 pub use groth16::Rep3CoGroth16;
+#[cfg(feature = "prover")]
 pub use groth16::ShamirCoGroth16;
+#[cfg(feature = "prover")]
+pub use groth16::{MsmArtifact, WitnessMapArtifact};
+#[cfg(feature = "prover")]
+pub use proving_key::{PointQuery, ProvingKey};
 
-#[cfg(test)]
+/// The plain Groth16 type. Without the `prover` feature, it can only be used to verify proofs.
+#[cfg(not(feature = "prover"))]
+pub struct Groth16<P: ark_ec::pairing::Pairing>(std::marker::PhantomData<P>);
 #[cfg(feature = "verifier")]
+pub use verifier::{
+    verification_key_hash, PreparedVerificationKey, VerificationKeyCache, VerificationKeyHash,
+};
+
+#[cfg(test)]
+#[cfg(all(feature = "prover", feature = "verifier"))]
 mod tests {
     use ark_bls12_381::Bls12_381;
     use ark_bn254::Bn254;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
     use circom_types::{
-        groth16::{Groth16Proof, JsonPublicInput, JsonVerificationKey, ZKey},
+        groth16::{Groth16Proof, JsonPublicInput, JsonVerificationKey, LazyZKey, ZKey},
         traits::CheckElement,
-        Witness,
+        Witness, R1CS,
     };
     use co_circom_snarks::SharedWitness;
+    use rand::thread_rng;
     use std::{
         fs::{self, File},
         sync::Arc,
     };
 
+    use crate::folding::{CoFolding, CommitmentKey, RelaxedR1CS};
     use crate::groth16::Groth16;
+    use crate::mpc::PlainGroth16Driver;
+    use crate::{MsmArtifact, VerificationKeyCache, WitnessMapArtifact};
 
     #[test]
     fn create_proof_and_verify_bn254() {
@@ -56,6 +87,146 @@ mod tests {
         }
     }
 
+    #[test]
+    fn create_proof_with_lazy_zkey_and_verify_bn254() {
+        let witness_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/witness.wtns").unwrap();
+        let vk_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/verification_key.json")
+                .unwrap();
+
+        let witness = Witness::<ark_bn254::Fr>::from_reader(witness_file).unwrap();
+        let zkey = Arc::new(
+            LazyZKey::<Bn254>::from_path(
+                "../../test_vectors/Groth16/bn254/multiplier2/circuit.zkey",
+                CheckElement::Yes,
+            )
+            .unwrap(),
+        );
+        let vk: JsonVerificationKey<Bn254> = serde_json::from_reader(vk_file).unwrap();
+        let public_input = witness.values[..=zkey.n_public].to_vec();
+        let witness = SharedWitness {
+            public_inputs: public_input.clone(),
+            witness: witness.values[zkey.n_public + 1..].to_vec(),
+        };
+        // a budget of zero processes the MSMs point by point, so every point is read on its own
+        let proof = Groth16::<Bn254>::new(PlainGroth16Driver)
+            .with_msm_memory_budget(0)
+            .prove(zkey, witness)
+            .expect("proof generation works");
+        Groth16::verify(&vk, &proof, &public_input[1..]).expect("can verify");
+    }
+
+    #[test]
+    fn prove_in_phases_and_verify_bn254() {
+        let zkey_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/circuit.zkey").unwrap();
+        let witness_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/witness.wtns").unwrap();
+        let vk_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/verification_key.json")
+                .unwrap();
+
+        let witness = Witness::<ark_bn254::Fr>::from_reader(witness_file).unwrap();
+        let zkey = Arc::new(ZKey::<Bn254>::from_reader(zkey_file, CheckElement::No).unwrap());
+        let vk: JsonVerificationKey<Bn254> = serde_json::from_reader(vk_file).unwrap();
+        let public_input = witness.values[..=zkey.n_public].to_vec();
+        let witness = SharedWitness {
+            public_inputs: public_input.clone(),
+            witness: witness.values[zkey.n_public + 1..].to_vec(),
+        };
+
+        let mut prover = Groth16::<Bn254>::new(PlainGroth16Driver);
+        let witness_map = prover.witness_map(zkey.as_ref(), &witness).unwrap();
+        let mut bytes = Vec::new();
+        witness_map.serialize_uncompressed(&mut bytes).unwrap();
+        let witness_map =
+            WitnessMapArtifact::<ark_bn254::Fr>::deserialize_uncompressed(bytes.as_slice())
+                .unwrap();
+
+        let msm = prover.msm(Arc::clone(&zkey), witness, witness_map).unwrap();
+        let mut bytes = Vec::new();
+        msm.serialize_uncompressed(&mut bytes).unwrap();
+        let msm =
+            MsmArtifact::<Bn254, PlainGroth16Driver>::deserialize_uncompressed(bytes.as_slice())
+                .unwrap();
+
+        let proof = prover.openings(msm).unwrap();
+        Groth16::verify(&vk, &proof, &public_input[1..]).expect("can verify");
+    }
+
+    #[test]
+    fn fold_and_check_bn254() {
+        let r1cs_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/circuit.r1cs").unwrap();
+        let witness_file =
+            File::open("../../test_vectors/Groth16/bn254/multiplier2/witness.wtns").unwrap();
+        let r1cs = RelaxedR1CS::from_r1cs(&R1CS::<Bn254>::from_reader(r1cs_file).unwrap());
+        let witness = Witness::<ark_bn254::Fr>::from_reader(witness_file).unwrap();
+        let witness = SharedWitness {
+            public_inputs: witness.values[..r1cs.num_public()].to_vec(),
+            witness: witness.values[r1cs.num_public()..].to_vec(),
+        };
+        let ck = CommitmentKey::<ark_bn254::G1Projective>::setup(&r1cs, &mut thread_rng());
+        let mut folding = CoFolding::<Bn254, _>::new(PlainGroth16Driver);
+
+        let (instance1, witness1) = folding.export(&r1cs, &ck, witness.clone()).unwrap();
+        let (instance2, witness2) = folding.export(&r1cs, &ck, witness).unwrap();
+        assert!(r1cs.is_satisfied(&ck, &instance1, &witness1));
+        let (folded, folded_witness) = folding
+            .fold(&r1cs, &ck, (&instance1, &witness1), (&instance2, &witness2))
+            .unwrap();
+        assert!(r1cs.is_satisfied(&ck, &folded, &folded_witness));
+        let (folded, mut folded_witness) = folding
+            .fold(
+                &r1cs,
+                &ck,
+                (&folded, &folded_witness),
+                (&instance1, &witness1),
+            )
+            .unwrap();
+        assert!(r1cs.is_satisfied(&ck, &folded, &folded_witness));
+
+        folded_witness.w[0] += ark_bn254::Fr::from(1u64);
+        assert!(!r1cs.is_satisfied(&ck, &folded, &folded_witness));
+    }
+
+    #[test]
+    fn verify_with_cache_bn254() {
+        let vk_string = fs::read_to_string(
+            "../../test_vectors/Groth16/bn254/multiplier2/verification_key.json",
+        )
+        .unwrap();
+        let proof_string =
+            fs::read_to_string("../../test_vectors/Groth16/bn254/multiplier2/circom.proof")
+                .unwrap();
+        let vk = serde_json::from_str::<JsonVerificationKey<Bn254>>(&vk_string).unwrap();
+        let proof = serde_json::from_str::<Groth16Proof<Bn254>>(&proof_string).unwrap();
+        let public_input = [ark_bn254::Fr::from(33u64)];
+
+        let cache = VerificationKeyCache::<Bn254>::new();
+        cache
+            .verify(&vk, &proof, &public_input)
+            .expect("can verify");
+        cache
+            .verify(&vk, &proof, &public_input)
+            .expect("can verify");
+        assert_eq!(cache.len(), 1);
+        assert!(cache
+            .verify(&vk, &proof, &[ark_bn254::Fr::from(34u64)])
+            .is_err());
+        assert!(cache.verify(&vk, &proof, &[]).is_err());
+
+        let hash = cache.insert(&vk).expect("can hash vk");
+        assert_eq!(cache.len(), 1);
+        cache
+            .verify_by_hash(&hash, &proof, &public_input)
+            .expect("can verify");
+        assert!(cache
+            .verify_by_hash(&[0; 32], &proof, &public_input)
+            .is_err());
+    }
+
     #[test]
     fn verify_circom_proof_bn254() {
         let vk_string = fs::read_to_string(
@@ -4,6 +4,8 @@ use std::fmt::Debug;
 use ark_ec::{pairing::Pairing, CurveGroup};
 use ark_poly::domain::DomainCoeff;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use co_circom_snarks::Constraint;
+use mpc_core::protocols::rep3::network::TranscriptDigest;
 
 pub(crate) mod plain;
 pub(crate) mod rep3;
@@ -28,7 +30,7 @@ pub trait CircomGroth16Prover<P: Pairing>: Send + Sized {
         + DomainCoeff<P::ScalarField>
         + 'static;
     /// The G1 point share type
-    type PointShare<C>: Debug + Send + 'static
+    type PointShare<C>: CanonicalSerialize + CanonicalDeserialize + Debug + Send + 'static
     where
         C: CurveGroup;
     /// The party id type
@@ -43,7 +45,7 @@ pub trait CircomGroth16Prover<P: Pairing>: Send + Sized {
     /// Each value of lhs consists of a coefficient c and an index i. This function computes the sum of the coefficients times the corresponding public input or private witness. In other words, an accumulator a is initialized to 0, and for each (c, i) in lhs, a += c * public_inputs\[i\] is computed if i corresponds to a public input, or c * private_witness[i - public_inputs.len()] if i corresponds to a private witness.
     fn evaluate_constraint(
         party_id: Self::PartyID,
-        lhs: &[(P::ScalarField, usize)],
+        lhs: Constraint<'_, P::ScalarField>,
         public_inputs: &[P::ScalarField],
         private_witness: &[Self::ArithmeticShare],
     ) -> Self::ArithmeticShare;
@@ -61,10 +63,30 @@ pub trait CircomGroth16Prover<P: Pairing>: Send + Sized {
     /// You must *NOT* perform additional non-linear operations on the result of this function.
     fn local_mul_vec(
         &mut self,
-        a: Vec<Self::ArithmeticShare>,
-        b: Vec<Self::ArithmeticShare>,
+        a: &[Self::ArithmeticShare],
+        b: &[Self::ArithmeticShare],
     ) -> Vec<P::ScalarField>;
 
+    /// Performs element-wise multiplication of two vectors of shared values and subtracts the result from `c` in place, i.e., `c = a * b - c`. `c` is the result of a previous call to [`CircomGroth16Prover::local_mul_vec`].
+    /// Does not perform any networking.
+    ///
+    /// # Security
+    /// You must *NOT* perform additional non-linear operations on the result of this function.
+    fn local_mul_sub_vec(
+        &mut self,
+        a: &[Self::ArithmeticShare],
+        b: &[Self::ArithmeticShare],
+        c: &mut [P::ScalarField],
+    );
+
+    /// Performs element-wise multiplication of two vectors of shared values.
+    /// Requires network communication.
+    fn mul_vec(
+        &mut self,
+        a: &[Self::ArithmeticShare],
+        b: &[Self::ArithmeticShare],
+    ) -> IoResult<Vec<Self::ArithmeticShare>>;
+
     /// Compute the msm of `h` and `h_query` and multiplication `r` * `s`.
     fn mul(
         &mut self,
@@ -136,4 +158,9 @@ pub trait CircomGroth16Prover<P: Pairing>: Send + Sized {
         g1_b: &Self::PointShare<P::G1>,
         r: Self::ArithmeticShare,
     ) -> std::io::Result<(P::G1, Self::PointShare<P::G1>)>;
+
+    /// Compares the transcripts of all messages exchanged with the other parties so far and returns their digest. Fails if the transcripts do not match. Returns `None` if the driver does not record a transcript. This function has a default implementation returning `None`.
+    fn check_transcript(&mut self) -> IoResult<Option<TranscriptDigest>> {
+        Ok(None)
+    }
 }
@@ -4,6 +4,7 @@ use ark_ff::UniformRand;
 use rand::thread_rng;
 
 use super::CircomGroth16Prover;
+use co_circom_snarks::Constraint;
 
 type IoResult<T> = std::io::Result<T>;
 
@@ -32,16 +33,16 @@ impl<P: Pairing> CircomGroth16Prover<P> for PlainGroth16Driver {
 
     fn evaluate_constraint(
         _: Self::PartyID,
-        lhs: &[(P::ScalarField, usize)],
+        lhs: Constraint<'_, P::ScalarField>,
         public_inputs: &[P::ScalarField],
         private_witness: &[Self::ArithmeticShare],
     ) -> Self::ArithmeticShare {
         let mut acc = P::ScalarField::default();
-        for (coeff, index) in lhs {
-            if index < &public_inputs.len() {
-                acc += *coeff * public_inputs[*index];
+        for (coeff, index) in lhs.iter() {
+            if index < public_inputs.len() {
+                acc += coeff * public_inputs[index];
             } else {
-                acc += *coeff * private_witness[*index - public_inputs.len()];
+                acc += coeff * private_witness[index - public_inputs.len()];
             }
         }
         acc
@@ -56,12 +57,31 @@ impl<P: Pairing> CircomGroth16Prover<P> for PlainGroth16Driver {
 
     fn local_mul_vec(
         &mut self,
-        a: Vec<Self::ArithmeticShare>,
-        b: Vec<Self::ArithmeticShare>,
+        a: &[Self::ArithmeticShare],
+        b: &[Self::ArithmeticShare],
     ) -> Vec<P::ScalarField> {
         a.iter().zip(b.iter()).map(|(a, b)| *a * b).collect()
     }
 
+    fn local_mul_sub_vec(
+        &mut self,
+        a: &[Self::ArithmeticShare],
+        b: &[Self::ArithmeticShare],
+        c: &mut [P::ScalarField],
+    ) {
+        for ((c, a), b) in c.iter_mut().zip(a.iter()).zip(b.iter()) {
+            *c = *a * b - *c;
+        }
+    }
+
+    fn mul_vec(
+        &mut self,
+        a: &[Self::ArithmeticShare],
+        b: &[Self::ArithmeticShare],
+    ) -> IoResult<Vec<Self::ArithmeticShare>> {
+        Ok(a.iter().zip(b.iter()).map(|(a, b)| *a * b).collect())
+    }
+
     fn mul(
         &mut self,
         r: Self::ArithmeticShare,
@@ -2,12 +2,14 @@ use ark_ec::{pairing::Pairing, CurveGroup};
 use mpc_core::protocols::rep3::{
     arithmetic,
     id::PartyID,
-    network::{IoContext, Rep3Network},
+    network::{IoContext, Rep3Network, TranscriptDigest},
     pointshare, Rep3PointShare, Rep3PrimeFieldShare,
 };
 use rayon::prelude::*;
+use sha2::{Digest, Sha256};
 
 use super::{CircomGroth16Prover, IoResult};
+use co_circom_snarks::Constraint;
 
 /// A Groth16 driver for REP3 secret sharing
 ///
@@ -49,19 +51,19 @@ where
 
     fn evaluate_constraint(
         party_id: Self::PartyID,
-        lhs: &[(P::ScalarField, usize)],
+        lhs: Constraint<'_, P::ScalarField>,
         public_inputs: &[P::ScalarField],
         private_witness: &[Self::ArithmeticShare],
     ) -> Self::ArithmeticShare {
         let mut acc = Self::ArithmeticShare::default();
-        for (coeff, index) in lhs {
-            if index < &public_inputs.len() {
-                let val = public_inputs[*index];
+        for (coeff, index) in lhs.iter() {
+            if index < public_inputs.len() {
+                let val = public_inputs[index];
                 let mul_result = val * coeff;
                 arithmetic::add_assign_public(&mut acc, mul_result, party_id);
             } else {
-                let current_witness = private_witness[*index - public_inputs.len()];
-                arithmetic::add_assign(&mut acc, arithmetic::mul_public(current_witness, *coeff));
+                let current_witness = private_witness[index - public_inputs.len()];
+                acc += current_witness * coeff;
             }
         }
         acc
@@ -80,10 +82,28 @@ where
 
     fn local_mul_vec(
         &mut self,
-        a: Vec<Self::ArithmeticShare>,
-        b: Vec<Self::ArithmeticShare>,
+        a: &[Self::ArithmeticShare],
+        b: &[Self::ArithmeticShare],
     ) -> Vec<P::ScalarField> {
-        arithmetic::local_mul_vec(&a, &b, &mut self.io_context0.rngs)
+        arithmetic::local_mul_vec(a, b, &mut self.io_context0.rngs)
+    }
+
+    fn local_mul_sub_vec(
+        &mut self,
+        a: &[Self::ArithmeticShare],
+        b: &[Self::ArithmeticShare],
+        c: &mut [P::ScalarField],
+    ) {
+        arithmetic::local_mul_sub_vec(a, b, c, &mut self.io_context0.rngs)
+    }
+
+    fn mul_vec(
+        &mut self,
+        a: &[Self::ArithmeticShare],
+        b: &[Self::ArithmeticShare],
+    ) -> IoResult<Vec<Self::ArithmeticShare>> {
+        let local = arithmetic::local_mul_vec(a, b, &mut self.io_context0.rngs);
+        arithmetic::io_mul_vec(local, &mut self.io_context0)
     }
 
     fn mul(
@@ -103,7 +123,7 @@ where
             .zip_eq(roots.par_iter())
             .with_min_len(512)
             .for_each(|(c, pow)| {
-                arithmetic::mul_assign_public(c, *pow);
+                *c *= pow;
             })
     }
 
@@ -195,4 +215,21 @@ where
             Ok((opened.join().expect("can join")?, mul_result))
         })
     }
+
+    fn check_transcript(&mut self) -> IoResult<Option<TranscriptDigest>> {
+        let (r0, r1) = std::thread::scope(|s| {
+            let r0 = s.spawn(|| self.io_context0.network.check_transcript());
+            let r1 = s.spawn(|| self.io_context1.network.check_transcript());
+            (r0.join().expect("can join"), r1.join().expect("can join"))
+        });
+        match (r0?, r1?) {
+            (Some(digest0), Some(digest1)) => {
+                let mut hasher = Sha256::new();
+                hasher.update(digest0);
+                hasher.update(digest1);
+                Ok(Some(hasher.finalize().into()))
+            }
+            _ => Ok(None),
+        }
+    }
 }
@@ -1,6 +1,7 @@
 use super::{CircomGroth16Prover, IoResult};
 use ark_ec::{pairing::Pairing, CurveGroup};
 use ark_ff::PrimeField;
+use co_circom_snarks::Constraint;
 use mpc_core::protocols::shamir::{
     arithmetic, core, network::ShamirNetwork, pointshare, ShamirPointShare, ShamirPrimeFieldShare,
     ShamirProtocol,
@@ -46,19 +47,19 @@ impl<P: Pairing, N: ShamirNetwork> CircomGroth16Prover<P>
 
     fn evaluate_constraint(
         _party_id: Self::PartyID,
-        lhs: &[(P::ScalarField, usize)],
+        lhs: Constraint<'_, P::ScalarField>,
         public_inputs: &[P::ScalarField],
         private_witness: &[Self::ArithmeticShare],
     ) -> Self::ArithmeticShare {
         let mut acc = Self::ArithmeticShare::default();
-        for (coeff, index) in lhs {
-            if index < &public_inputs.len() {
-                let val = public_inputs[*index];
+        for (coeff, index) in lhs.iter() {
+            if index < public_inputs.len() {
+                let val = public_inputs[index];
                 let mul_result = val * coeff;
                 arithmetic::add_assign_public(&mut acc, mul_result);
             } else {
-                let current_witness = private_witness[*index - public_inputs.len()];
-                arithmetic::add_assign(&mut acc, arithmetic::mul_public(current_witness, *coeff));
+                let current_witness = private_witness[index - public_inputs.len()];
+                acc += current_witness * coeff;
             }
         }
         acc
@@ -73,10 +74,28 @@ impl<P: Pairing, N: ShamirNetwork> CircomGroth16Prover<P>
 
     fn local_mul_vec(
         &mut self,
-        a: Vec<Self::ArithmeticShare>,
-        b: Vec<Self::ArithmeticShare>,
+        a: &[Self::ArithmeticShare],
+        b: &[Self::ArithmeticShare],
     ) -> Vec<P::ScalarField> {
-        arithmetic::local_mul_vec(&a, &b)
+        arithmetic::local_mul_vec(a, b)
+    }
+
+    fn local_mul_sub_vec(
+        &mut self,
+        a: &[Self::ArithmeticShare],
+        b: &[Self::ArithmeticShare],
+        c: &mut [P::ScalarField],
+    ) {
+        arithmetic::local_mul_sub_vec(a, b, c)
+    }
+
+    fn mul_vec(
+        &mut self,
+        a: &[Self::ArithmeticShare],
+        b: &[Self::ArithmeticShare],
+    ) -> IoResult<Vec<Self::ArithmeticShare>> {
+        let local = arithmetic::local_mul_vec(a, b);
+        self.protocol0.degree_reduce_vec(local)
     }
 
     fn mul(
@@ -96,7 +115,7 @@ impl<P: Pairing, N: ShamirNetwork> CircomGroth16Prover<P>
             .zip_eq(roots.par_iter())
             .with_min_len(512)
             .for_each(|(c, pow)| {
-                arithmetic::mul_assign_public(c, *pow);
+                *c *= pow;
             })
     }
 
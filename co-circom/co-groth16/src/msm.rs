@@ -0,0 +1,100 @@
+//! Multi-scalar multiplications with a bounded memory footprint, see [CoGroth16::with_msm_memory_budget](crate::CoGroth16::with_msm_memory_budget).
+//!
+//! The Pippenger MSM of arkworks materializes the scalars as big integers, the signed digits of all scalars for every window and one bucket array per window, where all windows are processed in parallel. Its peak memory therefore grows with the number of points. Splitting an MSM into chunks that are processed one after the other bounds the peak memory by the memory of the MSM of a single chunk. The result of every chunk is a single point that is accumulated right away, thus no intermediate bucket sums need to be kept in memory or spilled to disk.
+use std::ops::Range;
+
+use ark_ec::CurveGroup;
+use ark_ff::PrimeField;
+
+/// The window size arkworks chooses for an MSM of `n` points.
+fn window_size(n: usize) -> usize {
+    if n < 32 {
+        3
+    } else {
+        // ln(n) computed as log2(n) * ln(2), see ark_ec::scalar_mul::ln_without_floats
+        n.next_power_of_two().trailing_zeros() as usize * 69 / 100 + 2
+    }
+}
+
+/// Estimates the peak memory in bytes of an MSM of `n` points in `C`, including a copy of the points and the scalars.
+pub(crate) fn msm_memory<C: CurveGroup>(n: usize) -> usize {
+    let c = window_size(n);
+    let num_windows = (C::ScalarField::MODULUS_BIT_SIZE as usize).div_ceil(c);
+    let points = n * std::mem::size_of::<C::Affine>();
+    let scalars = n * std::mem::size_of::<<C::ScalarField as PrimeField>::BigInt>();
+    let digits = n * num_windows * std::mem::size_of::<i64>();
+    let buckets = num_windows * (1 << c) * std::mem::size_of::<C>();
+    points + scalars + digits + buckets
+}
+
+/// Returns the largest number of points such that the MSM of a chunk of that size stays within `budget` bytes, but at least one point.
+pub(crate) fn chunk_size<C: CurveGroup>(budget: usize) -> usize {
+    // the estimate is monotone in the number of points, so we search for the largest chunk
+    let (mut low, mut high) = (1, 2);
+    if msm_memory::<C>(low) > budget {
+        return low;
+    }
+    while msm_memory::<C>(high) <= budget {
+        low = high;
+        high *= 2;
+    }
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if msm_memory::<C>(mid) <= budget {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+/// Splits `0..len` into ranges of at most `chunk_size` elements, computes the MSM of every range one after the other and accumulates the results with `add`. For `len = 0`, `msm` is called with the empty range. The MSM of a range may fail, e.g., if its points are read from a file, see [`PointQuery`](crate::proving_key::PointQuery).
+pub(crate) fn chunked<R>(
+    len: usize,
+    chunk_size: usize,
+    msm: impl Fn(Range<usize>) -> eyre::Result<R>,
+    add: impl Fn(&mut R, &R),
+) -> eyre::Result<R> {
+    let mut end = len.min(chunk_size);
+    let mut acc = msm(0..end)?;
+    while end < len {
+        let start = end;
+        end = len.min(start + chunk_size);
+        add(&mut acc, &msm(start..end)?);
+    }
+    Ok(acc)
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::G1Projective;
+
+    use super::{chunk_size, chunked, msm_memory};
+
+    #[test]
+    fn chunk_size_stays_within_budget() {
+        for budget in [0, 1 << 10, 1 << 20, 1 << 30] {
+            let n = chunk_size::<G1Projective>(budget);
+            assert!(n == 1 || msm_memory::<G1Projective>(n) <= budget);
+            assert!(msm_memory::<G1Projective>(n + 1) > budget);
+        }
+    }
+
+    #[test]
+    fn chunks_cover_all_elements() {
+        let sum = |len, chunk_size| {
+            chunked(
+                len,
+                chunk_size,
+                |range| Ok(range.sum::<usize>()),
+                |a, b| *a += b,
+            )
+            .unwrap()
+        };
+        assert_eq!(sum(0, 4), 0);
+        assert_eq!(sum(10, 3), 45);
+        assert_eq!(sum(10, 10), 45);
+        assert_eq!(sum(10, usize::MAX), 45);
+    }
+}
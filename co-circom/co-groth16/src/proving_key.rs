@@ -0,0 +1,151 @@
+//! Proving keys for the Groth16 prover.
+//!
+//! The prover accesses the proving key through the [`ProvingKey`] trait, which is implemented for the [`ZKey`] that holds all points in memory and for the [`LazyZKey`] that reads the points of the queries from the zkey file on demand. With a [`LazyZKey`], the MSMs only read the points of the chunk they are currently processing, see [`CoGroth16::with_msm_memory_budget`](crate::CoGroth16::with_msm_memory_budget), so proving keys of several GB do not have to be loaded into memory.
+use std::borrow::Cow;
+use std::ops::Range;
+
+use ark_ec::pairing::Pairing;
+use circom_types::groth16::{LazyZKey, ZKey, ZKeySection};
+use circom_types::ConstraintEvaluator;
+use eyre::Result;
+
+/// The maximal number of points that are read at once from a query that is not held in memory.
+const LAZY_CHUNK_SIZE: usize = 1 << 18;
+
+/// The points of a query of a Groth16 proving key.
+pub trait PointQuery<A>: Send + Sync {
+    /// Returns the number of points of the query.
+    fn num_points(&self) -> usize;
+
+    /// Returns the points in the given range. Panics if the range is out of bounds.
+    fn points(&self, range: Range<usize>) -> Result<Cow<'_, [A]>>;
+
+    /// Returns the points at the given indices. Panics if an index is out of bounds.
+    fn points_at(&self, indices: &[usize]) -> Result<Vec<A>>;
+
+    /// Returns the maximal number of points that should be requested at once.
+    fn max_chunk_size(&self) -> usize {
+        usize::MAX
+    }
+}
+
+impl<A: Clone + Send + Sync> PointQuery<A> for Vec<A> {
+    fn num_points(&self) -> usize {
+        self.len()
+    }
+
+    fn points(&self, range: Range<usize>) -> Result<Cow<'_, [A]>> {
+        Ok(Cow::Borrowed(&self[range]))
+    }
+
+    fn points_at(&self, indices: &[usize]) -> Result<Vec<A>> {
+        Ok(indices.iter().map(|i| self[*i].clone()).collect())
+    }
+}
+
+impl<A: Clone + Send + Sync> PointQuery<A> for ZKeySection<A> {
+    fn num_points(&self) -> usize {
+        self.len()
+    }
+
+    fn points(&self, range: Range<usize>) -> Result<Cow<'_, [A]>> {
+        Ok(Cow::Owned(self.read_range(range)?))
+    }
+
+    fn points_at(&self, indices: &[usize]) -> Result<Vec<A>> {
+        Ok(self.read_indices(indices)?)
+    }
+
+    fn max_chunk_size(&self) -> usize {
+        LAZY_CHUNK_SIZE
+    }
+}
+
+/// A Groth16 proving key in the format defined by circom, see the [module documentation](self).
+pub trait ProvingKey<P: Pairing>: Send + Sync {
+    /// The amount of public inputs
+    fn n_public(&self) -> usize;
+    /// The domain size
+    fn pow(&self) -> usize;
+    /// The amount of constraints
+    fn num_constraints(&self) -> usize;
+    /// alpha in G1
+    fn alpha_g1(&self) -> P::G1Affine;
+    /// beta in G1
+    fn beta_g1(&self) -> P::G1Affine;
+    /// beta in G2
+    fn beta_g2(&self) -> P::G2Affine;
+    /// delta in G1
+    fn delta_g1(&self) -> P::G1Affine;
+    /// delta in G2
+    fn delta_g2(&self) -> P::G2Affine;
+    /// The constraint matrix A
+    fn a_matrix(&self) -> &ConstraintEvaluator<P::ScalarField>;
+    /// The constraint matrix B
+    fn b_matrix(&self) -> &ConstraintEvaluator<P::ScalarField>;
+    /// The a query
+    fn a_query(&self) -> &dyn PointQuery<P::G1Affine>;
+    /// The b query in G1
+    fn b_g1_query(&self) -> &dyn PointQuery<P::G1Affine>;
+    /// The b query in G2
+    fn b_g2_query(&self) -> &dyn PointQuery<P::G2Affine>;
+    /// The h query
+    fn h_query(&self) -> &dyn PointQuery<P::G1Affine>;
+    /// The l query
+    fn l_query(&self) -> &dyn PointQuery<P::G1Affine>;
+}
+
+macro_rules! impl_proving_key {
+    ($zkey: ident) => {
+        impl<P: Pairing> ProvingKey<P> for $zkey<P> {
+            fn n_public(&self) -> usize {
+                self.n_public
+            }
+            fn pow(&self) -> usize {
+                self.pow
+            }
+            fn num_constraints(&self) -> usize {
+                self.num_constraints
+            }
+            fn alpha_g1(&self) -> P::G1Affine {
+                self.alpha_g1
+            }
+            fn beta_g1(&self) -> P::G1Affine {
+                self.beta_g1
+            }
+            fn beta_g2(&self) -> P::G2Affine {
+                self.beta_g2
+            }
+            fn delta_g1(&self) -> P::G1Affine {
+                self.delta_g1
+            }
+            fn delta_g2(&self) -> P::G2Affine {
+                self.delta_g2
+            }
+            fn a_matrix(&self) -> &ConstraintEvaluator<P::ScalarField> {
+                &self.a_matrix
+            }
+            fn b_matrix(&self) -> &ConstraintEvaluator<P::ScalarField> {
+                &self.b_matrix
+            }
+            fn a_query(&self) -> &dyn PointQuery<P::G1Affine> {
+                &self.a_query
+            }
+            fn b_g1_query(&self) -> &dyn PointQuery<P::G1Affine> {
+                &self.b_g1_query
+            }
+            fn b_g2_query(&self) -> &dyn PointQuery<P::G2Affine> {
+                &self.b_g2_query
+            }
+            fn h_query(&self) -> &dyn PointQuery<P::G1Affine> {
+                &self.h_query
+            }
+            fn l_query(&self) -> &dyn PointQuery<P::G1Affine> {
+                &self.l_query
+            }
+        }
+    };
+}
+
+impl_proving_key!(ZKey);
+impl_proving_key!(LazyZKey);
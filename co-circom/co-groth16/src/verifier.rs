@@ -1,31 +1,181 @@
 //! This module implements the verification of a Groth16 proof on the [`Groth16`] type.
 //!
 //! We use [arkworks Groth16 implementation](https://docs.rs/ark-groth16/latest/ark_groth16/struct.Groth16.html#method.verify_proof)
-//! for verification.
+//! for verification. The public inputs are accumulated with a single MSM over the IC points of the verification key. Verifiers checking many proofs for circuits with a large amount of public inputs can keep the prepared keys in a [VerificationKeyCache].
 
-use crate::groth16::Groth16;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::Groth16;
 use ark_ec::pairing::Pairing;
+use ark_ec::scalar_mul::variable_base::VariableBaseMSM;
+use ark_ec::AffineRepr;
+use ark_groth16::PreparedVerifyingKey;
 use ark_groth16::Proof;
 use ark_groth16::VerifyingKey;
+use ark_serialize::CanonicalSerialize;
 use circom_types::groth16::{Groth16Proof, JsonVerificationKey};
 use circom_types::traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge};
 
 use ark_groth16::Groth16 as ArkworksGroth16;
 use co_circom_snarks::VerificationError;
+use sha2::{Digest, Sha256};
 
-impl<P: Pairing> Groth16<P>
+/// The SHA-256 hash of a verification key, used as key of the [VerificationKeyCache].
+pub type VerificationKeyHash = [u8; 32];
+
+/// A verification key with the pairing-friendly parts precomputed, see [`Groth16::prepare_verification_key`].
+pub struct PreparedVerificationKey<P: Pairing> {
+    pvk: PreparedVerifyingKey<P>,
+}
+
+impl<P: Pairing> PreparedVerificationKey<P> {
+    /// Returns the amount of public inputs (without the constant 1) accepted by this key.
+    pub fn n_public(&self) -> usize {
+        self.pvk.vk.gamma_abc_g1.len().saturating_sub(1)
+    }
+}
+
+/// A cache of [PreparedVerificationKey]s, keyed by the [VerificationKeyHash] of the verification key.
+pub struct VerificationKeyCache<P: Pairing> {
+    keys: Mutex<HashMap<VerificationKeyHash, Arc<PreparedVerificationKey<P>>>>,
+}
+
+impl<P: Pairing> Default for VerificationKeyCache<P> {
+    fn default() -> Self {
+        Self {
+            keys: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<P: Pairing> VerificationKeyCache<P>
 where
     P::ScalarField: CircomArkworksPrimeFieldBridge,
     P: Pairing + CircomArkworksPairingBridge,
     P::BaseField: CircomArkworksPrimeFieldBridge,
 {
-    /// Verify a Groth16 proof.
-    /// This method is a wrapper arkworks Groth16 and does not use MPC.
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the prepared key for the provided verification key. The key is prepared on the first call and cached for all further calls.
+    pub fn get_or_prepare(
+        &self,
+        vk: &JsonVerificationKey<P>,
+    ) -> Result<Arc<PreparedVerificationKey<P>>, VerificationError> {
+        let hash = verification_key_hash(vk)?;
+        if let Some(pvk) = self.keys.lock().expect("not poisoned").get(&hash) {
+            return Ok(Arc::clone(pvk));
+        }
+        // prepare without holding the lock, a concurrent insert of the same key is harmless
+        let pvk = Arc::new(Groth16::<P>::prepare_verification_key(vk));
+        Ok(Arc::clone(
+            self.keys
+                .lock()
+                .expect("not poisoned")
+                .entry(hash)
+                .or_insert(pvk),
+        ))
+    }
+
+    /// Prepares and caches the verification key and returns its hash. The hash can be used with [Self::verify_by_hash] to verify proofs without hashing the key on every call.
+    pub fn insert(
+        &self,
+        vk: &JsonVerificationKey<P>,
+    ) -> Result<VerificationKeyHash, VerificationError> {
+        let hash = verification_key_hash(vk)?;
+        let mut keys = self.keys.lock().expect("not poisoned");
+        keys.entry(hash)
+            .or_insert_with(|| Arc::new(Groth16::<P>::prepare_verification_key(vk)));
+        Ok(hash)
+    }
+
+    /// Returns the cached prepared key with the provided hash, if any.
+    pub fn get(&self, hash: &VerificationKeyHash) -> Option<Arc<PreparedVerificationKey<P>>> {
+        self.keys.lock().expect("not poisoned").get(hash).cloned()
+    }
+
+    /// Verifies a proof with the cached prepared key with the provided hash, see [Self::insert]. Fails if no key with this hash is cached.
+    pub fn verify_by_hash(
+        &self,
+        hash: &VerificationKeyHash,
+        proof: &Groth16Proof<P>,
+        public_inputs: &[P::ScalarField],
+    ) -> Result<(), VerificationError> {
+        let pvk = self
+            .get(hash)
+            .ok_or_else(|| eyre::eyre!("no cached verification key with this hash"))?;
+        Groth16::<P>::verify_prepared(&pvk, proof, public_inputs)
+    }
+
+    /// Verifies a proof with the cached prepared key of `vk`, see [`Groth16::verify`].
     pub fn verify(
+        &self,
         vk: &JsonVerificationKey<P>,
         proof: &Groth16Proof<P>,
         public_inputs: &[P::ScalarField],
     ) -> Result<(), VerificationError> {
+        let pvk = self.get_or_prepare(vk)?;
+        Groth16::<P>::verify_prepared(&pvk, proof, public_inputs)
+    }
+
+    /// Returns the amount of cached keys.
+    pub fn len(&self) -> usize {
+        self.keys.lock().expect("not poisoned").len()
+    }
+
+    /// Returns `true` if no key is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all cached keys.
+    pub fn clear(&self) {
+        self.keys.lock().expect("not poisoned").clear();
+    }
+}
+
+/// Computes the [VerificationKeyHash] of a verification key.
+pub fn verification_key_hash<P: Pairing>(
+    vk: &JsonVerificationKey<P>,
+) -> Result<VerificationKeyHash, VerificationError> {
+    let mut hasher = Sha256::new();
+    let mut buf = Vec::new();
+    vk.alpha_1
+        .serialize_compressed(&mut buf)
+        .map_err(eyre::Report::from)?;
+    vk.beta_2
+        .serialize_compressed(&mut buf)
+        .map_err(eyre::Report::from)?;
+    vk.gamma_2
+        .serialize_compressed(&mut buf)
+        .map_err(eyre::Report::from)?;
+    vk.delta_2
+        .serialize_compressed(&mut buf)
+        .map_err(eyre::Report::from)?;
+    hasher.update(&buf);
+    hasher.update((vk.ic.len() as u64).to_le_bytes());
+    // stream the IC points into the hasher, the key can be large for many public inputs
+    for point in vk.ic.iter() {
+        buf.clear();
+        point
+            .serialize_compressed(&mut buf)
+            .map_err(eyre::Report::from)?;
+        hasher.update(&buf);
+    }
+    Ok(hasher.finalize().into())
+}
+
+impl<P: Pairing> Groth16<P>
+where
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P: Pairing + CircomArkworksPairingBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    /// Prepares a verification key for [`Groth16::verify_prepared`]. Use this method (or a [VerificationKeyCache]) if you verify multiple proofs with the same key.
+    pub fn prepare_verification_key(vk: &JsonVerificationKey<P>) -> PreparedVerificationKey<P> {
         let vk = VerifyingKey::<P> {
             alpha_g1: vk.alpha_1,
             beta_g2: vk.beta_2,
@@ -33,15 +183,54 @@ where
             delta_g2: vk.delta_2,
             gamma_abc_g1: vk.ic.clone(),
         };
+        PreparedVerificationKey {
+            pvk: ark_groth16::prepare_verifying_key(&vk),
+        }
+    }
+
+    /// Verify a Groth16 proof.
+    /// This method is a wrapper arkworks Groth16 and does not use MPC.
+    pub fn verify(
+        vk: &JsonVerificationKey<P>,
+        proof: &Groth16Proof<P>,
+        public_inputs: &[P::ScalarField],
+    ) -> Result<(), VerificationError> {
+        let pvk = Self::prepare_verification_key(vk);
+        Self::verify_prepared(&pvk, proof, public_inputs)
+    }
+
+    /// Verify a Groth16 proof with a prepared verification key.
+    /// The public inputs are accumulated with a single MSM instead of one scalar multiplication per input.
+    pub fn verify_prepared(
+        pvk: &PreparedVerificationKey<P>,
+        proof: &Groth16Proof<P>,
+        public_inputs: &[P::ScalarField],
+    ) -> Result<(), VerificationError> {
+        let ic = &pvk.pvk.vk.gamma_abc_g1;
+        if ic.is_empty() {
+            return Err(eyre::eyre!("verification key has no IC points").into());
+        }
+        if public_inputs.len() != pvk.n_public() {
+            return Err(eyre::eyre!(
+                "amount of public inputs do not match with provided vk! Expected {}, but got {}",
+                pvk.n_public(),
+                public_inputs.len()
+            )
+            .into());
+        }
+        let prepared_inputs = P::G1::msm_unchecked(&ic[1..], public_inputs) + ic[0].into_group();
         let proof = Proof {
             a: proof.pi_a,
             b: proof.pi_b,
             c: proof.pi_c,
         };
 
-        let vk = ark_groth16::prepare_verifying_key(&vk);
-        let proof_valid = ArkworksGroth16::<P>::verify_proof(&vk, &proof, public_inputs)
-            .map_err(eyre::Report::from)?;
+        let proof_valid = ArkworksGroth16::<P>::verify_proof_with_prepared_inputs(
+            &pvk.pvk,
+            &proof,
+            &prepared_inputs,
+        )
+        .map_err(eyre::Report::from)?;
         if proof_valid {
             Ok(())
         } else {
@@ -0,0 +1,136 @@
+//! This module contains the persistent [DomainCache] of the prover.
+
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use ark_ec::pairing::Pairing;
+use ark_ff::{Field, One, PrimeField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use circom_types::traits::CircomArkworksPairingBridge;
+
+use crate::{
+    types::{DomainParams, Domains},
+    PlonkProofResult,
+};
+
+impl<F: PrimeField> DomainParams<F> {
+    /// Returns whether the roots have the order of the domains of size `domain_size`, i.e., whether a cache entry is valid.
+    fn is_valid(&self, domain_size: usize) -> bool {
+        let size = domain_size as u64;
+        self.root_of_unity_pow.pow([size]).is_one()
+            && self.root_of_unity_pow_2.pow([size * 4]).is_one()
+            && self.root_of_unity_2.pow([4]).is_one()
+            && (self.root_of_unity_pow * self.root_of_unity_pow_inv).is_one()
+            && (self.root_of_unity_pow_2 * self.root_of_unity_pow_2_inv).is_one()
+    }
+}
+
+/// A persistent cache of the evaluation domains of the prover.
+///
+/// Building the evaluation domains of a proof computes the roots of unity of the scalar field, which snarkjs derives from a quadratic non-residue, and their inverses for the inverse FFTs. The cache stores these values per curve and power of the domain size in a directory, so repeated proofs of circuits of the same size skip the setup arithmetic. Invalid or unreadable cache entries are recomputed and overwritten.
+#[derive(Debug, Clone)]
+pub struct DomainCache {
+    dir: PathBuf,
+}
+
+impl DomainCache {
+    /// Opens the cache in the provided directory, which is created if it does not exist.
+    pub fn open(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// Returns the directory of the cache.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn entry_path<P: Pairing + CircomArkworksPairingBridge>(&self, pow: u32) -> PathBuf {
+        self.dir
+            .join(format!("{}-{pow}.domain", P::get_circom_name()))
+    }
+
+    fn read_entry<P: Pairing + CircomArkworksPairingBridge>(
+        &self,
+        path: &Path,
+    ) -> io::Result<DomainParams<P::ScalarField>> {
+        let reader = BufReader::new(File::open(path)?);
+        DomainParams::deserialize_uncompressed(reader)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    fn write_entry<F: PrimeField>(path: &Path, params: &DomainParams<F>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        params
+            .serialize_uncompressed(&mut writer)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        writer.flush()
+    }
+
+    /// Returns the domains of the provided size, read from the cache or computed and stored in the cache.
+    pub(crate) fn domains<P: Pairing + CircomArkworksPairingBridge>(
+        &self,
+        domain_size: usize,
+    ) -> PlonkProofResult<Domains<P::ScalarField>> {
+        Domains::check_size(domain_size)?;
+        let path = self.entry_path::<P>(domain_size.ilog2());
+        match self.read_entry::<P>(&path) {
+            Ok(params) if params.is_valid(domain_size) => {
+                tracing::debug!("read domains from cache entry {}", path.display());
+                return Domains::with_params(domain_size, params);
+            }
+            Ok(_) => tracing::warn!("ignoring invalid cache entry {}", path.display()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => tracing::warn!("ignoring cache entry {}: {err}", path.display()),
+        }
+        let params = DomainParams::compute(domain_size);
+        if let Err(err) = Self::write_entry(&path, &params) {
+            tracing::warn!("could not write cache entry {}: {err}", path.display());
+        }
+        Domains::with_params(domain_size, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bn254::Bn254;
+    use ark_poly::EvaluationDomain;
+
+    use super::*;
+
+    #[test]
+    fn cached_domains_match_computed_domains() {
+        let dir =
+            std::env::temp_dir().join(format!("co-plonk-domain-cache-{}", std::process::id()));
+        let cache = DomainCache::open(&dir).expect("can create cache dir");
+        let domain_size = 1 << 10;
+        let expected = Domains::<ark_bn254::Fr>::new(domain_size).unwrap();
+        // the first call fills the cache, the second one reads it
+        for _ in 0..2 {
+            let domains = cache.domains::<Bn254>(domain_size).unwrap();
+            assert_eq!(domains.domain.group_gen, expected.domain.group_gen);
+            assert_eq!(domains.domain.group_gen_inv, expected.domain.group_gen_inv);
+            assert_eq!(
+                domains.extended_domain.group_gen_inv,
+                expected.extended_domain.group_gen_inv
+            );
+            assert_eq!(
+                domains.extended_domain.size(),
+                expected.extended_domain.size()
+            );
+            assert_eq!(domains.root_of_unity_2, expected.root_of_unity_2);
+            assert_eq!(domains.root_of_unity_pow_2, expected.root_of_unity_pow_2);
+        }
+        assert!(cache.entry_path::<Bn254>(10).exists());
+
+        // a corrupted entry is recomputed
+        std::fs::write(cache.entry_path::<Bn254>(10), b"garbage").unwrap();
+        let domains = cache.domains::<Bn254>(domain_size).unwrap();
+        assert_eq!(domains.root_of_unity_pow, expected.root_of_unity_pow);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,46 +1,36 @@
 //! A Plonk proof protocol that uses a collaborative MPC protocol to generate the proof.
 
 #![warn(missing_docs)]
-use ark_ec::pairing::Pairing;
-use circom_types::plonk::PlonkProof;
-use circom_types::plonk::ZKey;
-use circom_types::traits::CircomArkworksPairingBridge;
-use circom_types::traits::CircomArkworksPrimeFieldBridge;
-use co_circom_snarks::SharedWitness;
-use mpc::rep3::Rep3PlonkDriver;
-use mpc::shamir::ShamirPlonkDriver;
-use mpc::CircomPlonkProver;
-use mpc_core::protocols::rep3::network::IoContext;
-use mpc_core::protocols::rep3::network::Rep3MpcNet;
-use mpc_core::protocols::shamir::ShamirPreprocessing;
-use mpc_core::protocols::shamir::{network::ShamirMpcNet, ShamirProtocol};
-use mpc_net::config::NetworkConfig;
-use round1::Round1;
 use std::io;
-use std::marker::PhantomData;
-use std::sync::Arc;
-use std::time::Instant;
 
+#[cfg(feature = "prover")]
+mod domain_cache;
 /// This module contains the Plonk prover trait
+#[cfg(feature = "prover")]
 pub mod mpc;
 mod plonk;
+#[cfg(feature = "prover")]
+mod prover;
+#[cfg(feature = "prover")]
 mod round1;
+#[cfg(feature = "prover")]
 mod round2;
+#[cfg(feature = "prover")]
 mod round3;
+#[cfg(feature = "prover")]
 mod round4;
+#[cfg(feature = "prover")]
 mod round5;
 pub(crate) mod types;
 
+#[cfg(feature = "prover")]
+pub use domain_cache::DomainCache;
 pub use plonk::Plonk;
+#[cfg(feature = "prover")]
+pub use prover::{CoPlonk, Rep3CoPlonk, ShamirCoPlonk};
 
 type PlonkProofResult<T> = std::result::Result<T, PlonkProofError>;
 
-/// A type alias for a [CoPlonk] protocol using replicated secret sharing.
-pub type Rep3CoPlonk<P> = CoPlonk<P, Rep3PlonkDriver<Rep3MpcNet>>;
-/// A type alias for a [CoPlonk] protocol using shamir secret sharing.
-pub type ShamirCoPlonk<P> =
-    CoPlonk<P, ShamirPlonkDriver<<P as Pairing>::ScalarField, ShamirMpcNet>>;
-
 /// The errors that may arise during the computation of a co-PLONK proof.
 #[derive(Debug, thiserror::Error)]
 pub enum PlonkProofError {
@@ -58,78 +48,25 @@ pub enum PlonkProofError {
     IOError(#[from] io::Error),
 }
 
-/// A Plonk proof protocol that uses a collaborative MPC protocol to generate the proof.
-pub struct CoPlonk<P: Pairing, T: CircomPlonkProver<P>> {
-    pub(crate) driver: T,
-    phantom_data: PhantomData<P>,
-}
-
-impl<P, T> CoPlonk<P, T>
-where
-    T: CircomPlonkProver<P>,
-    P: Pairing + CircomArkworksPairingBridge,
-    P::ScalarField: CircomArkworksPrimeFieldBridge,
-    P::BaseField: CircomArkworksPrimeFieldBridge,
-{
-    /// Creates a new [CoPlonk] protocol with a given MPC driver.
-    pub fn new(driver: T) -> Self {
-        Self {
-            driver,
-            phantom_data: PhantomData,
-        }
-    }
-
-    /// Execute the PLONK prover using the internal MPC driver.
-    pub fn prove(
-        self,
-        zkey: Arc<ZKey<P>>,
-        witness: SharedWitness<P::ScalarField, T::ArithmeticShare>,
-    ) -> PlonkProofResult<PlonkProof<P>> {
-        let id = self.driver.get_party_id();
-        tracing::info!("Party {}: starting proof generation..", id);
-        let start = Instant::now();
-        tracing::debug!("starting PLONK prove!");
-        tracing::debug!(
-            "we have {} constraints and {} addition constraints",
-            zkey.n_constraints,
-            zkey.n_additions
-        );
-        tracing::debug!("the domain size is {}", zkey.domain_size);
-        tracing::debug!(
-            "we have {} n_vars and {} public inputs",
-            zkey.n_vars,
-            zkey.n_public
-        );
-        let state = Round1::init_round(self.driver, zkey.as_ref(), witness)?;
-        tracing::debug!("init round done..");
-        let state = state.round1()?;
-        tracing::debug!("round 1 done..");
-        let state = state.round2()?;
-        tracing::debug!("round 2 done..");
-        let state = state.round3()?;
-        tracing::debug!("round 3 done..");
-        let state = state.round4()?;
-        tracing::debug!("round 4 done..");
-        let result = state.round5();
-        tracing::debug!("round 5 done! We are done!");
-        let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
-        tracing::info!("Party {}: Proof generation took {} ms", id, duration_ms);
-        result
-    }
-}
-
 mod plonk_utils {
     use ark_ec::pairing::Pairing;
+    #[cfg(feature = "prover")]
     use circom_types::plonk::ZKey;
+    #[cfg(feature = "prover")]
     use rayon::prelude::*;
 
+    #[cfg(feature = "prover")]
     use crate::mpc::CircomPlonkProver;
-    use crate::types::{Domains, PlonkWitness};
+    use crate::types::Domains;
+    #[cfg(feature = "prover")]
+    use crate::types::PlonkWitness;
+    #[cfg(feature = "prover")]
     use crate::{PlonkProofError, PlonkProofResult};
     use ark_ff::Field;
     use num_traits::One;
     use num_traits::Zero;
 
+    #[cfg(feature = "prover")]
     macro_rules! rayon_join {
         ($t1: expr, $t2: expr, $t3: expr) => {{
             let ((x, y), z) = rayon::join(|| rayon::join(|| $t1, || $t2), || $t3);
@@ -137,8 +74,10 @@ mod plonk_utils {
         }};
     }
 
+    #[cfg(feature = "prover")]
     pub(crate) use rayon_join;
 
+    #[cfg(feature = "prover")]
     pub(crate) fn get_witness<P: Pairing, T: CircomPlonkProver<P>>(
         party_id: T::PartyID,
         witness: &PlonkWitness<P, T>,
@@ -163,6 +102,7 @@ mod plonk_utils {
     }
 
     // For convenience coeff is given in reverse order
+    #[cfg(feature = "prover")]
     pub(crate) fn blind_coefficients<P: Pairing, T: CircomPlonkProver<P>>(
         poly: &mut Vec<T::ArithmeticShare>,
         coeff_rev: &[T::ArithmeticShare],
@@ -180,6 +120,17 @@ mod plonk_utils {
         }
     }
 
+    /// Returns the indices of the evaluations of a selector polynomial that are not zero.
+    #[cfg(feature = "prover")]
+    pub(crate) fn non_zero_indices<F: Field>(evaluations: &[F]) -> Vec<usize> {
+        evaluations
+            .iter()
+            .enumerate()
+            .filter(|(_, eval)| !eval.is_zero())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
     pub(crate) fn calculate_lagrange_evaluations<P: Pairing>(
         power: usize,
         n_public: usize,
@@ -218,49 +169,7 @@ mod plonk_utils {
     }
 }
 
-impl<P: Pairing> Rep3CoPlonk<P> {
-    /// Create a new [Rep3CoPlonk] protocol with a given network.
-    pub fn with_network(mpc_net: Rep3MpcNet) -> eyre::Result<Self> {
-        let mut io_context0 = IoContext::init(mpc_net)?;
-        let io_context1 = io_context0.fork()?;
-        let driver = Rep3PlonkDriver::new(io_context0, io_context1);
-        Ok(CoPlonk {
-            driver,
-            phantom_data: PhantomData,
-        })
-    }
-
-    /// Create a new [Rep3CoPlonk] protocol with a given network configuration.
-    pub fn with_network_config(config: NetworkConfig) -> eyre::Result<Self> {
-        let mpc_net = Rep3MpcNet::new(config)?;
-        Self::with_network(mpc_net)
-    }
-}
-
-impl<P: Pairing> ShamirCoPlonk<P> {
-    /// Create a new [ShamirCoPlonk] protocol with a given network configuration.
-    pub fn with_network_config(
-        threshold: usize,
-        config: NetworkConfig,
-        zkey: &ZKey<P>,
-    ) -> eyre::Result<Self> {
-        let domain_size = zkey.domain_size;
-        // TODO check and explain numbers
-        let num_pairs = domain_size * 222 + 15;
-        let mpc_net = ShamirMpcNet::new(config)?;
-        let preprocessing = ShamirPreprocessing::new(threshold, mpc_net, num_pairs)?;
-        let mut protocol0 = ShamirProtocol::from(preprocessing);
-        // TODO check and explain numbers
-        let protocol1 = protocol0.fork_with_pairs(domain_size * 7 + 2)?;
-        let driver = ShamirPlonkDriver::new(protocol0, protocol1);
-        Ok(CoPlonk {
-            driver,
-            phantom_data: PhantomData,
-        })
-    }
-}
-
-#[cfg(test)]
+#[cfg(all(test, feature = "prover"))]
 mod tests {
     use ark_bn254::Bn254;
     use circom_types::groth16::JsonPublicInput;
@@ -3,10 +3,14 @@ use ark_poly::EvaluationDomain;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
 pub(crate) mod plain;
+#[cfg(feature = "instrumentation")]
+pub(crate) mod recording;
 pub(crate) mod rep3;
 pub(crate) mod shamir;
 
 pub use plain::PlainPlonkDriver;
+#[cfg(feature = "instrumentation")]
+pub use recording::{OpenedValue, OpenedValues, OpeningDivergence, RecordingPlonkDriver};
 pub use rep3::Rep3PlonkDriver;
 pub use shamir::ShamirPlonkDriver;
 
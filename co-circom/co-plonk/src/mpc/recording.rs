@@ -0,0 +1,305 @@
+use std::panic::Location;
+use std::sync::{Arc, Mutex};
+
+use ark_ec::pairing::Pairing;
+use ark_poly::EvaluationDomain;
+
+use super::{CircomPlonkProver, IoResult, VecShares};
+
+/// A value opened during the proof generation, see [`RecordingPlonkDriver`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenedValue {
+    /// The position of the opening among all openings of the proof generation
+    pub index: usize,
+    /// The driver method and its call site in the prover, e.g., `open_vec at co-circom/co-plonk/src/round4.rs:138`
+    pub label: String,
+    /// The opened values
+    pub values: Vec<String>,
+}
+
+impl std::fmt::Display for OpenedValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "#{} {}: [{}]",
+            self.index,
+            self.label,
+            self.values.join(", ")
+        )
+    }
+}
+
+/// The first opening in which two [`OpenedValues`] differ, see [`OpenedValues::first_divergence`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpeningDivergence {
+    /// Our opening, `None` if we opened fewer values than the reference
+    pub ours: Option<OpenedValue>,
+    /// The opening of the reference execution, `None` if it opened fewer values than we did
+    pub reference: Option<OpenedValue>,
+}
+
+impl std::fmt::Display for OpeningDivergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fmt = |opening: &Option<OpenedValue>| match opening {
+            Some(opening) => opening.to_string(),
+            None => "<none>".to_owned(),
+        };
+        write!(
+            f,
+            "ours {} != reference {}",
+            fmt(&self.ours),
+            fmt(&self.reference)
+        )
+    }
+}
+
+/// The log of the values opened by a [`RecordingPlonkDriver`]. Clones share the same log, so a handle can be kept while the driver is moved into the prover.
+#[derive(Debug, Clone, Default)]
+pub struct OpenedValues(Arc<Mutex<Vec<OpenedValue>>>);
+
+impl OpenedValues {
+    /// Creates an empty log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[track_caller]
+    fn record<V: ToString>(&self, method: &str, values: &[V]) {
+        let location = Location::caller();
+        let mut openings = self.0.lock().expect("not poisoned");
+        let index = openings.len();
+        openings.push(OpenedValue {
+            index,
+            label: format!("{method} at {}:{}", location.file(), location.line()),
+            values: values.iter().map(ToString::to_string).collect(),
+        });
+    }
+
+    /// Returns the recorded openings in the order they happened.
+    pub fn openings(&self) -> Vec<OpenedValue> {
+        self.0.lock().expect("not poisoned").clone()
+    }
+
+    /// Returns the first opening that differs from the reference execution, e.g., a run of the same prover with the [`PlainPlonkDriver`](super::PlainPlonkDriver).
+    pub fn first_divergence(&self, reference: &OpenedValues) -> Option<OpeningDivergence> {
+        let ours = self.openings();
+        let reference = reference.openings();
+        (0..ours.len().max(reference.len()))
+            .find(|i| ours.get(*i) != reference.get(*i))
+            .map(|i| OpeningDivergence {
+                ours: ours.get(i).cloned(),
+                reference: reference.get(i).cloned(),
+            })
+    }
+}
+
+impl std::fmt::Display for OpenedValues {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for opening in self.openings() {
+            writeln!(f, "{opening}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A Plonk driver that wraps another driver and records every value it opens into an [`OpenedValues`] log, labeled with the call site in the prover. Comparing the log of an MPC run with the log of a plain run of the same circuit shows where the runs diverge.
+///
+/// To make the logs comparable, the blinding factors are not random but public constants. The resulting proofs are NOT zero-knowledge, so this driver is only available with the test-only `instrumentation` feature.
+pub struct RecordingPlonkDriver<T> {
+    driver: T,
+    log: OpenedValues,
+    blinding: u64,
+}
+
+impl<T> RecordingPlonkDriver<T> {
+    /// Wraps the driver and records its openings into the log.
+    pub fn new(driver: T, log: OpenedValues) -> Self {
+        Self {
+            driver,
+            log,
+            blinding: 0,
+        }
+    }
+}
+
+impl<P: Pairing, T: CircomPlonkProver<P>> CircomPlonkProver<P> for RecordingPlonkDriver<T> {
+    type ArithmeticShare = T::ArithmeticShare;
+
+    type PointShareG1 = T::PointShareG1;
+
+    type PointShareG2 = T::PointShareG2;
+
+    type PartyID = T::PartyID;
+
+    type IoContext = T::IoContext;
+
+    fn rand(&mut self) -> IoResult<Self::ArithmeticShare> {
+        self.blinding += 1;
+        Ok(T::promote_to_trivial_share(
+            self.driver.get_party_id(),
+            P::ScalarField::from(self.blinding),
+        ))
+    }
+
+    fn get_party_id(&self) -> Self::PartyID {
+        self.driver.get_party_id()
+    }
+
+    fn add(a: Self::ArithmeticShare, b: Self::ArithmeticShare) -> Self::ArithmeticShare {
+        T::add(a, b)
+    }
+
+    fn add_with_public(
+        party_id: Self::PartyID,
+        shared: Self::ArithmeticShare,
+        public: P::ScalarField,
+    ) -> Self::ArithmeticShare {
+        T::add_with_public(party_id, shared, public)
+    }
+
+    fn sub(a: Self::ArithmeticShare, b: Self::ArithmeticShare) -> Self::ArithmeticShare {
+        T::sub(a, b)
+    }
+
+    fn neg_vec_in_place(&mut self, a: &mut [Self::ArithmeticShare]) {
+        self.driver.neg_vec_in_place(a)
+    }
+
+    fn mul_with_public(
+        shared: Self::ArithmeticShare,
+        public: P::ScalarField,
+    ) -> Self::ArithmeticShare {
+        T::mul_with_public(shared, public)
+    }
+
+    fn local_mul_vec(
+        &mut self,
+        a: &[Self::ArithmeticShare],
+        b: &[Self::ArithmeticShare],
+    ) -> Vec<P::ScalarField> {
+        self.driver.local_mul_vec(a, b)
+    }
+
+    fn io_round_mul_vec(&mut self, a: Vec<P::ScalarField>) -> IoResult<Vec<Self::ArithmeticShare>> {
+        self.driver.io_round_mul_vec(a)
+    }
+
+    fn mul_vec(
+        &mut self,
+        a: &[Self::ArithmeticShare],
+        b: &[Self::ArithmeticShare],
+    ) -> IoResult<Vec<Self::ArithmeticShare>> {
+        self.driver.mul_vec(a, b)
+    }
+
+    fn mul_vecs(
+        &mut self,
+        a: &[Self::ArithmeticShare],
+        b: &[Self::ArithmeticShare],
+        c: &[Self::ArithmeticShare],
+    ) -> IoResult<Vec<Self::ArithmeticShare>> {
+        self.driver.mul_vecs(a, b, c)
+    }
+
+    fn add_mul_vec(
+        &mut self,
+        a: &[Self::ArithmeticShare],
+        b: &[Self::ArithmeticShare],
+        c: &[Self::ArithmeticShare],
+    ) -> IoResult<Vec<Self::ArithmeticShare>> {
+        self.driver.add_mul_vec(a, b, c)
+    }
+
+    #[track_caller]
+    fn mul_open_vec(
+        &mut self,
+        a: &[Self::ArithmeticShare],
+        b: &[Self::ArithmeticShare],
+    ) -> IoResult<Vec<P::ScalarField>> {
+        let opened = self.driver.mul_open_vec(a, b)?;
+        self.log.record("mul_open_vec", &opened);
+        Ok(opened)
+    }
+
+    #[track_caller]
+    fn open_vec(&mut self, a: &[Self::ArithmeticShare]) -> IoResult<Vec<P::ScalarField>> {
+        let opened = self.driver.open_vec(a)?;
+        self.log.record("open_vec", &opened);
+        Ok(opened)
+    }
+
+    fn inv_vec(&mut self, a: &[Self::ArithmeticShare]) -> IoResult<Vec<Self::ArithmeticShare>> {
+        self.driver.inv_vec(a)
+    }
+
+    fn promote_to_trivial_share(
+        party_id: Self::PartyID,
+        public_value: P::ScalarField,
+    ) -> Self::ArithmeticShare {
+        T::promote_to_trivial_share(party_id, public_value)
+    }
+
+    fn fft<D: EvaluationDomain<P::ScalarField>>(
+        data: &[Self::ArithmeticShare],
+        domain: &D,
+    ) -> Vec<Self::ArithmeticShare> {
+        T::fft(data, domain)
+    }
+
+    fn ifft<D: EvaluationDomain<P::ScalarField>>(
+        data: &[Self::ArithmeticShare],
+        domain: &D,
+    ) -> Vec<Self::ArithmeticShare> {
+        T::ifft(data, domain)
+    }
+
+    #[track_caller]
+    fn open_point_g1(&mut self, a: Self::PointShareG1) -> IoResult<P::G1> {
+        let opened = self.driver.open_point_g1(a)?;
+        self.log.record("open_point_g1", &[opened]);
+        Ok(opened)
+    }
+
+    #[track_caller]
+    fn open_point_vec_g1(&mut self, a: &[Self::PointShareG1]) -> IoResult<Vec<P::G1>> {
+        let opened = self.driver.open_point_vec_g1(a)?;
+        self.log.record("open_point_vec_g1", &opened);
+        Ok(opened)
+    }
+
+    fn msm_public_points_g1(
+        points: &[P::G1Affine],
+        scalars: &[Self::ArithmeticShare],
+    ) -> Self::PointShareG1 {
+        T::msm_public_points_g1(points, scalars)
+    }
+
+    fn evaluate_poly_public(
+        poly: Vec<Self::ArithmeticShare>,
+        point: P::ScalarField,
+    ) -> (Self::ArithmeticShare, Vec<Self::ArithmeticShare>) {
+        T::evaluate_poly_public(poly, point)
+    }
+
+    fn array_prod_mul(
+        io_context: &mut Self::IoContext,
+        inv: bool,
+        arr1: &[Self::ArithmeticShare],
+        arr2: &[Self::ArithmeticShare],
+        arr3: &[Self::ArithmeticShare],
+    ) -> IoResult<Vec<Self::ArithmeticShare>> {
+        T::array_prod_mul(io_context, inv, arr1, arr2, arr3)
+    }
+
+    fn array_prod_mul2(
+        &mut self,
+        n1: &[Self::ArithmeticShare],
+        n2: &[Self::ArithmeticShare],
+        n3: &[Self::ArithmeticShare],
+        d1: &[Self::ArithmeticShare],
+        d2: &[Self::ArithmeticShare],
+        d3: &[Self::ArithmeticShare],
+    ) -> IoResult<VecShares<Self::ArithmeticShare>> {
+        self.driver.array_prod_mul2(n1, n2, n3, d1, d2, d3)
+    }
+}
@@ -45,7 +45,7 @@ impl<P: Pairing, N: Rep3Network> CircomPlonkProver<P> for Rep3PlonkDriver<N> {
     }
 
     fn add(a: Self::ArithmeticShare, b: Self::ArithmeticShare) -> Self::ArithmeticShare {
-        arithmetic::add(a, b)
+        a + b
     }
 
     fn add_with_public(
@@ -57,12 +57,12 @@ impl<P: Pairing, N: Rep3Network> CircomPlonkProver<P> for Rep3PlonkDriver<N> {
     }
 
     fn sub(a: Self::ArithmeticShare, b: Self::ArithmeticShare) -> Self::ArithmeticShare {
-        arithmetic::sub(a, b)
+        a - b
     }
 
     fn neg_vec_in_place(&mut self, vec: &mut [Self::ArithmeticShare]) {
         for a in vec.iter_mut() {
-            *a = arithmetic::neg(*a);
+            *a = -*a;
         }
     }
 
@@ -70,7 +70,7 @@ impl<P: Pairing, N: Rep3Network> CircomPlonkProver<P> for Rep3PlonkDriver<N> {
         shared: Self::ArithmeticShare,
         public: P::ScalarField,
     ) -> Self::ArithmeticShare {
-        arithmetic::mul_public(shared, public)
+        shared * public
     }
 
     fn local_mul_vec(
@@ -204,7 +204,7 @@ impl<P: Pairing, N: Rep3Network> CircomPlonkProver<P> for Rep3PlonkDriver<N> {
         }
 
         for (unblind, open) in unblind.iter_mut().zip(open.into_iter()) {
-            *unblind = arithmetic::mul_public(*unblind, open);
+            *unblind *= open;
         }
         if inv {
             Ok(arithmetic::inv_vec(&unblind, io_context)?)
@@ -47,7 +47,7 @@ impl<P: Pairing, N: ShamirNetwork> CircomPlonkProver<P> for ShamirPlonkDriver<P:
     }
 
     fn add(a: Self::ArithmeticShare, b: Self::ArithmeticShare) -> Self::ArithmeticShare {
-        arithmetic::add(a, b)
+        a + b
     }
 
     fn add_with_public(
@@ -59,12 +59,12 @@ impl<P: Pairing, N: ShamirNetwork> CircomPlonkProver<P> for ShamirPlonkDriver<P:
     }
 
     fn sub(a: Self::ArithmeticShare, b: Self::ArithmeticShare) -> Self::ArithmeticShare {
-        arithmetic::sub(a, b)
+        a - b
     }
 
     fn neg_vec_in_place(&mut self, a: &mut [Self::ArithmeticShare]) {
         for a in a.iter_mut() {
-            *a = arithmetic::neg(*a);
+            *a = -*a;
         }
     }
 
@@ -72,7 +72,7 @@ impl<P: Pairing, N: ShamirNetwork> CircomPlonkProver<P> for ShamirPlonkDriver<P:
         shared: Self::ArithmeticShare,
         public: <P as Pairing>::ScalarField,
     ) -> Self::ArithmeticShare {
-        arithmetic::mul_public(shared, public)
+        shared * public
     }
 
     fn local_mul_vec(
@@ -213,7 +213,7 @@ impl<P: Pairing, N: ShamirNetwork> CircomPlonkProver<P> for ShamirPlonkDriver<P:
         }
 
         for (unblind, open) in unblind.iter_mut().zip(open.into_iter()) {
-            *unblind = arithmetic::mul_public(*unblind, open);
+            *unblind *= open;
         }
         if inv {
             Ok(arithmetic::inv_vec(&unblind, io_context)?)
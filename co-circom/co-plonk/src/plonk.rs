@@ -4,16 +4,24 @@
 //! You will most likely need the plain PLONK implementation to verify a proof from co-PLONK. For that
 //! see the [`Plonk::verify`] method.
 
-use std::{marker::PhantomData, sync::Arc};
+use std::marker::PhantomData;
+#[cfg(feature = "prover")]
+use std::sync::Arc;
 
-use crate::{mpc::plain::PlainPlonkDriver, plonk_utils, types::Domains, CoPlonk};
+#[cfg(feature = "prover")]
+use crate::{mpc::plain::PlainPlonkDriver, CoPlonk};
+use crate::{plonk_utils, types::Domains};
 use ark_ec::{pairing::Pairing, Group};
 use ark_ff::Field;
+#[cfg(feature = "prover")]
+use circom_types::plonk::ZKey;
 use circom_types::{
-    plonk::{JsonVerificationKey, PlonkProof, ZKey},
+    plonk::{JsonVerificationKey, PlonkProof},
     traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge},
 };
-use co_circom_snarks::{SharedWitness, VerificationError};
+#[cfg(feature = "prover")]
+use co_circom_snarks::SharedWitness;
+use co_circom_snarks::VerificationError;
 use num_traits::One;
 use num_traits::Zero;
 
@@ -29,8 +37,13 @@ use crate::types::Keccak256Transcript;
 ///
 /// More interesting is the [`Plonk::verify`] method. You can verify any circom PLONK proof, be it
 /// from snarkjs or one created by this project.
+#[cfg(feature = "prover")]
 pub type Plonk<P> = CoPlonk<P, PlainPlonkDriver>;
 
+/// The plain [`Plonk`] type. Without the `prover` feature, it can only be used to verify proofs.
+#[cfg(not(feature = "prover"))]
+pub struct Plonk<P: Pairing>(PhantomData<P>);
+
 pub(crate) struct VerifierChallenges<P: Pairing> {
     pub(super) alpha: P::ScalarField,
     pub(super) beta: P::ScalarField,
@@ -63,9 +76,7 @@ where
         transcript.add_point(vk.s2);
         transcript.add_point(vk.s3);
 
-        for p in public_inputs.iter().cloned() {
-            transcript.add_scalar(p);
-        }
+        transcript.add_scalars(public_inputs.iter().cloned());
 
         transcript.add_point(proof.a);
         transcript.add_point(proof.b);
@@ -273,6 +284,7 @@ where
     }
 }
 
+#[cfg(feature = "prover")]
 impl<P: Pairing> Plonk<P>
 where
     P: CircomArkworksPairingBridge,
@@ -0,0 +1,155 @@
+//! This module contains the [CoPlonk] prover, which is only available with the `prover` feature.
+
+use ark_ec::pairing::Pairing;
+use circom_types::plonk::PlonkProof;
+use circom_types::plonk::ZKey;
+use circom_types::traits::CircomArkworksPairingBridge;
+use circom_types::traits::CircomArkworksPrimeFieldBridge;
+use co_circom_snarks::SharedWitness;
+use mpc_core::protocols::rep3::network::IoContext;
+use mpc_core::protocols::rep3::network::Rep3MpcNet;
+use mpc_core::protocols::shamir::ShamirPreprocessing;
+use mpc_core::protocols::shamir::{network::ShamirMpcNet, ShamirProtocol};
+use mpc_net::config::NetworkConfig;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::mpc::rep3::Rep3PlonkDriver;
+use crate::mpc::shamir::ShamirPlonkDriver;
+use crate::mpc::CircomPlonkProver;
+use crate::round1::Round1;
+use crate::types::Domains;
+use crate::DomainCache;
+use crate::PlonkProofResult;
+
+/// A type alias for a [CoPlonk] protocol using replicated secret sharing.
+pub type Rep3CoPlonk<P> = CoPlonk<P, Rep3PlonkDriver<Rep3MpcNet>>;
+/// A type alias for a [CoPlonk] protocol using shamir secret sharing.
+pub type ShamirCoPlonk<P> =
+    CoPlonk<P, ShamirPlonkDriver<<P as Pairing>::ScalarField, ShamirMpcNet>>;
+
+/// A Plonk proof protocol that uses a collaborative MPC protocol to generate the proof.
+pub struct CoPlonk<P: Pairing, T: CircomPlonkProver<P>> {
+    pub(crate) driver: T,
+    pub(crate) domain_cache: Option<DomainCache>,
+    pub(crate) phantom_data: PhantomData<P>,
+}
+
+impl<P, T> CoPlonk<P, T>
+where
+    T: CircomPlonkProver<P>,
+    P: Pairing + CircomArkworksPairingBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+{
+    /// Creates a new [CoPlonk] protocol with a given MPC driver.
+    pub fn new(driver: T) -> Self {
+        Self {
+            driver,
+            domain_cache: None,
+            phantom_data: PhantomData,
+        }
+    }
+
+    /// Reads the evaluation domains from the provided [DomainCache] instead of computing them for every proof.
+    pub fn with_domain_cache(mut self, cache: DomainCache) -> Self {
+        self.domain_cache = Some(cache);
+        self
+    }
+
+    /// Execute the PLONK prover using the internal MPC driver.
+    pub fn prove(
+        self,
+        zkey: Arc<ZKey<P>>,
+        witness: SharedWitness<P::ScalarField, T::ArithmeticShare>,
+    ) -> PlonkProofResult<PlonkProof<P>> {
+        let id = self.driver.get_party_id();
+        tracing::info!("Party {}: starting proof generation..", id);
+        let start = Instant::now();
+        tracing::debug!("starting PLONK prove!");
+        tracing::debug!(
+            "we have {} constraints and {} addition constraints",
+            zkey.n_constraints,
+            zkey.n_additions
+        );
+        tracing::debug!("the domain size is {}", zkey.domain_size);
+        tracing::debug!(
+            "we have {} n_vars and {} public inputs",
+            zkey.n_vars,
+            zkey.n_public
+        );
+        let domains = match &self.domain_cache {
+            Some(cache) => cache.domains::<P>(zkey.domain_size)?,
+            None => Domains::new(zkey.domain_size)?,
+        };
+        let state = Round1::init_round_with_domains(self.driver, zkey.as_ref(), witness, domains)?;
+        tracing::debug!("init round done..");
+        let state = state.round1()?;
+        tracing::debug!("round 1 done..");
+        let state = state.round2()?;
+        tracing::debug!("round 2 done..");
+        let state = state.round3()?;
+        tracing::debug!("round 3 done..");
+        let state = state.round4()?;
+        tracing::debug!("round 4 done..");
+        let result = state.round5();
+        tracing::debug!("round 5 done! We are done!");
+        let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
+        tracing::info!("Party {}: Proof generation took {} ms", id, duration_ms);
+        result
+    }
+}
+
+impl<P: Pairing> Rep3CoPlonk<P> {
+    /// Create a new [Rep3CoPlonk] protocol with a given network.
+    pub fn with_network(mpc_net: Rep3MpcNet) -> eyre::Result<Self> {
+        let mut io_context0 = IoContext::init(mpc_net)?;
+        let io_context1 = io_context0.fork()?;
+        let driver = Rep3PlonkDriver::new(io_context0, io_context1);
+        Ok(CoPlonk {
+            driver,
+            domain_cache: None,
+            phantom_data: PhantomData,
+        })
+    }
+
+    /// Create a new [Rep3CoPlonk] protocol with a given network configuration.
+    pub fn with_network_config(config: NetworkConfig) -> eyre::Result<Self> {
+        let mpc_net = Rep3MpcNet::new(config)?;
+        Self::with_network(mpc_net)
+    }
+}
+
+impl<P: Pairing> ShamirCoPlonk<P> {
+    /// Create a new [ShamirCoPlonk] protocol with a given network configuration.
+    pub fn with_network_config(
+        threshold: usize,
+        config: NetworkConfig,
+        zkey: &ZKey<P>,
+    ) -> eyre::Result<Self> {
+        let mpc_net = ShamirMpcNet::new(config)?;
+        Self::with_network(threshold, mpc_net, zkey)
+    }
+
+    /// Create a new [ShamirCoPlonk] protocol from an already established network.
+    pub fn with_network(
+        threshold: usize,
+        mpc_net: ShamirMpcNet,
+        zkey: &ZKey<P>,
+    ) -> eyre::Result<Self> {
+        let domain_size = zkey.domain_size;
+        // TODO check and explain numbers
+        let num_pairs = domain_size * 222 + 15;
+        let preprocessing = ShamirPreprocessing::new(threshold, mpc_net, num_pairs)?;
+        let mut protocol0 = ShamirProtocol::from(preprocessing);
+        // TODO check and explain numbers
+        let protocol1 = protocol0.fork_with_pairs(domain_size * 7 + 2)?;
+        let driver = ShamirPlonkDriver::new(protocol0, protocol1);
+        Ok(CoPlonk {
+            driver,
+            domain_cache: None,
+            phantom_data: PhantomData,
+        })
+    }
+}
@@ -212,16 +212,26 @@ impl<'a, P: Pairing, T: CircomPlonkProver<P>> Round1<'a, P, T> {
         Ok(witness)
     }
 
-    #[instrument(level = "debug", name = "Plonk - Round Init", skip_all)]
+    #[cfg(test)]
     pub(super) fn init_round(
+        driver: T,
+        zkey: &'a ZKey<P>,
+        private_witness: SharedWitness<P::ScalarField, T::ArithmeticShare>,
+    ) -> PlonkProofResult<Self> {
+        let domains = Domains::new(zkey.domain_size)?;
+        Self::init_round_with_domains(driver, zkey, private_witness, domains)
+    }
+
+    #[instrument(level = "debug", name = "Plonk - Round Init", skip_all)]
+    pub(super) fn init_round_with_domains(
         mut driver: T,
         zkey: &'a ZKey<P>,
         private_witness: SharedWitness<P::ScalarField, T::ArithmeticShare>,
+        domains: Domains<P::ScalarField>,
     ) -> PlonkProofResult<Self> {
         let plonk_witness = Self::calculate_additions(&mut driver, private_witness, zkey)?;
         // TODO: we do not want that to be
         let challenges = Round1Challenges::random(&mut driver)?;
-        let domains = Domains::new(zkey.domain_size)?;
         Ok(Self {
             challenges,
             driver,
@@ -214,9 +214,7 @@ impl<'a, P: Pairing, T: CircomPlonkProver<P>> Round2<'a, P, T> {
         transcript.add_point(zkey.verifying_key.s1);
         transcript.add_point(zkey.verifying_key.s2);
         transcript.add_point(zkey.verifying_key.s3);
-        for val in public_input.iter().cloned() {
-            transcript.add_scalar(val);
-        }
+        transcript.add_scalars(public_input.iter().cloned());
         transcript.add_point(proof.commit_a.into());
         transcript.add_point(proof.commit_b.into());
         transcript.add_point(proof.commit_c.into());
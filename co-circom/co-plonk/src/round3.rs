@@ -1,5 +1,6 @@
 use crate::{
     mpc::CircomPlonkProver,
+    plonk_utils,
     round2::{Round2Challenges, Round2Polys, Round2Proof},
     round4::Round4,
     types::{Domains, Keccak256Transcript, PlonkData, PolyEval},
@@ -205,6 +206,26 @@ impl<'a, P: Pairing, T: CircomPlonkProver<P>> Round3<'a, P, T> {
         [zero, two + two_root_unity, neg_eight, two - two_root_unity]
     }
 
+    // Multiplies lhs and rhs at the provided indices. The products at all other indices are set to zero.
+    fn mul_vec_at(
+        driver: &mut T,
+        lhs: &[T::ArithmeticShare],
+        rhs: &[T::ArithmeticShare],
+        indices: &[usize],
+    ) -> PlonkProofResult<Vec<T::ArithmeticShare>> {
+        if indices.len() == lhs.len() {
+            return Ok(driver.mul_vec(lhs, rhs)?);
+        }
+        let lhs_at = indices.iter().map(|i| lhs[*i]).collect::<Vec<_>>();
+        let rhs_at = indices.iter().map(|i| rhs[*i]).collect::<Vec<_>>();
+        let products = driver.mul_vec(&lhs_at, &rhs_at)?;
+        let mut result = vec![T::ArithmeticShare::default(); lhs.len()];
+        for (i, product) in izip!(indices, products) {
+            result[*i] = product;
+        }
+        Ok(result)
+    }
+
     // Compute the quotient polynomial T(X) (see https://eprint.iacr.org/2019/953.pdf)
     // It is implemented with a constant number of communication rounds in MPC
     fn compute_t(
@@ -234,11 +255,23 @@ impl<'a, P: Pairing, T: CircomPlonkProver<P>> Round3<'a, P, T> {
             w *= &pow_plus2_root_of_unity;
         });
 
+        // The products of a and b are only used multiplied with qm. For sparse circuits qm vanishes on a large part of the extended domain, so we only multiply at the indices where qm is not zero.
+        let qm_non_zero = plonk_utils::non_zero_indices(&zkey.qm_poly.evaluations);
+        tracing::debug!(
+            "qm is not zero at {} of {} evaluations",
+            qm_non_zero.len(),
+            zkey.domain_size * 4
+        );
         // TODO parallelize these? With a different network structure this might not be needed though
-        let a_b = driver.mul_vec(&polys.poly_eval_a.eval, &polys.poly_eval_b.eval)?;
-        let a_bp = driver.mul_vec(&polys.poly_eval_a.eval, &bp)?;
-        let ap_b = driver.mul_vec(&polys.poly_eval_b.eval, &ap)?;
-        let ap_bp = driver.mul_vec(&ap, &bp)?;
+        let a_b = Self::mul_vec_at(
+            driver,
+            &polys.poly_eval_a.eval,
+            &polys.poly_eval_b.eval,
+            &qm_non_zero,
+        )?;
+        let a_bp = Self::mul_vec_at(driver, &polys.poly_eval_a.eval, &bp, &qm_non_zero)?;
+        let ap_b = Self::mul_vec_at(driver, &polys.poly_eval_b.eval, &ap, &qm_non_zero)?;
+        let ap_bp = Self::mul_vec_at(driver, &ap, &bp, &qm_non_zero)?;
 
         // TODO keep RAM requirements in mind
         let mut e1 = Vec::with_capacity(zkey.domain_size * 4);
@@ -292,27 +325,38 @@ impl<'a, P: Pairing, T: CircomPlonkProver<P>> Round3<'a, P, T> {
             let zwp_ = T::add(challenges.b[8], zwp_);
             zwp.push(zwp_);
 
-            let mut a0 = T::add(*a_bp, *ap_b);
-            let mod_i = i % 4;
-            if mod_i != 0 {
-                let z1 = z1[mod_i];
-                let ap_bp = ap_bp[i];
-                let tmp = T::mul_with_public(ap_bp, z1);
-                a0 = T::add(a0, tmp);
-            }
-
-            let (mut e1_, mut e1z_) = (a_b.to_owned(), a0.to_owned());
-            e1_ = T::mul_with_public(e1_, qm);
-            e1z_ = T::mul_with_public(e1z_, qm);
+            // skip the multiplications with selectors that are zero at this index
+            let (mut e1_, mut e1z_) = if qm.is_zero() {
+                (T::ArithmeticShare::default(), T::ArithmeticShare::default())
+            } else {
+                let mut a0 = T::add(*a_bp, *ap_b);
+                let mod_i = i % 4;
+                if mod_i != 0 {
+                    let z1 = z1[mod_i];
+                    let ap_bp = ap_bp[i];
+                    let tmp = T::mul_with_public(ap_bp, z1);
+                    a0 = T::add(a0, tmp);
+                }
+                (
+                    T::mul_with_public(a_b.to_owned(), qm),
+                    T::mul_with_public(a0, qm),
+                )
+            };
 
-            e1_ = driver.add_mul_public(e1_, *a, ql);
-            e1z_ = driver.add_mul_public(e1z_, *ap, ql);
+            if !ql.is_zero() {
+                e1_ = driver.add_mul_public(e1_, *a, ql);
+                e1z_ = driver.add_mul_public(e1z_, *ap, ql);
+            }
 
-            e1_ = driver.add_mul_public(e1_, *b, qr);
-            e1z_ = driver.add_mul_public(e1z_, *bp, qr);
+            if !qr.is_zero() {
+                e1_ = driver.add_mul_public(e1_, *b, qr);
+                e1z_ = driver.add_mul_public(e1z_, *bp, qr);
+            }
 
-            e1_ = driver.add_mul_public(e1_, *c, qo);
-            e1z_ = driver.add_mul_public(e1z_, cp[i], qo);
+            if !qo.is_zero() {
+                e1_ = driver.add_mul_public(e1_, *c, qo);
+                e1z_ = driver.add_mul_public(e1z_, cp[i], qo);
+            }
 
             let mut pi = T::ArithmeticShare::default();
             for (j, lagrange) in zkey.lagrange.iter().enumerate() {
@@ -493,14 +537,23 @@ pub mod tests {
 
     use crate::{
         mpc::plain::PlainPlonkDriver,
+        plonk_utils,
         round1::{Round1, Round1Challenges},
+        round3::Round3,
     };
 
     use ark_ec::pairing::Pairing;
+    use circom_types::groth16::JsonPublicInput;
+    use circom_types::plonk::{JsonVerificationKey, PlonkProof};
+    use circom_types::traits::{CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge};
+    use num_traits::Zero;
     use std::str::FromStr;
+    use std::sync::Arc;
 
     use circom_types::traits::CheckElement;
 
+    use crate::plonk::Plonk;
+
     macro_rules! g1_from_xy {
         ($x: expr,$y: expr) => {
             <ark_bn254::Bn254 as Pairing>::G1Affine::new(
@@ -556,4 +609,87 @@ pub mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_mul_vec_at_skips_zero_selectors() {
+        let mut driver = PlainPlonkDriver;
+        let mut reader = BufReader::new(
+            File::open("../../test_vectors/Plonk/bn254/multiplier2/circuit.zkey").unwrap(),
+        );
+        let zkey = ZKey::<Bn254>::from_reader(&mut reader, CheckElement::No).unwrap();
+        let qm = &zkey.qm_poly.evaluations;
+        let indices = plonk_utils::non_zero_indices(qm);
+        assert!(indices.len() < qm.len());
+
+        let lhs = (0..qm.len() as u64)
+            .map(ark_bn254::Fr::from)
+            .collect::<Vec<_>>();
+        let rhs = (0..qm.len() as u64)
+            .map(|i| ark_bn254::Fr::from(i + 7))
+            .collect::<Vec<_>>();
+        let products =
+            Round3::<Bn254, PlainPlonkDriver>::mul_vec_at(&mut driver, &lhs, &rhs, &indices)
+                .unwrap();
+        for (i, (product, qm)) in products.into_iter().zip(qm).enumerate() {
+            if qm.is_zero() {
+                assert!(product.is_zero());
+            } else {
+                assert_eq!(product, lhs[i] * rhs[i]);
+            }
+        }
+    }
+
+    // Proves with the sparse quotient computation and checks the proof against the verification key generated by snarkjs. The proof of snarkjs has to verify as well, such that the verifier agrees with snarkjs on the test vectors.
+    fn prove_and_verify_with_snarkjs_vk<P>(curve: &str, name: &str)
+    where
+        P: Pairing + CircomArkworksPairingBridge,
+        P::BaseField: CircomArkworksPrimeFieldBridge,
+        P::ScalarField: CircomArkworksPrimeFieldBridge,
+    {
+        let dir = format!("../../test_vectors/Plonk/{curve}/{name}");
+        let zkey = ZKey::<P>::from_reader(
+            BufReader::new(File::open(format!("{dir}/circuit.zkey")).unwrap()),
+            CheckElement::No,
+        )
+        .unwrap();
+        let witness = Witness::<P::ScalarField>::from_reader(
+            File::open(format!("{dir}/witness.wtns")).unwrap(),
+        )
+        .unwrap();
+        let vk: JsonVerificationKey<P> =
+            serde_json::from_reader(File::open(format!("{dir}/verification_key.json")).unwrap())
+                .unwrap();
+        let public_input: JsonPublicInput<P::ScalarField> =
+            serde_json::from_reader(File::open(format!("{dir}/public.json")).unwrap()).unwrap();
+        let snarkjs_proof: PlonkProof<P> =
+            serde_json::from_reader(File::open(format!("{dir}/circom.proof")).unwrap()).unwrap();
+        Plonk::<P>::verify(&vk, &snarkjs_proof, &public_input.values).unwrap();
+
+        // the multiplications are only skipped if qm vanishes somewhere on the extended domain
+        let qm = &zkey.qm_poly.evaluations;
+        assert!(plonk_utils::non_zero_indices(qm).len() < qm.len());
+
+        let witness = SharedWitness {
+            public_inputs: witness.values[..=zkey.n_public].to_vec(),
+            witness: witness.values[zkey.n_public + 1..].to_vec(),
+        };
+        let mut proof = Plonk::<P>::plain_prove(Arc::new(zkey), witness).unwrap();
+        Plonk::<P>::verify(&vk, &proof, &public_input.values).unwrap();
+
+        // the commitments to the quotient polynomial are checked by the verifier
+        std::mem::swap(&mut proof.t1, &mut proof.t2);
+        assert!(Plonk::<P>::verify(&vk, &proof, &public_input.values).is_err());
+    }
+
+    #[test]
+    fn test_round3_proofs_verify_with_snarkjs_vk_bn254() {
+        prove_and_verify_with_snarkjs_vk::<Bn254>("bn254", "multiplier2");
+        prove_and_verify_with_snarkjs_vk::<Bn254>("bn254", "poseidon");
+    }
+
+    #[test]
+    fn test_round3_proofs_verify_with_snarkjs_vk_bls12_381() {
+        prove_and_verify_with_snarkjs_vk::<ark_bls12_381::Bls12_381>("bls12_381", "multiplier2");
+        prove_and_verify_with_snarkjs_vk::<ark_bls12_381::Bls12_381>("bls12_381", "poseidon");
+    }
 }
@@ -1,13 +1,18 @@
 use ark_ec::AffineRepr;
 use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+#[cfg(feature = "prover")]
 use circom_types::plonk::ZKey;
+#[cfg(feature = "prover")]
 use co_circom_snarks::SharedWitness;
 use std::marker::PhantomData;
 
-use crate::{mpc::CircomPlonkProver, PlonkProofError, PlonkProofResult};
+#[cfg(feature = "prover")]
+use crate::mpc::CircomPlonkProver;
+use crate::{PlonkProofError, PlonkProofResult};
 use ark_ec::pairing::Pairing;
 use ark_ff::PrimeField;
-use ark_serialize::CanonicalSerialize;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+#[cfg(feature = "prover")]
 use num_traits::Zero;
 use sha3::{Digest, Keccak256};
 
@@ -22,6 +27,7 @@ where
     phantom_data: PhantomData<P>,
 }
 
+#[cfg(feature = "prover")]
 pub(super) struct PolyEval<P: Pairing, T: CircomPlonkProver<P>> {
     pub(super) poly: Vec<T::ArithmeticShare>,
     pub(super) eval: Vec<T::ArithmeticShare>,
@@ -35,59 +41,97 @@ pub(super) struct Domains<F: PrimeField> {
     pub(super) root_of_unity_pow_2: F,
 }
 
+#[cfg(feature = "prover")]
 pub(super) struct PlonkWitness<P: Pairing, T: CircomPlonkProver<P>> {
     pub(super) public_inputs: Vec<P::ScalarField>,
     pub(super) witness: Vec<T::ArithmeticShare>,
     pub(super) addition_witness: Vec<T::ArithmeticShare>,
 }
 
+#[cfg(feature = "prover")]
 pub(super) struct PlonkData<'a, P: Pairing, T: CircomPlonkProver<P>> {
     pub(super) witness: PlonkWitness<P, T>,
     pub(super) zkey: &'a ZKey<P>,
 }
 
+/// The roots of unity of the domains of one domain size, see [Domains::with_params].
+#[derive(Debug, Clone, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub(crate) struct DomainParams<F: PrimeField> {
+    pub(crate) root_of_unity_2: F,
+    pub(crate) root_of_unity_pow: F,
+    pub(crate) root_of_unity_pow_2: F,
+    pub(crate) root_of_unity_pow_inv: F,
+    pub(crate) root_of_unity_pow_2_inv: F,
+}
+
 impl<F: PrimeField> Domains<F> {
-    pub(super) fn new(domain_size: usize) -> PlonkProofResult<Self> {
-        tracing::debug!("building domains/roots of unity for domain size: {domain_size}");
-        if domain_size & (domain_size - 1) != 0 || domain_size == 0 {
+    pub(super) fn check_size(domain_size: usize) -> PlonkProofResult<()> {
+        if domain_size == 0 || domain_size & (domain_size - 1) != 0 {
             Err(PlonkProofError::InvalidDomainSize(domain_size))
         } else {
-            let mut domain = Radix2EvaluationDomain::<F>::new(domain_size)
-                .ok_or(PlonkProofError::PolynomialDegreeTooLarge)?;
-            let mut extended_domain = Radix2EvaluationDomain::<F>::new(domain_size * 4)
-                .ok_or(PlonkProofError::PolynomialDegreeTooLarge)?;
-            let (_, roots_of_unity) = co_circom_snarks::utils::roots_of_unity();
-            let pow = usize::try_from(domain_size.ilog2()).expect("u32 fits into usize");
-
-            tracing::trace!(
-                "setting arkworks root of unity (domain size) by hand: {}",
-                roots_of_unity[pow]
-            );
-            tracing::trace!(
-                "setting arkworks root of unity (extended) by hand: {}",
-                roots_of_unity[pow + 2]
-            );
-            // snarkjs and arkworks use different roots of unity to compute (i)fft.
-            // therefore we compute the roots of unity by hand like snarkjs and
-            // set the root of unity accordingly by hand
-            domain.group_gen = roots_of_unity[pow];
-            domain.group_gen_inv = domain.group_gen.inverse().expect("can compute inverse");
-            extended_domain.group_gen = roots_of_unity[pow + 2];
-            extended_domain.group_gen_inv = extended_domain
-                .group_gen
+            Ok(())
+        }
+    }
+
+    pub(super) fn new(domain_size: usize) -> PlonkProofResult<Self> {
+        Self::check_size(domain_size)?;
+        Self::with_params(domain_size, DomainParams::compute(domain_size))
+    }
+
+    pub(super) fn with_params(
+        domain_size: usize,
+        params: DomainParams<F>,
+    ) -> PlonkProofResult<Self> {
+        tracing::debug!("building domains/roots of unity for domain size: {domain_size}");
+        let mut domain = Radix2EvaluationDomain::<F>::new(domain_size)
+            .ok_or(PlonkProofError::PolynomialDegreeTooLarge)?;
+        let mut extended_domain = Radix2EvaluationDomain::<F>::new(domain_size * 4)
+            .ok_or(PlonkProofError::PolynomialDegreeTooLarge)?;
+        // snarkjs and arkworks use different roots of unity to compute (i)fft.
+        // therefore we compute the roots of unity by hand like snarkjs and
+        // set the root of unity accordingly by hand
+        domain.group_gen = params.root_of_unity_pow;
+        domain.group_gen_inv = params.root_of_unity_pow_inv;
+        extended_domain.group_gen = params.root_of_unity_pow_2;
+        extended_domain.group_gen_inv = params.root_of_unity_pow_2_inv;
+
+        Ok(Self {
+            domain,
+            extended_domain,
+            root_of_unity_2: params.root_of_unity_2,
+            root_of_unity_pow: params.root_of_unity_pow,
+            root_of_unity_pow_2: params.root_of_unity_pow_2,
+        })
+    }
+}
+
+impl<F: PrimeField> DomainParams<F> {
+    /// Computes the roots of unity of the domains of size `domain_size`, which needs to be a power of two.
+    pub(super) fn compute(domain_size: usize) -> Self {
+        let (_, roots_of_unity) = co_circom_snarks::utils::roots_of_unity::<F>();
+        let pow = usize::try_from(domain_size.ilog2()).expect("u32 fits into usize");
+
+        tracing::trace!(
+            "setting arkworks root of unity (domain size) by hand: {}",
+            roots_of_unity[pow]
+        );
+        tracing::trace!(
+            "setting arkworks root of unity (extended) by hand: {}",
+            roots_of_unity[pow + 2]
+        );
+        Self {
+            root_of_unity_2: roots_of_unity[2],
+            root_of_unity_pow: roots_of_unity[pow],
+            root_of_unity_pow_2: roots_of_unity[pow + 2],
+            root_of_unity_pow_inv: roots_of_unity[pow].inverse().expect("can compute inverse"),
+            root_of_unity_pow_2_inv: roots_of_unity[pow + 2]
                 .inverse()
-                .expect("can compute inverse");
-
-            Ok(Self {
-                domain,
-                extended_domain,
-                root_of_unity_2: roots_of_unity[2],
-                root_of_unity_pow: roots_of_unity[pow],
-                root_of_unity_pow_2: roots_of_unity[pow + 2],
-            })
+                .expect("can compute inverse"),
         }
     }
 }
+
+#[cfg(feature = "prover")]
 impl<P: Pairing, T: CircomPlonkProver<P>> PlonkWitness<P, T> {
     pub(super) fn new(
         mut shared_witness: SharedWitness<P::ScalarField, T::ArithmeticShare>,
@@ -126,6 +170,19 @@ where
         self.digest.update(&buf);
     }
 
+    /// Adds the scalars to the transcript one by one, reusing a single buffer. Use this for the public inputs, which may be large.
+    pub(super) fn add_scalars(&mut self, scalars: impl IntoIterator<Item = P::ScalarField>) {
+        let mut buf = vec![];
+        for scalar in scalars {
+            buf.clear();
+            scalar
+                .serialize_uncompressed(&mut buf)
+                .expect("Can Fr write into Vec<u8>");
+            buf.reverse();
+            self.digest.update(&buf);
+        }
+    }
+
     pub(super) fn add_point(&mut self, point: P::G1Affine) {
         let byte_len: usize = P::BaseField::MODULUS_BIT_SIZE
             .div_ceil(8)
@@ -2,6 +2,7 @@
 
 pub mod mpc;
 pub mod solver;
+pub use co_brillig::{BrilligExecution, BrilligPolicy, SharedLoopPolicy};
 pub use mpc::plain::PlainAcvmSolver;
 pub use mpc::rep3::{Rep3AcvmSolver, Rep3AcvmType};
 pub use mpc::shamir::{ShamirAcvmSolver, ShamirAcvmType};
@@ -1,11 +1,18 @@
 use std::{fmt, io};
 
-use ark_ff::PrimeField;
+use acir::BlackBoxFunc;
+use ark_ff::{BigInteger, PrimeField};
 use co_brillig::mpc::BrilligDriver;
-use mpc_core::lut::LookupTableProvider;
+use mpc_core::{gadgets::poseidon2::Poseidon2Params, lut::LookupTableProvider};
+use num_bigint::BigUint;
 
+use embedded_curve::EmbeddedCurvePoint;
+
+pub mod embedded_curve;
+pub mod pedersen;
 pub(super) mod plain;
 pub(super) mod rep3;
+pub mod schnorr;
 pub(super) mod shamir; // Does not support everything, but basic circuits can be build using Shamir (co-builder)
 
 /// A trait representing the MPC operations required for extending the secret-shared Noir witness in MPC.
@@ -129,6 +136,9 @@ pub trait NoirWitnessExtensionProtocol<F: PrimeField> {
     /// Returns the value if the value is public
     fn get_public(a: &Self::AcvmType) -> Option<F>;
 
+    /// Checks that all parties hold the same public digest, e.g., of a configuration that has to be identical for all parties. Returns an error if the digest of any other party differs.
+    fn check_consistent_digest(&mut self, digest: &[u8]) -> io::Result<()>;
+
     // TODO do we want this here?
     fn open_many(&mut self, a: &[Self::ArithmeticShare]) -> io::Result<Vec<F>>;
 
@@ -153,4 +163,145 @@ pub trait NoirWitnessExtensionProtocol<F: PrimeField> {
         inputs: &[Self::ArithmeticShare],
         bitsize: usize,
     ) -> std::io::Result<Vec<Self::ArithmeticShare>>;
+
+    /// Checks whether two ACVM-types are equal. The result is 1 if they are equal and 0 otherwise, and shared if any of the inputs is shared.
+    fn equal(&mut self, a: &Self::AcvmType, b: &Self::AcvmType) -> io::Result<Self::AcvmType>;
+
+    /// Decomposes an ACVM-type into its `num_bits` least significant bits, starting with the least significant bit. The bits of a shared value are shared as well.
+    fn decompose_bits(
+        &mut self,
+        a: Self::AcvmType,
+        num_bits: usize,
+    ) -> io::Result<Vec<Self::AcvmType>> {
+        if let Some(public) = Self::get_public(&a) {
+            let bits = public.into_bigint().to_bits_le();
+            Ok((0..num_bits)
+                .map(|i| F::from(bits.get(i).copied().unwrap_or(false)).into())
+                .collect())
+        } else {
+            let shared = Self::get_shared(&a).expect("value is either public or shared");
+            Ok(self
+                .decompose_arithmetic(shared, num_bits, 1)?
+                .into_iter()
+                .map(Self::AcvmType::from)
+                .collect())
+        }
+    }
+
+    /// Adds two points of the embedded curve (Grumpkin for BN254), see [embedded_curve].
+    fn embedded_curve_add(
+        &mut self,
+        p1: EmbeddedCurvePoint<Self::AcvmType>,
+        p2: EmbeddedCurvePoint<Self::AcvmType>,
+    ) -> eyre::Result<EmbeddedCurvePoint<Self::AcvmType>> {
+        embedded_curve::add(self, p1, p2)
+    }
+
+    /// Computes the multi-scalar multiplication of points of the embedded curve (Grumpkin for BN254), see [embedded_curve]. Every scalar is given by its lower and upper 128 bits.
+    fn embedded_curve_msm(
+        &mut self,
+        points: Vec<EmbeddedCurvePoint<Self::AcvmType>>,
+        scalars: Vec<(Self::AcvmType, Self::AcvmType)>,
+    ) -> eyre::Result<EmbeddedCurvePoint<Self::AcvmType>> {
+        embedded_curve::msm(self, points, scalars)
+    }
+
+    /// Computes the Pedersen commitment to the inputs over the embedded curve (Grumpkin for BN254) like barretenberg, see [pedersen]. The generators start at the index starting_index.
+    fn pedersen_commitment(
+        &mut self,
+        inputs: Vec<Self::AcvmType>,
+        starting_index: u32,
+    ) -> eyre::Result<EmbeddedCurvePoint<Self::AcvmType>> {
+        let mut commitments = self.pedersen_commitment_many(vec![inputs], starting_index)?;
+        Ok(commitments.pop().expect("one commitment per input"))
+    }
+
+    /// Computes the Pedersen commitments to many inputs at once, see [Self::pedersen_commitment].
+    fn pedersen_commitment_many(
+        &mut self,
+        inputs: Vec<Vec<Self::AcvmType>>,
+        starting_index: u32,
+    ) -> eyre::Result<Vec<EmbeddedCurvePoint<Self::AcvmType>>> {
+        pedersen::commit_many(self, inputs, starting_index)
+    }
+
+    /// Computes the Pedersen hash of the inputs over the embedded curve (Grumpkin for BN254) like barretenberg, see [pedersen]. The generators start at the index starting_index.
+    fn pedersen_hash(
+        &mut self,
+        inputs: Vec<Self::AcvmType>,
+        starting_index: u32,
+    ) -> eyre::Result<Self::AcvmType> {
+        let mut hashes = self.pedersen_hash_many(vec![inputs], starting_index)?;
+        Ok(hashes.pop().expect("one hash per input"))
+    }
+
+    /// Computes the Pedersen hashes of many inputs at once, see [Self::pedersen_hash].
+    fn pedersen_hash_many(
+        &mut self,
+        inputs: Vec<Vec<Self::AcvmType>>,
+        starting_index: u32,
+    ) -> eyre::Result<Vec<Self::AcvmType>> {
+        pedersen::hash_many(self, inputs, starting_index)
+    }
+
+    /// Computes the BLAKE2s-256 hash of a message, which is given as bytes, where each byte is expected to be smaller than 2^8. Only the length of the message is public. The digest is returned as 32 bytes, which are shared if any byte of the message is shared.
+    fn blake2s_hash(&mut self, message: &[Self::AcvmType]) -> io::Result<Vec<Self::AcvmType>>;
+
+    /// Computes the Poseidon2 permutation with the given parameters of a state of `T` elements. The result is shared if any element of the state is shared.
+    fn poseidon2_permutation<const T: usize, const D: u64>(
+        &mut self,
+        state: Vec<Self::AcvmType>,
+        params: &Poseidon2Params<F, T, D>,
+    ) -> io::Result<Vec<Self::AcvmType>>;
+
+    /// Encrypts a message with AES-128 in CBC mode with PKCS#7 padding like the AES128Encrypt blackbox of Noir. The message, the 16 bytes of the IV and the 16 bytes of the key are given as bytes, where each byte is expected to be smaller than 2^8. Only the length of the message is public. The ciphertext, whose length is the length of the message rounded up to the next multiple of 16 bytes, is returned as bytes, which are shared if any of the inputs is shared.
+    fn aes128_encrypt(
+        &mut self,
+        plaintext: &[Self::AcvmType],
+        iv: &[Self::AcvmType],
+        key: &[Self::AcvmType],
+    ) -> io::Result<Vec<Self::AcvmType>>;
+
+    /// Verifies a Schnorr signature over the embedded curve (Grumpkin for BN254) like barretenberg, see [schnorr]. The signature consists of the 32 big-endian bytes of s followed by the 32 big-endian bytes of e. The result is 1 if the signature is valid and 0 otherwise, and shared if any of the inputs is shared.
+    fn schnorr_verify(
+        &mut self,
+        public_key_x: Self::AcvmType,
+        public_key_y: Self::AcvmType,
+        signature: &[Self::AcvmType],
+        message: &[Self::AcvmType],
+    ) -> eyre::Result<Self::AcvmType> {
+        schnorr::verify(self, public_key_x, public_key_y, signature, message)
+    }
+
+    /// Computes the BigInt operation func (BigIntAdd, BigIntSub, BigIntMul or BigIntDiv) modulo a public modulus. The operands are given as little-endian bytes, where each byte is expected to be smaller than 2^8. The result is reduced and returned as modulus.bits().div_ceil(8) little-endian bytes. For BigIntDiv, the modulus has to be prime.
+    fn bigint_op(
+        &mut self,
+        lhs: &[Self::AcvmType],
+        rhs: &[Self::AcvmType],
+        modulus: &BigUint,
+        func: BlackBoxFunc,
+    ) -> io::Result<Vec<Self::AcvmType>>;
+
+    /// Computes the quotient and the remainder of lhs * rhs + add - sub divided by a public modulus. Each integer is given by its limbs, i.e., it is the sum of limb_i * 2^(limb_bits * i), where the limbs may be larger than 2^limb_bits. add and sub can be empty, and lhs * rhs + add must not be smaller than sub. The quotient and the remainder are returned as num_limbs limbs of limb_bits bits each.
+    #[expect(clippy::too_many_arguments)]
+    fn bigint_quotient_remainder(
+        &mut self,
+        lhs: &[Self::AcvmType],
+        rhs: &[Self::AcvmType],
+        add: &[Self::AcvmType],
+        sub: &[Self::AcvmType],
+        modulus: &BigUint,
+        limb_bits: usize,
+        num_limbs: usize,
+    ) -> io::Result<(Vec<Self::AcvmType>, Vec<Self::AcvmType>)>;
+
+    /// Computes lhs / rhs modulo a public prime modulus, where both integers are given by their limbs like in [Self::bigint_quotient_remainder]. The result is reduced and returned as num_limbs limbs of limb_bits bits each. If rhs is zero modulo the modulus, the result is zero.
+    fn bigint_div_limbs(
+        &mut self,
+        lhs: &[Self::AcvmType],
+        rhs: &[Self::AcvmType],
+        modulus: &BigUint,
+        limb_bits: usize,
+        num_limbs: usize,
+    ) -> io::Result<Vec<Self::AcvmType>>;
 }
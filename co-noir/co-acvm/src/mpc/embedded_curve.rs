@@ -0,0 +1,130 @@
+//! Arithmetic on the embedded curve of Noir, i.e., Grumpkin for BN254, over ACVM-types.
+//!
+//! The embedded curve is the short Weierstrass curve `y^2 = x^3 + b` over the field of the witness (for Grumpkin, `b = -17`). Since `a = 0`, the formulas for the addition and doubling of points do not depend on the curve. The coordinates and the flags for the point at infinity may be public or shared, and all case distinctions (doubling, adding a point to its inverse, adding the point at infinity) are evaluated obliviously, so the computation does not leak which case occurred. The circuit builder constrains these operations with the cycle_group gadget of barretenberg, which computes the same results.
+use ark_ff::PrimeField;
+
+use super::NoirWitnessExtensionProtocol;
+
+/// The number of bits of each of the two limbs of a scalar of the embedded curve.
+pub const SCALAR_LIMB_BITS: usize = 128;
+
+/// A point of the embedded curve in affine coordinates, together with a flag for the point at infinity. The point at infinity has the coordinates (0, 0).
+#[derive(Clone, Debug)]
+pub struct EmbeddedCurvePoint<A> {
+    /// The x coordinate
+    pub x: A,
+    /// The y coordinate
+    pub y: A,
+    /// 1 if the point is the point at infinity and 0 otherwise
+    pub is_infinite: A,
+}
+
+impl<A> EmbeddedCurvePoint<A> {
+    /// Returns the point at infinity.
+    pub fn infinity<F: PrimeField>() -> Self
+    where
+        A: From<F>,
+    {
+        Self {
+            x: F::zero().into(),
+            y: F::zero().into(),
+            is_infinite: F::one().into(),
+        }
+    }
+}
+
+/// Returns `truthy` if `cond` is 1 and `falsy` otherwise.
+fn select<F: PrimeField, T: NoirWitnessExtensionProtocol<F> + ?Sized>(
+    driver: &mut T,
+    cond: &T::AcvmType,
+    truthy: EmbeddedCurvePoint<T::AcvmType>,
+    falsy: EmbeddedCurvePoint<T::AcvmType>,
+) -> eyre::Result<EmbeddedCurvePoint<T::AcvmType>> {
+    Ok(EmbeddedCurvePoint {
+        x: driver.cmux(cond.to_owned(), truthy.x, falsy.x)?,
+        y: driver.cmux(cond.to_owned(), truthy.y, falsy.y)?,
+        is_infinite: driver.cmux(cond.to_owned(), truthy.is_infinite, falsy.is_infinite)?,
+    })
+}
+
+/// Adds two points of the embedded curve, see the [module documentation](self).
+pub(super) fn add<F: PrimeField, T: NoirWitnessExtensionProtocol<F> + ?Sized>(
+    driver: &mut T,
+    p1: EmbeddedCurvePoint<T::AcvmType>,
+    p2: EmbeddedCurvePoint<T::AcvmType>,
+) -> eyre::Result<EmbeddedCurvePoint<T::AcvmType>> {
+    let x_eq = driver.equal(&p1.x, &p2.x)?;
+    let y_eq = driver.equal(&p1.y, &p2.y)?;
+    let double = driver.mul(x_eq.to_owned(), y_eq)?;
+    // p1 = -p2, the sum is the point at infinity
+    let opposite = driver.sub(x_eq, double.to_owned());
+
+    // lambda = 3 * x1^2 / (2 * y1) for doubling and (y2 - y1) / (x2 - x1) otherwise
+    let x1_squared = driver.mul(p1.x.to_owned(), p1.x.to_owned())?;
+    let double_num = driver.mul_with_public(F::from(3u64), x1_squared);
+    let add_num = driver.sub(p2.y.to_owned(), p1.y.to_owned());
+    let num = driver.cmux(double.to_owned(), double_num, add_num)?;
+    let double_den = driver.mul_with_public(F::from(2u64), p1.y.to_owned());
+    let add_den = driver.sub(p2.x.to_owned(), p1.x.to_owned());
+    let den = driver.cmux(double, double_den, add_den)?;
+    // the denominator is zero for p1 = -p2 and the point at infinity, where lambda is not used
+    let den_is_zero = driver.equal(&den, &T::public_zero())?;
+    let den = driver.add(den, den_is_zero);
+    let mut neg_num = num;
+    driver.negate_inplace(&mut neg_num);
+    let lambda = driver.solve_equation(den, neg_num)?;
+
+    // x3 = lambda^2 - x1 - x2, y3 = lambda * (x1 - x3) - y1
+    let lambda_squared = driver.mul(lambda.to_owned(), lambda.to_owned())?;
+    let x3 = driver.sub(lambda_squared, p1.x.to_owned());
+    let x3 = driver.sub(x3, p2.x.to_owned());
+    let x1_minus_x3 = driver.sub(p1.x.to_owned(), x3.to_owned());
+    let y3 = driver.mul(lambda, x1_minus_x3)?;
+    let y3 = driver.sub(y3, p1.y.to_owned());
+
+    let sum = EmbeddedCurvePoint {
+        x: x3,
+        y: y3,
+        is_infinite: T::public_zero(),
+    };
+    let sum = select(driver, &opposite, EmbeddedCurvePoint::infinity(), sum)?;
+    let p2_is_infinite = p2.is_infinite.to_owned();
+    let sum = select(driver, &p2_is_infinite, p1.to_owned(), sum)?;
+    let p1_is_infinite = p1.is_infinite.to_owned();
+    select(driver, &p1_is_infinite, p2, sum)
+}
+
+/// Computes the multi-scalar multiplication of the points with the scalars, which are given as pairs of their lower and upper 128 bits, with a double-and-add over the bits of the scalars.
+pub(super) fn msm<F: PrimeField, T: NoirWitnessExtensionProtocol<F> + ?Sized>(
+    driver: &mut T,
+    points: Vec<EmbeddedCurvePoint<T::AcvmType>>,
+    scalars: Vec<(T::AcvmType, T::AcvmType)>,
+) -> eyre::Result<EmbeddedCurvePoint<T::AcvmType>> {
+    if points.len() != scalars.len() {
+        eyre::bail!(
+            "multi-scalar multiplication with {} points and {} scalars",
+            points.len(),
+            scalars.len()
+        );
+    }
+    let mut result = EmbeddedCurvePoint::infinity();
+    for (point, (lo, hi)) in points.into_iter().zip(scalars) {
+        let mut bits = driver.decompose_bits(lo, SCALAR_LIMB_BITS)?;
+        bits.extend(driver.decompose_bits(hi, SCALAR_LIMB_BITS)?);
+        let num_bits = bits.len();
+
+        let mut acc = EmbeddedCurvePoint::infinity();
+        let mut base = point;
+        for (i, bit) in bits.into_iter().enumerate() {
+            if !T::is_public_zero(&bit) {
+                let sum = add(driver, acc.to_owned(), base.to_owned())?;
+                acc = select(driver, &bit, sum, acc)?;
+            }
+            if i + 1 < num_bits {
+                base = add(driver, base.to_owned(), base)?;
+            }
+        }
+        result = add(driver, result, acc)?;
+    }
+    Ok(result)
+}
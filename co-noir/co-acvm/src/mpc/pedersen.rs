@@ -0,0 +1,98 @@
+//! Pedersen commitments and hashes over the embedded curve of Noir, i.e., Grumpkin for BN254, over ACVM-types.
+//!
+//! The commitment to the inputs `x_i` is the point `sum_i x_i * G_(i + starting_index)`, where the `G_i` are the generators barretenberg derives for its default domain separator. The hash is the x coordinate of `n * H + commitment` for the number of inputs `n` and a dedicated length generator `H`. This matches the Pedersen functions of barretenberg and of the standard library of Noir, which computes them with the MultiScalarMul blackbox. The inputs may be public or shared. Since the generators are public, the computation is a fixed-base multi-scalar multiplication with the oblivious arithmetic of [embedded_curve](super::embedded_curve), where the inputs are used as scalars.
+use ark_ff::{BigInteger, PrimeField};
+use bn254_blackbox_solver::derive_generators;
+use num_bigint::BigUint;
+
+use super::embedded_curve::{self, EmbeddedCurvePoint, SCALAR_LIMB_BITS};
+use super::NoirWitnessExtensionProtocol;
+
+/// The domain separator of the generators of the Pedersen commitment.
+const DEFAULT_DOMAIN_SEPARATOR: &[u8] = b"DEFAULT_DOMAIN_SEPARATOR";
+/// The domain separator of the generator for the length of the input of the Pedersen hash.
+const LENGTH_DOMAIN_SEPARATOR: &[u8] = b"pedersen_hash_length";
+
+/// Derives `num_generators` generators of barretenberg for the domain separator, starting at the generator with index `starting_index`.
+fn generators<F: PrimeField, A: From<F>>(
+    domain_separator: &[u8],
+    num_generators: usize,
+    starting_index: u32,
+) -> Vec<EmbeddedCurvePoint<A>> {
+    derive_generators(domain_separator, num_generators as u32, starting_index)
+        .into_iter()
+        .map(|point| EmbeddedCurvePoint {
+            x: F::from_le_bytes_mod_order(&point.x.into_bigint().to_bytes_le()).into(),
+            y: F::from_le_bytes_mod_order(&point.y.into_bigint().to_bytes_le()).into(),
+            is_infinite: F::zero().into(),
+        })
+        .collect()
+}
+
+/// Splits a field element into its lower and upper 128 bits, such that it can be used as a scalar of the embedded curve.
+fn field_to_scalar<F: PrimeField, T: NoirWitnessExtensionProtocol<F> + ?Sized>(
+    driver: &mut T,
+    a: T::AcvmType,
+) -> std::io::Result<(T::AcvmType, T::AcvmType)> {
+    if let Some(public) = T::get_public(&a) {
+        let value: BigUint = public.into();
+        let lo = &value % (BigUint::from(1u64) << SCALAR_LIMB_BITS);
+        let hi = value >> SCALAR_LIMB_BITS;
+        Ok((F::from(lo).into(), F::from(hi).into()))
+    } else {
+        let shared = T::get_shared(&a).expect("value is either public or shared");
+        let mut limbs = driver
+            .decompose_arithmetic(shared, F::MODULUS_BIT_SIZE as usize, SCALAR_LIMB_BITS)?
+            .into_iter()
+            .map(T::AcvmType::from);
+        let lo = limbs.next().expect("a field element has two limbs");
+        let hi = limbs.next().unwrap_or_else(T::public_zero);
+        Ok((lo, hi))
+    }
+}
+
+/// Computes the Pedersen commitments to the inputs of every batch element, see the [module documentation](self). The generators are derived only once for all elements.
+pub(super) fn commit_many<F: PrimeField, T: NoirWitnessExtensionProtocol<F> + ?Sized>(
+    driver: &mut T,
+    inputs: Vec<Vec<T::AcvmType>>,
+    starting_index: u32,
+) -> eyre::Result<Vec<EmbeddedCurvePoint<T::AcvmType>>> {
+    let max_len = inputs.iter().map(Vec::len).max().unwrap_or_default();
+    let points = generators::<F, _>(DEFAULT_DOMAIN_SEPARATOR, max_len, starting_index);
+    inputs
+        .into_iter()
+        .map(|inputs| {
+            let scalars = inputs
+                .into_iter()
+                .map(|input| field_to_scalar(driver, input))
+                .collect::<std::io::Result<Vec<_>>>()?;
+            embedded_curve::msm(driver, points[..scalars.len()].to_vec(), scalars)
+        })
+        .collect()
+}
+
+/// Computes the Pedersen hashes of the inputs of every batch element, see the [module documentation](self). The generators are derived only once for all elements.
+pub(super) fn hash_many<F: PrimeField, T: NoirWitnessExtensionProtocol<F> + ?Sized>(
+    driver: &mut T,
+    inputs: Vec<Vec<T::AcvmType>>,
+    starting_index: u32,
+) -> eyre::Result<Vec<T::AcvmType>> {
+    let max_len = inputs.iter().map(Vec::len).max().unwrap_or_default();
+    let mut points = generators::<F, _>(LENGTH_DOMAIN_SEPARATOR, 1, 0);
+    points.extend(generators::<F, _>(
+        DEFAULT_DOMAIN_SEPARATOR,
+        max_len,
+        starting_index,
+    ));
+    inputs
+        .into_iter()
+        .map(|inputs| {
+            let mut scalars = vec![(F::from(inputs.len() as u64).into(), T::public_zero())];
+            for input in inputs {
+                scalars.push(field_to_scalar(driver, input)?);
+            }
+            let hash = embedded_curve::msm(driver, points[..scalars.len()].to_vec(), scalars)?;
+            Ok(hash.x)
+        })
+        .collect()
+}
@@ -2,8 +2,10 @@ use std::collections::HashMap;
 use std::io;
 use std::marker::PhantomData;
 
-use ark_ff::{One, PrimeField};
+use acir::BlackBoxFunc;
+use ark_ff::{One, PrimeField, Zero};
 use co_brillig::mpc::{PlainBrilligDriver, PlainBrilligType};
+use mpc_core::gadgets::poseidon2::Poseidon2Params;
 use mpc_core::lut::{LookupTableProvider, PlainLookupTableProvider};
 use num_bigint::BigUint;
 
@@ -157,6 +159,10 @@ impl<F: PrimeField> NoirWitnessExtensionProtocol<F> for PlainAcvmSolver<F> {
         Some(*a)
     }
 
+    fn check_consistent_digest(&mut self, _digest: &[u8]) -> io::Result<()> {
+        Ok(())
+    }
+
     fn open_many(&mut self, a: &[Self::ArithmeticShare]) -> io::Result<Vec<F>> {
         Ok(a.to_vec())
     }
@@ -203,4 +209,183 @@ impl<F: PrimeField> NoirWitnessExtensionProtocol<F> for PlainAcvmSolver<F> {
         result.sort();
         Ok(result)
     }
+
+    fn equal(&mut self, a: &Self::AcvmType, b: &Self::AcvmType) -> io::Result<Self::AcvmType> {
+        Ok(F::from(a == b))
+    }
+
+    fn blake2s_hash(&mut self, message: &[Self::AcvmType]) -> io::Result<Vec<Self::AcvmType>> {
+        let message = message
+            .iter()
+            .map(|byte| {
+                let byte: BigUint = (*byte).into();
+                u8::try_from(byte).map_err(|_| {
+                    io::Error::new(io::ErrorKind::InvalidInput, "BLAKE2s byte out of range")
+                })
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        let digest = acvm::blackbox_solver::blake2s(&message)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err.to_string()))?;
+        Ok(digest
+            .into_iter()
+            .map(|byte| F::from(byte as u64))
+            .collect())
+    }
+
+    fn poseidon2_permutation<const T: usize, const D: u64>(
+        &mut self,
+        state: Vec<Self::AcvmType>,
+        params: &Poseidon2Params<F, T, D>,
+    ) -> io::Result<Vec<Self::AcvmType>> {
+        let mut state: [F; T] = state.try_into().map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("the Poseidon2 permutation expects a state of {T} elements"),
+            )
+        })?;
+        params.permutation_in_place(&mut state);
+        Ok(state.to_vec())
+    }
+
+    fn aes128_encrypt(
+        &mut self,
+        plaintext: &[Self::AcvmType],
+        iv: &[Self::AcvmType],
+        key: &[Self::AcvmType],
+    ) -> io::Result<Vec<Self::AcvmType>> {
+        let to_bytes = |bytes: &[F]| {
+            bytes
+                .iter()
+                .map(|byte| {
+                    let byte: BigUint = (*byte).into();
+                    u8::try_from(byte).map_err(|_| {
+                        io::Error::new(io::ErrorKind::InvalidInput, "AES-128 byte out of range")
+                    })
+                })
+                .collect::<io::Result<Vec<_>>>()
+        };
+        let to_block = |bytes: &[F]| -> io::Result<[u8; 16]> {
+            to_bytes(bytes)?.try_into().map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "the key and the IV of AES-128 must consist of 16 bytes",
+                )
+            })
+        };
+        let ciphertext = acvm::blackbox_solver::aes128_encrypt(
+            &to_bytes(plaintext)?,
+            to_block(iv)?,
+            to_block(key)?,
+        )
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err.to_string()))?;
+        Ok(ciphertext
+            .into_iter()
+            .map(|byte| F::from(byte as u64))
+            .collect())
+    }
+
+    fn bigint_op(
+        &mut self,
+        lhs: &[Self::AcvmType],
+        rhs: &[Self::AcvmType],
+        modulus: &BigUint,
+        func: BlackBoxFunc,
+    ) -> io::Result<Vec<Self::AcvmType>> {
+        let compose = |bytes: &[F]| -> io::Result<BigUint> {
+            let bytes = bytes
+                .iter()
+                .map(|byte| {
+                    let byte: BigUint = (*byte).into();
+                    u8::try_from(byte).map_err(|_| {
+                        io::Error::new(io::ErrorKind::InvalidInput, "BigInt byte out of range")
+                    })
+                })
+                .collect::<io::Result<Vec<_>>>()?;
+            Ok(BigUint::from_bytes_le(&bytes) % modulus)
+        };
+        let lhs = compose(lhs)?;
+        let rhs = compose(rhs)?;
+
+        let result = match func {
+            BlackBoxFunc::BigIntAdd => (lhs + rhs) % modulus,
+            BlackBoxFunc::BigIntSub => (lhs + modulus - rhs) % modulus,
+            BlackBoxFunc::BigIntMul => (lhs * rhs) % modulus,
+            // Fermat's little theorem, also maps zero to zero
+            BlackBoxFunc::BigIntDiv => (lhs * rhs.modpow(&(modulus - 2u64), modulus)) % modulus,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{} is not a BigInt operation", func.name()),
+                ))
+            }
+        };
+
+        let mut bytes = result.to_bytes_le();
+        bytes.resize((modulus.bits() as usize).div_ceil(8), 0);
+        Ok(bytes.into_iter().map(F::from).collect())
+    }
+
+    fn bigint_quotient_remainder(
+        &mut self,
+        lhs: &[Self::AcvmType],
+        rhs: &[Self::AcvmType],
+        add: &[Self::AcvmType],
+        sub: &[Self::AcvmType],
+        modulus: &BigUint,
+        limb_bits: usize,
+        num_limbs: usize,
+    ) -> io::Result<(Vec<Self::AcvmType>, Vec<Self::AcvmType>)> {
+        let value = compose_limbs(lhs, limb_bits) * compose_limbs(rhs, limb_bits)
+            + compose_limbs(add, limb_bits);
+        let sub = compose_limbs(sub, limb_bits);
+        if value < sub {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "BigInt quotient of a negative value",
+            ));
+        }
+        let value = value - sub;
+        let quotient = decompose_limbs(&(&value / modulus), limb_bits, num_limbs)?;
+        let remainder = decompose_limbs(&(value % modulus), limb_bits, num_limbs)?;
+        Ok((quotient, remainder))
+    }
+
+    fn bigint_div_limbs(
+        &mut self,
+        lhs: &[Self::AcvmType],
+        rhs: &[Self::AcvmType],
+        modulus: &BigUint,
+        limb_bits: usize,
+        num_limbs: usize,
+    ) -> io::Result<Vec<Self::AcvmType>> {
+        let lhs = compose_limbs(lhs, limb_bits);
+        let rhs = compose_limbs(rhs, limb_bits);
+        // Fermat's little theorem, also maps zero to zero
+        let result = (lhs * rhs.modpow(&(modulus - 2u64), modulus)) % modulus;
+        decompose_limbs(&result, limb_bits, num_limbs)
+    }
+}
+
+fn compose_limbs<F: PrimeField>(limbs: &[F], limb_bits: usize) -> BigUint {
+    limbs.iter().rev().fold(BigUint::zero(), |acc, limb| {
+        let limb: BigUint = (*limb).into();
+        (acc << limb_bits) + limb
+    })
+}
+
+fn decompose_limbs<F: PrimeField>(
+    value: &BigUint,
+    limb_bits: usize,
+    num_limbs: usize,
+) -> io::Result<Vec<F>> {
+    if value.bits() as usize > limb_bits * num_limbs {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "BigInt does not fit into the limbs",
+        ));
+    }
+    let mask = (BigUint::one() << limb_bits) - 1u64;
+    Ok((0..num_limbs)
+        .map(|i| F::from((value >> (i * limb_bits)) & &mask))
+        .collect())
 }
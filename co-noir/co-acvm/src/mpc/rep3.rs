@@ -1,9 +1,11 @@
 use std::marker::PhantomData;
 
+use acir::BlackBoxFunc;
 use ark_ff::PrimeField;
 use co_brillig::mpc::{Rep3BrilligDriver, Rep3BrilligType};
 use itertools::{izip, Itertools};
-use mpc_core::protocols::rep3::{arithmetic, yao};
+use mpc_core::gadgets::poseidon2::Poseidon2Params;
+use mpc_core::protocols::rep3::{arithmetic, gadgets, yao, yao::BigIntModOp};
 use mpc_core::protocols::rep3_ring::gadgets::sort::radix_sort_fields;
 use mpc_core::{
     lut::LookupTableProvider,
@@ -13,6 +15,7 @@ use mpc_core::{
         Rep3PrimeFieldShare,
     },
 };
+use num_bigint::BigUint;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -20,6 +23,9 @@ use super::plain::PlainAcvmSolver;
 use super::NoirWitnessExtensionProtocol;
 type ArithmeticShare<F> = Rep3PrimeFieldShare<F>;
 
+/// Memory blocks larger than this threshold trigger a warning when accessed at a secret index.
+const LARGE_MEMORY_WARNING_THRESHOLD: usize = 256;
+
 pub struct Rep3AcvmSolver<F: PrimeField, N: Rep3Network> {
     lut_provider: NaiveRep3LookupTable<N>,
     io_context: IoContext<N>,
@@ -44,6 +50,39 @@ impl<F: PrimeField, N: Rep3Network> Rep3AcvmSolver<F, N> {
     pub fn get_io_contexts(self) -> (IoContext<N>, IoContext<N>) {
         (self.io_context, self.lut_provider.get_io_context())
     }
+
+    /// Translates a public index into an index of a LUT created by [`NoirWitnessExtensionProtocol::init_lut_by_acvm_type`].
+    fn public_lut_index(index: F, len: usize) -> std::io::Result<usize> {
+        let index: BigUint = index.into();
+        match usize::try_from(index) {
+            Ok(index) if index < len => Ok(index),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Index out of bounds for memory block",
+            )),
+        }
+    }
+
+    /// Promotes public values to trivial shares.
+    fn to_shares(&self, values: &[Rep3AcvmType<F>]) -> Vec<ArithmeticShare<F>> {
+        let id = self.io_context.id;
+        values
+            .iter()
+            .map(|value| match value {
+                Rep3AcvmType::Public(public) => arithmetic::promote_to_trivial_share(id, *public),
+                Rep3AcvmType::Shared(shared) => *shared,
+            })
+            .collect()
+    }
+
+    /// Accessing memory at a secret index requires a linear scan over the whole memory block, which becomes expensive for large blocks.
+    fn warn_on_large_memory(len: usize) {
+        if len > LARGE_MEMORY_WARNING_THRESHOLD {
+            tracing::warn!(
+                "accessing memory block of size {len} at a secret index - this requires an oblivious linear scan and is expensive"
+            );
+        }
+    }
 }
 
 // TODO maybe we want to merge that with the Rep3VmType?? Atm we do not need
@@ -400,11 +439,14 @@ impl<F: PrimeField, N: Rep3Network> NoirWitnessExtensionProtocol<F> for Rep3Acvm
     ) -> std::io::Result<Self::AcvmType> {
         let value = match index {
             Rep3AcvmType::Public(public) => {
-                let id = self.io_context.id;
-                let promoted_key = arithmetic::promote_to_trivial_share(id, *public);
-                self.lut_provider.get_from_lut(promoted_key, lut)
+                // The keys of the LUT are the indices of the memory block, thus we can directly access it
+                let (_, value) = lut[Self::public_lut_index(*public, lut.len())?];
+                Ok(value)
+            }
+            Rep3AcvmType::Shared(shared) => {
+                Self::warn_on_large_memory(lut.len());
+                self.lut_provider.get_from_lut(*shared, lut)
             }
-            Rep3AcvmType::Shared(shared) => self.lut_provider.get_from_lut(*shared, lut),
         };
         Ok(Rep3AcvmType::Shared(value?))
     }
@@ -416,21 +458,19 @@ impl<F: PrimeField, N: Rep3Network> NoirWitnessExtensionProtocol<F> for Rep3Acvm
         lut: &mut <Self::Lookup as mpc_core::lut::LookupTableProvider<F>>::SecretSharedMap,
     ) -> std::io::Result<()> {
         let id = self.io_context.id;
-        match (index, value) {
-            (Rep3AcvmType::Public(index), Rep3AcvmType::Public(value)) => {
-                let index = arithmetic::promote_to_trivial_share(id, index);
-                let value = arithmetic::promote_to_trivial_share(id, value);
-                self.lut_provider.write_to_lut(index, value, lut)
-            }
-            (Rep3AcvmType::Public(index), Rep3AcvmType::Shared(value)) => {
-                let index = arithmetic::promote_to_trivial_share(id, index);
-                self.lut_provider.write_to_lut(index, value, lut)
-            }
-            (Rep3AcvmType::Shared(index), Rep3AcvmType::Public(value)) => {
-                let value = arithmetic::promote_to_trivial_share(id, value);
-                self.lut_provider.write_to_lut(index, value, lut)
-            }
-            (Rep3AcvmType::Shared(index), Rep3AcvmType::Shared(value)) => {
+        let value = match value {
+            Rep3AcvmType::Public(value) => arithmetic::promote_to_trivial_share(id, value),
+            Rep3AcvmType::Shared(value) => value,
+        };
+        match index {
+            Rep3AcvmType::Public(index) => {
+                // The keys of the LUT are the indices of the memory block, thus we can directly access it
+                let index = Self::public_lut_index(index, lut.len())?;
+                lut[index].1 = value;
+                Ok(())
+            }
+            Rep3AcvmType::Shared(index) => {
+                Self::warn_on_large_memory(lut.len());
                 self.lut_provider.write_to_lut(index, value, lut)
             }
         }
@@ -453,6 +493,17 @@ impl<F: PrimeField, N: Rep3Network> NoirWitnessExtensionProtocol<F> for Rep3Acvm
         }
     }
 
+    fn check_consistent_digest(&mut self, digest: &[u8]) -> std::io::Result<()> {
+        let (prev, next) = self.io_context.network.broadcast(digest.to_vec())?;
+        if prev != digest || next != digest {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "the digest of another party differs from ours",
+            ));
+        }
+        Ok(())
+    }
+
     fn open_many(&mut self, a: &[Self::ArithmeticShare]) -> std::io::Result<Vec<F>> {
         let bs = a.iter().map(|x| x.b).collect_vec();
         self.io_context.network.send_next(bs)?;
@@ -498,4 +549,250 @@ impl<F: PrimeField, N: Rep3Network> NoirWitnessExtensionProtocol<F> for Rep3Acvm
     ) -> std::io::Result<Vec<Self::ArithmeticShare>> {
         radix_sort_fields(inputs, &mut self.io_context, bitsize)
     }
+
+    fn equal(&mut self, a: &Self::AcvmType, b: &Self::AcvmType) -> std::io::Result<Self::AcvmType> {
+        match (a, b) {
+            (Rep3AcvmType::Public(a), Rep3AcvmType::Public(b)) => {
+                Ok(Rep3AcvmType::Public(F::from(a == b)))
+            }
+            (Rep3AcvmType::Public(public), Rep3AcvmType::Shared(shared))
+            | (Rep3AcvmType::Shared(shared), Rep3AcvmType::Public(public)) => {
+                Ok(arithmetic::eq_public(*shared, *public, &mut self.io_context)?.into())
+            }
+            (Rep3AcvmType::Shared(a), Rep3AcvmType::Shared(b)) => {
+                Ok(arithmetic::eq(*a, *b, &mut self.io_context)?.into())
+            }
+        }
+    }
+
+    fn blake2s_hash(&mut self, message: &[Self::AcvmType]) -> std::io::Result<Vec<Self::AcvmType>> {
+        if let Some(public) = message
+            .iter()
+            .map(Self::get_public)
+            .collect::<Option<Vec<_>>>()
+        {
+            let digest = self.plain_solver.blake2s_hash(&public)?;
+            return Ok(digest.into_iter().map(Rep3AcvmType::from).collect());
+        }
+
+        let id = self.io_context.id;
+        let message = message
+            .iter()
+            .map(|byte| match byte {
+                Rep3AcvmType::Public(public) => arithmetic::promote_to_trivial_share(id, *public),
+                Rep3AcvmType::Shared(shared) => *shared,
+            })
+            .collect_vec();
+        let digest = yao::blake2s_bytes(&message, &mut self.io_context)?;
+        Ok(digest.into_iter().map(Rep3AcvmType::from).collect())
+    }
+
+    fn aes128_encrypt(
+        &mut self,
+        plaintext: &[Self::AcvmType],
+        iv: &[Self::AcvmType],
+        key: &[Self::AcvmType],
+    ) -> std::io::Result<Vec<Self::AcvmType>> {
+        const BLOCK_BYTES: usize = 16;
+        let to_public = |bytes: &[Self::AcvmType]| {
+            bytes
+                .iter()
+                .map(Self::get_public)
+                .collect::<Option<Vec<_>>>()
+        };
+        if let (Some(plaintext), Some(iv), Some(key)) =
+            (to_public(plaintext), to_public(iv), to_public(key))
+        {
+            let ciphertext = self.plain_solver.aes128_encrypt(&plaintext, &iv, &key)?;
+            return Ok(ciphertext.into_iter().map(Rep3AcvmType::from).collect());
+        }
+
+        let id = self.io_context.id;
+        let promote = |bytes: &[Self::AcvmType]| {
+            bytes
+                .iter()
+                .map(|byte| match byte {
+                    Rep3AcvmType::Public(public) => {
+                        arithmetic::promote_to_trivial_share(id, *public)
+                    }
+                    Rep3AcvmType::Shared(shared) => *shared,
+                })
+                .collect_vec()
+        };
+        // PKCS#7 padding, which only depends on the public length of the message
+        let mut message = promote(plaintext);
+        let padding = BLOCK_BYTES - message.len() % BLOCK_BYTES;
+        let padding_byte = arithmetic::promote_to_trivial_share(id, F::from(padding as u64));
+        message.resize(message.len() + padding, padding_byte);
+        let ciphertext = yao::aes128_cbc_encrypt_bytes(
+            &promote(key),
+            &promote(iv),
+            &message,
+            &mut self.io_context,
+        )?;
+        Ok(ciphertext.into_iter().map(Rep3AcvmType::from).collect())
+    }
+
+    fn poseidon2_permutation<const T: usize, const D: u64>(
+        &mut self,
+        state: Vec<Self::AcvmType>,
+        params: &Poseidon2Params<F, T, D>,
+    ) -> std::io::Result<Vec<Self::AcvmType>> {
+        if let Some(public) = state
+            .iter()
+            .map(Self::get_public)
+            .collect::<Option<Vec<_>>>()
+        {
+            let result = self.plain_solver.poseidon2_permutation(public, params)?;
+            return Ok(result.into_iter().map(Rep3AcvmType::from).collect());
+        }
+
+        let id = self.io_context.id;
+        let state: [ArithmeticShare<F>; T] = state
+            .iter()
+            .map(|value| match value {
+                Rep3AcvmType::Public(public) => arithmetic::promote_to_trivial_share(id, *public),
+                Rep3AcvmType::Shared(shared) => *shared,
+            })
+            .collect_vec()
+            .try_into()
+            .map_err(|_| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("the Poseidon2 permutation expects a state of {T} elements"),
+                )
+            })?;
+        let result =
+            gadgets::poseidon2::poseidon2_permutation(params, &state, &mut self.io_context)?;
+        Ok(result.into_iter().map(Rep3AcvmType::from).collect())
+    }
+
+    fn bigint_op(
+        &mut self,
+        lhs: &[Self::AcvmType],
+        rhs: &[Self::AcvmType],
+        modulus: &BigUint,
+        func: BlackBoxFunc,
+    ) -> std::io::Result<Vec<Self::AcvmType>> {
+        let public = lhs
+            .iter()
+            .chain(rhs.iter())
+            .map(Self::get_public)
+            .collect::<Option<Vec<_>>>();
+        if let Some(public) = public {
+            let (lhs, rhs) = public.split_at(lhs.len());
+            let result = self.plain_solver.bigint_op(lhs, rhs, modulus, func)?;
+            return Ok(result.into_iter().map(Rep3AcvmType::from).collect());
+        }
+
+        let id = self.io_context.id;
+        let to_shares = |bytes: &[Self::AcvmType]| {
+            bytes
+                .iter()
+                .map(|byte| match byte {
+                    Rep3AcvmType::Public(public) => {
+                        arithmetic::promote_to_trivial_share(id, *public)
+                    }
+                    Rep3AcvmType::Shared(shared) => *shared,
+                })
+                .collect_vec()
+        };
+        let lhs = to_shares(lhs);
+        let rhs = to_shares(rhs);
+
+        let op = match func {
+            BlackBoxFunc::BigIntAdd => BigIntModOp::Add,
+            BlackBoxFunc::BigIntSub => BigIntModOp::Sub,
+            BlackBoxFunc::BigIntMul => BigIntModOp::Mul,
+            BlackBoxFunc::BigIntDiv => {
+                let result = yao::bigint_div_mod_bytes(&lhs, &rhs, modulus, &mut self.io_context)?;
+                return Ok(result.into_iter().map(Rep3AcvmType::from).collect());
+            }
+            _ => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("{} is not a BigInt operation", func.name()),
+                ))
+            }
+        };
+        let result = yao::bigint_mod_op_bytes(&lhs, &rhs, modulus, op, &mut self.io_context)?;
+        Ok(result.into_iter().map(Rep3AcvmType::from).collect())
+    }
+
+    fn bigint_quotient_remainder(
+        &mut self,
+        lhs: &[Self::AcvmType],
+        rhs: &[Self::AcvmType],
+        add: &[Self::AcvmType],
+        sub: &[Self::AcvmType],
+        modulus: &BigUint,
+        limb_bits: usize,
+        num_limbs: usize,
+    ) -> std::io::Result<(Vec<Self::AcvmType>, Vec<Self::AcvmType>)> {
+        let public = [lhs, rhs, add, sub].map(|limbs| {
+            limbs
+                .iter()
+                .map(Self::get_public)
+                .collect::<Option<Vec<_>>>()
+        });
+        if let [Some(lhs), Some(rhs), Some(add), Some(sub)] = public {
+            let (quotient, remainder) = self
+                .plain_solver
+                .bigint_quotient_remainder(&lhs, &rhs, &add, &sub, modulus, limb_bits, num_limbs)?;
+            return Ok((
+                quotient.into_iter().map(Rep3AcvmType::from).collect(),
+                remainder.into_iter().map(Rep3AcvmType::from).collect(),
+            ));
+        }
+
+        let [lhs, rhs, add, sub] = [lhs, rhs, add, sub].map(|limbs| self.to_shares(limbs));
+        let (quotient, remainder) = yao::bigint_quotient_remainder_limbs(
+            &lhs,
+            &rhs,
+            &add,
+            &sub,
+            modulus,
+            limb_bits,
+            num_limbs,
+            &mut self.io_context,
+        )?;
+        Ok((
+            quotient.into_iter().map(Rep3AcvmType::from).collect(),
+            remainder.into_iter().map(Rep3AcvmType::from).collect(),
+        ))
+    }
+
+    fn bigint_div_limbs(
+        &mut self,
+        lhs: &[Self::AcvmType],
+        rhs: &[Self::AcvmType],
+        modulus: &BigUint,
+        limb_bits: usize,
+        num_limbs: usize,
+    ) -> std::io::Result<Vec<Self::AcvmType>> {
+        let public = lhs
+            .iter()
+            .chain(rhs.iter())
+            .map(Self::get_public)
+            .collect::<Option<Vec<_>>>();
+        if let Some(public) = public {
+            let (lhs, rhs) = public.split_at(lhs.len());
+            let result = self
+                .plain_solver
+                .bigint_div_limbs(lhs, rhs, modulus, limb_bits, num_limbs)?;
+            return Ok(result.into_iter().map(Rep3AcvmType::from).collect());
+        }
+
+        let lhs = self.to_shares(lhs);
+        let rhs = self.to_shares(rhs);
+        let result = yao::bigint_div_limbs(
+            &lhs,
+            &rhs,
+            modulus,
+            limb_bits,
+            num_limbs,
+            &mut self.io_context,
+        )?;
+        Ok(result.into_iter().map(Rep3AcvmType::from).collect())
+    }
 }
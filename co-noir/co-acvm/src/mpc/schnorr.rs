@@ -0,0 +1,127 @@
+//! Verification of Schnorr signatures over the embedded curve of Noir, i.e., Grumpkin for BN254, over ACVM-types.
+//!
+//! The verification follows barretenberg and the blackbox solver of Noir: For a public key `pk` and a signature `(s, e)`, it computes `R = s * G + e * pk` and accepts the signature if `e = blake2s(pedersen_hash(R.x, pk.x, pk.y) || message)`, where the Pedersen hash is the one of [pedersen](super::pedersen). The public key, the signature and the message may be public or shared. The points are computed with the oblivious arithmetic of [embedded_curve](super::embedded_curve), and the hash of a shared message is computed with [blake2s_hash](super::NoirWitnessExtensionProtocol::blake2s_hash), so the verification only reveals the length of the message.
+use ark_ff::{BigInteger, PrimeField};
+use num_bigint::BigUint;
+
+use super::embedded_curve::{self, EmbeddedCurvePoint, SCALAR_LIMB_BITS};
+use super::NoirWitnessExtensionProtocol;
+
+/// The y coordinate of the generator (1, y) of Grumpkin.
+const GENERATOR_Y: &[u8] = b"17631683881184975370165255887551781615748388533673675138860";
+/// The number of bytes of each of s and e of a signature.
+const SIGNATURE_SCALAR_BYTES: usize = 32;
+
+/// Composes big-endian bytes to a single value.
+fn compose_be_bytes<F: PrimeField, T: NoirWitnessExtensionProtocol<F> + ?Sized>(
+    driver: &mut T,
+    bytes: &[T::AcvmType],
+) -> T::AcvmType {
+    bytes.iter().fold(T::public_zero(), |acc, byte| {
+        let acc = driver.mul_with_public(F::from(256u64), acc);
+        driver.add(acc, byte.to_owned())
+    })
+}
+
+/// Composes the 32 big-endian bytes of a scalar of the signature to the lower and upper 128 bits of the scalar.
+fn bytes_to_scalar<F: PrimeField, T: NoirWitnessExtensionProtocol<F> + ?Sized>(
+    driver: &mut T,
+    bytes: &[T::AcvmType],
+) -> (T::AcvmType, T::AcvmType) {
+    let (hi, lo) = bytes.split_at(bytes.len() - SCALAR_LIMB_BITS / 8);
+    (compose_be_bytes(driver, lo), compose_be_bytes(driver, hi))
+}
+
+/// Decomposes a field element into its big-endian bytes.
+fn field_to_be_bytes<F: PrimeField, T: NoirWitnessExtensionProtocol<F> + ?Sized>(
+    driver: &mut T,
+    a: T::AcvmType,
+) -> std::io::Result<Vec<T::AcvmType>> {
+    let num_bytes = (F::MODULUS_BIT_SIZE as usize).div_ceil(8);
+    let mut bytes = if let Some(public) = T::get_public(&a) {
+        let mut bytes = public.into_bigint().to_bytes_le();
+        bytes.resize(num_bytes, 0);
+        bytes
+            .into_iter()
+            .map(|byte| F::from(byte as u64).into())
+            .collect::<Vec<_>>()
+    } else {
+        let shared = T::get_shared(&a).expect("value is either public or shared");
+        driver
+            .decompose_arithmetic(shared, F::MODULUS_BIT_SIZE as usize, 8)?
+            .into_iter()
+            .map(T::AcvmType::from)
+            .collect()
+    };
+    bytes.reverse();
+    Ok(bytes)
+}
+
+/// Returns 1 - a for a bit a.
+fn not<F: PrimeField, T: NoirWitnessExtensionProtocol<F> + ?Sized>(
+    driver: &mut T,
+    mut a: T::AcvmType,
+) -> T::AcvmType {
+    driver.negate_inplace(&mut a);
+    driver.add_assign_with_public(F::one(), &mut a);
+    a
+}
+
+/// Verifies a Schnorr signature, see the [module documentation](self).
+pub(super) fn verify<F: PrimeField, T: NoirWitnessExtensionProtocol<F> + ?Sized>(
+    driver: &mut T,
+    public_key_x: T::AcvmType,
+    public_key_y: T::AcvmType,
+    signature: &[T::AcvmType],
+    message: &[T::AcvmType],
+) -> eyre::Result<T::AcvmType> {
+    if signature.len() != 2 * SIGNATURE_SCALAR_BYTES {
+        eyre::bail!(
+            "a Schnorr signature has {} bytes, but got {}",
+            2 * SIGNATURE_SCALAR_BYTES,
+            signature.len()
+        );
+    }
+    let (s, e) = signature.split_at(SIGNATURE_SCALAR_BYTES);
+    let public_key = EmbeddedCurvePoint {
+        x: public_key_x,
+        y: public_key_y,
+        is_infinite: T::public_zero(),
+    };
+
+    // The public key has to be on the curve y^2 = x^3 - 17, which also excludes (0, 0)
+    let y_squared = driver.mul(public_key.y.to_owned(), public_key.y.to_owned())?;
+    let x_squared = driver.mul(public_key.x.to_owned(), public_key.x.to_owned())?;
+    let x_cubed = driver.mul(x_squared, public_key.x.to_owned())?;
+    let mut curve_equation = driver.sub(y_squared, x_cubed);
+    driver.add_assign_with_public(F::from(17u64), &mut curve_equation);
+    let mut valid = driver.equal(&curve_equation, &T::public_zero())?;
+
+    // R = s * G + e * pk. Since the order of the curve is prime, s * G (e * pk) is the point at infinity if and only if s (e) is zero modulo the order.
+    let generator_y = BigUint::parse_bytes(GENERATOR_Y, 10).expect("valid constant");
+    let generator = EmbeddedCurvePoint {
+        x: F::one().into(),
+        y: F::from(generator_y).into(),
+        is_infinite: T::public_zero(),
+    };
+    let s = bytes_to_scalar(driver, s);
+    let s_g = embedded_curve::msm(driver, vec![generator], vec![s])?;
+    let e_scalar = bytes_to_scalar(driver, e);
+    let e_pk = embedded_curve::msm(driver, vec![public_key.to_owned()], vec![e_scalar])?;
+    let r = embedded_curve::add(driver, s_g.to_owned(), e_pk.to_owned())?;
+    for is_infinite in [s_g.is_infinite, e_pk.is_infinite, r.is_infinite] {
+        let is_finite = not(driver, is_infinite);
+        valid = driver.mul(valid, is_finite)?;
+    }
+
+    // The challenge blake2s(pedersen_hash || message) has to equal e
+    let pedersen_hash = driver.pedersen_hash(vec![r.x, public_key.x, public_key.y], 0)?;
+    let mut hash_input = field_to_be_bytes(driver, pedersen_hash)?;
+    hash_input.extend_from_slice(message);
+    let challenge = driver.blake2s_hash(&hash_input)?;
+    for (challenge, e) in challenge.iter().zip(e) {
+        let is_equal = driver.equal(challenge, e)?;
+        valid = driver.mul(valid, is_equal)?;
+    }
+    Ok(valid)
+}
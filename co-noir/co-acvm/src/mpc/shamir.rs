@@ -1,10 +1,13 @@
 use super::{plain::PlainAcvmSolver, NoirWitnessExtensionProtocol};
+use acir::BlackBoxFunc;
 use ark_ff::PrimeField;
 use co_brillig::mpc::{ShamirBrilligDriver, ShamirBrilligType};
+use mpc_core::gadgets::poseidon2::Poseidon2Params;
 use mpc_core::protocols::{
     rep3::{lut::NaiveRep3LookupTable, network::Rep3MpcNet},
     shamir::{arithmetic, network::ShamirNetwork, ShamirPrimeFieldShare, ShamirProtocol},
 };
+use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
 
@@ -387,6 +390,17 @@ impl<F: PrimeField, N: ShamirNetwork> NoirWitnessExtensionProtocol<F> for Shamir
         }
     }
 
+    fn check_consistent_digest(&mut self, digest: &[u8]) -> std::io::Result<()> {
+        let digests = self.protocol.network.broadcast(digest.to_vec())?;
+        if digests.iter().any(|other| other != digest) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "the digest of another party differs from ours",
+            ));
+        }
+        Ok(())
+    }
+
     fn open_many(&mut self, a: &[Self::ArithmeticShare]) -> std::io::Result<Vec<F>> {
         arithmetic::open_vec(a, &mut self.protocol)
     }
@@ -415,4 +429,149 @@ impl<F: PrimeField, N: ShamirNetwork> NoirWitnessExtensionProtocol<F> for Shamir
     ) -> std::io::Result<Vec<Self::ArithmeticShare>> {
         panic!("functionality sort not feasible for Shamir")
     }
+
+    fn equal(&mut self, a: &Self::AcvmType, b: &Self::AcvmType) -> std::io::Result<Self::AcvmType> {
+        match (a, b) {
+            (ShamirAcvmType::Public(a), ShamirAcvmType::Public(b)) => {
+                Ok(ShamirAcvmType::Public(F::from(a == b)))
+            }
+            _ => panic!("functionality equal with shared inputs not feasible for Shamir"),
+        }
+    }
+
+    fn blake2s_hash(&mut self, message: &[Self::AcvmType]) -> std::io::Result<Vec<Self::AcvmType>> {
+        match message
+            .iter()
+            .map(Self::get_public)
+            .collect::<Option<Vec<_>>>()
+        {
+            Some(public) => {
+                let digest = self.plain_solver.blake2s_hash(&public)?;
+                Ok(digest.into_iter().map(ShamirAcvmType::from).collect())
+            }
+            None => panic!("functionality blake2s_hash with shared inputs not feasible for Shamir"),
+        }
+    }
+
+    fn poseidon2_permutation<const T: usize, const D: u64>(
+        &mut self,
+        state: Vec<Self::AcvmType>,
+        params: &Poseidon2Params<F, T, D>,
+    ) -> std::io::Result<Vec<Self::AcvmType>> {
+        match state
+            .iter()
+            .map(Self::get_public)
+            .collect::<Option<Vec<_>>>()
+        {
+            Some(public) => {
+                let result = self.plain_solver.poseidon2_permutation(public, params)?;
+                Ok(result.into_iter().map(ShamirAcvmType::from).collect())
+            }
+            None => panic!(
+                "functionality poseidon2_permutation with shared inputs not feasible for Shamir"
+            ),
+        }
+    }
+
+    fn aes128_encrypt(
+        &mut self,
+        plaintext: &[Self::AcvmType],
+        iv: &[Self::AcvmType],
+        key: &[Self::AcvmType],
+    ) -> std::io::Result<Vec<Self::AcvmType>> {
+        let to_public = |bytes: &[Self::AcvmType]| {
+            bytes
+                .iter()
+                .map(Self::get_public)
+                .collect::<Option<Vec<_>>>()
+        };
+        match (to_public(plaintext), to_public(iv), to_public(key)) {
+            (Some(plaintext), Some(iv), Some(key)) => {
+                let ciphertext = self.plain_solver.aes128_encrypt(&plaintext, &iv, &key)?;
+                Ok(ciphertext.into_iter().map(ShamirAcvmType::from).collect())
+            }
+            _ => panic!("functionality aes128_encrypt with shared inputs not feasible for Shamir"),
+        }
+    }
+
+    fn bigint_op(
+        &mut self,
+        lhs: &[Self::AcvmType],
+        rhs: &[Self::AcvmType],
+        modulus: &BigUint,
+        func: BlackBoxFunc,
+    ) -> std::io::Result<Vec<Self::AcvmType>> {
+        let public = lhs
+            .iter()
+            .chain(rhs.iter())
+            .map(Self::get_public)
+            .collect::<Option<Vec<_>>>();
+        match public {
+            Some(public) => {
+                let (lhs, rhs) = public.split_at(lhs.len());
+                let result = self.plain_solver.bigint_op(lhs, rhs, modulus, func)?;
+                Ok(result.into_iter().map(ShamirAcvmType::from).collect())
+            }
+            None => panic!("functionality bigint_op with shared inputs not feasible for Shamir"),
+        }
+    }
+
+    fn bigint_quotient_remainder(
+        &mut self,
+        lhs: &[Self::AcvmType],
+        rhs: &[Self::AcvmType],
+        add: &[Self::AcvmType],
+        sub: &[Self::AcvmType],
+        modulus: &BigUint,
+        limb_bits: usize,
+        num_limbs: usize,
+    ) -> std::io::Result<(Vec<Self::AcvmType>, Vec<Self::AcvmType>)> {
+        let public = [lhs, rhs, add, sub].map(|limbs| {
+            limbs
+                .iter()
+                .map(Self::get_public)
+                .collect::<Option<Vec<_>>>()
+        });
+        match public {
+            [Some(lhs), Some(rhs), Some(add), Some(sub)] => {
+                let (quotient, remainder) = self.plain_solver.bigint_quotient_remainder(
+                    &lhs, &rhs, &add, &sub, modulus, limb_bits, num_limbs,
+                )?;
+                Ok((
+                    quotient.into_iter().map(ShamirAcvmType::from).collect(),
+                    remainder.into_iter().map(ShamirAcvmType::from).collect(),
+                ))
+            }
+            _ => panic!(
+                "functionality bigint_quotient_remainder with shared inputs not feasible for Shamir"
+            ),
+        }
+    }
+
+    fn bigint_div_limbs(
+        &mut self,
+        lhs: &[Self::AcvmType],
+        rhs: &[Self::AcvmType],
+        modulus: &BigUint,
+        limb_bits: usize,
+        num_limbs: usize,
+    ) -> std::io::Result<Vec<Self::AcvmType>> {
+        let public = lhs
+            .iter()
+            .chain(rhs.iter())
+            .map(Self::get_public)
+            .collect::<Option<Vec<_>>>();
+        match public {
+            Some(public) => {
+                let (lhs, rhs) = public.split_at(lhs.len());
+                let result = self
+                    .plain_solver
+                    .bigint_div_limbs(lhs, rhs, modulus, limb_bits, num_limbs)?;
+                Ok(result.into_iter().map(ShamirAcvmType::from).collect())
+            }
+            None => {
+                panic!("functionality bigint_div_limbs with shared inputs not feasible for Shamir")
+            }
+        }
+    }
 }
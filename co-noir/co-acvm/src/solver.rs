@@ -1,11 +1,12 @@
 use acir::{
     acir_field::GenericFieldElement,
-    circuit::{Circuit, ExpressionWidth, Opcode, Program},
+    circuit::{Circuit, ExpressionWidth, Opcode},
     native_types::{WitnessMap, WitnessStack},
     FieldElement,
 };
 use ark_ff::PrimeField;
 use co_brillig::CoBrilligVM;
+use eyre::Context;
 use intmap::IntMap;
 use mpc_core::{
     lut::LookupTableProvider,
@@ -14,7 +15,7 @@ use mpc_core::{
         shamir::{network::ShamirNetwork, ShamirPreprocessing, ShamirProtocol},
     },
 };
-use noirc_abi::{input_parser::Format, Abi, MAIN_RETURN_NAME};
+use noirc_abi::{Abi, MAIN_RETURN_NAME};
 use noirc_artifacts::program::ProgramArtifact;
 use partial_abi::PublicMarker;
 use std::{collections::BTreeMap, io, path::PathBuf};
@@ -84,6 +85,8 @@ where
     function_index: usize,
     // the memory blocks
     memory_access: IntMap<<T::Lookup as LookupTableProvider<F>>::SecretSharedMap>,
+    // the values of the BigInt blackbox functions
+    bigints: IntMap<blackbox_solver::BigIntValue<T::AcvmType>>,
 }
 
 impl<T> CoSolver<T, ark_bn254::Fr>
@@ -102,8 +105,9 @@ where
         if abi.is_empty() {
             Ok(WitnessMap::default())
         } else {
-            let input_string = std::fs::read_to_string(PathBuf::from(path))?;
-            let mut input_map = Format::Toml.parse(&input_string, abi)?;
+            let path = PathBuf::from(path);
+            let input_string = std::fs::read_to_string(&path)?;
+            let mut input_map = partial_abi::input_format(&path).parse(&input_string, abi)?;
             let return_value = input_map.remove(MAIN_RETURN_NAME);
             // TACEO TODO the return value can be none for the witness extension
             // do we want to keep it like that? Seems not necessary but maybe
@@ -112,11 +116,10 @@ where
         }
     }
 
-    // This is the same as read_abi_bn254_fieldelement, but only warns if parameters are missing instead of throwing an error and returns a map with strings instead
+    // This is the same as read_abi_bn254_fieldelement, but only warns if parameters are missing instead of throwing an error and returns a map with strings instead. The elements are marked public or private according to the visibility of their parameters in the ABI.
     pub fn partially_read_abi_bn254_fieldelement<P>(
         path: P,
         abi: &Abi,
-        program: &Program<FieldElement>,
     ) -> eyre::Result<BTreeMap<String, PublicMarker<FieldElement>>>
     where
         PathBuf: From<P>,
@@ -124,20 +127,17 @@ where
         if abi.is_empty() {
             Ok(BTreeMap::default())
         } else {
-            let input_string = std::fs::read_to_string(PathBuf::from(path))?;
-            let abi_ = Self::create_partial_abi(&input_string, abi)?;
-            let mut input_map = Format::Toml.parse(&input_string, &abi_)?;
+            let path = PathBuf::from(path);
+            let input_string = std::fs::read_to_string(&path)?;
+            let format = partial_abi::input_format(&path);
+            let abi_ = Self::create_partial_abi(&input_string, abi, &format)?;
+            let mut input_map = format.parse(&input_string, &abi_)?;
             let return_value = input_map.remove(MAIN_RETURN_NAME);
             // TACEO TODO the return value can be none for the witness extension
             // do we want to keep it like that? Seems not necessary but maybe
             // we need it for proving/verifying
             let encoded = abi_.encode(&input_map, return_value.clone())?;
-            Ok(Self::create_string_map(
-                abi,
-                &abi_,
-                encoded,
-                &program.functions[Self::DEFAULT_FUNCTION_INDEX].public_parameters,
-            )?)
+            Self::create_string_map(&abi_, encoded)
         }
     }
 
@@ -183,6 +183,7 @@ where
             witness_map,
             function_index: Self::DEFAULT_FUNCTION_INDEX,
             memory_access: IntMap::new(),
+            bigints: IntMap::new(),
         })
     }
 
@@ -213,6 +214,7 @@ where
             witness_map,
             function_index: Self::DEFAULT_FUNCTION_INDEX,
             memory_access: IntMap::new(),
+            bigints: IntMap::new(),
         })
     }
 }
@@ -337,6 +339,16 @@ where
     fn witness(&mut self) -> &mut WitnessMap<T::AcvmType> {
         &mut self.witness_map[self.function_index]
     }
+
+    /// Sets the policy of the coBrillig-VM for loops on shared conditions in unconstrained functions.
+    pub fn set_shared_loop_policy(&mut self, policy: co_brillig::SharedLoopPolicy) {
+        self.brillig.set_shared_loop_policy(policy);
+    }
+
+    /// Sets the policy that chooses the execution of the unconstrained functions, see [`co_brillig::BrilligPolicy`].
+    pub fn set_brillig_policy(&mut self, policy: &co_brillig::BrilligPolicy) -> eyre::Result<()> {
+        self.brillig.set_policy(policy)
+    }
 }
 
 impl<T, F> CoSolver<T, F>
@@ -367,6 +379,12 @@ where
 
     pub fn solve(mut self) -> CoAcvmResult<WitnessStack<T::AcvmType>> {
         let functions = std::mem::take(&mut self.functions);
+        if !self.brillig.profiles().is_empty() {
+            // the policy decides which arguments are opened, so the parties must agree on it before solving
+            self.driver
+                .check_consistent_digest(&self.brillig.plan_digest())
+                .context("while checking that all parties use the same brillig policy")?;
+        }
 
         for opcode in functions[self.function_index].opcodes.iter() {
             match opcode {
@@ -374,7 +392,7 @@ where
                 Opcode::MemoryInit {
                     block_id,
                     init,
-                    block_type: _, // apparently not used
+                    block_type: _, // call_data and return_data are solved like any other memory block
                 } => self.solve_memory_init_block(*block_id, init)?,
                 Opcode::MemoryOp {
                     block_id,
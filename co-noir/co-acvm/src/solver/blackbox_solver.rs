@@ -1,13 +1,24 @@
-use crate::mpc::NoirWitnessExtensionProtocol;
+use crate::mpc::{embedded_curve::EmbeddedCurvePoint, NoirWitnessExtensionProtocol};
 
 use super::{CoAcvmResult, CoSolver};
 use acir::{
     acir_field::GenericFieldElement,
     circuit::opcodes::{BlackBoxFuncCall, ConstantOrWitnessEnum, FunctionInput},
     native_types::{Witness, WitnessMap},
-    AcirField,
+    AcirField, BlackBoxFunc,
 };
-use ark_ff::PrimeField;
+use ark_ff::{PrimeField, Zero};
+use mpc_core::gadgets::poseidon2::POSEIDON2_BN254_T4_PARAMS;
+use num_bigint::BigUint;
+
+/// The value of a BigInt created by the BigInt blackbox functions.
+#[derive(Clone, Debug)]
+pub(crate) struct BigIntValue<A> {
+    /// The value as little-endian bytes, each byte is an ACVM-type
+    bytes: Vec<A>,
+    /// The public modulus of the BigInt
+    modulus: BigUint,
+}
 
 impl<T, F> CoSolver<T, F>
 where
@@ -129,9 +140,286 @@ where
 
         match bb_func {
             BlackBoxFuncCall::RANGE { input } => Self::solve_range_opcode(initial_witness, input)?,
+            BlackBoxFuncCall::BigIntFromLeBytes {
+                inputs,
+                modulus,
+                output,
+            } => self.solve_bigint_from_le_bytes(inputs, modulus, *output)?,
+            BlackBoxFuncCall::BigIntToLeBytes { input, outputs } => {
+                self.solve_bigint_to_le_bytes(*input, outputs)?
+            }
+            BlackBoxFuncCall::BigIntAdd { lhs, rhs, output }
+            | BlackBoxFuncCall::BigIntSub { lhs, rhs, output }
+            | BlackBoxFuncCall::BigIntMul { lhs, rhs, output }
+            | BlackBoxFuncCall::BigIntDiv { lhs, rhs, output } => {
+                self.solve_bigint_op(*lhs, *rhs, *output, bb_func.get_black_box_func())?
+            }
+            BlackBoxFuncCall::EmbeddedCurveAdd {
+                input1,
+                input2,
+                outputs,
+            } => self.solve_embedded_curve_add(&input1[..], &input2[..], *outputs)?,
+            BlackBoxFuncCall::MultiScalarMul {
+                points,
+                scalars,
+                outputs,
+            } => self.solve_multi_scalar_mul(points, scalars, *outputs)?,
+            BlackBoxFuncCall::SchnorrVerify {
+                public_key_x,
+                public_key_y,
+                signature,
+                message,
+                output,
+            } => self.solve_schnorr_verify(
+                *public_key_x,
+                *public_key_y,
+                &signature[..],
+                message,
+                *output,
+            )?,
+            BlackBoxFuncCall::Poseidon2Permutation {
+                inputs,
+                outputs,
+                len,
+            } => self.solve_poseidon2_permutation(inputs, outputs, *len)?,
+            BlackBoxFuncCall::AES128Encrypt {
+                inputs,
+                iv,
+                key,
+                outputs,
+            } => self.solve_aes128_encrypt(inputs, &iv[..], &key[..], outputs)?,
             _ => todo!("solve blackbox funciton {} not supported", bb_func.name()),
         }
 
         Ok(())
     }
+    /// Reads a point of the embedded curve from the inputs (x, y, is_infinite).
+    fn input_to_point(
+        &self,
+        input: &[FunctionInput<GenericFieldElement<F>>],
+    ) -> CoAcvmResult<EmbeddedCurvePoint<T::AcvmType>> {
+        let initial_witness = &self.witness_map[self.function_index];
+        Ok(EmbeddedCurvePoint {
+            x: Self::input_to_value(initial_witness, input[0], false)?,
+            y: Self::input_to_value(initial_witness, input[1], false)?,
+            is_infinite: Self::input_to_value(initial_witness, input[2], false)?,
+        })
+    }
+
+    fn insert_point(
+        &mut self,
+        point: EmbeddedCurvePoint<T::AcvmType>,
+        outputs: (Witness, Witness, Witness),
+    ) {
+        let initial_witness = self.witness();
+        initial_witness.insert(outputs.0, point.x);
+        initial_witness.insert(outputs.1, point.y);
+        initial_witness.insert(outputs.2, point.is_infinite);
+    }
+
+    fn solve_embedded_curve_add(
+        &mut self,
+        input1: &[FunctionInput<GenericFieldElement<F>>],
+        input2: &[FunctionInput<GenericFieldElement<F>>],
+        outputs: (Witness, Witness, Witness),
+    ) -> CoAcvmResult<()> {
+        let p1 = self.input_to_point(input1)?;
+        let p2 = self.input_to_point(input2)?;
+        let sum = self.driver.embedded_curve_add(p1, p2)?;
+        self.insert_point(sum, outputs);
+        Ok(())
+    }
+
+    fn solve_multi_scalar_mul(
+        &mut self,
+        points: &[FunctionInput<GenericFieldElement<F>>],
+        scalars: &[FunctionInput<GenericFieldElement<F>>],
+        outputs: (Witness, Witness, Witness),
+    ) -> CoAcvmResult<()> {
+        if points.len() % 3 != 0 || scalars.len() % 2 != 0 {
+            Err(eyre::eyre!(
+                "MultiScalarMul expects three inputs per point and two inputs per scalar"
+            ))?;
+        }
+        let points = points
+            .chunks(3)
+            .map(|point| self.input_to_point(point))
+            .collect::<CoAcvmResult<Vec<_>>>()?;
+        let initial_witness = &self.witness_map[self.function_index];
+        let scalars = scalars
+            .chunks(2)
+            .map(|scalar| {
+                Ok((
+                    Self::input_to_value(initial_witness, scalar[0], false)?,
+                    Self::input_to_value(initial_witness, scalar[1], false)?,
+                ))
+            })
+            .collect::<CoAcvmResult<Vec<_>>>()?;
+        let result = self.driver.embedded_curve_msm(points, scalars)?;
+        self.insert_point(result, outputs);
+        Ok(())
+    }
+
+    fn solve_schnorr_verify(
+        &mut self,
+        public_key_x: FunctionInput<GenericFieldElement<F>>,
+        public_key_y: FunctionInput<GenericFieldElement<F>>,
+        signature: &[FunctionInput<GenericFieldElement<F>>],
+        message: &[FunctionInput<GenericFieldElement<F>>],
+        output: Witness,
+    ) -> CoAcvmResult<()> {
+        let initial_witness = &self.witness_map[self.function_index];
+        let public_key_x = Self::input_to_value(initial_witness, public_key_x, false)?;
+        let public_key_y = Self::input_to_value(initial_witness, public_key_y, false)?;
+        let signature = signature
+            .iter()
+            .map(|input| Self::input_to_value(initial_witness, *input, false))
+            .collect::<CoAcvmResult<Vec<_>>>()?;
+        let message = message
+            .iter()
+            .map(|input| Self::input_to_value(initial_witness, *input, false))
+            .collect::<CoAcvmResult<Vec<_>>>()?;
+        let valid = self
+            .driver
+            .schnorr_verify(public_key_x, public_key_y, &signature, &message)?;
+        self.witness().insert(output, valid);
+        Ok(())
+    }
+
+    fn solve_poseidon2_permutation(
+        &mut self,
+        inputs: &[FunctionInput<GenericFieldElement<F>>],
+        outputs: &[Witness],
+        len: u32,
+    ) -> CoAcvmResult<()> {
+        if inputs.len() != len as usize || outputs.len() != len as usize {
+            Err(eyre::eyre!(
+                "Poseidon2Permutation expects {len} inputs and outputs, but got {} inputs and {} outputs",
+                inputs.len(),
+                outputs.len()
+            ))?;
+        }
+        // the parameters of barretenberg, which are only defined over the scalar field of BN254
+        let params = POSEIDON2_BN254_T4_PARAMS.to_field::<F>().ok_or_else(|| {
+            eyre::eyre!("Poseidon2Permutation is only supported over the scalar field of BN254")
+        })?;
+        let initial_witness = &self.witness_map[self.function_index];
+        let state = inputs
+            .iter()
+            .map(|input| Self::input_to_value(initial_witness, *input, false))
+            .collect::<CoAcvmResult<Vec<_>>>()?;
+        let result = self.driver.poseidon2_permutation(state, &params)?;
+        let initial_witness = self.witness();
+        for (output, value) in outputs.iter().zip(result) {
+            initial_witness.insert(*output, value);
+        }
+        Ok(())
+    }
+
+    fn solve_aes128_encrypt(
+        &mut self,
+        inputs: &[FunctionInput<GenericFieldElement<F>>],
+        iv: &[FunctionInput<GenericFieldElement<F>>],
+        key: &[FunctionInput<GenericFieldElement<F>>],
+        outputs: &[Witness],
+    ) -> CoAcvmResult<()> {
+        // the ciphertext is padded to the next full block with PKCS#7
+        let ciphertext_len = (inputs.len() / 16 + 1) * 16;
+        if outputs.len() != ciphertext_len {
+            Err(eyre::eyre!(
+                "AES128Encrypt of {} bytes expects {ciphertext_len} outputs, but got {}",
+                inputs.len(),
+                outputs.len()
+            ))?;
+        }
+        let initial_witness = &self.witness_map[self.function_index];
+        let to_values = |inputs: &[FunctionInput<GenericFieldElement<F>>]| {
+            inputs
+                .iter()
+                .map(|input| Self::input_to_value(initial_witness, *input, false))
+                .collect::<CoAcvmResult<Vec<_>>>()
+        };
+        let plaintext = to_values(inputs)?;
+        let iv = to_values(iv)?;
+        let key = to_values(key)?;
+        let ciphertext = self.driver.aes128_encrypt(&plaintext, &iv, &key)?;
+        let initial_witness = self.witness();
+        for (output, value) in outputs.iter().zip(ciphertext) {
+            initial_witness.insert(*output, value);
+        }
+        Ok(())
+    }
+
+    fn get_bigint(&self, id: u32) -> CoAcvmResult<&BigIntValue<T::AcvmType>> {
+        match self.bigints.get(id.into()) {
+            Some(value) => Ok(value),
+            None => Err(eyre::eyre!("BigInt with id {id} is not initialized"))?,
+        }
+    }
+
+    fn solve_bigint_from_le_bytes(
+        &mut self,
+        inputs: &[FunctionInput<GenericFieldElement<F>>],
+        modulus: &[u8],
+        output: u32,
+    ) -> CoAcvmResult<()> {
+        let initial_witness = &self.witness_map[self.function_index];
+        let bytes = inputs
+            .iter()
+            .map(|input| Self::input_to_value(initial_witness, *input, false))
+            .collect::<CoAcvmResult<Vec<_>>>()?;
+        let modulus = BigUint::from_bytes_le(modulus);
+        if modulus.bits() < 2 {
+            Err(eyre::eyre!("BigInt modulus must be at least 2"))?;
+        }
+        self.bigints
+            .insert(output.into(), BigIntValue { bytes, modulus });
+        Ok(())
+    }
+
+    fn solve_bigint_to_le_bytes(&mut self, input: u32, outputs: &[Witness]) -> CoAcvmResult<()> {
+        let mut bytes = self.get_bigint(input)?.bytes.to_owned();
+        if bytes.len() < outputs.len() {
+            bytes.resize(outputs.len(), T::public_zero());
+        }
+        // We cannot check whether shared bytes are zero without opening them, so we only accept truncating public zeros
+        let truncates_value = bytes[outputs.len()..]
+            .iter()
+            .any(|byte| !matches!(T::get_public(byte), Some(byte) if byte.is_zero()));
+        if truncates_value {
+            Err(eyre::eyre!(
+                "BigIntToLeBytes has {} outputs, which cannot hold the {} bytes of the BigInt",
+                outputs.len(),
+                bytes.len()
+            ))?;
+        }
+        let initial_witness = self.witness();
+        for (witness, byte) in outputs.iter().zip(bytes) {
+            initial_witness.insert(*witness, byte);
+        }
+        Ok(())
+    }
+
+    fn solve_bigint_op(
+        &mut self,
+        lhs: u32,
+        rhs: u32,
+        output: u32,
+        func: BlackBoxFunc,
+    ) -> CoAcvmResult<()> {
+        let lhs = self.get_bigint(lhs)?;
+        let rhs = self.get_bigint(rhs)?;
+        if lhs.modulus != rhs.modulus {
+            Err(eyre::eyre!(
+                "BigInt operands of {} have different moduli",
+                func.name()
+            ))?;
+        }
+        let modulus = lhs.modulus.to_owned();
+        let (lhs, rhs) = (lhs.bytes.to_owned(), rhs.bytes.to_owned());
+        let bytes = self.driver.bigint_op(&lhs, &rhs, &modulus, func)?;
+        self.bigints
+            .insert(output.into(), BigIntValue { bytes, modulus });
+        Ok(())
+    }
 }
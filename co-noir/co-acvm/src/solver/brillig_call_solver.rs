@@ -5,7 +5,7 @@ use acir::{
     native_types::Expression,
 };
 use ark_ff::PrimeField;
-use co_brillig::CoBrilligResult;
+use co_brillig::{BrilligExecution, CoBrilligResult};
 use eyre::Context;
 use itertools::izip;
 
@@ -81,26 +81,30 @@ where
             BrilligMask::NoMask
         };
         tracing::debug!("solving brillig call: {}", id);
-        let mut calldata = vec![];
+        let mut params = vec![];
         for input in inputs {
             match input {
                 BrilligInputs::Single(expr) => {
                     let param = self
                         .evaluate_expression(expr)
                         .context("during call data init for brillig")?;
-                    calldata.push(param.into());
+                    params.push(param);
                 }
                 BrilligInputs::Array(array) => {
                     for expr in array.iter() {
                         let param = self
                             .evaluate_expression(expr)
                             .context("during call data init for brillig")?;
-                        calldata.push(param.into());
+                        params.push(param);
                     }
                 }
                 BrilligInputs::MemoryArray(_) => todo!("memory array calldata TODO"),
             }
         }
+        if self.brillig.execution(id) == BrilligExecution::Reveal {
+            params = self.open_brillig_params(params)?;
+        }
+        let calldata = params.into_iter().map(Into::into).collect();
         let brillig_result = self.brillig.run(id, calldata)?;
         if let CoBrilligResult::Success(brillig_result) = brillig_result {
             let brillig_result = self.driver.parse_brillig_result(brillig_result)?;
@@ -112,6 +116,27 @@ where
         }
     }
 
+    /// Opens the shared arguments of an unconstrained function that the
+    /// Brillig policy allows to reveal.
+    fn open_brillig_params(&mut self, params: Vec<T::AcvmType>) -> CoAcvmResult<Vec<T::AcvmType>> {
+        let shares = params.iter().filter_map(T::get_shared).collect::<Vec<_>>();
+        if shares.is_empty() {
+            return Ok(params);
+        }
+        tracing::warn!("revealing {} arguments of brillig call", shares.len());
+        let mut opened = self.driver.open_many(&shares)?.into_iter();
+        Ok(params
+            .into_iter()
+            .map(|param| {
+                if T::is_shared(&param) {
+                    T::AcvmType::from(opened.next().expect("opened all shares"))
+                } else {
+                    param
+                }
+            })
+            .collect())
+    }
+
     fn fill_output(&mut self, brillig_result: Vec<T::AcvmType>, outputs: &[BrilligOutputs]) {
         let mut current_ret_data_idx = 0;
         for output in outputs.iter() {
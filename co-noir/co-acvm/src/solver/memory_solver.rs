@@ -87,20 +87,24 @@ where
                     "tried to access block {} but not present",
                     block_id.0
                 ))?;
-            let value = self.driver.read_lut_by_acvm_type(&index, lut)?;
             if let Some(predicate) = predicate {
                 let predicate = predicate?;
                 if T::is_public_zero(&predicate) {
                     tracing::trace!("predicate is false - we read zero!");
                     self.witness().insert(witness, T::public_zero());
                 } else if T::is_public_one(&predicate) {
+                    let value = self.driver.read_lut_by_acvm_type(&index, lut)?;
+                    self.witness().insert(witness, value);
+                } else if T::is_shared(&predicate) {
+                    tracing::trace!("predicate is shared - we read predicate * value!");
+                    let value = self.driver.read_lut_by_acvm_type(&index, lut)?;
+                    let value = self.driver.mul(predicate, value)?;
                     self.witness().insert(witness, value);
                 } else {
-                    Err(eyre::eyre!(
-                        "predicate must be public and either zero or one"
-                    ))?
+                    Err(eyre::eyre!("predicate must be either zero or one"))?
                 }
             } else {
+                let value = self.driver.read_lut_by_acvm_type(&index, lut)?;
                 self.witness().insert(witness, value);
             }
         } else if read_write.is_one() {
@@ -119,10 +123,13 @@ where
                     tracing::trace!("predicate is false - we skip!");
                 } else if T::is_public_one(&predicate) {
                     self.driver.write_lut_by_acvm_type(index, value.q_c, lut)?;
+                } else if T::is_shared(&predicate) {
+                    tracing::trace!("predicate is shared - we write cmux(predicate, value, old)!");
+                    let old = self.driver.read_lut_by_acvm_type(&index, lut)?;
+                    let value = self.driver.cmux(predicate, value.q_c, old)?;
+                    self.driver.write_lut_by_acvm_type(index, value, lut)?;
                 } else {
-                    Err(eyre::eyre!(
-                        "predicate must be public and either zero or one"
-                    ))?
+                    Err(eyre::eyre!("predicate must be either zero or one"))?
                 }
             } else {
                 self.driver.write_lut_by_acvm_type(index, value.q_c, lut)?;
@@ -1,10 +1,10 @@
 use super::CoSolver;
 use crate::mpc::NoirWitnessExtensionProtocol;
-use acir::{circuit::PublicInputs, native_types::WitnessMap, FieldElement};
+use acir::{native_types::WitnessMap, FieldElement};
 use eyre::eyre;
-use noirc_abi::Abi;
+use noirc_abi::{input_parser::Format, Abi, AbiVisibility};
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, path::Path};
 
 // This type is copied from the noirc_abi crate, as it is not public. I just need it to be able to parse input.toml files.
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -24,24 +24,49 @@ enum TomlTypes {
     Table(BTreeMap<String, TomlTypes>),
 }
 
+/// Marks an input element as public or private, according to the visibility of its parameter in the ABI.
+#[derive(Debug, Clone, PartialEq)]
 pub enum PublicMarker<F> {
     Public(F),
     Private(F),
 }
 
+/// Returns the [Format] of an input file based on its extension, i.e., JSON for `.json` files (e.g., `Prover.json`) and TOML otherwise (e.g., `Prover.toml`).
+pub fn input_format(path: &Path) -> Format {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("json") => Format::Json,
+        _ => Format::Toml,
+    }
+}
+
 impl<T> CoSolver<T, ark_bn254::Fr>
 where
     T: NoirWitnessExtensionProtocol<ark_bn254::Fr>,
 {
-    pub(crate) fn create_partial_abi(input_string: &str, abi: &Abi) -> eyre::Result<Abi> {
+    pub(crate) fn create_partial_abi(
+        input_string: &str,
+        abi: &Abi,
+        format: &Format,
+    ) -> eyre::Result<Abi> {
         let mut res_abi = Abi {
             parameters: Vec::new(),
             return_type: abi.return_type.clone(),
             error_types: BTreeMap::new(),
         };
 
-        // Parse input.toml into a BTreeMap.
-        let data: BTreeMap<String, TomlTypes> = toml::from_str(input_string)?;
+        // Parse the names of the parameters in the input file. The values are parsed by noirc_abi with the partial ABI.
+        let data: BTreeMap<String, ()> = match format {
+            Format::Toml => toml::from_str::<BTreeMap<String, TomlTypes>>(input_string)?
+                .into_keys()
+                .map(|k| (k, ()))
+                .collect(),
+            Format::Json => {
+                serde_json::from_str::<BTreeMap<String, serde_json::Value>>(input_string)?
+                    .into_keys()
+                    .map(|k| (k, ()))
+                    .collect()
+            }
+        };
 
         // Make a new Abi with only the parameters that are present in the input file.
         for param in abi.parameters.iter() {
@@ -60,33 +85,18 @@ where
         Ok(res_abi)
     }
 
+    /// Flattens the encoded parameters of the partial ABI into a map from names to elements. Parameters with more than one element, i.e., arrays, structs, and tuples, are flattened in the order of the ABI encoding into `name[i]`. Every element is marked public or private according to the visibility of its parameter in the ABI.
     pub(crate) fn create_string_map(
-        original_abi: &Abi,
         partial_abi: &Abi,
         witness: WitnessMap<FieldElement>,
-        public_parameters: &PublicInputs,
     ) -> eyre::Result<BTreeMap<String, PublicMarker<FieldElement>>> {
         let mut res_map = BTreeMap::new();
         let mut wit_iter = witness.into_iter();
 
-        let mut orig_params = original_abi.parameters.iter();
-        let mut offset = 0;
-
         for param in partial_abi.parameters.iter() {
             let arg_name = &param.name;
             let typ_field_len = param.typ.field_count();
-
-            // Calculate real witness offset for the public parameter marker
-            loop {
-                let next = orig_params
-                    .next()
-                    .ok_or(eyre!("Corrupted Witness: Too few witnesses"))?;
-
-                if &next.name == arg_name {
-                    break;
-                }
-                offset += next.typ.field_count();
-            }
+            let is_public = param.visibility == AbiVisibility::Public;
 
             for i in 0..typ_field_len {
                 let name = if typ_field_len == 1 {
@@ -98,12 +108,11 @@ where
                 let (_, el) = wit_iter
                     .next()
                     .ok_or(eyre!("Corrupted Witness: Too few witnesses"))?;
-                if public_parameters.contains((offset) as usize) {
+                if is_public {
                     res_map.insert(name, PublicMarker::Public(el));
                 } else {
                     res_map.insert(name, PublicMarker::Private(el));
                 }
-                offset += 1;
             }
         }
         if wit_iter.next().is_some() {
@@ -1,5 +1,7 @@
+use crate::loops::{self, LoopRegion, SharedLoopPolicy};
 use crate::memory::Memory;
 use crate::mpc::BrilligDriver;
+use crate::policy::{BrilligExecution, BrilligPolicy, FunctionProfile};
 use acir::{
     acir_field::GenericFieldElement,
     circuit::brillig::{BrilligBytecode, BrilligFunctionId},
@@ -22,6 +24,15 @@ where
     pub(crate) shared_ctx: Option<T::BrilligType>,
     calldata: Vec<T::BrilligType>,
     unconstrained_functions: Vec<BrilligBytecode<GenericFieldElement<F>>>,
+    /// the loops of the unconstrained functions, see [`loops::find_loops`]
+    loops: Vec<Vec<LoopRegion>>,
+    shared_loop_policy: SharedLoopPolicy,
+    /// the amount of iterations on shared loop conditions in this universe
+    shared_loop_iterations: usize,
+    /// the profiles of the unconstrained functions, see [`FunctionProfile`]
+    profiles: Vec<FunctionProfile>,
+    /// the execution of the unconstrained functions, chosen by the [`BrilligPolicy`]
+    executions: Vec<BrilligExecution>,
     call_stack: Vec<usize>,
     ip: usize,
 }
@@ -63,9 +74,31 @@ where
         id: &BrilligFunctionId,
         calldata: Vec<T::BrilligType>,
     ) -> eyre::Result<CoBrilligResult<T, F>> {
-        self.calldata = calldata;
         self.ip = 0;
-        self.run_inner(id)
+        self.shared_loop_iterations = 0;
+        match self.execution(id) {
+            BrilligExecution::Mpc => {
+                self.calldata = calldata;
+                self.run_inner(id)
+            }
+            BrilligExecution::GarbledCircuit => {
+                self.calldata = calldata;
+                self.driver.set_garbled_circuits(true);
+                let result = self.run_inner(id);
+                self.driver.set_garbled_circuits(false);
+                result
+            }
+            BrilligExecution::Reveal => {
+                if !calldata.iter().cloned().all(T::is_public) {
+                    eyre::bail!(
+                        "unconstrained function {} is revealed, but its arguments are shared",
+                        id.as_usize()
+                    );
+                }
+                self.calldata = calldata;
+                self.run_inner(id)
+            }
+        }
     }
 
     fn run_inner(&mut self, id: &BrilligFunctionId) -> eyre::Result<CoBrilligResult<T, F>> {
@@ -175,9 +208,23 @@ where
         driver: T,
         unconstrained_functions: Vec<BrilligBytecode<GenericFieldElement<F>>>,
     ) -> Self {
+        let loops = unconstrained_functions
+            .iter()
+            .map(|function| loops::find_loops(&function.bytecode))
+            .collect();
+        let profiles = unconstrained_functions
+            .iter()
+            .map(|function| FunctionProfile::analyze(&function.bytecode))
+            .collect::<Vec<_>>();
+        let executions = vec![BrilligExecution::default(); profiles.len()];
         Self {
             driver,
             unconstrained_functions,
+            loops,
+            shared_loop_policy: SharedLoopPolicy::default(),
+            shared_loop_iterations: 0,
+            profiles,
+            executions,
             calldata: vec![],
             call_stack: vec![],
             memory: Memory::new(),
@@ -186,6 +233,37 @@ where
         }
     }
 
+    /// Sets the [SharedLoopPolicy] of the VM. Defaults to [`SharedLoopPolicy::Reject`].
+    pub fn set_shared_loop_policy(&mut self, policy: SharedLoopPolicy) {
+        self.shared_loop_policy = policy;
+    }
+
+    /// Chooses the [BrilligExecution] of every unconstrained function
+    /// with the provided [BrilligPolicy]. Defaults to [`BrilligExecution::Mpc`]
+    /// for all functions.
+    pub fn set_policy(&mut self, policy: &BrilligPolicy) -> eyre::Result<()> {
+        self.executions = policy.plan(&self.profiles)?;
+        Ok(())
+    }
+
+    /// Returns the digest of the [BrilligExecution]s of all unconstrained
+    /// functions. The parties compare it to make sure that they use the
+    /// same [BrilligPolicy].
+    pub fn plan_digest(&self) -> [u8; 32] {
+        crate::policy::plan_digest(&self.executions)
+    }
+
+    /// Returns the [BrilligExecution] of the unconstrained function
+    /// identified by the provided id.
+    pub fn execution(&self, id: &BrilligFunctionId) -> BrilligExecution {
+        self.executions[id.as_usize()]
+    }
+
+    /// Returns the profiles of the unconstrained functions, indexed by id.
+    pub fn profiles(&self) -> &[FunctionProfile] {
+        &self.profiles
+    }
+
     pub(crate) fn increment_program_counter(&mut self) {
         self.set_program_counter(self.ip + 1)
     }
@@ -249,21 +327,48 @@ where
     ) -> eyre::Result<Option<CoBrilligResult<T, F>>> {
         let condition = self.memory.read(address)?;
         match T::try_into_bool(condition) {
-            Ok(true) => {
-                self.set_program_counter(location);
-                Ok(None)
-            }
-            Ok(false) => {
-                self.increment_program_counter();
-                Ok(None)
-            }
+            Ok(condition) => self.handle_jump_if_public(condition, location),
             Err(condition) => {
-                if location <= self.ip {
+                let fallthrough = loops::fallthrough(
+                    &self.unconstrained_functions[id.as_usize()].bytecode,
+                    self.ip,
+                );
+                let shared_loop =
+                    loops::bounded_loop(&self.loops[id.as_usize()], self.ip, location, fallthrough);
+                // the universes that stay inside the loop after the jump
+                let mut padded = (false, false);
+                if let Some(region) = shared_loop {
+                    match self.shared_loop_policy {
+                        SharedLoopPolicy::Reject => eyre::bail!(
+                            "secret-dependent loop in unconstrained function {} at opcode {}: condition of {:?} is shared (loop spans {region}). Set a SharedLoopPolicy to execute it",
+                            id.as_usize(),
+                            self.ip,
+                            self.unconstrained_functions[id.as_usize()].bytecode[self.ip],
+                        ),
+                        SharedLoopPolicy::OpenWithConsent => {
+                            tracing::warn!(
+                                "opening secret-dependent loop condition in unconstrained function {} at opcode {} (loop spans {region})",
+                                id.as_usize(),
+                                self.ip
+                            );
+                            let condition = self.driver.open_bool(condition)?;
+                            return self.handle_jump_if_public(condition, location);
+                        }
+                        SharedLoopPolicy::BoundAndPad { max_iterations } => {
+                            if self.shared_loop_iterations >= max_iterations {
+                                tracing::debug!(
+                                    "reached maximum of {max_iterations} iterations on shared loop condition at opcode {} - pad the universes staying in the loop",
+                                    self.ip
+                                );
+                                padded = (region.contains(location), region.contains(fallthrough));
+                            }
+                            self.shared_loop_iterations += 1;
+                        }
+                    }
+                } else if location <= self.ip {
                     eyre::bail!("can only jump forward with shared if");
                 }
-                if self.shared_ctx.is_some() {
-                    eyre::bail!("we can only support one shared if atm");
-                }
+                // shared ifs inside another universe, e.g., after a padded loop, fork again with a nested context
                 tracing::debug!("encountered shared if - fork the universe!");
                 let (mut truthy, mut falsy) = self.fork_universe(condition.clone())?;
                 truthy.memory.write(address, T::public_true())?;
@@ -272,8 +377,16 @@ where
                 falsy.increment_program_counter();
 
                 // run both universes to the end
-                let truthy_result = truthy.run_inner(id)?;
-                let falsy_result = falsy.run_inner(id)?;
+                let truthy_result = if padded.0 {
+                    CoBrilligResult::Failed
+                } else {
+                    truthy.run_inner(id)?
+                };
+                let falsy_result = if padded.1 {
+                    CoBrilligResult::Failed
+                } else {
+                    falsy.run_inner(id)?
+                };
                 let (truthy_result, falsy_result) = match (truthy_result, falsy_result) {
                     (
                         CoBrilligResult::Success(truthy_result),
@@ -320,6 +433,19 @@ where
         }
     }
 
+    fn handle_jump_if_public(
+        &mut self,
+        condition: bool,
+        location: Label,
+    ) -> eyre::Result<Option<CoBrilligResult<T, F>>> {
+        if condition {
+            self.set_program_counter(location);
+        } else {
+            self.increment_program_counter();
+        }
+        Ok(None)
+    }
+
     fn handle_jump(&mut self, location: Label) -> eyre::Result<()> {
         self.set_program_counter(location);
         Ok(())
@@ -403,14 +529,30 @@ where
     fn fork_universe(&mut self, condition: T::BrilligType) -> eyre::Result<(Self, Self)> {
         let (driver1, driver2) = self.driver.fork()?;
         let (mem1, mem2) = self.memory.fork();
+        let negated_condition = self.driver.not(condition.clone())?;
+        // nested universes (only possible in shared loops) are active if the outer universe is active as well
+        let (truthy_ctx, falsy_ctx) = match self.shared_ctx.clone() {
+            Some(outer) => (
+                self.driver
+                    .cmux(outer.clone(), condition, T::public_false())?,
+                self.driver
+                    .cmux(outer, negated_condition, T::public_false())?,
+            ),
+            None => (condition, negated_condition),
+        };
         let truthy_universe = Self {
             memory: mem1,
             driver: driver1,
             calldata: self.calldata.clone(),
             unconstrained_functions: self.unconstrained_functions.clone(),
+            loops: self.loops.clone(),
+            shared_loop_policy: self.shared_loop_policy,
+            shared_loop_iterations: self.shared_loop_iterations,
+            profiles: self.profiles.clone(),
+            executions: self.executions.clone(),
             call_stack: self.call_stack.clone(),
             ip: self.ip,
-            shared_ctx: Some(condition.clone()),
+            shared_ctx: Some(truthy_ctx),
         };
 
         let falsy_universe = Self {
@@ -418,9 +560,14 @@ where
             driver: driver2,
             calldata: self.calldata.clone(),
             unconstrained_functions: self.unconstrained_functions.clone(),
+            loops: self.loops.clone(),
+            shared_loop_policy: self.shared_loop_policy,
+            shared_loop_iterations: self.shared_loop_iterations,
+            profiles: self.profiles.clone(),
+            executions: self.executions.clone(),
             call_stack: self.call_stack.clone(),
             ip: self.ip,
-            shared_ctx: Some(self.driver.not(condition)?),
+            shared_ctx: Some(falsy_ctx),
         };
         Ok((truthy_universe, falsy_universe))
     }
@@ -7,8 +7,12 @@ mod blackbox;
 mod brillig_vm;
 mod field_ops;
 mod int_ops;
+mod loops;
 pub(crate) mod memory;
 pub mod mpc;
+mod policy;
 
 pub use brillig_vm::CoBrilligResult;
 pub use brillig_vm::CoBrilligVM;
+pub use loops::SharedLoopPolicy;
+pub use policy::{BrilligExecution, BrilligPolicy, FunctionProfile};
@@ -0,0 +1,203 @@
+use std::fmt;
+
+use acir::acir_field::GenericFieldElement;
+use ark_ff::PrimeField;
+use brillig::Opcode as BrilligOpcode;
+
+/// Defines how the coBrillig-VM handles a conditional jump on a shared
+/// value that controls a loop.
+///
+/// Unconstrained functions may loop on secret values. As the VM can
+/// not decide on its own how often to execute the loop body, it
+/// either hangs or leaks the secret. The policy determines which
+/// trade-off the parties are willing to make. All parties MUST use
+/// the same policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SharedLoopPolicy {
+    /// Abort the execution with an error naming the offending opcode.
+    #[default]
+    Reject,
+    /// Execute both paths of the loop condition for at most
+    /// `max_iterations` iterations. Paths that are still inside the
+    /// loop after `max_iterations` iterations are padded with random
+    /// noise, i.e., the resulting proof does not verify if the loop
+    /// needs more iterations.
+    BoundAndPad {
+        /// The maximum amount of iterations on a shared condition
+        max_iterations: usize,
+    },
+    /// Open the loop condition and continue with the public value.
+    /// This leaks the condition (and therefore the amount of
+    /// iterations) to all parties. Only use this if all parties
+    /// consent to the leakage.
+    OpenWithConsent,
+}
+
+/// A loop in the bytecode of an unconstrained function, found by the
+/// static analysis in [`find_loops`]. The loop spans all opcodes from
+/// `start` to `end` (inclusive), where `end` is the opcode that jumps
+/// back to `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LoopRegion {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+impl LoopRegion {
+    pub(crate) fn contains(&self, ip: usize) -> bool {
+        (self.start..=self.end).contains(&ip)
+    }
+}
+
+impl fmt::Display for LoopRegion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "opcodes {}..={}", self.start, self.end)
+    }
+}
+
+/// Finds all loops in the provided bytecode. Every backward jump
+/// (conditional or not) closes a loop.
+pub(crate) fn find_loops<F: PrimeField>(
+    bytecode: &[BrilligOpcode<GenericFieldElement<F>>],
+) -> Vec<LoopRegion> {
+    bytecode
+        .iter()
+        .enumerate()
+        .filter_map(|(ip, opcode)| match opcode {
+            BrilligOpcode::Jump { location }
+            | BrilligOpcode::JumpIf { location, .. }
+            | BrilligOpcode::JumpIfNot { location, .. }
+                if *location <= ip =>
+            {
+                Some(LoopRegion {
+                    start: *location,
+                    end: ip,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the opcode the conditional jump at `ip` continues with if
+/// the condition is false. Noir emits loop headers as a conditional
+/// jump into the body followed by an unconditional jump out of the
+/// loop, so an unconditional jump directly after `ip` is followed.
+pub(crate) fn fallthrough<F: PrimeField>(
+    bytecode: &[BrilligOpcode<GenericFieldElement<F>>],
+    ip: usize,
+) -> usize {
+    match bytecode.get(ip + 1) {
+        Some(BrilligOpcode::Jump { location }) => *location,
+        _ => ip + 1,
+    }
+}
+
+/// Returns the innermost loop that is left by exactly one of the two
+/// branches of the conditional jump at `ip` to `location` with the
+/// provided [`fallthrough`], i.e., the loop whose bound depends on the
+/// condition of the jump.
+pub(crate) fn bounded_loop(
+    loops: &[LoopRegion],
+    ip: usize,
+    location: usize,
+    fallthrough: usize,
+) -> Option<LoopRegion> {
+    loops
+        .iter()
+        .filter(|region| region.contains(ip))
+        .filter(|region| region.contains(location) != region.contains(fallthrough))
+        .min_by_key(|region| region.end - region.start)
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use brillig::MemoryAddress;
+
+    type Opcode = BrilligOpcode<GenericFieldElement<ark_bn254::Fr>>;
+
+    fn jump_if(location: usize) -> Opcode {
+        BrilligOpcode::JumpIf {
+            condition: MemoryAddress::direct(0),
+            location,
+        }
+    }
+
+    fn mov() -> Opcode {
+        BrilligOpcode::Mov {
+            destination: MemoryAddress::direct(1),
+            source: MemoryAddress::direct(2),
+        }
+    }
+
+    // 0: mov
+    // 1: jump_if 3     <- header of the outer loop
+    // 2: jump 9
+    // 3: jump_if 5     <- header of the inner loop
+    // 4: jump 7
+    // 5: mov
+    // 6: jump 3
+    // 7: jump_if 1     <- conditional backward jump
+    // 8: jump 1
+    // 9: mov
+    fn nested_loops() -> Vec<Opcode> {
+        vec![
+            mov(),
+            jump_if(3),
+            BrilligOpcode::Jump { location: 9 },
+            jump_if(5),
+            BrilligOpcode::Jump { location: 7 },
+            mov(),
+            BrilligOpcode::Jump { location: 3 },
+            jump_if(1),
+            BrilligOpcode::Jump { location: 1 },
+            mov(),
+        ]
+    }
+
+    #[test]
+    fn find_loops_of_backward_jumps() {
+        let loops = find_loops(&nested_loops());
+        assert_eq!(
+            loops,
+            vec![
+                LoopRegion { start: 3, end: 6 },
+                LoopRegion { start: 1, end: 7 },
+                LoopRegion { start: 1, end: 8 },
+            ]
+        );
+        assert!(find_loops(&[mov(), jump_if(2), mov()]).is_empty());
+    }
+
+    #[test]
+    fn fallthrough_follows_unconditional_jump() {
+        let bytecode = nested_loops();
+        assert_eq!(fallthrough(&bytecode, 1), 9);
+        assert_eq!(fallthrough(&bytecode, 3), 7);
+        assert_eq!(fallthrough(&bytecode, 7), 1);
+        assert_eq!(fallthrough(&bytecode, 0), 1);
+        assert_eq!(fallthrough(&bytecode, 9), 10);
+    }
+
+    #[test]
+    fn bounded_loop_is_innermost_loop_left_by_one_branch() {
+        let bytecode = nested_loops();
+        let loops = find_loops(&bytecode);
+        // the inner loop is left if the condition at 3 is false
+        assert_eq!(
+            bounded_loop(&loops, 3, 5, fallthrough(&bytecode, 3)),
+            Some(LoopRegion { start: 3, end: 6 })
+        );
+        // the outer loops are left if the condition at 1 is false
+        assert_eq!(
+            bounded_loop(&loops, 1, 3, fallthrough(&bytecode, 1)),
+            Some(LoopRegion { start: 1, end: 7 })
+        );
+        // both branches of the jump at 7 stay in the loop spanning 1..=8
+        assert_eq!(bounded_loop(&loops, 7, 1, fallthrough(&bytecode, 7)), None);
+        // a forward jump outside of any loop is a plain shared if
+        assert_eq!(bounded_loop(&loops, 0, 9, 1), None);
+    }
+}
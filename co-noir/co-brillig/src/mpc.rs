@@ -52,6 +52,14 @@ pub trait BrilligDriver<F: PrimeField>: Sized {
     /// communication.
     fn fork(&mut self) -> eyre::Result<(Self, Self)>;
 
+    /// Enables or disables the evaluation of the conversions between
+    /// arithmetic and binary shares with garbled circuits, see
+    /// [`BrilligExecution::GarbledCircuit`](crate::BrilligExecution::GarbledCircuit).
+    /// Disabling restores the previous conversion protocol. The default
+    /// implementation does nothing, as not all protocols support
+    /// garbled circuits.
+    fn set_garbled_circuits(&mut self, _enabled: bool) {}
+
     /// Casts the provided value to the provided bit size. This includes upcasts
     /// and downcasts between integer types, but also between fields to integers
     /// and vice verca.
@@ -69,6 +77,10 @@ pub trait BrilligDriver<F: PrimeField>: Sized {
     /// again, if it is not possible (e.g., is a shared value).
     fn try_into_bool(val: Self::BrilligType) -> Result<bool, Self::BrilligType>;
 
+    /// Opens the provided `bool` value, i.e., reveals it to all parties
+    /// if it is shared. Returns an error if the value is not a `u1`.
+    fn open_bool(&mut self, val: Self::BrilligType) -> eyre::Result<bool>;
+
     /// Creates a new public value from the provided value. The type
     /// of the new value is determined by the provided `bit_size`.
     fn public_value(val: F, bit_size: BitSize) -> Self::BrilligType;
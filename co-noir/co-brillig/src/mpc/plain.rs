@@ -136,6 +136,10 @@ impl<F: PrimeField> BrilligDriver<F> for PlainBrilligDriver<F> {
         }
     }
 
+    fn open_bool(&mut self, val: Self::BrilligType) -> eyre::Result<bool> {
+        Self::try_into_bool(val).map_err(|val| eyre::eyre!("expected u1, but got {val:?}"))
+    }
+
     fn public_value(val: F, bit_size: BitSize) -> Self::BrilligType {
         match bit_size {
             BitSize::Field => PlainBrilligType::Field(val),
@@ -2,6 +2,7 @@ use super::{BrilligDriver, PlainBrilligDriver};
 use ark_ff::{One as _, PrimeField};
 use brillig::{BitSize, IntegerBitSize};
 use core::panic;
+use mpc_core::protocols::rep3::conversion::A2BType;
 use mpc_core::protocols::rep3::network::{IoContext, Rep3Network};
 use mpc_core::protocols::rep3::{self, Rep3PrimeFieldShare};
 use mpc_core::protocols::rep3_ring::ring::bit::Bit;
@@ -19,6 +20,8 @@ use super::PlainBrilligType as Public;
 pub struct Rep3BrilligDriver<F: PrimeField, N: Rep3Network> {
     io_context: IoContext<N>,
     plain_driver: PlainBrilligDriver<F>,
+    /// the conversion protocol before garbled circuits were enabled
+    a2b_type_before_gc: Option<A2BType>,
     phantom_data: PhantomData<F>,
 }
 
@@ -63,6 +66,7 @@ impl<F: PrimeField, N: Rep3Network> Rep3BrilligDriver<F, N> {
         Self {
             io_context,
             plain_driver: PlainBrilligDriver::default(),
+            a2b_type_before_gc: None,
             phantom_data: PhantomData,
         }
     }
@@ -192,16 +196,28 @@ impl<F: PrimeField, N: Rep3Network> BrilligDriver<F> for Rep3BrilligDriver<F, N>
         let fork1 = Self {
             io_context: network1,
             plain_driver: PlainBrilligDriver::default(),
+            a2b_type_before_gc: self.a2b_type_before_gc,
             phantom_data: PhantomData,
         };
         let fork2 = Self {
             io_context: network2,
             plain_driver: PlainBrilligDriver::default(),
+            a2b_type_before_gc: self.a2b_type_before_gc,
             phantom_data: PhantomData,
         };
         Ok((fork1, fork2))
     }
 
+    fn set_garbled_circuits(&mut self, enabled: bool) {
+        if enabled {
+            self.a2b_type_before_gc
+                .get_or_insert(self.io_context.a2b_type);
+            self.io_context.set_a2b_type(A2BType::Yao);
+        } else if let Some(a2b_type) = self.a2b_type_before_gc.take() {
+            self.io_context.set_a2b_type(a2b_type);
+        }
+    }
+
     fn cast(
         &mut self,
         val: Self::BrilligType,
@@ -332,40 +348,57 @@ impl<F: PrimeField, N: Rep3Network> BrilligDriver<F> for Rep3BrilligDriver<F, N>
         }
     }
 
+    fn open_bool(&mut self, val: Self::BrilligType) -> eyre::Result<bool> {
+        match val {
+            Rep3BrilligType::Public(public) => self.plain_driver.open_bool(public),
+            Rep3BrilligType::Shared(Shared::Ring1(share)) => {
+                let opened = rep3_ring::binary::open(&share, &mut self.io_context)?;
+                Ok(opened.0.convert())
+            }
+            x => eyre::bail!("expected u1, but got {x:?}"),
+        }
+    }
+
     fn public_value(val: F, bit_size: BitSize) -> Self::BrilligType {
         Rep3BrilligType::Public(PlainBrilligDriver::public_value(val, bit_size))
     }
 
     fn random(&mut self, other: &Self::BrilligType) -> Self::BrilligType {
+        // the noise is always shared, as the parties would otherwise sample
+        // different public values
         match other {
-            Rep3BrilligType::Public(other) => {
-                Rep3BrilligType::Public(self.plain_driver.random(other))
-            }
-            Rep3BrilligType::Shared(Shared::Field(_)) => {
+            Rep3BrilligType::Public(Public::Field(_))
+            | Rep3BrilligType::Shared(Shared::Field(_)) => {
                 let (a, b) = self.io_context.random_fes();
                 Rep3BrilligType::shared_field(Rep3PrimeFieldShare::new(a, b))
             }
-            Rep3BrilligType::Shared(Shared::Ring128(_)) => {
+            Rep3BrilligType::Public(Public::Int(_, IntegerBitSize::U128))
+            | Rep3BrilligType::Shared(Shared::Ring128(_)) => {
                 let (a, b) = self.io_context.random_elements();
                 Rep3BrilligType::shared_u128(Rep3RingShare::new(a, b))
             }
-            Rep3BrilligType::Shared(Shared::Ring64(_)) => {
+            Rep3BrilligType::Public(Public::Int(_, IntegerBitSize::U64))
+            | Rep3BrilligType::Shared(Shared::Ring64(_)) => {
                 let (a, b) = self.io_context.random_elements();
                 Rep3BrilligType::shared_u64(Rep3RingShare::new(a, b))
             }
-            Rep3BrilligType::Shared(Shared::Ring32(_)) => {
+            Rep3BrilligType::Public(Public::Int(_, IntegerBitSize::U32))
+            | Rep3BrilligType::Shared(Shared::Ring32(_)) => {
                 let (a, b) = self.io_context.random_elements();
                 Rep3BrilligType::shared_u32(Rep3RingShare::new(a, b))
             }
-            Rep3BrilligType::Shared(Shared::Ring16(_)) => {
+            Rep3BrilligType::Public(Public::Int(_, IntegerBitSize::U16))
+            | Rep3BrilligType::Shared(Shared::Ring16(_)) => {
                 let (a, b) = self.io_context.random_elements();
                 Rep3BrilligType::shared_u16(Rep3RingShare::new(a, b))
             }
-            Rep3BrilligType::Shared(Shared::Ring8(_)) => {
+            Rep3BrilligType::Public(Public::Int(_, IntegerBitSize::U8))
+            | Rep3BrilligType::Shared(Shared::Ring8(_)) => {
                 let (a, b) = self.io_context.random_elements();
                 Rep3BrilligType::shared_u8(Rep3RingShare::new(a, b))
             }
-            Rep3BrilligType::Shared(Shared::Ring1(_)) => {
+            Rep3BrilligType::Public(Public::Int(_, IntegerBitSize::U1))
+            | Rep3BrilligType::Shared(Shared::Ring1(_)) => {
                 let (a, b) = self.io_context.random_elements();
                 Rep3BrilligType::shared_u1(Rep3RingShare::new(a, b))
             }
@@ -733,7 +766,12 @@ impl<F: PrimeField, N: Rep3Network> BrilligDriver<F> for Rep3BrilligDriver<F, N>
                             )?;
                             Rep3BrilligType::shared_u128(divided)
                         } else {
-                            todo!("Implement division for shared/public with divisor not being a power-of-2")
+                            let divided = rep3_ring::yao::ring_div_public(
+                                shared,
+                                &mut self.io_context,
+                                RingElement(public),
+                            )?;
+                            Rep3BrilligType::shared_u128(divided)
                         }
                     }
                     (Public::Int(public, IntegerBitSize::U64), Shared::Ring64(shared)) => {
@@ -746,7 +784,12 @@ impl<F: PrimeField, N: Rep3Network> BrilligDriver<F> for Rep3BrilligDriver<F, N>
                             )?;
                             Rep3BrilligType::shared_u64(divided)
                         } else {
-                            todo!("Implement division for shared/public with divisor not being a power-of-2")
+                            let divided = rep3_ring::yao::ring_div_public(
+                                shared,
+                                &mut self.io_context,
+                                RingElement(u64::try_from(public).expect("must be u64")),
+                            )?;
+                            Rep3BrilligType::shared_u64(divided)
                         }
                     }
                     (Public::Int(public, IntegerBitSize::U32), Shared::Ring32(shared)) => {
@@ -759,7 +802,12 @@ impl<F: PrimeField, N: Rep3Network> BrilligDriver<F> for Rep3BrilligDriver<F, N>
                             )?;
                             Rep3BrilligType::shared_u32(divided)
                         } else {
-                            todo!("Implement division for shared/public with divisor not being a power-of-2")
+                            let divided = rep3_ring::yao::ring_div_public(
+                                shared,
+                                &mut self.io_context,
+                                RingElement(u32::try_from(public).expect("must be u32")),
+                            )?;
+                            Rep3BrilligType::shared_u32(divided)
                         }
                     }
                     (Public::Int(public, IntegerBitSize::U16), Shared::Ring16(shared)) => {
@@ -772,7 +820,12 @@ impl<F: PrimeField, N: Rep3Network> BrilligDriver<F> for Rep3BrilligDriver<F, N>
                             )?;
                             Rep3BrilligType::shared_u16(divided)
                         } else {
-                            todo!("Implement division for shared/public with divisor not being a power-of-2")
+                            let divided = rep3_ring::yao::ring_div_public(
+                                shared,
+                                &mut self.io_context,
+                                RingElement(u16::try_from(public).expect("must be u16")),
+                            )?;
+                            Rep3BrilligType::shared_u16(divided)
                         }
                     }
                     (Public::Int(public, IntegerBitSize::U8), Shared::Ring8(shared)) => {
@@ -785,7 +838,12 @@ impl<F: PrimeField, N: Rep3Network> BrilligDriver<F> for Rep3BrilligDriver<F, N>
                             )?;
                             Rep3BrilligType::shared_u8(divided)
                         } else {
-                            todo!("Implement division for shared/public with divisor not being a power-of-2")
+                            let divided = rep3_ring::yao::ring_div_public(
+                                shared,
+                                &mut self.io_context,
+                                RingElement(u8::try_from(public).expect("must be u8")),
+                            )?;
+                            Rep3BrilligType::shared_u8(divided)
                         }
                     }
                     (Public::Int(public, IntegerBitSize::U1), Shared::Ring1(shared)) => {
@@ -798,7 +856,12 @@ impl<F: PrimeField, N: Rep3Network> BrilligDriver<F> for Rep3BrilligDriver<F, N>
                             )?;
                             Rep3BrilligType::shared_u1(divided)
                         } else {
-                            todo!("Implement division for shared/public with divisor not being a power-of-2")
+                            let divided = rep3_ring::yao::ring_div_public(
+                                shared,
+                                &mut self.io_context,
+                                bit_from_u128!(public),
+                            )?;
+                            Rep3BrilligType::shared_u1(divided)
                         }
                     }
                     _ => todo!("Implement division for shared/public"),
@@ -843,7 +906,9 @@ impl<F: PrimeField, N: Rep3Network> BrilligDriver<F> for Rep3BrilligDriver<F, N>
                         )?;
                         Rep3BrilligType::shared_field(divided)
                     } else {
-                        todo!("Implement IntDiv for shared/public with divisor not being a power-of-2")
+                        let divided =
+                            rep3::yao::field_int_div_public(shared, &mut self.io_context, public)?;
+                        Rep3BrilligType::shared_field(divided)
                     }
                 } else {
                     eyre::bail!("IntDiv only supported on fields")
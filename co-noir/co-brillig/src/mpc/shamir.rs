@@ -1,5 +1,5 @@
 use super::{BrilligDriver, PlainBrilligDriver};
-use ark_ff::{One, PrimeField};
+use ark_ff::{One, PrimeField, Zero};
 use brillig::{BitSize, IntegerBitSize};
 use mpc_core::protocols::shamir::{
     self, network::ShamirNetwork, ShamirPrimeFieldShare, ShamirProtocol,
@@ -100,6 +100,17 @@ impl<F: PrimeField, N: ShamirNetwork> BrilligDriver<F> for ShamirBrilligDriver<F
         }
     }
 
+    fn open_bool(&mut self, val: Self::BrilligType) -> eyre::Result<bool> {
+        match val {
+            ShamirBrilligType::Public(public) => self.plain_driver.open_bool(public),
+            // we only support prime fields, the shared value is either zero or one
+            ShamirBrilligType::Shared(share) => {
+                let opened = shamir::arithmetic::open(share, &mut self.protocol)?;
+                Ok(!opened.is_zero())
+            }
+        }
+    }
+
     fn public_value(val: F, bit_size: BitSize) -> Self::BrilligType {
         ShamirBrilligType::Public(PlainBrilligDriver::public_value(val, bit_size))
     }
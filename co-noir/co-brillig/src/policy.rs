@@ -0,0 +1,217 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use acir::acir_field::GenericFieldElement;
+use ark_ff::PrimeField;
+use brillig::{BinaryFieldOp, BinaryIntOp, BlackBoxOp, Opcode as BrilligOpcode};
+use serde::{Deserialize, Deserializer};
+use sha3::{Digest, Sha3_256};
+
+/// Defines how the coBrillig-VM executes an unconstrained function.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BrilligExecution {
+    /// Execute the function in MPC with the conversion protocol
+    /// configured for the rest of the program.
+    #[default]
+    Mpc,
+    /// Execute the function in MPC, but evaluate all conversions
+    /// between arithmetic and binary shares with garbled circuits.
+    /// This trades communicated bytes for a low number of
+    /// communication rounds, which pays off for functions dominated
+    /// by comparisons, bit operations and casts.
+    GarbledCircuit,
+    /// Open the arguments of the function and execute it on public
+    /// values. This leaks the arguments (and therefore the results)
+    /// to all parties.
+    Reveal,
+}
+
+impl BrilligExecution {
+    fn to_byte(self) -> u8 {
+        match self {
+            BrilligExecution::Mpc => 0,
+            BrilligExecution::GarbledCircuit => 1,
+            BrilligExecution::Reveal => 2,
+        }
+    }
+}
+
+/// Returns the digest of the executions chosen for the unconstrained
+/// functions, which all parties compare before solving, see
+/// [BrilligPolicy].
+pub(crate) fn plan_digest(executions: &[BrilligExecution]) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update((executions.len() as u64).to_le_bytes());
+    hasher.update(executions.iter().map(|e| e.to_byte()).collect::<Vec<_>>());
+    hasher.finalize().into()
+}
+
+/// The profile of an unconstrained function, found by a static
+/// analysis of its bytecode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FunctionProfile {
+    /// The amount of opcodes of the function
+    pub opcodes: usize,
+    /// The amount of opcodes that operate on the bits of their inputs
+    /// (comparisons, integer divisions, bitwise operations, shifts,
+    /// casts and radix decompositions). In MPC, these require
+    /// conversions to binary shares.
+    pub bit_opcodes: usize,
+}
+
+impl FunctionProfile {
+    pub(crate) fn analyze<F: PrimeField>(
+        bytecode: &[BrilligOpcode<GenericFieldElement<F>>],
+    ) -> Self {
+        let bit_opcodes = bytecode
+            .iter()
+            .filter(|opcode| match opcode {
+                BrilligOpcode::BinaryFieldOp { op, .. } => matches!(
+                    op,
+                    BinaryFieldOp::IntegerDiv
+                        | BinaryFieldOp::LessThan
+                        | BinaryFieldOp::LessThanEquals
+                ),
+                BrilligOpcode::BinaryIntOp { op, .. } => !matches!(
+                    op,
+                    BinaryIntOp::Add | BinaryIntOp::Sub | BinaryIntOp::Mul | BinaryIntOp::Equals
+                ),
+                BrilligOpcode::Not { .. } | BrilligOpcode::Cast { .. } => true,
+                BrilligOpcode::BlackBox(op) => matches!(op, BlackBoxOp::ToRadix { .. }),
+                _ => false,
+            })
+            .count();
+        Self {
+            opcodes: bytecode.len(),
+            bit_opcodes,
+        }
+    }
+
+    /// Returns the share of opcodes that operate on bits.
+    pub fn bit_ratio(&self) -> f64 {
+        if self.opcodes == 0 {
+            0.0
+        } else {
+            self.bit_opcodes as f64 / self.opcodes as f64
+        }
+    }
+}
+
+/// Defines how the coBrillig-VM executes the unconstrained functions
+/// of a program, see [BrilligExecution].
+///
+/// Unconstrained functions with many comparisons and bit operations
+/// are disproportionately slow in MPC. The policy declares which
+/// performance trade-offs the parties are willing to make per
+/// function. Functions without a fixed execution in `functions` are
+/// revealed if they are `revealable` and the parties gave their
+/// consent with [BrilligPolicy::with_reveal_consent], executed with
+/// garbled circuits if `garbled_circuits` is set and their share of
+/// bit operations reaches the `garbled_circuit_threshold`, and
+/// executed in MPC otherwise. All parties MUST use the same policy,
+/// which is checked by comparing the digests of the chosen executions
+/// before solving, such that no arguments are opened if the parties
+/// disagree.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BrilligPolicy {
+    /// Whether functions may be executed with garbled circuits
+    pub garbled_circuits: bool,
+    /// The minimal share of bit operations (see [FunctionProfile::bit_ratio])
+    /// for which a function is executed with garbled circuits
+    pub garbled_circuit_threshold: f64,
+    /// The ids of the functions whose arguments are not sensitive and may be revealed
+    pub revealable: BTreeSet<usize>,
+    /// The fixed executions of functions by id, overriding the thresholds
+    #[serde(deserialize_with = "deserialize_function_ids")]
+    pub functions: BTreeMap<usize, BrilligExecution>,
+    #[serde(skip)]
+    reveal_consent: bool,
+}
+
+/// TOML tables only have string keys, so we parse the function ids ourselves.
+fn deserialize_function_ids<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<usize, BrilligExecution>, D::Error> {
+    BTreeMap::<String, BrilligExecution>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(id, execution)| {
+            id.parse()
+                .map(|id| (id, execution))
+                .map_err(|_| serde::de::Error::custom(format!("invalid function id {id}")))
+        })
+        .collect()
+}
+
+impl Default for BrilligPolicy {
+    fn default() -> Self {
+        Self {
+            garbled_circuits: false,
+            garbled_circuit_threshold: 0.25,
+            revealable: BTreeSet::new(),
+            functions: BTreeMap::new(),
+            reveal_consent: false,
+        }
+    }
+}
+
+impl BrilligPolicy {
+    /// Records the consent of the parties to reveal the arguments of
+    /// the functions the policy declares revealable. Without consent,
+    /// no function is revealed.
+    pub fn with_reveal_consent(mut self, consent: bool) -> Self {
+        self.reveal_consent = consent;
+        self
+    }
+
+    /// Chooses the execution of every function with the provided profiles.
+    ///
+    /// Returns an error if the policy references unknown functions or
+    /// fixes a function to [`BrilligExecution::Reveal`] that is not
+    /// revealable or without consent.
+    pub fn plan(&self, profiles: &[FunctionProfile]) -> eyre::Result<Vec<BrilligExecution>> {
+        if !(0.0..=1.0).contains(&self.garbled_circuit_threshold) {
+            eyre::bail!(
+                "garbled circuit threshold must be between 0 and 1, but is {}",
+                self.garbled_circuit_threshold
+            );
+        }
+        if let Some(id) = self
+            .revealable
+            .iter()
+            .chain(self.functions.keys())
+            .find(|id| **id >= profiles.len())
+        {
+            eyre::bail!(
+                "Brillig policy references unconstrained function {id}, but the program only has {}",
+                profiles.len()
+            );
+        }
+        let may_reveal = |id| self.reveal_consent && self.revealable.contains(&id);
+        profiles
+            .iter()
+            .enumerate()
+            .map(|(id, profile)| {
+                let execution = match self.functions.get(&id) {
+                    Some(BrilligExecution::Reveal) if !may_reveal(id) => eyre::bail!(
+                        "unconstrained function {id} may only be revealed if it is revealable and all parties consent"
+                    ),
+                    Some(execution) => *execution,
+                    None if may_reveal(id) => BrilligExecution::Reveal,
+                    None if self.garbled_circuits
+                        && profile.bit_ratio() >= self.garbled_circuit_threshold =>
+                    {
+                        BrilligExecution::GarbledCircuit
+                    }
+                    None => BrilligExecution::Mpc,
+                };
+                tracing::debug!(
+                    "unconstrained function {id} ({} opcodes, {} on bits): {execution:?}",
+                    profile.opcodes,
+                    profile.bit_opcodes
+                );
+                Ok(execution)
+            })
+            .collect()
+    }
+}
@@ -1,7 +1,10 @@
 use acir::{
     acir_field::GenericFieldElement,
     circuit::{
-        opcodes::{BlackBoxFuncCall, MemOp},
+        opcodes::{
+            BlackBoxFuncCall, BlockType as AcirBlockType, ConstantOrWitnessEnum, FunctionInput,
+            MemOp,
+        },
         Circuit,
     },
     native_types::{Expression, Witness, WitnessMap},
@@ -11,8 +14,9 @@ use ark_ff::{PrimeField, Zero};
 use std::collections::{BTreeMap, HashMap, HashSet};
 
 use crate::types::types::{
-    AcirFormatOriginalOpcodeIndices, BlockConstraint, BlockType, MulQuad, PolyTriple,
-    RangeConstraint, RecursionConstraint,
+    AcirFormatOriginalOpcodeIndices, BigIntFromLeBytes, BigIntOperation, BigIntOperationType,
+    BigIntToLeBytes, BlockConstraint, BlockType, EcAdd, MulQuad, MultiScalarMul, PolyTriple,
+    RangeConstraint, RecursionConstraint, WitnessOrConstant,
 };
 
 #[derive(Default)]
@@ -42,15 +46,15 @@ pub struct AcirFormat<F: PrimeField> {
     //  std::vector<PedersenConstraint> pedersen_constraints;
     //  std::vector<PedersenHashConstraint> pedersen_hash_constraints;
     //  std::vector<Poseidon2Constraint> poseidon2_constraints;
-    //  std::vector<MultiScalarMul> multi_scalar_mul_constraints;
-    //  std::vector<EcAdd> ec_add_constraints;
+    pub(crate) multi_scalar_mul_constraints: Vec<MultiScalarMul<F>>,
+    pub(crate) ec_add_constraints: Vec<EcAdd<F>>,
     pub(crate) recursion_constraints: Vec<RecursionConstraint>,
     pub(crate) honk_recursion_constraints: Vec<RecursionConstraint>,
     pub(crate) avm_recursion_constraints: Vec<RecursionConstraint>,
     //  std::vector<RecursionConstraint> ivc_recursion_constraints;
-    //  std::vector<BigIntFromLeBytes> bigint_from_le_bytes_constraints;
-    //  std::vector<BigIntToLeBytes> bigint_to_le_bytes_constraints;
-    //  std::vector<BigIntOperation> bigint_operations;
+    pub(crate) bigint_from_le_bytes_constraints: Vec<BigIntFromLeBytes<F>>,
+    pub(crate) bigint_to_le_bytes_constraints: Vec<BigIntToLeBytes>,
+    pub(crate) bigint_operations: Vec<BigIntOperation>,
     pub(crate) assert_equalities: Vec<PolyTriple<F>>,
 
     /// A standard plonk arithmetic constraint, as defined in the poly_triple struct, consists of selector values
@@ -92,6 +96,11 @@ impl<F: PrimeField> AcirFormat<F> {
         wv
     }
 
+    /// Returns the number of variables of the circuit, which is an upper bound for the length of a witness vector.
+    pub fn num_variables(&self) -> usize {
+        self.varnum as usize
+    }
+
     #[expect(clippy::field_reassign_with_default)]
     pub fn circuit_serde_to_acir_format(
         circuit: Circuit<GenericFieldElement<F>>,
@@ -138,9 +147,9 @@ impl<F: PrimeField> AcirFormat<F> {
                 acir::circuit::Opcode::MemoryInit {
                     block_id,
                     init,
-                    block_type: _,
+                    block_type,
                 } => {
-                    let block = Self::handle_memory_init(init);
+                    let block = Self::handle_memory_init(init, &block_type);
                     let block_id = block_id.0;
                     let opcode_indices = vec![i];
                     block_id_to_block_constraint.insert(block_id, (block, opcode_indices));
@@ -150,11 +159,10 @@ impl<F: PrimeField> AcirFormat<F> {
         }
 
         for (_, (block_constraint, opcode_indices)) in block_id_to_block_constraint {
-            // Note: the trace will always be empty for ReturnData since it cannot be explicitly read from in noir
-            if !block_constraint.trace.is_empty()
-                || block_constraint.type_ == BlockType::ReturnData
-                || block_constraint.type_ == BlockType::CallData
-            {
+            // Note: the trace will always be empty for ReturnData since it cannot be explicitly read from in noir.
+            // The Ultra builder treats the databus as normal arrays (see `handle_memory_init`), so unread databus
+            // blocks do not need any constraints, just like unread arrays.
+            if !block_constraint.trace.is_empty() {
                 af.block_constraints.push(block_constraint);
                 af.original_opcode_indices
                     .block_constraints
@@ -533,7 +541,10 @@ impl<F: PrimeField> AcirFormat<F> {
         }
     }
 
-    fn handle_memory_init(mem_init: Vec<Witness>) -> BlockConstraint<F> {
+    fn handle_memory_init(
+        mem_init: Vec<Witness>,
+        block_type: &AcirBlockType,
+    ) -> BlockConstraint<F> {
         let mut block = BlockConstraint::default();
         block.init.reserve(mem_init.len());
 
@@ -551,8 +562,16 @@ impl<F: PrimeField> AcirFormat<F> {
         }
 
         // Databus is only supported for Goblin, non Goblin builders will treat call_data and return_data as normal
-        // array.
-        block.type_ = BlockType::ReturnData;
+        // array. We keep the type of the block nevertheless, so that writes to the databus are rejected, and the
+        // builder processes call_data and return_data as ROM.
+        match block_type {
+            AcirBlockType::Memory => block.type_ = BlockType::ROM,
+            AcirBlockType::CallData(id) => {
+                block.type_ = BlockType::CallData;
+                block.calldata = *id;
+            }
+            AcirBlockType::ReturnData => block.type_ = BlockType::ReturnData,
+        }
         block
     }
 
@@ -579,6 +598,17 @@ impl<F: PrimeField> AcirFormat<F> {
         block.trace.push(acir_mem_op);
     }
 
+    fn parse_input(input: FunctionInput<GenericFieldElement<F>>) -> WitnessOrConstant<F> {
+        match input.input() {
+            ConstantOrWitnessEnum::Witness(witness) => {
+                WitnessOrConstant::Witness(witness.witness_index())
+            }
+            ConstantOrWitnessEnum::Constant(constant) => {
+                WitnessOrConstant::Constant(constant.into_repr())
+            }
+        }
+    }
+
     fn handle_blackbox_func_call(
         arg: BlackBoxFuncCall<GenericFieldElement<F>>,
         af: &mut AcirFormat<F>,
@@ -650,58 +680,117 @@ impl<F: PrimeField> AcirFormat<F> {
                 output: _,
             } => todo!("BlackBoxFuncCall::EcdsaSecp256r1"),
             BlackBoxFuncCall::MultiScalarMul {
-                points: _,
-                scalars: _,
-                outputs: _,
-            } => todo!(),
+                points,
+                scalars,
+                outputs,
+            } => {
+                af.multi_scalar_mul_constraints.push(MultiScalarMul {
+                    points: points.into_iter().map(Self::parse_input).collect(),
+                    scalars: scalars.into_iter().map(Self::parse_input).collect(),
+                    out_point_x: outputs.0.witness_index(),
+                    out_point_y: outputs.1.witness_index(),
+                    out_point_is_infinite: outputs.2.witness_index(),
+                });
+                af.original_opcode_indices
+                    .multi_scalar_mul_constraints
+                    .push(opcode_index);
+            }
             BlackBoxFuncCall::EmbeddedCurveAdd {
-                input1: _,
-                input2: _,
-                outputs: _,
-            } => todo!("BlackBoxFuncCall::EmbeddedCurveAdd"),
+                input1,
+                input2,
+                outputs,
+            } => {
+                af.ec_add_constraints.push(EcAdd {
+                    input1_x: Self::parse_input(input1[0]),
+                    input1_y: Self::parse_input(input1[1]),
+                    input1_infinite: Self::parse_input(input1[2]),
+                    input2_x: Self::parse_input(input2[0]),
+                    input2_y: Self::parse_input(input2[1]),
+                    input2_infinite: Self::parse_input(input2[2]),
+                    result_x: outputs.0.witness_index(),
+                    result_y: outputs.1.witness_index(),
+                    result_infinite: outputs.2.witness_index(),
+                });
+                af.original_opcode_indices
+                    .ec_add_constraints
+                    .push(opcode_index);
+            }
             BlackBoxFuncCall::Keccakf1600 {
                 inputs: _,
                 outputs: _,
             } => {
                 todo!("BlackBoxFuncCall::Keccakf1600")
             }
-            BlackBoxFuncCall::BigIntAdd {
-                lhs: _,
-                rhs: _,
-                output: _,
-            } => {
-                todo!("BlackBoxFuncCall::BigIntAdd")
+            BlackBoxFuncCall::BigIntAdd { lhs, rhs, output } => {
+                af.bigint_operations.push(BigIntOperation {
+                    lhs,
+                    rhs,
+                    result: output,
+                    opcode: BigIntOperationType::Add,
+                });
+                af.original_opcode_indices
+                    .bigint_operations
+                    .push(opcode_index);
             }
-            BlackBoxFuncCall::BigIntSub {
-                lhs: _,
-                rhs: _,
-                output: _,
-            } => {
-                todo!("BlackBoxFuncCall::BigIntSub")
+            BlackBoxFuncCall::BigIntSub { lhs, rhs, output } => {
+                af.bigint_operations.push(BigIntOperation {
+                    lhs,
+                    rhs,
+                    result: output,
+                    opcode: BigIntOperationType::Sub,
+                });
+                af.original_opcode_indices
+                    .bigint_operations
+                    .push(opcode_index);
             }
-            BlackBoxFuncCall::BigIntMul {
-                lhs: _,
-                rhs: _,
-                output: _,
-            } => {
-                todo!("BlackBoxFuncCall::BigIntMul")
+            BlackBoxFuncCall::BigIntMul { lhs, rhs, output } => {
+                af.bigint_operations.push(BigIntOperation {
+                    lhs,
+                    rhs,
+                    result: output,
+                    opcode: BigIntOperationType::Mul,
+                });
+                af.original_opcode_indices
+                    .bigint_operations
+                    .push(opcode_index);
             }
-            BlackBoxFuncCall::BigIntDiv {
-                lhs: _,
-                rhs: _,
-                output: _,
-            } => {
-                todo!("BlackBoxFuncCall::BigIntDiv")
+            BlackBoxFuncCall::BigIntDiv { lhs, rhs, output } => {
+                af.bigint_operations.push(BigIntOperation {
+                    lhs,
+                    rhs,
+                    result: output,
+                    opcode: BigIntOperationType::Div,
+                });
+                af.original_opcode_indices
+                    .bigint_operations
+                    .push(opcode_index);
             }
             BlackBoxFuncCall::BigIntFromLeBytes {
-                inputs: _,
-                modulus: _,
-                output: _,
-            } => todo!("BlackBoxFuncCall::BigIntFromLeBytes"),
-            BlackBoxFuncCall::BigIntToLeBytes {
-                input: _,
-                outputs: _,
-            } => todo!(),
+                inputs,
+                modulus,
+                output,
+            } => {
+                af.bigint_from_le_bytes_constraints.push(BigIntFromLeBytes {
+                    inputs: inputs.into_iter().map(Self::parse_input).collect(),
+                    modulus: modulus.into_iter().map(u32::from).collect(),
+                    result: output,
+                });
+                af.original_opcode_indices
+                    .bigint_from_le_bytes_constraints
+                    .push(opcode_index);
+            }
+            BlackBoxFuncCall::BigIntToLeBytes { input, outputs } => {
+                af.bigint_to_le_bytes_constraints.push(BigIntToLeBytes {
+                    input,
+                    result: outputs
+                        .iter()
+                        .map(|output| output.witness_index())
+                        .collect(),
+                });
+                af.original_opcode_indices
+                    .bigint_to_le_bytes_constraints
+                    .push(opcode_index);
+            }
             BlackBoxFuncCall::Poseidon2Permutation {
                 inputs: _,
                 outputs: _,
@@ -722,3 +811,133 @@ impl<F: PrimeField> AcirFormat<F> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use acir::{
+        circuit::{
+            opcodes::{BlockId, BlockType as AcirBlockType, MemOp},
+            Circuit, Opcode,
+        },
+        native_types::Witness,
+        FieldElement,
+    };
+
+    use super::AcirFormat;
+    use crate::types::types::BlockType;
+
+    const BLOCK: BlockId = BlockId(0);
+    const OTHER_BLOCK: BlockId = BlockId(1);
+
+    fn init(
+        block_id: BlockId,
+        witnesses: &[u32],
+        block_type: AcirBlockType,
+    ) -> Opcode<FieldElement> {
+        Opcode::MemoryInit {
+            block_id,
+            init: witnesses.iter().map(|w| Witness(*w)).collect(),
+            block_type,
+        }
+    }
+
+    fn read(block_id: BlockId, index: u32, value: u32) -> Opcode<FieldElement> {
+        Opcode::MemoryOp {
+            block_id,
+            op: MemOp::read_at_mem_index(Witness(index).into(), Witness(value)),
+            predicate: None,
+        }
+    }
+
+    fn write(block_id: BlockId, index: u32, value: u32) -> Opcode<FieldElement> {
+        Opcode::MemoryOp {
+            block_id,
+            op: MemOp::write_to_mem_index(Witness(index).into(), Witness(value).into()),
+            predicate: None,
+        }
+    }
+
+    fn acir_format(opcodes: Vec<Opcode<FieldElement>>) -> AcirFormat<ark_bn254::Fr> {
+        let circuit = Circuit {
+            current_witness_index: 5,
+            opcodes,
+            ..Default::default()
+        };
+        AcirFormat::circuit_serde_to_acir_format(circuit, false)
+    }
+
+    #[test]
+    fn calldata_keeps_its_type_and_id() {
+        let af = acir_format(vec![
+            init(BLOCK, &[0, 1, 2], AcirBlockType::CallData(1)),
+            read(BLOCK, 3, 4),
+        ]);
+        assert_eq!(af.block_constraints.len(), 1);
+        let block = &af.block_constraints[0];
+        assert!(block.type_ == BlockType::CallData);
+        assert_eq!(block.calldata, 1);
+        assert_eq!(block.init.len(), 3);
+        assert_eq!(block.trace.len(), 1);
+        assert_eq!(block.trace[0].access_type, 0);
+        assert_eq!(
+            af.original_opcode_indices.block_constraints,
+            vec![vec![0, 1]]
+        );
+    }
+
+    #[test]
+    fn unread_databus_has_no_block_constraints() {
+        let af = acir_format(vec![
+            init(BLOCK, &[0, 1], AcirBlockType::CallData(0)),
+            init(OTHER_BLOCK, &[2], AcirBlockType::ReturnData),
+        ]);
+        assert!(af.block_constraints.is_empty());
+        assert!(af.original_opcode_indices.block_constraints.is_empty());
+    }
+
+    #[test]
+    fn only_read_databus_blocks_are_constrained() {
+        let af = acir_format(vec![
+            init(BLOCK, &[0, 1], AcirBlockType::CallData(0)),
+            init(OTHER_BLOCK, &[2], AcirBlockType::ReturnData),
+            read(BLOCK, 3, 4),
+        ]);
+        assert_eq!(af.block_constraints.len(), 1);
+        assert!(af.block_constraints[0].type_ == BlockType::CallData);
+    }
+
+    #[test]
+    fn memory_is_rom_until_written() {
+        let af = acir_format(vec![
+            init(BLOCK, &[0, 1], AcirBlockType::Memory),
+            read(BLOCK, 3, 4),
+        ]);
+        assert!(af.block_constraints[0].type_ == BlockType::ROM);
+
+        let af = acir_format(vec![
+            init(BLOCK, &[0, 1], AcirBlockType::Memory),
+            read(BLOCK, 3, 4),
+            write(BLOCK, 3, 2),
+        ]);
+        assert!(af.block_constraints[0].type_ == BlockType::RAM);
+        assert_eq!(af.block_constraints[0].trace[1].access_type, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn write_to_calldata() {
+        acir_format(vec![
+            init(BLOCK, &[0, 1], AcirBlockType::CallData(0)),
+            write(BLOCK, 3, 2),
+        ]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn write_to_return_data() {
+        acir_format(vec![
+            init(BLOCK, &[0, 1], AcirBlockType::ReturnData),
+            write(BLOCK, 3, 2),
+        ]);
+    }
+}
@@ -7,23 +7,33 @@ use crate::{
     },
     polynomials::polynomial_types::PrecomputedEntities,
     types::{
+        big_field::{BigFieldCT, BigFieldParams},
+        cycle_group::{CycleGroupCT, CycleScalarCT},
+        grumpkin::GrumpkinPoint,
         plookup::{BasicTableId, MultiTableId, Plookup},
         types::{
-            AddQuad, AddTriple, AggregationObjectIndices, AggregationObjectPubInputIndices,
-            AuxSelectors, BlockConstraint, BlockType, CachedPartialNonNativeFieldMultiplication,
-            ColumnIdx, FieldCT, GateCounter, MulQuad, PlookupBasicTable, PolyTriple, RamTranscript,
-            RangeList, ReadData, RomRecord, RomTable, RomTranscript, UltraTraceBlock,
-            UltraTraceBlocks, NUM_WIRES,
+            AddQuad, AddSimple, AddTriple, AggregationObjectIndices,
+            AggregationObjectPubInputIndices, AuxSelectors, BigIntFromLeBytes, BigIntOperation,
+            BigIntOperationType, BigIntToLeBytes, BlockConstraint, BlockType, BoolCT,
+            CachedPartialNonNativeFieldMultiplication, ColumnIdx, EcAdd, EccAddGate, EccDblGate,
+            FieldCT, GateCounter, MulQuad, MultiScalarMul, NonNativeFieldWitnesses,
+            PlookupBasicTable, PolyTriple, RamTranscript, RangeList, ReadData, RomRecord, RomTable,
+            RomTranscript, UltraTraceBlock, UltraTraceBlocks, WitnessOrConstant, NUM_WIRES,
         },
     },
     utils::Utils,
     HonkProofError, HonkProofResult,
 };
 use ark_ec::pairing::Pairing;
-use ark_ff::{One, Zero};
+use ark_ff::{Field, One, Zero};
 use co_acvm::{mpc::NoirWitnessExtensionProtocol, PlainAcvmSolver};
+use itertools::izip;
+use mpc_core::lut::LookupTableProvider;
 use num_bigint::BigUint;
-use std::collections::BTreeMap;
+use std::{
+    array,
+    collections::{BTreeMap, HashMap},
+};
 
 type GateBlocks<F> = UltraTraceBlocks<UltraTraceBlock<F>>;
 
@@ -155,7 +165,7 @@ pub struct GenericUltraCircuitBuilder<P: Pairing, T: NoirWitnessExtensionProtoco
     rom_arrays: Vec<RomTranscript>,
     ram_arrays: Vec<RamTranscript>,
     pub(crate) lookup_tables: Vec<PlookupBasicTable<P::ScalarField>>,
-    plookup: Plookup<P::ScalarField>,
+    pub(crate) plookup: Plookup<P::ScalarField>,
     range_lists: BTreeMap<u64, RangeList>,
     cached_partial_non_native_field_multiplications:
         Vec<CachedPartialNonNativeFieldMultiplication<P::ScalarField>>,
@@ -186,6 +196,7 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
     pub(crate) const DEFAULT_PLOOKUP_RANGE_STEP_SIZE: usize = 3;
     // number of gates created per non-native field operation in process_non_native_field_multiplications
     pub(crate) const GATES_PER_NON_NATIVE_FIELD_MULTIPLICATION_ARITHMETIC: usize = 7;
+    pub(crate) const DEFAULT_NON_NATIVE_FIELD_LIMB_BITS: usize = 68;
 
     pub fn create_circuit(
         constraint_system: AcirFormat<P::ScalarField>,
@@ -328,7 +339,7 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
         }
     }
 
-    fn create_poly_gate(&mut self, inp: &PolyTriple<P::ScalarField>) {
+    pub(crate) fn create_poly_gate(&mut self, inp: &PolyTriple<P::ScalarField>) {
         self.assert_valid_variables(&[inp.a, inp.b, inp.c]);
 
         self.blocks
@@ -368,7 +379,7 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
         self.num_gates += 1;
     }
 
-    fn create_big_mul_gate(&mut self, inp: &MulQuad<P::ScalarField>) {
+    pub(crate) fn create_big_mul_gate(&mut self, inp: &MulQuad<P::ScalarField>) {
         self.assert_valid_variables(&[inp.a, inp.b, inp.c, inp.d]);
 
         self.blocks
@@ -571,7 +582,7 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
         }
     }
 
-    fn fix_witness(&mut self, witness_index: u32, witness_value: P::ScalarField) {
+    pub(crate) fn fix_witness(&mut self, witness_index: u32, witness_value: P::ScalarField) {
         self.assert_valid_variables(&[witness_index]);
 
         self.blocks.arithmetic.populate_wires(
@@ -791,14 +802,34 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
         // }
 
         // Add multi scalar mul constraints
-        // for (i, constraint) in constraint_system.multi_scalar_mul_constraints.iter().enumerate() {
-        //     todo!("multi scalar mul gates");
-        // }
+        for (i, constraint) in constraint_system
+            .multi_scalar_mul_constraints
+            .iter()
+            .enumerate()
+        {
+            self.create_multi_scalar_mul_constraint(
+                driver,
+                constraint,
+                has_valid_witness_assignments,
+            )?;
+            gate_counter.track_diff(
+                self,
+                &mut constraint_system.gates_per_opcode,
+                constraint_system
+                    .original_opcode_indices
+                    .multi_scalar_mul_constraints[i],
+            );
+        }
 
         // Add ec add constraints
-        // for (i, constraint) in constraint_system.ec_add_constraints.iter().enumerate() {
-        //     todo!("ec add gates");
-        // }
+        for (i, constraint) in constraint_system.ec_add_constraints.iter().enumerate() {
+            self.create_ec_add_constraint(driver, constraint, has_valid_witness_assignments)?;
+            gate_counter.track_diff(
+                self,
+                &mut constraint_system.gates_per_opcode,
+                constraint_system.original_opcode_indices.ec_add_constraints[i],
+            );
+        }
 
         // Add block constraints
         for (i, constraint) in constraint_system.block_constraints.iter().enumerate() {
@@ -816,17 +847,7 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
         }
 
         // Add big_int constraints
-        // for (i, constraint) in constraint_system.bigint_from_le_bytes_constraints.iter().enumerate() {
-        //     todo!("bigint from le bytes gates");
-        // }
-
-        // for (i, constraint) in constraint_system.bigint_operations.iter().enumerate() {
-        //     todo!("bigint operations gates");
-        // }
-
-        // for (i, constraint) in constraint_system.bigint_to_le_bytes_constraints.iter().enumerate() {
-        //     todo!("bigint to le bytes gates");
-        // }
+        self.process_bigint_constraints(&mut constraint_system, &mut gate_counter, driver)?;
 
         // assert equals
         for (i, constraint) in constraint_system.assert_equalities.iter().enumerate() {
@@ -873,6 +894,329 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
         Ok(())
     }
 
+    fn process_bigint_constraints(
+        &mut self,
+        constraint_system: &mut AcirFormat<P::ScalarField>,
+        gate_counter: &mut GateCounter,
+        driver: &mut T,
+    ) -> std::io::Result<()> {
+        let mut dsl_bigints = HashMap::new();
+        for (i, constraint) in constraint_system
+            .bigint_from_le_bytes_constraints
+            .iter()
+            .enumerate()
+        {
+            self.create_bigint_from_le_bytes_constraint(driver, constraint, &mut dsl_bigints)?;
+            gate_counter.track_diff(
+                self,
+                &mut constraint_system.gates_per_opcode,
+                constraint_system
+                    .original_opcode_indices
+                    .bigint_from_le_bytes_constraints[i],
+            );
+        }
+        for (i, constraint) in constraint_system.bigint_operations.iter().enumerate() {
+            self.create_bigint_operations_constraint(driver, constraint, &mut dsl_bigints)?;
+            gate_counter.track_diff(
+                self,
+                &mut constraint_system.gates_per_opcode,
+                constraint_system.original_opcode_indices.bigint_operations[i],
+            );
+        }
+        for (i, constraint) in constraint_system
+            .bigint_to_le_bytes_constraints
+            .iter()
+            .enumerate()
+        {
+            self.create_bigint_to_le_bytes_constraint(driver, constraint, &dsl_bigints)?;
+            gate_counter.track_diff(
+                self,
+                &mut constraint_system.gates_per_opcode,
+                constraint_system
+                    .original_opcode_indices
+                    .bigint_to_le_bytes_constraints[i],
+            );
+        }
+        Ok(())
+    }
+
+    fn get_dsl_bigint(
+        dsl_bigints: &HashMap<u32, BigFieldCT<P::ScalarField>>,
+        bigint_id: u32,
+    ) -> std::io::Result<BigFieldCT<P::ScalarField>> {
+        dsl_bigints.get(&bigint_id).cloned().ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("BigInt {bigint_id} is used before it is defined"),
+            )
+        })
+    }
+
+    fn create_bigint_from_le_bytes_constraint(
+        &mut self,
+        driver: &mut T,
+        constraint: &BigIntFromLeBytes<P::ScalarField>,
+        dsl_bigints: &mut HashMap<u32, BigFieldCT<P::ScalarField>>,
+    ) -> std::io::Result<()> {
+        if constraint.inputs.len() > 32 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "BigIntFromLeBytes expects at most 32 bytes",
+            ));
+        }
+        let modulus_bytes = constraint
+            .modulus
+            .iter()
+            .map(|byte| *byte as u8)
+            .collect::<Vec<_>>();
+        let params = BigFieldParams::new(BigUint::from_bytes_le(&modulus_bytes))?;
+
+        // Range constrain the inputs to bytes and pad them with zeros to 32 bytes
+        let mut bytes = Vec::with_capacity(32);
+        for input in constraint.inputs.iter() {
+            let element = Self::to_field_ct(input);
+            bytes.extend(BigFieldCT::field_to_bytes(&element, 1, self, driver)?);
+        }
+        bytes.resize(32, FieldCT::from(P::ScalarField::zero()));
+        bytes.reverse();
+
+        let bigint = BigFieldCT::from_be_bytes(&bytes, params, self, driver)?;
+        dsl_bigints.insert(constraint.result, bigint);
+        Ok(())
+    }
+
+    fn create_bigint_operations_constraint(
+        &mut self,
+        driver: &mut T,
+        constraint: &BigIntOperation,
+        dsl_bigints: &mut HashMap<u32, BigFieldCT<P::ScalarField>>,
+    ) -> std::io::Result<()> {
+        let mut lhs = Self::get_dsl_bigint(dsl_bigints, constraint.lhs)?;
+        let mut rhs = Self::get_dsl_bigint(dsl_bigints, constraint.rhs)?;
+        if lhs.params != rhs.params {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "BigInt operation on operands with different moduli",
+            ));
+        }
+        let result = match constraint.opcode {
+            BigIntOperationType::Add => lhs.add(&mut rhs, self, driver)?,
+            BigIntOperationType::Sub => lhs.sub(&mut rhs, self, driver)?,
+            BigIntOperationType::Mul => lhs.mul(&mut rhs, self, driver)?,
+            BigIntOperationType::Div => lhs.div(&mut rhs, self, driver)?,
+        };
+        dsl_bigints.insert(constraint.result, result);
+        Ok(())
+    }
+
+    fn create_bigint_to_le_bytes_constraint(
+        &mut self,
+        driver: &mut T,
+        constraint: &BigIntToLeBytes,
+        dsl_bigints: &HashMap<u32, BigFieldCT<P::ScalarField>>,
+    ) -> std::io::Result<()> {
+        if constraint.result.len() > 32 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "BigIntToLeBytes expects at most 32 bytes",
+            ));
+        }
+        let mut bigint = Self::get_dsl_bigint(dsl_bigints, constraint.input)?;
+        bigint.assert_is_in_field(self, driver)?;
+        let mut bytes = bigint.to_byte_array(self, driver)?;
+        bytes.reverse();
+
+        for (byte, result) in bytes.iter().zip(constraint.result.iter()) {
+            if byte.is_constant() {
+                self.assert_equal_constant(*result as usize, byte.additive_constant);
+            } else {
+                let byte = byte.normalize(self, driver);
+                self.create_poly_gate(&PolyTriple {
+                    a: byte.witness_index,
+                    b: *result,
+                    c: self.zero_idx,
+                    q_m: P::ScalarField::zero(),
+                    q_l: P::ScalarField::one(),
+                    q_r: -P::ScalarField::one(),
+                    q_o: P::ScalarField::zero(),
+                    q_c: P::ScalarField::zero(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn to_field_ct(input: &WitnessOrConstant<P::ScalarField>) -> FieldCT<P::ScalarField> {
+        match input {
+            WitnessOrConstant::Witness(index) => FieldCT::from_witness_index(*index),
+            WitnessOrConstant::Constant(value) => FieldCT::from(*value),
+        }
+    }
+
+    fn to_grumpkin_point(
+        &mut self,
+        driver: &mut T,
+        input_x: &WitnessOrConstant<P::ScalarField>,
+        input_y: &WitnessOrConstant<P::ScalarField>,
+        input_infinite: &WitnessOrConstant<P::ScalarField>,
+        has_valid_witness_assignments: bool,
+    ) -> CycleGroupCT<P::ScalarField> {
+        let point_x = Self::to_field_ct(input_x);
+        let point_y = Self::to_field_ct(input_y);
+        let infinite = BoolCT::from_field(&Self::to_field_ct(input_infinite), self, driver);
+
+        // If the witnesses are not provided, we set the point to the point at infinity or to the generator (just to avoid errors during the construction of the circuit)
+        if !has_valid_witness_assignments {
+            if let WitnessOrConstant::Witness(index) = input_infinite {
+                self.variables[*index as usize] = T::AcvmType::from(P::ScalarField::one());
+            } else if let (
+                WitnessOrConstant::Constant(infinite),
+                WitnessOrConstant::Witness(x),
+                WitnessOrConstant::Witness(y),
+            ) = (input_infinite, input_x, input_y)
+            {
+                if infinite.is_zero() {
+                    let generator = GrumpkinPoint::<P::ScalarField>::generator();
+                    self.variables[*x as usize] = T::AcvmType::from(generator.x);
+                    self.variables[*y as usize] = T::AcvmType::from(generator.y);
+                }
+            }
+        }
+        CycleGroupCT::new(&point_x, &point_y, infinite, self, driver)
+    }
+
+    /// Constrains the output variable to the result of an embedded curve operation. Without a valid witness, the output variable is first set to the computed value, since the inputs were replaced by dummy points.
+    fn assert_equal_embedded_curve_output(
+        &mut self,
+        driver: &mut T,
+        result: &FieldCT<P::ScalarField>,
+        output_index: u32,
+        has_valid_witness_assignments: bool,
+    ) {
+        if result.is_constant() {
+            self.fix_witness(output_index, result.additive_constant);
+        } else {
+            if !has_valid_witness_assignments {
+                let value = result.get_value(self, driver);
+                self.update_variable(output_index as usize, value);
+            }
+            self.assert_equal(result.get_witness_index() as usize, output_index as usize);
+        }
+    }
+
+    fn create_ec_add_constraint(
+        &mut self,
+        driver: &mut T,
+        constraint: &EcAdd<P::ScalarField>,
+        has_valid_witness_assignments: bool,
+    ) -> std::io::Result<()> {
+        let input1_point = self.to_grumpkin_point(
+            driver,
+            &constraint.input1_x,
+            &constraint.input1_y,
+            &constraint.input1_infinite,
+            has_valid_witness_assignments,
+        );
+        let input2_point = self.to_grumpkin_point(
+            driver,
+            &constraint.input2_x,
+            &constraint.input2_y,
+            &constraint.input2_infinite,
+            has_valid_witness_assignments,
+        );
+
+        let result = input1_point.add(&input2_point, self, driver)?;
+        let standard_result = result.get_standard_form(self, driver)?;
+        let x_normalized = standard_result.x.normalize(self, driver);
+        let y_normalized = standard_result.y.normalize(self, driver);
+        let infinite = FieldCT::from(&standard_result.is_infinity.normalize(self, driver));
+
+        self.assert_equal_embedded_curve_output(
+            driver,
+            &x_normalized,
+            constraint.result_x,
+            has_valid_witness_assignments,
+        );
+        self.assert_equal_embedded_curve_output(
+            driver,
+            &y_normalized,
+            constraint.result_y,
+            has_valid_witness_assignments,
+        );
+        self.assert_equal_embedded_curve_output(
+            driver,
+            &infinite,
+            constraint.result_infinite,
+            has_valid_witness_assignments,
+        );
+        Ok(())
+    }
+
+    fn create_multi_scalar_mul_constraint(
+        &mut self,
+        driver: &mut T,
+        constraint: &MultiScalarMul<P::ScalarField>,
+        has_valid_witness_assignments: bool,
+    ) -> std::io::Result<()> {
+        if constraint.points.len() != 3 * (constraint.scalars.len() / 2)
+            || constraint.scalars.len() % 2 != 0
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "MultiScalarMul expects three inputs per point and two inputs per scalar",
+            ));
+        }
+
+        let mut points = Vec::with_capacity(constraint.scalars.len() / 2);
+        let mut scalars = Vec::with_capacity(constraint.scalars.len() / 2);
+        for (point, scalar) in constraint
+            .points
+            .chunks(3)
+            .zip(constraint.scalars.chunks(2))
+        {
+            let input_point = self.to_grumpkin_point(
+                driver,
+                &point[0],
+                &point[1],
+                &point[2],
+                has_valid_witness_assignments,
+            );
+            // Reconstruct the scalar from the low and high limbs
+            let scalar_low_as_field = Self::to_field_ct(&scalar[0]);
+            let scalar_high_as_field = Self::to_field_ct(&scalar[1]);
+            points.push(input_point);
+            scalars.push(CycleScalarCT::new(
+                scalar_low_as_field,
+                scalar_high_as_field,
+            ));
+        }
+
+        let output_point = CycleGroupCT::batch_mul(&points, &scalars, self, driver)?
+            .get_standard_form(self, driver)?;
+
+        // Add the constraints and handle constant values
+        let infinite = FieldCT::from(&output_point.is_infinity);
+        self.assert_equal_embedded_curve_output(
+            driver,
+            &infinite,
+            constraint.out_point_is_infinite,
+            has_valid_witness_assignments,
+        );
+        self.assert_equal_embedded_curve_output(
+            driver,
+            &output_point.x,
+            constraint.out_point_x,
+            has_valid_witness_assignments,
+        );
+        self.assert_equal_embedded_curve_output(
+            driver,
+            &output_point.y,
+            constraint.out_point_y,
+            has_valid_witness_assignments,
+        );
+        Ok(())
+    }
+
     fn process_plonk_recursion_constraints(
         &mut self,
         constraint_system: &AcirFormat<P::ScalarField>,
@@ -1167,13 +1511,12 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
         self.variables[self.real_variable_index[index] as usize].to_owned()
     }
 
-    fn update_variable(&mut self, index: usize, value: T::AcvmType) {
+    pub(crate) fn update_variable(&mut self, index: usize, value: T::AcvmType) {
         assert!(self.variables.len() > index);
         self.variables[self.real_variable_index[index] as usize] = value;
     }
 
     pub(crate) fn assert_equal_constant(&mut self, a_idx: usize, b: P::ScalarField) {
-        assert_eq!(self.variables[a_idx], T::AcvmType::from(b));
         let b_idx = self.put_constant_variable(b);
         self.assert_equal(a_idx, b_idx as usize);
     }
@@ -1303,7 +1646,7 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
         self.rom_arrays[rom_id].records.push(new_record);
     }
 
-    fn set_rom_element_pair(
+    pub(crate) fn set_rom_element_pair(
         &mut self,
         rom_id: usize,
         index_value: usize,
@@ -1381,6 +1724,74 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
         Ok(value_witness)
     }
 
+    /// Reads both value columns of a ROM array. In contrast to [`Self::read_rom_array`], the index may be shared, in which case the values are read with a shared lookup.
+    pub(crate) fn read_rom_array_pair(
+        &mut self,
+        rom_id: usize,
+        index_witness: u32,
+        driver: &mut T,
+    ) -> std::io::Result<[u32; 2]> {
+        assert!(self.rom_arrays.len() > rom_id);
+        let index_value = self.get_variable(index_witness as usize);
+        let (index, [value1, value2]) = if let Some(index_value) = T::get_public(&index_value) {
+            let val: BigUint = index_value.into();
+            let index: usize = val.try_into().unwrap();
+            assert!(self.rom_arrays[rom_id].state.len() > index);
+            assert!(self.rom_arrays[rom_id].state[index][0] != Self::UNINITIALIZED_MEMORY_RECORD);
+            let [value1, value2] = self.rom_arrays[rom_id].state[index];
+            (
+                index as u32,
+                [
+                    self.get_variable(value1 as usize),
+                    self.get_variable(value2 as usize),
+                ],
+            )
+        } else {
+            // The index of a shared read is only used in process_rom_array, where the shared indices are sorted
+            let [lut1, lut2] = self.create_rom_luts(rom_id, driver);
+            let value1 = driver.read_lut_by_acvm_type(&index_value, &lut1)?;
+            let value2 = driver.read_lut_by_acvm_type(&index_value, &lut2)?;
+            (0, [value1, value2])
+        };
+        let value1_witness = self.add_variable(value1);
+        let value2_witness = self.add_variable(value2);
+
+        let mut new_record = RomRecord {
+            index_witness,
+            value_column1_witness: value1_witness,
+            value_column2_witness: value2_witness,
+            index,
+            record_witness: 0,
+            gate_index: 0,
+        };
+        self.create_rom_gate(&mut new_record);
+        self.rom_arrays[rom_id].records.push(new_record);
+
+        Ok([value1_witness, value2_witness])
+    }
+
+    /// Creates a LUT for each value column of a ROM array. Uninitialized cells are read as zero.
+    fn create_rom_luts(
+        &self,
+        rom_id: usize,
+        driver: &mut T,
+    ) -> [<T::Lookup as LookupTableProvider<P::ScalarField>>::SecretSharedMap; 2] {
+        array::from_fn(|column| {
+            let values = self.rom_arrays[rom_id]
+                .state
+                .iter()
+                .map(|cell| {
+                    if cell[column] == Self::UNINITIALIZED_MEMORY_RECORD {
+                        T::public_zero()
+                    } else {
+                        self.get_variable(cell[column] as usize)
+                    }
+                })
+                .collect();
+            driver.init_lut_by_acvm_type(values)
+        })
+    }
+
     fn apply_aux_selectors(&mut self, type_: AuxSelectors) {
         let block = &mut self.blocks.aux;
         block.q_aux().push(if type_ == AuxSelectors::None {
@@ -1424,6 +1835,66 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
                 block.q_arith().push(P::ScalarField::zero());
                 self.check_selector_length_consistency();
             }
+            AuxSelectors::LimbAccumulate1 => {
+                block.q_1().push(P::ScalarField::zero());
+                block.q_2().push(P::ScalarField::zero());
+                block.q_3().push(P::ScalarField::one());
+                block.q_4().push(P::ScalarField::one());
+                block.q_m().push(P::ScalarField::zero());
+                block.q_c().push(P::ScalarField::zero());
+                block.q_arith().push(P::ScalarField::zero());
+                self.check_selector_length_consistency();
+            }
+            AuxSelectors::LimbAccumulate2 => {
+                block.q_1().push(P::ScalarField::zero());
+                block.q_2().push(P::ScalarField::zero());
+                block.q_3().push(P::ScalarField::one());
+                block.q_4().push(P::ScalarField::zero());
+                block.q_m().push(P::ScalarField::one());
+                block.q_c().push(P::ScalarField::zero());
+                block.q_arith().push(P::ScalarField::zero());
+                self.check_selector_length_consistency();
+            }
+            AuxSelectors::NonNativeField1 => {
+                block.q_1().push(P::ScalarField::zero());
+                block.q_2().push(P::ScalarField::one());
+                block.q_3().push(P::ScalarField::one());
+                block.q_4().push(P::ScalarField::zero());
+                block.q_m().push(P::ScalarField::zero());
+                block.q_c().push(P::ScalarField::zero());
+                block.q_arith().push(P::ScalarField::zero());
+                self.check_selector_length_consistency();
+            }
+            AuxSelectors::NonNativeField2 => {
+                block.q_1().push(P::ScalarField::zero());
+                block.q_2().push(P::ScalarField::one());
+                block.q_3().push(P::ScalarField::zero());
+                block.q_4().push(P::ScalarField::one());
+                block.q_m().push(P::ScalarField::zero());
+                block.q_c().push(P::ScalarField::zero());
+                block.q_arith().push(P::ScalarField::zero());
+                self.check_selector_length_consistency();
+            }
+            AuxSelectors::NonNativeField3 => {
+                block.q_1().push(P::ScalarField::zero());
+                block.q_2().push(P::ScalarField::one());
+                block.q_3().push(P::ScalarField::zero());
+                block.q_4().push(P::ScalarField::zero());
+                block.q_m().push(P::ScalarField::one());
+                block.q_c().push(P::ScalarField::zero());
+                block.q_arith().push(P::ScalarField::zero());
+                self.check_selector_length_consistency();
+            }
+            AuxSelectors::None => {
+                block.q_1().push(P::ScalarField::zero());
+                block.q_2().push(P::ScalarField::zero());
+                block.q_3().push(P::ScalarField::zero());
+                block.q_4().push(P::ScalarField::zero());
+                block.q_m().push(P::ScalarField::zero());
+                block.q_c().push(P::ScalarField::zero());
+                block.q_arith().push(P::ScalarField::zero());
+                self.check_selector_length_consistency();
+            }
             _ => todo!("Aux selectors"),
         }
     }
@@ -1457,6 +1928,24 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
         // self.num_gates += 1;
     }
 
+    /// Adds a dummy gate to the arithmetic block, e.g., to provide the fourth wire of the next gate to a preceding big addition gate.
+    pub(crate) fn create_arithmetic_dummy_gate(
+        &mut self,
+        idx_1: u32,
+        idx_2: u32,
+        idx_3: u32,
+        idx_4: u32,
+    ) {
+        create_dummy_gate!(
+            self,
+            &mut self.blocks.arithmetic,
+            idx_1,
+            idx_2,
+            idx_3,
+            idx_4,
+        );
+    }
+
     pub fn add_gates_to_ensure_all_polys_are_non_zero(&mut self) {
         // q_m, q_1, q_2, q_3, q_4
         self.blocks.arithmetic.populate_wires(
@@ -1871,7 +2360,7 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
         }
     }
 
-    fn create_gates_from_plookup_accumulators(
+    pub(crate) fn create_gates_from_plookup_accumulators(
         &mut self,
         id: MultiTableId,
         read_values: ReadData<P::ScalarField>,
@@ -2002,7 +2491,7 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
             }
 
             self.process_non_native_field_multiplications();
-            self.process_rom_arrays()?;
+            self.process_rom_arrays(driver)?;
             self.process_ram_arrays();
             self.process_range_lists(driver)?;
             self.circuit_finalized = true;
@@ -2010,9 +2499,9 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
         Ok(())
     }
 
-    fn process_rom_arrays(&mut self) -> std::io::Result<()> {
+    fn process_rom_arrays(&mut self, driver: &mut T) -> std::io::Result<()> {
         for i in 0..self.rom_arrays.len() {
-            self.process_rom_array(i)?;
+            self.process_rom_array(i, driver)?;
         }
         Ok(())
     }
@@ -2041,7 +2530,7 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
         Ok(())
     }
 
-    fn process_rom_array(&mut self, rom_id: usize) -> std::io::Result<()> {
+    fn process_rom_array(&mut self, rom_id: usize, driver: &mut T) -> std::io::Result<()> {
         let read_tag = self.get_new_tag(); // current_tag + 1;
         let sorted_list_tag = self.get_new_tag(); // current_tag + 2;
         self.create_tag(read_tag, sorted_list_tag);
@@ -2053,13 +2542,32 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
                 self.set_rom_element_pair(rom_id, i, [self.zero_idx, self.zero_idx]);
             }
         }
-        self.rom_arrays[rom_id].records.sort();
+        let has_shared_index = self.rom_arrays[rom_id]
+            .records
+            .iter()
+            .any(|record| T::is_shared(&self.get_variable(record.index_witness as usize)));
+        let sorted_entries = if has_shared_index {
+            self.sort_rom_records_shared(rom_id, driver)?
+        } else {
+            self.rom_arrays[rom_id].records.sort();
+            self.rom_arrays[rom_id]
+                .records
+                .iter()
+                .map(|record| {
+                    (
+                        record.index,
+                        T::AcvmType::from(P::ScalarField::from(record.index)),
+                        self.get_variable(record.value_column1_witness.try_into().unwrap()),
+                        self.get_variable(record.value_column2_witness.try_into().unwrap()),
+                    )
+                })
+                .collect()
+        };
         let records = self.rom_arrays[rom_id].records.clone();
-        for record in records {
-            let index = record.index;
-            let value1 = self.get_variable(record.value_column1_witness.try_into().unwrap());
-            let value2 = self.get_variable(record.value_column2_witness.try_into().unwrap());
-            let index_witness = self.add_variable(T::AcvmType::from(P::ScalarField::from(index)));
+        for (record, (index, index_value, value1, value2)) in
+            records.into_iter().zip(sorted_entries)
+        {
+            let index_witness = self.add_variable(index_value);
 
             let value1_witness = self.add_variable(value1);
 
@@ -2131,6 +2639,44 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
         // because the first cell is explicitly initialized using zero_idx as the index field.
         Ok(())
     }
+
+    /// Sorts the indices of the reads of a ROM array with shared indices and reads the values of the sorted indices. Returns the entries of the sorted list.
+    #[expect(clippy::type_complexity)]
+    fn sort_rom_records_shared(
+        &mut self,
+        rom_id: usize,
+        driver: &mut T,
+    ) -> std::io::Result<Vec<(u32, T::AcvmType, T::AcvmType, T::AcvmType)>> {
+        let indices = self.rom_arrays[rom_id]
+            .records
+            .iter()
+            .map(|record| {
+                let index = self.get_variable(record.index_witness as usize);
+                match T::get_shared(&index) {
+                    Some(index) => index,
+                    None => driver.promote_to_trivial_share(
+                        T::get_public(&index).expect("Already checked it is public"),
+                    ),
+                }
+            })
+            .collect::<Vec<_>>();
+        let max_index = self.rom_arrays[rom_id].state.len().saturating_sub(1);
+        let bitsize = (usize::BITS - max_index.leading_zeros()).max(1) as usize;
+        let sorted_indices = driver.sort(&indices, bitsize)?;
+
+        let [lut1, lut2] = self.create_rom_luts(rom_id, driver);
+        sorted_indices
+            .into_iter()
+            .map(|index| {
+                let index = T::AcvmType::from(index);
+                let value1 = driver.read_lut_by_acvm_type(&index, &lut1)?;
+                let value2 = driver.read_lut_by_acvm_type(&index, &lut2)?;
+                // The index field is only used for sorting, which is already done
+                Ok((0, index, value1, value2))
+            })
+            .collect()
+    }
+
     fn create_sorted_rom_gate(&mut self, record: &mut RomRecord) {
         record.record_witness = self.add_variable(T::AcvmType::from(P::ScalarField::zero()));
 
@@ -2254,7 +2800,7 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
             self.blocks
                 .aux
                 .populate_wires(input.a[2], input.b[2], self.zero_idx, input_hi_0);
-            self.apply_aux_selectors(AuxSelectors::NonNativeField2);
+            self.apply_aux_selectors(AuxSelectors::NonNativeField3);
             self.num_gates += 1;
 
             let input_hi_1: BigUint = input.hi_1.into();
@@ -2355,7 +2901,7 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
         Ok(())
     }
 
-    fn create_new_range_constraint(&mut self, variable_index: u32, target_range: u64) {
+    pub(crate) fn create_new_range_constraint(&mut self, variable_index: u32, target_range: u64) {
         // We ignore this check because it is definitely more expensive in MPC, the proof will just not verify if this constraint is not given
         // if (uint256_t(self.get_variable(variable_index)).data[0] > target_range) {
         //     if (!self.failed()) {
@@ -2415,7 +2961,7 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
         }
     }
 
-    fn decompose_into_default_range(
+    pub(crate) fn decompose_into_default_range(
         &mut self,
         driver: &mut T,
         variable_index: u32,
@@ -2580,6 +3126,456 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
         Ok(sublimb_indices)
     }
 
+    /// Decomposes a value into total_bits.div_ceil(chunk_bits) chunks of chunk_bits bits each (LSB first), where bits beyond total_bits are ignored.
+    pub(crate) fn decompose_into_chunks(
+        driver: &mut T,
+        value: T::AcvmType,
+        total_bits: usize,
+        chunk_bits: usize,
+    ) -> std::io::Result<Vec<T::AcvmType>> {
+        if let Some(shared) = T::get_shared(&value) {
+            let chunks = T::decompose_arithmetic(driver, shared, total_bits, chunk_bits)?;
+            return Ok(chunks.into_iter().map(T::AcvmType::from).collect());
+        }
+        let mut value: BigUint = T::get_public(&value)
+            .expect("Already checked it is public")
+            .into();
+        let mask = (BigUint::one() << chunk_bits) - BigUint::one();
+        let mut chunks = Vec::with_capacity(total_bits.div_ceil(chunk_bits));
+        for _ in 0..total_bits.div_ceil(chunk_bits) {
+            chunks.push(T::AcvmType::from(P::ScalarField::from(&value & &mask)));
+            value >>= chunk_bits;
+        }
+        Ok(chunks)
+    }
+
+    /// Range constrains two limbs of at most 70 bits each with three gates, by accumulating five 14-bit sublimbs per limb.
+    pub(crate) fn range_constrain_two_limbs(
+        &mut self,
+        driver: &mut T,
+        lo_idx: u32,
+        hi_idx: u32,
+        lo_limb_bits: usize,
+        hi_limb_bits: usize,
+    ) -> std::io::Result<()> {
+        const SUBLIMB_BITS: usize = 14;
+        const NUM_SUBLIMBS: usize = 5;
+        assert!(lo_limb_bits <= SUBLIMB_BITS * NUM_SUBLIMBS);
+        assert!(hi_limb_bits <= SUBLIMB_BITS * NUM_SUBLIMBS);
+
+        // The masks of the sublimbs, where a mask of 0 means the sublimb is replaced by the zero variable
+        let get_limb_masks = |limb_bits: usize| {
+            array::from_fn::<u64, NUM_SUBLIMBS, _>(|i| {
+                let bits = limb_bits.saturating_sub(i * SUBLIMB_BITS).min(SUBLIMB_BITS);
+                (1u64 << bits) - 1
+            })
+        };
+        let lo_masks = get_limb_masks(lo_limb_bits);
+        let hi_masks = get_limb_masks(hi_limb_bits);
+
+        let mut get_sublimbs = |builder: &mut Self, limb_idx: u32, masks: &[u64; NUM_SUBLIMBS]| {
+            let limb = builder.get_variable(limb_idx as usize);
+            let sublimbs = Self::decompose_into_chunks(
+                driver,
+                limb,
+                SUBLIMB_BITS * NUM_SUBLIMBS,
+                SUBLIMB_BITS,
+            )?;
+            let mut indices = [builder.zero_idx; NUM_SUBLIMBS];
+            for (index, mask, sublimb) in izip!(indices.iter_mut(), masks, sublimbs) {
+                if *mask != 0 {
+                    *index = builder.add_variable(sublimb);
+                }
+            }
+            std::io::Result::Ok(indices)
+        };
+        let lo_sublimbs = get_sublimbs(self, lo_idx, &lo_masks)?;
+        let hi_sublimbs = get_sublimbs(self, hi_idx, &hi_masks)?;
+
+        self.blocks
+            .aux
+            .populate_wires(lo_sublimbs[0], lo_sublimbs[1], lo_sublimbs[2], lo_idx);
+        self.blocks.aux.populate_wires(
+            lo_sublimbs[3],
+            lo_sublimbs[4],
+            hi_sublimbs[0],
+            hi_sublimbs[1],
+        );
+        self.blocks
+            .aux
+            .populate_wires(hi_sublimbs[2], hi_sublimbs[3], hi_sublimbs[4], hi_idx);
+
+        self.apply_aux_selectors(AuxSelectors::LimbAccumulate1);
+        self.apply_aux_selectors(AuxSelectors::LimbAccumulate2);
+        self.apply_aux_selectors(AuxSelectors::None);
+        self.num_gates += 3;
+
+        for i in 0..NUM_SUBLIMBS {
+            if lo_masks[i] != 0 {
+                self.create_new_range_constraint(lo_sublimbs[i], lo_masks[i]);
+            }
+            if hi_masks[i] != 0 {
+                self.create_new_range_constraint(hi_sublimbs[i], hi_masks[i]);
+            }
+        }
+        Ok(())
+    }
+
+    /// Splits a limb of num_limb_bits bits into a lower limb of DEFAULT_NON_NATIVE_FIELD_LIMB_BITS bits and an upper limb with the remaining bits, which are both range constrained. The caller has to constrain that the two limbs compose the input limb.
+    pub(crate) fn decompose_non_native_field_double_width_limb(
+        &mut self,
+        driver: &mut T,
+        limb_idx: u32,
+        num_limb_bits: usize,
+    ) -> std::io::Result<[u32; 2]> {
+        assert!(num_limb_bits > Self::DEFAULT_NON_NATIVE_FIELD_LIMB_BITS);
+        let value = self.get_variable(limb_idx as usize);
+        let chunks = Self::decompose_into_chunks(
+            driver,
+            value,
+            num_limb_bits,
+            Self::DEFAULT_NON_NATIVE_FIELD_LIMB_BITS,
+        )?;
+        debug_assert_eq!(chunks.len(), 2);
+        let [low, hi]: [T::AcvmType; 2] = chunks.try_into().expect("two chunks");
+        let low_idx = self.add_variable(low);
+        let hi_idx = self.add_variable(hi);
+
+        let lo_bits = Self::DEFAULT_NON_NATIVE_FIELD_LIMB_BITS;
+        let hi_bits = num_limb_bits - Self::DEFAULT_NON_NATIVE_FIELD_LIMB_BITS;
+        self.range_constrain_two_limbs(driver, low_idx, hi_idx, lo_bits, hi_bits)?;
+        Ok([low_idx, hi_idx])
+    }
+
+    /// Queues the gates for the non-native field multiplication a * b = q * p + r without the prime basis limbs, see barretenberg's evaluate_non_native_field_multiplication. Returns the witness indices of the lower and upper carries, which still have to be range constrained by the caller.
+    pub(crate) fn evaluate_non_native_field_multiplication(
+        &mut self,
+        driver: &mut T,
+        input: &NonNativeFieldWitnesses<P::ScalarField>,
+    ) -> std::io::Result<[u32; 2]> {
+        let get = |builder: &Self, indices: &[u32; 4]| {
+            indices.map(|index| builder.get_variable(index as usize))
+        };
+        let a = get(self, &input.a);
+        let b = get(self, &input.b);
+        let q = get(self, &input.q);
+        let r = get(self, &input.r);
+        let neg_modulus = input.neg_modulus;
+
+        let limb_shift =
+            P::ScalarField::from(BigUint::one() << Self::DEFAULT_NON_NATIVE_FIELD_LIMB_BITS);
+        let limb_shift_2 =
+            P::ScalarField::from(BigUint::one() << (2 * Self::DEFAULT_NON_NATIVE_FIELD_LIMB_BITS));
+        let limb_rshift = limb_shift.inverse().expect("non-zero");
+        let limb_rshift_2 = limb_shift_2.inverse().expect("non-zero");
+
+        // Sum of the products of the given pairs of limbs of a and b
+        let mut cross_terms = |pairs: &[(usize, usize)]| {
+            let mut sum = T::public_zero();
+            for (i, j) in pairs {
+                let product = driver.mul(a[*i].to_owned(), b[*j].to_owned())?;
+                driver.add_assign(&mut sum, product);
+            }
+            std::io::Result::Ok(sum)
+        };
+        let a0b0 = cross_terms(&[(0, 0)])?;
+        let lo_cross = cross_terms(&[(1, 0), (0, 1)])?;
+        let hi_0_lo = cross_terms(&[(2, 0), (0, 2)])?;
+        let hi_0_hi = cross_terms(&[(0, 3), (3, 0)])?;
+        let a1b1 = cross_terms(&[(1, 1)])?;
+        let hi_1_hi = cross_terms(&[(1, 2), (2, 1)])?;
+
+        // Linear combination of public coefficients and shared values
+        let linear = |driver: &mut T, terms: &[(P::ScalarField, &T::AcvmType)]| {
+            let mut sum = T::public_zero();
+            for (coefficient, value) in terms {
+                let term = driver.mul_with_public(*coefficient, (*value).to_owned());
+                driver.add_assign(&mut sum, term);
+            }
+            sum
+        };
+        let one = P::ScalarField::one();
+
+        // lo_0 = a0 * b0 - r0 + (a1 * b0 + a0 * b1) * 2^b
+        let lo_0 = linear(
+            driver,
+            &[(one, &a0b0), (-one, &r[0]), (limb_shift, &lo_cross)],
+        );
+        // lo_1 = (lo_0 + q0 * p0 + (q1 * p0 + q0 * p1 - r1) * 2^b) * 2^-2b
+        let lo_1 = linear(
+            driver,
+            &[
+                (one, &lo_0),
+                (neg_modulus[0] + neg_modulus[1] * limb_shift, &q[0]),
+                (neg_modulus[0] * limb_shift, &q[1]),
+                (-limb_shift, &r[1]),
+            ],
+        );
+        let lo_1 = driver.mul_with_public(limb_rshift_2, lo_1);
+        // hi_0 = a2 * b0 + a0 * b2 + (a0 * b3 + a3 * b0 - r3) * 2^b
+        let hi_0 = linear(
+            driver,
+            &[
+                (one, &hi_0_lo),
+                (limb_shift, &hi_0_hi),
+                (-limb_shift, &r[3]),
+            ],
+        );
+        // hi_1 = hi_0 + a1 * b1 - r2 + (a1 * b2 + a2 * b1) * 2^b
+        let hi_1 = linear(
+            driver,
+            &[
+                (one, &hi_0),
+                (one, &a1b1),
+                (-one, &r[2]),
+                (limb_shift, &hi_1_hi),
+            ],
+        );
+        // hi_2 = hi_1 + lo_1 + q2 * p0 + (q3 * p0 + q2 * p1) * 2^b
+        let hi_2 = linear(
+            driver,
+            &[
+                (one, &hi_1),
+                (one, &lo_1),
+                (neg_modulus[0] + neg_modulus[1] * limb_shift, &q[2]),
+                (neg_modulus[0] * limb_shift, &q[3]),
+            ],
+        );
+        // hi_3 = (hi_2 + (q0 * p3 + q1 * p2) * 2^b + (q0 * p2 + q1 * p1)) * 2^-2b
+        let hi_3 = linear(
+            driver,
+            &[
+                (one, &hi_2),
+                (neg_modulus[3] * limb_shift + neg_modulus[2], &q[0]),
+                (neg_modulus[2] * limb_shift + neg_modulus[1], &q[1]),
+            ],
+        );
+        let hi_3 = driver.mul_with_public(limb_rshift_2, hi_3);
+
+        let lo_0_idx = self.add_variable(lo_0);
+        let lo_1_idx = self.add_variable(lo_1);
+        let hi_0_idx = self.add_variable(hi_0);
+        let hi_1_idx = self.add_variable(hi_1);
+        let hi_2_idx = self.add_variable(hi_2);
+        let hi_3_idx = self.add_variable(hi_3);
+
+        // product gate 1
+        // (lo_0 + q_0(p_0 + p_1*2^b) + q_1(p_0*2^b) - (r_1)2^b)2^-2b - lo_1 = 0
+        self.create_big_add_gate(
+            &AddQuad {
+                a: input.q[0],
+                b: input.q[1],
+                c: input.r[1],
+                d: lo_1_idx,
+                a_scaling: neg_modulus[0] + neg_modulus[1] * limb_shift,
+                b_scaling: neg_modulus[0] * limb_shift,
+                c_scaling: -limb_shift,
+                d_scaling: -limb_shift.square(),
+                const_scaling: P::ScalarField::zero(),
+            },
+            true,
+        );
+        // The add gate above reads lo_0 from the next gate of the arithmetic block
+        create_dummy_gate!(
+            self,
+            &mut self.blocks.arithmetic,
+            input.a[1],
+            input.b[1],
+            input.r[0],
+            lo_0_idx,
+        );
+
+        self.blocks
+            .aux
+            .populate_wires(input.a[1], input.b[1], input.r[0], lo_0_idx);
+        self.apply_aux_selectors(AuxSelectors::NonNativeField1);
+        self.num_gates += 1;
+
+        self.blocks
+            .aux
+            .populate_wires(input.a[0], input.b[0], input.a[3], input.b[3]);
+        self.apply_aux_selectors(AuxSelectors::NonNativeField2);
+        self.num_gates += 1;
+
+        self.blocks
+            .aux
+            .populate_wires(input.a[2], input.b[2], input.r[3], hi_0_idx);
+        self.apply_aux_selectors(AuxSelectors::NonNativeField3);
+        self.num_gates += 1;
+
+        self.blocks
+            .aux
+            .populate_wires(input.a[1], input.b[1], input.r[2], hi_1_idx);
+        self.apply_aux_selectors(AuxSelectors::None);
+        self.num_gates += 1;
+
+        // product gate 6
+        // hi_2 - hi_1 - lo_1 - q[2](p[1].2^b + p[0]) - q[3](p[0].2^b) = 0
+        self.create_big_add_gate(
+            &AddQuad {
+                a: input.q[2],
+                b: input.q[3],
+                c: lo_1_idx,
+                d: hi_1_idx,
+                a_scaling: -neg_modulus[1] * limb_shift - neg_modulus[0],
+                b_scaling: -neg_modulus[0] * limb_shift,
+                c_scaling: -one,
+                d_scaling: -one,
+                const_scaling: P::ScalarField::zero(),
+            },
+            true,
+        );
+
+        // product gate 7
+        // hi_3 - (hi_2 - q[0](p[3].2^{2b} + p[2].2^b) - q[1](p[2].2^{2b} + p[1].2^b))2^{-2b}
+        self.create_big_add_gate(
+            &AddQuad {
+                a: hi_3_idx,
+                b: input.q[0],
+                c: input.q[1],
+                d: hi_2_idx,
+                a_scaling: -one,
+                b_scaling: neg_modulus[3] * limb_rshift + neg_modulus[2] * limb_rshift_2,
+                c_scaling: neg_modulus[2] * limb_rshift + neg_modulus[1] * limb_rshift_2,
+                d_scaling: limb_rshift_2,
+                const_scaling: P::ScalarField::zero(),
+            },
+            false,
+        );
+
+        Ok([lo_1_idx, hi_3_idx])
+    }
+
+    /// Adds two non-native field elements limb-wise with four gates, see [Self::evaluate_non_native_field_subtraction] for the layout. Returns the witness indices of the four limbs and the prime basis limb of the sum.
+    pub(crate) fn evaluate_non_native_field_addition(
+        &mut self,
+        driver: &mut T,
+        limbs: [AddSimple<P::ScalarField>; 4],
+        limbp: (u32, u32, P::ScalarField),
+    ) -> [u32; 5] {
+        self.evaluate_non_native_field_add_sub(driver, limbs, limbp, false)
+    }
+
+    /// Subtracts two non-native field elements limb-wise with four gates. Returns the witness indices of the four limbs and the prime basis limb of the difference.
+    ///
+    /// The gates have the following layout for x - y = z:
+    ///
+    /// |  1  |  2  |  3  |  4  |
+    /// |-----|-----|-----|-----|
+    /// | y.p | x.0 | y.0 | z.p | (x.p - y.p - z.p = 0) AND (x.0 - y.0 - z.0 = 0)
+    /// | x.p | x.1 | y.1 | z.0 | (x.1 - y.1 - z.1 = 0)
+    /// | x.2 | y.2 | z.2 | z.1 | (x.2 - y.2 - z.2 = 0)
+    /// | x.3 | y.3 | z.3 | --- | (x.3 - y.3 - z.3 = 0)
+    ///
+    /// With q_arith = 3 in the first gate, the arithmetic relation additionally validates the prime basis limbs. The addition uses the same gates, but swaps x.p and z.p.
+    pub(crate) fn evaluate_non_native_field_subtraction(
+        &mut self,
+        driver: &mut T,
+        limbs: [AddSimple<P::ScalarField>; 4],
+        limbp: (u32, u32, P::ScalarField),
+    ) -> [u32; 5] {
+        self.evaluate_non_native_field_add_sub(driver, limbs, limbp, true)
+    }
+
+    fn evaluate_non_native_field_add_sub(
+        &mut self,
+        driver: &mut T,
+        limbs: [AddSimple<P::ScalarField>; 4],
+        limbp: (u32, u32, P::ScalarField),
+        is_subtraction: bool,
+    ) -> [u32; 5] {
+        let one = P::ScalarField::one();
+        let two = P::ScalarField::from(2u64);
+        // The sign of the y terms in the selectors, which is inverted compared to the operation since z is on the other side of the equation
+        let y_sign = if is_subtraction { one } else { -one };
+
+        let mut z = [0u32; 5];
+        for (z, ((x, x_mulconst), (y, y_mulconst), addconst)) in z.iter_mut().zip(limbs.iter()) {
+            let x_value = self.get_variable(*x as usize);
+            let y_value = self.get_variable(*y as usize);
+            let x_term = driver.mul_with_public(*x_mulconst, x_value);
+            let y_term = driver.mul_with_public(*y_mulconst, y_value);
+            let mut value = if is_subtraction {
+                driver.sub(x_term, y_term)
+            } else {
+                driver.add(x_term, y_term)
+            };
+            driver.add_assign_with_public(*addconst, &mut value);
+            *z = self.add_variable(value);
+        }
+        let (x_p, y_p, addconstp) = limbp;
+        let x_p_value = self.get_variable(x_p as usize);
+        let y_p_value = self.get_variable(y_p as usize);
+        let mut z_p_value = if is_subtraction {
+            driver.sub(x_p_value, y_p_value)
+        } else {
+            driver.add(x_p_value, y_p_value)
+        };
+        driver.add_assign_with_public(addconstp, &mut z_p_value);
+        z[4] = self.add_variable(z_p_value);
+
+        let [((x_0, x_mulconst0), (y_0, y_mulconst0), addconst0), ((x_1, x_mulconst1), (y_1, y_mulconst1), addconst1), ((x_2, x_mulconst2), (y_2, y_mulconst2), addconst2), ((x_3, x_mulconst3), (y_3, y_mulconst3), addconst3)] =
+            limbs;
+        let [z_0, z_1, z_2, z_3, z_p] = z;
+
+        // For the addition, the first wire and the shifted first wire are swapped compared to the subtraction, i.e., y.p + x.p - z.p + q_m = 0
+        let (first_p, second_p, q_m) = if is_subtraction {
+            (x_p, z_p, -addconstp)
+        } else {
+            (z_p, x_p, addconstp)
+        };
+        let block = &mut self.blocks.arithmetic;
+        block.populate_wires(y_p, x_0, y_0, second_p);
+        block.populate_wires(first_p, x_1, y_1, z_0);
+        block.populate_wires(x_2, y_2, z_2, z_1);
+        block.populate_wires(x_3, y_3, z_3, self.zero_idx);
+
+        // The constants of the first gate are scaled by 2, since q_arith = 3 scales the shifted fourth wire z_0 by 2
+        block.q_m().push(q_m);
+        block.q_1().push(P::ScalarField::zero());
+        block.q_2().push(-x_mulconst0 * two);
+        block.q_3().push(y_sign * y_mulconst0 * two);
+        block.q_4().push(P::ScalarField::zero());
+        block.q_c().push(-addconst0 * two);
+        block.q_arith().push(P::ScalarField::from(3u64));
+
+        block.q_m().push(P::ScalarField::zero());
+        block.q_1().push(P::ScalarField::zero());
+        block.q_2().push(-x_mulconst1);
+        block.q_3().push(y_sign * y_mulconst1);
+        block.q_4().push(P::ScalarField::zero());
+        block.q_c().push(-addconst1);
+        block.q_arith().push(two);
+
+        block.q_m().push(P::ScalarField::zero());
+        block.q_1().push(-x_mulconst2);
+        block.q_2().push(y_sign * y_mulconst2);
+        block.q_3().push(one);
+        block.q_4().push(P::ScalarField::zero());
+        block.q_c().push(-addconst2);
+        block.q_arith().push(one);
+
+        block.q_m().push(P::ScalarField::zero());
+        block.q_1().push(-x_mulconst3);
+        block.q_2().push(y_sign * y_mulconst3);
+        block.q_3().push(one);
+        block.q_4().push(P::ScalarField::zero());
+        block.q_c().push(-addconst3);
+        block.q_arith().push(one);
+
+        for _ in 0..4 {
+            block.q_delta_range().push(P::ScalarField::zero());
+            block.q_lookup_type().push(P::ScalarField::zero());
+            block.q_elliptic().push(P::ScalarField::zero());
+            block.q_aux().push(P::ScalarField::zero());
+            block.q_poseidon2_external().push(P::ScalarField::zero());
+            block.q_poseidon2_internal().push(P::ScalarField::zero());
+        }
+        self.check_selector_length_consistency();
+        self.num_gates += 4;
+        [z_0, z_1, z_2, z_3, z_p]
+    }
+
     fn assign_tag(&mut self, variable_index: u32, tag: u32) {
         assert!(
             tag <= self.current_tag,
@@ -2807,7 +3803,7 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
         });
     }
 
-    fn create_bool_gate(&mut self, variable_index: u32) {
+    pub(crate) fn create_bool_gate(&mut self, variable_index: u32) {
         self.is_valid_variable(variable_index as usize);
 
         self.blocks.arithmetic.populate_wires(
@@ -2850,4 +3846,105 @@ impl<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> GenericUltraCi
         self.check_selector_length_consistency();
         self.num_gates += 1;
     }
+
+    pub(crate) fn create_ecc_add_gate(&mut self, inp: &EccAddGate<P::ScalarField>) {
+        // /**
+        //  * gate structure:
+        //  * | 1  | 2  | 3  | 4  |
+        //  * | -- | x1 | y1 | -- |
+        //  * | x2 | x3 | y3 | y2 |
+        //  * we can chain successive ecc_add_gates if x3 y3 of previous gate equals x1 y1 of current gate
+        //  **/
+        self.assert_valid_variables(&[inp.x1, inp.x2, inp.x3, inp.y1, inp.y2, inp.y3]);
+
+        let block = &mut self.blocks.elliptic;
+        let size = block.len();
+        let can_fuse_into_previous_gate = size > 0
+            && block.w_r()[size - 1] == inp.x1
+            && block.w_o()[size - 1] == inp.y1
+            && block.q_3()[size - 1].is_zero()
+            && block.q_4()[size - 1].is_zero()
+            && block.q_1()[size - 1].is_zero()
+            && block.q_arith()[size - 1].is_zero();
+
+        if can_fuse_into_previous_gate {
+            block.q_1()[size - 1] = inp.sign_coefficient;
+            block.q_elliptic()[size - 1] = P::ScalarField::one();
+        } else {
+            block.populate_wires(self.zero_idx, inp.x1, inp.y1, self.zero_idx);
+            block.q_3().push(P::ScalarField::zero());
+            block.q_4().push(P::ScalarField::zero());
+            block.q_1().push(inp.sign_coefficient);
+
+            block.q_arith().push(P::ScalarField::zero());
+            block.q_2().push(P::ScalarField::zero());
+            block.q_m().push(P::ScalarField::zero());
+            block.q_c().push(P::ScalarField::zero());
+            block.q_delta_range().push(P::ScalarField::zero());
+            block.q_lookup_type().push(P::ScalarField::zero());
+            block.q_elliptic().push(P::ScalarField::one());
+            block.q_aux().push(P::ScalarField::zero());
+            block.q_poseidon2_external().push(P::ScalarField::zero());
+            block.q_poseidon2_internal().push(P::ScalarField::zero());
+            self.check_selector_length_consistency();
+            self.num_gates += 1;
+        }
+        create_dummy_gate!(
+            self,
+            &mut self.blocks.elliptic,
+            inp.x2,
+            inp.x3,
+            inp.y3,
+            inp.y2,
+        );
+    }
+
+    pub(crate) fn create_ecc_dbl_gate(&mut self, inp: &EccDblGate) {
+        // /**
+        //  * gate structure:
+        //  * | 1  | 2  | 3  | 4  |
+        //  * | -  | x1 | y1 | -  |
+        //  * | -  | x3 | y3 | -  |
+        //  * we can chain an ecc_add_gate + an ecc_dbl_gate if x3 y3 of previous add_gate equals x1 y1 of current gate
+        //  * can also chain double gates together
+        //  **/
+        let block = &mut self.blocks.elliptic;
+        let size = block.len();
+        let can_fuse_into_previous_gate = size > 0
+            && block.w_r()[size - 1] == inp.x1
+            && block.w_o()[size - 1] == inp.y1
+            && block.q_arith()[size - 1].is_zero()
+            && block.q_lookup_type()[size - 1].is_zero()
+            && block.q_aux()[size - 1].is_zero();
+
+        if can_fuse_into_previous_gate {
+            block.q_elliptic()[size - 1] = P::ScalarField::one();
+            block.q_m()[size - 1] = P::ScalarField::one();
+        } else {
+            block.populate_wires(self.zero_idx, inp.x1, inp.y1, self.zero_idx);
+            block.q_elliptic().push(P::ScalarField::one());
+            block.q_m().push(P::ScalarField::one());
+            block.q_1().push(P::ScalarField::zero());
+            block.q_2().push(P::ScalarField::zero());
+            block.q_3().push(P::ScalarField::zero());
+            block.q_c().push(P::ScalarField::zero());
+            block.q_arith().push(P::ScalarField::zero());
+            block.q_4().push(P::ScalarField::zero());
+            block.q_delta_range().push(P::ScalarField::zero());
+            block.q_lookup_type().push(P::ScalarField::zero());
+            block.q_aux().push(P::ScalarField::zero());
+            block.q_poseidon2_external().push(P::ScalarField::zero());
+            block.q_poseidon2_internal().push(P::ScalarField::zero());
+            self.check_selector_length_consistency();
+            self.num_gates += 1;
+        }
+        create_dummy_gate!(
+            self,
+            &mut self.blocks.elliptic,
+            self.zero_idx,
+            inp.x3,
+            inp.y3,
+            self.zero_idx,
+        );
+    }
 }
@@ -36,6 +36,9 @@ pub enum HonkProofError {
     /// Expected Public Witness, Shared received
     #[error("Expected Public Witness, Shared received")]
     ExpectedPublicWitness,
+    /// A value that has to be inverted is zero
+    #[error("Cannot invert zero")]
+    InverseOfZero,
     #[error(transparent)]
     IOError(#[from] std::io::Error),
 }
@@ -0,0 +1,1393 @@
+use super::types::{AddQuad, BoolCT, FieldCT, NonNativeFieldWitnesses};
+use crate::builder::GenericUltraCircuitBuilder;
+use ark_ec::pairing::Pairing;
+use ark_ff::{Field, One, PrimeField, Zero};
+use co_acvm::mpc::NoirWitnessExtensionProtocol;
+use num_bigint::BigUint;
+use std::{array, io};
+
+const NUM_LIMBS: usize = 4;
+const NUM_LIMB_BITS: usize = 68;
+const MAX_UNREDUCED_LIMB_BITS: usize = 117;
+
+/// The moduli of the non-native fields supported by barretenberg's bigint constraints: the base and scalar fields of BN254, secp256k1 and secp256r1.
+const SUPPORTED_MODULI: [&str; 6] = [
+    "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47",
+    "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+    "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+    "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+    "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+    "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+];
+
+/// Returns the number of bits of a value, where zero has one bit (like get_msb() + 1 in barretenberg).
+fn num_bits(value: &BigUint) -> usize {
+    (value.bits() as usize).max(1)
+}
+
+/// Returns bits [start, end) of a value.
+fn slice(value: &BigUint, start: usize, end: usize) -> BigUint {
+    (value >> start) & ((BigUint::one() << (end - start)) - 1u64)
+}
+
+/// Multiplies a field by a constant without adding a gate.
+fn scale<F: PrimeField>(field: &FieldCT<F>, scalar: F) -> FieldCT<F> {
+    let mut result = field.to_owned();
+    result.additive_constant *= scalar;
+    if !result.is_constant() {
+        result.multiplicative_constant *= scalar;
+    }
+    result
+}
+
+/// Returns 2^(NUM_LIMB_BITS * i) as a field element.
+fn shift<F: PrimeField>(i: usize) -> F {
+    F::from(BigUint::one() << (NUM_LIMB_BITS * i))
+}
+
+/// The parameters of the non-native field of a [BigFieldCT].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct BigFieldParams {
+    pub(crate) modulus: BigUint,
+    num_last_limb_bits: usize,
+}
+
+impl BigFieldParams {
+    pub(crate) fn new(modulus: BigUint) -> io::Result<Self> {
+        let supported = SUPPORTED_MODULI.iter().any(|supported| {
+            BigUint::parse_bytes(supported.as_bytes(), 16).expect("valid hex") == modulus
+        });
+        if !supported {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("BigInt modulus {modulus} is not supported"),
+            ));
+        }
+        let num_last_limb_bits = modulus.bits() as usize - NUM_LIMB_BITS * (NUM_LIMBS - 1);
+        Ok(Self {
+            modulus,
+            num_last_limb_bits,
+        })
+    }
+
+    fn default_maximum_limb() -> BigUint {
+        (BigUint::one() << NUM_LIMB_BITS) - 1u64
+    }
+
+    fn default_maximum_most_significant_limb(&self) -> BigUint {
+        (BigUint::one() << self.num_last_limb_bits) - 1u64
+    }
+
+    fn default_maximum_remainder(&self) -> BigUint {
+        (BigUint::one() << (NUM_LIMB_BITS * (NUM_LIMBS - 1) + self.num_last_limb_bits)) - 1u64
+    }
+
+    fn maximum_unreduced_limb_value() -> BigUint {
+        BigUint::one() << MAX_UNREDUCED_LIMB_BITS
+    }
+
+    /// The product of the binary basis modulus 2^272 and the prime basis modulus, i.e., the modulus of the circuit.
+    fn maximum_crt_product<F: PrimeField>() -> BigUint {
+        let modulus: BigUint = F::MODULUS.into();
+        modulus << (NUM_LIMB_BITS * NUM_LIMBS)
+    }
+
+    fn maximum_unreduced_value<F: PrimeField>() -> BigUint {
+        let maximum_product_bits = Self::maximum_crt_product::<F>().bits() as usize - 1;
+        (BigUint::one() << ((maximum_product_bits - 1) / 2)) - 1u64
+    }
+
+    /// The limbs of -p modulo 2^272.
+    fn neg_modulus_limbs(&self) -> [BigUint; NUM_LIMBS] {
+        let neg_modulus = (BigUint::one() << (NUM_LIMB_BITS * NUM_LIMBS)) - &self.modulus;
+        array::from_fn(|i| slice(&neg_modulus, NUM_LIMB_BITS * i, NUM_LIMB_BITS * (i + 1)))
+    }
+}
+
+/// A limb of the binary basis of a [BigFieldCT] together with an upper bound of its value.
+#[derive(Clone, Debug)]
+pub(crate) struct Limb<F: PrimeField> {
+    pub(crate) element: FieldCT<F>,
+    pub(crate) maximum_value: BigUint,
+}
+
+impl<F: PrimeField> Limb<F> {
+    fn new(element: FieldCT<F>) -> Self {
+        Self::with_maximum(element, BigFieldParams::default_maximum_limb())
+    }
+
+    /// The maximum of a constant limb is always its value + 1.
+    fn with_maximum(element: FieldCT<F>, maximum_value: BigUint) -> Self {
+        let maximum_value = if element.is_constant() {
+            let value: BigUint = element.additive_constant.into();
+            value + 1u64
+        } else {
+            maximum_value
+        };
+        Self {
+            element,
+            maximum_value,
+        }
+    }
+}
+
+/// An element of a non-native field in the circuit, i.e., the port of barretenberg's bigfield. The value is given by four binary basis limbs of 68 bits each, which may overflow up to the maximum values tracked alongside, and its residue modulo the circuit field in the prime basis limb.
+#[derive(Clone, Debug)]
+pub(crate) struct BigFieldCT<F: PrimeField> {
+    pub(crate) binary_basis_limbs: [Limb<F>; NUM_LIMBS],
+    pub(crate) prime_basis_limb: FieldCT<F>,
+    pub(crate) params: BigFieldParams,
+}
+
+impl<F: PrimeField> BigFieldCT<F> {
+    pub(crate) fn from_constant(value: &BigUint, params: BigFieldParams) -> Self {
+        let binary_basis_limbs = array::from_fn(|i| {
+            let end = if i == NUM_LIMBS - 1 {
+                NUM_LIMB_BITS * NUM_LIMBS
+            } else {
+                NUM_LIMB_BITS * (i + 1)
+            };
+            Limb::new(FieldCT::from(F::from(slice(value, NUM_LIMB_BITS * i, end))))
+        });
+        Self {
+            binary_basis_limbs,
+            prime_basis_limb: FieldCT::from(F::from(value.to_owned())),
+            params,
+        }
+    }
+
+    pub(crate) fn zero(params: BigFieldParams) -> Self {
+        Self::from_constant(&BigUint::zero(), params)
+    }
+
+    pub(crate) fn one(params: BigFieldParams) -> Self {
+        Self::from_constant(&BigUint::one(), params)
+    }
+
+    /// A constant multiple of the modulus which is larger than the maximum unreduced value. Adding it to an element allows subtracting any unreduced element without underflow.
+    fn unreduced_zero(params: BigFieldParams) -> Self {
+        let modulus = &params.modulus;
+        let multiple_of_modulus =
+            (BigFieldParams::maximum_unreduced_value::<F>() / modulus + 1u64) * modulus;
+        let msb = multiple_of_modulus.bits() as usize - 1;
+        let binary_basis_limbs = array::from_fn(|i| {
+            let end = if i == NUM_LIMBS - 1 {
+                msb + 1
+            } else {
+                NUM_LIMB_BITS * (i + 1)
+            };
+            Limb::new(FieldCT::from(F::from(slice(
+                &multiple_of_modulus,
+                NUM_LIMB_BITS * i,
+                end,
+            ))))
+        });
+        Self {
+            binary_basis_limbs,
+            prime_basis_limb: FieldCT::from(F::from(multiple_of_modulus)),
+            params,
+        }
+    }
+
+    pub(crate) fn is_constant(&self) -> bool {
+        self.prime_basis_limb.is_constant()
+    }
+
+    /// Returns the value of a constant element, which is not necessarily reduced.
+    fn get_constant_value(&self) -> BigUint {
+        assert!(self.is_constant());
+        self.binary_basis_limbs
+            .iter()
+            .rev()
+            .fold(BigUint::zero(), |acc, limb| {
+                let value: BigUint = limb.element.additive_constant.into();
+                (acc << NUM_LIMB_BITS) + value
+            })
+    }
+
+    /// Returns the values of the binary basis limbs, where the value of the element is the sum of limb_i * 2^(68 * i).
+    fn get_limb_values<P: Pairing<ScalarField = F>, T: NoirWitnessExtensionProtocol<F>>(
+        &self,
+        builder: &GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> Vec<T::AcvmType> {
+        self.binary_basis_limbs
+            .iter()
+            .map(|limb| limb.element.get_value(builder, driver))
+            .collect()
+    }
+
+    fn get_maximum_value(&self) -> BigUint {
+        self.binary_basis_limbs
+            .iter()
+            .rev()
+            .fold(BigUint::zero(), |acc, limb| {
+                (acc << NUM_LIMB_BITS) + &limb.maximum_value
+            })
+    }
+
+    /// Constructs an element from four limbs without range constraints.
+    fn unsafe_construct_from_limbs<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<F>,
+    >(
+        limbs: [FieldCT<F>; NUM_LIMBS],
+        can_overflow: bool,
+        params: BigFieldParams,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> Self {
+        let [limb_0, limb_1, limb_2, limb_3] = limbs;
+        let limb_3 = if can_overflow {
+            Limb::new(limb_3)
+        } else {
+            Limb::with_maximum(limb_3, params.default_maximum_most_significant_limb())
+        };
+        let binary_basis_limbs = [
+            Limb::new(limb_0),
+            Limb::new(limb_1),
+            Limb::new(limb_2),
+            limb_3,
+        ];
+        let prime_basis_limb = scale(&binary_basis_limbs[3].element, shift(3))
+            .add_two(
+                &scale(&binary_basis_limbs[2].element, shift(2)),
+                &scale(&binary_basis_limbs[1].element, shift(1)),
+                builder,
+                driver,
+            )
+            .add(&binary_basis_limbs[0].element, builder, driver);
+        Self {
+            binary_basis_limbs,
+            prime_basis_limb,
+            params,
+        }
+    }
+
+    /// Constructs an element from 32 big-endian bytes, which are expected to be range constrained already.
+    pub(crate) fn from_be_bytes<P: Pairing<ScalarField = F>, T: NoirWitnessExtensionProtocol<F>>(
+        bytes: &[FieldCT<F>],
+        params: BigFieldParams,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<Self> {
+        assert_eq!(bytes.len(), 32);
+
+        let hi_8_bytes = Self::field_from_bytes(&bytes[0..6], builder, driver);
+        let mid_split_byte = Self::field_from_bytes(&bytes[6..7], builder, driver);
+        let mid_8_bytes = Self::field_from_bytes(&bytes[7..15], builder, driver);
+
+        let lo_8_bytes = Self::field_from_bytes(&bytes[15..23], builder, driver);
+        let lo_split_byte = Self::field_from_bytes(&bytes[23..24], builder, driver);
+        let lolo_8_bytes = Self::field_from_bytes(&bytes[24..32], builder, driver);
+
+        let (limb_0, limb_1) = Self::reconstruct_two_limbs(
+            &lolo_8_bytes,
+            &lo_8_bytes,
+            &lo_split_byte,
+            builder,
+            driver,
+        )?;
+        let (limb_2, limb_3) = Self::reconstruct_two_limbs(
+            &mid_8_bytes,
+            &hi_8_bytes,
+            &mid_split_byte,
+            builder,
+            driver,
+        )?;
+
+        let mut result = Self::unsafe_construct_from_limbs(
+            [limb_0, limb_1, limb_2, limb_3],
+            true,
+            params,
+            builder,
+            driver,
+        );
+        let num_last_limb_bits = 256 - NUM_LIMB_BITS * (NUM_LIMBS - 1);
+        result.binary_basis_limbs[3].maximum_value = BigUint::one() << num_last_limb_bits;
+        Ok(result)
+    }
+
+    /// Composes the 64 bits of the lower bytes and the lower nibble of the split byte into the lower limb, and the upper nibble of the split byte and the 48 or 64 bits of the upper bytes into the upper limb.
+    fn reconstruct_two_limbs<P: Pairing<ScalarField = F>, T: NoirWitnessExtensionProtocol<F>>(
+        lo_bytes: &FieldCT<F>,
+        hi_bytes: &FieldCT<F>,
+        split_byte: &FieldCT<F>,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<(FieldCT<F>, FieldCT<F>)> {
+        let (lo_nibble, hi_nibble) = Self::split_byte_into_nibbles(split_byte, builder, driver)?;
+        let hi_limb = hi_nibble.add(&scale(hi_bytes, F::from(16u64)), builder, driver);
+        let lo_limb = lo_bytes.add(
+            &scale(&lo_nibble, F::from(BigUint::one() << 64)),
+            builder,
+            driver,
+        );
+        Ok((lo_limb, hi_limb))
+    }
+
+    fn split_byte_into_nibbles<P: Pairing<ScalarField = F>, T: NoirWitnessExtensionProtocol<F>>(
+        split_byte: &FieldCT<F>,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<(FieldCT<F>, FieldCT<F>)> {
+        let value = split_byte.get_value(builder, driver);
+        let nibbles =
+            GenericUltraCircuitBuilder::<P, T>::decompose_into_chunks(driver, value, 8, 4)?;
+        let [lo_nibble, hi_nibble]: [T::AcvmType; 2] =
+            nibbles.try_into().expect("a byte has two nibbles");
+        let lo_nibble = FieldCT::from_witness(lo_nibble, builder);
+        let hi_nibble = FieldCT::from_witness(hi_nibble, builder);
+        lo_nibble.create_range_constraint(4, builder, driver)?;
+        hi_nibble.create_range_constraint(4, builder, driver)?;
+
+        let sum = lo_nibble.add(&scale(&hi_nibble, F::from(16u64)), builder, driver);
+        sum.assert_equal(split_byte, builder, driver);
+        Ok((lo_nibble, hi_nibble))
+    }
+
+    /// Composes big-endian bytes into a field element (barretenberg's conversion of a byte_array into a field_t).
+    fn field_from_bytes<P: Pairing<ScalarField = F>, T: NoirWitnessExtensionProtocol<F>>(
+        bytes: &[FieldCT<F>],
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> FieldCT<F> {
+        let num_bytes = bytes.len();
+        let mut result = FieldCT::from(F::zero());
+        for (i, byte) in bytes.iter().enumerate() {
+            let scaling_factor = F::from(BigUint::one() << (8 * (num_bytes - i - 1)));
+            result = result.add(&scale(byte, scaling_factor), builder, driver);
+        }
+        result.normalize(builder, driver)
+    }
+
+    /// Decomposes a field element into num_bytes range constrained big-endian bytes (barretenberg's byte_array constructor from a field_t).
+    pub(crate) fn field_to_bytes<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<F>,
+    >(
+        input: &FieldCT<F>,
+        num_bytes: usize,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<Vec<FieldCT<F>>> {
+        if input.is_constant() {
+            let value: BigUint = input.additive_constant.into();
+            return Ok((0..num_bytes)
+                .map(|i| {
+                    let start = 8 * (num_bytes - i - 1);
+                    FieldCT::from(F::from(slice(&value, start, start + 8)))
+                })
+                .collect());
+        }
+
+        let value = input.get_value(builder, driver);
+        let bytes = GenericUltraCircuitBuilder::<P, T>::decompose_into_chunks(
+            driver,
+            value,
+            8 * num_bytes,
+            8,
+        )?;
+        let mut validator = FieldCT::from(F::zero());
+        let mut result = Vec::with_capacity(num_bytes);
+        for (i, byte) in bytes.into_iter().rev().enumerate() {
+            let byte = FieldCT::from_witness(byte, builder);
+            byte.create_range_constraint(8, builder, driver)?;
+            let scaling_factor = F::from(BigUint::one() << (8 * (num_bytes - i - 1)));
+            validator = validator.add(&scale(&byte, scaling_factor), builder, driver);
+            result.push(byte);
+        }
+        validator.assert_equal(input, builder, driver);
+        Ok(result)
+    }
+
+    /// Returns the 32 big-endian bytes of the element, which has to be reduced.
+    pub(crate) fn to_byte_array<P: Pairing<ScalarField = F>, T: NoirWitnessExtensionProtocol<F>>(
+        &self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<Vec<FieldCT<F>>> {
+        let lo = self.binary_basis_limbs[0].element.add(
+            &scale(&self.binary_basis_limbs[1].element, shift(1)),
+            builder,
+            driver,
+        );
+        let hi = self.binary_basis_limbs[2].element.add(
+            &scale(&self.binary_basis_limbs[3].element, shift(1)),
+            builder,
+            driver,
+        );
+        // this only works if NUM_LIMB_BITS * 2 is divisible by 8
+        let num_lo_bytes = NUM_LIMB_BITS / 4;
+        let mut result = Self::field_to_bytes(&hi, 32 - num_lo_bytes, builder, driver)?;
+        result.extend(Self::field_to_bytes(&lo, num_lo_bytes, builder, driver)?);
+        Ok(result)
+    }
+
+    /// Constructs a range constrained element from a lower value of 136 bits and an upper value of the remaining bits.
+    fn from_lo_hi<P: Pairing<ScalarField = F>, T: NoirWitnessExtensionProtocol<F>>(
+        low_bits_in: &FieldCT<F>,
+        high_bits_in: &FieldCT<F>,
+        params: BigFieldParams,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<Self> {
+        let mut split = |input: &FieldCT<F>, num_limb_bits: usize| {
+            if input.is_constant() {
+                let value: BigUint = input.additive_constant.into();
+                return io::Result::Ok((
+                    FieldCT::from(F::from(slice(&value, 0, NUM_LIMB_BITS))),
+                    FieldCT::from(F::from(slice(&value, NUM_LIMB_BITS, num_limb_bits))),
+                ));
+            }
+            let normalized = input.normalize(builder, driver);
+            let [lo_idx, hi_idx] = builder.decompose_non_native_field_double_width_limb(
+                driver,
+                normalized.witness_index,
+                num_limb_bits,
+            )?;
+            let lo = FieldCT::from_witness_index(lo_idx);
+            let hi = FieldCT::from_witness_index(hi_idx);
+            FieldCT::evaluate_linear_identity(
+                input,
+                &lo.neg(),
+                &scale(&hi.neg(), shift(1)),
+                &FieldCT::from(F::zero()),
+                builder,
+            );
+            Ok((lo, hi))
+        };
+        let (limb_0, limb_1) = split(low_bits_in, NUM_LIMB_BITS * 2)?;
+        let (limb_2, limb_3) = split(high_bits_in, NUM_LIMB_BITS + params.num_last_limb_bits)?;
+
+        let binary_basis_limbs = [
+            Limb::new(limb_0),
+            Limb::new(limb_1),
+            Limb::new(limb_2),
+            Limb::with_maximum(limb_3, params.default_maximum_most_significant_limb()),
+        ];
+        let prime_basis_limb = low_bits_in.add(&scale(high_bits_in, shift(2)), builder, driver);
+        Ok(Self {
+            binary_basis_limbs,
+            prime_basis_limb,
+            params,
+        })
+    }
+
+    /// Creates a range constrained element from the witnesses of its four limbs of 68 bits each. If maximum_bitlength is not zero, it bounds the value instead of the modulus.
+    fn create_from_limbs_as_witness<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<F>,
+    >(
+        limbs: Vec<T::AcvmType>,
+        maximum_bitlength: usize,
+        params: BigFieldParams,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<Self> {
+        assert!(maximum_bitlength == 0 || maximum_bitlength > NUM_LIMB_BITS * (NUM_LIMBS - 1));
+        let limbs: [T::AcvmType; NUM_LIMBS] = limbs.try_into().expect("four limbs");
+        let mut prime_value = limbs[0].to_owned();
+        for (i, limb) in limbs.iter().enumerate().skip(1) {
+            let term = driver.mul_with_public(shift(i), limb.to_owned());
+            driver.add_assign(&mut prime_value, term);
+        }
+        let limb_indices = limbs.map(|limb| builder.add_variable(limb));
+        let prime_index = builder.add_variable(prime_value);
+
+        // evaluate the prime basis limb with an addition gate that taps into the fourth wire of the next gate
+        builder.create_big_add_gate(
+            &AddQuad {
+                a: limb_indices[1],
+                b: limb_indices[2],
+                c: limb_indices[3],
+                d: prime_index,
+                a_scaling: shift(1),
+                b_scaling: shift(2),
+                c_scaling: shift(3),
+                d_scaling: -F::one(),
+                const_scaling: F::zero(),
+            },
+            true,
+        );
+        builder.create_arithmetic_dummy_gate(
+            builder.zero_idx,
+            builder.zero_idx,
+            builder.zero_idx,
+            limb_indices[0],
+        );
+
+        let num_last_limb_bits = if maximum_bitlength > 0 {
+            maximum_bitlength - NUM_LIMB_BITS * (NUM_LIMBS - 1)
+        } else {
+            params.num_last_limb_bits
+        };
+        let binary_basis_limbs = [
+            Limb::new(FieldCT::from_witness_index(limb_indices[0])),
+            Limb::new(FieldCT::from_witness_index(limb_indices[1])),
+            Limb::new(FieldCT::from_witness_index(limb_indices[2])),
+            Limb::with_maximum(
+                FieldCT::from_witness_index(limb_indices[3]),
+                (BigUint::one() << num_last_limb_bits) - 1u64,
+            ),
+        ];
+        builder.range_constrain_two_limbs(
+            driver,
+            limb_indices[0],
+            limb_indices[1],
+            NUM_LIMB_BITS,
+            NUM_LIMB_BITS,
+        )?;
+        builder.range_constrain_two_limbs(
+            driver,
+            limb_indices[2],
+            limb_indices[3],
+            NUM_LIMB_BITS,
+            num_last_limb_bits,
+        )?;
+        Ok(Self {
+            binary_basis_limbs,
+            prime_basis_limb: FieldCT::from_witness_index(prime_index),
+            params,
+        })
+    }
+
+    /// Reduces a constant element modulo the modulus, or reduces a witness element if its maximum value or any of its limbs might overflow in the next operation.
+    pub(crate) fn reduction_check<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<F>,
+    >(
+        &mut self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<()> {
+        if self.is_constant() {
+            let reduced = self.get_constant_value() % &self.params.modulus;
+            *self = Self::from_constant(&reduced, self.params.to_owned());
+            return Ok(());
+        }
+
+        let maximum_unreduced_limb_value = BigFieldParams::maximum_unreduced_limb_value();
+        let limb_overflow = self
+            .binary_basis_limbs
+            .iter()
+            .any(|limb| limb.maximum_value > maximum_unreduced_limb_value);
+        if self.get_maximum_value() > BigFieldParams::maximum_unreduced_value::<F>()
+            || limb_overflow
+        {
+            self.self_reduce(builder, driver)?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the element by its remainder modulo the modulus.
+    pub(crate) fn self_reduce<P: Pairing<ScalarField = F>, T: NoirWitnessExtensionProtocol<F>>(
+        &mut self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<()> {
+        if self.is_constant() {
+            return Ok(());
+        }
+        let modulus = self.params.modulus.to_owned();
+        let limbs = self.get_limb_values(builder, driver);
+        let (quotient_limbs, remainder_limbs) = driver.bigint_quotient_remainder(
+            &limbs,
+            &[T::AcvmType::from(F::one())],
+            &[],
+            &[],
+            &modulus,
+            NUM_LIMB_BITS,
+            NUM_LIMBS,
+        )?;
+
+        let maximum_quotient_size = self.get_maximum_value() / &modulus;
+        let mut maximum_quotient_bits = num_bits(&maximum_quotient_size);
+        if maximum_quotient_bits & 1 == 1 {
+            maximum_quotient_bits += 1;
+        }
+        assert!(maximum_quotient_bits <= NUM_LIMB_BITS);
+
+        // The quotient fits into the lowest limb
+        let mut quotient_value = quotient_limbs[0].to_owned();
+        for (i, limb) in quotient_limbs.into_iter().enumerate().skip(1) {
+            let term = driver.mul_with_public(shift(i), limb);
+            driver.add_assign(&mut quotient_value, term);
+        }
+        let quotient_limb_index = builder.add_variable(quotient_value);
+        let quotient_limb = FieldCT::from_witness_index(quotient_limb_index);
+        builder.decompose_into_default_range(
+            driver,
+            quotient_limb_index,
+            maximum_quotient_bits as u64,
+            GenericUltraCircuitBuilder::<P, T>::DEFAULT_PLOOKUP_RANGE_BITNUM as u64,
+        )?;
+        let zero_idx = builder.zero_idx;
+        let zero = || Limb::new(FieldCT::from_witness_index(zero_idx));
+        let quotient = Self {
+            binary_basis_limbs: [
+                Limb::with_maximum(
+                    quotient_limb.to_owned(),
+                    BigUint::one() << maximum_quotient_bits,
+                ),
+                zero(),
+                zero(),
+                zero(),
+            ],
+            prime_basis_limb: quotient_limb,
+            params: self.params.to_owned(),
+        };
+
+        let mut compose = |lo: &T::AcvmType, hi: &T::AcvmType| {
+            let mut value = driver.mul_with_public(shift(1), hi.to_owned());
+            driver.add_assign(&mut value, lo.to_owned());
+            value
+        };
+        let remainder_lo = compose(&remainder_limbs[0], &remainder_limbs[1]);
+        let remainder_hi = compose(&remainder_limbs[2], &remainder_limbs[3]);
+        let remainder_lo = FieldCT::from_witness(remainder_lo, builder);
+        let remainder_hi = FieldCT::from_witness(remainder_hi, builder);
+        let remainder = Self::from_lo_hi(
+            &remainder_lo,
+            &remainder_hi,
+            self.params.to_owned(),
+            builder,
+            driver,
+        )?;
+
+        Self::unsafe_evaluate_multiply_add(
+            self,
+            &Self::one(self.params.to_owned()),
+            &[],
+            &quotient,
+            &[remainder.to_owned()],
+            builder,
+            driver,
+        )?;
+        *self = remainder;
+        Ok(())
+    }
+
+    /// Reduces the element and constrains it to be smaller than the modulus, such that its limbs are unique.
+    pub(crate) fn assert_is_in_field<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<F>,
+    >(
+        &mut self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<()> {
+        if self.is_constant() {
+            return Ok(());
+        }
+        // this method in particular enforces limb vals are < 2^68 - needed for logic described below
+        self.self_reduce(builder, driver)?;
+
+        let modulus_minus_one = &self.params.modulus - 1u64;
+        let modulus_limbs: [BigUint; NUM_LIMBS] = array::from_fn(|i| {
+            slice(
+                &modulus_minus_one,
+                NUM_LIMB_BITS * i,
+                NUM_LIMB_BITS * (i + 1),
+            )
+        });
+
+        // The limbs of (p - 1) - value determine the borrows, so we do not have to compare the limbs
+        let limbs = self.get_limb_values(builder, driver);
+        let modulus_limb_values = modulus_limbs
+            .iter()
+            .map(|limb| T::AcvmType::from(F::from(limb.to_owned())))
+            .collect::<Vec<_>>();
+        let (_, difference_limbs) = driver.bigint_quotient_remainder(
+            &modulus_limb_values,
+            &[T::AcvmType::from(F::one())],
+            &[],
+            &limbs,
+            &self.params.modulus,
+            NUM_LIMB_BITS,
+            NUM_LIMBS,
+        )?;
+        let shift_1 = shift::<F>(1);
+        let inverse_shift_1 = shift_1.inverse().expect("non-zero");
+        let mut borrows: Vec<FieldCT<F>> = Vec::with_capacity(NUM_LIMBS - 1);
+        let mut previous_borrow = T::AcvmType::from(F::zero());
+        for i in 0..NUM_LIMBS - 1 {
+            let mut borrow = driver.add(difference_limbs[i].to_owned(), limbs[i].to_owned());
+            driver.add_assign(&mut borrow, previous_borrow);
+            driver.add_assign_with_public(-F::from(modulus_limbs[i].to_owned()), &mut borrow);
+            let borrow = driver.mul_with_public(inverse_shift_1, borrow);
+            borrows.push(FieldCT::from(&BoolCT::from_witness(
+                borrow.to_owned(),
+                builder,
+            )));
+            previous_borrow = borrow;
+        }
+
+        // The way we use borrows here ensures that we are checking that modulus - binary_basis > 0.
+        // We check that the result in each limb is > 0.
+        // If the modulus part in this limb is smaller, we simply borrow the value from the higher limb.
+        // The prover can rearrange the borrows the way they like. The important thing is that the borrows are
+        // constrained.
+        for (i, modulus_limb) in modulus_limbs.into_iter().enumerate() {
+            let mut r = FieldCT::from(F::from(modulus_limb)).sub(
+                &self.binary_basis_limbs[i].element,
+                builder,
+                driver,
+            );
+            if i < NUM_LIMBS - 1 {
+                r = r.add(&scale(&borrows[i], shift_1), builder, driver);
+            }
+            if i > 0 {
+                r = r.sub(&borrows[i - 1], builder, driver);
+            }
+            let r = r.normalize(builder, driver);
+            builder.decompose_into_default_range(
+                driver,
+                r.witness_index,
+                NUM_LIMB_BITS as u64,
+                GenericUltraCircuitBuilder::<P, T>::DEFAULT_PLOOKUP_RANGE_BITNUM as u64,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Returns whether the product of the elements with the given maximum values needs a reduction first, and otherwise the number of bits of the quotient.
+    fn get_quotient_reduction_info(
+        params: &BigFieldParams,
+        as_max: &[BigUint],
+        bs_max: &[BigUint],
+        to_add: &[Self],
+        remainders_max: &[BigUint],
+    ) -> (bool, usize) {
+        assert_eq!(as_max.len(), bs_max.len());
+        let to_add_max = to_add
+            .iter()
+            .map(|element| element.get_maximum_value())
+            .sum::<BigUint>();
+        let product_sum = as_max
+            .iter()
+            .zip(bs_max)
+            .map(|(a, b)| a * b)
+            .sum::<BigUint>()
+            + to_add_max;
+
+        // Check if the product sum can overflow the CRT modulus
+        let maximum_crt_product = BigFieldParams::maximum_crt_product::<F>();
+        if product_sum >= maximum_crt_product {
+            return (true, 0);
+        }
+
+        // Find the largest quotient q such that q * p + remainders < 2^272 * r
+        let remainders_sum = remainders_max.iter().sum::<BigUint>();
+        let num_quotient_bits =
+            ((maximum_crt_product - remainders_sum) / &params.modulus).bits() as usize - 2;
+
+        let maximum_quotient = product_sum / &params.modulus;
+        if maximum_quotient >= BigUint::one() << num_quotient_bits {
+            return (true, 0);
+        }
+        (false, num_quotient_bits)
+    }
+
+    pub(crate) fn add<P: Pairing<ScalarField = F>, T: NoirWitnessExtensionProtocol<F>>(
+        &mut self,
+        other: &mut Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<Self> {
+        self.reduction_check(builder, driver)?;
+        other.reduction_check(builder, driver)?;
+
+        let maximum_values: [BigUint; NUM_LIMBS] = array::from_fn(|i| {
+            &self.binary_basis_limbs[i].maximum_value + &other.binary_basis_limbs[i].maximum_value
+        });
+
+        if self.uses_non_native_field_gate(other) {
+            let limbs = array::from_fn(|i| {
+                let x = &self.binary_basis_limbs[i].element;
+                let y = &other.binary_basis_limbs[i].element;
+                (
+                    (x.witness_index, x.multiplicative_constant),
+                    (y.witness_index, y.multiplicative_constant),
+                    x.additive_constant + y.additive_constant,
+                )
+            });
+            let limbp = (
+                self.prime_basis_limb.witness_index,
+                other.prime_basis_limb.witness_index,
+                self.prime_basis_limb.additive_constant + other.prime_basis_limb.additive_constant,
+            );
+            let output_witnesses = builder.evaluate_non_native_field_addition(driver, limbs, limbp);
+            return Ok(Self::from_output_witnesses(
+                output_witnesses,
+                maximum_values,
+                self.params.to_owned(),
+            ));
+        }
+
+        let mut result_limbs = Vec::with_capacity(NUM_LIMBS);
+        for (i, maximum_value) in maximum_values.into_iter().enumerate() {
+            let element = self.binary_basis_limbs[i].element.add(
+                &other.binary_basis_limbs[i].element,
+                builder,
+                driver,
+            );
+            result_limbs.push(Limb {
+                element,
+                maximum_value,
+            });
+        }
+        Ok(Self {
+            binary_basis_limbs: result_limbs.try_into().expect("four limbs"),
+            prime_basis_limb: self
+                .prime_basis_limb
+                .add(&other.prime_basis_limb, builder, driver),
+            params: self.params.to_owned(),
+        })
+    }
+
+    pub(crate) fn sub<P: Pairing<ScalarField = F>, T: NoirWitnessExtensionProtocol<F>>(
+        &mut self,
+        other: &mut Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<Self> {
+        self.reduction_check(builder, driver)?;
+        other.reduction_check(builder, driver)?;
+        let modulus = self.params.modulus.to_owned();
+
+        if self.is_constant() && other.is_constant() {
+            let left = self.get_constant_value() % &modulus;
+            let right = other.get_constant_value() % &modulus;
+            let out = (left + &modulus - right) % &modulus;
+            return Ok(Self::from_constant(&out, self.params.to_owned()));
+        }
+
+        if other.is_constant() {
+            let right = other.get_constant_value() % &modulus;
+            let neg_right = (&modulus - right) % &modulus;
+            return self.add(
+                &mut Self::from_constant(&neg_right, self.params.to_owned()),
+                builder,
+                driver,
+            );
+        }
+
+        // Step 1: For each limb compute the maximum value we will have to borrow from the next significant limb
+        let one = BigUint::one();
+        let other_max = |i: usize| &other.binary_basis_limbs[i].maximum_value;
+        let limb_0_borrow_shift = num_bits(other_max(0)).max(NUM_LIMB_BITS);
+        let limb_1_maximum_value = other_max(1) + (&one << (limb_0_borrow_shift - NUM_LIMB_BITS));
+        let limb_1_borrow_shift = num_bits(&limb_1_maximum_value).max(NUM_LIMB_BITS);
+        let limb_2_maximum_value = other_max(2) + (&one << (limb_1_borrow_shift - NUM_LIMB_BITS));
+        let limb_2_borrow_shift = num_bits(&limb_2_maximum_value).max(NUM_LIMB_BITS);
+        let limb_3_maximum_value = other_max(3) + (&one << (limb_2_borrow_shift - NUM_LIMB_BITS));
+
+        // Step 2: Compute the multiple of the modulus we add to this, such that the result is positive
+        let constant_to_add_factor =
+            ((limb_3_maximum_value << (NUM_LIMB_BITS * 3)) / &modulus) + 1u64;
+        let constant_to_add = constant_to_add_factor * &modulus;
+
+        // Step 3: Compute the offsets which move the borrows between the limbs
+        let t0 = &one << limb_0_borrow_shift;
+        let t1 = (&one << limb_1_borrow_shift) - (&one << (limb_0_borrow_shift - NUM_LIMB_BITS));
+        let t2 = (&one << limb_2_borrow_shift) - (&one << (limb_1_borrow_shift - NUM_LIMB_BITS));
+        let t3 = &one << (limb_2_borrow_shift - NUM_LIMB_BITS);
+
+        let constant_limb =
+            |i: usize| slice(&constant_to_add, NUM_LIMB_BITS * i, NUM_LIMB_BITS * (i + 1));
+        let to_add = [
+            constant_limb(0) + t0,
+            constant_limb(1) + t1,
+            constant_limb(2) + t2,
+            constant_limb(3) - t3,
+        ];
+
+        let mut result_limbs = Vec::with_capacity(NUM_LIMBS);
+        for (limb, to_add) in self.binary_basis_limbs.iter().zip(to_add) {
+            result_limbs.push(Limb {
+                element: limb.element.add(
+                    &FieldCT::from(F::from(to_add.to_owned())),
+                    builder,
+                    driver,
+                ),
+                maximum_value: &limb.maximum_value + to_add,
+            });
+        }
+        let mut result_limbs: [Limb<F>; NUM_LIMBS] = result_limbs.try_into().expect("four limbs");
+        let constant_to_add_mod_r = F::from(constant_to_add);
+
+        let result = Self {
+            binary_basis_limbs: result_limbs.to_owned(),
+            prime_basis_limb: self.prime_basis_limb.to_owned(),
+            params: self.params.to_owned(),
+        };
+        if result.uses_non_native_field_gate(other) {
+            let limbs = array::from_fn(|i| {
+                let x = &result.binary_basis_limbs[i].element;
+                let y = &other.binary_basis_limbs[i].element;
+                (
+                    (
+                        x.witness_index,
+                        self.binary_basis_limbs[i].element.multiplicative_constant,
+                    ),
+                    (y.witness_index, y.multiplicative_constant),
+                    x.additive_constant - y.additive_constant,
+                )
+            });
+            let limbp = (
+                self.prime_basis_limb.witness_index,
+                other.prime_basis_limb.witness_index,
+                self.prime_basis_limb.additive_constant - other.prime_basis_limb.additive_constant
+                    + constant_to_add_mod_r,
+            );
+            let output_witnesses =
+                builder.evaluate_non_native_field_subtraction(driver, limbs, limbp);
+            return Ok(Self::from_output_witnesses(
+                output_witnesses,
+                result_limbs.map(|limb| limb.maximum_value),
+                self.params.to_owned(),
+            ));
+        }
+
+        for (limb, other_limb) in result_limbs.iter_mut().zip(other.binary_basis_limbs.iter()) {
+            limb.element = limb.element.sub(&other_limb.element, builder, driver);
+        }
+        let prime_basis_limb = self
+            .prime_basis_limb
+            .add(&FieldCT::from(constant_to_add_mod_r), builder, driver)
+            .sub(&other.prime_basis_limb, builder, driver);
+        Ok(Self {
+            binary_basis_limbs: result_limbs,
+            prime_basis_limb,
+            params: self.params.to_owned(),
+        })
+    }
+
+    /// Returns whether an addition or subtraction of the two elements can use the non-native field addition gates, which require that all limbs are distinct witnesses.
+    fn uses_non_native_field_gate(&self, other: &Self) -> bool {
+        if !self.prime_basis_limb.multiplicative_constant.is_one()
+            || !other.prime_basis_limb.multiplicative_constant.is_one()
+            || self.is_constant()
+            || other.is_constant()
+        {
+            return false;
+        }
+        let limb_is_constant = self
+            .binary_basis_limbs
+            .iter()
+            .chain(other.binary_basis_limbs.iter())
+            .any(|limb| limb.element.is_constant());
+        !limb_is_constant
+            && self.prime_basis_limb.witness_index != other.prime_basis_limb.witness_index
+    }
+
+    fn from_output_witnesses(
+        output_witnesses: [u32; NUM_LIMBS + 1],
+        maximum_values: [BigUint; NUM_LIMBS],
+        params: BigFieldParams,
+    ) -> Self {
+        let mut maximum_values = maximum_values.into_iter();
+        Self {
+            binary_basis_limbs: array::from_fn(|i| Limb {
+                element: FieldCT::from_witness_index(output_witnesses[i]),
+                maximum_value: maximum_values.next().expect("four limbs"),
+            }),
+            prime_basis_limb: FieldCT::from_witness_index(output_witnesses[NUM_LIMBS]),
+            params,
+        }
+    }
+
+    pub(crate) fn mul<P: Pairing<ScalarField = F>, T: NoirWitnessExtensionProtocol<F>>(
+        &mut self,
+        other: &mut Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<Self> {
+        self.reduction_check(builder, driver)?;
+        other.reduction_check(builder, driver)?;
+
+        if self.is_constant() && other.is_constant() {
+            let remainder =
+                (self.get_constant_value() * other.get_constant_value()) % &self.params.modulus;
+            return Ok(Self::from_constant(&remainder, self.params.to_owned()));
+        }
+
+        // Check if the product overflows the CRT modulus or the quotient can't be range constrained and reduce accordingly
+        let (reduction_required, num_quotient_bits) = Self::get_quotient_reduction_info(
+            &self.params,
+            &[self.get_maximum_value()],
+            &[other.get_maximum_value()],
+            &[],
+            &[self.params.default_maximum_remainder()],
+        );
+        if reduction_required {
+            if self.get_maximum_value() > other.get_maximum_value() {
+                self.self_reduce(builder, driver)?;
+            } else {
+                other.self_reduce(builder, driver)?;
+            }
+            return self.mul(other, builder, driver);
+        }
+
+        let lhs = self.get_limb_values(builder, driver);
+        let rhs = other.get_limb_values(builder, driver);
+        let (quotient, remainder) = driver.bigint_quotient_remainder(
+            &lhs,
+            &rhs,
+            &[],
+            &[],
+            &self.params.modulus,
+            NUM_LIMB_BITS,
+            NUM_LIMBS,
+        )?;
+        let quotient = Self::create_from_limbs_as_witness(
+            quotient,
+            num_quotient_bits,
+            self.params.to_owned(),
+            builder,
+            driver,
+        )?;
+        let remainder = Self::create_from_limbs_as_witness(
+            remainder,
+            0,
+            self.params.to_owned(),
+            builder,
+            driver,
+        )?;
+
+        Self::unsafe_evaluate_multiply_add(
+            self,
+            other,
+            &[],
+            &quotient,
+            &[remainder.to_owned()],
+            builder,
+            driver,
+        )?;
+        Ok(remainder)
+    }
+
+    /// Computes self / other, where the proof does not verify if other is zero.
+    pub(crate) fn div<P: Pairing<ScalarField = F>, T: NoirWitnessExtensionProtocol<F>>(
+        &mut self,
+        other: &mut Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<Self> {
+        Self::internal_div(self.to_owned(), other, true, builder, driver)
+    }
+
+    fn internal_div<P: Pairing<ScalarField = F>, T: NoirWitnessExtensionProtocol<F>>(
+        mut numerator: Self,
+        denominator: &mut Self,
+        check_for_zero: bool,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<Self> {
+        denominator.reduction_check(builder, driver)?;
+        numerator.reduction_check(builder, driver)?;
+        let params = denominator.params.to_owned();
+        let modulus = &params.modulus;
+
+        if numerator.is_constant() && denominator.is_constant() {
+            // a / b = c => c * b = a mod p, where Fermat's little theorem maps a zero denominator to zero
+            let inverse = denominator
+                .get_constant_value()
+                .modpow(&(modulus - 2u64), modulus);
+            let result = (numerator.get_constant_value() * inverse) % modulus;
+            return Ok(Self::from_constant(&result, params));
+        }
+
+        let unreduced_zero = Self::unreduced_zero(params.to_owned());
+        let (reduction_required, num_quotient_bits) = Self::get_quotient_reduction_info(
+            &params,
+            &[params.default_maximum_remainder()],
+            &[denominator.get_maximum_value()],
+            &[unreduced_zero.to_owned()],
+            &[numerator.get_maximum_value()],
+        );
+        if reduction_required {
+            denominator.self_reduce(builder, driver)?;
+            return Self::internal_div(numerator, denominator, check_for_zero, builder, driver);
+        }
+        // We do this after the quotient check, since this creates gates and we don't want to add them twice
+        if check_for_zero {
+            denominator.assert_is_not_equal(&Self::zero(params.to_owned()), builder, driver)?;
+        }
+
+        let numerator_values = numerator.get_limb_values(builder, driver);
+        let denominator_values = denominator.get_limb_values(builder, driver);
+        let inverse = driver.bigint_div_limbs(
+            &numerator_values,
+            &denominator_values,
+            modulus,
+            NUM_LIMB_BITS,
+            NUM_LIMBS,
+        )?;
+        let unreduced_zero_values = unreduced_zero.get_limb_values(builder, driver);
+        let (quotient, _) = driver.bigint_quotient_remainder(
+            &inverse,
+            &denominator_values,
+            &unreduced_zero_values,
+            &numerator_values,
+            modulus,
+            NUM_LIMB_BITS,
+            NUM_LIMBS,
+        )?;
+
+        let quotient = Self::create_from_limbs_as_witness(
+            quotient,
+            num_quotient_bits,
+            params.to_owned(),
+            builder,
+            driver,
+        )?;
+        let inverse =
+            Self::create_from_limbs_as_witness(inverse, 0, params.to_owned(), builder, driver)?;
+
+        Self::unsafe_evaluate_multiply_add(
+            denominator,
+            &inverse,
+            &[unreduced_zero],
+            &quotient,
+            &[numerator],
+            builder,
+            driver,
+        )?;
+        Ok(inverse)
+    }
+
+    /// Constrains self != other, where we only check that the values differ modulo the circuit field after subtracting all possible multiples of the modulus.
+    fn assert_is_not_equal<P: Pairing<ScalarField = F>, T: NoirWitnessExtensionProtocol<F>>(
+        &self,
+        other: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<()> {
+        let modulus = &self.params.modulus;
+        let get_overload_count = |maximum_value: &BigUint| {
+            let mut target = modulus.to_owned();
+            let mut overload_count = 0;
+            while &target <= maximum_value {
+                overload_count += 1;
+                target += modulus;
+            }
+            overload_count
+        };
+        let lhs_overload_count = get_overload_count(&self.get_maximum_value());
+        let rhs_overload_count = get_overload_count(&other.get_maximum_value());
+
+        // if a == b then a == b mod r, to save gates we only check the latter for all a' + p * q with q up to the overload count
+        let base_diff = self
+            .prime_basis_limb
+            .sub(&other.prime_basis_limb, builder, driver);
+        let mut diff = base_diff.to_owned();
+        let prime_basis = FieldCT::from(F::from(modulus.to_owned()));
+        let mut prime_basis_accumulator = prime_basis.to_owned();
+        for _ in 0..lhs_overload_count {
+            let factor = base_diff.sub(&prime_basis_accumulator, builder, driver);
+            diff = diff.multiply(&factor, builder, driver)?;
+            prime_basis_accumulator = prime_basis_accumulator.add(&prime_basis, builder, driver);
+        }
+        prime_basis_accumulator = prime_basis.to_owned();
+        for _ in 0..rhs_overload_count {
+            let factor = base_diff.add(&prime_basis_accumulator, builder, driver);
+            diff = diff.multiply(&factor, builder, driver)?;
+            prime_basis_accumulator = prime_basis_accumulator.add(&prime_basis, builder, driver);
+        }
+        diff.assert_is_not_zero(builder, driver)
+    }
+
+    /// Replaces the limbs of a constant element by fixed witnesses, since the non-native field gates require witnesses.
+    fn convert_constant_to_fixed_witness<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<F>,
+    >(
+        &self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+    ) -> Self {
+        let mut output = self.to_owned();
+        let mut fix = |field: &FieldCT<F>| {
+            FieldCT::from_witness_index(builder.put_constant_variable(field.additive_constant))
+        };
+        output.prime_basis_limb = fix(&self.prime_basis_limb);
+        for limb in output.binary_basis_limbs.iter_mut() {
+            limb.element = fix(&limb.element);
+        }
+        output
+    }
+
+    /// Evaluates left * to_mul + to_add = quotient * p + remainders, without range constraints on the quotient and the remainders.
+    fn unsafe_evaluate_multiply_add<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<F>,
+    >(
+        input_left: &Self,
+        input_to_mul: &Self,
+        to_add: &[Self],
+        input_quotient: &Self,
+        input_remainders: &[Self],
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<()> {
+        let params = &input_left.params;
+        let neg_modulus_limbs = params.neg_modulus_limbs();
+        let mut left = input_left.to_owned();
+        let mut to_mul = input_to_mul.to_owned();
+        let mut quotient = input_quotient.to_owned();
+        let mut remainders = input_remainders.to_vec();
+
+        let max = |element: &Self, i: usize| element.binary_basis_limbs[i].maximum_value.to_owned();
+        let max_b0 = max(&left, 1) * max(&to_mul, 0) + &neg_modulus_limbs[1] * max(&quotient, 0);
+        let max_b1 = max(&left, 0) * max(&to_mul, 1) + &neg_modulus_limbs[0] * max(&quotient, 1);
+        let max_c0 = max(&left, 1) * max(&to_mul, 1) + &neg_modulus_limbs[1] * max(&quotient, 1);
+        let max_c1 = max(&left, 2) * max(&to_mul, 0) + &neg_modulus_limbs[2] * max(&quotient, 0);
+        let max_c2 = max(&left, 0) * max(&to_mul, 2) + &neg_modulus_limbs[0] * max(&quotient, 2);
+        let max_d0 = max(&left, 3) * max(&to_mul, 0) + &neg_modulus_limbs[3] * max(&quotient, 0);
+        let max_d1 = max(&left, 2) * max(&to_mul, 1) + &neg_modulus_limbs[2] * max(&quotient, 1);
+        let max_d2 = max(&left, 1) * max(&to_mul, 2) + &neg_modulus_limbs[1] * max(&quotient, 2);
+        let max_d3 = max(&left, 0) * max(&to_mul, 3) + &neg_modulus_limbs[0] * max(&quotient, 3);
+        let max_r0 = max(&left, 0) * max(&to_mul, 0) + &neg_modulus_limbs[0] * max(&quotient, 0);
+
+        let max_r1 = max_b0 + max_b1;
+        let max_r2 = max_c0 + max_c1 + max_c2;
+        let max_r3 = max_d0 + max_d1 + max_d2 + max_d3;
+
+        let mut max_a0 = BigUint::zero();
+        let mut max_a1 = BigUint::zero();
+        for add in to_add {
+            max_a0 += max(add, 0) + (max(add, 1) << NUM_LIMB_BITS);
+            max_a1 += max(add, 2) + (max(add, 3) << NUM_LIMB_BITS);
+        }
+        let max_lo = max_r0 + (max_r1 << NUM_LIMB_BITS) + max_a0;
+        let max_hi = max_r2 + (max_r3 << NUM_LIMB_BITS) + max_a1;
+
+        let mut max_lo_bits = num_bits(&max_lo);
+        let mut max_hi_bits = num_bits(&max_hi);
+        if max_lo_bits & 1 == 1 {
+            max_lo_bits += 1;
+        }
+        if max_hi_bits & 1 == 1 {
+            max_hi_bits += 1;
+        }
+
+        // The non-native field gates require witnesses, hence we add constants as fixed witnesses
+        if left.is_constant() {
+            left = left.convert_constant_to_fixed_witness(builder);
+        }
+        if to_mul.is_constant() {
+            to_mul = to_mul.convert_constant_to_fixed_witness(builder);
+        }
+        if quotient.is_constant() {
+            quotient = quotient.convert_constant_to_fixed_witness(builder);
+        }
+        if remainders[0].is_constant() {
+            remainders[0] = remainders[0].convert_constant_to_fixed_witness(builder);
+        }
+
+        let shift_1 = shift::<F>(1);
+        let mut limb_0_accumulator = vec![remainders[0].binary_basis_limbs[0].element.to_owned()];
+        let mut limb_2_accumulator = vec![remainders[0].binary_basis_limbs[2].element.to_owned()];
+        let mut prime_limb_accumulator = vec![remainders[0].prime_basis_limb.to_owned()];
+        for remainder in remainders.iter().skip(1) {
+            limb_0_accumulator.push(remainder.binary_basis_limbs[0].element.to_owned());
+            limb_0_accumulator.push(scale(&remainder.binary_basis_limbs[1].element, shift_1));
+            limb_2_accumulator.push(remainder.binary_basis_limbs[2].element.to_owned());
+            limb_2_accumulator.push(scale(&remainder.binary_basis_limbs[3].element, shift_1));
+            prime_limb_accumulator.push(remainder.prime_basis_limb.to_owned());
+        }
+        for add in to_add {
+            limb_0_accumulator.push(add.binary_basis_limbs[0].element.neg());
+            limb_0_accumulator.push(scale(&add.binary_basis_limbs[1].element.neg(), shift_1));
+            limb_2_accumulator.push(add.binary_basis_limbs[2].element.neg());
+            limb_2_accumulator.push(scale(&add.binary_basis_limbs[3].element.neg(), shift_1));
+            prime_limb_accumulator.push(add.prime_basis_limb.neg());
+        }
+
+        let is_normalized = |field: &FieldCT<F>| {
+            field.additive_constant.is_zero() && field.multiplicative_constant.is_one()
+        };
+        let t0 = &remainders[0].binary_basis_limbs[1].element;
+        let t1 = &remainders[0].binary_basis_limbs[3].element;
+        let needs_normalize = !is_normalized(t0) || !is_normalized(t1);
+        if needs_normalize {
+            limb_0_accumulator.push(scale(t0, shift_1));
+            limb_2_accumulator.push(scale(t1, shift_1));
+        }
+
+        let zero = FieldCT::from_witness_index(builder.zero_idx);
+        let remainder_limbs = [
+            FieldCT::accumulate(&limb_0_accumulator, builder, driver),
+            if needs_normalize {
+                zero.to_owned()
+            } else {
+                t0.to_owned()
+            },
+            FieldCT::accumulate(&limb_2_accumulator, builder, driver),
+            if needs_normalize { zero } else { t1.to_owned() },
+        ];
+        let remainder_prime_limb = FieldCT::accumulate(&prime_limb_accumulator, builder, driver);
+
+        let mut normalized_indices = |element: &Self| {
+            let mut indices = [0; NUM_LIMBS];
+            for (index, limb) in indices.iter_mut().zip(element.binary_basis_limbs.iter()) {
+                *index = limb.element.normalize(builder, driver).witness_index;
+            }
+            indices
+        };
+        let a = normalized_indices(&left);
+        let b = normalized_indices(&to_mul);
+        let q = normalized_indices(&quotient);
+        let mut r = [0; NUM_LIMBS];
+        for (index, limb) in r.iter_mut().zip(remainder_limbs.iter()) {
+            *index = limb.normalize(builder, driver).witness_index;
+        }
+        let witnesses = NonNativeFieldWitnesses {
+            a,
+            b,
+            q,
+            r,
+            neg_modulus: neg_modulus_limbs.map(F::from),
+        };
+        // N.B. this does not evaluate the prime field component of the non-native field multiplication
+        let [lo_idx, hi_idx] =
+            builder.evaluate_non_native_field_multiplication(driver, &witnesses)?;
+
+        let neg_prime = -F::from(params.modulus.to_owned());
+        FieldCT::evaluate_polynomial_identity(
+            &left.prime_basis_limb,
+            &to_mul.prime_basis_limb,
+            &scale(&quotient.prime_basis_limb, neg_prime),
+            &remainder_prime_limb.neg(),
+            builder,
+        );
+
+        let carry_lo_msb = max_lo_bits.saturating_sub(2 * NUM_LIMB_BITS);
+        let carry_hi_msb = max_hi_bits.saturating_sub(2 * NUM_LIMB_BITS);
+        // if both the hi and lo output limbs have less than 70 bits, we can use the limb accumulation gates
+        if carry_lo_msb <= 70 && carry_hi_msb <= 70 {
+            builder.range_constrain_two_limbs(
+                driver,
+                hi_idx,
+                lo_idx,
+                carry_hi_msb,
+                carry_lo_msb,
+            )?;
+        } else {
+            builder.decompose_into_default_range(
+                driver,
+                hi_idx,
+                carry_hi_msb as u64,
+                GenericUltraCircuitBuilder::<P, T>::DEFAULT_PLOOKUP_RANGE_BITNUM as u64,
+            )?;
+            builder.decompose_into_default_range(
+                driver,
+                lo_idx,
+                carry_lo_msb as u64,
+                GenericUltraCircuitBuilder::<P, T>::DEFAULT_PLOOKUP_RANGE_BITNUM as u64,
+            )?;
+        }
+        Ok(())
+    }
+}
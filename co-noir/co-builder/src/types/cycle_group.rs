@@ -0,0 +1,1044 @@
+use super::grumpkin::GrumpkinPoint;
+use super::plookup::{FixedBaseParams, MultiTableId};
+use super::types::{BoolCT, ColumnIdx, EccAddGate, EccDblGate, FieldCT};
+use crate::builder::GenericUltraCircuitBuilder;
+use ark_ec::pairing::Pairing;
+use ark_ff::{BigInteger, One, PrimeField};
+use co_acvm::mpc::NoirWitnessExtensionProtocol;
+use num_bigint::BigUint;
+use std::io;
+
+/// A point of Grumpkin in the circuit, i.e., the port of barretenberg's cycle_group. The coordinates are elements of the scalar field of BN254, which is the field of the circuit.
+#[derive(Clone, Debug)]
+pub(crate) struct CycleGroupCT<F: PrimeField> {
+    pub(crate) x: FieldCT<F>,
+    pub(crate) y: FieldCT<F>,
+    pub(crate) is_infinity: BoolCT,
+    is_constant: bool,
+}
+
+impl<F: PrimeField> CycleGroupCT<F> {
+    const OFFSET_GENERATOR_DOMAIN_SEPARATOR: &'static [u8] = b"cycle_group_offset_generator";
+    const TABLE_BITS: usize = 4;
+
+    pub(crate) fn new<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        x: &FieldCT<F>,
+        y: &FieldCT<F>,
+        is_infinity: BoolCT,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> Self {
+        let is_constant = x.is_constant() && y.is_constant() && is_infinity.is_constant();
+        Self {
+            x: x.normalize(builder, driver),
+            y: y.normalize(builder, driver),
+            is_infinity,
+            is_constant,
+        }
+    }
+
+    pub(crate) fn from_constant(point: &GrumpkinPoint<F>) -> Self {
+        Self {
+            x: FieldCT::from(point.x),
+            y: FieldCT::from(point.y),
+            is_infinity: BoolCT::from(point.infinity),
+            is_constant: true,
+        }
+    }
+
+    /// Adds the coordinates of a constant point as witnesses which are constrained to the constant values. The point at infinity is not a valid input to the ecc gates, hence its coordinates are the zero witness.
+    pub(crate) fn from_constant_witness<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        point: &GrumpkinPoint<F>,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> Self {
+        let (x, y) = if point.infinity {
+            (
+                FieldCT::from_witness_index(builder.zero_idx),
+                FieldCT::from_witness_index(builder.zero_idx),
+            )
+        } else {
+            let x = FieldCT::from_witness(T::AcvmType::from(point.x), builder);
+            let y = FieldCT::from_witness(T::AcvmType::from(point.y), builder);
+            x.assert_equal(&FieldCT::from(point.x), builder, driver);
+            y.assert_equal(&FieldCT::from(point.y), builder, driver);
+            (x, y)
+        };
+        Self {
+            x,
+            y,
+            is_infinity: BoolCT::from(point.infinity),
+            is_constant: false,
+        }
+    }
+
+    pub(crate) fn is_constant(&self) -> bool {
+        self.is_constant
+    }
+
+    /// Returns the value of a constant point.
+    pub(crate) fn get_constant_value(&self) -> GrumpkinPoint<F> {
+        assert!(self.is_constant());
+        if self.is_infinity.get_constant_value() {
+            GrumpkinPoint::infinity()
+        } else {
+            GrumpkinPoint::new(self.x.additive_constant, self.y.additive_constant)
+        }
+    }
+
+    pub(crate) fn dbl<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        &self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<Self> {
+        // ensure we use a value of y that is not zero (only happens for the point at infinity)
+        let modified_y = FieldCT::conditional_assign(
+            &self.is_infinity,
+            &FieldCT::from(F::one()),
+            &self.y,
+            builder,
+            driver,
+        )?
+        .normalize(builder, driver);
+        let x1 = self.x.get_value(builder, driver);
+        let y1 = modified_y.get_value(builder, driver);
+
+        // Like barretenberg, we derive x^4 via the curve equation y^2 = x^3 - 17, i.e., x^4 = x * (y^2 + 17), to mirror the elliptic relation for the point at infinity
+        let y_pow_2 = driver.mul(y1.to_owned(), y1.to_owned())?;
+        let y_pow_2_plus_17 = driver.add(y_pow_2.to_owned(), T::AcvmType::from(F::from(17u64)));
+        let x_pow_4 = driver.mul(x1.to_owned(), y_pow_2_plus_17)?;
+        let lambda_squared = FieldCT::divide_acvm_types(
+            driver.mul_with_public(F::from(9u64), x_pow_4),
+            driver.mul_with_public(F::from(4u64), y_pow_2),
+            driver,
+        )?;
+        let x1_squared = driver.mul(x1.to_owned(), x1.to_owned())?;
+        let lambda = FieldCT::divide_acvm_types(
+            driver.mul_with_public(F::from(3u64), x1_squared),
+            driver.add(y1.to_owned(), y1.to_owned()),
+            driver,
+        )?;
+        let x3 = driver.sub(lambda_squared, x1.to_owned());
+        let x3 = driver.sub(x3, x1.to_owned());
+        let y3 = driver.sub(x1, x3.to_owned());
+        let y3 = driver.mul(lambda, y3)?;
+        let y3 = driver.sub(y3, y1);
+
+        if self.is_constant() {
+            let x3 = T::get_public(&x3).expect("Constant should be public");
+            let y3 = T::get_public(&y3).expect("Constant should be public");
+            return Ok(Self::new(
+                &FieldCT::from(x3),
+                &FieldCT::from(y3),
+                self.is_infinity.to_owned(),
+                builder,
+                driver,
+            ));
+        }
+
+        let x3 = FieldCT::from_witness(x3, builder);
+        let y3 = FieldCT::from_witness(y3, builder);
+        let result = Self::new(&x3, &y3, self.is_infinity.to_owned(), builder, driver);
+        builder.create_ecc_dbl_gate(&EccDblGate {
+            x1: self.x.get_witness_index(),
+            y1: modified_y.get_witness_index(),
+            x3: result.x.get_witness_index(),
+            y3: result.y.get_witness_index(),
+        });
+        Ok(result)
+    }
+
+    /// Adds two points without handling the edge cases, i.e., the x-coordinates must differ and neither point is the point at infinity.
+    pub(crate) fn unconditional_add<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        &self,
+        other: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<Self> {
+        let lhs_constant = self.is_constant();
+        let rhs_constant = other.is_constant();
+        if lhs_constant && !rhs_constant {
+            let lhs = Self::from_constant_witness(&self.get_constant_value(), builder, driver);
+            return lhs.unconditional_add(other, builder, driver);
+        }
+        if !lhs_constant && rhs_constant {
+            let rhs = Self::from_constant_witness(&other.get_constant_value(), builder, driver);
+            return self.unconditional_add(&rhs, builder, driver);
+        }
+        if lhs_constant && rhs_constant {
+            return Ok(Self::from_constant(
+                &self.get_constant_value().add(&other.get_constant_value()),
+            ));
+        }
+
+        let x1 = self.x.get_value(builder, driver);
+        let y1 = self.y.get_value(builder, driver);
+        let x2 = other.x.get_value(builder, driver);
+        let y2 = other.y.get_value(builder, driver);
+        let lambda = FieldCT::divide_acvm_types(
+            driver.sub(y2, y1.to_owned()),
+            driver.sub(x2.to_owned(), x1.to_owned()),
+            driver,
+        )?;
+        let lambda_squared = driver.mul(lambda.to_owned(), lambda.to_owned())?;
+        let x3 = driver.sub(lambda_squared, x1.to_owned());
+        let x3 = driver.sub(x3, x2);
+        let y3 = driver.sub(x1, x3.to_owned());
+        let y3 = driver.mul(lambda, y3)?;
+        let y3 = driver.sub(y3, y1);
+
+        let x3 = FieldCT::from_witness(x3, builder);
+        let y3 = FieldCT::from_witness(y3, builder);
+        let result = Self::new(&x3, &y3, BoolCT::from(false), builder, driver);
+        builder.create_ecc_add_gate(&EccAddGate {
+            x1: self.x.get_witness_index(),
+            y1: self.y.get_witness_index(),
+            x2: other.x.get_witness_index(),
+            y2: other.y.get_witness_index(),
+            x3: result.x.get_witness_index(),
+            y3: result.y.get_witness_index(),
+            sign_coefficient: F::one(),
+        });
+        Ok(result)
+    }
+
+    /// Like [`Self::unconditional_add`], but constrains the x-coordinates to differ.
+    pub(crate) fn checked_unconditional_add<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        &self,
+        other: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<Self> {
+        let x_delta = self.x.sub(&other.x, builder, driver);
+        x_delta.assert_is_not_zero(builder, driver)?;
+        self.unconditional_add(other, builder, driver)
+    }
+
+    /// Adds two points, handling doubling and the point at infinity.
+    pub(crate) fn add<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        &self,
+        other: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<Self> {
+        let x_coordinates_match = self.x.equals(&other.x, builder, driver)?;
+        let y_coordinates_match = self.y.equals(&other.y, builder, driver)?;
+        let double_predicate = x_coordinates_match.and(&y_coordinates_match, builder, driver)?;
+        let infinity_predicate =
+            x_coordinates_match.and(&y_coordinates_match.not(), builder, driver)?;
+
+        let x1 = &self.x;
+        let y1 = &self.y;
+        let x2 = &other.x;
+        let y2 = &other.y;
+        // if the x-coordinates match, lambda triggers a division by zero, adding x_coordinates_match ensures that lambda is always well-formed
+        let x_diff = x2.add_two(
+            &x1.neg(),
+            &FieldCT::from(&x_coordinates_match),
+            builder,
+            driver,
+        );
+        let lambda = y2
+            .sub(y1, builder, driver)
+            .divide(&x_diff, builder, driver)?;
+        let x3 = lambda.madd(&lambda, &x2.add(x1, builder, driver).neg(), builder, driver)?;
+        let y3 = lambda.madd(&x1.sub(&x3, builder, driver), &y1.neg(), builder, driver)?;
+        let add_result = Self::new(&x3, &y3, x_coordinates_match, builder, driver);
+
+        let dbl_result = self.dbl(builder, driver)?;
+
+        // dbl if x_match, y_match
+        // infinity if x_match, !y_match
+        let result_x = FieldCT::conditional_assign(
+            &double_predicate,
+            &dbl_result.x,
+            &add_result.x,
+            builder,
+            driver,
+        )?;
+        let result_y = FieldCT::conditional_assign(
+            &double_predicate,
+            &dbl_result.y,
+            &add_result.y,
+            builder,
+            driver,
+        )?;
+
+        let lhs_infinity = &self.is_infinity;
+        let rhs_infinity = &other.is_infinity;
+        // if lhs infinity, return rhs
+        let result_x =
+            FieldCT::conditional_assign(lhs_infinity, &other.x, &result_x, builder, driver)?;
+        let result_y =
+            FieldCT::conditional_assign(lhs_infinity, &other.y, &result_y, builder, driver)?;
+        // if rhs infinity, return lhs
+        let result_x =
+            FieldCT::conditional_assign(rhs_infinity, &self.x, &result_x, builder, driver)?;
+        let result_y =
+            FieldCT::conditional_assign(rhs_infinity, &self.y, &result_y, builder, driver)?;
+
+        // the result is the point at infinity if the points are inverses of each other and none of them is the point at infinity, or if both are the point at infinity
+        let neither_infinity = lhs_infinity
+            .not()
+            .and(&rhs_infinity.not(), builder, driver)?;
+        let result_is_infinity = infinity_predicate.and(&neither_infinity, builder, driver)?;
+        let both_infinity = lhs_infinity.and(rhs_infinity, builder, driver)?;
+        let result_is_infinity = result_is_infinity.or(&both_infinity, builder, driver)?;
+
+        Ok(Self::new(
+            &result_x,
+            &result_y,
+            result_is_infinity,
+            builder,
+            driver,
+        ))
+    }
+
+    /// Subtracts two points, handling doubling and the point at infinity.
+    pub(crate) fn sub<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        &self,
+        other: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<Self> {
+        let x_coordinates_match = self.x.equals(&other.x, builder, driver)?;
+        let y_coordinates_match = self.y.equals(&other.y, builder, driver)?;
+        let double_predicate = x_coordinates_match
+            .and(&y_coordinates_match.not(), builder, driver)?
+            .normalize(builder, driver);
+        let infinity_predicate = x_coordinates_match
+            .and(&y_coordinates_match, builder, driver)?
+            .normalize(builder, driver);
+
+        let x1 = &self.x;
+        let y1 = &self.y;
+        let x2 = &other.x;
+        let y2 = &other.y;
+        let x_diff = x2.add_two(
+            &x1.neg(),
+            &FieldCT::from(&x_coordinates_match),
+            builder,
+            driver,
+        );
+        let lambda = y2
+            .neg()
+            .sub(y1, builder, driver)
+            .divide(&x_diff, builder, driver)?;
+        let x3 = lambda.madd(&lambda, &x2.add(x1, builder, driver).neg(), builder, driver)?;
+        let y3 = lambda.madd(&x1.sub(&x3, builder, driver), &y1.neg(), builder, driver)?;
+        let add_result = Self::new(&x3, &y3, x_coordinates_match, builder, driver);
+
+        let dbl_result = self.dbl(builder, driver)?;
+
+        // dbl if x_match, !y_match
+        // infinity if x_match, y_match
+        let result_x = FieldCT::conditional_assign(
+            &double_predicate,
+            &dbl_result.x,
+            &add_result.x,
+            builder,
+            driver,
+        )?;
+        let result_y = FieldCT::conditional_assign(
+            &double_predicate,
+            &dbl_result.y,
+            &add_result.y,
+            builder,
+            driver,
+        )?;
+
+        let lhs_infinity = &self.is_infinity;
+        let rhs_infinity = &other.is_infinity;
+        // if lhs infinity, return -rhs
+        let result_x =
+            FieldCT::conditional_assign(lhs_infinity, &other.x, &result_x, builder, driver)?;
+        let neg_other_y = other.y.neg().normalize(builder, driver);
+        let result_y =
+            FieldCT::conditional_assign(lhs_infinity, &neg_other_y, &result_y, builder, driver)?;
+        // if rhs infinity, return lhs
+        let result_x =
+            FieldCT::conditional_assign(rhs_infinity, &self.x, &result_x, builder, driver)?;
+        let result_y =
+            FieldCT::conditional_assign(rhs_infinity, &self.y, &result_y, builder, driver)?;
+
+        let neither_infinity = lhs_infinity
+            .not()
+            .and(&rhs_infinity.not(), builder, driver)?;
+        let result_is_infinity = infinity_predicate.and(&neither_infinity, builder, driver)?;
+        let both_infinity = lhs_infinity.and(rhs_infinity, builder, driver)?;
+        let result_is_infinity = result_is_infinity.or(&both_infinity, builder, driver)?;
+
+        Ok(Self::new(
+            &result_x,
+            &result_y,
+            result_is_infinity,
+            builder,
+            driver,
+        ))
+    }
+
+    /// Returns lhs if the predicate is true and rhs otherwise.
+    pub(crate) fn conditional_assign<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        predicate: &BoolCT,
+        lhs: &Self,
+        rhs: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<Self> {
+        let x = FieldCT::conditional_assign(predicate, &lhs.x, &rhs.x, builder, driver)?;
+        let y = FieldCT::conditional_assign(predicate, &lhs.y, &rhs.y, builder, driver)?;
+        let is_infinity = BoolCT::conditional_assign(
+            predicate,
+            &lhs.is_infinity,
+            &rhs.is_infinity,
+            builder,
+            driver,
+        )?;
+        Ok(Self::new(&x, &y, is_infinity, builder, driver))
+    }
+
+    /// Sets the coordinates of the point at infinity to (0, 0).
+    pub(crate) fn get_standard_form<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        &self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<Self> {
+        let zero = FieldCT::from(F::zero());
+        let x = FieldCT::conditional_assign(&self.is_infinity, &zero, &self.x, builder, driver)?;
+        let y = FieldCT::conditional_assign(&self.is_infinity, &zero, &self.y, builder, driver)?;
+        Ok(Self::new(
+            &x,
+            &y,
+            self.is_infinity.to_owned(),
+            builder,
+            driver,
+        ))
+    }
+
+    /// Computes the multi-scalar multiplication of the points and scalars. Constant terms are accumulated natively, constant points with lookup tables (i.e., the Pedersen generators) use the fixed-base tables, and all other points use the Straus algorithm with ROM tables.
+    pub(crate) fn batch_mul<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        base_points: &[Self],
+        scalars: &[CycleScalarCT<F>],
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<Self> {
+        assert_eq!(base_points.len(), scalars.len());
+        for scalar in scalars {
+            scalar.validate_scalar_is_in_field(builder, driver)?;
+        }
+
+        let mut variable_base_scalars = Vec::new();
+        let mut variable_base_points = Vec::new();
+        let mut fixed_base_scalars = Vec::new();
+        let mut fixed_base_points = Vec::new();
+
+        // we can unconditionally add iff all of the input points are constant, i.e., we are ok with ignoring the x-coordinate collision check
+        let mut can_unconditional_add = true;
+        let mut has_non_constant_component = false;
+        let mut constant_acc = GrumpkinPoint::infinity();
+        for (scalar, point) in scalars.iter().zip(base_points.iter()) {
+            match (scalar.is_constant(), point.is_constant()) {
+                (true, true) => {
+                    constant_acc = constant_acc
+                        .add(&point.get_constant_value().mul(&scalar.get_constant_value()));
+                }
+                (false, true) => {
+                    let point_value = point.get_constant_value();
+                    if point_value.infinity {
+                        // a free point at infinity, ignore it
+                        continue;
+                    }
+                    if FixedBaseParams::lookup_table_exists_for_point(&point_value) {
+                        fixed_base_scalars.push(scalar.to_owned());
+                        fixed_base_points.push(point_value);
+                    } else {
+                        variable_base_scalars.push(scalar.to_owned());
+                        variable_base_points.push(point.to_owned());
+                    }
+                    has_non_constant_component = true;
+                }
+                _ => {
+                    variable_base_scalars.push(scalar.to_owned());
+                    variable_base_points.push(point.to_owned());
+                    can_unconditional_add = false;
+                    has_non_constant_component = true;
+                }
+            }
+        }
+
+        if !has_non_constant_component {
+            return Ok(Self::from_constant(&constant_acc));
+        }
+
+        // we subtract the offset accumulator from the result, hence we negate the constant terms
+        let mut offset_accumulator = constant_acc.neg();
+        let has_variable_points = !variable_base_points.is_empty();
+        let has_fixed_points = !fixed_base_points.is_empty();
+
+        let num_offset_generators = variable_base_points.len()
+            + fixed_base_points.len()
+            + usize::from(has_variable_points)
+            + usize::from(has_fixed_points);
+        let offset_generators = GrumpkinPoint::derive_generators(
+            Self::OFFSET_GENERATOR_DOMAIN_SEPARATOR,
+            num_offset_generators,
+            0,
+        );
+
+        let mut result = None;
+        if has_fixed_points {
+            let (fixed_accumulator, offset_generator_delta) = Self::fixed_base_batch_mul_internal(
+                &fixed_base_scalars,
+                &fixed_base_points,
+                builder,
+                driver,
+            )?;
+            offset_accumulator = offset_accumulator.add(&offset_generator_delta);
+            result = Some(fixed_accumulator);
+        }
+
+        if has_variable_points {
+            let (variable_accumulator, offset_generator_delta) =
+                Self::variable_base_batch_mul_internal(
+                    &variable_base_scalars,
+                    &variable_base_points,
+                    &offset_generators[fixed_base_points.len()..],
+                    can_unconditional_add,
+                    builder,
+                    driver,
+                )?;
+            offset_accumulator = offset_accumulator.add(&offset_generator_delta);
+            result = Some(match result {
+                Some(fixed_accumulator) if can_unconditional_add => {
+                    fixed_accumulator.unconditional_add(&variable_accumulator, builder, driver)?
+                }
+                Some(fixed_accumulator) => fixed_accumulator.checked_unconditional_add(
+                    &variable_accumulator,
+                    builder,
+                    driver,
+                )?,
+                None => variable_accumulator,
+            });
+        }
+        let result = result.expect("There is a non-constant component");
+
+        // If all inputs are constant points and constant_acc is not the point at infinity, we cannot hit the edge cases of the incomplete addition. Otherwise, we need the complete subtraction.
+        if !constant_acc.infinity && can_unconditional_add {
+            result.unconditional_add(
+                &Self::from_constant(&offset_accumulator.neg()),
+                builder,
+                driver,
+            )
+        } else {
+            result.sub(&Self::from_constant(&offset_accumulator), builder, driver)
+        }
+    }
+
+    /// The Straus algorithm for the points without lookup tables. Returns the result and the sum of the offset generators it contains.
+    fn variable_base_batch_mul_internal<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        scalars: &[CycleScalarCT<F>],
+        base_points: &[Self],
+        offset_generators: &[GrumpkinPoint<F>],
+        unconditional_add: bool,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<(Self, GrumpkinPoint<F>)> {
+        let num_rounds = CycleScalarCT::<F>::NUM_BITS.div_ceil(Self::TABLE_BITS);
+        let num_points = scalars.len();
+
+        let mut scalar_slices = Vec::with_capacity(num_points);
+        let mut point_tables = Vec::with_capacity(num_points);
+        for (i, (scalar, point)) in scalars.iter().zip(base_points.iter()).enumerate() {
+            scalar_slices.push(StrausScalarSlice::new(
+                scalar,
+                Self::TABLE_BITS,
+                builder,
+                driver,
+            )?);
+            point_tables.push(StrausLookupTable::new(
+                point,
+                &Self::from_constant(&offset_generators[i + 1]),
+                Self::TABLE_BITS,
+                builder,
+                driver,
+            )?);
+        }
+
+        let mut points_to_add = Vec::with_capacity(num_rounds * num_points);
+        for i in 0..num_rounds {
+            for (scalar_slice, point_table) in scalar_slices.iter().zip(point_tables.iter()) {
+                if let Some(scalar_slice) = scalar_slice.read(num_rounds - i - 1) {
+                    points_to_add.push(point_table.read(scalar_slice, builder, driver)?);
+                }
+            }
+        }
+
+        let mut accumulator = Self::from_constant(&offset_generators[0]);
+        let mut offset_generator_accumulator = offset_generators[0].to_owned();
+        let mut x_coordinate_checks = Vec::new();
+        let mut points_to_add = points_to_add.into_iter();
+        for i in 0..num_rounds {
+            if i != 0 {
+                for _ in 0..Self::TABLE_BITS {
+                    accumulator = accumulator.dbl(builder, driver)?;
+                    offset_generator_accumulator = offset_generator_accumulator.dbl();
+                }
+            }
+            for (scalar_slice, offset_generator) in
+                scalar_slices.iter().zip(offset_generators.iter().skip(1))
+            {
+                if scalar_slice.read(num_rounds - i - 1).is_some() {
+                    let point = points_to_add.next().expect("A point for each slice");
+                    if !unconditional_add {
+                        x_coordinate_checks.push((accumulator.x.to_owned(), point.x.to_owned()));
+                    }
+                    accumulator = accumulator.unconditional_add(&point, builder, driver)?;
+                }
+                offset_generator_accumulator = offset_generator_accumulator.add(offset_generator);
+            }
+        }
+
+        // batch the x-coordinate checks together, since assert_is_not_zero needs an inversion
+        let mut coordinate_check_product = FieldCT::from(F::one());
+        for (x1, x2) in x_coordinate_checks {
+            let x_diff = x2.sub(&x1, builder, driver);
+            coordinate_check_product =
+                coordinate_check_product.multiply(&x_diff, builder, driver)?;
+        }
+        coordinate_check_product.assert_is_not_zero(builder, driver)?;
+
+        Ok((accumulator, offset_generator_accumulator))
+    }
+
+    /// The scalar multiplications with the fixed-base lookup tables. Returns the result and the sum of the offset generators it contains.
+    fn fixed_base_batch_mul_internal<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        scalars: &[CycleScalarCT<F>],
+        base_points: &[GrumpkinPoint<F>],
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<(Self, GrumpkinPoint<F>)> {
+        let mut plookup_table_ids = Vec::with_capacity(2 * scalars.len());
+        let mut plookup_scalars = Vec::with_capacity(2 * scalars.len());
+        for (scalar, point) in scalars.iter().zip(base_points.iter()) {
+            let [lo_table_id, hi_table_id] = FixedBaseParams::get_lookup_table_ids_for_point(point)
+                .expect("Fixed-base points have lookup tables");
+            plookup_table_ids.push(lo_table_id);
+            plookup_table_ids.push(hi_table_id);
+            plookup_scalars.push(&scalar.lo);
+            plookup_scalars.push(&scalar.hi);
+        }
+
+        let mut lookup_points = Vec::new();
+        let mut offset_generator_accumulator = GrumpkinPoint::infinity();
+        for (id, scalar) in plookup_table_ids.into_iter().zip(plookup_scalars) {
+            let offset = FixedBaseParams::get_generator_offset_for_table_id(&id)
+                .expect("Fixed-base tables have offsets");
+            let (xs, ys) = Self::get_lookup_accumulators(id, scalar, builder, driver)?;
+            for (x, y) in xs.iter().zip(ys.iter()) {
+                lookup_points.push(Self::new(x, y, BoolCT::from(false), builder, driver));
+            }
+            offset_generator_accumulator = offset_generator_accumulator.add(&offset);
+        }
+
+        // we cannot enforce that the fixed-base points do not collide with each other, but the probability of a collision is negligible
+        let mut lookup_points = lookup_points.into_iter();
+        let mut accumulator = lookup_points.next().expect("At least one lookup");
+        for point in lookup_points {
+            accumulator = accumulator.unconditional_add(&point, builder, driver)?;
+        }
+        Ok((accumulator, offset_generator_accumulator))
+    }
+
+    /// Reads the x- and y-coordinates of the accumulated points of a fixed-base multitable.
+    fn get_lookup_accumulators<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        id: MultiTableId,
+        key: &FieldCT<F>,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<(Vec<FieldCT<F>>, Vec<FieldCT<F>>)> {
+        let key = key.normalize(builder, driver);
+        let key_value = T::get_public(&key.get_value(builder, driver)).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Unsupported,
+                "Fixed-base scalar multiplications with shared scalars are not supported",
+            )
+        })?;
+        let lookup_data =
+            builder
+                .plookup
+                .get_lookup_accumulators(id.to_owned(), key_value, F::zero(), false);
+
+        if key.is_constant() {
+            let xs = lookup_data[ColumnIdx::C2]
+                .iter()
+                .map(|x| FieldCT::from(*x))
+                .collect();
+            let ys = lookup_data[ColumnIdx::C3]
+                .iter()
+                .map(|y| FieldCT::from(*y))
+                .collect();
+            return Ok((xs, ys));
+        }
+
+        let accumulators = builder.create_gates_from_plookup_accumulators(
+            id,
+            lookup_data,
+            key.get_witness_index(),
+            None,
+        );
+        let xs = accumulators[ColumnIdx::C2]
+            .iter()
+            .map(|x| FieldCT::from_witness_index(*x))
+            .collect();
+        let ys = accumulators[ColumnIdx::C3]
+            .iter()
+            .map(|y| FieldCT::from_witness_index(*y))
+            .collect();
+        Ok((xs, ys))
+    }
+}
+
+/// A scalar of Grumpkin in the circuit, split into a 128-bit low and a 126-bit high limb.
+#[derive(Clone, Debug)]
+pub(crate) struct CycleScalarCT<F: PrimeField> {
+    pub(crate) lo: FieldCT<F>,
+    pub(crate) hi: FieldCT<F>,
+}
+
+impl<F: PrimeField> CycleScalarCT<F> {
+    const LO_BITS: usize = 128;
+    const HI_BITS: usize = 126;
+    const NUM_BITS: usize = Self::LO_BITS + Self::HI_BITS;
+
+    pub(crate) fn new(lo: FieldCT<F>, hi: FieldCT<F>) -> Self {
+        Self { lo, hi }
+    }
+
+    pub(crate) fn is_constant(&self) -> bool {
+        self.lo.is_constant() && self.hi.is_constant()
+    }
+
+    /// Returns the value of a constant scalar as integer.
+    pub(crate) fn get_constant_value(&self) -> BigUint {
+        assert!(self.is_constant());
+        let lo: BigUint = self.lo.additive_constant.into();
+        let hi: BigUint = self.hi.additive_constant.into();
+        lo + (hi << Self::LO_BITS)
+    }
+
+    /// Constrains hi * 2^128 + lo to be smaller than the modulus of the scalar field of Grumpkin, which is the base field of BN254.
+    pub(crate) fn validate_scalar_is_in_field<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        &self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<()> {
+        if self.is_constant() {
+            return Ok(());
+        }
+
+        let modulus = BigUint::from_bytes_le(&P::BaseField::MODULUS.to_bytes_le());
+        let lo_shift = BigUint::one() << Self::LO_BITS;
+        let r_lo = &modulus % &lo_shift;
+        let r_hi = &modulus >> Self::LO_BITS;
+
+        let lo_value = self.lo.get_value(builder, driver);
+        let need_borrow = if let Some(lo_value) = T::get_public(&lo_value) {
+            let lo_value: BigUint = lo_value.into();
+            T::AcvmType::from(F::from(lo_value > r_lo))
+        } else {
+            // The borrow is 1 iff the 129-bit value r_lo + 2^128 - lo has no bit 128 set
+            let shifted = driver.sub(T::AcvmType::from(F::from(&r_lo + &lo_shift)), lo_value);
+            let chunks = driver.decompose_arithmetic(
+                T::get_shared(&shifted).expect("Checked it is shared"),
+                Self::LO_BITS + 1,
+                Self::LO_BITS,
+            )?;
+            let msb = T::AcvmType::from(chunks[1].to_owned());
+            driver.sub(T::AcvmType::from(F::one()), msb)
+        };
+        let borrow = if self.lo.is_constant() {
+            FieldCT::from(T::get_public(&need_borrow).expect("Constant should be public"))
+        } else {
+            let borrow = FieldCT::from_witness(need_borrow, builder);
+            // directly call create_new_range_constraint to avoid creating an arithmetic gate
+            builder.create_new_range_constraint(borrow.get_witness_index(), 1);
+            borrow
+        };
+
+        // Hi range check = r_hi - y_hi - borrow
+        // Lo range check = r_lo - y_lo + borrow * 2^128
+        let hi_diff = self
+            .hi
+            .neg()
+            .add(&FieldCT::from(F::from(r_hi)), builder, driver)
+            .sub(&borrow, builder, driver);
+        let borrow_shifted = borrow.multiply(&FieldCT::from(F::from(lo_shift)), builder, driver)?;
+        let lo_diff = self
+            .lo
+            .neg()
+            .add(&FieldCT::from(F::from(r_lo)), builder, driver)
+            .add(&borrow_shifted, builder, driver);
+
+        hi_diff.create_range_constraint(Self::HI_BITS, builder, driver)?;
+        lo_diff.create_range_constraint(Self::LO_BITS, builder, driver)
+    }
+}
+
+/// The slices of a scalar for the Straus algorithm, starting with the least significant slice of the low limb.
+struct StrausScalarSlice<F: PrimeField> {
+    slices: Vec<FieldCT<F>>,
+}
+
+impl<F: PrimeField> StrausScalarSlice<F> {
+    fn new<P: Pairing<ScalarField = F>, T: NoirWitnessExtensionProtocol<P::ScalarField>>(
+        scalar: &CycleScalarCT<F>,
+        table_bits: usize,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<Self> {
+        let hi_slices = Self::slice_scalar(
+            &scalar.hi,
+            CycleScalarCT::<F>::HI_BITS,
+            table_bits,
+            builder,
+            driver,
+        )?;
+        let lo_slices = Self::slice_scalar(
+            &scalar.lo,
+            CycleScalarCT::<F>::LO_BITS,
+            table_bits,
+            builder,
+            driver,
+        )?;
+        let mut slices = lo_slices;
+        slices.extend(hi_slices);
+        Ok(Self { slices })
+    }
+
+    /// Splits the limb into slices of table_bits bits, which also range constrains the limb.
+    fn slice_scalar<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        scalar: &FieldCT<F>,
+        num_bits: usize,
+        table_bits: usize,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<Vec<FieldCT<F>>> {
+        if scalar.is_constant() {
+            let num_slices = num_bits.div_ceil(table_bits);
+            let table_mask = BigUint::from((1u64 << table_bits) - 1);
+            let mut raw_value: BigUint = scalar.additive_constant.into();
+            let mut result = Vec::with_capacity(num_slices);
+            for _ in 0..num_slices {
+                result.push(FieldCT::from(F::from(&raw_value & &table_mask)));
+                raw_value >>= table_bits;
+            }
+            return Ok(result);
+        }
+
+        let normalized = scalar.normalize(builder, driver);
+        let slice_indices = builder.decompose_into_default_range(
+            driver,
+            normalized.get_witness_index(),
+            num_bits as u64,
+            table_bits as u64,
+        )?;
+        Ok(slice_indices
+            .into_iter()
+            .map(FieldCT::from_witness_index)
+            .collect())
+    }
+
+    fn read(&self, index: usize) -> Option<&FieldCT<F>> {
+        self.slices.get(index)
+    }
+}
+
+/// The multiples offset_generator + i * base_point for i in 0..2^table_bits, stored in a ROM array.
+struct StrausLookupTable {
+    rom_id: usize,
+}
+
+impl StrausLookupTable {
+    fn new<
+        F: PrimeField,
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        base_point: &CycleGroupCT<F>,
+        offset_generator: &CycleGroupCT<F>,
+        table_bits: usize,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<Self> {
+        let table_size = 1 << table_bits;
+        let mut point_table = Vec::with_capacity(table_size);
+        point_table.push(offset_generator.to_owned());
+
+        // If the base point is the point at infinity, every entry of the table is the offset generator. We achieve this by using the generator instead of the base point in the additions (to prevent x-coordinate collisions in the honest case) and conditionally assigning the offset generator to the entries afterwards.
+        let fallback_point = CycleGroupCT::from_constant(&GrumpkinPoint::generator());
+        let modded_x = FieldCT::conditional_assign(
+            &base_point.is_infinity,
+            &fallback_point.x,
+            &base_point.x,
+            builder,
+            driver,
+        )?;
+        let modded_y = FieldCT::conditional_assign(
+            &base_point.is_infinity,
+            &fallback_point.y,
+            &base_point.y,
+            builder,
+            driver,
+        )?;
+        let modded_base_point =
+            CycleGroupCT::new(&modded_x, &modded_y, BoolCT::from(false), builder, driver);
+
+        let base_point_is_infinity =
+            T::get_public(&base_point.is_infinity.get_value(builder, driver))
+                .is_some_and(|is_infinity| is_infinity.is_one());
+        if modded_base_point.is_constant() && !base_point_is_infinity {
+            // for a constant point, it is cheaper to fix the point as a witness and derive the table than to fix all entries of the table
+            let modded_base_point = CycleGroupCT::from_constant_witness(
+                &modded_base_point.get_constant_value(),
+                builder,
+                driver,
+            );
+            point_table[0] = CycleGroupCT::from_constant_witness(
+                &offset_generator.get_constant_value(),
+                builder,
+                driver,
+            );
+            for i in 1..table_size {
+                let point =
+                    point_table[i - 1].unconditional_add(&modded_base_point, builder, driver)?;
+                point_table.push(point);
+            }
+        } else {
+            let mut x_coordinate_checks = Vec::with_capacity(table_size - 1);
+            for i in 1..table_size {
+                x_coordinate_checks.push((
+                    point_table[i - 1].x.to_owned(),
+                    modded_base_point.x.to_owned(),
+                ));
+                let point =
+                    point_table[i - 1].unconditional_add(&modded_base_point, builder, driver)?;
+                point_table.push(point);
+            }
+
+            // batch the x-coordinate checks together, since assert_is_not_zero needs an inversion
+            let mut coordinate_check_product = FieldCT::from(F::one());
+            for (x1, x2) in x_coordinate_checks {
+                let x_diff = x2.sub(&x1, builder, driver);
+                coordinate_check_product =
+                    coordinate_check_product.multiply(&x_diff, builder, driver)?;
+            }
+            coordinate_check_product.assert_is_not_zero(builder, driver)?;
+
+            for point in point_table.iter_mut().skip(1) {
+                *point = CycleGroupCT::conditional_assign(
+                    &base_point.is_infinity,
+                    offset_generator,
+                    point,
+                    builder,
+                    driver,
+                )?;
+            }
+        }
+
+        let rom_id = builder.create_rom_array(table_size);
+        for (i, point) in point_table.into_iter().enumerate() {
+            let point = if point.is_constant() {
+                let element = point.get_constant_value();
+                let point = CycleGroupCT::from_constant_witness(&element, builder, driver);
+                point
+                    .x
+                    .assert_equal(&FieldCT::from(element.x), builder, driver);
+                point
+                    .y
+                    .assert_equal(&FieldCT::from(element.y), builder, driver);
+                point
+            } else {
+                point
+            };
+            builder.set_rom_element_pair(
+                rom_id,
+                i,
+                [point.x.get_witness_index(), point.y.get_witness_index()],
+            );
+        }
+        Ok(Self { rom_id })
+    }
+
+    fn read<
+        F: PrimeField,
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        &self,
+        index: &FieldCT<F>,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> io::Result<CycleGroupCT<F>> {
+        let index = if index.is_constant() {
+            let witness =
+                FieldCT::from_witness(T::AcvmType::from(index.additive_constant), builder);
+            witness.assert_equal(index, builder, driver);
+            witness
+        } else {
+            index.to_owned()
+        };
+        let [x, y] = builder.read_rom_array_pair(self.rom_id, index.get_witness_index(), driver)?;
+        Ok(CycleGroupCT::new(
+            &FieldCT::from_witness_index(x),
+            &FieldCT::from_witness_index(y),
+            BoolCT::from(false),
+            builder,
+            driver,
+        ))
+    }
+}
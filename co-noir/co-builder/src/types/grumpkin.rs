@@ -0,0 +1,123 @@
+use ark_ff::{BigInteger, PrimeField};
+use num_bigint::BigUint;
+
+/// The y coordinate of the generator (1, y) of Grumpkin.
+const GENERATOR_Y: &str = "17631683881184975370165255887551781615748388533673675138860";
+
+/// A point of Grumpkin, the embedded curve y^2 = x^3 - 17 of BN254, in affine coordinates over the scalar field F of BN254. It is used for the values of constant points and offset generators of the embedded curve gadgets. The point at infinity has the coordinates (0, 0).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct GrumpkinPoint<F: PrimeField> {
+    pub(crate) x: F,
+    pub(crate) y: F,
+    pub(crate) infinity: bool,
+}
+
+impl<F: PrimeField> GrumpkinPoint<F> {
+    pub(crate) fn new(x: F, y: F) -> Self {
+        Self {
+            x,
+            y,
+            infinity: false,
+        }
+    }
+
+    pub(crate) fn infinity() -> Self {
+        Self {
+            x: F::zero(),
+            y: F::zero(),
+            infinity: true,
+        }
+    }
+
+    pub(crate) fn generator() -> Self {
+        let y: BigUint = GENERATOR_Y.parse().expect("valid constant");
+        Self::new(F::one(), F::from(y))
+    }
+
+    pub(crate) fn neg(&self) -> Self {
+        if self.infinity {
+            return self.to_owned();
+        }
+        Self::new(self.x, -self.y)
+    }
+
+    pub(crate) fn dbl(&self) -> Self {
+        if self.infinity || self.y.is_zero() {
+            return Self::infinity();
+        }
+        let x_squared = self.x.square();
+        let lambda = (x_squared + x_squared + x_squared) / self.y.double();
+        let x3 = lambda.square() - self.x.double();
+        let y3 = lambda * (self.x - x3) - self.y;
+        Self::new(x3, y3)
+    }
+
+    pub(crate) fn add(&self, other: &Self) -> Self {
+        if self.infinity {
+            return other.to_owned();
+        }
+        if other.infinity {
+            return self.to_owned();
+        }
+        if self.x == other.x {
+            return if self.y == other.y {
+                self.dbl()
+            } else {
+                Self::infinity()
+            };
+        }
+        let lambda = (other.y - self.y) / (other.x - self.x);
+        let x3 = lambda.square() - self.x - other.x;
+        let y3 = lambda * (self.x - x3) - self.y;
+        Self::new(x3, y3)
+    }
+
+    /// Multiplies the point with a scalar, which may be larger than the scalar field of Grumpkin.
+    pub(crate) fn mul(&self, scalar: &BigUint) -> Self {
+        let mut result = Self::infinity();
+        for i in (0..scalar.bits()).rev() {
+            result = result.dbl();
+            if scalar.bit(i) {
+                result = result.add(self);
+            }
+        }
+        result
+    }
+
+    /// Converts the coordinates into another field, i.e., between the scalar field of BN254 and the field of a generic builder.
+    pub(crate) fn convert<G: PrimeField>(&self) -> GrumpkinPoint<G> {
+        GrumpkinPoint {
+            x: G::from_le_bytes_mod_order(&self.x.into_bigint().to_bytes_le()),
+            y: G::from_le_bytes_mod_order(&self.y.into_bigint().to_bytes_le()),
+            infinity: self.infinity,
+        }
+    }
+
+    /// Serializes the point like barretenberg, i.e., the big-endian coordinates x and y.
+    pub(crate) fn to_buffer(&self) -> Vec<u8> {
+        let mut buffer = self.x.into_bigint().to_bytes_be();
+        buffer.extend(self.y.into_bigint().to_bytes_be());
+        buffer
+    }
+
+    /// Derives `num_generators` generators of barretenberg for the domain separator, starting at the generator with index `starting_index`.
+    pub(crate) fn derive_generators(
+        domain_separator: &[u8],
+        num_generators: usize,
+        starting_index: u32,
+    ) -> Vec<Self> {
+        bn254_blackbox_solver::derive_generators(
+            domain_separator,
+            u32::try_from(num_generators).expect("number of generators fits into u32"),
+            starting_index,
+        )
+        .into_iter()
+        .map(|point| {
+            Self::new(
+                F::from_le_bytes_mod_order(&point.x.into_bigint().to_bytes_le()),
+                F::from_le_bytes_mod_order(&point.y.into_bigint().to_bytes_le()),
+            )
+        })
+        .collect()
+    }
+}
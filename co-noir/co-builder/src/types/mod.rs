@@ -1,3 +1,6 @@
+pub(crate) mod big_field;
+pub(crate) mod cycle_group;
+pub(crate) mod grumpkin;
 #[expect(dead_code)]
 pub(crate) mod plookup;
 #[expect(dead_code, clippy::module_inception)]
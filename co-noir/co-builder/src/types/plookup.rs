@@ -1,7 +1,9 @@
 use ark_ff::PrimeField;
 use num_bigint::BigUint;
 use std::array::from_fn;
+use std::sync::OnceLock;
 
+use super::grumpkin::GrumpkinPoint;
 use super::types::{ColumnIdx, LookupEntry, PlookupMultiTable, ReadData};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -51,14 +53,64 @@ pub(crate) enum BasicTableId {
     BlakeXorRotate2,
     BlakeXorRotate4,
     FixedBase0_0,
-    FixedBase1_0 = BasicTableId::FixedBase0_0 as isize
-        + FixedBaseParams::NUM_TABLES_PER_LO_MULTITABLE as isize,
-    FixedBase2_0 = BasicTableId::FixedBase1_0 as isize
-        + FixedBaseParams::NUM_TABLES_PER_HI_MULTITABLE as isize,
-    FixedBase3_0 = BasicTableId::FixedBase2_0 as isize
-        + FixedBaseParams::NUM_TABLES_PER_LO_MULTITABLE as isize,
-    HonkDummyBasic1 = BasicTableId::FixedBase3_0 as isize
-        + FixedBaseParams::NUM_TABLES_PER_HI_MULTITABLE as isize,
+    FixedBase0_1,
+    FixedBase0_2,
+    FixedBase0_3,
+    FixedBase0_4,
+    FixedBase0_5,
+    FixedBase0_6,
+    FixedBase0_7,
+    FixedBase0_8,
+    FixedBase0_9,
+    FixedBase0_10,
+    FixedBase0_11,
+    FixedBase0_12,
+    FixedBase0_13,
+    FixedBase0_14,
+    FixedBase1_0,
+    FixedBase1_1,
+    FixedBase1_2,
+    FixedBase1_3,
+    FixedBase1_4,
+    FixedBase1_5,
+    FixedBase1_6,
+    FixedBase1_7,
+    FixedBase1_8,
+    FixedBase1_9,
+    FixedBase1_10,
+    FixedBase1_11,
+    FixedBase1_12,
+    FixedBase1_13,
+    FixedBase2_0,
+    FixedBase2_1,
+    FixedBase2_2,
+    FixedBase2_3,
+    FixedBase2_4,
+    FixedBase2_5,
+    FixedBase2_6,
+    FixedBase2_7,
+    FixedBase2_8,
+    FixedBase2_9,
+    FixedBase2_10,
+    FixedBase2_11,
+    FixedBase2_12,
+    FixedBase2_13,
+    FixedBase2_14,
+    FixedBase3_0,
+    FixedBase3_1,
+    FixedBase3_2,
+    FixedBase3_3,
+    FixedBase3_4,
+    FixedBase3_5,
+    FixedBase3_6,
+    FixedBase3_7,
+    FixedBase3_8,
+    FixedBase3_9,
+    FixedBase3_10,
+    FixedBase3_11,
+    FixedBase3_12,
+    FixedBase3_13,
+    HonkDummyBasic1,
     HonkDummyBasic2,
     KeccakInput,
     KeccakTheta,
@@ -91,7 +143,91 @@ impl BasicTableId {
     }
 }
 
-struct FixedBaseParams {}
+/// The basic tables of the fixed-base multitables, indexed by the multitable and the position of the basic table in it.
+const FIXED_BASE_TABLE_IDS: [&[BasicTableId]; FixedBaseParams::NUM_FIXED_BASE_MULTI_TABLES] = [
+    &[
+        BasicTableId::FixedBase0_0,
+        BasicTableId::FixedBase0_1,
+        BasicTableId::FixedBase0_2,
+        BasicTableId::FixedBase0_3,
+        BasicTableId::FixedBase0_4,
+        BasicTableId::FixedBase0_5,
+        BasicTableId::FixedBase0_6,
+        BasicTableId::FixedBase0_7,
+        BasicTableId::FixedBase0_8,
+        BasicTableId::FixedBase0_9,
+        BasicTableId::FixedBase0_10,
+        BasicTableId::FixedBase0_11,
+        BasicTableId::FixedBase0_12,
+        BasicTableId::FixedBase0_13,
+        BasicTableId::FixedBase0_14,
+    ],
+    &[
+        BasicTableId::FixedBase1_0,
+        BasicTableId::FixedBase1_1,
+        BasicTableId::FixedBase1_2,
+        BasicTableId::FixedBase1_3,
+        BasicTableId::FixedBase1_4,
+        BasicTableId::FixedBase1_5,
+        BasicTableId::FixedBase1_6,
+        BasicTableId::FixedBase1_7,
+        BasicTableId::FixedBase1_8,
+        BasicTableId::FixedBase1_9,
+        BasicTableId::FixedBase1_10,
+        BasicTableId::FixedBase1_11,
+        BasicTableId::FixedBase1_12,
+        BasicTableId::FixedBase1_13,
+    ],
+    &[
+        BasicTableId::FixedBase2_0,
+        BasicTableId::FixedBase2_1,
+        BasicTableId::FixedBase2_2,
+        BasicTableId::FixedBase2_3,
+        BasicTableId::FixedBase2_4,
+        BasicTableId::FixedBase2_5,
+        BasicTableId::FixedBase2_6,
+        BasicTableId::FixedBase2_7,
+        BasicTableId::FixedBase2_8,
+        BasicTableId::FixedBase2_9,
+        BasicTableId::FixedBase2_10,
+        BasicTableId::FixedBase2_11,
+        BasicTableId::FixedBase2_12,
+        BasicTableId::FixedBase2_13,
+        BasicTableId::FixedBase2_14,
+    ],
+    &[
+        BasicTableId::FixedBase3_0,
+        BasicTableId::FixedBase3_1,
+        BasicTableId::FixedBase3_2,
+        BasicTableId::FixedBase3_3,
+        BasicTableId::FixedBase3_4,
+        BasicTableId::FixedBase3_5,
+        BasicTableId::FixedBase3_6,
+        BasicTableId::FixedBase3_7,
+        BasicTableId::FixedBase3_8,
+        BasicTableId::FixedBase3_9,
+        BasicTableId::FixedBase3_10,
+        BasicTableId::FixedBase3_11,
+        BasicTableId::FixedBase3_12,
+        BasicTableId::FixedBase3_13,
+    ],
+];
+
+impl BasicTableId {
+    /// Returns the index of the multitable and the index of the basic table in it if this is a fixed-base table.
+    pub(crate) fn fixed_base_table_position(&self) -> Option<(usize, usize)> {
+        FIXED_BASE_TABLE_IDS
+            .iter()
+            .enumerate()
+            .find_map(|(multitable_index, ids)| {
+                ids.iter()
+                    .position(|id| id == self)
+                    .map(|table_index| (multitable_index, table_index))
+            })
+    }
+}
+
+pub(crate) struct FixedBaseParams {}
 impl FixedBaseParams {
     const BITS_PER_TABLE: usize = 9;
     const BITS_ON_CURVE: usize = 254;
@@ -141,6 +277,186 @@ impl FixedBaseParams {
         Self::NUM_BASIC_TABLES_PER_BASE_POINT * Self::NUM_POINTS;
 }
 
+/// The points of the fixed-base lookup tables of barretenberg. They are computed once over the scalar field of BN254 and converted into the field of the builder on access.
+struct FixedBaseTables {
+    /// The points of the basic tables, indexed by the multitable, the basic table and the key
+    tables: Vec<Vec<Vec<GrumpkinPoint<ark_bn254::Fr>>>>,
+    /// The sum of the offset generators of the basic tables of each multitable
+    offsets: Vec<GrumpkinPoint<ark_bn254::Fr>>,
+}
+
+static FIXED_BASE_TABLES: OnceLock<FixedBaseTables> = OnceLock::new();
+
+macro_rules! fixed_base_table_values {
+    ($m:literal, [$($t:literal),*]) => {
+        vec![$(FixedBaseParams::get_basic_fixed_base_table_values::<F, $m, $t> as fn([u64; 2]) -> [F; 2]),*]
+    };
+}
+
+impl FixedBaseParams {
+    /// The domain separator of the generators of the Pedersen hash.
+    const DEFAULT_DOMAIN_SEPARATOR: &[u8] = b"DEFAULT_DOMAIN_SEPARATOR";
+
+    pub(crate) fn lhs_generator_point<F: PrimeField>() -> GrumpkinPoint<F> {
+        GrumpkinPoint::derive_generators(Self::DEFAULT_DOMAIN_SEPARATOR, 1, 0).remove(0)
+    }
+
+    pub(crate) fn rhs_generator_point<F: PrimeField>() -> GrumpkinPoint<F> {
+        GrumpkinPoint::derive_generators(Self::DEFAULT_DOMAIN_SEPARATOR, 1, 1).remove(0)
+    }
+
+    fn get_num_bits_of_multi_table(multitable_index: usize) -> usize {
+        if multitable_index % 2 == 0 {
+            Self::BITS_PER_LO_SCALAR
+        } else {
+            Self::BITS_PER_HI_SCALAR
+        }
+    }
+
+    /// Returns the base point of the multitable, i.e., the generator for the low scalar limb and the generator multiplied by 2^128 for the high scalar limb.
+    fn get_base_point(multitable_index: usize) -> GrumpkinPoint<ark_bn254::Fr> {
+        let generator = if multitable_index < 2 {
+            Self::lhs_generator_point()
+        } else {
+            Self::rhs_generator_point()
+        };
+        if multitable_index % 2 == 0 {
+            generator
+        } else {
+            generator.mul(&(BigUint::from(1u64) << Self::BITS_PER_LO_SCALAR))
+        }
+    }
+
+    /// Computes the tables for the scalar multiplication of the input point with a scalar of num_bits bits. The i-th table contains the points offset_i + k * 2^(9i) * input for all keys k, where the offset generators are derived from the input point.
+    fn generate_tables(
+        input: &GrumpkinPoint<ark_bn254::Fr>,
+        num_bits: usize,
+    ) -> (
+        Vec<Vec<GrumpkinPoint<ark_bn254::Fr>>>,
+        GrumpkinPoint<ark_bn254::Fr>,
+    ) {
+        let num_tables = num_bits.div_ceil(Self::BITS_PER_TABLE);
+        let offset_generators = GrumpkinPoint::derive_generators(&input.to_buffer(), num_tables, 0);
+
+        let mut base = input.to_owned();
+        let mut tables = Vec::with_capacity(num_tables);
+        for offset_generator in offset_generators.iter() {
+            let mut accumulator = offset_generator.to_owned();
+            let mut table = Vec::with_capacity(Self::MAX_TABLE_SIZE);
+            for _ in 0..Self::MAX_TABLE_SIZE {
+                let next = accumulator.add(&base);
+                table.push(accumulator);
+                accumulator = next;
+            }
+            tables.push(table);
+            for _ in 0..Self::BITS_PER_TABLE {
+                base = base.dbl();
+            }
+        }
+
+        let offset = offset_generators
+            .iter()
+            .fold(GrumpkinPoint::infinity(), |acc, generator| {
+                acc.add(generator)
+            });
+        (tables, offset)
+    }
+
+    fn fixed_base_tables() -> &'static FixedBaseTables {
+        FIXED_BASE_TABLES.get_or_init(|| {
+            let (tables, offsets) = (0..Self::NUM_FIXED_BASE_MULTI_TABLES)
+                .map(|multitable_index| {
+                    Self::generate_tables(
+                        &Self::get_base_point(multitable_index),
+                        Self::get_num_bits_of_multi_table(multitable_index),
+                    )
+                })
+                .unzip();
+            FixedBaseTables { tables, offsets }
+        })
+    }
+
+    /// Returns the x and y coordinates of the point for the key in the basic table.
+    fn get_basic_fixed_base_table_values<
+        F: PrimeField,
+        const MULTITABLE_INDEX: usize,
+        const TABLE_INDEX: usize,
+    >(
+        key: [u64; 2],
+    ) -> [F; 2] {
+        let point = Self::fixed_base_tables().tables[MULTITABLE_INDEX][TABLE_INDEX]
+            [key[0] as usize]
+            .convert::<F>();
+        [point.x, point.y]
+    }
+
+    /// Returns the functions that compute the values of the basic tables of the multitable.
+    pub(crate) fn get_table_values_fns<F: PrimeField>(
+        multitable_index: usize,
+    ) -> Vec<fn([u64; 2]) -> [F; 2]> {
+        match multitable_index {
+            0 => fixed_base_table_values!(0, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14]),
+            1 => fixed_base_table_values!(1, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13]),
+            2 => fixed_base_table_values!(2, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14]),
+            3 => fixed_base_table_values!(3, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13]),
+            _ => unreachable!("there are only four fixed-base multitables"),
+        }
+    }
+
+    /// Returns the points (key, x, y) of the basic table with the given index in the multitable.
+    pub(crate) fn get_basic_table_points<F: PrimeField>(
+        multitable_index: usize,
+        table_index: usize,
+    ) -> Vec<GrumpkinPoint<F>> {
+        let multitable_bits = Self::get_num_bits_of_multi_table(multitable_index);
+        let table_bits =
+            (multitable_bits - Self::BITS_PER_TABLE * table_index).min(Self::BITS_PER_TABLE);
+        Self::fixed_base_tables().tables[multitable_index][table_index][..1 << table_bits]
+            .iter()
+            .map(GrumpkinPoint::convert)
+            .collect()
+    }
+
+    fn get_multitable_index(id: &MultiTableId) -> Option<usize> {
+        match id {
+            MultiTableId::FixedBaseLeftLo => Some(0),
+            MultiTableId::FixedBaseLeftHi => Some(1),
+            MultiTableId::FixedBaseRightLo => Some(2),
+            MultiTableId::FixedBaseRightHi => Some(3),
+            _ => None,
+        }
+    }
+
+    /// Returns true if there are lookup tables for the scalar multiplication of the point.
+    pub(crate) fn lookup_table_exists_for_point<F: PrimeField>(point: &GrumpkinPoint<F>) -> bool {
+        *point == Self::lhs_generator_point() || *point == Self::rhs_generator_point()
+    }
+
+    /// Returns the multitables for the low and high limb of a scalar multiplied with the point.
+    pub(crate) fn get_lookup_table_ids_for_point<F: PrimeField>(
+        point: &GrumpkinPoint<F>,
+    ) -> Option<[MultiTableId; 2]> {
+        if *point == Self::lhs_generator_point() {
+            Some([MultiTableId::FixedBaseLeftLo, MultiTableId::FixedBaseLeftHi])
+        } else if *point == Self::rhs_generator_point() {
+            Some([
+                MultiTableId::FixedBaseRightLo,
+                MultiTableId::FixedBaseRightHi,
+            ])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the sum of the offset generators which a lookup in the multitable adds to the result.
+    pub(crate) fn get_generator_offset_for_table_id<F: PrimeField>(
+        id: &MultiTableId,
+    ) -> Option<GrumpkinPoint<F>> {
+        Self::get_multitable_index(id)
+            .map(|multitable_index| Self::fixed_base_tables().offsets[multitable_index].convert())
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) enum MultiTableId {
     Sha256ChInput,
@@ -232,15 +548,46 @@ impl<F: PrimeField> Plookup<F> {
         table
     }
 
+    fn get_fixed_base_table(id: MultiTableId, multitable_index: usize) -> PlookupMultiTable<F> {
+        let num_tables = FIXED_BASE_TABLE_IDS[multitable_index].len();
+        let mut table = PlookupMultiTable::new(
+            F::from(FixedBaseParams::MAX_TABLE_SIZE as u64),
+            F::zero(),
+            F::zero(),
+            num_tables,
+        );
+        table.id = id;
+        table.basic_table_ids = FIXED_BASE_TABLE_IDS[multitable_index].to_vec();
+        table.slice_sizes = vec![FixedBaseParams::MAX_TABLE_SIZE as u64; num_tables];
+        table.get_table_values = FixedBaseParams::get_table_values_fns(multitable_index);
+        table
+    }
+
     fn init_multi_tables() -> [PlookupMultiTable<F>; MultiTableId::NumMultiTables as usize] {
         // TACEO TODO not all are initialized here!
         let mut multi_tables = from_fn(|_| PlookupMultiTable::default());
         multi_tables[usize::from(MultiTableId::HonkDummyMulti)] = Self::get_honk_dummy_multitable();
+        for (multitable_index, id) in [
+            MultiTableId::FixedBaseLeftLo,
+            MultiTableId::FixedBaseLeftHi,
+            MultiTableId::FixedBaseRightLo,
+            MultiTableId::FixedBaseRightHi,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            multi_tables[usize::from(id.to_owned())] =
+                Self::get_fixed_base_table(id, multitable_index);
+        }
         multi_tables
     }
 
     pub(crate) fn get_multitable(&self, id: MultiTableId) -> &PlookupMultiTable<F> {
-        assert_eq!(id, MultiTableId::HonkDummyMulti); // The only one implemented so far
+        // The only ones implemented so far
+        assert!(
+            id == MultiTableId::HonkDummyMulti
+                || FixedBaseParams::get_multitable_index(&id).is_some()
+        );
         &self.multi_tables[usize::from(id)]
     }
 
@@ -1,7 +1,7 @@
 use crate::builder::{GenericUltraCircuitBuilder, UltraCircuitBuilder};
 use crate::keys::proving_key::ProvingKey;
 use crate::polynomials::polynomial::Polynomial;
-use crate::types::plookup::BasicTableId;
+use crate::types::plookup::{BasicTableId, FixedBaseParams};
 use crate::utils::Utils;
 use ark_ec::pairing::Pairing;
 use ark_ff::PrimeField;
@@ -66,6 +66,35 @@ pub(crate) struct MulQuad<F: PrimeField> {
     pub(crate) const_scaling: F,
 }
 
+/// The witness indices of the limbs of the operands of a non-native field multiplication a * b = q * p + r, where neg_modulus are the limbs of -p modulo 2^272.
+pub(crate) struct NonNativeFieldWitnesses<F: PrimeField> {
+    pub(crate) a: [u32; 4],
+    pub(crate) b: [u32; 4],
+    pub(crate) q: [u32; 4],
+    pub(crate) r: [u32; 4],
+    pub(crate) neg_modulus: [F; 4],
+}
+
+/// A limb of a non-native field addition: the witness index and the multiplicative constant of both operands, followed by the sum of their additive constants.
+pub(crate) type AddSimple<F> = ((u32, F), (u32, F), F);
+
+pub(crate) struct EccAddGate<F: PrimeField> {
+    pub(crate) x1: u32,
+    pub(crate) y1: u32,
+    pub(crate) x2: u32,
+    pub(crate) y2: u32,
+    pub(crate) x3: u32,
+    pub(crate) y3: u32,
+    pub(crate) sign_coefficient: F,
+}
+
+pub(crate) struct EccDblGate {
+    pub(crate) x1: u32,
+    pub(crate) y1: u32,
+    pub(crate) x3: u32,
+    pub(crate) y3: u32,
+}
+
 pub(crate) struct MemOp<F: PrimeField> {
     pub(crate) access_type: u8,
     pub(crate) index: PolyTriple<F>,
@@ -112,15 +141,15 @@ pub(crate) struct AcirFormatOriginalOpcodeIndices {
     // pub(crate) pedersen_constraints: Vec<usize>,
     // pub(crate) pedersen_hash_constraints: Vec<usize>,
     // pub(crate) poseidon2_constraints: Vec<usize>,
-    // pub(crate) multi_scalar_mul_constraints: Vec<usize>,
-    // pub(crate) ec_add_constraints: Vec<usize>,
+    pub(crate) multi_scalar_mul_constraints: Vec<usize>,
+    pub(crate) ec_add_constraints: Vec<usize>,
     // pub(crate) recursion_constraints: Vec<usize>,
     // pub(crate) honk_recursion_constraints: Vec<usize>,
     // pub(crate) avm_recursion_constraints: Vec<usize>,
     // pub(crate) ivc_recursion_constraints: Vec<usize>,
-    // pub(crate) bigint_from_le_bytes_constraints: Vec<usize>,
-    // pub(crate) bigint_to_le_bytes_constraints: Vec<usize>,
-    // pub(crate) bigint_operations: Vec<usize>,
+    pub(crate) bigint_from_le_bytes_constraints: Vec<usize>,
+    pub(crate) bigint_to_le_bytes_constraints: Vec<usize>,
+    pub(crate) bigint_operations: Vec<usize>,
     pub(crate) assert_equalities: Vec<usize>,
     pub(crate) poly_triple_constraints: Vec<usize>,
     pub(crate) quad_constraints: Vec<usize>,
@@ -400,6 +429,60 @@ pub(crate) struct RangeConstraint {
     pub(crate) num_bits: u32,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum WitnessOrConstant<F: PrimeField> {
+    Witness(u32),
+    Constant(F),
+}
+
+pub(crate) struct MultiScalarMul<F: PrimeField> {
+    // (x, y, is_infinite) per point
+    pub(crate) points: Vec<WitnessOrConstant<F>>,
+    // (lo, hi) per scalar
+    pub(crate) scalars: Vec<WitnessOrConstant<F>>,
+    pub(crate) out_point_x: u32,
+    pub(crate) out_point_y: u32,
+    pub(crate) out_point_is_infinite: u32,
+}
+
+pub(crate) struct EcAdd<F: PrimeField> {
+    pub(crate) input1_x: WitnessOrConstant<F>,
+    pub(crate) input1_y: WitnessOrConstant<F>,
+    pub(crate) input1_infinite: WitnessOrConstant<F>,
+    pub(crate) input2_x: WitnessOrConstant<F>,
+    pub(crate) input2_y: WitnessOrConstant<F>,
+    pub(crate) input2_infinite: WitnessOrConstant<F>,
+    pub(crate) result_x: u32,
+    pub(crate) result_y: u32,
+    pub(crate) result_infinite: u32,
+}
+
+pub(crate) struct BigIntFromLeBytes<F: PrimeField> {
+    pub(crate) inputs: Vec<WitnessOrConstant<F>>,
+    pub(crate) modulus: Vec<u32>,
+    pub(crate) result: u32,
+}
+
+pub(crate) struct BigIntToLeBytes {
+    pub(crate) input: u32,
+    pub(crate) result: Vec<u32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum BigIntOperationType {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+pub(crate) struct BigIntOperation {
+    pub(crate) lhs: u32,
+    pub(crate) rhs: u32,
+    pub(crate) result: u32,
+    pub(crate) opcode: BigIntOperationType,
+}
+
 pub(crate) struct RecursionConstraint {
     // An aggregation state is represented by two G1 affine elements. Each G1 point has
     // two field element coordinates (x, y). Thus, four field elements
@@ -638,11 +721,14 @@ impl<F: PrimeField> FieldCT<F> {
         }
     }
 
-    fn is_constant(&self) -> bool {
+    pub(crate) fn is_constant(&self) -> bool {
         self.witness_index == Self::IS_CONSTANT
     }
 
-    fn normalize<P: Pairing<ScalarField = F>, T: NoirWitnessExtensionProtocol<P::ScalarField>>(
+    pub(crate) fn normalize<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
         &self,
         builder: &mut GenericUltraCircuitBuilder<P, T>,
         driver: &mut T,
@@ -682,6 +768,603 @@ impl<F: PrimeField> FieldCT<F> {
         });
         result
     }
+
+    /// Computes \[numerator\] / \[denominator\]. Like barretenberg, a public division by zero results in zero.
+    pub(crate) fn divide_acvm_types<T: NoirWitnessExtensionProtocol<F>>(
+        numerator: T::AcvmType,
+        denominator: T::AcvmType,
+        driver: &mut T,
+    ) -> std::io::Result<T::AcvmType> {
+        if let Some(denominator) = T::get_public(&denominator) {
+            let inverse = denominator.inverse().unwrap_or_else(F::zero);
+            Ok(driver.mul_with_public(inverse, numerator))
+        } else {
+            let mut neg_numerator = numerator;
+            driver.negate_inplace(&mut neg_numerator);
+            driver
+                .solve_equation(denominator, neg_numerator)
+                .map_err(|e| std::io::Error::other(e.to_string()))
+        }
+    }
+
+    pub(crate) fn neg(&self) -> Self {
+        Self {
+            additive_constant: -self.additive_constant,
+            multiplicative_constant: -self.multiplicative_constant,
+            witness_index: self.witness_index,
+        }
+    }
+
+    pub(crate) fn add<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        &self,
+        other: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> Self {
+        let mut result = Self::default();
+        if self.witness_index == other.witness_index {
+            result.additive_constant = self.additive_constant + other.additive_constant;
+            result.multiplicative_constant =
+                self.multiplicative_constant + other.multiplicative_constant;
+            result.witness_index = self.witness_index;
+        } else if self.is_constant() && other.is_constant() {
+            // both inputs are constant - don't add a gate
+            result.additive_constant = self.additive_constant + other.additive_constant;
+        } else if !self.is_constant() && other.is_constant() {
+            // one input is constant - don't add a gate, but update scaling factors
+            result.additive_constant = self.additive_constant + other.additive_constant;
+            result.multiplicative_constant = self.multiplicative_constant;
+            result.witness_index = self.witness_index;
+        } else if self.is_constant() && !other.is_constant() {
+            result.additive_constant = self.additive_constant + other.additive_constant;
+            result.multiplicative_constant = other.multiplicative_constant;
+            result.witness_index = other.witness_index;
+        } else {
+            let left = self.get_value(builder, driver);
+            let right = other.get_value(builder, driver);
+            let out = driver.add(left, right);
+            result.witness_index = builder.add_variable(out);
+
+            builder.create_add_gate(&AddTriple {
+                a: self.witness_index,
+                b: other.witness_index,
+                c: result.witness_index,
+                a_scaling: self.multiplicative_constant,
+                b_scaling: other.multiplicative_constant,
+                c_scaling: -P::ScalarField::one(),
+                const_scaling: self.additive_constant + other.additive_constant,
+            });
+        }
+        result
+    }
+
+    pub(crate) fn sub<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        &self,
+        other: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> Self {
+        let mut rhs = other.to_owned();
+        rhs.additive_constant = -rhs.additive_constant;
+        if !rhs.is_constant() {
+            rhs.multiplicative_constant = -rhs.multiplicative_constant;
+        }
+        self.add(&rhs, builder, driver)
+    }
+
+    pub(crate) fn multiply<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        &self,
+        other: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> std::io::Result<Self> {
+        let mut result = Self::default();
+        if self.is_constant() && other.is_constant() {
+            // Both inputs are constant - don't add a gate.
+            // The value of a constant is tracked in `.additive_constant`.
+            result.additive_constant = self.additive_constant * other.additive_constant;
+        } else if !self.is_constant() && other.is_constant() {
+            // One input is constant: don't add a gate, but update scaling factors.
+            result.additive_constant = self.additive_constant * other.additive_constant;
+            result.multiplicative_constant = self.multiplicative_constant * other.additive_constant;
+            result.witness_index = self.witness_index;
+        } else if self.is_constant() && !other.is_constant() {
+            result.additive_constant = self.additive_constant * other.additive_constant;
+            result.multiplicative_constant = other.multiplicative_constant * self.additive_constant;
+            result.witness_index = other.witness_index;
+        } else {
+            // result = a * b
+            //        = [a.v * a.mul + a.add] * [b.v * b.mul + b.add]
+            //        = a.v * b.v * [a.mul * b.mul] + a.v * [a.mul * b.add] + b.v * [a.add * b.mul] + [a.ad * b.add]
+            //        = a.v * b.v * [    q_m      ] + a.v * [     q_l     ] + b.v * [     q_r     ] + [    q_c     ]
+            let left = self.get_value(builder, driver);
+            let right = other.get_value(builder, driver);
+            let out = driver.mul(left, right)?;
+            result.witness_index = builder.add_variable(out);
+
+            builder.create_poly_gate(&PolyTriple {
+                a: self.witness_index,
+                b: other.witness_index,
+                c: result.witness_index,
+                q_m: self.multiplicative_constant * other.multiplicative_constant,
+                q_l: self.multiplicative_constant * other.additive_constant,
+                q_r: self.additive_constant * other.multiplicative_constant,
+                q_o: -P::ScalarField::one(),
+                q_c: self.additive_constant * other.additive_constant,
+            });
+        }
+        Ok(result)
+    }
+
+    /// Computes this * to_mul + to_add with a single gate.
+    pub(crate) fn madd<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        &self,
+        to_mul: &Self,
+        to_add: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> std::io::Result<Self> {
+        if self.is_constant() && to_mul.is_constant() && to_add.is_constant() {
+            return Ok(self
+                .multiply(to_mul, builder, driver)?
+                .add(to_add, builder, driver));
+        }
+
+        // result = a * b + c
+        //   = (a.v * a.mul + a.add) * (b.v * b.mul + b.add) + (c.v * c.mul + c.add)
+        //   = a.v * b.v * [a.mul * b.mul] + a.v * [a.mul * b.add] + b.v * [b.mul + a.add] + c.v * [c.mul] + [a.add * b.add + c.add]
+        //   = a.v * b.v * [     q_m     ] + a.v * [     q_1     ] + b.v * [     q_2     ] + c.v * [ q_3 ] + [ q_c ]
+        let q_m = self.multiplicative_constant * to_mul.multiplicative_constant;
+        let q_1 = self.multiplicative_constant * to_mul.additive_constant;
+        let q_2 = to_mul.multiplicative_constant * self.additive_constant;
+        let q_3 = to_add.multiplicative_constant;
+        let q_c = self.additive_constant * to_mul.additive_constant + to_add.additive_constant;
+
+        let a = self.get_value(builder, driver);
+        let b = to_mul.get_value(builder, driver);
+        let c = to_add.get_value(builder, driver);
+        let out = driver.mul(a, b)?;
+        let out = driver.add(out, c);
+        let witness_index = builder.add_variable(out);
+
+        // Note: the value of a constant field_t is wholly tracked by the field_t's `additive_constant` member, which is
+        // accounted for in the above-calculated selectors (`q_`'s). Therefore no witness (`variables[witness_index]`)
+        // exists for constants, and so the field_t's corresponding wire value is set to `0` in the gate equation.
+        builder.create_big_mul_gate(&MulQuad {
+            a: self.witness_index_or(builder.zero_idx),
+            b: to_mul.witness_index_or(builder.zero_idx),
+            c: to_add.witness_index_or(builder.zero_idx),
+            d: witness_index,
+            mul_scaling: q_m,
+            a_scaling: q_1,
+            b_scaling: q_2,
+            c_scaling: q_3,
+            d_scaling: -P::ScalarField::one(),
+            const_scaling: q_c,
+        });
+        Ok(Self::from_witness_index(witness_index))
+    }
+
+    /// Computes this + add_a + add_b with a single gate.
+    pub(crate) fn add_two<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        &self,
+        add_a: &Self,
+        add_b: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> Self {
+        if self.is_constant() && add_a.is_constant() && add_b.is_constant() {
+            return self.add(add_a, builder, driver).add(add_b, builder, driver);
+        }
+
+        let q_1 = self.multiplicative_constant;
+        let q_2 = add_a.multiplicative_constant;
+        let q_3 = add_b.multiplicative_constant;
+        let q_c = self.additive_constant + add_a.additive_constant + add_b.additive_constant;
+
+        let a = self.get_value(builder, driver);
+        let b = add_a.get_value(builder, driver);
+        let c = add_b.get_value(builder, driver);
+        let out = driver.add(a, b);
+        let out = driver.add(out, c);
+        let witness_index = builder.add_variable(out);
+
+        builder.create_big_mul_gate(&MulQuad {
+            a: self.witness_index_or(builder.zero_idx),
+            b: add_a.witness_index_or(builder.zero_idx),
+            c: add_b.witness_index_or(builder.zero_idx),
+            d: witness_index,
+            mul_scaling: P::ScalarField::zero(),
+            a_scaling: q_1,
+            b_scaling: q_2,
+            c_scaling: q_3,
+            d_scaling: -P::ScalarField::one(),
+            const_scaling: q_c,
+        });
+        Self::from_witness_index(witness_index)
+    }
+
+    /// Computes the sum of the inputs with one gate per three non-constant inputs.
+    pub(crate) fn accumulate<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        input: &[Self],
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> Self {
+        if input.is_empty() {
+            return Self::from(F::zero());
+        }
+        if input.len() == 1 {
+            return input[0].to_owned();
+        }
+
+        // Constants are folded into the first non-constant input, since they do not need a wire
+        let mut constant_term = F::zero();
+        let mut accumulator = Vec::with_capacity(input.len());
+        for element in input {
+            if element.is_constant() {
+                constant_term += element.additive_constant;
+            } else {
+                accumulator.push(element.to_owned());
+            }
+        }
+        if accumulator.is_empty() {
+            return Self::from(constant_term);
+        }
+        accumulator[0].additive_constant += constant_term;
+
+        // Pad the accumulator to a multiple of 3
+        while accumulator.len() % 3 != 0 {
+            accumulator.push(Self::from_witness_index(builder.zero_idx));
+        }
+
+        let mut sum = T::public_zero();
+        for element in accumulator.iter() {
+            let value = element.get_value(builder, driver);
+            driver.add_assign(&mut sum, value);
+        }
+        let total = builder.add_variable(sum.to_owned());
+
+        // Each gate subtracts three inputs from the accumulating total and passes the difference on to the fourth wire of the next gate, the last gate has to result in zero
+        let num_gates = accumulator.len() / 3;
+        let mut accumulating_total = total;
+        let mut accumulating_value = sum;
+        for (i, chunk) in accumulator.chunks_exact(3).enumerate() {
+            builder.create_big_add_gate(
+                &AddQuad {
+                    a: chunk[0].witness_index,
+                    b: chunk[1].witness_index,
+                    c: chunk[2].witness_index,
+                    d: accumulating_total,
+                    a_scaling: chunk[0].multiplicative_constant,
+                    b_scaling: chunk[1].multiplicative_constant,
+                    c_scaling: chunk[2].multiplicative_constant,
+                    d_scaling: -F::one(),
+                    const_scaling: chunk[0].additive_constant
+                        + chunk[1].additive_constant
+                        + chunk[2].additive_constant,
+                },
+                i != num_gates - 1,
+            );
+            if i != num_gates - 1 {
+                for element in chunk {
+                    let value = element.get_value(builder, driver);
+                    accumulating_value = driver.sub(accumulating_value, value);
+                }
+                accumulating_total = builder.add_variable(accumulating_value.to_owned());
+            }
+        }
+        Self::from_witness_index(total)
+    }
+
+    /// Constrains a + b + c + d = 0 with a single gate.
+    pub(crate) fn evaluate_linear_identity<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        a: &Self,
+        b: &Self,
+        c: &Self,
+        d: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+    ) {
+        if a.is_constant() && b.is_constant() && c.is_constant() && d.is_constant() {
+            assert!((a.additive_constant
+                + b.additive_constant
+                + c.additive_constant
+                + d.additive_constant)
+                .is_zero());
+            return;
+        }
+
+        // validate that a + b + c + d = 0
+        let q_1 = a.multiplicative_constant;
+        let q_2 = b.multiplicative_constant;
+        let q_3 = c.multiplicative_constant;
+        let q_4 = d.multiplicative_constant;
+        let q_c =
+            a.additive_constant + b.additive_constant + c.additive_constant + d.additive_constant;
+
+        builder.create_big_add_gate(
+            &AddQuad {
+                a: a.witness_index_or(builder.zero_idx),
+                b: b.witness_index_or(builder.zero_idx),
+                c: c.witness_index_or(builder.zero_idx),
+                d: d.witness_index_or(builder.zero_idx),
+                a_scaling: q_1,
+                b_scaling: q_2,
+                c_scaling: q_3,
+                d_scaling: q_4,
+                const_scaling: q_c,
+            },
+            false,
+        );
+    }
+
+    /// Constrains a * b + c + d = 0 with a single gate.
+    pub(crate) fn evaluate_polynomial_identity<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        a: &Self,
+        b: &Self,
+        c: &Self,
+        d: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+    ) {
+        if a.is_constant() && b.is_constant() && c.is_constant() && d.is_constant() {
+            assert!((a.additive_constant * b.additive_constant
+                + c.additive_constant
+                + d.additive_constant)
+                .is_zero());
+            return;
+        }
+
+        // validate that a * b + c + d = 0
+        let q_m = a.multiplicative_constant * b.multiplicative_constant;
+        let q_1 = a.multiplicative_constant * b.additive_constant;
+        let q_2 = b.multiplicative_constant * a.additive_constant;
+        let q_3 = c.multiplicative_constant;
+        let q_4 = d.multiplicative_constant;
+        let q_c =
+            a.additive_constant * b.additive_constant + c.additive_constant + d.additive_constant;
+
+        builder.create_big_mul_gate(&MulQuad {
+            a: a.witness_index_or(builder.zero_idx),
+            b: b.witness_index_or(builder.zero_idx),
+            c: c.witness_index_or(builder.zero_idx),
+            d: d.witness_index_or(builder.zero_idx),
+            mul_scaling: q_m,
+            a_scaling: q_1,
+            b_scaling: q_2,
+            c_scaling: q_3,
+            d_scaling: q_4,
+            const_scaling: q_c,
+        });
+    }
+
+    /// Returns a boolean which is true if this field is equal to the given field.
+    pub(crate) fn equals<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        &self,
+        other: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> std::io::Result<BoolCT> {
+        if self.is_constant() && other.is_constant() {
+            return Ok(BoolCT::from(
+                self.additive_constant == other.additive_constant,
+            ));
+        }
+
+        let fa = self.get_value(builder, driver);
+        let fb = other.get_value(builder, driver);
+        let is_equal = driver.equal(&fa, &fb)?;
+        // fc = 1 if the values are equal and the inverse of their difference otherwise
+        let fd = driver.sub(fa, fb);
+        let fd = driver.add(fd, is_equal.to_owned());
+        let fc = Self::divide_acvm_types(T::AcvmType::from(F::one()), fd, driver)?;
+
+        let result = BoolCT::from_witness(is_equal, builder);
+        let r = FieldCT::from(&result);
+        let x = FieldCT::from_witness(fc, builder);
+        let diff = self.sub(other, builder, driver);
+        // these constraints ensure that result is a boolean
+        let one = FieldCT::from(F::one());
+        let t1 = r.madd(&x.neg().add(&one, builder, driver), &x, builder, driver)?;
+        let t2 = diff.madd(&t1, &r.sub(&one, builder, driver), builder, driver)?;
+        t2.assert_equal(&FieldCT::from(F::zero()), builder, driver);
+        Ok(result)
+    }
+
+    pub(crate) fn assert_is_not_zero<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        &self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> std::io::Result<()> {
+        if self.is_constant() {
+            assert!(!self.additive_constant.is_zero());
+            return Ok(());
+        }
+
+        // We cannot check that the value is non-zero in MPC, the proof will just not verify if it is zero
+        let value = self.get_value(builder, driver);
+        let inverse = Self::divide_acvm_types(T::AcvmType::from(F::one()), value, driver)?;
+        let inverse = builder.add_variable(inverse);
+
+        // Let a := *this;
+        //     q_l := a.multiplicative_constant;
+        //     q_c := a.additive_constant;
+        //     a.v := ctx->variables[a.witness_index];
+        // Let b := inverse;
+        // (a.v * q_l + q_c) * b = 1  =>  a.v * b * q_l + q_c * b - 1 = 0
+        builder.create_poly_gate(&PolyTriple {
+            a: self.witness_index,
+            b: inverse,
+            c: builder.zero_idx,
+            q_m: self.multiplicative_constant,
+            q_l: P::ScalarField::zero(),
+            q_r: self.additive_constant,
+            q_o: P::ScalarField::zero(),
+            q_c: -P::ScalarField::one(),
+        });
+        Ok(())
+    }
+
+    pub(crate) fn divide<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        &self,
+        other: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> std::io::Result<Self> {
+        other.assert_is_not_zero(builder, driver)?;
+        self.divide_no_zero_check(other, builder, driver)
+    }
+
+    pub(crate) fn divide_no_zero_check<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        &self,
+        other: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> std::io::Result<Self> {
+        let mut result = Self::default();
+        if other.is_constant() {
+            // don't add a gate, but update scaling factors
+            let additive_multiplier = other.additive_constant.inverse().unwrap_or_else(F::one);
+            result.additive_constant = self.additive_constant * additive_multiplier;
+            if !self.is_constant() {
+                result.multiplicative_constant = self.multiplicative_constant * additive_multiplier;
+                result.witness_index = self.witness_index;
+            }
+        } else if self.is_constant() {
+            // numerator 0?
+            if self.additive_constant.is_zero() {
+                return Ok(result);
+            }
+            let denominator = other.get_value(builder, driver);
+            let out = Self::divide_acvm_types(
+                T::AcvmType::from(self.additive_constant),
+                denominator,
+                driver,
+            )?;
+            result.witness_index = builder.add_variable(out);
+            builder.create_poly_gate(&PolyTriple {
+                a: result.witness_index,
+                b: other.witness_index,
+                c: result.witness_index,
+                q_m: other.multiplicative_constant,
+                q_l: other.additive_constant,
+                q_r: P::ScalarField::zero(),
+                q_o: P::ScalarField::zero(),
+                q_c: -self.additive_constant,
+            });
+        } else {
+            let numerator = self.get_value(builder, driver);
+            let denominator = other.get_value(builder, driver);
+            let out = Self::divide_acvm_types(numerator, denominator, driver)?;
+            result.witness_index = builder.add_variable(out);
+            builder.create_poly_gate(&PolyTriple {
+                a: result.witness_index,
+                b: other.witness_index,
+                c: self.witness_index,
+                q_m: other.multiplicative_constant,
+                q_l: other.additive_constant,
+                q_r: P::ScalarField::zero(),
+                q_o: -self.multiplicative_constant,
+                q_c: -self.additive_constant,
+            });
+        }
+        Ok(result)
+    }
+
+    /// Returns lhs if the predicate is true and rhs otherwise.
+    pub(crate) fn conditional_assign<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        predicate: &BoolCT,
+        lhs: &Self,
+        rhs: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> std::io::Result<Self> {
+        if predicate.is_constant() {
+            return Ok(if predicate.get_constant_value() {
+                lhs.to_owned()
+            } else {
+                rhs.to_owned()
+            });
+        }
+        // if lhs and rhs are the same witness, just return it!
+        if lhs.witness_index == rhs.witness_index
+            && lhs.additive_constant == rhs.additive_constant
+            && lhs.multiplicative_constant == rhs.multiplicative_constant
+        {
+            return Ok(lhs.to_owned());
+        }
+        lhs.sub(rhs, builder, driver)
+            .madd(&FieldCT::from(predicate), rhs, builder, driver)
+    }
+
+    pub(crate) fn create_range_constraint<
+        P: Pairing<ScalarField = F>,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        &self,
+        num_bits: usize,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> std::io::Result<()> {
+        if self.is_constant() {
+            let value: BigUint = self.additive_constant.into();
+            assert!(value.bits() <= num_bits as u64);
+        } else {
+            let normalized = self.normalize(builder, driver);
+            builder.decompose_into_default_range(
+                driver,
+                normalized.witness_index,
+                num_bits as u64,
+                GenericUltraCircuitBuilder::<P, T>::DEFAULT_PLOOKUP_RANGE_BITNUM as u64,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn witness_index_or(&self, constant_index: u32) -> u32 {
+        if self.is_constant() {
+            constant_index
+        } else {
+            self.witness_index
+        }
+    }
 }
 
 impl<F: PrimeField> From<F> for FieldCT<F> {
@@ -719,6 +1402,281 @@ impl<F: PrimeField> Default for FieldCT<F> {
     }
 }
 
+impl<F: PrimeField> From<&BoolCT> for FieldCT<F> {
+    fn from(value: &BoolCT) -> Self {
+        if value.is_constant() {
+            Self::from(F::from(value.witness_bool ^ value.witness_inverted))
+        } else if value.witness_inverted {
+            Self {
+                additive_constant: F::one(),
+                multiplicative_constant: -F::one(),
+                witness_index: value.witness_index,
+            }
+        } else {
+            Self::from_witness_index(value.witness_index)
+        }
+    }
+}
+
+/// A boolean in the circuit. The value of a constant is tracked in `witness_bool`, a witness is inverted lazily by flipping `witness_inverted`.
+#[derive(Clone, Debug)]
+pub(crate) struct BoolCT {
+    pub(crate) witness_bool: bool,
+    pub(crate) witness_inverted: bool,
+    pub(crate) witness_index: u32,
+}
+
+impl BoolCT {
+    const IS_CONSTANT: u32 = u32::MAX;
+
+    pub(crate) fn is_constant(&self) -> bool {
+        self.witness_index == Self::IS_CONSTANT
+    }
+
+    /// Returns the value of a constant boolean.
+    pub(crate) fn get_constant_value(&self) -> bool {
+        assert!(self.is_constant());
+        self.witness_bool ^ self.witness_inverted
+    }
+
+    /// Adds the given value as a witness and constrains it to be a boolean.
+    pub(crate) fn from_witness<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>>(
+        value: T::AcvmType,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+    ) -> Self {
+        let witness_index = builder.add_variable(value);
+        builder.create_bool_gate(witness_index);
+        Self {
+            witness_bool: false,
+            witness_inverted: false,
+            witness_index,
+        }
+    }
+
+    /// Constrains the given field to be a boolean and converts it.
+    pub(crate) fn from_field<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>>(
+        value: &FieldCT<P::ScalarField>,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> Self {
+        if value.is_constant() {
+            let additive_constant = value.additive_constant;
+            assert!(additive_constant.is_zero() || additive_constant.is_one());
+            return Self::from(additive_constant.is_one());
+        }
+
+        let add_constant_check = value.additive_constant.is_zero();
+        let mul_constant_check = value.multiplicative_constant.is_one();
+        let inverted_check = value.additive_constant.is_one()
+            && value.multiplicative_constant == -P::ScalarField::one();
+        let witness_index = if (!add_constant_check || !mul_constant_check) && !inverted_check {
+            value.normalize(builder, driver).witness_index
+        } else {
+            value.witness_index
+        };
+        builder.create_bool_gate(witness_index);
+        Self {
+            witness_bool: false,
+            witness_inverted: inverted_check,
+            witness_index,
+        }
+    }
+
+    pub(crate) fn get_value<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>>(
+        &self,
+        builder: &GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> T::AcvmType {
+        FieldCT::from(self).get_value(builder, driver)
+    }
+
+    pub(crate) fn not(&self) -> Self {
+        let mut result = self.to_owned();
+        if result.is_constant() {
+            result.witness_bool = !result.witness_bool;
+        } else {
+            result.witness_inverted = !result.witness_inverted;
+        }
+        result
+    }
+
+    pub(crate) fn and<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>>(
+        &self,
+        other: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> std::io::Result<Self> {
+        match (self.is_constant(), other.is_constant()) {
+            (true, true) => Ok(Self::from(
+                self.get_constant_value() && other.get_constant_value(),
+            )),
+            (true, false) => Ok(if self.get_constant_value() {
+                other.to_owned()
+            } else {
+                Self::from(false)
+            }),
+            (false, true) => Ok(if other.get_constant_value() {
+                self.to_owned()
+            } else {
+                Self::from(false)
+            }),
+            (false, false) => {
+                let left = self.get_value(builder, driver);
+                let right = other.get_value(builder, driver);
+                let value = driver.mul(left, right)?;
+                let witness_index = builder.add_variable(value);
+
+                // (a.v * (1 - 2 * a.inv) + a.inv) * (b.v * (1 - 2 * b.inv) + b.inv) = result
+                let one = P::ScalarField::one();
+                let two = one + one;
+                let ia = P::ScalarField::from(self.witness_inverted);
+                let ib = P::ScalarField::from(other.witness_inverted);
+                builder.create_poly_gate(&PolyTriple {
+                    a: self.witness_index,
+                    b: other.witness_index,
+                    c: witness_index,
+                    q_m: (one - two * ia) * (one - two * ib),
+                    q_l: ib * (one - two * ia),
+                    q_r: ia * (one - two * ib),
+                    q_o: -one,
+                    q_c: ia * ib,
+                });
+                Ok(Self {
+                    witness_bool: false,
+                    witness_inverted: false,
+                    witness_index,
+                })
+            }
+        }
+    }
+
+    pub(crate) fn or<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>>(
+        &self,
+        other: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> std::io::Result<Self> {
+        match (self.is_constant(), other.is_constant()) {
+            (true, true) => Ok(Self::from(
+                self.get_constant_value() || other.get_constant_value(),
+            )),
+            (true, false) => Ok(if self.get_constant_value() {
+                Self::from(true)
+            } else {
+                other.to_owned()
+            }),
+            (false, true) => Ok(if other.get_constant_value() {
+                Self::from(true)
+            } else {
+                self.to_owned()
+            }),
+            (false, false) => {
+                // result = a + b - a * b
+                let left = self.get_value(builder, driver);
+                let right = other.get_value(builder, driver);
+                let product = driver.mul(left.to_owned(), right.to_owned())?;
+                let sum = driver.add(left, right);
+                let value = driver.sub(sum, product);
+                let witness_index = builder.add_variable(value);
+
+                let one = P::ScalarField::one();
+                let two = one + one;
+                let ia = P::ScalarField::from(self.witness_inverted);
+                let ib = P::ScalarField::from(other.witness_inverted);
+                let rhs_multiplicative_constant = one - two * ib;
+                let lhs_multiplicative_constant = one - two * ia;
+                builder.create_poly_gate(&PolyTriple {
+                    a: self.witness_index,
+                    b: other.witness_index,
+                    c: witness_index,
+                    q_m: -(lhs_multiplicative_constant * rhs_multiplicative_constant),
+                    q_l: lhs_multiplicative_constant * (one - ib),
+                    q_r: rhs_multiplicative_constant * (one - ia),
+                    q_o: -one,
+                    q_c: ia + ib - ia * ib,
+                });
+                Ok(Self {
+                    witness_bool: false,
+                    witness_inverted: false,
+                    witness_index,
+                })
+            }
+        }
+    }
+
+    /// Removes a lazy inversion of a witness by adding a new witness.
+    pub(crate) fn normalize<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>>(
+        &self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> Self {
+        if self.is_constant() || !self.witness_inverted {
+            return self.to_owned();
+        }
+
+        let value = self.get_value(builder, driver);
+        let witness_index = builder.add_variable(value);
+        // 1 - a.v - result.v = 0
+        builder.create_poly_gate(&PolyTriple {
+            a: self.witness_index,
+            b: self.witness_index,
+            c: witness_index,
+            q_m: P::ScalarField::zero(),
+            q_l: -P::ScalarField::one(),
+            q_r: P::ScalarField::zero(),
+            q_o: -P::ScalarField::one(),
+            q_c: P::ScalarField::one(),
+        });
+        Self {
+            witness_bool: false,
+            witness_inverted: false,
+            witness_index,
+        }
+    }
+
+    /// Returns lhs if the predicate is true and rhs otherwise.
+    pub(crate) fn conditional_assign<
+        P: Pairing,
+        T: NoirWitnessExtensionProtocol<P::ScalarField>,
+    >(
+        predicate: &Self,
+        lhs: &Self,
+        rhs: &Self,
+        builder: &mut GenericUltraCircuitBuilder<P, T>,
+        driver: &mut T,
+    ) -> std::io::Result<Self> {
+        if predicate.is_constant() {
+            return Ok(if predicate.get_constant_value() {
+                lhs.to_owned()
+            } else {
+                rhs.to_owned()
+            });
+        }
+
+        let same = lhs.witness_index == rhs.witness_index;
+        let witness_same =
+            same && !lhs.is_constant() && lhs.witness_inverted == rhs.witness_inverted;
+        let const_same =
+            same && lhs.is_constant() && lhs.get_constant_value() == rhs.get_constant_value();
+        if witness_same || const_same {
+            return Ok(lhs.to_owned());
+        }
+        let left = predicate.and(lhs, builder, driver)?;
+        let right = predicate.not().and(rhs, builder, driver)?;
+        left.or(&right, builder, driver)
+    }
+}
+
+impl From<bool> for BoolCT {
+    fn from(value: bool) -> Self {
+        Self {
+            witness_bool: value,
+            witness_inverted: false,
+            witness_index: Self::IS_CONSTANT,
+        }
+    }
+}
+
 pub(crate) struct WitnessCT<P: Pairing, T: NoirWitnessExtensionProtocol<P::ScalarField>> {
     pub(crate) witness: T::AcvmType,
     pub(crate) witness_index: u32,
@@ -965,8 +1923,38 @@ impl<F: PrimeField> PlookupBasicTable<F> {
         table
     }
 
+    fn generate_basic_fixed_base_table(
+        id: BasicTableId,
+        basic_table_index: usize,
+        multitable_index: usize,
+        table_index: usize,
+    ) -> PlookupBasicTable<F> {
+        let points = FixedBaseParams::get_basic_table_points::<F>(multitable_index, table_index);
+        let table_size = points.len();
+        let mut table = PlookupBasicTable::new();
+        table.id = id;
+        table.table_index = basic_table_index;
+        table.use_twin_keys = false;
+        for (i, point) in points.into_iter().enumerate() {
+            table.column_1.push(F::from(i as u64));
+            table.column_2.push(point.x);
+            table.column_3.push(point.y);
+        }
+
+        table.get_values_from_key =
+            FixedBaseParams::get_table_values_fns::<F>(multitable_index)[table_index];
+        table.column_1_step_size = F::from(table_size as u64);
+        table.column_2_step_size = F::zero();
+        table.column_3_step_size = F::zero();
+
+        table
+    }
+
     pub(crate) fn create_basic_table(id: BasicTableId, index: usize) -> Self {
         // TACEO TODO this is a dummy implementation
+        if let Some((multitable_index, table_index)) = id.fixed_base_table_position() {
+            return Self::generate_basic_fixed_base_table(id, index, multitable_index, table_index);
+        }
         assert!(id == BasicTableId::HonkDummyBasic1 || id == BasicTableId::HonkDummyBasic2);
 
         match id {
@@ -21,6 +21,15 @@ impl Utils {
         ark_ff::batch_inversion(coeffs);
     }
 
+    /// Inverts all elements with a single field inversion (Montgomery's trick). In contrast to [Self::batch_invert], which maps zeros to zero, this returns an error if one of the elements is zero.
+    pub fn batch_invert_nonzero<F: PrimeField>(coeffs: &mut [F]) -> HonkProofResult<()> {
+        if coeffs.iter().any(|c| c.is_zero()) {
+            return Err(HonkProofError::InverseOfZero);
+        }
+        ark_ff::batch_inversion(coeffs);
+        Ok(())
+    }
+
     pub fn commit<P: Pairing>(
         poly: &[P::ScalarField],
         crs: &ProverCrs<P>,
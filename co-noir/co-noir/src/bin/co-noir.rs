@@ -1,17 +1,19 @@
 use ark_bn254::Bn254;
 use ark_ff::Zero;
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
 use co_acvm::{
-    solver::Rep3CoSolver, PlainAcvmSolver, Rep3AcvmSolver, Rep3AcvmType, ShamirAcvmSolver,
-    ShamirAcvmType,
+    solver::Rep3CoSolver, BrilligPolicy, PlainAcvmSolver, Rep3AcvmSolver, Rep3AcvmType,
+    ShamirAcvmSolver, ShamirAcvmType,
 };
 use co_noir::{
-    convert_witness_to_vec_rep3, file_utils, share_input_rep3, share_rep3, share_shamir,
-    translate_witness_share_rep3, BuildAndGenerateProofCli, BuildAndGenerateProofConfig,
-    BuildProvingKeyCLi, BuildProvingKeyConfig, CreateVKCli, CreateVKConfig, GenerateProofCli,
-    GenerateProofConfig, GenerateWitnessCli, GenerateWitnessConfig, MPCProtocol,
-    MergeInputSharesCli, MergeInputSharesConfig, PubShared, SplitInputCli, SplitInputConfig,
-    SplitProvingKeyCli, SplitProvingKeyConfig, SplitWitnessCli, SplitWitnessConfig, TranscriptHash,
+    convert_witness_to_vec_rep3, file_utils,
+    output::{CommandOutput, CommandReport, OutputFormat},
+    share_input_rep3, share_rep3, share_shamir, translate_witness_share_rep3,
+    BuildAndGenerateProofCli, BuildAndGenerateProofConfig, BuildProvingKeyCLi,
+    BuildProvingKeyConfig, CreateVKCli, CreateVKConfig, GenerateProofCli, GenerateProofConfig,
+    GenerateWitnessCli, GenerateWitnessConfig, MPCProtocol, MergeInputSharesCli,
+    MergeInputSharesConfig, PubShared, SplitInputCli, SplitInputConfig, SplitProvingKeyCli,
+    SplitProvingKeyConfig, SplitWitnessCli, SplitWitnessConfig, TranscriptHash,
     TranslateProvingKeyCli, TranslateProvingKeyConfig, TranslateWitnessCli, TranslateWitnessConfig,
     VerifyCli, VerifyConfig,
 };
@@ -42,18 +44,27 @@ use std::{
     array,
     collections::BTreeMap,
     fs::File,
-    io::{BufReader, BufWriter, Write},
+    io::{BufWriter, Write},
     path::PathBuf,
     process::ExitCode,
     time::Instant,
 };
 use tracing::instrument;
 
-fn install_tracing() {
+fn install_tracing(output_format: OutputFormat) {
+    use tracing_subscriber::fmt::writer::BoxMakeWriter;
     use tracing_subscriber::prelude::*;
     use tracing_subscriber::{fmt, EnvFilter};
 
-    let fmt_layer = fmt::layer().with_target(true).with_line_number(true);
+    // keep stdout free for the structured output
+    let writer = match output_format {
+        OutputFormat::Text => BoxMakeWriter::new(std::io::stdout),
+        OutputFormat::Json => BoxMakeWriter::new(std::io::stderr),
+    };
+    let fmt_layer = fmt::layer()
+        .with_target(true)
+        .with_line_number(true)
+        .with_writer(writer);
     let filter_layer = EnvFilter::try_from_default_env()
         .or_else(|_| EnvFilter::try_new("info"))
         .unwrap();
@@ -67,6 +78,9 @@ fn install_tracing() {
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
 struct Cli {
+    /// The format of the output. With `json`, a single JSON object with the schema version, the status, the written files, and the results of the command is written to stdout and the log lines are written to stderr
+    #[arg(long, value_enum, global = true, default_value_t = OutputFormat::Text)]
+    output_format: OutputFormat,
     #[command(subcommand)]
     command: Commands,
 }
@@ -100,13 +114,32 @@ enum Commands {
 }
 
 fn main() -> color_eyre::Result<ExitCode> {
-    install_tracing();
+    let matches = Cli::command().get_matches();
+    let args = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    let output_format = args.output_format;
+    install_tracing(output_format);
     rustls::crypto::aws_lc_rs::default_provider()
         .install_default()
         .map_err(|_| eyre!("Could not install default rustls crypto provider"))?;
-    let args = Cli::parse();
 
-    match args.command {
+    let start = Instant::now();
+    let result = run_command(args.command);
+    match output_format {
+        OutputFormat::Text => result.map(|output| output.exit_code()),
+        OutputFormat::Json => {
+            let command = matches.subcommand_name().unwrap_or_default();
+            let report = CommandReport::new(command, start.elapsed(), result);
+            println!(
+                "{}",
+                serde_json::to_string(&report).context("while serializing the output")?
+            );
+            Ok(report.exit_code())
+        }
+    }
+}
+
+fn run_command(command: Commands) -> color_eyre::Result<CommandOutput> {
+    match command {
         Commands::SplitWitness(cli) => {
             let config = SplitWitnessConfig::parse(cli).context("while parsing config")?;
             run_split_witness(config)
@@ -159,7 +192,7 @@ fn main() -> color_eyre::Result<ExitCode> {
 }
 
 #[instrument(skip(config))]
-fn run_split_witness(config: SplitWitnessConfig) -> color_eyre::Result<ExitCode> {
+fn run_split_witness(config: SplitWitnessConfig) -> color_eyre::Result<CommandOutput> {
     let witness_path = config.witness;
     let circuit_path = config.circuit;
     let protocol = config.protocol;
@@ -198,6 +231,7 @@ fn run_split_witness(config: SplitWitnessConfig) -> color_eyre::Result<ExitCode>
     }
 
     let mut rng = rand::thread_rng();
+    let mut output = CommandOutput::success();
 
     match protocol {
         MPCProtocol::REP3 => {
@@ -226,6 +260,7 @@ fn run_split_witness(config: SplitWitnessConfig) -> color_eyre::Result<ExitCode>
                 bincode::serialize_into(out_file, share)
                     .context("while serializing witness share")?;
                 tracing::info!("Wrote witness share {} to file {}", i, path.display());
+                output.add_file("witness_share", path);
             }
         }
         MPCProtocol::SHAMIR => {
@@ -248,15 +283,16 @@ fn run_split_witness(config: SplitWitnessConfig) -> color_eyre::Result<ExitCode>
                 bincode::serialize_into(out_file, share)
                     .context("while serializing witness share")?;
                 tracing::info!("Wrote witness share {} to file {}", i, path.display());
+                output.add_file("witness_share", path);
             }
         }
     }
     tracing::info!("Split witness into shares successfully");
-    Ok(ExitCode::SUCCESS)
+    Ok(output)
 }
 
 #[instrument(skip(config))]
-fn run_split_proving_key(config: SplitProvingKeyConfig) -> color_eyre::Result<ExitCode> {
+fn run_split_proving_key(config: SplitProvingKeyConfig) -> color_eyre::Result<CommandOutput> {
     let witness_path = config.witness;
     let circuit_path = config.circuit;
     let crs_path = config.crs;
@@ -305,6 +341,7 @@ fn run_split_proving_key(config: SplitProvingKeyConfig) -> color_eyre::Result<Ex
         .collect::<Vec<_>>();
 
     let mut rng = rand::thread_rng();
+    let mut output = CommandOutput::success();
 
     match protocol {
         MPCProtocol::REP3 => {
@@ -336,6 +373,7 @@ fn run_split_proving_key(config: SplitProvingKeyConfig) -> color_eyre::Result<Ex
                 bincode::serialize_into(out_file, &key)
                     .context("while serializing proving_key share")?;
                 tracing::info!("Wrote proving_key share {} to file {}", i, path.display());
+                output.add_file("proving_key_share", path);
             }
         }
         MPCProtocol::SHAMIR => {
@@ -360,15 +398,16 @@ fn run_split_proving_key(config: SplitProvingKeyConfig) -> color_eyre::Result<Ex
                 bincode::serialize_into(out_file, &key)
                     .context("while serializing proving_key share")?;
                 tracing::info!("Wrote proving_key share {} to file {}", i, path.display());
+                output.add_file("proving_key_share", path);
             }
         }
     }
     tracing::info!("Split proving keys into shares successfully");
-    Ok(ExitCode::SUCCESS)
+    Ok(output)
 }
 
 #[instrument(skip(config))]
-fn run_split_input(config: SplitInputConfig) -> color_eyre::Result<ExitCode> {
+fn run_split_input(config: SplitInputConfig) -> color_eyre::Result<CommandOutput> {
     let input = config.input;
     let circuit = config.circuit;
     let protocol = config.protocol;
@@ -392,7 +431,6 @@ fn run_split_input(config: SplitInputConfig) -> color_eyre::Result<ExitCode> {
     let inputs = Rep3CoSolver::<_, Rep3MpcNet>::partially_read_abi_bn254_fieldelement(
         &input,
         &compiled_program.abi,
-        &compiled_program.bytecode,
     )?;
 
     // create input shares
@@ -408,19 +446,21 @@ fn run_split_input(config: SplitInputConfig) -> color_eyre::Result<ExitCode> {
         .context("we have a file name")?
         .to_str()
         .context("input file name is not valid UTF-8")?;
+    let mut output = CommandOutput::success();
     for (i, share) in shares.iter().enumerate() {
         let path = out_dir.join(format!("{}.{}.shared", base_name, i));
         let out_file = BufWriter::new(File::create(&path).context("while creating output file")?);
         bincode::serialize_into(out_file, share).context("while serializing input share")?;
         tracing::info!("Wrote input share {} to file {}", i, path.display());
+        output.add_file("input_share", path);
     }
 
     tracing::info!("Split input into shares successfully");
-    Ok(ExitCode::SUCCESS)
+    Ok(output)
 }
 
 #[instrument(skip(config))]
-fn run_merge_input_shares(config: MergeInputSharesConfig) -> color_eyre::Result<ExitCode> {
+fn run_merge_input_shares(config: MergeInputSharesConfig) -> color_eyre::Result<CommandOutput> {
     let inputs = config.inputs;
     let protocol = config.protocol;
     let out = config.out;
@@ -443,10 +483,11 @@ fn run_merge_input_shares(config: MergeInputSharesConfig) -> color_eyre::Result<
         .iter()
         .map(|input| {
             // parse input shares
-            let input_share_file =
-                BufReader::new(File::open(input).context("while opening input share file")?);
+            let (input_share_file, input_share_size) =
+                file_utils::open_untrusted(input, file_utils::MAX_SHARE_FILE_SIZE)
+                    .context("while opening input share file")?;
             let input_share: BTreeMap<String, Rep3AcvmType<ark_bn254::Fr>> =
-                bincode::deserialize_from(input_share_file)
+                file_utils::deserialize_untrusted(input_share_file, input_share_size)
                     .context("while deserializing input share")?;
             color_eyre::Result::<_>::Ok(input_share)
         })
@@ -470,11 +511,11 @@ fn run_merge_input_shares(config: MergeInputSharesConfig) -> color_eyre::Result<
     tracing::info!("Witness successfully written to {}", out.display());
 
     tracing::info!("Merge input into shares successfully");
-    Ok(ExitCode::SUCCESS)
+    Ok(CommandOutput::success().with_file("input_share", out))
 }
 
 #[instrument(skip(config))]
-fn run_generate_witness(config: GenerateWitnessConfig) -> color_eyre::Result<ExitCode> {
+fn run_generate_witness(config: GenerateWitnessConfig) -> color_eyre::Result<CommandOutput> {
     let input = config.input;
     let circuit = config.circuit;
     let protocol = config.protocol;
@@ -485,6 +526,10 @@ fn run_generate_witness(config: GenerateWitnessConfig) -> color_eyre::Result<Exi
             "Only REP3 protocol is supported for merging input shares"
         ));
     }
+    if config.allow_reveal && config.brillig_policy.is_none() {
+        return Err(eyre!("--allow-reveal requires a Brillig policy"));
+    }
+    let shared_loop_policy = config.shared_loop_policy()?;
     file_utils::check_file_exists(&input)?;
     let circuit_path = PathBuf::from(&circuit);
     file_utils::check_file_exists(&circuit_path)?;
@@ -494,12 +539,26 @@ fn run_generate_witness(config: GenerateWitnessConfig) -> color_eyre::Result<Exi
         .context("while parsing program artifact")?;
 
     // parse input shares
-    let input_share_file =
-        BufReader::new(File::open(&input).context("while opening input share file")?);
+    let (input_share_file, input_share_size) =
+        file_utils::open_untrusted(&input, file_utils::MAX_SHARE_FILE_SIZE)
+            .context("while opening input share file")?;
     let input_share: BTreeMap<String, Rep3AcvmType<ark_bn254::Fr>> =
-        bincode::deserialize_from(input_share_file).context("while deserializing input share")?;
+        file_utils::deserialize_untrusted(input_share_file, input_share_size)
+            .context("while deserializing input share")?;
     let input_share = translate_witness_share_rep3(input_share, &compiled_program.abi)?;
 
+    // parse Brillig policy
+    let brillig_policy = config
+        .brillig_policy
+        .as_ref()
+        .map(|path| {
+            let policy = std::fs::read_to_string(path).context("while reading Brillig policy")?;
+            toml::from_str::<BrilligPolicy>(&policy)
+                .context("while parsing Brillig policy")
+                .map(|policy| policy.with_reveal_consent(config.allow_reveal))
+        })
+        .transpose()?;
+
     // connect to network
     let network_config = config
         .network
@@ -507,17 +566,25 @@ fn run_generate_witness(config: GenerateWitnessConfig) -> color_eyre::Result<Exi
         .try_into()
         .context("while converting network config")?;
     let net = Rep3MpcNet::new(network_config).context("while connecting to network")?;
+    let session = net.session_guard().abort_on_ctrl_c();
     let id = usize::from(net.get_id());
 
     // init MPC protocol
-    let rep3_vm = Rep3CoSolver::from_network_with_witness(net, compiled_program, input_share)
+    let mut rep3_vm = Rep3CoSolver::from_network_with_witness(net, compiled_program, input_share)
         .context("while creating VM")?;
+    if let Some(policy) = &brillig_policy {
+        rep3_vm
+            .set_brillig_policy(policy)
+            .context("while applying Brillig policy")?;
+    }
+    rep3_vm.set_shared_loop_policy(shared_loop_policy);
 
     // execute witness generation in MPC
     let start = Instant::now();
     let result_witness_share = rep3_vm
         .solve()
         .context("while running witness generation")?;
+    session.finish();
     let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
     tracing::info!("Party {}: Witness extension took {} ms", id, duration_ms);
 
@@ -528,11 +595,11 @@ fn run_generate_witness(config: GenerateWitnessConfig) -> color_eyre::Result<Exi
     bincode::serialize_into(out_file, &result_witness_share)
         .context("while serializing witness share")?;
     tracing::info!("Witness successfully written to {}", out.display());
-    Ok(ExitCode::SUCCESS)
+    Ok(CommandOutput::success().with_file("witness_share", out))
 }
 
 #[instrument(skip(config))]
-fn run_translate_witness(config: TranslateWitnessConfig) -> color_eyre::Result<ExitCode> {
+fn run_translate_witness(config: TranslateWitnessConfig) -> color_eyre::Result<CommandOutput> {
     let witness = config.witness;
     let src_protocol = config.src_protocol;
     let target_protocol = config.target_protocol;
@@ -544,10 +611,12 @@ fn run_translate_witness(config: TranslateWitnessConfig) -> color_eyre::Result<E
     file_utils::check_file_exists(&witness)?;
 
     // parse witness shares
-    let witness_file =
-        BufReader::new(File::open(witness).context("trying to open witness share file")?);
+    let (witness_file, witness_size) =
+        file_utils::open_untrusted(&witness, file_utils::MAX_SHARE_FILE_SIZE)
+            .context("trying to open witness share file")?;
     let witness_share: Vec<Rep3AcvmType<ark_bn254::Fr>> =
-        bincode::deserialize_from(witness_file).context("while deserializing witness share")?;
+        file_utils::deserialize_untrusted(witness_file, witness_size)
+            .context("while deserializing witness share")?;
 
     // extract shares only
     let mut shares = vec![];
@@ -564,6 +633,7 @@ fn run_translate_witness(config: TranslateWitnessConfig) -> color_eyre::Result<E
         .try_into()
         .context("while converting network config")?;
     let net = Rep3MpcNet::new(network_config)?;
+    let session = net.session_guard().abort_on_ctrl_c();
     let id = usize::from(net.get_id());
 
     // init MPC protocol
@@ -576,6 +646,7 @@ fn run_translate_witness(config: TranslateWitnessConfig) -> color_eyre::Result<E
     // Translate witness to shamir shares
     let start = Instant::now();
     let translated_shares = protocol.translate_primefield_repshare_vec(shares)?;
+    session.finish();
     let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
     tracing::info!("Party {}: Translating witness took {} ms", id, duration_ms);
 
@@ -595,11 +666,13 @@ fn run_translate_witness(config: TranslateWitnessConfig) -> color_eyre::Result<E
     let out_file = BufWriter::new(std::fs::File::create(&out)?);
     bincode::serialize_into(out_file, &result)?;
     tracing::info!("Witness successfully written to {}", out.display());
-    Ok(ExitCode::SUCCESS)
+    Ok(CommandOutput::success().with_file("witness_share", out))
 }
 
 #[instrument(skip(config))]
-fn run_translate_proving_key(config: TranslateProvingKeyConfig) -> color_eyre::Result<ExitCode> {
+fn run_translate_proving_key(
+    config: TranslateProvingKeyConfig,
+) -> color_eyre::Result<CommandOutput> {
     let proving_key = config.proving_key;
     let src_protocol = config.src_protocol;
     let target_protocol = config.target_protocol;
@@ -611,10 +684,12 @@ fn run_translate_proving_key(config: TranslateProvingKeyConfig) -> color_eyre::R
     file_utils::check_file_exists(&proving_key)?;
 
     // parse proving_key shares
-    let proving_key_file =
-        BufReader::new(File::open(proving_key).context("trying to open witness share file")?);
+    let (proving_key_file, proving_key_size) =
+        file_utils::open_untrusted(&proving_key, file_utils::MAX_SHARE_FILE_SIZE)
+            .context("trying to open proving key file")?;
     let proving_key: ProvingKey<Rep3UltraHonkDriver<Rep3MpcNet>, Bn254> =
-        bincode::deserialize_from(proving_key_file).context("while deserializing witness share")?;
+        file_utils::deserialize_untrusted(proving_key_file, proving_key_size)
+            .context("while deserializing proving key")?;
 
     // extract shares
     let shares = proving_key
@@ -632,6 +707,7 @@ fn run_translate_proving_key(config: TranslateProvingKeyConfig) -> color_eyre::R
         .try_into()
         .context("while converting network config")?;
     let net = Rep3MpcNet::new(network_config)?;
+    let session = net.session_guard().abort_on_ctrl_c();
     let id = usize::from(net.get_id());
 
     // init MPC protocol
@@ -644,6 +720,7 @@ fn run_translate_proving_key(config: TranslateProvingKeyConfig) -> color_eyre::R
     // Translate witness to shamir shares
     let start = Instant::now();
     let translated_shares = protocol.translate_primefield_repshare_vec(shares)?;
+    session.finish();
     let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
     tracing::info!("Party {}: Translating shares took {} ms", id, duration_ms);
 
@@ -680,11 +757,11 @@ fn run_translate_proving_key(config: TranslateProvingKeyConfig) -> color_eyre::R
     let out_file = BufWriter::new(std::fs::File::create(&out)?);
     bincode::serialize_into(out_file, &result)?;
     tracing::info!("Proving_key successfully written to {}", out.display());
-    Ok(ExitCode::SUCCESS)
+    Ok(CommandOutput::success().with_file("proving_key_share", out))
 }
 
 #[instrument(skip(config))]
-fn run_build_proving_key(config: BuildProvingKeyConfig) -> color_eyre::Result<ExitCode> {
+fn run_build_proving_key(config: BuildProvingKeyConfig) -> color_eyre::Result<CommandOutput> {
     let witness = config.witness;
     let circuit_path = config.circuit;
     let crs_path = config.crs;
@@ -697,12 +774,14 @@ fn run_build_proving_key(config: BuildProvingKeyConfig) -> color_eyre::Result<Ex
     file_utils::check_file_exists(&crs_path)?;
 
     // parse witness shares
-    let witness_file =
-        BufReader::new(File::open(witness).context("trying to open witness share file")?);
+    let (witness_file, witness_size) =
+        file_utils::open_untrusted(&witness, file_utils::MAX_SHARE_FILE_SIZE)
+            .context("trying to open witness share file")?;
 
     // parse constraint system
     let constraint_system = Utils::get_constraint_system_from_file(&circuit_path, true)
         .context("while parsing program artifact")?;
+    let num_variables = constraint_system.num_variables();
 
     let network_config = config
         .network
@@ -715,10 +794,13 @@ fn run_build_proving_key(config: BuildProvingKeyConfig) -> color_eyre::Result<Ex
             if t != 1 {
                 return Err(eyre!("REP3 only allows the threshold to be 1"));
             }
-            let witness_share = bincode::deserialize_from(witness_file)
-                .context("while deserializing witness share")?;
+            let witness_share: Vec<_> =
+                file_utils::deserialize_untrusted(witness_file, witness_size)
+                    .context("while deserializing witness share")?;
+            check_witness_len(witness_share.len(), num_variables)?;
             // connect to network
             let net = Rep3MpcNet::new(network_config)?;
+            let session = net.session_guard().abort_on_ctrl_c();
             let id = net.get_id();
 
             // Create driver for circuit builder
@@ -746,6 +828,7 @@ fn run_build_proving_key(config: BuildProvingKeyConfig) -> color_eyre::Result<Ex
             // Get the proving key and prover
             let proving_key: ProvingKey<Rep3UltraHonkDriver<Rep3MpcNet>, _> =
                 ProvingKey::create(id, builder, prover_crs, &mut circuit_driver)?;
+            session.finish();
             let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
             tracing::info!(
                 "Party {}: Proving key generation took {} ms",
@@ -758,10 +841,13 @@ fn run_build_proving_key(config: BuildProvingKeyConfig) -> color_eyre::Result<Ex
             tracing::info!("Proving Key successfully written to {}", out.display());
         }
         MPCProtocol::SHAMIR => {
-            let witness_share = bincode::deserialize_from(witness_file)
-                .context("while deserializing witness share")?;
+            let witness_share: Vec<_> =
+                file_utils::deserialize_untrusted(witness_file, witness_size)
+                    .context("while deserializing witness share")?;
+            check_witness_len(witness_share.len(), num_variables)?;
             // connect to network
             let net = ShamirMpcNet::new(network_config)?;
+            let session = net.session_guard().abort_on_ctrl_c();
             let id = net.get_id();
 
             // Create driver for circuit builder
@@ -793,6 +879,7 @@ fn run_build_proving_key(config: BuildProvingKeyConfig) -> color_eyre::Result<Ex
             // Get the proving key and prover
             let proving_key: ProvingKey<ShamirUltraHonkDriver<_, ShamirMpcNet>, _> =
                 ProvingKey::create(id, builder, prover_crs, &mut circuit_driver)?;
+            session.finish();
             let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
             tracing::info!(
                 "Party {}: Proving key generation took {} ms",
@@ -807,11 +894,11 @@ fn run_build_proving_key(config: BuildProvingKeyConfig) -> color_eyre::Result<Ex
     };
 
     tracing::info!("Proving Key generation finished successfully");
-    Ok(ExitCode::SUCCESS)
+    Ok(CommandOutput::success().with_file("proving_key_share", out))
 }
 
 #[instrument(skip(config))]
-fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCode> {
+fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<CommandOutput> {
     let proving_key = config.proving_key;
     let protocol = config.protocol;
     let hasher = config.hasher;
@@ -828,8 +915,9 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
         .context("while converting network config")?;
 
     // parse proving_key file
-    let proving_key_file =
-        BufReader::new(File::open(proving_key).context("trying to open proving_key file")?);
+    let (proving_key_file, proving_key_size) =
+        file_utils::open_untrusted(&proving_key, file_utils::MAX_SHARE_FILE_SIZE)
+            .context("trying to open proving_key file")?;
 
     let (proof, public_input) = match protocol {
         MPCProtocol::REP3 => {
@@ -837,6 +925,7 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
                 return Err(eyre!("REP3 only allows the threshold to be 1"));
             }
             let net = Rep3MpcNet::new(network_config)?;
+            let session = net.session_guard().abort_on_ctrl_c();
             let id = net.get_id();
 
             let mut io_context0 = IoContext::init(net)?;
@@ -846,7 +935,7 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
 
             // Get the proving key and prover
             let proving_key: ProvingKey<Rep3UltraHonkDriver<Rep3MpcNet>, Bn254> =
-                bincode::deserialize_from(proving_key_file)
+                file_utils::deserialize_untrusted(proving_key_file, proving_key_size)
                     .context("while deserializing input share")?;
             let public_input = proving_key.get_public_inputs();
             match hasher {
@@ -857,6 +946,7 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
                     tracing::info!("Party {}: starting proof generation..", id);
                     let start = Instant::now();
                     let proof = prover.prove(proving_key)?;
+                    session.finish();
                     let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
                     tracing::info!("Party {}: Proof generation took {} ms", id, duration_ms);
                     (proof, public_input)
@@ -868,6 +958,7 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
                     tracing::info!("Party {}: starting proof generation..", id);
                     let start = Instant::now();
                     let proof = prover.prove(proving_key)?;
+                    session.finish();
                     let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
                     tracing::info!("Party {}: Proof generation took {} ms", id, duration_ms);
                     (proof, public_input)
@@ -877,11 +968,12 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
         MPCProtocol::SHAMIR => {
             // connect to network
             let net = ShamirMpcNet::new(network_config)?;
+            let session = net.session_guard().abort_on_ctrl_c();
             let id = net.get_id();
 
             // Get the proving key and prover
             let proving_key: ProvingKey<ShamirUltraHonkDriver<ark_bn254::Fr, ShamirMpcNet>, Bn254> =
-                bincode::deserialize_from(proving_key_file)
+                file_utils::deserialize_untrusted(proving_key_file, proving_key_size)
                     .context("while deserializing input share")?;
             let public_input = proving_key.get_public_inputs();
 
@@ -903,6 +995,7 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
                     let start = Instant::now();
                     let prover = CoUltraHonk::<_, _, Poseidon2Sponge>::new(driver);
                     let proof = prover.prove(proving_key)?;
+                    session.finish();
                     let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
                     tracing::info!("Party {}: Proof generation took {} ms", id, duration_ms);
 
@@ -912,6 +1005,7 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
                     let start = Instant::now();
                     let prover = CoUltraHonk::<_, _, Keccak256>::new(driver);
                     let proof = prover.prove(proving_key)?;
+                    session.finish();
                     let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
                     tracing::info!("Party {}: Proof generation took {} ms", id, duration_ms);
 
@@ -922,6 +1016,7 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
     };
 
     // write result to output file
+    let mut output = CommandOutput::success();
     if let Some(out) = out {
         let mut out_file =
             BufWriter::new(std::fs::File::create(&out).context("while creating output file")?);
@@ -931,6 +1026,7 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
             .write(proof_u8.as_slice())
             .context("while writing proof to file")?;
         tracing::info!("Wrote proof to file {}", out.display());
+        output.add_file("proof", out);
     }
 
     // write public input to output file
@@ -955,16 +1051,17 @@ fn run_generate_proof(config: GenerateProofConfig) -> color_eyre::Result<ExitCod
             "Wrote public inputs to file {}",
             public_input_filename.display()
         );
+        output.add_file("public_input", public_input_filename);
     }
 
     tracing::info!("Proof generation finished successfully");
-    Ok(ExitCode::SUCCESS)
+    Ok(output)
 }
 
 #[instrument(skip(config))]
 fn run_build_and_generate_proof(
     config: BuildAndGenerateProofConfig,
-) -> color_eyre::Result<ExitCode> {
+) -> color_eyre::Result<CommandOutput> {
     let witness = config.witness;
     let circuit_path = config.circuit;
     let crs_path = config.crs;
@@ -979,12 +1076,14 @@ fn run_build_and_generate_proof(
     file_utils::check_file_exists(&crs_path)?;
 
     // parse witness shares
-    let witness_file =
-        BufReader::new(File::open(witness).context("trying to open witness share file")?);
+    let (witness_file, witness_size) =
+        file_utils::open_untrusted(&witness, file_utils::MAX_SHARE_FILE_SIZE)
+            .context("trying to open witness share file")?;
 
     // parse constraint system
     let constraint_system = Utils::get_constraint_system_from_file(&circuit_path, true)
         .context("while parsing program artifact")?;
+    let num_variables = constraint_system.num_variables();
 
     let network_config = config
         .network
@@ -997,10 +1096,13 @@ fn run_build_and_generate_proof(
             if t != 1 {
                 return Err(eyre!("REP3 only allows the threshold to be 1"));
             }
-            let witness_share = bincode::deserialize_from(witness_file)
-                .context("while deserializing witness share")?;
+            let witness_share: Vec<_> =
+                file_utils::deserialize_untrusted(witness_file, witness_size)
+                    .context("while deserializing witness share")?;
+            check_witness_len(witness_share.len(), num_variables)?;
             // connect to network
             let net = Rep3MpcNet::new(network_config)?;
+            let session = net.session_guard().abort_on_ctrl_c();
             let id = net.get_id();
 
             // Create driver for circuit builder
@@ -1045,6 +1147,7 @@ fn run_build_and_generate_proof(
                     tracing::info!("Party {}: starting proof generation..", id);
                     let start = Instant::now();
                     let proof = prover.prove(proving_key)?;
+                    session.finish();
                     let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
                     tracing::info!("Party {}: Proof generation took {} ms", id, duration_ms);
                     (proof, public_input)
@@ -1062,6 +1165,7 @@ fn run_build_and_generate_proof(
                     tracing::info!("Party {}: starting proof generation..", id);
                     let start = Instant::now();
                     let proof = prover.prove(proving_key)?;
+                    session.finish();
                     let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
                     tracing::info!("Party {}: Proof generation took {} ms", id, duration_ms);
                     (proof, public_input)
@@ -1071,10 +1175,13 @@ fn run_build_and_generate_proof(
             (proof, public_input)
         }
         MPCProtocol::SHAMIR => {
-            let witness_share = bincode::deserialize_from(witness_file)
-                .context("while deserializing witness share")?;
+            let witness_share: Vec<_> =
+                file_utils::deserialize_untrusted(witness_file, witness_size)
+                    .context("while deserializing witness share")?;
+            check_witness_len(witness_share.len(), num_variables)?;
             // connect to network
             let net = ShamirMpcNet::new(network_config)?;
+            let session = net.session_guard().abort_on_ctrl_c();
             let id = net.get_id();
 
             // Create driver for circuit builder
@@ -1132,6 +1239,7 @@ fn run_build_and_generate_proof(
                     let start = Instant::now();
                     let prover = CoUltraHonk::<_, _, Poseidon2Sponge>::new(driver);
                     let proof = prover.prove(proving_key)?;
+                    session.finish();
                     let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
                     tracing::info!("Party {}: Proof generation took {} ms", id, duration_ms);
                     (proof, public_input)
@@ -1142,6 +1250,7 @@ fn run_build_and_generate_proof(
                     let start = Instant::now();
                     let prover = CoUltraHonk::<_, _, Keccak256>::new(driver);
                     let proof = prover.prove(proving_key)?;
+                    session.finish();
                     let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
                     tracing::info!("Party {}: Proof generation took {} ms", id, duration_ms);
                     (proof, public_input)
@@ -1152,6 +1261,7 @@ fn run_build_and_generate_proof(
     };
 
     // write result to output file
+    let mut output = CommandOutput::success();
     if let Some(out) = out {
         let mut out_file =
             BufWriter::new(std::fs::File::create(&out).context("while creating output file")?);
@@ -1161,6 +1271,7 @@ fn run_build_and_generate_proof(
             .write(proof_u8.as_slice())
             .context("while writing proof to file")?;
         tracing::info!("Wrote proof to file {}", out.display());
+        output.add_file("proof", out);
     }
 
     // write public input to output file
@@ -1185,14 +1296,15 @@ fn run_build_and_generate_proof(
             "Wrote public inputs to file {}",
             public_input_filename.display()
         );
+        output.add_file("public_input", public_input_filename);
     }
 
     tracing::info!("Proof generation finished successfully");
-    Ok(ExitCode::SUCCESS)
+    Ok(output)
 }
 
 #[instrument(skip(config))]
-fn run_generate_vk(config: CreateVKConfig) -> color_eyre::Result<ExitCode> {
+fn run_generate_vk(config: CreateVKConfig) -> color_eyre::Result<CommandOutput> {
     let circuit_path = config.circuit;
     let crs_path = config.crs;
     let vk_path = config.vk;
@@ -1247,11 +1359,11 @@ fn run_generate_vk(config: CreateVKConfig) -> color_eyre::Result<ExitCode> {
     tracing::info!("Wrote vk to file {}", vk_path.display());
 
     tracing::info!("Verification key generation finished successfully");
-    Ok(ExitCode::SUCCESS)
+    Ok(CommandOutput::success().with_file("vk", vk_path))
 }
 
 #[instrument(skip(config))]
-fn run_verify(config: VerifyConfig) -> color_eyre::Result<ExitCode> {
+fn run_verify(config: VerifyConfig) -> color_eyre::Result<CommandOutput> {
     let proof = config.proof;
     let vk_path: PathBuf = config.vk;
     let crs_path = config.crs;
@@ -1279,7 +1391,6 @@ fn run_verify(config: VerifyConfig) -> color_eyre::Result<ExitCode> {
 
     // The actual verifier
     let start = Instant::now();
-    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
     let res = match hasher {
         TranscriptHash::POSEIDON => {
             UltraHonk::<_, Poseidon2Sponge>::verify(proof, vk).context("while verifying proof")?
@@ -1288,13 +1399,25 @@ fn run_verify(config: VerifyConfig) -> color_eyre::Result<ExitCode> {
             UltraHonk::<_, Keccak256>::verify(proof, vk).context("while verifying proof")?
         }
     };
+    let duration_ms = start.elapsed().as_micros() as f64 / 1000.;
     tracing::info!("Proof verification took {} ms", duration_ms);
 
     if res {
         tracing::info!("Proof verified successfully");
-        Ok(ExitCode::SUCCESS)
+        Ok(CommandOutput::success().with_value("valid", true))
     } else {
         tracing::error!("Proof verification failed");
-        Ok(ExitCode::FAILURE)
+        Ok(CommandOutput::failure().with_value("valid", false))
+    }
+}
+
+fn check_witness_len(len: usize, num_variables: usize) -> color_eyre::Result<()> {
+    if len > num_variables {
+        return Err(eyre!(
+            "witness share has {} elements, but the circuit only has {} variables",
+            len,
+            num_variables
+        ));
     }
+    Ok(())
 }
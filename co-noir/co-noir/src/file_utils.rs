@@ -1,4 +1,14 @@
-use std::path::{Path, PathBuf};
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+use bincode::Options;
+use serde::de::DeserializeOwned;
+
+/// The maximum size in bytes of a share or proving key file read from an untrusted source.
+pub const MAX_SHARE_FILE_SIZE: u64 = 1 << 36;
 
 /// An error type for file utility functions.
 #[derive(Debug, thiserror::Error)]
@@ -15,6 +25,9 @@ pub enum Error {
     /// The path was expected to be a file, but it is a directory.
     #[error("Expected {0} to be a file, but it is a directory.")]
     ExpectedFile(PathBuf),
+    /// The file exceeds the maximum allowed size.
+    #[error("File {0} has {1} bytes, which exceeds the maximum of {2} bytes.")]
+    FileTooLarge(PathBuf, u64, u64),
     /// An I/O error occurred.
     #[error(transparent)]
     IoError(#[from] std::io::Error),
@@ -41,3 +54,65 @@ pub fn check_dir_exists(dir_path: &Path) -> Result<(), Error> {
     }
     Ok(())
 }
+
+/// Opens a file from an untrusted source and checks that it is not larger than `max_size` bytes. Returns a buffered reader together with the size of the file, which should be used as the limit for [deserialize_untrusted].
+pub fn open_untrusted(file_path: &Path, max_size: u64) -> Result<(BufReader<File>, u64), Error> {
+    check_file_exists(file_path)?;
+    let file = File::open(file_path)?;
+    let size = file.metadata()?.len();
+    if size > max_size {
+        return Err(Error::FileTooLarge(file_path.to_path_buf(), size, max_size));
+    }
+    Ok((BufReader::new(file), size))
+}
+
+/// Deserializes a bincode encoded value from an untrusted source. In contrast to [bincode::deserialize_from], at most `limit` bytes are read, so that a forged length prefix cannot make the deserializer allocate arbitrary amounts of memory. The encoding is the same as the one of [bincode::serialize_into].
+pub fn deserialize_untrusted<T: DeserializeOwned, R: Read>(
+    reader: R,
+    limit: u64,
+) -> bincode::Result<T> {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+        .with_limit(limit)
+        .deserialize_from(reader)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn forged_length_prefix_is_rejected() {
+        // claims a string of u64::MAX bytes, which must fail before the buffer is allocated
+        let mut bytes = u64::MAX.to_le_bytes().to_vec();
+        bytes.extend(b"short");
+        let err =
+            deserialize_untrusted::<String, _>(bytes.as_slice(), bytes.len() as u64).unwrap_err();
+        assert!(matches!(*err, bincode::ErrorKind::SizeLimit), "{err}");
+
+        // claims more elements than the limit allows
+        let mut bytes = (1u64 << 40).to_le_bytes().to_vec();
+        bytes.extend(1u64.to_le_bytes());
+        let err =
+            deserialize_untrusted::<Vec<u64>, _>(bytes.as_slice(), bytes.len() as u64).unwrap_err();
+        assert!(matches!(*err, bincode::ErrorKind::SizeLimit), "{err}");
+    }
+
+    #[test]
+    fn open_untrusted_checks_size() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&[0u8; 100]).unwrap();
+        file.flush().unwrap();
+
+        let (_, size) = open_untrusted(file.path(), 100).unwrap();
+        assert_eq!(size, 100);
+        let err = open_untrusted(file.path(), 99).unwrap_err();
+        assert!(
+            matches!(err, Error::FileTooLarge(ref path, 100, 99) if path == file.path()),
+            "{err}"
+        );
+    }
+}
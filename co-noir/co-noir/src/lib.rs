@@ -8,8 +8,9 @@ use ark_ff::PrimeField;
 use clap::{Args, ValueEnum};
 use co_acvm::{
     solver::{partial_abi::PublicMarker, Rep3CoSolver},
-    Rep3AcvmType, ShamirAcvmType,
+    Rep3AcvmType, ShamirAcvmType, SharedLoopPolicy,
 };
+pub use co_circom_snarks::output;
 use figment::{
     providers::{Env, Format, Serialized, Toml},
     Figment,
@@ -66,6 +67,20 @@ pub enum TranscriptHash {
     KECCAK,
 }
 
+/// An enum representing how the witness extension executes loops on shared conditions in unconstrained functions, see [SharedLoopPolicy].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ValueEnum)]
+#[clap(rename_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum SharedLoops {
+    /// Abort the witness extension at the first loop on a shared condition
+    #[default]
+    Reject,
+    /// Execute both paths of the loop condition for at most `--max-loop-iterations` iterations. The proof does not verify if a loop needs more iterations
+    BoundAndPad,
+    /// Open the loop conditions, which leaks the amount of iterations to all parties
+    OpenWithConsent,
+}
+
 impl std::fmt::Display for MPCProtocol {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -138,7 +153,7 @@ pub struct SplitInputCli {
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub config: Option<PathBuf>,
-    /// The path to the input JSON file
+    /// The path to the input file, either a `Prover.toml` or a JSON file with the same structure (`.json`). Inputs are shared or public according to the visibility of their parameters in the ABI of the program
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub input: Option<PathBuf>,
@@ -159,7 +174,7 @@ pub struct SplitInputCli {
 /// Config for `split_input`
 #[derive(Debug, Clone, Deserialize)]
 pub struct SplitInputConfig {
-    /// The path to the input JSON file
+    /// The path to the input file, either a `Prover.toml` or a JSON file with the same structure (`.json`)
     pub input: PathBuf,
     /// The path to the circuit file
     pub circuit: String,
@@ -277,6 +292,21 @@ pub struct GenerateWitnessCli {
     #[arg(long)]
     #[serde(skip_serializing_if = "::std::option::Option::is_none")]
     pub out: Option<PathBuf>,
+    /// The path to a TOML file with the Brillig policy that chooses the execution of the unconstrained functions
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub brillig_policy: Option<PathBuf>,
+    /// Consent to reveal the arguments of the unconstrained functions the Brillig policy declares revealable
+    #[arg(long, default_value_t = false)]
+    pub allow_reveal: bool,
+    /// How loops on shared conditions in unconstrained functions are executed
+    #[arg(long, value_enum)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub shared_loops: Option<SharedLoops>,
+    /// The maximum amount of iterations of loops on shared conditions, required by `--shared-loops bound-and-pad`
+    #[arg(long)]
+    #[serde(skip_serializing_if = "::std::option::Option::is_none")]
+    pub max_loop_iterations: Option<usize>,
 }
 
 /// Config for `generate_witness`
@@ -290,10 +320,39 @@ pub struct GenerateWitnessConfig {
     pub protocol: MPCProtocol,
     /// The output file where the final witness share is written to
     pub out: PathBuf,
+    /// The path to a TOML file with the Brillig policy that chooses the execution of the unconstrained functions
+    pub brillig_policy: Option<PathBuf>,
+    /// Consent to reveal the arguments of the unconstrained functions the Brillig policy declares revealable
+    #[serde(default)]
+    pub allow_reveal: bool,
+    /// How loops on shared conditions in unconstrained functions are executed
+    #[serde(default)]
+    pub shared_loops: SharedLoops,
+    /// The maximum amount of iterations of loops on shared conditions
+    pub max_loop_iterations: Option<usize>,
     /// Network config
     pub network: NetworkConfigFile,
 }
 
+impl GenerateWitnessConfig {
+    /// Returns the [SharedLoopPolicy] of the coBrillig-VM. All parties need to use the same policy.
+    pub fn shared_loop_policy(&self) -> color_eyre::Result<SharedLoopPolicy> {
+        match (self.shared_loops, self.max_loop_iterations) {
+            (SharedLoops::Reject, None) => Ok(SharedLoopPolicy::Reject),
+            (SharedLoops::OpenWithConsent, None) => Ok(SharedLoopPolicy::OpenWithConsent),
+            (SharedLoops::BoundAndPad, Some(max_iterations)) => {
+                Ok(SharedLoopPolicy::BoundAndPad { max_iterations })
+            }
+            (SharedLoops::BoundAndPad, None) => {
+                color_eyre::eyre::bail!("--shared-loops bound-and-pad requires --max-loop-iterations")
+            }
+            (_, Some(_)) => color_eyre::eyre::bail!(
+                "--max-loop-iterations is only used with --shared-loops bound-and-pad"
+            ),
+        }
+    }
+}
+
 /// Cli arguments for `translate_witness`
 #[derive(Debug, Serialize, Args)]
 pub struct TranslateWitnessCli {
@@ -618,10 +677,7 @@ pub struct VerifyConfig {
 /// Prefix for config env variables
 pub const CONFIG_ENV_PREFIX: &str = "CONOIR_";
 
-/// Error type for config parsing and merging
-#[derive(thiserror::Error, Debug)]
-#[error(transparent)]
-pub struct ConfigError(#[from] figment::error::Error);
+pub use output::ConfigError;
 
 macro_rules! impl_config {
     ($cli: ty, $config: ty) => {
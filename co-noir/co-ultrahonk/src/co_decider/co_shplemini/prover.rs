@@ -380,12 +380,7 @@ impl<
 
         let z = transcript.get_challenge::<P>("Shplonk:z".to_string());
 
-        Ok(self.compute_partially_evaluated_batched_quotient(
-            opening_claims,
-            batched_quotient,
-            nu,
-            z,
-        ))
+        self.compute_partially_evaluated_batched_quotient(opening_claims, batched_quotient, nu, z)
     }
 
     pub(crate) fn shplemini_prove(
@@ -422,7 +417,7 @@ impl<
         batched_quotient_q: SharedPolynomial<T, P>,
         nu_challenge: P::ScalarField,
         z_challenge: P::ScalarField,
-    ) -> ZeroMorphOpeningClaim<T, P> {
+    ) -> HonkProofResult<ZeroMorphOpeningClaim<T, P>> {
         tracing::trace!("Compute partially evaluated batched quotient");
         let num_opening_claims = opening_claims.len();
 
@@ -431,9 +426,7 @@ impl<
         for claim in &opening_claims {
             inverse_vanishing_evals.push(z_challenge - claim.opening_pair.challenge);
         }
-        inverse_vanishing_evals.iter_mut().for_each(|x| {
-            x.inverse_in_place();
-        });
+        Utils::batch_invert_nonzero(&mut inverse_vanishing_evals)?;
 
         let mut g = batched_quotient_q;
 
@@ -1,7 +1,9 @@
 use ark_ec::pairing::Pairing;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ultrahonk::prelude::Poseidon2Params;
 
 pub(crate) mod plain;
+pub(crate) mod poseidon2;
 pub(crate) mod rep3;
 pub(crate) mod shamir;
 
@@ -115,4 +117,13 @@ pub trait NoirUltraHonkProver<P: Pairing>: Send + Sized {
         coeffs: &[Self::ArithmeticShare],
         point: P::ScalarField,
     ) -> Self::ArithmeticShare;
+
+    /// Computes the Poseidon2 permutations of many shared states in place, e.g., to hash shared values with the same permutation as the transcript. The states of a batch are permuted together, such that the number of communication rounds does not depend on the number of states.
+    fn poseidon2_permutation_many<const T: usize, const D: u64>(
+        &mut self,
+        states: &mut [[Self::ArithmeticShare; T]],
+        params: &Poseidon2Params<P::ScalarField, T, D>,
+    ) -> std::io::Result<()> {
+        poseidon2::permutation_many(self, states, params)
+    }
 }
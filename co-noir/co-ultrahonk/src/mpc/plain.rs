@@ -7,6 +7,7 @@ use ark_poly::DenseUVPolynomial;
 use ark_poly::{univariate::DensePolynomial, Polynomial};
 use num_traits::Zero;
 use rand::thread_rng;
+use ultrahonk::prelude::Poseidon2Params;
 
 pub struct PlainUltraHonkDriver;
 
@@ -160,4 +161,15 @@ impl<P: Pairing> NoirUltraHonkProver<P> for PlainUltraHonkDriver {
         let poly = DensePolynomial::from_coefficients_slice(coeffs);
         poly.evaluate(&point)
     }
+
+    fn poseidon2_permutation_many<const T: usize, const D: u64>(
+        &mut self,
+        states: &mut [[Self::ArithmeticShare; T]],
+        params: &Poseidon2Params<P::ScalarField, T, D>,
+    ) -> std::io::Result<()> {
+        for state in states.iter_mut() {
+            params.permutation_in_place(state);
+        }
+        Ok(())
+    }
 }
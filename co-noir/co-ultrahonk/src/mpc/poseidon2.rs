@@ -0,0 +1,131 @@
+//! The Poseidon2 permutation over shared states, expressed with the operations of [NoirUltraHonkProver]. This is the fallback for drivers without a dedicated implementation, the Rep3 driver uses the implementation of mpc-core instead.
+use ark_ec::pairing::Pairing;
+use ark_ff::{One, Zero};
+use ultrahonk::prelude::Poseidon2Params;
+
+use super::NoirUltraHonkProver;
+
+/// Computes x^D of all inputs with a square-and-multiply over the bits of `D`, where the multiplications of each bit are batched.
+fn sbox_many<P: Pairing, T: NoirUltraHonkProver<P>, const D: u64>(
+    driver: &mut T,
+    inputs: &mut [T::ArithmeticShare],
+) -> std::io::Result<()> {
+    let mut result: Option<Vec<T::ArithmeticShare>> = None;
+    let mut base = inputs.to_vec();
+    let mut exp = D;
+    loop {
+        let bit_set = exp & 1 == 1;
+        exp >>= 1;
+        let square = exp != 0;
+
+        let mut lhs = Vec::with_capacity(2 * base.len());
+        let mut rhs = Vec::with_capacity(2 * base.len());
+        let multiply = match (&result, bit_set) {
+            (Some(result), true) => {
+                lhs.extend_from_slice(result);
+                rhs.extend_from_slice(&base);
+                true
+            }
+            _ => false,
+        };
+        if square {
+            lhs.extend_from_slice(&base);
+            rhs.extend_from_slice(&base);
+        }
+        let mut products = driver.mul_many(&lhs, &rhs)?;
+        let squares = products.split_off(if multiply { base.len() } else { 0 });
+
+        if multiply {
+            result = Some(products);
+        } else if bit_set {
+            result = Some(base);
+        }
+        if !square {
+            break;
+        }
+        base = squares;
+    }
+    inputs.copy_from_slice(&result.expect("the S-box degree is odd"));
+    Ok(())
+}
+
+/// Applies the public linear map `f` to the shared state. The columns of the matrix of `f` are the images of the unit vectors.
+fn linear_layer<P: Pairing, T: NoirUltraHonkProver<P>, const S: usize>(
+    driver: &T,
+    state: &mut [T::ArithmeticShare; S],
+    f: impl Fn(&mut [P::ScalarField; S]),
+) {
+    let mut result = [T::ArithmeticShare::default(); S];
+    for (j, share) in state.iter().enumerate() {
+        let mut column = [P::ScalarField::zero(); S];
+        column[j] = P::ScalarField::one();
+        f(&mut column);
+        for (result, entry) in result.iter_mut().zip(column) {
+            let product = driver.mul_with_public(entry, *share);
+            *result = driver.add(*result, product);
+        }
+    }
+    *state = result;
+}
+
+/// Computes the Poseidon2 permutations of many shared states in place. The S-boxes of all states are computed together.
+pub(crate) fn permutation_many<
+    P: Pairing,
+    T: NoirUltraHonkProver<P>,
+    const S: usize,
+    const D: u64,
+>(
+    driver: &mut T,
+    states: &mut [[T::ArithmeticShare; S]],
+    params: &Poseidon2Params<P::ScalarField, S, D>,
+) -> std::io::Result<()> {
+    let external_layer = Poseidon2Params::<P::ScalarField, S, D>::matmul_external;
+    let external_round = |driver: &mut T,
+                          states: &mut [[T::ArithmeticShare; S]],
+                          round_constants: &[P::ScalarField; S]|
+     -> std::io::Result<()> {
+        let mut sbox = Vec::with_capacity(states.len() * S);
+        for state in states.iter() {
+            for (share, rc) in state.iter().zip(round_constants) {
+                sbox.push(driver.add_with_public(*rc, *share));
+            }
+        }
+        sbox_many::<P, T, D>(driver, &mut sbox)?;
+        for (state, sbox) in states.iter_mut().zip(sbox.chunks_exact(S)) {
+            state.copy_from_slice(sbox);
+            linear_layer(driver, state, external_layer);
+        }
+        Ok(())
+    };
+
+    // Linear layer at beginning
+    for state in states.iter_mut() {
+        linear_layer(driver, state, external_layer);
+    }
+
+    // First set of external rounds
+    for round_constants in &params.round_constants_external[..params.rounds_f_beginning] {
+        external_round(driver, states, round_constants)?;
+    }
+
+    // Internal rounds, where the S-box is only applied to the first element of each state
+    for round_constant in &params.round_constants_internal[..params.rounds_p] {
+        let mut sbox = states
+            .iter()
+            .map(|state| driver.add_with_public(*round_constant, state[0]))
+            .collect::<Vec<_>>();
+        sbox_many::<P, T, D>(driver, &mut sbox)?;
+        for (state, sbox) in states.iter_mut().zip(sbox) {
+            state[0] = sbox;
+            linear_layer(driver, state, |state| params.matmul_internal(state));
+        }
+    }
+
+    // Remaining external rounds
+    for round_constants in &params.round_constants_external
+        [params.rounds_f_beginning..params.rounds_f_beginning + params.rounds_f_end]
+    {
+        external_round(driver, states, round_constants)?;
+    }
+    Ok(())
+}
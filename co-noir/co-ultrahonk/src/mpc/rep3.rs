@@ -3,13 +3,14 @@ use ark_ec::pairing::Pairing;
 use ark_ff::Field;
 use itertools::izip;
 use mpc_core::protocols::rep3::{
-    arithmetic,
+    arithmetic, gadgets,
     id::PartyID,
     network::{IoContext, Rep3Network},
     pointshare, poly, Rep3PointShare, Rep3PrimeFieldShare,
 };
 use num_traits::Zero;
 use rayon::prelude::*;
+use ultrahonk::prelude::Poseidon2Params;
 
 // TODO use io_context1
 pub struct Rep3UltraHonkDriver<N: Rep3Network> {
@@ -42,15 +43,15 @@ impl<P: Pairing, N: Rep3Network> NoirUltraHonkProver<P> for Rep3UltraHonkDriver<
 
     // TODO dont take by ref cause impl Copy, remove self
     fn sub(&self, a: Self::ArithmeticShare, b: Self::ArithmeticShare) -> Self::ArithmeticShare {
-        arithmetic::sub(a, b)
+        a - b
     }
 
     fn add(&self, a: Self::ArithmeticShare, b: Self::ArithmeticShare) -> Self::ArithmeticShare {
-        arithmetic::add(a, b)
+        a + b
     }
 
     fn neg(&mut self, a: Self::ArithmeticShare) -> Self::ArithmeticShare {
-        arithmetic::neg(a)
+        -a
     }
 
     fn mul_with_public(
@@ -58,7 +59,7 @@ impl<P: Pairing, N: Rep3Network> NoirUltraHonkProver<P> for Rep3UltraHonkDriver<
         public: <P as Pairing>::ScalarField,
         shared: Self::ArithmeticShare,
     ) -> Self::ArithmeticShare {
-        arithmetic::mul_public(shared, public)
+        shared * public
     }
 
     fn mul_many(
@@ -183,4 +184,12 @@ impl<P: Pairing, N: Rep3Network> NoirUltraHonkProver<P> for Rep3UltraHonkDriver<
     ) -> Self::ArithmeticShare {
         poly::eval_poly(coeffs, point)
     }
+
+    fn poseidon2_permutation_many<const T: usize, const D: u64>(
+        &mut self,
+        states: &mut [[Self::ArithmeticShare; T]],
+        params: &Poseidon2Params<P::ScalarField, T, D>,
+    ) -> std::io::Result<()> {
+        gadgets::poseidon2::poseidon2_permutation_many(params, states, &mut self.io_context0)
+    }
 }
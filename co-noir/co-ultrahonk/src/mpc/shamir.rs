@@ -45,15 +45,15 @@ impl<P: Pairing, N: ShamirNetwork> NoirUltraHonkProver<P>
     }
 
     fn sub(&self, a: Self::ArithmeticShare, b: Self::ArithmeticShare) -> Self::ArithmeticShare {
-        arithmetic::sub(a, b)
+        a - b
     }
 
     fn add(&self, a: Self::ArithmeticShare, b: Self::ArithmeticShare) -> Self::ArithmeticShare {
-        arithmetic::add(a, b)
+        a + b
     }
 
     fn neg(&mut self, a: Self::ArithmeticShare) -> Self::ArithmeticShare {
-        arithmetic::neg(a)
+        -a
     }
 
     fn mul_with_public(
@@ -61,7 +61,7 @@ impl<P: Pairing, N: ShamirNetwork> NoirUltraHonkProver<P>
         public: P::ScalarField,
         shared: Self::ArithmeticShare,
     ) -> Self::ArithmeticShare {
-        arithmetic::mul_public(shared, public)
+        shared * public
     }
 
     fn mul_many(
@@ -290,12 +290,7 @@ impl<P: HonkCurve<TranscriptFieldType>, H: TranscriptHasher<TranscriptFieldType>
 
         let z = transcript.get_challenge::<P>("Shplonk:z".to_string());
 
-        Ok(Self::compute_partially_evaluated_batched_quotient(
-            opening_claims,
-            batched_quotient,
-            nu,
-            z,
-        ))
+        Self::compute_partially_evaluated_batched_quotient(opening_claims, batched_quotient, nu, z)
     }
 
     pub(crate) fn shplemini_prove(
@@ -331,7 +326,7 @@ impl<P: HonkCurve<TranscriptFieldType>, H: TranscriptHasher<TranscriptFieldType>
         batched_quotient_q: Polynomial<P::ScalarField>,
         nu_challenge: P::ScalarField,
         z_challenge: P::ScalarField,
-    ) -> ShpleminiOpeningClaim<P::ScalarField> {
+    ) -> HonkProofResult<ShpleminiOpeningClaim<P::ScalarField>> {
         tracing::trace!("Compute partially evaluated batched quotient");
         let num_opening_claims = opening_claims.len();
 
@@ -340,9 +335,7 @@ impl<P: HonkCurve<TranscriptFieldType>, H: TranscriptHasher<TranscriptFieldType>
         for claim in &opening_claims {
             inverse_vanishing_evals.push(z_challenge - claim.opening_pair.challenge);
         }
-        inverse_vanishing_evals.iter_mut().for_each(|x| {
-            x.inverse_in_place();
-        });
+        Utils::batch_invert_nonzero(&mut inverse_vanishing_evals)?;
 
         let mut g = batched_quotient_q;
 
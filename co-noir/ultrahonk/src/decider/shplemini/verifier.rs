@@ -90,24 +90,17 @@ impl<P: HonkCurve<TranscriptFieldType>, H: TranscriptHasher<TranscriptFieldType>
         num_gemini_claims: usize,
         shplonk_eval_challenge: &P::ScalarField,
         gemini_eval_challenge_powers: &[P::ScalarField],
-    ) -> Vec<P::ScalarField> {
+    ) -> HonkVerifyResult<Vec<P::ScalarField>> {
         tracing::trace!("Compute inverted gemini denominators");
         let mut inverted_denominators = Vec::with_capacity(num_gemini_claims);
-        inverted_denominators.push(
-            (*shplonk_eval_challenge - gemini_eval_challenge_powers[0])
-                .inverse()
-                .unwrap(),
-        );
+        inverted_denominators.push(*shplonk_eval_challenge - gemini_eval_challenge_powers[0]);
 
         for gemini_eval_challenge_power in gemini_eval_challenge_powers {
-            let round_inverted_denominator = (*shplonk_eval_challenge
-                + gemini_eval_challenge_power)
-                .inverse()
-                .unwrap();
-            inverted_denominators.push(round_inverted_denominator);
+            inverted_denominators.push(*shplonk_eval_challenge + gemini_eval_challenge_power);
         }
+        Utils::batch_invert_nonzero(&mut inverted_denominators)?;
 
-        inverted_denominators
+        Ok(inverted_denominators)
     }
 
     pub fn compute_batch_opening_claim(
@@ -169,7 +162,7 @@ impl<P: HonkCurve<TranscriptFieldType>, H: TranscriptHasher<TranscriptFieldType>
                 (log_circuit_size + 1).try_into().unwrap(),
                 &opening_claim.challenge,
                 &gemini_eval_challenge_powers,
-            );
+            )?;
 
         // Compute the additional factors to be multiplied with unshifted and shifted commitments when lazily
         // reconstructing the commitment of Q_z
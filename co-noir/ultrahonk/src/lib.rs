@@ -16,7 +16,6 @@ use co_builder::{
     prelude::{AcirFormat, ProverCrs},
     HonkProofResult,
 };
-use eyre::Error;
 use noirc_artifacts::program::ProgramArtifact;
 use std::{io, path::Path};
 
@@ -29,7 +28,8 @@ pub const N_MAX: usize = 1 << 25;
 pub struct Utils {}
 
 impl Utils {
-    pub(crate) fn field_from_hex_string<F: PrimeField>(str: &str) -> Result<F, Error> {
+    #[cfg(test)]
+    pub(crate) fn field_from_hex_string<F: PrimeField>(str: &str) -> Result<F, eyre::Error> {
         co_builder::prelude::Utils::field_from_hex_string(str)
     }
 
@@ -91,6 +91,10 @@ impl Utils {
         co_builder::prelude::Utils::batch_invert(coeffs);
     }
 
+    pub fn batch_invert_nonzero<F: PrimeField>(coeffs: &mut [F]) -> HonkProofResult<()> {
+        co_builder::prelude::Utils::batch_invert_nonzero(coeffs)
+    }
+
     pub fn commit<P: Pairing>(
         poly: &[P::ScalarField],
         crs: &ProverCrs<P>,
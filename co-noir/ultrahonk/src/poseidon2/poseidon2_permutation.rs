@@ -1,6 +1,7 @@
-use super::poseidon2_params::Poseidon2Params;
+use super::poseidon2_params::{Poseidon2ParamSet, Poseidon2Params};
 use crate::sponge_hasher::FieldHash;
 use ark_ff::PrimeField;
+use std::marker::PhantomData;
 
 #[derive(Clone, Debug)]
 pub struct Poseidon2<F: PrimeField, const T: usize, const D: u64> {
@@ -11,157 +12,11 @@ impl<F: PrimeField, const T: usize, const D: u64> Poseidon2<F, T, D> {
     pub(crate) fn new(params: &'static Poseidon2Params<F, T, D>) -> Self {
         Self { params }
     }
-
-    fn sbox(input: &mut [F; T]) {
-        input.iter_mut().for_each(Self::single_sbox);
-    }
-
-    fn single_sbox(input: &mut F) {
-        match D {
-            3 => {
-                let input2 = input.square();
-                *input *= input2;
-            }
-            5 => {
-                let input2 = input.square();
-                let input4 = input2.square();
-                *input *= input4;
-            }
-            7 => {
-                let input2 = input.square();
-                let input4 = input2.square();
-                *input *= input4;
-                *input *= input2;
-            }
-            _ => {
-                *input = input.pow([D]);
-            }
-        }
-    }
-
-    /**
-     * hardcoded algorithm that evaluates matrix multiplication using the following MDS matrix:
-     * /         \
-     * | 5 7 1 3 |
-     * | 4 6 1 1 |
-     * | 1 3 5 7 |
-     * | 1 1 4 6 |
-     * \         /
-     *
-     * Algorithm is taken directly from the Poseidon2 paper.
-     */
-    fn matmul_m4(input: &mut [F; 4]) {
-        let t_0 = input[0] + input[1]; // A + B
-        let t_1 = input[2] + input[3]; // C + D
-        let t_2 = input[1].double() + t_1; // 2B + C + D
-        let t_3 = input[3].double() + t_0; // A + B + 2D
-        let t_4 = t_1.double().double() + t_3; // A + B + 4C + 6D
-        let t_5 = t_0.double().double() + t_2; // 4A + 6B + C + D
-        let t_6 = t_3 + t_5; // 5A + 7B + C + 3D
-        let t_7 = t_2 + t_4; // A + 3B + 5C + 7D
-        input[0] = t_6;
-        input[1] = t_5;
-        input[2] = t_7;
-        input[3] = t_4;
-    }
-
-    fn matmul_external(input: &mut [F; T]) {
-        match T {
-            2 => {
-                // Matrix circ(2, 1)
-                let sum = input[0] + input[1];
-                input[0] += &sum;
-                input[1] += sum;
-            }
-            3 => {
-                // Matrix circ(2, 1, 1)
-                let sum = input[0] + input[1] + input[2];
-                input[0] += &sum;
-                input[1] += &sum;
-                input[2] += sum;
-            }
-            4 => {
-                Self::matmul_m4(input.as_mut_slice().try_into().unwrap());
-            }
-            8 | 12 | 16 | 20 | 24 => {
-                // Applying cheap 4x4 MDS matrix to each 4-element part of the state
-                for state in input.chunks_exact_mut(4) {
-                    Self::matmul_m4(state.try_into().unwrap());
-                }
-
-                // Applying second cheap matrix for t > 4
-                let mut stored = [F::zero(); 4];
-                for l in 0..4 {
-                    stored[l] = input[l];
-                    for j in 1..T / 4 {
-                        stored[l] += input[4 * j + l];
-                    }
-                }
-                for i in 0..T {
-                    input[i] += stored[i % 4];
-                }
-            }
-            _ => {
-                panic!("Invalid Statesize");
-            }
-        }
-    }
-
-    fn matmul_internal(&self, input: &mut [F; T]) {
-        // Compute input sum
-        let sum: F = input.iter().sum();
-        // Add sum + diag entry * element to each element
-
-        for (s, m) in input
-            .iter_mut()
-            .zip(self.params.mat_internal_diag_m_1.iter())
-        {
-            *s *= m;
-            *s += sum;
-        }
-    }
-
-    fn add_rc_external(&self, input: &mut [F; T], rc_offset: usize) {
-        for (s, rc) in input
-            .iter_mut()
-            .zip(self.params.round_constants_external[rc_offset].iter())
-        {
-            *s += rc;
-        }
-    }
-
-    fn add_rc_internal(&self, input: &mut [F; T], rc_offset: usize) {
-        input[0] += &self.params.round_constants_internal[rc_offset];
-    }
 }
 
 impl<F: PrimeField, const T: usize, const D: u64> FieldHash<F, T> for Poseidon2<F, T, D> {
     fn permutation_in_place(&self, state: &mut [F; T]) {
-        // Linear layer at beginning
-        Self::matmul_external(state);
-
-        // First set of external rounds
-        for r in 0..self.params.rounds_f_beginning {
-            self.add_rc_external(state, r);
-            Self::sbox(state);
-            Self::matmul_external(state);
-        }
-
-        // Internal rounds
-        for r in 0..self.params.rounds_p {
-            self.add_rc_internal(state, r);
-            Self::single_sbox(&mut state[0]);
-            self.matmul_internal(state);
-        }
-
-        // Remaining external rounds
-        for r in self.params.rounds_f_beginning
-            ..self.params.rounds_f_beginning + self.params.rounds_f_end
-        {
-            self.add_rc_external(state, r);
-            Self::sbox(state);
-            Self::matmul_external(state);
-        }
+        self.params.permutation_in_place(state);
     }
 }
 
@@ -172,6 +27,32 @@ impl Default for Poseidon2<ark_bn254::Fr, 4, 5> {
     }
 }
 
+/// The Poseidon2 permutation instantiated with the registered parameter set `P`.
+#[derive(Clone, Debug)]
+pub struct Poseidon2WithParams<F: PrimeField, const T: usize, const D: u64, P> {
+    poseidon2: Poseidon2<F, T, D>,
+    phantom_data: PhantomData<P>,
+}
+
+impl<F: PrimeField, const T: usize, const D: u64, P: Poseidon2ParamSet<F, T, D>> Default
+    for Poseidon2WithParams<F, T, D, P>
+{
+    fn default() -> Self {
+        Self {
+            poseidon2: Poseidon2::new(P::params()),
+            phantom_data: PhantomData,
+        }
+    }
+}
+
+impl<F: PrimeField, const T: usize, const D: u64, P> FieldHash<F, T>
+    for Poseidon2WithParams<F, T, D, P>
+{
+    fn permutation_in_place(&self, state: &mut [F; T]) {
+        self.poseidon2.permutation_in_place(state);
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
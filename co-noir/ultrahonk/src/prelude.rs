@@ -1,9 +1,11 @@
 pub use crate::decider::barycentric::Barycentric;
 pub use crate::decider::types::GateSeparatorPolynomial;
 pub use crate::decider::univariate::Univariate;
-pub use crate::poseidon2::poseidon2_bn254::POSEIDON2_BN254_T4_PARAMS;
+pub use crate::poseidon2::poseidon2_bn254::{Poseidon2Bn254T4, POSEIDON2_BN254_T4_PARAMS};
+pub use crate::poseidon2::poseidon2_params::{Poseidon2ParamSet, Poseidon2Params};
+pub use crate::poseidon2::poseidon2_permutation::Poseidon2WithParams;
 pub use crate::prover::UltraHonk;
-pub use crate::transcript::Poseidon2Sponge;
+pub use crate::transcript::{Poseidon2Sponge, Poseidon2SpongeWith};
 pub use crate::transcript::{Transcript, TranscriptHasher};
 pub use crate::types::HonkProof;
 pub use crate::types::{ShiftedTableEntities, ShiftedWitnessEntities};
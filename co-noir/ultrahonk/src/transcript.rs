@@ -1,5 +1,5 @@
 use crate::{
-    poseidon2::poseidon2_permutation::Poseidon2,
+    poseidon2::{poseidon2_bn254::Poseidon2Bn254T4, poseidon2_permutation::Poseidon2WithParams},
     sponge_hasher::{FieldHash, FieldSponge},
     types::HonkProof,
 };
@@ -10,8 +10,11 @@ use num_bigint::BigUint;
 use std::{collections::BTreeMap, ops::Index};
 
 pub type TranscriptFieldType = ark_bn254::Fr;
-pub type Poseidon2Sponge =
-    FieldSponge<TranscriptFieldType, 4, 3, Poseidon2<TranscriptFieldType, 4, 5>>;
+/// The Poseidon2 sponge with state size `T`, rate `R`, and S-box degree `D`, instantiated with the Poseidon2 parameter set `P` over the field `F`.
+pub type Poseidon2SpongeWith<F, const T: usize, const R: usize, const D: u64, P> =
+    FieldSponge<F, T, R, Poseidon2WithParams<F, T, D, P>>;
+/// The Poseidon2 sponge used as transcript hasher by Barretenberg.
+pub type Poseidon2Sponge = Poseidon2SpongeWith<TranscriptFieldType, 4, 3, 5, Poseidon2Bn254T4>;
 
 pub trait TranscriptHasher<F: PrimeField> {
     fn hash(buffer: Vec<F>) -> F;
@@ -380,3 +383,78 @@ impl Index<usize> for TranscriptManifest {
         &self.manifest[&index]
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        poseidon2::{
+            poseidon2_bn254::{EXTERNAL_RC, INTERNAL_RC, MAT_DIAG_M_1},
+            poseidon2_params::{Poseidon2ParamSet, Poseidon2Params},
+        },
+        Utils,
+    };
+    use std::sync::LazyLock;
+
+    type Scalar = ark_bn254::Fr;
+
+    static REDUCED_INTERNAL_RC: LazyLock<Vec<Scalar>> =
+        LazyLock::new(|| INTERNAL_RC[..28].to_vec());
+    static REDUCED_ROUNDS_PARAMS: LazyLock<Poseidon2Params<Scalar, 4, 5>> = LazyLock::new(|| {
+        Poseidon2Params::new(8, 28, &MAT_DIAG_M_1, &EXTERNAL_RC, &REDUCED_INTERNAL_RC)
+    });
+
+    /// A custom parameter set with only half of the partial rounds of [Poseidon2Bn254T4]
+    struct ReducedRoundsBn254T4;
+
+    impl Poseidon2ParamSet<Scalar, 4, 5> for ReducedRoundsBn254T4 {
+        fn params() -> &'static Poseidon2Params<Scalar, 4, 5> {
+            &REDUCED_ROUNDS_PARAMS
+        }
+    }
+
+    fn sponge_kat<H: TranscriptHasher<Scalar>>(input: Vec<Scalar>, expected: &str) {
+        let expected = Utils::field_from_hex_string(expected).unwrap();
+        assert_eq!(H::hash(input), expected);
+    }
+
+    #[test]
+    fn poseidon2_sponge_bn254_t4_kat1() {
+        let input = (0..4u64).map(Scalar::from).collect();
+        sponge_kat::<Poseidon2Sponge>(
+            input,
+            "0x109dc2e0b6bad6e1f2954e9ea0b58462c30257ad3876eae682ed33a22992f427",
+        );
+    }
+
+    #[test]
+    fn poseidon2_sponge_bn254_t4_kat2() {
+        // HashConsistencyCheck of Barretenberg
+        let input = Utils::field_from_hex_string(
+            "0x9a807b615c4d3e2fa0b1c2d3e4f56789fedcba9876543210abcdef0123456789",
+        )
+        .unwrap();
+        sponge_kat::<Poseidon2Sponge>(
+            vec![input; 4],
+            "0x2f43a0f83b51a6f5fc839dea0ecec74947637802a579fa9841930a25a0bcec11",
+        );
+    }
+
+    #[test]
+    fn poseidon2_sponge_explicit_params_match_default() {
+        let input: Vec<Scalar> = (0..7u64).map(Scalar::from).collect();
+        assert_eq!(
+            Poseidon2SpongeWith::<Scalar, 4, 3, 5, Poseidon2Bn254T4>::hash(input.clone()),
+            Poseidon2Sponge::hash(input)
+        );
+    }
+
+    #[test]
+    fn poseidon2_sponge_custom_params_kat() {
+        let input = (0..4u64).map(Scalar::from).collect();
+        sponge_kat::<Poseidon2SpongeWith<Scalar, 4, 3, 5, ReducedRoundsBn254T4>>(
+            input,
+            "0x1fdd71a02802717b0142f1a346f912680ad771d5ea1787f4a02e766d1847113b",
+        );
+    }
+}
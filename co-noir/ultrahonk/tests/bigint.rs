@@ -0,0 +1,264 @@
+use acir::{
+    circuit::{
+        opcodes::{BlackBoxFuncCall, FunctionInput},
+        Circuit, Opcode,
+    },
+    native_types::Witness,
+    AcirField, FieldElement,
+};
+use ark_bn254::Bn254;
+use co_builder::prelude::AcirFormat;
+use num_bigint::BigUint;
+use sha3::Keccak256;
+use ultrahonk::prelude::{
+    PlainAcvmSolver, Poseidon2Sponge, ProvingKey, TranscriptFieldType, TranscriptHasher,
+    UltraCircuitBuilder, UltraHonk,
+};
+
+const CRS_PATH_G1: &str = "../co-builder/src/crs/bn254_g1.dat";
+const CRS_PATH_G2: &str = "../co-builder/src/crs/bn254_g2.dat";
+const SECP256K1_FQ: &str = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f";
+const BN254_FQ: &str = "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47";
+
+#[derive(Clone, Copy)]
+enum Operation {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Operation {
+    fn apply(self, lhs: &BigUint, rhs: &BigUint, modulus: &BigUint) -> BigUint {
+        match self {
+            Operation::Add => (lhs + rhs) % modulus,
+            Operation::Sub => (lhs + modulus - rhs) % modulus,
+            Operation::Mul => (lhs * rhs) % modulus,
+            Operation::Div => (lhs * rhs.modpow(&(modulus - 2u64), modulus)) % modulus,
+        }
+    }
+
+    fn call(self, lhs: u32, rhs: u32, output: u32) -> BlackBoxFuncCall<FieldElement> {
+        match self {
+            Operation::Add => BlackBoxFuncCall::BigIntAdd { lhs, rhs, output },
+            Operation::Sub => BlackBoxFuncCall::BigIntSub { lhs, rhs, output },
+            Operation::Mul => BlackBoxFuncCall::BigIntMul { lhs, rhs, output },
+            Operation::Div => BlackBoxFuncCall::BigIntDiv { lhs, rhs, output },
+        }
+    }
+}
+
+/// Collects the witness and the opcodes of a circuit that is built in code.
+#[derive(Default)]
+struct CircuitBuilder {
+    values: Vec<FieldElement>,
+    opcodes: Vec<Opcode<FieldElement>>,
+}
+
+impl CircuitBuilder {
+    fn witness(&mut self, value: FieldElement) -> Witness {
+        self.values.push(value);
+        Witness(self.values.len() as u32 - 1)
+    }
+
+    fn bigint_from_le_bytes(&mut self, value: &BigUint, modulus: &BigUint, output: u32) {
+        let inputs = value
+            .to_bytes_le()
+            .into_iter()
+            .map(|byte| FunctionInput::witness(self.witness(FieldElement::from(byte as u128)), 8))
+            .collect();
+        self.call(BlackBoxFuncCall::BigIntFromLeBytes {
+            inputs,
+            modulus: modulus.to_bytes_le(),
+            output,
+        });
+    }
+
+    fn bigint_to_le_bytes(&mut self, input: u32, value: &BigUint) {
+        let mut bytes = value.to_bytes_le();
+        bytes.resize(32, 0);
+        let outputs = bytes
+            .into_iter()
+            .map(|byte| self.witness(FieldElement::from(byte as u128)))
+            .collect();
+        self.call(BlackBoxFuncCall::BigIntToLeBytes { input, outputs });
+    }
+
+    fn call(&mut self, call: BlackBoxFuncCall<FieldElement>) {
+        self.opcodes.push(Opcode::BlackBoxFuncCall(call));
+    }
+
+    fn circuit(&self) -> Circuit<FieldElement> {
+        Circuit {
+            current_witness_index: self.values.len() as u32,
+            opcodes: self.opcodes.to_owned(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Builds a circuit which reads lhs and rhs, applies the operation and writes the bytes of the given output.
+fn bigint_circuit(
+    modulus: &str,
+    lhs: &BigUint,
+    rhs: &BigUint,
+    operation: Operation,
+    output: &BigUint,
+) -> (Circuit<FieldElement>, Vec<FieldElement>) {
+    let modulus = BigUint::parse_bytes(modulus.as_bytes(), 16).unwrap();
+    let mut builder = CircuitBuilder::default();
+    builder.bigint_from_le_bytes(lhs, &modulus, 0);
+    builder.bigint_from_le_bytes(rhs, &modulus, 1);
+    builder.call(operation.call(0, 1, 2));
+    builder.bigint_to_le_bytes(2, output);
+    (builder.circuit(), builder.values)
+}
+
+fn prove_and_verify<H: TranscriptHasher<TranscriptFieldType>>(
+    circuit: &Circuit<FieldElement>,
+    witness: &[FieldElement],
+) {
+    let witness = witness.iter().map(|value| value.into_repr()).collect();
+    let constraint_system = AcirFormat::circuit_serde_to_acir_format(circuit.to_owned(), true);
+    let mut driver = PlainAcvmSolver::new();
+    let builder = UltraCircuitBuilder::<Bn254>::create_circuit(
+        constraint_system,
+        false,
+        0,
+        witness,
+        true,
+        false,
+        &mut driver,
+    )
+    .unwrap();
+    let crs = ProvingKey::get_crs(&builder, CRS_PATH_G1, CRS_PATH_G2).unwrap();
+    let (proving_key, verifying_key) = builder.create_keys(crs, &mut driver).unwrap();
+
+    let proof = UltraHonk::<_, H>::prove(proving_key).unwrap();
+    let is_valid = UltraHonk::<_, H>::verify(proof, verifying_key).unwrap();
+    assert!(is_valid);
+}
+
+/// The verifying key must not depend on the witness, i.e., a circuit without witness has to produce the same gates.
+fn assert_vk_independent_of_witness(circuit: &Circuit<FieldElement>, witness: &[FieldElement]) {
+    let vk = |witness: Vec<ark_bn254::Fr>| {
+        let constraint_system = AcirFormat::circuit_serde_to_acir_format(circuit.to_owned(), true);
+        let mut driver = PlainAcvmSolver::new();
+        let builder = UltraCircuitBuilder::<Bn254>::create_circuit(
+            constraint_system,
+            false,
+            0,
+            witness,
+            true,
+            false,
+            &mut driver,
+        )
+        .unwrap();
+        let crs = ProvingKey::get_prover_crs(&builder, CRS_PATH_G1).unwrap();
+        builder
+            .create_vk_barretenberg(crs, &mut driver)
+            .unwrap()
+            .to_buffer()
+    };
+    let witness = witness.iter().map(|value| value.into_repr()).collect();
+    assert_eq!(vk(witness), vk(vec![]));
+}
+
+fn test_operation(modulus: &str, lhs: &BigUint, rhs: &BigUint, operation: Operation) {
+    let output = operation.apply(
+        lhs,
+        rhs,
+        &BigUint::parse_bytes(modulus.as_bytes(), 16).unwrap(),
+    );
+    let (circuit, witness) = bigint_circuit(modulus, lhs, rhs, operation, &output);
+    prove_and_verify::<Poseidon2Sponge>(&circuit, &witness);
+    prove_and_verify::<Keccak256>(&circuit, &witness);
+    assert_vk_independent_of_witness(&circuit, &witness);
+}
+
+fn operands(modulus: &str) -> (BigUint, BigUint) {
+    let modulus = BigUint::parse_bytes(modulus.as_bytes(), 16).unwrap();
+    let lhs = &modulus - 12345u64;
+    let rhs = (BigUint::from(0x1234_5678_9abc_def0_u64) << 130) + 987_654_321u64;
+    (lhs, rhs)
+}
+
+#[test]
+fn bigint_add() {
+    let (lhs, rhs) = operands(SECP256K1_FQ);
+    test_operation(SECP256K1_FQ, &lhs, &rhs, Operation::Add);
+}
+
+#[test]
+fn bigint_sub() {
+    let (lhs, rhs) = operands(SECP256K1_FQ);
+    test_operation(SECP256K1_FQ, &rhs, &lhs, Operation::Sub);
+}
+
+#[test]
+fn bigint_mul() {
+    let (lhs, rhs) = operands(BN254_FQ);
+    test_operation(BN254_FQ, &lhs, &rhs, Operation::Mul);
+}
+
+#[test]
+fn bigint_div() {
+    let (lhs, rhs) = operands(SECP256K1_FQ);
+    test_operation(SECP256K1_FQ, &lhs, &rhs, Operation::Div);
+}
+
+#[test]
+fn bigint_chained_operations() {
+    let modulus = BigUint::parse_bytes(BN254_FQ.as_bytes(), 16).unwrap();
+    let (lhs, rhs) = operands(BN254_FQ);
+    // (lhs * rhs + lhs) / rhs - rhs
+    let product = Operation::Mul.apply(&lhs, &rhs, &modulus);
+    let sum = Operation::Add.apply(&product, &lhs, &modulus);
+    let quotient = Operation::Div.apply(&sum, &rhs, &modulus);
+    let output = Operation::Sub.apply(&quotient, &rhs, &modulus);
+
+    let mut builder = CircuitBuilder::default();
+    builder.bigint_from_le_bytes(&lhs, &modulus, 0);
+    builder.bigint_from_le_bytes(&rhs, &modulus, 1);
+    builder.call(Operation::Mul.call(0, 1, 2));
+    builder.call(Operation::Add.call(2, 0, 3));
+    builder.call(Operation::Div.call(3, 1, 4));
+    builder.call(Operation::Sub.call(4, 1, 5));
+    builder.bigint_to_le_bytes(5, &output);
+    let circuit = builder.circuit();
+    prove_and_verify::<Poseidon2Sponge>(&circuit, &builder.values);
+    assert_vk_independent_of_witness(&circuit, &builder.values);
+}
+
+#[test]
+#[should_panic]
+fn bigint_wrong_output() {
+    let (lhs, rhs) = operands(SECP256K1_FQ);
+    let output = &lhs + 1u64;
+    let (circuit, witness) = bigint_circuit(SECP256K1_FQ, &lhs, &rhs, Operation::Mul, &output);
+    prove_and_verify::<Poseidon2Sponge>(&circuit, &witness);
+}
+
+#[test]
+fn bigint_unsupported_modulus() {
+    let modulus = BigUint::from(1_000_000_007u64);
+    let mut builder = CircuitBuilder::default();
+    builder.bigint_from_le_bytes(&BigUint::from(5u64), &modulus, 0);
+    let constraint_system = AcirFormat::circuit_serde_to_acir_format(builder.circuit(), true);
+    let witness = builder
+        .values
+        .iter()
+        .map(|value| value.into_repr())
+        .collect();
+    let mut driver = PlainAcvmSolver::new();
+    let result = UltraCircuitBuilder::<Bn254>::create_circuit(
+        constraint_system,
+        false,
+        0,
+        witness,
+        true,
+        false,
+        &mut driver,
+    );
+    assert!(result.is_err());
+}
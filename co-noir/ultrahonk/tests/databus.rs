@@ -0,0 +1,126 @@
+use acir::{
+    circuit::{
+        opcodes::{BlockId, BlockType, MemOp},
+        Circuit, Opcode, PublicInputs,
+    },
+    native_types::{Expression, Witness},
+    AcirField, FieldElement,
+};
+use ark_bn254::Bn254;
+use co_builder::prelude::AcirFormat;
+use ultrahonk::prelude::{
+    PlainAcvmSolver, Poseidon2Sponge, ProvingKey, UltraCircuitBuilder, UltraHonk,
+};
+
+const CRS_PATH_G1: &str = "../co-builder/src/crs/bn254_g1.dat";
+const CRS_PATH_G2: &str = "../co-builder/src/crs/bn254_g2.dat";
+
+/// Builds a circuit which reads call_data[w3] into w4 and returns w5 = w4 + w0 as return_data. The call_data consists of w0, w1 and w2.
+fn databus_circuit() -> Circuit<FieldElement> {
+    let calldata = BlockId(0);
+    let returndata = BlockId(1);
+    Circuit {
+        current_witness_index: 5,
+        opcodes: vec![
+            Opcode::MemoryInit {
+                block_id: calldata,
+                init: vec![Witness(0), Witness(1), Witness(2)],
+                block_type: BlockType::CallData(0),
+            },
+            Opcode::MemoryOp {
+                block_id: calldata,
+                op: MemOp::read_at_mem_index(Witness(3).into(), Witness(4)),
+                predicate: None,
+            },
+            Opcode::AssertZero(Expression {
+                mul_terms: vec![],
+                linear_combinations: vec![
+                    (FieldElement::one(), Witness(4)),
+                    (FieldElement::one(), Witness(0)),
+                    (-FieldElement::one(), Witness(5)),
+                ],
+                q_c: FieldElement::zero(),
+            }),
+            Opcode::MemoryInit {
+                block_id: returndata,
+                init: vec![Witness(5)],
+                block_type: BlockType::ReturnData,
+            },
+        ],
+        return_values: PublicInputs([Witness(5)].into()),
+        ..Default::default()
+    }
+}
+
+fn witness(calldata: [u64; 3], index: u64, read: u64) -> Vec<ark_bn254::Fr> {
+    let mut witness = calldata.map(ark_bn254::Fr::from).to_vec();
+    witness.push(index.into());
+    witness.push(read.into());
+    witness.push((read + calldata[0]).into());
+    witness
+}
+
+fn prove_and_verify(witness: Vec<ark_bn254::Fr>) {
+    let constraint_system = AcirFormat::circuit_serde_to_acir_format(databus_circuit(), true);
+    let mut driver = PlainAcvmSolver::new();
+    let builder = UltraCircuitBuilder::<Bn254>::create_circuit(
+        constraint_system,
+        false,
+        0,
+        witness,
+        true,
+        false,
+        &mut driver,
+    )
+    .unwrap();
+    let crs = ProvingKey::get_crs(&builder, CRS_PATH_G1, CRS_PATH_G2).unwrap();
+    let (proving_key, verifying_key) = builder.create_keys(crs, &mut driver).unwrap();
+
+    let proof = UltraHonk::<_, Poseidon2Sponge>::prove(proving_key).unwrap();
+    let is_valid = UltraHonk::<_, Poseidon2Sponge>::verify(proof, verifying_key).unwrap();
+    assert!(is_valid);
+}
+
+fn vk(witness: Vec<ark_bn254::Fr>) -> Vec<u8> {
+    let constraint_system = AcirFormat::circuit_serde_to_acir_format(databus_circuit(), true);
+    let mut driver = PlainAcvmSolver::new();
+    let builder = UltraCircuitBuilder::<Bn254>::create_circuit(
+        constraint_system,
+        false,
+        0,
+        witness,
+        true,
+        false,
+        &mut driver,
+    )
+    .unwrap();
+    let crs = ProvingKey::get_prover_crs(&builder, CRS_PATH_G1).unwrap();
+    builder
+        .create_vk_barretenberg(crs, &mut driver)
+        .unwrap()
+        .to_buffer()
+}
+
+#[test]
+fn databus_read_calldata() {
+    prove_and_verify(witness([10, 20, 30], 1, 20));
+    prove_and_verify(witness([10, 20, 30], 2, 30));
+}
+
+#[test]
+fn databus_vk_independent_of_witness() {
+    // the call_data is treated as a ROM array, whose gates must not depend on the witness
+    assert_eq!(vk(witness([10, 20, 30], 1, 20)), vk(vec![]));
+}
+
+#[test]
+#[should_panic]
+fn databus_wrong_read() {
+    prove_and_verify(witness([10, 20, 30], 1, 30));
+}
+
+#[test]
+#[should_panic]
+fn databus_index_out_of_bounds() {
+    prove_and_verify(witness([10, 20, 30], 3, 30));
+}
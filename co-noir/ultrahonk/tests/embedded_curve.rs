@@ -0,0 +1,275 @@
+use acir::{
+    circuit::{
+        opcodes::{BlackBoxFuncCall, FunctionInput},
+        Circuit, Opcode,
+    },
+    native_types::Witness,
+    AcirField, FieldElement,
+};
+use ark_bn254::Bn254;
+use ark_ff::{BigInteger, PrimeField};
+use co_builder::prelude::AcirFormat;
+use sha3::Keccak256;
+use ultrahonk::prelude::{
+    PlainAcvmSolver, Poseidon2Sponge, ProvingKey, TranscriptFieldType, TranscriptHasher,
+    UltraCircuitBuilder, UltraHonk,
+};
+
+const CRS_PATH_G1: &str = "../co-builder/src/crs/bn254_g1.dat";
+const CRS_PATH_G2: &str = "../co-builder/src/crs/bn254_g2.dat";
+const GENERATOR_Y: &str = "17631683881184975370165255887551781615748388533673675138860";
+
+type Point = [FieldElement; 3];
+
+fn generator() -> Point {
+    [
+        FieldElement::one(),
+        FieldElement::try_from_str(GENERATOR_Y).unwrap(),
+        FieldElement::zero(),
+    ]
+}
+
+fn pedersen_generator(index: u32) -> Point {
+    let point = bn254_blackbox_solver::derive_generators(b"DEFAULT_DOMAIN_SEPARATOR", 1, index)
+        .pop()
+        .unwrap();
+    [
+        FieldElement::from_le_bytes_reduce(&point.x.into_bigint().to_bytes_le()),
+        FieldElement::from_le_bytes_reduce(&point.y.into_bigint().to_bytes_le()),
+        FieldElement::zero(),
+    ]
+}
+
+fn add(p1: Point, p2: Point) -> Point {
+    let (x, y, infinity) = bn254_blackbox_solver::embedded_curve_add(p1, p2).unwrap();
+    [x, y, infinity]
+}
+
+fn msm(points: &[Point], scalars: &[(u128, u128)]) -> Point {
+    let points = points.concat();
+    let (lo, hi): (Vec<_>, Vec<_>) = scalars
+        .iter()
+        .map(|(lo, hi)| (FieldElement::from(*lo), FieldElement::from(*hi)))
+        .unzip();
+    let (x, y, infinity) = bn254_blackbox_solver::multi_scalar_mul(&points, &lo, &hi).unwrap();
+    [x, y, infinity]
+}
+
+/// Collects the witness of a circuit that is built in code.
+#[derive(Default)]
+struct WitnessBuilder {
+    values: Vec<FieldElement>,
+}
+
+impl WitnessBuilder {
+    fn witness(&mut self, value: FieldElement) -> Witness {
+        self.values.push(value);
+        Witness(self.values.len() as u32 - 1)
+    }
+
+    fn input(&mut self, value: FieldElement, num_bits: u32) -> FunctionInput<FieldElement> {
+        FunctionInput::witness(self.witness(value), num_bits)
+    }
+
+    fn point(&mut self, point: Point) -> Vec<FunctionInput<FieldElement>> {
+        vec![
+            self.input(point[0], FieldElement::max_num_bits()),
+            self.input(point[1], FieldElement::max_num_bits()),
+            self.input(point[2], 1),
+        ]
+    }
+
+    fn outputs(&mut self, point: Point) -> (Witness, Witness, Witness) {
+        (
+            self.witness(point[0]),
+            self.witness(point[1]),
+            self.witness(point[2]),
+        )
+    }
+
+    fn circuit(&self, call: BlackBoxFuncCall<FieldElement>) -> Circuit<FieldElement> {
+        Circuit {
+            current_witness_index: self.values.len() as u32,
+            opcodes: vec![Opcode::BlackBoxFuncCall(call)],
+            ..Default::default()
+        }
+    }
+}
+
+fn constant_point(point: Point) -> Vec<FunctionInput<FieldElement>> {
+    vec![
+        FunctionInput::constant(point[0], FieldElement::max_num_bits()).unwrap(),
+        FunctionInput::constant(point[1], FieldElement::max_num_bits()).unwrap(),
+        FunctionInput::constant(point[2], 1).unwrap(),
+    ]
+}
+
+fn prove_and_verify<H: TranscriptHasher<TranscriptFieldType>>(
+    circuit: &Circuit<FieldElement>,
+    witness: &[FieldElement],
+) {
+    let witness = witness.iter().map(|value| value.into_repr()).collect();
+    let constraint_system = AcirFormat::circuit_serde_to_acir_format(circuit.to_owned(), true);
+    let mut driver = PlainAcvmSolver::new();
+    let builder = UltraCircuitBuilder::<Bn254>::create_circuit(
+        constraint_system,
+        false,
+        0,
+        witness,
+        true,
+        false,
+        &mut driver,
+    )
+    .unwrap();
+    let crs = ProvingKey::get_crs(&builder, CRS_PATH_G1, CRS_PATH_G2).unwrap();
+    let (proving_key, verifying_key) = builder.create_keys(crs, &mut driver).unwrap();
+
+    let proof = UltraHonk::<_, H>::prove(proving_key).unwrap();
+    let is_valid = UltraHonk::<_, H>::verify(proof, verifying_key).unwrap();
+    assert!(is_valid);
+}
+
+/// The verifying key must not depend on the witness, i.e., a circuit without witness has to produce the same gates.
+fn assert_vk_independent_of_witness(circuit: &Circuit<FieldElement>, witness: &[FieldElement]) {
+    let vk = |witness: Vec<ark_bn254::Fr>| {
+        let constraint_system = AcirFormat::circuit_serde_to_acir_format(circuit.to_owned(), true);
+        let mut driver = PlainAcvmSolver::new();
+        let builder = UltraCircuitBuilder::<Bn254>::create_circuit(
+            constraint_system,
+            false,
+            0,
+            witness,
+            true,
+            false,
+            &mut driver,
+        )
+        .unwrap();
+        let crs = ProvingKey::get_prover_crs(&builder, CRS_PATH_G1).unwrap();
+        builder
+            .create_vk_barretenberg(crs, &mut driver)
+            .unwrap()
+            .to_buffer()
+    };
+    let witness = witness.iter().map(|value| value.into_repr()).collect();
+    assert_eq!(vk(witness), vk(vec![]));
+}
+
+fn msm_circuit(
+    points: &[Point],
+    scalars: &[(u128, u128)],
+    output: Point,
+    constant_points: bool,
+) -> (Circuit<FieldElement>, Vec<FieldElement>) {
+    let mut witness = WitnessBuilder::default();
+    let points = points
+        .iter()
+        .flat_map(|point| {
+            if constant_points {
+                constant_point(*point)
+            } else {
+                witness.point(*point)
+            }
+        })
+        .collect();
+    let scalars = scalars
+        .iter()
+        .flat_map(|(lo, hi)| {
+            [
+                witness.input(FieldElement::from(*lo), 128),
+                witness.input(FieldElement::from(*hi), 126),
+            ]
+        })
+        .collect();
+    let outputs = witness.outputs(output);
+    let circuit = witness.circuit(BlackBoxFuncCall::MultiScalarMul {
+        points,
+        scalars,
+        outputs,
+    });
+    (circuit, witness.values)
+}
+
+fn ec_add_circuit(
+    input1: Point,
+    input2: Point,
+    output: Point,
+) -> (Circuit<FieldElement>, Vec<FieldElement>) {
+    let mut witness = WitnessBuilder::default();
+    let input1 = witness.point(input1).try_into().unwrap();
+    let input2 = witness.point(input2).try_into().unwrap();
+    let outputs = witness.outputs(output);
+    let circuit = witness.circuit(BlackBoxFuncCall::EmbeddedCurveAdd {
+        input1: Box::new(input1),
+        input2: Box::new(input2),
+        outputs,
+    });
+    (circuit, witness.values)
+}
+
+fn test_circuit(circuit: &Circuit<FieldElement>, witness: &[FieldElement]) {
+    prove_and_verify::<Poseidon2Sponge>(circuit, witness);
+    prove_and_verify::<Keccak256>(circuit, witness);
+    assert_vk_independent_of_witness(circuit, witness);
+}
+
+#[test]
+fn msm_variable_base() {
+    let points = [generator(), add(generator(), generator())];
+    let scalars = [
+        ((0x1234_5678_9abc_def0_u128 << 64) | 42, 7),
+        (u128::MAX, 1 << 100),
+    ];
+    let output = msm(&points, &scalars);
+    let (circuit, witness) = msm_circuit(&points, &scalars, output, false);
+    test_circuit(&circuit, &witness);
+}
+
+#[test]
+fn msm_fixed_base() {
+    let points = [pedersen_generator(0), pedersen_generator(1)];
+    let scalars = [(123_456_789, 0), (u128::MAX - 5, (1 << 125) + 3)];
+    let output = msm(&points, &scalars);
+    let (circuit, witness) = msm_circuit(&points, &scalars, output, true);
+    test_circuit(&circuit, &witness);
+}
+
+#[test]
+fn msm_zero_scalar() {
+    let points = [generator()];
+    let scalars = [(0, 0)];
+    let output = msm(&points, &scalars);
+    assert_eq!(output[2], FieldElement::one());
+    let (circuit, witness) = msm_circuit(&points, &scalars, output, false);
+    test_circuit(&circuit, &witness);
+}
+
+#[test]
+fn ec_add() {
+    let p1 = generator();
+    let p2 = msm(&[generator()], &[(5, 0)]);
+    let (circuit, witness) = ec_add_circuit(p1, p2, add(p1, p2));
+    test_circuit(&circuit, &witness);
+}
+
+#[test]
+fn ec_add_doubling_and_inverse() {
+    let p = msm(&[generator()], &[(11, 0)]);
+    let (circuit, witness) = ec_add_circuit(p, p, add(p, p));
+    test_circuit(&circuit, &witness);
+
+    let neg_p = [p[0], -p[1], p[2]];
+    let output = add(p, neg_p);
+    assert_eq!(output[2], FieldElement::one());
+    let (circuit, witness) = ec_add_circuit(p, neg_p, output);
+    test_circuit(&circuit, &witness);
+}
+
+#[test]
+#[should_panic]
+fn msm_wrong_output() {
+    let points = [generator()];
+    let scalars = [(3, 0)];
+    let output = msm(&points, &[(4, 0)]);
+    let (circuit, witness) = msm_circuit(&points, &scalars, output, false);
+    prove_and_verify::<Poseidon2Sponge>(&circuit, &witness);
+}
@@ -0,0 +1,64 @@
+//! The stable public API of the coSNARKs.
+//!
+//! The functionality of the coSNARKs is split over several crates, e.g., `co-groth16`, `co-plonk`, `circom-types`, `co-circom-snarks`, and `mpc-core`, which change their APIs whenever the protocols need it. This crate is a facade over them: the [prelude] re-exports a curated set of the drivers, configs, prove and verify entry points, and share types, which follows semantic versioning of this crate. Items that are removed from or change their signature in the [prelude] require a new major (or, before 1.0, minor) release of `co-snarks`, even if the underlying crate only had a patch release.
+//!
+//! The underlying crates are available in [unstable] for everything that is not part of the [prelude]. They do not follow the versioning of this crate.
+//!
+//! The features `groth16` and `plonk` (both enabled by default) select the proof systems. The feature `witness-extension` adds the circom compiler and the MPC-VM, which makes the build subject to the GPL-3.0 license of the circom compiler.
+#![warn(missing_docs)]
+
+pub mod prelude;
+
+/// The crates behind the [prelude](crate::prelude). Their APIs are NOT covered by the versioning of this crate and may change in any release.
+pub mod unstable {
+    #[cfg(feature = "witness-extension")]
+    pub use circom_mpc_compiler;
+    #[cfg(feature = "witness-extension")]
+    pub use circom_mpc_vm;
+    pub use circom_types;
+    pub use co_circom_snarks;
+    #[cfg(feature = "groth16")]
+    pub use co_groth16;
+    #[cfg(feature = "plonk")]
+    pub use co_plonk;
+    pub use mpc_core;
+    pub use mpc_net;
+}
+
+#[cfg(test)]
+#[cfg(feature = "groth16")]
+mod tests {
+    use std::{fs::File, sync::Arc};
+
+    use crate::prelude::*;
+
+    #[test]
+    fn prove_and_verify_with_prelude_bn254() {
+        let zkey_file =
+            File::open("../test_vectors/Groth16/bn254/multiplier2/circuit.zkey").unwrap();
+        let witness_file =
+            File::open("../test_vectors/Groth16/bn254/multiplier2/witness.wtns").unwrap();
+        let vk_file =
+            File::open("../test_vectors/Groth16/bn254/multiplier2/verification_key.json").unwrap();
+
+        let witness = Witness::<ark_bn254::Fr>::from_reader(witness_file).unwrap();
+        let zkey =
+            Arc::new(Groth16ZKey::<Bn254>::from_reader(zkey_file, CheckElement::No).unwrap());
+        let vk: Groth16VerificationKey<Bn254> = serde_json::from_reader(vk_file).unwrap();
+
+        let [share1, share2, share3] =
+            rep3::share_field_elements(&witness.values, &mut rand::thread_rng());
+        assert_eq!(
+            rep3::combine_field_elements(&share1, &share2, &share3),
+            witness.values
+        );
+
+        let public_input = witness.values[..=zkey.n_public].to_vec();
+        let witness = SharedWitness {
+            public_inputs: public_input.clone(),
+            witness: witness.values[zkey.n_public + 1..].to_vec(),
+        };
+        let proof = Groth16::<Bn254>::plain_prove(zkey, witness).expect("proof generation works");
+        Groth16::<Bn254>::verify(&vk, &proof, &public_input[1..]).expect("can verify");
+    }
+}
@@ -0,0 +1,56 @@
+//! The curated, stable API of the coSNARKs, see the [crate documentation](crate).
+//!
+//! Import it with `use co_snarks::prelude::*;`. The sharing functions of the MPC protocols are grouped in the [rep3] and [shamir] modules, as they share their names.
+
+pub use ark_bls12_377::Bls12_377;
+pub use ark_bls12_381::Bls12_381;
+pub use ark_bn254::Bn254;
+
+pub use circom_types::{
+    traits::{CheckElement, CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge},
+    Witness, R1CS,
+};
+pub use co_circom_snarks::{SharedInput, SharedWitness, UsagePolicy, VerificationError};
+pub use mpc_net::config::{NetworkConfig, NetworkConfigFile, NetworkParty};
+
+#[cfg(feature = "groth16")]
+pub use circom_types::groth16::{
+    Groth16Proof, JsonPublicInput, JsonVerificationKey as Groth16VerificationKey,
+    ZKey as Groth16ZKey,
+};
+#[cfg(feature = "groth16")]
+pub use co_groth16::{
+    mpc::{CircomGroth16Prover, PlainGroth16Driver, Rep3Groth16Driver, ShamirGroth16Driver},
+    CoGroth16, Groth16, Rep3CoGroth16, ShamirCoGroth16, VerificationKeyCache,
+};
+
+#[cfg(feature = "plonk")]
+pub use circom_types::plonk::{
+    JsonVerificationKey as PlonkVerificationKey, PlonkProof, ZKey as PlonkZKey,
+};
+#[cfg(feature = "plonk")]
+pub use co_plonk::{
+    mpc::{CircomPlonkProver, PlainPlonkDriver, Rep3PlonkDriver, ShamirPlonkDriver},
+    CoPlonk, Plonk, PlonkProofError, Rep3CoPlonk, ShamirCoPlonk,
+};
+
+#[cfg(feature = "witness-extension")]
+pub use circom_mpc_compiler::{CoCircomCompiler, CompilerConfig, SimplificationLevel};
+#[cfg(feature = "witness-extension")]
+pub use circom_mpc_vm::mpc_vm::{
+    PlainWitnessExtension, Rep3WitnessExtension, VMConfig, WitnessExtension,
+};
+
+/// The replicated secret-sharing protocol for three parties.
+pub mod rep3 {
+    pub use mpc_core::protocols::rep3::{
+        combine_field_elements, network::Rep3MpcNet, share_field_elements, Rep3PrimeFieldShare,
+    };
+}
+
+/// The Shamir secret-sharing protocol.
+pub mod shamir {
+    pub use mpc_core::protocols::shamir::{
+        combine_field_elements, network::ShamirMpcNet, share_field_elements, ShamirPrimeFieldShare,
+    };
+}
@@ -0,0 +1,9 @@
+#![no_main]
+
+use ark_bn254::Bn254;
+use circom_types::{plonk::ZKey, traits::CheckElement};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = ZKey::<Bn254>::from_reader(data, CheckElement::Yes);
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use ark_bn254::Bn254;
+use circom_types::{
+    groth16::{Groth16Proof, JsonPublicInput},
+    plonk::PlonkProof,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = serde_json::from_slice::<Groth16Proof<Bn254>>(data);
+    let _ = serde_json::from_slice::<PlonkProof<Bn254>>(data);
+    let _ = serde_json::from_slice::<JsonPublicInput<ark_bn254::Fr>>(data);
+});
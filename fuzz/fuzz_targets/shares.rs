@@ -0,0 +1,15 @@
+#![no_main]
+
+use co_circom::file_utils::deserialize_untrusted;
+use co_circom_snarks::{SerializeableSharedRep3Input, SerializeableSharedRep3Witness};
+use libfuzzer_sys::fuzz_target;
+use rand_chacha::ChaCha12Rng;
+
+type F = ark_bn254::Fr;
+
+// the share files are read with the same limit the CLI uses, i.e., the size of the file
+fuzz_target!(|data: &[u8]| {
+    let limit = data.len() as u64;
+    let _ = deserialize_untrusted::<SerializeableSharedRep3Witness<F, ChaCha12Rng>, _>(data, limit);
+    let _ = deserialize_untrusted::<SerializeableSharedRep3Input<F, ChaCha12Rng>, _>(data, limit);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use circom_types::Witness;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = Witness::<ark_bn254::Fr>::from_reader(data);
+});
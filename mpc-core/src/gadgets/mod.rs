@@ -0,0 +1,5 @@
+//! Gadgets
+//!
+//! This module contains gadgets which are not specific to one MPC protocol, such as the parameters and the plain implementations of hash functions, which the protocol specific gadgets build on.
+
+pub mod poseidon2;
@@ -0,0 +1,240 @@
+//! Poseidon2
+//!
+//! This module contains the parameters and the plain implementation of the [Poseidon2](https://eprint.iacr.org/2023/323) permutation. The shared implementation for the Rep3 protocol is in [rep3::gadgets::poseidon2](crate::protocols::rep3::gadgets::poseidon2).
+
+pub mod poseidon2_bn254;
+
+use ark_ff::PrimeField;
+use num_bigint::BigUint;
+use num_traits::Num;
+
+pub use poseidon2_bn254::POSEIDON2_BN254_T4_PARAMS;
+
+fn field_from_hex_string<F: PrimeField>(str: &str) -> Result<F, num_bigint::ParseBigIntError> {
+    let value = BigUint::from_str_radix(str.strip_prefix("0x").unwrap_or(str), 16)?;
+    Ok(value.into())
+}
+
+/// The parameters of the Poseidon2 permutation over the field `F` with state size `T` and S-box degree `D`.
+#[derive(Clone, Debug)]
+pub struct Poseidon2Params<F: PrimeField, const T: usize, const D: u64> {
+    /// The number of full rounds before the partial rounds
+    pub rounds_f_beginning: usize,
+    /// The number of full rounds after the partial rounds
+    pub rounds_f_end: usize,
+    /// The number of partial rounds
+    pub rounds_p: usize,
+    /// The diagonal of the internal matrix, each element taken minus 1 for more efficient implementations
+    pub mat_internal_diag_m_1: [F; T],
+    /// The round constants of the full rounds
+    pub round_constants_external: Vec<[F; T]>,
+    /// The round constants of the partial rounds
+    pub round_constants_internal: Vec<F>,
+}
+
+impl<F: PrimeField, const T: usize, const D: u64> Poseidon2Params<F, T, D> {
+    /// Creates a new parameter set with `rounds_f` full rounds, split evenly before and after the `rounds_p` partial rounds. Panics if the state size `T`, the S-box degree `D`, or the amount of round constants are not supported.
+    pub fn new(
+        rounds_f: usize,
+        rounds_p: usize,
+        mat_internal_diag_m_1: &[F; T],
+        round_constants_external: &[[F; T]],
+        round_constants_internal: &[F],
+    ) -> Self {
+        assert!(T == 2 || T == 3 || ((T <= 24) && (T % 4 == 0)));
+        assert!(D % 2 == 1);
+        assert_eq!(rounds_f % 2, 0);
+        assert_eq!(round_constants_external.len(), rounds_f);
+        assert_eq!(round_constants_internal.len(), rounds_p);
+        let rounds_f_beginning = rounds_f / 2;
+        let rounds_f_end = rounds_f / 2;
+
+        Self {
+            rounds_f_beginning,
+            rounds_f_end,
+            rounds_p,
+            mat_internal_diag_m_1: mat_internal_diag_m_1.to_owned(),
+            round_constants_external: round_constants_external.to_vec(),
+            round_constants_internal: round_constants_internal.to_vec(),
+        }
+    }
+
+    /// Maps the parameters to the field `G`, which has to have the same modulus as `F`. Returns `None` if the moduli differ.
+    pub fn to_field<G: PrimeField>(&self) -> Option<Poseidon2Params<G, T, D>> {
+        let modulus: BigUint = F::MODULUS.into();
+        if modulus != G::MODULUS.into() {
+            return None;
+        }
+        let map = |x: &F| {
+            let x: BigUint = (*x).into();
+            G::from(x)
+        };
+        Some(Poseidon2Params {
+            rounds_f_beginning: self.rounds_f_beginning,
+            rounds_f_end: self.rounds_f_end,
+            rounds_p: self.rounds_p,
+            mat_internal_diag_m_1: self.mat_internal_diag_m_1.each_ref().map(map),
+            round_constants_external: self
+                .round_constants_external
+                .iter()
+                .map(|rc| rc.each_ref().map(map))
+                .collect(),
+            round_constants_internal: self.round_constants_internal.iter().map(map).collect(),
+        })
+    }
+
+    /// Computes the Poseidon2 permutation of the input.
+    pub fn permutation(&self, input: &[F; T]) -> [F; T] {
+        let mut state = *input;
+        self.permutation_in_place(&mut state);
+        state
+    }
+
+    /// Computes the Poseidon2 permutation of the state in place.
+    pub fn permutation_in_place(&self, state: &mut [F; T]) {
+        // Linear layer at beginning
+        Self::matmul_external(state);
+
+        // First set of external rounds
+        for r in 0..self.rounds_f_beginning {
+            self.add_rc_external(state, r);
+            Self::sbox(state);
+            Self::matmul_external(state);
+        }
+
+        // Internal rounds
+        for r in 0..self.rounds_p {
+            self.add_rc_internal(state, r);
+            Self::single_sbox(&mut state[0]);
+            self.matmul_internal(state);
+        }
+
+        // Remaining external rounds
+        for r in self.rounds_f_beginning..self.rounds_f_beginning + self.rounds_f_end {
+            self.add_rc_external(state, r);
+            Self::sbox(state);
+            Self::matmul_external(state);
+        }
+    }
+
+    fn sbox(input: &mut [F; T]) {
+        input.iter_mut().for_each(Self::single_sbox);
+    }
+
+    fn single_sbox(input: &mut F) {
+        match D {
+            3 => {
+                let input2 = input.square();
+                *input *= input2;
+            }
+            5 => {
+                let input2 = input.square();
+                let input4 = input2.square();
+                *input *= input4;
+            }
+            7 => {
+                let input2 = input.square();
+                let input4 = input2.square();
+                *input *= input4;
+                *input *= input2;
+            }
+            _ => {
+                *input = input.pow([D]);
+            }
+        }
+    }
+
+    /**
+     * hardcoded algorithm that evaluates matrix multiplication using the following MDS matrix:
+     * /         \
+     * | 5 7 1 3 |
+     * | 4 6 1 1 |
+     * | 1 3 5 7 |
+     * | 1 1 4 6 |
+     * \         /
+     *
+     * Algorithm is taken directly from the Poseidon2 paper.
+     */
+    fn matmul_m4(input: &mut [F; 4]) {
+        let t_0 = input[0] + input[1]; // A + B
+        let t_1 = input[2] + input[3]; // C + D
+        let t_2 = input[1].double() + t_1; // 2B + C + D
+        let t_3 = input[3].double() + t_0; // A + B + 2D
+        let t_4 = t_1.double().double() + t_3; // A + B + 4C + 6D
+        let t_5 = t_0.double().double() + t_2; // 4A + 6B + C + D
+        let t_6 = t_3 + t_5; // 5A + 7B + C + 3D
+        let t_7 = t_2 + t_4; // A + 3B + 5C + 7D
+        input[0] = t_6;
+        input[1] = t_5;
+        input[2] = t_7;
+        input[3] = t_4;
+    }
+
+    /// Multiplies the state with the matrix of the full rounds. Since the matrix is linear, this can be applied to each component of a share.
+    pub fn matmul_external(input: &mut [F; T]) {
+        match T {
+            2 => {
+                // Matrix circ(2, 1)
+                let sum = input[0] + input[1];
+                input[0] += &sum;
+                input[1] += sum;
+            }
+            3 => {
+                // Matrix circ(2, 1, 1)
+                let sum = input[0] + input[1] + input[2];
+                input[0] += &sum;
+                input[1] += &sum;
+                input[2] += sum;
+            }
+            4 => {
+                Self::matmul_m4(input.as_mut_slice().try_into().unwrap());
+            }
+            8 | 12 | 16 | 20 | 24 => {
+                // Applying cheap 4x4 MDS matrix to each 4-element part of the state
+                for state in input.chunks_exact_mut(4) {
+                    Self::matmul_m4(state.try_into().unwrap());
+                }
+
+                // Applying second cheap matrix for t > 4
+                let mut stored = [F::zero(); 4];
+                for l in 0..4 {
+                    stored[l] = input[l];
+                    for j in 1..T / 4 {
+                        stored[l] += input[4 * j + l];
+                    }
+                }
+                for i in 0..T {
+                    input[i] += stored[i % 4];
+                }
+            }
+            _ => {
+                panic!("Invalid Statesize");
+            }
+        }
+    }
+
+    /// Multiplies the state with the matrix of the partial rounds. Since the matrix is linear, this can be applied to each component of a share.
+    pub fn matmul_internal(&self, input: &mut [F; T]) {
+        // Compute input sum
+        let sum: F = input.iter().sum();
+        // Add sum + diag entry * element to each element
+
+        for (s, m) in input.iter_mut().zip(self.mat_internal_diag_m_1.iter()) {
+            *s *= m;
+            *s += sum;
+        }
+    }
+
+    fn add_rc_external(&self, input: &mut [F; T], rc_offset: usize) {
+        for (s, rc) in input
+            .iter_mut()
+            .zip(self.round_constants_external[rc_offset].iter())
+        {
+            *s += rc;
+        }
+    }
+
+    fn add_rc_internal(&self, input: &mut [F; T], rc_offset: usize) {
+        input[0] += &self.round_constants_internal[rc_offset];
+    }
+}
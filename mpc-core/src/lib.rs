@@ -4,8 +4,11 @@
 
 #![warn(missing_docs)]
 
+pub mod gadgets;
 pub mod lut;
+pub mod nonnative;
 pub mod protocols;
+pub mod snapshot;
 pub use protocols::serde_compat::{ark_de, ark_se};
 
 pub(crate) type RngType = rand_chacha::ChaCha12Rng;
@@ -0,0 +1,454 @@
+//! Non-native field arithmetic
+//!
+//! This module contains arithmetic on shared integers modulo an arbitrary public modulus (e.g., the base field of secp256k1 or an RSA modulus). The integers are emulated by limbs of [LIMB_BITS] bits, where each limb is a share of a native field element. Additions and multiplications are performed on the limbs and only the reduction modulo the public modulus requires opening masked values. Thus, the arithmetic only requires the operations from the [NonNativeArithmetic] trait and works for REP3 as well as for Shamir secret sharing.
+
+use std::{io, marker::PhantomData};
+
+use ark_ff::PrimeField;
+use num_bigint::BigUint;
+use rand::Rng;
+
+/// The number of bits per limb of a reduced [NonNativeShare].
+pub const LIMB_BITS: usize = 64;
+
+/// The statistical security parameter used for masking values before opening them.
+const STATISTICAL_SECURITY: usize = 40;
+
+/// The operations on shares of the native field required for emulating non-native field arithmetic.
+pub trait NonNativeArithmetic<F: PrimeField> {
+    /// A share of a native field element.
+    type Share: Clone;
+
+    /// Adds two shares.
+    fn add(&self, a: &Self::Share, b: &Self::Share) -> Self::Share;
+
+    /// Subtracts two shares.
+    fn sub(&self, a: &Self::Share, b: &Self::Share) -> Self::Share;
+
+    /// Adds a public value to a share.
+    fn add_public(&self, a: &Self::Share, b: F) -> Self::Share;
+
+    /// Multiplies a share with a public value.
+    fn mul_public(&self, a: &Self::Share, b: F) -> Self::Share;
+
+    /// Transforms a public value into a trivial share.
+    fn promote_to_trivial_share(&self, a: F) -> Self::Share;
+
+    /// Multiplies two vectors of shares elementwise.
+    fn mul_many(&mut self, a: &[Self::Share], b: &[Self::Share]) -> io::Result<Vec<Self::Share>>;
+
+    /// Opens a vector of shares.
+    fn open_many(&mut self, a: &[Self::Share]) -> io::Result<Vec<F>>;
+
+    /// Each party provides a vector of private values of the same length. Returns the shares of the elementwise sum of the values of all input parties (see [Self::num_input_parties]).
+    fn sum_of_inputs(&mut self, inputs: Vec<F>) -> io::Result<Vec<Self::Share>>;
+
+    /// Returns the number of parties whose values are considered in [Self::sum_of_inputs]. It is assumed that at least one of them is honest.
+    fn num_input_parties(&self) -> usize;
+}
+
+/// A shared integer, represented as limbs of shares of the native field. The value of the integer is the sum of limbs\[i\] * 2^(i * [LIMB_BITS]). The limbs are not necessarily normalized, i.e., each limb is only guaranteed to be smaller than 2^limb_bits.
+#[derive(Clone, Debug)]
+pub struct NonNativeShare<S> {
+    limbs: Vec<S>,
+    limb_bits: usize,
+}
+
+impl<S> NonNativeShare<S> {
+    /// Creates a shared integer from its limbs. The caller must ensure that each limb is smaller than 2^limb_bits.
+    pub fn from_limbs(limbs: Vec<S>, limb_bits: usize) -> Self {
+        Self { limbs, limb_bits }
+    }
+
+    /// Returns the limbs of the shared integer.
+    pub fn limbs(&self) -> &[S] {
+        &self.limbs
+    }
+
+    /// Returns the upper bound on the bit size of the limbs.
+    pub fn limb_bits(&self) -> usize {
+        self.limb_bits
+    }
+}
+
+/// Emulated arithmetic modulo a public modulus on [NonNativeShare]s. All results are congruent to the correct result modulo the modulus, but are only fully reduced when opened.
+#[derive(Clone, Debug)]
+pub struct NonNativeField<F: PrimeField> {
+    modulus: BigUint,
+    num_limbs: usize,
+    phantom: PhantomData<F>,
+}
+
+impl<F: PrimeField> NonNativeField<F> {
+    /// Creates a new instance for the given modulus.
+    ///
+    /// # Panics
+    /// Panics if the modulus is smaller than 2.
+    pub fn new(modulus: BigUint) -> Self {
+        assert!(modulus.bits() >= 2, "Modulus must be at least 2");
+        let num_limbs = (modulus.bits() as usize).div_ceil(LIMB_BITS);
+        Self {
+            modulus,
+            num_limbs,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the modulus.
+    pub fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    /// Returns the number of limbs of a reduced value.
+    pub fn num_limbs(&self) -> usize {
+        self.num_limbs
+    }
+
+    fn bit_len(x: usize) -> usize {
+        (usize::BITS - x.leading_zeros()) as usize
+    }
+
+    /// The maximum limb size such that masking and opening a limb does not overflow the native field.
+    fn max_limb_bits<P: NonNativeArithmetic<F>>(protocol: &P) -> usize {
+        F::MODULUS_BIT_SIZE as usize
+            - 2
+            - STATISTICAL_SECURITY
+            - Self::bit_len(protocol.num_input_parties() + 1)
+    }
+
+    /// The limb size of a value returned by [Self::reduce].
+    fn reduced_limb_bits<P: NonNativeArithmetic<F>>(protocol: &P) -> usize {
+        LIMB_BITS + Self::bit_len(protocol.num_input_parties())
+    }
+
+    fn random_bits<R: Rng>(rng: &mut R, bits: usize) -> BigUint {
+        let mut words = (0..bits.div_ceil(32))
+            .map(|_| rng.gen::<u32>())
+            .collect::<Vec<_>>();
+        if bits % 32 != 0 {
+            if let Some(last) = words.last_mut() {
+                *last &= (1 << (bits % 32)) - 1;
+            }
+        }
+        BigUint::new(words)
+    }
+
+    fn to_limbs(&self, value: &BigUint, num_limbs: usize) -> Vec<F> {
+        let mut limbs = value
+            .to_u64_digits()
+            .into_iter()
+            .map(F::from)
+            .collect::<Vec<_>>();
+        debug_assert!(limbs.len() <= num_limbs);
+        limbs.resize(num_limbs, F::zero());
+        limbs
+    }
+
+    fn compose(limbs: &[F]) -> BigUint {
+        limbs.iter().rev().fold(BigUint::default(), |acc, limb| {
+            (acc << LIMB_BITS) + Into::<BigUint>::into(*limb)
+        })
+    }
+
+    fn pad<P: NonNativeArithmetic<F>>(
+        protocol: &P,
+        a: &NonNativeShare<P::Share>,
+        len: usize,
+    ) -> Vec<P::Share> {
+        let mut limbs = a.limbs.to_owned();
+        limbs.resize(len, protocol.promote_to_trivial_share(F::zero()));
+        limbs
+    }
+
+    fn check_native_field_size<P: NonNativeArithmetic<F>>(&self, protocol: &P) -> io::Result<()> {
+        // A product of two reduced values has to fit into the limbs
+        let product_bits =
+            2 * Self::reduced_limb_bits(protocol) + Self::bit_len(self.num_limbs) + 1;
+        if product_bits > Self::max_limb_bits(protocol) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Native field too small for non-native field arithmetic",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Transforms a public value into a trivially shared value.
+    pub fn from_public<P: NonNativeArithmetic<F>>(
+        &self,
+        protocol: &P,
+        value: &BigUint,
+    ) -> NonNativeShare<P::Share> {
+        let limbs = self
+            .to_limbs(&(value % &self.modulus), self.num_limbs)
+            .into_iter()
+            .map(|limb| protocol.promote_to_trivial_share(limb))
+            .collect();
+        NonNativeShare::from_limbs(limbs, LIMB_BITS)
+    }
+
+    /// Returns random shared value, which is uniformly distributed modulo the modulus.
+    pub fn rand<P: NonNativeArithmetic<F>>(
+        &self,
+        protocol: &mut P,
+    ) -> io::Result<NonNativeShare<P::Share>> {
+        // Statistically close to uniform modulo the modulus
+        let value = Self::random_bits(
+            &mut rand::thread_rng(),
+            self.modulus.bits() as usize + STATISTICAL_SECURITY,
+        ) % &self.modulus;
+        let limbs = protocol.sum_of_inputs(self.to_limbs(&value, self.num_limbs))?;
+        Ok(NonNativeShare::from_limbs(
+            limbs,
+            Self::reduced_limb_bits(protocol),
+        ))
+    }
+
+    /// Adds two shared values.
+    pub fn add<P: NonNativeArithmetic<F>>(
+        &self,
+        protocol: &mut P,
+        a: &NonNativeShare<P::Share>,
+        b: &NonNativeShare<P::Share>,
+    ) -> io::Result<NonNativeShare<P::Share>> {
+        if a.limb_bits.max(b.limb_bits) + 1 > Self::max_limb_bits(protocol) {
+            let a = self.reduce(protocol, a)?;
+            let b = self.reduce(protocol, b)?;
+            return self.add(protocol, &a, &b);
+        }
+
+        let len = a.limbs.len().max(b.limbs.len());
+        let limbs = Self::pad(protocol, a, len)
+            .iter()
+            .zip(Self::pad(protocol, b, len).iter())
+            .map(|(a, b)| protocol.add(a, b))
+            .collect();
+        Ok(NonNativeShare::from_limbs(
+            limbs,
+            a.limb_bits.max(b.limb_bits) + 1,
+        ))
+    }
+
+    /// Subtracts two shared values.
+    pub fn sub<P: NonNativeArithmetic<F>>(
+        &self,
+        protocol: &mut P,
+        a: &NonNativeShare<P::Share>,
+        b: &NonNativeShare<P::Share>,
+    ) -> io::Result<NonNativeShare<P::Share>> {
+        let limb_bits = a.limb_bits.max(b.limb_bits + 1).max(LIMB_BITS) + 2;
+        if limb_bits > Self::max_limb_bits(protocol) {
+            let a = self.reduce(protocol, a)?;
+            let b = self.reduce(protocol, b)?;
+            return self.sub(protocol, &a, &b);
+        }
+
+        // We compute a + offset - b, where offset is a multiple of the modulus whose limbs are larger than the limbs of b. Thus, no limb becomes negative.
+        let len = a.limbs.len().max(b.limbs.len()).max(self.num_limbs);
+        let offset = (0..len).fold(BigUint::default(), |acc, i| {
+            acc + (BigUint::from(1u64) << (b.limb_bits + i * LIMB_BITS))
+        });
+        let correction = (&self.modulus - &offset % &self.modulus) % &self.modulus;
+        let correction = self.to_limbs(&correction, len);
+        let offset_limb = F::from(BigUint::from(1u64) << b.limb_bits);
+
+        let limbs = Self::pad(protocol, a, len)
+            .iter()
+            .zip(Self::pad(protocol, b, len).iter())
+            .zip(correction)
+            .map(|((a, b), correction)| {
+                let c = protocol.sub(a, b);
+                protocol.add_public(&c, offset_limb + correction)
+            })
+            .collect();
+        Ok(NonNativeShare::from_limbs(limbs, limb_bits))
+    }
+
+    /// Multiplies two shared values. The result is reduced.
+    pub fn mul<P: NonNativeArithmetic<F>>(
+        &self,
+        protocol: &mut P,
+        a: &NonNativeShare<P::Share>,
+        b: &NonNativeShare<P::Share>,
+    ) -> io::Result<NonNativeShare<P::Share>> {
+        self.check_native_field_size(protocol)?;
+        let product_bits =
+            a.limb_bits + b.limb_bits + Self::bit_len(a.limbs.len().min(b.limbs.len())) + 1;
+        if product_bits > Self::max_limb_bits(protocol) {
+            let a = self.reduce(protocol, a)?;
+            let b = self.reduce(protocol, b)?;
+            return self.mul(protocol, &a, &b);
+        }
+
+        // Schoolbook multiplication, where all limb products are computed at once
+        let (lhs, rhs): (Vec<_>, Vec<_>) = a
+            .limbs
+            .iter()
+            .flat_map(|a| b.limbs.iter().map(move |b| (a.to_owned(), b.to_owned())))
+            .unzip();
+        let products = protocol.mul_many(&lhs, &rhs)?;
+
+        let mut limbs =
+            vec![protocol.promote_to_trivial_share(F::zero()); a.limbs.len() + b.limbs.len() - 1];
+        for (i, row) in products.chunks(b.limbs.len()).enumerate() {
+            for (j, product) in row.iter().enumerate() {
+                limbs[i + j] = protocol.add(&limbs[i + j], product);
+            }
+        }
+
+        self.reduce(protocol, &NonNativeShare::from_limbs(limbs, product_bits))
+    }
+
+    /// Multiplies a shared value with a public value. The result is reduced.
+    pub fn mul_public<P: NonNativeArithmetic<F>>(
+        &self,
+        protocol: &mut P,
+        a: &NonNativeShare<P::Share>,
+        b: &BigUint,
+    ) -> io::Result<NonNativeShare<P::Share>> {
+        self.check_native_field_size(protocol)?;
+        let product_bits = a.limb_bits + LIMB_BITS + Self::bit_len(self.num_limbs) + 1;
+        if product_bits > Self::max_limb_bits(protocol) {
+            let a = self.reduce(protocol, a)?;
+            return self.mul_public(protocol, &a, b);
+        }
+
+        let b = self.to_limbs(&(b % &self.modulus), self.num_limbs);
+        let mut limbs =
+            vec![protocol.promote_to_trivial_share(F::zero()); a.limbs.len() + b.len() - 1];
+        for (i, a) in a.limbs.iter().enumerate() {
+            for (j, b) in b.iter().enumerate() {
+                let product = protocol.mul_public(a, *b);
+                limbs[i + j] = protocol.add(&limbs[i + j], &product);
+            }
+        }
+
+        self.reduce(protocol, &NonNativeShare::from_limbs(limbs, product_bits))
+    }
+
+    /// Reduces a shared value, such that it has [Self::num_limbs] limbs of small size. The result is congruent to the input modulo the modulus, but not necessarily smaller than the modulus.
+    ///
+    /// Each input party samples a random integer u_i and shares it together with t_i = u_i mod modulus. The value x + sum(u_i) is opened and reduced publicly, from which a congruent value is computed by subtracting sum(t_i).
+    pub fn reduce<P: NonNativeArithmetic<F>>(
+        &self,
+        protocol: &mut P,
+        a: &NonNativeShare<P::Share>,
+    ) -> io::Result<NonNativeShare<P::Share>> {
+        if a.limb_bits > Self::max_limb_bits(protocol) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Limbs too large to be reduced",
+            ));
+        }
+        let len = a.limbs.len();
+        let num_input_parties = protocol.num_input_parties();
+
+        // Sample the masks and their reduction
+        let mut rng = rand::thread_rng();
+        let mask_limbs = (0..len)
+            .map(|_| Self::random_bits(&mut rng, a.limb_bits + STATISTICAL_SECURITY))
+            .collect::<Vec<_>>();
+        let mask = mask_limbs
+            .iter()
+            .rev()
+            .fold(BigUint::default(), |acc, limb| (acc << LIMB_BITS) + limb);
+        let mut inputs = mask_limbs.into_iter().map(F::from).collect::<Vec<_>>();
+        inputs.extend(self.to_limbs(&(mask % &self.modulus), self.num_limbs));
+
+        let shares = protocol.sum_of_inputs(inputs)?;
+        let (mask, mask_reduced) = shares.split_at(len);
+
+        // Open the masked value
+        let masked = a
+            .limbs
+            .iter()
+            .zip(mask.iter())
+            .map(|(a, mask)| protocol.add(a, mask))
+            .collect::<Vec<_>>();
+        let masked = Self::compose(&protocol.open_many(&masked)?);
+
+        // The sum of the t_i has limbs smaller than num_input_parties * 2^LIMB_BITS, so we add a multiple of the modulus with larger limbs to prevent negative limbs
+        let offset_limb = BigUint::from(num_input_parties) << LIMB_BITS;
+        let offset = (0..self.num_limbs).fold(BigUint::default(), |acc, i| {
+            acc + (&offset_limb << (i * LIMB_BITS))
+        });
+        let public =
+            (&masked % &self.modulus + &self.modulus - &offset % &self.modulus) % &self.modulus;
+        let public = self.to_limbs(&public, self.num_limbs);
+        let offset_limb = F::from(offset_limb);
+
+        let limbs = mask_reduced
+            .iter()
+            .zip(public)
+            .map(|(t, public)| {
+                let neg = protocol.mul_public(t, -F::one());
+                protocol.add_public(&neg, public + offset_limb)
+            })
+            .collect();
+        Ok(NonNativeShare::from_limbs(
+            limbs,
+            Self::reduced_limb_bits(protocol),
+        ))
+    }
+
+    /// Computes the inverse of a shared value. The modulus is required to be prime. If the value is zero, the result is zero.
+    ///
+    /// The value is multiplied with a random value r and the product is opened, which only reveals whether the value is zero. The inverse is then r times the inverse of the opened product.
+    pub fn inv<P: NonNativeArithmetic<F>>(
+        &self,
+        protocol: &mut P,
+        a: &NonNativeShare<P::Share>,
+    ) -> io::Result<NonNativeShare<P::Share>> {
+        let r = self.rand(protocol)?;
+        let masked = self.mul(protocol, a, &r)?;
+        let masked = self.open(protocol, &masked)?;
+        // Fermat's little theorem, also maps zero to zero
+        let masked_inv = masked.modpow(&(&self.modulus - 2u64), &self.modulus);
+        self.mul_public(protocol, &r, &masked_inv)
+    }
+
+    /// Divides two shared values. The modulus is required to be prime. See [Self::inv].
+    pub fn div<P: NonNativeArithmetic<F>>(
+        &self,
+        protocol: &mut P,
+        a: &NonNativeShare<P::Share>,
+        b: &NonNativeShare<P::Share>,
+    ) -> io::Result<NonNativeShare<P::Share>> {
+        let b_inv = self.inv(protocol, b)?;
+        self.mul(protocol, a, &b_inv)
+    }
+
+    /// Opens a shared value and returns it fully reduced modulo the modulus.
+    ///
+    /// Before opening, a random multiple of the modulus is added, such that only the value modulo the modulus is revealed.
+    pub fn open<P: NonNativeArithmetic<F>>(
+        &self,
+        protocol: &mut P,
+        a: &NonNativeShare<P::Share>,
+    ) -> io::Result<BigUint> {
+        if a.limb_bits > Self::max_limb_bits(protocol) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Limbs too large to be opened",
+            ));
+        }
+        let value_bits = a.limb_bits + (a.limbs.len() - 1) * LIMB_BITS;
+        let factor_bits =
+            (value_bits + STATISTICAL_SECURITY).saturating_sub(self.modulus.bits() as usize - 1);
+        let mask = Self::random_bits(&mut rand::thread_rng(), factor_bits) * &self.modulus;
+        // All parties have to agree on the number of limbs, thus it only depends on public values
+        let len = a
+            .limbs
+            .len()
+            .max((factor_bits + self.modulus.bits() as usize).div_ceil(LIMB_BITS));
+        let mask = protocol.sum_of_inputs(self.to_limbs(&mask, len))?;
+
+        let masked = Self::pad(protocol, a, len)
+            .iter()
+            .zip(mask.iter())
+            .map(|(a, mask)| protocol.add(a, mask))
+            .collect::<Vec<_>>();
+        let masked = Self::compose(&protocol.open_many(&masked)?);
+        Ok(masked % &self.modulus)
+    }
+}
@@ -22,6 +22,7 @@ impl RepToShamirNetwork<ShamirMpcNet> for Rep3MpcNet {
             net_handler,
             chan_next,
             chan_prev,
+            transcript: _,
         } = self;
 
         let mut channels = HashMap::with_capacity(2);
@@ -3,6 +3,8 @@
 //! This module implements the rep3 share and combine operations
 
 pub mod arithmetic;
+#[cfg(feature = "async-net")]
+pub mod async_network;
 pub mod binary;
 pub mod conversion;
 mod detail;
@@ -10,8 +12,10 @@ pub mod gadgets;
 pub mod id;
 pub mod lut;
 pub mod network;
+pub mod nonnative;
 pub mod pointshare;
 pub mod poly;
+pub mod pvss;
 pub mod rngs;
 pub mod yao;
 
@@ -16,7 +16,8 @@ use crate::protocols::rep3::{detail, id::PartyID, network::Rep3Network};
 use rayon::prelude::*;
 
 use super::{
-    binary, conversion, network::IoContext, rngs::Rep3CorrelatedRng, IoResult, Rep3BigUintShare,
+    binary, conversion, network::IoContext, rngs::Rep3CorrelatedRng, yao, IoResult,
+    Rep3BigUintShare,
 };
 
 /// Type alias for a [`Rep3PrimeFieldShare`]
@@ -144,6 +145,30 @@ pub fn local_mul_vec<F: PrimeField>(
         .collect()
 }
 
+/// Performs element-wise multiplication of two vectors of shared values and subtracts the result of a previous [`local_mul_vec`] in place, i.e., `c = a * b - c`. *DOES NOT PERFORM RESHARE*
+///
+/// This avoids allocating an additional vector for the products.
+///
+/// # Security
+/// If you want to perform additional non-linear operations on the result of this function,
+/// you *MUST* call [`io_mul_vec`] first. Only then, a reshare is performed.
+pub fn local_mul_sub_vec<F: PrimeField>(
+    lhs: &[FieldShare<F>],
+    rhs: &[FieldShare<F>],
+    c: &mut [F],
+    rngs: &mut Rep3CorrelatedRng,
+) {
+    //squeeze all random elements at once in beginning for determinismus
+    let masking_fes = rngs.rand.masking_field_elements_vec::<F>(lhs.len());
+
+    c.par_iter_mut()
+        .zip_eq(lhs.par_iter())
+        .zip_eq(rhs.par_iter())
+        .zip_eq(masking_fes.par_iter())
+        .with_min_len(1024)
+        .for_each(|(((c, lhs), rhs), masking)| *c = lhs * rhs + masking - *c);
+}
+
 /// Performs a reshare on all shares in the vector.
 pub fn io_mul_vec<F: PrimeField, N: Rep3Network>(
     local_a: Vec<F>,
@@ -622,6 +647,64 @@ pub fn is_zero<F: PrimeField, N: Rep3Network>(
     Ok(x.is_one())
 }
 
+/// Checks whether a shared value is zero without opening the result. The result is a shared value that has value 1 if the shared value is zero and 0 otherwise.
+///
+/// In contrast to [eq], which decomposes the value into binary shares and evaluates a logarithmic-depth AND tree, this protocol evaluates the whole check in a garbled circuit and therefore needs a constant number of communication rounds.
+pub fn is_zero_shared<F: PrimeField, N: Rep3Network>(
+    a: FieldShare<F>,
+    io_context: &mut IoContext<N>,
+) -> IoResult<FieldShare<F>> {
+    yao::field_is_zero(a, io_context)
+}
+
+/// Batched version of [is_zero_shared]. Checks whether shared values are zero in a constant number of communication rounds, independent of the amount of values.
+pub fn is_zero_shared_many<F: PrimeField, N: Rep3Network>(
+    a: &[FieldShare<F>],
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<FieldShare<F>>> {
+    yao::field_is_zero_many(a, io_context)
+}
+
+/// Divides a shared value, interpreted as an integer, by a public divisor, rounding down. In contrast to [div_shared_by_public], which multiplies with the inverse of the divisor in the field, this computes the integer quotient.
+///
+/// The division is evaluated in a garbled circuit and therefore needs a constant number of communication rounds. Returns an error if the divisor is zero.
+pub fn int_div_public<F: PrimeField, N: Rep3Network>(
+    shared: FieldShare<F>,
+    public: F,
+    io_context: &mut IoContext<N>,
+) -> IoResult<FieldShare<F>> {
+    yao::field_int_div_public(shared, io_context, public)
+}
+
+/// Batched version of [int_div_public].
+pub fn int_div_public_many<F: PrimeField, N: Rep3Network>(
+    shared: &[FieldShare<F>],
+    public: F,
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<FieldShare<F>>> {
+    yao::field_int_div_public_many(shared, io_context, public)
+}
+
+/// Reduces a shared value, interpreted as an integer, modulo a public divisor.
+///
+/// The reduction is evaluated in a garbled circuit and therefore needs a constant number of communication rounds. Returns an error if the divisor is zero.
+pub fn int_mod_public<F: PrimeField, N: Rep3Network>(
+    shared: FieldShare<F>,
+    public: F,
+    io_context: &mut IoContext<N>,
+) -> IoResult<FieldShare<F>> {
+    yao::field_int_mod_public(shared, io_context, public)
+}
+
+/// Batched version of [int_mod_public].
+pub fn int_mod_public_many<F: PrimeField, N: Rep3Network>(
+    shared: &[FieldShare<F>],
+    public: F,
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<FieldShare<F>>> {
+    yao::field_int_mod_public_many(shared, io_context, public)
+}
+
 /// Computes `shared*2^public`. This is the same as `shared << public`.
 ///
 /// #Panics
@@ -643,43 +726,3 @@ pub fn pow_2_public<F: PrimeField>(shared: FieldShare<F>, public: F) -> FieldSha
         }
     }
 }
-
-/// computes XOR using arithmetic operations, only valid when x and y are known to be 0 or 1.
-pub(crate) fn arithmetic_xor<F: PrimeField, N: Rep3Network>(
-    x: Rep3PrimeFieldShare<F>,
-    y: Rep3PrimeFieldShare<F>,
-    io_context: &mut IoContext<N>,
-) -> IoResult<Rep3PrimeFieldShare<F>> {
-    let mut d = x * y + io_context.rngs.rand.masking_field_element::<F>();
-    d.double_in_place();
-    let e = x.a + y.a;
-    let res_a = e - d;
-
-    let res_b = io_context.network.reshare(res_a)?;
-    Ok(FieldShare { a: res_a, b: res_b })
-}
-
-pub(crate) fn arithmetic_xor_many<F: PrimeField, N: Rep3Network>(
-    x: &[Rep3PrimeFieldShare<F>],
-    y: &[Rep3PrimeFieldShare<F>],
-    io_context: &mut IoContext<N>,
-) -> IoResult<Vec<Rep3PrimeFieldShare<F>>> {
-    debug_assert_eq!(x.len(), y.len());
-
-    let mut a = Vec::with_capacity(x.len());
-    for (x, y) in x.iter().zip(y.iter()) {
-        let mut d = x * y + io_context.rngs.rand.masking_field_element::<F>();
-        d.double_in_place();
-        let e = x.a + y.a;
-        let res_a = e - d;
-        a.push(res_a);
-    }
-
-    let b = io_context.network.reshare_many(&a)?;
-    let res = a
-        .into_iter()
-        .zip(b)
-        .map(|(a, b)| FieldShare { a, b })
-        .collect();
-    Ok(res)
-}
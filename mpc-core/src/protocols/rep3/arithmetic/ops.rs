@@ -23,6 +23,28 @@ impl<F: PrimeField> std::ops::Add<&Rep3PrimeFieldShare<F>> for &'_ Rep3PrimeFiel
     }
 }
 
+impl<F: PrimeField> std::ops::Add<&Rep3PrimeFieldShare<F>> for Rep3PrimeFieldShare<F> {
+    type Output = Rep3PrimeFieldShare<F>;
+
+    fn add(self, rhs: &Rep3PrimeFieldShare<F>) -> Self::Output {
+        Rep3PrimeFieldShare::<F> {
+            a: self.a + rhs.a,
+            b: self.b + rhs.b,
+        }
+    }
+}
+
+impl<F: PrimeField> std::ops::Add<Rep3PrimeFieldShare<F>> for &'_ Rep3PrimeFieldShare<F> {
+    type Output = Rep3PrimeFieldShare<F>;
+
+    fn add(self, rhs: Rep3PrimeFieldShare<F>) -> Self::Output {
+        Rep3PrimeFieldShare::<F> {
+            a: self.a + rhs.a,
+            b: self.b + rhs.b,
+        }
+    }
+}
+
 impl<F: PrimeField> std::ops::AddAssign<Rep3PrimeFieldShare<F>> for Rep3PrimeFieldShare<F> {
     fn add_assign(&mut self, rhs: Self) {
         self.a += rhs.a;
@@ -59,6 +81,28 @@ impl<F: PrimeField> std::ops::Sub<&Rep3PrimeFieldShare<F>> for &'_ Rep3PrimeFiel
     }
 }
 
+impl<F: PrimeField> std::ops::Sub<&Rep3PrimeFieldShare<F>> for Rep3PrimeFieldShare<F> {
+    type Output = Rep3PrimeFieldShare<F>;
+
+    fn sub(self, rhs: &Rep3PrimeFieldShare<F>) -> Self::Output {
+        Rep3PrimeFieldShare::<F> {
+            a: self.a - rhs.a,
+            b: self.b - rhs.b,
+        }
+    }
+}
+
+impl<F: PrimeField> std::ops::Sub<Rep3PrimeFieldShare<F>> for &'_ Rep3PrimeFieldShare<F> {
+    type Output = Rep3PrimeFieldShare<F>;
+
+    fn sub(self, rhs: Rep3PrimeFieldShare<F>) -> Self::Output {
+        Rep3PrimeFieldShare::<F> {
+            a: self.a - rhs.a,
+            b: self.b - rhs.b,
+        }
+    }
+}
+
 impl<F: PrimeField> std::ops::SubAssign<Rep3PrimeFieldShare<F>> for Rep3PrimeFieldShare<F> {
     fn sub_assign(&mut self, rhs: Self) {
         self.a -= rhs.a;
@@ -66,6 +110,14 @@ impl<F: PrimeField> std::ops::SubAssign<Rep3PrimeFieldShare<F>> for Rep3PrimeFie
     }
 }
 
+impl<F: PrimeField> std::ops::SubAssign<&Rep3PrimeFieldShare<F>> for Rep3PrimeFieldShare<F> {
+    fn sub_assign(&mut self, rhs: &Rep3PrimeFieldShare<F>) {
+        self.a -= rhs.a;
+        self.b -= rhs.b;
+    }
+}
+
+/// Computes the local part of the multiplication of two shares only. The result is an additive (3-out-of-3) share of the product, not a replicated share. It has to be masked and reshared (see [local_mul_vec](super::local_mul_vec) and [mul](super::mul)) before it can be used in further computations or opened.
 impl<F: PrimeField> std::ops::Mul for Rep3PrimeFieldShare<F> {
     type Output = F;
 
@@ -97,6 +149,29 @@ impl<F: PrimeField> std::ops::Mul<F> for &Rep3PrimeFieldShare<F> {
     }
 }
 
+impl<F: PrimeField> std::ops::Mul<&F> for Rep3PrimeFieldShare<F> {
+    type Output = Rep3PrimeFieldShare<F>;
+
+    fn mul(self, rhs: &F) -> Self::Output {
+        Self::Output {
+            a: self.a * rhs,
+            b: self.b * rhs,
+        }
+    }
+}
+
+impl<F: PrimeField> std::ops::Mul<&F> for &Rep3PrimeFieldShare<F> {
+    type Output = Rep3PrimeFieldShare<F>;
+
+    fn mul(self, rhs: &F) -> Self::Output {
+        Self::Output {
+            a: self.a * rhs,
+            b: self.b * rhs,
+        }
+    }
+}
+
+/// Computes the local part of the multiplication of two shares only, see the [Mul](std::ops::Mul) implementation for owned shares.
 impl<F: PrimeField> std::ops::Mul<&Rep3PrimeFieldShare<F>> for &'_ Rep3PrimeFieldShare<F> {
     type Output = F;
 
@@ -106,6 +181,16 @@ impl<F: PrimeField> std::ops::Mul<&Rep3PrimeFieldShare<F>> for &'_ Rep3PrimeFiel
     }
 }
 
+/// Computes the local part of the multiplication of two shares only, see the [Mul](std::ops::Mul) implementation for owned shares.
+impl<F: PrimeField> std::ops::Mul<&Rep3PrimeFieldShare<F>> for Rep3PrimeFieldShare<F> {
+    type Output = F;
+
+    // Local part of mul only
+    fn mul(self, rhs: &Rep3PrimeFieldShare<F>) -> Self::Output {
+        self.a * rhs.a + self.a * rhs.b + self.b * rhs.a
+    }
+}
+
 impl<F: PrimeField> std::ops::MulAssign<F> for Rep3PrimeFieldShare<F> {
     fn mul_assign(&mut self, rhs: F) {
         self.a *= rhs;
@@ -113,6 +198,13 @@ impl<F: PrimeField> std::ops::MulAssign<F> for Rep3PrimeFieldShare<F> {
     }
 }
 
+impl<F: PrimeField> std::ops::MulAssign<&F> for Rep3PrimeFieldShare<F> {
+    fn mul_assign(&mut self, rhs: &F) {
+        self.a *= rhs;
+        self.b *= rhs;
+    }
+}
+
 impl<F: PrimeField> std::ops::Neg for Rep3PrimeFieldShare<F> {
     type Output = Rep3PrimeFieldShare<F>;
 
@@ -124,6 +216,29 @@ impl<F: PrimeField> std::ops::Neg for Rep3PrimeFieldShare<F> {
     }
 }
 
+impl<F: PrimeField> std::ops::Neg for &Rep3PrimeFieldShare<F> {
+    type Output = Rep3PrimeFieldShare<F>;
+
+    fn neg(self) -> Self::Output {
+        Rep3PrimeFieldShare::<F> {
+            a: -self.a,
+            b: -self.b,
+        }
+    }
+}
+
+impl<F: PrimeField> std::iter::Sum for Rep3PrimeFieldShare<F> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero_share(), |acc, x| acc + x)
+    }
+}
+
+impl<'a, F: PrimeField> std::iter::Sum<&'a Rep3PrimeFieldShare<F>> for Rep3PrimeFieldShare<F> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::zero_share(), |acc, x| acc + x)
+    }
+}
+
 impl<F: PrimeField> ark_ff::Zero for Rep3PrimeFieldShare<F> {
     fn zero() -> Self {
         Self {
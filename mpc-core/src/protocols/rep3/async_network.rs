@@ -0,0 +1,230 @@
+//! Rep3 Async Network
+//!
+//! This module contains an async implementation of the rep3 mpc network. In contrast to [Rep3MpcNet](super::network::Rep3MpcNet), which creates its own tokio runtime, it runs on the runtime of the caller. Thus, many concurrent sessions can be hosted on a single runtime. The [Rep3BlockingNet] adapter implements the synchronous [Rep3Network] trait on top of it, such that the MPC protocols can be executed unchanged, e.g., via [tokio::task::spawn_blocking].
+
+use std::sync::Arc;
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use bytes::{Bytes, BytesMut};
+use eyre::{bail, eyre, Report};
+use mpc_net::{channel::ChannelHandle, config::NetworkConfig, MpcNetworkHandler};
+use tokio::runtime::Handle;
+
+use super::{id::PartyID, network::Rep3Network};
+
+/// This struct can be used to facilitate async network communication for the REP3 MPC protocol.
+#[derive(Debug)]
+pub struct Rep3AsyncMpcNet {
+    id: PartyID,
+    chan_next: ChannelHandle<Bytes, BytesMut>,
+    chan_prev: ChannelHandle<Bytes, BytesMut>,
+    net_handler: Arc<MpcNetworkHandler>,
+}
+
+impl Rep3AsyncMpcNet {
+    /// Takes a [NetworkConfig] struct and constructs the network interface on the current tokio runtime. The network needs to contain exactly 3 parties with ids 0, 1, and 2.
+    pub async fn new(config: NetworkConfig) -> Result<Self, Report> {
+        if config.parties.len() != 3 {
+            bail!("REP3 protocol requires exactly 3 parties")
+        }
+        let id = PartyID::try_from(config.my_id)?;
+        let net_handler = Arc::new(MpcNetworkHandler::establish(config).await?);
+        let (chan_next, chan_prev) = Self::setup_channels(id, &net_handler).await?;
+        Ok(Self {
+            id,
+            chan_next,
+            chan_prev,
+            net_handler,
+        })
+    }
+
+    async fn setup_channels(
+        id: PartyID,
+        net_handler: &MpcNetworkHandler,
+    ) -> Result<
+        (
+            ChannelHandle<Bytes, BytesMut>,
+            ChannelHandle<Bytes, BytesMut>,
+        ),
+        Report,
+    > {
+        let mut channels = net_handler.get_byte_channels().await?;
+        let chan_next = channels
+            .remove(&id.next_id().into())
+            .ok_or(eyre!("no next channel found"))?;
+        let chan_prev = channels
+            .remove(&id.prev_id().into())
+            .ok_or(eyre!("no prev channel found"))?;
+        if !channels.is_empty() {
+            bail!("unexpected channels found")
+        }
+        Ok((
+            ChannelHandle::manage(chan_next),
+            ChannelHandle::manage(chan_prev),
+        ))
+    }
+
+    /// Returns the id of the party.
+    pub fn get_id(&self) -> PartyID {
+        self.id
+    }
+
+    /// Sends bytes over the network to the target party. Returns once the bytes are written to the connection, such that errors of the connection are returned to the caller.
+    pub async fn send_bytes(&mut self, target: PartyID, data: Bytes) -> std::io::Result<()> {
+        let result = if target == self.id.next_id() {
+            self.chan_next.send(data).await
+        } else if target == self.id.prev_id() {
+            self.chan_prev.send(data).await
+        } else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Cannot send to self",
+            ));
+        };
+        result.await.map_err(|_| {
+            std::io::Error::new(std::io::ErrorKind::BrokenPipe, "send channel end died")
+        })?
+    }
+
+    /// Receives bytes over the network from the party with the given id.
+    pub async fn recv_bytes(&mut self, from: PartyID) -> std::io::Result<BytesMut> {
+        let data = if from == self.id.prev_id() {
+            self.chan_prev.recv().await.await
+        } else if from == self.id.next_id() {
+            self.chan_next.recv().await.await
+        } else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Cannot recv from self",
+            ));
+        };
+        data.map_err(|_| {
+            std::io::Error::new(std::io::ErrorKind::BrokenPipe, "receive channel end died")
+        })?
+        .map_err(|err| self.net_handler.map_session_error(err))
+    }
+
+    /// Sends a vector of data to the target party.
+    pub async fn send_many<F: CanonicalSerialize>(
+        &mut self,
+        target: PartyID,
+        data: &[F],
+    ) -> std::io::Result<()> {
+        let size = data.serialized_size(ark_serialize::Compress::No);
+        let mut ser_data = Vec::with_capacity(size);
+        data.serialize_uncompressed(&mut ser_data)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        self.send_bytes(target, Bytes::from(ser_data)).await
+    }
+
+    /// Receives a vector of data from the party with the given id.
+    pub async fn recv_many<F: CanonicalDeserialize>(
+        &mut self,
+        from: PartyID,
+    ) -> std::io::Result<Vec<F>> {
+        let data = self.recv_bytes(from).await?;
+        Vec::<F>::deserialize_uncompressed(&data[..])
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    /// Sends `data` to the next party and receives from the previous party.
+    pub async fn reshare_many<F: CanonicalSerialize + CanonicalDeserialize>(
+        &mut self,
+        data: &[F],
+    ) -> std::io::Result<Vec<F>> {
+        self.send_many(self.id.next_id(), data).await?;
+        self.recv_many(self.id.prev_id()).await
+    }
+
+    /// Broadcast data to the other two parties and receive data from them
+    pub async fn broadcast_many<F: CanonicalSerialize + CanonicalDeserialize>(
+        &mut self,
+        data: &[F],
+    ) -> std::io::Result<(Vec<F>, Vec<F>)> {
+        self.send_many(self.id.next_id(), data).await?;
+        self.send_many(self.id.prev_id(), data).await?;
+        let recv_next = self.recv_many(self.id.next_id()).await?;
+        let recv_prev = self.recv_many(self.id.prev_id()).await?;
+        Ok((recv_prev, recv_next))
+    }
+
+    /// Fork the network into two separate instances with their own channels over the same connections
+    pub async fn fork(&mut self) -> std::io::Result<Self> {
+        let (chan_next, chan_prev) = Self::setup_channels(self.id, &self.net_handler)
+            .await
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        Ok(Self {
+            id: self.id,
+            chan_next,
+            chan_prev,
+            net_handler: Arc::clone(&self.net_handler),
+        })
+    }
+
+    /// Gracefully shuts down the connections to the other parties. All forks of this network become unusable.
+    pub async fn shutdown(self) -> std::io::Result<()> {
+        self.net_handler.shutdown().await
+    }
+
+    /// Wraps the network into a [Rep3BlockingNet], which implements the synchronous [Rep3Network] trait. The handle needs to belong to the runtime the network was created on.
+    pub fn into_blocking(self, handle: Handle) -> Rep3BlockingNet {
+        Rep3BlockingNet {
+            inner: self,
+            handle,
+        }
+    }
+}
+
+/// An adapter implementing the synchronous [Rep3Network] trait for a [Rep3AsyncMpcNet]. Its methods block the current thread, so it must not be used from within an async context. Use, e.g., [tokio::task::spawn_blocking] instead.
+#[derive(Debug)]
+pub struct Rep3BlockingNet {
+    inner: Rep3AsyncMpcNet,
+    handle: Handle,
+}
+
+impl Rep3BlockingNet {
+    /// Returns the wrapped [Rep3AsyncMpcNet].
+    pub fn into_inner(self) -> Rep3AsyncMpcNet {
+        self.inner
+    }
+}
+
+impl Rep3Network for Rep3BlockingNet {
+    fn get_id(&self) -> PartyID {
+        self.inner.id
+    }
+
+    fn reshare_many<F: CanonicalSerialize + CanonicalDeserialize>(
+        &mut self,
+        data: &[F],
+    ) -> std::io::Result<Vec<F>> {
+        self.handle.block_on(self.inner.reshare_many(data))
+    }
+
+    fn broadcast_many<F: CanonicalSerialize + CanonicalDeserialize>(
+        &mut self,
+        data: &[F],
+    ) -> std::io::Result<(Vec<F>, Vec<F>)> {
+        self.handle.block_on(self.inner.broadcast_many(data))
+    }
+
+    fn send_many<F: CanonicalSerialize>(
+        &mut self,
+        target: PartyID,
+        data: &[F],
+    ) -> std::io::Result<()> {
+        self.handle.block_on(self.inner.send_many(target, data))
+    }
+
+    fn recv_many<F: CanonicalDeserialize>(&mut self, from: PartyID) -> std::io::Result<Vec<F>> {
+        self.handle.block_on(self.inner.recv_many(from))
+    }
+
+    fn fork(&mut self) -> std::io::Result<Self> {
+        let inner = self.handle.block_on(self.inner.fork())?;
+        Ok(Self {
+            inner,
+            handle: self.handle.clone(),
+        })
+    }
+}
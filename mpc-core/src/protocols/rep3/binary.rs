@@ -17,6 +17,7 @@ use crate::protocols::rep3::{
 use super::{network::IoContext, Rep3PrimeFieldShare};
 use num_traits::cast::ToPrimitive;
 
+pub mod batched;
 mod ops;
 pub(super) mod types;
 
@@ -0,0 +1,193 @@
+//! Batched binary operations
+//!
+//! This module contains bitwise operations on slices of binary shares, e.g., for implementing hash functions over shared words. The operations with public values are local and work in place on both components of the shares, so they do not allocate new shares. The operations between shared values need one communication round for the whole batch.
+//!
+//! Shifts to the left and rotations work on words of a fixed bit width `bits`. Both components of the shares are truncated to `bits` before the operation, which is a valid local operation since truncation is an AND with a public mask.
+
+use ark_ff::PrimeField;
+use itertools::izip;
+use num_bigint::BigUint;
+use num_traits::One;
+
+use crate::protocols::rep3::{
+    id::PartyID,
+    network::{IoContext, Rep3Network},
+};
+
+use super::{BinaryShare, IoResult};
+
+/// Returns the public mask with the lowest `bits` bits set.
+pub fn word_mask(bits: usize) -> BigUint {
+    (BigUint::one() << bits) - BigUint::one()
+}
+
+fn check_lens(lhs: usize, rhs: usize) -> IoResult<()> {
+    if lhs != rhs {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("batched binary operation on slices of different lengths {lhs} and {rhs}"),
+        ));
+    }
+    Ok(())
+}
+
+/// Computes the bitwise AND of every share with the same public `mask` in place.
+pub fn and_with_public<F: PrimeField>(shares: &mut [BinaryShare<F>], mask: &BigUint) {
+    for share in shares.iter_mut() {
+        share.a &= mask;
+        share.b &= mask;
+    }
+}
+
+/// Computes the bitwise AND of every share with the public value at the same index in place.
+///
+/// # Panics
+/// Panics if the slices have different lengths.
+pub fn and_with_public_elementwise<F: PrimeField>(
+    shares: &mut [BinaryShare<F>],
+    public: &[BigUint],
+) {
+    assert_eq!(shares.len(), public.len());
+    for (share, public) in izip!(shares.iter_mut(), public) {
+        share.a &= public;
+        share.b &= public;
+    }
+}
+
+/// Computes the bitwise XOR of every share with the same public value in place.
+pub fn xor_public<F: PrimeField>(shares: &mut [BinaryShare<F>], public: &BigUint, id: PartyID) {
+    match id {
+        PartyID::ID0 => shares.iter_mut().for_each(|share| share.a ^= public),
+        PartyID::ID1 => shares.iter_mut().for_each(|share| share.b ^= public),
+        PartyID::ID2 => {}
+    }
+}
+
+/// Computes the bitwise XOR of every share with the public value at the same index in place.
+///
+/// # Panics
+/// Panics if the slices have different lengths.
+pub fn xor_public_elementwise<F: PrimeField>(
+    shares: &mut [BinaryShare<F>],
+    public: &[BigUint],
+    id: PartyID,
+) {
+    assert_eq!(shares.len(), public.len());
+    for (share, public) in izip!(shares.iter_mut(), public) {
+        match id {
+            PartyID::ID0 => share.a ^= public,
+            PartyID::ID1 => share.b ^= public,
+            PartyID::ID2 => {}
+        }
+    }
+}
+
+/// Computes the bitwise OR of every share with the same public value in place, using `x | p = x ^ p ^ (x & p)`.
+pub fn or_public<F: PrimeField>(shares: &mut [BinaryShare<F>], public: &BigUint, id: PartyID) {
+    for share in shares.iter_mut() {
+        let and = &*share & public;
+        *share ^= and;
+    }
+    xor_public(shares, public, id);
+}
+
+/// Computes the bitwise XOR of the shares at the same index in place, i.e., `lhs[i] ^= rhs[i]`.
+///
+/// # Panics
+/// Panics if the slices have different lengths.
+pub fn xor_assign<F: PrimeField>(lhs: &mut [BinaryShare<F>], rhs: &[BinaryShare<F>]) {
+    assert_eq!(lhs.len(), rhs.len());
+    for (lhs, rhs) in izip!(lhs.iter_mut(), rhs) {
+        *lhs ^= rhs;
+    }
+}
+
+/// Computes the bitwise AND of the shares at the same index with one communication round for the whole batch.
+pub fn and<F: PrimeField, N: Rep3Network>(
+    lhs: &[BinaryShare<F>],
+    rhs: &[BinaryShare<F>],
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<BinaryShare<F>>> {
+    check_lens(lhs.len(), rhs.len())?;
+    let bitlen = usize::try_from(F::MODULUS_BIT_SIZE).expect("u32 fits into usize");
+    let local_a = izip!(lhs, rhs)
+        .map(|(lhs, rhs)| {
+            debug_assert!(lhs.a.bits() <= bitlen as u64);
+            debug_assert!(rhs.a.bits() <= bitlen as u64);
+            let (mut mask, mask_b) = io_context.rngs.rand.random_biguint(bitlen);
+            mask ^= mask_b;
+            (lhs & rhs) ^ mask
+        })
+        .collect::<Vec<_>>();
+    let local_b = io_context.network.reshare_many(&local_a)?;
+    check_lens(local_a.len(), local_b.len())?;
+    Ok(izip!(local_a, local_b)
+        .map(|(a, b)| BinaryShare::new(a, b))
+        .collect())
+}
+
+/// Opens all shares with one communication round.
+pub fn open<F: PrimeField, N: Rep3Network>(
+    shares: &[BinaryShare<F>],
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<BigUint>> {
+    let bs = shares
+        .iter()
+        .map(|share| share.b.clone())
+        .collect::<Vec<_>>();
+    let cs = io_context.network.reshare_many(&bs)?;
+    check_lens(shares.len(), cs.len())?;
+    Ok(izip!(shares, cs)
+        .map(|(share, c)| &share.a ^ &share.b ^ c)
+        .collect())
+}
+
+/// Shifts every share to the right by `shift` bits in place.
+pub fn shift_r<F: PrimeField>(shares: &mut [BinaryShare<F>], shift: usize) {
+    for share in shares.iter_mut() {
+        share.a >>= shift;
+        share.b >>= shift;
+    }
+}
+
+/// Shifts every share, interpreted as a word of `bits` bits, to the left by `shift` bits in place. The bits shifted out of the word are dropped.
+pub fn shift_l<F: PrimeField>(shares: &mut [BinaryShare<F>], shift: usize, bits: usize) {
+    let mask = word_mask(bits);
+    for share in shares.iter_mut() {
+        share.a <<= shift;
+        share.a &= &mask;
+        share.b <<= shift;
+        share.b &= &mask;
+    }
+}
+
+fn rotate_l_component(x: &mut BigUint, rot: usize, bits: usize, mask: &BigUint) {
+    *x &= mask;
+    let high = &*x >> (bits - rot);
+    *x <<= rot;
+    *x &= mask;
+    *x |= high;
+}
+
+/// Rotates every share, interpreted as a word of `bits` bits, to the left by `rot` bits in place.
+///
+/// # Panics
+/// Panics if `bits` is zero.
+pub fn rotate_l<F: PrimeField>(shares: &mut [BinaryShare<F>], rot: usize, bits: usize) {
+    assert!(bits > 0, "can not rotate words of 0 bits");
+    let rot = rot % bits;
+    let mask = word_mask(bits);
+    for share in shares.iter_mut() {
+        rotate_l_component(&mut share.a, rot, bits, &mask);
+        rotate_l_component(&mut share.b, rot, bits, &mask);
+    }
+}
+
+/// Rotates every share, interpreted as a word of `bits` bits, to the right by `rot` bits in place.
+///
+/// # Panics
+/// Panics if `bits` is zero.
+pub fn rotate_r<F: PrimeField>(shares: &mut [BinaryShare<F>], rot: usize, bits: usize) {
+    assert!(bits > 0, "can not rotate words of 0 bits");
+    rotate_l(shares, bits - rot % bits, bits);
+}
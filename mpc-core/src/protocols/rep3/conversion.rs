@@ -3,7 +3,7 @@
 //! This module contains conversions between share types
 
 use super::{
-    arithmetic, detail,
+    detail,
     id::PartyID,
     network::{IoContext, Rep3Network},
     yao::{
@@ -13,7 +13,7 @@ use super::{
     },
     IoResult, Rep3BigUintShare, Rep3PrimeFieldShare,
 };
-use ark_ff::PrimeField;
+use ark_ff::{One, PrimeField};
 use fancy_garbling::{BinaryBundle, WireMod2};
 use itertools::izip;
 use num_bigint::BigUint;
@@ -162,74 +162,108 @@ pub fn b2a<F: PrimeField, N: Rep3Network>(
     Ok(res)
 }
 
-/// Translates one shared bits into an arithmetic sharing of the same bit. I.e., the shared bit x = x_1 xor x_2 xor x_3 gets transformed into x = x'_1 + x'_2 + x'_3, with x being either 0 or 1.
+/// Translates one shared bits into an arithmetic sharing of the same bit. I.e., the shared bit x = x_1 xor x_2 xor x_3 gets transformed into x = x'_1 + x'_2 + x'_3, with x being either 0 or 1. See [bit_inject_many] for details.
 pub fn bit_inject<F: PrimeField, N: Rep3Network>(
     x: &Rep3BigUintShare<F>,
     io_context: &mut IoContext<N>,
 ) -> IoResult<Rep3PrimeFieldShare<F>> {
-    // standard bit inject
-    assert!(x.a.bits() <= 1);
-
-    let mut b0 = Rep3PrimeFieldShare::<F>::default();
-    let mut b1 = Rep3PrimeFieldShare::<F>::default();
-    let mut b2 = Rep3PrimeFieldShare::<F>::default();
-
-    match io_context.id {
-        PartyID::ID0 => {
-            b0.a = x.a.to_owned().into();
-            b2.b = x.b.to_owned().into();
-        }
-        PartyID::ID1 => {
-            b1.a = x.a.to_owned().into();
-            b0.b = x.b.to_owned().into();
-        }
-        PartyID::ID2 => {
-            b2.a = x.a.to_owned().into();
-            b1.b = x.b.to_owned().into();
-        }
-    };
-
-    let d = arithmetic::arithmetic_xor(b0, b1, io_context)?;
-    let e = arithmetic::arithmetic_xor(d, b2, io_context)?;
-    Ok(e)
+    let mut res = bit_inject_many(std::slice::from_ref(x), io_context)?;
+    Ok(res.pop().expect("we injected one bit"))
 }
 
-/// Translates a vector of shared bit into a vector of arithmetic sharings of the same bits. See [bit_inject] for details.
+/// Translates a vector of shared bits into a vector of arithmetic sharings of the same bits.
+///
+/// This is the bit injection of [ABY3](https://eprint.iacr.org/2018/403.pdf) based on three-party OT, which needs a single communication round instead of two multiplications for the arithmetic XOR of the three components x_0, x_1, x_2. Party 0 knows w = x_0 xor x_2 and acts as the sender, parties 1 and 2 both know the choice bit x_1 and act as receivers and helpers for each other. For both choices c, party 0 sends m_c = (w xor c) - r_0 - r_2 masked with randomness shared with the other receiver, which sends the mask of the chosen message. Here r_0 is the randomness shared between parties 0 and 1, and r_2 the randomness shared between parties 2 and 0, so the resulting components are (r_0, m_{x_1}, r_2).
 pub fn bit_inject_many<F: PrimeField, N: Rep3Network>(
     x: &[Rep3BigUintShare<F>],
     io_context: &mut IoContext<N>,
 ) -> IoResult<Vec<Rep3PrimeFieldShare<F>>> {
-    // standard bit inject
-    assert!(x.iter().all(|a| a.a.bits() <= 1));
-
-    let mut b0 = vec![Rep3PrimeFieldShare::<F>::default(); x.len()];
-    let mut b1 = vec![Rep3PrimeFieldShare::<F>::default(); x.len()];
-    let mut b2 = vec![Rep3PrimeFieldShare::<F>::default(); x.len()];
+    assert!(x.iter().all(|a| a.a.bits() <= 1 && a.b.bits() <= 1));
 
+    let rand = &mut io_context.rngs.rand;
     match io_context.id {
         PartyID::ID0 => {
-            for (b0, b2, x) in izip!(&mut b0, &mut b2, x.iter().cloned()) {
-                b0.a = x.a.into();
-                b2.b = x.b.into();
+            let mut to_next = Vec::with_capacity(2 * x.len());
+            let mut to_prev = Vec::with_capacity(2 * x.len());
+            let mut res = Vec::with_capacity(x.len());
+            for x in x {
+                let r0 = rand.random_field_element_rng1::<F>();
+                let r2 = rand.random_field_element_rng2::<F>();
+                let w: F = (&x.a ^ &x.b).into();
+                let m0 = w - r0 - r2;
+                let m1 = F::one() - w - r0 - r2;
+                // party 1 gets the mask from party 2 and vice versa
+                to_prev.push(m0 + rand.random_field_element_rng1::<F>());
+                to_prev.push(m1 + rand.random_field_element_rng1::<F>());
+                to_next.push(m0 + rand.random_field_element_rng2::<F>());
+                to_next.push(m1 + rand.random_field_element_rng2::<F>());
+                res.push(Rep3PrimeFieldShare { a: r0, b: r2 });
             }
+            io_context.network.send_next_many(&to_next)?;
+            io_context
+                .network
+                .send_many(io_context.id.prev_id(), &to_prev)?;
+            Ok(res)
         }
         PartyID::ID1 => {
-            for (b1, b0, x) in izip!(&mut b1, &mut b0, x.iter().cloned()) {
-                b1.a = x.a.into();
-                b0.b = x.b.into();
+            let mut randomness = Vec::with_capacity(x.len());
+            let mut to_next = Vec::with_capacity(x.len());
+            for x in x {
+                let r0 = rand.random_field_element_rng2::<F>();
+                let masks = [
+                    rand.random_field_element_rng2::<F>(),
+                    rand.random_field_element_rng2::<F>(),
+                ];
+                to_next.push(masks[usize::from(x.a.bit(0))]);
+                randomness.push(r0);
             }
+            io_context.network.send_next_many(&to_next)?;
+            let masked = io_context.network.recv_prev_many::<F>()?;
+            let masks = io_context.network.recv_many::<F>(io_context.id.next_id())?;
+            check_bit_inject_len(x.len(), &masked, &masks)?;
+            Ok(izip!(x, randomness, masked.chunks_exact(2), masks)
+                .map(|(x, r0, masked, mask)| Rep3PrimeFieldShare {
+                    a: masked[usize::from(x.a.bit(0))] - mask,
+                    b: r0,
+                })
+                .collect())
         }
         PartyID::ID2 => {
-            for (b2, b1, x) in izip!(&mut b2, &mut b1, x.iter().cloned()) {
-                b2.a = x.a.into();
-                b1.b = x.b.into();
+            let mut randomness = Vec::with_capacity(x.len());
+            let mut to_prev = Vec::with_capacity(x.len());
+            for x in x {
+                let r2 = rand.random_field_element_rng1::<F>();
+                let masks = [
+                    rand.random_field_element_rng1::<F>(),
+                    rand.random_field_element_rng1::<F>(),
+                ];
+                to_prev.push(masks[usize::from(x.b.bit(0))]);
+                randomness.push(r2);
             }
+            io_context
+                .network
+                .send_many(io_context.id.prev_id(), &to_prev)?;
+            let masked = io_context.network.recv_many::<F>(io_context.id.next_id())?;
+            let masks = io_context.network.recv_prev_many::<F>()?;
+            check_bit_inject_len(x.len(), &masked, &masks)?;
+            Ok(izip!(x, randomness, masked.chunks_exact(2), masks)
+                .map(|(x, r2, masked, mask)| Rep3PrimeFieldShare {
+                    a: r2,
+                    b: masked[usize::from(x.b.bit(0))] - mask,
+                })
+                .collect())
         }
-    };
+    }
+}
 
-    let d = arithmetic::arithmetic_xor_many(&b0, &b1, io_context)?;
-    let e = arithmetic::arithmetic_xor_many(&d, &b2, io_context)?;
-    Ok(e)
+fn check_bit_inject_len<F>(len: usize, masked: &[F], masks: &[F]) -> IoResult<()> {
+    if masked.len() != 2 * len || masks.len() != len {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "bit_inject: received unexpected number of elements",
+        ));
+    }
+    Ok(())
 }
 
 /// Transforms the replicated shared value x from an arithmetic sharing to a yao sharing. I.e., x = x_1 + x_2 + x_3 gets transformed into wires, such that the garbler have keys (k_0, delta) for each bit of x, while the evaluator has k_x = k_0 xor delta * x.
@@ -2,4 +2,5 @@
 //!
 //! This module contains some commonly used gadgets for the Rep3 protocol.
 
+pub mod poseidon2;
 pub mod sort;
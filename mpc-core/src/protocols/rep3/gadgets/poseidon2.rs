@@ -0,0 +1,141 @@
+//! Poseidon2
+//!
+//! This module contains the [Poseidon2](crate::gadgets::poseidon2) permutation over shared states for the Rep3 protocol. The linear layers and the addition of the round constants are local operations, thus only the S-boxes require communication. The S-boxes of all states of a batch are computed together, such that the number of communication rounds does not depend on the number of states.
+
+use crate::gadgets::poseidon2::Poseidon2Params;
+use crate::protocols::rep3::{
+    arithmetic::{self, FieldShare},
+    network::{IoContext, Rep3Network},
+    IoResult,
+};
+use ark_ff::PrimeField;
+
+/// Computes the S-box x^D of all inputs in place with a square-and-multiply over the bits of `D`. The multiplications of each bit are batched, so this requires one round of communication per bit of `D`.
+fn sbox_many<F: PrimeField, N: Rep3Network, const D: u64>(
+    inputs: &mut [FieldShare<F>],
+    io_context: &mut IoContext<N>,
+) -> IoResult<()> {
+    let mut result: Option<Vec<FieldShare<F>>> = None;
+    let mut base = inputs.to_vec();
+    let mut exp = D;
+    loop {
+        let bit_set = exp & 1 == 1;
+        exp >>= 1;
+        let square = exp != 0;
+
+        let mut lhs = Vec::with_capacity(2 * base.len());
+        let mut rhs = Vec::with_capacity(2 * base.len());
+        let multiply = match (&result, bit_set) {
+            (Some(result), true) => {
+                lhs.extend_from_slice(result);
+                rhs.extend_from_slice(&base);
+                true
+            }
+            _ => false,
+        };
+        if square {
+            lhs.extend_from_slice(&base);
+            rhs.extend_from_slice(&base);
+        }
+        let mut products = if lhs.is_empty() {
+            Vec::new()
+        } else {
+            arithmetic::mul_vec(&lhs, &rhs, io_context)?
+        };
+        let squares = products.split_off(if multiply { base.len() } else { 0 });
+
+        if multiply {
+            result = Some(products);
+        } else if bit_set {
+            result = Some(base);
+        }
+        if !square {
+            break;
+        }
+        base = squares;
+    }
+    inputs.copy_from_slice(&result.expect("the S-box degree is odd"));
+    Ok(())
+}
+
+/// Applies the public linear map `f` to the shared state, by applying it to both components of the shares.
+fn linear_layer<F: PrimeField, const T: usize>(
+    state: &mut [FieldShare<F>; T],
+    f: impl Fn(&mut [F; T]),
+) {
+    let mut a = state.map(|share| share.a);
+    let mut b = state.map(|share| share.b);
+    f(&mut a);
+    f(&mut b);
+    for (share, a, b) in itertools::izip!(state.iter_mut(), a, b) {
+        *share = FieldShare::new(a, b);
+    }
+}
+
+/// Computes the Poseidon2 permutations of many shared states in place, see the [module documentation](self).
+pub fn poseidon2_permutation_many<F: PrimeField, N: Rep3Network, const T: usize, const D: u64>(
+    params: &Poseidon2Params<F, T, D>,
+    states: &mut [[FieldShare<F>; T]],
+    io_context: &mut IoContext<N>,
+) -> IoResult<()> {
+    let id = io_context.id;
+    let external_round = |states: &mut [[FieldShare<F>; T]],
+                          round_constants: &[F; T],
+                          io_context: &mut IoContext<N>|
+     -> IoResult<()> {
+        for state in states.iter_mut() {
+            for (share, rc) in state.iter_mut().zip(round_constants) {
+                *share = arithmetic::add_public(*share, *rc, id);
+            }
+        }
+        let mut sbox = states.iter().flatten().copied().collect::<Vec<_>>();
+        sbox_many::<_, _, D>(&mut sbox, io_context)?;
+        for (state, sbox) in states.iter_mut().zip(sbox.chunks_exact(T)) {
+            state.copy_from_slice(sbox);
+            linear_layer(state, Poseidon2Params::<F, T, D>::matmul_external);
+        }
+        Ok(())
+    };
+
+    // Linear layer at beginning
+    for state in states.iter_mut() {
+        linear_layer(state, Poseidon2Params::<F, T, D>::matmul_external);
+    }
+
+    // First set of external rounds
+    for round_constants in &params.round_constants_external[..params.rounds_f_beginning] {
+        external_round(states, round_constants, io_context)?;
+    }
+
+    // Internal rounds, where the S-box is only applied to the first element of each state
+    for round_constant in &params.round_constants_internal[..params.rounds_p] {
+        let mut sbox = states
+            .iter()
+            .map(|state| arithmetic::add_public(state[0], *round_constant, id))
+            .collect::<Vec<_>>();
+        sbox_many::<_, _, D>(&mut sbox, io_context)?;
+        for (state, sbox) in states.iter_mut().zip(sbox) {
+            state[0] = sbox;
+            linear_layer(state, |state| params.matmul_internal(state));
+        }
+    }
+
+    // Remaining external rounds
+    for round_constants in &params.round_constants_external
+        [params.rounds_f_beginning..params.rounds_f_beginning + params.rounds_f_end]
+    {
+        external_round(states, round_constants, io_context)?;
+    }
+    Ok(())
+}
+
+/// Computes the Poseidon2 permutation of a shared state, see the [module documentation](self).
+pub fn poseidon2_permutation<F: PrimeField, N: Rep3Network, const T: usize, const D: u64>(
+    params: &Poseidon2Params<F, T, D>,
+    input: &[FieldShare<F>; T],
+    io_context: &mut IoContext<N>,
+) -> IoResult<[FieldShare<F>; T]> {
+    let mut states = [*input];
+    poseidon2_permutation_many(params, &mut states, io_context)?;
+    Ok(states[0])
+}
@@ -93,28 +93,14 @@ impl<F: PrimeField, N: Rep3Network> LookupTableProvider<F> for NaiveRep3LookupTa
         needle: Self::SecretShare,
         map: &Self::SecretSharedMap,
     ) -> IoResult<Self::SecretShare> {
-        // make some experiments which is faster
-        // a single for each or multiple chained iterators...
-        //
-        //first iterate over keys and perform equality check
         tracing::debug!("doing read on LUT-map of size {}", map.len());
-        tracing::debug!("get random zeros for blinding..");
-        let mut zeros_a = Vec::with_capacity(map.len());
-        zeros_a.resize_with(map.len(), || {
-            self.io_context.rngs.rand.masking_field_element::<F>()
-        });
-        let zeros_b = self.io_context.network.reshare_many(&zeros_a)?;
-        tracing::debug!("now perform equals and cmux...");
-        let mut result = Self::SecretShare::default();
-        for ((key, map), zero_a, zero_b) in
-            izip!(map.iter(), zeros_a.into_iter(), zeros_b.into_iter())
-        {
-            // this is super slow - we can batch it?
-            let zero_share = Self::SecretShare::new(zero_a, zero_b);
-            let equals = arithmetic::eq(needle, *key, &mut self.io_context)?;
-            let cmux = arithmetic::cmux(equals, *map, zero_share, &mut self.io_context)?;
-            result = arithmetic::add(result, cmux);
-        }
+        let equals = self.equals_keys(needle, map)?;
+        tracing::debug!("now perform the masked sum...");
+        let values = map.iter().map(|(_, value)| *value).collect::<Vec<_>>();
+        let masked = arithmetic::mul_vec(&equals, &values, &mut self.io_context)?;
+        let result = masked
+            .into_iter()
+            .fold(Self::SecretShare::default(), arithmetic::add);
         tracing::debug!("got a result!");
         Ok(result)
     }
@@ -126,14 +112,35 @@ impl<F: PrimeField, N: Rep3Network> LookupTableProvider<F> for NaiveRep3LookupTa
         map: &mut Self::SecretSharedMap,
     ) -> IoResult<()> {
         tracing::debug!("doing write on LUT-map of size {}", map.len());
-        // we do not need any zeros here
-        for (key, map) in map.iter_mut() {
-            // this is super slow - we can batch it?
-            let equals = arithmetic::eq(needle, *key, &mut self.io_context)?;
-            let cmux = arithmetic::cmux(equals, value, *map, &mut self.io_context)?;
-            *map = cmux;
+        let equals = self.equals_keys(needle, map)?;
+        tracing::debug!("now perform the cmuxes...");
+        // cmux(eq, value, old) = old + eq * (value - old)
+        let diffs = map
+            .iter()
+            .map(|(_, old)| arithmetic::sub(value, *old))
+            .collect::<Vec<_>>();
+        let updates = arithmetic::mul_vec(&equals, &diffs, &mut self.io_context)?;
+        for ((_, old), update) in izip!(map.iter_mut(), updates) {
+            *old = arithmetic::add(*old, update);
         }
         tracing::debug!("we are done");
         Ok(())
     }
 }
+
+impl<N: Rep3Network> NaiveRep3LookupTable<N> {
+    /// Computes for each key of the map whether it is equal to the needle. The result is a vector of arithmetically shared bits.
+    ///
+    /// All keys are compared in a single batched zero check, so the amount of communication rounds does not depend on the size of the map.
+    fn equals_keys<F: PrimeField>(
+        &mut self,
+        needle: Rep3PrimeFieldShare<F>,
+        map: &MpcMap<Rep3PrimeFieldShare<F>>,
+    ) -> IoResult<Vec<Rep3PrimeFieldShare<F>>> {
+        let differences = map
+            .iter()
+            .map(|(key, _)| arithmetic::sub(needle, *key))
+            .collect::<Vec<_>>();
+        arithmetic::is_zero_shared_many(&differences, &mut self.io_context)
+    }
+}
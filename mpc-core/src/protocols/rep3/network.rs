@@ -2,15 +2,16 @@
 //!
 //! This module contains implementation of the rep3 mpc network
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Instant};
 
-use crate::RngType;
+use crate::{snapshot::RngState, RngType};
 use ark_ff::PrimeField;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use bytes::{Bytes, BytesMut};
 use eyre::{bail, eyre, Report};
 use mpc_net::{
-    channel::ChannelHandle, config::NetworkConfig, MpcNetworkHandler, MpcNetworkHandlerWrapper,
+    channel::ChannelHandle, config::NetworkConfig, pool::PooledSession, AbortHandle,
+    MessageCounter, MpcNetworkHandler, MpcNetworkHandlerWrapper, SessionGuard, WarmUpStats,
 };
 
 use super::{
@@ -20,6 +21,31 @@ use super::{
     IoResult,
 };
 use rand::{distributions::Standard, prelude::Distribution, CryptoRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// The digest of the transcript of a session, see [Rep3Network::check_transcript].
+pub type TranscriptDigest = [u8; 32];
+
+/// A snapshot of the internal state of an [IoContext], see [IoContext::snapshot] and the [snapshot](crate::snapshot) module. It is zeroized when dropped.
+#[derive(Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+pub struct Rep3Snapshot {
+    id: usize,
+    rngs: [Vec<RngState>; 3],
+    rng: RngState,
+    #[zeroize(skip)]
+    a2b_type: A2BType,
+}
+
+impl std::fmt::Debug for Rep3Snapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Rep3Snapshot")
+            .field("id", &self.id)
+            .field("a2b_type", &self.a2b_type)
+            .finish_non_exhaustive()
+    }
+}
 
 // this will be moved later
 /// This struct handles networking and rng
@@ -94,6 +120,37 @@ impl<N: Rep3Network> IoContext<N> {
         })
     }
 
+    /// Takes a snapshot of the state of the correlated and the local rngs, which can be restored with [IoContext::restore]. The network is not part of the snapshot.
+    pub fn snapshot(&self) -> Rep3Snapshot {
+        Rep3Snapshot {
+            id: usize::from(self.id),
+            rngs: self.rngs.snapshot(),
+            rng: RngState::capture(&self.rng),
+            a2b_type: self.a2b_type,
+        }
+    }
+
+    /// Restores an [IoContext] from a snapshot taken with [IoContext::snapshot], using a freshly connected network. In contrast to [IoContext::init], this does not communicate. All parties need to restore the snapshots they took at the same point of the protocol.
+    pub fn restore(snapshot: Rep3Snapshot, network: N) -> IoResult<Self> {
+        let id = network.get_id();
+        if usize::from(id) != snapshot.id {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "snapshot of party {} can not be restored by party {id}",
+                    snapshot.id
+                ),
+            ));
+        }
+        Ok(Self {
+            id,
+            rngs: Rep3CorrelatedRng::restore(&snapshot.rngs)?,
+            rng: snapshot.rng.restore(),
+            network,
+            a2b_type: snapshot.a2b_type,
+        })
+    }
+
     /// Allows to change the used arithmetic/binary conversion protocol
     pub fn set_a2b_type(&mut self, a2b_type: A2BType) {
         self.a2b_type = a2b_type;
@@ -243,6 +300,70 @@ pub trait Rep3Network: Send {
     fn fork(&mut self) -> std::io::Result<Self>
     where
         Self: Sized;
+
+    /// Exchanges the digests of the messages sent to and received from the other parties and checks that they match, i.e., that no message was tampered with or lost. Returns the [TranscriptDigest] of all messages sent and received by this party so far, or `None` if the network does not record a transcript. This function has a default implementation returning `None`.
+    fn check_transcript(&mut self) -> std::io::Result<Option<TranscriptDigest>> {
+        Ok(None)
+    }
+}
+
+/// The running hashes of all messages sent to and received from the other two parties.
+#[derive(Default, Clone)]
+pub(crate) struct Transcript {
+    sent_next: Sha3_256,
+    sent_prev: Sha3_256,
+    recv_next: Sha3_256,
+    recv_prev: Sha3_256,
+}
+
+impl std::fmt::Debug for Transcript {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Transcript").finish_non_exhaustive()
+    }
+}
+
+impl Transcript {
+    fn absorb(hasher: &mut Sha3_256, data: &[u8]) {
+        // absorb the length as well, so that the boundaries of the messages are part of the transcript
+        hasher.update((data.len() as u64).to_le_bytes());
+        hasher.update(data);
+    }
+
+    fn digest(hasher: &Sha3_256) -> TranscriptDigest {
+        hasher.clone().finalize().into()
+    }
+
+    fn fmt_digest(digest: &TranscriptDigest) -> String {
+        digest.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    fn session_digest(&self) -> TranscriptDigest {
+        let mut hasher = Sha3_256::new();
+        hasher.update(Self::digest(&self.sent_next));
+        hasher.update(Self::digest(&self.sent_prev));
+        hasher.update(Self::digest(&self.recv_next));
+        hasher.update(Self::digest(&self.recv_prev));
+        hasher.finalize().into()
+    }
+
+    fn check(
+        id: PartyID,
+        from: PartyID,
+        expected: &TranscriptDigest,
+        received: &[u8],
+    ) -> std::io::Result<()> {
+        if received != expected {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "transcript mismatch on the link from party {from} to party {id}: party {from} sent messages with digest {}, but party {id} received messages with digest {}. Messages were tampered with or the parties are out of sync",
+                    Self::fmt_digest(&received.try_into().unwrap_or_default()),
+                    Self::fmt_digest(expected),
+                ),
+            ));
+        }
+        Ok(())
+    }
 }
 
 // TODO make generic over codec?
@@ -253,6 +374,7 @@ pub struct Rep3MpcNet {
     pub(crate) chan_next: ChannelHandle<Bytes, BytesMut>,
     pub(crate) chan_prev: ChannelHandle<Bytes, BytesMut>,
     pub(crate) net_handler: Arc<MpcNetworkHandlerWrapper>,
+    pub(crate) transcript: Transcript,
 }
 
 impl Rep3MpcNet {
@@ -287,11 +409,87 @@ impl Rep3MpcNet {
             net_handler: Arc::new(MpcNetworkHandlerWrapper::new(runtime, net_handler)),
             chan_next,
             chan_prev,
+            transcript: Transcript::default(),
+        })
+    }
+
+    /// Constructs the network interface for a session leased from a [MpcConnectionPool](mpc_net::pool::MpcConnectionPool), reusing the pooled connections instead of establishing new ones. The pool needs to contain exactly 3 parties with ids 0, 1, and 2.
+    pub fn from_session(session: &PooledSession) -> Result<Self, Report> {
+        if session.get_num_parties() != 3 {
+            bail!("REP3 protocol requires exactly 3 parties")
+        }
+        let id = PartyID::try_from(session.get_id())?;
+        let net_handler = Arc::clone(session.handler());
+        let (chan_next, chan_prev) = net_handler.runtime.block_on(async {
+            let mut channels = session.get_byte_channels().await?;
+            let chan_next = channels
+                .remove(&id.next_id().into())
+                .ok_or(eyre!("no next channel found"))?;
+            let chan_prev = channels
+                .remove(&id.prev_id().into())
+                .ok_or(eyre!("no prev channel found"))?;
+            if !channels.is_empty() {
+                bail!("unexpected channels found")
+            }
+
+            let chan_next = ChannelHandle::manage(chan_next);
+            let chan_prev = ChannelHandle::manage(chan_prev);
+            Ok((chan_next, chan_prev))
+        })?;
+        Ok(Self {
+            id,
+            net_handler,
+            chan_next,
+            chan_prev,
+            transcript: Transcript::default(),
         })
     }
 
-    /// Sends bytes over the network to the target party.
+    /// Returns a [SessionGuard] that aborts the session at all parties if it is dropped before [SessionGuard::finish] is called, e.g., because a command failed at this party. Must not be used with a network constructed by [Rep3MpcNet::from_session].
+    pub fn session_guard(&self) -> SessionGuard {
+        SessionGuard::new(Arc::clone(&self.net_handler))
+    }
+
+    /// Returns an [AbortHandle] to abort the session at all parties from another thread, e.g., if the user interrupts the command.
+    pub fn abort_handle(&self) -> AbortHandle {
+        self.net_handler.inner.abort_handle()
+    }
+
+    /// Returns the [MessageCounter] of the messages this network and its forks exchanged with the other parties. Messages sent with [Rep3MpcNet::send_bytes] and received with [Rep3MpcNet::recv_bytes] are counted, the dummy messages of the warm-up are not.
+    pub fn message_counter(&self) -> Arc<MessageCounter> {
+        Arc::clone(&self.net_handler.messages)
+    }
+
+    /// Sends bytes over the network to the target party. The bytes are added to the transcript of this network.
     pub fn send_bytes(&mut self, target: PartyID, data: Bytes) -> std::io::Result<()> {
+        self.net_handler
+            .messages
+            .record_sent(target.into(), data.len());
+        if target == self.id.next_id() {
+            Transcript::absorb(&mut self.transcript.sent_next, &data);
+        } else if target == self.id.prev_id() {
+            Transcript::absorb(&mut self.transcript.sent_prev, &data);
+        }
+        self.send_bytes_untracked(target, data)
+    }
+
+    /// Warms up the connections to the other parties by exchanging dummy messages of at most `max_message_len` bytes until the `deadline` of one of the parties has passed, see [WarmUpStats::run]. The dummy messages are not added to the transcript of this network.
+    pub fn warm_up(
+        &mut self,
+        deadline: Instant,
+        max_message_len: usize,
+    ) -> std::io::Result<WarmUpStats> {
+        WarmUpStats::run(deadline, max_message_len, |message| {
+            self.send_bytes_untracked(self.id.next_id(), message.clone())?;
+            self.send_bytes_untracked(self.id.prev_id(), message)?;
+            Ok(vec![
+                self.recv_bytes_untracked(self.id.prev_id())?,
+                self.recv_bytes_untracked(self.id.next_id())?,
+            ])
+        })
+    }
+
+    fn send_bytes_untracked(&mut self, target: PartyID, data: Bytes) -> std::io::Result<()> {
         if target == self.id.next_id() {
             std::mem::drop(self.chan_next.blocking_send(data));
             Ok(())
@@ -306,8 +504,21 @@ impl Rep3MpcNet {
         }
     }
 
-    /// Receives bytes over the network from the party with the given id.
+    /// Receives bytes over the network from the party with the given id. The bytes are added to the transcript of this network.
     pub fn recv_bytes(&mut self, from: PartyID) -> std::io::Result<BytesMut> {
+        let data = self.recv_bytes_untracked(from)?;
+        self.net_handler
+            .messages
+            .record_received(from.into(), data.len());
+        if from == self.id.prev_id() {
+            Transcript::absorb(&mut self.transcript.recv_prev, &data);
+        } else {
+            Transcript::absorb(&mut self.transcript.recv_next, &data);
+        }
+        Ok(data)
+    }
+
+    fn recv_bytes_untracked(&mut self, from: PartyID) -> std::io::Result<BytesMut> {
         let data = if from == self.id.prev_id() {
             self.chan_prev.blocking_recv().blocking_recv()
         } else if from == self.id.next_id() {
@@ -318,10 +529,10 @@ impl Rep3MpcNet {
                 "Cannot recv from self",
             ));
         };
-        let data = data.map_err(|_| {
+        data.map_err(|_| {
             std::io::Error::new(std::io::ErrorKind::BrokenPipe, "receive channel end died")
-        })??;
-        Ok(data)
+        })?
+        .map_err(|err| self.net_handler.inner.map_session_error(err))
     }
 }
 
@@ -370,6 +581,30 @@ impl Rep3Network for Rep3MpcNet {
         Ok(res)
     }
 
+    fn check_transcript(&mut self) -> std::io::Result<Option<TranscriptDigest>> {
+        // the digests themselves are not part of the transcript
+        let sent_next = Transcript::digest(&self.transcript.sent_next);
+        let sent_prev = Transcript::digest(&self.transcript.sent_prev);
+        self.send_bytes_untracked(self.id.next_id(), Bytes::copy_from_slice(&sent_next))?;
+        self.send_bytes_untracked(self.id.prev_id(), Bytes::copy_from_slice(&sent_prev))?;
+        let from_prev = self.recv_bytes_untracked(self.id.prev_id())?;
+        let from_next = self.recv_bytes_untracked(self.id.next_id())?;
+        Transcript::check(
+            self.id,
+            self.id.prev_id(),
+            &Transcript::digest(&self.transcript.recv_prev),
+            &from_prev,
+        )?;
+        Transcript::check(
+            self.id,
+            self.id.next_id(),
+            &Transcript::digest(&self.transcript.recv_next),
+            &from_next,
+        )?;
+        Ok(Some(self.transcript.session_digest()))
+    }
+
+    /// Forks the network. The fork records its own transcript, i.e., it needs to be checked separately.
     fn fork(&mut self) -> std::io::Result<Self> {
         let id = self.id;
         let net_handler = Arc::clone(&self.net_handler);
@@ -396,6 +631,41 @@ impl Rep3Network for Rep3MpcNet {
             net_handler,
             chan_next,
             chan_prev,
+            transcript: Transcript::default(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Transcript, TranscriptDigest};
+    use crate::protocols::rep3::id::PartyID;
+
+    #[test]
+    fn transcript_detects_tampering() {
+        let mut sender = Transcript::default();
+        let mut receiver = Transcript::default();
+        Transcript::absorb(&mut sender.sent_next, b"message");
+        Transcript::absorb(&mut receiver.recv_prev, b"message");
+        let sent: TranscriptDigest = Transcript::digest(&sender.sent_next);
+        Transcript::check(
+            PartyID::ID1,
+            PartyID::ID0,
+            &Transcript::digest(&receiver.recv_prev),
+            &sent,
+        )
+        .expect("transcripts match");
+
+        // the message boundaries are part of the transcript
+        let mut receiver = Transcript::default();
+        Transcript::absorb(&mut receiver.recv_prev, b"mess");
+        Transcript::absorb(&mut receiver.recv_prev, b"age");
+        assert!(Transcript::check(
+            PartyID::ID1,
+            PartyID::ID0,
+            &Transcript::digest(&receiver.recv_prev),
+            &sent,
+        )
+        .is_err());
+    }
+}
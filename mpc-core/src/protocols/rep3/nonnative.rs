@@ -0,0 +1,62 @@
+//! Non-native field arithmetic
+//!
+//! This module implements the operations required for [NonNativeField](crate::nonnative::NonNativeField) for REP3.
+
+use ark_ff::PrimeField;
+use itertools::izip;
+
+use crate::nonnative::NonNativeArithmetic;
+
+use super::{
+    arithmetic,
+    network::{IoContext, Rep3Network},
+    IoResult, Rep3PrimeFieldShare,
+};
+
+impl<F: PrimeField, N: Rep3Network> NonNativeArithmetic<F> for IoContext<N> {
+    type Share = Rep3PrimeFieldShare<F>;
+
+    fn add(&self, a: &Self::Share, b: &Self::Share) -> Self::Share {
+        arithmetic::add(*a, *b)
+    }
+
+    fn sub(&self, a: &Self::Share, b: &Self::Share) -> Self::Share {
+        arithmetic::sub(*a, *b)
+    }
+
+    fn add_public(&self, a: &Self::Share, b: F) -> Self::Share {
+        arithmetic::add_public(*a, b, self.id)
+    }
+
+    fn mul_public(&self, a: &Self::Share, b: F) -> Self::Share {
+        arithmetic::mul_public(*a, b)
+    }
+
+    fn promote_to_trivial_share(&self, a: F) -> Self::Share {
+        arithmetic::promote_to_trivial_share(self.id, a)
+    }
+
+    fn mul_many(&mut self, a: &[Self::Share], b: &[Self::Share]) -> IoResult<Vec<Self::Share>> {
+        arithmetic::mul_vec(a, b, self)
+    }
+
+    fn open_many(&mut self, a: &[Self::Share]) -> IoResult<Vec<F>> {
+        arithmetic::open_vec(a, self)
+    }
+
+    fn sum_of_inputs(&mut self, inputs: Vec<F>) -> IoResult<Vec<Self::Share>> {
+        // Each party holds an additive share of the sum, which is rerandomized and then reshared
+        let a = inputs
+            .into_iter()
+            .map(|input| input + self.rngs.rand.masking_field_element::<F>())
+            .collect::<Vec<_>>();
+        let b = self.network.reshare_many(&a)?;
+        Ok(izip!(a, b)
+            .map(|(a, b)| Rep3PrimeFieldShare::new(a, b))
+            .collect())
+    }
+
+    fn num_input_parties(&self) -> usize {
+        3
+    }
+}
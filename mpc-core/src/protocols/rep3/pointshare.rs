@@ -112,6 +112,65 @@ pub fn open_point_many<C: CurveGroup, N: Rep3Network>(
     Ok(izip!(a, cs).map(|(x, c)| x.a + x.b + c).collect_vec())
 }
 
+/// Transforms an additive share of a point (e.g., produced by an external threshold scheme) into a [`Rep3PointShare`]. The additive share is rerandomized before it is reshared, so the resulting share does not reveal the additive share to the other parties. Requires one round of communication.
+pub fn from_additive<C: CurveGroup, N: Rep3Network>(
+    a: C,
+    io_context: &mut IoContext<N>,
+) -> IoResult<PointShare<C>> {
+    let local_a = a + io_context.rngs.rand.masking_ec_element::<C>();
+    let local_b = io_context.network.reshare(local_a)?;
+    Ok(PointShare {
+        a: local_a,
+        b: local_b,
+    })
+}
+
+/// Transforms a vector of additive shares of points into a vector of [`Rep3PointShare`]s, see [`from_additive`]. Requires one round of communication.
+pub fn from_additive_many<C: CurveGroup, N: Rep3Network>(
+    a: &[C],
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<PointShare<C>>> {
+    let local_a = a
+        .iter()
+        .map(|a| *a + io_context.rngs.rand.masking_ec_element::<C>())
+        .collect_vec();
+    let local_b = io_context.network.reshare_many(&local_a)?;
+    if local_b.len() != local_a.len() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "During execution of from_additive_many in MPC: Invalid number of elements received",
+        ));
+    }
+    Ok(izip!(local_a, local_b)
+        .map(|(a, b)| PointShare::new(a, b))
+        .collect())
+}
+
+/// Transforms a [`Rep3PointShare`] into a fresh additive share of the same point, e.g., to hand it to an external threshold scheme. The additive shares of the three parties sum up to the shared point. The additive share is rerandomized, so it is not known to the other parties. Does not require communication.
+pub fn to_additive<C: CurveGroup, N: Rep3Network>(
+    a: &PointShare<C>,
+    io_context: &mut IoContext<N>,
+) -> C {
+    a.a + io_context.rngs.rand.masking_ec_element::<C>()
+}
+
+/// Rerandomizes a [`Rep3PointShare`], i.e., computes a fresh sharing of the same point that is independent of the input share. Requires one round of communication.
+pub fn rerandomize<C: CurveGroup, N: Rep3Network>(
+    a: &PointShare<C>,
+    io_context: &mut IoContext<N>,
+) -> IoResult<PointShare<C>> {
+    from_additive(a.a, io_context)
+}
+
+/// Rerandomizes a vector of [`Rep3PointShare`]s, see [`rerandomize`]. Requires one round of communication.
+pub fn rerandomize_many<C: CurveGroup, N: Rep3Network>(
+    a: &[PointShare<C>],
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<PointShare<C>>> {
+    let additive = a.iter().map(|a| a.a).collect_vec();
+    from_additive_many(&additive, io_context)
+}
+
 /// Perform msm between `points` and `scalars`
 pub fn msm_public_points<C: CurveGroup>(
     points: &[C::Affine],
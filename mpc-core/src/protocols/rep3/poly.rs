@@ -1,13 +1,17 @@
 //! Polynomial Evaluation
 //!
-//! This module contains functions to evaluate shared polynomials
+//! This module contains functions to evaluate and interpolate shared polynomials
 
 use rayon::prelude::*;
 use std::cmp::max;
 
 use ark_ff::PrimeField;
 
-use super::{arithmetic, Rep3PrimeFieldShare};
+use super::{
+    arithmetic,
+    network::{IoContext, Rep3Network},
+    IoResult, Rep3PrimeFieldShare,
+};
 
 type FieldShare<F> = Rep3PrimeFieldShare<F>;
 
@@ -67,3 +71,144 @@ pub fn eval_poly<F: PrimeField>(coeffs: &[FieldShare<F>], point: F) -> FieldShar
         .reduce(FieldShare::zero_share, |acc, e| arithmetic::add(acc, e));
     result
 }
+
+/// Evaluate the shared polynomial at multiple public points.
+pub fn eval_poly_multi<F: PrimeField>(
+    coeffs: &[FieldShare<F>],
+    points: &[F],
+) -> Vec<FieldShare<F>> {
+    points
+        .par_iter()
+        .map(|point| horner_evaluate(coeffs, *point))
+        .collect()
+}
+
+// Computes the Lagrange basis polynomials for the public points in O(n^2). The i-th basis polynomial is the vanishing polynomial of all points divided by (X - x_i), scaled by the inverse of its evaluation at x_i.
+fn lagrange_basis_polys<F: PrimeField>(points: &[F]) -> Vec<Vec<F>> {
+    let mut vanishing = vec![F::one()];
+    for x in points {
+        vanishing.push(F::zero());
+        for k in (0..vanishing.len()).rev() {
+            let lower = if k > 0 { vanishing[k - 1] } else { F::zero() };
+            vanishing[k] = lower - vanishing[k] * x;
+        }
+    }
+    let mut denominators = points
+        .iter()
+        .enumerate()
+        .map(|(i, x_i)| {
+            points
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .fold(F::one(), |acc, (_, x_j)| acc * (*x_i - x_j))
+        })
+        .collect::<Vec<_>>();
+    assert!(
+        denominators.iter().all(|d| !d.is_zero()),
+        "interpolation points must be distinct"
+    );
+    ark_ff::batch_inversion(&mut denominators);
+    points
+        .par_iter()
+        .zip(denominators.par_iter())
+        .map(|(x_i, inv)| {
+            // synthetic division of the vanishing polynomial by (X - x_i)
+            let n = points.len();
+            let mut quotient = vec![F::zero(); n];
+            quotient[n - 1] = vanishing[n];
+            for k in (1..n).rev() {
+                quotient[k - 1] = vanishing[k] + *x_i * quotient[k];
+            }
+            quotient.iter_mut().for_each(|q| *q *= inv);
+            quotient
+        })
+        .collect()
+}
+
+/// Computes the coefficients of the polynomial of degree `points.len() - 1` that evaluates to the shared `evals` at the public `points`. As the points are public, this is a local operation.
+///
+/// # Panics
+/// Panics if the amounts of points and evaluations differ or if the points are not distinct.
+pub fn interpolate_poly<F: PrimeField>(
+    points: &[F],
+    evals: &[FieldShare<F>],
+) -> Vec<FieldShare<F>> {
+    assert_eq!(
+        points.len(),
+        evals.len(),
+        "amount of points and evaluations must match"
+    );
+    if points.is_empty() {
+        return vec![];
+    }
+    let basis = lagrange_basis_polys(points);
+    basis
+        .par_iter()
+        .zip(evals.par_iter())
+        .fold(
+            || vec![FieldShare::zero_share(); points.len()],
+            |mut acc, (basis, eval)| {
+                for (acc, b) in acc.iter_mut().zip(basis.iter()) {
+                    *acc = arithmetic::add(*acc, arithmetic::mul_public(*eval, *b));
+                }
+                acc
+            },
+        )
+        .reduce(
+            || vec![FieldShare::zero_share(); points.len()],
+            |mut acc, other| {
+                arithmetic::add_vec_assign(&mut acc, &other);
+                acc
+            },
+        )
+}
+
+/// Evaluate the shared polynomial at multiple shared points.
+///
+/// The powers of all points are computed together, which requires log2(`coeffs.len()`) communication rounds plus one round for the final inner products.
+pub fn eval_poly_at_shared_points<F: PrimeField, N: Rep3Network>(
+    coeffs: &[FieldShare<F>],
+    points: &[FieldShare<F>],
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<FieldShare<F>>> {
+    if coeffs.is_empty() {
+        return Ok(vec![FieldShare::zero_share(); points.len()]);
+    }
+    let degree = coeffs.len() - 1;
+    // powers[p] holds x_p^1, ..., x_p^k, where k doubles in every round
+    let mut powers = points.iter().map(|x| vec![*x]).collect::<Vec<_>>();
+    let mut known = 1;
+    while known < degree {
+        let amount = known.min(degree - known);
+        let mut lhs = Vec::with_capacity(points.len() * amount);
+        let mut rhs = Vec::with_capacity(points.len() * amount);
+        for powers in powers.iter() {
+            lhs.extend_from_slice(&powers[..amount]);
+            rhs.extend(std::iter::repeat(powers[known - 1]).take(amount));
+        }
+        let local = arithmetic::local_mul_vec(&lhs, &rhs, &mut io_context.rngs);
+        let higher = arithmetic::io_mul_vec(local, io_context)?;
+        for (powers, higher) in powers.iter_mut().zip(higher.chunks(amount)) {
+            powers.extend_from_slice(higher);
+        }
+        known += amount;
+    }
+
+    // the inner products of the coefficients and the powers only need a single reshare per point
+    let local = powers
+        .iter()
+        .map(|powers| {
+            coeffs[1..]
+                .iter()
+                .zip(powers.iter())
+                .fold(F::zero(), |acc, (c, x)| acc + c * x)
+                + io_context.rngs.rand.masking_field_element::<F>()
+        })
+        .collect::<Vec<_>>();
+    let evals = arithmetic::io_mul_vec(local, io_context)?;
+    Ok(evals
+        .into_iter()
+        .map(|eval| arithmetic::add(eval, coeffs[0]))
+        .collect())
+}
@@ -0,0 +1,211 @@
+//! # PVSS
+//!
+//! This module implements a publicly verifiable variant of the additive sharing for data owners that go offline after contributing their input. Instead of delivering the shares interactively, the data owner publishes a [PvssSharing]: the additive share of each party encrypted to the [PvssKeyPair] of this party, together with a proof of correct sharing. Everybody can check the proof with [PvssSharing::verify], and the parties decrypt their shares later with [PvssSharing::decrypt]. The decrypted additive shares are converted to replicated shares with a reshare, as for other additive inputs.
+//!
+//! The shares are encrypted with a hashed ElGamal encryption: the data owner publishes an ephemeral key `R = r * G`, and the additive share `x_i` of party `i` is masked with a hash of the Diffie-Hellman key `r * pk_i`. The proof is a Schnorr proof of knowledge of `r` that binds the committee keys and all ciphertexts, i.e., a published sharing can neither be modified nor be replayed for a different committee. As any three field elements form a valid additive sharing, the proof together with the well-formedness of the ciphertexts shows that the parties decrypt a correct sharing.
+
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{PrimeField, UniformRand};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rand::{CryptoRng, Rng};
+use sha3::{Digest, Sha3_512};
+
+use super::{id::PartyID, share_field_elements_additive};
+
+/// The domain separation tag of the masks of the encrypted shares.
+const MASK_DST: &[u8] = b"CO-SNARKS-REP3-PVSS-MASK";
+/// The domain separation tag of the challenge of the proof of correct sharing.
+const CHALLENGE_DST: &[u8] = b"CO-SNARKS-REP3-PVSS-CHALLENGE";
+
+/// The key pair of a party for receiving shares of a [PvssSharing].
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PvssKeyPair<C: CurveGroup> {
+    /// The secret key
+    pub secret_key: C::ScalarField,
+    /// The public key, i.e., the secret key times the generator
+    pub public_key: C::Affine,
+}
+
+impl<C: CurveGroup> PvssKeyPair<C> {
+    /// Generates a new random key pair.
+    pub fn generate<R: Rng + CryptoRng>(rng: &mut R) -> Self {
+        let secret_key = C::ScalarField::rand(rng);
+        Self {
+            secret_key,
+            public_key: (C::generator() * secret_key).into_affine(),
+        }
+    }
+}
+
+/// The published encrypted shares of a vector of field elements, see [share_field_elements_pvss].
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct PvssSharing<C: CurveGroup> {
+    /// The ephemeral key `R = r * G` of the data owner
+    pub ephemeral_key: C::Affine,
+    /// The encrypted additive shares of the three parties
+    pub ciphertexts: Vec<Vec<C::ScalarField>>,
+    /// The commitment of the proof of correct sharing
+    pub proof_commitment: C::Affine,
+    /// The response of the proof of correct sharing
+    pub proof_response: C::ScalarField,
+}
+
+fn mask<C: CurveGroup>(shared_key: &C::Affine, id: usize, index: usize) -> C::ScalarField {
+    let mut bytes = Vec::new();
+    shared_key
+        .serialize_compressed(&mut bytes)
+        .expect("can serialize into vec");
+    let mut hasher = Sha3_512::new();
+    hasher.update(MASK_DST);
+    hasher.update(bytes);
+    hasher.update((id as u64).to_le_bytes());
+    hasher.update((index as u64).to_le_bytes());
+    C::ScalarField::from_le_bytes_mod_order(&hasher.finalize())
+}
+
+fn challenge<C: CurveGroup>(
+    public_keys: &[C::Affine; 3],
+    ephemeral_key: &C::Affine,
+    ciphertexts: &[Vec<C::ScalarField>],
+    proof_commitment: &C::Affine,
+) -> C::ScalarField {
+    let mut bytes = Vec::new();
+    public_keys
+        .as_slice()
+        .serialize_compressed(&mut bytes)
+        .expect("can serialize into vec");
+    ephemeral_key
+        .serialize_compressed(&mut bytes)
+        .expect("can serialize into vec");
+    ciphertexts
+        .serialize_compressed(&mut bytes)
+        .expect("can serialize into vec");
+    proof_commitment
+        .serialize_compressed(&mut bytes)
+        .expect("can serialize into vec");
+    let mut hasher = Sha3_512::new();
+    hasher.update(CHALLENGE_DST);
+    hasher.update(bytes);
+    C::ScalarField::from_le_bytes_mod_order(&hasher.finalize())
+}
+
+fn is_valid_public_key<C: CurveGroup>(public_key: &C::Affine) -> bool {
+    !public_key.is_zero() && public_key.is_in_correct_subgroup_assuming_on_curve()
+}
+
+/// Secret shares a vector of field elements using additive secret sharing and encrypts the share of party `i` to `public_keys[i]`. The result can be published, see the [module level documentation](self).
+pub fn share_field_elements_pvss<C: CurveGroup, R: Rng + CryptoRng>(
+    vals: &[C::ScalarField],
+    public_keys: &[C::Affine; 3],
+    rng: &mut R,
+) -> eyre::Result<PvssSharing<C>> {
+    let shares = share_field_elements_additive(vals, rng);
+    encrypt_shares(shares, public_keys, rng)
+}
+
+/// Encrypts the additive shares `shares[i]` of party `i` to `public_keys[i]` and proves the correct sharing. All parties need to have the same amount of shares.
+pub fn encrypt_shares<C: CurveGroup, R: Rng + CryptoRng>(
+    shares: [Vec<C::ScalarField>; 3],
+    public_keys: &[C::Affine; 3],
+    rng: &mut R,
+) -> eyre::Result<PvssSharing<C>> {
+    if let Some(id) = public_keys
+        .iter()
+        .position(|pk| !is_valid_public_key::<C>(pk))
+    {
+        eyre::bail!("invalid public key of party {id}");
+    }
+    if shares.iter().any(|s| s.len() != shares[0].len()) {
+        eyre::bail!("the parties have different amounts of shares");
+    }
+
+    let r = C::ScalarField::rand(rng);
+    let ephemeral_key = (C::generator() * r).into_affine();
+    let ciphertexts = shares
+        .into_iter()
+        .zip(public_keys.iter())
+        .enumerate()
+        .map(|(id, (shares, pk))| {
+            let shared_key = (*pk * r).into_affine();
+            shares
+                .into_iter()
+                .enumerate()
+                .map(|(index, share)| share + mask::<C>(&shared_key, id, index))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let k = C::ScalarField::rand(rng);
+    let proof_commitment = (C::generator() * k).into_affine();
+    let c = challenge::<C>(public_keys, &ephemeral_key, &ciphertexts, &proof_commitment);
+    Ok(PvssSharing {
+        ephemeral_key,
+        ciphertexts,
+        proof_commitment,
+        proof_response: k + c * r,
+    })
+}
+
+impl<C: CurveGroup> PvssSharing<C> {
+    /// Returns the amount of shared field elements.
+    pub fn len(&self) -> usize {
+        self.ciphertexts.first().map(Vec::len).unwrap_or_default()
+    }
+
+    /// Returns `true` if no field elements are shared.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Verifies the proof of correct sharing for the committee with the provided public keys. Does not require any secret key.
+    pub fn verify(&self, public_keys: &[C::Affine; 3]) -> eyre::Result<()> {
+        if let Some(id) = public_keys
+            .iter()
+            .position(|pk| !is_valid_public_key::<C>(pk))
+        {
+            eyre::bail!("invalid public key of party {id}");
+        }
+        if self.ciphertexts.len() != 3 {
+            eyre::bail!(
+                "expected ciphertexts for 3 parties, got {}",
+                self.ciphertexts.len()
+            );
+        }
+        if self.ciphertexts.iter().any(|c| c.len() != self.len()) {
+            eyre::bail!("the ciphertexts of the parties have different lengths");
+        }
+        if !is_valid_public_key::<C>(&self.ephemeral_key) {
+            eyre::bail!("invalid ephemeral key");
+        }
+        let c = challenge::<C>(
+            public_keys,
+            &self.ephemeral_key,
+            &self.ciphertexts,
+            &self.proof_commitment,
+        );
+        if C::generator() * self.proof_response != self.ephemeral_key * c + self.proof_commitment {
+            eyre::bail!("invalid proof of correct sharing");
+        }
+        Ok(())
+    }
+
+    /// Verifies the sharing with [PvssSharing::verify] and decrypts the additive shares of party `id`.
+    pub fn decrypt(
+        &self,
+        key: &PvssKeyPair<C>,
+        public_keys: &[C::Affine; 3],
+        id: PartyID,
+    ) -> eyre::Result<Vec<C::ScalarField>> {
+        let id = id as usize;
+        if public_keys[id] != key.public_key {
+            eyre::bail!("the key pair does not belong to party {id}");
+        }
+        self.verify(public_keys)?;
+        let shared_key = (self.ephemeral_key * key.secret_key).into_affine();
+        Ok(self.ciphertexts[id]
+            .iter()
+            .enumerate()
+            .map(|(index, c)| *c - mask::<C>(&shared_key, id, index))
+            .collect())
+    }
+}
@@ -3,7 +3,7 @@
 //! This module contains implementations of rep3 rngs
 
 use super::{id::PartyID, yao::GCUtils};
-use crate::RngType;
+use crate::{snapshot::RngState, RngType};
 use ark_ec::CurveGroup;
 use ark_ff::{One, PrimeField};
 use fancy_garbling::WireMod2;
@@ -13,6 +13,15 @@ use rand::{
 };
 use rayon::prelude::*;
 
+type IoResult<T> = std::io::Result<T>;
+
+fn invalid_snapshot() -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        "snapshot contains an invalid number of rng states",
+    )
+}
+
 #[derive(Debug)]
 /// A correlated rng for rep3
 pub struct Rep3CorrelatedRng {
@@ -43,6 +52,22 @@ impl Rep3CorrelatedRng {
         }
     }
 
+    pub(crate) fn snapshot(&self) -> [Vec<RngState>; 3] {
+        [
+            self.rand.snapshot(),
+            self.bitcomp1.snapshot(),
+            self.bitcomp2.snapshot(),
+        ]
+    }
+
+    pub(crate) fn restore(states: &[Vec<RngState>; 3]) -> IoResult<Self> {
+        Ok(Self {
+            rand: Rep3Rand::restore(&states[0])?,
+            bitcomp1: Rep3RandBitComp::restore(&states[1])?,
+            bitcomp2: Rep3RandBitComp::restore(&states[2])?,
+        })
+    }
+
     /// Generate a value that is equal on all three parties
     pub fn generate_shared<T>(&mut self, id: PartyID) -> T
     where
@@ -98,6 +123,20 @@ impl Rep3Rand {
         Self::new(seed1, seed2)
     }
 
+    fn snapshot(&self) -> Vec<RngState> {
+        vec![RngState::capture(&self.rng1), RngState::capture(&self.rng2)]
+    }
+
+    fn restore(states: &[RngState]) -> IoResult<Self> {
+        match states {
+            [rng1, rng2] => Ok(Self {
+                rng1: rng1.restore(),
+                rng2: rng2.restore(),
+            }),
+            _ => Err(invalid_snapshot()),
+        }
+    }
+
     /// Generate a masking field element
     pub fn masking_field_element<F: PrimeField>(&mut self) -> F {
         let (a, b) = self.random_fes::<F>();
@@ -271,6 +310,30 @@ impl Rep3RandBitComp {
         }
     }
 
+    fn snapshot(&self) -> Vec<RngState> {
+        std::iter::once(&self.rng1)
+            .chain(std::iter::once(&self.rng2))
+            .chain(self.rng3.as_ref())
+            .map(RngState::capture)
+            .collect()
+    }
+
+    fn restore(states: &[RngState]) -> IoResult<Self> {
+        match states {
+            [rng1, rng2] => Ok(Self {
+                rng1: rng1.restore(),
+                rng2: rng2.restore(),
+                rng3: None,
+            }),
+            [rng1, rng2, rng3] => Ok(Self {
+                rng1: rng1.restore(),
+                rng2: rng2.restore(),
+                rng3: Some(rng3.restore()),
+            }),
+            _ => Err(invalid_snapshot()),
+        }
+    }
+
     /// Contruct a new [`Rep3RandBitComp`] with 3 rngs
     pub fn new_3keys(
         rng1: [u8; crate::SEED_SIZE],
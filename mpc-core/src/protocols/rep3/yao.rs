@@ -2,9 +2,11 @@
 //!
 //! This module contains operations with Yao's garbled circuits
 
+pub mod bristol;
 pub mod circuits;
 pub mod evaluator;
 pub mod garbler;
+pub mod hash;
 pub mod streaming_evaluator;
 pub mod streaming_garbler;
 
@@ -13,9 +15,10 @@ use super::{
     IoResult, Rep3BigUintShare, Rep3PrimeFieldShare,
 };
 use crate::protocols::rep3::id::PartyID;
-use ark_ff::{PrimeField, Zero};
+use ark_ff::{One, PrimeField, Zero};
 use circuits::GarbledCircuits;
-use fancy_garbling::{hash_wires, util::tweak2, BinaryBundle, WireLabel, WireMod2};
+use fancy_garbling::{util::tweak2, BinaryBundle, WireLabel, WireMod2};
+use hash::hash_wires;
 use itertools::{izip, Itertools};
 use num_bigint::BigUint;
 use rand::{CryptoRng, Rng};
@@ -196,6 +199,38 @@ impl GCUtils {
         Ok(())
     }
 
+    /// Concatenates binary shares into one binary share, where each share is truncated to the given bit size. The first share ends up in the lowest bits.
+    pub(crate) fn concat_binary_shares<F: PrimeField>(
+        shares: &[(&Rep3BigUintShare<F>, usize)],
+    ) -> Rep3BigUintShare<F> {
+        let mut a = BigUint::zero();
+        let mut b = BigUint::zero();
+        for &(share, bitlen) in shares.iter().rev() {
+            let mask = (BigUint::from(1u64) << bitlen) - BigUint::from(1u64);
+            a <<= bitlen;
+            b <<= bitlen;
+            a |= &share.a & &mask;
+            b |= &share.b & &mask;
+        }
+        Rep3BigUintShare::new(a, b)
+    }
+
+    /// Splits a binary share into binary shares of the given bit sizes, starting from the lowest bits. This is the inverse of [Self::concat_binary_shares].
+    pub(crate) fn split_binary_share<F: PrimeField>(
+        share: Rep3BigUintShare<F>,
+        bitlens: &[usize],
+    ) -> Vec<Rep3BigUintShare<F>> {
+        let (mut a, mut b) = share.ab();
+        let mut result = Vec::with_capacity(bitlens.len());
+        for &bitlen in bitlens {
+            let mask = (BigUint::from(1u64) << bitlen) - BigUint::from(1u64);
+            result.push(Rep3BigUintShare::new(&a & &mask, &b & &mask));
+            a >>= bitlen;
+            b >>= bitlen;
+        }
+        result
+    }
+
     /// Samples a random delta
     pub fn random_delta<R: Rng + CryptoRng>(rng: &mut R) -> WireMod2 {
         WireMod2::rand_delta(rng, 2)
@@ -708,6 +743,113 @@ pub fn field_int_div_power_2<F: PrimeField, N: Rep3Network>(
     Ok(res[0])
 }
 
+/// Checks whether a vector of shared field elements are zero in a constant number of communication rounds. The result is a shared bit per field element, which has value 1 if the field element is zero and 0 otherwise.
+pub fn field_is_zero_many<F: PrimeField, N: Rep3Network>(
+    inputs: &[Rep3PrimeFieldShare<F>],
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<Rep3PrimeFieldShare<F>>> {
+    let num_inputs = inputs.len();
+    if num_inputs == 0 {
+        return Ok(vec![]);
+    }
+
+    decompose_circuit_compose_blueprint!(
+        inputs,
+        io_context,
+        num_inputs,
+        GarbledCircuits::field_is_zero_many::<_, F>,
+        ()
+    )
+}
+
+/// Checks whether a shared field element is zero in a constant number of communication rounds. The result is a shared bit, which has value 1 if the field element is zero and 0 otherwise.
+pub fn field_is_zero<F: PrimeField, N: Rep3Network>(
+    input: Rep3PrimeFieldShare<F>,
+    io_context: &mut IoContext<N>,
+) -> IoResult<Rep3PrimeFieldShare<F>> {
+    let res = field_is_zero_many(&[input], io_context)?;
+    Ok(res[0])
+}
+
+/// Divides a vector of shared field elements, interpreted as integers, by a public divisor in a constant number of communication rounds. Returns the quotients (rounded down) and the remainders.
+///
+/// Returns an error if the divisor is zero.
+#[expect(clippy::type_complexity)]
+pub fn field_int_div_rem_public_many<F: PrimeField, N: Rep3Network>(
+    inputs: &[Rep3PrimeFieldShare<F>],
+    io_context: &mut IoContext<N>,
+    divisor: F,
+) -> IoResult<(Vec<Rep3PrimeFieldShare<F>>, Vec<Rep3PrimeFieldShare<F>>)> {
+    let num_inputs = inputs.len();
+    if divisor.is_zero() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "Cannot divide by zero",
+        ));
+    }
+    if divisor.is_one() {
+        return Ok((
+            inputs.to_owned(),
+            vec![Rep3PrimeFieldShare::zero_share(); num_inputs],
+        ));
+    }
+    if num_inputs == 0 {
+        return Ok((vec![], vec![]));
+    }
+
+    let divisor: BigUint = divisor.into();
+    let res: IoResult<Vec<_>> = decompose_circuit_compose_blueprint!(
+        inputs,
+        io_context,
+        2 * num_inputs,
+        GarbledCircuits::field_int_div_rem_public_many::<_, F>,
+        (&divisor)
+    );
+    let mut quotients = res?;
+    let rems = quotients.split_off(num_inputs);
+    Ok((quotients, rems))
+}
+
+/// Divides a vector of shared field elements, interpreted as integers, by a public divisor, rounding down. See [field_int_div_rem_public_many].
+pub fn field_int_div_public_many<F: PrimeField, N: Rep3Network>(
+    inputs: &[Rep3PrimeFieldShare<F>],
+    io_context: &mut IoContext<N>,
+    divisor: F,
+) -> IoResult<Vec<Rep3PrimeFieldShare<F>>> {
+    let (quotients, _) = field_int_div_rem_public_many(inputs, io_context, divisor)?;
+    Ok(quotients)
+}
+
+/// Divides a shared field element, interpreted as an integer, by a public divisor, rounding down. See [field_int_div_rem_public_many].
+pub fn field_int_div_public<F: PrimeField, N: Rep3Network>(
+    input: Rep3PrimeFieldShare<F>,
+    io_context: &mut IoContext<N>,
+    divisor: F,
+) -> IoResult<Rep3PrimeFieldShare<F>> {
+    let res = field_int_div_public_many(&[input], io_context, divisor)?;
+    Ok(res[0])
+}
+
+/// Reduces a vector of shared field elements, interpreted as integers, modulo a public divisor. See [field_int_div_rem_public_many].
+pub fn field_int_mod_public_many<F: PrimeField, N: Rep3Network>(
+    inputs: &[Rep3PrimeFieldShare<F>],
+    io_context: &mut IoContext<N>,
+    divisor: F,
+) -> IoResult<Vec<Rep3PrimeFieldShare<F>>> {
+    let (_, rems) = field_int_div_rem_public_many(inputs, io_context, divisor)?;
+    Ok(rems)
+}
+
+/// Reduces a shared field element, interpreted as an integer, modulo a public divisor. See [field_int_div_rem_public_many].
+pub fn field_int_mod_public<F: PrimeField, N: Rep3Network>(
+    input: Rep3PrimeFieldShare<F>,
+    io_context: &mut IoContext<N>,
+    divisor: F,
+) -> IoResult<Rep3PrimeFieldShare<F>> {
+    let res = field_int_mod_public_many(&[input], io_context, divisor)?;
+    Ok(res[0])
+}
+
 macro_rules! decompose_circuit_compose_blueprint {
     ($inputs:expr, $io_context:expr, $output_size:expr, $circuit:expr, ($( $args:expr ),*)) => {{
         use $crate::protocols::rep3::id::PartyID;
@@ -829,3 +971,403 @@ pub fn decompose_arithmetic_many<F: PrimeField, N: Rep3Network>(
         (decompose_bit_size, total_bit_size_per_field)
     )
 }
+
+/// Encrypts a vector of binary shared 128-bit blocks with AES-128 under a binary shared 128-bit key using garbled circuits. The result is binary shared as well, such that neither the key nor the plaintexts or ciphertexts are revealed.
+///
+/// Each 128-bit value is interpreted as the little-endian encoding of the 16 AES bytes, i.e., the AES byte i corresponds to the bits 8i..8i+8 of the value.
+pub fn aes128_encrypt_many<F: PrimeField, N: Rep3Network>(
+    key: &Rep3BigUintShare<F>,
+    plaintexts: &[Rep3BigUintShare<F>],
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<Rep3BigUintShare<F>>> {
+    const BLOCK_SIZE: usize = 128;
+    let num_blocks = plaintexts.len();
+    if num_blocks == 0 {
+        return Ok(Vec::new());
+    }
+
+    // Input the key and the plaintexts at once
+    let inputs = std::iter::once(key)
+        .chain(plaintexts.iter())
+        .map(|x| (x, BLOCK_SIZE))
+        .collect_vec();
+    let combined = GCUtils::concat_binary_shares(&inputs);
+
+    let delta = io_context.rngs.generate_random_garbler_delta(io_context.id);
+    let [x01, x2] =
+        joint_input_binary_xored(&combined, delta, io_context, BLOCK_SIZE * (num_blocks + 1))?;
+
+    let encrypted = match io_context.id {
+        PartyID::ID0 => {
+            let mut evaluator = evaluator::Rep3Evaluator::new(io_context);
+            evaluator.receive_circuit()?;
+            let inputs = GarbledCircuits::xor_many(&mut evaluator, &x01, &x2);
+            let inputs = GCUtils::garbled_circuits_error(inputs)?;
+            let (key, blocks) = inputs.wires().split_at(BLOCK_SIZE);
+            let res = GarbledCircuits::aes128_encrypt_many(
+                &mut evaluator,
+                &BinaryBundle::new(key.to_vec()),
+                &BinaryBundle::new(blocks.to_vec()),
+            );
+            let res = GCUtils::garbled_circuits_error(res)?;
+            evaluator.output_shared(res)?
+        }
+        PartyID::ID1 | PartyID::ID2 => {
+            let delta = match delta {
+                Some(delta) => delta,
+                None => Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "No delta provided",
+                ))?,
+            };
+            let mut garbler = garbler::Rep3Garbler::new_with_delta(io_context, delta);
+            let inputs = GarbledCircuits::xor_many(&mut garbler, &x01, &x2);
+            let inputs = GCUtils::garbled_circuits_error(inputs)?;
+            let (key, blocks) = inputs.wires().split_at(BLOCK_SIZE);
+            let res = GarbledCircuits::aes128_encrypt_many(
+                &mut garbler,
+                &BinaryBundle::new(key.to_vec()),
+                &BinaryBundle::new(blocks.to_vec()),
+            );
+            let res = GCUtils::garbled_circuits_error(res)?;
+            garbler.output_shared(res)?
+        }
+    };
+
+    // Split the binary shared result into the individual blocks
+    let bitlens = vec![BLOCK_SIZE; num_blocks];
+    Ok(GCUtils::split_binary_share(encrypted, &bitlens))
+}
+
+/// Encrypts a binary shared 128-bit block with AES-128 under a binary shared 128-bit key using garbled circuits. See [aes128_encrypt_many] for details.
+pub fn aes128_encrypt<F: PrimeField, N: Rep3Network>(
+    key: &Rep3BigUintShare<F>,
+    plaintext: &Rep3BigUintShare<F>,
+    io_context: &mut IoContext<N>,
+) -> IoResult<Rep3BigUintShare<F>> {
+    let res = aes128_encrypt_many(key, std::slice::from_ref(plaintext), io_context)?;
+    Ok(res[0].to_owned())
+}
+
+/// Evaluates a [Bristol Fashion](bristol::BristolFashionCircuit) circuit on binary shared inputs using garbled circuits. The inputs correspond to the input values of the circuit, where only the lowest bits (according to the input sizes of the circuit) of each input are used. The outputs are binary shared values corresponding to the output values of the circuit.
+pub fn evaluate_bristol_circuit<F: PrimeField, N: Rep3Network>(
+    circuit: &bristol::BristolFashionCircuit,
+    inputs: &[Rep3BigUintShare<F>],
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<Rep3BigUintShare<F>>> {
+    if inputs.len() != circuit.input_sizes().len() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "Number of inputs does not match the circuit",
+        ));
+    }
+
+    let inputs = izip!(inputs, circuit.input_sizes().iter().copied()).collect_vec();
+    let combined = GCUtils::concat_binary_shares(&inputs);
+
+    let delta = io_context.rngs.generate_random_garbler_delta(io_context.id);
+    let [x01, x2] =
+        joint_input_binary_xored(&combined, delta, io_context, circuit.num_input_wires())?;
+
+    let evaluated = match io_context.id {
+        PartyID::ID0 => {
+            let mut evaluator = evaluator::Rep3Evaluator::new(io_context);
+            evaluator.receive_circuit()?;
+            let inputs = GarbledCircuits::xor_many(&mut evaluator, &x01, &x2);
+            let inputs = GCUtils::garbled_circuits_error(inputs)?;
+            let res = circuit.evaluate(&mut evaluator, &inputs);
+            let res = GCUtils::garbled_circuits_error(res)?;
+            evaluator.output_shared(res)?
+        }
+        PartyID::ID1 | PartyID::ID2 => {
+            let delta = match delta {
+                Some(delta) => delta,
+                None => Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "No delta provided",
+                ))?,
+            };
+            let mut garbler = garbler::Rep3Garbler::new_with_delta(io_context, delta);
+            let inputs = GarbledCircuits::xor_many(&mut garbler, &x01, &x2);
+            let inputs = GCUtils::garbled_circuits_error(inputs)?;
+            let res = circuit.evaluate(&mut garbler, &inputs);
+            let res = GCUtils::garbled_circuits_error(res)?;
+            garbler.output_shared(res)?
+        }
+    };
+
+    Ok(GCUtils::split_binary_share(
+        evaluated,
+        circuit.output_sizes(),
+    ))
+}
+
+/// The modular operations on big integers which can be computed with [bigint_mod_op_bytes].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BigIntModOp {
+    /// lhs + rhs mod m
+    Add,
+    /// lhs - rhs mod m
+    Sub,
+    /// lhs * rhs mod m
+    Mul,
+    /// lhs * rhs * scalar mod m for a public scalar
+    MulScaled(BigUint),
+}
+
+/// Computes a modular operation on two shared integers modulo a public modulus using garbled circuits. The integers are given as vectors of shared bytes in little-endian order, where each byte is expected to be smaller than 2^8. The operands do not need to be reduced and can have a different number of bytes. The result is reduced and returned as modulus.bits().div_ceil(8) shared bytes in little-endian order.
+pub fn bigint_mod_op_bytes<F: PrimeField, N: Rep3Network>(
+    lhs: &[Rep3PrimeFieldShare<F>],
+    rhs: &[Rep3PrimeFieldShare<F>],
+    modulus: &BigUint,
+    op: BigIntModOp,
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<Rep3PrimeFieldShare<F>>> {
+    if lhs.is_empty() || rhs.is_empty() || modulus.bits() < 2 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "BigInt operands must not be empty and the modulus must be at least 2",
+        ));
+    }
+    let output_size = (modulus.bits() as usize).div_ceil(8);
+    let inputs = lhs.iter().chain(rhs.iter()).cloned().collect_vec();
+
+    decompose_circuit_compose_blueprint!(
+        &inputs,
+        io_context,
+        output_size,
+        GarbledCircuits::bigint_mod_op_bytes::<_, F>,
+        (lhs.len(), modulus, &op)
+    )
+}
+
+/// Computes lhs / rhs modulo a public prime modulus, i.e., lhs * rhs^(modulus - 2), using garbled circuits. The integers are encoded as in [bigint_mod_op_bytes]. If rhs is zero modulo the modulus, the result is zero.
+///
+/// The inverse of rhs is computed by masking rhs with a random r, opening c = rhs * r and computing rhs^-1 = r * c^-1. Since the modulus is prime, c is uniformly random and only reveals whether rhs is zero.
+pub fn bigint_div_mod_bytes<F: PrimeField, N: Rep3Network>(
+    lhs: &[Rep3PrimeFieldShare<F>],
+    rhs: &[Rep3PrimeFieldShare<F>],
+    modulus: &BigUint,
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<Rep3PrimeFieldShare<F>>> {
+    // Each random field element contributes 128 (almost) uniform bits, we use 64 additional bits to make the reduced random value statistically close to uniform
+    const RAND_BITS_PER_FIELD: usize = 128;
+    const STATISTICAL_SECURITY: usize = 64;
+    if lhs.is_empty() || rhs.is_empty() || modulus.bits() < 2 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "BigInt operands must not be empty and the modulus must be at least 2",
+        ));
+    }
+    debug_assert!(F::MODULUS_BIT_SIZE as usize > RAND_BITS_PER_FIELD);
+
+    let modulus_bits = modulus.bits() as usize;
+    let output_size = modulus_bits.div_ceil(8);
+    let num_rands = (modulus_bits + STATISTICAL_SECURITY).div_ceil(RAND_BITS_PER_FIELD);
+    let mut inputs = rhs.to_vec();
+    for _ in 0..num_rands {
+        inputs.push(super::arithmetic::rand(io_context));
+    }
+
+    let masked: IoResult<Vec<_>> = decompose_circuit_compose_blueprint!(
+        &inputs,
+        io_context,
+        2 * output_size,
+        GarbledCircuits::bigint_mask_bytes::<_, F>,
+        (rhs.len(), modulus)
+    );
+    let masked = masked?;
+    let (masked, rand) = masked.split_at(output_size);
+
+    let masked = super::arithmetic::open_vec(masked, io_context)?;
+    let mut masked_bytes = Vec::with_capacity(output_size);
+    for byte in masked {
+        let byte: BigUint = byte.into();
+        masked_bytes.push(
+            u8::try_from(byte).map_err(|_| {
+                std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid byte")
+            })?,
+        );
+    }
+    let masked = BigUint::from_bytes_le(&masked_bytes);
+    // Fermat's little theorem, also maps zero to zero
+    let masked_inv = masked.modpow(&(modulus - 2u64), modulus);
+
+    bigint_mod_op_bytes(
+        lhs,
+        rand,
+        modulus,
+        BigIntModOp::MulScaled(masked_inv),
+        io_context,
+    )
+}
+
+/// Computes the quotient and the remainder of lhs * rhs + add - sub divided by a public modulus using garbled circuits. Each integer is given as a vector of shared limbs, i.e., it is the sum of limb_i * 2^(limb_bits * i), where the limbs may be larger than 2^limb_bits. add and sub can be empty, and lhs * rhs + add must not be smaller than sub. The quotient and the remainder are returned as num_limbs shared limbs of limb_bits bits each, so both have to be smaller than 2^(limb_bits * num_limbs).
+#[expect(clippy::too_many_arguments)]
+pub fn bigint_quotient_remainder_limbs<F: PrimeField, N: Rep3Network>(
+    lhs: &[Rep3PrimeFieldShare<F>],
+    rhs: &[Rep3PrimeFieldShare<F>],
+    add: &[Rep3PrimeFieldShare<F>],
+    sub: &[Rep3PrimeFieldShare<F>],
+    modulus: &BigUint,
+    limb_bits: usize,
+    num_limbs: usize,
+    io_context: &mut IoContext<N>,
+) -> IoResult<(Vec<Rep3PrimeFieldShare<F>>, Vec<Rep3PrimeFieldShare<F>>)> {
+    check_bigint_limbs::<F>(lhs, rhs, modulus, limb_bits, num_limbs)?;
+    let num_inputs = [lhs.len(), rhs.len(), add.len(), sub.len()];
+    let inputs = lhs
+        .iter()
+        .chain(rhs.iter())
+        .chain(add.iter())
+        .chain(sub.iter())
+        .cloned()
+        .collect_vec();
+
+    let result: IoResult<Vec<_>> = decompose_circuit_compose_blueprint!(
+        &inputs,
+        io_context,
+        2 * num_limbs,
+        GarbledCircuits::bigint_quotient_remainder_limbs::<_, F>,
+        (&num_inputs, limb_bits, num_limbs, modulus)
+    );
+    let mut quotient = result?;
+    let remainder = quotient.split_off(num_limbs);
+    Ok((quotient, remainder))
+}
+
+/// Computes lhs / rhs modulo a public prime modulus using garbled circuits, analogous to [bigint_div_mod_bytes]. The integers are given as vectors of shared limbs, see [bigint_quotient_remainder_limbs]. The result is reduced and returned as num_limbs shared limbs of limb_bits bits each. If rhs is zero modulo the modulus, the result is zero.
+pub fn bigint_div_limbs<F: PrimeField, N: Rep3Network>(
+    lhs: &[Rep3PrimeFieldShare<F>],
+    rhs: &[Rep3PrimeFieldShare<F>],
+    modulus: &BigUint,
+    limb_bits: usize,
+    num_limbs: usize,
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<Rep3PrimeFieldShare<F>>> {
+    const RAND_BITS_PER_FIELD: usize = 128;
+    const STATISTICAL_SECURITY: usize = 64;
+    check_bigint_limbs::<F>(lhs, rhs, modulus, limb_bits, num_limbs)?;
+    if (modulus.bits() as usize) > limb_bits * num_limbs {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "The limbs are too small for the modulus",
+        ));
+    }
+
+    let modulus_bits = modulus.bits() as usize;
+    let num_rands = (modulus_bits + STATISTICAL_SECURITY).div_ceil(RAND_BITS_PER_FIELD);
+    let mut inputs = rhs.to_vec();
+    for _ in 0..num_rands {
+        inputs.push(super::arithmetic::rand(io_context));
+    }
+
+    let masked: IoResult<Vec<_>> = decompose_circuit_compose_blueprint!(
+        &inputs,
+        io_context,
+        2 * num_limbs,
+        GarbledCircuits::bigint_mask_limbs::<_, F>,
+        (rhs.len(), limb_bits, num_limbs, modulus)
+    );
+    let masked = masked?;
+    let (masked, rand) = masked.split_at(num_limbs);
+
+    let masked = super::arithmetic::open_vec(masked, io_context)?;
+    let masked = masked
+        .into_iter()
+        .rev()
+        .fold(BigUint::zero(), |acc, limb| {
+            let limb: BigUint = limb.into();
+            (acc << limb_bits) + limb
+        });
+    let masked_inv = masked.modpow(&(modulus - 2u64), modulus);
+
+    let inputs = lhs.iter().chain(rand.iter()).cloned().collect_vec();
+    decompose_circuit_compose_blueprint!(
+        &inputs,
+        io_context,
+        num_limbs,
+        GarbledCircuits::bigint_mul_scaled_limbs::<_, F>,
+        (lhs.len(), limb_bits, num_limbs, modulus, &masked_inv)
+    )
+}
+
+fn check_bigint_limbs<F: PrimeField>(
+    lhs: &[Rep3PrimeFieldShare<F>],
+    rhs: &[Rep3PrimeFieldShare<F>],
+    modulus: &BigUint,
+    limb_bits: usize,
+    num_limbs: usize,
+) -> IoResult<()> {
+    if lhs.is_empty() || rhs.is_empty() || modulus.bits() < 2 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "BigInt operands must not be empty and the modulus must be at least 2",
+        ));
+    }
+    if limb_bits == 0 || limb_bits >= F::MODULUS_BIT_SIZE as usize || num_limbs == 0 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "Invalid limb decomposition",
+        ));
+    }
+    Ok(())
+}
+
+/// Encrypts a shared message with AES-128 in CBC mode under a shared key and a shared IV using garbled circuits. Key, IV, and message are given as vectors of shared bytes, where each byte is expected to be smaller than 2^8. The key and the IV consist of 16 bytes, and the length of the message has to be a non-zero multiple of 16 bytes, i.e., the message has to be padded already. Only the length of the message is public. The ciphertext is returned as shared bytes.
+pub fn aes128_cbc_encrypt_bytes<F: PrimeField, N: Rep3Network>(
+    key: &[Rep3PrimeFieldShare<F>],
+    iv: &[Rep3PrimeFieldShare<F>],
+    message: &[Rep3PrimeFieldShare<F>],
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<Rep3PrimeFieldShare<F>>> {
+    const BLOCK_BYTES: usize = 16;
+    if key.len() != BLOCK_BYTES || iv.len() != BLOCK_BYTES {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "The key and the IV of AES-128 must consist of 16 bytes",
+        ));
+    }
+    if message.is_empty() || message.len() % BLOCK_BYTES != 0 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "The message of AES-128 in CBC mode must consist of full blocks of 16 bytes",
+        ));
+    }
+
+    let inputs = key
+        .iter()
+        .chain(iv.iter())
+        .chain(message.iter())
+        .cloned()
+        .collect_vec();
+    decompose_circuit_compose_blueprint!(
+        &inputs,
+        io_context,
+        message.len(),
+        GarbledCircuits::aes128_cbc_encrypt_bytes::<_, F>,
+        ()
+    )
+}
+
+/// Computes the BLAKE2s-256 hash of a shared message using garbled circuits. The message is given as a vector of shared bytes, where each byte is expected to be smaller than 2^8. Only the length of the message is public. The digest is returned as 32 shared bytes.
+pub fn blake2s_bytes<F: PrimeField, N: Rep3Network>(
+    message: &[Rep3PrimeFieldShare<F>],
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<Rep3PrimeFieldShare<F>>> {
+    const DIGEST_SIZE: usize = 32;
+    if message.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "The message of BLAKE2s must not be empty",
+        ));
+    }
+
+    decompose_circuit_compose_blueprint!(
+        message,
+        io_context,
+        DIGEST_SIZE,
+        GarbledCircuits::blake2s_bytes::<_, F>,
+        ()
+    )
+}
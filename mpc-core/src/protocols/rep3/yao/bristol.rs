@@ -0,0 +1,451 @@
+//! Bristol Fashion
+//!
+//! This module contains a parser for boolean circuits in the [Bristol Fashion](https://nigelsmart.github.io/MPC-Circuits/) format and allows to evaluate them with any [FancyBinary] implementation, e.g., the Rep3 garbler and evaluator.
+
+use eyre::{bail, eyre, Context};
+use fancy_garbling::{errors::FancyError, BinaryBundle, FancyBinary};
+use std::{io::BufRead, path::Path};
+
+// Upper bound for preallocating the gates, since the number of gates in the header is not trusted
+const MAX_PREALLOCATED_GATES: usize = 1 << 20;
+
+/// A single gate of a Bristol Fashion circuit. The contained values are wire indices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BristolGate {
+    /// out = in0 xor in1
+    Xor(usize, usize, usize),
+    /// out = in0 and in1
+    And(usize, usize, usize),
+    /// out = not in
+    Inv(usize, usize),
+    /// out = constant
+    Eq(bool, usize),
+    /// out = in
+    EqW(usize, usize),
+    /// out_i = in0_i and in1_i for all i
+    MAnd(Vec<usize>, Vec<usize>, Vec<usize>),
+}
+
+/// A boolean circuit in the Bristol Fashion format.
+///
+/// The input wires of the circuit are the wires 0..num_input_wires, whereas the output wires are the last num_output_wires wires of the circuit. Each input and output value is a group of consecutive wires, where the first wire of a group is the least significant bit of the value.
+///
+/// The circuit is validated when it is parsed: every wire which is not an input wire is assigned by exactly one gate, and no gate reads a wire before it is assigned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BristolFashionCircuit {
+    num_wires: usize,
+    input_sizes: Vec<usize>,
+    output_sizes: Vec<usize>,
+    gates: Vec<BristolGate>,
+}
+
+impl BristolFashionCircuit {
+    /// Reads a Bristol Fashion circuit from a file.
+    pub fn from_file(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("while opening bristol circuit {}", path.display()))?;
+        Self::from_reader(std::io::BufReader::new(file))
+    }
+
+    /// Parses a Bristol Fashion circuit from a reader.
+    pub fn from_reader<R: BufRead>(reader: R) -> eyre::Result<Self> {
+        let mut lines = reader
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| line.as_ref().map_or(true, |l| !l.trim().is_empty()));
+
+        let mut next_line = |what: &str| -> eyre::Result<(usize, Vec<String>)> {
+            let (line_nr, line) = lines
+                .next()
+                .ok_or_else(|| eyre!("unexpected end of file, expected {what}"))?;
+            let line = line?;
+            Ok((
+                line_nr + 1,
+                line.split_whitespace().map(ToOwned::to_owned).collect(),
+            ))
+        };
+
+        let (line_nr, header) = next_line("header")?;
+        if header.len() != 2 {
+            bail!("line {line_nr}: expected <num_gates> <num_wires>");
+        }
+        let num_gates = parse_usize(&header[0], line_nr)?;
+        let num_wires = parse_usize(&header[1], line_nr)?;
+
+        let (line_nr, inputs) = next_line("input description")?;
+        let input_sizes = parse_size_list(&inputs, line_nr)?;
+        let (line_nr, outputs) = next_line("output description")?;
+        let output_sizes = parse_size_list(&outputs, line_nr)?;
+
+        let num_input_wires =
+            checked_sum(&input_sizes).ok_or_else(|| eyre!("number of input wires overflows"))?;
+        let num_output_wires =
+            checked_sum(&output_sizes).ok_or_else(|| eyre!("number of output wires overflows"))?;
+        if num_input_wires > num_wires || num_output_wires > num_wires {
+            bail!("more input or output wires than wires in the circuit");
+        }
+
+        let mut gates = Vec::with_capacity(num_gates.min(MAX_PREALLOCATED_GATES));
+        let mut num_assigned_wires = num_input_wires;
+        for _ in 0..num_gates {
+            let (line_nr, gate) = next_line("gate")?;
+            let gate = parse_gate(&gate, line_nr, num_wires)?;
+            num_assigned_wires = num_assigned_wires
+                .checked_add(gate.outputs().len())
+                .filter(|n| *n <= num_wires)
+                .ok_or_else(|| {
+                    eyre!("line {line_nr}: more wires assigned than declared in header")
+                })?;
+            gates.push(gate);
+        }
+        if let Some((line_nr, _)) = lines.next() {
+            bail!("line {}: more gates than declared in header", line_nr + 1);
+        }
+        if num_assigned_wires != num_wires {
+            bail!("header declares {num_wires} wires, but only {num_assigned_wires} are assigned");
+        }
+
+        let circuit = Self {
+            num_wires,
+            input_sizes,
+            output_sizes,
+            gates,
+        };
+        circuit.validate_wires()?;
+        Ok(circuit)
+    }
+
+    // Checks that each wire is assigned exactly once and only read after it was assigned. Together with the number of assigned wires matching the header, this ensures that all output wires are assigned.
+    //
+    // The number of input wires is taken from the untrusted header, so only the wires assigned by the parsed gates are tracked.
+    fn validate_wires(&self) -> eyre::Result<()> {
+        let num_input_wires = self.num_input_wires();
+        let mut assigned = vec![false; self.num_wires - num_input_wires];
+        let is_assigned = |assigned: &[bool], wire: usize| {
+            wire < num_input_wires || assigned[wire - num_input_wires]
+        };
+
+        for (i, gate) in self.gates.iter().enumerate() {
+            if let Some(wire) = gate
+                .inputs()
+                .into_iter()
+                .find(|wire| !is_assigned(&assigned, *wire))
+            {
+                bail!("gate {i}: wire {wire} is read before it is assigned");
+            }
+            if matches!(gate, BristolGate::Eq(..)) && num_input_wires == 0 {
+                bail!("gate {i}: EQ gates require the circuit to have input wires");
+            }
+            for wire in gate.outputs() {
+                if *wire < num_input_wires
+                    || std::mem::replace(&mut assigned[*wire - num_input_wires], true)
+                {
+                    bail!("gate {i}: wire {wire} is assigned more than once");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the total number of wires of the circuit.
+    pub fn num_wires(&self) -> usize {
+        self.num_wires
+    }
+
+    /// Returns the bit sizes of the input values.
+    pub fn input_sizes(&self) -> &[usize] {
+        &self.input_sizes
+    }
+
+    /// Returns the bit sizes of the output values.
+    pub fn output_sizes(&self) -> &[usize] {
+        &self.output_sizes
+    }
+
+    /// Returns the gates of the circuit.
+    pub fn gates(&self) -> &[BristolGate] {
+        &self.gates
+    }
+
+    /// Returns the total number of input wires.
+    pub fn num_input_wires(&self) -> usize {
+        self.input_sizes.iter().sum()
+    }
+
+    /// Returns the total number of output wires.
+    pub fn num_output_wires(&self) -> usize {
+        self.output_sizes.iter().sum()
+    }
+
+    /// Returns the number of AND gates (including the ones in MAND gates), which determines the size of the garbled circuit.
+    pub fn num_and_gates(&self) -> usize {
+        self.gates
+            .iter()
+            .map(|g| match g {
+                BristolGate::And(..) => 1,
+                BristolGate::MAnd(_, _, out) => out.len(),
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Evaluates the circuit using the provided [FancyBinary] implementation. The inputs are the concatenation of all input values, the outputs are the concatenation of all output values.
+    ///
+    /// Returns an error if the number of inputs does not match [Self::num_input_wires].
+    pub fn evaluate<G: FancyBinary>(
+        &self,
+        g: &mut G,
+        inputs: &BinaryBundle<G::Item>,
+    ) -> Result<BinaryBundle<G::Item>, G::Error> {
+        if inputs.size() != self.num_input_wires() {
+            return Err(FancyError::InvalidArgNum {
+                got: inputs.size(),
+                needed: self.num_input_wires(),
+            }
+            .into());
+        }
+        // the wires are backed by the provided inputs and the parsed gates, since the parser checks that the gates assign all other wires
+        let mut wires: Vec<Option<G::Item>> = vec![None; self.num_wires];
+        for (wire, input) in wires.iter_mut().zip(inputs.wires().iter()) {
+            *wire = Some(input.to_owned());
+        }
+
+        let get = |wires: &[Option<G::Item>], idx: usize| -> G::Item {
+            wires[idx]
+                .to_owned()
+                .expect("the circuit is validated on construction")
+        };
+
+        for gate in self.gates.iter() {
+            match gate {
+                BristolGate::Xor(a, b, out) => {
+                    let res = g.xor(&get(&wires, *a), &get(&wires, *b))?;
+                    wires[*out] = Some(res);
+                }
+                BristolGate::And(a, b, out) => {
+                    let res = g.and(&get(&wires, *a), &get(&wires, *b))?;
+                    wires[*out] = Some(res);
+                }
+                BristolGate::Inv(a, out) => {
+                    let res = g.negate(&get(&wires, *a))?;
+                    wires[*out] = Some(res);
+                }
+                BristolGate::EqW(a, out) => {
+                    wires[*out] = Some(get(&wires, *a));
+                }
+                BristolGate::Eq(val, out) => {
+                    // A wire xored with itself is a free constant zero
+                    let any = get(&wires, 0);
+                    let zero = g.xor(&any, &any)?;
+                    let res = if *val { g.negate(&zero)? } else { zero };
+                    wires[*out] = Some(res);
+                }
+                BristolGate::MAnd(a, b, out) => {
+                    for (a, b, out) in itertools::izip!(a, b, out) {
+                        let res = g.and(&get(&wires, *a), &get(&wires, *b))?;
+                        wires[*out] = Some(res);
+                    }
+                }
+            }
+        }
+
+        let output_offset = self.num_wires - self.num_output_wires();
+        let outputs = wires[output_offset..]
+            .iter()
+            .map(|w| {
+                w.to_owned()
+                    .expect("the circuit is validated on construction")
+            })
+            .collect();
+        Ok(BinaryBundle::new(outputs))
+    }
+}
+
+impl BristolGate {
+    fn inputs(&self) -> Vec<usize> {
+        match self {
+            BristolGate::Xor(a, b, _) | BristolGate::And(a, b, _) => vec![*a, *b],
+            BristolGate::Inv(a, _) | BristolGate::EqW(a, _) => vec![*a],
+            BristolGate::Eq(..) => vec![],
+            BristolGate::MAnd(a, b, _) => a.iter().chain(b.iter()).copied().collect(),
+        }
+    }
+
+    fn outputs(&self) -> &[usize] {
+        match self {
+            BristolGate::Xor(_, _, out)
+            | BristolGate::And(_, _, out)
+            | BristolGate::Inv(_, out)
+            | BristolGate::Eq(_, out)
+            | BristolGate::EqW(_, out) => std::slice::from_ref(out),
+            BristolGate::MAnd(_, _, out) => out,
+        }
+    }
+}
+
+fn checked_sum(sizes: &[usize]) -> Option<usize> {
+    sizes
+        .iter()
+        .try_fold(0usize, |acc, size| acc.checked_add(*size))
+}
+
+fn parse_usize(s: &str, line_nr: usize) -> eyre::Result<usize> {
+    s.parse()
+        .with_context(|| format!("line {line_nr}: expected a number, got {s}"))
+}
+
+fn parse_size_list(tokens: &[String], line_nr: usize) -> eyre::Result<Vec<usize>> {
+    let (len, sizes) = tokens
+        .split_first()
+        .ok_or_else(|| eyre!("line {line_nr}: empty input/output description"))?;
+    let len = parse_usize(len, line_nr)?;
+    if sizes.len() != len {
+        bail!("line {line_nr}: expected {len} sizes, got {}", sizes.len());
+    }
+    sizes.iter().map(|s| parse_usize(s, line_nr)).collect()
+}
+
+fn parse_gate(tokens: &[String], line_nr: usize, num_wires: usize) -> eyre::Result<BristolGate> {
+    if tokens.len() < 3 {
+        bail!("line {line_nr}: malformed gate");
+    }
+    let num_in = parse_usize(&tokens[0], line_nr)?;
+    let num_out = parse_usize(&tokens[1], line_nr)?;
+    let num_gate_wires = num_in
+        .checked_add(num_out)
+        .ok_or_else(|| eyre!("line {line_nr}: number of gate wires overflows"))?;
+    if tokens.len() - 3 != num_gate_wires {
+        bail!("line {line_nr}: gate has wrong number of wires");
+    }
+    let gate_type = tokens.last().expect("checked above").as_str();
+
+    // EQ gates have a constant instead of an input wire
+    if gate_type == "EQ" {
+        if num_in != 1 || num_out != 1 {
+            bail!("line {line_nr}: EQ gate must have one input and one output");
+        }
+        let val = match tokens[2].as_str() {
+            "0" => false,
+            "1" => true,
+            x => bail!("line {line_nr}: EQ gate expects constant 0 or 1, got {x}"),
+        };
+        let out = parse_wire(&tokens[3], line_nr, num_wires)?;
+        return Ok(BristolGate::Eq(val, out));
+    }
+
+    let wires = tokens[2..2 + num_gate_wires]
+        .iter()
+        .map(|t| parse_wire(t, line_nr, num_wires))
+        .collect::<eyre::Result<Vec<_>>>()?;
+    let (ins, outs) = wires.split_at(num_in);
+
+    let gate = match (gate_type, num_in, num_out) {
+        ("XOR", 2, 1) => BristolGate::Xor(ins[0], ins[1], outs[0]),
+        ("AND", 2, 1) => BristolGate::And(ins[0], ins[1], outs[0]),
+        ("INV", 1, 1) | ("NOT", 1, 1) => BristolGate::Inv(ins[0], outs[0]),
+        ("EQW", 1, 1) => BristolGate::EqW(ins[0], outs[0]),
+        ("MAND", _, _) if num_out.checked_mul(2) == Some(num_in) => {
+            let (a, b) = ins.split_at(num_out);
+            BristolGate::MAnd(a.to_vec(), b.to_vec(), outs.to_vec())
+        }
+        (gate_type, num_in, num_out) => bail!(
+            "line {line_nr}: unsupported gate {gate_type} with {num_in} inputs and {num_out} outputs"
+        ),
+    };
+    Ok(gate)
+}
+
+fn parse_wire(s: &str, line_nr: usize, num_wires: usize) -> eyre::Result<usize> {
+    let wire = parse_usize(s, line_nr)?;
+    if wire >= num_wires {
+        bail!("line {line_nr}: wire {wire} out of range");
+    }
+    Ok(wire)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const HEADER: &str = "2 1 1\n1 1\n";
+
+    fn parse(circuit: &str) -> eyre::Result<BristolFashionCircuit> {
+        BristolFashionCircuit::from_reader(circuit.as_bytes())
+    }
+
+    #[test]
+    fn parses_valid_circuit() {
+        let circuit = parse(&format!("2 4\n{HEADER}2 1 0 1 2 AND\n1 1 2 3 INV\n")).unwrap();
+        assert_eq!(circuit.num_input_wires(), 2);
+        assert_eq!(circuit.num_output_wires(), 1);
+        assert_eq!(
+            circuit.gates(),
+            [BristolGate::And(0, 1, 2), BristolGate::Inv(2, 3)]
+        );
+    }
+
+    #[test]
+    fn rejects_wire_out_of_range() {
+        assert!(parse(&format!("2 4\n{HEADER}2 1 0 1 2 AND\n1 1 2 4 INV\n")).is_err());
+    }
+
+    #[test]
+    fn rejects_read_before_assign() {
+        assert!(parse(&format!("2 4\n{HEADER}2 1 0 3 2 AND\n1 1 2 3 INV\n")).is_err());
+    }
+
+    #[test]
+    fn rejects_double_assignment() {
+        // overwriting an input wire
+        assert!(parse(&format!("2 4\n{HEADER}2 1 0 1 1 AND\n1 1 1 3 INV\n")).is_err());
+        // assigning an intermediate wire twice
+        assert!(parse(&format!("2 4\n{HEADER}2 1 0 1 2 AND\n1 1 2 2 INV\n")).is_err());
+    }
+
+    #[test]
+    fn rejects_unassigned_wires() {
+        // wire 2 is never assigned, so the output wire 3 is the only gate output
+        assert!(parse(&format!("1 4\n{HEADER}2 1 0 1 3 AND\n")).is_err());
+    }
+
+    #[test]
+    fn rejects_wrong_number_of_gates() {
+        assert!(parse(&format!("3 4\n{HEADER}2 1 0 1 2 AND\n1 1 2 3 INV\n")).is_err());
+        assert!(parse(&format!("1 4\n{HEADER}2 1 0 1 2 AND\n1 1 2 3 INV\n")).is_err());
+    }
+
+    #[test]
+    fn rejects_huge_header_without_allocating() {
+        let max = usize::MAX;
+        assert!(parse(&format!("{max} 4\n{HEADER}2 1 0 1 2 AND\n1 1 2 3 INV\n")).is_err());
+        assert!(parse(&format!("2 {max}\n{HEADER}2 1 0 1 2 AND\n1 1 2 3 INV\n")).is_err());
+    }
+
+    #[test]
+    fn huge_input_sizes_do_not_allocate_wires() {
+        // the input wires are the output wires, so no gate assigns a wire
+        let huge = usize::MAX / 2;
+        let circuit = parse(&format!("0 {huge}\n1 {huge}\n1 {huge}\n")).unwrap();
+        assert_eq!(circuit.num_input_wires(), huge);
+        assert_eq!(circuit.num_output_wires(), huge);
+        assert!(parse(&format!("1 {huge}\n1 {huge}\n1 {huge}\n1 1 0 0 INV\n")).is_err());
+    }
+
+    #[test]
+    fn rejects_overflowing_sizes() {
+        let max = usize::MAX;
+        assert!(parse(&format!("0 {max}\n2 {max} 1\n1 1\n")).is_err());
+        assert!(parse(&format!("0 {max}\n1 1\n2 {max} 1\n")).is_err());
+        assert!(parse(&format!("1 4\n{HEADER}{max} 1 0 1 2 AND\n")).is_err());
+        assert!(parse(&format!("1 4\n{HEADER}{max} {max} 0 1 2 MAND\n")).is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_eq_gates() {
+        assert!(parse(&format!("2 4\n{HEADER}1 1 2 2 EQ\n1 1 2 3 INV\n")).is_err());
+        assert!(parse(&format!("2 4\n{HEADER}1 1 0 2 3 EQ\n1 1 2 3 INV\n")).is_err());
+        // a circuit without inputs has no wire to derive the constant from
+        assert!(parse("1 1\n0\n1 1\n1 1 1 0 EQ\n").is_err());
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! This module contains some garbled circuit implementations.
 
-use crate::protocols::rep3::yao::GCUtils;
+use crate::protocols::rep3::yao::{BigIntModOp, GCUtils};
 use ark_ff::PrimeField;
 use fancy_garbling::{BinaryBundle, FancyBinary};
 use itertools::izip;
@@ -158,7 +158,7 @@ impl GarbledCircuits {
 
     /// Binary subtraction. Returns the result and whether it underflowed.
     /// I.e., calculates 2^k + x1 - x2
-    #[expect(dead_code, clippy::type_complexity)]
+    #[expect(clippy::type_complexity)]
     fn bin_subtraction<G: FancyBinary>(
         g: &mut G,
         xs: &[G::Item],
@@ -940,6 +940,149 @@ impl GarbledCircuits {
         Ok(result)
     }
 
+    /// Checks whether a field element is zero. The field element is represented as two bitdecompositions wires_a, wires_b which need to be added first. The output bit (1 if the field element is zero) is composed to a field element using wires_c, whereas wires_c are the same size as wires_a and wires_b
+    fn field_is_zero<G: FancyBinary, F: PrimeField>(
+        g: &mut G,
+        wires_a: &[G::Item],
+        wires_b: &[G::Item],
+        wires_c: &[G::Item],
+    ) -> Result<Vec<G::Item>, G::Error> {
+        let input_bitlen = wires_a.len();
+        debug_assert_eq!(input_bitlen, F::MODULUS_BIT_SIZE as usize);
+        debug_assert_eq!(input_bitlen, wires_b.len());
+        debug_assert_eq!(input_bitlen, wires_c.len());
+
+        // Add wires_a and wires_b to get the input bits as Yao wires
+        let (added, carry_add) = Self::bin_addition(g, wires_a, wires_b)?;
+        let (subtracted, ov) = Self::sub_p::<_, F>(g, &added, carry_add)?;
+
+        // the input is zero iff none of the bits of the reduced input is set
+        let mut is_zero: Option<G::Item> = None;
+        for (s, a) in subtracted.iter().zip(added.iter()) {
+            // CMUX
+            let bit = g.mux(&ov, s, a)?;
+            let not_bit = g.negate(&bit)?;
+            is_zero = Some(match is_zero {
+                Some(is_zero) => g.and(&is_zero, &not_bit)?,
+                None => not_bit,
+            });
+        }
+        let is_zero = is_zero.expect("modulus has at least one bit");
+
+        // compose the bit to a field element
+        Self::compose_field_element::<G, F>(g, &[is_zero], wires_c)
+    }
+
+    /// Checks whether field elements are zero, see [Self::field_is_zero]. The field elements are represented as two bitdecompositions wires_a, wires_b which need to be added first. The output bits are composed using wires_c, whereas wires_c are the same size as wires_a and wires_b
+    pub(crate) fn field_is_zero_many<G: FancyBinary, F: PrimeField>(
+        g: &mut G,
+        wires_a: &BinaryBundle<G::Item>,
+        wires_b: &BinaryBundle<G::Item>,
+        wires_c: &BinaryBundle<G::Item>,
+    ) -> Result<BinaryBundle<G::Item>, G::Error> {
+        debug_assert_eq!(wires_a.size(), wires_b.size());
+        let input_size = wires_a.size();
+        let input_bitlen = F::MODULUS_BIT_SIZE as usize;
+
+        debug_assert_eq!(input_size % input_bitlen, 0);
+        debug_assert_eq!(wires_c.size(), input_size);
+
+        let mut results = Vec::with_capacity(wires_c.size());
+
+        for (chunk_a, chunk_b, chunk_c) in izip!(
+            wires_a.wires().chunks(input_bitlen),
+            wires_b.wires().chunks(input_bitlen),
+            wires_c.wires().chunks(input_bitlen),
+        ) {
+            results.extend(Self::field_is_zero::<G, F>(g, chunk_a, chunk_b, chunk_c)?);
+        }
+
+        Ok(BinaryBundle::new(results))
+    }
+
+    /// Divides field elements, interpreted as integers, by a public divisor. The field elements are represented as two bitdecompositions wires_a, wires_b which need to be added first. The quotients are composed using the first half of wires_c, the remainders using the second half, whereas each half of wires_c is the same size as wires_a and wires_b
+    pub(crate) fn field_int_div_rem_public_many<G: FancyBinary, F: PrimeField>(
+        g: &mut G,
+        wires_a: &BinaryBundle<G::Item>,
+        wires_b: &BinaryBundle<G::Item>,
+        wires_c: &BinaryBundle<G::Item>,
+        divisor: &BigUint,
+    ) -> Result<BinaryBundle<G::Item>, G::Error> {
+        debug_assert_eq!(wires_a.size(), wires_b.size());
+        let input_size = wires_a.size();
+        let input_bitlen = F::MODULUS_BIT_SIZE as usize;
+
+        debug_assert_eq!(input_size % input_bitlen, 0);
+        debug_assert_eq!(wires_c.size(), 2 * input_size);
+
+        let (wires_c_quotient, wires_c_rem) = wires_c.wires().split_at(input_size);
+        let mut quotients = Vec::with_capacity(input_size);
+        let mut rems = Vec::with_capacity(input_size);
+
+        for (chunk_a, chunk_b, chunk_c_quotient, chunk_c_rem) in izip!(
+            wires_a.wires().chunks(input_bitlen),
+            wires_b.wires().chunks(input_bitlen),
+            wires_c_quotient.chunks(input_bitlen),
+            wires_c_rem.chunks(input_bitlen),
+        ) {
+            // Add chunk_a and chunk_b to get the input bits as Yao wires
+            let (added, carry_add) = Self::bin_addition(g, chunk_a, chunk_b)?;
+            let (subtracted, ov) = Self::sub_p::<_, F>(g, &added, carry_add)?;
+            let input_bits = izip!(subtracted.iter(), added.iter())
+                .map(|(s, a)| g.mux(&ov, s, a))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let (quotient, rem) = Self::bigint_div_rem_public(g, &input_bits, divisor)?;
+            quotients.extend(Self::compose_field_element::<G, F>(
+                g,
+                &quotient,
+                chunk_c_quotient,
+            )?);
+            rems.extend(Self::compose_field_element::<G, F>(g, &rem, chunk_c_rem)?);
+        }
+        quotients.extend(rems);
+
+        Ok(BinaryBundle::new(quotients))
+    }
+
+    /// Divides ring elements by a public divisor. The ring elements are represented as two bitdecompositions wires_a, wires_b which need to be added first. The quotients are composed using the first half of wires_c, the remainders using the second half, whereas each half of wires_c is the same size as wires_a and wires_b
+    pub(crate) fn ring_div_rem_public_many<G: FancyBinary>(
+        g: &mut G,
+        wires_a: &BinaryBundle<G::Item>,
+        wires_b: &BinaryBundle<G::Item>,
+        wires_c: &BinaryBundle<G::Item>,
+        input_bitlen: usize,
+        divisor: &BigUint,
+    ) -> Result<BinaryBundle<G::Item>, G::Error> {
+        debug_assert_eq!(wires_a.size(), wires_b.size());
+        let input_size = wires_a.size();
+
+        debug_assert_eq!(input_size % input_bitlen, 0);
+        debug_assert!(divisor.bits() as usize <= input_bitlen);
+        debug_assert_eq!(wires_c.size(), 2 * input_size);
+
+        let (wires_c_quotient, wires_c_rem) = wires_c.wires().split_at(input_size);
+        let mut quotients = Vec::with_capacity(input_size);
+        let mut rems = Vec::with_capacity(input_size);
+
+        for (chunk_a, chunk_b, chunk_c_quotient, chunk_c_rem) in izip!(
+            wires_a.wires().chunks(input_bitlen),
+            wires_b.wires().chunks(input_bitlen),
+            wires_c_quotient.chunks(input_bitlen),
+            wires_c_rem.chunks(input_bitlen),
+        ) {
+            // Add chunk_a and chunk_b to get the input bits as Yao wires
+            let input_bits = Self::bin_addition_no_carry(g, chunk_a, chunk_b)?;
+
+            let (quotient, rem) = Self::bigint_div_rem_public(g, &input_bits, divisor)?;
+            quotients.extend(Self::compose_ring_element(g, &quotient, chunk_c_quotient)?);
+            rems.extend(Self::compose_ring_element(g, &rem, chunk_c_rem)?);
+        }
+        quotients.extend(rems);
+
+        Ok(BinaryBundle::new(quotients))
+    }
+
     /// Divides a ring element by a power of 2. The ring element is represented as two bitdecompositions wires_a, wires_b which need to be added first. The output is composed using wires_c, whereas wires_c are the same size as wires_a and wires_b
     pub(crate) fn ring_div_power_2_many<G: FancyBinary>(
         g: &mut G,
@@ -1017,6 +1160,893 @@ impl GarbledCircuits {
 
         Ok(BinaryBundle::new(results))
     }
+
+    /// XORs a wire onto an optional wire, where `None` represents a constant zero wire.
+    fn xor_optional<G: FancyBinary>(
+        g: &mut G,
+        a: Option<G::Item>,
+        b: &G::Item,
+    ) -> Result<G::Item, G::Error> {
+        match a {
+            Some(a) => g.xor(&a, b),
+            None => Ok(b.to_owned()),
+        }
+    }
+
+    /// Reduces a polynomial over GF(2) of degree at most 14 modulo the AES polynomial x^8 + x^4 + x^3 + x + 1. Coefficients which are `None` are treated as zero. Only requires XOR gates.
+    fn gf256_reduce<G: FancyBinary>(
+        g: &mut G,
+        mut coeffs: Vec<Option<G::Item>>,
+    ) -> Result<Vec<G::Item>, G::Error> {
+        debug_assert_eq!(coeffs.len(), 15);
+        // x^k = x^(k-8) * (x^4 + x^3 + x + 1)
+        for k in (8..15).rev() {
+            if let Some(c) = coeffs[k].take() {
+                for pos in [k - 4, k - 5, k - 7, k - 8] {
+                    let prev = coeffs[pos].take();
+                    coeffs[pos] = Some(Self::xor_optional(g, prev, &c)?);
+                }
+            }
+        }
+        coeffs.truncate(8);
+        Ok(coeffs
+            .into_iter()
+            .map(|c| c.expect("Reduced GF(2^8) coefficients are never constant zero"))
+            .collect())
+    }
+
+    /// Multiplies two elements of GF(2^8) (LSB first) using the AES polynomial. Requires 64 AND gates.
+    fn gf256_mul<G: FancyBinary>(
+        g: &mut G,
+        a: &[G::Item],
+        b: &[G::Item],
+    ) -> Result<Vec<G::Item>, G::Error> {
+        debug_assert_eq!(a.len(), 8);
+        debug_assert_eq!(b.len(), 8);
+        let mut coeffs: Vec<Option<G::Item>> = vec![None; 15];
+        for (i, a_) in a.iter().enumerate() {
+            for (j, b_) in b.iter().enumerate() {
+                let prod = g.and(a_, b_)?;
+                let prev = coeffs[i + j].take();
+                coeffs[i + j] = Some(Self::xor_optional(g, prev, &prod)?);
+            }
+        }
+        Self::gf256_reduce(g, coeffs)
+    }
+
+    /// Squares an element of GF(2^8) (LSB first) using the AES polynomial. Squaring is linear in GF(2^8) and thus only requires XOR gates.
+    fn gf256_square<G: FancyBinary>(g: &mut G, a: &[G::Item]) -> Result<Vec<G::Item>, G::Error> {
+        debug_assert_eq!(a.len(), 8);
+        let mut coeffs: Vec<Option<G::Item>> = vec![None; 15];
+        for (i, a_) in a.iter().enumerate() {
+            coeffs[2 * i] = Some(a_.to_owned());
+        }
+        Self::gf256_reduce(g, coeffs)
+    }
+
+    /// Computes the AES S-box of a byte (LSB first). The multiplicative inverse is computed as a^254 using 4 multiplications in GF(2^8), followed by the affine transformation of the S-box.
+    fn aes_sbox<G: FancyBinary>(g: &mut G, a: &[G::Item]) -> Result<Vec<G::Item>, G::Error> {
+        debug_assert_eq!(a.len(), 8);
+        let a2 = Self::gf256_square(g, a)?;
+        let a3 = Self::gf256_mul(g, &a2, a)?;
+        let a6 = Self::gf256_square(g, &a3)?;
+        let a12 = Self::gf256_square(g, &a6)?;
+        let a14 = Self::gf256_mul(g, &a12, &a2)?;
+        let a15 = Self::gf256_mul(g, &a12, &a3)?;
+        let mut a240 = a15;
+        for _ in 0..4 {
+            a240 = Self::gf256_square(g, &a240)?;
+        }
+        let inv = Self::gf256_mul(g, &a240, &a14)?;
+
+        // Affine transformation: b_i = x_i ^ x_{i+4} ^ x_{i+5} ^ x_{i+6} ^ x_{i+7} ^ c_i with c = 0x63
+        let mut result = Vec::with_capacity(8);
+        for i in 0..8 {
+            let mut b = g.xor(&inv[i], &inv[(i + 4) % 8])?;
+            for k in 5..8 {
+                b = g.xor(&b, &inv[(i + k) % 8])?;
+            }
+            if (0x63 >> i) & 1 == 1 {
+                b = g.negate(&b)?;
+            }
+            result.push(b);
+        }
+        Ok(result)
+    }
+
+    /// Multiplies a byte (LSB first) by x in GF(2^8). Only requires XOR gates.
+    fn aes_xtime<G: FancyBinary>(g: &mut G, a: &[G::Item]) -> Result<Vec<G::Item>, G::Error> {
+        debug_assert_eq!(a.len(), 8);
+        Ok(vec![
+            a[7].to_owned(),
+            g.xor(&a[0], &a[7])?,
+            a[1].to_owned(),
+            g.xor(&a[2], &a[7])?,
+            g.xor(&a[3], &a[7])?,
+            a[4].to_owned(),
+            a[5].to_owned(),
+            a[6].to_owned(),
+        ])
+    }
+
+    /// XORs two slices of wires of the same length.
+    fn xor_slices<G: FancyBinary>(
+        g: &mut G,
+        a: &[G::Item],
+        b: &[G::Item],
+    ) -> Result<Vec<G::Item>, G::Error> {
+        debug_assert_eq!(a.len(), b.len());
+        a.iter().zip(b.iter()).map(|(a, b)| g.xor(a, b)).collect()
+    }
+
+    /// Expands a 128-bit AES key into the 11 round keys of AES-128. The key is given as the 16 key bytes in order, each byte LSB first. The output contains 11 * 128 wires in the same encoding.
+    pub(crate) fn aes128_key_expansion<G: FancyBinary>(
+        g: &mut G,
+        key: &[G::Item],
+    ) -> Result<Vec<G::Item>, G::Error> {
+        debug_assert_eq!(key.len(), 128);
+        const RCON: [u8; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
+
+        // The expanded key as 44 words of 32 bits
+        let mut words: Vec<Vec<G::Item>> = key.chunks(32).map(|w| w.to_vec()).collect();
+        for i in 4..44 {
+            let prev = &words[i - 1];
+            let temp = if i % 4 == 0 {
+                // RotWord followed by SubWord
+                let mut temp = Vec::with_capacity(32);
+                for byte in 0..4 {
+                    let rotated = &prev[((byte + 1) % 4) * 8..((byte + 1) % 4 + 1) * 8];
+                    temp.extend(Self::aes_sbox(g, rotated)?);
+                }
+                // XOR the round constant into the first byte
+                let rcon = RCON[i / 4 - 1];
+                for (bit, t) in temp.iter_mut().take(8).enumerate() {
+                    if (rcon >> bit) & 1 == 1 {
+                        *t = g.negate(t)?;
+                    }
+                }
+                temp
+            } else {
+                prev.to_owned()
+            };
+            let word = Self::xor_slices(g, &words[i - 4], &temp)?;
+            words.push(word);
+        }
+
+        Ok(words.into_iter().flatten().collect())
+    }
+
+    /// Encrypts a single 128-bit block with AES-128 given the already expanded round keys (see [Self::aes128_key_expansion]). The block is given as the 16 bytes in order, each byte LSB first.
+    pub(crate) fn aes128_encrypt_block<G: FancyBinary>(
+        g: &mut G,
+        round_keys: &[G::Item],
+        block: &[G::Item],
+    ) -> Result<Vec<G::Item>, G::Error> {
+        debug_assert_eq!(round_keys.len(), 11 * 128);
+        debug_assert_eq!(block.len(), 128);
+
+        let mut state = Self::xor_slices(g, block, &round_keys[..128])?;
+
+        for (round, round_key) in round_keys.chunks(128).enumerate().skip(1) {
+            // SubBytes
+            let mut sub = Vec::with_capacity(16);
+            for byte in state.chunks(8) {
+                sub.push(Self::aes_sbox(g, byte)?);
+            }
+
+            // ShiftRows: the state is stored column-major, i.e., byte r + 4c is in row r and column c
+            let shifted = (0..16)
+                .map(|i| {
+                    let (r, c) = (i % 4, i / 4);
+                    sub[r + 4 * ((c + r) % 4)].to_owned()
+                })
+                .collect::<Vec<_>>();
+
+            // MixColumns, omitted in the last round
+            let mixed = if round == 10 {
+                shifted
+            } else {
+                let mut mixed = Vec::with_capacity(16);
+                for col in shifted.chunks(4) {
+                    let doubled = col
+                        .iter()
+                        .map(|a| Self::aes_xtime(g, a))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    for r in 0..4 {
+                        // b_r = 2 * a_r + 3 * a_{r+1} + a_{r+2} + a_{r+3}
+                        let mut b = Self::xor_slices(g, &doubled[r], &doubled[(r + 1) % 4])?;
+                        b = Self::xor_slices(g, &b, &col[(r + 1) % 4])?;
+                        b = Self::xor_slices(g, &b, &col[(r + 2) % 4])?;
+                        b = Self::xor_slices(g, &b, &col[(r + 3) % 4])?;
+                        mixed.push(b);
+                    }
+                }
+                mixed
+            };
+
+            // AddRoundKey
+            let mixed = mixed.into_iter().flatten().collect::<Vec<_>>();
+            state = Self::xor_slices(g, &mixed, round_key)?;
+        }
+
+        Ok(state)
+    }
+
+    /// Encrypts a vector of 128-bit blocks with AES-128 under the same key. The key is expanded only once. Key and blocks are given as 16 bytes in order, each byte LSB first, i.e., they are the little-endian bit decompositions of the 128-bit values whose little-endian byte encoding is the AES input. Each S-box requires 256 AND gates.
+    pub fn aes128_encrypt_many<G: FancyBinary>(
+        g: &mut G,
+        key: &BinaryBundle<G::Item>,
+        blocks: &BinaryBundle<G::Item>,
+    ) -> Result<BinaryBundle<G::Item>, G::Error> {
+        debug_assert_eq!(key.size(), 128);
+        debug_assert_eq!(blocks.size() % 128, 0);
+
+        let round_keys = Self::aes128_key_expansion(g, key.wires())?;
+
+        let mut results = Vec::with_capacity(blocks.size());
+        for block in blocks.wires().chunks(128) {
+            results.extend(Self::aes128_encrypt_block(g, &round_keys, block)?);
+        }
+
+        Ok(BinaryBundle::new(results))
+    }
+
+    /// Returns a wire with a constant value. Since xoring a wire with itself is a free constant zero, the constant is derived from an arbitrary wire.
+    fn constant_wire<G: FancyBinary>(
+        g: &mut G,
+        any: &G::Item,
+        value: bool,
+    ) -> Result<G::Item, G::Error> {
+        let zero = g.xor(any, any)?;
+        if value {
+            g.negate(&zero)
+        } else {
+            Ok(zero)
+        }
+    }
+
+    /// Returns bitlen wires (LSB first) encoding a public constant. See [Self::constant_wire].
+    fn constant_wires<G: FancyBinary>(
+        g: &mut G,
+        any: &G::Item,
+        value: &BigUint,
+        bitlen: usize,
+    ) -> Result<Vec<G::Item>, G::Error> {
+        let zero = Self::constant_wire(g, any, false)?;
+        let one = g.negate(&zero)?;
+        Ok(GCUtils::biguint_to_bits(value.to_owned(), bitlen)
+            .into_iter()
+            .map(|bit| if bit { one.to_owned() } else { zero.to_owned() })
+            .collect())
+    }
+
+    /// Subtracts the public modulus from xs if xs >= modulus.
+    fn bigint_cond_sub_modulus<G: FancyBinary>(
+        g: &mut G,
+        xs: &[G::Item],
+        modulus: &BigUint,
+    ) -> Result<Vec<G::Item>, G::Error> {
+        let (result, _) = Self::bigint_cond_sub_modulus_with_flag(g, xs, modulus)?;
+        Ok(result)
+    }
+
+    /// Subtracts the public modulus from xs if xs >= modulus. Additionally returns whether the modulus was subtracted.
+    #[expect(clippy::type_complexity)]
+    fn bigint_cond_sub_modulus_with_flag<G: FancyBinary>(
+        g: &mut G,
+        xs: &[G::Item],
+        modulus: &BigUint,
+    ) -> Result<(Vec<G::Item>, G::Item), G::Error> {
+        let modulus_wires = Self::constant_wires(g, &xs[0], modulus, xs.len())?;
+        let (subtracted, no_underflow) = Self::bin_subtraction(g, xs, &modulus_wires)?;
+        let result = izip!(subtracted.iter(), xs.iter())
+            .map(|(s, x)| g.mux(&no_underflow, x, s))
+            .collect::<Result<_, _>>()?;
+        Ok((result, no_underflow))
+    }
+
+    /// Divides an arbitrary sized integer xs (LSB first) by a public divisor using a restoring division. Returns the quotient, which has exactly xs.len() wires, and the remainder, which has exactly divisor.bits() wires.
+    #[expect(clippy::type_complexity)]
+    fn bigint_div_rem_public<G: FancyBinary>(
+        g: &mut G,
+        xs: &[G::Item],
+        divisor: &BigUint,
+    ) -> Result<(Vec<G::Item>, Vec<G::Item>), G::Error> {
+        debug_assert!(divisor.bits() > 0);
+        let divisor_bits = divisor.bits() as usize;
+        let bitlen = xs.len();
+        let zero = Self::constant_wire(g, &xs[0], false)?;
+        let mut quotient = vec![zero.to_owned(); bitlen];
+        let mut rem = xs.to_vec();
+        if bitlen < divisor_bits {
+            // Inputs with fewer bits than the divisor are smaller than the divisor
+            rem.resize(divisor_bits, zero);
+            return Ok((quotient, rem));
+        }
+
+        // Same invariant as in bigint_reduce, the flag of each conditional subtraction is a bit of the quotient
+        for shift in (0..=bitlen - divisor_bits).rev() {
+            let end = (shift + divisor_bits + 1).min(bitlen);
+            let (reduced, subtracted) =
+                Self::bigint_cond_sub_modulus_with_flag(g, &rem[shift..end], divisor)?;
+            rem.splice(shift..end, reduced);
+            quotient[shift] = subtracted;
+        }
+        rem.truncate(divisor_bits);
+        Ok((quotient, rem))
+    }
+
+    /// Composes a ring element with the lower bits xs using the random bits rand_wires, i.e., computes xs + rand_wires in the ring. xs must not have more wires than rand_wires.
+    fn compose_ring_element<G: FancyBinary>(
+        g: &mut G,
+        xs: &[G::Item],
+        rand_wires: &[G::Item],
+    ) -> Result<Vec<G::Item>, G::Error> {
+        debug_assert!(xs.len() <= rand_wires.len());
+        let mut xs = xs.to_vec();
+        let zero = Self::constant_wire(g, &rand_wires[0], false)?;
+        xs.resize(rand_wires.len(), zero);
+        Self::bin_addition_no_carry(g, &xs, rand_wires)
+    }
+
+    /// Reduces an arbitrary sized integer xs (LSB first) modulo a public modulus using a restoring division. The result has exactly modulus.bits() wires.
+    fn bigint_reduce<G: FancyBinary>(
+        g: &mut G,
+        xs: &[G::Item],
+        modulus: &BigUint,
+    ) -> Result<Vec<G::Item>, G::Error> {
+        let modulus_bits = modulus.bits() as usize;
+        let mut rem = xs.to_vec();
+        if rem.len() < modulus_bits {
+            // Inputs with fewer bits than the modulus are already reduced
+            let zero = Self::constant_wire(g, &xs[0], false)?;
+            rem.resize(modulus_bits, zero);
+            return Ok(rem);
+        }
+
+        // Invariant: before subtracting modulus * 2^shift, rem < modulus * 2^(shift + 1), thus only modulus_bits + 1 wires starting at shift are affected
+        let bitlen = rem.len();
+        for shift in (0..=bitlen - modulus_bits).rev() {
+            let end = (shift + modulus_bits + 1).min(bitlen);
+            let reduced = Self::bigint_cond_sub_modulus(g, &rem[shift..end], modulus)?;
+            rem.splice(shift..end, reduced);
+        }
+        rem.truncate(modulus_bits);
+        Ok(rem)
+    }
+
+    /// Adds two integers which are already reduced modulo the public modulus.
+    fn bigint_add_mod<G: FancyBinary>(
+        g: &mut G,
+        a: &[G::Item],
+        b: &[G::Item],
+        modulus: &BigUint,
+    ) -> Result<Vec<G::Item>, G::Error> {
+        let (mut added, carry) = Self::bin_addition(g, a, b)?;
+        added.push(carry);
+        let mut result = Self::bigint_cond_sub_modulus(g, &added, modulus)?;
+        result.truncate(a.len());
+        Ok(result)
+    }
+
+    /// Subtracts two integers which are already reduced modulo the public modulus.
+    fn bigint_sub_mod<G: FancyBinary>(
+        g: &mut G,
+        a: &[G::Item],
+        b: &[G::Item],
+        modulus: &BigUint,
+    ) -> Result<Vec<G::Item>, G::Error> {
+        let (subtracted, no_underflow) = Self::bin_subtraction(g, a, b)?;
+        // Add the modulus again on underflow, which only requires XOR gates since the modulus is public
+        let underflow = g.negate(&no_underflow)?;
+        let zero = Self::constant_wire(g, &underflow, false)?;
+        let correction = GCUtils::biguint_to_bits(modulus.to_owned(), a.len())
+            .into_iter()
+            .map(|bit| {
+                if bit {
+                    underflow.to_owned()
+                } else {
+                    zero.to_owned()
+                }
+            })
+            .collect::<Vec<_>>();
+        Self::bin_addition_no_carry(g, &subtracted, &correction)
+    }
+
+    /// Schoolbook multiplication of two integers. The result has a.len() + b.len() wires.
+    fn bin_multiplication<G: FancyBinary>(
+        g: &mut G,
+        a: &[G::Item],
+        b: &[G::Item],
+    ) -> Result<Vec<G::Item>, G::Error> {
+        let zero = Self::constant_wire(g, &a[0], false)?;
+        let mut result = vec![zero; a.len() + b.len()];
+        for (i, b_) in b.iter().enumerate() {
+            let partial = a
+                .iter()
+                .map(|a_| g.and(a_, b_))
+                .collect::<Result<Vec<_>, _>>()?;
+            if i == 0 {
+                result.splice(..a.len(), partial);
+                continue;
+            }
+            // The current result is smaller than 2^(a.len() + i), so the carry goes into a zero wire
+            let (added, carry) = Self::bin_addition(g, &result[i..i + a.len()], &partial)?;
+            result.splice(i..i + a.len(), added);
+            result[i + a.len()] = carry;
+        }
+        Ok(result)
+    }
+
+    /// Multiplies two integers modulo the public modulus.
+    fn bigint_mul_mod<G: FancyBinary>(
+        g: &mut G,
+        a: &[G::Item],
+        b: &[G::Item],
+        modulus: &BigUint,
+    ) -> Result<Vec<G::Item>, G::Error> {
+        let product = Self::bin_multiplication(g, a, b)?;
+        Self::bigint_reduce(g, &product, modulus)
+    }
+
+    /// Composes an integer (LSB first) from bytes which are given as field elements (represented as two bitdecompositions wires_a, wires_b which need to be added first). Each field element is expected to be smaller than 2^8.
+    fn field_bytes_to_bigint<G: FancyBinary, F: PrimeField>(
+        g: &mut G,
+        wires_a: &[G::Item],
+        wires_b: &[G::Item],
+    ) -> Result<Vec<G::Item>, G::Error> {
+        let input_bitlen = F::MODULUS_BIT_SIZE as usize;
+        debug_assert_eq!(wires_a.len(), wires_b.len());
+        debug_assert_eq!(wires_a.len() % input_bitlen, 0);
+
+        let mut result = Vec::with_capacity(wires_a.len() / input_bitlen * 8);
+        for (chunk_a, chunk_b) in izip!(wires_a.chunks(input_bitlen), wires_b.chunks(input_bitlen))
+        {
+            result.extend(Self::adder_mod_p_with_output_size::<_, F>(
+                g, chunk_a, chunk_b, 8,
+            )?);
+        }
+        Ok(result)
+    }
+
+    /// Decomposes an integer (LSB first) into bytes, which are composed to field elements using wires_c as randomness.
+    fn bigint_to_field_bytes<G: FancyBinary, F: PrimeField>(
+        g: &mut G,
+        xs: &[G::Item],
+        wires_c: &[G::Item],
+    ) -> Result<Vec<G::Item>, G::Error> {
+        let output_bitlen = F::MODULUS_BIT_SIZE as usize;
+        debug_assert_eq!(wires_c.len(), xs.len().div_ceil(8) * output_bitlen);
+
+        let mut result = Vec::with_capacity(wires_c.len());
+        for (byte, rand) in izip!(xs.chunks(8), wires_c.chunks(output_bitlen)) {
+            result.extend(Self::compose_field_element::<_, F>(g, byte, rand)?);
+        }
+        Ok(result)
+    }
+
+    /// Computes a modular operation on two integers, which are given as little-endian bytes. The inputs are field elements (represented as two bitdecompositions wires_a, wires_b which need to be added first), where the first lhs_len field elements are the bytes of the left-hand side and the remaining ones are the bytes of the right-hand side. Both operands are reduced modulo the public modulus before the operation. The result is decomposed into modulus.bits().div_ceil(8) bytes, which are composed to field elements using wires_c.
+    pub(crate) fn bigint_mod_op_bytes<G: FancyBinary, F: PrimeField>(
+        g: &mut G,
+        wires_a: &BinaryBundle<G::Item>,
+        wires_b: &BinaryBundle<G::Item>,
+        wires_c: &BinaryBundle<G::Item>,
+        lhs_len: usize,
+        modulus: &BigUint,
+        op: &BigIntModOp,
+    ) -> Result<BinaryBundle<G::Item>, G::Error> {
+        let input_bitlen = F::MODULUS_BIT_SIZE as usize;
+        debug_assert_eq!(wires_a.size(), wires_b.size());
+        debug_assert!(wires_a.size() > lhs_len * input_bitlen);
+
+        let (lhs_a, rhs_a) = wires_a.wires().split_at(lhs_len * input_bitlen);
+        let (lhs_b, rhs_b) = wires_b.wires().split_at(lhs_len * input_bitlen);
+        let lhs = Self::field_bytes_to_bigint::<_, F>(g, lhs_a, lhs_b)?;
+        let lhs = Self::bigint_reduce(g, &lhs, modulus)?;
+        let rhs = Self::field_bytes_to_bigint::<_, F>(g, rhs_a, rhs_b)?;
+        let rhs = Self::bigint_reduce(g, &rhs, modulus)?;
+
+        let result = match op {
+            BigIntModOp::Add => Self::bigint_add_mod(g, &lhs, &rhs, modulus)?,
+            BigIntModOp::Sub => Self::bigint_sub_mod(g, &lhs, &rhs, modulus)?,
+            BigIntModOp::Mul => Self::bigint_mul_mod(g, &lhs, &rhs, modulus)?,
+            BigIntModOp::MulScaled(scalar) => {
+                let product = Self::bigint_mul_mod(g, &lhs, &rhs, modulus)?;
+                let scalar = Self::constant_wires(g, &product[0], scalar, product.len())?;
+                Self::bigint_mul_mod(g, &product, &scalar, modulus)?
+            }
+        };
+
+        let result = Self::bigint_to_field_bytes::<_, F>(g, &result, wires_c.wires())?;
+        Ok(BinaryBundle::new(result))
+    }
+
+    /// Masks an integer, given as num_bytes little-endian bytes, with a random value r modulo the public modulus. The inputs are field elements (represented as two bitdecompositions wires_a, wires_b which need to be added first), where the first num_bytes ones are the bytes of the integer and the remaining ones are random field elements. Of each random field element, only the lowest 128 bits are used to sample r. The output is the product of the integer and r, followed by r, both decomposed into modulus.bits().div_ceil(8) bytes which are composed to field elements using wires_c.
+    pub(crate) fn bigint_mask_bytes<G: FancyBinary, F: PrimeField>(
+        g: &mut G,
+        wires_a: &BinaryBundle<G::Item>,
+        wires_b: &BinaryBundle<G::Item>,
+        wires_c: &BinaryBundle<G::Item>,
+        num_bytes: usize,
+        modulus: &BigUint,
+    ) -> Result<BinaryBundle<G::Item>, G::Error> {
+        let input_bitlen = F::MODULUS_BIT_SIZE as usize;
+        debug_assert_eq!(wires_a.size(), wires_b.size());
+        debug_assert!(wires_a.size() > num_bytes * input_bitlen);
+
+        let (val_a, rand_a) = wires_a.wires().split_at(num_bytes * input_bitlen);
+        let (val_b, rand_b) = wires_b.wires().split_at(num_bytes * input_bitlen);
+        let val = Self::field_bytes_to_bigint::<_, F>(g, val_a, val_b)?;
+        let val = Self::bigint_reduce(g, &val, modulus)?;
+
+        let mut rand = Vec::with_capacity(rand_a.len() / input_bitlen * 128);
+        for (chunk_a, chunk_b) in izip!(rand_a.chunks(input_bitlen), rand_b.chunks(input_bitlen)) {
+            rand.extend(Self::adder_mod_p_with_output_size::<_, F>(
+                g, chunk_a, chunk_b, 128,
+            )?);
+        }
+        let rand = Self::bigint_reduce(g, &rand, modulus)?;
+
+        let masked = Self::bigint_mul_mod(g, &val, &rand, modulus)?;
+
+        let (wires_c_masked, wires_c_rand) = wires_c.wires().split_at(wires_c.size() / 2);
+        let mut result = Self::bigint_to_field_bytes::<_, F>(g, &masked, wires_c_masked)?;
+        result.extend(Self::bigint_to_field_bytes::<_, F>(g, &rand, wires_c_rand)?);
+        Ok(BinaryBundle::new(result))
+    }
+
+    /// Adds two integers (LSB first) of arbitrary sizes. The result has one wire more than the larger input.
+    fn bigint_addition<G: FancyBinary>(
+        g: &mut G,
+        a: &[G::Item],
+        b: &[G::Item],
+    ) -> Result<Vec<G::Item>, G::Error> {
+        let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+        let zero = Self::constant_wire(g, &long[0], false)?;
+        let mut short = short.to_vec();
+        short.resize(long.len(), zero);
+        let (mut result, carry) = Self::bin_addition(g, long, &short)?;
+        result.push(carry);
+        Ok(result)
+    }
+
+    /// Composes an integer (LSB first) from limbs which are given as field elements (represented as two bitdecompositions wires_a, wires_b which need to be added first), i.e., computes the sum of limb_i * 2^(limb_bits * i). In contrast to [Self::field_bytes_to_bigint], the limbs can be larger than 2^limb_bits.
+    fn field_limbs_to_bigint<G: FancyBinary, F: PrimeField>(
+        g: &mut G,
+        wires_a: &[G::Item],
+        wires_b: &[G::Item],
+        limb_bits: usize,
+    ) -> Result<Vec<G::Item>, G::Error> {
+        let input_bitlen = F::MODULUS_BIT_SIZE as usize;
+        debug_assert_eq!(wires_a.len(), wires_b.len());
+        debug_assert_eq!(wires_a.len() % input_bitlen, 0);
+        debug_assert!(limb_bits <= input_bitlen);
+
+        let mut result: Vec<G::Item> = Vec::new();
+        for (i, (chunk_a, chunk_b)) in
+            izip!(wires_a.chunks(input_bitlen), wires_b.chunks(input_bitlen)).enumerate()
+        {
+            let limb =
+                Self::adder_mod_p_with_output_size::<_, F>(g, chunk_a, chunk_b, input_bitlen)?;
+            if i == 0 {
+                result = limb;
+                continue;
+            }
+            // The accumulated value has at least offset wires, since the first limb has input_bitlen wires
+            let offset = i * limb_bits;
+            let added = Self::bigint_addition(g, &result[offset..], &limb)?;
+            result.truncate(offset);
+            result.extend(added);
+        }
+        Ok(result)
+    }
+
+    /// Decomposes an integer (LSB first) into num_limbs limbs of limb_bits bits each, which are composed to field elements using wires_c as randomness. Wires beyond num_limbs * limb_bits are ignored, missing wires are zero.
+    fn bigint_to_field_limbs<G: FancyBinary, F: PrimeField>(
+        g: &mut G,
+        xs: &[G::Item],
+        wires_c: &[G::Item],
+        limb_bits: usize,
+        num_limbs: usize,
+    ) -> Result<Vec<G::Item>, G::Error> {
+        let output_bitlen = F::MODULUS_BIT_SIZE as usize;
+        debug_assert_eq!(wires_c.len(), num_limbs * output_bitlen);
+
+        let zero = Self::constant_wire(g, &xs[0], false)?;
+        let mut xs = xs.to_vec();
+        xs.resize(num_limbs * limb_bits, zero);
+
+        let mut result = Vec::with_capacity(wires_c.len());
+        for (limb, rand) in izip!(xs.chunks(limb_bits), wires_c.chunks(output_bitlen)) {
+            result.extend(Self::compose_field_element::<_, F>(g, limb, rand)?);
+        }
+        Ok(result)
+    }
+
+    /// Computes the quotient and the remainder of lhs * rhs + add - sub divided by a public modulus, where lhs * rhs + add must not be smaller than sub. The inputs are field elements (represented as two bitdecompositions wires_a, wires_b which need to be added first), which are the limbs of lhs, rhs, add and sub (see [Self::field_limbs_to_bigint]), where the numbers of limbs are given by num_inputs. add and sub can have zero limbs. The output are num_limbs limbs of limb_bits bits of the quotient, followed by the same number of limbs of the remainder, which are composed to field elements using wires_c.
+    #[expect(clippy::too_many_arguments)]
+    pub(crate) fn bigint_quotient_remainder_limbs<G: FancyBinary, F: PrimeField>(
+        g: &mut G,
+        wires_a: &BinaryBundle<G::Item>,
+        wires_b: &BinaryBundle<G::Item>,
+        wires_c: &BinaryBundle<G::Item>,
+        num_inputs: &[usize; 4],
+        limb_bits: usize,
+        num_limbs: usize,
+        modulus: &BigUint,
+    ) -> Result<BinaryBundle<G::Item>, G::Error> {
+        let input_bitlen = F::MODULUS_BIT_SIZE as usize;
+        debug_assert_eq!(wires_a.size(), wires_b.size());
+        debug_assert_eq!(wires_a.size(), num_inputs.iter().sum::<usize>() * input_bitlen);
+        debug_assert!(num_inputs[0] > 0 && num_inputs[1] > 0);
+
+        let mut operands = Vec::with_capacity(4);
+        let (mut rest_a, mut rest_b) = (wires_a.wires(), wires_b.wires());
+        for num in num_inputs {
+            let (a, tail_a) = rest_a.split_at(num * input_bitlen);
+            let (b, tail_b) = rest_b.split_at(num * input_bitlen);
+            (rest_a, rest_b) = (tail_a, tail_b);
+            operands.push(if *num == 0 {
+                None
+            } else {
+                Some(Self::field_limbs_to_bigint::<_, F>(g, a, b, limb_bits)?)
+            });
+        }
+
+        let [lhs, rhs, add, sub]: [Option<Vec<G::Item>>; 4] =
+            operands.try_into().expect("four operands");
+        let (lhs, rhs) = (lhs.expect("lhs has limbs"), rhs.expect("rhs has limbs"));
+        let mut value = Self::bin_multiplication(g, &lhs, &rhs)?;
+        if let Some(add) = add {
+            value = Self::bigint_addition(g, &value, &add)?;
+        }
+        if let Some(mut sub) = sub {
+            let zero = Self::constant_wire(g, &value[0], false)?;
+            // The subtrahend is not larger than the value, so it has at most as many significant wires
+            sub.resize(value.len().max(sub.len()), zero.to_owned());
+            value.resize(sub.len(), zero);
+            (value, _) = Self::bin_subtraction(g, &value, &sub)?;
+        }
+
+        let (quotient, remainder) = Self::bigint_div_rem_public(g, &value, modulus)?;
+        let (wires_c_quotient, wires_c_remainder) = wires_c.wires().split_at(wires_c.size() / 2);
+        let mut result = Self::bigint_to_field_limbs::<_, F>(
+            g,
+            &quotient,
+            wires_c_quotient,
+            limb_bits,
+            num_limbs,
+        )?;
+        result.extend(Self::bigint_to_field_limbs::<_, F>(
+            g,
+            &remainder,
+            wires_c_remainder,
+            limb_bits,
+            num_limbs,
+        )?);
+        Ok(BinaryBundle::new(result))
+    }
+
+    /// Masks an integer, given as num_inputs limbs (see [Self::field_limbs_to_bigint]), with a random value r modulo the public modulus, like [Self::bigint_mask_bytes]. The inputs following the limbs are random field elements, of which only the lowest 128 bits are used to sample r. The output is the product of the integer and r, followed by r, both decomposed into num_limbs limbs of limb_bits bits which are composed to field elements using wires_c.
+    #[expect(clippy::too_many_arguments)]
+    pub(crate) fn bigint_mask_limbs<G: FancyBinary, F: PrimeField>(
+        g: &mut G,
+        wires_a: &BinaryBundle<G::Item>,
+        wires_b: &BinaryBundle<G::Item>,
+        wires_c: &BinaryBundle<G::Item>,
+        num_inputs: usize,
+        limb_bits: usize,
+        num_limbs: usize,
+        modulus: &BigUint,
+    ) -> Result<BinaryBundle<G::Item>, G::Error> {
+        let input_bitlen = F::MODULUS_BIT_SIZE as usize;
+        debug_assert_eq!(wires_a.size(), wires_b.size());
+        debug_assert!(wires_a.size() > num_inputs * input_bitlen);
+
+        let (val_a, rand_a) = wires_a.wires().split_at(num_inputs * input_bitlen);
+        let (val_b, rand_b) = wires_b.wires().split_at(num_inputs * input_bitlen);
+        let val = Self::field_limbs_to_bigint::<_, F>(g, val_a, val_b, limb_bits)?;
+        let val = Self::bigint_reduce(g, &val, modulus)?;
+
+        let mut rand = Vec::with_capacity(rand_a.len() / input_bitlen * 128);
+        for (chunk_a, chunk_b) in izip!(rand_a.chunks(input_bitlen), rand_b.chunks(input_bitlen)) {
+            rand.extend(Self::adder_mod_p_with_output_size::<_, F>(
+                g, chunk_a, chunk_b, 128,
+            )?);
+        }
+        let rand = Self::bigint_reduce(g, &rand, modulus)?;
+
+        let masked = Self::bigint_mul_mod(g, &val, &rand, modulus)?;
+
+        let (wires_c_masked, wires_c_rand) = wires_c.wires().split_at(wires_c.size() / 2);
+        let mut result =
+            Self::bigint_to_field_limbs::<_, F>(g, &masked, wires_c_masked, limb_bits, num_limbs)?;
+        result.extend(Self::bigint_to_field_limbs::<_, F>(
+            g,
+            &rand,
+            wires_c_rand,
+            limb_bits,
+            num_limbs,
+        )?);
+        Ok(BinaryBundle::new(result))
+    }
+
+    /// Computes lhs * rhs * scalar modulo a public modulus for a public scalar. The inputs are field elements (represented as two bitdecompositions wires_a, wires_b which need to be added first), where the first lhs_len ones are the limbs of lhs and the remaining ones are the limbs of rhs (see [Self::field_limbs_to_bigint]). The result is decomposed into num_limbs limbs of limb_bits bits which are composed to field elements using wires_c.
+    #[expect(clippy::too_many_arguments)]
+    pub(crate) fn bigint_mul_scaled_limbs<G: FancyBinary, F: PrimeField>(
+        g: &mut G,
+        wires_a: &BinaryBundle<G::Item>,
+        wires_b: &BinaryBundle<G::Item>,
+        wires_c: &BinaryBundle<G::Item>,
+        lhs_len: usize,
+        limb_bits: usize,
+        num_limbs: usize,
+        modulus: &BigUint,
+        scalar: &BigUint,
+    ) -> Result<BinaryBundle<G::Item>, G::Error> {
+        let input_bitlen = F::MODULUS_BIT_SIZE as usize;
+        debug_assert_eq!(wires_a.size(), wires_b.size());
+        debug_assert!(wires_a.size() > lhs_len * input_bitlen);
+
+        let (lhs_a, rhs_a) = wires_a.wires().split_at(lhs_len * input_bitlen);
+        let (lhs_b, rhs_b) = wires_b.wires().split_at(lhs_len * input_bitlen);
+        let lhs = Self::field_limbs_to_bigint::<_, F>(g, lhs_a, lhs_b, limb_bits)?;
+        let lhs = Self::bigint_reduce(g, &lhs, modulus)?;
+        let rhs = Self::field_limbs_to_bigint::<_, F>(g, rhs_a, rhs_b, limb_bits)?;
+        let rhs = Self::bigint_reduce(g, &rhs, modulus)?;
+
+        let product = Self::bigint_mul_mod(g, &lhs, &rhs, modulus)?;
+        let scalar = Self::constant_wires(g, &product[0], scalar, product.len())?;
+        let result = Self::bigint_mul_mod(g, &product, &scalar, modulus)?;
+
+        let result =
+            Self::bigint_to_field_limbs::<_, F>(g, &result, wires_c.wires(), limb_bits, num_limbs)?;
+        Ok(BinaryBundle::new(result))
+    }
+
+    /// The mixing function G of BLAKE2s, which mixes the message words x and y into the words a, b, c and d of the working vector v.
+    fn blake2s_g<G: FancyBinary>(
+        g: &mut G,
+        v: &mut [Vec<G::Item>],
+        [a, b, c, d]: [usize; 4],
+        x: &[G::Item],
+        y: &[G::Item],
+    ) -> Result<(), G::Error> {
+        // Rotations to the right only permute the wires (LSB first)
+        let rotate_right = |mut word: Vec<G::Item>, n: usize| {
+            word.rotate_left(n);
+            word
+        };
+        for (m, rot_d, rot_b) in [(x, 16, 12), (y, 8, 7)] {
+            let sum = Self::bin_addition_no_carry(g, &v[a], &v[b])?;
+            v[a] = Self::bin_addition_no_carry(g, &sum, m)?;
+            v[d] = rotate_right(Self::xor_slices(g, &v[d], &v[a])?, rot_d);
+            v[c] = Self::bin_addition_no_carry(g, &v[c], &v[d])?;
+            v[b] = rotate_right(Self::xor_slices(g, &v[b], &v[c])?, rot_b);
+        }
+        Ok(())
+    }
+
+    /// Computes the BLAKE2s-256 hash (without a key) of a message, which is given as bytes in order, each byte LSB first. The message must not be empty, but its length is public. The digest is returned as 32 bytes in the same encoding.
+    pub(crate) fn blake2s<G: FancyBinary>(
+        g: &mut G,
+        message: &[G::Item],
+    ) -> Result<Vec<G::Item>, G::Error> {
+        debug_assert!(!message.is_empty());
+        debug_assert_eq!(message.len() % 8, 0);
+        const BLOCK_BITS: usize = 512;
+        const IV: [u32; 8] = [
+            0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB,
+            0x5BE0CD19,
+        ];
+        const SIGMA: [[usize; 16]; 10] = [
+            [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+            [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+            [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+            [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+            [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+            [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+            [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+            [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+            [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+            [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+        ];
+        // The columns and diagonals of the working vector
+        const MIX: [[usize; 4]; 8] = [
+            [0, 4, 8, 12],
+            [1, 5, 9, 13],
+            [2, 6, 10, 14],
+            [3, 7, 11, 15],
+            [0, 5, 10, 15],
+            [1, 6, 11, 12],
+            [2, 7, 8, 13],
+            [3, 4, 9, 14],
+        ];
+
+        let num_bytes = message.len() / 8;
+        let zero = Self::constant_wire(g, &message[0], false)?;
+        let mut h = Vec::with_capacity(IV.len());
+        for (i, iv) in IV.iter().enumerate() {
+            // The parameter block of an unkeyed hash with a 32 byte digest
+            let value = if i == 0 { iv ^ 0x0101_0020 } else { *iv };
+            h.push(Self::constant_wires(g, &zero, &BigUint::from(value), 32)?);
+        }
+
+        // The message is padded with zeros to a multiple of the block size
+        let num_blocks = num_bytes.div_ceil(BLOCK_BITS / 8);
+        let mut padded = message.to_vec();
+        padded.resize(num_blocks * BLOCK_BITS, zero.to_owned());
+        for (i, block) in padded.chunks(BLOCK_BITS).enumerate() {
+            let last = i + 1 == num_blocks;
+            // The number of message bytes processed up to and including this block
+            let counter = if last {
+                num_bytes as u64
+            } else {
+                ((i + 1) * BLOCK_BITS / 8) as u64
+            };
+            let mut v = h.to_owned();
+            for (j, iv) in IV.iter().enumerate() {
+                let value = match j {
+                    4 => iv ^ counter as u32,
+                    5 => iv ^ (counter >> 32) as u32,
+                    6 if last => !*iv,
+                    _ => *iv,
+                };
+                v.push(Self::constant_wires(g, &zero, &BigUint::from(value), 32)?);
+            }
+            let m = block.chunks(32).collect::<Vec<_>>();
+            for sigma in SIGMA.iter() {
+                for (j, indices) in MIX.into_iter().enumerate() {
+                    Self::blake2s_g(g, &mut v, indices, m[sigma[2 * j]], m[sigma[2 * j + 1]])?;
+                }
+            }
+            for (j, h) in h.iter_mut().enumerate() {
+                let mixed = Self::xor_slices(g, &v[j], &v[j + 8])?;
+                *h = Self::xor_slices(g, h, &mixed)?;
+            }
+        }
+
+        Ok(h.into_iter().flatten().collect())
+    }
+
+    /// Computes the BLAKE2s-256 hash of a message, which is given as bytes in field elements (represented as two bitdecompositions wires_a, wires_b which need to be added first). Each field element is expected to be smaller than 2^8. The digest is decomposed into 32 bytes, which are composed to field elements using wires_c.
+    pub(crate) fn blake2s_bytes<G: FancyBinary, F: PrimeField>(
+        g: &mut G,
+        wires_a: &BinaryBundle<G::Item>,
+        wires_b: &BinaryBundle<G::Item>,
+        wires_c: &BinaryBundle<G::Item>,
+    ) -> Result<BinaryBundle<G::Item>, G::Error> {
+        let message = Self::field_bytes_to_bigint::<_, F>(g, wires_a.wires(), wires_b.wires())?;
+        let digest = Self::blake2s(g, &message)?;
+        let result = Self::bigint_to_field_bytes::<_, F>(g, &digest, wires_c.wires())?;
+        Ok(BinaryBundle::new(result))
+    }
+
+    /// Encrypts a message with AES-128 in CBC mode. The inputs are bytes in field elements (represented as two bitdecompositions wires_a, wires_b which need to be added first), where the first 16 field elements are the key, the next 16 field elements are the IV, and the remaining ones are the message, whose length has to be a multiple of 16 bytes. Each field element is expected to be smaller than 2^8. The ciphertext is decomposed into bytes, which are composed to field elements using wires_c.
+    pub(crate) fn aes128_cbc_encrypt_bytes<G: FancyBinary, F: PrimeField>(
+        g: &mut G,
+        wires_a: &BinaryBundle<G::Item>,
+        wires_b: &BinaryBundle<G::Item>,
+        wires_c: &BinaryBundle<G::Item>,
+    ) -> Result<BinaryBundle<G::Item>, G::Error> {
+        let bytes = Self::field_bytes_to_bigint::<_, F>(g, wires_a.wires(), wires_b.wires())?;
+        debug_assert!(bytes.len() >= 2 * 128);
+        debug_assert_eq!(bytes.len() % 128, 0);
+        let (key, rest) = bytes.split_at(128);
+        let (iv, message) = rest.split_at(128);
+
+        let round_keys = Self::aes128_key_expansion(g, key)?;
+        let mut ciphertext = Vec::with_capacity(message.len());
+        let mut prev = iv.to_vec();
+        for block in message.chunks(128) {
+            let block = Self::xor_slices(g, block, &prev)?;
+            prev = Self::aes128_encrypt_block(g, &round_keys, &block)?;
+            ciphertext.extend_from_slice(&prev);
+        }
+
+        let result = Self::bigint_to_field_bytes::<_, F>(g, &ciphertext, wires_c.wires())?;
+        Ok(BinaryBundle::new(result))
+    }
 }
 
 #[cfg(test)]
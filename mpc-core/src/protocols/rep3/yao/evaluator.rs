@@ -6,10 +6,12 @@
 
 use super::GCUtils;
 use crate::protocols::rep3::{
+    conversion,
     id::PartyID,
     network::{IoContext, Rep3Network},
-    IoResult,
+    IoResult, Rep3BigUintShare,
 };
+use ark_ff::PrimeField;
 use fancy_garbling::{
     errors::EvaluatorError, util::output_tweak, BinaryBundle, Fancy, FancyBinary, WireLabel,
     WireMod2,
@@ -138,8 +140,8 @@ impl<'a, N: Rep3Network> Rep3Evaluator<'a, N> {
         Ok(())
     }
 
-    /// Outputs the values to the garbler with id1.
-    fn output_garbler_id1(&mut self, x: &[WireMod2]) -> IoResult<()> {
+    /// Outputs the values to the garbler with the given id.
+    fn output_garbler_to(&mut self, x: &[WireMod2], id: PartyID) -> IoResult<()> {
         let mut blocks = Vec::with_capacity(x.len());
         for val in x {
             let block = val.as_block();
@@ -147,7 +149,7 @@ impl<'a, N: Rep3Network> Rep3Evaluator<'a, N> {
             gate.copy_from_slice(block.as_ref());
             blocks.push(gate);
         }
-        self.io_context.network.send_many(PartyID::ID1, &blocks)?;
+        self.io_context.network.send_many(id, &blocks)?;
 
         Ok(())
     }
@@ -163,13 +165,34 @@ impl<'a, N: Rep3Network> Rep3Evaluator<'a, N> {
         Ok(res)
     }
 
+    /// Outputs the value only to the party with the given id. Returns `Some` if the id is the one of the evaluator and `None` otherwise. If the value is output to a garbler, the evaluator does not learn it.
+    pub fn output_to_party(&mut self, x: &[WireMod2], id: PartyID) -> IoResult<Option<Vec<bool>>> {
+        if id == PartyID::ID0 {
+            // Garbler's to evaluator
+            let res = self.output_evaluator(x)?;
+            Ok(Some(res))
+        } else {
+            // Evaluator to garbler
+            self.output_garbler_to(x, id)?;
+            Ok(None)
+        }
+    }
+
+    /// Outputs the value as a fresh binary sharing using [y2b](conversion::y2b), such that no party learns it. The circuit has to be received with [receive_circuit](Self::receive_circuit) before it is evaluated.
+    pub fn output_shared<F: PrimeField>(
+        self,
+        x: BinaryBundle<WireMod2>,
+    ) -> IoResult<Rep3BigUintShare<F>> {
+        conversion::y2b(x, self.io_context)
+    }
+
     /// Outputs the value to parties ID0 and ID1
     pub fn output_to_id0_and_id1(&mut self, x: &[WireMod2]) -> IoResult<Vec<bool>> {
         // Garbler's to evaluator
         let res = self.output_evaluator(x)?;
 
         // Evaluator to garbler
-        self.output_garbler_id1(x)?;
+        self.output_garbler_to(x, PartyID::ID1)?;
 
         Ok(res)
     }
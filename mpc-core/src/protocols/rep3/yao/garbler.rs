@@ -7,9 +7,10 @@
 use super::{GCInputs, GCUtils};
 use crate::{
     protocols::rep3::{
+        conversion,
         id::PartyID,
         network::{IoContext, Rep3Network},
-        IoResult,
+        IoResult, Rep3BigUintShare,
     },
     RngType,
 };
@@ -45,6 +46,24 @@ impl<'a, N: Rep3Network> Rep3Garbler<'a, N> {
     pub fn new_with_delta(io_context: &'a mut IoContext<N>, delta: WireMod2) -> Self {
         let id = io_context.id;
         let seed = io_context.rngs.generate_garbler_randomness(id);
+        Self::new_with_delta_and_seed(io_context, delta, seed)
+    }
+
+    /// Create a new garbler whose delta and randomness are derived from the given seed instead of the correlated randomness of the parties. Both garblers have to use the same seed. The garbled circuit then only depends on the seed and the garbled inputs, which allows comparing it against a golden transcript in regression tests.
+    ///
+    /// This is only meant for testing, as the security of the garbled circuit relies on the seed being unknown to the evaluator.
+    #[cfg(feature = "dangerous")]
+    pub fn new_from_seed(io_context: &'a mut IoContext<N>, seed: [u8; 32]) -> Self {
+        let mut res = Self::new_with_delta_and_seed(io_context, WireMod2::default(), seed);
+        res.delta = GCUtils::random_delta(&mut res.rng);
+        res
+    }
+
+    fn new_with_delta_and_seed(
+        io_context: &'a mut IoContext<N>,
+        delta: WireMod2,
+        seed: [u8; 32],
+    ) -> Self {
         let rng = RngType::from_seed(seed);
 
         Self {
@@ -107,6 +126,12 @@ impl<'a, N: Rep3Network> Rep3Garbler<'a, N> {
         GCUtils::encode_field(field, &mut self.rng, self.delta)
     }
 
+    /// Returns the garbled gates that are buffered for the evaluator, i.e., everything added since the last [Self::send_circuit]. Only party ID1 buffers the circuit, party ID2 only keeps a hash of it.
+    #[cfg(feature = "dangerous")]
+    pub fn garbled_circuit(&self) -> &[[u8; 16]] {
+        &self.circuit
+    }
+
     /// Consumes the Garbler and returns the delta.
     pub fn into_delta(self) -> WireMod2 {
         self.delta
@@ -173,6 +198,33 @@ impl<'a, N: Rep3Network> Rep3Garbler<'a, N> {
         self.output_garbler(x)
     }
 
+    /// Outputs the value only to the party with the given id. Returns `Some` for the garbler with this id and `None` otherwise. If the value is output to a garbler, the evaluator does not learn it.
+    pub fn output_to_party(&mut self, x: &[WireMod2], id: PartyID) -> IoResult<Option<Vec<bool>>> {
+        // Garbler's to evaluator
+        if id == PartyID::ID0 {
+            self.output_evaluator(x)?;
+        }
+
+        // Check consistency with the second garbled circuit before receiving the result
+        self.send_circuit()?;
+
+        // Evaluator to garbler
+        if self.io_context.id == id {
+            Ok(Some(self.output_garbler(x)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Outputs the value as a fresh binary sharing using [y2b](conversion::y2b), such that no party learns it. Sends the circuit to the evaluator beforehand.
+    pub fn output_shared<F: PrimeField>(
+        mut self,
+        x: BinaryBundle<WireMod2>,
+    ) -> IoResult<Rep3BigUintShare<F>> {
+        self.send_circuit()?;
+        conversion::y2b(x, self.io_context)
+    }
+
     /// Outputs the value to parties ID0 and ID1
     pub fn output_to_id0_and_id1(&mut self, x: &[WireMod2]) -> IoResult<Option<Vec<bool>>> {
         // Garbler's to evaluator
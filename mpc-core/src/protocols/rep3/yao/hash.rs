@@ -0,0 +1,150 @@
+//! Wire hashing
+//!
+//! This module contains the hash function used to garble and evaluate the AND gates of the garbled circuits, which dominates the computation of the garbler and the evaluator. It is the tweakable circular correlation robust hash `H(x, i) = π(π(x) ⊕ i) ⊕ π(x)` of [Guo et al.](https://eprint.iacr.org/2019/074), where π is AES-128 with a fixed key.
+//!
+//! The AES implementation selects the fastest backend at runtime: AES-NI on x86-64, the ARMv8 crypto extensions on AArch64 (e.g., AWS Graviton or Apple silicon), and a constant-time software implementation otherwise. All backends compute the same function, so parties on different architectures can garble and evaluate circuits for each other.
+//!
+//! The ARMv8 backend of the `aes` crate is only compiled in when building with `RUSTFLAGS="--cfg aes_armv8"`, and `--cfg aes_force_soft` disables all hardware backends.
+
+use std::sync::OnceLock;
+
+use aes::{
+    cipher::{BlockEncrypt, KeyInit},
+    Aes128,
+};
+use fancy_garbling::{WireLabel, WireMod2};
+use scuttlebutt::Block;
+
+/// The fixed AES key of the hash. It is public, the security of the hash relies on AES being an ideal permutation.
+const FIXED_KEY: [u8; 16] = *b"co-snarks garble";
+
+/// The AES implementation used for hashing the wires, see [hash_backend].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashBackend {
+    /// The AES-NI instructions of x86-64 CPUs
+    AesNi,
+    /// The crypto extensions of ARMv8 CPUs
+    ArmV8,
+    /// The constant-time software implementation
+    Software,
+}
+
+impl std::fmt::Display for HashBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HashBackend::AesNi => f.write_str("AES-NI"),
+            HashBackend::ArmV8 => f.write_str("ARMv8 crypto extensions"),
+            HashBackend::Software => f.write_str("software"),
+        }
+    }
+}
+
+/// Returns the AES implementation selected at runtime for the CPU of this machine.
+pub fn hash_backend() -> HashBackend {
+    select_backend(cpu_has_aes())
+}
+
+fn cpu_has_aes() -> bool {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        std::arch::is_x86_feature_detected!("aes") && std::arch::is_x86_feature_detected!("sse2")
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        std::arch::is_aarch64_feature_detected!("aes")
+    }
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+    {
+        false
+    }
+}
+
+// Mirrors the backend selection of the aes crate, which only uses the hardware backends that are compiled in
+fn select_backend(cpu_has_aes: bool) -> HashBackend {
+    if !cpu_has_aes || cfg!(aes_force_soft) {
+        HashBackend::Software
+    } else if cfg!(any(target_arch = "x86", target_arch = "x86_64")) {
+        HashBackend::AesNi
+    } else if cfg!(all(target_arch = "aarch64", aes_armv8)) {
+        HashBackend::ArmV8
+    } else {
+        HashBackend::Software
+    }
+}
+
+fn fixed_key_aes() -> &'static Aes128 {
+    static AES: OnceLock<Aes128> = OnceLock::new();
+    AES.get_or_init(|| {
+        tracing::debug!("garbling with the {} AES backend", hash_backend());
+        Aes128::new(&FIXED_KEY.into())
+    })
+}
+
+fn to_aes_block(block: Block) -> aes::Block {
+    u128::from(block).to_le_bytes().into()
+}
+
+fn from_aes_block(block: &aes::Block) -> Block {
+    Block::from(u128::from_le_bytes((*block).into()))
+}
+
+/// Hashes a batch of blocks with the same tweak. The blocks are encrypted together, which allows the hardware backends to pipeline the AES rounds.
+pub fn tccr_hash_many<const N: usize>(tweak: Block, blocks: [Block; N]) -> [Block; N] {
+    let aes = fixed_key_aes();
+    let mut xs = blocks.map(to_aes_block);
+    aes.encrypt_blocks(&mut xs);
+    let tweak = to_aes_block(tweak);
+    let mut ys = xs.map(|x| {
+        let mut y = x;
+        y.iter_mut().zip(tweak.iter()).for_each(|(y, t)| *y ^= t);
+        y
+    });
+    aes.encrypt_blocks(&mut ys);
+    std::array::from_fn(|i| from_aes_block(&xs[i]) ^ from_aes_block(&ys[i]))
+}
+
+/// Hashes the labels of a batch of wires with the same tweak.
+pub(crate) fn hash_wires<const N: usize>(wires: [&WireMod2; N], tweak: Block) -> [Block; N] {
+    tccr_hash_many(tweak, wires.map(|wire| wire.as_block()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // known answers computed with an independent AES implementation, such that all backends are checked to compute the same function
+    #[test]
+    fn tccr_hash_known_answers() {
+        let [h0, h1] = tccr_hash_many(
+            Block::from(42u128),
+            [
+                Block::from(0u128),
+                Block::from(0x0123456789abcdef0011223344556677u128),
+            ],
+        );
+        assert_eq!(
+            u128::from(tccr_hash_many(Block::from(0u128), [Block::from(0u128)])[0]),
+            0xf724ae31ca633b13cc772fd7f3659092
+        );
+        assert_eq!(u128::from(h1), 0x7851baec318ff6f86a4f09ea93d2045d);
+        assert_ne!(h0, h1);
+    }
+
+    #[test]
+    fn soft_backend_without_cpu_support() {
+        assert_eq!(select_backend(false), HashBackend::Software);
+    }
+
+    #[test]
+    #[cfg(any(aes_force_soft, all(target_arch = "aarch64", not(aes_armv8))))]
+    fn soft_backend_if_hardware_backend_is_not_compiled_in() {
+        assert_eq!(select_backend(true), HashBackend::Software);
+        assert_eq!(hash_backend(), HashBackend::Software);
+    }
+
+    #[test]
+    #[cfg(all(target_arch = "aarch64", aes_armv8, not(aes_force_soft)))]
+    fn armv8_backend_if_compiled_in() {
+        assert_eq!(select_backend(true), HashBackend::ArmV8);
+    }
+}
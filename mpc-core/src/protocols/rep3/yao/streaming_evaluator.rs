@@ -6,10 +6,12 @@
 
 use super::GCUtils;
 use crate::protocols::rep3::{
+    conversion,
     id::PartyID,
     network::{IoContext, Rep3Network},
-    IoResult,
+    IoResult, Rep3BigUintShare,
 };
+use ark_ff::PrimeField;
 use fancy_garbling::{
     errors::EvaluatorError, util::output_tweak, BinaryBundle, Fancy, FancyBinary, WireLabel,
     WireMod2,
@@ -91,11 +93,11 @@ impl<'a, N: Rep3Network> StreamingRep3Evaluator<'a, N> {
         Ok(())
     }
 
-    /// Outputs the values to the garbler with id1.
-    fn output_garbler_id1(&mut self, x: &[WireMod2]) -> IoResult<()> {
+    /// Outputs the values to the garbler with the given id.
+    fn output_garbler_to(&mut self, x: &[WireMod2], id: PartyID) -> IoResult<()> {
         for val in x {
             let block = val.as_block();
-            self.io_context.network.send(PartyID::ID1, block.as_ref())?;
+            self.io_context.network.send(id, block.as_ref())?;
         }
         Ok(())
     }
@@ -114,6 +116,35 @@ impl<'a, N: Rep3Network> StreamingRep3Evaluator<'a, N> {
         Ok(res)
     }
 
+    /// Outputs the value only to the party with the given id. Returns `Some` if the id is the one of the evaluator and `None` otherwise. If the value is output to a garbler, the evaluator does not learn it.
+    pub fn output_to_party(&mut self, x: &[WireMod2], id: PartyID) -> IoResult<Option<Vec<bool>>> {
+        if id == PartyID::ID0 {
+            // Garbler's to evaluator
+            let res = self.output_evaluator(x)?;
+
+            // Check consistency with the second garbled circuit
+            self.receive_hash()?;
+            Ok(Some(res))
+        } else {
+            // Check consistency with the second garbled circuit before releasing the result
+            self.receive_hash()?;
+
+            // Evaluator to garbler
+            self.output_garbler_to(x, id)?;
+            Ok(None)
+        }
+    }
+
+    /// Outputs the value as a fresh binary sharing using [y2b](conversion::y2b), such that no party learns it. Receives the hash of the circuit from the second garbler beforehand.
+    pub fn output_shared<F: PrimeField>(
+        mut self,
+        x: BinaryBundle<WireMod2>,
+    ) -> IoResult<Rep3BigUintShare<F>> {
+        // Check consistency with the second garbled circuit
+        self.receive_hash()?;
+        conversion::y2b(x, self.io_context)
+    }
+
     /// Outputs the value to parties ID0 and ID1
     pub fn output_to_id0_and_id1(&mut self, x: &[WireMod2]) -> IoResult<Vec<bool>> {
         // Garbler's to evaluator
@@ -123,7 +154,7 @@ impl<'a, N: Rep3Network> StreamingRep3Evaluator<'a, N> {
         self.receive_hash()?;
 
         // Evaluator to garbler
-        self.output_garbler_id1(x)?;
+        self.output_garbler_to(x, PartyID::ID1)?;
 
         Ok(res)
     }
@@ -17,6 +17,7 @@ use types::Rep3RingShare;
 use super::{
     binary, conversion, detail,
     ring::{bit::Bit, int_ring::IntRing2k, ring_impl::RingElement},
+    yao,
 };
 
 pub(super) mod ops;
@@ -521,6 +522,58 @@ where
     Ok(x.0.convert())
 }
 
+/// Divides a shared value by a public divisor, rounding down.
+///
+/// The division is evaluated in a garbled circuit and therefore needs a constant number of communication rounds. Returns an error if the divisor is zero.
+pub fn div_shared_by_public<T: IntRing2k, N: Rep3Network>(
+    shared: RingShare<T>,
+    public: RingElement<T>,
+    io_context: &mut IoContext<N>,
+) -> IoResult<RingShare<T>>
+where
+    Standard: Distribution<T>,
+{
+    yao::ring_div_public(shared, io_context, public)
+}
+
+/// Batched version of [div_shared_by_public].
+pub fn div_shared_by_public_many<T: IntRing2k, N: Rep3Network>(
+    shared: &[RingShare<T>],
+    public: RingElement<T>,
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<RingShare<T>>>
+where
+    Standard: Distribution<T>,
+{
+    yao::ring_div_public_many(shared, io_context, public)
+}
+
+/// Reduces a shared value modulo a public divisor.
+///
+/// The reduction is evaluated in a garbled circuit and therefore needs a constant number of communication rounds. Returns an error if the divisor is zero.
+pub fn mod_shared_by_public<T: IntRing2k, N: Rep3Network>(
+    shared: RingShare<T>,
+    public: RingElement<T>,
+    io_context: &mut IoContext<N>,
+) -> IoResult<RingShare<T>>
+where
+    Standard: Distribution<T>,
+{
+    yao::ring_mod_public(shared, io_context, public)
+}
+
+/// Batched version of [mod_shared_by_public].
+pub fn mod_shared_by_public_many<T: IntRing2k, N: Rep3Network>(
+    shared: &[RingShare<T>],
+    public: RingElement<T>,
+    io_context: &mut IoContext<N>,
+) -> IoResult<Vec<RingShare<T>>>
+where
+    Standard: Distribution<T>,
+{
+    yao::ring_mod_public_many(shared, io_context, public)
+}
+
 /// Computes `shared*2^public`. This is the same as `shared << public`.
 ///
 /// #Panics
@@ -730,6 +730,101 @@ where
     Ok(res[0])
 }
 
+/// Divides a vector of ring elements by a public divisor in a constant number of communication rounds. Returns the quotients (rounded down) and the remainders.
+///
+/// Returns an error if the divisor is zero.
+#[expect(clippy::type_complexity)]
+pub fn ring_div_rem_public_many<T: IntRing2k, N: Rep3Network>(
+    inputs: &[Rep3RingShare<T>],
+    io_context: &mut IoContext<N>,
+    divisor: RingElement<T>,
+) -> IoResult<(Vec<Rep3RingShare<T>>, Vec<Rep3RingShare<T>>)>
+where
+    Standard: Distribution<T>,
+{
+    let num_inputs = inputs.len();
+    if divisor.is_zero() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "Cannot divide by zero",
+        ));
+    }
+    if divisor.is_one() {
+        return Ok((
+            inputs.to_owned(),
+            vec![Rep3RingShare::zero_share(); num_inputs],
+        ));
+    }
+    if num_inputs == 0 {
+        return Ok((vec![], vec![]));
+    }
+
+    let divisor = BigUint::from(divisor.0.into());
+    let res: IoResult<Vec<_>> = decompose_circuit_compose_blueprint!(
+        inputs,
+        io_context,
+        2 * num_inputs,
+        T,
+        GarbledCircuits::ring_div_rem_public_many,
+        (T::K, &divisor)
+    );
+    let mut quotients = res?;
+    let rems = quotients.split_off(num_inputs);
+    Ok((quotients, rems))
+}
+
+/// Divides a vector of ring elements by a public divisor, rounding down. See [ring_div_rem_public_many].
+pub fn ring_div_public_many<T: IntRing2k, N: Rep3Network>(
+    inputs: &[Rep3RingShare<T>],
+    io_context: &mut IoContext<N>,
+    divisor: RingElement<T>,
+) -> IoResult<Vec<Rep3RingShare<T>>>
+where
+    Standard: Distribution<T>,
+{
+    let (quotients, _) = ring_div_rem_public_many(inputs, io_context, divisor)?;
+    Ok(quotients)
+}
+
+/// Divides a ring element by a public divisor, rounding down. See [ring_div_rem_public_many].
+pub fn ring_div_public<T: IntRing2k, N: Rep3Network>(
+    input: Rep3RingShare<T>,
+    io_context: &mut IoContext<N>,
+    divisor: RingElement<T>,
+) -> IoResult<Rep3RingShare<T>>
+where
+    Standard: Distribution<T>,
+{
+    let res = ring_div_public_many(&[input], io_context, divisor)?;
+    Ok(res[0])
+}
+
+/// Reduces a vector of ring elements modulo a public divisor. See [ring_div_rem_public_many].
+pub fn ring_mod_public_many<T: IntRing2k, N: Rep3Network>(
+    inputs: &[Rep3RingShare<T>],
+    io_context: &mut IoContext<N>,
+    divisor: RingElement<T>,
+) -> IoResult<Vec<Rep3RingShare<T>>>
+where
+    Standard: Distribution<T>,
+{
+    let (_, rems) = ring_div_rem_public_many(inputs, io_context, divisor)?;
+    Ok(rems)
+}
+
+/// Reduces a ring element modulo a public divisor. See [ring_div_rem_public_many].
+pub fn ring_mod_public<T: IntRing2k, N: Rep3Network>(
+    input: Rep3RingShare<T>,
+    io_context: &mut IoContext<N>,
+    divisor: RingElement<T>,
+) -> IoResult<Rep3RingShare<T>>
+where
+    Standard: Distribution<T>,
+{
+    let res = ring_mod_public_many(&[input], io_context, divisor)?;
+    Ok(res[0])
+}
+
 /// Decomposes a FieldElement into a vector of RingElements of size decompose_bitlen each. In total, there will be num_decomps_per_field decompositions. The output is stored in the ring specified by T.
 pub fn decompose_field_to_rings_many<F: PrimeField, T: IntRing2k, N: Rep3Network>(
     inputs: &[Rep3PrimeFieldShare<F>],
@@ -4,21 +4,28 @@
 
 use ark_ec::CurveGroup;
 use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use itertools::izip;
 use network::ShamirNetwork;
 use rngs::ShamirRng;
 use std::time::Instant;
 
 use rand::{CryptoRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
-use crate::RngType;
+use crate::{snapshot::RngState, RngType};
 
 pub mod arithmetic;
+pub mod bls;
 pub mod core;
+pub mod dkg;
 pub mod network;
+pub mod nonnative;
 pub mod pointshare;
 pub mod poly;
 mod rngs;
+pub mod weighted;
 
 pub use arithmetic::types::ShamirPrimeFieldShare;
 pub use pointshare::types::ShamirPointShare;
@@ -246,6 +253,8 @@ impl<F: PrimeField, N: ShamirNetwork> From<ShamirPreprocessing<F, N>> for Shamir
             open_lagrange_2t,
             mul_lagrange_2t,
             mul_reconstruct_with_zeros,
+            reconstruction: Reconstruction::default(),
+            subset_lagrange_t: Vec::new(),
             network: value.network,
             rng_buffer: value.rng_buffer,
             generation_amount: Self::DEFAULT_PAIR_GEN_AMOUNT,
@@ -253,6 +262,48 @@ impl<F: PrimeField, N: ShamirNetwork> From<ShamirPreprocessing<F, N>> for Shamir
     }
 }
 
+/// Determines the parties whose shares are used to reconstruct a value in [open](arithmetic::open), [open_vec](arithmetic::open_vec), [open_point](pointshare::open_point), and [open_point_many](pointshare::open_point_many). All parties need to use the same variant.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Reconstruction {
+    /// Each party uses its own share and the shares of the `threshold` preceding parties. This requires the least communication.
+    #[default]
+    Next,
+    /// All parties use the shares of the given `threshold + 1` parties. This can be used to avoid parties that are known to be slow.
+    Subset(Vec<usize>),
+    /// Each party sends its share to all other parties and uses the first `threshold` shares to arrive. This reduces the latency if some parties are slow, at the cost of additional communication.
+    Fastest,
+}
+
+/// A snapshot of the internal state of a [ShamirProtocol], i.e., its rngs and its buffered correlated randomness, see [ShamirProtocol::snapshot] and the [snapshot](crate::snapshot) module. It is zeroized when dropped.
+#[derive(Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+#[serde(bound = "")]
+pub struct ShamirSnapshot<F: PrimeField> {
+    id: usize,
+    num_parties: usize,
+    threshold: usize,
+    #[zeroize(skip)]
+    reconstruction: Reconstruction,
+    generation_amount: usize,
+    rng: RngState,
+    shared_rngs: Vec<RngState>,
+    #[serde(serialize_with = "crate::ark_se", deserialize_with = "crate::ark_de")]
+    r_t: Vec<F>,
+    #[serde(serialize_with = "crate::ark_se", deserialize_with = "crate::ark_de")]
+    r_2t: Vec<F>,
+}
+
+impl<F: PrimeField> std::fmt::Debug for ShamirSnapshot<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShamirSnapshot")
+            .field("id", &self.id)
+            .field("num_parties", &self.num_parties)
+            .field("threshold", &self.threshold)
+            .field("reconstruction", &self.reconstruction)
+            .field("buffered_pairs", &self.r_t.len())
+            .finish_non_exhaustive()
+    }
+}
+
 /// This struct holds all necessary information for an MPC protocol based on Shamir. It contains
 /// a [`ShamirNetwork`], the randomness, the threshold and the lagrange
 /// polynomials for opening.
@@ -265,6 +316,8 @@ pub struct ShamirProtocol<F: PrimeField, N: ShamirNetwork> {
     pub open_lagrange_2t: Vec<F>,
     mul_lagrange_2t: Vec<F>,
     mul_reconstruct_with_zeros: Vec<F>,
+    reconstruction: Reconstruction,
+    subset_lagrange_t: Vec<F>,
     /// The underlying [`ShamirNetwork`]
     pub network: N,
     rng_buffer: ShamirRng<F>,
@@ -283,12 +336,129 @@ impl<F: PrimeField, N: ShamirNetwork> ShamirProtocol<F, N> {
             open_lagrange_2t: self.open_lagrange_2t.clone(),
             mul_lagrange_2t: self.mul_lagrange_2t.clone(),
             mul_reconstruct_with_zeros: self.mul_reconstruct_with_zeros.clone(),
+            reconstruction: self.reconstruction.clone(),
+            subset_lagrange_t: self.subset_lagrange_t.clone(),
             network: self.network.fork()?,
             rng_buffer: self.rng_buffer.fork_with_pairs(amount),
             generation_amount: self.generation_amount,
         })
     }
 
+    /// Takes a snapshot of the rngs and the buffered correlated randomness, which can be restored with [ShamirProtocol::restore]. The network is not part of the snapshot.
+    pub fn snapshot(&self) -> ShamirSnapshot<F> {
+        ShamirSnapshot {
+            id: self.network.get_id(),
+            num_parties: self.network.get_num_parties(),
+            threshold: self.threshold,
+            reconstruction: self.reconstruction.clone(),
+            generation_amount: self.generation_amount,
+            rng: RngState::capture(&self.rng_buffer.rng),
+            shared_rngs: self
+                .rng_buffer
+                .shared_rngs
+                .iter()
+                .map(RngState::capture)
+                .collect(),
+            r_t: self.rng_buffer.r_t.clone(),
+            r_2t: self.rng_buffer.r_2t.clone(),
+        }
+    }
+
+    /// Restores a [ShamirProtocol] from a snapshot taken with [ShamirProtocol::snapshot], using a freshly connected network. In contrast to [ShamirPreprocessing], this does not communicate. All parties need to restore the snapshots they took at the same point of the protocol.
+    pub fn restore(snapshot: ShamirSnapshot<F>, network: N) -> eyre::Result<Self> {
+        let id = network.get_id();
+        let num_parties = network.get_num_parties();
+        if id != snapshot.id || num_parties != snapshot.num_parties {
+            eyre::bail!(
+                "snapshot of party {} of {} can not be restored by party {id} of {num_parties}",
+                snapshot.id,
+                snapshot.num_parties
+            );
+        }
+        if snapshot.shared_rngs.len() != num_parties - 1
+            || snapshot.r_t.len() != snapshot.r_2t.len()
+        {
+            eyre::bail!("snapshot contains inconsistent randomness");
+        }
+        let mut rng_buffer = ShamirRng::from_rngs(
+            id,
+            num_parties,
+            snapshot.threshold,
+            snapshot.rng.restore(),
+            snapshot.shared_rngs.iter().map(RngState::restore).collect(),
+        );
+        rng_buffer.r_t = snapshot.r_t.clone();
+        rng_buffer.r_2t = snapshot.r_2t.clone();
+        let mut protocol = Self::from(ShamirPreprocessing {
+            threshold: snapshot.threshold,
+            rng_buffer,
+            network,
+        });
+        protocol.set_reconstruction(snapshot.reconstruction.clone())?;
+        protocol.generation_amount = snapshot.generation_amount;
+        Ok(protocol)
+    }
+
+    /// Returns the [Reconstruction] used for opening values.
+    pub fn reconstruction(&self) -> &Reconstruction {
+        &self.reconstruction
+    }
+
+    /// Sets the [Reconstruction] used for opening values. For [Reconstruction::Subset], the subset needs to consist of exactly `threshold + 1` distinct party ids.
+    pub fn set_reconstruction(&mut self, reconstruction: Reconstruction) -> eyre::Result<()> {
+        self.subset_lagrange_t = Vec::new();
+        if let Reconstruction::Subset(subset) = &reconstruction {
+            let num_parties = self.network.get_num_parties();
+            if subset.len() != self.threshold + 1 {
+                eyre::bail!(
+                    "reconstruction subset needs {} parties, but has {}",
+                    self.threshold + 1,
+                    subset.len()
+                );
+            }
+            for (i, id) in subset.iter().enumerate() {
+                if *id >= num_parties {
+                    eyre::bail!("invalid party id={} for {} parties", id, num_parties);
+                }
+                if subset[..i].contains(id) {
+                    eyre::bail!(
+                        "party id={} is contained twice in reconstruction subset",
+                        id
+                    );
+                }
+            }
+            self.subset_lagrange_t =
+                core::lagrange_from_coeff(&subset.iter().map(|id| id + 1).collect::<Vec<_>>());
+        }
+        self.reconstruction = reconstruction;
+        Ok(())
+    }
+
+    /// Sends our share of a value to open to the other parties according to the configured [Reconstruction] and receives the shares required to reconstruct it. Returns the received shares together with the matching lagrange coefficients for a polynomial of degree `threshold`.
+    pub(crate) fn broadcast_for_open<T: CanonicalSerialize + CanonicalDeserialize + Clone>(
+        &mut self,
+        data: T,
+    ) -> std::io::Result<(Vec<T>, Vec<F>)> {
+        match &self.reconstruction {
+            Reconstruction::Next => {
+                let rcv = self.network.broadcast_next(data, self.threshold + 1)?;
+                Ok((rcv, self.open_lagrange_t.clone()))
+            }
+            Reconstruction::Subset(subset) => {
+                let rcv = self.network.broadcast_subset(data, subset)?;
+                Ok((rcv, self.subset_lagrange_t.clone()))
+            }
+            Reconstruction::Fastest => {
+                let rcv = self.network.broadcast_fastest(data, self.threshold + 1)?;
+                let (ids, rcv): (Vec<_>, Vec<_>) = rcv.into_iter().unzip();
+                let lagrange = core::lagrange_from_coeff(
+                    &ids.into_iter().map(|id| id + 1).collect::<Vec<_>>(),
+                );
+                Ok((rcv, lagrange))
+            }
+        }
+    }
+
     /// Get a correlated randomness pair
     pub fn get_pair(&mut self) -> std::io::Result<(F, F)> {
         if self.rng_buffer.r_t.is_empty() {
@@ -520,4 +690,97 @@ impl<F: PrimeField, N: ShamirNetwork> ShamirProtocol<F, N> {
 
         Ok(ShamirPointShare::new(my_share - r_t))
     }
+
+    /// Reduces the degree of a vector of point shares of degree 2t in a single round, see [Self::degree_reduce_vec].
+    pub(crate) fn degree_reduce_point_vec<C>(
+        &mut self,
+        mut inputs: Vec<C>,
+    ) -> std::io::Result<Vec<ShamirPointShare<C>>>
+    where
+        C: CurveGroup + std::ops::Mul<F, Output = C> + for<'a> std::ops::Mul<&'a F, Output = C>,
+    {
+        let num_non_zero = self.network.get_num_parties() - self.threshold;
+
+        let len = inputs.len();
+        let mut r_ts = Vec::with_capacity(len);
+
+        for inp in inputs.iter_mut() {
+            let (r_t, r_2t) = self.get_pair()?;
+            *inp += C::generator().mul(r_2t);
+            r_ts.push(C::generator().mul(r_t));
+        }
+
+        let my_id = self.network.get_id();
+        let mut my_shares = if my_id == Self::KING_ID {
+            // Accumulate the result
+            let mut acc = vec![C::zero(); len];
+            for (other_id, lagrange) in self.mul_lagrange_2t.iter().enumerate() {
+                if other_id == Self::KING_ID {
+                    for (acc, muls) in izip!(&mut acc, &inputs) {
+                        *acc += *muls * lagrange;
+                    }
+                } else {
+                    let r = self.network.recv_many::<C>(other_id)?;
+                    if r.len() != len {
+                        return Err(std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,"During execution of degree_reduce_point_vec in MPC: Invalid number of elements received",
+                        ));
+                    }
+                    for (acc, muls) in izip!(&mut acc, r) {
+                        *acc += muls * lagrange;
+                    }
+                }
+            }
+            // So far parties who do not require sending, do not send, so no receive here
+
+            // Send fresh shares
+            // As in degree_reduce_point, <acc> is shared as a known polynomial
+            let shares = acc
+                .iter()
+                .map(|acc| {
+                    let poly = core::poly_with_zeros_from_precomputed_point(
+                        acc,
+                        &self.mul_reconstruct_with_zeros,
+                    );
+                    (0..num_non_zero)
+                        .map(|id| {
+                            core::evaluate_poly_point(&poly, C::ScalarField::from(id as u64 + 1))
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+
+            let mut my_share = Vec::new();
+            for id in 0..num_non_zero {
+                let vals = shares.iter().map(|s| s[id]).collect::<Vec<_>>();
+                if id == my_id {
+                    my_share = vals;
+                } else {
+                    self.network.send_many(id, &vals)?;
+                }
+            }
+            my_share
+        } else {
+            if my_id <= self.threshold * 2 {
+                // Only send if my items are required
+                self.network.send_many(Self::KING_ID, &inputs)?;
+            }
+            if my_id < num_non_zero {
+                let r = self.network.recv_many::<C>(Self::KING_ID)?;
+                if r.len() != len {
+                    return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,"During execution of degree_reduce_point_vec in MPC: Invalid number of elements received",
+                ));
+                }
+                r
+            } else {
+                vec![C::zero(); len]
+            }
+        };
+
+        for (share, r) in izip!(&mut my_shares, r_ts) {
+            *share -= r;
+        }
+        Ok(ShamirPointShare::convert_vec_rev(my_shares))
+    }
 }
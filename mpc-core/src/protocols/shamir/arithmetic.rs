@@ -69,6 +69,15 @@ pub fn local_mul_vec<F: PrimeField>(a: &[ShamirShare<F>], b: &[ShamirShare<F>])
         .collect::<Vec<_>>()
 }
 
+/// Performs element-wise multiplication of two slices of shares and subtracts the result of a previous [`local_mul_vec`] in place, i.e., `c = a * b - c`. *DOES NOT REDUCE DEGREE*
+pub fn local_mul_sub_vec<F: PrimeField>(a: &[ShamirShare<F>], b: &[ShamirShare<F>], c: &mut [F]) {
+    c.par_iter_mut()
+        .zip_eq(a.par_iter())
+        .zip_eq(b.par_iter())
+        .with_min_len(1024)
+        .for_each(|((c, a), b)| *c = a.a * b.a - *c);
+}
+
 /// Performs element-wise multiplication of two slices of shares.
 pub fn mul_vec<F: PrimeField, N: ShamirNetwork>(
     a: &[ShamirShare<F>],
@@ -173,8 +182,8 @@ pub fn open<F: PrimeField, N: ShamirNetwork>(
     a: ShamirShare<F>,
     shamir: &mut ShamirProtocol<F, N>,
 ) -> IoResult<F> {
-    let rcv = shamir.network.broadcast_next(a.a, shamir.threshold + 1)?;
-    let res = core::reconstruct(&rcv, &shamir.open_lagrange_t);
+    let (rcv, lagrange) = shamir.broadcast_for_open(a.a)?;
+    let res = core::reconstruct(&rcv, &lagrange);
     Ok(res)
 }
 
@@ -185,9 +194,7 @@ pub fn open_vec<F: PrimeField, N: ShamirNetwork>(
 ) -> IoResult<Vec<F>> {
     let a_a = ShamirShare::convert_slice(a);
 
-    let rcv = shamir
-        .network
-        .broadcast_next(a_a.to_owned(), shamir.threshold + 1)?;
+    let (rcv, lagrange) = shamir.broadcast_for_open(a_a.to_owned())?;
 
     let mut transposed = vec![vec![F::zero(); shamir.threshold + 1]; a.len()];
 
@@ -199,7 +206,7 @@ pub fn open_vec<F: PrimeField, N: ShamirNetwork>(
 
     let res = transposed
         .into_iter()
-        .map(|r| core::reconstruct(&r, &shamir.open_lagrange_t))
+        .map(|r| core::reconstruct(&r, &lagrange))
         .collect();
     Ok(res)
 }
@@ -74,6 +74,12 @@ impl<F: PrimeField> std::ops::SubAssign for ShamirPrimeFieldShare<F> {
     }
 }
 
+impl<F: PrimeField> std::ops::SubAssign<&ShamirPrimeFieldShare<F>> for ShamirPrimeFieldShare<F> {
+    fn sub_assign(&mut self, rhs: &Self) {
+        self.a -= rhs.a;
+    }
+}
+
 impl<F: PrimeField> std::ops::Sub<&ShamirPrimeFieldShare<F>> for ShamirPrimeFieldShare<F> {
     type Output = Self;
 
@@ -133,12 +139,26 @@ impl<F: PrimeField> std::ops::Mul<F> for ShamirPrimeFieldShare<F> {
     }
 }
 
+impl<F: PrimeField> std::ops::Mul<&F> for ShamirPrimeFieldShare<F> {
+    type Output = ShamirPrimeFieldShare<F>;
+
+    fn mul(self, rhs: &F) -> Self::Output {
+        Self::Output { a: self.a * rhs }
+    }
+}
+
 impl<F: PrimeField> std::ops::MulAssign<F> for ShamirPrimeFieldShare<F> {
     fn mul_assign(&mut self, rhs: F) {
         self.a *= rhs;
     }
 }
 
+impl<F: PrimeField> std::ops::MulAssign<&F> for ShamirPrimeFieldShare<F> {
+    fn mul_assign(&mut self, rhs: &F) {
+        self.a *= rhs;
+    }
+}
+
 impl<F: PrimeField> std::ops::Neg for ShamirPrimeFieldShare<F> {
     type Output = Self;
 
@@ -154,6 +174,18 @@ impl<F: PrimeField> std::ops::Neg for &ShamirPrimeFieldShare<F> {
     }
 }
 
+impl<F: PrimeField> std::iter::Sum for ShamirPrimeFieldShare<F> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self { a: F::zero() }, |acc, x| acc + x)
+    }
+}
+
+impl<'a, F: PrimeField> std::iter::Sum<&'a ShamirPrimeFieldShare<F>> for ShamirPrimeFieldShare<F> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self { a: F::zero() }, |acc, x| acc + x)
+    }
+}
+
 impl<F: PrimeField> ark_ff::Zero for ShamirPrimeFieldShare<F> {
     fn zero() -> Self {
         Self { a: F::zero() }
@@ -0,0 +1,185 @@
+//! # Threshold BLS
+//!
+//! This module implements threshold [BLS signatures](https://www.iacr.org/archive/asiacrypt2001/22480516.pdf) over Shamir-shared keys, e.g., so that the parties computing a proof can also sign the proof and its public inputs for downstream consumers. Signatures are points in G1 and public keys are points in G2.
+//!
+//! The secret key is generated with the distributed key generation of the [dkg](super::dkg) module in [keygen], so it is never known to any party. Each party creates a partial signature with [partial_sign]. Any `threshold + 1` valid partial signatures are combined into the signature with [combine_signatures], which can be verified against the public key with [verify] like a plain BLS signature.
+//!
+//! The distributed key generation follows the semi-honest model of the rest of this crate: a party sending shares that do not match its commitments is detected and the key generation aborts, but a malicious party may bias the distribution of the public key.
+
+use ark_ec::{
+    pairing::Pairing,
+    short_weierstrass::{Affine, Projective, SWCurveConfig},
+    AffineRepr, CurveGroup,
+};
+use ark_ff::Field;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use itertools::Itertools;
+use rand::{CryptoRng, Rng};
+use sha3::{Digest, Sha3_512};
+
+use super::{core, dkg, network::ShamirNetwork, ShamirPrimeFieldShare};
+
+/// The domain separation tag of the hash to G1.
+const DST: &[u8] = b"CO-SNARKS-THRESHOLD-BLS-SIG-G1-TAI-SHA3-512";
+
+/// The share of a threshold BLS key held by a party, the result of [keygen].
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct BlsKeyShare<P: Pairing> {
+    /// The id of the party holding this share
+    pub id: usize,
+    /// The threshold, i.e., `threshold + 1` partial signatures are required for a signature
+    pub threshold: usize,
+    /// The Shamir share of the secret key
+    pub secret_key: ShamirPrimeFieldShare<P::ScalarField>,
+    /// The public key
+    pub public_key: P::G2Affine,
+    /// The public keys of the shares of the secret key of all parties, used to verify partial signatures
+    pub public_key_shares: Vec<P::G2Affine>,
+}
+
+/// Jointly generates a threshold BLS key with the distributed key generation in [dkg](super::dkg::dkg). The secret key is never reconstructed.
+pub fn keygen<P: Pairing, N: ShamirNetwork, R: Rng + CryptoRng>(
+    threshold: usize,
+    network: &mut N,
+    rng: &mut R,
+) -> eyre::Result<BlsKeyShare<P>> {
+    let num_parties = network.get_num_parties();
+    let output = dkg::dkg::<P::G2, _, _>(threshold, network, rng)?;
+    Ok(BlsKeyShare {
+        id: output.id,
+        threshold,
+        secret_key: output.secret_share,
+        public_key: output.public_key(),
+        public_key_shares: output.public_key_shares(num_parties),
+    })
+}
+
+/// Hashes a message to a point in the prime order subgroup of the curve with the try-and-increment method. The message is public, so it is not an issue that the runtime depends on the message.
+pub fn hash_to_curve<C: SWCurveConfig>(msg: &[u8]) -> Affine<C> {
+    let len = 64 * C::BaseField::extension_degree() as usize;
+    let mut ctr = 0u64;
+    loop {
+        let mut bytes = Vec::with_capacity(len + 64);
+        let mut block = 0u64;
+        // one additional byte for the sign of the y-coordinate
+        while bytes.len() <= len {
+            let mut hasher = Sha3_512::new();
+            hasher.update(DST);
+            hasher.update(ctr.to_le_bytes());
+            hasher.update(block.to_le_bytes());
+            hasher.update(msg);
+            bytes.extend(hasher.finalize());
+            block += 1;
+        }
+        let greatest = bytes[len] & 1 == 1;
+        if let Some(point) = C::BaseField::from_random_bytes(&bytes[..len])
+            .and_then(|x| Affine::<C>::get_point_from_x_unchecked(x, greatest))
+        {
+            let point = point.clear_cofactor();
+            if !point.is_zero() {
+                return point;
+            }
+        }
+        ctr += 1;
+    }
+}
+
+/// Computes the partial signature of this party on the message. Does not require communication.
+pub fn partial_sign<P, C>(key: &BlsKeyShare<P>, msg: &[u8]) -> P::G1Affine
+where
+    P: Pairing<G1Affine = Affine<C>>,
+    C: SWCurveConfig<ScalarField = P::ScalarField>,
+{
+    (hash_to_curve::<C>(msg).into_group() * key.secret_key.a).into_affine()
+}
+
+/// Verifies the partial signature of party `id` on the message against the public key share of this party.
+pub fn verify_partial_signature<P, C>(
+    key: &BlsKeyShare<P>,
+    id: usize,
+    msg: &[u8],
+    partial_signature: &P::G1Affine,
+) -> bool
+where
+    P: Pairing<G1Affine = Affine<C>>,
+    C: SWCurveConfig<ScalarField = P::ScalarField>,
+{
+    match key.public_key_shares.get(id) {
+        Some(public_key_share) => verify_inner::<P, C>(public_key_share, msg, partial_signature),
+        None => false,
+    }
+}
+
+/// Combines the partial signatures of the parties with the given ids into a signature. Invalid partial signatures are skipped, and `threshold + 1` valid partial signatures are required.
+pub fn combine_signatures<P, C>(
+    key: &BlsKeyShare<P>,
+    msg: &[u8],
+    partial_signatures: &[(usize, P::G1Affine)],
+) -> eyre::Result<P::G1Affine>
+where
+    P: Pairing<G1Affine = Affine<C>>,
+    C: SWCurveConfig<ScalarField = P::ScalarField>,
+{
+    let mut ids = Vec::with_capacity(key.threshold + 1);
+    let mut shares = Vec::with_capacity(key.threshold + 1);
+    for (id, partial_signature) in partial_signatures {
+        if ids.len() == key.threshold + 1 {
+            break;
+        }
+        if ids.contains(&(id + 1)) {
+            continue;
+        }
+        if verify_partial_signature::<P, C>(key, *id, msg, partial_signature) {
+            ids.push(id + 1);
+            shares.push(partial_signature.into_group());
+        } else {
+            tracing::warn!("skipping invalid partial signature of party {id}");
+        }
+    }
+    if ids.len() <= key.threshold {
+        eyre::bail!(
+            "not enough valid partial signatures. Expected {}, got {}",
+            key.threshold + 1,
+            ids.len()
+        );
+    }
+    let lagrange = core::lagrange_from_coeff(&ids);
+    Ok(core::reconstruct_point::<Projective<C>>(&shares, &lagrange).into_affine())
+}
+
+/// Jointly signs the message, i.e., computes the partial signature of this party, broadcasts it, and combines the partial signatures of all parties. All parties receive the signature.
+pub fn sign<P, C, N>(key: &BlsKeyShare<P>, msg: &[u8], network: &mut N) -> eyre::Result<P::G1Affine>
+where
+    P: Pairing<G1Affine = Affine<C>>,
+    C: SWCurveConfig<ScalarField = P::ScalarField>,
+    N: ShamirNetwork,
+{
+    let partial_signature = partial_sign::<P, C>(key, msg);
+    let partial_signatures = network
+        .broadcast(partial_signature)?
+        .into_iter()
+        .enumerate()
+        .collect_vec();
+    combine_signatures::<P, C>(key, msg, &partial_signatures)
+}
+
+/// Verifies a BLS signature on the message against the public key.
+pub fn verify<P, C>(public_key: &P::G2Affine, msg: &[u8], signature: &P::G1Affine) -> bool
+where
+    P: Pairing<G1Affine = Affine<C>>,
+    C: SWCurveConfig<ScalarField = P::ScalarField>,
+{
+    verify_inner::<P, C>(public_key, msg, signature)
+}
+
+fn verify_inner<P, C>(public_key: &P::G2Affine, msg: &[u8], signature: &P::G1Affine) -> bool
+where
+    P: Pairing<G1Affine = Affine<C>>,
+    C: SWCurveConfig<ScalarField = P::ScalarField>,
+{
+    if signature.is_zero() || !signature.is_in_correct_subgroup_assuming_on_curve() {
+        return false;
+    }
+    let hash = hash_to_curve::<C>(msg);
+    P::pairing(*signature, P::G2Affine::generator()) == P::pairing(hash, *public_key)
+}
@@ -0,0 +1,111 @@
+//! # Distributed Key Generation
+//!
+//! This module implements the distributed key generation of [Pedersen](https://link.springer.com/chapter/10.1007/3-540-46416-6_47) (also known as joint Feldman VSS) over a [ShamirNetwork]. The parties jointly generate a Shamir-shared secret `x` together with the public key `x * G` without a trusted dealer, e.g., to bootstrap a committee. The secret is never known to any party.
+//!
+//! Every party publishes Feldman commitments to the coefficients of the joint sharing polynomial, so anybody can compute the public key share `x_i * G` of every party `i` and verify shares against them.
+//!
+//! The key generation follows the semi-honest model of the rest of this crate: a party sending shares that do not match its commitments is detected and the key generation aborts, but a malicious party may bias the distribution of the public key.
+
+use ark_ec::CurveGroup;
+use ark_ff::{UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use itertools::Itertools;
+use rand::{CryptoRng, Rng};
+
+use super::{core, network::ShamirNetwork, ShamirPrimeFieldShare};
+
+/// The result of the distributed key generation of a party, see [dkg].
+#[derive(Debug, Clone, CanonicalSerialize, CanonicalDeserialize)]
+pub struct DkgOutput<C: CurveGroup> {
+    /// The id of the party holding this share
+    pub id: usize,
+    /// The degree of the sharing polynomial, i.e., `threshold + 1` shares are required to reconstruct the secret
+    pub threshold: usize,
+    /// The Shamir share of the secret
+    pub secret_share: ShamirPrimeFieldShare<C::ScalarField>,
+    /// The Feldman commitments to the coefficients of the joint sharing polynomial. The first commitment is the public key.
+    pub commitments: Vec<C::Affine>,
+}
+
+impl<C: CurveGroup> DkgOutput<C> {
+    /// Returns the public key, i.e., the secret times the generator.
+    pub fn public_key(&self) -> C::Affine {
+        self.commitments[0]
+    }
+
+    /// Returns the public key share of party `id`, i.e., the share of party `id` times the generator.
+    pub fn public_key_share(&self, id: usize) -> C::Affine {
+        evaluate_commitment::<C>(&self.commitments, C::ScalarField::from(id as u64 + 1))
+            .into_affine()
+    }
+
+    /// Returns the public key shares of all `num_parties` parties.
+    pub fn public_key_shares(&self, num_parties: usize) -> Vec<C::Affine> {
+        let shares = (1..=num_parties)
+            .map(|j| evaluate_commitment::<C>(&self.commitments, C::ScalarField::from(j as u64)))
+            .collect_vec();
+        C::normalize_batch(&shares)
+    }
+
+    /// Checks whether `share` is the share of party `id` committed to in the commitments.
+    pub fn verify_share(&self, id: usize, share: &ShamirPrimeFieldShare<C::ScalarField>) -> bool {
+        (C::generator() * share.a).into_affine() == self.public_key_share(id)
+    }
+}
+
+fn evaluate_commitment<C: CurveGroup>(commitment: &[C::Affine], x: C::ScalarField) -> C {
+    let commitment = commitment.iter().map(|c| (*c).into()).collect_vec();
+    core::evaluate_poly_point(&commitment, x)
+}
+
+/// Jointly generates a Shamir-shared secret of degree `threshold` and the public Feldman commitments to it. Every party contributes a random polynomial of degree `threshold`, sends the evaluations to the other parties, and broadcasts Feldman commitments to the coefficients. The shares received from the other parties are checked against the commitments. The secret is the sum of the constant terms of all polynomials and is never reconstructed.
+pub fn dkg<C: CurveGroup, N: ShamirNetwork, R: Rng + CryptoRng>(
+    threshold: usize,
+    network: &mut N,
+    rng: &mut R,
+) -> eyre::Result<DkgOutput<C>> {
+    let id = network.get_id();
+    let num_parties = network.get_num_parties();
+    if threshold >= num_parties {
+        eyre::bail!(
+            "threshold {threshold} needs to be smaller than the number of parties {num_parties}"
+        );
+    }
+
+    let coeffs = (0..=threshold)
+        .map(|_| C::ScalarField::rand(rng))
+        .collect_vec();
+    let generator = C::generator();
+    let commitments = C::normalize_batch(&coeffs.iter().map(|c| generator * c).collect_vec());
+    let shares = (1..=num_parties)
+        .map(|j| vec![core::evaluate_poly(&coeffs, C::ScalarField::from(j as u64))])
+        .collect_vec();
+
+    let commitments = network.broadcast(commitments)?;
+    let shares = network.send_and_recv_each_many(shares)?;
+
+    let eval_point = C::ScalarField::from(id as u64 + 1);
+    let mut secret_share = C::ScalarField::zero();
+    let mut joint_commitments = vec![C::zero(); threshold + 1];
+    for (other_id, (commitment, share)) in commitments.iter().zip(shares).enumerate() {
+        if commitment.len() != threshold + 1 || share.len() != 1 {
+            eyre::bail!("party {other_id} sent a malformed contribution to the key generation");
+        }
+        if generator * share[0] != evaluate_commitment::<C>(commitment, eval_point) {
+            eyre::bail!(
+                "party {other_id} sent a share that does not match its commitment during the key generation"
+            );
+        }
+        secret_share += share[0];
+        for (joint, c) in joint_commitments.iter_mut().zip(commitment.iter()) {
+            *joint += c;
+        }
+    }
+
+    Ok(DkgOutput {
+        id,
+        threshold,
+        secret_share: ShamirPrimeFieldShare::new(secret_share),
+        commitments: C::normalize_batch(&joint_commitments),
+    })
+}
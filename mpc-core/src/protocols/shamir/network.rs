@@ -6,9 +6,10 @@ use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use bytes::{Bytes, BytesMut};
 use eyre::{bail, eyre, Report};
 use mpc_net::{
-    channel::ChannelHandle, config::NetworkConfig, MpcNetworkHandler, MpcNetworkHandlerWrapper,
+    channel::ChannelHandle, config::NetworkConfig, pool::PooledSession, AbortHandle,
+    MessageCounter, MpcNetworkHandler, MpcNetworkHandlerWrapper, SessionGuard, WarmUpStats,
 };
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::Instant};
 
 /// This trait defines the network interface for the Shamir protocol.
 pub trait ShamirNetwork: Send {
@@ -46,6 +47,23 @@ pub trait ShamirNetwork: Send {
     /// Receives a vector of data from the party with the given id.
     fn recv_many<F: CanonicalDeserialize>(&mut self, from: usize) -> std::io::Result<Vec<F>>;
 
+    /// Sends the same data to all parties in `targets` in a single round. This function has a default implementation for calling [ShamirNetwork::send] for every target, implementations should serialize the data once and write to all parties in parallel.
+    fn send_all<F: CanonicalSerialize + Clone>(
+        &mut self,
+        targets: &[usize],
+        data: F,
+    ) -> std::io::Result<()> {
+        for &target in targets {
+            self.send(target, data.to_owned())?;
+        }
+        Ok(())
+    }
+
+    /// Receives data from all parties in `from` in a single round. The result is a vector where the data from party `from[i]` is at index i. This function has a default implementation for calling [ShamirNetwork::recv] for every party, implementations should read from all parties in parallel.
+    fn recv_all<F: CanonicalDeserialize>(&mut self, from: &[usize]) -> std::io::Result<Vec<F>> {
+        from.iter().map(|&other_id| self.recv(other_id)).collect()
+    }
+
     /// Sends data to all parties and receives data from all other parties. The result is a vector where the data from party i is at index i, including my own data.
     fn broadcast<F: CanonicalSerialize + CanonicalDeserialize + Clone>(
         &mut self,
@@ -59,6 +77,56 @@ pub trait ShamirNetwork: Send {
         num: usize,
     ) -> std::io::Result<Vec<F>>;
 
+    /// Sends data to all other parties if our own id is contained in `subset` and receives data from all other parties in `subset`. Thus, the result is a vector of the same length as `subset`, where the data from party `subset[i]` is at index i, including my own data if I am part of the subset. All parties need to call this function with the same `subset`.
+    fn broadcast_subset<F: CanonicalSerialize + CanonicalDeserialize + Clone>(
+        &mut self,
+        data: F,
+        subset: &[usize],
+    ) -> std::io::Result<Vec<F>> {
+        let id = self.get_id();
+        if subset.contains(&id) {
+            let targets = (0..self.get_num_parties())
+                .filter(|&other_id| other_id != id)
+                .collect::<Vec<_>>();
+            self.send_all(&targets, data.to_owned())?;
+        }
+        let from = subset
+            .iter()
+            .copied()
+            .filter(|&other_id| other_id != id)
+            .collect::<Vec<_>>();
+        let mut rcv = self.recv_all(&from)?.into_iter();
+        Ok(subset
+            .iter()
+            .map(|&other_id| {
+                if other_id == id {
+                    data.to_owned()
+                } else {
+                    rcv.next().expect("received from all parties in the subset")
+                }
+            })
+            .collect())
+    }
+
+    /// Sends data to all other parties and returns the data of the first `num` - 1 parties to respond together with their ids. My own data is always at index 0, thus the result is a vector of length num. The data of the remaining parties is still received, but discarded. This function has a default implementation for calling [ShamirNetwork::broadcast], which waits for all parties and returns the data in order of the party ids.
+    fn broadcast_fastest<F: CanonicalSerialize + CanonicalDeserialize + Clone>(
+        &mut self,
+        data: F,
+        num: usize,
+    ) -> std::io::Result<Vec<(usize, F)>> {
+        let id = self.get_id();
+        let mut res = Vec::with_capacity(num);
+        res.push((id, data.to_owned()));
+        res.extend(
+            self.broadcast(data)?
+                .into_iter()
+                .enumerate()
+                .filter(|(other_id, _)| *other_id != id)
+                .take(num - 1),
+        );
+        Ok(res)
+    }
+
     /// Sends and receives to and from each party. Data must be of shape num_parties x n. The element that is "sent" to yourself is passed back directly.
     fn send_and_recv_each_many<
         F: CanonicalSerialize + CanonicalDeserialize + Clone + Send + 'static,
@@ -126,9 +194,61 @@ impl ShamirMpcNet {
         })
     }
 
+    /// Constructs the network interface for a session leased from a [MpcConnectionPool](mpc_net::pool::MpcConnectionPool), reusing the pooled connections instead of establishing new ones. The pool needs to contain at least 3 parties.
+    pub fn from_session(session: &PooledSession) -> Result<Self, Report> {
+        let num_parties = session.get_num_parties();
+        if num_parties <= 2 {
+            bail!("Shamir protocol requires at least 3 parties")
+        }
+        let id = session.get_id();
+        let net_handler = Arc::clone(session.handler());
+        let channels = net_handler.runtime.block_on(async {
+            let mut channels = session.get_byte_channels().await?;
+
+            let mut channels_ = HashMap::with_capacity(num_parties - 1);
+
+            for other_id in 0..num_parties {
+                if other_id != id {
+                    let chan = channels
+                        .remove(&other_id)
+                        .ok_or_else(|| eyre!("no channel found for party id={}", other_id))?;
+                    channels_.insert(other_id, ChannelHandle::manage(chan));
+                }
+            }
+
+            if !channels.is_empty() {
+                bail!("unexpected channels found")
+            }
+
+            Ok(channels_)
+        })?;
+        Ok(Self {
+            id,
+            num_parties,
+            net_handler,
+            channels,
+        })
+    }
+
+    /// Returns a [SessionGuard] that aborts the session at all parties if it is dropped before [SessionGuard::finish] is called, e.g., because a command failed at this party. Must not be used with a network constructed by [ShamirMpcNet::from_session].
+    pub fn session_guard(&self) -> SessionGuard {
+        SessionGuard::new(Arc::clone(&self.net_handler))
+    }
+
+    /// Returns an [AbortHandle] to abort the session at all parties from another thread, e.g., if the user interrupts the command.
+    pub fn abort_handle(&self) -> AbortHandle {
+        self.net_handler.inner.abort_handle()
+    }
+
+    /// Returns the [MessageCounter] of the messages this network and its forks exchanged with the other parties. The dummy messages of the warm-up are counted as well.
+    pub fn message_counter(&self) -> Arc<MessageCounter> {
+        Arc::clone(&self.net_handler.messages)
+    }
+
     /// Sends bytes over the network to the target party.
     pub fn send_bytes(&mut self, target: usize, data: Bytes) -> std::io::Result<()> {
         if let Some(chan) = self.channels.get_mut(&target) {
+            self.net_handler.messages.record_sent(target, data.len());
             std::mem::drop(chan.blocking_send(data));
             Ok(())
         } else {
@@ -150,17 +270,89 @@ impl ShamirMpcNet {
             ));
         };
 
-        let data = data.map_err(|_| {
-            std::io::Error::new(std::io::ErrorKind::BrokenPipe, "receive channel end died")
-        })??;
+        let data = data
+            .map_err(|_| {
+                std::io::Error::new(std::io::ErrorKind::BrokenPipe, "receive channel end died")
+            })?
+            .map_err(|err| self.net_handler.inner.map_session_error(err))?;
+        self.net_handler.messages.record_received(from, data.len());
         Ok(data)
     }
 
+    /// Sends the same bytes to all target parties. The bytes are only queued for the channels here, so the writes to the parties happen in parallel.
+    pub fn send_bytes_all(&mut self, targets: &[usize], data: Bytes) -> std::io::Result<()> {
+        for &target in targets {
+            self.send_bytes(target, data.clone())?;
+        }
+        Ok(())
+    }
+
+    /// Receives bytes from all provided parties. The reads from all parties are requested before waiting for the first one, so the data is received in parallel.
+    pub fn recv_bytes_all(&mut self, from: &[usize]) -> std::io::Result<Vec<BytesMut>> {
+        let mut pending = Vec::with_capacity(from.len());
+        for other_id in from {
+            let chan = self.channels.get_mut(other_id).ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("No channel found for party id={}", other_id),
+                )
+            })?;
+            pending.push(chan.blocking_recv());
+        }
+        pending
+            .into_iter()
+            .zip(from)
+            .map(|(data, other_id)| {
+                let data = data
+                    .blocking_recv()
+                    .map_err(|_| {
+                        std::io::Error::new(
+                            std::io::ErrorKind::BrokenPipe,
+                            "receive channel end died",
+                        )
+                    })?
+                    .map_err(|err| self.net_handler.inner.map_session_error(err))?;
+                self.net_handler
+                    .messages
+                    .record_received(*other_id, data.len());
+                Ok(data)
+            })
+            .collect()
+    }
+
+    /// Warms up the connections to the other parties by exchanging dummy messages of at most `max_message_len` bytes until the `deadline` of one of the parties has passed, see [WarmUpStats::run].
+    pub fn warm_up(
+        &mut self,
+        deadline: Instant,
+        max_message_len: usize,
+    ) -> std::io::Result<WarmUpStats> {
+        let others = (0..self.num_parties)
+            .filter(|&other_id| other_id != self.id)
+            .collect::<Vec<_>>();
+        WarmUpStats::run(deadline, max_message_len, |message| {
+            self.send_bytes_all(&others, message)?;
+            self.recv_bytes_all(&others)
+        })
+    }
+
     pub(crate) fn _id(&self) -> usize {
         self.id
     }
 }
 
+fn serialize_bytes<F: CanonicalSerialize>(data: &F) -> std::io::Result<Bytes> {
+    let size = data.serialized_size(ark_serialize::Compress::No);
+    let mut ser_data = Vec::with_capacity(size);
+    data.serialize_uncompressed(&mut ser_data)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    Ok(Bytes::from(ser_data))
+}
+
+fn deserialize_bytes<F: CanonicalDeserialize>(data: &BytesMut) -> std::io::Result<F> {
+    F::deserialize_uncompressed(&data[..])
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
 impl ShamirNetwork for ShamirMpcNet {
     fn get_id(&self) -> usize {
         self.id
@@ -191,38 +383,35 @@ impl ShamirNetwork for ShamirMpcNet {
         Ok(res)
     }
 
+    fn send_all<F: CanonicalSerialize + Clone>(
+        &mut self,
+        targets: &[usize],
+        data: F,
+    ) -> std::io::Result<()> {
+        let send_data = serialize_bytes(&data)?;
+        self.send_bytes_all(targets, send_data)
+    }
+
+    fn recv_all<F: CanonicalDeserialize>(&mut self, from: &[usize]) -> std::io::Result<Vec<F>> {
+        self.recv_bytes_all(from)?
+            .iter()
+            .map(deserialize_bytes)
+            .collect()
+    }
+
     fn broadcast<F: CanonicalSerialize + CanonicalDeserialize + Clone>(
         &mut self,
         data: F,
     ) -> std::io::Result<Vec<F>> {
-        // Serialize
-        let size = data.serialized_size(ark_serialize::Compress::No);
-        let mut ser_data = Vec::with_capacity(size);
-        data.to_owned()
-            .serialize_uncompressed(&mut ser_data)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
-        let send_data = Bytes::from(ser_data);
+        let others = (0..self.num_parties)
+            .filter(|&other_id| other_id != self.id)
+            .collect::<Vec<_>>();
+        self.send_all(&others, data.to_owned())?;
+        let mut rcv = self.recv_all::<F>(&others)?;
 
-        // Send
-        for other_id in 0..self.num_parties {
-            if other_id != self.id {
-                self.send_bytes(other_id, send_data.to_owned())?;
-            }
-        }
-
-        // Receive
         let mut res = Vec::with_capacity(self.num_parties);
-        for other_id in 0..self.num_parties {
-            if other_id != self.id {
-                let data = self.recv_bytes(other_id)?;
-                let deser = F::deserialize_uncompressed(&data[..])
-                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
-                res.push(deser);
-            } else {
-                res.push(data.to_owned());
-            }
-        }
-
+        res.append(&mut rcv);
+        res.insert(self.id, data);
         Ok(res)
     }
 
@@ -231,31 +420,63 @@ impl ShamirNetwork for ShamirMpcNet {
         data: F,
         num: usize,
     ) -> std::io::Result<Vec<F>> {
-        // Serialize
-        let size = data.serialized_size(ark_serialize::Compress::No);
-        let mut ser_data = Vec::with_capacity(size);
-        data.to_owned()
-            .serialize_uncompressed(&mut ser_data)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
-        let send_data = Bytes::from(ser_data);
+        let targets = (1..num)
+            .map(|s| (self.id + s) % self.num_parties)
+            .collect::<Vec<_>>();
+        self.send_all(&targets, data.to_owned())?;
+
+        let from = (1..num)
+            .map(|r| (self.id + self.num_parties - r) % self.num_parties)
+            .collect::<Vec<_>>();
+        let mut res = Vec::with_capacity(num);
+        res.push(data);
+        res.extend(self.recv_all(&from)?);
+        Ok(res)
+    }
 
+    fn broadcast_fastest<F: CanonicalSerialize + CanonicalDeserialize + Clone>(
+        &mut self,
+        data: F,
+        num: usize,
+    ) -> std::io::Result<Vec<(usize, F)>> {
         // Send
-        for s in 1..num {
-            let other_id = (self.id + s) % self.num_parties;
-            // if other_id != self.id {
-            self.send_bytes(other_id, send_data.to_owned())?;
-            // }
+        let others = (0..self.num_parties)
+            .filter(|&other_id| other_id != self.id)
+            .collect::<Vec<_>>();
+        self.send_all(&others, data.to_owned())?;
+
+        // Receive from all parties in the background and forward the results in order of arrival. Late messages are consumed by their task and dropped, such that the channels stay in sync.
+        let (send, mut recv) = tokio::sync::mpsc::unbounded_channel();
+        for (other_id, chan) in self.channels.iter_mut() {
+            let other_id = *other_id;
+            let data = chan.blocking_recv();
+            let send = send.clone();
+            let messages = Arc::clone(&self.net_handler.messages);
+            self.net_handler.runtime.spawn(async move {
+                let data = data.await.map_err(|_| {
+                    std::io::Error::new(std::io::ErrorKind::BrokenPipe, "receive channel end died")
+                });
+                // late messages are counted as well, as they were sent by the other party
+                if let Ok(Ok(data)) = &data {
+                    messages.record_received(other_id, data.len());
+                }
+                // the receiver is gone if enough other parties were faster
+                let _ = send.send((other_id, data));
+            });
         }
+        drop(send);
 
-        // Receive
         let mut res = Vec::with_capacity(num);
-        res.push(data);
-        for r in 1..num {
-            let other_id = (self.id + self.num_parties - r) % self.num_parties;
-            let data = self.recv_bytes(other_id)?;
-            let deser = F::deserialize_uncompressed(&data[..])
-                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
-            res.push(deser);
+        res.push((self.id, data));
+        while res.len() < num {
+            let (other_id, data) = recv.blocking_recv().ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("cannot receive from {} parties", num - 1),
+                )
+            })?;
+            let data = data?.map_err(|err| self.net_handler.inner.map_session_error(err))?;
+            res.push((other_id, deserialize_bytes(&data)?));
         }
 
         Ok(res)
@@ -0,0 +1,63 @@
+//! Non-native field arithmetic
+//!
+//! This module implements the operations required for [NonNativeField](crate::nonnative::NonNativeField) for Shamir secret sharing.
+
+use ark_ff::PrimeField;
+
+use crate::nonnative::NonNativeArithmetic;
+
+use super::{arithmetic, network::ShamirNetwork, IoResult, ShamirProtocol, ShamirShare};
+
+impl<F: PrimeField, N: ShamirNetwork> NonNativeArithmetic<F> for ShamirProtocol<F, N> {
+    type Share = ShamirShare<F>;
+
+    fn add(&self, a: &Self::Share, b: &Self::Share) -> Self::Share {
+        arithmetic::add(*a, *b)
+    }
+
+    fn sub(&self, a: &Self::Share, b: &Self::Share) -> Self::Share {
+        arithmetic::sub(*a, *b)
+    }
+
+    fn add_public(&self, a: &Self::Share, b: F) -> Self::Share {
+        arithmetic::add_public(*a, b)
+    }
+
+    fn mul_public(&self, a: &Self::Share, b: F) -> Self::Share {
+        arithmetic::mul_public(*a, b)
+    }
+
+    fn promote_to_trivial_share(&self, a: F) -> Self::Share {
+        arithmetic::promote_to_trivial_share(a)
+    }
+
+    fn mul_many(&mut self, a: &[Self::Share], b: &[Self::Share]) -> IoResult<Vec<Self::Share>> {
+        arithmetic::mul_vec(a, b, self)
+    }
+
+    fn open_many(&mut self, a: &[Self::Share]) -> IoResult<Vec<F>> {
+        arithmetic::open_vec(a, self)
+    }
+
+    fn sum_of_inputs(&mut self, inputs: Vec<F>) -> IoResult<Vec<Self::Share>> {
+        // The degree reduction computes the sum of the inputs of the first 2t+1 parties weighted by their lagrange coefficients, thus we divide by them in advance
+        let id = self.network.get_id();
+        let inputs = match self.mul_lagrange_2t.get(id) {
+            Some(lagrange) => {
+                let lagrange_inv = lagrange
+                    .inverse()
+                    .expect("Lagrange coefficients are non-zero");
+                inputs
+                    .into_iter()
+                    .map(|input| input * lagrange_inv)
+                    .collect()
+            }
+            None => vec![F::zero(); inputs.len()],
+        };
+        self.degree_reduce_vec(inputs)
+    }
+
+    fn num_input_parties(&self) -> usize {
+        self.mul_lagrange_2t.len()
+    }
+}
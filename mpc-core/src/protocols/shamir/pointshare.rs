@@ -6,6 +6,7 @@ mod ops;
 pub(super) mod types;
 
 use ark_ec::CurveGroup;
+use rayon::prelude::*;
 
 use super::{
     core, network::ShamirNetwork, IoResult, ShamirPointShare, ShamirPrimeFieldShare,
@@ -15,6 +16,9 @@ use super::{
 type FieldShare<C> = ShamirPrimeFieldShare<C>;
 type PointShare<C> = ShamirPointShare<C>;
 
+/// The default number of MSM results that [msm_many] reduces in a single degree reduction round.
+pub const MSM_DEGREE_REDUCE_BATCH: usize = 64;
+
 /// Performs addition between two shares.
 pub fn add<C: CurveGroup>(a: &PointShare<C>, b: &PointShare<C>) -> PointShare<C> {
     a + b
@@ -94,8 +98,8 @@ pub fn open_point<C: CurveGroup, N: ShamirNetwork>(
     a: &PointShare<C>,
     shamir: &mut ShamirProtocol<C::ScalarField, N>,
 ) -> IoResult<C> {
-    let rcv = shamir.network.broadcast_next(a.a, shamir.threshold + 1)?;
-    let res = core::reconstruct_point(&rcv, &shamir.open_lagrange_t);
+    let (rcv, lagrange) = shamir.broadcast_for_open(a.a)?;
+    let res = core::reconstruct_point(&rcv, &lagrange);
     Ok(res)
 }
 
@@ -106,9 +110,7 @@ pub fn open_point_many<C: CurveGroup, N: ShamirNetwork>(
 ) -> IoResult<Vec<C>> {
     let a_a = ShamirPointShare::convert_slice(a);
 
-    let rcv = shamir
-        .network
-        .broadcast_next(a_a.to_owned(), shamir.threshold + 1)?;
+    let (rcv, lagrange) = shamir.broadcast_for_open(a_a.to_owned())?;
 
     let mut transposed = vec![vec![C::zero(); shamir.threshold + 1]; a.len()];
 
@@ -120,7 +122,7 @@ pub fn open_point_many<C: CurveGroup, N: ShamirNetwork>(
 
     let res = transposed
         .into_iter()
-        .map(|r| core::reconstruct_point(&r, &shamir.open_lagrange_t))
+        .map(|r| core::reconstruct_point(&r, &lagrange))
         .collect();
     Ok(res)
 }
@@ -136,3 +138,61 @@ pub fn msm_public_points<C: CurveGroup>(
     tracing::trace!("< MSM public points for {} elements", points.len());
     PointShare::<C> { a: res }
 }
+
+/// Computes the local product of shared points and shared scalars, i.e., a point share of degree 2t.
+fn msm_local<C: CurveGroup>(points: &[PointShare<C>], scalars: &[FieldShare<C::ScalarField>]) -> C {
+    debug_assert_eq!(points.len(), scalars.len());
+    let bases = C::normalize_batch(PointShare::convert_slice(points));
+    C::msm_unchecked(&bases, &scalars.iter().map(|s| s.a).collect::<Vec<_>>())
+}
+
+/// Perfoms MSM between shared curve points and field shares. The local products are accumulated before the degree reduction, so the MSM requires a single degree reduction independent of its size.
+pub fn msm<C: CurveGroup, N: ShamirNetwork>(
+    points: &[PointShare<C>],
+    scalars: &[FieldShare<C::ScalarField>],
+    shamir: &mut ShamirProtocol<C::ScalarField, N>,
+) -> IoResult<PointShare<C>> {
+    tracing::trace!("> MSM shared points for {} elements", points.len());
+    let res = shamir.degree_reduce_point(msm_local(points, scalars))?;
+    tracing::trace!("< MSM shared points for {} elements", points.len());
+    Ok(res)
+}
+
+/// Performs many MSMs between shared curve points and field shares, see [msm].
+///
+/// Instead of one degree reduction round per MSM, the MSMs are split into batches of `batch_size` MSMs whose results are reduced in a single round, e.g., [MSM_DEGREE_REDUCE_BATCH]. The degree reduction of a batch runs concurrently with the local bucket accumulation of the next batch, so the parties do not idle while waiting for the network. The batches only depend on the number of MSMs, so all parties use the same schedule.
+pub fn msm_many<C: CurveGroup, N: ShamirNetwork>(
+    msms: &[(&[PointShare<C>], &[FieldShare<C::ScalarField>])],
+    batch_size: usize,
+    shamir: &mut ShamirProtocol<C::ScalarField, N>,
+) -> IoResult<Vec<PointShare<C>>> {
+    tracing::trace!(
+        "> {} MSMs shared points in batches of {batch_size}",
+        msms.len()
+    );
+    let mut results = Vec::with_capacity(msms.len());
+    let mut pending: Option<Vec<C>> = None;
+    for batch in msms.chunks(batch_size.max(1)) {
+        let (local, reduced) = rayon::join(
+            || {
+                batch
+                    .par_iter()
+                    .map(|(points, scalars)| msm_local(points, scalars))
+                    .collect::<Vec<_>>()
+            },
+            || {
+                pending
+                    .take()
+                    .map(|pending| shamir.degree_reduce_point_vec(pending))
+                    .transpose()
+            },
+        );
+        results.extend(reduced?.into_iter().flatten());
+        pending = Some(local);
+    }
+    if let Some(pending) = pending {
+        results.extend(shamir.degree_reduce_point_vec(pending)?);
+    }
+    tracing::trace!("< {} MSMs shared points", msms.len());
+    Ok(results)
+}
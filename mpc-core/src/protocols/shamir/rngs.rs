@@ -32,13 +32,29 @@ impl<F: PrimeField> ShamirRng<F> {
 
         let shared_rngs = Self::get_shared_rngs(network, &mut rng)?;
 
+        Ok(Self::from_rngs(
+            network.get_id(),
+            num_parties,
+            threshold,
+            rng,
+            shared_rngs,
+        ))
+    }
+
+    /// Creates the rng from already established shared rngs, e.g., when restoring a snapshot.
+    pub(super) fn from_rngs(
+        id: usize,
+        num_parties: usize,
+        threshold: usize,
+        rng: RngType,
+        shared_rngs: Vec<RngType>,
+    ) -> Self {
         // We use the DN07 Vandermonde matrix to create t+1 random double shares at once.
         // We do not use Atlas to create n shares at once, since only t+1 out of n shares would be uniformly random, thus the King server during multiplication would have to be rotated.
 
         // let atlas_dn_matrix = Self::generate_atlas_dn_matrix(num_parties, threshold);
         let matrix = Self::create_vandermonde_matrix(num_parties, threshold);
 
-        let id = network.get_id();
         let mut ids = Vec::with_capacity(threshold + 1);
         for i in 1..=threshold + 1 {
             let id_ = (id + i) % num_parties + 1;
@@ -50,7 +66,7 @@ impl<F: PrimeField> ShamirRng<F> {
         let precomputed_interpolation_r_2t =
             Self::precompute_interpolation_polys(id, threshold * 2, num_parties);
 
-        Ok(Self {
+        Self {
             id,
             rng,
             threshold,
@@ -61,7 +77,7 @@ impl<F: PrimeField> ShamirRng<F> {
             matrix,
             r_t: Vec::new(),
             r_2t: Vec::new(),
-        })
+        }
     }
 
     // For DN07 we generate t+1 double shares at once, for Atlas it is n
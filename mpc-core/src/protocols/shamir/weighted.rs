@@ -0,0 +1,232 @@
+//! Weighted Shamir
+//!
+//! This module implements Shamir sharing for weighted trust structures, where a party can hold more than one share. A party with weight `w` holds the shares of `w` consecutive virtual parties. Thus, a sharing of degree `t` hides the secret from every set of parties with a total weight of at most `t`, and every set of parties with a total weight of more than `t` can reconstruct it.
+
+use std::ops::Range;
+
+use ark_ff::PrimeField;
+use itertools::izip;
+use rand::{CryptoRng, Rng};
+
+use super::{core, network::ShamirNetwork, IoResult, ShamirShare};
+
+/// The weights of the parties of a weighted Shamir sharing, i.e., the number of shares every party holds. Party `i` holds the shares of the virtual parties in [ShareWeights::virtual_ids].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShareWeights {
+    // offsets[i] is the first virtual id of party i, offsets[num_parties] the number of shares
+    offsets: Vec<usize>,
+}
+
+impl ShareWeights {
+    /// Creates the weights from the number of shares of every party. Fails if there are no parties or a party has no shares.
+    pub fn new(weights: &[usize]) -> eyre::Result<Self> {
+        if weights.is_empty() {
+            eyre::bail!("weighted sharing requires at least one party");
+        }
+        let mut offsets = Vec::with_capacity(weights.len() + 1);
+        offsets.push(0);
+        for (party, weight) in weights.iter().enumerate() {
+            if *weight == 0 {
+                eyre::bail!("party {party} has a weight of 0");
+            }
+            offsets.push(offsets[party] + weight);
+        }
+        Ok(Self { offsets })
+    }
+
+    /// Creates the weights of an unweighted sharing, where every party holds one share.
+    pub fn uniform(num_parties: usize) -> Self {
+        Self {
+            offsets: (0..=num_parties).collect(),
+        }
+    }
+
+    /// Returns the number of (physical) parties.
+    pub fn num_parties(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Returns the total number of shares, i.e., the number of virtual parties.
+    pub fn num_shares(&self) -> usize {
+        self.offsets[self.num_parties()]
+    }
+
+    /// Returns the number of shares of the given party.
+    pub fn weight(&self, party: usize) -> usize {
+        self.offsets[party + 1] - self.offsets[party]
+    }
+
+    /// Returns the total weight of the given parties.
+    pub fn total_weight(&self, parties: &[usize]) -> usize {
+        parties.iter().map(|party| self.weight(*party)).sum()
+    }
+
+    /// Returns the virtual ids of the shares of the given party. The share of virtual party `i` is the evaluation of the sharing polynomial at `i + 1`.
+    pub fn virtual_ids(&self, party: usize) -> Range<usize> {
+        self.offsets[party]..self.offsets[party + 1]
+    }
+
+    /// Returns the party holding the share of the given virtual party.
+    pub fn party_of(&self, virtual_id: usize) -> Option<usize> {
+        if virtual_id >= self.num_shares() {
+            return None;
+        }
+        Some(self.offsets.partition_point(|offset| *offset <= virtual_id) - 1)
+    }
+
+    /// Returns whether the given parties hold enough shares to reconstruct a sharing of the given degree.
+    pub fn can_reconstruct(&self, parties: &[usize], degree: usize) -> bool {
+        self.total_weight(parties) > degree
+    }
+
+    /// Returns the parties whose shares the given party uses to reconstruct a sharing of the given degree, i.e., the party itself and its predecessors until their total weight exceeds the degree. This is the weighted analogue of [ShamirNetwork::broadcast_next].
+    pub fn reconstruction_set(&self, party: usize, degree: usize) -> eyre::Result<Vec<usize>> {
+        if self.num_shares() <= degree {
+            eyre::bail!(
+                "a sharing of degree {degree} can not be reconstructed from {} shares",
+                self.num_shares()
+            );
+        }
+        let num_parties = self.num_parties();
+        let mut set = Vec::new();
+        let mut weight = 0;
+        let mut other = party;
+        while weight <= degree {
+            set.push(other);
+            weight += self.weight(other);
+            other = (other + num_parties - 1) % num_parties;
+        }
+        Ok(set)
+    }
+
+    /// Returns the evaluation points (virtual id + 1) of the shares of the given parties, in the order of the parties.
+    fn coeffs(&self, parties: &[usize]) -> Vec<usize> {
+        parties
+            .iter()
+            .flat_map(|party| self.virtual_ids(*party).map(|id| id + 1))
+            .collect()
+    }
+}
+
+/// Shares a field element for a weighted trust structure. The result contains the shares of every party, party `i` gets [ShareWeights::weight] shares.
+pub fn share_field_element_weighted<F: PrimeField, R: Rng + CryptoRng>(
+    val: F,
+    degree: usize,
+    weights: &ShareWeights,
+    rng: &mut R,
+) -> Vec<Vec<ShamirShare<F>>> {
+    let mut shares = core::share(val, weights.num_shares(), degree, rng).into_iter();
+    (0..weights.num_parties())
+        .map(|party| {
+            ShamirShare::convert_vec_rev(shares.by_ref().take(weights.weight(party)).collect())
+        })
+        .collect()
+}
+
+/// Shares a vector of field elements for a weighted trust structure. The result is indexed by party, virtual share of the party, and element, i.e., `shares[i][j][k]` is the `j`-th share of party `i` of the `k`-th element.
+pub fn share_field_elements_weighted<F: PrimeField, R: Rng + CryptoRng>(
+    vals: &[F],
+    degree: usize,
+    weights: &ShareWeights,
+    rng: &mut R,
+) -> Vec<Vec<Vec<ShamirShare<F>>>> {
+    let mut result = (0..weights.num_parties())
+        .map(|party| vec![Vec::with_capacity(vals.len()); weights.weight(party)])
+        .collect::<Vec<_>>();
+    for val in vals {
+        let shares = share_field_element_weighted(*val, degree, weights, rng);
+        for (party_result, party_shares) in izip!(&mut result, shares) {
+            for (r, s) in izip!(party_result, party_shares) {
+                r.push(s);
+            }
+        }
+    }
+    result
+}
+
+/// Reconstructs a vector of field elements from the weighted shares of the given `parties`, see [share_field_elements_weighted] for the layout. Fails if the parties do not hold enough shares.
+pub fn combine_field_elements_weighted<F: PrimeField>(
+    shares: &[Vec<Vec<ShamirShare<F>>>],
+    parties: &[usize],
+    weights: &ShareWeights,
+    degree: usize,
+) -> eyre::Result<Vec<F>> {
+    if shares.len() != parties.len() {
+        eyre::bail!(
+            "Number of shares ({}) does not match number of parties ({})",
+            shares.len(),
+            parties.len()
+        );
+    }
+    for (party_shares, party) in izip!(shares, parties) {
+        if *party >= weights.num_parties() || party_shares.len() != weights.weight(*party) {
+            eyre::bail!("Party {party} does not hold the shares of its weight");
+        }
+    }
+    let shares = shares.iter().flatten().cloned().collect::<Vec<_>>();
+    super::combine_field_elements(&shares, &weights.coeffs(parties), degree)
+}
+
+/// Opens a vector of weighted shared field elements, where `shares[j][k]` is our `j`-th share of the `k`-th element. Every party receives the shares of its [ShareWeights::reconstruction_set] with one communication round.
+pub fn open_many_weighted<F: PrimeField, N: ShamirNetwork>(
+    shares: &[Vec<ShamirShare<F>>],
+    weights: &ShareWeights,
+    degree: usize,
+    network: &mut N,
+) -> IoResult<Vec<F>> {
+    let invalid = |msg: String| std::io::Error::new(std::io::ErrorKind::InvalidInput, msg);
+    let id = network.get_id();
+    if weights.num_parties() != network.get_num_parties() {
+        return Err(invalid(format!(
+            "weights for {} parties, but the network has {} parties",
+            weights.num_parties(),
+            network.get_num_parties()
+        )));
+    }
+    if shares.len() != weights.weight(id) {
+        return Err(invalid(format!(
+            "expected {} shares of party {id}, got {}",
+            weights.weight(id),
+            shares.len()
+        )));
+    }
+    let sets = (0..weights.num_parties())
+        .map(|party| weights.reconstruction_set(party, degree))
+        .collect::<eyre::Result<Vec<_>>>()
+        .map_err(|err| invalid(err.to_string()))?;
+
+    // send our shares to every party that uses them
+    let targets = (0..weights.num_parties())
+        .filter(|party| *party != id && sets[*party].contains(&id))
+        .collect::<Vec<_>>();
+    let my_shares = shares
+        .iter()
+        .map(|shares| ShamirShare::convert_slice(shares).to_vec())
+        .collect::<Vec<_>>();
+    network.send_all(&targets, my_shares.clone())?;
+    let from = &sets[id][1..];
+    let mut received = vec![my_shares];
+    received.extend(network.recv_all::<Vec<Vec<F>>>(from)?);
+
+    let num_vals = shares.first().map(Vec::len).unwrap_or_default();
+    let virtual_shares = received.into_iter().flatten().collect::<Vec<_>>();
+    if virtual_shares.len() != weights.total_weight(&sets[id])
+        || virtual_shares.iter().any(|shares| shares.len() != num_vals)
+    {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "received an invalid number of weighted shares",
+        ));
+    }
+    let lagrange = core::lagrange_from_coeff(&weights.coeffs(&sets[id])[..=degree]);
+    Ok((0..num_vals)
+        .map(|k| {
+            let s = virtual_shares
+                .iter()
+                .take(degree + 1)
+                .map(|shares| shares[k])
+                .collect::<Vec<_>>();
+            core::reconstruct(&s, &lagrange)
+        })
+        .collect())
+}
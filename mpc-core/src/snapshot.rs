@@ -0,0 +1,42 @@
+//! Snapshots
+//!
+//! This module contains the building blocks for snapshotting the internal state of the protocol drivers, see [Rep3Snapshot](crate::protocols::rep3::network::Rep3Snapshot) and [ShamirSnapshot](crate::protocols::shamir::ShamirSnapshot). A snapshot contains the secret state of a party, i.e., the keys of its correlated randomness and its buffered randomness. It is therefore zeroized when dropped and must only be stored encrypted at rest.
+//!
+//! The network is not part of a snapshot. A restored driver uses a freshly connected network, so all parties need to take their snapshots at the same point of the protocol, i.e., between two communication rounds, and restore them together.
+
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::RngType;
+
+/// The state of one of the seeded RNGs of a party: the seed and the position in the key stream.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
+pub struct RngState {
+    seed: [u8; crate::SEED_SIZE],
+    stream: u64,
+    word_pos: u128,
+}
+
+impl std::fmt::Debug for RngState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RngState").finish_non_exhaustive()
+    }
+}
+
+impl RngState {
+    pub(crate) fn capture(rng: &RngType) -> Self {
+        Self {
+            seed: rng.get_seed(),
+            stream: rng.get_stream(),
+            word_pos: rng.get_word_pos(),
+        }
+    }
+
+    pub(crate) fn restore(&self) -> RngType {
+        let mut rng = RngType::from_seed(self.seed);
+        rng.set_stream(self.stream);
+        rng.set_word_pos(self.word_pos);
+        rng
+    }
+}
@@ -0,0 +1,72 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use clap::Parser;
+use color_eyre::{
+    eyre::{eyre, Context},
+    Result,
+};
+use mpc_net::{
+    channel::ChannelHandle,
+    config::{NetworkConfig, NetworkConfigFile},
+    pool::{MpcConnectionPool, PoolConfig},
+};
+
+#[derive(Parser)]
+struct Args {
+    /// The config file path
+    #[clap(short, long, value_name = "FILE")]
+    config_file: PathBuf,
+    /// The number of sessions to run over the pooled connections
+    #[clap(short, long, default_value_t = 3)]
+    sessions: usize,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    rustls::crypto::aws_lc_rs::default_provider()
+        .install_default()
+        .map_err(|_| eyre!("Could not install default rustls crypto provider"))?;
+
+    let config: NetworkConfigFile =
+        toml::from_str(&std::fs::read_to_string(args.config_file).context("opening config file")?)
+            .context("parsing config file")?;
+    let config = NetworkConfig::try_from(config).context("converting network config")?;
+    let my_id = config.my_id;
+
+    let pool = MpcConnectionPool::new(config, PoolConfig::default())?;
+
+    for _ in 0..args.sessions {
+        let session = pool.lease()?;
+        let handler = session.handler().to_owned();
+        handler.runtime.block_on(async {
+            let channels = session.get_byte_channels().await?;
+            let mut managed_channels = channels
+                .into_iter()
+                .map(|(i, c)| (i, ChannelHandle::manage(c)))
+                .collect::<HashMap<_, _>>();
+
+            // send to all channels
+            for (&i, channel) in managed_channels.iter_mut() {
+                let buf = vec![i as u8; 1024];
+                let _ = channel.send(buf.into()).await.await?;
+            }
+            // recv from all channels
+            for (&_, channel) in managed_channels.iter_mut() {
+                let buf = channel.recv().await.await;
+                if let Ok(Ok(b)) = buf {
+                    println!(
+                        "session {}: received {}, should be {}",
+                        session.session_id(),
+                        b[0],
+                        my_id as u8
+                    );
+                    assert!(b.iter().all(|&x| x == my_id as u8))
+                }
+            }
+            Ok::<_, color_eyre::Report>(())
+        })?;
+    }
+    println!("{:?}", pool.metrics());
+
+    Ok(())
+}
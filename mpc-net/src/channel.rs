@@ -198,6 +198,8 @@ where
                     }
                     Err(err) => {
                         tracing::error!("Write job failed: {err}");
+                        // the error is returned to the sender if it waits for the result of the write job
+                        std::mem::drop(write_job.ret.send(Err(err)));
                     }
                 }
             }
@@ -8,6 +8,7 @@ use std::{
     num::ParseIntError,
     path::PathBuf,
     str::FromStr,
+    time::Duration,
 };
 
 /// A network address wrapper.
@@ -114,6 +115,30 @@ impl TryFrom<NetworkPartyConfig> for NetworkParty {
     }
 }
 
+/// The limits of a session. If a limit is exceeded, the [MpcNetworkHandler](crate::MpcNetworkHandler) closes the connections to all other parties with [BUDGET_EXCEEDED_CODE](crate::BUDGET_EXCEEDED_CODE), such that the protocol aborts on all parties with [SessionBudgetExceeded](crate::SessionBudgetExceeded). The limits are measured by every party on its own connections.
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, Eq, PartialEq, PartialOrd, Ord, Hash,
+)]
+#[serde(default)]
+pub struct SessionBudget {
+    /// The maximum wall-clock time of the session in seconds, measured from the establishment of the connections.
+    pub max_wall_clock_secs: Option<u64>,
+    /// The maximum number of bytes sent and received over all connections.
+    pub max_bytes: Option<u64>,
+}
+
+impl SessionBudget {
+    /// Returns true if no limit is set.
+    pub fn is_unlimited(&self) -> bool {
+        self.max_wall_clock_secs.is_none() && self.max_bytes.is_none()
+    }
+
+    /// Returns the maximum wall-clock time of the session.
+    pub fn max_wall_clock(&self) -> Option<Duration> {
+        self.max_wall_clock_secs.map(Duration::from_secs)
+    }
+}
+
 /// The network configuration file.
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, PartialOrd, Ord, Hash)]
 pub struct NetworkConfigFile {
@@ -125,6 +150,9 @@ pub struct NetworkConfigFile {
     pub bind_addr: SocketAddr,
     /// The path to our private key file.
     pub key_path: PathBuf,
+    /// The limits of a session, unlimited by default.
+    #[serde(default)]
+    pub budget: SessionBudget,
 }
 
 /// The network configuration.
@@ -138,6 +166,8 @@ pub struct NetworkConfig {
     pub bind_addr: SocketAddr,
     /// The private key.
     pub key: PrivateKeyDer<'static>,
+    /// The limits of a session.
+    pub budget: SessionBudget,
 }
 
 impl TryFrom<NetworkConfigFile> for NetworkConfig {
@@ -155,6 +185,7 @@ impl TryFrom<NetworkConfigFile> for NetworkConfig {
             my_id: value.my_id,
             bind_addr: value.bind_addr,
             key,
+            budget: value.budget,
         })
     }
 }
@@ -166,6 +197,7 @@ impl Clone for NetworkConfig {
             my_id: self.my_id,
             bind_addr: self.bind_addr,
             key: self.key.clone_key(),
+            budget: self.budget,
         }
     }
 }
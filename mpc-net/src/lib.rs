@@ -4,32 +4,329 @@ use std::{
     collections::{BTreeMap, HashMap},
     io,
     net::{SocketAddr, ToSocketAddrs},
-    sync::Arc,
-    time::Duration,
+    sync::{Arc, Mutex, Once},
+    time::{Duration, Instant},
 };
 
+use bytes::{Bytes, BytesMut};
 use channel::{BytesChannel, Channel};
 use codecs::BincodeCodec;
 use color_eyre::eyre::{self, Context, Report};
-use config::NetworkConfig;
+use config::{NetworkConfig, SessionBudget};
 use quinn::{
     crypto::rustls::QuicClientConfig,
     rustls::{pki_types::CertificateDer, RootCertStore},
 };
 use quinn::{
-    ClientConfig, Connection, Endpoint, IdleTimeout, RecvStream, SendStream, TransportConfig,
-    VarInt,
+    ClientConfig, Connection, ConnectionError, Endpoint, IdleTimeout, RecvStream, SendStream,
+    TransportConfig, VarInt,
 };
 use serde::{de::DeserializeOwned, Serialize};
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
     runtime::Runtime,
+    task::JoinHandle,
 };
 use tokio_util::codec::{Decoder, Encoder, LengthDelimitedCodec};
 
 pub mod channel;
 pub mod codecs;
 pub mod config;
+pub mod pool;
+
+/// The default time after which a connection to an unresponsive party is closed.
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+/// The default interval in which keep-alive packets are sent.
+pub const DEFAULT_KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(1);
+/// The default time in which all parties need to set up the channels of a session, see [MpcNetworkHandler::get_byte_channels_for_session].
+pub const DEFAULT_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(60);
+/// The application error code with which the connections are closed if the [SessionBudget] of a party is exceeded.
+pub const BUDGET_EXCEEDED_CODE: u32 = 0xb0d6e7;
+/// The application error code with which the connections are closed if a party aborts the session, see [MpcNetworkHandler::abort]. The reason of the close starts with the id of the aborting party as a little-endian `u32`, followed by the UTF-8 encoded reason of the abort.
+pub const ABORT_CODE: u32 = 0xab0127;
+/// The interval in which the [SessionBudget] is checked.
+const BUDGET_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+/// The number of budget checks between two reports of the connection metrics, i.e., the metrics are reported every second.
+const METRICS_REPORT_TICKS: u64 = 10;
+/// The tracing target of the connection metrics. Every second, a trace event with the fields `peer`, `bytes_sent`, `bytes_received`, `alive`, and `rtt_ms` is emitted for every connection, e.g., to monitor a running session.
+pub const METRICS_TARGET: &str = "mpc_net::metrics";
+
+/// The error with which a session aborts if a [SessionBudget] is exceeded. The send and receive operations of the networks of the MPC protocols return it as the inner error of an [io::Error] of kind [io::ErrorKind::TimedOut], see [MpcNetworkHandler::map_budget_error].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionBudgetExceeded {
+    /// The session ran longer than the maximum wall-clock time of our budget.
+    WallClock(Duration),
+    /// The session sent and received more than the maximum number of bytes of our budget.
+    Bytes(u64),
+    /// The party with the given id aborted the session because a budget was exceeded.
+    Remote(usize),
+}
+
+impl std::fmt::Display for SessionBudgetExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SessionBudgetExceeded::WallClock(max) => write!(
+                f,
+                "session budget exceeded: ran longer than {} seconds",
+                max.as_secs()
+            ),
+            SessionBudgetExceeded::Bytes(max) => write!(
+                f,
+                "session budget exceeded: transferred more than {max} bytes"
+            ),
+            SessionBudgetExceeded::Remote(id) => {
+                write!(f, "session budget exceeded: party {id} aborted the session")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SessionBudgetExceeded {}
+
+/// The error with which a session fails if a party aborted it, e.g., because the party failed or was interrupted by the user. The send and receive operations of the networks of the MPC protocols return it as the inner error of an [io::Error] of kind [io::ErrorKind::ConnectionAborted], see [MpcNetworkHandler::map_session_error].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionAborted {
+    /// The id of the party that aborted the session
+    pub party: usize,
+    /// The reason the party gave for the abort
+    pub reason: String,
+}
+
+impl SessionAborted {
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = (self.party as u32).to_le_bytes().to_vec();
+        bytes.extend_from_slice(self.reason.as_bytes());
+        bytes
+    }
+
+    fn decode(bytes: &[u8], fallback_party: usize) -> Self {
+        match bytes.split_first_chunk::<4>() {
+            Some((party, reason)) => Self {
+                party: u32::from_le_bytes(*party) as usize,
+                reason: String::from_utf8_lossy(reason).into_owned(),
+            },
+            None => Self {
+                party: fallback_party,
+                reason: "no reason given".to_owned(),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for SessionAborted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "session aborted by party {}: {}",
+            self.party, self.reason
+        )
+    }
+}
+
+impl std::error::Error for SessionAborted {}
+
+/// A handle to abort a session from another thread, e.g., from a Ctrl-C handler, see [MpcNetworkHandler::abort_handle].
+#[derive(Debug, Clone)]
+pub struct AbortHandle {
+    connections: BTreeMap<usize, Connection>,
+    my_id: usize,
+    aborted: Arc<Mutex<Option<SessionAborted>>>,
+}
+
+impl AbortHandle {
+    /// Aborts the session, see [MpcNetworkHandler::abort].
+    pub fn abort(&self, reason: &str) {
+        let abort = SessionAborted {
+            party: self.my_id,
+            reason: reason.to_owned(),
+        };
+        {
+            let mut aborted = self.aborted.lock().expect("abort lock is not poisoned");
+            if aborted.is_some() {
+                return;
+            }
+            *aborted = Some(abort.clone());
+        }
+        tracing::error!("aborting the session: {reason}");
+        let payload = abort.encode();
+        for conn in self.connections.values() {
+            conn.close(VarInt::from_u32(ABORT_CODE), &payload);
+        }
+    }
+
+    fn is_same_session(&self, other: &AbortHandle) -> bool {
+        Arc::ptr_eq(&self.aborted, &other.aborted)
+    }
+}
+
+/// The session that is aborted on Ctrl-C, see [SessionGuard::abort_on_ctrl_c].
+static CTRL_C_SESSION: Mutex<Option<AbortHandle>> = Mutex::new(None);
+static CTRL_C_LISTENER: Once = Once::new();
+
+/// Starts the thread listening for Ctrl-C, once per process. The first Ctrl-C aborts the registered session, a second Ctrl-C or a Ctrl-C without a registered session exits the process, as the handler of tokio can not be uninstalled.
+fn listen_for_ctrl_c() {
+    CTRL_C_LISTENER.call_once(|| {
+        std::thread::spawn(|| {
+            let Ok(runtime) = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+            else {
+                tracing::warn!("could not install the Ctrl-C handler");
+                return;
+            };
+            runtime.block_on(async {
+                while tokio::signal::ctrl_c().await.is_ok() {
+                    let session = CTRL_C_SESSION
+                        .lock()
+                        .expect("ctrl-c lock is not poisoned")
+                        .take();
+                    match session {
+                        Some(abort_handle) => abort_handle.abort("interrupted by the user"),
+                        None => std::process::exit(130),
+                    }
+                }
+            });
+        });
+    });
+}
+
+/// Aborts the session if it is dropped before [SessionGuard::finish] is called, e.g., because the command failed with an error or panicked at this party. The guard keeps the network handler alive, so the graceful shutdown of the handler only starts after the other parties were notified and does not wait for them. Must not be used with a handler of a [MpcConnectionPool](pool::MpcConnectionPool), as the abort closes the pooled connections.
+#[derive(Debug)]
+pub struct SessionGuard {
+    handler: Arc<MpcNetworkHandlerWrapper>,
+    finished: bool,
+    ctrl_c: bool,
+}
+
+impl SessionGuard {
+    /// Guards the session of the `handler`.
+    pub fn new(handler: Arc<MpcNetworkHandlerWrapper>) -> Self {
+        Self {
+            handler,
+            finished: false,
+            ctrl_c: false,
+        }
+    }
+
+    /// Additionally aborts the session if the user presses Ctrl-C, until the guard is finished or dropped. Afterwards, the session is no longer referenced by the Ctrl-C handler and Ctrl-C exits the process.
+    pub fn abort_on_ctrl_c(mut self) -> Self {
+        listen_for_ctrl_c();
+        *CTRL_C_SESSION.lock().expect("ctrl-c lock is not poisoned") =
+            Some(self.handler.inner.abort_handle());
+        self.ctrl_c = true;
+        self
+    }
+
+    /// Marks the session as successfully finished, so it is shut down gracefully instead of aborted.
+    pub fn finish(mut self) {
+        self.finished = true;
+    }
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.handler.inner.abort("the command failed");
+        }
+        if self.ctrl_c {
+            let mut session = CTRL_C_SESSION.lock().expect("ctrl-c lock is not poisoned");
+            let ours = self.handler.inner.abort_handle();
+            if session
+                .as_ref()
+                .is_some_and(|session| session.is_same_session(&ours))
+            {
+                *session = None;
+            }
+        }
+    }
+}
+
+/// The size in bytes of the first dummy message of a warm-up, see [WarmUpStats::run].
+pub const WARM_UP_INITIAL_MESSAGE_LEN: usize = 1024;
+
+/// The statistics of a warm-up of the connections to the other parties.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct WarmUpStats {
+    /// The number of rounds in which dummy messages were exchanged
+    pub rounds: usize,
+    /// The number of bytes sent to the other parties
+    pub bytes_sent: usize,
+}
+
+impl WarmUpStats {
+    /// Exchanges dummy messages with the other parties until the `deadline` of one of the parties has passed. `exchange` sends a message to all other parties and returns their messages of the same round.
+    ///
+    /// The first message has [WARM_UP_INITIAL_MESSAGE_LEN] bytes and the size doubles every round up to `max_message_len` bytes, so that the congestion windows of the connections grow as during a session. The first byte of every message tells the other parties whether the deadline of the sender has passed. As every party receives the messages of all other parties, all parties stop after the same round and leave the warm-up in lockstep.
+    pub fn run(
+        deadline: Instant,
+        max_message_len: usize,
+        mut exchange: impl FnMut(Bytes) -> io::Result<Vec<BytesMut>>,
+    ) -> io::Result<Self> {
+        let max_message_len = max_message_len.max(1);
+        let mut stats = Self::default();
+        let mut len = WARM_UP_INITIAL_MESSAGE_LEN.min(max_message_len);
+        loop {
+            let done = Instant::now() >= deadline;
+            let mut message = vec![0u8; len];
+            message[0] = u8::from(done);
+            let received = exchange(Bytes::from(message))?;
+            stats.rounds += 1;
+            stats.bytes_sent += len * received.len();
+            if done || received.iter().any(|message| message.first() != Some(&0)) {
+                return Ok(stats);
+            }
+            len = (2 * len).min(max_message_len);
+        }
+    }
+}
+
+/// The number of messages and their payload bytes exchanged with another party, see [MessageCounter].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct MessageCount {
+    /// The number of messages sent to the party
+    pub messages_sent: u64,
+    /// The number of payload bytes sent to the party
+    pub bytes_sent: u64,
+    /// The number of messages received from the party
+    pub messages_received: u64,
+    /// The number of payload bytes received from the party
+    pub bytes_received: u64,
+}
+
+/// Counts the messages the MPC protocols exchange with every other party, including the messages of all forks of a network.
+///
+/// In contrast to the connection metrics (see [METRICS_TARGET]), only the payloads of the messages are counted, so the messages a party sent to another party match the messages the other party received from it once the session is finished.
+#[derive(Debug, Default)]
+pub struct MessageCounter(Mutex<BTreeMap<usize, MessageCount>>);
+
+impl MessageCounter {
+    fn update(&self, party: usize, f: impl FnOnce(&mut MessageCount)) {
+        // a panic while holding the lock does not leave the counts in an inconsistent state
+        let mut counts = self.0.lock().unwrap_or_else(|err| err.into_inner());
+        f(counts.entry(party).or_default());
+    }
+
+    /// Records a message of `len` bytes sent to the party with id `to`.
+    pub fn record_sent(&self, to: usize, len: usize) {
+        self.update(to, |count| {
+            count.messages_sent += 1;
+            count.bytes_sent += len as u64;
+        });
+    }
+
+    /// Records a message of `len` bytes received from the party with id `from`.
+    pub fn record_received(&self, from: usize, len: usize) {
+        self.update(from, |count| {
+            count.messages_received += 1;
+            count.bytes_received += len as u64;
+        });
+    }
+
+    /// Returns the current counts by the ids of the other parties.
+    pub fn counts(&self) -> BTreeMap<usize, MessageCount> {
+        self.0.lock().unwrap_or_else(|err| err.into_inner()).clone()
+    }
+}
 
 /// A warapper for a runtime and a network handler for MPC protocols.
 /// Ensures a gracefull shutdown on drop
@@ -39,12 +336,18 @@ pub struct MpcNetworkHandlerWrapper {
     pub runtime: Runtime,
     /// The wrapped network handler
     pub inner: MpcNetworkHandler,
+    /// The messages exchanged by the networks of the MPC protocols using this handler
+    pub messages: Arc<MessageCounter>,
 }
 
 impl MpcNetworkHandlerWrapper {
     /// Create a new wrapper
     pub fn new(runtime: Runtime, inner: MpcNetworkHandler) -> Self {
-        Self { runtime, inner }
+        Self {
+            runtime,
+            inner,
+            messages: Arc::default(),
+        }
     }
 }
 
@@ -62,12 +365,28 @@ pub struct MpcNetworkHandler {
     connections: BTreeMap<usize, Connection>,
     endpoints: Vec<Endpoint>,
     my_id: usize,
+    budget_exceeded: Arc<Mutex<Option<SessionBudgetExceeded>>>,
+    aborted: Arc<Mutex<Option<SessionAborted>>>,
+    budget_watchdog: JoinHandle<()>,
 }
 
 impl MpcNetworkHandler {
     /// Tries to establish a connection to other parties in the network based on the provided [NetworkConfig].
     pub async fn establish(config: NetworkConfig) -> Result<Self, Report> {
+        Self::establish_with_timeouts(config, DEFAULT_IDLE_TIMEOUT, DEFAULT_KEEP_ALIVE_INTERVAL)
+            .await
+    }
+
+    /// Tries to establish a connection to other parties in the network based on the provided [NetworkConfig]. A connection is closed if the other party does not respond for `idle_timeout`, while keep-alive packets are sent every `keep_alive_interval`.
+    ///
+    /// The [SessionBudget] of the config is enforced from the moment the connections are established.
+    pub async fn establish_with_timeouts(
+        config: NetworkConfig,
+        idle_timeout: Duration,
+        keep_alive_interval: Duration,
+    ) -> Result<Self, Report> {
         config.check_config()?;
+        let budget = config.budget;
         let certs: HashMap<usize, CertificateDer> = config
             .parties
             .iter()
@@ -87,10 +406,10 @@ impl MpcNetworkHandler {
         let client_config = {
             let mut transport_config = TransportConfig::default();
             transport_config.max_idle_timeout(Some(
-                IdleTimeout::try_from(Duration::from_secs(60)).unwrap(),
+                IdleTimeout::try_from(idle_timeout).context("invalid idle timeout")?,
             ));
             // atm clients send keepalive packets
-            transport_config.keep_alive_interval(Some(Duration::from_secs(1)));
+            transport_config.keep_alive_interval(Some(keep_alive_interval));
             let mut client_config =
                 ClientConfig::new(Arc::new(QuicClientConfig::try_from(crypto)?));
             client_config.transport_config(Arc::new(transport_config));
@@ -190,13 +509,229 @@ impl MpcNetworkHandler {
         }
         endpoints.push(server_endpoint);
 
+        let budget_exceeded = Arc::new(Mutex::new(None));
+        let aborted = Arc::new(Mutex::new(None));
+        let budget_watchdog = tokio::spawn(Self::watch_budget(
+            budget,
+            connections.clone(),
+            Arc::clone(&budget_exceeded),
+            Arc::clone(&aborted),
+        ));
+
         Ok(MpcNetworkHandler {
             connections,
             endpoints,
             my_id: config.my_id,
+            budget_exceeded,
+            aborted,
+            budget_watchdog,
         })
     }
 
+    /// Periodically checks our [SessionBudget] and whether another party aborted the session. In both cases, all connections are closed with [BUDGET_EXCEEDED_CODE] or [ABORT_CODE], so the abort propagates to all parties, even to those that are not directly waiting for the aborting party. Also reports the connection metrics, see [METRICS_TARGET].
+    async fn watch_budget(
+        budget: SessionBudget,
+        connections: BTreeMap<usize, Connection>,
+        budget_exceeded: Arc<Mutex<Option<SessionBudgetExceeded>>>,
+        aborted: Arc<Mutex<Option<SessionAborted>>>,
+    ) {
+        let start = Instant::now();
+        let mut interval = tokio::time::interval(BUDGET_CHECK_INTERVAL);
+        let mut ticks = 0u64;
+        loop {
+            interval.tick().await;
+            if ticks % METRICS_REPORT_TICKS == 0 {
+                Self::report_metrics(&connections);
+            }
+            ticks += 1;
+            if let Some(abort) = Self::remote_abort(&connections) {
+                tracing::error!("{abort}, closing all connections");
+                let payload = abort.encode();
+                aborted
+                    .lock()
+                    .expect("abort lock is not poisoned")
+                    .get_or_insert(abort);
+                for conn in connections.values() {
+                    conn.close(VarInt::from_u32(ABORT_CODE), &payload);
+                }
+                return;
+            }
+            let reason = if let Some(id) = Self::remote_budget_abort(&connections) {
+                SessionBudgetExceeded::Remote(id)
+            } else if connections
+                .values()
+                .any(|conn| conn.close_reason().is_some())
+            {
+                // the connections are closed for another reason, e.g., the shutdown
+                return;
+            } else if let Some(max) = budget.max_wall_clock().filter(|max| start.elapsed() > *max) {
+                SessionBudgetExceeded::WallClock(max)
+            } else if let Some(max) = budget.max_bytes.filter(|max| {
+                let (tx, rx) = Self::total_send_receive(&connections);
+                tx + rx > *max
+            }) {
+                SessionBudgetExceeded::Bytes(max)
+            } else {
+                continue;
+            };
+            Self::close_for_budget(&connections, &budget_exceeded, reason);
+            return;
+        }
+    }
+
+    /// Enforces the [SessionBudget] of a session over connections that outlive the session, i.e., the ones of a [MpcConnectionPool](pool::MpcConnectionPool). The wall-clock time and the transferred bytes are measured from this call on. If the budget is exceeded, all connections are closed with [BUDGET_EXCEEDED_CODE], which also aborts the other sessions over these connections. The returned task needs to be aborted when the session ends. Needs to be called from within the runtime of the handler.
+    pub(crate) fn watch_session_budget(&self, budget: SessionBudget) -> JoinHandle<()> {
+        let connections = self.connections.clone();
+        let budget_exceeded = Arc::clone(&self.budget_exceeded);
+        let (tx, rx) = self.get_total_send_receive();
+        let bytes_before = tx + rx;
+        tokio::spawn(async move {
+            let start = Instant::now();
+            let mut interval = tokio::time::interval(BUDGET_CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                if connections
+                    .values()
+                    .any(|conn| conn.close_reason().is_some())
+                {
+                    // the watchdog of the handler takes care of closed connections
+                    return;
+                }
+                let reason = if let Some(max) =
+                    budget.max_wall_clock().filter(|max| start.elapsed() > *max)
+                {
+                    SessionBudgetExceeded::WallClock(max)
+                } else if let Some(max) = budget.max_bytes.filter(|max| {
+                    let (tx, rx) = Self::total_send_receive(&connections);
+                    tx + rx - bytes_before > *max
+                }) {
+                    SessionBudgetExceeded::Bytes(max)
+                } else {
+                    continue;
+                };
+                Self::close_for_budget(&connections, &budget_exceeded, reason);
+                return;
+            }
+        })
+    }
+
+    fn close_for_budget(
+        connections: &BTreeMap<usize, Connection>,
+        budget_exceeded: &Mutex<Option<SessionBudgetExceeded>>,
+        reason: SessionBudgetExceeded,
+    ) {
+        tracing::error!("{reason}, closing all connections");
+        let msg = reason.to_string();
+        *budget_exceeded.lock().expect("budget lock is not poisoned") = Some(reason);
+        for conn in connections.values() {
+            conn.close(VarInt::from_u32(BUDGET_EXCEEDED_CODE), msg.as_bytes());
+        }
+    }
+
+    fn remote_budget_abort(connections: &BTreeMap<usize, Connection>) -> Option<usize> {
+        connections
+            .iter()
+            .find_map(|(id, conn)| match conn.close_reason() {
+                Some(ConnectionError::ApplicationClosed(close))
+                    if close.error_code == VarInt::from_u32(BUDGET_EXCEEDED_CODE) =>
+                {
+                    Some(*id)
+                }
+                _ => None,
+            })
+    }
+
+    fn remote_abort(connections: &BTreeMap<usize, Connection>) -> Option<SessionAborted> {
+        connections
+            .iter()
+            .find_map(|(id, conn)| match conn.close_reason() {
+                Some(ConnectionError::ApplicationClosed(close))
+                    if close.error_code == VarInt::from_u32(ABORT_CODE) =>
+                {
+                    Some(SessionAborted::decode(&close.reason, *id))
+                }
+                _ => None,
+            })
+    }
+
+    fn report_metrics(connections: &BTreeMap<usize, Connection>) {
+        for (id, conn) in connections {
+            let stats = conn.stats();
+            tracing::trace!(
+                target: METRICS_TARGET,
+                peer = *id as u64,
+                bytes_sent = stats.udp_tx.bytes,
+                bytes_received = stats.udp_rx.bytes,
+                alive = conn.close_reason().is_none(),
+                rtt_ms = conn.rtt().as_millis() as u64,
+                "connection metrics"
+            );
+        }
+    }
+
+    fn total_send_receive(connections: &BTreeMap<usize, Connection>) -> (u64, u64) {
+        connections
+            .values()
+            .map(|conn| conn.stats())
+            .fold((0, 0), |(tx, rx), stats| {
+                (tx + stats.udp_tx.bytes, rx + stats.udp_rx.bytes)
+            })
+    }
+
+    /// Returns the reason if the session was aborted because a [SessionBudget] was exceeded, either ours or the one of another party.
+    pub fn budget_exceeded(&self) -> Option<SessionBudgetExceeded> {
+        let reason = self
+            .budget_exceeded
+            .lock()
+            .expect("budget lock is not poisoned")
+            .clone();
+        // the watchdog may not have noticed a remote abort yet
+        reason.or_else(|| {
+            Self::remote_budget_abort(&self.connections).map(SessionBudgetExceeded::Remote)
+        })
+    }
+
+    /// Replaces the error of a failed send or receive operation with an [io::Error] of kind [io::ErrorKind::TimedOut] wrapping the [SessionBudgetExceeded], if the session was aborted because of a budget. Other errors are returned unchanged.
+    pub fn map_budget_error(&self, err: io::Error) -> io::Error {
+        match self.budget_exceeded() {
+            Some(reason) => io::Error::new(io::ErrorKind::TimedOut, reason),
+            None => err,
+        }
+    }
+
+    /// Aborts the session by closing all connections with [ABORT_CODE] and the `reason`. The other parties are notified immediately and fail with a [SessionAborted] naming this party, instead of waiting for an idle timeout. Does nothing if the session was already aborted.
+    pub fn abort(&self, reason: &str) {
+        self.abort_handle().abort(reason);
+    }
+
+    /// Returns an [AbortHandle] to abort the session from another thread.
+    pub fn abort_handle(&self) -> AbortHandle {
+        AbortHandle {
+            connections: self.connections.clone(),
+            my_id: self.my_id,
+            aborted: Arc::clone(&self.aborted),
+        }
+    }
+
+    /// Returns the reason if the session was aborted by a party, either by us or by another party.
+    pub fn aborted(&self) -> Option<SessionAborted> {
+        let abort = self
+            .aborted
+            .lock()
+            .expect("abort lock is not poisoned")
+            .clone();
+        // the watchdog may not have noticed a remote abort yet
+        abort.or_else(|| Self::remote_abort(&self.connections))
+    }
+
+    /// Replaces the error of a failed send or receive operation with an [io::Error] of kind [io::ErrorKind::ConnectionAborted] wrapping the [SessionAborted], if a party aborted the session, or with the error of [MpcNetworkHandler::map_budget_error] otherwise.
+    pub fn map_session_error(&self, err: io::Error) -> io::Error {
+        match self.aborted() {
+            Some(abort) => io::Error::new(io::ErrorKind::ConnectionAborted, abort),
+            None => self.map_budget_error(err),
+        }
+    }
+
     /// Returns the number of sent and received bytes.
     pub fn get_send_receive(&self, i: usize) -> std::io::Result<(u64, u64)> {
         let conn = self
@@ -207,6 +742,18 @@ impl MpcNetworkHandler {
         Ok((stats.udp_tx.bytes, stats.udp_rx.bytes))
     }
 
+    /// Returns the number of sent and received bytes summed over all connections.
+    pub fn get_total_send_receive(&self) -> (u64, u64) {
+        Self::total_send_receive(&self.connections)
+    }
+
+    /// Returns true if any of the connections to the other parties was closed, e.g., because of an idle timeout.
+    pub fn is_closed(&self) -> bool {
+        self.connections
+            .values()
+            .any(|conn| conn.close_reason().is_some())
+    }
+
     /// Prints the connection statistics.
     pub fn print_connection_stats(&self, out: &mut impl std::io::Write) -> std::io::Result<()> {
         for (i, conn) in &self.connections {
@@ -220,18 +767,21 @@ impl MpcNetworkHandler {
         Ok(())
     }
 
-    /// Sets up a new [BytesChannel] between each party. The resulting map maps the id of the party to its respective [BytesChannel].
-    pub async fn get_byte_channels(
-        &self,
-    ) -> std::io::Result<HashMap<usize, BytesChannel<RecvStream, SendStream>>> {
+    fn byte_codec() -> LengthDelimitedCodec {
         // set max frame length to 1Tb and length_field_length to 5 bytes
         const NUM_BYTES: usize = 5;
-        let codec = LengthDelimitedCodec::builder()
+        LengthDelimitedCodec::builder()
             .length_field_type::<u64>() // u64 because this is the type the length is decoded into, and u32 doesnt fit 5 bytes
             .length_field_length(NUM_BYTES)
             .max_frame_length(1usize << (NUM_BYTES * 8))
-            .new_codec();
-        self.get_custom_channels(codec).await
+            .new_codec()
+    }
+
+    /// Sets up a new [BytesChannel] between each party. The resulting map maps the id of the party to its respective [BytesChannel].
+    pub async fn get_byte_channels(
+        &self,
+    ) -> std::io::Result<HashMap<usize, BytesChannel<RecvStream, SendStream>>> {
+        self.get_custom_channels(Self::byte_codec()).await
     }
 
     /// Set up a new [Channel] using [BincodeCodec] between each party. The resulting map maps the id of the party to its respective [Channel].
@@ -242,6 +792,28 @@ impl MpcNetworkHandler {
         self.get_custom_channels(bincodec).await
     }
 
+    /// Sets up a new [BytesChannel] between each party for the session with the given id. The resulting map maps the id of the party to its respective [BytesChannel].
+    ///
+    /// All parties have to use the same session id. Both sides of a channel check the session id of the other side, so a mismatch fails with an error of kind [io::ErrorKind::InvalidData]. If the channels are not set up within `timeout`, e.g., because another party failed on a mismatch with a third party, an error of kind [io::ErrorKind::TimedOut] is returned.
+    pub async fn get_byte_channels_for_session(
+        &self,
+        session_id: u64,
+        timeout: Duration,
+    ) -> std::io::Result<HashMap<usize, BytesChannel<RecvStream, SendStream>>> {
+        let mut pending = None;
+        let setup = self.setup_channels(Self::byte_codec(), Some(session_id), &mut pending);
+        let result = tokio::time::timeout(timeout, setup).await;
+        result.unwrap_or_else(|_| {
+            let party = pending.map(|id| format!(" with party {id}")).unwrap_or_default();
+            Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!(
+                    "could not set up the channels of session {session_id}{party} within {timeout:?}, check that all parties lease their sessions in the same order"
+                ),
+            ))
+        })
+    }
+
     /// Set up a new [Channel] using the provided codec between each party. The resulting map maps the id of the party to its respective [Channel].
     pub async fn get_custom_channels<
         MSend,
@@ -253,32 +825,101 @@ impl MpcNetworkHandler {
     >(
         &self,
         codec: C,
+    ) -> std::io::Result<HashMap<usize, Channel<RecvStream, SendStream, C>>> {
+        self.setup_channels(codec, None, &mut None).await
+    }
+
+    async fn setup_channels<
+        MSend,
+        MRecv,
+        C: Encoder<MSend, Error = io::Error>
+            + Decoder<Item = MRecv, Error = io::Error>
+            + 'static
+            + Clone,
+    >(
+        &self,
+        codec: C,
+        session_id: Option<u64>,
+        pending: &mut Option<usize>,
     ) -> std::io::Result<HashMap<usize, Channel<RecvStream, SendStream, C>>> {
         let mut channels = HashMap::with_capacity(self.connections.len() - 1);
         for (&id, conn) in self.connections.iter() {
-            if id < self.my_id {
-                // we are the client, so we are the receiver
+            *pending = Some(id);
+            // both sides send their ids before checking the ones of the other side, so both detect a mismatch
+            let (mut send_stream, mut recv_stream) = if id < self.my_id {
+                // we are the client, so we open the stream
                 let (mut send_stream, mut recv_stream) = conn.open_bi().await?;
-                send_stream.write_u32(self.my_id as u32).await?;
-                let their_id = recv_stream.read_u32().await?;
-                assert!(their_id == id as u32);
-                let conn = Channel::new(recv_stream, send_stream, codec.clone());
-                assert!(channels.insert(id, conn).is_none());
+                Self::write_hello(&mut send_stream, self.my_id, session_id).await?;
+                let hello = Self::read_hello(&mut recv_stream, session_id).await?;
+                Self::check_hello(id, session_id, hello)?;
+                (send_stream, recv_stream)
             } else {
-                // we are the server, so we are the sender
+                // we are the server, so we accept the stream
                 let (mut send_stream, mut recv_stream) = conn.accept_bi().await?;
-                let their_id = recv_stream.read_u32().await?;
-                assert!(their_id == id as u32);
-                send_stream.write_u32(self.my_id as u32).await?;
-                let conn = Channel::new(recv_stream, send_stream, codec.clone());
-                assert!(channels.insert(id, conn).is_none());
-            }
+                let hello = Self::read_hello(&mut recv_stream, session_id).await?;
+                Self::write_hello(&mut send_stream, self.my_id, session_id).await?;
+                Self::check_hello(id, session_id, hello)?;
+                (send_stream, recv_stream)
+            };
+            let conn = Channel::new(recv_stream, send_stream, codec.clone());
+            assert!(channels.insert(id, conn).is_none());
         }
         Ok(channels)
     }
 
+    async fn write_hello(
+        send_stream: &mut SendStream,
+        my_id: usize,
+        session_id: Option<u64>,
+    ) -> io::Result<()> {
+        send_stream
+            .write_u32(u32::try_from(my_id).expect("party id fits into u32"))
+            .await?;
+        if let Some(session_id) = session_id {
+            send_stream.write_u64(session_id).await?;
+        }
+        send_stream.flush().await
+    }
+
+    async fn read_hello(
+        recv_stream: &mut RecvStream,
+        session_id: Option<u64>,
+    ) -> io::Result<(u32, Option<u64>)> {
+        let their_id = recv_stream.read_u32().await?;
+        let their_session_id = match session_id {
+            Some(_) => Some(recv_stream.read_u64().await?),
+            None => None,
+        };
+        Ok((their_id, their_session_id))
+    }
+
+    fn check_hello(
+        id: usize,
+        session_id: Option<u64>,
+        (their_id, their_session_id): (u32, Option<u64>),
+    ) -> io::Result<()> {
+        if usize::try_from(their_id).ok() != Some(id) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("expected party {id} on the connection, but party {their_id} answered"),
+            ));
+        }
+        if their_session_id != session_id {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "party {id} opened session {}, but we expected session {}",
+                    their_session_id.unwrap_or_default(),
+                    session_id.unwrap_or_default()
+                ),
+            ));
+        }
+        Ok(())
+    }
+
     /// Shutdown all connections, and call [`quinn::Endpoint::wait_idle`] on all of them
     pub async fn shutdown(&self) -> std::io::Result<()> {
+        self.budget_watchdog.abort();
         tracing::debug!(
             "party {} shutting down, conns = {:?}",
             self.my_id,
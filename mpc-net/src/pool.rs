@@ -0,0 +1,264 @@
+//! A pool of persistent connections between the parties.
+//!
+//! Establishing the authenticated connections between the parties is expensive compared to small proofs. When running many sessions between the same parties, a [MpcConnectionPool] keeps the connections alive and each session leases new channels over the existing connections.
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use color_eyre::eyre::{self, Context, Report};
+use quinn::{RecvStream, SendStream};
+use tokio::task::JoinHandle;
+
+use crate::{
+    channel::BytesChannel,
+    config::{NetworkConfig, SessionBudget},
+    MpcNetworkHandler, MpcNetworkHandlerWrapper, DEFAULT_HANDSHAKE_TIMEOUT, DEFAULT_IDLE_TIMEOUT,
+    DEFAULT_KEEP_ALIVE_INTERVAL,
+};
+
+/// The configuration of a [MpcConnectionPool]. The [SessionBudget] of the [NetworkConfig] is not applied to the pooled connections, as they outlive the sessions, but to each leased [PooledSession] instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolConfig {
+    /// The time after which the connection to an unresponsive party is closed. The pool reconnects on the next lease.
+    pub idle_timeout: Duration,
+    /// The interval in which keep-alive packets are sent.
+    pub keep_alive_interval: Duration,
+    /// The time in which all parties need to set up the channels of a leased session, see [PooledSession::get_byte_channels].
+    pub handshake_timeout: Duration,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            keep_alive_interval: DEFAULT_KEEP_ALIVE_INTERVAL,
+            handshake_timeout: DEFAULT_HANDSHAKE_TIMEOUT,
+        }
+    }
+}
+
+/// Metrics of a [MpcConnectionPool].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoolMetrics {
+    /// The total number of leased sessions
+    pub sessions_leased: u64,
+    /// The number of sessions that are currently leased
+    pub active_sessions: u64,
+    /// The number of times the connections had to be re-established
+    pub reconnects: u64,
+    /// The number of bytes sent over the current connections
+    pub bytes_sent: u64,
+    /// The number of bytes received over the current connections
+    pub bytes_received: u64,
+}
+
+#[derive(Debug, Default)]
+struct PoolCounters {
+    sessions_leased: AtomicU64,
+    active_sessions: AtomicU64,
+    reconnects: AtomicU64,
+}
+
+#[derive(Debug)]
+struct PoolState {
+    // None if reconnecting failed, the next lease tries again
+    handler: Option<Arc<MpcNetworkHandlerWrapper>>,
+    next_session_id: u64,
+}
+
+/// A pool of persistent connections to the other parties, from which sessions can be leased. All parties have to lease their sessions in the same order, which is checked when setting up the channels of a session.
+#[derive(Debug)]
+pub struct MpcConnectionPool {
+    config: NetworkConfig,
+    pool_config: PoolConfig,
+    state: Mutex<PoolState>,
+    counters: Arc<PoolCounters>,
+}
+
+impl MpcConnectionPool {
+    /// Establishes the connections to the other parties based on the provided [NetworkConfig].
+    pub fn new(config: NetworkConfig, pool_config: PoolConfig) -> Result<Self, Report> {
+        let handler = Self::connect(&config, &pool_config)?;
+        Ok(Self {
+            config,
+            pool_config,
+            state: Mutex::new(PoolState {
+                handler: Some(Arc::new(handler)),
+                next_session_id: 0,
+            }),
+            counters: Arc::new(PoolCounters::default()),
+        })
+    }
+
+    fn connect(
+        config: &NetworkConfig,
+        pool_config: &PoolConfig,
+    ) -> Result<MpcNetworkHandlerWrapper, Report> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()?;
+        let config = NetworkConfig {
+            budget: SessionBudget::default(),
+            ..config.to_owned()
+        };
+        let handler = runtime
+            .block_on(MpcNetworkHandler::establish_with_timeouts(
+                config,
+                pool_config.idle_timeout,
+                pool_config.keep_alive_interval,
+            ))
+            .context("while establishing pooled connections")?;
+        Ok(MpcNetworkHandlerWrapper::new(runtime, handler))
+    }
+
+    /// Returns our own id in the network.
+    pub fn get_id(&self) -> usize {
+        self.config.my_id
+    }
+
+    /// Returns the number of parties in the network.
+    pub fn get_num_parties(&self) -> usize {
+        self.config.parties.len()
+    }
+
+    /// Leases a new session with the [SessionBudget] of the [NetworkConfig]. If the connections were closed in the meantime, e.g., because of the idle timeout, they are re-established first, which requires all other parties to do the same.
+    ///
+    /// Re-establishing the connections fails if sessions over the closed connections are still leased, as they keep the sockets of the old connections open. After a reconnect, all parties start again with the first session id, so a party that did not reconnect fails to set up the channels of its next session instead of running it over different connections.
+    pub fn lease(&self) -> Result<PooledSession, Report> {
+        self.lease_with_budget(self.config.budget)
+    }
+
+    /// Leases a new session with the provided [SessionBudget], see [MpcConnectionPool::lease]. The budget is measured from the lease on. If it is exceeded, the pooled connections are closed, which aborts all sessions leased at that time, and re-established on the next lease.
+    pub fn lease_with_budget(&self, budget: SessionBudget) -> Result<PooledSession, Report> {
+        let mut state = self.state.lock().expect("pool lock is not poisoned");
+        let handler = match state.handler.take() {
+            Some(handler) if !handler.inner.is_closed() => handler,
+            closed => {
+                if let Some(closed) = closed {
+                    tracing::info!(
+                        "party {}: pooled connections were closed, reconnecting",
+                        self.config.my_id
+                    );
+                    // shut down the old connections before binding our address again
+                    let closed = Arc::try_unwrap(closed).map_err(|closed| {
+                        state.handler = Some(closed);
+                        eyre::eyre!(
+                            "cannot re-establish the pooled connections while sessions over the closed connections are still leased"
+                        )
+                    })?;
+                    drop(closed);
+                    self.counters.reconnects.fetch_add(1, Ordering::Relaxed);
+                }
+                state.next_session_id = 0;
+                Arc::new(Self::connect(&self.config, &self.pool_config)?)
+            }
+        };
+        state.handler = Some(Arc::clone(&handler));
+        let session_id = state.next_session_id;
+        state.next_session_id += 1;
+        self.counters
+            .sessions_leased
+            .fetch_add(1, Ordering::Relaxed);
+        self.counters
+            .active_sessions
+            .fetch_add(1, Ordering::Relaxed);
+        let budget_watchdog = (!budget.is_unlimited()).then(|| {
+            let _guard = handler.runtime.enter();
+            handler.inner.watch_session_budget(budget)
+        });
+        Ok(PooledSession {
+            session_id,
+            my_id: self.config.my_id,
+            num_parties: self.config.parties.len(),
+            budget,
+            handshake_timeout: self.pool_config.handshake_timeout,
+            handler,
+            counters: Arc::clone(&self.counters),
+            budget_watchdog,
+        })
+    }
+
+    /// Returns the current [PoolMetrics].
+    pub fn metrics(&self) -> PoolMetrics {
+        let state = self.state.lock().expect("pool lock is not poisoned");
+        let (bytes_sent, bytes_received) = state
+            .handler
+            .as_ref()
+            .map_or((0, 0), |handler| handler.inner.get_total_send_receive());
+        PoolMetrics {
+            sessions_leased: self.counters.sessions_leased.load(Ordering::Relaxed),
+            active_sessions: self.counters.active_sessions.load(Ordering::Relaxed),
+            reconnects: self.counters.reconnects.load(Ordering::Relaxed),
+            bytes_sent,
+            bytes_received,
+        }
+    }
+}
+
+/// A session leased from a [MpcConnectionPool]. The session is returned to the pool on drop.
+#[derive(Debug)]
+pub struct PooledSession {
+    session_id: u64,
+    my_id: usize,
+    num_parties: usize,
+    budget: SessionBudget,
+    handshake_timeout: Duration,
+    handler: Arc<MpcNetworkHandlerWrapper>,
+    counters: Arc<PoolCounters>,
+    budget_watchdog: Option<JoinHandle<()>>,
+}
+
+impl PooledSession {
+    /// Returns the id of the session, which is the same for all parties.
+    pub fn session_id(&self) -> u64 {
+        self.session_id
+    }
+
+    /// Returns the [SessionBudget] of the session.
+    pub fn budget(&self) -> SessionBudget {
+        self.budget
+    }
+
+    /// Returns the network handler of the pooled connections.
+    pub fn handler(&self) -> &Arc<MpcNetworkHandlerWrapper> {
+        &self.handler
+    }
+
+    /// Returns our own id in the network.
+    pub fn get_id(&self) -> usize {
+        self.my_id
+    }
+
+    /// Returns the number of parties in the network.
+    pub fn get_num_parties(&self) -> usize {
+        self.num_parties
+    }
+
+    /// Sets up a new [BytesChannel] to each party for this session. The resulting map maps the id of the party to its respective [BytesChannel]. Needs to be called from within the runtime of the [handler](Self::handler).
+    ///
+    /// Fails if another party leased a different session, or if the channels are not set up within the [handshake timeout](PoolConfig::handshake_timeout).
+    pub async fn get_byte_channels(
+        &self,
+    ) -> std::io::Result<HashMap<usize, BytesChannel<RecvStream, SendStream>>> {
+        self.handler
+            .inner
+            .get_byte_channels_for_session(self.session_id, self.handshake_timeout)
+            .await
+    }
+}
+
+impl Drop for PooledSession {
+    fn drop(&mut self) {
+        if let Some(budget_watchdog) = &self.budget_watchdog {
+            budget_watchdog.abort();
+        }
+        self.counters
+            .active_sessions
+            .fetch_sub(1, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,96 @@
+use std::{
+    collections::HashMap,
+    io,
+    sync::{mpsc, Arc},
+    thread,
+};
+
+use bytes::Bytes;
+use futures::{SinkExt, StreamExt};
+use mpc_net::{
+    channel::BytesChannel,
+    config::{Address, NetworkConfig, NetworkParty, SessionBudget},
+};
+use quinn::{
+    rustls::pki_types::{PrivateKeyDer, PrivatePkcs8KeyDer},
+    RecvStream, SendStream,
+};
+use rcgen::CertifiedKey;
+
+const HOST: &str = "127.0.0.1";
+
+/// Returns the configs of three parties on localhost, listening on the ports `base_port..base_port + 3`.
+pub fn configs(base_port: u16, budget: SessionBudget) -> Vec<NetworkConfig> {
+    let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+    let keys = (0..3)
+        .map(|_| rcgen::generate_simple_self_signed(vec![HOST.to_owned()]).unwrap())
+        .collect::<Vec<_>>();
+    let parties = keys
+        .iter()
+        .enumerate()
+        .map(|(id, CertifiedKey { cert, .. })| NetworkParty {
+            id,
+            dns_name: Address {
+                hostname: HOST.to_owned(),
+                port: base_port + id as u16,
+            },
+            cert: cert.der().to_owned(),
+        })
+        .collect::<Vec<_>>();
+    keys.iter()
+        .enumerate()
+        .map(|(id, CertifiedKey { key_pair, .. })| NetworkConfig {
+            parties: parties.clone(),
+            my_id: id,
+            bind_addr: format!("{HOST}:{}", base_port + id as u16).parse().unwrap(),
+            key: PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key_pair.serialize_der())),
+            budget,
+        })
+        .collect()
+}
+
+/// Runs `f` for each party in its own thread and returns the results ordered by the party id.
+pub fn run_parties<T: Send + 'static>(
+    configs: Vec<NetworkConfig>,
+    f: impl Fn(NetworkConfig) -> T + Send + Sync + 'static,
+) -> Vec<T> {
+    let f = Arc::new(f);
+    let receivers = configs
+        .into_iter()
+        .map(|config| {
+            let (tx, rx) = mpsc::channel();
+            let f = Arc::clone(&f);
+            thread::spawn(move || tx.send(f(config)));
+            rx
+        })
+        .collect::<Vec<_>>();
+    receivers
+        .into_iter()
+        .map(|rx| rx.recv().expect("party thread panicked"))
+        .collect()
+}
+
+/// Sends a message of `len` bytes to every other party and receives one from every other party, `rounds` times.
+pub async fn exchange(
+    channels: &mut HashMap<usize, BytesChannel<RecvStream, SendStream>>,
+    len: usize,
+    rounds: usize,
+) -> io::Result<()> {
+    for _ in 0..rounds {
+        for channel in channels.values_mut() {
+            channel.send(Bytes::from(vec![0u8; len])).await?;
+        }
+        for channel in channels.values_mut() {
+            match channel.next().await {
+                Some(msg) => assert_eq!(msg?.len(), len),
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "channel was closed",
+                    ))
+                }
+            }
+        }
+    }
+    Ok(())
+}
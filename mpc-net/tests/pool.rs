@@ -0,0 +1,263 @@
+mod common;
+
+use std::{
+    io,
+    sync::{Arc, Barrier},
+    time::{Duration, Instant},
+};
+
+use mpc_net::{
+    config::SessionBudget,
+    pool::{MpcConnectionPool, PoolConfig},
+    MpcNetworkHandler, MpcNetworkHandlerWrapper, SessionAborted, SessionBudgetExceeded,
+    SessionGuard,
+};
+
+// more rounds than any of the budgets allows, so the sessions only end when the budget is exceeded
+const ROUNDS: usize = 10_000;
+const MSG_LEN: usize = 1 << 16;
+
+fn assert_budget_exceeded(err: io::Error) {
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut, "{err}");
+    assert!(err
+        .get_ref()
+        .and_then(|err| err.downcast_ref::<SessionBudgetExceeded>())
+        .is_some());
+}
+
+fn assert_aborted_by(err: io::Error, party: usize, reason: &str) {
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionAborted, "{err}");
+    let abort = err
+        .get_ref()
+        .and_then(|err| err.downcast_ref::<SessionAborted>())
+        .expect("error is a SessionAborted");
+    assert_eq!(abort.party, party);
+    assert_eq!(abort.reason, reason);
+}
+
+#[test]
+fn pooled_session_exceeds_bytes_budget() {
+    let budget = SessionBudget {
+        max_bytes: Some(1 << 20),
+        ..Default::default()
+    };
+    let configs = common::configs(21000, budget);
+    let results = common::run_parties(configs, |config| {
+        let pool = MpcConnectionPool::new(config, PoolConfig::default()).unwrap();
+        let session = pool.lease().unwrap();
+        assert_eq!(session.budget(), budget);
+        let handler = session.handler().to_owned();
+        let result = handler.runtime.block_on(async {
+            let mut channels = session.get_byte_channels().await?;
+            common::exchange(&mut channels, MSG_LEN, ROUNDS).await
+        });
+        result.map_err(|err| handler.inner.map_session_error(err))
+    });
+    for result in results {
+        assert_budget_exceeded(result.expect_err("budget was not enforced"));
+    }
+}
+
+#[test]
+fn pooled_session_exceeds_wall_clock_budget() {
+    let budget = SessionBudget {
+        max_wall_clock_secs: Some(1),
+        ..Default::default()
+    };
+    let configs = common::configs(21010, budget);
+    let results = common::run_parties(configs, |config| {
+        let pool = MpcConnectionPool::new(config, PoolConfig::default()).unwrap();
+        let session = pool.lease().unwrap();
+        let handler = session.handler().to_owned();
+        let result = handler.runtime.block_on(async {
+            let mut channels = session.get_byte_channels().await?;
+            tokio::time::sleep(Duration::from_secs(2)).await;
+            common::exchange(&mut channels, 1, 1).await
+        });
+        result.map_err(|err| handler.inner.map_session_error(err))
+    });
+    for result in results {
+        assert_budget_exceeded(result.expect_err("budget was not enforced"));
+    }
+}
+
+#[test]
+fn pooled_session_budget_is_measured_from_lease() {
+    let configs = common::configs(21020, SessionBudget::default());
+    let results = common::run_parties(configs, |config| {
+        let pool = MpcConnectionPool::new(config, PoolConfig::default()).unwrap();
+        // the first session transfers more than the budget of the second one
+        let session = pool.lease().unwrap();
+        let handler = session.handler().to_owned();
+        handler.runtime.block_on(async {
+            let mut channels = session.get_byte_channels().await?;
+            common::exchange(&mut channels, MSG_LEN, 32).await
+        })?;
+        drop(session);
+
+        let budget = SessionBudget {
+            max_bytes: Some(1 << 20),
+            ..Default::default()
+        };
+        let session = pool.lease_with_budget(budget).unwrap();
+        handler.runtime.block_on(async {
+            let mut channels = session.get_byte_channels().await?;
+            common::exchange(&mut channels, 1024, 8).await?;
+            // give the watchdog the chance to run
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            common::exchange(&mut channels, 1024, 8).await
+        })?;
+        assert_eq!(pool.metrics().reconnects, 0);
+        io::Result::Ok(())
+    });
+    for result in results {
+        result.unwrap();
+    }
+}
+
+#[test]
+fn pooled_session_id_mismatch_fails_instead_of_hanging() {
+    let pool_config = PoolConfig {
+        handshake_timeout: Duration::from_secs(2),
+        ..Default::default()
+    };
+    let configs = common::configs(21030, SessionBudget::default());
+    let results = common::run_parties(configs, move |config| {
+        let pool = MpcConnectionPool::new(config, pool_config).unwrap();
+        // party 0 skips a session, so it opens session 1 while the others open session 0
+        if pool.get_id() == 0 {
+            drop(pool.lease().unwrap());
+        }
+        let session = pool.lease().unwrap();
+        let handler = session.handler().to_owned();
+        handler
+            .runtime
+            .block_on(session.get_byte_channels())
+            .map(|_| ())
+    });
+    let kinds = results
+        .into_iter()
+        .map(|result| result.expect_err("sessions did not match").kind())
+        .collect::<Vec<_>>();
+    // party 0 and 1 detect the mismatch, party 2 waits for party 0 until the timeout
+    assert_eq!(
+        kinds,
+        [
+            io::ErrorKind::InvalidData,
+            io::ErrorKind::InvalidData,
+            io::ErrorKind::TimedOut
+        ]
+    );
+}
+
+#[test]
+fn pool_reconnects_after_connections_were_closed() {
+    let budget = SessionBudget {
+        max_bytes: Some(1 << 20),
+        ..Default::default()
+    };
+    let configs = common::configs(21040, SessionBudget::default());
+    let barrier = Arc::new(Barrier::new(configs.len()));
+    let results = common::run_parties(configs, move |config| {
+        let pool = MpcConnectionPool::new(config, PoolConfig::default()).unwrap();
+        // exceeding the budget closes the pooled connections
+        let session = pool.lease_with_budget(budget).unwrap();
+        let handler = session.handler().to_owned();
+        let result = handler.runtime.block_on(async {
+            let mut channels = session.get_byte_channels().await?;
+            common::exchange(&mut channels, MSG_LEN, ROUNDS).await
+        });
+        assert!(result.is_err());
+        // the closed connections can not be replaced while the session still uses them
+        assert!(pool.lease().is_err());
+        drop(handler);
+        drop(session);
+
+        // all parties reconnect and start again with the first session
+        barrier.wait();
+        let session = pool.lease().unwrap();
+        assert_eq!(session.session_id(), 0);
+        let handler = session.handler().to_owned();
+        handler.runtime.block_on(async {
+            let mut channels = session.get_byte_channels().await?;
+            common::exchange(&mut channels, 1024, 8).await
+        })?;
+        assert_eq!(pool.metrics().reconnects, 1);
+        io::Result::Ok(())
+    });
+    for result in results {
+        result.unwrap();
+    }
+}
+
+#[test]
+fn aborted_session_fails_at_other_parties() {
+    let configs = common::configs(21050, SessionBudget::default());
+    let results = common::run_parties(configs, |config| {
+        let pool = MpcConnectionPool::new(config, PoolConfig::default()).unwrap();
+        let session = pool.lease().unwrap();
+        let handler = session.handler().to_owned();
+        let start = Instant::now();
+        let result = handler.runtime.block_on(async {
+            let mut channels = session.get_byte_channels().await?;
+            common::exchange(&mut channels, 1024, 8).await?;
+            if pool.get_id() == 0 {
+                handler.inner.abort("party 0 failed");
+                return Ok(());
+            }
+            common::exchange(&mut channels, 1024, ROUNDS).await
+        });
+        let result = result.map_err(|err| handler.inner.map_session_error(err));
+        (start.elapsed(), result)
+    });
+    for (id, (elapsed, result)) in results.into_iter().enumerate() {
+        // the other parties fail right away instead of waiting for the idle timeout
+        assert!(elapsed < Duration::from_secs(10));
+        if id == 0 {
+            result.unwrap();
+        } else {
+            assert_aborted_by(result.unwrap_err(), 0, "party 0 failed");
+        }
+    }
+}
+
+#[test]
+fn dropped_session_guard_fails_at_other_parties() {
+    let configs = common::configs(21060, SessionBudget::default());
+    let results = common::run_parties(configs, |config| {
+        let id = config.my_id;
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let inner = runtime
+            .block_on(MpcNetworkHandler::establish(config))
+            .unwrap();
+        let handler = Arc::new(MpcNetworkHandlerWrapper::new(runtime, inner));
+        let guard = SessionGuard::new(Arc::clone(&handler));
+        let start = Instant::now();
+        let result = handler.runtime.block_on(async {
+            let mut channels = handler.inner.get_byte_channels().await?;
+            common::exchange(&mut channels, 1024, 8).await?;
+            if id == 0 {
+                // party 0 fails, e.g., with an error, before it finishes the session
+                drop(guard);
+                return Ok(());
+            }
+            let result = common::exchange(&mut channels, 1024, ROUNDS).await;
+            // the other parties only fail because of the abort of party 0
+            guard.finish();
+            result
+        });
+        let result = result.map_err(|err| handler.inner.map_session_error(err));
+        (start.elapsed(), result)
+    });
+    for (id, (elapsed, result)) in results.into_iter().enumerate() {
+        assert!(elapsed < Duration::from_secs(10));
+        if id == 0 {
+            result.unwrap();
+        } else {
+            assert_aborted_by(result.unwrap_err(), 0, "the command failed");
+        }
+    }
+}
@@ -0,0 +1,23 @@
+pragma circom 2.2.0;
+
+bus Point() {
+    signal y;
+    signal x;
+}
+
+bus Segment() {
+    Point() start;
+    signal weights[2];
+    Point() end;
+}
+
+template BusInput() {
+    input Segment() in[2];
+    signal input scale;
+    signal output out;
+
+    signal prod <== in[0].start.x * in[1].end.y;
+    out <== prod * scale + in[0].weights[1];
+}
+
+component main {public [scale]} = BusInput();
@@ -63,7 +63,6 @@ fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
     let inputs = Rep3CoSolver::<_, PartyTestNetwork>::partially_read_abi_bn254_fieldelement(
         &input,
         &program_artifact.abi,
-        &program_artifact.bytecode,
     )?;
 
     // create input shares
@@ -0,0 +1,151 @@
+use std::{collections::BTreeMap, thread};
+
+use ark_bn254::{Bn254, Fr};
+use ark_ec::pairing::Pairing;
+use ark_ff::UniformRand;
+use co_circom_snarks::commitment::{CommitmentOpening, InputCommitment};
+use color_eyre::eyre;
+use mpc_core::protocols::rep3::{self, Rep3PrimeFieldShare};
+use rand::thread_rng;
+use tests::rep3_network::Rep3TestNetwork;
+
+type G1 = <Bn254 as Pairing>::G1;
+
+fn inputs() -> BTreeMap<String, Vec<Fr>> {
+    let mut rng = thread_rng();
+    BTreeMap::from([
+        ("a".to_owned(), vec![Fr::rand(&mut rng), Fr::rand(&mut rng)]),
+        ("b".to_owned(), vec![Fr::rand(&mut rng)]),
+    ])
+}
+
+fn share_inputs(
+    inputs: &BTreeMap<String, Vec<Fr>>,
+) -> [BTreeMap<String, Vec<Rep3PrimeFieldShare<Fr>>>; 3] {
+    let mut rng = thread_rng();
+    let mut shares = [BTreeMap::new(), BTreeMap::new(), BTreeMap::new()];
+    for (name, values) in inputs {
+        let [a, b, c] = rep3::share_field_elements(values, &mut rng);
+        shares[0].insert(name.clone(), a);
+        shares[1].insert(name.clone(), b);
+        shares[2].insert(name.clone(), c);
+    }
+    shares
+}
+
+// all parties fail at the same point, so no party waits for a message of a party that already failed
+fn verify_rep3(
+    commitment: InputCommitment<G1>,
+    public_inputs: BTreeMap<String, Vec<Fr>>,
+    shared_inputs: [BTreeMap<String, Vec<Rep3PrimeFieldShare<Fr>>>; 3],
+    openings: [CommitmentOpening<Rep3PrimeFieldShare<Fr>>; 3],
+) -> Vec<eyre::Result<()>> {
+    let threads = Rep3TestNetwork::default()
+        .get_party_networks()
+        .into_iter()
+        .zip(shared_inputs)
+        .zip(openings)
+        .map(|((mut net, shared_inputs), opening)| {
+            let commitment = commitment.clone();
+            let public_inputs = public_inputs.clone();
+            thread::spawn(move || {
+                commitment.verify_rep3(&public_inputs, &shared_inputs, &opening, &mut net)
+            })
+        })
+        .collect::<Vec<_>>();
+    threads
+        .into_iter()
+        .map(|thread| thread.join().unwrap())
+        .collect()
+}
+
+fn names() -> Vec<String> {
+    vec!["a".to_owned(), "b".to_owned()]
+}
+
+#[test]
+fn rep3_input_commitment() {
+    let inputs = inputs();
+    let (commitment, blinding) =
+        InputCommitment::<G1>::commit("input.json", &inputs, &names(), &mut thread_rng()).unwrap();
+    let openings = CommitmentOpening::share_rep3(names(), blinding, &mut thread_rng());
+    for result in verify_rep3(commitment, BTreeMap::new(), share_inputs(&inputs), openings) {
+        result.unwrap();
+    }
+}
+
+#[test]
+fn rep3_input_commitment_rejects_wrong_blinding() {
+    let inputs = inputs();
+    let (commitment, blinding) =
+        InputCommitment::<G1>::commit("input.json", &inputs, &names(), &mut thread_rng()).unwrap();
+    let openings =
+        CommitmentOpening::share_rep3(names(), blinding + Fr::from(1), &mut thread_rng());
+    for result in verify_rep3(commitment, BTreeMap::new(), share_inputs(&inputs), openings) {
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("do not open to the commitment"));
+    }
+}
+
+#[test]
+fn rep3_input_commitment_rejects_other_inputs() {
+    let inputs = inputs();
+    let (commitment, blinding) =
+        InputCommitment::<G1>::commit("input.json", &inputs, &names(), &mut thread_rng()).unwrap();
+    let openings = CommitmentOpening::share_rep3(names(), blinding, &mut thread_rng());
+    let mut other_inputs = inputs.clone();
+    other_inputs.get_mut("b").unwrap()[0] += Fr::from(1);
+    for result in verify_rep3(
+        commitment,
+        BTreeMap::new(),
+        share_inputs(&other_inputs),
+        openings,
+    ) {
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("do not open to the commitment"));
+    }
+}
+
+#[test]
+fn rep3_input_commitment_as_public_input() {
+    let inputs = inputs();
+    let (mut commitment, blinding) =
+        InputCommitment::<G1>::commit("input.json", &inputs, &names(), &mut thread_rng()).unwrap();
+    let value = commitment.expose_as_public_input("commitment");
+    assert_eq!(value, commitment.public_input_value());
+    assert_eq!(
+        commitment.public_input.as_ref().unwrap().value,
+        value.to_string()
+    );
+    let openings = CommitmentOpening::share_rep3(names(), blinding, &mut thread_rng());
+
+    let public_inputs = BTreeMap::from([("commitment".to_owned(), vec![value])]);
+    for result in verify_rep3(
+        commitment.clone(),
+        public_inputs,
+        share_inputs(&inputs),
+        openings.clone(),
+    ) {
+        result.unwrap();
+    }
+
+    // the public input of the proof differs from the published commitment
+    let public_inputs = BTreeMap::from([("commitment".to_owned(), vec![value + Fr::from(1)])]);
+    for result in verify_rep3(
+        commitment.clone(),
+        public_inputs,
+        share_inputs(&inputs),
+        openings.clone(),
+    ) {
+        let err = result.unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("public input \"commitment\" differs from the commitment"));
+    }
+
+    // the public input is missing
+    for result in verify_rep3(commitment, BTreeMap::new(), share_inputs(&inputs), openings) {
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("which is missing"));
+    }
+}
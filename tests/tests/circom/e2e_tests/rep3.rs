@@ -1,25 +1,39 @@
+use ark_bls12_377::Bls12_377;
 use ark_bls12_381::Bls12_381;
 use ark_bn254::Bn254;
+use ark_ec::{pairing::Pairing, CurveGroup, Group};
+use ark_ff::{Field, One, UniformRand, Zero};
+use circom_mpc_compiler::{CoCircomCompiler, CompilerConfig};
+use circom_mpc_vm::mpc_vm::VMConfig;
 use circom_types::plonk::PlonkProof;
-use circom_types::Witness;
 use circom_types::{
-    groth16::{Groth16Proof, JsonPublicInput, JsonVerificationKey as Groth16VK, ZKey as Groth16ZK},
+    groth16::{
+        ConstraintMatrix, Groth16Proof, JsonPublicInput, JsonVerificationKey as Groth16VK,
+        ZKey as Groth16ZK,
+    },
     plonk::{JsonVerificationKey as PlonkVK, ZKey as PlonkZK},
     R1CS,
 };
 use mpc_core::protocols::rep3::network::IoContext;
+use mpc_core::protocols::rep3::Rep3PrimeFieldShare;
+use mpc_core::protocols::shamir::ShamirPrimeFieldShare;
 use std::sync::Arc;
 
-use circom_types::traits::CheckElement;
-use co_circom_snarks::SharedWitness;
+use circom_types::traits::{
+    CheckElement, CircomArkworksPairingBridge, CircomArkworksPrimeFieldBridge,
+};
+use circom_types::{ConstraintEvaluator, Witness};
+use co_circom_snarks::sharing_proof::{SharingProof, SharingScheme};
+use co_circom_snarks::{mp_spdz, SharedWitness, SparseSharedWitness, WitnessSection};
+use co_groth16::folding::{CoFolding, CommitmentKey, RelaxedR1CS, RelaxedR1CSWitness};
 use co_groth16::mpc::Rep3Groth16Driver;
 use co_groth16::CoGroth16;
 use co_groth16::Groth16;
-use co_plonk::mpc::Rep3PlonkDriver;
+use co_plonk::mpc::{OpenedValues, PlainPlonkDriver, RecordingPlonkDriver, Rep3PlonkDriver};
 use co_plonk::CoPlonk;
 use co_plonk::Plonk;
 use itertools::izip;
-use rand::thread_rng;
+use rand::{thread_rng, Rng};
 use std::{fs::File, thread};
 use tests::rep3_network::{PartyTestNetwork, Rep3TestNetwork};
 
@@ -106,3 +120,597 @@ macro_rules! add_test_impl {
 }
 e2e_test!("multiplier2");
 e2e_test!("poseidon");
+
+#[test]
+fn e2e_proof_insecure_non_hiding_multiplier2_bn254_groth16() {
+    let zkey_file = File::open("../test_vectors/Groth16/bn254/multiplier2/circuit.zkey").unwrap();
+    let r1cs_file = File::open("../test_vectors/Groth16/bn254/multiplier2/circuit.r1cs").unwrap();
+    let witness_file =
+        File::open("../test_vectors/Groth16/bn254/multiplier2/witness.wtns").unwrap();
+    let vk_file =
+        File::open("../test_vectors/Groth16/bn254/multiplier2/verification_key.json").unwrap();
+    let witness = Witness::<ark_bn254::Fr>::from_reader(witness_file).unwrap();
+    let zkey = Arc::new(Groth16ZK::<Bn254>::from_reader(zkey_file, CheckElement::No).unwrap());
+    let r1cs = R1CS::<Bn254>::from_reader(r1cs_file).unwrap();
+    let vk: Groth16VK<Bn254> = serde_json::from_reader(vk_file).unwrap();
+    let public_input = witness.values[1..r1cs.num_inputs].to_vec();
+    let plain_witness = SharedWitness {
+        public_inputs: witness.values[..r1cs.num_inputs].to_vec(),
+        witness: witness.values[r1cs.num_inputs..].to_vec(),
+    };
+    let plain_proof =
+        Groth16::<Bn254>::plain_prove_insecure_non_hiding(Arc::clone(&zkey), plain_witness)
+            .unwrap();
+
+    let mut rng = thread_rng();
+    let witness_shares = SharedWitness::share_rep3(witness, r1cs.num_inputs, &mut rng);
+    let test_network = Rep3TestNetwork::default();
+    let mut threads = vec![];
+    for (net, x) in izip!(test_network.get_party_networks(), witness_shares) {
+        let zkey = Arc::clone(&zkey);
+        threads.push(thread::spawn(move || {
+            let mut io_context0 = IoContext::init(net).unwrap();
+            let io_context1 = io_context0.fork().unwrap();
+            let rep3 = Rep3Groth16Driver::new(io_context0, io_context1);
+            let prover = CoGroth16::<Bn254, Rep3Groth16Driver<PartyTestNetwork>>::new(rep3);
+            prover.prove_insecure_non_hiding(zkey, x).unwrap()
+        }));
+    }
+    for thread in threads {
+        // without blinding, the MPC proof is the same as the plain one
+        assert_eq!(thread.join().unwrap(), plain_proof);
+    }
+    Groth16::<Bn254>::verify(&vk, &plain_proof, &public_input).expect("can verify");
+}
+
+#[test]
+fn e2e_proof_sparse_poseidon_bn254_groth16() {
+    let zkey_file = File::open("../test_vectors/Groth16/bn254/poseidon/circuit.zkey").unwrap();
+    let r1cs_file = File::open("../test_vectors/Groth16/bn254/poseidon/circuit.r1cs").unwrap();
+    let witness_file = File::open("../test_vectors/Groth16/bn254/poseidon/witness.wtns").unwrap();
+    let vk_file =
+        File::open("../test_vectors/Groth16/bn254/poseidon/verification_key.json").unwrap();
+    let witness = Witness::<ark_bn254::Fr>::from_reader(witness_file).unwrap();
+    let zkey = Arc::new(Groth16ZK::<Bn254>::from_reader(zkey_file, CheckElement::No).unwrap());
+    let r1cs = R1CS::<Bn254>::from_reader(r1cs_file).unwrap();
+    let vk: Groth16VK<Bn254> = serde_json::from_reader(vk_file).unwrap();
+    let public_input = witness.values[1..r1cs.num_inputs].to_vec();
+
+    let mut rng = thread_rng();
+    let witness_shares = SparseSharedWitness::share_rep3(witness, r1cs.num_inputs, &mut rng);
+    let test_network = Rep3TestNetwork::default();
+    let mut threads = vec![];
+    for (net, x) in izip!(test_network.get_party_networks(), witness_shares) {
+        let zkey = Arc::clone(&zkey);
+        threads.push(thread::spawn(move || {
+            let mut io_context0 = IoContext::init(net).unwrap();
+            let io_context1 = io_context0.fork().unwrap();
+            let rep3 = Rep3Groth16Driver::new(io_context0, io_context1);
+            let prover = CoGroth16::<Bn254, Rep3Groth16Driver<PartyTestNetwork>>::new(rep3);
+            prover.prove_sparse(zkey, x).unwrap()
+        }));
+    }
+    let result3 = threads.pop().unwrap().join().unwrap();
+    let result2 = threads.pop().unwrap().join().unwrap();
+    let result1 = threads.pop().unwrap().join().unwrap();
+    assert_eq!(result1, result2);
+    assert_eq!(result2, result3);
+    Groth16::<Bn254>::verify(&vk, &result1, &public_input).expect("can verify");
+}
+
+#[test]
+fn e2e_recorded_openings_poseidon_bn254_plonk() {
+    let zkey_file = File::open("../test_vectors/Plonk/bn254/poseidon/circuit.zkey").unwrap();
+    let r1cs_file = File::open("../test_vectors/Plonk/bn254/poseidon/circuit.r1cs").unwrap();
+    let witness_file = File::open("../test_vectors/Plonk/bn254/poseidon/witness.wtns").unwrap();
+    let witness = Witness::<ark_bn254::Fr>::from_reader(witness_file).unwrap();
+    let zkey = Arc::new(PlonkZK::<Bn254>::from_reader(zkey_file, CheckElement::No).unwrap());
+    let r1cs = R1CS::<Bn254>::from_reader(r1cs_file).unwrap();
+    let plain_witness = SharedWitness {
+        public_inputs: witness.values[..r1cs.num_inputs].to_vec(),
+        witness: witness.values[r1cs.num_inputs..].to_vec(),
+    };
+    let reference = OpenedValues::new();
+    let plain_proof = CoPlonk::<Bn254, _>::new(RecordingPlonkDriver::new(
+        PlainPlonkDriver,
+        reference.clone(),
+    ))
+    .prove(Arc::clone(&zkey), plain_witness)
+    .unwrap();
+    assert!(!reference.openings().is_empty());
+
+    let mut rng = thread_rng();
+    let shares = SharedWitness::share_rep3(witness, r1cs.num_inputs, &mut rng);
+    let test_network = Rep3TestNetwork::default();
+    let mut threads = vec![];
+    for (net, x) in izip!(test_network.get_party_networks(), shares) {
+        let zkey = Arc::clone(&zkey);
+        threads.push(thread::spawn(move || {
+            let mut io_context0 = IoContext::init(net).unwrap();
+            let io_context1 = io_context0.fork().unwrap();
+            let openings = OpenedValues::new();
+            let driver = RecordingPlonkDriver::new(
+                Rep3PlonkDriver::new(io_context0, io_context1),
+                openings.clone(),
+            );
+            let proof = CoPlonk::<Bn254, _>::new(driver).prove(zkey, x).unwrap();
+            (proof, openings)
+        }));
+    }
+    for thread in threads {
+        let (proof, openings) = thread.join().unwrap();
+        if let Some(divergence) = openings.first_divergence(&reference) {
+            panic!("MPC run diverges from plain run: {divergence}");
+        }
+        assert_eq!(proof, plain_proof);
+    }
+}
+
+#[test]
+fn e2e_witness_consistency_rep3() {
+    let r1cs_file = File::open("../test_vectors/Groth16/bn254/poseidon/circuit.r1cs").unwrap();
+    let witness_file = File::open("../test_vectors/Groth16/bn254/poseidon/witness.wtns").unwrap();
+    let witness = Witness::<ark_bn254::Fr>::from_reader(witness_file).unwrap();
+    let r1cs = R1CS::<Bn254>::from_reader(r1cs_file).unwrap();
+
+    let mut rng = thread_rng();
+    let shares = SharedWitness::share_rep3(witness.clone(), r1cs.num_inputs, &mut rng);
+    let [_, _, other_share3] = SharedWitness::share_rep3(witness, r1cs.num_inputs, &mut rng);
+    let [share1, share2, share3] = shares.clone();
+    // the last party uses the share of a different sharing of the same witness
+    for (shares, should_succeed) in [
+        ([share1, share2, share3], true),
+        (
+            {
+                let [share1, share2, _] = shares;
+                [share1, share2, other_share3]
+            },
+            false,
+        ),
+    ] {
+        let test_network = Rep3TestNetwork::default();
+        let mut threads = vec![];
+        for (mut net, x) in izip!(test_network.get_party_networks(), shares) {
+            threads.push(thread::spawn(move || {
+                x.verify_consistency_rep3(&mut net).is_ok()
+            }));
+        }
+        for thread in threads {
+            assert_eq!(thread.join().unwrap(), should_succeed);
+        }
+    }
+}
+
+#[test]
+fn e2e_witness_determinism_rep3() {
+    let r1cs_file = File::open("../test_vectors/Groth16/bn254/poseidon/circuit.r1cs").unwrap();
+    let witness_file = File::open("../test_vectors/Groth16/bn254/poseidon/witness.wtns").unwrap();
+    let witness = Witness::<ark_bn254::Fr>::from_reader(witness_file).unwrap();
+    let r1cs = R1CS::<Bn254>::from_reader(r1cs_file).unwrap();
+
+    let mut rng = thread_rng();
+    let shares = SharedWitness::share_rep3(witness, r1cs.num_inputs, &mut rng);
+    // the last party diverges in the first component of one of its shares
+    let mut diverged = shares.clone();
+    diverged[2].witness[5].a += ark_bn254::Fr::from(1u64);
+    for (shares, divergent) in [(shares, None), (diverged, Some(5))] {
+        let test_network = Rep3TestNetwork::default();
+        let mut threads = vec![];
+        for (mut net, x) in izip!(test_network.get_party_networks(), shares) {
+            threads.push(thread::spawn(move || {
+                x.check_determinism_rep3(&mut net).unwrap()
+            }));
+        }
+        for (party, thread) in threads.into_iter().enumerate() {
+            let report = thread.join().unwrap();
+            assert_eq!(report.is_deterministic(), divergent.is_none());
+            let divergence = report.divergence;
+            if party == 0 && divergent.is_some() {
+                let divergence = divergence.expect("party 0 detects the divergence");
+                assert_eq!(divergence.other_party, 2);
+                assert_eq!(divergence.section, WitnessSection::Witness);
+                assert_eq!(Some(divergence.index), divergent);
+                assert_eq!(report.diverged, vec![0]);
+            } else {
+                assert!(divergence.is_none());
+            }
+        }
+    }
+}
+
+#[test]
+fn e2e_sharing_proof_rep3() {
+    let r1cs_file = File::open("../test_vectors/Groth16/bn254/poseidon/circuit.r1cs").unwrap();
+    let witness_file = File::open("../test_vectors/Groth16/bn254/poseidon/witness.wtns").unwrap();
+    let witness = Witness::<ark_bn254::Fr>::from_reader(witness_file).unwrap();
+    let r1cs = R1CS::<Bn254>::from_reader(r1cs_file).unwrap();
+    let (public_inputs, private_witness) = witness.values.split_at(r1cs.num_inputs);
+
+    let mut rng = thread_rng();
+    let shares = SharedWitness::share_rep3(witness.clone(), r1cs.num_inputs, &mut rng);
+    let additive = shares
+        .iter()
+        .map(|share| share.witness.iter().map(|x| x.a).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let (proof, openings) = SharingProof::<Bn254>::prove(
+        &r1cs,
+        public_inputs,
+        private_witness,
+        SharingScheme::Rep3,
+        &additive,
+        &mut rng,
+    )
+    .unwrap();
+    proof.verify(&r1cs).unwrap();
+    for (share, opening) in izip!(shares.iter(), openings.iter()) {
+        let a = share.witness.iter().map(|x| x.a).collect::<Vec<_>>();
+        let b = share.witness.iter().map(|x| x.b).collect::<Vec<_>>();
+        proof
+            .verify_share(opening, public_inputs, &[&a, &b])
+            .unwrap();
+    }
+
+    // a share that differs from the committed one is detected by its owner
+    let mut tampered = additive[1].clone();
+    tampered[3] += ark_bn254::Fr::from(1u64);
+    assert!(proof
+        .verify_share(&openings[1], public_inputs, &[&tampered])
+        .is_err());
+
+    // a witness that does not satisfy the R1CS can not be proven
+    let mut wrong_witness = private_witness.to_vec();
+    wrong_witness[0] += ark_bn254::Fr::from(1u64);
+    let mut wrong_additive = additive.clone();
+    wrong_additive[0][0] += ark_bn254::Fr::from(1u64);
+    assert!(SharingProof::<Bn254>::prove(
+        &r1cs,
+        public_inputs,
+        &wrong_witness,
+        SharingScheme::Rep3,
+        &wrong_additive,
+        &mut rng,
+    )
+    .is_err());
+
+    // the proof is bound to its public inputs
+    let mut forged = proof.clone();
+    forged.public_inputs[1] += ark_bn254::Fr::from(1u64);
+    assert!(forged.verify(&r1cs).is_err());
+}
+
+#[test]
+fn e2e_mp_spdz_interop_rep3() {
+    let r1cs_file = File::open("../test_vectors/Groth16/bn254/poseidon/circuit.r1cs").unwrap();
+    let witness_file = File::open("../test_vectors/Groth16/bn254/poseidon/witness.wtns").unwrap();
+    let witness = Witness::<ark_bn254::Fr>::from_reader(witness_file).unwrap();
+    let r1cs = R1CS::<Bn254>::from_reader(r1cs_file).unwrap();
+
+    let mut rng = thread_rng();
+    let shares = SharedWitness::share_rep3(witness.clone(), r1cs.num_inputs, &mut rng);
+    let files = shares
+        .iter()
+        .map(|share| {
+            let mut file = Vec::new();
+            share.to_mp_spdz(&mut file).unwrap();
+            file
+        })
+        .collect::<Vec<_>>();
+
+    // MP-SPDZ party i stores (x_i, x_{i+1}), so the second element of party i is the first element of party i + 1
+    let raw = files
+        .iter()
+        .map(|file| {
+            let header_len = 8 + u64::from_le_bytes(file[..8].try_into().unwrap()) as usize;
+            file[header_len..].to_vec()
+        })
+        .collect::<Vec<_>>();
+    for i in 0..3 {
+        assert_eq!(raw[i][32..64], raw[(i + 1) % 3][..32]);
+    }
+
+    let imported = files
+        .iter()
+        .map(|file| {
+            SharedWitness::<_, Rep3PrimeFieldShare<_>>::from_mp_spdz(
+                file.as_slice(),
+                shares[0].public_inputs.clone(),
+            )
+            .unwrap()
+        })
+        .collect::<Vec<_>>();
+    for (share, imported) in izip!(shares.iter(), imported.iter()) {
+        assert_eq!(share.witness, imported.witness);
+    }
+    let combined = mpc_core::protocols::rep3::combine_field_elements(
+        &imported[0].witness,
+        &imported[1].witness,
+        &imported[2].witness,
+    );
+    assert_eq!(combined, witness.values[r1cs.num_inputs..]);
+
+    // a truncated file is rejected
+    assert!(SharedWitness::<_, Rep3PrimeFieldShare<_>>::from_mp_spdz(
+        &files[0][..files[0].len() - 1],
+        shares[0].public_inputs.clone(),
+    )
+    .is_err());
+    // a file of another share type is rejected
+    assert!(
+        mp_spdz::read_shares::<ark_bn254::Fr, ShamirPrimeFieldShare<_>, _>(files[0].as_slice())
+            .is_err()
+    );
+    // a file of a different field is rejected
+    assert!(
+        mp_spdz::read_shares::<ark_bls12_381::Fr, Rep3PrimeFieldShare<_>, _>(files[0].as_slice())
+            .is_err()
+    );
+}
+
+#[test]
+fn e2e_mp_spdz_reference_files_rep3() {
+    let files = (0..3)
+        .map(|i| {
+            std::fs::read(format!(
+                "../test_vectors/MP-SPDZ/replicated-field-party/Transactions-P{i}.data"
+            ))
+            .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let shares = files
+        .iter()
+        .map(|file| {
+            mp_spdz::read_shares::<ark_bn254::Fr, Rep3PrimeFieldShare<_>, _>(file.as_slice())
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    // the files store the secrets 1, 2, 42 and p - 1, see the README of the test vectors
+    let combined =
+        mpc_core::protocols::rep3::combine_field_elements(&shares[0], &shares[1], &shares[2]);
+    assert_eq!(
+        combined,
+        [
+            ark_bn254::Fr::from(1u64),
+            ark_bn254::Fr::from(2u64),
+            ark_bn254::Fr::from(42u64),
+            -ark_bn254::Fr::one()
+        ]
+    );
+
+    // exporting the shares again yields the same files
+    for (share, file) in izip!(shares.iter(), files.iter()) {
+        let mut written = Vec::new();
+        mp_spdz::write_shares::<ark_bn254::Fr, _, _>(share, &mut written).unwrap();
+        assert_eq!(&written, file);
+    }
+}
+
+/// A Groth16 setup for the given constraint matrices with the toxic waste sampled from `rng`.
+///
+/// snarkjs cannot create zkeys for BLS12-377, so the proving key is computed here with the same layout as the zkeys of snarkjs: the public inputs are constrained in the rows of A after the constraints, and the h query holds the odd Lagrange polynomials of the domain of twice the size.
+fn groth16_setup<P: Pairing + CircomArkworksPairingBridge>(
+    num_vars: usize,
+    n_public: usize,
+    matrices: [ConstraintMatrix<P::ScalarField>; 3],
+    rng: &mut impl Rng,
+) -> (Groth16ZK<P>, Groth16VK<P>)
+where
+    P::BaseField: CircomArkworksPrimeFieldBridge,
+    P::ScalarField: CircomArkworksPrimeFieldBridge,
+{
+    let [a, b, c] = matrices;
+    let num_constraints = a.len();
+    let pow = (num_constraints + n_public + 1)
+        .next_power_of_two()
+        .trailing_zeros() as usize;
+    let domain_size = 1 << pow;
+    let (_, roots) = co_circom_snarks::utils::roots_of_unity::<P::ScalarField>();
+    let [tau, alpha, beta, gamma, delta] = std::array::from_fn(|_| P::ScalarField::rand(rng));
+    // the Lagrange polynomials of the domain of size n generated by omega, evaluated at tau
+    let lagrange = |n: usize, omega: P::ScalarField| {
+        let vanishing = tau.pow([n as u64]) - P::ScalarField::one();
+        let n_inv = P::ScalarField::from(n as u64).inverse().unwrap();
+        let mut omega_i = P::ScalarField::one();
+        (0..n)
+            .map(|_| {
+                let l = vanishing * n_inv * omega_i / (tau - omega_i);
+                omega_i *= omega;
+                l
+            })
+            .collect::<Vec<_>>()
+    };
+    let lagrange_n = lagrange(domain_size, roots[pow]);
+    let lagrange_2n = lagrange(2 * domain_size, roots[pow + 1]);
+
+    // the QAP polynomials of the variables evaluated at tau
+    let mut u = vec![P::ScalarField::zero(); num_vars];
+    let mut v = vec![P::ScalarField::zero(); num_vars];
+    let mut w = vec![P::ScalarField::zero(); num_vars];
+    for (row, (a, b, c)) in izip!(&a, &b, &c).enumerate() {
+        for (poly, constraint) in [(&mut u, a), (&mut v, b), (&mut w, c)] {
+            for (coeff, var) in constraint {
+                poly[*var] += lagrange_n[row] * coeff;
+            }
+        }
+    }
+    for (var, u) in u.iter_mut().enumerate().take(n_public + 1) {
+        *u += lagrange_n[num_constraints + var];
+    }
+    let k = |var: usize| beta * u[var] + alpha * v[var] + w[var];
+
+    let g1 = P::G1::generator();
+    let g2 = P::G2::generator();
+    let delta_inv = delta.inverse().unwrap();
+    let gamma_inv = gamma.inverse().unwrap();
+    let alpha_g1 = (g1 * alpha).into_affine();
+    let beta_g2 = (g2 * beta).into_affine();
+    let zkey = Groth16ZK {
+        n_public,
+        pow,
+        num_constraints,
+        beta_g1: (g1 * beta).into_affine(),
+        delta_g1: (g1 * delta).into_affine(),
+        a_query: u.iter().map(|u| (g1 * u).into_affine()).collect(),
+        b_g1_query: v.iter().map(|v| (g1 * v).into_affine()).collect(),
+        b_g2_query: v.iter().map(|v| (g2 * v).into_affine()).collect(),
+        h_query: (0..domain_size)
+            .map(|i| (g1 * (lagrange_2n[2 * i + 1] * delta_inv)).into_affine())
+            .collect(),
+        l_query: (n_public + 1..num_vars)
+            .map(|var| (g1 * (k(var) * delta_inv)).into_affine())
+            .collect(),
+        alpha_g1,
+        beta_g2,
+        delta_g2: (g2 * delta).into_affine(),
+        a_matrix: ConstraintEvaluator::new(&a),
+        b_matrix: ConstraintEvaluator::new(&b),
+    };
+    let vk = Groth16VK {
+        protocol: "groth16".to_owned(),
+        n_public,
+        alpha_1: alpha_g1,
+        beta_2: beta_g2,
+        gamma_2: (g2 * gamma).into_affine(),
+        delta_2: zkey.delta_g2,
+        alpha_beta_gt: P::pairing(alpha_g1, beta_g2).0,
+        ic: (0..=n_public)
+            .map(|var| (g1 * (k(var) * gamma_inv)).into_affine())
+            .collect(),
+    };
+    (zkey, vk)
+}
+
+#[test]
+fn e2e_proof_multiplier2_bls12_377_groth16() {
+    let mut rng = thread_rng();
+    // the witness of multiplier2 is [1, c, a, b] with the single constraint a * b = c
+    let one = ark_bls12_377::Fr::one();
+    let (zkey, vk) = groth16_setup::<Bls12_377>(
+        4,
+        1,
+        [
+            vec![vec![(one, 2)]],
+            vec![vec![(one, 3)]],
+            vec![vec![(one, 1)]],
+        ],
+        &mut rng,
+    );
+    let zkey = Arc::new(zkey);
+    // the verification key survives the JSON format of snarkjs
+    let vk: Groth16VK<Bls12_377> =
+        serde_json::from_str(&serde_json::to_string(&vk).unwrap()).unwrap();
+
+    let inputs = vec![
+        ark_bls12_377::Fr::from(3u64),
+        ark_bls12_377::Fr::from(11u64),
+    ];
+    let witness = CoCircomCompiler::<Bls12_377>::parse(
+        "../test_vectors/WitnessExtension/tests/multiplier2.circom",
+        CompilerConfig::default(),
+    )
+    .unwrap()
+    .to_plain_vm(VMConfig::default())
+    .run_with_flat(inputs, 0)
+    .unwrap()
+    .into_shared_witness();
+    let num_inputs = witness.public_inputs.len();
+    let public_input = witness.public_inputs[1..].to_vec();
+    assert_eq!(public_input, vec![ark_bls12_377::Fr::from(33u64)]);
+
+    let plain_proof =
+        Groth16::<Bls12_377>::plain_prove(Arc::clone(&zkey), witness.clone()).unwrap();
+    Groth16::<Bls12_377>::verify(&vk, &plain_proof, &public_input).expect("can verify");
+
+    let witness = Witness {
+        values: witness
+            .public_inputs
+            .into_iter()
+            .chain(witness.witness)
+            .collect(),
+    };
+    let witness_shares = SharedWitness::share_rep3(witness, num_inputs, &mut rng);
+    let test_network = Rep3TestNetwork::default();
+    let mut threads = vec![];
+    for (net, x) in izip!(test_network.get_party_networks(), witness_shares) {
+        let zkey = Arc::clone(&zkey);
+        threads.push(thread::spawn(move || {
+            let mut io_context0 = IoContext::init(net).unwrap();
+            let io_context1 = io_context0.fork().unwrap();
+            let rep3 = Rep3Groth16Driver::new(io_context0, io_context1);
+            let prover = CoGroth16::<Bls12_377, Rep3Groth16Driver<PartyTestNetwork>>::new(rep3);
+            prover.prove(zkey, x).unwrap()
+        }));
+    }
+    let result3 = threads.pop().unwrap().join().unwrap();
+    let result2 = threads.pop().unwrap().join().unwrap();
+    let result1 = threads.pop().unwrap().join().unwrap();
+    assert_eq!(result1, result2);
+    assert_eq!(result2, result3);
+    let ser_proof = serde_json::to_string(&result1).unwrap();
+    let der_proof = serde_json::from_str::<Groth16Proof<Bls12_377>>(&ser_proof).unwrap();
+    assert_eq!(der_proof, result1);
+    Groth16::<Bls12_377>::verify(&vk, &der_proof, &public_input).expect("can verify");
+    // the proof does not verify for a different public input
+    assert!(
+        Groth16::<Bls12_377>::verify(&vk, &der_proof, &[ark_bls12_377::Fr::from(34u64)]).is_err()
+    );
+}
+
+#[test]
+fn e2e_fold_multiplier2_bn254_rep3() {
+    let r1cs_file = File::open("../test_vectors/Groth16/bn254/multiplier2/circuit.r1cs").unwrap();
+    let witness_file =
+        File::open("../test_vectors/Groth16/bn254/multiplier2/witness.wtns").unwrap();
+    let r1cs = RelaxedR1CS::from_r1cs(&R1CS::<Bn254>::from_reader(r1cs_file).unwrap());
+    let witness = Witness::<ark_bn254::Fr>::from_reader(witness_file).unwrap();
+    let mut rng = thread_rng();
+    let ck = CommitmentKey::<ark_bn254::G1Projective>::setup(&r1cs, &mut rng);
+    let witness_shares = SharedWitness::share_rep3(witness, r1cs.num_public(), &mut rng);
+    let test_network = Rep3TestNetwork::default();
+    let mut threads = vec![];
+    for (net, x) in izip!(test_network.get_party_networks(), witness_shares) {
+        let r1cs = r1cs.clone();
+        let ck = ck.clone();
+        threads.push(thread::spawn(move || {
+            let mut io_context0 = IoContext::init(net).unwrap();
+            let io_context1 = io_context0.fork().unwrap();
+            let rep3 = Rep3Groth16Driver::new(io_context0, io_context1);
+            let mut folding = CoFolding::<Bn254, Rep3Groth16Driver<PartyTestNetwork>>::new(rep3);
+            let (instance1, witness1) = folding.export(&r1cs, &ck, x.clone()).unwrap();
+            let (instance2, witness2) = folding.export(&r1cs, &ck, x).unwrap();
+            let (folded, folded_witness) = folding
+                .fold(&r1cs, &ck, (&instance1, &witness1), (&instance2, &witness2))
+                .unwrap();
+            folding
+                .fold(
+                    &r1cs,
+                    &ck,
+                    (&folded, &folded_witness),
+                    (&instance1, &witness1),
+                )
+                .unwrap()
+        }));
+    }
+    let (instance3, witness3) = threads.pop().unwrap().join().unwrap();
+    let (instance2, witness2) = threads.pop().unwrap().join().unwrap();
+    let (instance1, witness1) = threads.pop().unwrap().join().unwrap();
+    // the folded instance is public, so all parties hold the same one
+    assert_eq!(instance1, instance2);
+    assert_eq!(instance2, instance3);
+    let combine = |f: fn(&RelaxedR1CSWitness<Rep3PrimeFieldShare<ark_bn254::Fr>>) -> Vec<_>| {
+        mpc_core::protocols::rep3::combine_field_elements(
+            &f(&witness1),
+            &f(&witness2),
+            &f(&witness3),
+        )
+    };
+    let mut witness = RelaxedR1CSWitness::new(
+        combine(|w| w.w.clone()),
+        combine(|w| w.e.clone()),
+        combine(|w| vec![w.r_w])[0],
+        combine(|w| vec![w.r_e])[0],
+    );
+    assert!(r1cs.is_satisfied(&ck, &instance1, &witness));
+    witness.e[0] += ark_bn254::Fr::one();
+    assert!(!r1cs.is_satisfied(&ck, &instance1, &witness));
+}
@@ -6,11 +6,13 @@ use circom_types::{
     plonk::{JsonVerificationKey as PlonkVK, PlonkProof, ZKey as PlonkZK},
     R1CS,
 };
-use mpc_core::protocols::shamir::{ShamirPreprocessing, ShamirProtocol};
+use mpc_core::protocols::shamir::{ShamirPreprocessing, ShamirPrimeFieldShare, ShamirProtocol};
+use num_bigint::BigUint;
 use std::sync::Arc;
 
 use circom_types::traits::CheckElement;
-use co_circom_snarks::SharedWitness;
+use co_circom_snarks::{mp_spdz, SharedWitness};
+use co_groth16::folding::{CoFolding, CommitmentKey, RelaxedR1CS, RelaxedR1CSWitness};
 use co_groth16::mpc::ShamirGroth16Driver;
 use co_groth16::CoGroth16;
 use co_groth16::Groth16;
@@ -117,3 +119,194 @@ macro_rules! add_test_impl {
 }
 e2e_test!("multiplier2");
 e2e_test!("poseidon");
+
+/// An MP-SPDZ file of Shamir shares over the scalar field of BN254 storing the shares 0 and 1
+fn mp_spdz_reference_file() -> Vec<u8> {
+    let mut file = Vec::new();
+    file.extend(51u64.to_le_bytes());
+    file.extend(10u32.to_be_bytes());
+    file.extend(b"Shamir gfp");
+    file.push(0);
+    file.extend(32u32.to_be_bytes());
+    file.extend(
+        BigUint::parse_bytes(
+            b"30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+            16,
+        )
+        .unwrap()
+        .to_bytes_be(),
+    );
+    // 0 in Montgomery form
+    file.extend([0u8; 32]);
+    // 1 in Montgomery form, i.e., 2^256 mod p
+    file.extend(
+        BigUint::parse_bytes(
+            b"0e0a77c19a07df2f666ea36f7879462e36fc76959f60cd29ac96341c4ffffffb",
+            16,
+        )
+        .unwrap()
+        .to_bytes_le(),
+    );
+    file
+}
+
+#[test]
+fn e2e_mp_spdz_interop_shamir() {
+    let reference = mp_spdz_reference_file();
+    let shares =
+        mp_spdz::read_shares::<ark_bn254::Fr, ShamirPrimeFieldShare<_>, _>(reference.as_slice())
+            .unwrap();
+    assert_eq!(
+        shares,
+        vec![
+            ShamirPrimeFieldShare::new(ark_bn254::Fr::from(0u64)),
+            ShamirPrimeFieldShare::new(ark_bn254::Fr::from(1u64))
+        ]
+    );
+    let mut written = Vec::new();
+    mp_spdz::write_shares::<ark_bn254::Fr, _, _>(&shares, &mut written).unwrap();
+    assert_eq!(written, reference);
+
+    // elements that are not reduced modulo the prime are rejected
+    let mut not_reduced = reference.clone();
+    let len = not_reduced.len();
+    not_reduced[len - 32..].fill(0xff);
+    assert!(
+        mp_spdz::read_shares::<ark_bn254::Fr, ShamirPrimeFieldShare<_>, _>(not_reduced.as_slice())
+            .is_err()
+    );
+
+    // a file of replicated shares is rejected
+    let mut replicated = reference.clone();
+    replicated[12..22].copy_from_slice(b"Rep3  gfp ");
+    assert!(
+        mp_spdz::read_shares::<ark_bn254::Fr, ShamirPrimeFieldShare<_>, _>(replicated.as_slice())
+            .is_err()
+    );
+
+    // round trip of the shares of a witness
+    let r1cs_file = File::open("../test_vectors/Groth16/bn254/poseidon/circuit.r1cs").unwrap();
+    let r1cs = R1CS::<Bn254>::from_reader(r1cs_file).unwrap();
+    let witness_file = File::open("../test_vectors/Groth16/bn254/poseidon/witness.wtns").unwrap();
+    let witness = Witness::<ark_bn254::Fr>::from_reader(witness_file).unwrap();
+    let mut rng = thread_rng();
+    let shares = SharedWitness::share_shamir(witness, r1cs.num_inputs, 1, 3, &mut rng);
+    for share in shares {
+        let mut file = Vec::new();
+        share.to_mp_spdz(&mut file).unwrap();
+        let imported = SharedWitness::<_, ShamirPrimeFieldShare<_>>::from_mp_spdz(
+            file.as_slice(),
+            share.public_inputs.clone(),
+        )
+        .unwrap();
+        assert_eq!(imported.witness, share.witness);
+    }
+}
+
+#[test]
+fn e2e_mp_spdz_reference_files_shamir() {
+    let files = (0..3)
+        .map(|i| {
+            std::fs::read(format!(
+                "../test_vectors/MP-SPDZ/shamir-party/Transactions-P{i}.data"
+            ))
+            .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let shares = files
+        .iter()
+        .map(|file| {
+            mp_spdz::read_shares::<ark_bn254::Fr, ShamirPrimeFieldShare<_>, _>(file.as_slice())
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    // the files store the secrets 1, 2, 42 and p - 1, see the README of the test vectors
+    let combined =
+        mpc_core::protocols::shamir::combine_field_elements(&shares, &[1, 2, 3], 1).unwrap();
+    assert_eq!(
+        combined,
+        [
+            ark_bn254::Fr::from(1u64),
+            ark_bn254::Fr::from(2u64),
+            ark_bn254::Fr::from(42u64),
+            -ark_bn254::Fr::from(1u64)
+        ]
+    );
+
+    // exporting the shares again yields the same files
+    for (share, file) in izip!(shares.iter(), files.iter()) {
+        let mut written = Vec::new();
+        mp_spdz::write_shares::<ark_bn254::Fr, _, _>(share, &mut written).unwrap();
+        assert_eq!(&written, file);
+    }
+}
+
+#[test]
+fn e2e_fold_multiplier2_bn254_shamir() {
+    let num_parties = 3;
+    let threshold = 1;
+    let r1cs_file = File::open("../test_vectors/Groth16/bn254/multiplier2/circuit.r1cs").unwrap();
+    let witness_file =
+        File::open("../test_vectors/Groth16/bn254/multiplier2/witness.wtns").unwrap();
+    let r1cs = RelaxedR1CS::from_r1cs(&R1CS::<Bn254>::from_reader(r1cs_file).unwrap());
+    let witness = Witness::<ark_bn254::Fr>::from_reader(witness_file).unwrap();
+    let mut rng = thread_rng();
+    let ck = CommitmentKey::<ark_bn254::G1Projective>::setup(&r1cs, &mut rng);
+    let witness_shares =
+        SharedWitness::share_shamir(witness, r1cs.num_public(), threshold, num_parties, &mut rng);
+    // one random share per export, and per fold the products of the cross term and one random share
+    let num_pairs = 2 + 2 * (2 * r1cs.num_constraints() + 1);
+    let test_network = ShamirTestNetwork::new(num_parties);
+    let mut threads = vec![];
+    for (net, x) in izip!(test_network.get_party_networks(), witness_shares) {
+        let r1cs = r1cs.clone();
+        let ck = ck.clone();
+        threads.push(thread::spawn(move || {
+            let preprocessing = ShamirPreprocessing::new(threshold, net, num_pairs).unwrap();
+            let mut io_context0 = ShamirProtocol::from(preprocessing);
+            let io_context1 = io_context0.fork_with_pairs(0).unwrap();
+            let shamir = ShamirGroth16Driver::new(io_context0, io_context1);
+            let mut folding = CoFolding::<
+                Bn254,
+                ShamirGroth16Driver<ark_bn254::Fr, PartyTestNetwork>,
+            >::new(shamir);
+            let (instance1, witness1) = folding.export(&r1cs, &ck, x.clone()).unwrap();
+            let (instance2, witness2) = folding.export(&r1cs, &ck, x).unwrap();
+            let (folded, folded_witness) = folding
+                .fold(&r1cs, &ck, (&instance1, &witness1), (&instance2, &witness2))
+                .unwrap();
+            folding
+                .fold(
+                    &r1cs,
+                    &ck,
+                    (&folded, &folded_witness),
+                    (&instance1, &witness1),
+                )
+                .unwrap()
+        }));
+    }
+    let results = threads
+        .into_iter()
+        .map(|thread| thread.join().unwrap())
+        .collect::<Vec<_>>();
+    // the folded instance is public, so all parties hold the same one
+    let instance = results[0].0.clone();
+    for (other, _) in results.iter() {
+        assert_eq!(other, &instance);
+    }
+    let parties = (1..=num_parties).collect::<Vec<_>>();
+    let combine = |f: fn(&RelaxedR1CSWitness<ShamirPrimeFieldShare<ark_bn254::Fr>>) -> Vec<_>| {
+        let shares = results.iter().map(|(_, w)| f(w)).collect::<Vec<_>>();
+        mpc_core::protocols::shamir::combine_field_elements(&shares, &parties, threshold).unwrap()
+    };
+    let mut witness = RelaxedR1CSWitness::new(
+        combine(|w| w.w.clone()),
+        combine(|w| w.e.clone()),
+        combine(|w| vec![w.r_w])[0],
+        combine(|w| vec![w.r_e])[0],
+    );
+    assert!(r1cs.is_satisfied(&ck, &instance, &witness));
+    witness.w[0] += ark_bn254::Fr::from(1u64);
+    assert!(!r1cs.is_satisfied(&ck, &instance, &witness));
+}
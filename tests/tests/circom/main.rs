@@ -1,4 +1,8 @@
 #[cfg(test)]
+mod commitment;
+#[cfg(test)]
 mod e2e_tests;
 #[cfg(test)]
+mod signing;
+#[cfg(test)]
 mod witness_extension_tests;
@@ -0,0 +1,118 @@
+use std::thread;
+
+use co_circom::signing::{self, Committee, CommitteeMember, SigningKeyPair};
+use color_eyre::eyre;
+use rand::thread_rng;
+use tests::{rep3_network::Rep3TestNetwork, shamir_network::ShamirTestNetwork};
+
+fn keys(num_parties: usize) -> Vec<SigningKeyPair> {
+    let mut rng = thread_rng();
+    (0..num_parties)
+        .map(|_| SigningKeyPair::generate(&mut rng))
+        .collect()
+}
+
+fn committee(keys: &[SigningKeyPair]) -> Committee {
+    Committee {
+        members: keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| CommitteeMember {
+                name: format!("party{i}"),
+                public_key: key.public_key(),
+            })
+            .collect(),
+    }
+}
+
+// the test network panics if a party sends to or receives from a party that already failed
+fn prove_possession_rep3(
+    committees: [Committee; 3],
+    keys: Vec<SigningKeyPair>,
+) -> Vec<thread::Result<eyre::Result<Vec<u8>>>> {
+    let threads = Rep3TestNetwork::default()
+        .get_party_networks()
+        .into_iter()
+        .zip(committees)
+        .zip(keys)
+        .map(|((mut net, committee), key)| {
+            thread::spawn(move || signing::prove_possession_rep3(&committee, &key, &mut net))
+        })
+        .collect::<Vec<_>>();
+    threads.into_iter().map(|thread| thread.join()).collect()
+}
+
+#[test]
+fn rep3_proof_of_possession() {
+    let keys = keys(3);
+    let committee = committee(&keys);
+    let session_ids =
+        prove_possession_rep3([committee.clone(), committee.clone(), committee], keys)
+            .into_iter()
+            .map(|result| result.unwrap().unwrap())
+            .collect::<Vec<_>>();
+    assert_eq!(session_ids[0], session_ids[1]);
+    assert_eq!(session_ids[0], session_ids[2]);
+}
+
+#[test]
+fn rep3_proof_of_possession_fails_for_wrong_member() {
+    let keys = keys(3);
+    let committee = committee(&keys);
+    // party 0 expects another key for party 1, e.g., because its network config points at another party
+    let mut wrong_committee = committee.clone();
+    wrong_committee.members[1].public_key =
+        SigningKeyPair::generate(&mut thread_rng()).public_key();
+    let results = prove_possession_rep3([wrong_committee, committee.clone(), committee], keys);
+    let err = results[0]
+        .as_ref()
+        .unwrap()
+        .as_ref()
+        .expect_err("party 1 is not the expected member");
+    assert!(err
+        .to_string()
+        .contains("party 1 could not prove possession"));
+    assert!(matches!(results[1], Ok(Ok(_))));
+    assert!(matches!(results[2], Ok(Ok(_))));
+}
+
+#[test]
+fn rep3_proof_of_possession_fails_for_swapped_keys() {
+    let mut keys = keys(3);
+    let committee = committee(&keys);
+    keys.swap(1, 2);
+    let results = prove_possession_rep3([committee.clone(), committee.clone(), committee], keys);
+    // party 1 and 2 notice that their keys do not belong to their members, party 0 does not get their proofs
+    assert!(results.iter().all(|result| !matches!(result, Ok(Ok(_)))));
+    assert!(matches!(results[1], Ok(Err(_))));
+    assert!(matches!(results[2], Ok(Err(_))));
+}
+
+#[test]
+fn shamir_proof_of_possession() {
+    let num_parties = 5;
+    let keys = keys(num_parties);
+    let committee = committee(&keys);
+    let threads = ShamirTestNetwork::new(num_parties)
+        .get_party_networks()
+        .into_iter()
+        .zip(keys)
+        .map(|(mut net, key)| {
+            let committee = committee.clone();
+            thread::spawn(move || signing::prove_possession_shamir(&committee, &key, &mut net))
+        })
+        .collect::<Vec<_>>();
+    let session_ids = threads
+        .into_iter()
+        .map(|thread| thread.join().unwrap().unwrap())
+        .collect::<Vec<_>>();
+    assert!(session_ids.iter().all(|id| *id == session_ids[0]));
+}
+
+#[test]
+fn shamir_proof_of_possession_needs_member_per_party() {
+    let keys = keys(3);
+    let committee = committee(&keys[..2]);
+    let mut net = ShamirTestNetwork::new(3).get_party_networks().remove(0);
+    assert!(signing::prove_possession_shamir(&committee, &keys[0], &mut net).is_err());
+}
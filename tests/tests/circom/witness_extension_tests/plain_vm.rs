@@ -1,9 +1,13 @@
 use ark_bn254::Bn254;
 use circom_mpc_compiler::CoCircomCompiler;
 use circom_mpc_compiler::CompilerConfig;
+use circom_mpc_vm::accelerator::{ComponentAcceleratorOutput, MpcAccelerator};
+use circom_mpc_vm::compiled_circuit::TemplateHash;
+use circom_mpc_vm::mpc::VmCircomWitnessExtension;
 use circom_mpc_vm::mpc_vm::VMConfig;
+use circom_mpc_vm::CircomPlainVmWitnessExtension;
 use circom_types::Witness;
-use co_circom_snarks::SharedWitness;
+use co_circom_snarks::{SharedInput, SharedWitness};
 use std::{
     fs::{self, File},
     str::FromStr,
@@ -173,3 +177,85 @@ witness_extension_test_plain!(bitonic_sort);
 witness_extension_test_plain!(num2bits_accelerator);
 witness_extension_test_plain!(reclaim_addbits_accelerator);
 witness_extension_test_plain!(reclaim_addbits_accelerator_small);
+
+fn parse_multiplier2() -> circom_mpc_vm::types::CoCircomCompilerParsed<ark_bn254::Fr> {
+    CoCircomCompiler::<Bn254>::parse(
+        "../test_vectors/WitnessExtension/tests/multiplier2.circom",
+        CompilerConfig::default(),
+    )
+    .unwrap()
+}
+
+fn multiplier2_accelerator(
+    name: &str,
+    hash: TemplateHash,
+    offset: ark_bn254::Fr,
+) -> MpcAccelerator<ark_bn254::Fr, CircomPlainVmWitnessExtension<ark_bn254::Fr>> {
+    let mut accelerator =
+        MpcAccelerator::<ark_bn254::Fr, CircomPlainVmWitnessExtension<ark_bn254::Fr>>::empty();
+    accelerator.register_template(name.to_owned(), hash, move |protocol, args, _| {
+        let product = protocol.mul(args[0], args[1])?;
+        Ok(ComponentAcceleratorOutput {
+            output: vec![protocol.add(product, offset)?],
+            intermediate: vec![],
+        })
+    });
+    accelerator
+}
+
+#[test]
+fn template_accelerator() {
+    let inputs = vec![ark_bn254::Fr::from(3), ark_bn254::Fr::from(11)];
+    let hashes = parse_multiplier2().template_hashes("Multiplier2").unwrap();
+    assert_eq!(hashes.len(), 1);
+    let run = |accelerator| {
+        let witness = parse_multiplier2()
+            .to_plain_vm_with_accelerator(VMConfig::default(), accelerator)
+            .unwrap()
+            .run_with_flat(inputs.clone(), 0)
+            .unwrap();
+        witness.get_output("c").unwrap()
+    };
+    // the accelerator replaces the bytecode of the matching instantiation
+    let offset = ark_bn254::Fr::from(1);
+    assert_eq!(
+        run(multiplier2_accelerator("Multiplier2", hashes[0], offset)),
+        vec![ark_bn254::Fr::from(34)]
+    );
+    // a different hash or name falls back to interpreting the bytecode
+    assert_eq!(
+        run(multiplier2_accelerator("Multiplier2", [0; 32], offset)),
+        vec![ark_bn254::Fr::from(33)]
+    );
+    assert_eq!(
+        run(multiplier2_accelerator("Multiplier3", hashes[0], offset)),
+        vec![ark_bn254::Fr::from(33)]
+    );
+}
+
+#[test]
+fn declared_bit_widths_are_enforced() {
+    let run = |a: u64, bit_widths: &[(&str, usize)]| {
+        let mut input = SharedInput::<ark_bn254::Fr, ark_bn254::Fr>::default();
+        input.add_shared_input("a".to_owned(), vec![ark_bn254::Fr::from(a)]);
+        input.add_public_input("b".to_owned(), vec![ark_bn254::Fr::from(11)]);
+        for (name, bits) in bit_widths {
+            input.add_bit_width(name.to_string(), *bits);
+        }
+        parse_multiplier2()
+            .to_plain_vm(VMConfig::default())
+            .run(input)
+            .is_ok()
+    };
+    assert!(run(255, &[]));
+    assert!(run(255, &[("a", 8)]));
+    assert!(run(0, &[("a", 0)]));
+    assert!(run(255, &[("a", 254)]));
+    // 256 needs 9 bits
+    assert!(!run(256, &[("a", 8)]));
+    assert!(!run(1, &[("a", 0)]));
+    assert!(!run(1, &[("a", 255)]));
+    // b is public and c is not an input
+    assert!(!run(1, &[("b", 8)]));
+    assert!(!run(1, &[("c", 8)]));
+}
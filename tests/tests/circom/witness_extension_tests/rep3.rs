@@ -1,7 +1,7 @@
 use ark_bn254::Bn254;
 use circom_mpc_compiler::CoCircomCompiler;
 use circom_types::Witness;
-use co_circom_snarks::SharedWitness;
+use co_circom_snarks::{SharedInput, SharedWitness};
 use itertools::izip;
 use mpc_core::protocols::rep3::{self};
 use rand::thread_rng;
@@ -103,7 +103,10 @@ pub fn from_test_name(fn_name: &str) -> TestInputs {
 }
 
 macro_rules! run_test {
-    ($file: expr, $input: expr) => {{
+    ($file: expr, $input: expr) => {
+        run_test!($file, $input, VMConfig::default())
+    };
+    ($file: expr, $input: expr, $vm_config: expr) => {{
         //install_tracing();
         let mut rng = thread_rng();
         let inputs = rep3::share_field_elements($input, &mut rng);
@@ -111,6 +114,7 @@ macro_rules! run_test {
         let mut threads = vec![];
 
         for (net, input) in izip!(test_network.get_party_networks(), inputs) {
+            let vm_config = $vm_config;
             threads.push(thread::spawn(move || {
                 let mut compiler_config = CompilerConfig::default();
                 compiler_config.simplification =
@@ -121,7 +125,7 @@ macro_rules! run_test {
                 let witness_extension =
                     CoCircomCompiler::<Bn254>::parse($file.to_owned(), compiler_config)
                         .unwrap()
-                        .to_rep3_vm_with_network(net, VMConfig::default())
+                        .to_rep3_vm_with_network(net, vm_config)
                         .unwrap();
                 witness_extension
                     .run_with_flat(
@@ -261,3 +265,85 @@ witness_extension_test_rep3!(winner);
 witness_extension_test_rep3!(bitonic_sort);
 witness_extension_test_rep3!(num2bits_accelerator);
 witness_extension_test_rep3!(reclaim_addbits_accelerator);
+
+#[test]
+fn circuit_hash_mismatch_aborts() {
+    let test_network = Rep3TestNetwork::default();
+    let circuits = ["sum_test", "sum_test", "winner"];
+    let mut threads = vec![];
+    for (net, circuit) in izip!(test_network.get_party_networks(), circuits) {
+        threads.push(thread::spawn(move || {
+            let mut compiler_config = CompilerConfig::default();
+            compiler_config
+                .link_library
+                .push("../test_vectors/WitnessExtension/tests/libs/".into());
+            CoCircomCompiler::<Bn254>::parse(
+                format!("../test_vectors/WitnessExtension/tests/{circuit}.circom"),
+                compiler_config,
+            )
+            .unwrap()
+            .to_rep3_vm_with_network(net, VMConfig::default())
+            .is_err()
+        }));
+    }
+    for thread in threads {
+        assert!(thread.join().unwrap());
+    }
+}
+
+#[test]
+fn parallel_components() {
+    let vm_config = VMConfig {
+        max_parallel_sessions: 4,
+        ..Default::default()
+    };
+    for name in ["bitonic_sort", "poseidon3_test", "winner"] {
+        let inp = from_test_name(name);
+        for (input, should_witness) in inp.inputs.iter().zip(inp.witnesses.iter()) {
+            let is_witness = run_test!(
+                format!("../test_vectors/WitnessExtension/tests/{name}.circom"),
+                input,
+                vm_config.clone()
+            );
+            assert_eq!(is_witness, should_witness.values);
+        }
+    }
+}
+
+#[test]
+fn declared_bit_widths_are_enforced() {
+    let run = |a: u64, bits: usize| {
+        let mut rng = thread_rng();
+        let shares = rep3::share_field_elements(&[ark_bn254::Fr::from(a)], &mut rng);
+        let test_network = Rep3TestNetwork::default();
+        let mut threads = vec![];
+        for (net, share) in izip!(test_network.get_party_networks(), shares) {
+            threads.push(thread::spawn(move || {
+                let mut input = SharedInput::default();
+                input.add_shared_input("a".to_owned(), share);
+                input.add_public_input("b".to_owned(), vec![ark_bn254::Fr::from(11)]);
+                input.add_bit_width("a".to_owned(), bits);
+                CoCircomCompiler::<Bn254>::parse(
+                    "../test_vectors/WitnessExtension/tests/multiplier2.circom",
+                    CompilerConfig::default(),
+                )
+                .unwrap()
+                .to_rep3_vm_with_network(net, VMConfig::default())
+                .unwrap()
+                .run(input)
+                .is_ok()
+            }));
+        }
+        let results = threads
+            .into_iter()
+            .map(|thread| thread.join().unwrap())
+            .collect::<Vec<_>>();
+        // all parties learn whether the check failed
+        assert!(results.iter().all(|result| *result == results[0]));
+        results[0]
+    };
+    assert!(run(255, 8));
+    assert!(run(0, 0));
+    assert!(!run(256, 8));
+    assert!(!run(1, 0));
+}
@@ -3,6 +3,10 @@ mod bridges;
 #[cfg(test)]
 mod rep3;
 #[cfg(test)]
+mod rep3_async_network;
+#[cfg(test)]
 mod rep3_ring;
 #[cfg(test)]
 mod shamir;
+#[cfg(test)]
+mod shamir_network;
@@ -5,17 +5,20 @@ mod field_share {
     use ark_std::{UniformRand, Zero};
     use itertools::izip;
     use itertools::Itertools;
+    use mpc_core::gadgets::poseidon2::POSEIDON2_BN254_T4_PARAMS;
+    use mpc_core::nonnative::{NonNativeField, NonNativeShare, LIMB_BITS};
     use mpc_core::protocols::rep3::conversion;
     use mpc_core::protocols::rep3::gadgets;
     use mpc_core::protocols::rep3::id::PartyID;
     use mpc_core::protocols::rep3::yao;
+    use mpc_core::protocols::rep3::yao::bristol::BristolFashionCircuit;
     use mpc_core::protocols::rep3::yao::circuits::GarbledCircuits;
     use mpc_core::protocols::rep3::yao::evaluator::Rep3Evaluator;
     use mpc_core::protocols::rep3::yao::garbler::Rep3Garbler;
     use mpc_core::protocols::rep3::yao::streaming_evaluator::StreamingRep3Evaluator;
     use mpc_core::protocols::rep3::yao::streaming_garbler::StreamingRep3Garbler;
     use mpc_core::protocols::rep3::yao::GCUtils;
-    use mpc_core::protocols::rep3::{self, arithmetic, network::IoContext};
+    use mpc_core::protocols::rep3::{self, arithmetic, binary::batched, network::IoContext};
     use mpc_core::protocols::rep3_ring;
     use num_bigint::BigUint;
     use rand::thread_rng;
@@ -69,6 +72,27 @@ mod field_share {
         assert_eq!(is_result, should_result);
     }
 
+    #[test]
+    fn rep3_local_ops() {
+        let mut rng = thread_rng();
+        let x = (0..10).map(|_| ark_bn254::Fr::rand(&mut rng)).collect_vec();
+        let y = ark_bn254::Fr::rand(&mut rng);
+        let c = ark_bn254::Fr::rand(&mut rng);
+        let x_shares = rep3::share_field_elements(&x, &mut rng);
+        let y_shares = rep3::share_field_element(y, &mut rng);
+        let should_result = (x.iter().sum::<ark_bn254::Fr>() - y) * c + y - x[0];
+        let mut results = vec![];
+        for (x, y) in izip!(x_shares.into_iter(), y_shares.into_iter()) {
+            let mut acc: rep3::Rep3PrimeFieldShare<_> = x.iter().sum();
+            acc -= &y;
+            acc *= &c;
+            acc += y;
+            results.push(acc + &(-&x[0]));
+        }
+        let is_result = rep3::combine_field_element(results[0], results[1], results[2]);
+        assert_eq!(is_result, should_result);
+    }
+
     #[test]
     fn rep3_sub_shared_by_public() {
         let mut rng = thread_rng();
@@ -148,6 +172,47 @@ mod field_share {
         assert_eq!(is_result, should_result);
     }
 
+    #[test]
+    fn rep3_snapshot_restore() {
+        let test_network = Rep3TestNetwork::default();
+        let restore_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let x = ark_bn254::Fr::rand(&mut rng);
+        let y = ark_bn254::Fr::rand(&mut rng);
+        let x_shares = rep3::share_field_element(x, &mut rng);
+        let y_shares = rep3::share_field_element(y, &mut rng);
+        let should_result = x * y;
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+        for (net, restore_net, tx, x, y) in izip!(
+            test_network.get_party_networks().into_iter(),
+            restore_network.get_party_networks().into_iter(),
+            [tx1, tx2, tx3],
+            x_shares.into_iter(),
+            y_shares.into_iter()
+        ) {
+            thread::spawn(move || {
+                let mut ctx = IoContext::init(net).unwrap();
+                let snapshot = serde_json::to_vec(&ctx.snapshot()).unwrap();
+                let mul = arithmetic::mul(x, y, &mut ctx).unwrap();
+                let snapshot = serde_json::from_slice(&snapshot).unwrap();
+                let mut restored = IoContext::restore(snapshot, restore_net).unwrap();
+                let restored_mul = arithmetic::mul(x, y, &mut restored).unwrap();
+                tx.send((mul, restored_mul))
+            });
+        }
+        let (result1, restored1) = rx1.recv().unwrap();
+        let (result2, restored2) = rx2.recv().unwrap();
+        let (result3, restored3) = rx3.recv().unwrap();
+        // the restored context uses the same randomness
+        assert_eq!(result1, restored1);
+        assert_eq!(result2, restored2);
+        assert_eq!(result3, restored3);
+        let is_result = rep3::combine_field_element(result1, result2, result3);
+        assert_eq!(is_result, should_result);
+    }
+
     #[test]
     fn rep3_div() {
         let test_network = Rep3TestNetwork::default();
@@ -375,6 +440,109 @@ mod field_share {
         assert_eq!(is_result, should_result);
     }
 
+    #[test]
+    fn rep3_local_mul_sub_vec() {
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let [x, y, z, w] = [(); 4].map(|_| {
+            (0..10)
+                .map(|_| ark_bn254::Fr::rand(&mut rng))
+                .collect::<Vec<_>>()
+        });
+        let should_result = izip!(&x, &y, &z, &w)
+            .map(|(x, y, z, w)| z * w - x * y)
+            .collect::<Vec<_>>();
+        let x_shares = rep3::share_field_elements(&x, &mut rng);
+        let y_shares = rep3::share_field_elements(&y, &mut rng);
+        let z_shares = rep3::share_field_elements(&z, &mut rng);
+        let w_shares = rep3::share_field_elements(&w, &mut rng);
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+
+        for (net, tx, x, y, z, w) in izip!(
+            test_network.get_party_networks(),
+            [tx1, tx2, tx3],
+            x_shares.into_iter(),
+            y_shares.into_iter(),
+            z_shares.into_iter(),
+            w_shares.into_iter()
+        ) {
+            thread::spawn(move || {
+                let mut rep3 = IoContext::init(net).unwrap();
+                let mut c = arithmetic::local_mul_vec(&x, &y, &mut rep3.rngs);
+                arithmetic::local_mul_sub_vec(&z, &w, &mut c, &mut rep3.rngs);
+                tx.send(arithmetic::io_mul_vec(c, &mut rep3).unwrap())
+            });
+        }
+        let result1 = rx1.recv().unwrap();
+        let result2 = rx2.recv().unwrap();
+        let result3 = rx3.recv().unwrap();
+        let is_result = rep3::combine_field_elements(&result1, &result2, &result3);
+        assert_eq!(is_result, should_result);
+    }
+
+    #[test]
+    fn rep3_interpolate_and_eval_poly() {
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let coeffs = (0..10)
+            .map(|_| ark_bn254::Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let points = (0..coeffs.len())
+            .map(|_| ark_bn254::Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let shared_points = (0..3)
+            .map(|_| ark_bn254::Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let eval = |x: &ark_bn254::Fr| {
+            coeffs
+                .iter()
+                .rev()
+                .fold(ark_bn254::Fr::zero(), |acc, c| acc * x + c)
+        };
+        let evals = points.iter().map(eval).collect::<Vec<_>>();
+        let should_result = shared_points.iter().map(eval).collect::<Vec<_>>();
+        let evals_shares = rep3::share_field_elements(&evals, &mut rng);
+        let point_shares = rep3::share_field_elements(&shared_points, &mut rng);
+
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+        for (net, tx, evals, shared_points) in izip!(
+            test_network.get_party_networks(),
+            [tx1, tx2, tx3],
+            evals_shares.into_iter(),
+            point_shares.into_iter()
+        ) {
+            let points = points.clone();
+            thread::spawn(move || {
+                let mut rep3 = IoContext::init(net).unwrap();
+                let coeffs = rep3::poly::interpolate_poly(&points, &evals);
+                let evals_again = rep3::poly::eval_poly_multi(&coeffs, &points);
+                let result =
+                    rep3::poly::eval_poly_at_shared_points(&coeffs, &shared_points, &mut rep3)
+                        .unwrap();
+                tx.send((coeffs, evals_again, result))
+            });
+        }
+        let (coeffs1, evals1, result1) = rx1.recv().unwrap();
+        let (coeffs2, evals2, result2) = rx2.recv().unwrap();
+        let (coeffs3, evals3, result3) = rx3.recv().unwrap();
+        assert_eq!(
+            rep3::combine_field_elements(&coeffs1, &coeffs2, &coeffs3),
+            coeffs
+        );
+        assert_eq!(
+            rep3::combine_field_elements(&evals1, &evals2, &evals3),
+            evals
+        );
+        assert_eq!(
+            rep3::combine_field_elements(&result1, &result2, &result3),
+            should_result
+        );
+    }
+
     #[test]
     fn rep3_neg() {
         let mut rng = thread_rng();
@@ -526,6 +694,44 @@ mod field_share {
         assert_eq!(is_result, should_result);
     }
 
+    #[test]
+    fn rep3_bit_inject_keeps_rngs_in_sync() {
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let x = ark_bn254::Fr::from(rng.gen::<bool>() as u64);
+        let y = ark_bn254::Fr::rand(&mut rng);
+        let mut x_shares = rep3::share_biguint(x, &mut rng);
+        // Simulate sharing of just one bit
+        for x in x_shares.iter_mut() {
+            x.a &= BigUint::one();
+            x.b &= BigUint::one();
+        }
+        let y_shares = rep3::share_field_element(y, &mut rng);
+
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+        for (((net, tx), x), y) in test_network
+            .get_party_networks()
+            .into_iter()
+            .zip([tx1, tx2, tx3])
+            .zip(x_shares.into_iter())
+            .zip(y_shares.into_iter())
+        {
+            thread::spawn(move || {
+                let mut rep3 = IoContext::init(net).unwrap();
+                let x = conversion::bit_inject(&x, &mut rep3).unwrap();
+                // the multiplication only succeeds if the parties consumed the same randomness
+                tx.send(arithmetic::mul(x, y, &mut rep3).unwrap())
+            });
+        }
+        let result1 = rx1.recv().unwrap();
+        let result2 = rx2.recv().unwrap();
+        let result3 = rx3.recv().unwrap();
+        let is_result = rep3::combine_field_element(result1, result2, result3);
+        assert_eq!(is_result, x * y);
+    }
+
     use arithmetic::ge_public;
     use arithmetic::gt_public;
     use arithmetic::le_public;
@@ -795,6 +1001,74 @@ mod field_share {
         assert_eq!(is_result, x);
     }
 
+    #[test]
+    fn rep3_binary_batched() {
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let x = (0..8).map(|_| rng.gen::<u32>()).collect_vec();
+        let y = (0..8).map(|_| rng.gen::<u32>()).collect_vec();
+        let mask = 0x0f0f_f0f0u32;
+        let constant = 0x1234_5678u32;
+        let should_result = izip!(&x, &y)
+            .map(|(x, y)| {
+                let z = (x & y).rotate_left(7) ^ (x.rotate_right(3) & mask);
+                let z = (z | constant) ^ (y << 5) ^ (y >> 11);
+                BigUint::from(z)
+            })
+            .collect_vec();
+
+        let mut x_shares = [Vec::new(), Vec::new(), Vec::new()];
+        let mut y_shares = [Vec::new(), Vec::new(), Vec::new()];
+        for (x, y) in izip!(&x, &y) {
+            for (i, share) in rep3::share_biguint(ark_bn254::Fr::from(*x), &mut rng)
+                .into_iter()
+                .enumerate()
+            {
+                x_shares[i].push(share);
+            }
+            for (i, share) in rep3::share_biguint(ark_bn254::Fr::from(*y), &mut rng)
+                .into_iter()
+                .enumerate()
+            {
+                y_shares[i].push(share);
+            }
+        }
+
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+        for (net, tx, mut x, y) in izip!(
+            test_network.get_party_networks(),
+            [tx1, tx2, tx3],
+            x_shares,
+            y_shares
+        ) {
+            thread::spawn(move || {
+                let mut rep3 = IoContext::init(net).unwrap();
+                let id = rep3.id;
+                let mut z = batched::and(&x, &y, &mut rep3).unwrap();
+                batched::rotate_l(&mut z, 7, 32);
+                batched::rotate_r(&mut x, 3, 32);
+                batched::and_with_public(&mut x, &BigUint::from(mask));
+                batched::xor_assign(&mut z, &x);
+                batched::or_public(&mut z, &BigUint::from(constant), id);
+                let mut y_l = y.clone();
+                batched::shift_l(&mut y_l, 5, 32);
+                let mut y_r = y;
+                batched::shift_r(&mut y_r, 11);
+                batched::xor_assign(&mut z, &y_l);
+                batched::xor_assign(&mut z, &y_r);
+                tx.send(batched::open(&z, &mut rep3).unwrap())
+            });
+        }
+        let result1 = rx1.recv().unwrap();
+        let result2 = rx2.recv().unwrap();
+        let result3 = rx3.recv().unwrap();
+        assert_eq!(result1, should_result);
+        assert_eq!(result2, should_result);
+        assert_eq!(result3, should_result);
+    }
+
     #[test]
     fn rep3_b2y2a() {
         let test_network = Rep3TestNetwork::default();
@@ -920,6 +1194,122 @@ mod field_share {
         assert_eq!(result3, should_result);
     }
 
+    #[test]
+    fn rep3_gc_from_seed_is_deterministic() {
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let x = ark_bn254::Fr::rand(&mut rng);
+        let y = ark_bn254::Fr::rand(&mut rng);
+        let seed: [u8; 32] = rng.gen();
+        let other_seed: [u8; 32] = rng.gen();
+        let (tx, rx) = mpsc::channel();
+
+        let [net1, net2, net3] = test_network.get_party_networks();
+
+        // Only the garbler ID1 buffers the garbled circuit, the other parties only set up the context
+        for net in [net1, net3] {
+            thread::spawn(move || IoContext::init(net).unwrap());
+        }
+        thread::spawn(move || {
+            let mut ctx = IoContext::init(net2).unwrap();
+            let mut garble = |seed| {
+                let mut garbler = Rep3Garbler::new_from_seed(&mut ctx, seed);
+                let x_ = garbler.encode_field(x);
+                let y_ = garbler.encode_field(y);
+                garbler.add_bundle_to_circuit(&x_.evaluator_wires);
+                garbler.add_bundle_to_circuit(&y_.evaluator_wires);
+                GarbledCircuits::adder_mod_p::<_, ark_bn254::Fr>(
+                    &mut garbler,
+                    &x_.garbler_wires,
+                    &y_.garbler_wires,
+                )
+                .unwrap();
+                garbler.garbled_circuit().to_vec()
+            };
+            let first = garble(seed);
+            let second = garble(seed);
+            let other = garble(other_seed);
+            tx.send((first, second, other))
+        });
+
+        let (first, second, other) = rx.recv().unwrap();
+        assert!(!first.is_empty());
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn rep3_gc_output_to_party() {
+        let mut rng = thread_rng();
+        let x = ark_bn254::Fr::rand(&mut rng);
+        let y = ark_bn254::Fr::rand(&mut rng);
+        let should_result = x + y;
+
+        for target in [PartyID::ID0, PartyID::ID1, PartyID::ID2] {
+            let test_network = Rep3TestNetwork::default();
+            let [net1, net2, net3] = test_network.get_party_networks();
+
+            // Both Garblers
+            let garblers = [net2, net3].map(|net| {
+                thread::spawn(move || {
+                    let mut ctx = IoContext::init(net).unwrap();
+
+                    let mut garbler = Rep3Garbler::new(&mut ctx);
+                    let x_ = garbler.encode_field(x);
+                    let y_ = garbler.encode_field(y);
+
+                    // This is without OT, just a simulation
+                    garbler.add_bundle_to_circuit(&x_.evaluator_wires);
+                    garbler.add_bundle_to_circuit(&y_.evaluator_wires);
+
+                    let circuit_output = GarbledCircuits::adder_mod_p::<_, ark_bn254::Fr>(
+                        &mut garbler,
+                        &x_.garbler_wires,
+                        &y_.garbler_wires,
+                    )
+                    .unwrap();
+
+                    garbler
+                        .output_to_party(circuit_output.wires(), target)
+                        .unwrap()
+                        .map(|output| GCUtils::bits_to_field::<ark_bn254::Fr>(&output).unwrap())
+                })
+            });
+
+            // The evaluator (ID0)
+            let evaluator = thread::spawn(move || {
+                let mut ctx = IoContext::init(net1).unwrap();
+
+                let mut evaluator = Rep3Evaluator::new(&mut ctx);
+                let n_bits = ark_bn254::Fr::MODULUS_BIT_SIZE as usize;
+
+                // This is without OT, just a simulation
+                evaluator.receive_circuit().unwrap();
+                let x_ = evaluator.receive_bundle_from_circuit(n_bits).unwrap();
+                let y_ = evaluator.receive_bundle_from_circuit(n_bits).unwrap();
+
+                let circuit_output =
+                    GarbledCircuits::adder_mod_p::<_, ark_bn254::Fr>(&mut evaluator, &x_, &y_)
+                        .unwrap();
+
+                evaluator
+                    .output_to_party(circuit_output.wires(), target)
+                    .unwrap()
+                    .map(|output| GCUtils::bits_to_field::<ark_bn254::Fr>(&output).unwrap())
+            });
+
+            let [result2, result3] = garblers.map(|t| t.join().unwrap());
+            let results = [evaluator.join().unwrap(), result2, result3];
+            for (id, result) in results.into_iter().enumerate() {
+                if id == usize::from(target) {
+                    assert_eq!(result, Some(should_result));
+                } else {
+                    assert_eq!(result, None);
+                }
+            }
+        }
+    }
+
     #[test]
     fn rep3_gc_streaming() {
         let test_network = Rep3TestNetwork::default();
@@ -1488,41 +1878,605 @@ mod field_share {
         let is_result = rep3::combine_field_elements(&result1, &result2, &result3);
         assert_eq!(is_result, should_result);
     }
-}
 
-mod curve_share {
-    use std::{sync::mpsc, thread};
+    #[test]
+    fn rep3_int_div_rem_public_via_yao() {
+        const VEC_SIZE: usize = 10;
 
-    use ark_std::UniformRand;
-    use itertools::izip;
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let x = (0..VEC_SIZE)
+            .map(|_| ark_bn254::Fr::rand(&mut rng))
+            .collect_vec();
+        let x_shares = rep3::share_field_elements(&x, &mut rng);
+        let divisor = ark_bn254::Fr::from(rng.gen_range(2..u64::MAX));
 
-    use mpc_core::protocols::rep3::{self, pointshare};
-    use rand::thread_rng;
+        let divisor_biguint: BigUint = divisor.into();
+        let mut should_quotients = Vec::with_capacity(VEC_SIZE);
+        let mut should_rems = Vec::with_capacity(VEC_SIZE);
+        for x in x.into_iter() {
+            let x: BigUint = x.into();
+            should_quotients.push(ark_bn254::Fr::from(&x / &divisor_biguint));
+            should_rems.push(ark_bn254::Fr::from(&x % &divisor_biguint));
+        }
+
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+
+        for (net, tx, x) in izip!(
+            test_network.get_party_networks().into_iter(),
+            [tx1, tx2, tx3],
+            x_shares.into_iter()
+        ) {
+            thread::spawn(move || {
+                let mut rep3 = IoContext::init(net).unwrap();
+
+                let divided = yao::field_int_div_rem_public_many(&x, &mut rep3, divisor).unwrap();
+                tx.send(divided)
+            });
+        }
+
+        let (quotients1, rems1) = rx1.recv().unwrap();
+        let (quotients2, rems2) = rx2.recv().unwrap();
+        let (quotients3, rems3) = rx3.recv().unwrap();
+        let is_quotients = rep3::combine_field_elements(&quotients1, &quotients2, &quotients3);
+        let is_rems = rep3::combine_field_elements(&rems1, &rems2, &rems3);
+        assert_eq!(is_quotients, should_quotients);
+        assert_eq!(is_rems, should_rems);
+    }
 
     #[test]
-    fn rep3_add() {
+    fn rep3_is_zero_shared_many() {
+        const VEC_SIZE: usize = 10;
+
+        let test_network = Rep3TestNetwork::default();
         let mut rng = thread_rng();
-        let x = ark_bn254::G1Projective::rand(&mut rng);
-        let y = ark_bn254::G1Projective::rand(&mut rng);
-        let x_shares = rep3::share_curve_point(x, &mut rng);
-        let y_shares = rep3::share_curve_point(y, &mut rng);
-        let should_result = x + y;
+        // every second element is zero
+        let x = (0..VEC_SIZE)
+            .map(|i| {
+                if i % 2 == 0 {
+                    ark_bn254::Fr::zero()
+                } else {
+                    ark_bn254::Fr::rand(&mut rng)
+                }
+            })
+            .collect_vec();
+        let x_shares = rep3::share_field_elements(&x, &mut rng);
+        let should_result = x
+            .iter()
+            .map(|x| ark_bn254::Fr::from(x.is_zero()))
+            .collect_vec();
+
         let (tx1, rx1) = mpsc::channel();
         let (tx2, rx2) = mpsc::channel();
         let (tx3, rx3) = mpsc::channel();
 
-        for (tx, x, y) in izip!([tx1, tx2, tx3], x_shares.into_iter(), y_shares.into_iter()) {
-            thread::spawn(move || tx.send(pointshare::add(&x, &y)));
+        for (net, tx, x) in izip!(
+            test_network.get_party_networks().into_iter(),
+            [tx1, tx2, tx3],
+            x_shares.into_iter()
+        ) {
+            thread::spawn(move || {
+                let mut rep3 = IoContext::init(net).unwrap();
+
+                let mut is_zero = arithmetic::is_zero_shared_many(&x, &mut rep3).unwrap();
+                is_zero.push(arithmetic::is_zero_shared(x[0], &mut rep3).unwrap());
+                tx.send(is_zero)
+            });
         }
+
         let result1 = rx1.recv().unwrap();
         let result2 = rx2.recv().unwrap();
         let result3 = rx3.recv().unwrap();
-        let is_result = rep3::combine_curve_point(result1, result2, result3);
+        let mut is_result = rep3::combine_field_elements(&result1, &result2, &result3);
+        assert_eq!(is_result.pop(), Some(ark_bn254::Fr::one()));
         assert_eq!(is_result, should_result);
     }
 
     #[test]
-    fn rep3_sub() {
+    fn rep3_aes128_via_yao() {
+        // Test vector from FIPS-197, Appendix C.1
+        let key = BigUint::from_bytes_le(&[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
+            0x0e, 0x0f,
+        ]);
+        let plaintext = BigUint::from_bytes_le(&[
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+            0xee, 0xff,
+        ]);
+        let should_result = BigUint::from_bytes_le(&[
+            0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30, 0xd8, 0xcd, 0xb7, 0x80, 0x70, 0xb4,
+            0xc5, 0x5a,
+        ]);
+
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let key_shares = rep3::share_biguint(ark_bn254::Fr::from(key), &mut rng);
+        let pt_shares = rep3::share_biguint(ark_bn254::Fr::from(plaintext), &mut rng);
+
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+
+        for (net, tx, key, pt) in izip!(
+            test_network.get_party_networks().into_iter(),
+            [tx1, tx2, tx3],
+            key_shares.into_iter(),
+            pt_shares.into_iter()
+        ) {
+            thread::spawn(move || {
+                let mut rep3 = IoContext::init(net).unwrap();
+
+                let encrypted =
+                    yao::aes128_encrypt_many(&key, &[pt.to_owned(), pt], &mut rep3).unwrap();
+                tx.send(encrypted)
+            });
+        }
+
+        let result1 = rx1.recv().unwrap();
+        let result2 = rx2.recv().unwrap();
+        let result3 = rx3.recv().unwrap();
+        assert_eq!(result1.len(), 2);
+        for (r1, r2, r3) in izip!(result1, result2, result3) {
+            let is_result = rep3::combine_binary_element(r1, r2, r3);
+            assert_eq!(is_result, should_result);
+        }
+    }
+
+    #[test]
+    fn rep3_bristol_circuit_via_yao() {
+        // Computes (a + b) mod 4 and !(a_0 & b_0) for two 2-bit inputs a and b
+        const CIRCUIT: &str = "6 10
+2 2 2
+2 2 1
+
+2 1 0 2 4 AND
+2 1 1 3 5 XOR
+2 1 0 2 7 XOR
+2 1 5 4 8 XOR
+1 1 4 6 INV
+1 1 6 9 EQW
+";
+        let circuit = BristolFashionCircuit::from_reader(CIRCUIT.as_bytes()).unwrap();
+        assert_eq!(circuit.num_and_gates(), 1);
+
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let a = rng.gen_range(0u64..4);
+        let b = rng.gen_range(0u64..4);
+        let a_shares = rep3::share_biguint(ark_bn254::Fr::from(a), &mut rng);
+        let b_shares = rep3::share_biguint(ark_bn254::Fr::from(b), &mut rng);
+        let should_result = vec![
+            BigUint::from((a + b) % 4),
+            BigUint::from(((a & b & 1) ^ 1) as u64),
+        ];
+
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+
+        for (net, tx, a, b) in izip!(
+            test_network.get_party_networks().into_iter(),
+            [tx1, tx2, tx3],
+            a_shares.into_iter(),
+            b_shares.into_iter()
+        ) {
+            let circuit = circuit.to_owned();
+            thread::spawn(move || {
+                let mut rep3 = IoContext::init(net).unwrap();
+
+                let evaluated =
+                    yao::evaluate_bristol_circuit(&circuit, &[a, b], &mut rep3).unwrap();
+                tx.send(evaluated)
+            });
+        }
+
+        let result1 = rx1.recv().unwrap();
+        let result2 = rx2.recv().unwrap();
+        let result3 = rx3.recv().unwrap();
+        let is_result = izip!(result1, result2, result3)
+            .map(|(r1, r2, r3)| rep3::combine_binary_element(r1, r2, r3))
+            .collect_vec();
+        assert_eq!(is_result, should_result);
+    }
+
+    #[test]
+    fn rep3_bigint_mod_ops_via_yao() {
+        // The base field of secp256k1
+        let modulus = BigUint::from_str(
+            "115792089237316195423570985008687907853269984665640564039457584007908834671663",
+        )
+        .unwrap();
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let lhs = rng.gen::<[u8; 32]>();
+        let rhs = rng.gen::<[u8; 32]>();
+        let lhs_int = BigUint::from_bytes_le(&lhs) % &modulus;
+        let rhs_int = BigUint::from_bytes_le(&rhs) % &modulus;
+        let rhs_inv = rhs_int.modpow(&(&modulus - 2u64), &modulus);
+        let should_result = vec![
+            (&lhs_int + &rhs_int) % &modulus,
+            (&lhs_int + &modulus - &rhs_int) % &modulus,
+            (&lhs_int * &rhs_int) % &modulus,
+            (&lhs_int * &rhs_inv) % &modulus,
+        ];
+
+        let to_fields = |bytes: &[u8]| {
+            bytes
+                .iter()
+                .map(|b| ark_bn254::Fr::from(*b as u64))
+                .collect_vec()
+        };
+        let lhs_shares = rep3::share_field_elements(&to_fields(&lhs), &mut rng);
+        let rhs_shares = rep3::share_field_elements(&to_fields(&rhs), &mut rng);
+
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+
+        for (net, tx, lhs, rhs) in izip!(
+            test_network.get_party_networks().into_iter(),
+            [tx1, tx2, tx3],
+            lhs_shares.into_iter(),
+            rhs_shares.into_iter()
+        ) {
+            let modulus = modulus.to_owned();
+            thread::spawn(move || {
+                let mut rep3 = IoContext::init(net).unwrap();
+
+                let mut results = Vec::with_capacity(4);
+                for op in [
+                    yao::BigIntModOp::Add,
+                    yao::BigIntModOp::Sub,
+                    yao::BigIntModOp::Mul,
+                ] {
+                    results.push(
+                        yao::bigint_mod_op_bytes(&lhs, &rhs, &modulus, op, &mut rep3).unwrap(),
+                    );
+                }
+                results.push(yao::bigint_div_mod_bytes(&lhs, &rhs, &modulus, &mut rep3).unwrap());
+                tx.send(results)
+            });
+        }
+
+        let result1 = rx1.recv().unwrap();
+        let result2 = rx2.recv().unwrap();
+        let result3 = rx3.recv().unwrap();
+        for (r1, r2, r3, should_result) in izip!(result1, result2, result3, should_result) {
+            let is_result = rep3::combine_field_elements(&r1, &r2, &r3);
+            assert_eq!(is_result.len(), 32);
+            let bytes = is_result
+                .into_iter()
+                .map(|b| u8::try_from(BigUint::from(b)).unwrap())
+                .collect_vec();
+            assert_eq!(BigUint::from_bytes_le(&bytes), should_result);
+        }
+    }
+
+    #[test]
+    fn rep3_bigint_limbs_via_yao() {
+        const LIMB_BITS: usize = 68;
+        const NUM_LIMBS: usize = 4;
+        // The base field of secp256k1
+        let modulus = BigUint::from_str(
+            "115792089237316195423570985008687907853269984665640564039457584007908834671663",
+        )
+        .unwrap();
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let mask = (BigUint::from(1u64) << LIMB_BITS) - 1u64;
+        let to_limbs = |value: &BigUint| {
+            (0..NUM_LIMBS)
+                .map(|i| ark_bn254::Fr::from((value >> (i * LIMB_BITS)) & &mask))
+                .collect_vec()
+        };
+        let lhs_int = BigUint::from_bytes_le(&rng.gen::<[u8; 32]>()) % &modulus;
+        let rhs_int = BigUint::from_bytes_le(&rng.gen::<[u8; 32]>()) % &modulus;
+        let add_int = BigUint::from_bytes_le(&rng.gen::<[u8; 16]>());
+        // The second limb exceeds LIMB_BITS, like an unreduced limb in the bigfield gadget
+        let mut sub = to_limbs(&(&lhs_int >> LIMB_BITS));
+        sub.truncate(2);
+        sub[1] += ark_bn254::Fr::from(BigUint::from(3u64) << LIMB_BITS);
+        let sub_int = ((&lhs_int >> LIMB_BITS) & ((BigUint::from(1u64) << (2 * LIMB_BITS)) - 1u64))
+            + (BigUint::from(3u64) << (2 * LIMB_BITS));
+
+        let value = &lhs_int * &rhs_int + &add_int - &sub_int;
+        let should_result = [
+            &value / &modulus,
+            &value % &modulus,
+            (&lhs_int * rhs_int.modpow(&(&modulus - 2u64), &modulus)) % &modulus,
+        ];
+
+        let [lhs_shares, rhs_shares, add_shares, sub_shares] = [
+            to_limbs(&lhs_int),
+            to_limbs(&rhs_int),
+            to_limbs(&add_int),
+            sub,
+        ]
+        .map(|limbs| rep3::share_field_elements(&limbs, &mut rng));
+
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+
+        for (net, tx, lhs, rhs, add, sub) in izip!(
+            test_network.get_party_networks().into_iter(),
+            [tx1, tx2, tx3],
+            lhs_shares.into_iter(),
+            rhs_shares.into_iter(),
+            add_shares.into_iter(),
+            sub_shares.into_iter()
+        ) {
+            let modulus = modulus.to_owned();
+            thread::spawn(move || {
+                let mut rep3 = IoContext::init(net).unwrap();
+                let (quotient, remainder) = yao::bigint_quotient_remainder_limbs(
+                    &lhs, &rhs, &add, &sub, &modulus, LIMB_BITS, NUM_LIMBS, &mut rep3,
+                )
+                .unwrap();
+                let inverse =
+                    yao::bigint_div_limbs(&lhs, &rhs, &modulus, LIMB_BITS, NUM_LIMBS, &mut rep3)
+                        .unwrap();
+                tx.send([quotient, remainder, inverse])
+            });
+        }
+
+        let result1 = rx1.recv().unwrap();
+        let result2 = rx2.recv().unwrap();
+        let result3 = rx3.recv().unwrap();
+        for (r1, r2, r3, should_result) in izip!(result1, result2, result3, should_result) {
+            let is_result = rep3::combine_field_elements(&r1, &r2, &r3);
+            assert_eq!(is_result.len(), NUM_LIMBS);
+            let is_result = is_result
+                .into_iter()
+                .rev()
+                .fold(BigUint::from(0u64), |acc, limb| {
+                    let limb = BigUint::from(limb);
+                    assert!(limb <= mask);
+                    (acc << LIMB_BITS) + limb
+                });
+            assert_eq!(is_result, should_result);
+        }
+    }
+
+    #[test]
+    fn rep3_blake2s_via_yao() {
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        // A message of a single block and a message of two blocks
+        let messages = [b"abc".to_vec(), (0..100).collect_vec()];
+        let should_result = [
+            "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982",
+            "81dcc3a505eace3f879d8f702776770f9df50e521d1428a85daf04f9ad2150e0",
+        ];
+
+        let [first_shares, second_shares] = messages.map(|message| {
+            let message = message
+                .iter()
+                .map(|b| ark_bn254::Fr::from(*b as u64))
+                .collect_vec();
+            rep3::share_field_elements(&message, &mut rng)
+        });
+
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+
+        for (net, tx, first, second) in izip!(
+            test_network.get_party_networks().into_iter(),
+            [tx1, tx2, tx3],
+            first_shares.into_iter(),
+            second_shares.into_iter()
+        ) {
+            thread::spawn(move || {
+                let mut rep3 = IoContext::init(net).unwrap();
+                let results = [first, second]
+                    .iter()
+                    .map(|message| yao::blake2s_bytes(message, &mut rep3).unwrap())
+                    .collect_vec();
+                tx.send(results)
+            });
+        }
+
+        let result1 = rx1.recv().unwrap();
+        let result2 = rx2.recv().unwrap();
+        let result3 = rx3.recv().unwrap();
+        for (r1, r2, r3, should_result) in izip!(result1, result2, result3, should_result) {
+            let is_result = rep3::combine_field_elements(&r1, &r2, &r3);
+            let digest = is_result
+                .into_iter()
+                .map(|b| format!("{:02x}", u8::try_from(BigUint::from(b)).unwrap()))
+                .collect::<String>();
+            assert_eq!(digest, should_result);
+        }
+    }
+
+    #[test]
+    fn rep3_aes128_cbc_via_yao() {
+        // The first two blocks of the CBC-AES128 test vector of NIST SP 800-38A, F.2.1
+        let hex_to_fr = |hex: &str| {
+            (0..hex.len())
+                .step_by(2)
+                .map(|i| ark_bn254::Fr::from(u8::from_str_radix(&hex[i..i + 2], 16).unwrap()))
+                .collect_vec()
+        };
+        let key = hex_to_fr("2b7e151628aed2a6abf7158809cf4f3c");
+        let iv = hex_to_fr("000102030405060708090a0b0c0d0e0f");
+        let plaintext =
+            hex_to_fr("6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51");
+        let should_result =
+            hex_to_fr("7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b2");
+
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let key_shares = rep3::share_field_elements(&key, &mut rng);
+        let iv_shares = rep3::share_field_elements(&iv, &mut rng);
+        let pt_shares = rep3::share_field_elements(&plaintext, &mut rng);
+
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+
+        for (net, tx, key, iv, pt) in izip!(
+            test_network.get_party_networks().into_iter(),
+            [tx1, tx2, tx3],
+            key_shares.into_iter(),
+            iv_shares.into_iter(),
+            pt_shares.into_iter()
+        ) {
+            thread::spawn(move || {
+                let mut rep3 = IoContext::init(net).unwrap();
+                let encrypted = yao::aes128_cbc_encrypt_bytes(&key, &iv, &pt, &mut rep3).unwrap();
+                tx.send(encrypted)
+            });
+        }
+
+        let result1 = rx1.recv().unwrap();
+        let result2 = rx2.recv().unwrap();
+        let result3 = rx3.recv().unwrap();
+        let is_result = rep3::combine_field_elements(&result1, &result2, &result3);
+        assert_eq!(is_result, should_result);
+    }
+
+    #[test]
+    fn rep3_poseidon2_permutation() {
+        const NUM_STATES: usize = 3;
+        let params = &POSEIDON2_BN254_T4_PARAMS;
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let states = (0..NUM_STATES)
+            .map(|_| std::array::from_fn::<_, 4, _>(|_| ark_bn254::Fr::rand(&mut rng)))
+            .collect_vec();
+        let should_result = states
+            .iter()
+            .flat_map(|state| params.permutation(state))
+            .collect_vec();
+        let [x1, x2, x3] = rep3::share_field_elements(&states.concat(), &mut rng).map(|shares| {
+            shares
+                .chunks_exact(4)
+                .map(|state| <[_; 4]>::try_from(state).unwrap())
+                .collect_vec()
+        });
+
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+
+        for (net, tx, mut x) in izip!(
+            test_network.get_party_networks().into_iter(),
+            [tx1, tx2, tx3],
+            [x1, x2, x3]
+        ) {
+            thread::spawn(move || {
+                let mut rep3 = IoContext::init(net).unwrap();
+                gadgets::poseidon2::poseidon2_permutation_many(params, &mut x, &mut rep3).unwrap();
+                tx.send(x.concat())
+            });
+        }
+
+        let result1 = rx1.recv().unwrap();
+        let result2 = rx2.recv().unwrap();
+        let result3 = rx3.recv().unwrap();
+        let is_result = rep3::combine_field_elements(&result1, &result2, &result3);
+        assert_eq!(is_result, should_result);
+    }
+
+    #[test]
+    fn rep3_nonnative_field_arithmetic() {
+        // The base field of secp256k1
+        let modulus = BigUint::from_str(
+            "115792089237316195423570985008687907853269984665640564039457584007908834671663",
+        )
+        .unwrap();
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let x = BigUint::from_bytes_le(&rng.gen::<[u8; 32]>()) % &modulus;
+        let y = BigUint::from_bytes_le(&rng.gen::<[u8; 32]>()) % &modulus;
+        let should_result = vec![
+            (&x * &y + &x + &modulus - &y) % &modulus,
+            (&x * y.modpow(&(&modulus - 2u64), &modulus)) % &modulus,
+        ];
+
+        let to_limbs = |x: &BigUint| {
+            let mut limbs = x.to_u64_digits();
+            limbs.resize(4, 0);
+            limbs.into_iter().map(ark_bn254::Fr::from).collect_vec()
+        };
+        let x_shares = rep3::share_field_elements(&to_limbs(&x), &mut rng);
+        let y_shares = rep3::share_field_elements(&to_limbs(&y), &mut rng);
+
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+
+        for (net, tx, x, y) in izip!(
+            test_network.get_party_networks().into_iter(),
+            [tx1, tx2, tx3],
+            x_shares.into_iter(),
+            y_shares.into_iter()
+        ) {
+            let modulus = modulus.to_owned();
+            thread::spawn(move || {
+                let mut rep3 = IoContext::init(net).unwrap();
+                let field = NonNativeField::new(modulus);
+                let x = NonNativeShare::from_limbs(x, LIMB_BITS);
+                let y = NonNativeShare::from_limbs(y, LIMB_BITS);
+
+                let mul = field.mul(&mut rep3, &x, &y).unwrap();
+                let add = field.add(&mut rep3, &mul, &x).unwrap();
+                let sub = field.sub(&mut rep3, &add, &y).unwrap();
+                let div = field.div(&mut rep3, &x, &y).unwrap();
+                tx.send([
+                    field.open(&mut rep3, &sub).unwrap(),
+                    field.open(&mut rep3, &div).unwrap(),
+                ])
+            });
+        }
+
+        let result1 = rx1.recv().unwrap();
+        let result2 = rx2.recv().unwrap();
+        let result3 = rx3.recv().unwrap();
+        assert_eq!(result1.to_vec(), should_result);
+        assert_eq!(result2.to_vec(), should_result);
+        assert_eq!(result3.to_vec(), should_result);
+    }
+}
+
+mod curve_share {
+    use std::{sync::mpsc, thread};
+
+    use ark_std::UniformRand;
+    use itertools::izip;
+
+    use mpc_core::protocols::rep3::{self, network::IoContext, pointshare};
+    use rand::thread_rng;
+    use tests::rep3_network::Rep3TestNetwork;
+
+    #[test]
+    fn rep3_add() {
+        let mut rng = thread_rng();
+        let x = ark_bn254::G1Projective::rand(&mut rng);
+        let y = ark_bn254::G1Projective::rand(&mut rng);
+        let x_shares = rep3::share_curve_point(x, &mut rng);
+        let y_shares = rep3::share_curve_point(y, &mut rng);
+        let should_result = x + y;
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+
+        for (tx, x, y) in izip!([tx1, tx2, tx3], x_shares.into_iter(), y_shares.into_iter()) {
+            thread::spawn(move || tx.send(pointshare::add(&x, &y)));
+        }
+        let result1 = rx1.recv().unwrap();
+        let result2 = rx2.recv().unwrap();
+        let result3 = rx3.recv().unwrap();
+        let is_result = rep3::combine_curve_point(result1, result2, result3);
+        assert_eq!(is_result, should_result);
+    }
+
+    #[test]
+    fn rep3_sub() {
         let mut rng = thread_rng();
         let x = ark_bn254::G1Projective::rand(&mut rng);
         let y = ark_bn254::G1Projective::rand(&mut rng);
@@ -1588,4 +2542,126 @@ mod curve_share {
         let is_result = rep3::combine_curve_point(result1, result2, result3);
         assert_eq!(is_result, should_result);
     }
+
+    #[test]
+    fn rep3_from_additive_g2() {
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let x = ark_bn254::G2Projective::rand(&mut rng);
+        // additive shares, e.g., from an external threshold scheme
+        let x0 = ark_bn254::G2Projective::rand(&mut rng);
+        let x1 = ark_bn254::G2Projective::rand(&mut rng);
+        let x_shares = [x0, x1, x - x0 - x1];
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+
+        for (net, tx, x) in izip!(
+            test_network.get_party_networks().into_iter(),
+            [tx1, tx2, tx3],
+            x_shares
+        ) {
+            thread::spawn(move || {
+                let mut rep3 = IoContext::init(net).unwrap();
+                let share = pointshare::from_additive(x, &mut rep3).unwrap();
+                // the share is fresh, i.e., it does not contain the additive share
+                assert_ne!(share.a, x);
+                tx.send(share)
+            });
+        }
+        let result1 = rx1.recv().unwrap();
+        let result2 = rx2.recv().unwrap();
+        let result3 = rx3.recv().unwrap();
+        let is_result = rep3::combine_curve_point(result1, result2, result3);
+        assert_eq!(is_result, x);
+    }
+
+    #[test]
+    fn rep3_to_additive_and_rerandomize() {
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let x = (0..10)
+            .map(|_| ark_bn254::G1Projective::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let mut x_shares = [Vec::new(), Vec::new(), Vec::new()];
+        for x in x.iter() {
+            for (shares, share) in x_shares
+                .iter_mut()
+                .zip(rep3::share_curve_point(*x, &mut rng))
+            {
+                shares.push(share);
+            }
+        }
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+
+        for (net, tx, x) in izip!(
+            test_network.get_party_networks().into_iter(),
+            [tx1, tx2, tx3],
+            x_shares
+        ) {
+            thread::spawn(move || {
+                let mut rep3 = IoContext::init(net).unwrap();
+                let additive = x
+                    .iter()
+                    .map(|x| pointshare::to_additive(x, &mut rep3))
+                    .collect::<Vec<_>>();
+                let rerandomized = pointshare::rerandomize_many(&x, &mut rep3).unwrap();
+                tx.send((additive, rerandomized))
+            });
+        }
+        let (additive1, result1) = rx1.recv().unwrap();
+        let (additive2, result2) = rx2.recv().unwrap();
+        let (additive3, result3) = rx3.recv().unwrap();
+        let is_additive = izip!(additive1, additive2, additive3)
+            .map(|(a, b, c)| a + b + c)
+            .collect::<Vec<_>>();
+        assert_eq!(is_additive, x);
+        let is_result = izip!(result1, result2, result3)
+            .map(|(a, b, c)| rep3::combine_curve_point(a, b, c))
+            .collect::<Vec<_>>();
+        assert_eq!(is_result, x);
+    }
+}
+
+mod pvss {
+    use ark_std::UniformRand;
+    use itertools::{izip, Itertools};
+    use mpc_core::protocols::rep3::{
+        id::PartyID,
+        pvss::{self, PvssKeyPair},
+    };
+    use rand::thread_rng;
+
+    #[test]
+    fn rep3_pvss_share_and_decrypt() {
+        let mut rng = thread_rng();
+        let vals = (0..10).map(|_| ark_bn254::Fr::rand(&mut rng)).collect_vec();
+        let keys = (0..3)
+            .map(|_| PvssKeyPair::<ark_bn254::G1Projective>::generate(&mut rng))
+            .collect_vec();
+        let public_keys = [keys[0].public_key, keys[1].public_key, keys[2].public_key];
+
+        let sharing = pvss::share_field_elements_pvss(&vals, &public_keys, &mut rng).unwrap();
+        sharing.verify(&public_keys).unwrap();
+        let shares = izip!(&keys, [PartyID::ID0, PartyID::ID1, PartyID::ID2])
+            .map(|(key, id)| sharing.decrypt(key, &public_keys, id).unwrap())
+            .collect_vec();
+        for (i, val) in vals.iter().enumerate() {
+            assert_eq!(shares[0][i] + shares[1][i] + shares[2][i], *val);
+        }
+
+        // a key pair can only decrypt the shares of its own party
+        assert!(sharing
+            .decrypt(&keys[0], &public_keys, PartyID::ID1)
+            .is_err());
+        // the sharing is bound to the committee
+        let other_keys = [public_keys[1], public_keys[0], public_keys[2]];
+        assert!(sharing.verify(&other_keys).is_err());
+        // the ciphertexts can not be modified
+        let mut tampered = sharing.clone();
+        tampered.ciphertexts[2][0] += ark_bn254::Fr::from(1u64);
+        assert!(tampered.verify(&public_keys).is_err());
+    }
 }
@@ -0,0 +1,133 @@
+use std::io::ErrorKind;
+
+use mpc_core::protocols::rep3::network::Rep3Network;
+use mpc_core::protocols::rep3::{async_network::Rep3AsyncMpcNet, id::PartyID};
+use tokio::task::JoinSet;
+
+use crate::shamir_network::configs;
+
+// Runs f for each of the three parties on a shared runtime and returns the results ordered by the party id
+fn run_parties<T: Send + 'static, Fut>(
+    base_port: u16,
+    f: impl Fn(Rep3AsyncMpcNet) -> Fut + Send + Sync + Copy + 'static,
+) -> Vec<T>
+where
+    Fut: std::future::Future<Output = std::io::Result<T>> + Send + 'static,
+{
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    runtime.block_on(async move {
+        let mut parties = JoinSet::new();
+        for config in configs(base_port, 3) {
+            parties.spawn(async move {
+                let id = config.my_id;
+                let net = Rep3AsyncMpcNet::new(config).await.unwrap();
+                (id, f(net).await.unwrap())
+            });
+        }
+        let mut results = Vec::new();
+        while let Some(result) = parties.join_next().await {
+            results.push(result.unwrap());
+        }
+        results.sort_by_key(|(id, _)| *id);
+        results.into_iter().map(|(_, result)| result).collect()
+    })
+}
+
+fn value(id: PartyID) -> Vec<u64> {
+    vec![100 + usize::from(id) as u64; 3]
+}
+
+#[test]
+fn async_send_and_recv() {
+    let results = run_parties(22100, |mut net| async move {
+        let id = net.get_id();
+        net.send_many(id.next_id(), &value(id)).await?;
+        let received = net.recv_many::<u64>(id.prev_id()).await?;
+        net.shutdown().await?;
+        Ok(received)
+    });
+    for (id, received) in results.into_iter().enumerate() {
+        let id = PartyID::try_from(id).unwrap();
+        assert_eq!(received, value(id.prev_id()));
+    }
+}
+
+#[test]
+fn async_reshare_and_broadcast() {
+    let results = run_parties(22110, |mut net| async move {
+        let id = net.get_id();
+        let reshared = net.reshare_many(&value(id)).await?;
+        let broadcast = net.broadcast_many(&value(id)).await?;
+        net.shutdown().await?;
+        Ok((reshared, broadcast))
+    });
+    for (id, (reshared, (from_prev, from_next))) in results.into_iter().enumerate() {
+        let id = PartyID::try_from(id).unwrap();
+        assert_eq!(reshared, value(id.prev_id()));
+        assert_eq!(from_prev, value(id.prev_id()));
+        assert_eq!(from_next, value(id.next_id()));
+    }
+}
+
+#[test]
+fn async_send_to_self_fails() {
+    let results = run_parties(22120, |mut net| async move {
+        let id = net.get_id();
+        let err = net.send_many(id, &value(id)).await.unwrap_err();
+        net.shutdown().await?;
+        Ok(err.kind())
+    });
+    assert_eq!(results, vec![ErrorKind::InvalidInput; 3]);
+}
+
+#[test]
+fn async_send_after_shutdown_fails() {
+    let results = run_parties(22130, |mut net| async move {
+        let id = net.get_id();
+        let mut fork = net.fork().await?;
+        net.shutdown().await?;
+        // the connections of the fork are closed, so the error of the write is returned
+        Ok(fork.send_many(id.next_id(), &value(id)).await.is_err())
+    });
+    assert_eq!(results, vec![true; 3]);
+}
+
+#[test]
+fn blocking_adapter() {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let results = runtime.block_on(async {
+        let mut parties = JoinSet::new();
+        for config in configs(22140, 3) {
+            let handle = tokio::runtime::Handle::current();
+            parties.spawn(async move {
+                let net = Rep3AsyncMpcNet::new(config).await.unwrap();
+                // the MPC protocols use the synchronous interface on a blocking thread
+                let (id, reshared, net) = tokio::task::spawn_blocking(move || {
+                    let mut net = net.into_blocking(handle);
+                    let id = net.get_id();
+                    let reshared = net.reshare_many(&value(id)).unwrap();
+                    (id, reshared, net.into_inner())
+                })
+                .await
+                .unwrap();
+                net.shutdown().await.unwrap();
+                (id, reshared)
+            });
+        }
+        let mut results = Vec::new();
+        while let Some(result) = parties.join_next().await {
+            results.push(result.unwrap());
+        }
+        results
+    });
+    assert_eq!(results.len(), 3);
+    for (id, reshared) in results {
+        assert_eq!(reshared, value(id.prev_id()));
+    }
+}
@@ -1732,4 +1732,64 @@ mod ring_share {
     fn rep3_div_power_2_via_yao() {
         apply_to_all!(rep3_div_power_2_via_yao_t, [Bit, u8, u16, u32, u64, u128]);
     }
+
+    fn rep3_div_rem_public_via_yao_t<T: IntRing2k>()
+    where
+        Standard: Distribution<T>,
+    {
+        const VEC_SIZE: usize = 10;
+
+        let test_network = Rep3TestNetwork::default();
+        let mut rng = thread_rng();
+        let x = (0..VEC_SIZE)
+            .map(|_| rng.gen::<RingElement<T>>())
+            .collect_vec();
+        let x_shares = rep3_ring::share_ring_elements(&x, &mut rng);
+        let mut divisor = rng.gen::<RingElement<T>>() >> rng.gen_range(0..T::K);
+        if divisor.is_zero() {
+            divisor = RingElement::one();
+        }
+
+        let divisor_u128: u128 = divisor.0.into();
+        let mut should_quotients = Vec::with_capacity(VEC_SIZE);
+        let mut should_rems = Vec::with_capacity(VEC_SIZE);
+        for x in x.into_iter() {
+            let x: u128 = x.0.into();
+            should_quotients.push(RingElement(T::try_from(x / divisor_u128).unwrap()));
+            should_rems.push(RingElement(T::try_from(x % divisor_u128).unwrap()));
+        }
+
+        let (tx1, rx1) = mpsc::channel();
+        let (tx2, rx2) = mpsc::channel();
+        let (tx3, rx3) = mpsc::channel();
+
+        for (net, tx, x) in izip!(
+            test_network.get_party_networks().into_iter(),
+            [tx1, tx2, tx3],
+            x_shares.into_iter()
+        ) {
+            thread::spawn(move || {
+                let mut rep3 = IoContext::init(net).unwrap();
+
+                let divided = yao::ring_div_rem_public_many(&x, &mut rep3, divisor).unwrap();
+                tx.send(divided)
+            });
+        }
+
+        let (quotients1, rems1) = rx1.recv().unwrap();
+        let (quotients2, rems2) = rx2.recv().unwrap();
+        let (quotients3, rems3) = rx3.recv().unwrap();
+        let is_quotients = rep3_ring::combine_ring_elements(&quotients1, &quotients2, &quotients3);
+        let is_rems = rep3_ring::combine_ring_elements(&rems1, &rems2, &rems3);
+        assert_eq!(is_quotients, should_quotients);
+        assert_eq!(is_rems, should_rems);
+    }
+
+    #[test]
+    fn rep3_div_rem_public_via_yao() {
+        apply_to_all!(
+            rep3_div_rem_public_via_yao_t,
+            [Bit, u8, u16, u32, u64, u128]
+        );
+    }
 }
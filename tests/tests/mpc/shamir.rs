@@ -2,8 +2,14 @@ mod field_share {
     use ark_ff::Field;
     use ark_std::{UniformRand, Zero};
     use itertools::{izip, Itertools};
-    use mpc_core::protocols::shamir::{self, arithmetic, ShamirPreprocessing};
-    use rand::thread_rng;
+    use mpc_core::nonnative::{NonNativeField, NonNativeShare, LIMB_BITS};
+    use mpc_core::protocols::shamir::{
+        self, arithmetic,
+        weighted::{self, ShareWeights},
+        Reconstruction, ShamirPreprocessing, ShamirProtocol,
+    };
+    use num_bigint::BigUint;
+    use rand::{thread_rng, Rng};
     use std::{str::FromStr, sync::mpsc, thread};
     use tests::shamir_network::ShamirTestNetwork;
 
@@ -224,6 +230,84 @@ mod field_share {
         shamir_mul_vec_bn_inner(10, 4);
     }
 
+    fn shamir_interpolate_and_eval_poly_inner(num_parties: usize, threshold: usize) {
+        let test_network = ShamirTestNetwork::new(num_parties);
+        let mut rng = thread_rng();
+        let coeffs = (0..10)
+            .map(|_| ark_bn254::Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let points = (0..coeffs.len())
+            .map(|_| ark_bn254::Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let shared_points = (0..3)
+            .map(|_| ark_bn254::Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let eval = |x: &ark_bn254::Fr| {
+            coeffs
+                .iter()
+                .rev()
+                .fold(ark_bn254::Fr::zero(), |acc, c| acc * x + c)
+        };
+        let evals = points.iter().map(eval).collect::<Vec<_>>();
+        let should_result = shared_points.iter().map(eval).collect::<Vec<_>>();
+        let evals_shares = shamir::share_field_elements(&evals, threshold, num_parties, &mut rng);
+        let point_shares =
+            shamir::share_field_elements(&shared_points, threshold, num_parties, &mut rng);
+
+        let mut tx = Vec::with_capacity(num_parties);
+        let mut rx = Vec::with_capacity(num_parties);
+        for _ in 0..num_parties {
+            let (t, r) = mpsc::channel();
+            tx.push(t);
+            rx.push(r);
+        }
+
+        for (net, tx, evals, shared_points) in izip!(
+            test_network.get_party_networks(),
+            tx,
+            evals_shares,
+            point_shares
+        ) {
+            let points = points.clone();
+            let num_pairs = shared_points.len() * (points.len() - 1);
+            thread::spawn(move || {
+                let mut shamir = ShamirPreprocessing::new(threshold, net, num_pairs)
+                    .unwrap()
+                    .into();
+                let coeffs = shamir::poly::interpolate_poly(&points, &evals);
+                let evals_again = shamir::poly::eval_poly_multi(&coeffs, &points);
+                let result =
+                    shamir::poly::eval_poly_at_shared_points(&coeffs, &shared_points, &mut shamir)
+                        .unwrap();
+                tx.send((coeffs, evals_again, result))
+            });
+        }
+
+        let mut coeff_results = Vec::with_capacity(num_parties);
+        let mut eval_results = Vec::with_capacity(num_parties);
+        let mut results = Vec::with_capacity(num_parties);
+        for r in rx {
+            let (coeffs, evals, result) = r.recv().unwrap();
+            coeff_results.push(coeffs);
+            eval_results.push(evals);
+            results.push(result);
+        }
+
+        let parties = (1..=num_parties).collect_vec();
+        let combine = |shares: &[Vec<_>]| {
+            shamir::combine_field_elements(shares, &parties, threshold).unwrap()
+        };
+        assert_eq!(combine(&coeff_results), coeffs);
+        assert_eq!(combine(&eval_results), evals);
+        assert_eq!(combine(&results), should_result);
+    }
+
+    #[test]
+    fn shamir_interpolate_and_eval_poly() {
+        shamir_interpolate_and_eval_poly_inner(3, 1);
+        shamir_interpolate_and_eval_poly_inner(10, 4);
+    }
+
     fn shamir_mul_vec_inner(num_parties: usize, threshold: usize) {
         let test_network = ShamirTestNetwork::new(num_parties);
         let mut rng = thread_rng();
@@ -273,6 +357,58 @@ mod field_share {
         assert_eq!(is_result, should_result);
     }
 
+    #[test]
+    fn shamir_weighted_share_and_open() {
+        let weights = ShareWeights::new(&[2, 1, 1, 3]).unwrap();
+        let degree = 3;
+        assert_eq!(weights.num_shares(), 7);
+        assert_eq!(weights.virtual_ids(3), 4..7);
+        assert_eq!(weights.party_of(4), Some(3));
+        assert_eq!(weights.party_of(7), None);
+        assert!(!weights.can_reconstruct(&[0, 1], degree));
+        assert!(weights.can_reconstruct(&[0, 3], degree));
+
+        let mut rng = thread_rng();
+        let x = (0..10)
+            .map(|_| ark_bn254::Fr::rand(&mut rng))
+            .collect::<Vec<_>>();
+        let x_shares = weighted::share_field_elements_weighted(&x, degree, &weights, &mut rng);
+
+        let is_result = weighted::combine_field_elements_weighted(
+            &[x_shares[1].clone(), x_shares[3].clone()],
+            &[1, 3],
+            &weights,
+            degree,
+        )
+        .unwrap();
+        assert_eq!(is_result, x);
+        assert!(weighted::combine_field_elements_weighted(
+            &[x_shares[0].clone(), x_shares[2].clone()],
+            &[0, 2],
+            &weights,
+            degree,
+        )
+        .is_err());
+
+        let test_network = ShamirTestNetwork::new(weights.num_parties());
+        let mut tx = Vec::with_capacity(weights.num_parties());
+        let mut rx = Vec::with_capacity(weights.num_parties());
+        for _ in 0..weights.num_parties() {
+            let (t, r) = mpsc::channel();
+            tx.push(t);
+            rx.push(r);
+        }
+        for (mut net, tx, x) in izip!(test_network.get_party_networks(), tx, x_shares) {
+            let weights = weights.clone();
+            thread::spawn(move || {
+                tx.send(weighted::open_many_weighted(&x, &weights, degree, &mut net).unwrap())
+            });
+        }
+        for r in rx {
+            assert_eq!(r.recv().unwrap(), x);
+        }
+    }
+
     #[test]
     fn shamir_mul_vec() {
         shamir_mul_vec_inner(3, 1);
@@ -357,6 +493,126 @@ mod field_share {
         shamir_inv_inner(3, 1);
         shamir_inv_inner(10, 4);
     }
+
+    fn shamir_nonnative_field_arithmetic_inner(num_parties: usize, threshold: usize) {
+        // The base field of secp256k1
+        let modulus = BigUint::from_str(
+            "115792089237316195423570985008687907853269984665640564039457584007908834671663",
+        )
+        .unwrap();
+        let test_network = ShamirTestNetwork::new(num_parties);
+        let mut rng = thread_rng();
+        let x = BigUint::from_bytes_le(&rng.gen::<[u8; 32]>()) % &modulus;
+        let y = BigUint::from_bytes_le(&rng.gen::<[u8; 32]>()) % &modulus;
+        let should_result = vec![
+            (&x * &y + &x + &modulus - &y) % &modulus,
+            (&x * y.modpow(&(&modulus - 2u64), &modulus)) % &modulus,
+        ];
+
+        let to_limbs = |x: &BigUint| {
+            let mut limbs = x.to_u64_digits();
+            limbs.resize(4, 0);
+            limbs.into_iter().map(ark_bn254::Fr::from).collect_vec()
+        };
+        let x_shares =
+            shamir::share_field_elements(&to_limbs(&x), threshold, num_parties, &mut rng);
+        let y_shares =
+            shamir::share_field_elements(&to_limbs(&y), threshold, num_parties, &mut rng);
+
+        let mut tx = Vec::with_capacity(num_parties);
+        let mut rx = Vec::with_capacity(num_parties);
+        for _ in 0..num_parties {
+            let (t, r) = mpsc::channel();
+            tx.push(t);
+            rx.push(r);
+        }
+
+        for (net, tx, x, y) in izip!(test_network.get_party_networks(), tx, x_shares, y_shares) {
+            let modulus = modulus.to_owned();
+            thread::spawn(move || {
+                let mut shamir = ShamirPreprocessing::new(threshold, net, 1024)
+                    .unwrap()
+                    .into();
+                let field = NonNativeField::new(modulus);
+                let x = NonNativeShare::from_limbs(x, LIMB_BITS);
+                let y = NonNativeShare::from_limbs(y, LIMB_BITS);
+
+                let mul = field.mul(&mut shamir, &x, &y).unwrap();
+                let add = field.add(&mut shamir, &mul, &x).unwrap();
+                let sub = field.sub(&mut shamir, &add, &y).unwrap();
+                let div = field.div(&mut shamir, &x, &y).unwrap();
+                tx.send([
+                    field.open(&mut shamir, &sub).unwrap(),
+                    field.open(&mut shamir, &div).unwrap(),
+                ])
+            });
+        }
+
+        for r in rx {
+            assert_eq!(r.recv().unwrap().to_vec(), should_result);
+        }
+    }
+
+    #[test]
+    fn shamir_nonnative_field_arithmetic() {
+        shamir_nonnative_field_arithmetic_inner(3, 1);
+        shamir_nonnative_field_arithmetic_inner(10, 4);
+    }
+
+    fn shamir_open_reconstruction_inner(
+        num_parties: usize,
+        threshold: usize,
+        reconstruction: Reconstruction,
+    ) {
+        let test_network = ShamirTestNetwork::new(num_parties);
+        let mut rng = thread_rng();
+        let x = ark_bn254::Fr::rand(&mut rng);
+        let y = (0..10).map(|_| ark_bn254::Fr::rand(&mut rng)).collect_vec();
+        let x_shares = shamir::share_field_element(x, threshold, num_parties, &mut rng);
+        let y_shares = shamir::share_field_elements(&y, threshold, num_parties, &mut rng);
+
+        let mut tx = Vec::with_capacity(num_parties);
+        let mut rx = Vec::with_capacity(num_parties);
+        for _ in 0..num_parties {
+            let (t, r) = mpsc::channel();
+            tx.push(t);
+            rx.push(r);
+        }
+
+        for (net, tx, x, y) in izip!(test_network.get_party_networks(), tx, x_shares, y_shares) {
+            let reconstruction = reconstruction.clone();
+            thread::spawn(move || {
+                let mut shamir: ShamirProtocol<_, _> =
+                    ShamirPreprocessing::new(threshold, net, 1).unwrap().into();
+                shamir.set_reconstruction(reconstruction).unwrap();
+                let is_x = arithmetic::open(x, &mut shamir).unwrap();
+                let is_y = arithmetic::open_vec(&y, &mut shamir).unwrap();
+                // open again to check that the late messages of the previous rounds were consumed
+                let is_x_again = arithmetic::open(x, &mut shamir).unwrap();
+                tx.send((is_x, is_y, is_x_again))
+            });
+        }
+
+        for r in rx {
+            let (is_x, is_y, is_x_again) = r.recv().unwrap();
+            assert_eq!(is_x, x);
+            assert_eq!(is_y, y);
+            assert_eq!(is_x_again, x);
+        }
+    }
+
+    #[test]
+    fn shamir_open_reconstruction() {
+        for (num_parties, threshold) in [(3, 1), (10, 4)] {
+            shamir_open_reconstruction_inner(num_parties, threshold, Reconstruction::Next);
+            shamir_open_reconstruction_inner(num_parties, threshold, Reconstruction::Fastest);
+            shamir_open_reconstruction_inner(
+                num_parties,
+                threshold,
+                Reconstruction::Subset((num_parties - threshold - 1..num_parties).collect()),
+            );
+        }
+    }
 }
 
 mod curve_share {
@@ -364,8 +620,9 @@ mod curve_share {
 
     use ark_ff::UniformRand;
     use itertools::{izip, Itertools};
-    use mpc_core::protocols::shamir::{self, pointshare};
+    use mpc_core::protocols::shamir::{self, pointshare, ShamirPreprocessing, ShamirProtocol};
     use rand::thread_rng;
+    use tests::shamir_network::ShamirTestNetwork;
 
     fn shamir_add_inner(num_parties: usize, threshold: usize) {
         let mut rng = thread_rng();
@@ -520,4 +777,209 @@ mod curve_share {
         shamir_scalar_mul_public_scalar_inner(3, 1);
         shamir_scalar_mul_public_scalar_inner(10, 4);
     }
+
+    fn shamir_msm_many_inner(num_parties: usize, threshold: usize) {
+        const NUM_MSMS: usize = 5;
+        const MSM_SIZE: usize = 10;
+        const BATCH_SIZE: usize = 2;
+        let test_network = ShamirTestNetwork::new(num_parties);
+        let mut rng = thread_rng();
+        let points = (0..NUM_MSMS * MSM_SIZE)
+            .map(|_| ark_bn254::G1Projective::rand(&mut rng))
+            .collect_vec();
+        let scalars = (0..NUM_MSMS * MSM_SIZE)
+            .map(|_| ark_bn254::Fr::rand(&mut rng))
+            .collect_vec();
+        let should_result = points
+            .chunks(MSM_SIZE)
+            .zip(scalars.chunks(MSM_SIZE))
+            .map(|(points, scalars)| {
+                izip!(points, scalars)
+                    .map(|(point, scalar)| *point * scalar)
+                    .sum::<ark_bn254::G1Projective>()
+            })
+            .collect_vec();
+        let mut point_shares = vec![Vec::with_capacity(points.len()); num_parties];
+        for point in points {
+            for (shares, share) in izip!(
+                &mut point_shares,
+                shamir::share_curve_point(point, threshold, num_parties, &mut rng)
+            ) {
+                shares.push(share);
+            }
+        }
+        let scalar_shares =
+            shamir::share_field_elements(&scalars, threshold, num_parties, &mut rng);
+
+        let mut tx = Vec::with_capacity(num_parties);
+        let mut rx = Vec::with_capacity(num_parties);
+        for _ in 0..num_parties {
+            let (t, r) = mpsc::channel();
+            tx.push(t);
+            rx.push(r);
+        }
+
+        for (net, tx, points, scalars) in izip!(
+            test_network.get_party_networks(),
+            tx,
+            point_shares,
+            scalar_shares
+        ) {
+            thread::spawn(move || {
+                let mut shamir: ShamirProtocol<ark_bn254::Fr, _> =
+                    ShamirPreprocessing::new(threshold, net, NUM_MSMS + 1)
+                        .unwrap()
+                        .into();
+                let msms = points
+                    .chunks(MSM_SIZE)
+                    .zip(scalars.chunks(MSM_SIZE))
+                    .collect_vec();
+                let mut results = pointshare::msm_many(&msms, BATCH_SIZE, &mut shamir).unwrap();
+                // a single MSM uses the same degree reduction
+                results.push(pointshare::msm(msms[0].0, msms[0].1, &mut shamir).unwrap());
+                tx.send(results)
+            });
+        }
+
+        let mut results = Vec::with_capacity(num_parties);
+        for r in rx {
+            results.push(r.recv().unwrap());
+        }
+
+        for (i, should_result) in should_result
+            .iter()
+            .chain(std::iter::once(&should_result[0]))
+            .enumerate()
+        {
+            let shares = results.iter().map(|r| r[i].clone()).collect_vec();
+            let is_result =
+                shamir::combine_curve_point(&shares, &(1..=num_parties).collect_vec(), threshold)
+                    .unwrap();
+            assert_eq!(is_result, *should_result);
+        }
+    }
+
+    #[test]
+    fn shamir_msm_many() {
+        shamir_msm_many_inner(3, 1);
+        shamir_msm_many_inner(10, 4);
+    }
+}
+
+mod bls {
+    use std::{sync::mpsc, thread};
+
+    use ark_bn254::Bn254;
+    use itertools::{izip, Itertools};
+    use mpc_core::protocols::shamir::bls;
+    use rand::thread_rng;
+    use tests::shamir_network::ShamirTestNetwork;
+
+    fn shamir_threshold_bls_inner(num_parties: usize, threshold: usize) {
+        let msg = b"proof and public inputs";
+        let test_network = ShamirTestNetwork::new(num_parties);
+
+        let mut tx = Vec::with_capacity(num_parties);
+        let mut rx = Vec::with_capacity(num_parties);
+        for _ in 0..num_parties {
+            let (t, r) = mpsc::channel();
+            tx.push(t);
+            rx.push(r);
+        }
+
+        for (mut net, tx) in izip!(test_network.get_party_networks(), tx) {
+            thread::spawn(move || {
+                let key =
+                    bls::keygen::<Bn254, _, _>(threshold, &mut net, &mut thread_rng()).unwrap();
+                let signature = bls::sign(&key, msg, &mut net).unwrap();
+                tx.send((key, signature))
+            });
+        }
+
+        let results = rx.into_iter().map(|r| r.recv().unwrap()).collect_vec();
+        let (key, signature) = &results[0];
+        for (other_key, other_signature) in results.iter() {
+            assert_eq!(key.public_key, other_key.public_key);
+            assert_eq!(signature, other_signature);
+        }
+        assert!(bls::verify::<Bn254, _>(&key.public_key, msg, signature));
+        assert!(!bls::verify::<Bn254, _>(
+            &key.public_key,
+            b"other message",
+            signature
+        ));
+
+        // any threshold + 1 partial signatures suffice, invalid ones are skipped
+        let mut partial_signatures = results
+            .iter()
+            .map(|(key, _)| (key.id, bls::partial_sign(key, msg)))
+            .rev()
+            .collect_vec();
+        partial_signatures[0].1 = bls::partial_sign(&results[1].0, msg);
+        let combined = bls::combine_signatures(key, msg, &partial_signatures).unwrap();
+        assert_eq!(&combined, signature);
+        assert!(bls::combine_signatures(key, msg, &partial_signatures[..threshold + 1]).is_err());
+    }
+
+    #[test]
+    fn shamir_threshold_bls() {
+        shamir_threshold_bls_inner(3, 1);
+        shamir_threshold_bls_inner(10, 4);
+    }
+}
+
+mod dkg {
+    use std::{sync::mpsc, thread};
+
+    use ark_ec::{CurveGroup, Group};
+    use itertools::{izip, Itertools};
+    use mpc_core::protocols::shamir::{self, dkg};
+    use rand::thread_rng;
+    use tests::shamir_network::ShamirTestNetwork;
+
+    fn shamir_dkg_inner(num_parties: usize, threshold: usize) {
+        let test_network = ShamirTestNetwork::new(num_parties);
+
+        let mut tx = Vec::with_capacity(num_parties);
+        let mut rx = Vec::with_capacity(num_parties);
+        for _ in 0..num_parties {
+            let (t, r) = mpsc::channel();
+            tx.push(t);
+            rx.push(r);
+        }
+
+        for (mut net, tx) in izip!(test_network.get_party_networks(), tx) {
+            thread::spawn(move || {
+                let output = dkg::dkg::<ark_bn254::G1Projective, _, _>(
+                    threshold,
+                    &mut net,
+                    &mut thread_rng(),
+                )
+                .unwrap();
+                tx.send(output)
+            });
+        }
+
+        let results = rx.into_iter().map(|r| r.recv().unwrap()).collect_vec();
+        for output in results.iter() {
+            assert_eq!(output.commitments, results[0].commitments);
+            for other in results.iter() {
+                assert!(output.verify_share(other.id, &other.secret_share));
+            }
+        }
+        let shares = results.iter().map(|o| o.secret_share).collect_vec();
+        let ids = results.iter().map(|o| o.id + 1).collect_vec();
+        let secret = shamir::combine_field_element(&shares, &ids, threshold).unwrap();
+        assert_eq!(
+            (ark_bn254::G1Projective::generator() * secret).into_affine(),
+            results[0].public_key()
+        );
+        assert!(!results[0].verify_share(1, &results[0].secret_share));
+    }
+
+    #[test]
+    fn shamir_dkg() {
+        shamir_dkg_inner(3, 1);
+        shamir_dkg_inner(10, 4);
+    }
 }
@@ -0,0 +1,170 @@
+use std::{sync::Arc, thread};
+
+use mpc_core::protocols::shamir::network::{ShamirMpcNet, ShamirNetwork};
+use mpc_net::config::{Address, NetworkConfig, NetworkParty, SessionBudget};
+use rcgen::CertifiedKey;
+use rustls::pki_types::{PrivateKeyDer, PrivatePkcs8KeyDer};
+
+const HOST: &str = "127.0.0.1";
+const NUM_PARTIES: usize = 5;
+
+// The configs of num_parties parties on localhost, listening on the ports base_port..base_port + num_parties
+pub(crate) fn configs(base_port: u16, num_parties: usize) -> Vec<NetworkConfig> {
+    let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+    let keys = (0..num_parties)
+        .map(|_| rcgen::generate_simple_self_signed(vec![HOST.to_owned()]).unwrap())
+        .collect::<Vec<_>>();
+    let parties = keys
+        .iter()
+        .enumerate()
+        .map(|(id, CertifiedKey { cert, .. })| NetworkParty {
+            id,
+            dns_name: Address {
+                hostname: HOST.to_owned(),
+                port: base_port + id as u16,
+            },
+            cert: cert.der().to_owned(),
+        })
+        .collect::<Vec<_>>();
+    keys.iter()
+        .enumerate()
+        .map(|(id, CertifiedKey { key_pair, .. })| NetworkConfig {
+            parties: parties.clone(),
+            my_id: id,
+            bind_addr: format!("{HOST}:{}", base_port + id as u16).parse().unwrap(),
+            key: PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key_pair.serialize_der())),
+            budget: SessionBudget::default(),
+        })
+        .collect()
+}
+
+// Runs f for each party over a ShamirMpcNet and returns the results ordered by the party id
+fn run_parties<T: Send + 'static>(
+    base_port: u16,
+    f: impl Fn(&mut ShamirMpcNet) -> std::io::Result<T> + Send + Sync + 'static,
+) -> Vec<T> {
+    let f = Arc::new(f);
+    let threads = configs(base_port, NUM_PARTIES)
+        .into_iter()
+        .map(|config| {
+            let f = Arc::clone(&f);
+            thread::spawn(move || {
+                let mut net = ShamirMpcNet::new(config).unwrap();
+                f(&mut net).unwrap()
+            })
+        })
+        .collect::<Vec<_>>();
+    threads
+        .into_iter()
+        .map(|thread| thread.join().unwrap())
+        .collect()
+}
+
+fn value(id: usize) -> u64 {
+    100 + id as u64
+}
+
+#[test]
+fn broadcast_is_ordered_by_party_id() {
+    let results = run_parties(22000, |net| net.broadcast(value(net.get_id())));
+    let expected = (0..NUM_PARTIES).map(value).collect::<Vec<_>>();
+    for result in results {
+        // the own value is inserted at the own id
+        assert_eq!(result, expected);
+    }
+}
+
+#[test]
+fn recv_all_is_ordered_like_from() {
+    let results = run_parties(22010, |net| {
+        let id = net.get_id();
+        let others = (0..NUM_PARTIES)
+            .filter(|&other_id| other_id != id)
+            .collect::<Vec<_>>();
+        net.send_all(&others, value(id))?;
+        // receive in descending order of the ids, which differs from the order of sending
+        let from = others.iter().rev().copied().collect::<Vec<_>>();
+        let received = net.recv_all::<u64>(&from)?;
+        Ok((from, received))
+    });
+    for (from, received) in results {
+        assert_eq!(received, from.into_iter().map(value).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn send_all_to_some_parties() {
+    // party 0 sends to 2 and 4, all other parties only receive from 0 if they are a target
+    let targets = [2, 4];
+    let results = run_parties(22020, move |net| {
+        let id = net.get_id();
+        let received = if id == 0 {
+            net.send_all(&targets, value(id))?;
+            vec![]
+        } else if targets.contains(&id) {
+            net.recv_all::<u64>(&[0])?
+        } else {
+            vec![]
+        };
+        // the parties that were not targeted receive the broadcast as the first message of party 0
+        assert_eq!(
+            net.broadcast(value(id))?,
+            (0..NUM_PARTIES).map(value).collect::<Vec<_>>()
+        );
+        Ok(received)
+    });
+    for (id, result) in results.into_iter().enumerate() {
+        if targets.contains(&id) {
+            assert_eq!(result, vec![value(0)]);
+        } else {
+            assert!(result.is_empty());
+        }
+    }
+}
+
+#[test]
+fn broadcast_next_is_ordered_by_distance() {
+    let num = 3;
+    let results = run_parties(22030, move |net| {
+        net.broadcast_next(value(net.get_id()), num)
+    });
+    for (id, result) in results.into_iter().enumerate() {
+        let expected = (0..num)
+            .map(|i| value((id + NUM_PARTIES - i) % NUM_PARTIES))
+            .collect::<Vec<_>>();
+        assert_eq!(result, expected);
+    }
+}
+
+#[test]
+fn broadcast_subset_is_ordered_like_subset() {
+    let subset = [3, 0, 2];
+    let results = run_parties(22040, move |net| {
+        net.broadcast_subset(value(net.get_id()), &subset)
+    });
+    let expected = subset.iter().copied().map(value).collect::<Vec<_>>();
+    for result in results {
+        assert_eq!(result, expected);
+    }
+}
+
+#[test]
+fn broadcast_fastest_starts_with_own_value() {
+    let num = 3;
+    let results = run_parties(22050, move |net| {
+        let fastest = net.broadcast_fastest(value(net.get_id()), num)?;
+        // the channels stay in sync for later messages
+        let all = net.broadcast(value(net.get_id()))?;
+        Ok((fastest, all))
+    });
+    let expected = (0..NUM_PARTIES).map(value).collect::<Vec<_>>();
+    for (id, (fastest, all)) in results.into_iter().enumerate() {
+        assert_eq!(fastest.len(), num);
+        assert_eq!(fastest[0], (id, value(id)));
+        for (other_id, data) in fastest.into_iter().skip(1) {
+            assert_ne!(other_id, id);
+            assert_eq!(data, value(other_id));
+        }
+        assert_eq!(all, expected);
+    }
+}
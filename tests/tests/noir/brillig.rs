@@ -0,0 +1,296 @@
+use std::thread;
+
+use acir::{
+    acir_field::GenericFieldElement,
+    circuit::brillig::{BrilligBytecode, BrilligFunctionId},
+};
+use ark_bn254::Fr;
+use brillig::{
+    BinaryFieldOp, BitSize, HeapVector, IntegerBitSize, MemoryAddress, Opcode as BrilligOpcode,
+};
+use co_acvm::SharedLoopPolicy;
+use co_brillig::{
+    mpc::{
+        PlainBrilligType, Rep3BrilligDriver, Rep3BrilligType, ShamirBrilligDriver,
+        ShamirBrilligType,
+    },
+    CoBrilligResult, CoBrilligVM,
+};
+use color_eyre::eyre;
+use itertools::Itertools;
+use mpc_core::protocols::{
+    rep3::{self, network::IoContext, Rep3PrimeFieldShare},
+    shamir::{self, ShamirPreprocessing, ShamirProtocol},
+};
+use rand::thread_rng;
+use tests::{rep3_network::Rep3TestNetwork, shamir_network::ShamirTestNetwork};
+
+type Opcode = BrilligOpcode<GenericFieldElement<Fr>>;
+
+fn d(address: usize) -> MemoryAddress {
+    MemoryAddress::direct(address)
+}
+
+fn u32_const(address: usize, value: u64) -> Opcode {
+    BrilligOpcode::Const {
+        destination: d(address),
+        bit_size: BitSize::Integer(IntegerBitSize::U32),
+        value: GenericFieldElement::from_repr(Fr::from(value)),
+    }
+}
+
+fn field_const(address: usize, value: u64) -> Opcode {
+    BrilligOpcode::Const {
+        destination: d(address),
+        bit_size: BitSize::Field,
+        value: GenericFieldElement::from_repr(Fr::from(value)),
+    }
+}
+
+fn field_op(op: BinaryFieldOp, destination: usize, lhs: usize, rhs: usize) -> Opcode {
+    BrilligOpcode::BinaryFieldOp {
+        destination: d(destination),
+        op,
+        lhs: d(lhs),
+        rhs: d(rhs),
+    }
+}
+
+fn jump_if(condition: usize, location: usize) -> Opcode {
+    BrilligOpcode::JumpIf {
+        condition: d(condition),
+        location,
+    }
+}
+
+// returns the value at d(11)
+fn stop() -> [Opcode; 2] {
+    [
+        u32_const(3, 11),
+        BrilligOpcode::Stop {
+            return_data: HeapVector {
+                pointer: d(3),
+                size: d(1),
+            },
+        },
+    ]
+}
+
+// counts i in d(11) up to the shared calldata n in d(10). The loop
+// condition is computed with `condition` into d(13).
+//
+// 0: d(1) = 1
+// 1: d(2) = 0
+// 2: d(10) = calldata[0]
+// 3: d(11) = 0
+// 4: d(12) = 1
+// 5: d(13) = condition
+// 6: jump_if d(13) 8    <- header of the shared loop
+// 7: jump 10
+// 8: d(11) = d(11) + d(12)
+// 9: jump 5
+fn counting_loop(condition: Opcode) -> Vec<Opcode> {
+    vec![
+        u32_const(1, 1),
+        u32_const(2, 0),
+        BrilligOpcode::CalldataCopy {
+            destination_address: d(10),
+            size_address: d(1),
+            offset_address: d(2),
+        },
+        field_const(11, 0),
+        field_const(12, 1),
+        condition,
+        jump_if(13, 8),
+        BrilligOpcode::Jump { location: 10 },
+        field_op(BinaryFieldOp::Add, 11, 11, 12),
+        BrilligOpcode::Jump { location: 5 },
+    ]
+}
+
+// while i < n { i += 1 }; return i
+fn less_than_loop() -> Vec<Opcode> {
+    let mut bytecode = counting_loop(field_op(BinaryFieldOp::LessThan, 13, 11, 10));
+    bytecode.extend(stop());
+    bytecode
+}
+
+// while i < n { i += 1 }; if !(1 < n) { i = 0 }; return i
+//
+// 10: d(14) = 1 < n
+// 11: jump_if d(14) 13  <- shared if after the shared loop
+// 12: d(11) = 0
+fn less_than_loop_then_if() -> Vec<Opcode> {
+    let mut bytecode = counting_loop(field_op(BinaryFieldOp::LessThan, 13, 11, 10));
+    bytecode.extend([
+        field_op(BinaryFieldOp::LessThan, 14, 12, 10),
+        jump_if(14, 13),
+        field_const(11, 0),
+    ]);
+    bytecode.extend(stop());
+    bytecode
+}
+
+// while n - i != 0 { i += 1 }; return i
+fn not_zero_loop() -> Vec<Opcode> {
+    let mut bytecode = counting_loop(field_op(BinaryFieldOp::Sub, 13, 10, 11));
+    bytecode.extend(stop());
+    bytecode
+}
+
+fn rep3_run(
+    bytecode: Vec<Opcode>,
+    n: u64,
+    policy: SharedLoopPolicy,
+) -> Vec<eyre::Result<Rep3PrimeFieldShare<Fr>>> {
+    let shares = rep3::share_field_element(Fr::from(n), &mut thread_rng());
+    let threads = Rep3TestNetwork::default()
+        .get_party_networks()
+        .into_iter()
+        .zip(shares)
+        .map(|(net, share)| {
+            let bytecode = bytecode.clone();
+            thread::spawn(move || {
+                let mut io_context = IoContext::init(net)?;
+                let driver = Rep3BrilligDriver::with_io_context(io_context.fork()?);
+                let mut vm = CoBrilligVM::init(driver, vec![BrilligBytecode { bytecode }]);
+                vm.set_shared_loop_policy(policy);
+                let result = vm.run(
+                    &BrilligFunctionId(0),
+                    vec![Rep3BrilligType::shared_field(share)],
+                )?;
+                let CoBrilligResult::Success(mut result) = result else {
+                    eyre::bail!("coBrillig-VM failed");
+                };
+                match result.pop() {
+                    Some(Rep3BrilligType::Public(PlainBrilligType::Field(value))) => Ok(
+                        rep3::arithmetic::promote_to_trivial_share(io_context.id, value),
+                    ),
+                    Some(Rep3BrilligType::Shared(shared)) => {
+                        Rep3BrilligType::into_arithmetic_share(&mut io_context, shared)
+                    }
+                    x => eyre::bail!("unexpected result {x:?}"),
+                }
+            })
+        })
+        .collect_vec();
+    threads
+        .into_iter()
+        .map(|thread| thread.join().unwrap())
+        .collect()
+}
+
+fn rep3_result(results: Vec<eyre::Result<Rep3PrimeFieldShare<Fr>>>) -> Fr {
+    let [a, b, c] = results
+        .into_iter()
+        .map(|result| result.unwrap())
+        .collect_vec()
+        .try_into()
+        .unwrap();
+    rep3::combine_field_element(a, b, c)
+}
+
+fn shamir_run(
+    bytecode: Vec<Opcode>,
+    n: u64,
+    policy: SharedLoopPolicy,
+) -> Vec<eyre::Result<Vec<ShamirBrilligType<Fr>>>> {
+    let (num_parties, threshold) = (3, 1);
+    let shares =
+        shamir::share_field_element(Fr::from(n), threshold, num_parties, &mut thread_rng());
+    let threads = ShamirTestNetwork::new(num_parties)
+        .get_party_networks()
+        .into_iter()
+        .zip(shares)
+        .map(|(net, share)| {
+            let bytecode = bytecode.clone();
+            thread::spawn(move || {
+                let protocol: ShamirProtocol<Fr, _> =
+                    ShamirPreprocessing::new(threshold, net, 1)?.into();
+                let driver = ShamirBrilligDriver::with_protocol(protocol);
+                let mut vm = CoBrilligVM::init(driver, vec![BrilligBytecode { bytecode }]);
+                vm.set_shared_loop_policy(policy);
+                match vm.run(
+                    &BrilligFunctionId(0),
+                    vec![ShamirBrilligType::Shared(share)],
+                )? {
+                    CoBrilligResult::Success(result) => Ok(result),
+                    CoBrilligResult::Failed => eyre::bail!("coBrillig-VM failed"),
+                }
+            })
+        })
+        .collect_vec();
+    threads
+        .into_iter()
+        .map(|thread| thread.join().unwrap())
+        .collect()
+}
+
+fn assert_rejected<T>(results: Vec<eyre::Result<T>>) {
+    for result in results {
+        let Err(err) = result else {
+            panic!("shared loop was not rejected");
+        };
+        let err = err.to_string();
+        assert!(err.contains("at opcode 6"), "{err}");
+        assert!(err.contains("JumpIf"), "{err}");
+        assert!(err.contains("opcodes 5..=9"), "{err}");
+    }
+}
+
+#[test]
+fn rep3_shared_loop_reject() {
+    assert_rejected(rep3_run(less_than_loop(), 3, SharedLoopPolicy::Reject));
+}
+
+#[test]
+fn rep3_shared_loop_open_with_consent() {
+    for n in [0, 1, 5] {
+        let result = rep3_result(rep3_run(
+            less_than_loop(),
+            n,
+            SharedLoopPolicy::OpenWithConsent,
+        ));
+        assert_eq!(result, Fr::from(n));
+    }
+}
+
+#[test]
+fn rep3_shared_loop_bound_and_pad() {
+    let policy = SharedLoopPolicy::BoundAndPad { max_iterations: 5 };
+    for n in [0, 2, 4] {
+        let result = rep3_result(rep3_run(less_than_loop(), n, policy));
+        assert_eq!(result, Fr::from(n));
+    }
+    // the loop needs more iterations, the universes still in the loop are padded with noise
+    let result = rep3_result(rep3_run(less_than_loop(), 7, policy));
+    assert_ne!(result, Fr::from(7));
+}
+
+#[test]
+fn rep3_shared_if_after_bound_and_pad() {
+    let policy = SharedLoopPolicy::BoundAndPad { max_iterations: 5 };
+    let result = rep3_result(rep3_run(less_than_loop_then_if(), 3, policy));
+    assert_eq!(result, Fr::from(3));
+    let result = rep3_result(rep3_run(less_than_loop_then_if(), 1, policy));
+    assert_eq!(result, Fr::from(0));
+}
+
+#[test]
+fn shamir_shared_loop_reject() {
+    assert_rejected(shamir_run(not_zero_loop(), 3, SharedLoopPolicy::Reject));
+}
+
+#[test]
+fn shamir_shared_loop_open_with_consent() {
+    for n in [0, 1, 5] {
+        for result in shamir_run(not_zero_loop(), n, SharedLoopPolicy::OpenWithConsent) {
+            assert_eq!(
+                result.unwrap(),
+                vec![ShamirBrilligType::Public(PlainBrilligType::Field(
+                    Fr::from(n)
+                ))]
+            );
+        }
+    }
+}
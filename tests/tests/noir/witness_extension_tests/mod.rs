@@ -59,7 +59,6 @@ macro_rules! add_rep3_acvm_test {
                 let inputs = Rep3CoSolver::<_, PartyTestNetwork>::partially_read_abi_bn254_fieldelement(
                     &input,
                     &program_artifact.abi,
-                    &program_artifact.bytecode,
                 ).expect("can share field elements for noir witness extension");
 
                 // create input shares
@@ -1,10 +1,19 @@
-use std::thread;
+use std::{collections::BTreeSet, thread};
 
-use acir::{native_types::WitnessStack, FieldElement};
+use acir::{native_types::WitnessStack, AcirField, FieldElement};
+use acvm::blackbox_solver::BlackBoxFunctionSolver;
 use ark_bn254::Bn254;
-use co_acvm::solver::PlainCoSolver;
+use ark_ff::{BigInteger, PrimeField};
+use bn254_blackbox_solver::Bn254BlackBoxSolver;
+use co_acvm::mpc::embedded_curve::{EmbeddedCurvePoint, SCALAR_LIMB_BITS};
+use co_acvm::solver::partial_abi::PublicMarker;
 use co_acvm::solver::Rep3CoSolver;
+use co_acvm::solver::{CoAcvmError, PlainCoSolver};
+use co_acvm::{
+    mpc::NoirWitnessExtensionProtocol, BrilligPolicy, PlainAcvmSolver, Rep3AcvmSolver, Rep3AcvmType,
+};
 use itertools::izip;
+use mpc_core::protocols::rep3;
 use noirc_artifacts::program::ProgramArtifact;
 use std::path::PathBuf;
 use tests::rep3_network::PartyTestNetwork;
@@ -31,3 +40,514 @@ add_rep3_acvm_test!("to_radix32");
 add_rep3_acvm_test!("unconstrained_fn");
 add_rep3_acvm_test!("unconstrained_fn_field");
 add_rep3_acvm_test!("write_access");
+
+#[test]
+fn test_rep3_split_json_input() {
+    let root = std::env!("CARGO_MANIFEST_DIR");
+    let program = std::fs::read_to_string(format!(
+        "{root}/../test_vectors/noir/poseidon_stdlib/kat/poseidon_stdlib.json"
+    ))
+    .unwrap();
+    let program_artifact = serde_json::from_str::<ProgramArtifact>(&program)
+        .expect("failed to parse program artifact");
+
+    let from_toml = Rep3CoSolver::<_, PartyTestNetwork>::partially_read_abi_bn254_fieldelement(
+        PathBuf::from(format!(
+            "{root}/../test_vectors/noir/poseidon_stdlib/Prover.toml"
+        )),
+        &program_artifact.abi,
+    )
+    .unwrap();
+    let from_json = Rep3CoSolver::<_, PartyTestNetwork>::partially_read_abi_bn254_fieldelement(
+        PathBuf::from(format!(
+            "{root}/../test_vectors/noir/poseidon_stdlib/Prover.json"
+        )),
+        &program_artifact.abi,
+    )
+    .unwrap();
+    assert_eq!(from_toml, from_json);
+
+    // the visibility is taken from the ABI, arrays are flattened
+    assert_eq!(from_json.len(), 8);
+    assert!(matches!(from_json["x1[0]"], PublicMarker::Private(_)));
+    assert!(matches!(from_json["x2[3]"], PublicMarker::Private(_)));
+    assert!(matches!(from_json["y1"], PublicMarker::Public(_)));
+    assert!(matches!(from_json["y2"], PublicMarker::Public(_)));
+}
+
+#[test]
+fn test_rep3_aes128_encrypt() {
+    // a shared key and message with a public IV, for a message with a partial block and a message of a full block, which is padded with a full block
+    let key = (0..16u64).map(ark_bn254::Fr::from).collect::<Vec<_>>();
+    let iv = (0..16u64)
+        .map(|i| ark_bn254::Fr::from(255 - i))
+        .collect::<Vec<_>>();
+    let messages = [20u64, 16].map(|len| {
+        (0..len)
+            .map(|i| ark_bn254::Fr::from(i * 7 % 256))
+            .collect::<Vec<_>>()
+    });
+
+    let mut plain_driver = PlainAcvmSolver::new();
+    for message in messages {
+        let should_result = plain_driver.aes128_encrypt(&message, &iv, &key).unwrap();
+        assert_eq!(should_result.len(), 32);
+
+        let mut rng = rand::thread_rng();
+        let key_shares = rep3::share_field_elements(&key, &mut rng);
+        let message_shares = rep3::share_field_elements(&message, &mut rng);
+        let test_network = Rep3TestNetwork::default();
+        let mut threads = vec![];
+        for (net, key, message, iv) in izip!(
+            test_network.get_party_networks(),
+            key_shares,
+            message_shares,
+            [iv.clone(), iv.clone(), iv.clone()]
+        ) {
+            threads.push(thread::spawn(move || {
+                let to_acvm = |shares: Vec<_>| {
+                    shares
+                        .into_iter()
+                        .map(Rep3AcvmType::from)
+                        .collect::<Vec<_>>()
+                };
+                let iv = iv.into_iter().map(Rep3AcvmType::from).collect::<Vec<_>>();
+                let mut driver = Rep3AcvmSolver::new(net);
+                driver
+                    .aes128_encrypt(&to_acvm(message), &iv, &to_acvm(key))
+                    .unwrap()
+                    .into_iter()
+                    .map(|byte| match byte {
+                        Rep3AcvmType::Shared(share) => share,
+                        Rep3AcvmType::Public(_) => panic!("ciphertext must be shared"),
+                    })
+                    .collect::<Vec<_>>()
+            }));
+        }
+        let [r1, r2, r3] = threads
+            .into_iter()
+            .map(|t| t.join().unwrap())
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+        let is_result = rep3::combine_field_elements(&r1, &r2, &r3);
+        assert_eq!(is_result, should_result);
+    }
+}
+
+/// Solves the unconstrained_fn test vector, where each party uses its own Brillig policy.
+fn solve_unconstrained_fn_with_policies(
+    policies: [BrilligPolicy; 3],
+) -> Vec<Result<WitnessStack<Rep3AcvmType<ark_bn254::Fr>>, CoAcvmError>> {
+    let root = std::env!("CARGO_MANIFEST_DIR");
+    let program = std::fs::read_to_string(format!(
+        "{root}/../test_vectors/noir/unconstrained_fn/kat/unconstrained_fn.json"
+    ))
+    .unwrap();
+    let program_artifact = serde_json::from_str::<ProgramArtifact>(&program)
+        .expect("failed to parse program artifact");
+    let inputs = Rep3CoSolver::<_, PartyTestNetwork>::partially_read_abi_bn254_fieldelement(
+        PathBuf::from(format!(
+            "{root}/../test_vectors/noir/unconstrained_fn/Prover.toml"
+        )),
+        &program_artifact.abi,
+    )
+    .unwrap();
+    let mut rng = rand::thread_rng();
+    let shares = co_noir::share_input_rep3::<Bn254, PartyTestNetwork, _>(inputs, &mut rng);
+    let test_network = Rep3TestNetwork::default();
+    let mut threads = vec![];
+    for (net, share, policy) in izip!(test_network.get_party_networks(), shares, policies) {
+        let program_artifact = program_artifact.clone();
+        threads.push(thread::spawn(move || {
+            let input_share =
+                co_noir::translate_witness_share_rep3(share, &program_artifact.abi).unwrap();
+            let mut solver =
+                Rep3CoSolver::from_network_with_witness(net, program_artifact, input_share)
+                    .unwrap();
+            solver.set_brillig_policy(&policy)?;
+            solver.solve()
+        }));
+    }
+    threads.into_iter().map(|t| t.join().unwrap()).collect()
+}
+
+fn reveal_policy() -> BrilligPolicy {
+    BrilligPolicy {
+        revealable: BTreeSet::from([0]),
+        ..Default::default()
+    }
+    .with_reveal_consent(true)
+}
+
+fn count_public(witness: &WitnessStack<Rep3AcvmType<ark_bn254::Fr>>) -> usize {
+    let mut witness = witness.to_owned();
+    let mut count = 0;
+    while let Some(item) = witness.pop() {
+        count += item
+            .witness
+            .into_iter()
+            .filter(|(_, value)| matches!(value, Rep3AcvmType::Public(_)))
+            .count();
+    }
+    count
+}
+
+#[test]
+fn test_rep3_brillig_policy_reveal() {
+    let root = std::env!("CARGO_MANIFEST_DIR");
+    let should_witness = std::fs::read(format!(
+        "{root}/../test_vectors/noir/unconstrained_fn/kat/unconstrained_fn.gz"
+    ))
+    .unwrap();
+    let should_witness = WitnessStack::<FieldElement>::try_from(should_witness.as_slice()).unwrap();
+
+    let mpc = solve_unconstrained_fn_with_policies([
+        BrilligPolicy::default(),
+        BrilligPolicy::default(),
+        BrilligPolicy::default(),
+    ]);
+    let revealed =
+        solve_unconstrained_fn_with_policies([reveal_policy(), reveal_policy(), reveal_policy()]);
+    let [r1, r2, r3] = <[_; 3]>::try_from(revealed).unwrap().map(Result::unwrap);
+
+    // the result of the revealed function is public
+    assert!(count_public(&r1) > count_public(mpc[0].as_ref().unwrap()));
+    let is_witness = super::combine_field_elements_for_acvm(r1, r2, r3);
+    let is_witness = PlainCoSolver::convert_to_plain_acvm_witness(is_witness);
+    assert_eq!(should_witness, is_witness);
+}
+
+#[test]
+fn test_rep3_brillig_policy_reveal_rejected_without_consent() {
+    let policy = reveal_policy().with_reveal_consent(false);
+    let mut policy_with_fixed_reveal = policy.clone();
+    policy_with_fixed_reveal
+        .functions
+        .insert(0, co_acvm::BrilligExecution::Reveal);
+    let results = solve_unconstrained_fn_with_policies([
+        policy_with_fixed_reveal.clone(),
+        policy_with_fixed_reveal.clone(),
+        policy_with_fixed_reveal,
+    ]);
+    assert!(results.iter().all(Result::is_err));
+}
+
+#[test]
+fn test_rep3_brillig_policy_mismatch_rejected() {
+    // only the first party would reveal the arguments, so no party may solve
+    let results = solve_unconstrained_fn_with_policies([
+        reveal_policy(),
+        BrilligPolicy::default(),
+        BrilligPolicy::default(),
+    ]);
+    for result in results {
+        let err = result.expect_err("mismatching policies must be rejected");
+        assert!(
+            format!("{err:?}").contains("same brillig policy"),
+            "{err:?}"
+        );
+    }
+}
+
+/// The y coordinate of the generator (1, y) of Grumpkin.
+const GRUMPKIN_GENERATOR_Y: &str = "17631683881184975370165255887551781615748388533673675138860";
+
+fn to_scalar_limbs(scalar: num_bigint::BigUint) -> (ark_bn254::Fr, ark_bn254::Fr) {
+    let lo = &scalar & ((num_bigint::BigUint::from(1u64) << SCALAR_LIMB_BITS) - 1u64);
+    let hi = scalar >> SCALAR_LIMB_BITS;
+    (lo.into(), hi.into())
+}
+
+/// Signs a message like barretenberg, i.e., for the public key pk = sk * G and R = k * G, the signature is (s, e) with e = blake2s(pedersen_hash(R.x, pk.x, pk.y) || message) and s = k - e * sk. Returns the public key and the signature.
+fn schnorr_sign(
+    private_key: u64,
+    nonce: u64,
+    message: &[u8],
+) -> (ark_bn254::Fr, ark_bn254::Fr, Vec<u8>) {
+    let mut driver = PlainAcvmSolver::new();
+    let generator = EmbeddedCurvePoint {
+        x: ark_bn254::Fr::from(1u64),
+        y: GRUMPKIN_GENERATOR_Y.parse().unwrap(),
+        is_infinite: ark_bn254::Fr::from(0u64),
+    };
+    let private_key = ark_bn254::Fq::from(private_key);
+    let nonce = ark_bn254::Fq::from(nonce);
+    let public_key = driver
+        .embedded_curve_msm(
+            vec![generator.clone()],
+            vec![to_scalar_limbs(private_key.into())],
+        )
+        .unwrap();
+    let r = driver
+        .embedded_curve_msm(vec![generator], vec![to_scalar_limbs(nonce.into())])
+        .unwrap();
+    let hash = driver
+        .pedersen_hash(vec![r.x, public_key.x, public_key.y], 0)
+        .unwrap();
+    let mut hash_input = hash.into_bigint().to_bytes_be();
+    hash_input.extend_from_slice(message);
+    let e = acvm::blackbox_solver::blake2s(&hash_input).unwrap();
+    let s = nonce - ark_bn254::Fq::from_be_bytes_mod_order(&e) * private_key;
+    let mut signature = s.into_bigint().to_bytes_be();
+    signature.extend_from_slice(&e);
+    (public_key.x, public_key.y, signature)
+}
+
+/// Verifies the signature with the blackbox solver of Noir, the plain driver, and the Rep3 driver with a shared public key, signature, and message. All results have to agree, the result of the blackbox solver of Noir is returned.
+fn schnorr_verify_all(
+    public_key_x: ark_bn254::Fr,
+    public_key_y: ark_bn254::Fr,
+    signature: &[u8],
+    message: &[u8],
+) -> bool {
+    let should_result = Bn254BlackBoxSolver
+        .schnorr_verify(
+            &FieldElement::from_repr(public_key_x),
+            &FieldElement::from_repr(public_key_y),
+            signature.try_into().unwrap(),
+            message,
+        )
+        .unwrap();
+
+    let to_field = |bytes: &[u8]| {
+        bytes
+            .iter()
+            .map(|byte| ark_bn254::Fr::from(*byte as u64))
+            .collect::<Vec<_>>()
+    };
+    let (signature, message) = (to_field(signature), to_field(message));
+    let plain_result = PlainAcvmSolver::new()
+        .schnorr_verify(public_key_x, public_key_y, &signature, &message)
+        .unwrap();
+    assert_eq!(plain_result, ark_bn254::Fr::from(should_result));
+
+    let mut rng = rand::thread_rng();
+    let public_key_shares = rep3::share_field_elements(&[public_key_x, public_key_y], &mut rng);
+    let signature_shares = rep3::share_field_elements(&signature, &mut rng);
+    let message_shares = rep3::share_field_elements(&message, &mut rng);
+    let test_network = Rep3TestNetwork::default();
+    let mut threads = vec![];
+    for (net, public_key, signature, message) in izip!(
+        test_network.get_party_networks(),
+        public_key_shares,
+        signature_shares,
+        message_shares
+    ) {
+        threads.push(thread::spawn(move || {
+            let to_acvm = |shares: Vec<_>| {
+                shares
+                    .into_iter()
+                    .map(Rep3AcvmType::from)
+                    .collect::<Vec<_>>()
+            };
+            let public_key = to_acvm(public_key);
+            let mut driver = Rep3AcvmSolver::new(net);
+            match driver
+                .schnorr_verify(
+                    public_key[0].to_owned(),
+                    public_key[1].to_owned(),
+                    &to_acvm(signature),
+                    &to_acvm(message),
+                )
+                .unwrap()
+            {
+                Rep3AcvmType::Shared(share) => share,
+                Rep3AcvmType::Public(_) => panic!("result must be shared"),
+            }
+        }));
+    }
+    let [r1, r2, r3] = threads
+        .into_iter()
+        .map(|t| t.join().unwrap())
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
+    let is_result = rep3::combine_field_element(r1, r2, r3);
+    assert_eq!(is_result, ark_bn254::Fr::from(should_result));
+    should_result
+}
+
+#[test]
+fn test_rep3_schnorr_verify() {
+    let message = b"co-snarks schnorr".to_vec();
+    let (public_key_x, public_key_y, signature) = schnorr_sign(42, 1337, &message);
+    assert!(schnorr_verify_all(
+        public_key_x,
+        public_key_y,
+        &signature,
+        &message
+    ));
+
+    // a different message
+    let mut tampered_message = message.clone();
+    tampered_message[0] ^= 1;
+    assert!(!schnorr_verify_all(
+        public_key_x,
+        public_key_y,
+        &signature,
+        &tampered_message
+    ));
+
+    // a different s and a different e
+    for i in [31, 63] {
+        let mut tampered_signature = signature.clone();
+        tampered_signature[i] ^= 1;
+        assert!(!schnorr_verify_all(
+            public_key_x,
+            public_key_y,
+            &tampered_signature,
+            &message
+        ));
+    }
+
+    // the public key of another private key
+    let (other_x, other_y, _) = schnorr_sign(43, 1337, &message);
+    assert!(!schnorr_verify_all(other_x, other_y, &signature, &message));
+}
+
+/// Derives the generators like the standard library of Noir, as the flattened coordinates of the points.
+fn noir_generators(domain_separator: &[u8], num: usize, starting_index: u32) -> Vec<FieldElement> {
+    bn254_blackbox_solver::derive_generators(domain_separator, num as u32, starting_index)
+        .into_iter()
+        .flat_map(|point| {
+            [
+                FieldElement::from_le_bytes_reduce(&point.x.into_bigint().to_bytes_le()),
+                FieldElement::from_le_bytes_reduce(&point.y.into_bigint().to_bytes_le()),
+                FieldElement::zero(),
+            ]
+        })
+        .collect()
+}
+
+/// Computes the MSM with the blackbox solver of Noir, which the standard library of Noir uses for the Pedersen functions.
+fn noir_msm(points: &[FieldElement], scalars: &[ark_bn254::Fr]) -> [ark_bn254::Fr; 3] {
+    let (lo, hi): (Vec<_>, Vec<_>) = scalars
+        .iter()
+        .map(|scalar| {
+            let (lo, hi) = to_scalar_limbs((*scalar).into());
+            (FieldElement::from_repr(lo), FieldElement::from_repr(hi))
+        })
+        .unzip();
+    let (x, y, is_infinite) = bn254_blackbox_solver::multi_scalar_mul(points, &lo, &hi).unwrap();
+    [x, y, is_infinite].map(FieldElement::into_repr)
+}
+
+fn noir_pedersen_commitment(inputs: &[ark_bn254::Fr], starting_index: u32) -> [ark_bn254::Fr; 3] {
+    let points = noir_generators(b"DEFAULT_DOMAIN_SEPARATOR", inputs.len(), starting_index);
+    noir_msm(&points, inputs)
+}
+
+fn noir_pedersen_hash(inputs: &[ark_bn254::Fr], starting_index: u32) -> ark_bn254::Fr {
+    let mut points = noir_generators(b"pedersen_hash_length", 1, 0);
+    points.extend(noir_generators(
+        b"DEFAULT_DOMAIN_SEPARATOR",
+        inputs.len(),
+        starting_index,
+    ));
+    let mut scalars = vec![ark_bn254::Fr::from(inputs.len() as u64)];
+    scalars.extend_from_slice(inputs);
+    noir_msm(&points, &scalars)[0]
+}
+
+fn combine_rep3_acvm(values: [Rep3AcvmType<ark_bn254::Fr>; 3]) -> ark_bn254::Fr {
+    match values {
+        [Rep3AcvmType::Shared(a), Rep3AcvmType::Shared(b), Rep3AcvmType::Shared(c)] => {
+            rep3::combine_field_element(a, b, c)
+        }
+        [Rep3AcvmType::Public(a), Rep3AcvmType::Public(b), Rep3AcvmType::Public(c)] => {
+            assert!(a == b && b == c);
+            a
+        }
+        _ => panic!("the parties disagree on the visibility of a value"),
+    }
+}
+
+#[test]
+fn test_rep3_pedersen() {
+    let inputs = [
+        vec![ark_bn254::Fr::from(1u64), ark_bn254::Fr::from(1u64)],
+        vec![
+            ark_bn254::Fr::from(0u64),
+            -ark_bn254::Fr::from(1u64),
+            ark_bn254::Fr::from(u128::MAX),
+            ark_bn254::Fr::from(42u64),
+        ],
+    ];
+    let starting_index = 3;
+
+    let should_commitments = inputs
+        .iter()
+        .map(|inputs| noir_pedersen_commitment(inputs, starting_index))
+        .collect::<Vec<_>>();
+    let should_hashes = inputs
+        .iter()
+        .map(|inputs| noir_pedersen_hash(inputs, starting_index))
+        .collect::<Vec<_>>();
+
+    let mut plain_driver = PlainAcvmSolver::new();
+    for (inputs, should_commitment, should_hash) in
+        izip!(&inputs, &should_commitments, &should_hashes)
+    {
+        let commitment = plain_driver
+            .pedersen_commitment(inputs.to_owned(), starting_index)
+            .unwrap();
+        assert_eq!(
+            [commitment.x, commitment.y, commitment.is_infinite],
+            *should_commitment
+        );
+        let hash = plain_driver
+            .pedersen_hash(inputs.to_owned(), starting_index)
+            .unwrap();
+        assert_eq!(hash, *should_hash);
+    }
+
+    let mut rng = rand::thread_rng();
+    let [first, second] = inputs
+        .clone()
+        .map(|inputs| rep3::share_field_elements(&inputs, &mut rng));
+    let test_network = Rep3TestNetwork::default();
+    let mut threads = vec![];
+    for (net, first, second) in izip!(test_network.get_party_networks(), first, second) {
+        threads.push(thread::spawn(move || {
+            let inputs = [first, second]
+                .into_iter()
+                .map(|shares| {
+                    shares
+                        .into_iter()
+                        .map(Rep3AcvmType::from)
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let mut driver = Rep3AcvmSolver::new(net);
+            let commitments = driver
+                .pedersen_commitment_many(inputs.clone(), starting_index)
+                .unwrap()
+                .into_iter()
+                .map(|point| [point.x, point.y, point.is_infinite])
+                .collect::<Vec<_>>();
+            let hashes = driver.pedersen_hash_many(inputs, starting_index).unwrap();
+            (commitments, hashes)
+        }));
+    }
+    let [r1, r2, r3] = threads
+        .into_iter()
+        .map(|t| t.join().unwrap())
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
+    for (i, (should_commitment, should_hash)) in
+        izip!(should_commitments, should_hashes).enumerate()
+    {
+        let commitment = std::array::from_fn(|j| {
+            combine_rep3_acvm([
+                r1.0[i][j].to_owned(),
+                r2.0[i][j].to_owned(),
+                r3.0[i][j].to_owned(),
+            ])
+        });
+        assert_eq!(commitment, should_commitment);
+        let hash = combine_rep3_acvm([r1.1[i].to_owned(), r2.1[i].to_owned(), r3.1[i].to_owned()]);
+        assert_eq!(hash, should_hash);
+    }
+}